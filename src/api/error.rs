@@ -0,0 +1,236 @@
+//! Error type returned by the Navidrome/Subsonic client.
+//!
+//! Callers that only show a message can keep treating failures as strings through
+//! `From<ApiError> for String`; the variants exist so the UI can react differently to a
+//! rejected login, a missing item or offline mode.
+use std::fmt;
+
+/// Subsonic error codes that mean the saved credentials are no longer accepted.
+const SUBSONIC_AUTH_CODES: [i32; 3] = [
+    40, // wrong username or password
+    41, // token authentication not supported for this user
+    44, // invalid API key
+];
+/// Subsonic error code for a requested item that does not exist.
+const SUBSONIC_NOT_FOUND: i32 = 70;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    /// The request never got a usable response (DNS, TLS, connection reset, blocked host).
+    Network(String),
+    Timeout,
+    /// The server rejected the saved credentials; the user has to sign in again.
+    AuthFailed,
+    /// The requested item does not exist on the server. `kind` names it for messages.
+    NotFound {
+        kind: &'static str,
+    },
+    /// A Subsonic `failed` envelope or an unexpected HTTP status.
+    ServerError {
+        code: i32,
+        message: String,
+    },
+    /// HTTP 429, with the `Retry-After` delay in seconds when the server sent one.
+    RateLimited {
+        retry_after: Option<u64>,
+    },
+    /// Offline mode is on, so no request was made.
+    Offline,
+    /// The response body could not be decoded.
+    Parse(String),
+}
+
+impl ApiError {
+    /// Maps a Subsonic `error` element to the matching variant.
+    pub fn from_subsonic(code: i32, message: impl Into<String>) -> Self {
+        if SUBSONIC_AUTH_CODES.contains(&code) {
+            ApiError::AuthFailed
+        } else if code == SUBSONIC_NOT_FOUND {
+            ApiError::NotFound { kind: "item" }
+        } else {
+            ApiError::ServerError {
+                code,
+                message: message.into(),
+            }
+        }
+    }
+
+    /// A failure described only by a message, reported like Subsonic's generic error (code 0).
+    pub fn generic(message: impl Into<String>) -> Self {
+        ApiError::ServerError {
+            code: 0,
+            message: message.into(),
+        }
+    }
+
+    /// Maps a non-success HTTP status; `None` for 2xx responses.
+    pub fn from_http_status(status: u16, retry_after: Option<u64>) -> Option<Self> {
+        match status {
+            200..=299 => None,
+            401 | 403 => Some(ApiError::AuthFailed),
+            404 => Some(ApiError::NotFound { kind: "resource" }),
+            408 | 504 => Some(ApiError::Timeout),
+            429 => Some(ApiError::RateLimited { retry_after }),
+            _ => Some(ApiError::ServerError {
+                code: i32::from(status),
+                message: format!("HTTP status {status}"),
+            }),
+        }
+    }
+
+    pub fn is_auth_failed(&self) -> bool {
+        matches!(self, ApiError::AuthFailed)
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, ApiError::NotFound { .. })
+    }
+
+    pub fn is_offline(&self) -> bool {
+        matches!(self, ApiError::Offline)
+    }
+
+    /// No answer came back: offline mode, a network failure or a timeout.
+    pub fn is_unreachable(&self) -> bool {
+        matches!(
+            self,
+            ApiError::Network(_) | ApiError::Timeout | ApiError::Offline
+        )
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Network(message) => write!(f, "Network error: {message}"),
+            ApiError::Timeout => write!(f, "The server took too long to respond."),
+            ApiError::AuthFailed => write!(f, "The server rejected the saved credentials."),
+            ApiError::NotFound { kind } => {
+                let mut chars = kind.chars();
+                match chars.next() {
+                    Some(first) => {
+                        write!(f, "{}{} not found", first.to_uppercase(), chars.as_str())
+                    }
+                    None => write!(f, "Not found"),
+                }
+            }
+            ApiError::ServerError { message, .. } => write!(f, "{message}"),
+            ApiError::RateLimited {
+                retry_after: Some(secs),
+            } => write!(f, "Too many requests; retry in {secs}s."),
+            ApiError::RateLimited { retry_after: None } => {
+                write!(f, "Too many requests; try again shortly.")
+            }
+            ApiError::Offline => write!(f, "Offline mode is on."),
+            ApiError::Parse(message) => write!(f, "Unexpected server response: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl From<ApiError> for String {
+    fn from(error: ApiError) -> Self {
+        error.to_string()
+    }
+}
+
+impl From<reqwest::Error> for ApiError {
+    fn from(error: reqwest::Error) -> Self {
+        // Offline mode swaps request URLs for an `offline://` target that reqwest refuses.
+        if error.url().is_some_and(|url| url.scheme() == "offline")
+            || crate::cache_service::is_offline_mode()
+        {
+            return ApiError::Offline;
+        }
+        if error.is_timeout() {
+            return ApiError::Timeout;
+        }
+        if error.is_decode() {
+            return ApiError::Parse(error.to_string());
+        }
+        if let Some(status) = error.status() {
+            if let Some(mapped) = ApiError::from_http_status(status.as_u16(), None) {
+                return mapped;
+            }
+        }
+        ApiError::Network(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ApiError {
+    fn from(error: serde_json::Error) -> Self {
+        ApiError::Parse(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credential_codes_map_to_auth_failed() {
+        for code in [40, 41, 44] {
+            assert_eq!(
+                ApiError::from_subsonic(code, "Wrong username or password"),
+                ApiError::AuthFailed
+            );
+        }
+    }
+
+    #[test]
+    fn missing_item_code_maps_to_not_found() {
+        assert_eq!(
+            ApiError::from_subsonic(70, "Song not found"),
+            ApiError::NotFound { kind: "item" }
+        );
+    }
+
+    #[test]
+    fn other_codes_keep_the_server_message() {
+        assert_eq!(
+            ApiError::from_subsonic(50, "User is not authorized for the given operation."),
+            ApiError::ServerError {
+                code: 50,
+                message: "User is not authorized for the given operation.".to_string(),
+            }
+        );
+        assert_eq!(
+            ApiError::from_subsonic(0, "boom").to_string(),
+            "boom".to_string()
+        );
+    }
+
+    #[test]
+    fn http_statuses_map_to_variants() {
+        assert_eq!(ApiError::from_http_status(200, None), None);
+        assert_eq!(
+            ApiError::from_http_status(401, None),
+            Some(ApiError::AuthFailed)
+        );
+        assert_eq!(
+            ApiError::from_http_status(403, None),
+            Some(ApiError::AuthFailed)
+        );
+        assert_eq!(
+            ApiError::from_http_status(429, Some(30)),
+            Some(ApiError::RateLimited {
+                retry_after: Some(30)
+            })
+        );
+        assert_eq!(
+            ApiError::from_http_status(504, None),
+            Some(ApiError::Timeout)
+        );
+        assert!(matches!(
+            ApiError::from_http_status(502, None),
+            Some(ApiError::ServerError { code: 502, .. })
+        ));
+    }
+
+    #[test]
+    fn converts_to_a_readable_string() {
+        let message: String = ApiError::NotFound { kind: "album" }.into();
+        assert_eq!(message, "Album not found");
+    }
+}
@@ -0,0 +1,295 @@
+// Splits radio ICY `StreamTitle` values into artist/title.
+//
+// Stations disagree on the format ("Artist - Title", "Title / Artist", "Artist: Title",
+// "\"Title\" by Artist") and interleave slogans, so parsing runs in three steps: slogan
+// blocklist, optional per-station template, then separator heuristics.
+
+/// Slogan fragments that mean "no track info"; matched case-insensitively.
+pub const DEFAULT_RADIO_SLOGAN_PATTERNS: [&str; 8] = [
+    "you're listening to",
+    "you are listening to",
+    "now on air",
+    "commercial break",
+    "advertisement",
+    "station id",
+    "more music variety",
+    "the best music",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IcyTitleParts {
+    pub artist: Option<String>,
+    /// `None` when the stream title carries no track info and the station name should show.
+    pub title: Option<String>,
+}
+
+fn clean_part(value: &str) -> Option<String> {
+    let trimmed = value
+        .trim()
+        .trim_matches(|ch: char| ch == '"' || ch == '\'' || ch == '|')
+        .trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    // Placeholder artists some encoders emit when tags are missing.
+    let lowered = trimmed.to_ascii_lowercase();
+    if matches!(
+        lowered.as_str(),
+        "unknown" | "unknown artist" | "various" | "various artists" | "n/a" | "-"
+    ) {
+        return None;
+    }
+    Some(trimmed.to_string())
+}
+
+fn parts(artist: &str, title: &str) -> Option<IcyTitleParts> {
+    let title = clean_part(title)?;
+    Some(IcyTitleParts {
+        artist: clean_part(artist),
+        title: Some(title),
+    })
+}
+
+pub fn is_radio_slogan(stream_title: &str, patterns: &[String]) -> bool {
+    let lowered = stream_title.trim().to_lowercase();
+    if lowered.is_empty() {
+        return true;
+    }
+    patterns
+        .iter()
+        .map(|pattern| pattern.trim().to_lowercase())
+        .filter(|pattern| !pattern.is_empty())
+        .any(|pattern| lowered.contains(&pattern))
+}
+
+enum TemplateToken {
+    Literal(String),
+    Artist,
+    Title,
+    Skip,
+}
+
+fn tokenize_template(template: &str) -> Option<Vec<TemplateToken>> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+    while !rest.is_empty() {
+        let Some(open) = rest.find('{') else {
+            tokens.push(TemplateToken::Literal(rest.to_string()));
+            break;
+        };
+        if open > 0 {
+            tokens.push(TemplateToken::Literal(rest[..open].to_string()));
+        }
+        let close = rest[open..].find('}')? + open;
+        tokens.push(match rest[open + 1..close].trim() {
+            "artist" => TemplateToken::Artist,
+            "title" => TemplateToken::Title,
+            "*" | "ignore" => TemplateToken::Skip,
+            _ => return None,
+        });
+        rest = &rest[close + 1..];
+    }
+    let has_title = tokens
+        .iter()
+        .any(|token| matches!(token, TemplateToken::Title));
+    has_title.then_some(tokens)
+}
+
+/// Matches a station template such as `"{title} / {artist}"` or `"{artist}: {title} {*}"`.
+/// Each placeholder captures up to the next literal, the last one takes the remainder.
+pub fn apply_icy_title_template(stream_title: &str, template: &str) -> Option<IcyTitleParts> {
+    let tokens = tokenize_template(template.trim())?;
+    let mut rest = stream_title.trim();
+    let mut artist = None;
+    let mut title = None;
+
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            TemplateToken::Literal(literal) => {
+                let literal = literal.as_str();
+                if !rest.starts_with(literal) {
+                    let trimmed_literal = literal.trim();
+                    rest = rest.trim_start().strip_prefix(trimmed_literal)?;
+                } else {
+                    rest = &rest[literal.len()..];
+                }
+            }
+            placeholder => {
+                let captured = match tokens.get(index + 1) {
+                    Some(TemplateToken::Literal(next)) => {
+                        let end = rest.find(next.as_str()).or_else(|| {
+                            let trimmed = next.trim();
+                            (!trimmed.is_empty()).then(|| rest.find(trimmed)).flatten()
+                        })?;
+                        let captured = &rest[..end];
+                        rest = &rest[end..];
+                        captured
+                    }
+                    // Adjacent placeholders are ambiguous.
+                    Some(_) => return None,
+                    None => std::mem::take(&mut rest),
+                };
+                match placeholder {
+                    TemplateToken::Artist => artist = Some(captured),
+                    TemplateToken::Title => title = Some(captured),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    parts(artist.unwrap_or_default(), title?)
+}
+
+/// Separator heuristics for stations without a template. Ordered from the most to the
+/// least common convention; the first separator present wins.
+pub fn split_icy_stream_title(stream_title: &str) -> IcyTitleParts {
+    let trimmed = stream_title.trim();
+
+    for separator in [" - ", " – ", " — ", " ~ "] {
+        if let Some((artist, title)) = trimmed.split_once(separator) {
+            if let Some(split) = parts(artist, title) {
+                return split;
+            }
+        }
+    }
+    if let Some((title, artist)) = trimmed.split_once(" / ") {
+        if let Some(split) = parts(artist, title) {
+            return split;
+        }
+    }
+    // "Artist: Title", but not clock times ("12:30") or long sentence-like prefixes.
+    if let Some((artist, title)) = trimmed.split_once(": ") {
+        let artist_looks_like_name = !artist.is_empty()
+            && artist.chars().count() <= 48
+            && !artist.ends_with(|ch: char| ch.is_ascii_digit());
+        if artist_looks_like_name {
+            if let Some(split) = parts(artist, title) {
+                return split;
+            }
+        }
+    }
+    // "by" is only a separator after a quoted title; "Stand By Me" is a title on its own.
+    for (opening, closing) in [('"', '"'), ('\u{201c}', '\u{201d}')] {
+        let Some(quoted) = trimmed.strip_prefix(opening) else {
+            continue;
+        };
+        if let Some((title, artist)) = quoted.split_once(closing) {
+            let artist = artist.trim_start();
+            let artist = artist
+                .strip_prefix("by ")
+                .or_else(|| artist.strip_prefix("By "));
+            if let Some(split) = artist.and_then(|artist| parts(artist, title)) {
+                return split;
+            }
+        }
+    }
+
+    IcyTitleParts {
+        artist: None,
+        title: clean_part(trimmed),
+    }
+}
+
+/// Full parsing pipeline used by the radio view: slogans first, then the station's
+/// template, then heuristics.
+pub fn parse_icy_stream_title(
+    stream_title: &str,
+    template: Option<&str>,
+    slogan_patterns: &[String],
+) -> IcyTitleParts {
+    if is_radio_slogan(stream_title, slogan_patterns) {
+        return IcyTitleParts::default();
+    }
+    template
+        .filter(|template| !template.trim().is_empty())
+        .and_then(|template| apply_icy_title_template(stream_title, template))
+        .unwrap_or_else(|| split_icy_stream_title(stream_title))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(artist: Option<&str>, title: Option<&str>) -> IcyTitleParts {
+        IcyTitleParts {
+            artist: artist.map(str::to_string),
+            title: title.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn stream_titles_split_on_clear_separators_only() {
+        let cases = [
+            (
+                "Daft Punk - One More Time",
+                Some("Daft Punk"),
+                Some("One More Time"),
+            ),
+            ("Björk – Jóga", Some("Björk"), Some("Jóga")),
+            (
+                "Roygbiv / Boards of Canada",
+                Some("Boards of Canada"),
+                Some("Roygbiv"),
+            ),
+            (
+                "Nina Simone: Feeling Good",
+                Some("Nina Simone"),
+                Some("Feeling Good"),
+            ),
+            (
+                "\"Teardrop\" by Massive Attack",
+                Some("Massive Attack"),
+                Some("Teardrop"),
+            ),
+            ("Stand By Me", None, Some("Stand By Me")),
+            ("Killed by Death", None, Some("Killed by Death")),
+            ("News at 12:30 today", None, Some("News at 12:30 today")),
+            ("Unknown - Intro", None, Some("Intro")),
+        ];
+        for (stream_title, artist, title) in cases {
+            assert_eq!(
+                split_icy_stream_title(stream_title),
+                split(artist, title),
+                "{stream_title}"
+            );
+        }
+    }
+
+    #[test]
+    fn slogans_and_templates_come_before_the_heuristics() {
+        let slogans: Vec<String> = DEFAULT_RADIO_SLOGAN_PATTERNS
+            .iter()
+            .map(|pattern| pattern.to_string())
+            .collect();
+        let cases = [
+            ("You're listening to Radio X", None, None, None),
+            ("", None, None, None),
+            (
+                "Artist - Title (https://radio.example)",
+                None,
+                Some("Artist"),
+                Some("Title (https://radio.example)"),
+            ),
+            (
+                "Roygbiv / Boards of Canada",
+                Some("{title} / {artist}"),
+                Some("Boards of Canada"),
+                Some("Roygbiv"),
+            ),
+            (
+                "Boards of Canada: Roygbiv [live]",
+                Some("{artist}: {title} [{*}]"),
+                Some("Boards of Canada"),
+                Some("Roygbiv"),
+            ),
+        ];
+        for (stream_title, template, artist, title) in cases {
+            assert_eq!(
+                parse_icy_stream_title(stream_title, template, &slogans),
+                split(artist, title),
+                "{stream_title}"
+            );
+        }
+    }
+}
@@ -1,7 +1,10 @@
 use crate::api::Song;
 use crate::cache_service::{
-    get_json as cache_get_json, is_offline_mode, put_json as cache_put_json,
+    get_json as cache_get_json, is_known_miss, is_offline_mode, put_json as cache_put_json,
+    remember_miss,
 };
+use crate::network_policy::{strict_privacy_enabled, GuardedClient};
+use futures_util::future::{AbortHandle, Abortable};
 use once_cell::sync::Lazy;
 use reqwest::header::HeaderMap;
 #[cfg(not(target_arch = "wasm32"))]
@@ -12,10 +15,11 @@ use serde::Serialize;
 use serde_json::Value;
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Mutex;
 use std::time::Duration;
 
-static LYRICS_HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
+static LYRICS_HTTP_CLIENT: Lazy<GuardedClient> = Lazy::new(GuardedClient::new);
 static LYRICS_SUCCESS_CACHE: Lazy<Mutex<HashMap<String, LyricsResult>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 
@@ -189,6 +193,61 @@ pub struct LyricsSearchCandidate {
     pub query: LyricsQuery,
 }
 
+#[derive(Debug, Default)]
+struct LyricsFetchState {
+    generation: u64,
+    song_key: String,
+    abort: Option<AbortHandle>,
+}
+
+/// Keeps only the newest lyrics lookup of one consumer. Starting a lookup supersedes the
+/// one in flight: its provider requests are dropped, which aborts them (reqwest closes the
+/// connection on native and aborts the `fetch` on wasm), and its result is discarded.
+#[derive(Debug, Default)]
+pub struct LyricsFetchGuard {
+    state: Mutex<LyricsFetchState>,
+}
+
+impl LyricsFetchGuard {
+    /// Runs `fetch` as the lookup for `song_key`. `None` means a newer lookup replaced it.
+    pub async fn run<T>(&self, song_key: &str, fetch: impl Future<Output = T>) -> Option<T> {
+        let (abort, registration) = AbortHandle::new_pair();
+        let generation = {
+            let mut state = self.state.lock().ok()?;
+            if let Some(previous) = state.abort.replace(abort) {
+                previous.abort();
+            }
+            state.generation = state.generation.wrapping_add(1);
+            state.song_key = song_key.to_string();
+            state.generation
+        };
+
+        let result = Abortable::new(fetch, registration).await.ok();
+
+        let mut state = self.state.lock().ok()?;
+        if state.generation != generation || state.song_key != song_key {
+            return None;
+        }
+        state.abort = None;
+        result
+    }
+}
+
+/// Lyrics an earlier lookup already found for `query`, from memory or the lyrics cache.
+/// Never goes to the network.
+pub fn cached_lyrics(query: &LyricsQuery) -> Option<LyricsResult> {
+    let query_cache_key = lyrics_query_cache_key(query);
+    if let Some(cached) = LYRICS_SUCCESS_CACHE
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(&query_cache_key).cloned())
+    {
+        return Some(cached);
+    }
+    cache_get_json::<PersistentLyricsResult>(&format!("lyrics:result:{query_cache_key}"))?
+        .to_runtime()
+}
+
 pub async fn fetch_lyrics_with_fallback(
     query: &LyricsQuery,
     provider_order: &[String],
@@ -204,7 +263,7 @@ pub async fn fetch_lyrics_with_fallback(
     let query_cache_key = lyrics_query_cache_key(query);
     let scoped_persistent_cache_key = format!("lyrics:result:{scoped_cache_key}");
     let query_persistent_cache_key = format!("lyrics:result:{query_cache_key}");
-    let miss_cache_key = format!("lyrics:miss:{scoped_cache_key}");
+    let miss_cache_key = format!("lyrics:{scoped_cache_key}");
 
     if let Ok(cache) = LYRICS_SUCCESS_CACHE.lock() {
         if let Some(cached) = cache.get(&scoped_cache_key).cloned() {
@@ -235,7 +294,7 @@ pub async fn fetch_lyrics_with_fallback(
         }
     }
 
-    if cache_get_json::<bool>(&miss_cache_key).unwrap_or(false) {
+    if is_known_miss(&miss_cache_key) {
         return Err("No lyrics found for this song.".to_string());
     }
 
@@ -245,6 +304,12 @@ pub async fn fetch_lyrics_with_fallback(
         );
     }
 
+    if strict_privacy_enabled() {
+        return Err(
+            "Strict privacy mode is enabled. External lyrics providers are blocked.".to_string(),
+        );
+    }
+
     let providers = normalized_provider_order
         .iter()
         .filter_map(|key| LyricsProvider::from_key(key))
@@ -282,7 +347,7 @@ pub async fn fetch_lyrics_with_fallback(
     }
 
     if !saw_hard_error {
-        let _ = cache_put_json(miss_cache_key, &true, Some(24));
+        remember_miss(&miss_cache_key);
     }
 
     if errors.is_empty() {
@@ -318,6 +383,12 @@ pub async fn search_lyrics_candidates(
         );
     }
 
+    if strict_privacy_enabled() {
+        return Err(
+            "Strict privacy mode is enabled. External lyrics providers are blocked.".to_string(),
+        );
+    }
+
     let mut ranked = Vec::<(LyricsSearchCandidate, i32, usize)>::new();
     let mut errors = Vec::<String>::new();
 
@@ -1375,3 +1446,44 @@ fn normalize_for_match(value: &str) -> String {
         .collect::<Vec<_>>()
         .join(" ")
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+    /// Stands in for a provider that answers after `delay_ms`.
+    async fn slow_provider(song: &str, delay_ms: u64, completed: &AtomicUsize) -> String {
+        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+        completed.fetch_add(1, AtomicOrdering::SeqCst);
+        format!("lyrics for {song}")
+    }
+
+    #[tokio::test]
+    async fn rapid_skips_leave_only_the_last_lookup() {
+        let guard = LyricsFetchGuard::default();
+        let completed = AtomicUsize::new(0);
+
+        // Each skip starts 10 ms after the previous one; earlier lookups are the slowest.
+        let lookup = |song: &'static str, start_ms: u64, delay_ms: u64| {
+            let guard = &guard;
+            let completed = &completed;
+            async move {
+                tokio::time::sleep(Duration::from_millis(start_ms)).await;
+                guard
+                    .run(song, slow_provider(song, delay_ms, completed))
+                    .await
+            }
+        };
+        let (first, second, last) = tokio::join!(
+            lookup("a", 0, 120),
+            lookup("b", 10, 80),
+            lookup("c", 20, 30)
+        );
+
+        assert_eq!(first, None);
+        assert_eq!(second, None);
+        assert_eq!(last.as_deref(), Some("lyrics for c"));
+        assert_eq!(completed.load(AtomicOrdering::SeqCst), 1);
+    }
+}
@@ -1,7 +1,21 @@
+pub mod error;
+pub mod icy_titles;
 pub mod lyrics;
 pub mod models;
+pub mod musicbrainz;
 pub mod navidrome;
+pub mod queue_export;
+pub mod server_flavor;
+pub mod track_resolver;
+pub mod user_permissions;
 
+pub use error::*;
+pub use icy_titles::*;
 pub use lyrics::*;
 pub use models::*;
+pub use musicbrainz::*;
 pub use navidrome::*;
+pub use queue_export::*;
+pub use server_flavor::*;
+pub use track_resolver::*;
+pub use user_permissions::*;
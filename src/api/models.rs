@@ -1,5 +1,14 @@
+use crate::api::server_flavor::ServerFlavor;
+use crate::api::user_permissions::{UserAction, UserPermissions};
 use serde::{Deserialize, Serialize};
 
+/// Client name reported to servers whose entry doesn't set its own.
+pub const DEFAULT_CLIENT_NAME: &str = "RustySound";
+
+fn default_client_name() -> String {
+    DEFAULT_CLIENT_NAME.to_string()
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ServerConfig {
     pub id: String,
@@ -8,6 +17,13 @@ pub struct ServerConfig {
     pub username: String,
     pub password: String,
     pub active: bool,
+    /// Sent as the Subsonic `c` parameter. Navidrome matches its per-player transcoding
+    /// rules against this name.
+    #[serde(default = "default_client_name")]
+    pub client_name: String,
+    /// Ask for the original file (`format=raw`) unless the app requests a transcode itself.
+    #[serde(default)]
+    pub raw_stream: bool,
 }
 
 impl ServerConfig {
@@ -19,8 +35,78 @@ impl ServerConfig {
             username,
             password,
             active: true,
+            client_name: default_client_name(),
+            raw_stream: false,
         }
     }
+
+    /// The client name requests carry; a blank name falls back to the default.
+    pub fn reported_client_name(&self) -> &str {
+        let name = self.client_name.trim();
+        if name.is_empty() {
+            DEFAULT_CLIENT_NAME
+        } else {
+            name
+        }
+    }
+
+    /// Key identifying the same account on the same server, ignoring scheme, case and
+    /// trailing slashes, so duplicate entries can be detected.
+    pub fn identity_key(&self) -> String {
+        Self::identity_key_for(&self.url, &self.username)
+    }
+
+    pub fn identity_key_for(url: &str, username: &str) -> String {
+        let url = url.trim().trim_end_matches('/').to_ascii_lowercase();
+        let host_path = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .unwrap_or(&url);
+        format!("{}|{}", host_path, username.trim().to_ascii_lowercase())
+    }
+}
+
+/// A server-side library root (Subsonic "music folder").
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct MusicFolder {
+    pub id: String,
+    pub name: String,
+}
+
+/// Optional endpoints a server answered for, from `NavidromeClient::probe_capabilities`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerCapabilities {
+    pub internet_radio: bool,
+    pub podcasts: bool,
+    /// Navidrome's own REST API, used for play-date and play-count song lists.
+    pub native_api: bool,
+    /// OpenSubsonic `songLyrics`, i.e. `getLyricsBySongId`.
+    pub lyrics_by_song: bool,
+    pub top_songs: bool,
+    /// Roles of the signed-in user; `None` when the server didn't answer `getUser`.
+    #[serde(default)]
+    pub permissions: Option<UserPermissions>,
+    #[serde(default)]
+    pub flavor: ServerFlavor,
+}
+
+impl ServerCapabilities {
+    /// Every probed feature with a user-facing name, for the server list in Settings.
+    pub fn features(&self) -> [(&'static str, bool); 5] {
+        [
+            ("Internet radio", self.internet_radio),
+            ("Podcasts", self.podcasts),
+            ("Navidrome API", self.native_api),
+            ("Server lyrics", self.lyrics_by_song),
+            ("Top songs", self.top_songs),
+        ]
+    }
+
+    /// Whether the signed-in user may do `action`; unknown roles count as granted.
+    pub fn user_can(&self, action: UserAction) -> bool {
+        self.permissions
+            .is_none_or(|permissions| permissions.user_can(action))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
@@ -79,6 +165,8 @@ pub struct Song {
     pub duration: u32,
     #[serde(default)]
     pub track: Option<u32>,
+    #[serde(default, alias = "discNumber")]
+    pub disc_number: Option<u32>,
     #[serde(default, alias = "coverArt")]
     pub cover_art: Option<String>,
     #[serde(default, alias = "contentType")]
@@ -101,6 +189,24 @@ pub struct Song {
     pub year: Option<u32>,
     #[serde(default)]
     pub genre: Option<String>,
+    /// OpenSubsonic artist list; the first entry is the primary artist.
+    #[serde(default)]
+    pub artists: Vec<ArtistRef>,
+    /// OpenSubsonic credited roles (composer, conductor, ...).
+    #[serde(default)]
+    pub contributors: Vec<SongContributor>,
+    #[serde(default, alias = "displayComposer")]
+    pub display_composer: Option<String>,
+    /// File path on the server. Unlike the id, it survives a rebuilt library database.
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Set by servers that keep entries whose file is gone from disk (Navidrome's missing
+    /// files). Such entries can't be streamed.
+    #[serde(default)]
+    pub missing: bool,
+    /// Loudness tags the server read from the file, if it has any.
+    #[serde(default, alias = "replayGain")]
+    pub replay_gain: Option<ReplayGain>,
     #[serde(default)]
     pub server_id: String,
     #[serde(default)]
@@ -109,6 +215,121 @@ pub struct Song {
     pub queue_meta: Option<QueueSongMeta>,
 }
 
+/// OpenSubsonic `replayGain`: gains in dB and peaks as a share of full scale.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub struct ReplayGain {
+    #[serde(default, alias = "trackGain")]
+    pub track_gain: Option<f64>,
+    #[serde(default, alias = "albumGain")]
+    pub album_gain: Option<f64>,
+    #[serde(default, alias = "trackPeak")]
+    pub track_peak: Option<f64>,
+    #[serde(default, alias = "albumPeak")]
+    pub album_peak: Option<f64>,
+}
+
+/// Artist named on an OpenSubsonic song entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct ArtistRef {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct SongContributor {
+    #[serde(default)]
+    pub role: String,
+    /// Instrument or detail for roles like `performer`.
+    #[serde(default, alias = "subRole")]
+    pub sub_role: Option<String>,
+    #[serde(default)]
+    pub artist: ArtistRef,
+}
+
+/// One "Credits" line: a role label and the names credited with it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SongCredit {
+    pub role: String,
+    pub names: Vec<String>,
+}
+
+impl Song {
+    /// Composer credit from `displayComposer`, falling back to `composer` contributors.
+    pub fn composer(&self) -> Option<String> {
+        if let Some(composer) = self
+            .display_composer
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        {
+            return Some(composer.to_string());
+        }
+        let names: Vec<&str> = self
+            .contributors
+            .iter()
+            .filter(|contributor| contributor.role.eq_ignore_ascii_case("composer"))
+            .map(|contributor| contributor.artist.name.trim())
+            .filter(|name| !name.is_empty())
+            .collect();
+        if names.is_empty() {
+            None
+        } else {
+            Some(names.join(" • "))
+        }
+    }
+
+    /// Credited roles in the order the server lists them, with artists after the first
+    /// in `artists` shown as "Featuring". Empty for servers without OpenSubsonic.
+    pub fn credits(&self) -> Vec<SongCredit> {
+        let mut credits: Vec<SongCredit> = Vec::new();
+        let mut push = |role: String, name: &str| {
+            let name = name.trim();
+            if name.is_empty() {
+                return;
+            }
+            match credits.iter_mut().find(|credit| credit.role == role) {
+                Some(credit) => {
+                    if !credit.names.iter().any(|existing| existing == name) {
+                        credit.names.push(name.to_string());
+                    }
+                }
+                None => credits.push(SongCredit {
+                    role,
+                    names: vec![name.to_string()],
+                }),
+            }
+        };
+
+        for artist in self.artists.iter().skip(1) {
+            push("Featuring".to_string(), &artist.name);
+        }
+        for contributor in &self.contributors {
+            push(credit_role_label(contributor), &contributor.artist.name);
+        }
+        credits
+    }
+}
+
+fn credit_role_label(contributor: &SongContributor) -> String {
+    let role = contributor.role.trim();
+    let mut chars = role.chars();
+    let mut label = match chars.next() {
+        Some(first) => format!("{}{}", first.to_uppercase(), chars.as_str()),
+        None => "Contributor".to_string(),
+    };
+    if let Some(sub_role) = contributor
+        .sub_role
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        label.push_str(&format!(" ({sub_role})"));
+    }
+    label
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum QueueSourceKind {
     Album,
@@ -116,6 +337,8 @@ pub enum QueueSourceKind {
     Favorites,
     RandomMix,
     Artist,
+    /// A locally pinned artist station; `source_id` is the station id.
+    ArtistStation,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -126,6 +349,19 @@ pub struct QueueSongMeta {
     pub source_position: usize,
 }
 
+/// Someone currently playing a song on the server, from `getNowPlaying`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct NowPlayingEntry {
+    #[serde(flatten)]
+    pub song: Song,
+    #[serde(default)]
+    pub username: String,
+    #[serde(default, alias = "minutesAgo")]
+    pub minutes_ago: u32,
+    #[serde(default, alias = "playerName")]
+    pub player_name: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct Bookmark {
     #[serde(default)]
@@ -170,6 +406,25 @@ pub struct Playlist {
     pub changed: Option<String>,
 }
 
+impl Playlist {
+    /// Returns true when `latest` (typically a fresh getPlaylists entry) describes a
+    /// different revision than `self`. Servers that omit `changed` fall back to songCount.
+    pub fn differs_from(&self, latest: &Playlist) -> bool {
+        let changed = |playlist: &Playlist| {
+            playlist
+                .changed
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        match (changed(self), changed(latest)) {
+            (Some(current), Some(next)) => current != next,
+            _ => self.song_count != latest.song_count,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct RadioStation {
     pub id: String,
@@ -0,0 +1,396 @@
+// Optional MusicBrainz lookups used to flag studio albums missing from the library.
+use crate::api::Album;
+use crate::cache_service::{
+    get_json as cache_get_json, is_offline_mode, put_json as cache_put_json,
+};
+use crate::diagnostics::PerfTimer;
+use crate::network_policy::{strict_privacy_enabled, GuardedClient};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+
+const MUSICBRAINZ_API_BASE: &str = "https://musicbrainz.org/ws/2";
+const MUSICBRAINZ_CACHE_HOURS: u32 = 24 * 30;
+/// MusicBrainz asks anonymous clients to stay at or below one request per second.
+const MUSICBRAINZ_MIN_INTERVAL_MS: u128 = 1100;
+const MUSICBRAINZ_TIMEOUT: Duration = Duration::from_secs(15);
+const MUSICBRAINZ_ARTIST_MIN_SCORE: u32 = 90;
+
+static MUSICBRAINZ_HTTP_CLIENT: Lazy<GuardedClient> = Lazy::new(GuardedClient::new);
+static MUSICBRAINZ_LAST_REQUEST: Lazy<Mutex<Option<PerfTimer>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MusicBrainzRelease {
+    pub id: String,
+    pub title: String,
+    pub year: Option<u32>,
+}
+
+impl MusicBrainzRelease {
+    pub fn url(&self) -> String {
+        format!("https://musicbrainz.org/release-group/{}", self.id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MusicBrainzDiscography {
+    pub artist_id: String,
+    pub artist_name: String,
+    pub releases: Vec<MusicBrainzRelease>,
+}
+
+impl MusicBrainzDiscography {
+    pub fn artist_url(&self) -> String {
+        format!("https://musicbrainz.org/artist/{}", self.artist_id)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ArtistSearchResponse {
+    #[serde(default)]
+    artists: Vec<ArtistSearchEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArtistSearchEntry {
+    id: String,
+    name: String,
+    #[serde(default)]
+    score: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseGroupBrowseResponse {
+    #[serde(default, rename = "release-groups")]
+    release_groups: Vec<ReleaseGroupEntry>,
+    #[serde(default, rename = "release-group-count")]
+    release_group_count: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseGroupEntry {
+    id: String,
+    title: String,
+    #[serde(default, rename = "primary-type")]
+    primary_type: Option<String>,
+    #[serde(default, rename = "secondary-types")]
+    secondary_types: Vec<String>,
+    #[serde(default, rename = "first-release-date")]
+    first_release_date: Option<String>,
+}
+
+/// Lowercases, drops edition suffixes like "(Deluxe Edition)" or "[Remastered]",
+/// folds "&" into "and" and strips punctuation so library and MusicBrainz titles compare.
+pub fn normalize_release_title(title: &str) -> String {
+    let mut stripped = String::with_capacity(title.len());
+    let mut depth = 0usize;
+    for ch in title.chars() {
+        match ch {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => stripped.push(ch),
+            _ => {}
+        }
+    }
+    // Keep the bracketed text when it was the whole title.
+    let source = if stripped.trim().is_empty() {
+        title
+    } else {
+        stripped.as_str()
+    };
+
+    let folded = source.to_lowercase().replace('&', " and ");
+    folded
+        .chars()
+        .map(|ch| if ch.is_alphanumeric() { ch } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parses the leading year out of MusicBrainz dates ("1997", "1997-05", "1997-05-21").
+pub fn release_year_from_date(date: &str) -> Option<u32> {
+    date.trim()
+        .get(..4)
+        .and_then(|year| year.parse::<u32>().ok())
+        .filter(|year| *year > 0)
+}
+
+/// Returns the releases with no matching library album. Titles must match after
+/// normalization; years only disambiguate when several releases share a title
+/// (e.g. multiple self-titled albums), since remasters often carry a later year.
+pub fn find_missing_releases(
+    releases: &[MusicBrainzRelease],
+    albums: &[Album],
+) -> Vec<MusicBrainzRelease> {
+    let library: Vec<(String, Option<u32>)> = albums
+        .iter()
+        .map(|album| (normalize_release_title(&album.name), album.year))
+        .collect();
+
+    releases
+        .iter()
+        .filter(|release| {
+            let title = normalize_release_title(&release.title);
+            let title_is_ambiguous = releases
+                .iter()
+                .filter(|other| normalize_release_title(&other.title) == title)
+                .count()
+                > 1;
+            let owned = library.iter().any(|(album_title, album_year)| {
+                if *album_title != title {
+                    return false;
+                }
+                if !title_is_ambiguous {
+                    return true;
+                }
+                match (release.year, *album_year) {
+                    (Some(expected), Some(actual)) => expected.abs_diff(actual) <= 1,
+                    _ => true,
+                }
+            });
+            !owned
+        })
+        .cloned()
+        .collect()
+}
+
+fn is_studio_album(entry: &ReleaseGroupEntry) -> bool {
+    entry
+        .primary_type
+        .as_deref()
+        .is_some_and(|kind| kind.eq_ignore_ascii_case("album"))
+        && entry.secondary_types.is_empty()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn musicbrainz_delay_ms(ms: u64) {
+    tokio::time::sleep(Duration::from_millis(ms)).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn musicbrainz_delay_ms(ms: u64) {
+    gloo_timers::future::TimeoutFuture::new(ms as u32).await;
+}
+
+async fn wait_for_rate_limit() {
+    let wait_ms = {
+        let mut last = MUSICBRAINZ_LAST_REQUEST
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let wait = last
+            .map(|timer| MUSICBRAINZ_MIN_INTERVAL_MS.saturating_sub(timer.elapsed_ms()))
+            .unwrap_or(0);
+        // Claim the slot before sleeping so concurrent callers back off too.
+        *last = Some(PerfTimer::now());
+        wait
+    };
+    if wait_ms > 0 {
+        musicbrainz_delay_ms(wait_ms as u64).await;
+        let mut last = MUSICBRAINZ_LAST_REQUEST
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        *last = Some(PerfTimer::now());
+    }
+}
+
+async fn musicbrainz_get<T>(path: &str, params: &[(&str, String)]) -> Result<T, String>
+where
+    T: serde::de::DeserializeOwned,
+{
+    wait_for_rate_limit().await;
+    let mut query: Vec<(&str, String)> = params.to_vec();
+    query.push(("fmt", "json".to_string()));
+
+    let request = MUSICBRAINZ_HTTP_CLIENT
+        .get(format!("{MUSICBRAINZ_API_BASE}/{path}"))
+        .query(&query)
+        .timeout(MUSICBRAINZ_TIMEOUT);
+    // Browsers forbid overriding User-Agent; MusicBrainz identifies web clients by Origin.
+    #[cfg(not(target_arch = "wasm32"))]
+    let request = request.header(
+        reqwest::header::USER_AGENT,
+        concat!(
+            "RustySound/",
+            env!("CARGO_PKG_VERSION"),
+            " ( https://github.com/AD-Archer/RustySound )"
+        ),
+    );
+
+    let response = request.send().await.map_err(|error| error.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "MusicBrainz returned status {}",
+            response.status().as_u16()
+        ));
+    }
+    response
+        .json::<T>()
+        .await
+        .map_err(|error| error.to_string())
+}
+
+/// Looks up an artist's studio albums on MusicBrainz, cached for 30 days.
+pub async fn fetch_musicbrainz_discography(
+    artist_name: &str,
+) -> Result<MusicBrainzDiscography, String> {
+    let normalized_name = normalize_release_title(artist_name);
+    if normalized_name.is_empty() {
+        return Err("Missing artist name.".to_string());
+    }
+
+    let cache_key = format!("musicbrainz:discography:v1:{normalized_name}");
+    if let Some(cached) = cache_get_json::<MusicBrainzDiscography>(&cache_key) {
+        return Ok(cached);
+    }
+
+    if is_offline_mode() {
+        return Err("Offline mode is enabled. Disable it to check MusicBrainz.".to_string());
+    }
+
+    if strict_privacy_enabled() {
+        return Err("Strict privacy mode is enabled. MusicBrainz lookups are blocked.".to_string());
+    }
+
+    let search: ArtistSearchResponse = musicbrainz_get(
+        "artist",
+        &[
+            (
+                "query",
+                format!("artist:\"{}\"", artist_name.replace('"', "")),
+            ),
+            ("limit", "5".to_string()),
+        ],
+    )
+    .await?;
+    let artist = search
+        .artists
+        .iter()
+        .find(|entry| normalize_release_title(&entry.name) == normalized_name)
+        .or_else(|| {
+            search
+                .artists
+                .first()
+                .filter(|entry| entry.score >= MUSICBRAINZ_ARTIST_MIN_SCORE)
+        })
+        .ok_or_else(|| format!("No MusicBrainz artist found for \"{artist_name}\"."))?;
+
+    let mut releases = Vec::new();
+    let mut offset = 0usize;
+    loop {
+        let page: ReleaseGroupBrowseResponse = musicbrainz_get(
+            "release-group",
+            &[
+                ("artist", artist.id.clone()),
+                ("type", "album".to_string()),
+                ("limit", "100".to_string()),
+                ("offset", offset.to_string()),
+            ],
+        )
+        .await?;
+        let page_len = page.release_groups.len();
+        releases.extend(
+            page.release_groups
+                .into_iter()
+                .filter(is_studio_album)
+                .map(|entry| MusicBrainzRelease {
+                    year: entry
+                        .first_release_date
+                        .as_deref()
+                        .and_then(release_year_from_date),
+                    id: entry.id,
+                    title: entry.title,
+                }),
+        );
+        offset += page_len;
+        if page_len == 0 || offset >= page.release_group_count {
+            break;
+        }
+    }
+    releases.sort_by(|left, right| {
+        left.year
+            .cmp(&right.year)
+            .then_with(|| left.title.cmp(&right.title))
+    });
+
+    let discography = MusicBrainzDiscography {
+        artist_id: artist.id.clone(),
+        artist_name: artist.name.clone(),
+        releases,
+    };
+    let _ = cache_put_json(cache_key, &discography, Some(MUSICBRAINZ_CACHE_HOURS));
+    Ok(discography)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(id: &str, title: &str, year: Option<u32>) -> MusicBrainzRelease {
+        MusicBrainzRelease {
+            id: id.to_string(),
+            title: title.to_string(),
+            year,
+        }
+    }
+
+    fn album(name: &str, year: Option<u32>) -> Album {
+        Album {
+            id: name.to_string(),
+            name: name.to_string(),
+            year,
+            ..Album::default()
+        }
+    }
+
+    #[test]
+    fn release_titles_normalize_across_editions_and_punctuation() {
+        for (title, normalized) in [
+            ("OK Computer (Collector's Edition)", "ok computer"),
+            ("Kid A [Remastered]", "kid a"),
+            (
+                "Simon & Garfunkel's Greatest",
+                "simon and garfunkel s greatest",
+            ),
+            ("  The   Bends  ", "the bends"),
+            ("(What's the Story) Morning Glory?", "morning glory"),
+            ("[Untitled]", "untitled"),
+            ("Ágætis byrjun", "ágætis byrjun"),
+        ] {
+            assert_eq!(normalize_release_title(title), normalized, "{title}");
+        }
+    }
+
+    #[test]
+    fn release_years_come_from_the_leading_digits() {
+        assert_eq!(release_year_from_date("1997"), Some(1997));
+        assert_eq!(release_year_from_date("1997-05"), Some(1997));
+        assert_eq!(release_year_from_date(" 1997-05-21 "), Some(1997));
+        assert_eq!(release_year_from_date("0000"), None);
+        assert_eq!(release_year_from_date("97"), None);
+        assert_eq!(release_year_from_date(""), None);
+    }
+
+    #[test]
+    fn missing_releases_match_titles_and_use_years_only_for_duplicates() {
+        let releases = [
+            release("rg1", "Weezer", Some(1994)),
+            release("rg2", "Weezer", Some(2001)),
+            release("rg3", "Pinkerton", Some(1996)),
+            release("rg4", "Maladroit", Some(2002)),
+        ];
+        let albums = [
+            // A remaster with a later year still counts as owned for a unique title.
+            album("Pinkerton (Deluxe Edition)", Some(2010)),
+            album("Weezer", Some(1994)),
+        ];
+        let missing: Vec<String> = find_missing_releases(&releases, &albums)
+            .into_iter()
+            .map(|release| release.id)
+            .collect();
+        assert_eq!(missing, ["rg2", "rg4"]);
+        assert_eq!(find_missing_releases(&releases, &[]).len(), 4);
+    }
+}
@@ -7,8 +7,12 @@ impl NavidromeClient {
     fn auth_params(&self) -> String {
         let mut cache = AUTH_CACHE.lock().unwrap_or_else(|e| e.into_inner());
         let cache_key = format!(
-            "{}:{}:{}:{}",
-            self.server.id, self.server.username, self.server.url, self.server.password
+            "{}:{}:{}:{}:{}",
+            self.server.id,
+            self.server.username,
+            self.server.url,
+            self.server.password,
+            self.server.reported_client_name()
         );
 
         if let Some(value) = cache.get(&cache_key) {
@@ -42,7 +46,11 @@ impl NavidromeClient {
 
         format!(
             "u={}&t={}&s={}&v={}&c={}&f=json",
-            self.server.username, token, salt, API_VERSION, CLIENT_NAME
+            self.server.username,
+            token,
+            salt,
+            API_VERSION,
+            urlencoding_simple(self.server.reported_client_name())
         )
     }
 
@@ -99,6 +107,70 @@ impl NavidromeClient {
         cache.remove(&key);
     }
 
+    /// Drops every cached session for this server and flags it for re-authentication.
+    pub fn mark_auth_rejected(&self) {
+        let prefix = format!("{}:", self.server.id);
+        AUTH_CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|key, _| !key.starts_with(&prefix));
+        NATIVE_AUTH_CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|key, _| !key.starts_with(&prefix));
+        REAUTH_REQUIRED
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(self.server.id.clone());
+    }
+
+    /// Drops this server's cached token, salt and native session so the next stream URL is
+    /// signed afresh. Unlike `mark_auth_rejected`, the server isn't flagged for re-login.
+    pub fn refresh_stream_auth(&self) {
+        let prefix = format!("{}:", self.server.id);
+        AUTH_CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|key, _| !key.starts_with(&prefix));
+        NATIVE_AUTH_CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|key, _| !key.starts_with(&prefix));
+    }
+
+    /// Parses a Subsonic response into an `ok` envelope, mapping HTTP failures and `failed`
+    /// envelopes to [`ApiError`]s and flagging the server when its credentials are rejected.
+    async fn read_subsonic_response(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<SubsonicResponse, ApiError> {
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok());
+        if let Some(error) = ApiError::from_http_status(status.as_u16(), retry_after) {
+            if error.is_auth_failed() {
+                self.mark_auth_rejected();
+            }
+            return Err(error);
+        }
+        let json: SubsonicResponse = response.json().await?;
+        if json.subsonic_response.status == "ok" {
+            return Ok(json);
+        }
+        // Subsonic reports bad credentials inside a 200 response.
+        let error = match json.subsonic_response.error {
+            Some(error) => ApiError::from_subsonic(error.code, error.message),
+            None => ApiError::generic("Unknown error"),
+        };
+        if error.is_auth_failed() {
+            self.mark_auth_rejected();
+        }
+        Err(error)
+    }
+
     fn invalidate_favorites_cache(&self) {
         let _ = cache_remove_prefix(&format!("api:getStarred2:v1:{}", self.server.id));
         let _ = cache_remove_prefix("view:favorites:v1:");
@@ -113,7 +185,7 @@ impl NavidromeClient {
         self.invalidate_playlist_cache();
     }
 
-    async fn ensure_native_auth_session(&self) -> Result<NativeAuthSession, String> {
+    async fn ensure_native_auth_session(&self) -> Result<NativeAuthSession, ApiError> {
         let key = self.native_cache_key();
         {
             let cache = NATIVE_AUTH_CACHE.lock().unwrap_or_else(|e| e.into_inner());
@@ -122,6 +194,23 @@ impl NavidromeClient {
             }
         }
 
+        let session = match self.native_login().await {
+            Ok(session) => session,
+            Err(error) => {
+                if error.is_auth_failed() {
+                    self.mark_auth_rejected();
+                }
+                return Err(error);
+            }
+        };
+        let mut cache = NATIVE_AUTH_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        cache.insert(key, session.clone());
+        Ok(session)
+    }
+
+    /// Signs in to Navidrome's own API without caching the session or flagging the account:
+    /// servers that lack the API may refuse the request with an auth status too.
+    async fn native_login(&self) -> Result<NativeAuthSession, ApiError> {
         let login_url = self.native_base_url("auth/login");
         let payload = NativeLoginRequest {
             username: self.server.username.clone(),
@@ -132,35 +221,14 @@ impl NavidromeClient {
             .post(login_url)
             .json(&payload)
             .send()
-            .await
-            .map_err(|e| e.to_string())?;
+            .await?;
 
-        if !response.status().is_success() {
-            return Err(format!(
-                "Native API login failed with status {}",
-                response.status()
-            ));
+        if let Some(error) = ApiError::from_http_status(response.status().as_u16(), None) {
+            return Err(error);
         }
 
-        let login: NativeLoginResponse = response.json().await.map_err(|e| e.to_string())?;
-        let token = login
-            .token
-            .map(|value| value.trim().to_string())
-            .filter(|value| !value.is_empty())
-            .ok_or_else(|| "Native API login did not return a token.".to_string())?;
-        let client_unique_id = login
-            .id
-            .map(|value| value.trim().to_string())
-            .filter(|value| !value.is_empty())
-            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
-
-        let session = NativeAuthSession {
-            token,
-            client_unique_id,
-        };
-        let mut cache = NATIVE_AUTH_CACHE.lock().unwrap_or_else(|e| e.into_inner());
-        cache.insert(key, session.clone());
-        Ok(session)
+        let login: NativeLoginResponse = response.json().await?;
+        NativeAuthSession::from_login(login)
     }
 
     fn normalize_native_song_list(&self, payload: serde_json::Value) -> Vec<Song> {
@@ -197,6 +265,7 @@ impl NavidromeClient {
             let artist_id = json_pick_string(&value, &["artistId", "artist_id", "artist_id_fk"]);
             let duration = json_pick_u32(&value, &["duration", "duration_seconds"]).unwrap_or(0);
             let track = json_pick_u32(&value, &["track", "trackNumber", "track_number"]);
+            let disc_number = json_pick_u32(&value, &["discNumber", "disc_number"]);
             let cover_art = json_pick_string(
                 &value,
                 &["coverArt", "coverArtId", "cover_art", "cover_art_id"],
@@ -228,6 +297,15 @@ impl NavidromeClient {
                 json_pick_string(&value, &["lastPlayed", "played", "playDate", "play_date"]);
             let year = json_pick_u32(&value, &["year"]);
             let genre = json_pick_string(&value, &["genre"]);
+            let display_composer = json_pick_string(&value, &["displayComposer", "composer"]);
+            let path = json_pick_string(&value, &["path"]);
+            let missing = json_pick_bool(&value, &["missing"]).unwrap_or(false);
+            let replay_gain = ReplayGain {
+                track_gain: json_pick_f64(&value, &["rgTrackGain", "rg_track_gain"]),
+                album_gain: json_pick_f64(&value, &["rgAlbumGain", "rg_album_gain"]),
+                track_peak: json_pick_f64(&value, &["rgTrackPeak", "rg_track_peak"]),
+                album_peak: json_pick_f64(&value, &["rgAlbumPeak", "rg_album_peak"]),
+            };
 
             let mut song = Song {
                 id,
@@ -238,6 +316,7 @@ impl NavidromeClient {
                 artist_id,
                 duration,
                 track,
+                disc_number,
                 cover_art,
                 content_type,
                 stream_url: None,
@@ -249,6 +328,12 @@ impl NavidromeClient {
                 played,
                 year,
                 genre,
+                artists: Vec::new(),
+                contributors: Vec::new(),
+                display_composer,
+                path,
+                missing,
+                replay_gain: (replay_gain != ReplayGain::default()).then_some(replay_gain),
                 server_id: self.server.id.clone(),
                 server_name: self.server.name.clone(),
                 queue_meta: None,
@@ -266,7 +351,7 @@ impl NavidromeClient {
         order: NativeSortOrder,
         start: usize,
         end: usize,
-    ) -> Result<Vec<Song>, String> {
+    ) -> Result<Vec<Song>, ApiError> {
         if end < start {
             return Ok(Vec::new());
         }
@@ -286,26 +371,25 @@ impl NavidromeClient {
                 .header("x-nd-authorization", format!("Bearer {}", session.token))
                 .header("x-nd-client-unique-id", session.client_unique_id)
                 .send()
-                .await
-                .map_err(|e| e.to_string())?;
+                .await?;
 
             if response.status() == reqwest::StatusCode::UNAUTHORIZED && attempt == 0 {
                 self.clear_native_auth_session();
                 continue;
             }
 
-            if !response.status().is_success() {
-                return Err(format!(
-                    "Native songs request failed with status {}",
-                    response.status()
-                ));
+            if let Some(error) = ApiError::from_http_status(response.status().as_u16(), None) {
+                if error.is_auth_failed() {
+                    self.mark_auth_rejected();
+                }
+                return Err(error);
             }
 
-            let payload: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+            let payload: serde_json::Value = response.json().await?;
             return Ok(self.normalize_native_song_list(payload));
         }
 
-        Err("Native songs request could not be authorized.".to_string())
+        Err(ApiError::AuthFailed)
     }
 
     pub fn get_cover_art_url(&self, cover_art_id: &str, size: u32) -> String {
@@ -352,8 +436,7 @@ impl NavidromeClient {
         )
     }
 
-    #[allow(dead_code)]
-    pub fn get_stream_url(&self, song_id: &str) -> String {
+    fn base_stream_url(&self, song_id: &str) -> String {
         let auth = self.auth_params_for_binary();
         format!(
             "{}/rest/stream?{}&id={}",
@@ -363,8 +446,164 @@ impl NavidromeClient {
         )
     }
 
+    /// Stream URL for playing a song as the server chooses, or as the original file when
+    /// the server entry asks for raw streams.
+    pub fn get_stream_url(&self, song_id: &str) -> String {
+        let url = self.base_stream_url(song_id);
+        if self.server.raw_stream {
+            format!("{url}&format=raw")
+        } else {
+            url
+        }
+    }
+
+    /// Stream URL that asks the server to transcode to `format` capped at `max_bitrate_kbps`.
+    pub fn get_transcoded_stream_url(
+        &self,
+        song_id: &str,
+        format: &str,
+        max_bitrate_kbps: u32,
+    ) -> String {
+        format!(
+            "{}&format={}&maxBitRate={}",
+            self.base_stream_url(song_id),
+            urlencoding_simple(format),
+            max_bitrate_kbps
+        )
+    }
+
+    /// HTTP status `stream_url` answers with right now, from a ranged request for its first
+    /// byte. `None` when the server can't be reached; expired links answer 401 or 403.
+    pub async fn stream_url_status(stream_url: &str) -> Option<u16> {
+        let request = HTTP_CLIENT
+            .get(stream_url)
+            .header(reqwest::header::RANGE, "bytes=0-0");
+        #[cfg(not(target_arch = "wasm32"))]
+        let request = request.timeout(Duration::from_secs(8));
+        let response = request.send().await.ok()?;
+        Some(response.status().as_u16())
+    }
+
+    /// Headers the server sends for `stream_url`, probed once per `track_key`. The probe is
+    /// its own ranged request for the first byte, so the playing stream is never touched.
     #[cfg(not(target_arch = "wasm32"))]
-    pub async fn read_icy_now_playing(stream_url: &str) -> Result<Option<IcyNowPlaying>, String> {
+    pub async fn probe_stream_headers(
+        track_key: &str,
+        stream_url: &str,
+    ) -> Result<StreamProbe, ApiError> {
+        if let Some(probe) = STREAM_PROBES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(track_key)
+        {
+            return Ok(probe.clone());
+        }
+
+        let response = HTTP_CLIENT
+            .get(stream_url)
+            .header(reqwest::header::RANGE, "bytes=0-0")
+            .header("Icy-MetaData", "1")
+            .header("User-Agent", DEFAULT_CLIENT_NAME)
+            .timeout(Duration::from_secs(8))
+            .send()
+            .await?;
+        let headers = response
+            .headers()
+            .iter()
+            .filter(|(name, _)| {
+                let name = name.as_str();
+                name.starts_with("icy-") || STREAM_PROBE_HEADERS.contains(&name)
+            })
+            .map(|(name, value)| {
+                let value = String::from_utf8_lossy(value.as_bytes()).trim().to_string();
+                (name.as_str().to_string(), value)
+            })
+            .collect();
+        let probe = StreamProbe {
+            status: response.status().as_u16(),
+            headers,
+        };
+        // Dropping the response closes the probe connection before any audio is read.
+        drop(response);
+
+        STREAM_PROBES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(track_key.to_string(), probe.clone());
+        Ok(probe)
+    }
+
+    /// Requests the first byte of a random song the way playback does and compares the
+    /// content type that arrives with the file on the server.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn observe_stream_negotiation(&self) -> Result<StreamNegotiation, ApiError> {
+        let song = self
+            .get_random_songs(1)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(ApiError::NotFound { kind: "song" })?;
+        let response = HTTP_CLIENT
+            .get(self.get_stream_url(&song.id))
+            .header(reqwest::header::RANGE, "bytes=0-0")
+            .header("User-Agent", DEFAULT_CLIENT_NAME)
+            .timeout(Duration::from_secs(8))
+            .send()
+            .await?;
+        let delivered_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(canonical_audio_mime);
+        drop(response);
+
+        let source_type = song.content_type.as_deref().and_then(canonical_audio_mime);
+        let transcoded = matches!(
+            (&source_type, &delivered_type),
+            (Some(source), Some(delivered)) if source != delivered
+        );
+        let mut source = song
+            .suffix
+            .as_deref()
+            .map(str::to_uppercase)
+            .or(source_type)
+            .unwrap_or_else(|| "unknown format".to_string());
+        if let Some(kbps) = song.bitrate.filter(|kbps| *kbps > 0) {
+            source.push_str(&format!(" {kbps} kbps"));
+        }
+
+        let negotiation = StreamNegotiation {
+            client_name: self.server.reported_client_name().to_string(),
+            raw_requested: self.server.raw_stream,
+            player_rules: self
+                .capabilities()
+                .is_some_and(|capabilities| capabilities.native_api),
+            sample_title: song.title,
+            source,
+            delivered_type,
+            transcoded,
+        };
+        STREAM_NEGOTIATIONS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(self.server.id.clone(), negotiation.clone());
+        Ok(negotiation)
+    }
+
+    /// The negotiation last observed for this server in this session.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn stream_negotiation(&self) -> Option<StreamNegotiation> {
+        STREAM_NEGOTIATIONS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&self.server.id)
+            .cloned()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn read_icy_now_playing(
+        stream_url: &str,
+    ) -> Result<Option<IcyNowPlaying>, ApiError> {
         for candidate_url in icy_metadata_candidate_urls(stream_url) {
             if let Ok(Some(now_playing)) = read_icy_now_playing_from_url(&candidate_url).await {
                 return Ok(Some(now_playing));
@@ -375,8 +614,14 @@ impl NavidromeClient {
     }
 
     #[cfg(target_arch = "wasm32")]
-    pub async fn read_icy_now_playing(stream_url: &str) -> Result<Option<IcyNowPlaying>, String> {
-        let seed_url = serde_json::to_string(stream_url).map_err(|e| e.to_string())?;
+    pub async fn read_icy_now_playing(
+        stream_url: &str,
+    ) -> Result<Option<IcyNowPlaying>, ApiError> {
+        // The browser fetch below bypasses the shared HTTP client, so check the allowlist here.
+        if !crate::network_policy::allows_url(stream_url) {
+            return Ok(None);
+        }
+        let seed_url = serde_json::to_string(stream_url)?;
         let script = format!(
             r#"return (async () => {{
                 const seedUrl = {seed_url};
@@ -491,6 +736,29 @@ impl NavidromeClient {
         document::eval(&script)
             .join::<Option<IcyNowPlaying>>()
             .await
-            .map_err(|e| e.to_string())
+            .map_err(|e| ApiError::Parse(e.to_string()))
     }
 }
+
+/// A content type without parameters, lowercased, with the common aliases of one format
+/// (`audio/x-flac`, `audio/mp3`, ...) folded to a single name so they compare equal.
+#[cfg(not(target_arch = "wasm32"))]
+fn canonical_audio_mime(value: &str) -> Option<String> {
+    let mime = value
+        .split(';')
+        .next()
+        .unwrap_or(value)
+        .trim()
+        .to_ascii_lowercase();
+    let canonical = match mime.as_str() {
+        "" => return None,
+        "audio/x-flac" => "audio/flac",
+        "audio/mp3" | "audio/mpeg3" | "audio/x-mp3" | "audio/x-mpeg" => "audio/mpeg",
+        "audio/x-m4a" | "audio/m4a" | "audio/x-mp4" => "audio/mp4",
+        "audio/x-aac" | "audio/aacp" => "audio/aac",
+        "audio/x-wav" | "audio/wave" | "audio/vnd.wave" => "audio/wav",
+        "audio/x-ogg" | "application/ogg" => "audio/ogg",
+        _ => return Some(mime),
+    };
+    Some(canonical.to_string())
+}
@@ -1,21 +1,9 @@
 // Bookmark/favorite/rating APIs plus playlist read endpoints.
 impl NavidromeClient {
-    pub async fn get_bookmarks(&self) -> Result<Vec<Bookmark>, String> {
+    pub async fn get_bookmarks(&self) -> Result<Vec<Bookmark>, ApiError> {
         let url = self.build_url("getBookmarks", &[]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
 
         let mut bookmarks = json
             .subsonic_response
@@ -39,27 +27,29 @@ impl NavidromeClient {
         Ok(bookmarks)
     }
 
-    pub async fn star(&self, id: &str, item_type: &str) -> Result<(), String> {
+    /// Stars an item. While the server can't be reached the change is queued for replay and
+    /// reported as done.
+    pub async fn star(&self, id: &str, item_type: &str) -> Result<(), ApiError> {
+        let result = self.star_now(id, item_type).await;
+        self.queue_if_unreachable(
+            result,
+            PendingMutationOp::Star {
+                item_id: id.to_string(),
+                item_type: item_type.to_string(),
+            },
+        )
+        .await
+    }
+
+    async fn star_now(&self, id: &str, item_type: &str) -> Result<(), ApiError> {
         let param = match item_type {
             "artist" => "artistId",
             "album" => "albumId",
             _ => "id",
         };
         let url = self.build_url("star", &[(param, id)]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        self.read_subsonic_response(response).await?;
 
         if item_type == "playlist" {
             self.invalidate_playlist_cache();
@@ -75,7 +65,7 @@ impl NavidromeClient {
         song_id: &str,
         position_ms: u64,
         comment: Option<&str>,
-    ) -> Result<(), String> {
+    ) -> Result<(), ApiError> {
         self.create_bookmark_with_limit(song_id, position_ms, comment, None)
             .await
     }
@@ -86,7 +76,7 @@ impl NavidromeClient {
         position_ms: u64,
         comment: Option<&str>,
         max_bookmarks: Option<usize>,
-    ) -> Result<(), String> {
+    ) -> Result<(), ApiError> {
         let position_string = position_ms.to_string();
         let mut params: Vec<(&str, &str)> =
             vec![("id", song_id), ("position", position_string.as_str())];
@@ -97,20 +87,8 @@ impl NavidromeClient {
         }
 
         let url = self.build_url("createBookmark", &params);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        self.read_subsonic_response(response).await?;
 
         if let Some(limit) = max_bookmarks.filter(|value| *value > 0) {
             self.prune_oldest_bookmarks(limit).await;
@@ -140,47 +118,36 @@ impl NavidromeClient {
         }
     }
 
-    pub async fn delete_bookmark(&self, song_id: &str) -> Result<(), String> {
+    pub async fn delete_bookmark(&self, song_id: &str) -> Result<(), ApiError> {
         let url = self.build_url("deleteBookmark", &[("id", song_id)]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        self.read_subsonic_response(response).await?;
 
         Ok(())
     }
 
-    pub async fn unstar(&self, id: &str, item_type: &str) -> Result<(), String> {
+    /// Unstars an item, queueing the change like `star` while the server can't be reached.
+    pub async fn unstar(&self, id: &str, item_type: &str) -> Result<(), ApiError> {
+        let result = self.unstar_now(id, item_type).await;
+        self.queue_if_unreachable(
+            result,
+            PendingMutationOp::Unstar {
+                item_id: id.to_string(),
+                item_type: item_type.to_string(),
+            },
+        )
+        .await
+    }
+
+    async fn unstar_now(&self, id: &str, item_type: &str) -> Result<(), ApiError> {
         let param = match item_type {
             "artist" => "artistId",
             "album" => "albumId",
             _ => "id",
         };
         let url = self.build_url("unstar", &[(param, id)]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        self.read_subsonic_response(response).await?;
 
         if item_type == "playlist" {
             self.invalidate_playlist_cache();
@@ -190,47 +157,47 @@ impl NavidromeClient {
         Ok(())
     }
 
-    pub async fn set_rating(&self, id: &str, rating: u32) -> Result<(), String> {
+    /// Rates a song or album, queueing the change like `star` while the server can't be
+    /// reached.
+    pub async fn set_rating(&self, id: &str, rating: u32) -> Result<(), ApiError> {
+        let previous = self.cached_user_rating(id);
+        let result = self.set_rating_now(id, rating).await;
+        self.queue_if_unreachable(
+            result,
+            PendingMutationOp::SetRating {
+                item_id: id.to_string(),
+                rating,
+                previous,
+            },
+        )
+        .await
+    }
+
+    async fn set_rating_now(&self, id: &str, rating: u32) -> Result<(), ApiError> {
         let url = self.build_url("setRating", &[("id", id), ("rating", &rating.to_string())]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        self.read_subsonic_response(response).await?;
 
+        // Cached copies would report the old rating as the baseline for a later offline edit.
+        self.invalidate_item_cache(id);
         Ok(())
     }
 
-    pub async fn get_playlists(&self) -> Result<Vec<Playlist>, String> {
+    pub async fn get_playlists(&self) -> Result<Vec<Playlist>, ApiError> {
         let cache_key = format!("api:getPlaylists:v1:{}", self.server.id);
         if let Some(cached) = cache_get_json::<Vec<Playlist>>(&cache_key) {
             return Ok(cached);
         }
 
+        self.get_playlists_fresh().await
+    }
+
+    /// Fetches getPlaylists from the server, bypassing (but refreshing) the cache.
+    pub async fn get_playlists_fresh(&self) -> Result<Vec<Playlist>, ApiError> {
+        let cache_key = format!("api:getPlaylists:v1:{}", self.server.id);
         let url = self.build_url("getPlaylists", &[]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
 
         let mut playlists = json
             .subsonic_response
@@ -247,32 +214,29 @@ impl NavidromeClient {
         Ok(playlists)
     }
 
-    pub async fn get_playlist(&self, playlist_id: &str) -> Result<(Playlist, Vec<Song>), String> {
+    pub async fn get_playlist(&self, playlist_id: &str) -> Result<(Playlist, Vec<Song>), ApiError> {
         let cache_key = format!("api:getPlaylist:v1:{}:{}", self.server.id, playlist_id);
         if let Some(cached) = cache_get_json::<(Playlist, Vec<Song>)>(&cache_key) {
             return Ok(cached);
         }
 
+        self.get_playlist_fresh(playlist_id).await
+    }
+
+    /// Fetches getPlaylist from the server, bypassing (but refreshing) the cache.
+    pub async fn get_playlist_fresh(
+        &self,
+        playlist_id: &str,
+    ) -> Result<(Playlist, Vec<Song>), ApiError> {
+        let cache_key = format!("api:getPlaylist:v1:{}:{}", self.server.id, playlist_id);
         let url = self.build_url("getPlaylist", &[("id", playlist_id)]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
 
         let mut playlist_with_entries = json
             .subsonic_response
             .playlist
-            .ok_or("Playlist not found")?;
+            .ok_or(ApiError::NotFound { kind: "playlist" })?;
         playlist_with_entries.server_id = self.server.id.clone();
         normalize_playlist_cover_art(&mut playlist_with_entries.playlist);
 
@@ -288,4 +252,30 @@ impl NavidromeClient {
         let _ = cache_put_json(cache_key, &payload, Some(12));
         Ok(payload)
     }
+
+    /// Like `get_playlist`, but uses a fresh getPlaylists entry as a cheap probe and
+    /// only refetches the detail when the cached copy is out of date.
+    pub async fn get_playlist_checked(
+        &self,
+        playlist_id: &str,
+    ) -> Result<(Playlist, Vec<Song>), ApiError> {
+        let cache_key = format!("api:getPlaylist:v1:{}:{}", self.server.id, playlist_id);
+        let Some(cached) = cache_get_json::<(Playlist, Vec<Song>)>(&cache_key) else {
+            return self.get_playlist(playlist_id).await;
+        };
+
+        let Ok(latest) = self.get_playlists_fresh().await else {
+            return Ok(cached);
+        };
+        let stale = latest
+            .iter()
+            .find(|playlist| playlist.id == playlist_id)
+            .map(|entry| cached.0.differs_from(entry))
+            .unwrap_or(true);
+        if !stale {
+            return Ok(cached);
+        }
+
+        self.get_playlist_fresh(playlist_id).await
+    }
 }
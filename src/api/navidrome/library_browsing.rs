@@ -1,45 +1,34 @@
 // Read-oriented browsing APIs for artists, albums, songs, scan status, and favorites.
 impl NavidromeClient {
-    pub async fn ping(&self) -> Result<bool, String> {
+    pub async fn ping(&self) -> Result<bool, ApiError> {
         let url = self.build_url("ping", &[]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        match json.subsonic_response.status.as_str() {
-            "ok" => Ok(true),
-            _ => Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string())),
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        self.read_subsonic_response(response).await?;
+        Ok(true)
     }
 
-    pub async fn get_artists(&self) -> Result<Vec<Artist>, String> {
-        let cache_key = format!("api:getArtists:v1:{}", self.server.id);
+    /// Artists across every music folder, or only `music_folder_id` when set. Always asks
+    /// getArtists, whose entries carry the album counts the album-artists filter needs.
+    pub async fn get_artists(
+        &self,
+        music_folder_id: Option<&str>,
+    ) -> Result<Vec<Artist>, ApiError> {
+        let cache_key = format!(
+            "api:getArtists:v2:{}{}",
+            self.server.id,
+            music_folder_cache_suffix(music_folder_id)
+        );
         if let Some(cached) = cache_get_json::<Vec<Artist>>(&cache_key) {
             return Ok(cached);
         }
 
-        let url = self.build_url("getArtists", &[]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let params: Vec<(&str, &str)> = music_folder_id
+            .map(|folder_id| ("musicFolderId", folder_id))
+            .into_iter()
+            .collect();
+        let url = self.build_url("getArtists", &params);
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
 
         let mut artists = Vec::new();
         if let Some(artists_data) = json.subsonic_response.artists {
@@ -55,42 +44,78 @@ impl NavidromeClient {
         Ok(artists)
     }
 
+    /// Music folders (libraries) reported by the server. Probed once per session alongside
+    /// the other per-server capability data, and kept in the response cache for a day.
+    pub async fn get_music_folders(&self) -> Result<Vec<MusicFolder>, ApiError> {
+        {
+            let folders = MUSIC_FOLDERS.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(cached) = folders.get(&self.server.id) {
+                return Ok(cached.clone());
+            }
+        }
+
+        let cache_key = format!("api:getMusicFolders:v1:{}", self.server.id);
+        let folders = match cache_get_json::<Vec<MusicFolder>>(&cache_key) {
+            Some(cached) => cached,
+            None => {
+                let url = self.build_url("getMusicFolders", &[]);
+                let response = HTTP_CLIENT.get(&url).send().await?;
+                let json = self.read_subsonic_response(response).await?;
+                let folders: Vec<MusicFolder> = json
+                    .subsonic_response
+                    .music_folders
+                    .and_then(|container| container.music_folder)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|folder| {
+                        let id = json_pick_string(folder, &["id"])?;
+                        let name = json_pick_string(folder, &["name"])
+                            .unwrap_or_else(|| format!("Folder {id}"));
+                        Some(MusicFolder { id, name })
+                    })
+                    .collect();
+                let _ = cache_put_json(cache_key, &folders, Some(24));
+                folders
+            }
+        };
+
+        let mut cache = MUSIC_FOLDERS.lock().unwrap_or_else(|e| e.into_inner());
+        cache.insert(self.server.id.clone(), folders.clone());
+        Ok(folders)
+    }
+
     pub async fn get_albums(
         &self,
         album_type: &str,
         size: u32,
         offset: u32,
-    ) -> Result<Vec<Album>, String> {
+        music_folder_id: Option<&str>,
+    ) -> Result<Vec<Album>, ApiError> {
         let cache_key = format!(
-            "api:getAlbumList2:v1:{}:{}:{}:{}",
-            self.server.id, album_type, size, offset
+            "api:getAlbumList2:v1:{}:{}:{}:{}{}",
+            self.server.id,
+            album_type,
+            size,
+            offset,
+            music_folder_cache_suffix(music_folder_id)
         );
         if let Some(cached) = cache_get_json::<Vec<Album>>(&cache_key) {
             return Ok(cached);
         }
 
-        let url = self.build_url(
-            "getAlbumList2",
-            &[
-                ("type", album_type),
-                ("size", &size.to_string()),
-                ("offset", &offset.to_string()),
-            ],
-        );
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
+        let size = size.to_string();
+        let offset = offset.to_string();
+        let mut params = vec![
+            ("type", album_type),
+            ("size", size.as_str()),
+            ("offset", offset.as_str()),
+        ];
+        if let Some(folder_id) = music_folder_id {
+            params.push(("musicFolderId", folder_id));
         }
+        let url = self.build_url("getAlbumList2", &params);
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
 
         let mut albums = json
             .subsonic_response
@@ -107,29 +132,20 @@ impl NavidromeClient {
         Ok(albums)
     }
 
-    pub async fn get_album(&self, album_id: &str) -> Result<(Album, Vec<Song>), String> {
+    pub async fn get_album(&self, album_id: &str) -> Result<(Album, Vec<Song>), ApiError> {
         let cache_key = format!("api:getAlbum:v1:{}:{}", self.server.id, album_id);
         if let Some(cached) = cache_get_json::<(Album, Vec<Song>)>(&cache_key) {
             return Ok(cached);
         }
 
         let url = self.build_url("getAlbum", &[("id", album_id)]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
 
-        let mut album_with_songs = json.subsonic_response.album.ok_or("Album not found")?;
+        let mut album_with_songs = json
+            .subsonic_response
+            .album
+            .ok_or(ApiError::NotFound { kind: "album" })?;
         album_with_songs.server_id = self.server.id.clone();
         normalize_album_cover_art(&mut album_with_songs.album);
 
@@ -146,34 +162,31 @@ impl NavidromeClient {
         Ok(payload)
     }
 
-    pub async fn get_song(&self, song_id: &str) -> Result<Song, String> {
+    pub async fn get_song(&self, song_id: &str) -> Result<Song, ApiError> {
+        let cache_key = format!("api:getSong:v1:{}:{}", self.server.id, song_id.trim());
+        if let Some(cached) = cache_get_json::<Song>(&cache_key) {
+            return Ok(cached);
+        }
+
+        self.get_song_fresh(song_id).await
+    }
+
+    /// Fetches getSong from the server, bypassing (but refreshing) the cache.
+    pub async fn get_song_fresh(&self, song_id: &str) -> Result<Song, ApiError> {
         let song_id = song_id.trim();
         if song_id.is_empty() {
-            return Err("Song not found".to_string());
+            return Err(ApiError::NotFound { kind: "song" });
         }
 
         let cache_key = format!("api:getSong:v1:{}:{}", self.server.id, song_id);
-        if let Some(cached) = cache_get_json::<Song>(&cache_key) {
-            return Ok(cached);
-        }
-
         let url = self.build_url("getSong", &[("id", song_id)]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
 
-        let mut song = json.subsonic_response.song.ok_or("Song not found")?;
+        let mut song = json
+            .subsonic_response
+            .song
+            .ok_or(ApiError::NotFound { kind: "song" })?;
         song.server_id = self.server.id.clone();
         song.server_name = self.server.name.clone();
         normalize_song_cover_art(&mut song);
@@ -181,32 +194,58 @@ impl NavidromeClient {
         Ok(song)
     }
 
-    pub async fn get_artist(&self, artist_id: &str) -> Result<(Artist, Vec<Album>), String> {
+    /// Every song in the library, paged through an empty search and read past the cache.
+    pub async fn get_library_songs(&self) -> Result<Vec<Song>, ApiError> {
+        const PAGE_SIZE: usize = 500;
+
+        let page_size = PAGE_SIZE.to_string();
+        let mut songs = Vec::new();
+        loop {
+            let offset = songs.len().to_string();
+            let url = self.build_url(
+                "search3",
+                &[
+                    ("query", ""),
+                    ("artistCount", "0"),
+                    ("albumCount", "0"),
+                    ("songCount", page_size.as_str()),
+                    ("songOffset", offset.as_str()),
+                ],
+            );
+            let response = HTTP_CLIENT.get(&url).send().await?;
+            let json = self.read_subsonic_response(response).await?;
+            let batch = json
+                .subsonic_response
+                .search_result3
+                .and_then(|search| search.song)
+                .unwrap_or_default();
+            let batch_len = batch.len();
+            for mut song in batch {
+                song.server_id = self.server.id.clone();
+                song.server_name = self.server.name.clone();
+                normalize_song_cover_art(&mut song);
+                songs.push(song);
+            }
+            if batch_len < PAGE_SIZE {
+                return Ok(songs);
+            }
+        }
+    }
+
+    pub async fn get_artist(&self, artist_id: &str) -> Result<(Artist, Vec<Album>), ApiError> {
         let cache_key = format!("api:getArtist:v1:{}:{}", self.server.id, artist_id);
         if let Some(cached) = cache_get_json::<(Artist, Vec<Album>)>(&cache_key) {
             return Ok(cached);
         }
 
         let url = self.build_url("getArtist", &[("id", artist_id)]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
 
         let mut artist_with_albums = json
             .subsonic_response
             .artist_detail
-            .ok_or("Artist not found")?;
+            .ok_or(ApiError::NotFound { kind: "artist" })?;
         artist_with_albums.server_id = self.server.id.clone();
 
         let mut albums = artist_with_albums.album.take().unwrap_or_default();
@@ -229,22 +268,10 @@ impl NavidromeClient {
         Ok(payload)
     }
 
-    pub async fn get_random_songs(&self, size: u32) -> Result<Vec<Song>, String> {
+    pub async fn get_random_songs(&self, size: u32) -> Result<Vec<Song>, ApiError> {
         let url = self.build_url("getRandomSongs", &[("size", &size.to_string())]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
 
         Ok(self.normalize_song_list(json.subsonic_response.random_songs))
     }
@@ -260,135 +287,91 @@ impl NavidromeClient {
     }
 
     #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
-    pub async fn get_similar_songs(&self, id: &str, count: u32) -> Result<Vec<Song>, String> {
+    pub async fn get_similar_songs(&self, id: &str, count: u32) -> Result<Vec<Song>, ApiError> {
         let url = self.build_url(
             "getSimilarSongs",
             &[("id", id), ("count", &count.to_string())],
         );
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
 
         Ok(self.normalize_song_list(json.subsonic_response.similar_songs))
     }
 
     #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
-    pub async fn get_similar_songs2(&self, id: &str, count: u32) -> Result<Vec<Song>, String> {
+    pub async fn get_similar_songs2(&self, id: &str, count: u32) -> Result<Vec<Song>, ApiError> {
         let url = self.build_url(
             "getSimilarSongs2",
             &[("id", id), ("count", &count.to_string())],
         );
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
 
         Ok(self.normalize_song_list(json.subsonic_response.similar_songs2))
     }
 
-    pub async fn get_top_songs(&self, artist: &str, count: u32) -> Result<Vec<Song>, String> {
+    pub async fn get_top_songs(&self, artist: &str, count: u32) -> Result<Vec<Song>, ApiError> {
         let url = self.build_url(
             "getTopSongs",
             &[("artist", artist), ("count", &count.to_string())],
         );
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
 
         Ok(self.normalize_song_list(json.subsonic_response.top_songs))
     }
 
-    pub async fn get_scan_status(&self) -> Result<ScanStatus, String> {
+    pub async fn get_scan_status(&self) -> Result<ScanStatus, ApiError> {
         let url = self.build_url("getScanStatus", &[]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
         Self::extract_scan_status(json)
     }
 
-    pub async fn start_scan(&self) -> Result<ScanStatus, String> {
+    pub async fn start_scan(&self) -> Result<ScanStatus, ApiError> {
         let url = self.build_url("startScan", &[]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
         Self::extract_scan_status(json)
     }
 
-    fn extract_scan_status(json: SubsonicResponse) -> Result<ScanStatus, String> {
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
-
-        if let Some(payload) = json.subsonic_response.scan_status {
-            Ok(payload.into_status())
-        } else {
-            Err("No scan status returned".to_string())
-        }
+    fn extract_scan_status(json: SubsonicResponse) -> Result<ScanStatus, ApiError> {
+        json.subsonic_response
+            .scan_status
+            .map(ScanStatusPayload::into_status)
+            .ok_or_else(|| ApiError::Parse("No scan status returned".to_string()))
     }
 
-    pub async fn get_starred(&self) -> Result<(Vec<Artist>, Vec<Album>, Vec<Song>), String> {
+    pub async fn get_starred(&self) -> Result<(Vec<Artist>, Vec<Album>, Vec<Song>), ApiError> {
         let cache_key = format!("api:getStarred2:v1:{}", self.server.id);
         if let Some(cached) = cache_get_json::<(Vec<Artist>, Vec<Album>, Vec<Song>)>(&cache_key) {
             return Ok(cached);
         }
+        self.fetch_starred(cache_key).await
+    }
 
-        let url = self.build_url("getStarred2", &[]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
+    /// Like `get_starred`, but asks the server even when a cached list exists. The cached
+    /// list is only used when the server can't be reached, including in offline mode.
+    pub async fn get_starred_fresh(
+        &self,
+    ) -> Result<(Vec<Artist>, Vec<Album>, Vec<Song>), ApiError> {
+        let cache_key = format!("api:getStarred2:v1:{}", self.server.id);
+        match self.fetch_starred(cache_key.clone()).await {
+            Ok(starred) => Ok(starred),
+            Err(error) if error.is_unreachable() => {
+                cache_get_json::<(Vec<Artist>, Vec<Album>, Vec<Song>)>(&cache_key).ok_or(error)
+            }
+            Err(error) => Err(error),
         }
+    }
+
+    async fn fetch_starred(
+        &self,
+        cache_key: String,
+    ) -> Result<(Vec<Artist>, Vec<Album>, Vec<Song>), ApiError> {
+        let url = self.build_url("getStarred2", &[]);
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
 
         let starred = json.subsonic_response.starred2.unwrap_or_default();
 
@@ -1,8 +1,16 @@
+use crate::api::error::ApiError;
+use crate::api::icy_titles::split_icy_stream_title;
 use crate::api::models::*;
+use crate::api::server_flavor::ServerIdentity;
+use crate::api::user_permissions::UserPermissions;
 use crate::cache_service::{
     get_json as cache_get_json, is_offline_mode, put_json as cache_put_json,
     remove_by_prefix as cache_remove_prefix,
 };
+use crate::db::{
+    queue_pending_mutation, PendingMutation, PendingMutationOp, PendingMutationOutcome,
+};
+use crate::network_policy::GuardedClient;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::offline_art::{cached_cover_art_data_url, maybe_prefetch_cover_art};
 use chrono::{DateTime, NaiveDateTime, Utc};
@@ -10,18 +18,35 @@ use chrono::{DateTime, NaiveDateTime, Utc};
 use dioxus::document;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::Duration;
 
-static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
+static HTTP_CLIENT: Lazy<GuardedClient> = Lazy::new(GuardedClient::new);
 static AUTH_CACHE: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
 static NATIVE_AUTH_CACHE: Lazy<Mutex<HashMap<String, NativeAuthSession>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
+static FORM_POST_SUPPORT: Lazy<Mutex<HashMap<String, bool>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static SERVER_CAPABILITIES: Lazy<Mutex<HashMap<String, ServerCapabilities>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static MUSIC_FOLDERS: Lazy<Mutex<HashMap<String, Vec<MusicFolder>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+/// Server ids whose saved credentials were rejected; cleared once the user re-authenticates.
+static REAUTH_REQUIRED: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+/// Stream header probes by track key, so each track is probed at most once per session.
+#[cfg(not(target_arch = "wasm32"))]
+static STREAM_PROBES: Lazy<Mutex<HashMap<String, StreamProbe>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+/// Last stream negotiation observed per server id, for the server list in Settings.
+#[cfg(not(target_arch = "wasm32"))]
+static STREAM_NEGOTIATIONS: Lazy<Mutex<HashMap<String, StreamNegotiation>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
 
-const CLIENT_NAME: &str = "RustySound";
 const API_VERSION: &str = "1.16.1";
+/// Max ids per updatePlaylist request; larger GETs overflow server/proxy URL limits.
+const PLAYLIST_UPDATE_BATCH: usize = 100;
 
 pub struct NavidromeClient {
     pub server: ServerConfig,
@@ -33,6 +58,29 @@ struct NativeAuthSession {
     client_unique_id: String,
 }
 
+impl NativeAuthSession {
+    /// The session a native login answered with; without a token there is none.
+    fn from_login(login: NativeLoginResponse) -> Result<Self, ApiError> {
+        let token = login
+            .token
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| {
+                ApiError::Parse("Native API login did not return a token.".to_string())
+            })?;
+        let client_unique_id = login
+            .id
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+        Ok(Self {
+            token,
+            client_unique_id,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum NativeSongSortField {
     PlayDate,
@@ -127,6 +175,16 @@ fn json_pick_u32(value: &serde_json::Value, keys: &[&str]) -> Option<u32> {
     }
 }
 
+fn json_pick_f64(value: &serde_json::Value, keys: &[&str]) -> Option<f64> {
+    let picked = json_pick_value(value, keys)?;
+    let number = match picked {
+        serde_json::Value::Number(number) => number.as_f64(),
+        serde_json::Value::String(text) => text.trim().parse::<f64>().ok(),
+        _ => None,
+    }?;
+    number.is_finite().then_some(number)
+}
+
 fn json_pick_bool(value: &serde_json::Value, keys: &[&str]) -> Option<bool> {
     let picked = json_pick_value(value, keys)?;
     match picked {
@@ -156,12 +214,93 @@ pub struct IcyNowPlaying {
     pub raw_title: String,
 }
 
+/// What the server answered to a one-byte request for a stream: the status and the headers
+/// that show whether it transcodes, caches or streams live.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamProbe {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+}
+
+/// What a server sent back for a plain stream request compared with the file it holds,
+/// showing whether its transcoding rules for this client applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamNegotiation {
+    pub client_name: String,
+    pub raw_requested: bool,
+    /// Navidrome servers pick a transcode from per-player rules keyed on the client name.
+    pub player_rules: bool,
+    pub sample_title: String,
+    /// Format and bitrate of the sampled file on the server.
+    pub source: String,
+    pub delivered_type: Option<String>,
+    pub transcoded: bool,
+}
+
+impl StreamNegotiation {
+    pub fn summary(&self) -> String {
+        let delivered = self.delivered_type.as_deref().unwrap_or("an unknown type");
+        if self.transcoded {
+            format!("Transcoded: {} arrives as {delivered}", self.source)
+        } else {
+            format!("Original file: {} arrives as {delivered}", self.source)
+        }
+    }
+}
+
+/// Response headers worth showing in stream diagnostics; `icy-*` headers are kept as well.
+#[cfg(not(target_arch = "wasm32"))]
+const STREAM_PROBE_HEADERS: [&str; 7] = [
+    "content-type",
+    "content-length",
+    "content-range",
+    "transfer-encoding",
+    "accept-ranges",
+    "x-content-duration",
+    "cache-control",
+];
+
+pub fn server_needs_reauth(server_id: &str) -> bool {
+    REAUTH_REQUIRED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains(server_id)
+}
+
+pub fn servers_needing_reauth() -> Vec<String> {
+    let mut ids: Vec<String> = REAUTH_REQUIRED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .cloned()
+        .collect();
+    ids.sort();
+    ids
+}
+
+pub fn clear_server_reauth(server_id: &str) {
+    REAUTH_REQUIRED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(server_id);
+}
+
 include!("auth_native_and_stream.rs");
 include!("library_browsing.rs");
+include!("server_capabilities.rs");
 include!("bookmarks_favorites_and_playlists.rs");
 include!("playlist_mutations.rs");
+include!("pending_sync_replay.rs");
 include!("radio_search_and_scrobble.rs");
 
+/// Cache key suffix for folder-scoped requests. Empty for "All folders" so the existing
+/// cache keys stay valid.
+fn music_folder_cache_suffix(music_folder_id: Option<&str>) -> String {
+    music_folder_id
+        .map(|folder_id| format!(":folder={folder_id}"))
+        .unwrap_or_default()
+}
+
 fn normalize_cover_art_id(cover_art_id: &str) -> String {
     let trimmed = cover_art_id.trim();
     if trimmed.is_empty() {
@@ -289,15 +428,16 @@ fn bookmark_sort_timestamp(bookmark: &Bookmark) -> i64 {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-async fn read_icy_now_playing_from_url(stream_url: &str) -> Result<Option<IcyNowPlaying>, String> {
+async fn read_icy_now_playing_from_url(
+    stream_url: &str,
+) -> Result<Option<IcyNowPlaying>, ApiError> {
     let mut response = HTTP_CLIENT
         .get(stream_url)
         .header("Icy-MetaData", "1")
-        .header("User-Agent", CLIENT_NAME)
+        .header("User-Agent", DEFAULT_CLIENT_NAME)
         .timeout(Duration::from_secs(8))
         .send()
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
     let metaint = response
         .headers()
@@ -320,7 +460,7 @@ async fn read_icy_now_playing_from_url(stream_url: &str) -> Result<Option<IcyNow
     const MAX_BYTES: usize = 1024 * 1024;
 
     while blocks_checked < MAX_BLOCKS && total_bytes < MAX_BYTES {
-        let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? else {
+        let Some(chunk) = response.chunk().await? else {
             break;
         };
         total_bytes += chunk.len();
@@ -385,21 +525,10 @@ fn parse_icy_metadata_block(block: &[u8]) -> Option<IcyNowPlaying> {
         return None;
     }
 
-    let (artist, title) = if let Some((artist, title)) = stream_title.split_once(" - ") {
-        let artist = artist.trim().to_string();
-        let title = title.trim().to_string();
-        if !artist.is_empty() && !title.is_empty() {
-            (Some(artist), title)
-        } else {
-            (None, stream_title.to_string())
-        }
-    } else {
-        (None, stream_title.to_string())
-    };
-
+    let parts = split_icy_stream_title(stream_title);
     Some(IcyNowPlaying {
-        title,
-        artist,
+        title: parts.title.unwrap_or_else(|| stream_title.to_string()),
+        artist: parts.artist,
         raw_title: stream_title.to_string(),
     })
 }
@@ -440,3 +569,6 @@ fn urlencoding_simple(s: &str) -> String {
 }
 
 include!("response_models.rs");
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,174 @@
+// Offline mutation queue: queueing unreachable writes and replaying them on reconnect.
+impl NavidromeClient {
+    /// Queues `op` when `result` failed because the server couldn't be reached, so the
+    /// caller can treat the change as made. Other errors are returned unchanged.
+    async fn queue_if_unreachable(
+        &self,
+        result: Result<(), ApiError>,
+        op: PendingMutationOp,
+    ) -> Result<(), ApiError> {
+        match result {
+            Err(error) if error.is_unreachable() => {
+                queue_pending_mutation(self.server.id.clone(), op)
+                    .await
+                    .map_err(|_| error)?;
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// The song or album rating the cache last saw, used as the baseline of a queued rating.
+    fn cached_user_rating(&self, id: &str) -> Option<u32> {
+        let song_key = format!("api:getSong:v1:{}:{}", self.server.id, id.trim());
+        if let Some(song) = cache_get_json::<Song>(&song_key) {
+            return Some(song.user_rating.unwrap_or(0));
+        }
+        let album_key = format!("api:getAlbum:v1:{}:{}", self.server.id, id);
+        cache_get_json::<(Album, Vec<Song>)>(&album_key)
+            .map(|(album, _)| album.user_rating.unwrap_or(0))
+    }
+
+    fn invalidate_item_cache(&self, id: &str) {
+        for endpoint in ["getSong", "getAlbum", "getArtist"] {
+            let _ = cache_remove_prefix(&format!(
+                "api:{endpoint}:v1:{}:{}",
+                self.server.id,
+                id.trim()
+            ));
+        }
+    }
+
+    /// Current star timestamp and display name of an item, read past the cache.
+    async fn fresh_starred(
+        &self,
+        item_type: &str,
+        item_id: &str,
+    ) -> Result<(Option<String>, String), ApiError> {
+        match item_type {
+            "album" => {
+                self.invalidate_item_cache(item_id);
+                let (album, _) = self.get_album(item_id).await?;
+                Ok((album.starred, album.name))
+            }
+            "artist" => {
+                self.invalidate_item_cache(item_id);
+                let (artist, _) = self.get_artist(item_id).await?;
+                Ok((artist.starred, artist.name))
+            }
+            "playlist" => {
+                let (playlist, _) = self.get_playlist_fresh(item_id).await?;
+                Ok((playlist.starred, playlist.name))
+            }
+            _ => {
+                let song = self.get_song_fresh(item_id).await?;
+                Ok((song.starred, song.title))
+            }
+        }
+    }
+
+    /// Current rating and display name of a song, or of an album when no song has that id.
+    async fn fresh_user_rating(&self, item_id: &str) -> Result<(u32, String), ApiError> {
+        match self.get_song_fresh(item_id).await {
+            Ok(song) => Ok((song.user_rating.unwrap_or(0), song.title)),
+            Err(error) if error.is_not_found() => {
+                self.invalidate_item_cache(item_id);
+                let (album, _) = self.get_album(item_id).await?;
+                Ok((album.user_rating.unwrap_or(0), album.name))
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Sends one queued mutation. The server's state wins when it changed after the mutation
+    /// was queued: a star newer than a queued unstar, a rating that moved away from the one
+    /// the app last saw, or a playlist that was deleted.
+    pub async fn replay_pending_mutation(
+        &self,
+        mutation: &PendingMutation,
+    ) -> PendingMutationOutcome {
+        match self.try_replay_pending_mutation(mutation).await {
+            Ok(outcome) => outcome,
+            Err(error) if error.is_unreachable() => PendingMutationOutcome::Unreachable,
+            Err(error) => PendingMutationOutcome::Failed(error.to_string()),
+        }
+    }
+
+    async fn try_replay_pending_mutation(
+        &self,
+        mutation: &PendingMutation,
+    ) -> Result<PendingMutationOutcome, ApiError> {
+        match &mutation.op {
+            PendingMutationOp::Star { item_id, item_type } => {
+                let (starred, _) = self.fresh_starred(item_type, item_id).await?;
+                if starred.is_none() {
+                    self.star_now(item_id, item_type).await?;
+                }
+                Ok(PendingMutationOutcome::Applied)
+            }
+            PendingMutationOp::Unstar { item_id, item_type } => {
+                let (starred, name) = self.fresh_starred(item_type, item_id).await?;
+                let Some(starred) = starred else {
+                    return Ok(PendingMutationOutcome::Applied);
+                };
+                let starred_after_unstar = parse_bookmark_timestamp(&starred)
+                    .is_some_and(|secs| secs.saturating_mul(1000) > mutation.queued_at_ms as i64);
+                if starred_after_unstar {
+                    return Ok(PendingMutationOutcome::Conflict(format!(
+                        "\"{name}\" was starred on {} after you unstarred it offline; \
+                         kept the star.",
+                        self.server.name
+                    )));
+                }
+                self.unstar_now(item_id, item_type).await?;
+                Ok(PendingMutationOutcome::Applied)
+            }
+            PendingMutationOp::SetRating {
+                item_id,
+                rating,
+                previous,
+            } => {
+                let (current, name) = self.fresh_user_rating(item_id).await?;
+                if current == *rating {
+                    return Ok(PendingMutationOutcome::Applied);
+                }
+                if previous.is_some_and(|previous| previous != current) {
+                    return Ok(PendingMutationOutcome::Conflict(format!(
+                        "\"{name}\" was rated {current}/5 on {} while you were offline; \
+                         kept that rating.",
+                        self.server.name
+                    )));
+                }
+                self.set_rating_now(item_id, *rating).await?;
+                Ok(PendingMutationOutcome::Applied)
+            }
+            PendingMutationOp::AppendToPlaylist {
+                playlist_id,
+                song_ids,
+            } => {
+                let entries = match self.get_playlist_fresh(playlist_id).await {
+                    Ok((_, entries)) => entries,
+                    Err(error) if error.is_not_found() => {
+                        return Ok(PendingMutationOutcome::Conflict(format!(
+                            "A playlist on {} was deleted before {} queued songs could be added.",
+                            self.server.name,
+                            song_ids.len()
+                        )));
+                    }
+                    Err(error) => return Err(error),
+                };
+                // Songs that made it in some other way are not added twice.
+                let present: HashSet<&str> = entries.iter().map(|song| song.id.as_str()).collect();
+                let missing: Vec<String> = song_ids
+                    .iter()
+                    .filter(|song_id| !present.contains(song_id.as_str()))
+                    .cloned()
+                    .collect();
+                // A partial add stays queued; the next replay skips what made it in.
+                self.add_songs_to_playlist_now(playlist_id, &missing, &mut 0)
+                    .await?;
+                Ok(PendingMutationOutcome::Applied)
+            }
+        }
+    }
+}
@@ -1,9 +1,9 @@
 // Playlist write/update operations.
 impl NavidromeClient {
-    pub async fn rename_playlist(&self, playlist_id: &str, name: &str) -> Result<(), String> {
+    pub async fn rename_playlist(&self, playlist_id: &str, name: &str) -> Result<(), ApiError> {
         let trimmed = name.trim();
         if trimmed.is_empty() {
-            return Err("Playlist name cannot be empty.".to_string());
+            return Err(ApiError::generic("Playlist name cannot be empty."));
         }
 
         let url = self.build_url_owned(
@@ -13,20 +13,8 @@ impl NavidromeClient {
                 ("name".to_string(), trimmed.to_string()),
             ],
         );
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        self.read_subsonic_response(response).await?;
 
         let _ = cache_remove_prefix(&format!(
             "api:getPlaylist:v1:{}:{}",
@@ -41,7 +29,7 @@ impl NavidromeClient {
         name: &str,
         comment: Option<&str>,
         song_ids: &[String],
-    ) -> Result<Option<String>, String> {
+    ) -> Result<Option<String>, ApiError> {
         let mut params = vec![("name".to_string(), name.to_string())];
         if let Some(comment) = comment {
             let trimmed = comment.trim();
@@ -49,66 +37,117 @@ impl NavidromeClient {
                 params.push(("comment".to_string(), trimmed.to_string()));
             }
         }
-        for song_id in song_ids {
+        // A form post carries every song; a GET URL only fits a batch.
+        let first_batch = if self.supports_form_post().await {
+            song_ids.len()
+        } else {
+            PLAYLIST_UPDATE_BATCH
+        };
+        let (initial_ids, remaining_ids) = song_ids.split_at(song_ids.len().min(first_batch));
+        for song_id in initial_ids {
             params.push(("songId".to_string(), song_id.clone()));
         }
 
-        let url = self.build_url_owned("createPlaylist", params);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let json = self.send_playlist_request("createPlaylist", params).await?;
 
         let playlist_id = json.subsonic_response.playlist.map(|p| p.id.clone());
         self.invalidate_playlist_cache();
+        if !remaining_ids.is_empty() {
+            let Some(created_id) = playlist_id.as_deref() else {
+                return Err(ApiError::generic(format!(
+                    "Playlist created with {} of {} songs; the server did not return its id.",
+                    initial_ids.len(),
+                    song_ids.len()
+                )));
+            };
+            self.add_songs_to_playlist(created_id, remaining_ids)
+                .await
+                .map_err(|err| {
+                    ApiError::generic(format!(
+                        "Playlist created, but only some songs were added. {err}"
+                    ))
+                })?;
+        }
         Ok(playlist_id)
     }
 
+    /// Appends songs to a playlist. If the server stops being reachable partway, the songs
+    /// not yet added are queued for replay and the append is reported as done.
     pub async fn add_songs_to_playlist(
         &self,
         playlist_id: &str,
         song_ids: &[String],
-    ) -> Result<(), String> {
+    ) -> Result<(), ApiError> {
+        let mut added = 0usize;
+        let result = self
+            .add_songs_to_playlist_now(playlist_id, song_ids, &mut added)
+            .await;
+        self.queue_if_unreachable(
+            result,
+            PendingMutationOp::AppendToPlaylist {
+                playlist_id: playlist_id.to_string(),
+                song_ids: song_ids[added.min(song_ids.len())..].to_vec(),
+            },
+        )
+        .await
+    }
+
+    /// Adds `song_ids` in batches, counting the songs the server accepted in `added`. An
+    /// unreachable server is returned as is so the rest can be queued.
+    async fn add_songs_to_playlist_now(
+        &self,
+        playlist_id: &str,
+        song_ids: &[String],
+        added: &mut usize,
+    ) -> Result<(), ApiError> {
         if song_ids.is_empty() {
             return Ok(());
         }
 
-        let mut params = vec![("playlistId".to_string(), playlist_id.to_string())];
-        for song_id in song_ids {
-            params.push(("songIdToAdd".to_string(), song_id.clone()));
-        }
+        // Bulk adds are verified against the server's songCount afterwards.
+        let expected_count = if song_ids.len() > 1 {
+            self.get_playlist_fresh(playlist_id)
+                .await
+                .ok()
+                .map(|(playlist, _)| playlist.song_count as usize + song_ids.len())
+        } else {
+            None
+        };
 
-        let url = self.build_url_owned("updatePlaylist", params);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
+        for batch in song_ids.chunks(PLAYLIST_UPDATE_BATCH) {
+            let mut params = vec![("playlistId".to_string(), playlist_id.to_string())];
+            for song_id in batch {
+                params.push(("songIdToAdd".to_string(), song_id.clone()));
+            }
+            if let Err(err) = self.send_update_playlist(params).await {
+                self.invalidate_single_playlist_cache(playlist_id);
+                if err.is_unreachable() {
+                    return Err(err);
+                }
+                return Err(partial_playlist_update_error(
+                    "Added",
+                    *added,
+                    song_ids.len(),
+                    err,
+                ));
+            }
+            *added += batch.len();
         }
 
-        let _ = cache_remove_prefix(&format!(
-            "api:getPlaylist:v1:{}:{}",
-            self.server.id, playlist_id
-        ));
-        self.invalidate_playlist_cache();
+        self.invalidate_single_playlist_cache(playlist_id);
+        if let Some(expected) = expected_count {
+            if let Ok((playlist, _)) = self.get_playlist_fresh(playlist_id).await {
+                let actual = playlist.song_count as usize;
+                if actual < expected {
+                    let missing = expected - actual;
+                    return Err(ApiError::generic(format!(
+                        "Added {} of {} songs; the server dropped {missing}.",
+                        song_ids.len().saturating_sub(missing),
+                        song_ids.len()
+                    )));
+                }
+            }
+        }
         Ok(())
     }
 
@@ -117,7 +156,7 @@ impl NavidromeClient {
         &self,
         playlist_id: &str,
         album_id: &str,
-    ) -> Result<(), String> {
+    ) -> Result<(), ApiError> {
         let (_, songs) = self.get_album(album_id).await?;
         let song_ids: Vec<String> = songs.iter().map(|s| s.id.clone()).collect();
         self.add_songs_to_playlist(playlist_id, &song_ids).await
@@ -128,29 +167,16 @@ impl NavidromeClient {
         &self,
         source_playlist_id: &str,
         target_playlist_id: &str,
-    ) -> Result<(), String> {
+    ) -> Result<(), ApiError> {
         let (_, songs) = self.get_playlist(source_playlist_id).await?;
         let song_ids: Vec<String> = songs.iter().map(|s| s.id.clone()).collect();
-        self.add_songs_to_playlist(target_playlist_id, &song_ids)
-            .await
+        self.add_songs_to_playlist(target_playlist_id, &song_ids).await
     }
 
-    pub async fn delete_playlist(&self, playlist_id: &str) -> Result<(), String> {
+    pub async fn delete_playlist(&self, playlist_id: &str) -> Result<(), ApiError> {
         let url = self.build_url("deletePlaylist", &[("id", playlist_id)]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or_else(|| "Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        self.read_subsonic_response(response).await?;
 
         let _ = cache_remove_prefix(&format!(
             "api:getPlaylist:v1:{}:{}",
@@ -164,42 +190,36 @@ impl NavidromeClient {
         &self,
         playlist_id: &str,
         song_indices: &[usize],
-    ) -> Result<(), String> {
+    ) -> Result<(), ApiError> {
         if song_indices.is_empty() {
             return Ok(());
         }
 
-        let mut params = vec![("playlistId".to_string(), playlist_id.to_string())];
         // Sort indices in descending order to remove from end to beginning
-        // This prevents index shifting issues
+        // This prevents index shifting issues, including across batches.
         let mut sorted_indices = song_indices.to_vec();
         sorted_indices.sort_by(|a, b| b.cmp(a));
+        sorted_indices.dedup();
 
-        for &index in &sorted_indices {
-            params.push(("songIndexToRemove".to_string(), index.to_string()));
-        }
-
-        let url = self.build_url_owned("updatePlaylist", params);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
+        let mut removed = 0usize;
+        for batch in sorted_indices.chunks(PLAYLIST_UPDATE_BATCH) {
+            let mut params = vec![("playlistId".to_string(), playlist_id.to_string())];
+            for index in batch {
+                params.push(("songIndexToRemove".to_string(), index.to_string()));
+            }
+            if let Err(err) = self.send_update_playlist(params).await {
+                self.invalidate_single_playlist_cache(playlist_id);
+                return Err(partial_playlist_update_error(
+                    "Removed",
+                    removed,
+                    sorted_indices.len(),
+                    err,
+                ));
+            }
+            removed += batch.len();
         }
 
-        let _ = cache_remove_prefix(&format!(
-            "api:getPlaylist:v1:{}:{}",
-            self.server.id, playlist_id
-        ));
-        self.invalidate_playlist_cache();
+        self.invalidate_single_playlist_cache(playlist_id);
         Ok(())
     }
 
@@ -208,42 +228,51 @@ impl NavidromeClient {
         playlist_id: &str,
         ordered_song_ids: &[String],
         existing_song_count: usize,
-    ) -> Result<(), String> {
+    ) -> Result<(), ApiError> {
         if ordered_song_ids.is_empty() && existing_song_count == 0 {
             return Ok(());
         }
 
-        let mut params = vec![("playlistId".to_string(), playlist_id.to_string())];
-
-        for index in (0..existing_song_count).rev() {
-            params.push(("songIndexToRemove".to_string(), index.to_string()));
-        }
-
-        for song_id in ordered_song_ids {
-            params.push(("songIdToAdd".to_string(), song_id.clone()));
-        }
-
-        let url = self.build_url_owned("updatePlaylist", params);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
+        let batch_limit = (!self.supports_form_post().await).then_some(PLAYLIST_UPDATE_BATCH);
+        let plan = plan_playlist_replace(
+            playlist_id,
+            ordered_song_ids,
+            existing_song_count,
+            batch_limit,
+        );
+        let (mut appended, mut removed) = (0usize, 0usize);
+        for (index, (params, ids)) in plan.batches.into_iter().enumerate() {
+            if let Err(err) = self.send_update_playlist(params).await {
+                let err = if index < plan.append_batches {
+                    // Appended songs sit after the old ones; taking them off again leaves
+                    // the playlist as it was.
+                    let appended_indices: Vec<usize> =
+                        (existing_song_count..existing_song_count + appended).collect();
+                    match self
+                        .remove_songs_from_playlist(playlist_id, &appended_indices)
+                        .await
+                    {
+                        Ok(()) => err,
+                        Err(_) => partial_playlist_update_error(
+                            "Added",
+                            appended,
+                            ordered_song_ids.len(),
+                            err,
+                        ),
+                    }
+                } else {
+                    partial_playlist_update_error("Removed", removed, existing_song_count, err)
+                };
+                self.invalidate_single_playlist_cache(playlist_id);
+                return Err(err);
+            }
+            if index < plan.append_batches {
+                appended += ids;
+            } else {
+                removed += ids;
+            }
         }
-
-        let _ = cache_remove_prefix(&format!(
-            "api:getPlaylist:v1:{}:{}",
-            self.server.id, playlist_id
-        ));
-        self.invalidate_playlist_cache();
+        self.invalidate_single_playlist_cache(playlist_id);
         Ok(())
     }
 
@@ -253,7 +282,7 @@ impl NavidromeClient {
         seed_song_id: &str,
         name: Option<&str>,
         count: u32,
-    ) -> Result<Option<String>, String> {
+    ) -> Result<Option<String>, ApiError> {
         let songs = self.get_similar_songs2(seed_song_id, count).await?;
         let mut song_ids: Vec<String> = songs.iter().map(|s| s.id.clone()).collect();
         if song_ids.is_empty() {
@@ -270,4 +299,130 @@ impl NavidromeClient {
         )
         .await
     }
+
+    fn invalidate_single_playlist_cache(&self, playlist_id: &str) {
+        let _ = cache_remove_prefix(&format!(
+            "api:getPlaylist:v1:{}:{}",
+            self.server.id, playlist_id
+        ));
+        self.invalidate_playlist_cache();
+    }
+
+    async fn send_update_playlist(&self, params: Vec<(String, String)>) -> Result<(), ApiError> {
+        self.send_playlist_request("updatePlaylist", params).await?;
+        Ok(())
+    }
+
+    /// Sends one playlist call, as a form POST when the server advertises the OpenSubsonic
+    /// `formPost` extension and as a plain GET otherwise.
+    async fn send_playlist_request(
+        &self,
+        endpoint: &str,
+        params: Vec<(String, String)>,
+    ) -> Result<SubsonicResponse, ApiError> {
+        let response = if self.supports_form_post().await {
+            let url = self.build_url_owned(endpoint, Vec::new());
+            HTTP_CLIENT.post(&url).form(&params).send().await
+        } else {
+            let url = self.build_url_owned(endpoint, params);
+            HTTP_CLIENT.get(&url).send().await
+        }?;
+        self.read_subsonic_response(response).await
+    }
+
+    async fn supports_form_post(&self) -> bool {
+        {
+            let cache = FORM_POST_SUPPORT.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(supported) = cache.get(&self.server.id) {
+                return *supported;
+            }
+        }
+
+        let supported = match self.open_subsonic_extensions().await {
+            Ok(extensions) => extensions.iter().any(|name| name == "formPost"),
+            // Don't cache transport failures; retry the probe on the next mutation.
+            Err(_) => return false,
+        };
+
+        let mut cache = FORM_POST_SUPPORT.lock().unwrap_or_else(|e| e.into_inner());
+        cache.insert(self.server.id.clone(), supported);
+        supported
+    }
+}
+
+/// The updatePlaylist calls that replace a playlist's songs, each with how many songs it
+/// carries, and how many of the leading calls append.
+struct PlaylistReplacePlan {
+    batches: Vec<(Vec<(String, String)>, usize)>,
+    append_batches: usize,
+}
+
+/// Plans replacing `existing_song_count` songs with `ordered_song_ids`. When everything fits
+/// one call (always, without a `batch_limit`) the server swaps them atomically. Otherwise the
+/// new songs are appended first and the old ones removed afterwards, so a batch failing part
+/// way never leaves the playlist emptied.
+fn plan_playlist_replace(
+    playlist_id: &str,
+    ordered_song_ids: &[String],
+    existing_song_count: usize,
+    batch_limit: Option<usize>,
+) -> PlaylistReplacePlan {
+    let playlist_param = || vec![("playlistId".to_string(), playlist_id.to_string())];
+    let removals = |indices: &[usize]| {
+        indices
+            .iter()
+            .map(|index| ("songIndexToRemove".to_string(), index.to_string()))
+            .collect::<Vec<_>>()
+    };
+    let additions = |song_ids: &[String]| {
+        song_ids
+            .iter()
+            .map(|song_id| ("songIdToAdd".to_string(), song_id.clone()))
+            .collect::<Vec<_>>()
+    };
+    // Removing from the end first keeps the remaining indices valid.
+    let old_indices: Vec<usize> = (0..existing_song_count).rev().collect();
+
+    let total = existing_song_count + ordered_song_ids.len();
+    let Some(limit) = batch_limit.filter(|limit| total > *limit) else {
+        let mut params = playlist_param();
+        params.extend(removals(&old_indices));
+        params.extend(additions(ordered_song_ids));
+        return PlaylistReplacePlan {
+            batches: vec![(params, total)],
+            append_batches: 0,
+        };
+    };
+
+    let mut batches = Vec::new();
+    for chunk in ordered_song_ids.chunks(limit) {
+        let mut params = playlist_param();
+        params.extend(additions(chunk));
+        batches.push((params, chunk.len()));
+    }
+    let append_batches = batches.len();
+    for chunk in old_indices.chunks(limit) {
+        let mut params = playlist_param();
+        params.extend(removals(chunk));
+        batches.push((params, chunk.len()));
+    }
+    PlaylistReplacePlan {
+        batches,
+        append_batches,
+    }
+}
+
+fn partial_playlist_update_error(
+    verb: &str,
+    done: usize,
+    total: usize,
+    err: ApiError,
+) -> ApiError {
+    if done == 0 {
+        err
+    } else {
+        ApiError::generic(format!(
+            "{verb} {done} of {total} songs before the server rejected a batch: {err}"
+        ))
+    }
 }
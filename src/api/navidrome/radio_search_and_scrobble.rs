@@ -1,21 +1,9 @@
-// Internet radio management, search, and scrobble reporting.
+// Internet radio management, search, scrobble reporting and now-playing listeners.
 impl NavidromeClient {
-    pub async fn get_internet_radio_stations(&self) -> Result<Vec<RadioStation>, String> {
+    pub async fn get_internet_radio_stations(&self) -> Result<Vec<RadioStation>, ApiError> {
         let url = self.build_url("getInternetRadioStations", &[]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
 
         let mut stations = json
             .subsonic_response
@@ -35,26 +23,14 @@ impl NavidromeClient {
         name: &str,
         stream_url: &str,
         home_page_url: Option<&str>,
-    ) -> Result<(), String> {
+    ) -> Result<(), ApiError> {
         let mut params = vec![("name", name), ("streamUrl", stream_url)];
         if let Some(url) = home_page_url.filter(|value| !value.trim().is_empty()) {
             params.push(("homePageUrl", url));
         }
         let url = self.build_url("createInternetRadioStation", &params);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        self.read_subsonic_response(response).await?;
 
         Ok(())
     }
@@ -65,7 +41,7 @@ impl NavidromeClient {
         name: &str,
         stream_url: &str,
         home_page_url: Option<&str>,
-    ) -> Result<(), String> {
+    ) -> Result<(), ApiError> {
         let mut params = vec![
             ("id", station_id),
             ("name", name),
@@ -75,40 +51,16 @@ impl NavidromeClient {
             params.push(("homePageUrl", url));
         }
         let url = self.build_url("updateInternetRadioStation", &params);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        self.read_subsonic_response(response).await?;
 
         Ok(())
     }
 
-    pub async fn delete_internet_radio_station(&self, station_id: &str) -> Result<(), String> {
+    pub async fn delete_internet_radio_station(&self, station_id: &str) -> Result<(), ApiError> {
         let url = self.build_url("deleteInternetRadioStation", &[("id", station_id)]);
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        self.read_subsonic_response(response).await?;
 
         Ok(())
     }
@@ -119,42 +71,36 @@ impl NavidromeClient {
         artist_count: u32,
         album_count: u32,
         song_count: u32,
-    ) -> Result<SearchResult, String> {
+        music_folder_id: Option<&str>,
+    ) -> Result<SearchResult, ApiError> {
         let cache_key = format!(
-            "api:search3:v1:{}:{}:{}:{}:{}",
+            "api:search3:v1:{}:{}:{}:{}:{}{}",
             self.server.id,
             query.trim().to_lowercase(),
             artist_count,
             album_count,
-            song_count
+            song_count,
+            music_folder_cache_suffix(music_folder_id)
         );
         if let Some(cached) = cache_get_json::<SearchResult>(&cache_key) {
             return Ok(cached);
         }
 
-        let url = self.build_url(
-            "search3",
-            &[
-                ("query", query),
-                ("artistCount", &artist_count.to_string()),
-                ("albumCount", &album_count.to_string()),
-                ("songCount", &song_count.to_string()),
-            ],
-        );
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
+        let artist_count = artist_count.to_string();
+        let album_count = album_count.to_string();
+        let song_count = song_count.to_string();
+        let mut params = vec![
+            ("query", query),
+            ("artistCount", artist_count.as_str()),
+            ("albumCount", album_count.as_str()),
+            ("songCount", song_count.as_str()),
+        ];
+        if let Some(folder_id) = music_folder_id {
+            params.push(("musicFolderId", folder_id));
         }
+        let url = self.build_url("search3", &params);
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
 
         let search = json.subsonic_response.search_result3.unwrap_or_default();
 
@@ -185,10 +131,72 @@ impl NavidromeClient {
         Ok(payload)
     }
 
+    /// Up to `limit` songs by one artist, paged through search3 offsets with the artist name
+    /// as the query. Search also matches titles and albums, so only the artist's own songs
+    /// are kept: by artist id when both sides have one, otherwise by name.
+    pub async fn search_artist_songs(
+        &self,
+        artist_name: &str,
+        artist_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Song>, ApiError> {
+        const PAGE_SIZE: usize = 100;
+        const MAX_PAGES: usize = 5;
+
+        let wanted_name = artist_name.trim().to_lowercase();
+        let page_size = PAGE_SIZE.to_string();
+        let mut songs = Vec::new();
+        for page in 0..MAX_PAGES {
+            let offset = (page * PAGE_SIZE).to_string();
+            let url = self.build_url(
+                "search3",
+                &[
+                    ("query", artist_name),
+                    ("artistCount", "0"),
+                    ("albumCount", "0"),
+                    ("songCount", page_size.as_str()),
+                    ("songOffset", offset.as_str()),
+                ],
+            );
+            let response = HTTP_CLIENT.get(&url).send().await?;
+            let json = self.read_subsonic_response(response).await?;
+            let batch = json
+                .subsonic_response
+                .search_result3
+                .and_then(|search| search.song)
+                .unwrap_or_default();
+            let batch_len = batch.len();
+
+            for mut song in batch {
+                let by_artist = match (artist_id, song.artist_id.as_deref()) {
+                    (Some(wanted_id), Some(song_artist_id)) => wanted_id == song_artist_id,
+                    _ => song
+                        .artist
+                        .as_deref()
+                        .is_some_and(|artist| artist.trim().to_lowercase() == wanted_name),
+                };
+                if !by_artist {
+                    continue;
+                }
+                song.server_id = self.server.id.clone();
+                song.server_name = self.server.name.clone();
+                normalize_song_cover_art(&mut song);
+                songs.push(song);
+            }
+
+            if batch_len < PAGE_SIZE || songs.len() >= limit {
+                break;
+            }
+        }
+
+        songs.truncate(limit);
+        Ok(songs)
+    }
+
     /// Report playback to Navidrome/Subsonic. If submission is false, it updates "Now Playing";
     /// when true, it scrobbles the play as finished.
     #[allow(dead_code)]
-    pub async fn scrobble(&self, id: &str, submission: bool) -> Result<(), String> {
+    pub async fn scrobble(&self, id: &str, submission: bool) -> Result<(), ApiError> {
         let millis = Utc::now().timestamp_millis().to_string();
         let url = self.build_url(
             "scrobble",
@@ -198,21 +206,32 @@ impl NavidromeClient {
                 ("submission", if submission { "true" } else { "false" }),
             ],
         );
-        let response = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-        let json: SubsonicResponse = response.json().await.map_err(|e| e.to_string())?;
-
-        if json.subsonic_response.status != "ok" {
-            return Err(json
-                .subsonic_response
-                .error
-                .map(|e| e.message)
-                .unwrap_or("Unknown error".to_string()));
-        }
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        self.read_subsonic_response(response).await?;
 
         Ok(())
     }
+
+    /// Who is listening to what right now. Servers may restrict this to admins, in which
+    /// case the Subsonic error is returned unchanged.
+    pub async fn get_now_playing(&self) -> Result<Vec<NowPlayingEntry>, ApiError> {
+        let url = self.build_url("getNowPlaying", &[]);
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let json = self.read_subsonic_response(response).await?;
+
+        let mut entries = json
+            .subsonic_response
+            .now_playing
+            .and_then(|now_playing| now_playing.entry)
+            .unwrap_or_default();
+
+        for entry in &mut entries {
+            entry.song.server_id = self.server.id.clone();
+            entry.song.server_name = self.server.name.clone();
+            normalize_song_cover_art(&mut entry.song);
+        }
+        entries.sort_by_key(|entry| entry.minutes_ago);
+
+        Ok(entries)
+    }
 }
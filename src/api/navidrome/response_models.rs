@@ -10,6 +10,8 @@ pub struct SubsonicResponseInner {
     pub status: String,
     pub error: Option<SubsonicError>,
     pub artists: Option<ArtistsContainer>,
+    #[serde(alias = "musicFolders")]
+    pub music_folders: Option<MusicFoldersContainer>,
     #[serde(alias = "albumList2")]
     pub album_list2: Option<AlbumList2>,
     pub album: Option<AlbumWithSongs>,
@@ -37,11 +39,23 @@ pub struct SubsonicResponseInner {
     #[serde(alias = "scanStatus")]
     pub scan_status: Option<ScanStatusPayload>,
     pub bookmarks: Option<BookmarksContainer>,
+    #[serde(alias = "nowPlaying")]
+    pub now_playing: Option<NowPlayingContainer>,
+    /// Subsonic API version every response envelope carries, e.g. "1.16.1".
+    pub version: Option<String>,
+    /// Server implementation from OpenSubsonic responses, e.g. "navidrome".
+    #[serde(alias = "type")]
+    pub server_type: Option<String>,
+    /// Release of the server software, from OpenSubsonic responses.
+    #[serde(alias = "serverVersion")]
+    pub server_version: Option<String>,
+    #[serde(alias = "openSubsonicExtensions")]
+    pub open_subsonic_extensions: Option<Vec<serde_json::Value>>,
+    pub user: Option<UserPermissions>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct SubsonicError {
-    #[allow(dead_code)]
     pub code: i32,
     pub message: String,
 }
@@ -51,6 +65,13 @@ pub struct ArtistsContainer {
     pub index: Option<Vec<ArtistIndex>>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct MusicFoldersContainer {
+    /// Raw entries: servers disagree on whether folder ids are numbers or strings.
+    #[serde(alias = "musicFolder")]
+    pub music_folder: Option<Vec<serde_json::Value>>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ArtistIndex {
     #[allow(dead_code)]
@@ -119,6 +140,8 @@ pub struct ScanStatusPayload {
     pub seconds_remaining: Option<u64>,
     #[serde(rename = "secondsElapsed")]
     pub seconds_elapsed: Option<u64>,
+    #[serde(rename = "lastScan")]
+    pub last_scan: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -127,6 +150,8 @@ pub struct ScanStatus {
     pub current_task: Option<String>,
     pub seconds_remaining: Option<u64>,
     pub seconds_elapsed: Option<u64>,
+    /// When the last scan finished, as the server's ISO 8601 timestamp.
+    pub last_scan: Option<String>,
 }
 
 impl ScanStatusPayload {
@@ -136,6 +161,7 @@ impl ScanStatusPayload {
             current_task: self.current_task,
             seconds_remaining: self.seconds_remaining,
             seconds_elapsed: self.seconds_elapsed,
+            last_scan: self.last_scan,
         }
     }
 }
@@ -182,3 +208,8 @@ pub struct SearchResult3 {
 pub struct BookmarksContainer {
     pub bookmark: Option<Vec<Bookmark>>,
 }
+
+#[derive(Debug, Deserialize, Default)]
+pub struct NowPlayingContainer {
+    pub entry: Option<Vec<NowPlayingEntry>>,
+}
@@ -0,0 +1,153 @@
+// Capability probe: which optional endpoints a server supports, cached per server.
+impl NavidromeClient {
+    /// Names of the OpenSubsonic extensions the server advertises. Servers without
+    /// OpenSubsonic support report none; only transport failures are errors.
+    async fn open_subsonic_extensions(&self) -> Result<Vec<String>, ApiError> {
+        let url = self.build_url("getOpenSubsonicExtensions", &[]);
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let extensions = match self.read_subsonic_response(response).await {
+            Ok(json) => json.subsonic_response.open_subsonic_extensions,
+            Err(error) if error.is_unreachable() => return Err(error),
+            Err(_) => None,
+        };
+        Ok(extensions
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|extension| json_pick_string(extension, &["name"]))
+            .collect())
+    }
+
+    /// Whether `endpoint` answers with an ok envelope. Failures that say nothing about
+    /// the endpoint itself (no connection, rejected login, rate limit) are returned.
+    async fn endpoint_supported(
+        &self,
+        endpoint: &str,
+        params: &[(&str, &str)],
+    ) -> Result<bool, ApiError> {
+        let url = self.build_url(endpoint, params);
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        match self.read_subsonic_response(response).await {
+            Ok(_) => Ok(true),
+            Err(error)
+                if error.is_unreachable()
+                    || error.is_auth_failed()
+                    || matches!(error, ApiError::RateLimited { .. }) =>
+            {
+                Err(error)
+            }
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// Roles of the signed-in user. Servers without `getUser`, or that refuse it, report
+    /// none; only transport failures are errors.
+    async fn user_permissions(&self) -> Result<Option<UserPermissions>, ApiError> {
+        let url = self.build_url("getUser", &[("username", &self.server.username)]);
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        match self.read_subsonic_response(response).await {
+            Ok(json) => Ok(json.subsonic_response.user),
+            Err(error) if error.is_unreachable() => Err(error),
+            Err(_) => Ok(None),
+        }
+    }
+
+    fn capabilities_cache_key(&self) -> String {
+        format!("api:capabilities:v3:{}", self.server.id)
+    }
+
+    /// Pings the server and works out which implementation answers. A native login is only
+    /// tried where one can exist; a session it opens is kept for later native calls.
+    pub async fn identify(&self) -> Result<ServerIdentity, ApiError> {
+        let url = self.build_url("ping", &[]);
+        let response = HTTP_CLIENT.get(&url).send().await?;
+        let ping = self.read_subsonic_response(response).await?.subsonic_response;
+        let server_type = ping.server_type.as_deref();
+        let mut native_login = false;
+        if ServerIdentity::may_have_native_api(server_type) {
+            match self.native_login().await {
+                Ok(session) => {
+                    NATIVE_AUTH_CACHE
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .insert(self.native_cache_key(), session);
+                    native_login = true;
+                }
+                Err(error) if error.is_unreachable() => return Err(error),
+                Err(_) => {}
+            }
+        }
+        Ok(ServerIdentity::detect(
+            server_type,
+            ping.version.as_deref(),
+            ping.server_version.as_deref(),
+            native_login,
+        ))
+    }
+
+    /// Capabilities from an earlier probe, if any, without touching the network.
+    pub fn capabilities(&self) -> Option<ServerCapabilities> {
+        {
+            let cache = SERVER_CAPABILITIES
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            if let Some(capabilities) = cache.get(&self.server.id) {
+                return Some(*capabilities);
+            }
+        }
+        let capabilities = cache_get_json::<ServerCapabilities>(&self.capabilities_cache_key())?;
+        let mut cache = SERVER_CAPABILITIES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        cache.insert(self.server.id.clone(), capabilities);
+        Some(capabilities)
+    }
+
+    /// Checks the optional endpoints the app uses. The result is kept in memory and in the
+    /// response cache for a week; `forget_capabilities` makes the next call probe again.
+    pub async fn probe_capabilities(&self) -> Result<ServerCapabilities, ApiError> {
+        if let Some(capabilities) = self.capabilities() {
+            return Ok(capabilities);
+        }
+
+        let identity = self.identify().await?;
+        let lyrics_by_song = self
+            .open_subsonic_extensions()
+            .await?
+            .iter()
+            .any(|name| name == "songLyrics");
+        let capabilities = ServerCapabilities {
+            internet_radio: self
+                .endpoint_supported("getInternetRadioStations", &[])
+                .await?,
+            podcasts: self
+                .endpoint_supported("getPodcasts", &[("includeEpisodes", "false")])
+                .await?,
+            native_api: identity.native_api,
+            lyrics_by_song,
+            // Any artist name works; unsupported servers fail before looking it up.
+            top_songs: self
+                .endpoint_supported(
+                    "getTopSongs",
+                    &[("artist", DEFAULT_CLIENT_NAME), ("count", "1")],
+                )
+                .await?,
+            permissions: self.user_permissions().await?,
+            flavor: identity.flavor,
+        };
+
+        let _ = cache_put_json(self.capabilities_cache_key(), &capabilities, Some(24 * 7));
+        let mut cache = SERVER_CAPABILITIES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        cache.insert(self.server.id.clone(), capabilities);
+        Ok(capabilities)
+    }
+
+    pub fn forget_capabilities(&self) {
+        SERVER_CAPABILITIES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&self.server.id);
+        let _ = cache_remove_prefix(&self.capabilities_cache_key());
+    }
+}
@@ -0,0 +1,112 @@
+//! Tests for the Navidrome client's request planning and response parsing.
+use super::*;
+
+fn ids(count: usize) -> Vec<String> {
+    (0..count).map(|index| format!("s{index}")).collect()
+}
+
+fn values<'a>(params: &'a [(String, String)], key: &str) -> Vec<&'a str> {
+    params
+        .iter()
+        .filter(|(name, _)| name == key)
+        .map(|(_, value)| value.as_str())
+        .collect()
+}
+
+#[test]
+fn small_or_form_posted_replacements_are_one_atomic_call() {
+    let plan = plan_playlist_replace("pl1", &ids(3), 2, Some(PLAYLIST_UPDATE_BATCH));
+    assert_eq!(plan.append_batches, 0);
+    assert_eq!(plan.batches.len(), 1);
+    let (params, songs) = &plan.batches[0];
+    assert_eq!(*songs, 5);
+    assert_eq!(values(params, "playlistId"), ["pl1"]);
+    assert_eq!(values(params, "songIndexToRemove"), ["1", "0"]);
+    assert_eq!(values(params, "songIdToAdd"), ["s0", "s1", "s2"]);
+
+    let plan = plan_playlist_replace("pl1", &ids(450), 300, None);
+    assert_eq!(plan.batches.len(), 1);
+    assert_eq!(plan.batches[0].1, 750);
+}
+
+#[test]
+fn large_replacements_append_before_removing_in_batches() {
+    let plan = plan_playlist_replace("pl1", &ids(250), 150, Some(PLAYLIST_UPDATE_BATCH));
+    let sizes: Vec<usize> = plan.batches.iter().map(|(_, songs)| *songs).collect();
+    assert_eq!(sizes, [100, 100, 50, 100, 50]);
+    assert_eq!(plan.append_batches, 3);
+
+    let (appends, removals) = plan.batches.split_at(plan.append_batches);
+    let added: Vec<&str> = appends
+        .iter()
+        .flat_map(|(params, _)| values(params, "songIdToAdd"))
+        .collect();
+    assert_eq!(added, ids(250));
+    assert!(appends
+        .iter()
+        .all(|(params, _)| values(params, "songIndexToRemove").is_empty()));
+    // The old songs go from the end down, so earlier indices stay valid between batches.
+    let removed: Vec<usize> = removals
+        .iter()
+        .flat_map(|(params, _)| values(params, "songIndexToRemove"))
+        .map(|index| index.parse().unwrap())
+        .collect();
+    assert_eq!(removed, (0..150).rev().collect::<Vec<_>>());
+    assert!(plan
+        .batches
+        .iter()
+        .all(|(params, _)| params.len() <= PLAYLIST_UPDATE_BATCH + 1));
+}
+
+#[test]
+fn partial_failures_report_how_far_the_update_got() {
+    let err = partial_playlist_update_error("Added", 0, 250, ApiError::generic("URI too long"));
+    assert_eq!(
+        err.to_string(),
+        ApiError::generic("URI too long").to_string()
+    );
+
+    let err = partial_playlist_update_error("Added", 200, 250, ApiError::generic("URI too long"));
+    let message = err.to_string();
+    assert!(message.contains("Added 200 of 250 songs"), "{message}");
+    assert!(message.contains("URI too long"), "{message}");
+}
+
+#[test]
+fn native_login_response_gives_a_session() {
+    let login: NativeLoginResponse =
+        serde_json::from_str(include_str!("../fixtures/login/navidrome.json")).unwrap();
+    let session = NativeAuthSession::from_login(login).unwrap();
+    assert!(session.token.starts_with("eyJ"));
+    assert_eq!(
+        session.client_unique_id,
+        "2a7b1f4e-6c35-4d4f-9a1c-0f4b7d2e8c61"
+    );
+
+    let login: NativeLoginResponse = serde_json::from_str(r#"{"token":" ","id":""}"#).unwrap();
+    assert!(matches!(
+        NativeAuthSession::from_login(login),
+        Err(ApiError::Parse(_))
+    ));
+}
+
+#[test]
+fn mime_aliases_of_one_format_compare_equal() {
+    assert_eq!(
+        canonical_audio_mime("audio/x-flac"),
+        canonical_audio_mime("audio/flac")
+    );
+    assert_eq!(
+        canonical_audio_mime("Audio/MP3; charset=binary").as_deref(),
+        Some("audio/mpeg")
+    );
+    assert_eq!(
+        canonical_audio_mime("audio/opus").as_deref(),
+        Some("audio/opus")
+    );
+    assert_ne!(
+        canonical_audio_mime("audio/flac"),
+        canonical_audio_mime("audio/mpeg")
+    );
+    assert_eq!(canonical_audio_mime(" ; "), None);
+}
@@ -0,0 +1,231 @@
+//! Queue files: a playback queue written as server-agnostic track descriptors, so it can be
+//! shared and played back from a different server or account.
+use crate::api::track_resolver::{resolve_track, TrackDescriptor, TrackLookup, TrackResolution};
+use crate::api::{ApiError, Song};
+use serde::{Deserialize, Serialize};
+
+/// Identifies a queue file among other JSON documents.
+pub const QUEUE_EXPORT_FORMAT: &str = "rustysound-queue";
+/// Bumped whenever a change to the file layout would confuse older readers.
+pub const QUEUE_EXPORT_VERSION: u32 = 1;
+
+/// The contents of a queue file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueueExport {
+    pub format: String,
+    pub version: u32,
+    /// Position of the playing track in `tracks`, when one was playing.
+    #[serde(default)]
+    pub current_index: Option<usize>,
+    pub tracks: Vec<TrackDescriptor>,
+}
+
+impl QueueExport {
+    pub fn from_queue(queue: &[Song], current_index: Option<usize>) -> Self {
+        Self {
+            format: QUEUE_EXPORT_FORMAT.to_string(),
+            version: QUEUE_EXPORT_VERSION,
+            current_index: current_index.filter(|index| *index < queue.len()),
+            tracks: queue.iter().map(TrackDescriptor::from_song).collect(),
+        }
+    }
+}
+
+/// `queue` as the pretty-printed JSON of a queue file.
+pub fn queue_export_json(queue: &[Song], current_index: Option<usize>) -> Result<String, String> {
+    serde_json::to_string_pretty(&QueueExport::from_queue(queue, current_index))
+        .map_err(|error| error.to_string())
+}
+
+/// Reads a queue file, refusing other JSON documents and files from a newer version.
+pub fn parse_queue_export(text: &str) -> Result<QueueExport, String> {
+    let export: QueueExport =
+        serde_json::from_str(text).map_err(|error| format!("Not a queue file: {error}"))?;
+    if export.format != QUEUE_EXPORT_FORMAT {
+        return Err(format!("Not a queue file (format \"{}\").", export.format));
+    }
+    if export.version > QUEUE_EXPORT_VERSION {
+        return Err(format!(
+            "This queue file is version {}; this app reads up to version {}.",
+            export.version, QUEUE_EXPORT_VERSION
+        ));
+    }
+    Ok(export)
+}
+
+/// The outcome of resolving a queue file against one server.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct QueueImportReport {
+    /// Resolved songs in file order; unresolved tracks are left out.
+    pub songs: Vec<Song>,
+    /// Index into `songs` of the track that was playing when the file was written.
+    pub current_index: Option<usize>,
+    pub by_id: usize,
+    pub matched: usize,
+    pub ambiguous: Vec<TrackDescriptor>,
+    pub not_found: Vec<TrackDescriptor>,
+}
+
+impl QueueImportReport {
+    pub fn resolved(&self) -> usize {
+        self.songs.len()
+    }
+}
+
+/// Resolves every track of `export` through `lookup`, tallying how each was found.
+pub(crate) async fn resolve_queue_export(
+    lookup: &impl TrackLookup,
+    export: &QueueExport,
+) -> Result<QueueImportReport, ApiError> {
+    let mut report = QueueImportReport::default();
+    for (index, track) in export.tracks.iter().enumerate() {
+        let resolution = resolve_track(lookup, track).await?;
+        match &resolution {
+            TrackResolution::ById(_) => report.by_id += 1,
+            TrackResolution::Matched(_) => report.matched += 1,
+            TrackResolution::Ambiguous(_) => report.ambiguous.push(track.clone()),
+            TrackResolution::NotFound => {
+                report.not_found.push(track.clone());
+                continue;
+            }
+        }
+        if export.current_index == Some(index) {
+            report.current_index = Some(report.songs.len());
+        }
+        report.songs.extend(resolution.song().cloned());
+    }
+    Ok(report)
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    /// An in-memory server library answering the resolver's lookups.
+    struct MockLibrary {
+        songs: Vec<Song>,
+    }
+
+    impl TrackLookup for MockLibrary {
+        async fn song_by_id(&self, id: &str) -> Result<Option<Song>, ApiError> {
+            Ok(self.songs.iter().find(|song| song.id == id).cloned())
+        }
+
+        async fn search_songs(&self, query: &str) -> Result<Vec<Song>, ApiError> {
+            let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+            Ok(self
+                .songs
+                .iter()
+                .filter(|song| {
+                    let text = format!(
+                        "{} {}",
+                        song.title,
+                        song.artist.as_deref().unwrap_or_default()
+                    )
+                    .to_lowercase();
+                    words.iter().all(|word| text.contains(word.as_str()))
+                })
+                .cloned()
+                .collect())
+        }
+    }
+
+    fn song(id: &str, server_id: &str, title: &str, artist: &str, duration: u32) -> Song {
+        Song {
+            id: id.to_string(),
+            server_id: server_id.to_string(),
+            title: title.to_string(),
+            artist: Some(artist.to_string()),
+            album: Some(format!("{artist} Album")),
+            duration,
+            ..Song::default()
+        }
+    }
+
+    async fn round_trip(
+        queue: &[Song],
+        current: Option<usize>,
+        library: &MockLibrary,
+    ) -> QueueImportReport {
+        let json = queue_export_json(queue, current).unwrap();
+        let export = parse_queue_export(&json).unwrap();
+        resolve_queue_export(library, &export).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn round_trip_resolves_songs_under_different_ids() {
+        let queue = vec![
+            song("a1", "home", "Intro", "Band", 95),
+            song("a2", "home", "Second Song (Remastered)", "Band", 210),
+        ];
+        let library = MockLibrary {
+            songs: vec![
+                song("x9", "friend", "Second Song", "Band", 211),
+                song("x8", "friend", "Intro", "Band", 95),
+            ],
+        };
+
+        let report = round_trip(&queue, Some(1), &library).await;
+
+        let ids: Vec<&str> = report.songs.iter().map(|song| song.id.as_str()).collect();
+        assert_eq!(ids, ["x8", "x9"]);
+        assert_eq!(report.matched, 2);
+        assert_eq!(report.current_index, Some(1));
+        assert!(report.ambiguous.is_empty() && report.not_found.is_empty());
+    }
+
+    #[tokio::test]
+    async fn round_trip_reuses_ids_that_still_name_the_same_song() {
+        let queue = vec![song("a1", "home", "Intro", "Band", 95)];
+        let library = MockLibrary {
+            songs: queue.clone(),
+        };
+
+        let report = round_trip(&queue, None, &library).await;
+
+        assert_eq!(report.by_id, 1);
+        assert_eq!(report.songs, queue);
+    }
+
+    #[tokio::test]
+    async fn round_trip_reports_missing_and_ambiguous_tracks() {
+        let queue = vec![
+            song("a1", "home", "Gone", "Band", 100),
+            song("a2", "home", "Live Take", "Band", 0),
+            song("a3", "home", "Intro", "Band", 95),
+        ];
+        let mut first_take = song("x1", "friend", "Live Take", "Band", 180);
+        let mut second_take = song("x2", "friend", "Live Take", "Band", 240);
+        first_take.album = Some("Tour One".to_string());
+        second_take.album = Some("Tour Two".to_string());
+        let library = MockLibrary {
+            songs: vec![
+                first_take,
+                second_take,
+                // Same title by someone else is never a match.
+                song("x3", "friend", "Gone", "Other Band", 100),
+                song("x4", "friend", "Intro", "Band", 95),
+            ],
+        };
+
+        let report = round_trip(&queue, Some(0), &library).await;
+
+        assert_eq!(report.not_found.len(), 1);
+        assert_eq!(report.not_found[0].title, "Gone");
+        assert_eq!(report.ambiguous.len(), 1);
+        assert_eq!(report.resolved(), 2);
+        // The playing track was not found, so nothing is marked as playing.
+        assert_eq!(report.current_index, None);
+    }
+
+    #[test]
+    fn rejects_other_documents_and_newer_versions() {
+        assert!(parse_queue_export(r#"{"format":"other","version":1,"tracks":[]}"#).is_err());
+        let newer = format!(
+            r#"{{"format":"{QUEUE_EXPORT_FORMAT}","version":{},"tracks":[]}}"#,
+            QUEUE_EXPORT_VERSION + 1
+        );
+        assert!(parse_queue_export(&newer).is_err());
+        assert!(parse_queue_export("[]").is_err());
+    }
+}
@@ -0,0 +1,190 @@
+//! Which server implementation answers a connection, from its `ping` and a native login
+//! attempt, so features that need Navidrome's own API can step aside on other servers.
+use serde::{Deserialize, Serialize};
+
+/// Server families the app tells apart. Anything that isn't recognised speaks plain Subsonic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerFlavor {
+    Navidrome,
+    Airsonic,
+    #[default]
+    Subsonic,
+}
+
+impl ServerFlavor {
+    pub fn label(self) -> &'static str {
+        match self {
+            ServerFlavor::Navidrome => "Navidrome",
+            ServerFlavor::Airsonic => "Airsonic",
+            ServerFlavor::Subsonic => "Subsonic",
+        }
+    }
+
+    /// Flavor named by the OpenSubsonic `type` field of a response, if any.
+    fn from_server_type(server_type: &str) -> Option<Self> {
+        let server_type = server_type.trim().to_ascii_lowercase();
+        if server_type.is_empty() {
+            None
+        } else if server_type == "navidrome" {
+            Some(ServerFlavor::Navidrome)
+        } else if server_type.contains("airsonic") {
+            Some(ServerFlavor::Airsonic)
+        } else {
+            Some(ServerFlavor::Subsonic)
+        }
+    }
+}
+
+/// What a connection test learned about a server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerIdentity {
+    pub flavor: ServerFlavor,
+    /// Implementation name as the server reports it, e.g. "gonic".
+    pub server_type: Option<String>,
+    /// Subsonic API version from the response envelope, e.g. "1.16.1".
+    pub api_version: Option<String>,
+    /// Release of the server software, reported by OpenSubsonic servers only.
+    pub server_version: Option<String>,
+    /// Whether Navidrome's own REST API accepted the account's credentials.
+    pub native_api: bool,
+}
+
+impl ServerIdentity {
+    /// Combines the `ping` envelope with the outcome of a native login. Navidrome releases
+    /// from before OpenSubsonic leave `type` out, so a working native login identifies them.
+    pub fn detect(
+        server_type: Option<&str>,
+        api_version: Option<&str>,
+        server_version: Option<&str>,
+        native_login: bool,
+    ) -> Self {
+        let reported = server_type.and_then(ServerFlavor::from_server_type);
+        let flavor = match reported {
+            Some(flavor) => flavor,
+            None if native_login => ServerFlavor::Navidrome,
+            None => ServerFlavor::Subsonic,
+        };
+        let non_empty = |value: Option<&str>| {
+            value
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        Self {
+            flavor,
+            server_type: non_empty(server_type),
+            api_version: non_empty(api_version),
+            server_version: non_empty(server_version),
+            native_api: flavor == ServerFlavor::Navidrome && native_login,
+        }
+    }
+
+    /// Whether a native login is worth trying: only Navidrome has one, and only servers
+    /// that name themselves something else can be ruled out up front.
+    pub fn may_have_native_api(server_type: Option<&str>) -> bool {
+        server_type
+            .and_then(ServerFlavor::from_server_type)
+            .is_none_or(|flavor| flavor == ServerFlavor::Navidrome)
+    }
+
+    /// One line for the connection test, e.g. "Navidrome 0.53.3 · Subsonic API 1.16.1".
+    pub fn summary(&self) -> String {
+        let name = match (self.flavor, self.server_type.as_deref()) {
+            // Keep the server's own name for implementations without a flavor of their own.
+            (ServerFlavor::Subsonic, Some(server_type)) => server_type.to_string(),
+            (flavor, _) => flavor.label().to_string(),
+        };
+        let mut summary = match self.server_version.as_deref() {
+            Some(version) => format!("{name} {version}"),
+            None => name,
+        };
+        if let Some(api_version) = self.api_version.as_deref() {
+            summary.push_str(&format!(" · Subsonic API {api_version}"));
+        }
+        if self.flavor == ServerFlavor::Navidrome && !self.native_api {
+            summary.push_str(" · Navidrome API unavailable");
+        } else if self.flavor != ServerFlavor::Navidrome {
+            summary.push_str(" · Navidrome-only features are off");
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::SubsonicResponse;
+
+    fn identify(fixture: &str, native_login: bool) -> ServerIdentity {
+        let ping: SubsonicResponse = serde_json::from_str(fixture).expect("fixture parses");
+        let ping = ping.subsonic_response;
+        ServerIdentity::detect(
+            ping.server_type.as_deref(),
+            ping.version.as_deref(),
+            ping.server_version.as_deref(),
+            native_login,
+        )
+    }
+
+    #[test]
+    fn navidrome_ping_with_native_login() {
+        let identity = identify(include_str!("fixtures/ping/navidrome.json"), true);
+        assert_eq!(identity.flavor, ServerFlavor::Navidrome);
+        assert!(identity.native_api);
+        assert_eq!(identity.api_version.as_deref(), Some("1.16.1"));
+        assert_eq!(
+            identity.summary(),
+            "Navidrome 0.53.3 (13af8ed4) · Subsonic API 1.16.1"
+        );
+    }
+
+    #[test]
+    fn navidrome_ping_with_failed_native_login_keeps_flavor_without_native_api() {
+        let identity = identify(include_str!("fixtures/ping/navidrome.json"), false);
+        assert_eq!(identity.flavor, ServerFlavor::Navidrome);
+        assert!(!identity.native_api);
+        assert!(identity.summary().ends_with("Navidrome API unavailable"));
+    }
+
+    #[test]
+    fn untyped_ping_is_navidrome_only_when_native_login_works() {
+        let legacy = identify(include_str!("fixtures/ping/navidrome_legacy.json"), true);
+        assert_eq!(legacy.flavor, ServerFlavor::Navidrome);
+        assert!(legacy.native_api);
+
+        let plain = identify(include_str!("fixtures/ping/subsonic.json"), false);
+        assert_eq!(plain.flavor, ServerFlavor::Subsonic);
+        assert!(!plain.native_api);
+        assert_eq!(plain.server_version, None);
+        assert_eq!(
+            plain.summary(),
+            "Subsonic · Subsonic API 1.16.1 · Navidrome-only features are off"
+        );
+    }
+
+    #[test]
+    fn airsonic_and_other_opensubsonic_servers_never_get_native_api() {
+        let airsonic = identify(include_str!("fixtures/ping/airsonic_advanced.json"), true);
+        assert_eq!(airsonic.flavor, ServerFlavor::Airsonic);
+        assert!(!airsonic.native_api);
+        assert_eq!(airsonic.api_version.as_deref(), Some("1.15.0"));
+
+        let gonic = identify(include_str!("fixtures/ping/gonic.json"), false);
+        assert_eq!(gonic.flavor, ServerFlavor::Subsonic);
+        assert_eq!(
+            gonic.summary(),
+            "gonic v0.16.4 · Subsonic API 1.15.0 · Navidrome-only features are off"
+        );
+    }
+
+    #[test]
+    fn native_login_is_only_tried_where_it_can_exist() {
+        assert!(ServerIdentity::may_have_native_api(None));
+        assert!(ServerIdentity::may_have_native_api(Some("Navidrome")));
+        assert!(!ServerIdentity::may_have_native_api(Some(
+            "airsonic-advanced"
+        )));
+        assert!(!ServerIdentity::may_have_native_api(Some("gonic")));
+    }
+}
@@ -0,0 +1,204 @@
+//! Finding songs on a server from what they are rather than from an id.
+//!
+//! Queue files and playlist files name songs by title, artist, album and duration, with the
+//! exporting server's id only as a hint. [`resolve_track`] tries the hint first, then
+//! searches the server and keeps the candidate that fits the descriptor best. The same
+//! descriptors and resolver serve every importer, so they all match songs the same way.
+use crate::api::{ApiError, NavidromeClient, Song};
+use crate::collation::match_key;
+use serde::{Deserialize, Serialize};
+
+/// Known durations further apart than this belong to different recordings.
+const DURATION_REJECT_SECS: u32 = 10;
+/// Durations this close count as the same recording.
+const DURATION_MATCH_SECS: u32 = 2;
+/// Songs fetched per search when resolving one track.
+const SEARCH_CANDIDATES: u32 = 20;
+
+/// A song described independently of any server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct TrackDescriptor {
+    pub title: String,
+    #[serde(default)]
+    pub artist: Option<String>,
+    #[serde(default)]
+    pub album: Option<String>,
+    /// Zero when unknown.
+    #[serde(default)]
+    pub duration_secs: u32,
+    /// Id on the server the descriptor came from. Used only while it still names a song
+    /// with the same title.
+    #[serde(default)]
+    pub id_hint: Option<String>,
+}
+
+impl TrackDescriptor {
+    pub fn from_song(song: &Song) -> Self {
+        Self {
+            title: song.title.clone(),
+            artist: song.artist.clone(),
+            album: song.album.clone(),
+            duration_secs: song.duration,
+            id_hint: Some(song.id.clone()),
+        }
+    }
+}
+
+/// How a descriptor was resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrackResolution {
+    /// The id hint names the same song on this server.
+    ById(Song),
+    /// One song fits the descriptor better than any other.
+    Matched(Song),
+    /// Several songs fit equally well; the first of them is used.
+    Ambiguous(Song),
+    NotFound,
+}
+
+impl TrackResolution {
+    pub fn song(&self) -> Option<&Song> {
+        match self {
+            Self::ById(song) | Self::Matched(song) | Self::Ambiguous(song) => Some(song),
+            Self::NotFound => None,
+        }
+    }
+}
+
+/// The lookups the resolver needs from a server.
+pub(crate) trait TrackLookup {
+    /// The song with `id`, or `None` when the server has no such song.
+    async fn song_by_id(&self, id: &str) -> Result<Option<Song>, ApiError>;
+    async fn search_songs(&self, query: &str) -> Result<Vec<Song>, ApiError>;
+}
+
+impl TrackLookup for NavidromeClient {
+    async fn song_by_id(&self, id: &str) -> Result<Option<Song>, ApiError> {
+        match self.get_song(id).await {
+            Ok(song) => Ok(Some(song)),
+            Err(error) if error.is_not_found() => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn search_songs(&self, query: &str) -> Result<Vec<Song>, ApiError> {
+        Ok(self
+            .search(query, 0, 0, SEARCH_CANDIDATES, None)
+            .await?
+            .songs)
+    }
+}
+
+/// The title without a trailing "(...)" or "[...]" part such as "(Remastered 2011)".
+fn base_title(title: &str) -> String {
+    let trimmed = title.trim();
+    let cut = trimmed
+        .rfind(['(', '['])
+        .filter(|index| *index > 0 && trimmed.ends_with([')', ']']))
+        .map(|index| &trimmed[..index])
+        .unwrap_or(trimmed);
+    match_key(cut)
+}
+
+fn same_optional(left: Option<&str>, right: Option<&str>) -> Option<bool> {
+    let left = match_key(left?);
+    let right = match_key(right?);
+    (!left.is_empty() && !right.is_empty()).then(|| left == right)
+}
+
+/// How well `song` fits `descriptor`, or `None` when it can't be the same song: a different
+/// title, a different artist, or a clearly different length.
+fn match_score(descriptor: &TrackDescriptor, song: &Song) -> Option<u32> {
+    let mut score = if match_key(&descriptor.title) == match_key(&song.title) {
+        4
+    } else if base_title(&descriptor.title) == base_title(&song.title) {
+        2
+    } else {
+        return None;
+    };
+
+    match same_optional(descriptor.artist.as_deref(), song.artist.as_deref()) {
+        Some(true) => score += 3,
+        Some(false) => return None,
+        None => {}
+    }
+    if same_optional(descriptor.album.as_deref(), song.album.as_deref()) == Some(true) {
+        score += 2;
+    }
+    if descriptor.duration_secs > 0 && song.duration > 0 {
+        let difference = descriptor.duration_secs.abs_diff(song.duration);
+        if difference > DURATION_REJECT_SECS {
+            return None;
+        }
+        if difference <= DURATION_MATCH_SECS {
+            score += 1;
+        }
+    }
+    Some(score)
+}
+
+/// The candidate that fits `descriptor` best. Ties between different songs are ambiguous.
+pub fn pick_best_match(descriptor: &TrackDescriptor, candidates: &[Song]) -> TrackResolution {
+    let mut best: Option<(u32, &Song)> = None;
+    let mut tied = false;
+    for song in candidates {
+        let Some(score) = match_score(descriptor, song) else {
+            continue;
+        };
+        match best {
+            Some((best_score, best_song)) if score == best_score => {
+                tied |= best_song.id != song.id;
+            }
+            Some((best_score, _)) if score < best_score => {}
+            _ => {
+                best = Some((score, song));
+                tied = false;
+            }
+        }
+    }
+    match best {
+        Some((_, song)) if tied => TrackResolution::Ambiguous(song.clone()),
+        Some((_, song)) => TrackResolution::Matched(song.clone()),
+        None => TrackResolution::NotFound,
+    }
+}
+
+/// Finds `descriptor` on the server behind `lookup`: by its id hint when that id still
+/// names the same song, otherwise by searching for the title and artist.
+pub(crate) async fn resolve_track(
+    lookup: &impl TrackLookup,
+    descriptor: &TrackDescriptor,
+) -> Result<TrackResolution, ApiError> {
+    if let Some(id) = descriptor
+        .id_hint
+        .as_deref()
+        .filter(|id| !id.trim().is_empty())
+    {
+        if let Some(song) = lookup.song_by_id(id).await? {
+            if base_title(&song.title) == base_title(&descriptor.title) {
+                return Ok(TrackResolution::ById(song));
+            }
+        }
+    }
+
+    let title = descriptor.title.trim();
+    if title.is_empty() {
+        return Ok(TrackResolution::NotFound);
+    }
+    let artist = descriptor.artist.as_deref().unwrap_or_default().trim();
+    // Falls back to the bare title, as servers seldom find "(Remastered)" style suffixes
+    // another library does not share.
+    let mut queries = vec![format!("{title} {artist}").trim().to_string()];
+    let bare_title = base_title(title);
+    if !bare_title.is_empty() && !queries.contains(&bare_title) {
+        queries.push(bare_title);
+    }
+    for query in queries {
+        let candidates = lookup.search_songs(&query).await?;
+        let resolution = pick_best_match(descriptor, &candidates);
+        if resolution != TrackResolution::NotFound {
+            return Ok(resolution);
+        }
+    }
+    Ok(TrackResolution::NotFound)
+}
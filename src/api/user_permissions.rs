@@ -0,0 +1,166 @@
+//! Roles of the signed-in user, from `getUser`, so features the account may not use can be
+//! disabled up front instead of failing when they're tried.
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Something the UI offers that the server may refuse for this account. A guest session may
+/// do none of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserAction {
+    Download,
+    CreatePlaylist,
+    /// Adding to, reordering, renaming or deleting an existing playlist.
+    EditPlaylist,
+    /// Stars and ratings.
+    Rate,
+}
+
+/// Tooltip on an affordance hidden from or refused to a guest session.
+pub const GUEST_DENIED_HINT: &str = "Not available in guest mode";
+
+impl UserAction {
+    /// Tooltip shown on an affordance the account lacks the role for.
+    pub fn denied_hint(self) -> &'static str {
+        match self {
+            UserAction::Download => "Your account on this server isn't allowed to download",
+            UserAction::CreatePlaylist => {
+                "Your account on this server isn't allowed to create playlists"
+            }
+            UserAction::EditPlaylist => {
+                "Your account on this server isn't allowed to edit playlists"
+            }
+            UserAction::Rate => GUEST_DENIED_HINT,
+        }
+    }
+}
+
+/// Servers that build their JSON from XML send roles as `"true"` / `"false"`.
+fn deserialize_role<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(
+        match Option::<serde_json::Value>::deserialize(deserializer)? {
+            Some(serde_json::Value::Bool(value)) => Some(value),
+            Some(serde_json::Value::String(value)) => value.trim().parse().ok(),
+            _ => None,
+        },
+    )
+}
+
+/// The `user` payload of `getUser`. A role the server leaves out is unknown and counts as
+/// granted, so nothing is hidden from servers that don't report roles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct UserPermissions {
+    #[serde(default, alias = "adminRole", deserialize_with = "deserialize_role")]
+    pub admin: Option<bool>,
+    #[serde(default, alias = "downloadRole", deserialize_with = "deserialize_role")]
+    pub download: Option<bool>,
+    #[serde(default, alias = "playlistRole", deserialize_with = "deserialize_role")]
+    pub playlist: Option<bool>,
+    #[serde(default, alias = "shareRole", deserialize_with = "deserialize_role")]
+    pub share: Option<bool>,
+    #[serde(default, alias = "uploadRole", deserialize_with = "deserialize_role")]
+    pub upload: Option<bool>,
+}
+
+impl UserPermissions {
+    /// Whether the account may do `action`. Admins may do everything.
+    pub fn user_can(&self, action: UserAction) -> bool {
+        if self.admin == Some(true) {
+            return true;
+        }
+        let role = match action {
+            UserAction::Download => self.download,
+            UserAction::CreatePlaylist => self.playlist,
+            UserAction::EditPlaylist => self.playlist,
+            // Not tied to a server role.
+            UserAction::Rate => None,
+        };
+        role.unwrap_or(true)
+    }
+
+    /// The roles the server reported, with user-facing names, for the server list.
+    pub fn summary(&self) -> Vec<(&'static str, bool)> {
+        [
+            ("Admin", self.admin),
+            ("Download", self.download),
+            ("Playlists", self.playlist),
+            ("Share", self.share),
+            ("Upload", self.upload),
+        ]
+        .into_iter()
+        .filter_map(|(label, role)| role.map(|granted| (label, granted)))
+        .collect()
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::api::SubsonicResponse;
+
+    fn permissions(fixture: &str) -> UserPermissions {
+        serde_json::from_str::<SubsonicResponse>(fixture)
+            .unwrap()
+            .subsonic_response
+            .user
+            .expect("user payload")
+    }
+
+    #[test]
+    fn navidrome_regular_user_without_download_rights() {
+        let user = permissions(
+            r#"{"subsonic-response":{"status":"ok","version":"1.16.1","type":"navidrome",
+            "user":{"username":"sam","adminRole":false,"settingsRole":true,
+            "downloadRole":false,"uploadRole":false,"playlistRole":true,
+            "coverArtRole":true,"commentRole":false,"podcastRole":false,
+            "streamRole":true,"jukeboxRole":false,"shareRole":false,
+            "videoConversionRole":false,"folder":[1]}}}"#,
+        );
+
+        assert!(!user.user_can(UserAction::Download));
+        assert!(user.user_can(UserAction::CreatePlaylist));
+        assert!(user.summary().contains(&("Download", false)));
+    }
+
+    #[test]
+    fn admins_may_do_everything() {
+        let user = permissions(
+            r#"{"subsonic-response":{"status":"ok","version":"1.16.1",
+            "user":{"username":"admin","adminRole":true,"downloadRole":false,
+            "playlistRole":false,"shareRole":false,"uploadRole":false}}}"#,
+        );
+
+        for action in [
+            UserAction::Download,
+            UserAction::CreatePlaylist,
+            UserAction::EditPlaylist,
+            UserAction::Rate,
+        ] {
+            assert!(user.user_can(action), "{action:?}");
+        }
+    }
+
+    #[test]
+    fn string_roles_are_read_and_missing_roles_count_as_granted() {
+        let user = permissions(
+            r#"{"subsonic-response":{"status":"ok","version":"1.15.0",
+            "user":{"username":"guest","playlistRole":"false","downloadRole":"true"}}}"#,
+        );
+
+        assert!(user.user_can(UserAction::Download));
+        assert!(!user.user_can(UserAction::CreatePlaylist));
+        assert!(!user.user_can(UserAction::EditPlaylist));
+        assert_eq!(
+            user.summary(),
+            vec![("Download", true), ("Playlists", false)]
+        );
+    }
+
+    #[test]
+    fn unknown_permissions_allow_everything() {
+        let user = UserPermissions::default();
+        assert!(user.user_can(UserAction::Download));
+        assert!(user.summary().is_empty());
+    }
+}
@@ -199,6 +199,43 @@ impl SimpleCache {
         removed
     }
 
+    /// Re-keys entries whose key has `from_id` as one of its segments so they belong to
+    /// `to_id`, rewriting quoted ids inside JSON payloads too. Entries the target already has
+    /// are kept; the duplicate's copy is dropped. Returns how many entries were moved or dropped.
+    pub fn reassign_server(&mut self, from_id: &str, to_id: &str) -> usize {
+        if from_id.is_empty() || from_id == to_id {
+            return 0;
+        }
+        let moves: Vec<(String, String)> = self
+            .entries
+            .keys()
+            .filter_map(|key| {
+                reassign_key_server(key, from_id, to_id).map(|new_key| (key.clone(), new_key))
+            })
+            .collect();
+        let quoted_from = format!("\"{from_id}\"");
+        let quoted_to = format!("\"{to_id}\"");
+        let mut touched = 0usize;
+        for (key, new_key) in moves {
+            let Some(mut entry) = self.entries.remove(&key) else {
+                continue;
+            };
+            touched += 1;
+            self.current_size_bytes = self.current_size_bytes.saturating_sub(entry.size_bytes());
+            if self.entries.contains_key(&new_key) {
+                continue;
+            }
+            if entry.content_type == "application/json" {
+                if let Ok(text) = std::str::from_utf8(&entry.data) {
+                    entry.data = text.replace(&quoted_from, &quoted_to).into_bytes();
+                }
+            }
+            self.current_size_bytes += entry.size_bytes();
+            self.entries.insert(new_key, entry);
+        }
+        touched
+    }
+
     pub fn stats(&self) -> CacheStats {
         CacheStats {
             entry_count: self.entries.len(),
@@ -208,6 +245,30 @@ impl SimpleCache {
     }
 }
 
+/// Rewrites the `:`-separated segments of a cache key (and the `|`-joined ids of multi-server
+/// segments) that are exactly `from_id`. Returns `None` when no segment matched.
+fn reassign_key_server(key: &str, from_id: &str, to_id: &str) -> Option<String> {
+    let mut matched = false;
+    let segments: Vec<String> = key
+        .split(':')
+        .map(|segment| {
+            segment
+                .split('|')
+                .map(|part| {
+                    if part == from_id {
+                        matched = true;
+                        to_id
+                    } else {
+                        part
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("|")
+        })
+        .collect();
+    matched.then(|| segments.join(":"))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheStats {
     pub entry_count: usize,
@@ -282,3 +343,24 @@ mod native_impl {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reassign_key_server_only_replaces_whole_segments() {
+        assert_eq!(
+            reassign_key_server("api:getPlaylist:v1:srv1:pl-srv1", "srv1", "keep").as_deref(),
+            Some("api:getPlaylist:v1:keep:pl-srv1")
+        );
+        assert_eq!(
+            reassign_key_server("view:artists:v1:srv10|srv1", "srv1", "keep").as_deref(),
+            Some("view:artists:v1:srv10|keep")
+        );
+        assert_eq!(
+            reassign_key_server("cover_art:srv10:al-1", "srv1", "keep"),
+            None
+        );
+    }
+}
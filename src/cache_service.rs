@@ -13,6 +13,7 @@ struct RuntimeCacheConfig {
     cache_expiry_days: i32,
     cache_size_mb: u32,
     offline_mode: bool,
+    negative_cache_enabled: bool,
 }
 
 impl Default for RuntimeCacheConfig {
@@ -23,6 +24,7 @@ impl Default for RuntimeCacheConfig {
             cache_expiry_days: 3,
             cache_size_mb: 100,
             offline_mode: false,
+            negative_cache_enabled: true,
         }
     }
 }
@@ -34,6 +36,10 @@ static CACHE: Lazy<Mutex<SimpleCache>> = Lazy::new(|| {
 static CACHE_CONFIG: Lazy<Mutex<RuntimeCacheConfig>> =
     Lazy::new(|| Mutex::new(RuntimeCacheConfig::default()));
 
+const NEGATIVE_CACHE_PREFIX: &str = "negative:";
+/// Kept short so newly added lyrics/artwork are picked up within the hour.
+const NEGATIVE_CACHE_TTL_HOURS: u32 = 1;
+
 fn effective_expiry_duration(override_hours: Option<u32>) -> Duration {
     let config = CACHE_CONFIG.lock().unwrap_or_else(|e| e.into_inner());
 
@@ -78,6 +84,7 @@ pub fn apply_settings(settings: &AppSettings) {
         config.cache_expiry_days = settings.cache_expiry_days.clamp(-1, 3650);
         config.cache_size_mb = settings.cache_size_mb.clamp(25, 2048);
         config.offline_mode = settings.offline_mode;
+        config.negative_cache_enabled = settings.negative_cache_enabled;
     }
 
     let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
@@ -128,6 +135,42 @@ pub fn remove_by_prefix(prefix: &str) -> usize {
     removed
 }
 
+/// Moves cached responses from a merged duplicate server to the server it was merged into.
+pub fn reassign_server(from_id: &str, to_id: &str) -> usize {
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let touched = cache.reassign_server(from_id, to_id);
+    if touched > 0 {
+        save_cache(&cache);
+    }
+    touched
+}
+
+fn negative_cache_active() -> bool {
+    let config = CACHE_CONFIG.lock().unwrap_or_else(|e| e.into_inner());
+    config.enabled && config.negative_cache_enabled
+}
+
+/// Remembers that `key` (e.g. "lyrics:..." or "cover_art:...") had no result.
+pub fn remember_miss(key: &str) {
+    if !negative_cache_active() {
+        return;
+    }
+    let _ = put_json(
+        format!("{NEGATIVE_CACHE_PREFIX}{key}"),
+        &true,
+        Some(NEGATIVE_CACHE_TTL_HOURS),
+    );
+}
+
+pub fn is_known_miss(key: &str) -> bool {
+    negative_cache_active()
+        && get_json::<bool>(&format!("{NEGATIVE_CACHE_PREFIX}{key}")).unwrap_or(false)
+}
+
+pub fn clear_negative_cache() -> usize {
+    remove_by_prefix(NEGATIVE_CACHE_PREFIX)
+}
+
 pub fn clear_all() {
     let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
     cache.clear();
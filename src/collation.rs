@@ -0,0 +1,277 @@
+//! Accent- and case-insensitive text handling shared by every search box, list filter and
+//! client-side sort, so "Édith Piaf" sorts with the E's and typing "bjork" finds "Björk".
+//!
+//! Text is decomposed with Unicode NFKD, which splits "é" into "e" and an accent and turns
+//! full-width "Ａ" into "A", then lowercased with the accents dropped. Letters that don't
+//! decompose ("ß", "ø", "æ") are spelled the way they are usually typed, and katakana is read
+//! as hiragana. Other scripts, CJK included, pass through as they are, so folded text orders
+//! Latin before Greek and Cyrillic, and those before CJK.
+use icu_normalizer::{ComposingNormalizerBorrowed, DecomposingNormalizerBorrowed};
+use std::cmp::Ordering;
+
+/// Leading article the "ignore articles" sort setting skips.
+const SORT_ARTICLE: &str = "the ";
+
+/// How a letter without a decomposition is usually typed on a plain keyboard.
+fn spelled_out(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        'ø' => "o",
+        'ł' => "l",
+        'đ' | 'ð' => "d",
+        'þ' => "th",
+        'ı' => "i",
+        'ħ' => "h",
+        'ŧ' => "t",
+        _ => return None,
+    })
+}
+
+/// Combining accents left over once text is decomposed. The kana voicing marks are kept,
+/// so "が" still differs from "か".
+fn is_combining_accent(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+/// Katakana letters read as their hiragana counterparts.
+fn as_hiragana(c: char) -> char {
+    match c {
+        '\u{30A1}'..='\u{30F6}' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// `text` lowercased without accents, for comparing.
+pub fn fold_text(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    let decomposed = DecomposingNormalizerBorrowed::new_nfkd().normalize(text);
+    for c in decomposed.chars().flat_map(char::to_lowercase) {
+        if is_combining_accent(c) {
+            continue;
+        }
+        match spelled_out(c) {
+            Some(spelling) => folded.push_str(spelling),
+            None => folded.push(as_hiragana(c)),
+        }
+    }
+    // Puts back together what wasn't dropped, such as kana with voicing marks.
+    ComposingNormalizerBorrowed::new_nfc()
+        .normalize(&folded)
+        .into_owned()
+}
+
+/// Folded `text` with punctuation dropped, so "Don't" reads as "dont" and "re-enter" as
+/// "reenter". Searches and filters match against this.
+pub fn search_text(text: &str) -> String {
+    fold_text(text)
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect()
+}
+
+/// The words of a query, as `search_text` reads them.
+pub fn search_tokens(text: &str) -> Vec<String> {
+    search_text(text)
+        .split_whitespace()
+        .map(String::from)
+        .collect()
+}
+
+/// The folded words of `text` joined by single spaces, so "Björk", " bjork" and "BJORK!"
+/// agree. Song records from different servers and files are matched on this.
+pub fn match_key(text: &str) -> String {
+    search_tokens(text).join(" ")
+}
+
+/// What `text` sorts by: its folded words, without a leading "The " when `ignore_articles`
+/// is on.
+pub fn sort_key(text: &str, ignore_articles: bool) -> String {
+    let key = match_key(text);
+    match key.strip_prefix(SORT_ARTICLE) {
+        Some(rest) if ignore_articles => rest.to_string(),
+        _ => key,
+    }
+}
+
+/// Orders two names the way lists are sorted. Names that only differ in case or accents
+/// keep a fixed order between them.
+pub fn collate(left: &str, right: &str, ignore_articles: bool) -> Ordering {
+    sort_key(left, ignore_articles)
+        .cmp(&sort_key(right, ignore_articles))
+        .then_with(|| fold_text(left).cmp(&fold_text(right)))
+        .then_with(|| left.cmp(right))
+}
+
+/// Sorts `items` by the name `name` reads off each, in `collate` order. Each name is folded
+/// once, which matters for long lists such as a library's artists.
+pub fn sort_by_name<T>(items: &mut [T], ignore_articles: bool, name: impl Fn(&T) -> &str) {
+    items.sort_by_cached_key(|item| {
+        let name = name(item);
+        (
+            sort_key(name, ignore_articles),
+            fold_text(name),
+            name.to_string(),
+        )
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_accents_and_case_across_scripts() {
+        for (text, folded) in [
+            // Latin
+            ("Édith Piaf", "edith piaf"),
+            ("Björk", "bjork"),
+            ("Sigur Rós", "sigur ros"),
+            ("Mötley Crüe", "motley crue"),
+            ("Françoise Hardy", "francoise hardy"),
+            ("Antonín Dvořák", "antonin dvorak"),
+            ("Łona", "lona"),
+            ("Die Ärzte", "die arzte"),
+            ("Straße", "strasse"),
+            ("Røyksopp", "royksopp"),
+            ("Ægir", "aegir"),
+            ("İstanbul", "istanbul"),
+            ("Ｂｅａｔｌｅｓ", "beatles"),
+            ("ﬁnale", "finale"),
+            // Greek
+            ("Μάνος Χατζιδάκις", "μανος χατζιδακις"),
+            // Cyrillic
+            ("Ёлка", "елка"),
+            ("КИНО", "кино"),
+            ("Земфира", "земфира"),
+            // CJK
+            ("坂本龍一", "坂本龍一"),
+            ("カタカナ", "かたかな"),
+            ("ｶﾀｶﾅ", "かたかな"),
+            ("ガ", "が"),
+            ("か", "か"),
+            ("", ""),
+        ] {
+            assert_eq!(fold_text(text), folded, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn hangul_and_kana_keep_their_syllables() {
+        assert_eq!(fold_text("이소라"), "이소라");
+        assert_eq!(fold_text("ｶﾞｯｺｳ"), "がっこう");
+        assert!(search_text("이소라 7집").contains(&search_text("소라")));
+        assert!(!search_text("이소라").contains(&search_text("솔")));
+        assert!(collate("가을", "나비", false).is_lt());
+    }
+
+    #[test]
+    fn search_text_drops_punctuation() {
+        assert_eq!(search_text("Don't Stop"), "dont stop");
+        assert_eq!(search_text("AC/DC"), "acdc");
+        assert_eq!(search_text("Guns N' Roses"), "guns n roses");
+        assert_eq!(search_text("«Любэ»"), "любэ");
+        assert_eq!(search_text("東京事変「教育」"), "東京事変教育");
+        assert_eq!(
+            search_tokens("  Édith   PIAF! "),
+            vec!["edith".to_string(), "piaf".to_string()]
+        );
+        assert!(search_tokens("?!").is_empty());
+        assert_eq!(match_key("  Don't   Stop (Live) "), "dont stop live");
+        assert_eq!(match_key("BJÖRK"), match_key("bjork"));
+    }
+
+    #[test]
+    fn queries_find_accented_names() {
+        for (name, query) in [
+            ("Édith Piaf", "edith"),
+            ("Björk", "bjork"),
+            ("Björk", "BJÖRK"),
+            ("Sigur Rós", "sigur ros"),
+            ("Ёлка", "елка"),
+            ("ДДТ", "ддт"),
+            ("カタカナ", "かた"),
+            ("坂本龍一", "龍一"),
+        ] {
+            let query = search_text(query);
+            assert!(search_text(name).contains(&query), "{name:?} / {query:?}");
+        }
+    }
+
+    #[test]
+    fn sorts_accented_names_with_their_letter() {
+        let mut names = vec![
+            "Zaz",
+            "Édith Piaf",
+            "Björk",
+            "ABBA",
+            "Eagles",
+            "björk",
+            "Blur",
+            "Земфира",
+            "Ёлка",
+            "Агата Кристи",
+            "坂本龍一",
+            "Μάνος Χατζιδάκις",
+            "10cc",
+        ];
+        names.sort_by(|left, right| collate(left, right, false));
+        assert_eq!(
+            names,
+            vec![
+                "10cc",
+                "ABBA",
+                "Björk",
+                "björk",
+                "Blur",
+                "Eagles",
+                "Édith Piaf",
+                "Zaz",
+                "Μάνος Χατζιδάκις",
+                "Агата Кристи",
+                "Ёлка",
+                "Земфира",
+                "坂本龍一",
+            ]
+        );
+    }
+
+    #[test]
+    fn leading_article_is_skipped_only_when_asked() {
+        assert_eq!(sort_key("The Beatles", true), "beatles");
+        assert_eq!(sort_key("The Beatles", false), "the beatles");
+        assert_eq!(sort_key("Theatre of Tragedy", true), "theatre of tragedy");
+        assert_eq!(sort_key("The The", true), "the");
+        assert_eq!(sort_key("  THE   Cure ", true), "cure");
+
+        let mut names = vec!["The Cure", "Blur", "The Beatles", "Coldplay"];
+        names.sort_by(|left, right| collate(left, right, true));
+        assert_eq!(names, vec!["The Beatles", "Blur", "Coldplay", "The Cure"]);
+        names.sort_by(|left, right| collate(left, right, false));
+        assert_eq!(names, vec!["Blur", "Coldplay", "The Beatles", "The Cure"]);
+    }
+
+    #[test]
+    fn sort_by_name_matches_collate() {
+        let mut names = vec![
+            "the Cure",
+            "Émilie Simon",
+            "Ёлка",
+            "emilie",
+            "The cure",
+            "Blur",
+        ];
+        let mut expected = names.clone();
+        expected.sort_by(|left, right| collate(left, right, true));
+        sort_by_name(&mut names, true, |name| name);
+        assert_eq!(names, expected);
+    }
+}
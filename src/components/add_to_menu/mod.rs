@@ -1,9 +1,11 @@
 //! Add-to-menu overlay and queue/playlist insertion workflows.
 
 use crate::api::*;
-use crate::components::audio_manager::normalize_manual_queue_songs;
+use crate::collation::search_text;
+use crate::components::audio_manager::{normalize_manual_queue_songs, play_song_from_beginning};
 use crate::components::{
-    AppView, Icon, Navigation, PlaybackPositionSignal, PreviewPlaybackSignal, SeekRequestSignal,
+    sized_cover_art_url, AppView, ArtworkKind, CachedImage, Icon, Navigation,
+    PlaybackPositionSignal, PreviewPlaybackSignal, SeekRequestSignal, ServerCapabilitiesSignal,
 };
 use dioxus::prelude::*;
 use std::collections::HashSet;
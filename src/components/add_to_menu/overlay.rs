@@ -1,4 +1,24 @@
 // Main add menu component split into setup, actions, and rendering chunks.
+
+/// Adds `items` after the current song when `mode` is "next", otherwise at the end.
+pub(crate) fn enqueue_items(
+    mut queue: Signal<Vec<Song>>,
+    queue_index: Signal<usize>,
+    items: Vec<Song>,
+    mode: &str,
+) {
+    let items = normalize_manual_queue_songs(items);
+    queue.with_mut(|q| match mode {
+        "next" => {
+            let insert_at = queue_index.peek().saturating_add(1).min(q.len());
+            for (idx, song) in items.into_iter().enumerate() {
+                q.insert(insert_at + idx, song);
+            }
+        }
+        _ => q.extend(items),
+    });
+}
+
 #[component]
 pub fn AddToMenuOverlay(controller: AddMenuController) -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
@@ -9,6 +29,7 @@ pub fn AddToMenuOverlay(controller: AddMenuController) -> Element {
     let playback_position = use_context::<PlaybackPositionSignal>().0;
     let seek_request = use_context::<SeekRequestSignal>().0;
     let preview_playback = use_context::<PreviewPlaybackSignal>().0;
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
 
     let show_playlist_picker = use_signal(|| false);
     let mut playlist_filter = use_signal(String::new);
@@ -179,12 +200,22 @@ pub fn AddToMenuOverlay(controller: AddMenuController) -> Element {
                         let Some(server) = server else { return None };
                         let client = NavidromeClient::new(server);
                         if let Some(ca) = cover_art {
-                            return Some(client.get_cover_art_url(&ca, 200));
+                            return Some(sized_cover_art_url(
+                                &client,
+                                &ca,
+                                ArtworkKind::Album,
+                                48,
+                            ));
                         }
                         if let Ok((_, songs)) = client.get_album(&album_id).await {
                             if let Some(song) = songs.first() {
                                 if let Some(cover) = &song.cover_art {
-                                    return Some(client.get_cover_art_url(cover, 180));
+                                    return Some(sized_cover_art_url(
+                                        &client,
+                                        cover,
+                                        ArtworkKind::Song,
+                                        48,
+                                    ));
                                 }
                             }
                         }
@@ -199,12 +230,22 @@ pub fn AddToMenuOverlay(controller: AddMenuController) -> Element {
                         let Some(server) = server else { return None };
                         let client = NavidromeClient::new(server);
                         if let Some(ca) = cover_art {
-                            return Some(client.get_cover_art_url(&ca, 200));
+                            return Some(sized_cover_art_url(
+                                &client,
+                                &ca,
+                                ArtworkKind::Playlist,
+                                48,
+                            ));
                         }
                         if let Ok((_, songs)) = client.get_playlist(&playlist_id).await {
                             if let Some(song) = songs.first() {
                                 if let Some(cover) = &song.cover_art {
-                                    return Some(client.get_cover_art_url(cover, 180));
+                                    return Some(sized_cover_art_url(
+                                        &client,
+                                        cover,
+                                        ArtworkKind::Song,
+                                        48,
+                                    ));
                                 }
                             }
                         }
@@ -310,19 +351,24 @@ pub fn AddToMenuOverlay(controller: AddMenuController) -> Element {
         }
     };
 
-    let enqueue_items =
-        |mut queue: Signal<Vec<Song>>, queue_index: Signal<usize>, items: Vec<Song>, mode: &str| {
-            let items = normalize_manual_queue_songs(items);
-            queue.with_mut(|q| match mode {
-                "next" => {
-                    let insert_at = queue_index().saturating_add(1).min(q.len());
-                    for (idx, song) in items.into_iter().enumerate() {
-                        q.insert(insert_at + idx, song);
-                    }
-                }
-                _ => q.extend(items),
-            });
-        };
+    let on_play_from_beginning = {
+        let mut controller = controller.clone();
+        let intent = intent_for_display.clone();
+        move |_: MouseEvent| {
+            if let AddTarget::Song(song) = &intent.target {
+                play_song_from_beginning(
+                    song.clone(),
+                    now_playing,
+                    queue,
+                    queue_index,
+                    is_playing,
+                    playback_position,
+                    seek_request,
+                );
+                controller.close();
+            }
+        }
+    };
 
     let (
         make_add_to_queue,
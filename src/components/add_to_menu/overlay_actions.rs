@@ -104,6 +104,11 @@
                     message.set(Some((false, "No active server found.".to_string())));
                     return;
                 };
+                if !capabilities.user_can(&active.id, UserAction::EditPlaylist) {
+                    let hint = capabilities.denied_hint(UserAction::EditPlaylist).to_string();
+                    message.set(Some((false, hint)));
+                    return;
+                }
 
                 let target = intent.target.clone();
                 let playlist_id_for_fetch = playlist_id.clone();
@@ -214,6 +219,11 @@
                 message.set(Some((false, "No active server found.".to_string())));
                 return;
             };
+            if !capabilities.user_can(&active.id, UserAction::CreatePlaylist) {
+                let hint = capabilities.denied_hint(UserAction::CreatePlaylist).to_string();
+                message.set(Some((false, hint)));
+                return;
+            }
 
             let target = intent.target.clone();
             is_processing.set(true);
@@ -268,7 +278,7 @@
                             // Hint to reload playlist list next time
                             playlists.restart();
                         }
-                        Err(err) => message.set(Some((false, err))),
+                        Err(err) => message.set(Some((false, err.to_string()))),
                     },
                 }
                 is_processing.set(false);
@@ -442,6 +452,7 @@
                             client
                                 .add_songs_to_playlist(&playlist_id, &[song_to_add.id.clone()])
                                 .await
+                                .map_err(String::from)
                         }
                         None => Err("Playlist server is not available.".to_string()),
                     },
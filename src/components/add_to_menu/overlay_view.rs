@@ -9,20 +9,19 @@
             && available
                 .iter()
                 .any(|playlist| playlist.name.trim().eq_ignore_ascii_case(trimmed_filter.as_str()));
-        let filter = trimmed_filter.to_lowercase();
+        let filter = search_text(&trimmed_filter);
         let mut filtered: Vec<Playlist> = if filter.is_empty() {
             available
         } else {
             available
                 .into_iter()
-                .filter(|p| p.name.to_lowercase().contains(&filter))
+                .filter(|p| search_text(&p.name).contains(&filter))
                 .collect()
         };
         let total_filtered = filtered.len();
         let limit = 40usize;
         let limited: Vec<Playlist> = filtered.drain(..).take(limit).collect();
         let truncated = total_filtered > limited.len();
-        let servers_list = servers();
         let can_create_from_search = playlist_guard.is_none()
             && !loading
             && !trimmed_filter.is_empty()
@@ -72,22 +71,14 @@
                             button {
                                 class: "w-full px-3 py-2 rounded-xl bg-zinc-900/50 border border-zinc-800 hover:border-emerald-500/60 hover:text-white text-left text-sm text-zinc-300 transition-colors flex items-center gap-3",
                                 onclick: make_add_to_playlist(playlist.id.clone()),
-                                if let Some(url) = playlist
-                                    .cover_art
-                                    .as_ref()
-                                    .and_then(|cover| {
-                                        servers_list
-                                            .iter()
-                                            .find(|s| s.id == playlist.server_id)
-                                            .map(|srv| {
-                                                NavidromeClient::new(srv.clone()).get_cover_art_url(cover, 80)
-                                            })
-                                    })
-                                {
-                                    img {
-                                        class: "w-10 h-10 rounded-md object-cover border border-zinc-800/80",
-                                        src: "{url}",
-                                        alt: "Playlist art",
+                                if let Some(cover_art) = playlist.cover_art.clone() {
+                                    CachedImage {
+                                        server_id: playlist.server_id.clone(),
+                                        cover_art,
+                                        alt: "Playlist art".to_string(),
+                                        class: "w-10 h-10 rounded-md object-cover border border-zinc-800/80".to_string(),
+                                        kind: ArtworkKind::Playlist,
+                                        render_size: 40,
                                     }
                                 } else {
                                     div { class: "w-10 h-10 rounded-md bg-zinc-800/70 border border-zinc-800/80 flex items-center justify-center",
@@ -148,11 +139,13 @@
                         }
                     }
                     div { class: "flex items-center gap-2",
-                        button {
-                            class: if show_playlist_picker() { "px-3 py-1.5 rounded-lg bg-emerald-500 text-white text-sm font-medium" } else { "px-3 py-1.5 rounded-lg bg-zinc-800 text-zinc-200 hover:bg-zinc-700 transition-colors text-sm font-medium" },
-                            disabled: is_processing(),
-                            onclick: on_open_playlist_picker,
-                            "Add to playlist"
+                        if !capabilities.is_guest_session() {
+                            button {
+                                class: if show_playlist_picker() { "px-3 py-1.5 rounded-lg bg-emerald-500 text-white text-sm font-medium" } else { "px-3 py-1.5 rounded-lg bg-zinc-800 text-zinc-200 hover:bg-zinc-700 transition-colors text-sm font-medium" },
+                                disabled: is_processing(),
+                                onclick: on_open_playlist_picker,
+                                "Add to playlist"
+                            }
                         }
                         button {
                             class: "p-2 rounded-lg text-zinc-400 hover:text-white hover:bg-zinc-800 transition-colors",
@@ -211,6 +204,16 @@
                             }
                         }
                         if matches!(intent_for_display.target, AddTarget::Song(_)) {
+                            button {
+                                class: "w-full flex items-center justify-between px-4 py-3 rounded-xl bg-zinc-800 text-white hover:bg-zinc-700 transition-colors",
+                                onclick: on_play_from_beginning,
+                                disabled: is_processing(),
+                                span { "Play from beginning" }
+                                Icon {
+                                    name: "play".to_string(),
+                                    class: "w-5 h-5".to_string(),
+                                }
+                            }
                             button {
                                 class: "w-full flex items-center justify-between px-4 py-3 rounded-xl bg-zinc-800 text-white hover:bg-zinc-700 transition-colors",
                                 onclick: on_create_similar,
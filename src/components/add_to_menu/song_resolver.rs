@@ -1,6 +1,6 @@
 // Server fetch helpers for resolving target songs and suggestion seeds.
 
-async fn resolve_target_songs(
+pub(crate) async fn resolve_target_songs(
     servers: &[ServerConfig],
     target: &AddTarget,
 ) -> Result<Vec<Song>, String> {
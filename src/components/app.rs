@@ -1,23 +1,31 @@
 use crate::api::*;
 use crate::cache_service::{
-    apply_settings as apply_cache_settings, get_json as cache_get_json, put_json as cache_put_json,
-    remove_by_prefix as cache_remove_prefix,
+    get_json as cache_get_json, put_json as cache_put_json, remove_by_prefix as cache_remove_prefix,
 };
+use crate::collation::collate;
 use crate::components::views::home_layout::HomeFeedLoadProfile;
 use crate::components::{
-    ios_audio_log_snapshot, ios_diag_log, view_instance_key, view_label, AddIntent,
-    AddMenuController, AddToMenuOverlay, AppView, AudioController, AudioState, HomeRefreshSignal,
-    Icon, IsPlayingSignal, Navigation, PlaybackPositionSignal, Player, PreviewPlaybackSignal,
-    SeekRequestSignal, ShuffleEnabledSignal, Sidebar, SidebarOpenSignal, SongDetailsController,
-    SongDetailsOverlay, SongDetailsState, VolumeSignal,
+    hidden_from_guests, ios_audio_log_snapshot, ios_diag_log, normalize_volume,
+    restore_playback_queue, schedule_playback_state_save, use_quiet_hours_clock, view_instance_key,
+    view_label, AddIntent, AddMenuController, AddToMenuOverlay, AppView, AudioController,
+    AudioState, CrashReportBanner, FavoritesShuffleController, FavoritesShuffleNotice,
+    GuestSessionBanner, GuestSessionController, HomeRefreshSignal, Icon, IsPlayingSignal,
+    JobRegistry, ListenAlongController, Navigation, PendingSyncBanner, PendingSyncController,
+    PlaybackPositionSignal, Player, PreviewPlaybackSignal, QueueDragController,
+    QuietHoursController, RadioRawTitleSignal, ReauthPrompt, SeekRequestSignal,
+    ServerCapabilitiesSignal, SessionSummaryBanner, SettingsController, ShuffleEnabledSignal,
+    Sidebar, SidebarOpenSignal, SleepTimer, SleepTimerSignal, SongDetailsController,
+    SongDetailsOverlay, SongDetailsState, StopAfter, StopAfterSignal, UnavailableSongsSignal,
+    ViewStates, VolumeSignal, AUTO_DOWNLOAD_JOB,
 };
 use crate::db::{
-    initialize_database, load_playback_state, load_servers, load_settings, save_playback_state,
-    save_servers, save_settings, save_temporary_queue_snapshot, AppSettings, PlaybackState,
-    QueueItem, TemporaryQueueSnapshot,
+    initialize_database, load_playback_state, load_servers, load_settings,
+    save_container_resume_point, save_servers, save_settings, save_temporary_queue_snapshot,
+    AppSettings, ContainerResumePoint, PlaybackState, ResumePointDebounce, TemporaryQueueSnapshot,
 };
 use crate::diagnostics::{log_perf, PerfTimer};
-use crate::offline_audio::{prune_temporary_queue_prefetch_downloads, run_auto_download_pass};
+use crate::network_policy::configure as configure_network_policy;
+use crate::offline_audio::prune_temporary_queue_prefetch_downloads;
 use chrono::{DateTime, NaiveDateTime, Utc};
 #[cfg(target_arch = "wasm32")]
 use dioxus::core::{Runtime, RuntimeGuard};
@@ -34,6 +42,7 @@ use web_sys::window;
 pub use crate::db::RepeatMode;
 use dioxus::prelude::*;
 use dioxus_router::use_navigator;
+use std::collections::{HashMap, HashSet};
 
 #[cfg(target_arch = "wasm32")]
 const HISTORY_SWIPE_THRESHOLD: f64 = 100.0;
@@ -47,6 +56,8 @@ const HOME_INIT_SECTION_LOAD_STEP_STANDARD: usize = 6;
 const HOME_INIT_SECTION_FETCH_LIMIT_STANDARD: usize = 180;
 const HOME_INIT_WARMUP_FLAG_CACHE_HOURS: u32 = 24 * 365;
 const AUTO_DOWNLOAD_POLL_INTERVAL_MS: u64 = 5 * 60 * 1000;
+/// How often mutations queued while a server was unreachable are retried.
+const PENDING_SYNC_RETRY_INTERVAL_MS: u64 = 60 * 1000;
 
 #[derive(Debug, Clone, Copy)]
 struct HomeInitFetchBudget {
@@ -182,18 +193,6 @@ fn LoadingProgressBar(progress: f32, stage: String) -> Element {
     }
 }
 
-fn normalize_volume(mut value: f64) -> f64 {
-    if !value.is_finite() {
-        return 0.8;
-    }
-    let mut passes = 0;
-    while value > 1.0 && passes < 4 {
-        value /= 100.0;
-        passes += 1;
-    }
-    value.clamp(0.0, 1.0)
-}
-
 fn home_init_warmup_cache_key(
     active_servers: &[ServerConfig],
     profile: HomeFeedLoadProfile,
@@ -486,7 +485,7 @@ fn compare_home_init_optional_u32_desc(
 }
 
 fn compare_home_init_song_title(left: &Song, right: &Song) -> std::cmp::Ordering {
-    left.title.to_lowercase().cmp(&right.title.to_lowercase())
+    collate(&left.title, &right.title, false)
 }
 
 fn sort_home_init_songs(songs: &mut [Song], sort: NativeSongSortField) {
@@ -551,13 +550,13 @@ async fn fetch_home_init_albums_for_servers(
     for server in active_servers.iter().cloned() {
         let client = NavidromeClient::new(server);
         let mut fetched = client
-            .get_albums(album_type, limit, 0)
+            .get_albums(album_type, limit, 0, None)
             .await
             .unwrap_or_default();
         if fetched.is_empty() {
             home_init_fetch_yield().await;
             fetched = client
-                .get_albums(album_type, limit, 0)
+                .get_albums(album_type, limit, 0, None)
                 .await
                 .unwrap_or_default();
         }
@@ -605,6 +604,9 @@ async fn fetch_home_init_sorted_songs_for_servers(
     let mut songs = Vec::<Song>::new();
     for server in active_servers.iter().cloned() {
         let client = NavidromeClient::new(server);
+        if client.capabilities().is_some_and(|c| !c.native_api) {
+            continue;
+        }
         let mut fetched = client
             .get_native_songs(sort, NativeSortOrder::Desc, 0, per_server_song_target)
             .await
@@ -656,6 +658,19 @@ async fn auto_download_poll_sleep() {
     gloo_timers::future::TimeoutFuture::new(AUTO_DOWNLOAD_POLL_INTERVAL_MS as u32).await;
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+async fn pending_sync_retry_sleep() {
+    tokio::time::sleep(std::time::Duration::from_millis(
+        PENDING_SYNC_RETRY_INTERVAL_MS,
+    ))
+    .await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn pending_sync_retry_sleep() {
+    gloo_timers::future::TimeoutFuture::new(PENDING_SYNC_RETRY_INTERVAL_MS as u32).await;
+}
+
 #[derive(Debug, Default, Clone, Copy)]
 struct HomeInitSummary {
     recent_albums: usize,
@@ -917,9 +932,11 @@ pub fn AppShell() -> Element {
     let mut queue_index = use_signal(|| 0usize);
     let is_playing = use_signal(|| false);
     let mut volume = use_signal(|| 0.8f64);
-    let mut app_settings = use_signal(AppSettings::default);
+    let app_settings = use_signal(AppSettings::default);
     let mut playback_position = use_signal(|| 0.0f64);
-    let mut last_playback_save = use_signal(|| None::<(String, String, u64, usize, usize)>);
+    let mut last_playback_save = use_signal(|| None::<(String, String, u64)>);
+    let playback_save_generation = use_signal(|| 0u64);
+    let mut playback_restored = use_signal(|| false);
     let mut last_queue_snapshot_signature = use_signal(String::new);
     let mut db_initialized = use_signal(|| false);
     let mut servers_loaded = use_signal(|| false);
@@ -928,6 +945,10 @@ pub fn AppShell() -> Element {
     let mut repeat_mode = use_signal(|| RepeatMode::Off);
     let mut auto_download_bootstrap_done = use_signal(|| false);
     let mut auto_download_poll_generation = use_signal(|| 0u64);
+    let mut pending_sync_generation = use_signal(|| 0u64);
+    let mut server_capabilities = use_signal(HashMap::<String, ServerCapabilities>::new);
+    let guest_session = use_signal(|| false);
+    let unavailable_songs = use_signal(HashSet::<String>::new);
     let mut home_init_in_progress = use_signal(|| false);
     let home_init_status = use_signal(|| None::<String>);
     let home_init_progress = use_signal(|| 0.0f32);
@@ -941,6 +962,8 @@ pub fn AppShell() -> Element {
     let mut ios_loading_log_poll_generation = use_signal(|| 0u64);
     let audio_state = use_signal(AudioState::default);
     let preview_playback = use_signal(|| false);
+    let mut stop_after = use_signal(|| None::<StopAfter>);
+    let sleep_timer = use_signal(|| None::<SleepTimer>);
     let sidebar_open = use_signal(|| false);
     use_effect({
         let current_view = current_view.clone();
@@ -951,10 +974,12 @@ pub fn AppShell() -> Element {
         }
     });
 
+    let view_states = use_hook(ViewStates::default);
     let navigation = Navigation::new(
         router_navigator,
         current_view_signal,
         pending_navigation_target,
+        view_states,
     );
     use_effect({
         let navigation = navigation;
@@ -975,6 +1000,7 @@ pub fn AppShell() -> Element {
         }
     });
     let seek_request = use_signal(|| None::<(String, f64)>);
+    let radio_raw_title = use_signal(|| None::<(String, String)>);
     let mut resume_bookmark_loaded = use_signal(|| false);
     #[cfg(target_arch = "wasm32")]
     let swipe_start = use_signal(|| None::<(f64, f64, i8)>);
@@ -997,9 +1023,21 @@ pub fn AppShell() -> Element {
 
     // Provide state via context
     use_context_provider(|| servers);
+    let pending_sync = use_hook(|| PendingSyncController::new(servers));
+    use_context_provider(|| pending_sync);
+    let capabilities = ServerCapabilitiesSignal {
+        capabilities: server_capabilities,
+        guest_session,
+    };
+    use_context_provider(|| capabilities);
+    use_context_provider(|| UnavailableSongsSignal(unavailable_songs));
     use_context_provider(|| current_view);
     use_context_provider(|| navigation.clone());
     use_context_provider(|| add_menu.clone());
+    let queue_drag = use_hook(|| QueueDragController::new(servers, queue, queue_index));
+    use_context_provider(|| queue_drag);
+    let job_registry = use_hook(JobRegistry::default);
+    use_context_provider(|| job_registry);
     use_context_provider(|| song_details.clone());
     use_context_provider(|| home_feed.clone());
     use_context_provider(|| HomeRefreshSignal(home_manual_refresh_generation));
@@ -1083,7 +1121,10 @@ pub fn AppShell() -> Element {
     if (resetRetries) current.retries = 0;
     if (current.queued || current.loading) return;
     current.queued = true;
-    queue.push(img);
+    // On-screen images go ahead of background prefetches.
+    const firstPrefetch = prefetch ? -1 : queue.findIndex((queued) => getState(queued).prefetch);
+    if (firstPrefetch < 0) queue.push(img);
+    else queue.splice(firstPrefetch, 0, img);
     pump();
   }
 
@@ -1299,8 +1340,106 @@ pub fn AppShell() -> Element {
     use_context_provider(|| IsPlayingSignal(is_playing));
     use_context_provider(|| VolumeSignal(volume));
     use_context_provider(|| app_settings);
+    let settings_controller = use_hook(|| SettingsController::new(app_settings, volume));
+    use_context_provider(|| settings_controller);
     use_context_provider(|| PlaybackPositionSignal(playback_position));
 
+    // Keep the machine awake while playing (desktop only); the worker debounces toggles.
+    #[cfg(feature = "desktop")]
+    {
+        use_effect(move || {
+            crate::sleep_inhibit::update_sleep_inhibit(
+                app_settings().sleep_inhibit_mode,
+                is_playing(),
+            );
+        });
+        use_drop(crate::sleep_inhibit::release_sleep_inhibit);
+    }
+
+    // Keep what a quit should save current, and save it when the window closes (desktop only).
+    #[cfg(feature = "desktop")]
+    {
+        use_effect(move || {
+            let song = now_playing();
+            let settings = app_settings();
+            let snapshot = song
+                .filter(|_| db_initialized() && !preview_playback())
+                .map(|song| crate::quit_flush::QuitSnapshot {
+                    state: PlaybackState::capture(
+                        &queue(),
+                        queue_index(),
+                        Some(&song),
+                        *playback_position.peek(),
+                    ),
+                    server: servers().iter().find(|s| s.id == song.server_id).cloned(),
+                    bookmark_auto_save: settings.bookmark_auto_save,
+                    bookmark_limit: settings.bookmark_limit as usize,
+                });
+            crate::quit_flush::set_quit_snapshot(snapshot);
+        });
+        dioxus::desktop::use_wry_event_handler(|event, _| {
+            use dioxus::desktop::tao::event::{Event, WindowEvent};
+            if let Event::WindowEvent {
+                event: WindowEvent::CloseRequested,
+                ..
+            } = event
+            {
+                crate::quit_flush::flush_on_quit("window close");
+            }
+        });
+    }
+
+    // Refresh the rotating database backup on a clean shutdown.
+    #[cfg(not(target_arch = "wasm32"))]
+    use_drop(crate::db::backup_database_on_shutdown);
+
+    // Mirror playback in the taskbar/dock badge and taskbar progress (desktop only). The
+    // window title follows track changes from the audio controller; this only catches
+    // setting changes.
+    #[cfg(feature = "desktop")]
+    {
+        let has_song = use_memo(move || now_playing.read().is_some());
+        use_effect(move || {
+            crate::taskbar_status::update_window_title(
+                app_settings().playback_indicator_mode,
+                now_playing.peek().as_ref(),
+            );
+        });
+        use_effect(move || {
+            crate::taskbar_status::update_playing_badge(
+                app_settings().playback_indicator_mode,
+                has_song() && is_playing(),
+            );
+        });
+        use_effect(move || {
+            let duration = (audio_state().duration)();
+            let fraction = (has_song() && duration > 0.0).then(|| playback_position() / duration);
+            crate::taskbar_status::update_taskbar_progress(
+                app_settings().playback_indicator_mode,
+                fraction,
+                is_playing(),
+            );
+        });
+    }
+
+    // Keep the outgoing request allowlist in sync with the privacy setting and server list.
+    use_effect(move || {
+        configure_network_policy(app_settings().strict_privacy_mode, &servers());
+    });
+
+    // Saved list views show what the previous servers held, so they go when the servers do.
+    let active_server_ids = use_memo(move || {
+        servers()
+            .iter()
+            .filter(|server| server.active)
+            .map(|server| server.id.clone())
+            .collect::<Vec<_>>()
+    });
+    use_effect(move || {
+        let _ = active_server_ids();
+        view_states.clear();
+    });
+
     // Inject user-defined custom CSS into the document whenever it changes
     use_effect(move || {
         let raw = app_settings().custom_css.clone();
@@ -1320,8 +1459,79 @@ pub fn AppShell() -> Element {
     use_context_provider(|| SidebarOpenSignal(sidebar_open));
     use_context_provider(|| PreviewPlaybackSignal(preview_playback));
     use_context_provider(|| ShuffleEnabledSignal(shuffle_enabled));
+    use_context_provider(|| StopAfterSignal(stop_after));
+    use_context_provider(|| SleepTimerSignal(sleep_timer));
+
+    // Skipping or jumping past the armed boundary disarms "stop after".
+    use_effect(move || {
+        let current = now_playing();
+        let leaves_boundary = stop_after
+            .peek()
+            .as_ref()
+            .is_some_and(|armed| current.as_ref().is_none_or(|song| !armed.covers(song)));
+        if leaves_boundary {
+            stop_after.set(None);
+        }
+    });
+
+    // An A-B loop belongs to the song it was marked in.
+    let now_playing_key = use_memo(move || now_playing().map(|song| (song.server_id, song.id)));
+    use_effect(move || {
+        let _ = now_playing_key();
+        let state = audio_state.peek().clone();
+        let (mut loop_start, mut loop_end) = (state.loop_start, state.loop_end);
+        if loop_start.peek().is_some() {
+            loop_start.set(None);
+        }
+        if loop_end.peek().is_some() {
+            loop_end.set(None);
+        }
+    });
+    use_context_provider(|| RadioRawTitleSignal(radio_raw_title));
     use_context_provider(|| repeat_mode);
     use_context_provider(|| audio_state);
+    let favorites_shuffle = use_hook(|| {
+        FavoritesShuffleController::new(
+            servers,
+            app_settings,
+            queue,
+            queue_index,
+            now_playing,
+            IsPlayingSignal(is_playing),
+            home_feed.recently_played_songs,
+        )
+    });
+    use_context_provider(|| favorites_shuffle);
+    let quiet_hours = use_hook(|| QuietHoursController::new(app_settings));
+    use_quiet_hours_clock(quiet_hours);
+    use_context_provider(|| quiet_hours);
+    let guest_session_controller = use_hook(|| {
+        GuestSessionController::new(
+            capabilities,
+            settings_controller,
+            queue,
+            queue_index,
+            now_playing,
+            is_playing,
+            shuffle_enabled,
+            repeat_mode,
+        )
+    });
+    use_context_provider(|| guest_session_controller);
+    let listen_along = use_hook(|| {
+        ListenAlongController::new(
+            servers,
+            queue,
+            queue_index,
+            now_playing,
+            is_playing,
+            playback_position,
+            seek_request,
+        )
+    });
+    #[cfg(not(target_arch = "wasm32"))]
+    crate::components::use_listen_along_host(listen_along);
+    use_context_provider(|| listen_along);
 
     // Initialize database and load saved state on mount
     use_effect(move || {
@@ -1339,9 +1549,10 @@ pub fn AppShell() -> Element {
                 settings_loaded.set(true);
                 startup_bootstrap_progress.set(1.0);
                 startup_bootstrap_status.set("Startup ready".to_string());
-                apply_cache_settings(&app_settings());
+                settings_controller.apply_loaded(app_settings.peek().clone());
                 return;
             }
+            guest_session_controller.resume().await;
             db_initialized.set(true);
 
             // Load servers
@@ -1355,26 +1566,37 @@ pub fn AppShell() -> Element {
             // Load settings
             startup_bootstrap_progress.set(0.72);
             startup_bootstrap_status.set("Loading app settings".to_string());
-            if let Ok(mut settings) = load_settings().await {
+            if let Ok(settings) = load_settings().await {
                 let original_volume = settings.volume;
-                settings.volume = normalize_volume(settings.volume);
-                apply_cache_settings(&settings);
-                volume.set(settings.volume);
+                configure_network_policy(settings.strict_privacy_mode, &servers.peek());
                 shuffle_enabled.set(settings.shuffle_enabled);
                 repeat_mode.set(settings.repeat_mode);
-                let normalized_settings = settings.clone();
-                app_settings.set(settings);
+                settings_controller.apply_loaded(settings);
+                let normalized_settings = app_settings.peek().clone();
                 if (normalized_settings.volume - original_volume).abs() > f64::EPSILON {
-                    let _ = save_settings(normalized_settings.clone()).await;
+                    let _ = save_settings(normalized_settings).await;
                 }
             } else {
-                apply_cache_settings(&app_settings());
+                settings_controller.apply_loaded(app_settings.peek().clone());
             }
             settings_loaded.set(true);
             startup_bootstrap_progress.set(1.0);
             startup_bootstrap_status.set("Startup ready".to_string());
 
-            // Load playback state (but don't auto-play)
+            // First run: open server setup. Installs that had servers before onboarding
+            // existed skip it.
+            if !app_settings.peek().onboarding_completed {
+                if servers.peek().is_empty() {
+                    navigation.navigate_to(AppView::SettingsView {});
+                } else {
+                    settings_controller.update_settings(|settings| {
+                        settings.onboarding_completed = true;
+                    });
+                }
+            }
+
+            // Restore the saved queue, paused where it was unless the bookmark setting resumes
+            // playback on launch.
             if let Ok(state) = load_playback_state().await {
                 ios_diag_log(
                     "app.playback.restore",
@@ -1386,11 +1608,33 @@ pub fn AppShell() -> Element {
                         state.song_id
                     ),
                 );
-                queue_index.set(state.queue_index);
-                playback_position.set(state.position);
-                // Note: We don't restore the full queue/song here since we'd need to re-fetch song details
-                // That would require knowing which server each song came from
+                let servers_snapshot = servers.peek().clone();
+                let restored = restore_playback_queue(&state, &servers_snapshot).await;
+                if let Some(restored) = restored.filter(|_| now_playing.peek().is_none()) {
+                    let song = restored.songs[restored.queue_index].clone();
+                    let position = restored.position.unwrap_or(0.0);
+                    ios_diag_log(
+                        "app.playback.restore",
+                        &format!(
+                            "restored queue_idx={} queue_len={} song_id={} position={position:.3}",
+                            restored.queue_index,
+                            restored.songs.len(),
+                            song.id
+                        ),
+                    );
+                    let mut queue = queue;
+                    let mut now_playing = now_playing;
+                    let mut is_playing = is_playing;
+                    let mut seek_request = seek_request;
+                    queue.set(restored.songs);
+                    queue_index.set(restored.queue_index);
+                    playback_position.set(position);
+                    seek_request.set(Some((song.id.clone(), position)));
+                    now_playing.set(Some(song));
+                    is_playing.set(app_settings.peek().bookmark_autoplay_on_launch);
+                }
             }
+            playback_restored.set(true);
         });
     });
 
@@ -1711,7 +1955,9 @@ pub fn AppShell() -> Element {
 
         auto_download_bootstrap_done.set(true);
         spawn(async move {
-            let _ = run_auto_download_pass(&active_servers, &settings_snapshot).await;
+            let _ = job_registry
+                .run_auto_download(active_servers, settings_snapshot)
+                .await;
         });
     });
 
@@ -1746,7 +1992,12 @@ pub fn AppShell() -> Element {
                     break;
                 }
 
-                let _ = run_auto_download_pass(&active_servers, &settings_snapshot).await;
+                // A pass started from Settings or Downloads covers this one.
+                if job_registry.running(AUTO_DOWNLOAD_JOB).is_none() {
+                    let _ = job_registry
+                        .run_auto_download(active_servers.clone(), settings_snapshot.clone())
+                        .await;
+                }
 
                 if *auto_download_poll_generation.peek() != generation {
                     break;
@@ -1755,12 +2006,61 @@ pub fn AppShell() -> Element {
         });
     });
 
+    // Replay offline stars, ratings and playlist additions once online, then keep retrying
+    // for servers that are still unreachable.
+    let offline_mode = use_memo(move || app_settings().offline_mode);
+    use_effect(move || {
+        pending_sync_generation.with_mut(|generation| *generation = generation.saturating_add(1));
+        let generation = *pending_sync_generation.peek();
+
+        if !db_initialized() || !settings_loaded() || !servers_loaded() || offline_mode() {
+            return;
+        }
+
+        spawn(async move {
+            loop {
+                pending_sync.replay();
+                pending_sync_retry_sleep().await;
+                if *pending_sync_generation.peek() != generation {
+                    break;
+                }
+            }
+        });
+    });
+
+    // Probe each active server's optional endpoints once connected, so views can hide
+    // features a server lacks instead of failing when they're used.
+    use_effect(move || {
+        if !db_initialized() || !servers_loaded() || offline_mode() {
+            return;
+        }
+
+        let unprobed: Vec<ServerConfig> = servers()
+            .into_iter()
+            .filter(|server| {
+                server.active
+                    && !server_needs_reauth(&server.id)
+                    && !server_capabilities.peek().contains_key(&server.id)
+            })
+            .collect();
+        for server in unprobed {
+            spawn(async move {
+                let server_id = server.id.clone();
+                if let Ok(capabilities) = NavidromeClient::new(server).probe_capabilities().await {
+                    server_capabilities.with_mut(|probed| {
+                        probed.insert(server_id, capabilities);
+                    });
+                }
+            });
+        }
+    });
+
     // Resume from the most recent bookmark on startup.
     use_effect(move || {
         if resume_bookmark_loaded() {
             return;
         }
-        if !settings_loaded() {
+        if !settings_loaded() || !playback_restored() {
             return;
         }
         if now_playing().is_some() {
@@ -1873,26 +2173,18 @@ pub fn AppShell() -> Element {
 
     // Auto-save settings when volume, shuffle, or repeat changes
     use_effect(move || {
-        let vol = volume();
-        let vol = normalize_volume(vol);
+        let vol = normalize_volume(volume());
         let shuffle = shuffle_enabled();
         let repeat = repeat_mode();
-        let mut settings = app_settings();
 
         if db_initialized() {
-            let changed = (settings.volume - vol).abs() > 0.01
-                || settings.shuffle_enabled != shuffle
-                || settings.repeat_mode != repeat;
-
-            if changed {
-                settings.volume = vol;
+            settings_controller.update_settings(|settings| {
+                if (settings.volume - vol).abs() > 0.01 {
+                    settings.volume = vol;
+                }
                 settings.shuffle_enabled = shuffle;
                 settings.repeat_mode = repeat;
-                app_settings.set(settings.clone());
-                spawn(async move {
-                    let _ = save_settings(settings).await;
-                });
-            }
+            });
         }
     });
 
@@ -1905,27 +2197,41 @@ pub fn AppShell() -> Element {
         }
     });
 
+    // Save the queue whenever it changes, once edits settle. Shuffle and repeat are kept with
+    // the settings.
+    use_effect(move || {
+        let q = queue();
+        let idx = queue_index();
+        let song = now_playing();
+        if !db_initialized() || !playback_restored() || preview_playback() {
+            return;
+        }
+        // A queue that didn't come back (servers unreachable at launch) stays saved until
+        // something plays this session.
+        if q.is_empty() && last_playback_save.peek().is_none() {
+            return;
+        }
+        let pos = *playback_position.peek();
+        let state = PlaybackState::capture(&q, idx, song.as_ref(), pos);
+        schedule_playback_state_save(playback_save_generation, state);
+    });
+
     // Auto-save playback position periodically
     use_effect(move || {
         let song = now_playing();
         let pos = playback_position();
-        let q = queue();
-        let idx = queue_index();
         let previewing = preview_playback();
 
-        if db_initialized() && song.is_some() && !previewing {
+        if db_initialized() && playback_restored() && song.is_some() && !previewing {
             let current = song.as_ref().expect("checked is_some");
             let song_id = current.id.clone();
             let server_id = current.server_id.clone();
             let position_ms = (pos.max(0.0) * 1000.0).round() as u64;
-            let queue_len = q.len();
 
             let should_save = match last_playback_save() {
-                Some((prev_song_id, prev_server_id, prev_pos_ms, prev_idx, prev_queue_len)) => {
+                Some((prev_song_id, prev_server_id, prev_pos_ms)) => {
                     prev_song_id != song_id
                         || prev_server_id != server_id
-                        || prev_idx != idx
-                        || prev_queue_len != queue_len
                         || position_ms.abs_diff(prev_pos_ms) >= 1500
                 }
                 None => true,
@@ -1935,23 +2241,35 @@ pub fn AppShell() -> Element {
                 return;
             }
 
-            last_playback_save.set(Some((song_id, server_id, position_ms, idx, queue_len)));
+            last_playback_save.set(Some((song_id, server_id, position_ms)));
 
-            let state = PlaybackState {
-                song_id: song.as_ref().map(|s| s.id.clone()),
-                server_id: song.as_ref().map(|s| s.server_id.clone()),
-                position: pos,
-                queue: q
-                    .iter()
-                    .map(|s| QueueItem {
-                        song_id: s.id.clone(),
-                        server_id: s.server_id.clone(),
-                    })
-                    .collect(),
-                queue_index: idx,
-            };
+            let state =
+                PlaybackState::capture(&queue.peek(), *queue_index.peek(), song.as_ref(), pos);
+            schedule_playback_state_save(playback_save_generation, state);
+        }
+    });
+
+    // Remember where playback is within albums and playlists so long works can be resumed.
+    let mut resume_point_debounce = use_signal(ResumePointDebounce::default);
+    use_effect(move || {
+        let song = now_playing();
+        let pos = playback_position();
+        let playing = is_playing();
+        if !db_initialized() || preview_playback() {
+            return;
+        }
+        let Some(song) = song else {
+            return;
+        };
+        let now_ms = Utc::now().timestamp_millis().max(0) as u64;
+        let Some(point) = ContainerResumePoint::for_song(&song, pos, now_ms) else {
+            return;
+        };
+        let due = resume_point_debounce
+            .with_mut(|debounce| debounce.should_save(&point, f64::from(song.duration), playing));
+        if due {
             spawn(async move {
-                let _ = save_playback_state(state).await;
+                let _ = save_container_resume_point(point).await;
             });
         }
     });
@@ -2010,6 +2328,7 @@ pub fn AppShell() -> Element {
     let show_ios_loading_logs = cfg!(all(not(target_arch = "wasm32"), target_os = "ios"));
     let ios_loading_logs_preview = ios_loading_log_lines();
     let offline_mode_enabled = app_settings().offline_mode;
+    let view_hidden_from_guest = capabilities.is_guest_session() && hidden_from_guests(&view);
     let transport_loading_state = audio_state();
     let is_transport_loading = (transport_loading_state.is_transport_loading)();
     let transport_loading_label = (transport_loading_state.transport_loading_label)()
@@ -2101,33 +2420,34 @@ pub fn AppShell() -> Element {
                                     }
                                     button {
                                         class: "px-3 py-2 rounded-lg border border-amber-400/60 text-amber-100 hover:text-white hover:border-amber-300 transition-colors text-sm",
-                                        onclick: {
-                                            let mut app_settings = app_settings.clone();
-                                            move |_| {
-                                                let mut settings = app_settings();
-                                                if !settings.offline_mode {
-                                                    return;
-                                                }
+                                        onclick: move |_| {
+                                            settings_controller.update_settings(|settings| {
                                                 settings.offline_mode = false;
-                                                apply_cache_settings(&settings);
-                                                let settings_clone = settings.clone();
-                                                app_settings.set(settings);
-                                                spawn(async move {
-                                                    let _ = save_settings(settings_clone).await;
-                                                });
-                                            }
+                                            });
                                         },
                                         "Disable Offline Mode"
                                     }
                                 }
                             }
-                            div {
-                                key: "{outlet_key}",
-                                Outlet::<AppView> {}
+                            GuestSessionBanner {}
+                            CrashReportBanner {}
+                            SessionSummaryBanner {}
+                            PendingSyncBanner {}
+                            ReauthPrompt {}
+                            if view_hidden_from_guest {
+                                div { class: "py-16 text-center text-sm text-zinc-400",
+                                    "{GUEST_DENIED_HINT}"
+                                }
+                            } else {
+                                div {
+                                    key: "{outlet_key}",
+                                    Outlet::<AppView> {}
+                                }
                             }
                         }
                     }
                     Player {}
+                    FavoritesShuffleNotice {}
                 }
             }
 
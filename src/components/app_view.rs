@@ -75,6 +75,11 @@ pub fn view_label(view: &AppView) -> &'static str {
     }
 }
 
+/// Views a guest session may not open: Settings, which also manages servers, and Downloads.
+pub fn hidden_from_guests(view: &AppView) -> bool {
+    matches!(view, AppView::SettingsView {} | AppView::DownloadsView {})
+}
+
 pub fn view_instance_key(view: &AppView) -> String {
     match view {
         AppView::HomeView {} => "home".to_string(),
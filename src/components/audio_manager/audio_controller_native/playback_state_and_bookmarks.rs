@@ -1,4 +1,4 @@
-// Native controller: apply play/pause/repeat/volume and pause-time bookmark persistence.
+// Native controller: apply play/pause/repeat/volume/speed and pause-time bookmark persistence.
 {
     let last_native_transport_play_state = use_signal(|| None::<bool>);
 
@@ -36,17 +36,62 @@
         });
     }
 
-    // Handle volume changes.
+    // Tell the backend whether losing an audio output should pause playback.
     {
-        let volume = volume.clone();
+        let app_settings = app_settings.clone();
+        let pause_on_unplug = use_memo(move || app_settings().pause_on_unplug);
         use_effect(move || {
             native_audio_command(serde_json::json!({
-                "type": "volume",
-                "value": volume().clamp(0.0, 1.0),
+                "type": "pause_on_unplug",
+                "enabled": pause_on_unplug(),
             }));
         });
     }
 
+    // Hold background downloads while the playing track streams from the server.
+    {
+        let app_settings = app_settings.clone();
+        let now_playing = now_playing.clone();
+        let is_playing = is_playing.clone();
+        let hold_downloads = use_memo(move || {
+            app_settings().pause_downloads_while_streaming
+                && is_playing()
+                && now_playing().as_ref().is_some_and(|song| {
+                    song.server_name == "Radio" || cached_audio_url(song).is_none()
+                })
+        });
+        use_effect(move || set_downloads_held_for_streaming(hold_downloads()));
+    }
+
+    // Handle volume and quiet-hours cap changes.
+    {
+        let applied = use_signal(|| None::<f64>);
+        let ramp_generation = use_signal(|| 0u64);
+        use_effect(move || {
+            apply_output_volume(output_volume(), applied, ramp_generation, |value| {
+                native_audio_command(serde_json::json!({
+                    "type": "volume",
+                    "value": value,
+                }));
+            });
+        });
+    }
+
+    // Handle playback speed changes; radio always plays at 1x.
+    {
+        let app_settings = app_settings.clone();
+        let now_playing = now_playing.clone();
+        let rate = use_memo(move || playback_rate_for(&app_settings(), now_playing().as_ref()));
+        use_effect(move || {
+            let rate = rate();
+            native_audio_command(serde_json::json!({
+                "type": "rate",
+                "value": rate,
+            }));
+            publish_playback_rate(audio_state, rate);
+        });
+    }
+
     // Persist a bookmark when playback pauses.
     {
         let servers = servers.clone();
@@ -71,41 +116,56 @@
                 return;
             }
 
-            let position_ms = (playback_position.peek().max(0.0) * 1000.0).round() as u64;
-            if position_ms <= 1500 {
-                return;
-            }
             if !app_settings.peek().bookmark_auto_save {
                 return;
             }
 
-            let should_save = match last_bookmark.peek().clone() {
-                Some((id, pos)) => id != song.id || position_ms.abs_diff(pos) >= 2000,
-                None => true,
-            };
-
-            if should_save {
-                let servers_snapshot = servers.peek().clone();
-                let bookmark_limit = app_settings.peek().bookmark_limit.clamp(1, 5000) as usize;
-                let song_id = song.id.clone();
-                let server_id = song.server_id.clone();
-                spawn(async move {
-                    last_bookmark.set(Some((song_id.clone(), position_ms)));
-                    if let Some(server) =
-                        servers_snapshot.iter().find(|s| s.id == server_id).cloned()
+            let servers_snapshot = servers.peek().clone();
+            let bookmark_limit = app_settings.peek().bookmark_limit.clamp(1, 5000) as usize;
+            spawn(async move {
+                // The progress signals only move once per displayed second, so ask the player
+                // for the exact position it paused at.
+                let position = match native_audio_snapshot().await {
+                    Some(snapshot)
+                        if snapshot
+                            .song_id
+                            .as_deref()
+                            .is_none_or(|song_id| song_id == song.id) =>
                     {
-                        let client = NavidromeClient::new(server);
-                        let _ = client
-                            .create_bookmark_with_limit(
-                                &song_id,
-                                position_ms,
-                                None,
-                                Some(bookmark_limit),
-                            )
-                            .await;
+                        snapshot.current_time
                     }
-                });
-            }
+                    _ => *playback_position.peek(),
+                };
+                let position_ms = (position.max(0.0) * 1000.0).round() as u64;
+                if position_ms <= 1500 {
+                    return;
+                }
+
+                let should_save = match last_bookmark.peek().clone() {
+                    Some((id, pos)) => id != song.id || position_ms.abs_diff(pos) >= 2000,
+                    None => true,
+                };
+                if !should_save {
+                    return;
+                }
+
+                last_bookmark.set(Some((song.id.clone(), position_ms)));
+                if let Some(server) = servers_snapshot
+                    .iter()
+                    .find(|s| s.id == song.server_id)
+                    .cloned()
+                {
+                    let client = NavidromeClient::new(server);
+                    let _ = client
+                        .create_bookmark_with_limit(
+                            &song.id,
+                            position_ms,
+                            None,
+                            Some(bookmark_limit),
+                        )
+                        .await;
+                }
+            });
         });
     }
 }
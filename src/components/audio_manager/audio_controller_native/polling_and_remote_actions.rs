@@ -16,12 +16,16 @@
         let mut last_bookmark = last_bookmark.clone();
         let mut last_ended_song = last_ended_song.clone();
         let mut repeat_one_replayed_song = repeat_one_replayed_song.clone();
+        let mut repeat_count_progress = repeat_count_progress.clone();
         let preview_playback = preview_playback.clone();
+        let mut stop_after = stop_after.clone();
+        let song_details = song_details.clone();
 
         use_effect(move || {
             ensure_native_audio_bridge();
             audio_state.write().is_initialized.set(true);
             ios_diag_log("controller.poll", "started");
+            let song_details = song_details.clone();
 
             spawn(async move {
                 let mut paused_streak: u8 = 0;
@@ -30,8 +34,15 @@
                 let mut play_retry_cooldown_ticks: u8 = 0;
                 let mut last_desired_playing: bool = *is_playing.peek();
                 let mut last_heartbeat_ms: u128 = 0;
+                let mut stream_retry = StreamRetryState::default();
+                let mut url_refresh = StreamUrlRefreshState::default();
+                let mut stall_ms: u64 = 0;
+                let mut stall_last_time: f64 = 0.0;
+                let mut next_track_prepared_for: Option<String> = None;
+                let mut poll_ms = native_poll_interval_ms(&app_settings.peek(), false);
                 loop {
-                    native_delay_ms(250).await;
+                    native_delay_ms(poll_ms).await;
+                    let tick_ms = poll_ms;
 
                     let Some(snapshot) = native_audio_snapshot().await else {
                         ios_diag_log("controller.poll", "snapshot unavailable");
@@ -69,9 +80,13 @@
                                 );
                                 let servers_snapshot = servers.peek().clone();
                                 let offline_mode = app_settings.peek().offline_mode;
-                                if let Some(url) =
-                                    resolve_stream_url(&native_song, &servers_snapshot, offline_mode)
-                                {
+                                let transcode = stream_transcode(&app_settings.peek());
+                                if let Some(url) = resolve_stream_url(
+                                    &native_song,
+                                    &servers_snapshot,
+                                    offline_mode,
+                                    transcode,
+                                ) {
                                     last_src.set(Some(url));
                                 }
                                 queue_index.set(native_idx);
@@ -88,8 +103,29 @@
                         }
                     }
 
-                    playback_position.set(current_time);
-                    audio_state.write().current_time.set(current_time);
+                    // Back off while nothing is moving or nobody can see it. Startup grace,
+                    // reconnects and the last seconds of a track keep the normal rate so
+                    // tick-counted windows and track changes stay on time.
+                    let near_end = effective_duration > 0.0
+                        && effective_duration - current_time < 3.0;
+                    let idle = play_request_grace_ticks == 0
+                        && !near_end
+                        && !*audio_state.peek().is_transport_loading.peek()
+                        && !stream_retry.is_reconnecting()
+                        && (snapshot.app_hidden || (snapshot.paused && !*is_playing.peek()));
+                    poll_ms = native_poll_interval_ms(&app_settings.peek(), idle);
+
+                    // Synced lyrics in the song-details overlay need sub-second progress.
+                    let precise_progress = app_settings.peek().smooth_progress_updates
+                        || song_details.is_open_untracked();
+                    let published_position = *playback_position.peek();
+                    if should_publish_progress(published_position, current_time, precise_progress) {
+                        playback_position.set(current_time);
+                    }
+                    let published_time = *audio_state.peek().current_time.peek();
+                    if should_publish_progress(published_time, current_time, precise_progress) {
+                        audio_state.write().current_time.set(current_time);
+                    }
 
                     let selected_song_id = now_playing.peek().as_ref().map(|song| song.id.clone());
                     if *audio_state.peek().is_transport_loading.peek() {
@@ -127,6 +163,15 @@
                         last_heartbeat_ms = now_ms;
                     }
 
+                    if let Some(song) = now_playing.peek().as_ref() {
+                        release_bookmark_hold_if_restarted(song, current_time);
+                        crate::quit_flush::note_player_position(
+                            &song.id,
+                            current_time,
+                            can_save_server_bookmark(song),
+                        );
+                    }
+
                     if !snapshot.paused
                         && app_settings.peek().bookmark_auto_save
                         && !*preview_playback.peek()
@@ -172,6 +217,143 @@
                         }
                     }
 
+                    // Reload the same stream after a network error or a long mid-track stall,
+                    // resuming from the last position that was actually playing.
+                    if let Some(song) = now_playing.peek().clone() {
+                        let is_radio = song.server_name == "Radio";
+                        let advancing = (current_time - stall_last_time).abs() > 0.05;
+                        stall_last_time = current_time;
+                        if !snapshot.paused
+                            && *is_playing.peek()
+                            && !advancing
+                            && current_time > 0.35
+                        {
+                            stall_ms = stall_ms.saturating_add(tick_ms);
+                        } else {
+                            stall_ms = 0;
+                        }
+                        let network_failure = !*preview_playback.peek()
+                            && (snapshot.network_error || stall_ms >= NATIVE_STALL_RETRY_MS);
+                        // A signed link that expired while the song waited in the queue is
+                        // refused with 401/403; rebuild it once before the usual recovery.
+                        let mut regenerated = false;
+                        if network_failure || (snapshot.load_error && !*preview_playback.peek()) {
+                            let servers_snapshot = servers.peek().clone();
+                            let settings_snapshot = app_settings.peek().clone();
+                            let failed_src = last_src.peek().clone();
+                            match refresh_expired_stream_url(
+                                &mut url_refresh,
+                                &song,
+                                failed_src.as_deref(),
+                                &servers_snapshot,
+                                &settings_snapshot,
+                                current_time,
+                            )
+                            .await
+                            {
+                                StreamUrlRefresh::Unchanged => {}
+                                StreamUrlRefresh::Regenerated(src) => {
+                                    regenerated = true;
+                                    stall_ms = 0;
+                                    ios_diag_log(
+                                        "controller.retry",
+                                        &format!(
+                                            "regenerated expired stream url song_id={} position={current_time:.3}",
+                                            song.id
+                                        ),
+                                    );
+                                    last_src.set(Some(src.clone()));
+                                    native_audio_command(serde_json::json!({
+                                        "type": "load",
+                                        "reload": true,
+                                        "src": src,
+                                        "song_id": song.id,
+                                        "position": current_time,
+                                        "volume": *output_volume.peek(),
+                                        "play": *is_playing.peek(),
+                                        "preload": settings_snapshot
+                                            .buffering_strategy
+                                            .preload_attribute(),
+                                        "meta": song_metadata(&song, &servers_snapshot),
+                                    }));
+                                }
+                                StreamUrlRefresh::Refused(status) => {
+                                    set_transport_loading(audio_state.clone(), false, None);
+                                    audio_state
+                                        .write()
+                                        .playback_error
+                                        .set(Some(stream_refused_message(status)));
+                                }
+                                StreamUrlRefresh::Gone => unavailable_songs.mark(&song),
+                            }
+                        } else {
+                            url_refresh.note_healthy(&song.id, current_time);
+                        }
+                        if network_failure && !regenerated {
+                            // A track removed from the server fails like a dropped stream.
+                            mark_song_if_removed(&song, &servers.peek(), unavailable_songs);
+                            match stream_retry.on_failure(&song.id, is_radio, tick_ms) {
+                                StreamRetryAction::Wait => {}
+                                StreamRetryAction::Reload(position) => {
+                                    stall_ms = 0;
+                                    let settings_snapshot = app_settings.peek().clone();
+                                    // Under aggressive buffering the track may have finished
+                                    // downloading meanwhile; resume from the local copy.
+                                    let fetched_src = (settings_snapshot.buffering_strategy
+                                        == BufferingStrategy::Aggressive)
+                                        .then(|| cached_audio_url(&song))
+                                        .flatten();
+                                    if let Some(src) = fetched_src.clone() {
+                                        last_src.set(Some(src));
+                                    }
+                                    if let Some(src) =
+                                        fetched_src.or_else(|| last_src.peek().clone())
+                                    {
+                                        ios_diag_log(
+                                            "controller.retry",
+                                            &format!(
+                                                "reload song_id={} position={position:.3} (URL redacted)",
+                                                song.id
+                                            ),
+                                        );
+                                        let servers_snapshot = servers.peek().clone();
+                                        native_audio_command(serde_json::json!({
+                                            "type": "load",
+                                            "reload": true,
+                                            "src": src,
+                                            "song_id": song.id,
+                                            "position": position,
+                                            "volume": *output_volume.peek(),
+                                            "play": *is_playing.peek(),
+                                            "preload": settings_snapshot
+                                                .buffering_strategy
+                                                .preload_attribute(),
+                                            "meta": song_metadata(&song, &servers_snapshot),
+                                        }));
+                                    }
+                                }
+                                StreamRetryAction::GiveUp => {
+                                    let message =
+                                        "Lost connection while streaming this track.".to_string();
+                                    // Keep a more specific error, e.g. a refused stream.
+                                    let current_error =
+                                        audio_state.peek().playback_error.peek().clone();
+                                    if current_error.is_none() {
+                                        audio_state.write().playback_error.set(Some(message));
+                                    }
+                                }
+                            }
+                        } else if !network_failure {
+                            stream_retry.note_healthy(&song.id, current_time);
+                        }
+                        let reconnecting = stream_retry.is_reconnecting();
+                        if *audio_state.peek().stream_reconnecting.peek() != reconnecting {
+                            audio_state.write().stream_reconnecting.set(reconnecting);
+                        }
+                    } else if *audio_state.peek().stream_reconnecting.peek() {
+                        audio_state.write().stream_reconnecting.set(false);
+                    }
+
                     let has_selected_song = now_playing.peek().is_some();
                     let desired_playing_before_sync = *is_playing.peek();
                     if desired_playing_before_sync && !last_desired_playing {
@@ -199,7 +381,11 @@
 
                         // Debounced sync from native player to UI state:
                         // avoid immediate false flips while a track is starting.
-                        if *is_playing.peek() && paused_streak >= 3 && !snapshot.ended {
+                        if *is_playing.peek()
+                            && paused_streak >= 3
+                            && !snapshot.ended
+                            && !stream_retry.is_reconnecting()
+                        {
                             if play_request_grace_ticks > 0 {
                                 play_request_grace_ticks =
                                     play_request_grace_ticks.saturating_sub(1);
@@ -273,7 +459,114 @@
 
                     let currently_playing = *is_playing.peek();
 
-                    let ended_action = matches!(snapshot.action.as_deref(), Some("ended"));
+                    // Decide and preload the next track shortly before this one ends.
+                    let playing_song = now_playing.peek().clone();
+                    if let Some(song) = playing_song.as_ref().filter(|song| {
+                        !snapshot.paused
+                            && !*preview_playback.peek()
+                            && within_next_track_peek_window(
+                                &app_settings.peek(),
+                                song,
+                                current_time,
+                                effective_duration,
+                            )
+                    }) {
+                        if next_track_prepared_for.as_deref() != Some(song.id.as_str()) {
+                            next_track_prepared_for = Some(song.id.clone());
+                            prepare_next_track(
+                                &servers.peek(),
+                                &app_settings.peek(),
+                                &queue.peek(),
+                                *queue_index.peek(),
+                                *repeat_mode.peek(),
+                                *shuffle_enabled.peek(),
+                                song,
+                            );
+                            top_up_artist_station(
+                                &servers.peek(),
+                                &app_settings.peek(),
+                                queue,
+                                *queue_index.peek(),
+                                song,
+                            );
+                        }
+                    }
+
+                    // With gapless playback on, the next track is buffered once this one is
+                    // most of the way through.
+                    let loop_armed = ab_loop_armed(&audio_state.peek());
+                    if let Some(song) = playing_song
+                        .as_ref()
+                        .filter(|_| !snapshot.paused && !*preview_playback.peek())
+                    {
+                        let queue_snapshot = queue.peek();
+                        let stop_after_snapshot = stop_after.peek();
+                        let repeat_count_snapshot = repeat_count_progress.peek();
+                        let next = gapless_next_song(
+                            &app_settings.peek(),
+                            &TransitionContext {
+                                queue: &queue_snapshot,
+                                queue_index: *queue_index.peek(),
+                                repeat: *repeat_mode.peek(),
+                                shuffle_enabled: *shuffle_enabled.peek(),
+                                stop_after: stop_after_snapshot.as_ref(),
+                                repeat_count_progress: repeat_count_snapshot.as_ref(),
+                                position: current_time,
+                                duration: effective_duration,
+                            },
+                            song,
+                        )
+                        .filter(|_| !loop_armed);
+                        native_prepare_gapless(song, next, &servers.peek(), &app_settings.peek());
+                    }
+
+                    // An A-B loop jumps back to A on reaching B, and the track doesn't end.
+                    let loop_restart = ab_loop_seek_target(
+                        &audio_state.peek(),
+                        playing_song.as_ref(),
+                        current_time,
+                    )
+                    .filter(|_| !snapshot.paused);
+                    if let Some(start) = loop_restart {
+                        seek_to(start);
+                    }
+
+                    let reached_end_offset = !snapshot.paused
+                        && !loop_armed
+                        && now_playing.peek().as_ref().is_some_and(|song| {
+                            song_reached_end_offset(&app_settings.peek(), song, current_time)
+                        });
+                    // With crossfade on, the next track starts this far before the end.
+                    let crossfade = now_playing
+                        .peek()
+                        .as_ref()
+                        .filter(|_| {
+                            NATIVE_CROSSFADE_SUPPORTED
+                                && !loop_armed
+                                && !snapshot.paused
+                                && !snapshot.ended
+                                && !*preview_playback.peek()
+                        })
+                        .and_then(|song| {
+                            crossfade_lead(
+                                &app_settings.peek(),
+                                &TransitionContext {
+                                    queue: &queue.peek(),
+                                    queue_index: *queue_index.peek(),
+                                    repeat: *repeat_mode.peek(),
+                                    shuffle_enabled: *shuffle_enabled.peek(),
+                                    stop_after: stop_after.peek().as_ref(),
+                                    repeat_count_progress: repeat_count_progress.peek().as_ref(),
+                                    position: current_time,
+                                    duration: effective_duration,
+                                },
+                                song,
+                            )
+                        });
+                    let ended_action = loop_restart.is_none()
+                        && (matches!(snapshot.action.as_deref(), Some("ended"))
+                            || reached_end_offset
+                            || crossfade.is_some());
                     let mut suppress_ended_for_this_tick = false;
 
                     if let Some(action) = snapshot.action.as_deref() {
@@ -286,6 +579,20 @@
                                 now_playing.peek().as_ref().map(|song| song.id.as_str())
                             ),
                         );
+                        if let Some(reason) = ignored_transport_action_reason(
+                            action,
+                            &queue.peek(),
+                            *queue_index.peek(),
+                            *repeat_mode.peek(),
+                            *shuffle_enabled.peek(),
+                            now_playing.peek().as_ref(),
+                        ) {
+                            report_ignored_transport_action(audio_state, "remote", action, reason);
+                        }
+                        if crate::listen_along::is_following() {
+                            // The host drives playback; only volume stays local.
+                            continue;
+                        }
                         if now_playing.peek().is_none() {
                             ios_diag_log("controller.action", "ignored action because now_playing is None");
                             continue;
@@ -350,7 +657,7 @@
                                         now_playing.set(Some(song));
                                         is_playing.set(resume_after_skip);
                                     }
-                                } else if repeat == RepeatMode::Off
+                                } else if matches!(repeat, RepeatMode::Off | RepeatMode::Count)
                                     && queue_should_generate_similar_on_end(
                                         &queue_snapshot,
                                         now_playing.peek().as_ref(),
@@ -437,6 +744,10 @@
                             continue;
                         }
                         last_ended_song.set(current_id.clone());
+                        if crate::listen_along::is_following() {
+                            // The host picks what plays next.
+                            continue;
+                        }
 
                         let queue_snapshot = queue.peek().clone();
                         let idx = *queue_index.peek();
@@ -447,21 +758,46 @@
                         if repeat != RepeatMode::One && repeat_one_replayed_song.peek().is_some() {
                             repeat_one_replayed_song.set(None);
                         }
+                        if repeat != RepeatMode::Count && repeat_count_progress.peek().is_some() {
+                            repeat_count_progress.set(None);
+                        }
 
                         if let Some(song) = current_song.clone() {
                             if *preview_playback.peek() {
                                 continue;
                             }
                             scrobble_song(&servers_snapshot, &song, true);
+                            record_session_play(&app_settings.peek(), &song);
+                            clear_finished_container(&queue_snapshot, &song);
+                        }
+
+                        let stop_here = stop_after.peek().as_ref().is_some_and(|armed| {
+                            stop_after_reached(
+                                armed,
+                                &queue_snapshot,
+                                idx,
+                                repeat,
+                                shuffle,
+                                current_song.as_ref(),
+                            )
+                        });
+                        if stop_here {
+                            stop_after.set(None);
+                            is_playing.set(false);
+                            continue;
                         }
 
                         if repeat == RepeatMode::One {
                             if let Some(song_id) = current_id.clone() {
                                 if repeat_one_replayed_song.peek().as_ref() != Some(&song_id) {
                                     repeat_one_replayed_song.set(Some(song_id));
+                                    let restart_at = current_song
+                                        .as_ref()
+                                        .map(|song| song_start_offset(&app_settings.peek(), song))
+                                        .unwrap_or(0.0);
                                     native_audio_command(serde_json::json!({
                                         "type": "seek",
-                                        "position": 0.0
+                                        "position": restart_at
                                     }));
                                     if *is_playing.peek() {
                                         native_audio_command(serde_json::json!({
@@ -479,41 +815,64 @@
                             continue;
                         }
 
-                        let len = queue_snapshot.len();
-                        if len == 0 {
-                            is_playing.set(false);
-                            continue;
+                        if repeat == RepeatMode::Count {
+                            let replay = current_id.as_deref().and_then(|song_id| {
+                                repeat_count_replay(
+                                    repeat_count_progress.peek().as_ref(),
+                                    song_id,
+                                    app_settings.peek().repeat_count,
+                                )
+                            });
+                            let replaying = replay.is_some();
+                            repeat_count_progress.set(replay);
+                            if replaying {
+                                let restart_at = current_song
+                                    .as_ref()
+                                    .map(|song| song_start_offset(&app_settings.peek(), song))
+                                    .unwrap_or(0.0);
+                                native_audio_command(serde_json::json!({
+                                    "type": "seek",
+                                    "position": restart_at
+                                }));
+                                if *is_playing.peek() {
+                                    native_audio_command(serde_json::json!({
+                                        "type": "play"
+                                    }));
+                                }
+                                continue;
+                            }
                         }
 
-                        if idx < len.saturating_sub(1) {
-                            if let Some(song) = queue_snapshot.get(idx + 1).cloned() {
-                                queue_index.set(idx + 1);
-                                now_playing.set(Some(song));
-                                is_playing.set(true);
-                            }
-                        } else if repeat == RepeatMode::All {
-                            if let Some(song) = queue_snapshot.first().cloned() {
-                                queue_index.set(0);
-                                now_playing.set(Some(song));
-                                is_playing.set(true);
-                            }
-                        } else if queue_should_generate_similar_on_end(
+                        match next_queue_advance(
                             &queue_snapshot,
-                            current_song.as_ref(),
+                            idx,
+                            repeat,
                             shuffle,
+                            current_song.as_ref(),
                         ) {
-                            spawn_shuffle_queue(
-                                servers_snapshot,
-                                queue.clone(),
-                                queue_index.clone(),
-                                now_playing.clone(),
-                                is_playing.clone(),
-                                audio_state.clone(),
-                                current_song,
-                                Some(true),
-                            );
-                        } else {
-                            is_playing.set(false);
+                            QueueAdvance::Index(next) => {
+                                if let Some(song) = queue_snapshot.get(next).cloned() {
+                                    arm_crossfade(&song, crossfade);
+                                    queue_index.set(next);
+                                    now_playing.set(Some(song));
+                                    is_playing.set(true);
+                                }
+                            }
+                            QueueAdvance::GenerateSimilar => {
+                                spawn_shuffle_queue(
+                                    servers_snapshot,
+                                    queue.clone(),
+                                    queue_index.clone(),
+                                    now_playing.clone(),
+                                    is_playing.clone(),
+                                    audio_state.clone(),
+                                    current_song,
+                                    Some(true),
+                                );
+                            }
+                            QueueAdvance::Stop => {
+                                is_playing.set(false);
+                            }
                         }
                     } else if last_ended_song.peek().is_some() {
                         last_ended_song.set(None);
@@ -18,11 +18,27 @@
         });
     }
 
+    // Restart the repeat-count tally when another track starts.
+    {
+        let now_playing = now_playing.clone();
+        let mut repeat_count_progress = repeat_count_progress.clone();
+        use_effect(move || {
+            let song_id = now_playing().map(|song| song.id);
+            let stale = repeat_count_progress
+                .peek()
+                .as_ref()
+                .is_some_and(|(tracked, _)| song_id.as_ref() != Some(tracked));
+            if stale {
+                repeat_count_progress.set(None);
+            }
+        });
+    }
+
     // Update source + metadata and persist bookmark when songs change.
     {
         let servers = servers.clone();
         let app_settings = app_settings.clone();
-        let volume = volume.clone();
+        let output_volume = output_volume.clone();
         let queue = queue.clone();
         let mut queue_index = queue_index.clone();
         #[cfg(all(not(target_arch = "wasm32"), target_os = "ios"))]
@@ -42,6 +58,11 @@
 
         use_effect(move || {
             let song = now_playing();
+            #[cfg(feature = "desktop")]
+            crate::taskbar_status::update_window_title(
+                app_settings.peek().playback_indicator_mode,
+                song.as_ref(),
+            );
             let song_id = song.as_ref().map(|s| s.id.clone());
             let previous_song_id = last_song_id.peek().clone();
             if song_id != previous_song_id {
@@ -56,10 +77,10 @@
             }
             let previous_song = last_song_for_bookmark.peek().clone();
 
-            if let Some(prev) = previous_song {
+            if let Some(prev) = &previous_song {
                 if Some(prev.id.clone()) != song_id {
                     let position_ms = (playback_position.peek().max(0.0) * 1000.0).round() as u64;
-                    if position_ms > 1500 && can_save_server_bookmark(&prev) {
+                    if position_ms > 1500 && can_save_server_bookmark(prev) {
                         if app_settings.peek().bookmark_auto_save && !*preview_playback.peek() {
                             let servers_snapshot = servers.peek().clone();
                             let bookmark_limit =
@@ -84,6 +105,9 @@
                             });
                         }
                     }
+                    if song_id.is_some() && !*preview_playback.peek() {
+                        record_session_skip(&app_settings.peek(), prev, *playback_position.peek());
+                    }
                 }
             }
 
@@ -99,13 +123,19 @@
                     let queue_idx = queue_index();
                     let servers_snapshot = servers.peek().clone();
                     let offline_mode = app_settings.peek().offline_mode;
+                    let transcode = stream_transcode(&app_settings.peek());
                     let repeat = repeat_mode();
                     let shuffle = shuffle_enabled();
                     let plan_items = queue_snapshot
                         .iter()
                         .map(|entry| IosPlaybackPlanItem {
                             song_id: entry.id.clone(),
-                            src: resolve_stream_url(entry, &servers_snapshot, offline_mode),
+                            src: resolve_stream_url(
+                                entry,
+                                &servers_snapshot,
+                                offline_mode,
+                                transcode,
+                            ),
                             meta: song_metadata(entry, &servers_snapshot),
                         })
                         .collect::<Vec<_>>();
@@ -128,6 +158,14 @@
 
             let servers_snapshot = servers.peek().clone();
             let offline_mode = app_settings.peek().offline_mode;
+            let transcode = stream_transcode(&app_settings.peek());
+            let armed_crossfade = track_change_crossfade(
+                &app_settings.peek(),
+                previous_song.as_ref(),
+                &song,
+                *repeat_mode.peek(),
+            )
+            .filter(|_| NATIVE_CROSSFADE_SUPPORTED);
             #[cfg(all(not(target_arch = "wasm32"), target_os = "ios"))]
             {
                 let queue_snapshot = queue();
@@ -138,14 +176,21 @@
                     .iter()
                     .map(|entry| IosPlaybackPlanItem {
                         song_id: entry.id.clone(),
-                        src: resolve_stream_url(entry, &servers_snapshot, offline_mode),
+                        src: resolve_stream_url(
+                            entry,
+                            &servers_snapshot,
+                            offline_mode,
+                            transcode,
+                        ),
                         meta: song_metadata(entry, &servers_snapshot),
                     })
                     .collect::<Vec<_>>();
                 ios_update_playback_plan(plan_items, queue_idx, repeat, shuffle);
             }
 
-            if let Some(url) = resolve_stream_url(&song, &servers_snapshot, offline_mode) {
+            if let Some(url) =
+                resolve_stream_url(&song, &servers_snapshot, offline_mode, transcode)
+            {
                 let requested_seek = seek_request.peek().clone().and_then(|(song_id, position)| {
                     if song_id == song.id {
                         Some(position)
@@ -160,6 +205,10 @@
                 let metadata = song_metadata(&song, &servers_snapshot);
                 let source_kind = if url.starts_with("file://") {
                     "cached"
+                } else if app_settings.peek().fast_start_enabled {
+                    "fast-start"
+                } else if transcode.is_some() {
+                    "transcoded"
                 } else {
                     "stream"
                 };
@@ -168,7 +217,11 @@
                 } else {
                     0.0
                 };
-                let mut target_start = requested_seek.unwrap_or(0.0).max(0.0);
+                // Bookmark resumes win only when they land past the song's start offset.
+                let start_offset = song_start_offset(&app_settings.peek(), &song);
+                let mut target_start = requested_seek
+                    .filter(|position| *position > start_offset)
+                    .unwrap_or(start_offset);
                 if known_duration > 0.0 {
                     target_start = target_start.min(known_duration);
                 }
@@ -223,15 +276,26 @@
                     } else {
                         audio_state.write().duration.set(0.0);
                     }
+                    let settings_snapshot = app_settings.peek().clone();
+                    // A crossfade loads the track beside the outgoing one instead of over it.
+                    let crossfade = armed_crossfade.filter(|_| should_play_after_load);
                     native_audio_command(serde_json::json!({
-                        "type": "load",
+                        "type": if crossfade.is_some() { "crossfade" } else { "load" },
+                        "duration": crossfade.unwrap_or(0.0),
                         "src": url,
                         "song_id": song.id,
                         "position": target_start,
-                        "volume": volume.peek().clamp(0.0, 1.0),
+                        "volume": *output_volume.peek(),
                         "play": should_play_after_load,
+                        "preload": settings_snapshot.buffering_strategy.preload_attribute(),
                         "meta": metadata,
                     }));
+                    native_forget_gapless();
+                    if source_kind != "cached" && !*preview_playback.peek() {
+                        fetch_ahead_current_song(&song, &servers_snapshot, &settings_snapshot);
+                    }
+                    let next_song = queue.peek().get(*queue_index.peek() + 1).cloned();
+                    warm_now_playing_artwork(next_song.as_ref(), &servers_snapshot);
                 } else if let Some(target_pos) = requested_seek {
                     ios_diag_log(
                         "track.sync.command",
@@ -316,7 +380,9 @@
                 last_src.set(None);
                 is_playing.set(false);
                 set_transport_loading(audio_state.clone(), false, None);
-                let message = if song.server_name == "Radio" {
+                let message = if let Some(blocked) = privacy_blocked_stream_message(&song) {
+                    blocked
+                } else if song.server_name == "Radio" {
                     "No station found: this station has no stream URL.".to_string()
                 } else {
                     "Unable to load this audio source.".to_string()
@@ -326,6 +392,17 @@
         });
     }
 
+    // Let pending download batches fetch the songs about to play first.
+    {
+        let queue = queue.clone();
+        let queue_index = queue_index.clone();
+        use_effect(move || {
+            let queue_snapshot = queue();
+            let current_index = queue_index().min(queue_snapshot.len());
+            set_download_queue_boost(&queue_snapshot[current_index..]);
+        });
+    }
+
     // Prefetch current + next two songs to local audio cache for brief offline continuity.
     {
         let queue = queue.clone();
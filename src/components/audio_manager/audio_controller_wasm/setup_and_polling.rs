@@ -14,12 +14,16 @@
         let mut last_bookmark = last_bookmark.clone();
         let mut audio_state = audio_state.clone();
         let preview_playback = preview_playback.clone();
+        let mut stop_after = stop_after.clone();
+        let mut repeat_count_progress = repeat_count_progress.clone();
+        let mut last_src = last_src.clone();
 
         use_effect(move || {
             let Some(_audio) = get_or_create_audio_element() else {
                 return;
             };
             ensure_web_media_session_shortcuts();
+            ensure_web_unplug_listener();
 
             if let Some(doc) = window().and_then(|w| w.document()) {
                 let click_cb = Closure::wrap(
@@ -29,6 +33,20 @@
                     USER_INTERACTED.with(|c| c.set(true));
                     if let Some(action) = shortcut_action_from_key(&event) {
                         event.prevent_default();
+                        if let Some(reason) = ignored_transport_action_reason(
+                            action,
+                            &queue.peek(),
+                            *queue_index.peek(),
+                            *repeat_mode.peek(),
+                            *shuffle_enabled.peek(),
+                            now_playing.peek().as_ref(),
+                        ) {
+                            WEB_IGNORED_SHORTCUTS
+                                .with(|ignored| ignored.borrow_mut().push((action, reason)));
+                        }
+                        if crate::listen_along::is_following() {
+                            return;
+                        }
                         match action {
                             "next" => click_player_control_button("next-btn"),
                             "previous" => click_player_control_button("prev-btn"),
@@ -56,6 +74,7 @@
             let mut current_time_signal = audio_state.peek().current_time;
             let mut duration_signal = audio_state.peek().duration;
             let mut playback_error_signal = audio_state.peek().playback_error;
+            let mut stream_reconnecting_signal = audio_state.peek().stream_reconnecting;
             let mut playback_pos = playback_position.clone();
             let mut queue = queue.clone();
             let mut queue_index = queue_index.clone();
@@ -74,9 +93,17 @@
                 let mut repeat_one_replayed_song: Option<String> = None;
                 let mut paused_streak: u8 = 0;
                 let mut playing_streak: u8 = 0;
+                let mut stream_retry = StreamRetryState::default();
+                let mut url_refresh = StreamUrlRefreshState::default();
+                let mut next_track_prepared_for: Option<String> = None;
 
                 loop {
-                    gloo_timers::future::TimeoutFuture::new(200).await;
+                    gloo_timers::future::TimeoutFuture::new(WEB_POLL_TICK_MS as u32).await;
+
+                    let ignored = WEB_IGNORED_SHORTCUTS.with(|ignored| ignored.take());
+                    for (action, reason) in ignored {
+                        report_ignored_transport_action(audio_state, "keyboard", action, reason);
+                    }
 
                     let Some(audio) = get_or_create_audio_element() else {
                         continue;
@@ -95,6 +122,9 @@
                         duration_signal.set(dur);
                     }
                     let paused = audio.paused();
+                    if let Some(song) = now_playing.peek().as_ref() {
+                        release_bookmark_hold_if_restarted(song, time);
+                    }
 
                     if !paused
                         && app_settings.peek().bookmark_auto_save
@@ -142,7 +172,77 @@
                     }
 
                     let current_song = { now_playing.read().clone() };
-                    if current_song.is_some() {
+                    if let Some(song) = current_song.as_ref() {
+                        // Network drops mid-track reload the same stream instead of failing.
+                        let is_radio = song.server_name == "Radio";
+                        let error_code = web_playback_error_code(&audio);
+                        let network_failure = !*preview_playback.peek()
+                            && error_code == Some(WEB_MEDIA_ERR_NETWORK);
+                        // A signed link that expired while the song waited in the queue is
+                        // refused with 401/403; rebuild it once before the usual recovery.
+                        let load_failed = network_failure
+                            || (!*preview_playback.peek()
+                                && error_code == Some(WEB_MEDIA_ERR_SRC_NOT_SUPPORTED));
+                        let regenerated = if load_failed {
+                            let servers_snapshot = servers.peek().clone();
+                            let settings_snapshot = app_settings.peek().clone();
+                            let failed_src = audio.src();
+                            let refresh = refresh_expired_stream_url(
+                                &mut url_refresh,
+                                song,
+                                Some(failed_src.as_str()),
+                                &servers_snapshot,
+                                &settings_snapshot,
+                                time,
+                            )
+                            .await;
+                            if refresh == StreamUrlRefresh::Gone {
+                                unavailable_songs.mark(song);
+                            }
+                            if let StreamUrlRefresh::Regenerated(src) = refresh {
+                                eprintln!("[web.poll] regenerated expired stream url at t={time:.3}");
+                                last_src.set(Some(src.clone()));
+                                audio.set_src(&src);
+                                audio.load();
+                                if time > 0.0 {
+                                    audio.set_current_time(time);
+                                }
+                                if *is_playing.peek() {
+                                    web_try_play(&audio);
+                                }
+                                true
+                            } else {
+                                false
+                            }
+                        } else {
+                            url_refresh.note_healthy(&song.id, time);
+                            false
+                        };
+                        let retry_action = if network_failure && !regenerated {
+                            Some(stream_retry.on_failure(&song.id, is_radio, WEB_POLL_TICK_MS))
+                        } else {
+                            if !network_failure {
+                                stream_retry.note_healthy(&song.id, time);
+                            }
+                            None
+                        };
+                        if let Some(StreamRetryAction::Reload(position)) = retry_action {
+                            eprintln!("[web.poll] reloading stream after network error at t={position:.3}");
+                            audio.load();
+                            if position > 0.0 {
+                                audio.set_current_time(position);
+                            }
+                            if *is_playing.peek() {
+                                web_try_play(&audio);
+                            }
+                        }
+                        let reconnecting = stream_retry.is_reconnecting();
+                        if *stream_reconnecting_signal.peek() != reconnecting {
+                            stream_reconnecting_signal.set(reconnecting);
+                        }
+                        let suppress_error = regenerated
+                            || (network_failure && retry_action != Some(StreamRetryAction::GiveUp));
+
                         // Keep UI play/pause signals synced when playback is controlled
                         // outside app buttons (browser media controls, hardware keys, etc.).
                         if paused {
@@ -153,7 +253,11 @@
                             paused_streak = 0;
                         }
 
-                        if *is_playing.peek() && paused_streak >= 2 && !audio.ended() {
+                        if *is_playing.peek()
+                            && paused_streak >= 2
+                            && !audio.ended()
+                            && !reconnecting
+                        {
                             eprintln!(
                                 "[web.poll] forcing is_playing=false paused_streak={} time={time:.3}",
                                 paused_streak
@@ -166,11 +270,14 @@
                             );
                         }
 
-                        if let Some(message) =
-                            web_playback_error_message(&audio, current_song.as_ref())
+                        let refused_message = url_refresh.refused.map(stream_refused_message);
+                        if let Some(message) = refused_message
+                            .or_else(|| web_playback_error_message(&audio, Some(song)))
+                            .filter(|_| !suppress_error)
                         {
                             if playback_error_signal.peek().as_ref() != Some(&message) {
                                 playback_error_signal.set(Some(message));
+                                mark_song_if_removed(song, &servers.peek(), unavailable_songs);
                             }
                             set_transport_loading(audio_state.clone(), false, None);
                         } else if playback_error_signal.peek().is_some() {
@@ -194,15 +301,122 @@
                         if playback_error_signal.peek().is_some() {
                             playback_error_signal.set(None);
                         }
+                        if *stream_reconnecting_signal.peek() {
+                            stream_reconnecting_signal.set(false);
+                        }
                         set_transport_loading(audio_state.clone(), false, None);
                     }
 
-                    if audio.ended() {
+                    // An A-B loop jumps back to A on reaching B, before the track can end.
+                    let loop_restart = ab_loop_seek_target(
+                        &audio_state.peek(),
+                        current_song.as_ref(),
+                        time,
+                    )
+                    .filter(|_| !paused);
+                    if let Some(start) = loop_restart {
+                        seek_to(start);
+                        continue;
+                    }
+
+                    // Decide and preload the next track shortly before this one ends.
+                    if let Some(song) = current_song.as_ref().filter(|song| {
+                        !paused
+                            && !*preview_playback.peek()
+                            && within_next_track_peek_window(&app_settings.peek(), song, time, dur)
+                    }) {
+                        if next_track_prepared_for.as_deref() != Some(song.id.as_str()) {
+                            next_track_prepared_for = Some(song.id.clone());
+                            prepare_next_track(
+                                &servers.peek(),
+                                &app_settings.peek(),
+                                &queue.peek(),
+                                *queue_index.peek(),
+                                *repeat_mode.peek(),
+                                *shuffle_enabled.peek(),
+                                song,
+                            );
+                            top_up_artist_station(
+                                &servers.peek(),
+                                &app_settings.peek(),
+                                queue,
+                                *queue_index.peek(),
+                                song,
+                            );
+                        }
+                    }
+
+                    // With gapless playback on, the next track is buffered once this one is
+                    // most of the way through, and starts from the `ended` event.
+                    let loop_armed = ab_loop_armed(&audio_state.peek());
+                    if let Some(song) = current_song
+                        .as_ref()
+                        .filter(|_| !paused && !*preview_playback.peek())
+                    {
+                        let queue_snapshot = queue.peek();
+                        let stop_after_snapshot = stop_after.peek();
+                        let repeat_count_snapshot = repeat_count_progress.peek();
+                        let next = gapless_next_song(
+                            &app_settings.peek(),
+                            &TransitionContext {
+                                queue: &queue_snapshot,
+                                queue_index: *queue_index.peek(),
+                                repeat: *repeat_mode.peek(),
+                                shuffle_enabled: *shuffle_enabled.peek(),
+                                stop_after: stop_after_snapshot.as_ref(),
+                                repeat_count_progress: repeat_count_snapshot.as_ref(),
+                                position: time,
+                                duration: dur,
+                            },
+                            song,
+                        )
+                        .filter(|_| !loop_armed);
+                        web_prepare_gapless(song, next, &servers.peek(), &app_settings.peek());
+                    }
+                    let gapless_handoff =
+                        current_song.as_ref().is_some_and(web_gapless_handed_off);
+
+                    let reached_end_offset = !paused
+                        && !loop_armed
+                        && current_song.as_ref().is_some_and(|song| {
+                            song_reached_end_offset(&app_settings.peek(), song, time)
+                        });
+                    // With crossfade on, the next track starts this far before the end.
+                    let crossfade = current_song
+                        .as_ref()
+                        .filter(|_| {
+                            !paused && !loop_armed && !*preview_playback.peek() && !audio.ended()
+                        })
+                        .and_then(|song| {
+                            crossfade_lead(
+                                &app_settings.peek(),
+                                &TransitionContext {
+                                    queue: &queue.peek(),
+                                    queue_index: *queue_index.peek(),
+                                    repeat: *repeat_mode.peek(),
+                                    shuffle_enabled: *shuffle_enabled.peek(),
+                                    stop_after: stop_after.peek().as_ref(),
+                                    repeat_count_progress: repeat_count_progress.peek().as_ref(),
+                                    position: time,
+                                    duration: dur,
+                                },
+                                song,
+                            )
+                        });
+                    if audio.ended()
+                        || reached_end_offset
+                        || crossfade.is_some()
+                        || gapless_handoff
+                    {
                         let current_id = current_song.as_ref().map(|s| s.id.clone());
                         if ended_for_song == current_id {
                             continue;
                         }
                         ended_for_song = current_id.clone();
+                        if crate::listen_along::is_following() {
+                            // The host picks what plays next.
+                            continue;
+                        }
 
                         let queue_snapshot = { queue.read().clone() };
                         let idx = { *queue_index.read() };
@@ -213,19 +427,44 @@
                         if repeat != RepeatMode::One {
                             repeat_one_replayed_song = None;
                         }
+                        if repeat != RepeatMode::Count && repeat_count_progress.peek().is_some() {
+                            repeat_count_progress.set(None);
+                        }
 
                         if let Some(song) = current_song.clone() {
                             if *preview_playback.peek() {
                                 continue;
                             }
                             scrobble_song(&servers_snapshot, &song, true);
+                            record_session_play(&app_settings.peek(), &song);
+                            clear_finished_container(&queue_snapshot, &song);
+                        }
+
+                        let stop_here = stop_after.peek().as_ref().is_some_and(|armed| {
+                            stop_after_reached(
+                                armed,
+                                &queue_snapshot,
+                                idx,
+                                repeat,
+                                shuffle,
+                                current_song.as_ref(),
+                            )
+                        });
+                        if stop_here {
+                            stop_after.set(None);
+                            is_playing.set(false);
+                            continue;
                         }
 
                         if repeat == RepeatMode::One {
                             if let Some(song_id) = current_id.clone() {
                                 if repeat_one_replayed_song.as_ref() != Some(&song_id) {
                                     repeat_one_replayed_song = Some(song_id);
-                                    audio.set_current_time(0.0);
+                                    let restart_at = current_song
+                                        .as_ref()
+                                        .map(|song| song_start_offset(&app_settings.peek(), song))
+                                        .unwrap_or(0.0);
+                                    audio.set_current_time(restart_at);
                                     if *is_playing.read() {
                                         web_try_play(&audio);
                                     }
@@ -239,39 +478,58 @@
                             continue;
                         }
 
-                        let len = queue_snapshot.len();
-                        if len == 0 {
-                            is_playing.set(false);
-                            continue;
+                        if repeat == RepeatMode::Count {
+                            let replay = current_id.as_deref().and_then(|song_id| {
+                                repeat_count_replay(
+                                    repeat_count_progress.peek().as_ref(),
+                                    song_id,
+                                    app_settings.peek().repeat_count,
+                                )
+                            });
+                            let replaying = replay.is_some();
+                            repeat_count_progress.set(replay);
+                            if replaying {
+                                let restart_at = current_song
+                                    .as_ref()
+                                    .map(|song| song_start_offset(&app_settings.peek(), song))
+                                    .unwrap_or(0.0);
+                                audio.set_current_time(restart_at);
+                                if *is_playing.read() {
+                                    web_try_play(&audio);
+                                }
+                                continue;
+                            }
                         }
 
-                        if idx < len.saturating_sub(1) {
-                            if let Some(song) = queue_snapshot.get(idx + 1).cloned() {
-                                queue_index.set(idx + 1);
-                                now_playing.set(Some(song));
-                            }
-                        } else if repeat == RepeatMode::All {
-                            if let Some(song) = queue_snapshot.get(0).cloned() {
-                                queue_index.set(0);
-                                now_playing.set(Some(song));
-                            }
-                        } else if queue_should_generate_similar_on_end(
+                        match next_queue_advance(
                             &queue_snapshot,
-                            current_song.as_ref(),
+                            idx,
+                            repeat,
                             shuffle,
+                            current_song.as_ref(),
                         ) {
-                            spawn_shuffle_queue(
-                                servers_snapshot,
-                                queue.clone(),
-                                queue_index.clone(),
-                                now_playing.clone(),
-                                is_playing.clone(),
-                                audio_state.clone(),
-                                current_song,
-                                Some(true),
-                            );
-                        } else {
-                            is_playing.set(false);
+                            QueueAdvance::Index(next) => {
+                                if let Some(song) = queue_snapshot.get(next).cloned() {
+                                    arm_crossfade(&song, crossfade);
+                                    queue_index.set(next);
+                                    now_playing.set(Some(song));
+                                }
+                            }
+                            QueueAdvance::GenerateSimilar => {
+                                spawn_shuffle_queue(
+                                    servers_snapshot,
+                                    queue.clone(),
+                                    queue_index.clone(),
+                                    now_playing.clone(),
+                                    is_playing.clone(),
+                                    audio_state.clone(),
+                                    current_song,
+                                    Some(true),
+                                );
+                            }
+                            QueueAdvance::Stop => {
+                                is_playing.set(false);
+                            }
                         }
                     } else {
                         ended_for_song = None;
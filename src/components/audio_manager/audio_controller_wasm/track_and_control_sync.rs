@@ -1,4 +1,5 @@
-// Web controller: sync track/queue state and apply transport, volume, and bookmark effects.
+// Web controller: sync track/queue state and apply transport, volume, speed, and bookmark
+// effects.
 {
     // Keep queue_index aligned when now_playing changes and the song is in the queue.
     {
@@ -20,11 +21,29 @@
         });
     }
 
+    // Restart the repeat-count tally when another track starts.
+    {
+        let now_playing = now_playing.clone();
+        let mut repeat_count_progress = repeat_count_progress.clone();
+        use_effect(move || {
+            let song_id = now_playing().map(|song| song.id);
+            let stale = repeat_count_progress
+                .peek()
+                .as_ref()
+                .is_some_and(|(tracked, _)| song_id.as_ref() != Some(tracked));
+            if stale {
+                defer_signal_update(move || {
+                    repeat_count_progress.set(None);
+                });
+            }
+        });
+    }
+
     // Update audio source and track changes for bookmarks/scrobbles.
     {
         let servers = servers.clone();
         let app_settings = app_settings.clone();
-        let volume = volume.clone();
+        let output_volume = output_volume.clone();
         let mut now_playing = now_playing.clone();
         let mut is_playing = is_playing.clone();
         let mut playback_position = playback_position.clone();
@@ -40,7 +59,7 @@
             let song_id = song.as_ref().map(|s| s.id.clone());
             let previous_song = last_song_for_bookmark.peek().clone();
 
-            if let Some(prev) = previous_song {
+            if let Some(prev) = &previous_song {
                 if Some(prev.id.clone()) != song_id {
                     let position_ms = get_or_create_audio_element()
                         .map(|a| a.current_time())
@@ -48,7 +67,7 @@
                         .mul_add(1000.0, 0.0)
                         .round()
                         .max(0.0) as u64;
-                    if position_ms > 1500 && can_save_server_bookmark(&prev) {
+                    if position_ms > 1500 && can_save_server_bookmark(prev) {
                         if app_settings.peek().bookmark_auto_save && !*preview_playback.peek() {
                             let servers_snapshot = servers.peek().clone();
                             let bookmark_limit =
@@ -73,6 +92,10 @@
                             });
                         }
                     }
+                    if song_id.is_some() && !*preview_playback.peek() {
+                        let position_secs = position_ms as f64 / 1000.0;
+                        record_session_skip(&app_settings.peek(), prev, position_secs);
+                    }
                 }
             }
 
@@ -99,9 +122,32 @@
             };
 
             let servers_snapshot = servers.peek().clone();
-            if let Some(url) = resolve_stream_url(&song, &servers_snapshot) {
+            let armed_crossfade = track_change_crossfade(
+                &app_settings.peek(),
+                previous_song.as_ref(),
+                &song,
+                *repeat_mode.peek(),
+            );
+            // Gapless playback may already have started this song on the element it was
+            // buffered on.
+            let gapless_started = web_take_gapless_start(&song);
+            web_drop_gapless_standby(&song);
+            let transcode = stream_transcode(&app_settings.peek());
+            let preload = app_settings.peek().buffering_strategy.preload_attribute();
+            // A track fetched in full ahead of time plays from memory, but only when it starts;
+            // switching a track that is already streaming would restart it.
+            let song_changed = last_id.as_deref() != Some(song.id.as_str());
+            let fetched_ahead = web_fetched_ahead_url(&song)
+                .filter(|url| song_changed || last_src.peek().as_deref() == Some(url.as_str()));
+            if let Some(url) = fetched_ahead
+                .or_else(|| resolve_stream_url(&song, &servers_snapshot, transcode))
+            {
                 web_sync_media_session_metadata(Some(&song), &servers_snapshot);
-                if Some(url.clone()) != *last_src.peek() {
+                if gapless_started && Some(url.clone()) != *last_src.peek() {
+                    last_src.set(Some(url.clone()));
+                    audio_state.write().playback_error.set(None);
+                    set_transport_loading(audio_state.clone(), false, None);
+                } else if Some(url.clone()) != *last_src.peek() {
                     last_src.set(Some(url.clone()));
                     let loading_label = if last_id.is_some() && last_id.as_deref() != Some(song.id.as_str()) {
                         "Switching songs..."
@@ -110,26 +156,58 @@
                     };
                     set_transport_loading(audio_state.clone(), true, Some(loading_label));
                     audio_state.write().playback_error.set(None);
-                    if let Some(audio) = get_or_create_audio_element() {
+                    // The outgoing track moves to its own element to fade out; this one
+                    // loads into a fresh player element and fades in.
+                    let crossfade = armed_crossfade
+                        .filter(|_| has_user_interacted() && *is_playing.peek())
+                        .filter(|_| web_begin_crossfade());
+                    if let Some(audio) = get_or_create_audio_element()
+                        .map(|audio| web_equalizer_route(audio, &song))
+                    {
+                        let _ = audio.set_attribute("preload", preload);
                         audio.set_src(&url);
-                        audio.set_volume(volume.peek().clamp(0.0, 1.0));
+                        audio.set_volume(if crossfade.is_some() {
+                            0.0
+                        } else {
+                            *output_volume.peek()
+                        });
 
-                        if let Some((target_id, target_pos)) = seek_request.peek().clone() {
-                            if target_id == song.id {
-                                audio.set_current_time(target_pos);
-                                let mut playback_position = playback_position.clone();
-                                let mut audio_state = audio_state.clone();
-                                defer_signal_update(move || {
-                                    playback_position.set(target_pos);
-                                    audio_state.write().current_time.set(target_pos);
+                        // Bookmark resumes win only when they land past the song's start offset.
+                        let start_offset = song_start_offset(&app_settings.peek(), &song);
+                        let requested_seek = seek_request
+                            .peek()
+                            .clone()
+                            .filter(|(target_id, _)| *target_id == song.id)
+                            .map(|(_, target_pos)| target_pos);
+                        let target_pos = requested_seek
+                            .filter(|position| *position > start_offset)
+                            .unwrap_or(start_offset);
+                        if requested_seek.is_some() || target_pos > 0.0 {
+                            audio.set_current_time(target_pos);
+                            let mut playback_position = playback_position.clone();
+                            let mut audio_state = audio_state.clone();
+                            let clear_request = requested_seek.is_some();
+                            defer_signal_update(move || {
+                                playback_position.set(target_pos);
+                                audio_state.write().current_time.set(target_pos);
+                                if clear_request {
                                     seek_request.set(None);
-                                });
-                            }
+                                }
+                            });
                         }
 
                         let was_playing = *is_playing.peek();
                         if has_user_interacted() && was_playing {
                             web_try_play(&audio);
+                            if let Some(secs) = crossfade {
+                                web_run_crossfade(
+                                    secs,
+                                    song.clone(),
+                                    output_volume,
+                                    now_playing,
+                                    is_playing,
+                                );
+                            }
                         } else {
                             let _ = audio.pause();
                             is_playing.set(false);
@@ -147,7 +225,9 @@
                 last_src.set(None);
                 is_playing.set(false);
                 set_transport_loading(audio_state.clone(), false, None);
-                let message = if song.server_name == "Radio" {
+                let message = if let Some(blocked) = privacy_blocked_stream_message(&song) {
+                    blocked
+                } else if song.server_name == "Radio" {
                     let station_name = song
                         .album
                         .clone()
@@ -204,14 +284,37 @@
         });
     }
 
-    // Handle volume changes.
+    // Mirror the unplug setting for the device-change listener.
     {
-        let volume = volume.clone();
+        let app_settings = app_settings.clone();
+        let pause_on_unplug = use_memo(move || app_settings().pause_on_unplug);
         use_effect(move || {
-            let vol = volume().clamp(0.0, 1.0);
-            if let Some(audio) = get_or_create_audio_element() {
-                audio.set_volume(vol);
-            }
+            set_web_pause_on_unplug(pause_on_unplug());
+        });
+    }
+
+    // Handle volume and quiet-hours cap changes.
+    {
+        let applied = use_signal(|| None::<f64>);
+        let ramp_generation = use_signal(|| 0u64);
+        use_effect(move || {
+            apply_output_volume(output_volume(), applied, ramp_generation, |value| {
+                if let Some(audio) = get_or_create_audio_element() {
+                    audio.set_volume(value);
+                }
+            });
+        });
+    }
+
+    // Handle playback speed changes; radio always plays at 1x.
+    {
+        let app_settings = app_settings.clone();
+        let now_playing = now_playing.clone();
+        let rate = use_memo(move || playback_rate_for(&app_settings(), now_playing().as_ref()));
+        use_effect(move || {
+            let rate = rate();
+            web_set_playback_rate(rate);
+            publish_playback_rate(audio_state, rate);
         });
     }
 
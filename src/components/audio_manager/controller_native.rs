@@ -14,6 +14,21 @@ pub fn AudioController() -> Element {
     let seek_request = use_context::<SeekRequestSignal>().0;
     let audio_state = use_context::<Signal<AudioState>>();
     let preview_playback = use_context::<PreviewPlaybackSignal>().0;
+    let stop_after = use_context::<crate::components::StopAfterSignal>().0;
+    let song_details = use_context::<crate::components::SongDetailsController>();
+    let quiet_hours = use_context::<QuietHoursController>();
+    let sleep_timer = use_context::<crate::components::SleepTimerSignal>().0;
+    let unavailable_songs = use_context::<UnavailableSongsSignal>();
+    let sleep_fade = crate::components::use_sleep_timer(sleep_timer, is_playing);
+    // Only changes with the song or the ReplayGain settings.
+    let replay_gain =
+        use_memo(move || replay_gain_multiplier(&app_settings.read(), now_playing.read().as_ref()));
+    // What reaches the output: the user's volume held under the quiet-hours cap and levelled
+    // by ReplayGain, faded out as the sleep timer runs out.
+    let output_volume = use_memo(move || {
+        let levelled = effective_output_volume(volume(), quiet_hours.cap()) * replay_gain();
+        levelled.min(1.0) * sleep_fade()
+    });
 
     let last_song_id = use_signal(|| None::<String>);
     let last_src = use_signal(|| None::<String>);
@@ -21,6 +36,31 @@ pub fn AudioController() -> Element {
     let last_song_for_bookmark = use_signal(|| None::<Song>);
     let last_ended_song = use_signal(|| None::<String>);
     let repeat_one_replayed_song = use_signal(|| None::<String>);
+    let repeat_count_progress = use_signal(|| None::<(String, u32)>);
+
+    use_unavailable_song_skip(
+        unavailable_songs,
+        queue,
+        queue_index,
+        now_playing,
+        is_playing,
+        preview_playback,
+        audio_state,
+    );
+
+    // Reload the skipped-songs shuffle weights whenever the preference changes.
+    let skip_shuffle_preference = use_memo(move || {
+        let settings = app_settings();
+        (
+            settings.skipped_song_shuffle,
+            settings.skip_ratio_threshold_percent,
+            settings.skip_min_plays,
+        )
+    });
+    use_effect(move || {
+        skip_shuffle_preference();
+        refresh_skip_shuffle_weights(&app_settings.peek());
+    });
 
     include!("audio_controller_native/polling_and_remote_actions.rs");
     include!("audio_controller_native/track_and_queue_sync.rs");
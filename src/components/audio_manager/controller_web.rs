@@ -1,3 +1,131 @@
+/// Quality factor of the equalizer's middle bands, about an octave and a half wide.
+#[cfg(target_arch = "wasm32")]
+const WEB_EQ_BAND_Q: f32 = 1.0;
+
+/// The equalizer's filter chain, which every player element feeds into.
+#[cfg(target_arch = "wasm32")]
+struct WebEqualizer {
+    context: web_sys::AudioContext,
+    bands: Vec<web_sys::BiquadFilterNode>,
+}
+
+#[cfg(target_arch = "wasm32")]
+thread_local! {
+    /// Band gains to apply, or None while the equalizer is off.
+    static WEB_EQ_GAINS: std::cell::RefCell<Option<Vec<f32>>> =
+        const { std::cell::RefCell::new(None) };
+    /// Built the first time a track loads with the equalizer on. Browsers only let an audio
+    /// context start after the user has interacted with the page, and routing an element
+    /// through one can't be undone, so nothing is built while it stays off.
+    static WEB_EQUALIZER: std::cell::RefCell<Option<WebEqualizer>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+#[cfg(target_arch = "wasm32")]
+fn build_web_equalizer() -> Option<WebEqualizer> {
+    let context = web_sys::AudioContext::new().ok()?;
+    let last_band = EQ_BAND_FREQUENCIES_HZ.len() - 1;
+    let mut bands: Vec<web_sys::BiquadFilterNode> = Vec::new();
+    for (band, frequency) in EQ_BAND_FREQUENCIES_HZ.into_iter().enumerate() {
+        let filter = context.create_biquad_filter().ok()?;
+        filter.set_type(match band {
+            0 => web_sys::BiquadFilterType::Lowshelf,
+            band if band == last_band => web_sys::BiquadFilterType::Highshelf,
+            _ => web_sys::BiquadFilterType::Peaking,
+        });
+        filter.frequency().set_value(frequency);
+        filter.q().set_value(WEB_EQ_BAND_Q);
+        if let Some(previous) = bands.last() {
+            previous.connect_with_audio_node(&filter).ok()?;
+        }
+        bands.push(filter);
+    }
+    bands
+        .last()?
+        .connect_with_audio_node(&context.destination())
+        .ok()?;
+    Some(WebEqualizer { context, bands })
+}
+
+/// Sets the bands to `gains`, or flat while the equalizer is off.
+#[cfg(target_arch = "wasm32")]
+fn apply_web_equalizer_gains(equalizer: &WebEqualizer, gains: Option<&[f32]>) {
+    for (band, filter) in equalizer.bands.iter().enumerate() {
+        let gain = gains.and_then(|gains| gains.get(band)).copied();
+        filter.gain().set_value(gain.unwrap_or(0.0));
+    }
+}
+
+/// Records the equalizer's bands, or None to bypass it. Takes effect at once on elements
+/// already routed through it.
+#[cfg(target_arch = "wasm32")]
+fn web_set_equalizer(gains: Option<Vec<f32>>) {
+    WEB_EQUALIZER.with(|equalizer| {
+        if let Some(equalizer) = equalizer.borrow().as_ref() {
+            apply_web_equalizer_gains(equalizer, gains.as_deref());
+        }
+    });
+    WEB_EQ_GAINS.with(|current| *current.borrow_mut() = gains);
+}
+
+/// Returns the element `song` should load into, routed through the equalizer while it is on,
+/// building the chain if this is the first time. Routed elements load with CORS, as Web Audio
+/// only hears cross-origin streams fetched that way. Radio stations rarely allow that, and
+/// routing can't be undone, so radio and tracks played with the equalizer off get a fresh,
+/// unrouted element in place of one that was routed before.
+#[cfg(target_arch = "wasm32")]
+fn web_equalizer_route(audio: HtmlAudioElement, song: &Song) -> HtmlAudioElement {
+    let wanted = song.server_name != "Radio" && WEB_EQ_GAINS.with(|gains| gains.borrow().is_some());
+    let routed = audio.has_attribute("data-equalizer");
+    if !wanted {
+        return if routed {
+            web_replace_audio_element(audio)
+        } else {
+            audio
+        };
+    }
+    WEB_EQUALIZER.with(|cell| {
+        let mut equalizer = cell.borrow_mut();
+        if equalizer.is_none() {
+            *equalizer = build_web_equalizer();
+            if let Some(built) = equalizer.as_ref() {
+                WEB_EQ_GAINS
+                    .with(|gains| apply_web_equalizer_gains(built, gains.borrow().as_deref()));
+            }
+        }
+        let Some(equalizer) = equalizer.as_ref() else {
+            return;
+        };
+        let _ = equalizer.context.resume();
+        if routed {
+            return;
+        }
+        let Ok(source) = equalizer.context.create_media_element_source(&audio) else {
+            return;
+        };
+        if source.connect_with_audio_node(&equalizer.bands[0]).is_ok() {
+            audio.set_cross_origin(Some("anonymous"));
+            let _ = audio.set_attribute("data-equalizer", "true");
+        }
+    });
+    audio
+}
+
+/// Swaps `audio` for a new element with the same id, unloading and removing the old one.
+#[cfg(target_arch = "wasm32")]
+fn web_replace_audio_element(audio: HtmlAudioElement) -> HtmlAudioElement {
+    let id = audio.id();
+    let _ = audio.pause();
+    audio.set_id("");
+    let _ = audio.remove_attribute("src");
+    audio.load();
+    audio.remove();
+    window()
+        .and_then(|w| w.document())
+        .and_then(|document| create_audio_element(&document, &id))
+        .unwrap_or(audio)
+}
+
 #[cfg(target_arch = "wasm32")]
 #[component]
 pub fn AudioController() -> Element {
@@ -14,11 +142,60 @@ pub fn AudioController() -> Element {
     let mut seek_request = use_context::<SeekRequestSignal>().0;
     let mut audio_state = use_context::<Signal<AudioState>>();
     let preview_playback = use_context::<PreviewPlaybackSignal>().0;
+    let stop_after = use_context::<crate::components::StopAfterSignal>().0;
+    let quiet_hours = use_context::<QuietHoursController>();
+    let sleep_timer = use_context::<crate::components::SleepTimerSignal>().0;
+    let unavailable_songs = use_context::<UnavailableSongsSignal>();
+    let sleep_fade = crate::components::use_sleep_timer(sleep_timer, is_playing);
+    // Only changes with the song or the ReplayGain settings.
+    let replay_gain =
+        use_memo(move || replay_gain_multiplier(&app_settings.read(), now_playing.read().as_ref()));
+    // What reaches the output: the user's volume held under the quiet-hours cap and levelled
+    // by ReplayGain, faded out as the sleep timer runs out.
+    let output_volume = use_memo(move || {
+        let levelled = effective_output_volume(volume(), quiet_hours.cap()) * replay_gain();
+        levelled.min(1.0) * sleep_fade()
+    });
 
     let mut last_song_id = use_signal(|| None::<String>);
     let mut last_src = use_signal(|| None::<String>);
     let mut last_bookmark = use_signal(|| None::<(String, u64)>);
     let mut last_song_for_bookmark = use_signal(|| None::<Song>);
+    let repeat_count_progress = use_signal(|| None::<(String, u32)>);
+
+    use_unavailable_song_skip(
+        unavailable_songs,
+        queue,
+        queue_index,
+        now_playing,
+        is_playing,
+        preview_playback,
+        audio_state,
+    );
+
+    // Reload the skipped-songs shuffle weights whenever the preference changes.
+    let skip_shuffle_preference = use_memo(move || {
+        let settings = app_settings();
+        (
+            settings.skipped_song_shuffle,
+            settings.skip_ratio_threshold_percent,
+            settings.skip_min_plays,
+        )
+    });
+    use_effect(move || {
+        skip_shuffle_preference();
+        refresh_skip_shuffle_weights(&app_settings.peek());
+    });
+
+    // Keeps the equalizer's bands in step with Settings; its filter chain waits for a track
+    // to load.
+    let equalizer_gains = use_memo(move || {
+        let settings = app_settings();
+        settings
+            .eq_enabled
+            .then(|| normalized_band_gains(&settings.eq_band_gains))
+    });
+    use_effect(move || web_set_equalizer(equalizer_gains()));
 
     thread_local! {
         static USER_INTERACTED: Cell<bool> = Cell::new(false);
@@ -25,3 +25,6 @@ include!("queue_and_stream_helpers.rs");
 include!("controller_web.rs");
 // Public playback utility API.
 include!("playback_api.rs");
+
+#[cfg(test)]
+mod tests;
@@ -11,17 +11,44 @@ const NATIVE_AUDIO_BOOTSTRAP_JS: &str = r#"
     return true;
   }
 
-  const existing = document.getElementById("rustysound-audio-native");
-  const audio = existing || document.createElement("audio");
-  if (!existing) {
-    audio.id = "rustysound-audio-native";
-    audio.preload = "metadata";
-    audio.style.display = "none";
-    audio.setAttribute("playsinline", "true");
-    audio.setAttribute("webkit-playsinline", "true");
-    audio.setAttribute("x-webkit-airplay", "allow");
-    document.body.appendChild(audio);
+  const AUDIO_ID = "rustysound-audio-native";
+  const FADE_STEP_MS = 50;
+  // Speed every element plays at. As the default rate it survives loading another source.
+  let playbackRate = 1;
+
+  const applyRate = (element) => {
+    element.defaultPlaybackRate = playbackRate;
+    element.playbackRate = playbackRate;
+  };
+
+  const createAudio = () => {
+    const element = document.createElement("audio");
+    element.preload = "metadata";
+    applyRate(element);
+    element.style.display = "none";
+    element.setAttribute("playsinline", "true");
+    element.setAttribute("webkit-playsinline", "true");
+    element.setAttribute("x-webkit-airplay", "allow");
+    document.body.appendChild(element);
+    return element;
+  };
+
+  // The player element. A crossfade replaces it with the element the next track loaded into.
+  let audio = document.getElementById(AUDIO_ID);
+  if (!audio) {
+    audio = createAudio();
+    audio.id = AUDIO_ID;
   }
+  // Next track buffered ahead of a crossfade or gapless transition, and the fade in progress.
+  let spare = null;
+  let fade = null;
+  // Whether the spare starts by itself when the player ends, and where it starts.
+  let spareHandoff = false;
+  let spareStart = 0;
+  // Source the spare was started with on its own, until the matching "load" arrives.
+  let handedOffSrc = null;
+
+  const clampVolume = (value) => Math.max(0, Math.min(1, value));
 
   const safePlay = async () => {
     try {
@@ -30,18 +57,14 @@ const NATIVE_AUDIO_BOOTSTRAP_JS: &str = r#"
   };
 
   let isLiveStream = false;
+  // Artwork on the media session right now, and the load that may replace it.
+  let shownArtwork = null;
+  let artworkLoad = 0;
 
-  const setMetadata = (meta) => {
-    if (!meta || !("mediaSession" in navigator) || typeof MediaMetadata === "undefined") {
-      return;
-    }
-
-    isLiveStream = !!meta.is_live;
-
-    const artwork = meta.artwork
-      ? [{ src: meta.artwork, sizes: "512x512", type: "image/png" }]
+  const publishMetadata = (meta, artworkSrc) => {
+    const artwork = artworkSrc
+      ? [{ src: artworkSrc, sizes: "512x512", type: "image/png" }]
       : undefined;
-
     try {
       navigator.mediaSession.metadata = new MediaMetadata({
         title: meta.title || "",
@@ -52,6 +75,38 @@ const NATIVE_AUDIO_BOOTSTRAP_JS: &str = r#"
     } catch (_err) {}
   };
 
+  // Remote artwork is loaded before it goes out, keeping the previous image meanwhile so
+  // the lock screen never shows a blank tile; a newer track cancels a load still running.
+  const setMetadata = (meta) => {
+    if (!meta || !("mediaSession" in navigator) || typeof MediaMetadata === "undefined") {
+      return;
+    }
+
+    isLiveStream = !!meta.is_live;
+
+    const load = ++artworkLoad;
+    const next = meta.artwork || null;
+    if (!next || next === shownArtwork || next.startsWith("data:")) {
+      shownArtwork = next;
+      publishMetadata(meta, next);
+      return;
+    }
+
+    publishMetadata(meta, shownArtwork);
+    const image = new Image();
+    image.onload = () => {
+      if (load !== artworkLoad) return;
+      shownArtwork = next;
+      publishMetadata(meta, next);
+    };
+    image.onerror = () => {
+      if (load !== artworkLoad) return;
+      shownArtwork = null;
+      publishMetadata(meta, null);
+    };
+    image.src = next;
+  };
+
   const setPlaybackState = () => {
     if (!("mediaSession" in navigator)) return;
     try {
@@ -96,45 +151,189 @@ const NATIVE_AUDIO_BOOTSTRAP_JS: &str = r#"
     return false;
   };
 
+  const loadTrack = (cmd, volume) => {
+    if (typeof cmd.preload === "string" && cmd.preload) {
+      audio.preload = cmd.preload;
+    }
+    // `reload` re-fetches the same source after a dropped connection.
+    if (cmd.src && (audio.src !== cmd.src || cmd.reload === true)) {
+      audio.src = cmd.src;
+    }
+    if (typeof volume === "number") {
+      audio.volume = clampVolume(volume);
+    }
+    if (typeof cmd.position === "number" && Number.isFinite(cmd.position)) {
+      try {
+        audio.currentTime = Math.max(0, cmd.position);
+      } catch (_err) {}
+    }
+    bridge.currentSongId = cmd.song_id || null;
+    setMetadata(cmd.meta || null);
+    updatePositionState();
+    if (cmd.play === true) {
+      safePlay();
+    } else if (cmd.play === false) {
+      audio.pause();
+    }
+    setPlaybackState();
+  };
+
+  // Makes the spare the player element and drops the one it replaces.
+  const swapInSpare = () => {
+    const outgoing = audio;
+    spare.volume = outgoing.volume;
+    outgoing.removeAttribute("id");
+    outgoing.pause();
+    outgoing.removeAttribute("src");
+    outgoing.load();
+    outgoing.remove();
+    spare.id = AUDIO_ID;
+    audio = spare;
+    bridge.audio = spare;
+    spare = null;
+    spareHandoff = false;
+  };
+
+  const dropSpare = () => {
+    if (!spare) return;
+    spare.removeAttribute("src");
+    spare.load();
+    spare.remove();
+    spare = null;
+    spareHandoff = false;
+  };
+
+  // Starts the buffered next track the moment the player ends, before the app has seen the
+  // end and sent its "load".
+  const handOffToSpare = () => {
+    if (!spare || !spareHandoff || fade) return;
+    const start = spareStart;
+    swapInSpare();
+    handedOffSrc = audio.src;
+    if (start > 0) {
+      try {
+        audio.currentTime = start;
+      } catch (_err) {}
+    }
+    safePlay();
+  };
+
+  // Ends a crossfade at once: the outgoing track stops and the new one plays at full volume.
+  const finishCrossfade = () => {
+    if (!fade) return;
+    clearInterval(fade.timer);
+    const outgoing = fade.outgoing;
+    outgoing.pause();
+    outgoing.removeAttribute("src");
+    outgoing.load();
+    outgoing.remove();
+    audio.volume = fade.volume;
+    fade = null;
+  };
+
   const bridge = {
     audio,
     currentSongId: null,
     remoteActions: [],
+    pauseOnUnplug: true,
     apply(cmd) {
       if (!cmd || !cmd.type) return;
 
       switch (cmd.type) {
-        case "load":
-          if (cmd.src && audio.src !== cmd.src) {
-            audio.src = cmd.src;
+        case "load": {
+          finishCrossfade();
+          // The spare already started this track when the last one ended: keep it playing.
+          const continuing = !!cmd.src && handedOffSrc === cmd.src && audio.src === cmd.src;
+          handedOffSrc = null;
+          if (spare && cmd.src && spare.src === cmd.src) {
+            // A track buffered on the spare element for gapless playback plays from there
+            // instead of fetching its stream again.
+            swapInSpare();
+          } else if (cmd.src && cmd.src !== audio.src) {
+            // Skipped past the buffered track before it was used.
+            dropSpare();
           }
-          if (typeof cmd.volume === "number") {
-            audio.volume = Math.max(0, Math.min(1, cmd.volume));
+          loadTrack(continuing ? { ...cmd, position: undefined } : cmd, cmd.volume);
+          break;
+        }
+        case "crossfade": {
+          // The playing track fades out on its own element while the next one fades in
+          // on another, which becomes the player element.
+          const duration = Number(cmd.duration);
+          if (audio.paused || !audio.src || cmd.play !== true || !(duration > 0)) {
+            finishCrossfade();
+            loadTrack(cmd, cmd.volume);
+            break;
           }
-          if (typeof cmd.position === "number" && Number.isFinite(cmd.position)) {
-            try {
-              audio.currentTime = Math.max(0, cmd.position);
-            } catch (_err) {}
+          finishCrossfade();
+          const outgoing = audio;
+          const outgoingVolume = outgoing.volume;
+          const incoming = spare && spare.src === cmd.src ? spare : createAudio();
+          if (incoming !== spare) {
+            dropSpare();
+            watchAudio(incoming);
           }
-          bridge.currentSongId = cmd.song_id || null;
-          setMetadata(cmd.meta || null);
-          updatePositionState();
-          if (cmd.play === true) {
-            safePlay();
-          } else if (cmd.play === false) {
-            audio.pause();
+          spare = null;
+          spareHandoff = false;
+          handedOffSrc = null;
+          outgoing.removeAttribute("id");
+          incoming.id = AUDIO_ID;
+          audio = incoming;
+          bridge.audio = incoming;
+
+          const steps = Math.max(1, Math.ceil((duration * 1000) / FADE_STEP_MS));
+          let step = 0;
+          fade = {
+            outgoing,
+            volume: typeof cmd.volume === "number" ? clampVolume(cmd.volume) : outgoingVolume,
+            timer: null,
+          };
+          loadTrack(cmd, 0);
+          fade.timer = setInterval(() => {
+            step += 1;
+            const progress = Math.min(1, step / steps);
+            // Both sides follow "volume" commands, so the fade ends at the current level.
+            audio.volume = fade.volume * progress;
+            outgoing.volume = fade.volume * (1 - progress);
+            if (progress >= 1) {
+              finishCrossfade();
+            }
+          }, FADE_STEP_MS);
+          break;
+        }
+        case "preload":
+          // Buffers the next track on a spare element, so a crossfade starts without a gap.
+          // `handoff` also has the spare start on its own when the player ends.
+          if (cmd.src) {
+            if (!spare) {
+              spare = createAudio();
+              watchAudio(spare);
+            }
+            if (spare.src !== cmd.src) {
+              spare.preload = "auto";
+              spare.src = cmd.src;
+              spareHandoff = false;
+              spareStart = 0;
+            }
+            if (typeof cmd.position === "number" && Number.isFinite(cmd.position)) {
+              spareStart = Math.max(0, cmd.position);
+            }
+          }
+          if (typeof cmd.handoff === "boolean") {
+            spareHandoff = cmd.handoff && !!spare;
           }
-          setPlaybackState();
           break;
         case "play":
           safePlay();
           setPlaybackState();
           break;
         case "pause":
+          finishCrossfade();
           audio.pause();
           setPlaybackState();
           break;
         case "seek":
+          finishCrossfade();
           if (typeof cmd.position === "number" && Number.isFinite(cmd.position)) {
             try {
               audio.currentTime = Math.max(0, cmd.position);
@@ -144,16 +343,34 @@ const NATIVE_AUDIO_BOOTSTRAP_JS: &str = r#"
           break;
         case "volume":
           if (typeof cmd.value === "number") {
-            audio.volume = Math.max(0, Math.min(1, cmd.value));
+            if (fade) {
+              fade.volume = clampVolume(cmd.value);
+            } else {
+              audio.volume = clampVolume(cmd.value);
+            }
+          }
+          break;
+        case "rate":
+          if (typeof cmd.value === "number" && cmd.value > 0) {
+            playbackRate = cmd.value;
+            applyRate(audio);
+            if (spare) applyRate(spare);
+            updatePositionState();
           }
           break;
         case "loop":
           audio.loop = !!cmd.enabled;
           break;
+        case "pause_on_unplug":
+          bridge.pauseOnUnplug = cmd.enabled !== false;
+          break;
         case "metadata":
           setMetadata(cmd.meta || null);
           break;
         case "clear":
+          finishCrossfade();
+          dropSpare();
+          handedOffSrc = null;
           audio.pause();
           audio.removeAttribute("src");
           audio.load();
@@ -178,6 +395,8 @@ const NATIVE_AUDIO_BOOTSTRAP_JS: &str = r#"
         duration,
         paused: !!audio.paused,
         ended: !!audio.ended,
+        network_error: !!(audio.error && audio.error.code === 2),
+        load_error: !!(audio.error && audio.error.code === 4),
         song_id: bridge.currentSongId,
         action: bridge.remoteActions.shift() || null,
       };
@@ -280,6 +499,7 @@ const NATIVE_AUDIO_BOOTSTRAP_JS: &str = r#"
           return;
         }
         if (details && typeof details.seekTime === "number") {
+          finishCrossfade();
           try {
             audio.currentTime = Math.max(0, details.seekTime);
           } catch (_err) {}
@@ -315,22 +535,64 @@ const NATIVE_AUDIO_BOOTSTRAP_JS: &str = r#"
     } catch (_err) {}
   }
 
-  audio.addEventListener("timeupdate", updatePositionState);
-  audio.addEventListener("durationchange", updatePositionState);
-  audio.addEventListener("ratechange", updatePositionState);
-  // Keep media session state updated, but do not mirror local transport events
-  // into remoteActions: app-initiated load/play/pause commands also fire these
-  // events and can create play/pause feedback loops in the native controller.
-  audio.addEventListener("play", () => {
-    setPlaybackState();
-  });
-  audio.addEventListener("pause", () => {
-    setPlaybackState();
-  });
-  audio.addEventListener("ended", () => bridge.remoteActions.push("ended"));
+  // Events only count from the player element, not from a track fading out or buffering.
+  function watchAudio(element) {
+    const whenPlayer = (handler) => () => {
+      if (element === audio) handler();
+    };
+    element.addEventListener("timeupdate", whenPlayer(updatePositionState));
+    element.addEventListener("durationchange", whenPlayer(updatePositionState));
+    element.addEventListener("ratechange", whenPlayer(updatePositionState));
+    // Keep media session state updated, but do not mirror local transport events
+    // into remoteActions: app-initiated load/play/pause commands also fire these
+    // events and can create play/pause feedback loops in the native controller.
+    element.addEventListener("play", whenPlayer(setPlaybackState));
+    element.addEventListener("pause", whenPlayer(setPlaybackState));
+    element.addEventListener(
+      "ended",
+      whenPlayer(() => {
+        handOffToSpare();
+        bridge.remoteActions.push("ended");
+      })
+    );
+  }
+  watchAudio(audio);
   document.addEventListener("keydown", handleShortcutKeyDown, true);
 
+  // Pause when an audio output disappears (headphones unplugged, Bluetooth dropped) so
+  // playback does not jump to the speakers.
+  const devices = navigator.mediaDevices;
+  if (devices && typeof devices.enumerateDevices === "function") {
+    const countOutputs = async () => {
+      try {
+        const list = await devices.enumerateDevices();
+        return list.filter((device) => device.kind === "audiooutput").length;
+      } catch (_err) {
+        return null;
+      }
+    };
+    let outputCount = null;
+    countOutputs().then((count) => {
+      outputCount = count;
+    });
+    devices.addEventListener("devicechange", async () => {
+      const count = await countOutputs();
+      const previous = outputCount;
+      outputCount = count;
+      if (count === null || previous === null || count >= previous) return;
+      if (!bridge.pauseOnUnplug || audio.paused) return;
+      audio.pause();
+      setPlaybackState();
+      pushRemoteAction("pause");
+    });
+  }
+
   window.__rustysoundAudioBridge = bridge;
   return true;
 })();
 "#;
+
+/// Only the webview bridge has a second element to overlap tracks on; the iOS and Windows
+/// players play each track out.
+#[cfg(not(target_arch = "wasm32"))]
+const NATIVE_CROSSFADE_SUPPORTED: bool = cfg!(not(any(target_os = "ios", target_os = "windows")));
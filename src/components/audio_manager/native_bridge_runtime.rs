@@ -75,7 +75,10 @@ async fn native_audio_snapshot() -> Option<NativeAudioSnapshot> {
               duration,
               paused,
               ended,
+              network_error: !!raw.network_error,
+              load_error: !!raw.load_error,
               action,
+              app_hidden: document.hidden === true,
             };
         })();"#,
     );
@@ -117,6 +120,28 @@ async fn native_delay_ms(ms: u64) {
     tokio::time::sleep(std::time::Duration::from_millis(ms)).await;
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+const NOW_PLAYING_ARTWORK_SIZE: u32 = 512;
+
+/// Lock-screen artwork for `song`: a `data:` URL once the cover is in the art cache, the
+/// server URL otherwise (which also starts caching it). Offline misses have no artwork.
+#[cfg(not(target_arch = "wasm32"))]
+fn now_playing_artwork_url(song: &Song, servers: &[ServerConfig]) -> Option<String> {
+    let cover = song.cover_art.as_ref()?;
+    let server = servers.iter().find(|s| s.id == song.server_id)?;
+    let client = NavidromeClient::new(server.clone());
+    Some(client.get_cover_art_url(cover, NOW_PLAYING_ARTWORK_SIZE)).filter(|url| !url.is_empty())
+}
+
+/// Caches the cover of the song that plays next, so its metadata goes out with the image
+/// inline instead of a URL the lock screen still has to fetch.
+#[cfg(not(target_arch = "wasm32"))]
+fn warm_now_playing_artwork(next: Option<&Song>, servers: &[ServerConfig]) {
+    if let Some(song) = next {
+        let _ = now_playing_artwork_url(song, servers);
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 fn song_metadata(song: &Song, servers: &[ServerConfig]) -> NativeTrackMetadata {
     let is_live = song.server_name == "Radio";
@@ -154,20 +179,11 @@ fn song_metadata(song: &Song, servers: &[ServerConfig]) -> NativeTrackMetadata {
         }
     }
 
-    let artwork = servers
-        .iter()
-        .find(|s| s.id == song.server_id)
-        .and_then(|server| {
-            song.cover_art
-                .as_ref()
-                .map(|cover| NavidromeClient::new(server.clone()).get_cover_art_url(cover, 512))
-        });
-
     NativeTrackMetadata {
         title,
         artist,
         album,
-        artwork,
+        artwork: now_playing_artwork_url(song, servers),
         duration: song.duration as f64,
         is_live,
     }
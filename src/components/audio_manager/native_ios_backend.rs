@@ -59,6 +59,8 @@ struct IosAudioPlayer {
     last_time_guard_code: u8,
     last_progress_sample: Option<f64>,
     near_end_stall_ticks: u8,
+    /// Speed playback runs at. `play` starts AVPlayer at 1x, so it is set again after.
+    playback_rate: f64,
 }
 
 #[cfg(all(not(target_arch = "wasm32"), target_os = "ios"))]
@@ -114,6 +116,7 @@ impl IosAudioPlayer {
             last_time_guard_code: u8::MAX,
             last_progress_sample: None,
             near_end_stall_ticks: 0,
+            playback_rate: 1.0,
         })
     }
 
@@ -176,7 +179,7 @@ impl IosAudioPlayer {
                             msg_send![self.player, setVolume: volume.clamp(0.0, 1.0) as f32];
                         self.seek(position);
                         if should_play {
-                            let _: () = msg_send![self.player, play];
+                            self.play_at_rate();
                         } else {
                             let _: () = msg_send![self.player, pause];
                         }
@@ -202,17 +205,21 @@ impl IosAudioPlayer {
                 self.last_progress_sample = Some(position.max(0.0));
                 self.near_end_stall_ticks = 0;
                 set_ios_remote_transport_state(should_play);
-                self.update_now_playing_info_cached(if should_play { 1.0 } else { 0.0 });
+                self.update_now_playing_info_cached(if should_play {
+                    self.playback_rate
+                } else {
+                    0.0
+                });
                 self.log_player_diagnostics("after-load", Some(position), None);
             }
-            "play" => unsafe {
-                let _: () = msg_send![self.player, play];
+            "play" => {
+                self.play_at_rate();
                 self.near_end_stall_ticks = 0;
                 set_ios_remote_transport_state(true);
-                self.update_now_playing_info_cached(1.0);
+                self.update_now_playing_info_cached(self.playback_rate);
                 ios_diag_log("player.transport", "play");
                 self.log_player_diagnostics("after-play", None, None);
-            },
+            }
             "pause" => unsafe {
                 let _: () = msg_send![self.player, pause];
                 self.near_end_stall_ticks = 0;
@@ -236,7 +243,7 @@ impl IosAudioPlayer {
                 self.last_progress_sample = Some(target.max(0.0));
                 self.near_end_stall_ticks = 0;
                 let rate: f32 = unsafe { msg_send![self.player, rate] };
-                self.update_now_playing_info_cached(if rate > 0.0 { 1.0 } else { 0.0 });
+                self.update_now_playing_info_cached(rate as f64);
                 self.log_player_diagnostics("after-seek", Some(target), None);
             }
             "volume" => {
@@ -262,7 +269,7 @@ impl IosAudioPlayer {
                     .unwrap_or(self.last_known_duration)
                     .max(0.0);
                 let rate: f32 = unsafe { msg_send![self.player, rate] };
-                self.update_now_playing_info_cached(if rate > 0.0 { 1.0 } else { 0.0 });
+                self.update_now_playing_info_cached(rate as f64);
                 ios_diag_log(
                     "player.metadata",
                     &format!(
@@ -298,11 +305,40 @@ impl IosAudioPlayer {
                 ios_diag_log("player.transport", "clear");
                 self.log_player_diagnostics("after-clear", Some(0.0), Some(0.0));
             }
+            "rate" => {
+                let rate = cmd.get("value").and_then(|v| v.as_f64()).unwrap_or(1.0);
+                if !(rate.is_finite() && rate > 0.0) {
+                    return;
+                }
+                self.playback_rate = rate;
+                let playing_rate: f32 = unsafe { msg_send![self.player, rate] };
+                if playing_rate > 0.0 {
+                    unsafe {
+                        let _: () = msg_send![self.player, setRate: rate as f32];
+                    }
+                    self.update_now_playing_info_cached(rate);
+                }
+                ios_diag_log("player.transport", &format!("rate={rate:.2}"));
+            }
             "loop" => {}
+            "pause_on_unplug" => {
+                let enabled = cmd.get("enabled").and_then(|v| v.as_bool()).unwrap_or(true);
+                set_ios_pause_on_unplug(enabled);
+            }
             _ => {}
         }
     }
 
+    /// Starts playback at `playback_rate`.
+    fn play_at_rate(&self) {
+        unsafe {
+            let _: () = msg_send![self.player, play];
+            if (self.playback_rate - 1.0).abs() > f64::EPSILON {
+                let _: () = msg_send![self.player, setRate: self.playback_rate as f32];
+            }
+        }
+    }
+
     fn seek(&self, position: f64) {
         unsafe {
             let time = CMTimeMakeWithSeconds(position.max(0.0), 1000);
@@ -524,8 +560,11 @@ impl IosAudioPlayer {
             duration,
             paused,
             ended,
+            network_error: false,
+            load_error: false,
             action,
             song_id: self.current_song_id.clone(),
+            app_hidden: false,
         }
     }
 
@@ -734,7 +773,7 @@ impl IosAudioPlayer {
             &meta,
             bounded_elapsed,
             bounded_duration,
-            if paused { 0.0 } else { 1.0 },
+            if paused { 0.0 } else { self.playback_rate },
             self.now_playing_artwork,
         );
     }
@@ -18,7 +18,13 @@ static IOS_NOW_PLAYING_SESSION: Lazy<Mutex<usize>> = Lazy::new(|| Mutex::new(0))
 static IOS_LAST_REMOTE_NAV_ACTION: Lazy<Mutex<(String, u128)>> =
     Lazy::new(|| Mutex::new((String::new(), 0)));
 #[cfg(all(not(target_arch = "wasm32"), target_os = "ios"))]
+static IOS_PAUSE_ON_UNPLUG: AtomicBool = AtomicBool::new(true);
+#[cfg(all(not(target_arch = "wasm32"), target_os = "ios"))]
 const IOS_REMOTE_NAV_DEBOUNCE_MS: u128 = 220;
+/// `AVAudioSessionRouteChangeReasonOldDeviceUnavailable`: headphones or a Bluetooth
+/// output went away.
+#[cfg(all(not(target_arch = "wasm32"), target_os = "ios"))]
+const IOS_ROUTE_CHANGE_OLD_DEVICE_UNAVAILABLE: u64 = 2;
 #[cfg(all(not(target_arch = "wasm32"), target_os = "ios"))]
 const IOS_REMOTE_DIAGNOSTIC_REV: &str = "ios-remote-2026-03-14b";
 
@@ -455,6 +461,10 @@ fn remote_handler_class() -> &'static objc::runtime::Class {
             sel!(handleWillResignActive:),
             ios_handle_will_resign_active as extern "C" fn(&Object, objc::runtime::Sel, *mut Object),
         );
+        decl.add_method(
+            sel!(handleRouteChange:),
+            ios_handle_route_change as extern "C" fn(&Object, objc::runtime::Sel, *mut Object),
+        );
 
         let cls = decl.register();
         CLASS_PTR = cls;
@@ -842,6 +852,51 @@ extern "C" fn ios_handle_will_resign_active(_: &Object, _: objc::runtime::Sel, _
     );
 }
 
+#[cfg(all(not(target_arch = "wasm32"), target_os = "ios"))]
+fn set_ios_pause_on_unplug(enabled: bool) {
+    IOS_PAUSE_ON_UNPLUG.store(enabled, Ordering::Relaxed);
+}
+
+#[cfg(all(not(target_arch = "wasm32"), target_os = "ios"))]
+unsafe fn ios_route_change_reason(notification: *mut Object) -> Option<u64> {
+    if notification.is_null() {
+        return None;
+    }
+    let user_info: *mut Object = msg_send![notification, userInfo];
+    if user_info.is_null() {
+        return None;
+    }
+    let key = ns_string("AVAudioSessionRouteChangeReasonKey")?;
+    let value: *mut Object = msg_send![user_info, objectForKey: key];
+    let _: () = msg_send![key, release];
+    if value.is_null() {
+        return None;
+    }
+    let reason: u64 = msg_send![value, unsignedIntegerValue];
+    Some(reason)
+}
+
+#[cfg(all(not(target_arch = "wasm32"), target_os = "ios"))]
+extern "C" fn ios_handle_route_change(
+    _: &Object,
+    _: objc::runtime::Sel,
+    notification: *mut Object,
+) {
+    let reason = unsafe { ios_route_change_reason(notification) };
+    let pause_on_unplug = IOS_PAUSE_ON_UNPLUG.load(Ordering::Relaxed);
+    ios_diag_log(
+        "audio.route",
+        &format!("route-change reason={reason:?} pause_on_unplug={pause_on_unplug}"),
+    );
+    if reason != Some(IOS_ROUTE_CHANGE_OLD_DEVICE_UNAVAILABLE) || !pause_on_unplug {
+        return;
+    }
+    // The notification arrives off the main thread; the player mutex keeps this in step
+    // with bridge commands, and the queued action brings the UI state along.
+    let _ = with_ios_player(|player| player.apply(serde_json::json!({ "type": "pause" })));
+    push_ios_remote_action("pause");
+}
+
 #[cfg(all(not(target_arch = "wasm32"), target_os = "ios"))]
 unsafe fn register_ios_remote_targets_on_center(center: *mut Object, observer: *mut Object, label: &str) {
     if center.is_null() {
@@ -1041,9 +1096,18 @@ fn configure_ios_remote_commands(player: *mut Object) {
                 ];
                 let _: () = msg_send![name, release];
             }
+            if let Some(name) = ns_string("AVAudioSessionRouteChangeNotification") {
+                let _: () = msg_send![notification_center,
+                    addObserver: observer
+                    selector: sel!(handleRouteChange:)
+                    name: name
+                    object: ptr::null_mut::<Object>()
+                ];
+                let _: () = msg_send![name, release];
+            }
         }
 
-        ios_diag_log("remote.init", "command center + lifecycle observers configured");
+        ios_diag_log("remote.init", "command center + lifecycle + route observers configured");
     });
 }
 
@@ -7,10 +7,19 @@ struct NativeAudioSnapshot {
     duration: f64,
     paused: bool,
     ended: bool,
+    /// Backend saw a network failure loading the current source.
+    #[serde(default)]
+    network_error: bool,
+    /// Backend couldn't open the current source at all, e.g. the server refused it.
+    #[serde(default)]
+    load_error: bool,
     #[serde(default)]
     action: Option<String>,
     #[serde(default)]
     song_id: Option<String>,
+    /// The app window is hidden, so the poll loop can back off.
+    #[serde(default)]
+    app_hidden: bool,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -56,6 +65,8 @@ struct WindowsAudioPlayer {
     has_source: bool,
     current_song_id: Option<String>,
     metadata: Option<NativeTrackMetadata>,
+    /// Speed playback runs at, set again on each new source.
+    playback_rate: f64,
     _button_pressed_handler: Option<
         TypedEventHandler<
             SystemMediaTransportControls,
@@ -143,6 +154,7 @@ impl WindowsAudioPlayer {
             has_source: false,
             current_song_id: None,
             metadata: None,
+            playback_rate: 1.0,
             _button_pressed_handler: button_pressed_handler,
             _media_ended_handler: Some(media_ended_handler),
         })
@@ -229,6 +241,7 @@ impl WindowsAudioPlayer {
                 let _ = self.player.SetVolume(volume.clamp(0.0, 1.0));
 
                 if let Ok(session) = self.player.PlaybackSession() {
+                    let _ = session.SetPlaybackRate(self.playback_rate);
                     let _ = session.SetPosition(seconds_to_timespan(position));
                 }
                 if should_play {
@@ -263,6 +276,15 @@ impl WindowsAudioPlayer {
                 let volume = cmd.get("value").and_then(|v| v.as_f64()).unwrap_or(1.0);
                 let _ = self.player.SetVolume(volume.clamp(0.0, 1.0));
             }
+            "rate" => {
+                let rate = cmd.get("value").and_then(|v| v.as_f64()).unwrap_or(1.0);
+                if rate.is_finite() && rate > 0.0 {
+                    self.playback_rate = rate;
+                    if let Ok(session) = self.player.PlaybackSession() {
+                        let _ = session.SetPlaybackRate(rate);
+                    }
+                }
+            }
             "loop" => {
                 let enabled = cmd
                     .get("enabled")
@@ -305,8 +327,11 @@ impl WindowsAudioPlayer {
                 duration: 0.0,
                 paused: true,
                 ended: self.ended_flag.swap(false, Ordering::SeqCst),
+                network_error: false,
+                load_error: false,
                 action,
                 song_id: self.current_song_id.clone(),
+                app_hidden: false,
             };
         }
 
@@ -334,8 +359,11 @@ impl WindowsAudioPlayer {
             duration,
             paused,
             ended: self.ended_flag.swap(false, Ordering::SeqCst),
+            network_error: false,
+            load_error: false,
             action,
             song_id: self.current_song_id.clone(),
+            app_hidden: false,
         }
     }
 }
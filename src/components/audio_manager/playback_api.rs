@@ -2,6 +2,7 @@
 /// Seek to a specific position in the current track.
 #[cfg(target_arch = "wasm32")]
 pub fn seek_to(position: f64) {
+    web_cancel_crossfade();
     if let Some(audio) = get_or_create_audio_element() {
         audio.set_current_time(position);
     }
@@ -94,3 +95,29 @@ pub fn play_song(
     }
     is_playing.set(true);
 }
+
+/// Plays `song` from 0:00 regardless of any stored bookmark. Bookmark auto-save is held
+/// until the restart is observed so the old position isn't saved again.
+pub fn play_song_from_beginning(
+    song: Song,
+    now_playing: Signal<Option<Song>>,
+    queue: Signal<Vec<Song>>,
+    queue_index: Signal<usize>,
+    mut is_playing: Signal<bool>,
+    mut playback_position: Signal<f64>,
+    mut seek_request: Signal<Option<(String, f64)>>,
+) {
+    hold_bookmark_autosave(&song);
+    playback_position.set(0.0);
+    seek_request.set(Some((song.id.clone(), 0.0)));
+    let already_current = now_playing
+        .peek()
+        .as_ref()
+        .is_some_and(|current| current.id == song.id && current.server_id == song.server_id);
+    if already_current {
+        seek_to(0.0);
+        is_playing.set(true);
+    } else {
+        play_song(song, now_playing, queue, queue_index, is_playing);
+    }
+}
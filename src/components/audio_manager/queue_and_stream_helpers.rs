@@ -100,6 +100,121 @@ pub(crate) async fn generate_queue_extension_from_seed(
     normalize_manual_queue_songs(additions)
 }
 
+/// Songs fetched for an artist station at a time.
+pub(crate) const ARTIST_STATION_BATCH: usize = 25;
+/// Tracks kept queued after the playing one while an artist station plays.
+const ARTIST_STATION_LOOKAHEAD: usize = 5;
+
+/// The next batch of an artist station: a few of the artist's top songs mixed into songs
+/// similar to the artist, skipping everything already in `existing_queue`. Empty once the
+/// server has nothing new to offer.
+pub(crate) async fn generate_artist_station_songs(
+    servers: &[ServerConfig],
+    station: &ArtistStation,
+    existing_queue: &[Song],
+    limit: usize,
+) -> Vec<Song> {
+    let Some(server) = servers
+        .iter()
+        .find(|server| server.id == station.server_id && server.active)
+        .cloned()
+    else {
+        return Vec::new();
+    };
+    let client = NavidromeClient::new(server);
+    let lookup_count = ((limit as u32).saturating_mul(3)).clamp(30, 120);
+
+    let mut top_songs = client
+        .get_top_songs(&station.artist_name, lookup_count)
+        .await
+        .unwrap_or_default();
+    let mut similar = match client
+        .get_similar_songs2(&station.artist_id, lookup_count)
+        .await
+    {
+        Ok(songs) if !songs.is_empty() => songs,
+        _ => client
+            .get_similar_songs(&station.artist_id, lookup_count)
+            .await
+            .unwrap_or_default(),
+    };
+    shuffle_songs_in_place(&mut top_songs);
+    shuffle_songs_in_place(&mut similar);
+
+    let mut excluded: std::collections::HashSet<String> =
+        existing_queue.iter().map(queue_extension_song_key).collect();
+    let mut additions = Vec::<Song>::new();
+    // About a third from the artist's own top songs, the rest from similar artists.
+    let top_share = (limit / 3).max(1);
+    extend_unique_queue_candidates(top_songs.clone(), &mut excluded, &mut additions, top_share);
+    extend_unique_queue_candidates(similar, &mut excluded, &mut additions, limit);
+    extend_unique_queue_candidates(top_songs, &mut excluded, &mut additions, limit);
+    additions.truncate(limit);
+    shuffle_songs_in_place(&mut additions);
+    additions
+}
+
+/// Marks a station batch as part of the station queue that `anchor` belongs to, continuing
+/// its positions.
+fn continue_artist_station_queue_meta(songs: Vec<Song>, anchor: &QueueSongMeta) -> Vec<Song> {
+    songs
+        .into_iter()
+        .enumerate()
+        .map(|(offset, mut song)| {
+            song.queue_meta = Some(QueueSongMeta {
+                source_position: anchor.source_position + 1 + offset,
+                ..anchor.clone()
+            });
+            song
+        })
+        .collect()
+}
+
+/// Keeps an artist station going: when `current_song` came from one and only a few tracks
+/// are left after it, fetches the next batch and appends it. Stations removed in the
+/// meantime stop growing and play out what is already queued.
+pub(crate) fn top_up_artist_station(
+    servers: &[ServerConfig],
+    settings: &AppSettings,
+    mut queue: Signal<Vec<Song>>,
+    queue_index: usize,
+    current_song: &Song,
+) {
+    let Some(meta) = current_song
+        .queue_meta
+        .clone()
+        .filter(|meta| meta.source_kind == QueueSourceKind::ArtistStation)
+    else {
+        return;
+    };
+    let queue_snapshot = queue.peek().clone();
+    if queue_snapshot.len().saturating_sub(queue_index + 1) >= ARTIST_STATION_LOOKAHEAD {
+        return;
+    }
+    let Some(station) = settings.artist_station(&meta.source_id).cloned() else {
+        return;
+    };
+    let servers = servers.to_vec();
+    spawn(async move {
+        let songs =
+            generate_artist_station_songs(&servers, &station, &queue_snapshot, ARTIST_STATION_BATCH)
+                .await;
+        if songs.is_empty() {
+            return;
+        }
+        // The queue may have been replaced while the batch was fetched.
+        let Some(anchor) = queue.peek().iter().rev().find_map(|song| {
+            song.queue_meta
+                .clone()
+                .filter(|entry| entry.group_id == meta.group_id)
+        }) else {
+            return;
+        };
+        let songs = continue_artist_station_queue_meta(songs, &anchor);
+        queue.with_mut(|items| items.extend(songs));
+    });
+}
+
 pub(crate) fn assign_collection_queue_meta(
     songs: Vec<Song>,
     source_kind: QueueSourceKind,
@@ -294,11 +409,44 @@ fn queue_source_kind_tag(source_kind: &QueueSourceKind) -> &'static str {
         QueueSourceKind::Favorites => "favorites",
         QueueSourceKind::RandomMix => "random_mix",
         QueueSourceKind::Artist => "artist",
+        QueueSourceKind::ArtistStation => "artist_station",
     }
 }
 
+thread_local! {
+    /// Weights of the skipped-songs shuffle preference, refreshed when the settings or the
+    /// skip history change.
+    static SKIP_SHUFFLE_WEIGHTS: std::cell::RefCell<SkipShuffleWeights> =
+        std::cell::RefCell::new(SkipShuffleWeights::default());
+}
+
+/// Reloads the skipped-songs shuffle weights for `settings`.
+pub(crate) fn refresh_skip_shuffle_weights(settings: &AppSettings) {
+    let weights = SkipShuffleWeights::load(settings);
+    SKIP_SHUFFLE_WEIGHTS.with(|cell| *cell.borrow_mut() = weights);
+}
+
+/// Shuffles with the skipped-songs weights. Returns `false`, leaving `songs` untouched, when
+/// no song is affected.
+fn skip_weighted_shuffle(songs: &mut [Song]) -> bool {
+    SKIP_SHUFFLE_WEIGHTS.with(|cell| {
+        let weights = cell.borrow();
+        if weights.is_empty() {
+            return false;
+        }
+        #[cfg(target_arch = "wasm32")]
+        weights.shuffle(songs, js_sys::Math::random);
+        #[cfg(not(target_arch = "wasm32"))]
+        weights.shuffle(songs, rand::random::<f64>);
+        true
+    })
+}
+
 #[cfg(target_arch = "wasm32")]
 fn shuffle_songs_in_place(songs: &mut [Song]) {
+    if skip_weighted_shuffle(songs) {
+        return;
+    }
     let len = songs.len();
     if len <= 1 {
         return;
@@ -311,10 +459,505 @@ fn shuffle_songs_in_place(songs: &mut [Song]) {
 
 #[cfg(not(target_arch = "wasm32"))]
 fn shuffle_songs_in_place(songs: &mut [Song]) {
+    if skip_weighted_shuffle(songs) {
+        return;
+    }
     let mut rng = rand::thread_rng();
     songs.shuffle(&mut rng);
 }
 
+/// Seconds before a track ends at which its successor is decided and preloaded.
+const NEXT_TRACK_PEEK_WINDOW_SECS: f64 = 10.0;
+
+/// What the queue does when the current track finishes on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum QueueAdvance {
+    /// Move to this index of the current queue.
+    Index(usize),
+    /// Replace the queue with songs similar to the current one.
+    GenerateSimilar,
+    Stop,
+}
+
+/// Decides the end-of-track transition. Repeat-one is handled by the callers before this.
+pub(crate) fn next_queue_advance(
+    queue_snapshot: &[Song],
+    queue_index: usize,
+    repeat: RepeatMode,
+    shuffle_enabled: bool,
+    current_song: Option<&Song>,
+) -> QueueAdvance {
+    let len = queue_snapshot.len();
+    if len == 0 {
+        QueueAdvance::Stop
+    } else if queue_index < len.saturating_sub(1) {
+        QueueAdvance::Index(queue_index + 1)
+    } else if repeat == RepeatMode::All {
+        QueueAdvance::Index(0)
+    } else if queue_should_generate_similar_on_end(queue_snapshot, current_song, shuffle_enabled)
+    {
+        QueueAdvance::GenerateSimilar
+    } else {
+        QueueAdvance::Stop
+    }
+}
+
+/// Whether an armed "stop after" ends playback at this track transition. Wrapping around
+/// with repeat-all or generating a similar queue always counts as leaving the boundary.
+pub(crate) fn stop_after_reached(
+    stop_after: &StopAfter,
+    queue_snapshot: &[Song],
+    queue_index: usize,
+    repeat: RepeatMode,
+    shuffle_enabled: bool,
+    current_song: Option<&Song>,
+) -> bool {
+    if stop_after.mode == StopAfterMode::Track {
+        return true;
+    }
+    match next_queue_advance(
+        queue_snapshot,
+        queue_index,
+        repeat,
+        shuffle_enabled,
+        current_song,
+    ) {
+        QueueAdvance::Index(next) if next > queue_index => queue_snapshot
+            .get(next)
+            .map(|song| !stop_after.covers(song))
+            .unwrap_or(true),
+        _ => true,
+    }
+}
+
+/// A similar-songs queue generated ahead of time for `seed_key`, not yet committed.
+struct PeekedShuffleQueue {
+    seed_key: String,
+    songs: Vec<Song>,
+}
+
+thread_local! {
+    static PEEKED_SHUFFLE_QUEUE: std::cell::RefCell<Option<PeekedShuffleQueue>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+fn shuffle_seed_key(seed: Option<&Song>) -> String {
+    seed.map(|song| format!("{}:{}", song.server_id, song.id))
+        .unwrap_or_default()
+}
+
+fn stash_peeked_shuffle_queue(seed: Option<&Song>, songs: Vec<Song>) {
+    let seed_key = shuffle_seed_key(seed);
+    PEEKED_SHUFFLE_QUEUE.with(|cell| {
+        *cell.borrow_mut() = Some(PeekedShuffleQueue { seed_key, songs });
+    });
+}
+
+fn has_peeked_shuffle_queue(seed: Option<&Song>) -> bool {
+    let seed_key = shuffle_seed_key(seed);
+    PEEKED_SHUFFLE_QUEUE.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .is_some_and(|peeked| peeked.seed_key == seed_key)
+    })
+}
+
+/// Consumes the pre-generated queue for `seed`, if one was peeked for it.
+fn take_peeked_shuffle_queue(seed: Option<&Song>) -> Option<Vec<Song>> {
+    let seed_key = shuffle_seed_key(seed);
+    PEEKED_SHUFFLE_QUEUE.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        if slot.as_ref().is_some_and(|peeked| peeked.seed_key == seed_key) {
+            slot.take().map(|peeked| peeked.songs)
+        } else {
+            None
+        }
+    })
+}
+
+/// The song that will play after the current one, without committing the transition.
+/// Returns `None` while a similar-songs queue has not been generated yet.
+pub(crate) fn peek_next_song(
+    queue_snapshot: &[Song],
+    queue_index: usize,
+    repeat: RepeatMode,
+    shuffle_enabled: bool,
+    current_song: Option<&Song>,
+) -> Option<Song> {
+    match next_queue_advance(
+        queue_snapshot,
+        queue_index,
+        repeat,
+        shuffle_enabled,
+        current_song,
+    ) {
+        QueueAdvance::Index(next) => queue_snapshot.get(next).cloned(),
+        QueueAdvance::GenerateSimilar => {
+            let seed_key = shuffle_seed_key(current_song);
+            PEEKED_SHUFFLE_QUEUE.with(|cell| {
+                cell.borrow()
+                    .as_ref()
+                    .filter(|peeked| peeked.seed_key == seed_key)
+                    .and_then(|peeked| peeked.songs.first().cloned())
+            })
+        }
+        QueueAdvance::Stop => None,
+    }
+}
+
+/// Seconds left before `song` ends, honoring its end offset. `None` while the length is unknown.
+fn seconds_until_track_end(
+    settings: &AppSettings,
+    song: &Song,
+    position: f64,
+    duration: f64,
+) -> Option<f64> {
+    let end = settings
+        .playback_offsets_for(&song.server_id, &song.id)
+        .end_secs
+        .filter(|end| *end > 0.0)
+        .or_else(|| (duration.is_finite() && duration > 0.0).then_some(duration))?;
+    Some((end - position).max(0.0))
+}
+
+/// Whether the next track should be decided now so it can be preloaded before this one ends.
+fn within_next_track_peek_window(
+    settings: &AppSettings,
+    song: &Song,
+    position: f64,
+    duration: f64,
+) -> bool {
+    song.server_name != "Radio"
+        && seconds_until_track_end(settings, song, position, duration)
+            .is_some_and(|remaining| remaining <= NEXT_TRACK_PEEK_WINDOW_SECS)
+}
+
+async fn fetch_shuffle_queue_candidates(
+    active_servers: &[ServerConfig],
+    seed_song: Option<&Song>,
+) -> Vec<Song> {
+    let mut songs = Vec::new();
+    if let Some(seed) = seed_song {
+        if let Some(server) = active_servers
+            .iter()
+            .find(|s| s.id == seed.server_id)
+            .cloned()
+        {
+            let client = NavidromeClient::new(server);
+            if let Ok(similar) = client.get_similar_songs(&seed.id, 50).await {
+                songs.extend(similar);
+            }
+        }
+    }
+
+    if songs.is_empty() {
+        for server in active_servers.iter().cloned() {
+            let client = NavidromeClient::new(server);
+            if let Ok(server_songs) = client.get_random_songs(25).await {
+                songs.extend(server_songs);
+            }
+        }
+    }
+
+    songs
+}
+
+fn finalize_shuffle_queue(songs: Vec<Song>) -> Vec<Song> {
+    let mut songs = SKIP_SHUFFLE_WEIGHTS.with(|cell| cell.borrow().exclude(songs));
+    shuffle_songs_in_place(&mut songs);
+    songs.truncate(50);
+    normalize_manual_queue_songs(songs)
+}
+
+/// Generates the similar-songs queue for `seed_song` ahead of time so its first track is
+/// known (and can be preloaded) before the current track ends.
+pub(crate) fn spawn_shuffle_queue_peek(
+    servers: Vec<ServerConfig>,
+    settings: AppSettings,
+    seed_song: Option<Song>,
+) {
+    if has_peeked_shuffle_queue(seed_song.as_ref()) {
+        return;
+    }
+    let active_servers: Vec<ServerConfig> = servers.iter().filter(|s| s.active).cloned().collect();
+    if active_servers.is_empty() {
+        return;
+    }
+    // Claim the slot so repeated ticks inside the peek window don't refetch.
+    stash_peeked_shuffle_queue(seed_song.as_ref(), Vec::new());
+
+    spawn(async move {
+        let songs = fetch_shuffle_queue_candidates(&active_servers, seed_song.as_ref()).await;
+        let songs = finalize_shuffle_queue(songs);
+        if !has_peeked_shuffle_queue(seed_song.as_ref()) {
+            return;
+        }
+        let first = songs.first().cloned();
+        stash_peeked_shuffle_queue(seed_song.as_ref(), songs);
+        if let Some(first) = first {
+            preload_next_song(first, servers, settings);
+        }
+    });
+}
+
+/// Rough size of the full file for `song`, from its bitrate and duration.
+fn estimated_song_bytes(song: &Song) -> Option<u64> {
+    let kbps = song.bitrate.filter(|kbps| *kbps > 0)?;
+    (song.duration > 0).then(|| u64::from(kbps) * 125 * u64::from(song.duration))
+}
+
+/// Whether the aggressive buffering strategy should fetch all of `song` ahead of playback.
+/// Songs of unknown size are allowed; the fetch itself stops at the limit.
+fn should_fetch_ahead(settings: &AppSettings, song: &Song) -> bool {
+    let limit_bytes = u64::from(settings.fetch_ahead_limit_mb) * 1024 * 1024;
+    settings.buffering_strategy == BufferingStrategy::Aggressive
+        && song.server_name != "Radio"
+        && estimated_song_bytes(song).is_none_or(|bytes| bytes <= limit_bytes)
+}
+
+/// Fetches the playing track into the audio cache under the aggressive strategy, so a stall
+/// can resume from the local copy. The download manager dedupes this against downloads of
+/// the same song that are already running.
+#[cfg(not(target_arch = "wasm32"))]
+fn fetch_ahead_current_song(song: &Song, servers: &[ServerConfig], settings: &AppSettings) {
+    if !should_fetch_ahead(settings, song) || is_song_downloaded(song) {
+        return;
+    }
+    let song = song.clone();
+    let servers = servers.to_vec();
+    let settings = settings.clone();
+    spawn(async move {
+        let _ = prefetch_song_audio_with_origin(
+            &song,
+            &servers,
+            &settings,
+            DownloadOrigin::QueuePrefetch,
+        )
+        .await;
+    });
+}
+
+/// Starts loading `song` ahead of time so the transition into it is gapless.
+#[cfg(not(target_arch = "wasm32"))]
+fn preload_next_song(song: Song, servers: Vec<ServerConfig>, settings: AppSettings) {
+    if settings.buffering_strategy == BufferingStrategy::Minimal {
+        return;
+    }
+    // A crossfade starts the next track early, so the bridge buffers it on a spare element.
+    if NATIVE_CROSSFADE_SUPPORTED && settings.crossfade_enabled && song.server_name != "Radio" {
+        let transcode = stream_transcode(&settings);
+        if let Some(src) = resolve_stream_url(&song, &servers, settings.offline_mode, transcode) {
+            native_audio_command(serde_json::json!({ "type": "preload", "src": src }));
+        }
+    }
+    if settings.buffering_strategy == BufferingStrategy::Aggressive
+        && !should_fetch_ahead(&settings, &song)
+    {
+        return;
+    }
+    spawn(async move {
+        let _ = prefetch_song_audio_with_origin(
+            &song,
+            &servers,
+            &settings,
+            DownloadOrigin::QueuePrefetch,
+        )
+        .await;
+    });
+}
+
+/// Object URLs of tracks fetched in full by the aggressive strategy, oldest first. Two are
+/// kept so the playing track's blob is not revoked while the next one is fetched.
+#[cfg(target_arch = "wasm32")]
+const WEB_FETCH_AHEAD_SLOTS: usize = 2;
+
+#[cfg(target_arch = "wasm32")]
+thread_local! {
+    static WEB_FETCHED_AHEAD: std::cell::RefCell<Vec<(String, String)>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Blob URL of `song` if the aggressive strategy already fetched it in full.
+#[cfg(target_arch = "wasm32")]
+fn web_fetched_ahead_url(song: &Song) -> Option<String> {
+    let key = queue_extension_song_key(song);
+    WEB_FETCHED_AHEAD.with(|slots| {
+        slots
+            .borrow()
+            .iter()
+            .find(|(slot_key, _)| *slot_key == key)
+            .map(|(_, url)| url.clone())
+    })
+}
+
+#[cfg(target_arch = "wasm32")]
+fn web_js_method(target: &wasm_bindgen::JsValue, name: &str) -> Option<js_sys::Function> {
+    js_sys::Reflect::get(target, &name.into())
+        .ok()?
+        .dyn_into::<js_sys::Function>()
+        .ok()
+}
+
+/// Downloads `url` into a Blob and returns an object URL for it, or `None` when the request
+/// fails or the body is larger than `max_bytes`.
+#[cfg(target_arch = "wasm32")]
+async fn web_fetch_blob_url(url: &str, max_bytes: u64) -> Option<String> {
+    use wasm_bindgen_futures::JsFuture;
+
+    let window_js = wasm_bindgen::JsValue::from(window()?);
+    let fetch = web_js_method(&window_js, "fetch")?;
+    let response_promise = fetch.call1(&window_js, &url.into()).ok()?;
+    let response = JsFuture::from(js_sys::Promise::from(response_promise))
+        .await
+        .ok()?;
+    if !js_sys::Reflect::get(&response, &"ok".into())
+        .ok()?
+        .as_bool()
+        .unwrap_or(false)
+    {
+        return None;
+    }
+
+    let headers = js_sys::Reflect::get(&response, &"headers".into()).ok()?;
+    let content_length = web_js_method(&headers, "get")
+        .and_then(|get| get.call1(&headers, &"content-length".into()).ok())
+        .and_then(|value| value.as_string())
+        .and_then(|value| value.trim().parse::<u64>().ok());
+    if content_length.is_some_and(|bytes| bytes > max_bytes) {
+        return None;
+    }
+
+    let blob_promise = web_js_method(&response, "blob")?
+        .call0(&response)
+        .ok()?;
+    let blob = JsFuture::from(js_sys::Promise::from(blob_promise))
+        .await
+        .ok()?;
+    let size = js_sys::Reflect::get(&blob, &"size".into())
+        .ok()?
+        .as_f64()
+        .unwrap_or(0.0);
+    if size <= 0.0 || size > max_bytes as f64 {
+        return None;
+    }
+
+    let url_api = js_sys::Reflect::get(&js_sys::global(), &"URL".into()).ok()?;
+    web_js_method(&url_api, "createObjectURL")?
+        .call1(&url_api, &blob)
+        .ok()?
+        .as_string()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn web_revoke_blob_url(url: &str) {
+    let Ok(url_api) = js_sys::Reflect::get(&js_sys::global(), &"URL".into()) else {
+        return;
+    };
+    if let Some(revoke) = web_js_method(&url_api, "revokeObjectURL") {
+        let _ = revoke.call1(&url_api, &url.into());
+    }
+}
+
+/// Fetches the whole of `song` into memory so its playback cannot stall mid-track. The web
+/// build has no offline downloads, so there is nothing else that could be fetching it.
+#[cfg(target_arch = "wasm32")]
+fn web_fetch_ahead_song(song: Song, url: String, settings: &AppSettings) {
+    if web_fetched_ahead_url(&song).is_some() {
+        return;
+    }
+    let max_bytes = u64::from(settings.fetch_ahead_limit_mb) * 1024 * 1024;
+    spawn(async move {
+        let Some(blob_url) = web_fetch_blob_url(&url, max_bytes).await else {
+            return;
+        };
+        let key = queue_extension_song_key(&song);
+        WEB_FETCHED_AHEAD.with(|slots| {
+            let mut slots = slots.borrow_mut();
+            slots.retain(|(slot_key, _)| *slot_key != key);
+            slots.push((key, blob_url));
+            while slots.len() > WEB_FETCH_AHEAD_SLOTS {
+                let (_, stale_url) = slots.remove(0);
+                web_revoke_blob_url(&stale_url);
+            }
+        });
+    });
+}
+
+/// Starts loading `song` ahead of time so the transition into it is gapless.
+#[cfg(target_arch = "wasm32")]
+fn preload_next_song(song: Song, servers: Vec<ServerConfig>, settings: AppSettings) {
+    thread_local! {
+        static PRELOAD_AUDIO: std::cell::RefCell<Option<HtmlAudioElement>> =
+            const { std::cell::RefCell::new(None) };
+    }
+
+    if settings.buffering_strategy == BufferingStrategy::Minimal {
+        return;
+    }
+    let Some(url) = resolve_stream_url(&song, &servers, stream_transcode(&settings)) else {
+        return;
+    };
+    if should_fetch_ahead(&settings, &song) {
+        web_fetch_ahead_song(song, url, &settings);
+        return;
+    }
+    PRELOAD_AUDIO.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        if slot.is_none() {
+            *slot = window()
+                .and_then(|w| w.document())
+                .and_then(|doc| doc.create_element("audio").ok())
+                .and_then(|el| el.dyn_into::<HtmlAudioElement>().ok());
+        }
+        if let Some(audio) = slot.as_ref() {
+            let _ = audio.set_attribute("preload", "auto");
+            audio.set_muted(true);
+            if audio.src() != url {
+                audio.set_src(&url);
+                audio.load();
+            }
+        }
+    });
+}
+
+/// Pre-decides the track after `current_song` and preloads it. Called once per track when it
+/// enters the peek window; the end-of-track handler later commits the same choice.
+pub(crate) fn prepare_next_track(
+    servers: &[ServerConfig],
+    settings: &AppSettings,
+    queue_snapshot: &[Song],
+    queue_index: usize,
+    repeat: RepeatMode,
+    shuffle_enabled: bool,
+    current_song: &Song,
+) {
+    if repeat == RepeatMode::One {
+        return;
+    }
+    let advance = next_queue_advance(
+        queue_snapshot,
+        queue_index,
+        repeat,
+        shuffle_enabled,
+        Some(current_song),
+    );
+    if advance == QueueAdvance::GenerateSimilar {
+        spawn_shuffle_queue_peek(
+            servers.to_vec(),
+            settings.clone(),
+            Some(current_song.clone()),
+        );
+    } else if let Some(song) = peek_next_song(
+        queue_snapshot,
+        queue_index,
+        repeat,
+        shuffle_enabled,
+        Some(current_song),
+    ) {
+        preload_next_song(song, servers.to_vec(), settings.clone());
+    }
+}
+
 #[cfg(target_arch = "wasm32")]
 pub(crate) fn spawn_shuffle_queue(
     servers: Vec<ServerConfig>,
@@ -330,42 +973,23 @@ pub(crate) fn spawn_shuffle_queue(
     if active_servers.is_empty() {
         return;
     }
+    let peeked = take_peeked_shuffle_queue(seed_song.as_ref()).filter(|songs| !songs.is_empty());
 
     set_transport_loading(audio_state, true, Some("Generating similar queue..."));
     spawn(async move {
-        let mut songs = Vec::new();
-        if let Some(seed) = seed_song {
-            if let Some(server) = active_servers
-                .iter()
-                .find(|s| s.id == seed.server_id)
-                .cloned()
-            {
-                let client = NavidromeClient::new(server);
-                if let Ok(similar) = client.get_similar_songs(&seed.id, 50).await {
-                    songs.extend(similar);
-                }
-            }
-        }
-
-        if songs.is_empty() {
-            for server in active_servers.iter().cloned() {
-                let client = NavidromeClient::new(server.clone());
-                if let Ok(server_songs) = client.get_random_songs(25).await {
-                    songs.extend(server_songs);
-                }
-            }
-        }
+        let songs = match peeked {
+            Some(songs) => songs,
+            None => finalize_shuffle_queue(
+                fetch_shuffle_queue_candidates(&active_servers, seed_song.as_ref()).await,
+            ),
+        };
 
         if songs.is_empty() {
             set_transport_loading(audio_state, false, None);
             return;
         }
 
-        shuffle_songs_in_place(&mut songs);
-        songs.truncate(50);
-        songs = normalize_manual_queue_songs(songs);
-
-        let first = songs.get(0).cloned();
+        let first = songs.first().cloned();
         let continue_loading_for_song = first.is_some() && play_state.unwrap_or(false);
         defer_signal_update(move || {
             queue.set(songs);
@@ -398,41 +1022,22 @@ pub(crate) fn spawn_shuffle_queue(
     if active_servers.is_empty() {
         return;
     }
+    let peeked = take_peeked_shuffle_queue(seed_song.as_ref()).filter(|songs| !songs.is_empty());
 
     set_transport_loading(audio_state, true, Some("Generating similar queue..."));
     spawn(async move {
-        let mut songs = Vec::new();
-        if let Some(seed) = seed_song {
-            if let Some(server) = active_servers
-                .iter()
-                .find(|s| s.id == seed.server_id)
-                .cloned()
-            {
-                let client = NavidromeClient::new(server);
-                if let Ok(similar) = client.get_similar_songs(&seed.id, 50).await {
-                    songs.extend(similar);
-                }
-            }
-        }
-
-        if songs.is_empty() {
-            for server in active_servers.iter().cloned() {
-                let client = NavidromeClient::new(server);
-                if let Ok(server_songs) = client.get_random_songs(25).await {
-                    songs.extend(server_songs);
-                }
-            }
-        }
+        let songs = match peeked {
+            Some(songs) => songs,
+            None => finalize_shuffle_queue(
+                fetch_shuffle_queue_candidates(&active_servers, seed_song.as_ref()).await,
+            ),
+        };
 
         if songs.is_empty() {
             set_transport_loading(audio_state, false, None);
             return;
         }
 
-        shuffle_songs_in_place(&mut songs);
-        songs.truncate(50);
-        songs = normalize_manual_queue_songs(songs);
-
         let first = songs.first().cloned();
         let continue_loading_for_song = first.is_some() && play_state.unwrap_or(false);
         queue.set(songs);
@@ -449,13 +1054,80 @@ pub(crate) fn spawn_shuffle_queue(
     });
 }
 
-#[cfg(target_arch = "wasm32")]
-fn resolve_stream_url(song: &Song, servers: &[ServerConfig]) -> Option<String> {
-    if song.server_name == "Radio" {
-        return song
-            .stream_url
-            .clone()
-            .filter(|value| !value.trim().is_empty());
+/// Format and bitrate cap the server is asked to transcode a stream to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct StreamTranscode {
+    format: &'static str,
+    max_bitrate_kbps: u32,
+}
+
+/// Transcode for streams under the current settings: the low-latency start transcode when
+/// enabled, held under the streaming quality cap, otherwise the cap alone. Read when a track
+/// loads, so a change applies from the next track on.
+fn stream_transcode(settings: &AppSettings) -> Option<StreamTranscode> {
+    let cap = Some(settings.stream_max_bitrate_kbps).filter(|kbps| *kbps > 0);
+    if settings.fast_start_enabled {
+        let kbps = cap.map_or(settings.fast_start_bitrate_kbps, |cap| {
+            cap.min(settings.fast_start_bitrate_kbps)
+        });
+        return Some(StreamTranscode {
+            format: "mp3",
+            max_bitrate_kbps: kbps,
+        });
+    }
+    let format = STREAM_FORMATS
+        .into_iter()
+        .find(|format| *format == settings.stream_format)
+        .unwrap_or("mp3");
+    cap.map(|kbps| StreamTranscode {
+        format,
+        max_bitrate_kbps: kbps,
+    })
+}
+
+fn stream_url_for_client(
+    client: &NavidromeClient,
+    song_id: &str,
+    transcode: Option<StreamTranscode>,
+) -> String {
+    match transcode {
+        Some(transcode) => client.get_transcoded_stream_url(
+            song_id,
+            transcode.format,
+            transcode.max_bitrate_kbps,
+        ),
+        None => client.get_stream_url(song_id),
+    }
+}
+
+/// Station stream URL, unless strict privacy mode blocks its host.
+fn radio_stream_url(song: &Song) -> Option<String> {
+    song.stream_url
+        .clone()
+        .filter(|value| !value.trim().is_empty())
+        .filter(|value| allows_url(value))
+}
+
+/// Explains an unresolved station when strict privacy mode is what blocked it.
+fn privacy_blocked_stream_message(song: &Song) -> Option<String> {
+    let has_stream_url = song
+        .stream_url
+        .as_deref()
+        .is_some_and(|value| !value.trim().is_empty());
+    (song.server_name == "Radio" && has_stream_url && strict_privacy_enabled()).then(|| {
+        "Strict privacy mode blocks this station because it streams from outside your servers."
+            .to_string()
+    })
+}
+
+#[cfg(target_arch = "wasm32")]
+fn resolve_stream_url(
+    song: &Song,
+    servers: &[ServerConfig],
+    transcode: Option<StreamTranscode>,
+) -> Option<String> {
+    if song.server_name == "Radio" {
+        return radio_stream_url(song);
     }
 
     let song_id = song.id.trim();
@@ -468,12 +1140,17 @@ fn resolve_stream_url(song: &Song, servers: &[ServerConfig]) -> Option<String> {
         .find(|s| s.id == song.server_id)
         .map(|server| {
             let client = NavidromeClient::new(server.clone());
-            client.get_stream_url(song_id)
+            stream_url_for_client(&client, song_id, transcode)
         })
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-fn resolve_stream_url(song: &Song, servers: &[ServerConfig], offline_mode: bool) -> Option<String> {
+fn resolve_stream_url(
+    song: &Song,
+    servers: &[ServerConfig],
+    offline_mode: bool,
+    transcode: Option<StreamTranscode>,
+) -> Option<String> {
     if let Some(cached_url) = cached_audio_url(song) {
         return Some(cached_url);
     }
@@ -483,10 +1160,7 @@ fn resolve_stream_url(song: &Song, servers: &[ServerConfig], offline_mode: bool)
     }
 
     if song.server_name == "Radio" {
-        return song
-            .stream_url
-            .clone()
-            .filter(|value| !value.trim().is_empty());
+        return radio_stream_url(song);
     }
 
     let song_id = song.id.trim();
@@ -499,12 +1173,384 @@ fn resolve_stream_url(song: &Song, servers: &[ServerConfig], offline_mode: bool)
         .find(|s| s.id == song.server_id)
         .map(|server| {
             let client = NavidromeClient::new(server.clone());
-            client.get_stream_url(song_id)
+            stream_url_for_client(&client, song_id, transcode)
         })
 }
 
+/// The URL the player requests for `song` with the current settings, for diagnostics.
+pub fn stream_request_url(
+    song: &Song,
+    servers: &[ServerConfig],
+    settings: &AppSettings,
+) -> Option<String> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        resolve_stream_url(
+            song,
+            servers,
+            settings.offline_mode,
+            stream_transcode(settings),
+        )
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        resolve_stream_url(song, servers, stream_transcode(settings))
+    }
+}
+
+/// Position a fresh play of `song` starts from; honors the per-song start offset.
+pub(crate) fn song_start_offset(settings: &AppSettings, song: &Song) -> f64 {
+    settings
+        .playback_offsets_for(&song.server_id, &song.id)
+        .start_secs
+        .unwrap_or(0.0)
+        .max(0.0)
+}
+
+/// Speed `song` plays at: the saved speed, except radio, which live streams can't be
+/// rate-shifted for.
+fn playback_rate_for(settings: &AppSettings, song: Option<&Song>) -> f64 {
+    if song.is_some_and(|song| song.server_name == "Radio") {
+        1.0
+    } else {
+        settings.playback_rate
+    }
+}
+
+/// Mirrors the speed the backend was just set to into `AudioState`.
+fn publish_playback_rate(audio_state: Signal<AudioState>, rate: f64) {
+    let mut playback_rate = audio_state.peek().playback_rate;
+    if *playback_rate.peek() != rate {
+        playback_rate.set(rate);
+    }
+}
+
+/// Whether playback has passed the per-song end offset and should advance like a track end.
+fn song_reached_end_offset(settings: &AppSettings, song: &Song, position: f64) -> bool {
+    settings
+        .playback_offsets_for(&song.server_id, &song.id)
+        .end_secs
+        .is_some_and(|end| end > 0.0 && position >= end)
+}
+
+/// Shortest A-B loop: a B point closer to A than this is pushed out to it.
+pub(crate) const AB_LOOP_MIN_SECS: f64 = 1.0;
+
+/// Where an A-B loop sends playback at `position`: back to A once it reaches B. The loop
+/// runs only while both points are set.
+fn ab_loop_restart(loop_start: Option<f64>, loop_end: Option<f64>, position: f64) -> Option<f64> {
+    let (start, end) = (loop_start?, loop_end?);
+    (end > start && position >= end).then_some(start)
+}
+
+/// The A point the poll loop should seek back to now that `song` is at `position`. Radio
+/// can't seek, so it never loops.
+fn ab_loop_seek_target(
+    audio_state: &AudioState,
+    song: Option<&Song>,
+    position: f64,
+) -> Option<f64> {
+    song.filter(|song| song.server_name != "Radio")?;
+    ab_loop_restart(
+        *audio_state.loop_start.peek(),
+        *audio_state.loop_end.peek(),
+        position,
+    )
+}
+
+/// Whether an A-B loop is set. While it is, the track never reaches its end, so crossfade,
+/// gapless buffering and the end offset stay out of the way of the loop.
+fn ab_loop_armed(audio_state: &AudioState) -> bool {
+    ab_loop_restart(
+        *audio_state.loop_start.peek(),
+        *audio_state.loop_end.peek(),
+        f64::INFINITY,
+    )
+    .is_some()
+}
+
+const STREAM_RETRY_MAX_ATTEMPTS: u32 = 3;
+const STREAM_RETRY_BASE_DELAY_MS: u64 = 1_000;
+const RADIO_RETRY_DELAY_MS: u64 = 5_000;
+/// Poll interval used while the player is paused or the window is hidden.
+#[cfg(not(target_arch = "wasm32"))]
+const NATIVE_IDLE_POLL_MS: u64 = 1_000;
+/// Time without progress while playing before a native stream counts as dropped.
+#[cfg(not(target_arch = "wasm32"))]
+const NATIVE_STALL_RETRY_MS: u64 = 8_000;
+
+/// Delay before the next native snapshot poll. Idle or hidden players back off to
+/// [`NATIVE_IDLE_POLL_MS`]; tick-counted startup windows keep the configured rate.
+#[cfg(not(target_arch = "wasm32"))]
+fn native_poll_interval_ms(settings: &AppSettings, idle: bool) -> u64 {
+    let base = u64::from(settings.progress_poll_interval_ms.clamp(100, 1000));
+    if idle {
+        base.max(NATIVE_IDLE_POLL_MS)
+    } else {
+        base
+    }
+}
+
+/// Whether a polled position should be written to a progress signal. Unless `precise` is
+/// set, only changes of the displayed second are published, so views that show `m:ss`
+/// don't re-render on every poll.
+#[cfg(not(target_arch = "wasm32"))]
+fn should_publish_progress(published: f64, polled: f64, precise: bool) -> bool {
+    if precise {
+        polled != published
+    } else {
+        polled.floor() != published.floor()
+    }
+}
+
+/// What a poll loop should do after seeing a network-class stream failure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StreamRetryAction {
+    /// Still backing off before the next attempt.
+    Wait,
+    /// Reload the same stream and resume from this position.
+    Reload(f64),
+    /// Retries are used up; surface the playback error.
+    GiveUp,
+}
+
+/// Per-song reload bookkeeping shared by the web and native poll loops.
+#[derive(Debug, Default)]
+struct StreamRetryState {
+    song_id: Option<String>,
+    attempts: u32,
+    last_good_position: f64,
+    resume_position: f64,
+    pending_wait_ms: Option<u64>,
+    gave_up: bool,
+}
+
+impl StreamRetryState {
+    fn reset_for(&mut self, song_id: &str) {
+        if self.song_id.as_deref() != Some(song_id) {
+            *self = Self {
+                song_id: Some(song_id.to_string()),
+                ..Self::default()
+            };
+        }
+    }
+
+    /// True while a reload is pending or in flight.
+    fn is_reconnecting(&self) -> bool {
+        self.pending_wait_ms.is_some() || (self.attempts > 0 && !self.gave_up)
+    }
+
+    /// Records a healthy tick. After a reload, playback has to move past the resume point
+    /// before the retry budget is refilled.
+    fn note_healthy(&mut self, song_id: &str, position: f64) {
+        self.reset_for(song_id);
+        self.pending_wait_ms = None;
+        if self.attempts == 0 {
+            self.last_good_position = position.max(0.0);
+        } else if position > self.resume_position + 1.0 {
+            self.attempts = 0;
+            self.gave_up = false;
+            self.last_good_position = position;
+        }
+    }
+
+    /// Advances the backoff by one poll tick of `tick_ms`. Radio streams retry forever at a
+    /// slower cadence; tracks get a few attempts with exponential backoff.
+    fn on_failure(&mut self, song_id: &str, is_radio: bool, tick_ms: u64) -> StreamRetryAction {
+        self.reset_for(song_id);
+        match self.pending_wait_ms {
+            None => {
+                if !is_radio && self.attempts >= STREAM_RETRY_MAX_ATTEMPTS {
+                    self.gave_up = true;
+                    return StreamRetryAction::GiveUp;
+                }
+                let delay = if is_radio {
+                    RADIO_RETRY_DELAY_MS
+                } else {
+                    STREAM_RETRY_BASE_DELAY_MS << self.attempts
+                };
+                self.pending_wait_ms = Some(delay);
+                StreamRetryAction::Wait
+            }
+            Some(remaining) if remaining > tick_ms => {
+                self.pending_wait_ms = Some(remaining - tick_ms);
+                StreamRetryAction::Wait
+            }
+            Some(_) => {
+                self.pending_wait_ms = None;
+                self.attempts = self.attempts.saturating_add(1);
+                self.resume_position = if is_radio {
+                    0.0
+                } else {
+                    self.last_good_position
+                };
+                StreamRetryAction::Reload(self.resume_position)
+            }
+        }
+    }
+}
+
+/// Statuses a server or signing proxy answers with once a stream URL has expired.
+fn is_expired_stream_status(status: u16) -> bool {
+    matches!(status, 401 | 403)
+}
+
+/// Statuses meaning the server no longer has the file behind a stream URL.
+fn is_gone_stream_status(status: u16) -> bool {
+    matches!(status, 404 | 410)
+}
+
+/// Per-song bookkeeping for rebuilding an expired stream URL. Each failure is checked
+/// once, and a song gets one fresh URL until playback moves past where it was reloaded.
+#[derive(Debug, Default)]
+struct StreamUrlRefreshState {
+    song_id: Option<String>,
+    checked: bool,
+    regenerated_at: Option<f64>,
+    /// Status of a refusal that survived the refresh, until playback recovers.
+    refused: Option<u16>,
+}
+
+impl StreamUrlRefreshState {
+    fn reset_for(&mut self, song_id: &str) {
+        if self.song_id.as_deref() != Some(song_id) {
+            *self = Self {
+                song_id: Some(song_id.to_string()),
+                ..Self::default()
+            };
+        }
+    }
+
+    /// Whether a failure of `song_id` should be checked against the server. Repeated
+    /// failure ticks only ask once; `note_healthy` arms the next check.
+    fn should_check(&mut self, song_id: &str) -> bool {
+        self.reset_for(song_id);
+        !std::mem::replace(&mut self.checked, true)
+    }
+
+    /// Whether a checked URL the server refused should be rebuilt, resuming at `position`.
+    /// A second refusal before playback recovers is surfaced instead.
+    fn should_regenerate(&mut self, position: f64) -> bool {
+        if self.regenerated_at.is_some() {
+            return false;
+        }
+        self.regenerated_at = Some(position.max(0.0));
+        true
+    }
+
+    /// Records a tick without a failure. Once playback is past the reload point, a link
+    /// that expires again later in the song gets its own refresh.
+    fn note_healthy(&mut self, song_id: &str, position: f64) {
+        self.reset_for(song_id);
+        self.checked = false;
+        self.refused = None;
+        if self
+            .regenerated_at
+            .is_some_and(|reloaded_at| position > reloaded_at + 1.0)
+        {
+            self.regenerated_at = None;
+        }
+    }
+}
+
+/// Outcome of checking a failed server stream for an expired URL.
+#[derive(Debug, Clone, PartialEq)]
+enum StreamUrlRefresh {
+    /// Not an expired link, or nothing to check; other recovery carries on.
+    Unchanged,
+    /// Load this freshly signed URL in place of the failed one.
+    Regenerated(String),
+    /// The server still refuses the stream after a refresh.
+    Refused(u16),
+    /// The server no longer has the song; it should be marked unavailable and skipped.
+    Gone,
+}
+
+/// Asks the server why `src` failed. A 401 or 403 means the signed link expired, so the
+/// URL is rebuilt once with fresh credentials before the refusal is surfaced.
+async fn refresh_expired_stream_url(
+    refresh: &mut StreamUrlRefreshState,
+    song: &Song,
+    src: Option<&str>,
+    servers: &[ServerConfig],
+    settings: &AppSettings,
+    position: f64,
+) -> StreamUrlRefresh {
+    let Some(src) = src.filter(|src| src.starts_with("http://") || src.starts_with("https://"))
+    else {
+        return StreamUrlRefresh::Unchanged;
+    };
+    if song.server_name == "Radio" || !refresh.should_check(&song.id) {
+        return StreamUrlRefresh::Unchanged;
+    }
+    let Some(status) = NavidromeClient::stream_url_status(src).await else {
+        return StreamUrlRefresh::Unchanged;
+    };
+    if is_gone_stream_status(status) {
+        return StreamUrlRefresh::Gone;
+    }
+    if !is_expired_stream_status(status) {
+        return StreamUrlRefresh::Unchanged;
+    }
+    let server = servers.iter().find(|server| server.id == song.server_id);
+    if let Some(server) = server.filter(|_| refresh.should_regenerate(position)) {
+        NavidromeClient::new(server.clone()).refresh_stream_auth();
+        if let Some(url) = stream_request_url(song, servers, settings) {
+            return StreamUrlRefresh::Regenerated(url);
+        }
+    }
+    refresh.refused = Some(status);
+    StreamUrlRefresh::Refused(status)
+}
+
+/// Error shown when a stream is still refused after its URL was rebuilt.
+fn stream_refused_message(status: u16) -> String {
+    format!(
+        "The server refused this track (HTTP {status}) even with a freshly signed link. \
+         Check the server or proxy access rules."
+    )
+}
+
+/// Playback seen below this position means a "play from beginning" restart has landed.
+const BOOKMARK_HOLD_RELEASE_SECS: f64 = 5.0;
+
+thread_local! {
+    // Song restarted from the beginning; auto-save stays off until the restart is observed
+    // so the old bookmark position isn't written straight back.
+    static BOOKMARK_SAVE_HOLD: std::cell::RefCell<Option<String>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+fn bookmark_hold_key(song: &Song) -> String {
+    format!("{}:{}", song.server_id, song.id)
+}
+
+fn hold_bookmark_autosave(song: &Song) {
+    let key = bookmark_hold_key(song);
+    BOOKMARK_SAVE_HOLD.with(|cell| *cell.borrow_mut() = Some(key));
+}
+
+fn release_bookmark_hold_if_restarted(song: &Song, position: f64) {
+    if position >= BOOKMARK_HOLD_RELEASE_SECS {
+        return;
+    }
+    let key = bookmark_hold_key(song);
+    BOOKMARK_SAVE_HOLD.with(|cell| {
+        let mut hold = cell.borrow_mut();
+        if hold.as_deref() == Some(key.as_str()) {
+            *hold = None;
+        }
+    });
+}
+
 fn can_save_server_bookmark(song: &Song) -> bool {
-    song.server_name != "Radio" && !song.id.trim().is_empty() && !song.server_id.trim().is_empty()
+    let held = BOOKMARK_SAVE_HOLD.with(|cell| {
+        cell.borrow().as_deref() == Some(bookmark_hold_key(song).as_str())
+    });
+    !held
+        && song.server_name != "Radio"
+        && !song.id.trim().is_empty()
+        && !song.server_id.trim().is_empty()
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -524,9 +1570,928 @@ fn scrobble_song(servers: &[ServerConfig], song: &Song, finished: bool) {
     let server = servers.iter().find(|s| s.id == song.server_id).cloned();
     if let Some(server) = server {
         let song_id = song.id.clone();
+        // Tracked until the request finishes so quitting can still send it.
+        let ticket = crate::quit_flush::track_scrobble(server.clone(), &song_id, finished);
         spawn(async move {
             let client = NavidromeClient::new(server);
-            let _ = client.scrobble(&song_id, finished).await;
+            let sent = client.scrobble(&song_id, finished).await.is_ok();
+            crate::quit_flush::scrobble_settled(ticket, sent);
+        });
+    }
+}
+
+/// What `song` was queued from, for the skip history.
+fn listening_context(song: &Song) -> Option<String> {
+    let tag = song
+        .queue_meta
+        .as_ref()
+        .map(|meta| queue_source_kind_tag(&meta.source_kind))
+        .unwrap_or("queue");
+    Some(tag.to_string())
+}
+
+/// Adds a finished song to the local listening session when session summaries are on.
+fn record_session_play(settings: &AppSettings, song: &Song) {
+    if !settings.session_summary_enabled {
+        return;
+    }
+    let song = song.clone();
+    let context = listening_context(&song);
+    let settings = settings.clone();
+    spawn(async move {
+        let _ = record_listening_play(song, context).await;
+        refresh_skip_shuffle_weights(&settings);
+    });
+}
+
+/// Records `song` as skipped when it was left for another track at `position_secs`, well
+/// before its end. Uses the same local history as session summaries.
+pub(crate) fn record_session_skip(settings: &AppSettings, song: &Song, position_secs: f64) {
+    if !settings.session_summary_enabled
+        || song.server_name == "Radio"
+        || !is_skip(position_secs, song.duration)
+    {
+        return;
+    }
+    let song = song.clone();
+    let context = listening_context(&song);
+    let settings = settings.clone();
+    spawn(async move {
+        let _ = record_listening_skip(song, position_secs, context).await;
+        refresh_skip_shuffle_weights(&settings);
+    });
+}
+
+/// Repeat-count bookkeeping when `song_id` ends. `progress` is the song being repeated and its
+/// plays so far. Returns the updated progress while the track has plays left, or `None` once it
+/// has played `count` times and the queue should advance.
+pub(crate) fn repeat_count_replay(
+    progress: Option<&(String, u32)>,
+    song_id: &str,
+    count: u32,
+) -> Option<(String, u32)> {
+    let played = match progress {
+        Some((tracked, played)) if tracked == song_id => played.saturating_add(1),
+        _ => 1,
+    };
+    (played < count).then(|| (song_id.to_string(), played))
+}
+
+/// Whether `song` was the last entry of its album or playlist in the queue.
+pub(crate) fn container_finished(queue_snapshot: &[Song], song: &Song) -> bool {
+    let Some(meta) = song.queue_meta.as_ref() else {
+        return false;
+    };
+    !queue_snapshot.iter().any(|other| {
+        other.queue_meta.as_ref().is_some_and(|other_meta| {
+            other_meta.group_id == meta.group_id
+                && other_meta.source_position > meta.source_position
+        })
+    })
+}
+
+/// Drops the resume point of an album or playlist that was just played to the end.
+fn clear_finished_container(queue_snapshot: &[Song], song: &Song) {
+    let Some(meta) = song.queue_meta.as_ref() else {
+        return;
+    };
+    let Some(kind) = ResumeContainerKind::from_queue_source(&meta.source_kind) else {
+        return;
+    };
+    if !container_finished(queue_snapshot, song) {
+        return;
+    }
+    let source_id = meta.source_id.clone();
+    spawn(async move {
+        clear_container_resume_point(kind, source_id).await;
+    });
+}
+
+/// Quiet period after the last queue change before the playback state is written.
+const PLAYBACK_STATE_SAVE_DEBOUNCE_MS: u64 = 1_000;
+
+#[cfg(target_arch = "wasm32")]
+async fn playback_state_save_pause() {
+    gloo_timers::future::TimeoutFuture::new(PLAYBACK_STATE_SAVE_DEBOUNCE_MS as u32).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn playback_state_save_pause() {
+    tokio::time::sleep(std::time::Duration::from_millis(
+        PLAYBACK_STATE_SAVE_DEBOUNCE_MS,
+    ))
+    .await;
+}
+
+/// Writes `state` once the queue has settled, so a burst of edits saves once. The write
+/// runs outside any component scope, and a newer call supersedes a pending one.
+pub fn schedule_playback_state_save(mut save_generation: Signal<u64>, state: PlaybackState) {
+    save_generation.with_mut(|generation| *generation = generation.saturating_add(1));
+    let generation = *save_generation.peek();
+    spawn_forever(async move {
+        playback_state_save_pause().await;
+        if *save_generation.peek() != generation {
+            return;
+        }
+        let _ = save_playback_state(state).await;
+    });
+}
+
+/// A saved queue, rebuilt from its songs' servers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestoredQueue {
+    pub songs: Vec<Song>,
+    pub queue_index: usize,
+    /// Where to pick the current song back up; `None` when it didn't come back and another
+    /// song took its place.
+    pub position: Option<f64>,
+}
+
+/// Where a restored queue picks up, given the saved indices of the entries that came back:
+/// the saved song if it's among them, otherwise the next one that is, or the last. The flag
+/// is whether it's the saved song itself.
+fn restored_queue_index(kept: &[usize], saved_index: usize) -> Option<(usize, bool)> {
+    match kept.iter().position(|&index| index >= saved_index) {
+        Some(position) => Some((position, kept[position] == saved_index)),
+        None => kept.len().checked_sub(1).map(|last| (last, false)),
+    }
+}
+
+/// How many saved queue entries are looked up at once while restoring.
+const QUEUE_RESTORE_CONCURRENCY: usize = 8;
+
+/// Rebuilds the saved queue by fetching each song from its server, a few at a time and in
+/// queue order. Entries whose server was removed, or that their server no longer has, are
+/// left out. `None` when nothing came back.
+pub async fn restore_playback_queue(
+    state: &PlaybackState,
+    servers: &[ServerConfig],
+) -> Option<RestoredQueue> {
+    use futures_util::StreamExt;
+
+    let lookups = state.queue.iter().enumerate().filter_map(|(index, item)| {
+        let server = servers
+            .iter()
+            .find(|server| server.id == item.server_id)?
+            .clone();
+        let song_id = item.song_id.clone();
+        Some(async move {
+            let song = NavidromeClient::new(server).get_song(&song_id).await.ok()?;
+            Some((index, song))
+        })
+    });
+    let (kept, songs): (Vec<usize>, Vec<Song>) = futures_util::stream::iter(lookups)
+        .buffered(QUEUE_RESTORE_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .flatten()
+        .unzip();
+    let (queue_index, is_saved_song) = restored_queue_index(&kept, state.queue_index)?;
+    Some(RestoredQueue {
+        songs,
+        queue_index,
+        position: is_saved_song.then_some(state.position),
+    })
+}
+
+const PLAYBACK_NOTICE_MS: u64 = 4_000;
+
+thread_local! {
+    // Songs already checked against their server after a playback failure.
+    static MISSING_SONG_PROBES: std::cell::RefCell<std::collections::HashSet<String>> =
+        std::cell::RefCell::new(std::collections::HashSet::new());
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn playback_notice_pause() {
+    gloo_timers::future::TimeoutFuture::new(PLAYBACK_NOTICE_MS as u32).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn playback_notice_pause() {
+    tokio::time::sleep(std::time::Duration::from_millis(PLAYBACK_NOTICE_MS)).await;
+}
+
+/// Shows `message` above the player for a few seconds.
+fn show_playback_notice(audio_state: Signal<AudioState>, message: String) {
+    let mut notice = audio_state.peek().playback_notice;
+    notice.set(Some(message.clone()));
+    spawn(async move {
+        playback_notice_pause().await;
+        if notice.peek().as_ref() == Some(&message) {
+            notice.set(None);
+        }
+    });
+}
+
+/// Why a skip or play/pause from a media key or remote can't do anything right now, worded
+/// for the notice shown in its place. `None` when the action goes ahead.
+pub(crate) fn ignored_transport_action_reason(
+    action: &str,
+    queue_snapshot: &[Song],
+    queue_index: usize,
+    repeat: RepeatMode,
+    shuffle_enabled: bool,
+    current_song: Option<&Song>,
+) -> Option<&'static str> {
+    if !matches!(
+        action,
+        "next" | "previous" | "toggle_play" | "playpause" | "play"
+    ) {
+        return None;
+    }
+    if crate::listen_along::is_following() {
+        return Some("Listening along. Leave to take control");
+    }
+    let Some(song) = current_song else {
+        return Some("Nothing is queued");
+    };
+    match action {
+        "next" | "previous" if song.server_name == "Radio" => {
+            Some("Skipping isn't available for radio")
+        }
+        "next"
+            if repeat != RepeatMode::One
+                && next_queue_advance(
+                    queue_snapshot,
+                    queue_index,
+                    repeat,
+                    shuffle_enabled,
+                    current_song,
+                ) == QueueAdvance::Stop =>
+        {
+            Some("End of queue")
+        }
+        _ => None,
+    }
+}
+
+/// Shows why a media-key or remote `action` from `source` did nothing, and records it in
+/// diagnostics.
+fn report_ignored_transport_action(
+    audio_state: Signal<AudioState>,
+    source: &str,
+    action: &str,
+    reason: &'static str,
+) {
+    let line = format!("[transport] ignored {action} from {source}: {reason}");
+    ios_diag_log("controller.action", &line);
+    crate::diagnostics::record_diagnostic(line);
+    show_playback_notice(audio_state, reason.to_string());
+}
+
+/// After `song` fails to play, asks its server whether it still exists. A song the server
+/// no longer has is marked unavailable, which skips it instead of leaving the queue stuck.
+fn mark_song_if_removed(
+    song: &Song,
+    servers: &[ServerConfig],
+    unavailable_songs: UnavailableSongsSignal,
+) {
+    if song.server_name == "Radio" || song.id.trim().is_empty() {
+        return;
+    }
+    let probe_key = format!("{}:{}", song.server_id, song.id);
+    if !MISSING_SONG_PROBES.with(|cell| cell.borrow_mut().insert(probe_key)) {
+        return;
+    }
+    let Some(server) = servers.iter().find(|s| s.id == song.server_id).cloned() else {
+        return;
+    };
+    let song = song.clone();
+    spawn(async move {
+        if let Err(err) = NavidromeClient::new(server).get_song_fresh(&song.id).await {
+            if err.is_not_found() {
+                unavailable_songs.mark(&song);
+            }
+        }
+    });
+}
+
+/// Moves the queue past unavailable songs whenever one becomes current. Songs passed over
+/// in quick succession share one notice that counts them all.
+fn use_unavailable_song_skip(
+    unavailable_songs: UnavailableSongsSignal,
+    queue: Signal<Vec<Song>>,
+    mut queue_index: Signal<usize>,
+    mut now_playing: Signal<Option<Song>>,
+    mut is_playing: Signal<bool>,
+    preview_playback: Signal<bool>,
+    audio_state: Signal<AudioState>,
+) {
+    // The last skip notice and how many songs it counted.
+    let mut skip_run = use_signal(|| (None::<String>, 0usize));
+    use_effect(move || {
+        let Some(song) = now_playing() else {
+            return;
+        };
+        if !unavailable_songs.contains(&song) || *preview_playback.peek() {
+            return;
+        }
+        let queue_snapshot = queue.peek().clone();
+        let current = Some(*queue_index.peek())
+            .filter(|index| {
+                queue_snapshot
+                    .get(*index)
+                    .is_some_and(|entry| entry.id == song.id && entry.server_id == song.server_id)
+            })
+            .or_else(|| find_song_instance_index(&queue_snapshot, &song));
+        let Some(current) = current else {
+            return;
+        };
+        let (next, skipped) = next_available_index(&queue_snapshot, current, |entry| {
+            unavailable_songs.peek_contains(entry)
         });
+        if next.is_none() && !*is_playing.peek() {
+            return;
+        }
+
+        let (last_notice, counted) = skip_run.peek().clone();
+        let showing_last = last_notice.is_some()
+            && *audio_state.peek().playback_notice.peek() == last_notice;
+        let total = if showing_last {
+            counted + skipped
+        } else {
+            skipped
+        };
+        let message = unavailable_skip_notice(total, next.is_some());
+        match next {
+            Some(next) => {
+                queue_index.set(next);
+                now_playing.set(Some(queue_snapshot[next].clone()));
+            }
+            None => is_playing.set(false),
+        }
+        skip_run.set((Some(message.clone()), total));
+        show_playback_notice(audio_state, message);
+    });
+}
+
+/// Output volume changes bigger than this ramp instead of jumping, so quiet hours starting,
+/// ending or being lifted mid-track don't pop.
+const OUTPUT_VOLUME_RAMP_MIN_JUMP: f64 = 0.05;
+const OUTPUT_VOLUME_RAMP_STEPS: u32 = 8;
+const OUTPUT_VOLUME_RAMP_STEP_MS: u64 = 25;
+
+#[cfg(target_arch = "wasm32")]
+async fn output_volume_ramp_pause() {
+    gloo_timers::future::TimeoutFuture::new(OUTPUT_VOLUME_RAMP_STEP_MS as u32).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn output_volume_ramp_pause() {
+    tokio::time::sleep(std::time::Duration::from_millis(OUTPUT_VOLUME_RAMP_STEP_MS)).await;
+}
+
+/// Moves the output from the last applied volume to `target` through `apply`. Small changes,
+/// such as dragging the volume slider, land at once; a newer change cancels a running ramp.
+fn apply_output_volume(
+    target: f64,
+    mut applied: Signal<Option<f64>>,
+    mut ramp_generation: Signal<u64>,
+    apply: impl Fn(f64) + 'static,
+) {
+    let ramp_id = ramp_generation.peek().wrapping_add(1);
+    ramp_generation.set(ramp_id);
+    let from = applied
+        .peek()
+        .filter(|from| (target - from).abs() > OUTPUT_VOLUME_RAMP_MIN_JUMP);
+    let Some(from) = from else {
+        apply(target);
+        applied.set(Some(target));
+        return;
+    };
+    spawn(async move {
+        for step in volume_ramp(from, target, OUTPUT_VOLUME_RAMP_STEPS) {
+            if *ramp_generation.peek() != ramp_id {
+                return;
+            }
+            apply(step);
+            applied.set(Some(step));
+            output_volume_ramp_pause().await;
+        }
+    });
+}
+
+/// Fades shorter than this play the track out instead; the overlap would only sound clipped.
+const CROSSFADE_MIN_SECS: f64 = 0.5;
+/// Volume steps of a running crossfade.
+#[cfg(target_arch = "wasm32")]
+const CROSSFADE_STEP_MS: u64 = 50;
+
+thread_local! {
+    // The song the end-of-track handler moved on to and the length of its fade, if it has
+    // one, until the track sync loads it.
+    static PENDING_CROSSFADE: std::cell::RefCell<Option<(String, Option<f64>)>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// What the poll loop sees as the playing track nears its end: the queue and the modes that
+/// decide what follows it, and how far into it playback is.
+#[derive(Clone, Copy)]
+struct TransitionContext<'a> {
+    queue: &'a [Song],
+    queue_index: usize,
+    repeat: RepeatMode,
+    shuffle_enabled: bool,
+    stop_after: Option<&'a StopAfter>,
+    repeat_count_progress: Option<&'a (String, u32)>,
+    position: f64,
+    duration: f64,
+}
+
+/// Seconds of crossfade to start now that `song` is near its end, or `None` when the track
+/// should play out: crossfade is off, either track is a radio stream, repeat-one or a repeat
+/// count restarts the track, "stop after" ends playback here, or no queued track follows.
+fn crossfade_lead(
+    settings: &AppSettings,
+    context: &TransitionContext,
+    song: &Song,
+) -> Option<f64> {
+    let TransitionContext {
+        repeat,
+        position,
+        duration,
+        ..
+    } = *context;
+    if !settings.crossfade_enabled || song.server_name == "Radio" || repeat == RepeatMode::One {
+        return None;
+    }
+    let fade = f64::from(settings.crossfade_duration);
+    let remaining = seconds_until_track_end(settings, song, position, duration)?;
+    if remaining > fade || remaining < CROSSFADE_MIN_SECS || position < remaining {
+        return None;
+    }
+    if repeat == RepeatMode::Count
+        && repeat_count_replay(
+            context.repeat_count_progress,
+            &song.id,
+            settings.repeat_count,
+        )
+        .is_some()
+    {
+        return None;
+    }
+    let stops_here = context.stop_after.is_some_and(|armed| {
+        stop_after_reached(
+            armed,
+            context.queue,
+            context.queue_index,
+            repeat,
+            context.shuffle_enabled,
+            Some(song),
+        )
+    });
+    if stops_here {
+        return None;
+    }
+    match next_queue_advance(
+        context.queue,
+        context.queue_index,
+        repeat,
+        context.shuffle_enabled,
+        Some(song),
+    ) {
+        QueueAdvance::Index(next) => context
+            .queue
+            .get(next)
+            .filter(|next| next.server_name != "Radio" && next.id != song.id)
+            .filter(|next| !continuous_transition(settings, song, next))
+            .map(|_| remaining),
+        _ => None,
+    }
+}
+
+/// Marks the next load of `song` as the end-of-track handler's: it fades in over `secs` while
+/// the current track fades out, or with `None` starts as the current one ends.
+fn arm_crossfade(song: &Song, secs: Option<f64>) {
+    let key = queue_extension_song_key(song);
+    PENDING_CROSSFADE.with(|cell| *cell.borrow_mut() = Some((key, secs)));
+}
+
+/// Seconds of crossfade for a skip from `previous` to `song`, or `None` when the switch is a
+/// hard cut: crossfading skips is off, either track is a radio stream, repeat-one is on, the
+/// same track restarts, or both belong to one continuous album.
+fn skip_crossfade(
+    settings: &AppSettings,
+    previous: &Song,
+    song: &Song,
+    repeat: RepeatMode,
+) -> Option<f64> {
+    let fade = f64::from(settings.crossfade_duration);
+    let crossfades = settings.crossfade_enabled
+        && settings.crossfade_on_skip
+        && fade >= CROSSFADE_MIN_SECS
+        && repeat != RepeatMode::One
+        && previous.server_name != "Radio"
+        && song.server_name != "Radio"
+        && queue_extension_song_key(previous) != queue_extension_song_key(song)
+        && !continuous_transition(settings, previous, song);
+    crossfades.then_some(fade)
+}
+
+/// Seconds of crossfade to load `song` with after `previous`. A track the end-of-track handler
+/// armed fades as it decided; any other track change is a skip. Clears whatever was armed.
+fn track_change_crossfade(
+    settings: &AppSettings,
+    previous: Option<&Song>,
+    song: &Song,
+    repeat: RepeatMode,
+) -> Option<f64> {
+    let key = queue_extension_song_key(song);
+    let armed = PENDING_CROSSFADE.with(|cell| cell.borrow_mut().take());
+    match armed.filter(|(armed, _)| *armed == key) {
+        Some((_, secs)) => secs,
+        None => previous.and_then(|previous| skip_crossfade(settings, previous, song, repeat)),
+    }
+}
+
+/// Id of the element still playing the outgoing track during a web crossfade.
+#[cfg(target_arch = "wasm32")]
+const WEB_FADING_AUDIO_ID: &str = "rustysound-audio-fading";
+
+/// Hands the playing track over to a fading element, so the next one loads into a fresh
+/// player element. Returns false when nothing is playing to fade out.
+#[cfg(target_arch = "wasm32")]
+fn web_begin_crossfade() -> bool {
+    web_cancel_crossfade();
+    let Some(outgoing) = get_or_create_audio_element() else {
+        return false;
+    };
+    if outgoing.paused() {
+        return false;
+    }
+    outgoing.set_id(WEB_FADING_AUDIO_ID);
+    true
+}
+
+/// Stops the outgoing track of a running crossfade at once, e.g. when the user seeks.
+#[cfg(target_arch = "wasm32")]
+fn web_cancel_crossfade() {
+    let fading = window()
+        .and_then(|w| w.document())
+        .and_then(|doc| doc.get_element_by_id(WEB_FADING_AUDIO_ID))
+        .and_then(|el| el.dyn_into::<HtmlAudioElement>().ok());
+    if let Some(fading) = fading {
+        fading.set_id("");
+        let _ = fading.pause();
+        let _ = fading.remove_attribute("src");
+        fading.load();
+        fading.remove();
+    }
+}
+
+/// Ramps the new player element up to the output volume over `secs` while the outgoing track
+/// ramps down. Pausing, seeking or leaving `song` ends the fade early with the new track at
+/// full volume.
+#[cfg(target_arch = "wasm32")]
+fn web_run_crossfade(
+    secs: f64,
+    song: Song,
+    output_volume: Memo<f64>,
+    now_playing: Signal<Option<Song>>,
+    is_playing: Signal<bool>,
+) {
+    let document = window().and_then(|w| w.document());
+    let fading = document
+        .as_ref()
+        .and_then(|doc| doc.get_element_by_id(WEB_FADING_AUDIO_ID))
+        .and_then(|el| el.dyn_into::<HtmlAudioElement>().ok());
+    let (Some(incoming), Some(fading)) = (get_or_create_audio_element(), fading) else {
+        return;
+    };
+    let steps = ((secs * 1000.0) / CROSSFADE_STEP_MS as f64).ceil().max(1.0) as u32;
+    spawn(async move {
+        for step in 1..=steps {
+            gloo_timers::future::TimeoutFuture::new(CROSSFADE_STEP_MS as u32).await;
+            // A newer crossfade renamed this element and now owns both volumes.
+            if incoming.id() == WEB_FADING_AUDIO_ID {
+                return;
+            }
+            let still_fading = fading.id() == WEB_FADING_AUDIO_ID
+                && *is_playing.peek()
+                && now_playing
+                    .peek()
+                    .as_ref()
+                    .is_some_and(|current| current.id == song.id);
+            if !still_fading {
+                break;
+            }
+            // Both sides follow the output volume, so a volume change mid-fade lands where
+            // it would have without one.
+            let progress = f64::from(step) / f64::from(steps);
+            let volume = *output_volume.peek();
+            incoming.set_volume(volume * progress);
+            fading.set_volume(volume * (1.0 - progress));
+        }
+        if fading.id() == WEB_FADING_AUDIO_ID {
+            web_cancel_crossfade();
+        }
+        incoming.set_volume(*output_volume.peek());
+    });
+}
+
+/// Share of a track that plays before gapless playback buffers the one after it.
+const GAPLESS_PRELOAD_PROGRESS: f64 = 0.8;
+
+/// The track gapless playback should buffer now that `song` is at `position`: the next queued
+/// one, once `song` is past `GAPLESS_PRELOAD_PROGRESS` and what follows is fixed. Shuffle,
+/// repeat-one, a repeat count still replaying `song` and "stop after" leave `None`, and so does
+/// crossfade unless `song` and the next track share a continuous album.
+fn gapless_next_song<'a>(
+    settings: &AppSettings,
+    context: &TransitionContext<'a>,
+    song: &Song,
+) -> Option<&'a Song> {
+    let TransitionContext {
+        queue: queue_snapshot,
+        queue_index,
+        repeat,
+        shuffle_enabled,
+        stop_after,
+        repeat_count_progress,
+        position,
+        duration,
+    } = *context;
+    if !settings.gapless_enabled
+        || shuffle_enabled
+        || repeat == RepeatMode::One
+        || song.server_name == "Radio"
+    {
+        return None;
+    }
+    if !duration.is_finite() || duration <= 0.0 || position < duration * GAPLESS_PRELOAD_PROGRESS {
+        return None;
+    }
+    if repeat == RepeatMode::Count
+        && repeat_count_replay(repeat_count_progress, &song.id, settings.repeat_count).is_some()
+    {
+        return None;
+    }
+    let stops_here = stop_after.is_some_and(|armed| {
+        stop_after_reached(
+            armed,
+            queue_snapshot,
+            queue_index,
+            repeat,
+            shuffle_enabled,
+            Some(song),
+        )
+    });
+    if stops_here {
+        return None;
+    }
+    match next_queue_advance(
+        queue_snapshot,
+        queue_index,
+        repeat,
+        shuffle_enabled,
+        Some(song),
+    ) {
+        QueueAdvance::Index(next) => queue_snapshot
+            .get(next)
+            .filter(|next| next.server_name != "Radio" && next.id != song.id)
+            .filter(|next| {
+                !settings.crossfade_enabled || continuous_transition(settings, song, next)
+            }),
+        _ => None,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+thread_local! {
+    // Keys of the song playing and of the one buffered to follow it, and whether the bridge
+    // starts the buffered one by itself when the playing one ends.
+    static NATIVE_GAPLESS_PRELOAD: std::cell::RefCell<Option<(String, String, bool)>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Has the bridge buffer `next` on its spare element and start it the moment `current` ends,
+/// before the poll loop has seen the end. With no `next` any longer (a seek back, a repeat,
+/// shuffle or "stop after" changed what follows) the buffer stays but doesn't start by itself.
+#[cfg(not(target_arch = "wasm32"))]
+fn native_prepare_gapless(
+    current: &Song,
+    next: Option<&Song>,
+    servers: &[ServerConfig],
+    settings: &AppSettings,
+) {
+    if !NATIVE_CROSSFADE_SUPPORTED {
+        return;
+    }
+    let current_key = queue_extension_song_key(current);
+    let Some(next) = next else {
+        let disarm = NATIVE_GAPLESS_PRELOAD.with(|cell| match cell.borrow_mut().as_mut() {
+            Some((playing, _, armed)) if *armed && *playing == current_key => {
+                *armed = false;
+                true
+            }
+            _ => false,
+        });
+        if disarm {
+            native_audio_command(serde_json::json!({ "type": "preload", "handoff": false }));
+        }
+        return;
+    };
+    let pending = (current_key, queue_extension_song_key(next), true);
+    if NATIVE_GAPLESS_PRELOAD.with(|cell| cell.borrow().as_ref() == Some(&pending)) {
+        return;
+    }
+    let transcode = stream_transcode(settings);
+    if let Some(src) = resolve_stream_url(next, servers, settings.offline_mode, transcode) {
+        native_audio_command(serde_json::json!({
+            "type": "preload",
+            "src": src,
+            "handoff": true,
+            "position": song_start_offset(settings, next),
+        }));
+        NATIVE_GAPLESS_PRELOAD.with(|cell| *cell.borrow_mut() = Some(pending));
+    }
+}
+
+/// Forgets the last gapless buffer once a track loads, so the same pair buffers again if it
+/// comes round after a skip dropped it.
+#[cfg(not(target_arch = "wasm32"))]
+fn native_forget_gapless() {
+    NATIVE_GAPLESS_PRELOAD.with(|cell| *cell.borrow_mut() = None);
+}
+
+/// Id of the element the next track is buffered on for gapless playback.
+#[cfg(target_arch = "wasm32")]
+const WEB_GAPLESS_AUDIO_ID: &str = "rustysound-audio-gapless";
+
+#[cfg(target_arch = "wasm32")]
+thread_local! {
+    // Keys of the playing song and of the song buffered to follow it.
+    static WEB_GAPLESS_STANDBY: std::cell::RefCell<Option<(String, String)>> =
+        const { std::cell::RefCell::new(None) };
+    // Set when the end of the first song started the second on the standby element, until
+    // the track sync sees the second load.
+    static WEB_GAPLESS_HANDOFF: std::cell::RefCell<Option<(String, String)>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// What becomes of the web standby element when the pair it holds is `armed` and `next` is
+/// what should follow `current` now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+enum GaplessStandbyChange {
+    Keep,
+    Buffer,
+    /// Nothing follows `current` gaplessly any more, so its standby mustn't start.
+    Clear,
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+fn gapless_standby_change(
+    armed: Option<&(String, String)>,
+    current: &str,
+    next: Option<&str>,
+) -> GaplessStandbyChange {
+    match (armed, next) {
+        (Some((playing, buffered)), Some(next)) if playing == current && buffered == next => {
+            GaplessStandbyChange::Keep
+        }
+        (_, Some(_)) => GaplessStandbyChange::Buffer,
+        (Some((playing, _)), None) if playing == current => GaplessStandbyChange::Clear,
+        (_, None) => GaplessStandbyChange::Keep,
+    }
+}
+
+/// Buffers `next` on the standby element, which takes over the moment `current` ends. With
+/// no `next` any longer (a seek back, repeat-one, shuffle, "stop after" or a queue edit
+/// changed what follows) a standby armed for `current` is dropped, so its end plays on as
+/// the queue says.
+#[cfg(target_arch = "wasm32")]
+fn web_prepare_gapless(
+    current: &Song,
+    next: Option<&Song>,
+    servers: &[ServerConfig],
+    settings: &AppSettings,
+) {
+    let current_key = queue_extension_song_key(current);
+    let next_key = next.map(queue_extension_song_key);
+    let change = WEB_GAPLESS_STANDBY.with(|cell| {
+        gapless_standby_change(cell.borrow().as_ref(), &current_key, next_key.as_deref())
+    });
+    match change {
+        GaplessStandbyChange::Keep => return,
+        GaplessStandbyChange::Clear => {
+            WEB_GAPLESS_STANDBY.with(|cell| *cell.borrow_mut() = None);
+            web_unload_gapless_standby();
+            return;
+        }
+        GaplessStandbyChange::Buffer => {}
+    }
+    let (Some(next), Some(next_key)) = (next, next_key) else {
+        return;
+    };
+    let pending = (current_key, next_key);
+    let Some(url) = web_fetched_ahead_url(next)
+        .or_else(|| resolve_stream_url(next, servers, stream_transcode(settings)))
+    else {
+        return;
+    };
+    let (Some(document), Some(player)) = (
+        window().and_then(|w| w.document()),
+        get_or_create_audio_element(),
+    ) else {
+        return;
+    };
+    let standby = document
+        .get_element_by_id(WEB_GAPLESS_AUDIO_ID)
+        .and_then(|el| el.dyn_into::<HtmlAudioElement>().ok())
+        .or_else(|| create_audio_element(&document, WEB_GAPLESS_AUDIO_ID));
+    let Some(standby) = standby.map(|standby| web_equalizer_route(standby, next)) else {
+        return;
+    };
+    let _ = standby.set_attribute("preload", "auto");
+    standby.set_src(&url);
+    standby.load();
+    // One listener per player element; it does nothing unless a standby is pending.
+    if !player.has_attribute("data-gapless") {
+        let _ = player.set_attribute("data-gapless", "true");
+        let on_ended = Closure::wrap(Box::new(web_gapless_handoff) as Box<dyn FnMut()>);
+        let _ = player
+            .add_event_listener_with_callback("ended", on_ended.as_ref().unchecked_ref());
+        on_ended.forget();
+    }
+    WEB_GAPLESS_STANDBY.with(|cell| *cell.borrow_mut() = Some(pending));
+}
+
+/// `ended` handler of the player element: starts the buffered track straight away and makes
+/// its element the player element. Runs outside the poll loop, so the swap has no tick of
+/// silence before it.
+#[cfg(target_arch = "wasm32")]
+fn web_gapless_handoff() {
+    let Some(pending) = WEB_GAPLESS_STANDBY.with(|cell| cell.borrow_mut().take()) else {
+        return;
+    };
+    let standby = window()
+        .and_then(|w| w.document())
+        .and_then(|doc| doc.get_element_by_id(WEB_GAPLESS_AUDIO_ID))
+        .and_then(|el| el.dyn_into::<HtmlAudioElement>().ok());
+    let (Some(standby), Some(player)) = (standby, get_or_create_audio_element()) else {
+        return;
+    };
+    standby.set_volume(player.volume());
+    player.set_id("");
+    let _ = player.remove_attribute("src");
+    player.load();
+    player.remove();
+    standby.set_id(WEB_PLAYER_AUDIO_ID);
+    let _ = standby.play();
+    WEB_GAPLESS_HANDOFF.with(|cell| *cell.borrow_mut() = Some(pending));
+}
+
+/// Whether the end of `song` already started the track buffered after it.
+#[cfg(target_arch = "wasm32")]
+fn web_gapless_handed_off(song: &Song) -> bool {
+    let key = queue_extension_song_key(song);
+    WEB_GAPLESS_HANDOFF.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .is_some_and(|(finished, _)| *finished == key)
+    })
+}
+
+/// Whether `song` is already playing because gapless playback started it. Clears the
+/// handoff either way, so a different track the queue moved to loads normally.
+#[cfg(target_arch = "wasm32")]
+fn web_take_gapless_start(song: &Song) -> bool {
+    let key = queue_extension_song_key(song);
+    WEB_GAPLESS_HANDOFF
+        .with(|cell| cell.borrow_mut().take())
+        .is_some_and(|(_, started)| started == key)
+}
+
+/// Drops a standby buffered to follow some song other than `song`, so the end of whatever
+/// plays now doesn't start it.
+#[cfg(target_arch = "wasm32")]
+fn web_drop_gapless_standby(song: &Song) {
+    let key = queue_extension_song_key(song);
+    let stale = WEB_GAPLESS_STANDBY.with(|cell| {
+        let mut pending = cell.borrow_mut();
+        let stale = pending.as_ref().is_some_and(|(current, _)| *current != key);
+        if stale {
+            *pending = None;
+        }
+        stale
+    });
+    if stale {
+        web_unload_gapless_standby();
+    }
+}
+
+/// Empties the standby element so nothing is left buffered on it.
+#[cfg(target_arch = "wasm32")]
+fn web_unload_gapless_standby() {
+    let standby = window()
+        .and_then(|w| w.document())
+        .and_then(|doc| doc.get_element_by_id(WEB_GAPLESS_AUDIO_ID))
+        .and_then(|el| el.dyn_into::<HtmlAudioElement>().ok());
+    if let Some(standby) = standby {
+        let _ = standby.remove_attribute("src");
+        standby.load();
     }
 }
@@ -1,4 +1,5 @@
 // Shared imports, state primitives, and browser-specific helper utilities.
+use dioxus::core::spawn_forever;
 use dioxus::prelude::*;
 
 #[cfg(target_arch = "wasm32")]
@@ -7,19 +8,33 @@ use crate::api::*;
 use crate::api::*;
 #[cfg(target_arch = "wasm32")]
 use crate::components::{
-    PlaybackPositionSignal, PreviewPlaybackSignal, SeekRequestSignal, VolumeSignal,
+    PlaybackPositionSignal, PreviewPlaybackSignal, QuietHoursController, SeekRequestSignal,
+    VolumeSignal,
 };
 #[cfg(not(target_arch = "wasm32"))]
 use crate::components::{
-    PlaybackPositionSignal, PreviewPlaybackSignal, SeekRequestSignal, VolumeSignal,
+    PlaybackPositionSignal, PreviewPlaybackSignal, QuietHoursController, SeekRequestSignal,
+    VolumeSignal,
 };
+use crate::continuous_albums::continuous_transition;
 #[cfg(target_arch = "wasm32")]
-use crate::db::{AppSettings, RepeatMode};
+use crate::equalizer::{normalized_band_gains, EQ_BAND_FREQUENCIES_HZ};
+use crate::components::{next_available_index, unavailable_skip_notice, UnavailableSongsSignal};
+#[cfg(target_arch = "wasm32")]
+use crate::db::{record_listening_play, AppSettings, BufferingStrategy, RepeatMode};
 #[cfg(not(target_arch = "wasm32"))]
-use crate::db::{AppSettings, RepeatMode};
+use crate::db::{record_listening_play, AppSettings, BufferingStrategy, RepeatMode};
+use crate::db::{
+    clear_container_resume_point, is_skip, record_listening_skip, save_playback_state,
+    ArtistStation, PlaybackState, ResumeContainerKind, SkipShuffleWeights, STREAM_FORMATS,
+};
+use crate::network_policy::{allows_url, strict_privacy_enabled};
+use crate::quiet_hours::{effective_output_volume, volume_ramp};
+use crate::replay_gain::replay_gain_multiplier;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::offline_audio::{
-    cached_audio_url, is_song_downloaded, prefetch_song_audio_with_origin, DownloadOrigin,
+    cached_audio_url, is_song_downloaded, prefetch_song_audio_with_origin,
+    set_download_queue_boost, set_downloads_held_for_streaming, DownloadOrigin,
 };
 
 #[cfg(target_arch = "wasm32")]
@@ -56,7 +71,10 @@ use std::sync::{
     Arc, Mutex,
 };
 #[cfg(all(not(target_arch = "wasm32"), target_os = "ios"))]
-use std::sync::{Mutex, Once};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex, Once,
+};
 #[cfg(all(not(target_arch = "wasm32"), target_os = "ios"))]
 use std::time::{SystemTime, UNIX_EPOCH};
 #[cfg(target_arch = "wasm32")]
@@ -87,8 +105,18 @@ pub struct AudioState {
     pub current_time: Signal<f64>,
     pub duration: Signal<f64>,
     pub playback_error: Signal<Option<String>>,
+    /// Short-lived message shown above the player, e.g. when a missing track was skipped.
+    pub playback_notice: Signal<Option<String>>,
+    /// Set while a dropped stream is being reloaded after a network error.
+    pub stream_reconnecting: Signal<bool>,
     pub is_transport_loading: Signal<bool>,
     pub transport_loading_label: Signal<Option<String>>,
+    /// Speed the player runs at: the saved speed, or 1x while radio plays.
+    pub playback_rate: Signal<f64>,
+    /// A and B points of the loop in the current track, in seconds. Playback jumps back to
+    /// A on reaching B while both are set; switching songs clears them.
+    pub loop_start: Signal<Option<f64>>,
+    pub loop_end: Signal<Option<f64>>,
     #[allow(dead_code)]
     pub is_initialized: Signal<bool>,
 }
@@ -99,13 +127,84 @@ impl Default for AudioState {
             current_time: Signal::new(0.0),
             duration: Signal::new(0.0),
             playback_error: Signal::new(None),
+            playback_notice: Signal::new(None),
+            stream_reconnecting: Signal::new(false),
             is_transport_loading: Signal::new(false),
             transport_loading_label: Signal::new(None),
+            playback_rate: Signal::new(1.0),
+            loop_start: Signal::new(None),
+            loop_end: Signal::new(None),
             is_initialized: Signal::new(false),
         }
     }
 }
 
+/// How far playback continues once "stop after" is armed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopAfterMode {
+    Track,
+    Album,
+    /// The playlist or collection the queue was started from, or the whole queue.
+    Queue,
+}
+
+impl StopAfterMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            StopAfterMode::Track => "Current track",
+            StopAfterMode::Album => "Current album",
+            StopAfterMode::Queue => "End of queue",
+        }
+    }
+}
+
+/// A one-shot "stop after" request, anchored to the song that was playing when it was armed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StopAfter {
+    pub mode: StopAfterMode,
+    pub anchor: Song,
+}
+
+impl StopAfter {
+    pub fn new(mode: StopAfterMode, anchor: Song) -> Self {
+        Self { mode, anchor }
+    }
+
+    /// Whether `song` still lies inside the armed boundary.
+    pub fn covers(&self, song: &Song) -> bool {
+        if song.server_id != self.anchor.server_id {
+            return false;
+        }
+        match self.mode {
+            StopAfterMode::Track => song.id == self.anchor.id,
+            StopAfterMode::Album => match (&self.anchor.album_id, &song.album_id) {
+                (Some(anchor_album), Some(album)) => anchor_album == album,
+                _ => self.anchor.album.is_some() && song.album == self.anchor.album,
+            },
+            StopAfterMode::Queue => match (&self.anchor.queue_meta, &song.queue_meta) {
+                (Some(anchor_meta), Some(meta)) => anchor_meta.group_id == meta.group_id,
+                (Some(_), None) => false,
+                (None, _) => true,
+            },
+        }
+    }
+
+    /// Chip text shown in the player while armed.
+    pub fn indicator_label(&self) -> &'static str {
+        match self.mode {
+            StopAfterMode::Track => "Stopping after this track",
+            StopAfterMode::Album => "Stopping after this album",
+            StopAfterMode::Queue => "Stopping at end of queue",
+        }
+    }
+}
+
+thread_local! {
+    // When the current transport load began, used to report time-to-first-audio.
+    static TRANSPORT_LOAD_STARTED: std::cell::Cell<Option<crate::diagnostics::PerfTimer>> =
+        const { std::cell::Cell::new(None) };
+}
+
 fn set_transport_loading(mut audio_state: Signal<AudioState>, loading: bool, message: Option<&str>) {
     let mut state = audio_state.write();
     let next_label = if loading {
@@ -118,22 +217,79 @@ fn set_transport_loading(mut audio_state: Signal<AudioState>, loading: bool, mes
     {
         return;
     }
+    if loading {
+        if !*state.is_transport_loading.peek() {
+            TRANSPORT_LOAD_STARTED.with(|started| {
+                started.set(Some(crate::diagnostics::PerfTimer::now()))
+            });
+        }
+    } else if let Some(started) = TRANSPORT_LOAD_STARTED.with(|started| started.take()) {
+        let label = state
+            .transport_loading_label
+            .peek()
+            .clone()
+            .unwrap_or_default();
+        crate::diagnostics::log_perf("playback.start", started, &label);
+        ios_diag_log(
+            "playback.start",
+            &format!("took {}ms label={label}", started.elapsed_ms()),
+        );
+    }
     state.is_transport_loading.set(loading);
     state.transport_loading_label.set(next_label);
 }
 
+/// Id of the element the web player plays through.
+#[cfg(target_arch = "wasm32")]
+const WEB_PLAYER_AUDIO_ID: &str = "rustysound-audio";
+
 /// Initialize the global audio element once.
 #[cfg(target_arch = "wasm32")]
 pub fn get_or_create_audio_element() -> Option<HtmlAudioElement> {
     let document = window()?.document()?;
 
-    if let Some(existing) = document.get_element_by_id("rustysound-audio") {
+    if let Some(existing) = document.get_element_by_id(WEB_PLAYER_AUDIO_ID) {
         return existing.dyn_into::<HtmlAudioElement>().ok();
     }
 
+    create_audio_element(&document, WEB_PLAYER_AUDIO_ID)
+}
+
+#[cfg(target_arch = "wasm32")]
+thread_local! {
+    /// Speed every player element plays at, including ones created for crossfades and
+    /// gapless playback.
+    static WEB_PLAYBACK_RATE: Cell<f64> = const { Cell::new(1.0) };
+}
+
+/// Sets the speed of the player element and of any track buffered next. As the default
+/// rate, it also survives the element loading another source.
+#[cfg(target_arch = "wasm32")]
+fn web_set_playback_rate(rate: f64) {
+    WEB_PLAYBACK_RATE.with(|current| current.set(rate));
+    let Some(document) = window().and_then(|window| window.document()) else {
+        return;
+    };
+    for id in [WEB_PLAYER_AUDIO_ID, WEB_GAPLESS_AUDIO_ID] {
+        if let Some(audio) = document
+            .get_element_by_id(id)
+            .and_then(|element| element.dyn_into::<HtmlAudioElement>().ok())
+        {
+            audio.set_default_playback_rate(rate);
+            audio.set_playback_rate(rate);
+        }
+    }
+}
+
+/// Appends a hidden audio element with the player's attributes to the page.
+#[cfg(target_arch = "wasm32")]
+fn create_audio_element(document: &web_sys::Document, id: &str) -> Option<HtmlAudioElement> {
     let audio: HtmlAudioElement = document.create_element("audio").ok()?.dyn_into().ok()?;
-    audio.set_id("rustysound-audio");
+    audio.set_id(id);
     audio.set_attribute("preload", "metadata").ok()?;
+    let rate = WEB_PLAYBACK_RATE.with(Cell::get);
+    audio.set_default_playback_rate(rate);
+    audio.set_playback_rate(rate);
     let _ = audio.set_attribute("playsinline", "true");
     let _ = audio.set_attribute("webkit-playsinline", "true");
     let _ = audio.set_attribute("x-webkit-airplay", "allow");
@@ -148,17 +304,35 @@ pub fn get_or_create_audio_element() -> Option<()> {
     None
 }
 
+/// Interval of the web controller's playback poll loop.
 #[cfg(target_arch = "wasm32")]
-fn web_playback_error_message(audio: &HtmlAudioElement, song: Option<&Song>) -> Option<String> {
+const WEB_POLL_TICK_MS: u64 = 200;
+
+/// `MediaError.code` for a network failure while fetching the media.
+#[cfg(target_arch = "wasm32")]
+const WEB_MEDIA_ERR_NETWORK: u16 = 2;
+/// What browsers report when the server refuses the source, e.g. with a 403.
+#[cfg(target_arch = "wasm32")]
+const WEB_MEDIA_ERR_SRC_NOT_SUPPORTED: u16 = 4;
+
+#[cfg(target_arch = "wasm32")]
+fn web_playback_error_code(audio: &HtmlAudioElement) -> Option<u16> {
     let audio_js = wasm_bindgen::JsValue::from(audio.clone());
     let error_js = js_sys::Reflect::get(&audio_js, &"error".into()).ok()?;
     if error_js.is_null() || error_js.is_undefined() {
         return None;
     }
-    let code = js_sys::Reflect::get(&error_js, &"code".into())
-        .ok()
-        .and_then(|value| value.as_f64())
-        .unwrap_or(0.0) as u16;
+    Some(
+        js_sys::Reflect::get(&error_js, &"code".into())
+            .ok()
+            .and_then(|value| value.as_f64())
+            .unwrap_or(0.0) as u16,
+    )
+}
+
+#[cfg(target_arch = "wasm32")]
+fn web_playback_error_message(audio: &HtmlAudioElement, song: Option<&Song>) -> Option<String> {
+    let code = web_playback_error_code(audio)?;
     let is_radio = song.map(|s| s.server_name == "Radio").unwrap_or(false);
     let station_name = song
         .and_then(|s| s.album.clone().or_else(|| s.artist.clone()))
@@ -167,7 +341,7 @@ fn web_playback_error_message(audio: &HtmlAudioElement, song: Option<&Song>) ->
 
     Some(match code {
         1 => "Playback was aborted before the stream loaded.".to_string(),
-        2 => {
+        WEB_MEDIA_ERR_NETWORK => {
             if is_radio {
                 format!("No station found: \"{station_name}\" is unreachable right now.")
             } else {
@@ -175,7 +349,7 @@ fn web_playback_error_message(audio: &HtmlAudioElement, song: Option<&Song>) ->
             }
         }
         3 => "Audio playback failed due to a decode error.".to_string(),
-        4 => {
+        WEB_MEDIA_ERR_SRC_NOT_SUPPORTED => {
             if is_radio {
                 format!("No station found: \"{station_name}\" has no supported stream source.")
             } else {
@@ -301,6 +475,14 @@ fn shortcut_action_from_key(event: &KeyboardEvent) -> Option<&'static str> {
     None
 }
 
+#[cfg(target_arch = "wasm32")]
+thread_local! {
+    /// Shortcuts the key handler found nothing to do for, with the reason, until the poll
+    /// loop shows it.
+    static WEB_IGNORED_SHORTCUTS: std::cell::RefCell<Vec<(&'static str, &'static str)>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
 #[cfg(target_arch = "wasm32")]
 fn click_player_control_button(id: &str) {
     if let Some(doc) = window().and_then(|w| w.document()) {
@@ -627,6 +809,9 @@ fn ios_diag_now_ms() -> u128 {
 #[cfg(any(target_arch = "wasm32", not(target_os = "ios")))]
 pub fn ios_diag_log(_tag: &str, _message: &str) {}
 
+#[cfg(target_arch = "wasm32")]
+const WEB_MEDIA_SESSION_ARTWORK_SIZES: [u32; 3] = [96, 256, 512];
+
 #[cfg(target_arch = "wasm32")]
 fn web_sync_media_session_metadata(song: Option<&Song>, servers: &[ServerConfig]) {
     let payload = if let Some(song) = song {
@@ -663,13 +848,23 @@ fn web_sync_media_session_metadata(song: Option<&Song>, servers: &[ServerConfig]
                 .unwrap_or_else(|| "Unknown Album".to_string())
         };
 
+        // Several sizes so notification surfaces (e.g. Android) can pick a sharp one.
         let artwork = servers
             .iter()
             .find(|server| server.id == song.server_id)
             .and_then(|server| {
-                song.cover_art
-                    .as_ref()
-                    .map(|cover| NavidromeClient::new(server.clone()).get_cover_art_url(cover, 512))
+                let client = NavidromeClient::new(server.clone());
+                song.cover_art.as_ref().map(|cover| {
+                    WEB_MEDIA_SESSION_ARTWORK_SIZES
+                        .iter()
+                        .map(|size| {
+                            serde_json::json!({
+                                "src": client.get_cover_art_url(cover, *size),
+                                "sizes": format!("{size}x{size}"),
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                })
             });
 
         serde_json::json!({
@@ -694,8 +889,8 @@ fn web_sync_media_session_metadata(song: Option<&Song>, servers: &[ServerConfig]
       return true;
     }}
 
-    const artwork = meta.artwork
-      ? [{{ src: meta.artwork, sizes: "512x512", type: "image/png" }}]
+    const artwork = Array.isArray(meta.artwork) && meta.artwork.length > 0
+      ? meta.artwork
       : undefined;
 
     navigator.mediaSession.metadata = new MediaMetadata({{
@@ -723,8 +918,9 @@ fn ensure_web_media_session_shortcuts() {
     return true;
   }
 
-  const audio = document.getElementById("rustysound-audio");
-  if (!audio) {
+  // Looked up on every use: a crossfade hands playback to a new player element.
+  const playerAudio = () => document.getElementById("rustysound-audio");
+  if (!playerAudio()) {
     return false;
   }
 
@@ -733,6 +929,27 @@ fn ensure_web_media_session_shortcuts() {
     return true;
   }
 
+  // Seeking mid-crossfade drops the outgoing track, as seeking from the player does.
+  const stopCrossfade = () => {
+    const fading = document.getElementById("rustysound-audio-fading");
+    if (!fading) return;
+    fading.id = "";
+    fading.pause();
+    fading.removeAttribute("src");
+    fading.load();
+    fading.remove();
+  };
+
+  const seekBy = (offset) => {
+    const audio = playerAudio();
+    if (!audio || !isSeekable()) return;
+    stopCrossfade();
+    try {
+      audio.currentTime = Math.max(0, Math.min(audio.duration, (audio.currentTime || 0) + offset));
+    } catch (_err) {}
+    updatePositionState();
+  };
+
   const clickById = (id) => {
     const element = document.getElementById(id);
     if (element && typeof element.click === "function") {
@@ -743,14 +960,28 @@ fn ensure_web_media_session_shortcuts() {
   };
 
   const updatePlaybackState = () => {
+    const audio = playerAudio();
+    if (!audio) return;
     try {
       navigator.mediaSession.playbackState = audio.paused ? "paused" : "playing";
     } catch (_err) {}
   };
 
+  const isSeekable = () => {
+    const audio = playerAudio();
+    return !!audio && Number.isFinite(audio.duration) && audio.duration > 0;
+  };
+
   const updatePositionState = () => {
     if (!navigator.mediaSession.setPositionState) return;
-    if (!Number.isFinite(audio.duration) || audio.duration <= 0) return;
+    if (!isSeekable()) {
+      // Live streams have no meaningful progress; clear any stale position.
+      try {
+        navigator.mediaSession.setPositionState();
+      } catch (_err) {}
+      return;
+    }
+    const audio = playerAudio();
     try {
       navigator.mediaSession.setPositionState({
         duration: audio.duration,
@@ -762,7 +993,8 @@ fn ensure_web_media_session_shortcuts() {
 
   try {
     navigator.mediaSession.setActionHandler("play", () => {
-      if (audio.paused) {
+      const audio = playerAudio();
+      if (audio && audio.paused) {
         if (!clickById("play-pause-btn")) {
           audio.play().catch(() => {});
         }
@@ -772,7 +1004,8 @@ fn ensure_web_media_session_shortcuts() {
   } catch (_err) {}
   try {
     navigator.mediaSession.setActionHandler("pause", () => {
-      if (!audio.paused) {
+      const audio = playerAudio();
+      if (audio && !audio.paused) {
         if (!clickById("play-pause-btn")) {
           audio.pause();
         }
@@ -788,7 +1021,9 @@ fn ensure_web_media_session_shortcuts() {
   } catch (_err) {}
   try {
     navigator.mediaSession.setActionHandler("seekto", (details) => {
-      if (details && typeof details.seekTime === "number") {
+      const audio = playerAudio();
+      if (audio && details && typeof details.seekTime === "number") {
+        stopCrossfade();
         try {
           audio.currentTime = Math.max(0, details.seekTime);
         } catch (_err) {}
@@ -796,12 +1031,56 @@ fn ensure_web_media_session_shortcuts() {
       }
     });
   } catch (_err) {}
+  try {
+    navigator.mediaSession.setActionHandler("seekforward", (details) => {
+      seekBy((details && details.seekOffset) || 10);
+    });
+  } catch (_err) {}
+  try {
+    navigator.mediaSession.setActionHandler("seekbackward", (details) => {
+      seekBy(-((details && details.seekOffset) || 10));
+    });
+  } catch (_err) {}
+  try {
+    navigator.mediaSession.setActionHandler("stop", () => {
+      const audio = playerAudio();
+      if (!audio) return;
+      if (!audio.paused) {
+        if (!clickById("play-pause-btn")) {
+          audio.pause();
+        }
+      }
+      if (isSeekable()) {
+        stopCrossfade();
+        try {
+          audio.currentTime = 0;
+        } catch (_err) {}
+      }
+      updatePlaybackState();
+      updatePositionState();
+    });
+  } catch (_err) {}
 
-  audio.addEventListener("play", updatePlaybackState);
-  audio.addEventListener("pause", updatePlaybackState);
-  audio.addEventListener("timeupdate", updatePositionState);
-  audio.addEventListener("durationchange", updatePositionState);
-  audio.addEventListener("ratechange", updatePositionState);
+  // Media events don't bubble, but capturing on the document still sees them and keeps
+  // following the player element across crossfades.
+  const onPlayerEvent = (type, handler) => {
+    document.addEventListener(
+      type,
+      (event) => {
+        if (event.target === playerAudio()) handler();
+      },
+      true
+    );
+  };
+  onPlayerEvent("play", updatePlaybackState);
+  onPlayerEvent("pause", updatePlaybackState);
+  onPlayerEvent("play", updatePositionState);
+  onPlayerEvent("pause", updatePositionState);
+  onPlayerEvent("seeked", updatePositionState);
+  onPlayerEvent("loadedmetadata", updatePositionState);
+  onPlayerEvent("timeupdate", updatePositionState);
+  onPlayerEvent("durationchange", updatePositionState);
+  onPlayerEvent("ratechange", updatePositionState);
   updatePlaybackState();
   updatePositionState();
 
@@ -811,3 +1090,63 @@ fn ensure_web_media_session_shortcuts() {
 "#,
     );
 }
+
+/// Pauses through the play/pause button when an audio output disappears (headphones
+/// unplugged, Bluetooth dropped), unless `__rustysoundPauseOnUnplug` was turned off.
+#[cfg(target_arch = "wasm32")]
+fn ensure_web_unplug_listener() {
+    let _ = js_sys::eval(
+        r#"
+(() => {
+  if (window.__rustysoundUnplugListenerInit) {
+    return true;
+  }
+  const devices = navigator.mediaDevices;
+  if (!devices || typeof devices.enumerateDevices !== "function") {
+    return false;
+  }
+  window.__rustysoundUnplugListenerInit = true;
+
+  const countOutputs = async () => {
+    try {
+      const list = await devices.enumerateDevices();
+      return list.filter((device) => device.kind === "audiooutput").length;
+    } catch (_err) {
+      return null;
+    }
+  };
+  let outputCount = null;
+  countOutputs().then((count) => {
+    outputCount = count;
+  });
+  devices.addEventListener("devicechange", async () => {
+    const count = await countOutputs();
+    const previous = outputCount;
+    outputCount = count;
+    if (count === null || previous === null || count >= previous) return;
+    if (window.__rustysoundPauseOnUnplug === false) return;
+    const audio = document.getElementById("rustysound-audio");
+    if (!audio || audio.paused) return;
+    const button = document.getElementById("play-pause-btn");
+    if (button) {
+      button.click();
+    } else {
+      audio.pause();
+    }
+  });
+  return true;
+})();
+"#,
+    );
+}
+
+#[cfg(target_arch = "wasm32")]
+fn set_web_pause_on_unplug(enabled: bool) {
+    if let Some(win) = window() {
+        let _ = js_sys::Reflect::set(
+            &win,
+            &wasm_bindgen::JsValue::from_str("__rustysoundPauseOnUnplug"),
+            &wasm_bindgen::JsValue::from_bool(enabled),
+        );
+    }
+}
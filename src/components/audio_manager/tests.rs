@@ -0,0 +1,573 @@
+//! Tests for the shared queue, stream and playback helpers.
+use super::*;
+
+fn song(id: &str) -> Song {
+    Song {
+        id: id.to_string(),
+        title: id.to_string(),
+        server_id: "server".to_string(),
+        server_name: "Server".to_string(),
+        ..Song::default()
+    }
+}
+
+#[test]
+fn peeked_next_matches_committed_next_in_queue() {
+    let queue = vec![song("a"), song("b"), song("c")];
+    let single = vec![song("solo")];
+    let radio = vec![Song {
+        server_name: "Radio".to_string(),
+        ..song("station")
+    }];
+    let cases = [
+        (
+            &queue,
+            0,
+            RepeatMode::Off,
+            false,
+            QueueAdvance::Index(1),
+            Some("b"),
+        ),
+        (
+            &queue,
+            1,
+            RepeatMode::All,
+            false,
+            QueueAdvance::Index(2),
+            Some("c"),
+        ),
+        (
+            &queue,
+            2,
+            RepeatMode::All,
+            false,
+            QueueAdvance::Index(0),
+            Some("a"),
+        ),
+        (&queue, 2, RepeatMode::Off, false, QueueAdvance::Stop, None),
+        // Shuffle only generates a similar queue after a lone, unsourced track.
+        (
+            &queue,
+            0,
+            RepeatMode::Off,
+            true,
+            QueueAdvance::Index(1),
+            Some("b"),
+        ),
+        (
+            &queue,
+            2,
+            RepeatMode::All,
+            true,
+            QueueAdvance::Index(0),
+            Some("a"),
+        ),
+        (&queue, 2, RepeatMode::Off, true, QueueAdvance::Stop, None),
+        (
+            &single,
+            0,
+            RepeatMode::All,
+            true,
+            QueueAdvance::Index(0),
+            Some("solo"),
+        ),
+        (&single, 0, RepeatMode::Off, false, QueueAdvance::Stop, None),
+        (&radio, 0, RepeatMode::Off, true, QueueAdvance::Stop, None),
+    ];
+    for (queue, idx, repeat, shuffle, advance, expected) in cases {
+        let current = queue.get(idx);
+        assert_eq!(
+            next_queue_advance(queue, idx, repeat, shuffle, current),
+            advance
+        );
+        let peeked = peek_next_song(queue, idx, repeat, shuffle, current);
+        assert_eq!(peeked.map(|song| song.id).as_deref(), expected);
+    }
+    for shuffle in [false, true] {
+        assert_eq!(
+            next_queue_advance(&[], 0, RepeatMode::All, shuffle, None),
+            QueueAdvance::Stop
+        );
+        assert_eq!(peek_next_song(&[], 0, RepeatMode::All, shuffle, None), None);
+    }
+}
+
+/// Answers every request with `body` as JSON and counts the requests it served.
+async fn serve_subsonic_json(
+    body: String,
+) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let served = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let counter = served.clone();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut request = Vec::new();
+            let mut chunk = [0u8; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                match stream.read(&mut chunk).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => request.extend_from_slice(&chunk[..read]),
+                }
+            }
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+    (url, served)
+}
+
+/// Polls the dom's tasks until `done` holds, or gives up after a few seconds.
+async fn drive_until(dom: &mut VirtualDom, mut done: impl FnMut() -> bool) {
+    for _ in 0..100 {
+        if done() {
+            return;
+        }
+        let _ =
+            tokio::time::timeout(std::time::Duration::from_millis(50), dom.wait_for_work()).await;
+        dom.render_immediate(&mut dioxus::dioxus_core::NoOpMutations);
+    }
+    panic!("background work did not finish");
+}
+
+#[tokio::test]
+async fn peeked_next_matches_committed_similar_queue() {
+    let body = serde_json::json!({
+        "subsonic-response": {
+            "status": "ok",
+            "similarSongs": {
+                "song": [
+                    { "id": "x", "title": "x" },
+                    { "id": "y", "title": "y" },
+                    { "id": "z", "title": "z" },
+                ]
+            }
+        }
+    })
+    .to_string();
+    let (url, served) = serve_subsonic_json(body).await;
+    let server = ServerConfig {
+        id: "server".to_string(),
+        ..ServerConfig::new(
+            "Server".to_string(),
+            url,
+            "user".to_string(),
+            "pass".to_string(),
+        )
+    };
+    let settings = AppSettings {
+        buffering_strategy: BufferingStrategy::Minimal,
+        ..AppSettings::default()
+    };
+    let seed = song("seed");
+    let queue = vec![seed.clone()];
+    assert_eq!(
+        next_queue_advance(&queue, 0, RepeatMode::Off, true, Some(&seed)),
+        QueueAdvance::GenerateSimilar
+    );
+    assert_eq!(
+        peek_next_song(&queue, 0, RepeatMode::Off, true, Some(&seed)),
+        None
+    );
+
+    let mut dom = VirtualDom::new(|| rsx! {});
+    dom.rebuild_in_place();
+
+    dom.in_scope(ScopeId::APP, || {
+        spawn_shuffle_queue_peek(vec![server.clone()], settings, Some(seed.clone()))
+    });
+    drive_until(&mut dom, || {
+        peek_next_song(&queue, 0, RepeatMode::Off, true, Some(&seed)).is_some()
+    })
+    .await;
+    let peeked = peek_next_song(&queue, 0, RepeatMode::Off, true, Some(&seed));
+    let fetches_for_peek = served.load(std::sync::atomic::Ordering::SeqCst);
+
+    let (installed_queue, now_playing) = dom.in_scope(ScopeId::APP, || {
+        let queue = Signal::new(Vec::new());
+        let now_playing = Signal::new(None);
+        spawn_shuffle_queue(
+            vec![server],
+            queue,
+            Signal::new(0),
+            now_playing,
+            Signal::new(false),
+            Signal::new(AudioState::default()),
+            Some(seed.clone()),
+            Some(true),
+        );
+        (queue, now_playing)
+    });
+    drive_until(&mut dom, || now_playing.peek().is_some()).await;
+
+    assert!(peeked.is_some());
+    assert_eq!(*now_playing.peek(), peeked);
+    assert_eq!(installed_queue.peek().first().cloned(), peeked);
+    // The commit used the peeked queue instead of asking the server again.
+    assert_eq!(
+        served.load(std::sync::atomic::Ordering::SeqCst),
+        fetches_for_peek
+    );
+    assert!(!has_peeked_shuffle_queue(Some(&seed)));
+}
+
+#[test]
+fn gapless_standby_is_dropped_once_nothing_follows_gaplessly() {
+    let armed = ("s:a".to_string(), "s:b".to_string());
+    let change = |armed, current, next| gapless_standby_change(armed, current, next);
+    assert_eq!(
+        change(Some(&armed), "s:a", Some("s:b")),
+        GaplessStandbyChange::Keep
+    );
+    assert_eq!(
+        change(Some(&armed), "s:a", Some("s:c")),
+        GaplessStandbyChange::Buffer
+    );
+    assert_eq!(
+        change(None, "s:a", Some("s:b")),
+        GaplessStandbyChange::Buffer
+    );
+    // Repeat-one, shuffle, "stop after" or a queue edit left nothing to follow "a".
+    assert_eq!(
+        change(Some(&armed), "s:a", None),
+        GaplessStandbyChange::Clear
+    );
+    // A standby for another song is for the track sync to drop.
+    assert_eq!(
+        change(Some(&armed), "s:c", None),
+        GaplessStandbyChange::Keep
+    );
+    assert_eq!(change(None, "s:a", None), GaplessStandbyChange::Keep);
+}
+
+#[test]
+fn restored_queue_resumes_at_the_saved_song_or_the_next_kept_one() {
+    assert_eq!(restored_queue_index(&[0, 1, 2], 1), Some((1, true)));
+    // The saved song's server is gone: the next song that came back plays instead.
+    assert_eq!(restored_queue_index(&[0, 2, 3], 1), Some((1, false)));
+    assert_eq!(restored_queue_index(&[0, 1], 3), Some((1, false)));
+    assert_eq!(restored_queue_index(&[], 0), None);
+}
+
+#[test]
+fn ignored_transport_actions_explain_why() {
+    let queue = vec![song("a"), song("b")];
+    let reason = |action: &str, idx: usize, repeat: RepeatMode, current: Option<&Song>| {
+        ignored_transport_action_reason(action, &queue, idx, repeat, false, current)
+    };
+    assert_eq!(reason("next", 0, RepeatMode::Off, queue.get(0)), None);
+    assert_eq!(
+        reason("next", 1, RepeatMode::Off, queue.get(1)),
+        Some("End of queue")
+    );
+    assert_eq!(reason("next", 1, RepeatMode::All, queue.get(1)), None);
+    assert_eq!(reason("previous", 0, RepeatMode::Off, queue.get(0)), None);
+    assert_eq!(
+        reason("toggle_play", 0, RepeatMode::Off, None),
+        Some("Nothing is queued")
+    );
+    assert_eq!(reason("seek:12", 0, RepeatMode::Off, None), None);
+
+    let radio = Song {
+        server_name: "Radio".to_string(),
+        ..song("station")
+    };
+    assert_eq!(
+        reason("previous", 0, RepeatMode::Off, Some(&radio)),
+        Some("Skipping isn't available for radio")
+    );
+    assert_eq!(
+        reason("toggle_play", 0, RepeatMode::Off, Some(&radio)),
+        None
+    );
+}
+
+#[test]
+fn stop_after_album_stops_at_the_album_boundary() {
+    let album_song = |id: &str, album_id: &str| Song {
+        album_id: Some(album_id.to_string()),
+        ..song(id)
+    };
+    let queue = vec![
+        album_song("a1", "a"),
+        album_song("a2", "a"),
+        album_song("b1", "b"),
+    ];
+    let stop_after = StopAfter::new(StopAfterMode::Album, queue[0].clone());
+    let reached = |idx: usize, repeat: RepeatMode| {
+        stop_after_reached(&stop_after, &queue, idx, repeat, false, queue.get(idx))
+    };
+    assert!(!reached(0, RepeatMode::Off));
+    assert!(reached(1, RepeatMode::Off));
+
+    // Wrapping around with repeat-all leaves the queue even within one album.
+    let single_album = &queue[..2];
+    let stop_after_queue = StopAfter::new(StopAfterMode::Queue, queue[0].clone());
+    assert!(stop_after_reached(
+        &stop_after_queue,
+        single_album,
+        1,
+        RepeatMode::All,
+        false,
+        single_album.get(1)
+    ));
+}
+
+#[test]
+fn container_finishes_on_its_last_queued_entry() {
+    let album = assign_collection_queue_meta(
+        vec![song("t1"), song("t2")],
+        QueueSourceKind::Album,
+        "server::album".to_string(),
+    );
+    let mut queue = album.clone();
+    queue.push(song("loose"));
+    assert!(!container_finished(&queue, &album[0]));
+    assert!(container_finished(&queue, &album[1]));
+    assert!(!container_finished(&queue, &queue[2]));
+}
+
+#[test]
+fn repeat_count_replays_until_the_track_played_count_times() {
+    let first = repeat_count_replay(None, "a", 3);
+    assert_eq!(first, Some(("a".to_string(), 1)));
+    let second = repeat_count_replay(first.as_ref(), "a", 3);
+    assert_eq!(second, Some(("a".to_string(), 2)));
+    assert_eq!(repeat_count_replay(second.as_ref(), "a", 3), None);
+
+    // Another track starts its own count.
+    assert_eq!(
+        repeat_count_replay(second.as_ref(), "b", 3),
+        Some(("b".to_string(), 1))
+    );
+    assert_eq!(repeat_count_replay(None, "a", 1), None);
+}
+
+#[test]
+fn expired_stream_urls_are_only_401_and_403() {
+    assert!(is_expired_stream_status(401));
+    assert!(is_expired_stream_status(403));
+    assert!(!is_expired_stream_status(404));
+    assert!(!is_expired_stream_status(500));
+}
+
+#[test]
+fn stream_url_is_regenerated_once_per_failure() {
+    let mut refresh = StreamUrlRefreshState::default();
+    assert!(refresh.should_check("a"));
+    // Further ticks of the same failure don't ask the server again.
+    assert!(!refresh.should_check("a"));
+    assert!(refresh.should_regenerate(42.0));
+
+    // The fresh URL is refused too before playback moved: surface it.
+    refresh.note_healthy("a", 42.0);
+    assert!(refresh.should_check("a"));
+    assert!(!refresh.should_regenerate(42.0));
+}
+
+#[test]
+fn stream_url_refresh_rearms_after_playback_recovers() {
+    let mut refresh = StreamUrlRefreshState::default();
+    assert!(refresh.should_check("a"));
+    assert!(refresh.should_regenerate(10.0));
+    refresh.refused = Some(403);
+
+    refresh.note_healthy("a", 30.0);
+    assert_eq!(refresh.refused, None);
+    assert!(refresh.should_check("a"));
+    assert!(refresh.should_regenerate(30.0));
+}
+
+#[test]
+fn stream_url_refresh_starts_over_for_the_next_song() {
+    let mut refresh = StreamUrlRefreshState::default();
+    assert!(refresh.should_check("a"));
+    assert!(refresh.should_regenerate(0.0));
+    assert!(refresh.should_check("b"));
+    assert!(refresh.should_regenerate(0.0));
+}
+
+/// `queue` playing at `queue_index`, `position` seconds into a 200-second track, with nothing
+/// armed.
+fn transition_at(
+    queue: &[Song],
+    queue_index: usize,
+    repeat: RepeatMode,
+    shuffle_enabled: bool,
+    position: f64,
+) -> TransitionContext<'_> {
+    TransitionContext {
+        queue,
+        queue_index,
+        repeat,
+        shuffle_enabled,
+        stop_after: None,
+        repeat_count_progress: None,
+        position,
+        duration: 200.0,
+    }
+}
+
+#[test]
+fn crossfade_starts_only_before_a_queued_track() {
+    let mut settings = AppSettings::default();
+    settings.crossfade_enabled = true;
+    settings.crossfade_duration = 4;
+    let queue = vec![song("a"), song("b")];
+    let lead = |settings: &AppSettings, index: usize, repeat, position| {
+        crossfade_lead(
+            settings,
+            &transition_at(&queue, index, repeat, false, position),
+            &queue[index],
+        )
+    };
+
+    assert_eq!(lead(&settings, 0, RepeatMode::Off, 197.0), Some(3.0));
+    assert_eq!(lead(&settings, 0, RepeatMode::Off, 190.0), None);
+    assert_eq!(lead(&settings, 0, RepeatMode::One, 197.0), None);
+    assert_eq!(lead(&settings, 1, RepeatMode::Off, 197.0), None);
+    assert_eq!(lead(&settings, 1, RepeatMode::All, 197.0), Some(3.0));
+
+    settings.crossfade_enabled = false;
+    assert_eq!(lead(&settings, 0, RepeatMode::Off, 197.0), None);
+}
+
+#[test]
+fn gapless_buffers_the_next_track_past_four_fifths() {
+    let mut settings = AppSettings::default();
+    settings.gapless_enabled = true;
+    let queue = vec![song("a"), song("b")];
+    let next = |settings: &AppSettings, index: usize, repeat, shuffle, position| {
+        gapless_next_song(
+            settings,
+            &transition_at(&queue, index, repeat, shuffle, position),
+            &queue[index],
+        )
+        .map(|song| song.id.clone())
+    };
+
+    assert_eq!(
+        next(&settings, 0, RepeatMode::Off, false, 160.0).as_deref(),
+        Some("b")
+    );
+    assert_eq!(next(&settings, 0, RepeatMode::Off, false, 150.0), None);
+    assert_eq!(next(&settings, 0, RepeatMode::One, false, 160.0), None);
+    assert_eq!(next(&settings, 0, RepeatMode::Off, true, 160.0), None);
+    assert_eq!(next(&settings, 1, RepeatMode::Off, false, 190.0), None);
+    assert_eq!(
+        next(&settings, 1, RepeatMode::All, false, 190.0).as_deref(),
+        Some("a")
+    );
+
+    settings.crossfade_enabled = true;
+    assert_eq!(next(&settings, 0, RepeatMode::Off, false, 160.0), None);
+    settings.crossfade_enabled = false;
+    settings.gapless_enabled = false;
+    assert_eq!(next(&settings, 0, RepeatMode::Off, false, 160.0), None);
+}
+
+#[test]
+fn continuous_albums_play_gapless_without_crossfading() {
+    let mut settings = AppSettings::default();
+    settings.crossfade_enabled = true;
+    settings.gapless_enabled = true;
+    let live = |id: &str| Song {
+        album_id: Some("al1".to_string()),
+        album: Some("Live at Leeds".to_string()),
+        ..song(id)
+    };
+    let queue = vec![live("a"), live("b"), song("c")];
+    let fade = |index: usize| {
+        crossfade_lead(
+            &settings,
+            &transition_at(&queue, index, RepeatMode::Off, false, 197.0),
+            &queue[index],
+        )
+    };
+    let gapless = |index: usize| {
+        gapless_next_song(
+            &settings,
+            &transition_at(&queue, index, RepeatMode::Off, false, 197.0),
+            &queue[index],
+        )
+        .map(|song| song.id.clone())
+    };
+
+    // Inside the live album the tracks run on; leaving it crossfades as usual.
+    assert_eq!(fade(0), None);
+    assert_eq!(gapless(0).as_deref(), Some("b"));
+    assert_eq!(fade(1), Some(3.0));
+    assert_eq!(gapless(1), None);
+}
+
+#[test]
+fn ab_loop_needs_both_points() {
+    assert_eq!(ab_loop_restart(Some(30.0), Some(45.0), 44.9), None);
+    assert_eq!(ab_loop_restart(Some(30.0), Some(45.0), 45.0), Some(30.0));
+    assert_eq!(ab_loop_restart(Some(30.0), Some(45.0), 120.0), Some(30.0));
+    assert_eq!(ab_loop_restart(Some(30.0), None, 120.0), None);
+    assert_eq!(ab_loop_restart(None, Some(45.0), 120.0), None);
+    assert_eq!(ab_loop_restart(Some(45.0), Some(30.0), 120.0), None);
+}
+
+#[test]
+fn skips_crossfade_only_when_asked() {
+    let mut settings = AppSettings::default();
+    settings.crossfade_enabled = true;
+    settings.crossfade_duration = 4;
+    let radio = Song {
+        server_name: "Radio".to_string(),
+        ..song("r")
+    };
+    let skip = |settings: &AppSettings, to: &Song, repeat| {
+        track_change_crossfade(settings, Some(&song("a")), to, repeat)
+    };
+
+    assert_eq!(skip(&settings, &song("b"), RepeatMode::Off), None);
+    settings.crossfade_on_skip = true;
+    assert_eq!(skip(&settings, &song("b"), RepeatMode::Off), Some(4.0));
+    assert_eq!(skip(&settings, &song("b"), RepeatMode::One), None);
+    assert_eq!(skip(&settings, &song("a"), RepeatMode::Off), None);
+    assert_eq!(skip(&settings, &radio, RepeatMode::Off), None);
+    assert_eq!(
+        track_change_crossfade(&settings, None, &song("b"), RepeatMode::Off),
+        None
+    );
+
+    // A track the end-of-track handler moved on to fades the way it decided.
+    arm_crossfade(&song("b"), None);
+    assert_eq!(skip(&settings, &song("b"), RepeatMode::Off), None);
+    arm_crossfade(&song("b"), Some(1.5));
+    assert_eq!(skip(&settings, &song("b"), RepeatMode::Off), Some(1.5));
+    // Picking another track instead is a skip.
+    arm_crossfade(&song("b"), None);
+    assert_eq!(skip(&settings, &song("c"), RepeatMode::Off), Some(4.0));
+    assert_eq!(skip(&settings, &song("b"), RepeatMode::Off), Some(4.0));
+
+    settings.crossfade_enabled = false;
+    assert_eq!(skip(&settings, &song("b"), RepeatMode::Off), None);
+}
+
+#[test]
+fn stream_transcode_follows_the_quality_settings() {
+    let mut settings = AppSettings::default();
+    assert_eq!(stream_transcode(&settings), None);
+
+    settings.stream_max_bitrate_kbps = 192;
+    settings.stream_format = "opus".to_string();
+    let capped = stream_transcode(&settings).map(|t| (t.format, t.max_bitrate_kbps));
+    assert_eq!(capped, Some(("opus", 192)));
+
+    // Fast start stays mp3 and never goes above the streaming cap.
+    settings.fast_start_enabled = true;
+    settings.fast_start_bitrate_kbps = 256;
+    let fast = stream_transcode(&settings).map(|t| (t.format, t.max_bitrate_kbps));
+    assert_eq!(fast, Some(("mp3", 192)));
+}
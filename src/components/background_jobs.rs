@@ -0,0 +1,573 @@
+//! Background jobs listed in the sidebar footer: auto-download passes, the smart cache
+//! warm-up, downloaded collection syncs, cache refreshes and download verification, so they
+//! stay visible after leaving the screen that started them.
+//!
+//! A job registers with [`JobRegistry::start`], hands its work to [`JobHandle::run`], and
+//! reports progress through the handle. The work runs on the app root, so it keeps going
+//! after the screen that started it closes, and it can be cancelled from the list; jobs
+//! that call [`JobHandle::allow_pause`] can be paused too. A finished job shows its outcome
+//! for a few seconds before it disappears.
+use crate::api::ServerConfig;
+use crate::components::Icon;
+use crate::db::AppSettings;
+use crate::offline_audio::{
+    refresh_downloaded_cache, run_auto_download_pass, verify_downloads, AutoDownloadReport,
+    DownloadCacheRefreshReport, DownloadVerifyReport,
+};
+use dioxus::core::spawn_forever;
+use dioxus::prelude::*;
+use futures_util::future::{AbortHandle, Abortable, FutureExt};
+use std::future::Future;
+
+/// How long a finished job stays in the list.
+const JOB_OUTCOME_MS: u32 = 4_000;
+/// Labels of the jobs more than one screen starts, which also keep a second one of each
+/// from starting while the first runs.
+pub const AUTO_DOWNLOAD_JOB: &str = "Auto-download";
+pub const CACHE_REFRESH_JOB: &str = "Downloaded cache refresh";
+pub const DOWNLOAD_VERIFY_JOB: &str = "Download verification";
+
+#[cfg(target_arch = "wasm32")]
+async fn job_pause(ms: u32) {
+    gloo_timers::future::TimeoutFuture::new(ms).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn job_pause(ms: u32) {
+    tokio::time::sleep(std::time::Duration::from_millis(u64::from(ms))).await;
+}
+
+/// How a job ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobOutcome {
+    Succeeded(String),
+    Failed(String),
+    Cancelled,
+}
+
+#[derive(Clone)]
+struct BackgroundJob {
+    id: u64,
+    label: String,
+    /// Share done from 0 to 1, when the job knows it.
+    progress: Option<f64>,
+    detail: Option<String>,
+    cancel: Option<AbortHandle>,
+    can_pause: bool,
+    paused: bool,
+    outcome: Option<JobOutcome>,
+}
+
+impl BackgroundJob {
+    fn new(id: u64, label: String) -> Self {
+        Self {
+            id,
+            label,
+            progress: None,
+            detail: None,
+            cancel: None,
+            can_pause: false,
+            paused: false,
+            outcome: None,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.outcome.is_none()
+    }
+}
+
+fn running_job_mut(jobs: &mut [BackgroundJob], id: u64) -> Option<&mut BackgroundJob> {
+    jobs.iter_mut().find(|job| job.id == id && job.is_running())
+}
+
+/// Records how the running job `id` ended. Returns `false` when it had already ended.
+fn finish_job(jobs: &mut [BackgroundJob], id: u64, outcome: JobOutcome) -> bool {
+    running_job_mut(jobs, id)
+        .map(|job| {
+            job.cancel = None;
+            job.outcome = Some(outcome);
+        })
+        .is_some()
+}
+
+/// Aborts the running job `id` if it offered Cancel. Returns whether it ended.
+fn cancel_job(jobs: &mut [BackgroundJob], id: u64) -> bool {
+    let Some(cancel) = running_job_mut(jobs, id).and_then(|job| job.cancel.take()) else {
+        return false;
+    };
+    cancel.abort();
+    finish_job(jobs, id, JobOutcome::Cancelled)
+}
+
+fn toggle_job_pause(jobs: &mut [BackgroundJob], id: u64) {
+    if let Some(job) = running_job_mut(jobs, id).filter(|job| job.can_pause) {
+        job.paused = !job.paused;
+    }
+}
+
+/// Drops job `id` once it has ended; a running job stays listed.
+fn remove_finished_job(jobs: &mut Vec<BackgroundJob>, id: u64) {
+    jobs.retain(|job| job.id != id || job.is_running());
+}
+
+/// Provided by the app root.
+#[derive(Clone, Copy)]
+pub struct JobRegistry {
+    jobs: Signal<Vec<BackgroundJob>>,
+    next_id: Signal<u64>,
+}
+
+impl Default for JobRegistry {
+    fn default() -> Self {
+        Self {
+            jobs: Signal::new(Vec::new()),
+            next_id: Signal::new(0),
+        }
+    }
+}
+
+impl JobRegistry {
+    /// Lists a job called `label` until the work given to [`JobHandle::run`] ends.
+    pub fn start(&self, label: impl Into<String>) -> JobHandle {
+        let mut next_id = self.next_id;
+        let id = next_id.peek().wrapping_add(1);
+        next_id.set(id);
+        let mut jobs = self.jobs;
+        jobs.write().push(BackgroundJob::new(id, label.into()));
+        JobHandle {
+            registry: *self,
+            id,
+        }
+    }
+
+    /// The running job called `label`, if there is one.
+    pub fn running(&self, label: &str) -> Option<JobHandle> {
+        self.jobs
+            .read()
+            .iter()
+            .find(|job| job.label == label && job.is_running())
+            .map(|job| JobHandle {
+                registry: *self,
+                id: job.id,
+            })
+    }
+
+    pub fn cancel(&self, id: u64) {
+        let mut jobs = self.jobs;
+        if cancel_job(&mut jobs.write(), id) {
+            self.remove_after_outcome(id);
+        }
+    }
+
+    pub fn toggle_pause(&self, id: u64) {
+        let mut jobs = self.jobs;
+        toggle_job_pause(&mut jobs.write(), id);
+    }
+
+    fn with_job<R>(&self, id: u64, f: impl FnOnce(&mut BackgroundJob) -> R) -> Option<R> {
+        let mut jobs = self.jobs;
+        let mut jobs = jobs.write();
+        running_job_mut(&mut jobs, id).map(f)
+    }
+
+    fn peek_job<R>(&self, id: u64, f: impl FnOnce(&BackgroundJob) -> R) -> Option<R> {
+        self.jobs.peek().iter().find(|job| job.id == id).map(f)
+    }
+
+    fn read_job<R>(&self, id: u64, f: impl FnOnce(&BackgroundJob) -> R) -> Option<R> {
+        self.jobs.read().iter().find(|job| job.id == id).map(f)
+    }
+
+    /// Shows `outcome` in place of the job's progress, then drops it from the list.
+    fn finish(&self, id: u64, outcome: JobOutcome) {
+        let mut jobs = self.jobs;
+        if finish_job(&mut jobs.write(), id, outcome) {
+            self.remove_after_outcome(id);
+        }
+    }
+
+    fn remove_after_outcome(&self, id: u64) {
+        let mut jobs = self.jobs;
+        spawn_forever(async move {
+            job_pause(JOB_OUTCOME_MS).await;
+            remove_finished_job(&mut jobs.write(), id);
+        });
+    }
+}
+
+impl JobRegistry {
+    /// An auto-download pass as a job. Resolves to the pass's report, or `None` if it was
+    /// cancelled.
+    pub fn run_auto_download(
+        &self,
+        servers: Vec<ServerConfig>,
+        settings: AppSettings,
+    ) -> impl Future<Output = Option<Result<AutoDownloadReport, String>>> {
+        self.start(AUTO_DOWNLOAD_JOB).run(
+            async move { run_auto_download_pass(&servers, &settings).await },
+            |result| {
+                result.clone().map(|report| {
+                    format!(
+                        "{} new, {} failed{}",
+                        report.downloaded,
+                        report.failed,
+                        report.cleanup_summary()
+                    )
+                })
+            },
+        )
+    }
+
+    /// A refresh of the lyrics and artwork of downloaded songs as a job.
+    pub fn run_cache_refresh(
+        &self,
+        servers: Vec<ServerConfig>,
+        settings: AppSettings,
+    ) -> impl Future<Output = Option<Result<DownloadCacheRefreshReport, String>>> {
+        self.start(CACHE_REFRESH_JOB).run(
+            async move { refresh_downloaded_cache(&servers, &settings).await },
+            |result| {
+                result.clone().map(|report| {
+                    format!(
+                        "{} scanned, {} lyrics warmed, {} artwork refreshed",
+                        report.scanned, report.lyrics_warmed, report.artwork_refreshed
+                    )
+                })
+            },
+        )
+    }
+
+    /// A check of every downloaded file against the download index as a job, which can be
+    /// paused.
+    pub fn run_download_verification(
+        &self,
+    ) -> impl Future<Output = Option<Result<DownloadVerifyReport, String>>> {
+        let job = self.start(DOWNLOAD_VERIFY_JOB);
+        job.allow_pause();
+        job.run(
+            async move {
+                verify_downloads(
+                    |checked, total| {
+                        if total > 0 {
+                            job.set_progress(checked as f64 / total as f64);
+                        }
+                        job.set_detail(format!("Checked {checked}/{total}"));
+                    },
+                    || job.is_paused(),
+                )
+                .await
+            },
+            |result| {
+                result.clone().map(|report| {
+                    format!(
+                        "{} checked, {} missing, {} damaged",
+                        report.checked, report.missing, report.damaged
+                    )
+                })
+            },
+        )
+    }
+}
+
+/// A job's side of the registry, handed to the work so it can report progress.
+#[derive(Clone, Copy)]
+pub struct JobHandle {
+    registry: JobRegistry,
+    id: u64,
+}
+
+impl JobHandle {
+    /// Sets how much is done, from 0 to 1.
+    pub fn set_progress(&self, fraction: f64) {
+        let fraction = fraction.clamp(0.0, 1.0);
+        let changed = self
+            .registry
+            .peek_job(self.id, |job| job.progress != Some(fraction))
+            .unwrap_or(false);
+        if changed {
+            self.registry
+                .with_job(self.id, |job| job.progress = Some(fraction));
+        }
+    }
+
+    /// Sets the line shown under the job's label.
+    pub fn set_detail(&self, detail: impl Into<String>) {
+        let detail = detail.into();
+        self.registry
+            .with_job(self.id, |job| job.detail = Some(detail));
+    }
+
+    pub fn progress(&self) -> Option<f64> {
+        self.registry
+            .read_job(self.id, |job| job.progress)
+            .flatten()
+    }
+
+    pub fn detail(&self) -> Option<String> {
+        self.registry
+            .read_job(self.id, |job| job.detail.clone())
+            .flatten()
+    }
+
+    /// Offers Pause in the list. The work itself has to honor it through `is_paused`.
+    pub fn allow_pause(&self) {
+        self.registry.with_job(self.id, |job| job.can_pause = true);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.registry
+            .peek_job(self.id, |job| job.paused)
+            .unwrap_or(false)
+    }
+
+    /// Runs `work` on the app root with Cancel offered in the list, so closing the screen
+    /// that started it doesn't stop it. `outcome` turns the result into the summary or
+    /// error the list shows. Resolves to the result, or `None` if the job was cancelled.
+    pub fn run<T: Clone + 'static>(
+        self,
+        work: impl Future<Output = T> + 'static,
+        outcome: impl FnOnce(&T) -> Result<String, String> + 'static,
+    ) -> impl Future<Output = Option<T>> {
+        let (abort, registration) = AbortHandle::new_pair();
+        self.registry
+            .with_job(self.id, |job| job.cancel = Some(abort));
+        let work = Abortable::new(work, registration).shared();
+        let driver = work.clone();
+        spawn_forever(async move {
+            if let Ok(result) = driver.await {
+                let outcome = match outcome(&result) {
+                    Ok(summary) => JobOutcome::Succeeded(summary),
+                    Err(error) => JobOutcome::Failed(error),
+                };
+                self.registry.finish(self.id, outcome);
+            }
+        });
+        async move { work.await.ok() }
+    }
+}
+
+/// Overall progress for the footer bar: the mean of the running jobs that report it, or
+/// `None` when none do.
+fn overall_progress(jobs: &[BackgroundJob]) -> Option<f64> {
+    let known: Vec<f64> = jobs
+        .iter()
+        .filter(|job| job.is_running())
+        .filter_map(|job| job.progress)
+        .collect();
+    (!known.is_empty()).then(|| known.iter().sum::<f64>() / known.len() as f64)
+}
+
+/// Activity line in the sidebar footer while background jobs run, opening a list with each
+/// job's progress and its Pause and Cancel buttons.
+#[component]
+pub fn BackgroundJobsIndicator() -> Element {
+    let registry = use_context::<JobRegistry>();
+    let mut open = use_signal(|| false);
+    let jobs = (registry.jobs)();
+    if jobs.is_empty() {
+        return rsx! {};
+    }
+
+    let running = jobs.iter().filter(|job| job.is_running()).count();
+    let summary = match running {
+        0 => "Background jobs done".to_string(),
+        1 => "1 background job".to_string(),
+        count => format!("{count} background jobs"),
+    };
+    let progress = overall_progress(&jobs);
+    let chevron = if open() { "chevron-down" } else { "chevron-up" };
+
+    rsx! {
+        div { class: "relative mb-2",
+            if open() {
+                div {
+                    class: "absolute bottom-full left-0 right-0 mb-2 z-20 rounded-xl border border-zinc-800 bg-zinc-900/95 p-2 space-y-1 shadow-2xl shadow-black/40 backdrop-blur-xl",
+                    role: "dialog",
+                    aria_label: "Background jobs",
+                    for job in jobs.clone() {
+                        BackgroundJobRow { key: "{job.id}", job_id: job.id }
+                    }
+                }
+            }
+            button {
+                class: "w-full px-3 py-2 rounded-xl text-left hover:bg-zinc-800/50 transition-colors space-y-1.5",
+                aria_expanded: "{open()}",
+                onclick: move |_| open.set(!open()),
+                div { class: "flex items-center gap-2 text-xs text-zinc-400",
+                    if running > 0 {
+                        Icon { name: "loader".to_string(), class: "w-3.5 h-3.5 animate-spin text-emerald-400".to_string() }
+                    } else {
+                        Icon { name: "check".to_string(), class: "w-3.5 h-3.5 text-emerald-400".to_string() }
+                    }
+                    span { class: "flex-1 truncate", "{summary}" }
+                    Icon { name: chevron.to_string(), class: "w-3.5 h-3.5".to_string() }
+                }
+                if running > 0 {
+                    div { class: "h-1 rounded-full bg-zinc-800 overflow-hidden",
+                        match progress {
+                            Some(fraction) => rsx! {
+                                div {
+                                    class: "h-full bg-emerald-500 transition-all",
+                                    style: "width: {fraction * 100.0:.1}%;",
+                                }
+                            },
+                            None => rsx! {
+                                div { class: "h-full w-1/3 bg-emerald-500/70 animate-pulse" }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn BackgroundJobRow(job_id: u64) -> Element {
+    let registry = use_context::<JobRegistry>();
+    let Some(job) = (registry.jobs)().into_iter().find(|job| job.id == job_id) else {
+        return rsx! {};
+    };
+    let can_cancel = job.cancel.is_some();
+
+    rsx! {
+        div { class: "px-2 py-1.5 rounded-lg bg-zinc-800/40 space-y-1",
+            div { class: "flex items-center gap-2",
+                p { class: "flex-1 min-w-0 text-xs font-medium text-white truncate", "{job.label}" }
+                if job.is_running() && job.can_pause {
+                    button {
+                        class: "p-1 rounded text-zinc-400 hover:text-white transition-colors",
+                        aria_label: if job.paused { "Resume" } else { "Pause" },
+                        title: if job.paused { "Resume" } else { "Pause" },
+                        onclick: move |_| registry.toggle_pause(job_id),
+                        Icon {
+                            name: if job.paused { "play".to_string() } else { "pause".to_string() },
+                            class: "w-3.5 h-3.5".to_string(),
+                        }
+                    }
+                }
+                if can_cancel {
+                    button {
+                        class: "p-1 rounded text-zinc-400 hover:text-red-300 transition-colors",
+                        aria_label: "Cancel",
+                        title: "Cancel",
+                        onclick: move |_| registry.cancel(job_id),
+                        Icon { name: "x".to_string(), class: "w-3.5 h-3.5".to_string() }
+                    }
+                }
+            }
+            match job.outcome.clone() {
+                None => rsx! {
+                    if let Some(fraction) = job.progress {
+                        div { class: "h-1 rounded-full bg-zinc-700/60 overflow-hidden",
+                            div {
+                                class: "h-full bg-emerald-500 transition-all",
+                                style: "width: {fraction * 100.0:.1}%;",
+                            }
+                        }
+                    }
+                    if job.paused {
+                        p { class: "text-[11px] text-amber-300", "Paused" }
+                    } else if let Some(detail) = job.detail.clone() {
+                        p { class: "text-[11px] text-zinc-500 truncate", "{detail}" }
+                    }
+                },
+                Some(JobOutcome::Succeeded(summary)) => rsx! {
+                    p { class: "text-[11px] text-emerald-300", "{summary}" }
+                },
+                Some(JobOutcome::Failed(error)) => rsx! {
+                    p { class: "text-[11px] text-red-300", "{error}" }
+                },
+                Some(JobOutcome::Cancelled) => rsx! {
+                    p { class: "text-[11px] text-zinc-500", "Cancelled" }
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: u64, progress: Option<f64>, outcome: Option<JobOutcome>) -> BackgroundJob {
+        BackgroundJob {
+            progress,
+            outcome,
+            ..BackgroundJob::new(id, format!("Job {id}"))
+        }
+    }
+
+    #[test]
+    fn finishing_a_job_keeps_its_outcome_until_it_is_removed() {
+        let mut jobs = vec![job(1, None, None), job(2, None, None)];
+        assert!(finish_job(
+            &mut jobs,
+            1,
+            JobOutcome::Succeeded("Done".to_string())
+        ));
+        assert!(!finish_job(
+            &mut jobs,
+            1,
+            JobOutcome::Failed("Late".to_string())
+        ));
+        assert_eq!(
+            jobs[0].outcome,
+            Some(JobOutcome::Succeeded("Done".to_string()))
+        );
+
+        remove_finished_job(&mut jobs, 2);
+        assert_eq!(jobs.len(), 2);
+        remove_finished_job(&mut jobs, 1);
+        assert_eq!(jobs.iter().map(|job| job.id).collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn cancel_aborts_the_work_and_ends_the_job() {
+        let (abort, _registration) = AbortHandle::new_pair();
+        let mut jobs = vec![job(1, None, None), job(2, None, None)];
+        jobs[0].cancel = Some(abort.clone());
+
+        assert!(!cancel_job(&mut jobs, 2));
+        assert_eq!(jobs[1].outcome, None);
+        assert!(cancel_job(&mut jobs, 1));
+        assert!(abort.is_aborted());
+        assert_eq!(jobs[0].outcome, Some(JobOutcome::Cancelled));
+        assert!(jobs[0].cancel.is_none());
+        assert!(!cancel_job(&mut jobs, 1));
+    }
+
+    #[test]
+    fn pause_toggles_only_running_jobs_that_allow_it() {
+        let mut jobs = vec![job(1, None, None), job(2, None, None)];
+        jobs[0].can_pause = true;
+        toggle_job_pause(&mut jobs, 1);
+        toggle_job_pause(&mut jobs, 2);
+        assert!(jobs[0].paused);
+        assert!(!jobs[1].paused);
+        toggle_job_pause(&mut jobs, 1);
+        assert!(!jobs[0].paused);
+
+        jobs[0].outcome = Some(JobOutcome::Cancelled);
+        toggle_job_pause(&mut jobs, 1);
+        assert!(!jobs[0].paused);
+    }
+
+    #[test]
+    fn overall_progress_averages_running_jobs_that_report_it() {
+        assert_eq!(overall_progress(&[]), None);
+        assert_eq!(overall_progress(&[job(1, None, None)]), None);
+        let jobs = [
+            job(1, Some(0.25), None),
+            job(2, Some(0.75), None),
+            job(3, None, None),
+            job(
+                4,
+                Some(1.0),
+                Some(JobOutcome::Succeeded("Done".to_string())),
+            ),
+        ];
+        assert_eq!(overall_progress(&jobs), Some(0.5));
+    }
+}
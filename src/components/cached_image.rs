@@ -1,25 +1,127 @@
+use crate::api::{NavidromeClient, ServerConfig};
 use dioxus::prelude::*;
 
-/// A simple image component that displays images directly.
-/// Caching functionality has been simplified to avoid hook conflicts.
+/// Artwork variants warmed by the smart cache, smallest first.
+pub const SONG_ART_SIZES: [u32; 3] = [80, 120, 160];
+pub const ALBUM_ART_SIZES: [u32; 4] = [120, 160, 300, 500];
+pub const PLAYLIST_ART_SIZES: [u32; 3] = [120, 160, 300];
+
+/// Which smart-cache size set a piece of artwork belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArtworkKind {
+    Song,
+    Album,
+    Playlist,
+}
+
+impl ArtworkKind {
+    pub fn cached_sizes(self) -> &'static [u32] {
+        match self {
+            ArtworkKind::Song => &SONG_ART_SIZES,
+            ArtworkKind::Album => &ALBUM_ART_SIZES,
+            ArtworkKind::Playlist => &PLAYLIST_ART_SIZES,
+        }
+    }
+
+    /// Smallest cached size that covers `render_px`, or the largest one for bigger targets.
+    pub fn request_size(self, render_px: u32) -> u32 {
+        let sizes = self.cached_sizes();
+        sizes
+            .iter()
+            .copied()
+            .find(|size| *size >= render_px)
+            .unwrap_or(sizes[sizes.len() - 1])
+    }
+}
+
+/// Cover art URL for an image drawn at `render_px`. Snapping to the smart-cache sizes keeps
+/// requests on the same `server:id:size` keys the cache was warmed with.
+pub fn sized_cover_art_url(
+    client: &NavidromeClient,
+    cover_art_id: &str,
+    kind: ArtworkKind,
+    render_px: u32,
+) -> String {
+    client.get_cover_art_url(cover_art_id, kind.request_size(render_px))
+}
+
+/// Gives `img[data-cover-src]` elements their `src` only once they scroll near the viewport.
+/// On web the `src` write then goes through the cover-art request queue, so a long list
+/// never requests more artwork than is on screen.
+const LAZY_COVER_LOADER_JS: &str = r#"
+(() => {
+  if (window.__rustyLazyCoverInstalled) return;
+  window.__rustyLazyCoverInstalled = true;
+  const load = (img) => {
+    const url = img.getAttribute('data-cover-src');
+    if (url && img.getAttribute('src') !== url) img.setAttribute('src', url);
+  };
+  const observer = 'IntersectionObserver' in window
+    ? new IntersectionObserver((entries) => {
+        for (const entry of entries) {
+          if (!entry.isIntersecting) continue;
+          observer.unobserve(entry.target);
+          load(entry.target);
+        }
+      }, { rootMargin: '300px 0px' })
+    : null;
+  const watch = (img) => (observer ? observer.observe(img) : load(img));
+  const scan = (root) => {
+    if (root instanceof HTMLImageElement && root.hasAttribute('data-cover-src')) watch(root);
+    if (root.querySelectorAll) root.querySelectorAll('img[data-cover-src]').forEach(watch);
+  };
+  scan(document);
+  new MutationObserver((records) => {
+    for (const record of records) {
+      if (record.type === 'attributes') {
+        watch(record.target);
+        continue;
+      }
+      record.addedNodes.forEach(scan);
+    }
+  }).observe(document.body, {
+    childList: true,
+    subtree: true,
+    attributes: true,
+    attributeFilter: ['data-cover-src'],
+  });
+})();
+"#;
+
+/// Installs the viewport-driven loader for `data-cover-src` images once per page.
+pub fn use_lazy_cover_loader() {
+    use_effect(|| {
+        let _ = document::eval(LAZY_COVER_LOADER_JS);
+    });
+}
+
+/// Lazily loaded cover art sized for where it is drawn.
 #[component]
 pub fn CachedImage(
-    src: String,
+    server_id: String,
+    cover_art: String,
     alt: String,
     class: String,
-    #[props(default = String::new())] cache_key: String,
-    #[props(default = 24)] expiry_hours: u32,
+    kind: ArtworkKind,
+    render_size: u32,
 ) -> Element {
-    // Suppress unused variable warnings
-    let _ = cache_key;
-    let _ = expiry_hours;
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let src = servers()
+        .into_iter()
+        .find(|server| server.id == server_id)
+        .map(|server| {
+            sized_cover_art_url(&NavidromeClient::new(server), &cover_art, kind, render_size)
+        })
+        .filter(|url| !url.is_empty());
 
     rsx! {
-        img {
-            src: "{src}",
-            alt: "{alt}",
-            class: "{class}",
-            loading: "lazy",
+        if let Some(src) = src {
+            img {
+                src: "{src}",
+                alt: "{alt}",
+                class: "{class}",
+                loading: "lazy",
+            }
         }
     }
 }
@@ -0,0 +1,81 @@
+use crate::components::Icon;
+use crate::crash_report::{dismiss_pending_crash_report, pending_crash_report};
+use dioxus::prelude::*;
+
+/// Shown on the launch after a panic, with the local crash report ready to copy into a
+/// GitHub issue. Reports never leave the machine on their own.
+#[component]
+pub fn CrashReportBanner() -> Element {
+    let mut report = use_signal(pending_crash_report);
+    let mut expanded = use_signal(|| false);
+    let mut copied = use_signal(|| false);
+
+    let Some(current) = report() else {
+        return rsx! {};
+    };
+
+    let on_copy = {
+        let contents = current.contents.clone();
+        move |_| {
+            let payload = serde_json::to_string(&contents).unwrap_or_else(|_| "\"\"".to_string());
+            let _ = document::eval(&format!(
+                "navigator.clipboard && navigator.clipboard.writeText({payload});"
+            ));
+            copied.set(true);
+        }
+    };
+
+    let on_dismiss = move |_| {
+        dismiss_pending_crash_report();
+        report.set(None);
+    };
+
+    rsx! {
+        div {
+            class: "mb-4 rounded-xl border border-amber-500/40 bg-amber-500/10 p-3 space-y-3",
+            role: "alert",
+            div { class: "flex items-start gap-3",
+                Icon {
+                    name: "alert".to_string(),
+                    class: "w-5 h-5 mt-0.5 text-amber-300 shrink-0".to_string(),
+                }
+                div { class: "min-w-0",
+                    p { class: "text-sm font-medium text-amber-100", "RustySound closed unexpectedly." }
+                    p { class: "text-xs text-amber-100/80",
+                        "A crash report was saved as {current.file_name}. It stays on this device; copy it into a GitHub issue if you want to report the problem."
+                    }
+                }
+            }
+            div { class: "flex flex-wrap items-center gap-2",
+                button {
+                    class: "px-3 py-2 rounded-lg border border-amber-400/60 text-amber-100 hover:text-white hover:border-amber-300 transition-colors text-sm",
+                    onclick: move |_| expanded.set(!expanded()),
+                    if expanded() {
+                        "Hide report"
+                    } else {
+                        "View report"
+                    }
+                }
+                button {
+                    class: "px-3 py-2 rounded-lg border border-amber-400/60 text-amber-100 hover:text-white hover:border-amber-300 transition-colors text-sm",
+                    onclick: on_copy,
+                    if copied() {
+                        "Copied"
+                    } else {
+                        "Copy report"
+                    }
+                }
+                button {
+                    class: "px-3 py-2 rounded-lg text-zinc-400 hover:text-white transition-colors text-sm",
+                    onclick: on_dismiss,
+                    "Dismiss"
+                }
+            }
+            if expanded() {
+                pre { class: "max-h-80 overflow-auto rounded-lg bg-zinc-950/80 border border-zinc-800 p-3 text-[11px] leading-snug text-zinc-300 whitespace-pre-wrap break-words select-text",
+                    "{current.contents}"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use crate::components::Icon;
+use crate::db::{
+    has_database_backup, restore_database_backup, start_fresh_database, DatabaseRecovery,
+};
+use dioxus::prelude::*;
+
+fn recovery_message(recovery: &DatabaseRecovery) -> String {
+    let restored = recovery
+        .restored_from
+        .as_ref()
+        .map(|path| format!("Restored {}. ", path.display()))
+        .unwrap_or_default();
+    match &recovery.preserved_at {
+        Some(path) => format!(
+            "{restored}The damaged database was kept at {}.",
+            path.display()
+        ),
+        None => restored,
+    }
+}
+
+/// Full-page screen shown instead of the app when the local database can't be opened or
+/// fails its integrity check. It runs before any component that reads the database is
+/// mounted, so it only relies on the database module itself. `on_retry` re-runs the
+/// startup check; each recovery action calls it once the database is replaced.
+#[component]
+pub fn DatabaseRecoveryScreen(error: String, on_retry: EventHandler<()>) -> Element {
+    let mut backup_available = use_signal(has_database_backup);
+    let mut failure = use_signal(|| None::<String>);
+
+    // Once the database is replaced this screen unmounts, so the outcome goes to the log.
+    let on_restore = move |_| match restore_database_backup() {
+        Ok(recovery) => {
+            eprintln!("{}", recovery_message(&recovery));
+            on_retry.call(());
+        }
+        Err(error) => {
+            backup_available.set(has_database_backup());
+            failure.set(Some(format!("Restore failed: {error}")));
+        }
+    };
+
+    let on_start_fresh = move |_| match start_fresh_database() {
+        Ok(recovery) => {
+            eprintln!("{}", recovery_message(&recovery));
+            on_retry.call(());
+        }
+        Err(error) => failure.set(Some(format!("Could not start fresh: {error}"))),
+    };
+
+    rsx! {
+        div { class: "min-h-screen flex items-center justify-center bg-zinc-950 px-4 py-8",
+            div { class: "w-full max-w-lg rounded-2xl border border-amber-500/40 bg-zinc-900/80 p-6 space-y-4",
+                role: "alert",
+                div { class: "flex items-start gap-3",
+                    Icon {
+                        name: "alert".to_string(),
+                        class: "w-6 h-6 mt-0.5 text-amber-300 shrink-0".to_string(),
+                    }
+                    div { class: "min-w-0 space-y-1",
+                        h1 { class: "text-lg font-semibold text-white", "The local database is damaged" }
+                        p { class: "text-sm text-zinc-400",
+                            "RustySound could not read its saved servers and settings. This can happen after a power loss or a full disk."
+                        }
+                    }
+                }
+                pre { class: "text-xs text-zinc-400 bg-zinc-950/70 border border-zinc-800 rounded-lg p-3 whitespace-pre-wrap break-all select-text",
+                    "{error}"
+                }
+                div { class: "space-y-2",
+                    button {
+                        class: "w-full px-4 py-2 rounded-lg border border-zinc-700 text-zinc-200 hover:text-white hover:border-zinc-500 transition-colors text-sm",
+                        onclick: move |_| on_retry.call(()),
+                        "Retry"
+                    }
+                    button {
+                        class: "w-full px-4 py-2 rounded-lg bg-emerald-500/15 text-emerald-300 hover:bg-emerald-500/25 transition-colors text-sm disabled:opacity-50 disabled:cursor-not-allowed",
+                        disabled: !backup_available(),
+                        onclick: on_restore,
+                        if backup_available() {
+                            "Restore the most recent backup"
+                        } else {
+                            "No backup available"
+                        }
+                    }
+                    button {
+                        class: "w-full px-4 py-2 rounded-lg border border-rose-500/50 text-rose-300 hover:text-white hover:border-rose-400 transition-colors text-sm",
+                        onclick: on_start_fresh,
+                        "Start fresh"
+                    }
+                    p { class: "text-xs text-zinc-500",
+                        "Both restoring and starting fresh keep the damaged file next to the new database for inspection."
+                    }
+                }
+                if let Some(message) = failure() {
+                    p { class: "text-sm text-red-400", "{message}" }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,416 @@
+//! One-tap "Shuffle favorites" for the home view and the sidebar.
+//!
+//! [`FavoritesShuffleController`] asks every active server for its starred songs (not the
+//! cached list, unless the server can't be reached), mixes them with [`favorites_mix`] and
+//! starts playing. When nothing is starred, [`FavoritesShuffleNotice`] says so and links to
+//! the favorites view; when the servers couldn't be asked, it shows the error instead. A
+//! rejected login is left to the re-authentication prompt.
+use crate::api::{ApiError, NavidromeClient, QueueSourceKind, ServerConfig, Song};
+use crate::components::audio_manager::assign_collection_queue_meta;
+use crate::components::{AppView, Icon, IsPlayingSignal, Navigation};
+use crate::db::{last_played_by_song, AppSettings};
+use dioxus::core::spawn_forever;
+use dioxus::prelude::*;
+use std::collections::HashSet;
+
+/// Songs played this recently go to the back of the mix.
+const RECENT_PLAY_WINDOW_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// How long the "nothing starred" and error notices stay up.
+const NOTICE_MS: u32 = 6_000;
+
+#[cfg(target_arch = "wasm32")]
+async fn notice_pause(ms: u32) {
+    gloo_timers::future::TimeoutFuture::new(ms).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn notice_pause(ms: u32) {
+    tokio::time::sleep(std::time::Duration::from_millis(u64::from(ms))).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+fn random_unit() -> f64 {
+    js_sys::Math::random()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn random_unit() -> f64 {
+    rand::random::<f64>()
+}
+
+/// Splits `limit` between lists of the given lengths in proportion to their lengths, by
+/// largest remainder.
+fn proportional_quotas(counts: &[usize], limit: usize) -> Vec<usize> {
+    let total: usize = counts.iter().sum();
+    if total == 0 {
+        return vec![0; counts.len()];
+    }
+    let limit = limit.min(total);
+    let mut quotas: Vec<usize> = counts.iter().map(|count| count * limit / total).collect();
+    let mut by_remainder: Vec<usize> = (0..counts.len()).collect();
+    by_remainder.sort_by_key(|&index| std::cmp::Reverse(counts[index] * limit % total));
+    let mut left = limit - quotas.iter().sum::<usize>();
+    for index in by_remainder {
+        if left == 0 {
+            break;
+        }
+        if quotas[index] < counts[index] {
+            quotas[index] += 1;
+            left -= 1;
+        }
+    }
+    quotas
+}
+
+/// Mixes the starred songs of each server into one queue of at most `limit` songs. Each
+/// server gets a share of the queue proportional to its starred count, spread evenly through
+/// it. Within a server the order is random, except that songs in `recently_played` (keyed by
+/// server id and song id) come after all the others.
+pub(crate) fn favorites_mix(
+    per_server: Vec<Vec<Song>>,
+    recently_played: &HashSet<(String, String)>,
+    limit: usize,
+    mut random: impl FnMut() -> f64,
+) -> Vec<Song> {
+    let lists: Vec<Vec<Song>> = per_server
+        .into_iter()
+        .filter(|songs| !songs.is_empty())
+        .map(|mut songs| {
+            for i in (1..songs.len()).rev() {
+                let j = ((random() * (i + 1) as f64) as usize).min(i);
+                songs.swap(i, j);
+            }
+            // Stable, so both groups keep their shuffled order.
+            songs.sort_by_key(|song| {
+                recently_played.contains(&(song.server_id.clone(), song.id.clone()))
+            });
+            songs
+        })
+        .collect();
+
+    let counts: Vec<usize> = lists.iter().map(Vec::len).collect();
+    let quotas = proportional_quotas(&counts, limit);
+    let mut taken = vec![0usize; lists.len()];
+    let mut sources: Vec<_> = lists.into_iter().map(Vec::into_iter).collect();
+    let mut mix = Vec::with_capacity(quotas.iter().sum());
+
+    // Each slot goes to the server furthest behind its share of the queue so far.
+    loop {
+        let next = (0..quotas.len())
+            .filter(|&index| taken[index] < quotas[index])
+            .min_by(|&a, &b| {
+                let progress = |index: usize| (taken[index] as f64 + 0.5) / quotas[index] as f64;
+                progress(a).total_cmp(&progress(b))
+            });
+        let Some(index) = next else {
+            break;
+        };
+        taken[index] += 1;
+        if let Some(song) = sources[index].next() {
+            mix.push(song);
+        }
+    }
+    mix
+}
+
+/// What [`FavoritesShuffleNotice`] is currently showing.
+#[derive(Clone, Debug, PartialEq)]
+enum ShuffleNotice {
+    /// Every server answered and none has starred songs.
+    Empty,
+    /// Nothing could be played because a server request failed.
+    Failed(String),
+}
+
+/// Provided by the app root once the queue signals exist.
+#[derive(Clone, Copy)]
+pub struct FavoritesShuffleController {
+    servers: Signal<Vec<ServerConfig>>,
+    app_settings: Signal<AppSettings>,
+    queue: Signal<Vec<Song>>,
+    queue_index: Signal<usize>,
+    now_playing: Signal<Option<Song>>,
+    is_playing: Signal<bool>,
+    /// Recently played songs as reported by the servers, from the home feed.
+    server_recent_songs: Signal<Option<Vec<Song>>>,
+    busy: Signal<bool>,
+    notice: Signal<Option<ShuffleNotice>>,
+    notice_generation: Signal<u64>,
+}
+
+impl FavoritesShuffleController {
+    pub fn new(
+        servers: Signal<Vec<ServerConfig>>,
+        app_settings: Signal<AppSettings>,
+        queue: Signal<Vec<Song>>,
+        queue_index: Signal<usize>,
+        now_playing: Signal<Option<Song>>,
+        is_playing: IsPlayingSignal,
+        server_recent_songs: Signal<Option<Vec<Song>>>,
+    ) -> Self {
+        Self {
+            servers,
+            app_settings,
+            queue,
+            queue_index,
+            now_playing,
+            is_playing: is_playing.0,
+            server_recent_songs,
+            busy: Signal::new(false),
+            notice: Signal::new(None),
+            notice_generation: Signal::new(0),
+        }
+    }
+
+    pub fn is_busy(&self) -> bool {
+        (self.busy)()
+    }
+
+    fn current_notice(&self) -> Option<ShuffleNotice> {
+        (self.notice)()
+    }
+
+    pub fn dismiss_notice(&self) {
+        let mut notice = self.notice;
+        notice.set(None);
+    }
+
+    /// Fetches, mixes and plays the starred songs of the active servers.
+    pub fn start(&self) {
+        let mut busy = self.busy;
+        if *busy.peek() {
+            return;
+        }
+        busy.set(true);
+        let controller = *self;
+
+        spawn_forever(async move {
+            let servers: Vec<ServerConfig> = controller
+                .servers
+                .peek()
+                .iter()
+                .filter(|server| server.active)
+                .cloned()
+                .collect();
+            let mut per_server = Vec::with_capacity(servers.len());
+            let mut failure: Option<ApiError> = None;
+            let mut auth_rejected = false;
+            for server in servers {
+                let client = NavidromeClient::new(server);
+                match client.get_starred_fresh().await {
+                    Ok((_, _, songs)) => per_server.push(songs),
+                    Err(ApiError::AuthFailed) => {
+                        // The prompt polls the flagged servers and asks for a new password.
+                        client.mark_auth_rejected();
+                        auth_rejected = true;
+                    }
+                    Err(error) => {
+                        failure.get_or_insert(error);
+                    }
+                }
+            }
+
+            let now_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+            let mut recently_played: HashSet<(String, String)> = last_played_by_song()
+                .await
+                .into_iter()
+                .filter(|(_, played_at_ms)| {
+                    now_ms.saturating_sub(*played_at_ms) < RECENT_PLAY_WINDOW_MS
+                })
+                .map(|(key, _)| key)
+                .collect();
+            if let Some(songs) = controller.server_recent_songs.peek().as_ref() {
+                recently_played.extend(
+                    songs
+                        .iter()
+                        .map(|song| (song.server_id.clone(), song.id.clone())),
+                );
+            }
+
+            let limit = controller.app_settings.peek().favorites_shuffle_limit;
+            let mix = favorites_mix(per_server, &recently_played, limit, random_unit);
+            if mix.is_empty() {
+                if let Some(error) = failure {
+                    controller.show_notice(ShuffleNotice::Failed(format!(
+                        "Couldn't load starred songs: {error}"
+                    )));
+                } else if !auth_rejected {
+                    controller.show_notice(ShuffleNotice::Empty);
+                }
+            } else {
+                let mix = assign_collection_queue_meta(
+                    mix,
+                    QueueSourceKind::Favorites,
+                    "favorites::shuffle".to_string(),
+                );
+                let mut queue = controller.queue;
+                let mut queue_index = controller.queue_index;
+                let mut now_playing = controller.now_playing;
+                let mut is_playing = controller.is_playing;
+                now_playing.set(mix.first().cloned());
+                queue.set(mix);
+                queue_index.set(0);
+                is_playing.set(true);
+            }
+            let mut busy = controller.busy;
+            busy.set(false);
+        });
+    }
+
+    fn show_notice(&self, shown: ShuffleNotice) {
+        let mut notice = self.notice;
+        let mut notice_generation = self.notice_generation;
+        notice_generation.with_mut(|generation| *generation = generation.saturating_add(1));
+        let generation = *notice_generation.peek();
+        notice.set(Some(shown));
+
+        spawn_forever(async move {
+            notice_pause(NOTICE_MS).await;
+            if *notice_generation.peek() == generation {
+                notice.set(None);
+            }
+        });
+    }
+}
+
+/// Toast shown when "Shuffle favorites" found nothing starred or couldn't reach a server.
+#[component]
+pub fn FavoritesShuffleNotice() -> Element {
+    let controller = use_context::<FavoritesShuffleController>();
+    let navigation = use_context::<Navigation>();
+
+    let Some(notice) = controller.current_notice() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        div { class: "fixed left-0 right-0 bottom-28 md:bottom-24 px-3 md:px-6 z-[60] flex justify-center pointer-events-none",
+            match notice {
+                ShuffleNotice::Empty => rsx! {
+                    div {
+                        role: "status",
+                        class: "pointer-events-auto flex items-center gap-3 rounded-xl border border-zinc-700 bg-zinc-900/95 px-4 py-2 text-sm text-zinc-200 shadow-xl",
+                        "No starred songs to shuffle yet."
+                        button {
+                            class: "text-emerald-400 hover:text-emerald-300 transition-colors",
+                            onclick: move |_| {
+                                controller.dismiss_notice();
+                                navigation.navigate_to(AppView::FavoritesView {});
+                            },
+                            "Open Favorites"
+                        }
+                        button {
+                            class: "p-1 text-zinc-500 hover:text-white transition-colors",
+                            aria_label: "Dismiss",
+                            onclick: move |_| controller.dismiss_notice(),
+                            Icon { name: "x".to_string(), class: "w-4 h-4".to_string() }
+                        }
+                    }
+                },
+                ShuffleNotice::Failed(message) => rsx! {
+                    div {
+                        role: "alert",
+                        class: "pointer-events-auto flex items-center gap-3 rounded-xl border border-red-500/40 bg-zinc-900/95 px-4 py-2 text-sm text-red-200 shadow-xl",
+                        "{message}"
+                        button {
+                            class: "p-1 text-zinc-500 hover:text-white transition-colors",
+                            aria_label: "Dismiss",
+                            onclick: move |_| controller.dismiss_notice(),
+                            Icon { name: "x".to_string(), class: "w-4 h-4".to_string() }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(server_id: &str, id: &str) -> Song {
+        Song {
+            id: id.to_string(),
+            server_id: server_id.to_string(),
+            title: id.to_string(),
+            ..Song::default()
+        }
+    }
+
+    fn starred(server_id: &str, count: usize) -> Vec<Song> {
+        (0..count)
+            .map(|index| song(server_id, &format!("{server_id}-{index}")))
+            .collect()
+    }
+
+    /// Deterministic stand-in for the random source.
+    fn sequence() -> impl FnMut() -> f64 {
+        let mut state = 0.37f64;
+        move || {
+            state = (state * 9301.0 + 0.49297).fract();
+            state
+        }
+    }
+
+    #[test]
+    fn caps_the_mix_and_keeps_every_song_once() {
+        let mix = favorites_mix(vec![starred("a", 250)], &HashSet::new(), 100, sequence());
+        assert_eq!(mix.len(), 100);
+        let unique: HashSet<_> = mix.iter().map(|song| song.id.clone()).collect();
+        assert_eq!(unique.len(), 100);
+
+        let short = favorites_mix(vec![starred("a", 7)], &HashSet::new(), 100, sequence());
+        assert_eq!(short.len(), 7);
+        assert!(favorites_mix(vec![Vec::new()], &HashSet::new(), 100, sequence()).is_empty());
+    }
+
+    #[test]
+    fn recently_played_songs_come_last() {
+        let recent: HashSet<_> = [("a", "a-0"), ("a", "a-1"), ("a", "a-2")]
+            .into_iter()
+            .map(|(server, id)| (server.to_string(), id.to_string()))
+            .collect();
+        let mix = favorites_mix(vec![starred("a", 10)], &recent, 100, sequence());
+        let tail: HashSet<_> = mix[7..]
+            .iter()
+            .map(|song| (song.server_id.clone(), song.id.clone()))
+            .collect();
+        assert_eq!(tail, recent);
+    }
+
+    #[test]
+    fn servers_share_the_queue_by_starred_count_and_interleave() {
+        let mix = favorites_mix(
+            vec![starred("a", 300), starred("b", 100)],
+            &HashSet::new(),
+            100,
+            sequence(),
+        );
+        let from_b: Vec<usize> = mix
+            .iter()
+            .enumerate()
+            .filter(|(_, song)| song.server_id == "b")
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(mix.len(), 100);
+        assert_eq!(from_b.len(), 25);
+        // One song from the smaller server in every run of four.
+        for window in mix.chunks(4) {
+            assert_eq!(
+                window.iter().filter(|song| song.server_id == "b").count(),
+                1
+            );
+        }
+    }
+
+    #[test]
+    fn quotas_add_up_and_respect_list_sizes() {
+        assert_eq!(proportional_quotas(&[300, 100], 100), vec![75, 25]);
+        assert_eq!(proportional_quotas(&[1, 1, 1], 2).iter().sum::<usize>(), 2);
+        assert_eq!(proportional_quotas(&[5, 0, 200], 100), vec![2, 0, 98]);
+        assert_eq!(proportional_quotas(&[3, 4], 100), vec![3, 4]);
+        assert_eq!(proportional_quotas(&[0, 0], 10), vec![0, 0]);
+    }
+}
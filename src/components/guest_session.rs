@@ -0,0 +1,283 @@
+//! Guest sessions for shared devices: anyone can play music, but nothing they do outlives
+//! the session.
+//!
+//! `crate::db` keeps the guest's queue, history and settings writes in memory while a
+//! session runs. [`ServerCapabilitiesSignal`] refuses every `UserAction` to a guest, which
+//! hides Settings, downloads management and playlist editing, and disables stars and
+//! ratings. Ending the session takes the PIN it was started with.
+use crate::api::Song;
+use crate::components::{AppView, Icon, Navigation, ServerCapabilitiesSignal, SettingsController};
+use crate::db::{
+    end_guest_session, is_valid_guest_pin, load_settings, resume_guest_session,
+    start_guest_session, RepeatMode,
+};
+use dioxus::prelude::*;
+
+/// Provided by the app root next to the playback signals.
+#[derive(Clone, Copy)]
+pub struct GuestSessionController {
+    capabilities: ServerCapabilitiesSignal,
+    settings_controller: SettingsController,
+    queue: Signal<Vec<Song>>,
+    queue_index: Signal<usize>,
+    now_playing: Signal<Option<Song>>,
+    is_playing: Signal<bool>,
+    shuffle_enabled: Signal<bool>,
+    repeat_mode: Signal<RepeatMode>,
+}
+
+impl GuestSessionController {
+    pub fn new(
+        capabilities: ServerCapabilitiesSignal,
+        settings_controller: SettingsController,
+        queue: Signal<Vec<Song>>,
+        queue_index: Signal<usize>,
+        now_playing: Signal<Option<Song>>,
+        is_playing: Signal<bool>,
+        shuffle_enabled: Signal<bool>,
+        repeat_mode: Signal<RepeatMode>,
+    ) -> Self {
+        Self {
+            capabilities,
+            settings_controller,
+            queue,
+            queue_index,
+            now_playing,
+            is_playing,
+            shuffle_enabled,
+            repeat_mode,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.capabilities.is_guest_session()
+    }
+
+    fn set_active(&self, active: bool) {
+        let mut guest_session = self.capabilities.guest_session;
+        guest_session.set(active);
+    }
+
+    /// Picks up the session the previous launch left running.
+    pub async fn resume(self) {
+        if resume_guest_session().await {
+            self.set_active(true);
+        }
+    }
+
+    /// Starts a guest session that only `pin` ends. The owner's last queue stays saved for
+    /// the Queue view's restore list; the guest starts with an empty one.
+    pub async fn start(self, pin: String) -> Result<(), String> {
+        if !is_valid_guest_pin(&pin) {
+            return Err("Use a PIN of 4 to 8 digits.".to_string());
+        }
+        start_guest_session(&pin)
+            .await
+            .map_err(|err| format!("Couldn't start the guest session: {err}"))?;
+        self.clear_playback();
+        self.set_active(true);
+        Ok(())
+    }
+
+    /// Ends the session if `pin` matches. What the guest queued and played is dropped, and
+    /// the saved settings replace any the guest changed.
+    pub async fn exit(self, pin: String) -> Result<(), String> {
+        match end_guest_session(&pin).await {
+            Ok(true) => {}
+            Ok(false) => return Err("Wrong PIN.".to_string()),
+            Err(err) => return Err(format!("Couldn't end the guest session: {err}")),
+        }
+        self.clear_playback();
+        if let Ok(settings) = load_settings().await {
+            let mut shuffle_enabled = self.shuffle_enabled;
+            let mut repeat_mode = self.repeat_mode;
+            shuffle_enabled.set(settings.shuffle_enabled);
+            repeat_mode.set(settings.repeat_mode);
+            self.settings_controller.apply_loaded(settings);
+        }
+        self.set_active(false);
+        Ok(())
+    }
+
+    fn clear_playback(self) {
+        let Self {
+            mut queue,
+            mut queue_index,
+            mut now_playing,
+            mut is_playing,
+            ..
+        } = self;
+        is_playing.set(false);
+        now_playing.set(None);
+        queue.set(Vec::new());
+        queue_index.set(0);
+    }
+}
+
+/// Shown on every page while a guest session runs, with the PIN prompt that ends it.
+#[component]
+pub fn GuestSessionBanner() -> Element {
+    let guest_session = use_context::<GuestSessionController>();
+    let mut prompt_open = use_signal(|| false);
+    let mut pin = use_signal(String::new);
+    let mut busy = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    if !guest_session.is_active() {
+        return rsx! {};
+    }
+
+    let on_exit = move |_| {
+        if busy() {
+            return;
+        }
+        busy.set(true);
+        error.set(None);
+        let entered = pin();
+        spawn(async move {
+            match guest_session.exit(entered).await {
+                Ok(()) => {
+                    prompt_open.set(false);
+                    pin.set(String::new());
+                }
+                Err(message) => error.set(Some(message)),
+            }
+            busy.set(false);
+        });
+    };
+
+    rsx! {
+        div {
+            class: "mb-4 rounded-xl border border-sky-500/30 bg-sky-500/10 p-3 space-y-3",
+            role: "status",
+            div { class: "flex flex-wrap items-center justify-between gap-3",
+                div { class: "flex items-start gap-3 min-w-0",
+                    Icon {
+                        name: "user".to_string(),
+                        class: "w-5 h-5 mt-0.5 text-sky-300 shrink-0".to_string(),
+                    }
+                    div { class: "min-w-0",
+                        p { class: "text-sm font-medium text-sky-100", "Guest mode" }
+                        p { class: "text-xs text-sky-100/80",
+                            "Play anything. Settings, downloads and playlist editing are hidden, and nothing played here is kept."
+                        }
+                    }
+                }
+                if !prompt_open() {
+                    button {
+                        class: "px-3 py-2 rounded-lg border border-sky-400/50 text-sky-100 hover:text-white hover:border-sky-300 transition-colors text-sm",
+                        onclick: move |_| prompt_open.set(true),
+                        "Exit guest mode"
+                    }
+                }
+            }
+            if prompt_open() {
+                div { class: "flex flex-wrap items-center gap-2",
+                    input {
+                        class: "w-36 bg-zinc-900/60 border border-zinc-700 rounded-lg px-3 py-2 text-white text-sm tracking-widest focus:outline-none focus:border-sky-400",
+                        r#type: "password",
+                        inputmode: "numeric",
+                        autocomplete: "off",
+                        placeholder: "PIN",
+                        aria_label: "Guest mode PIN",
+                        value: "{pin}",
+                        oninput: move |e| pin.set(e.value()),
+                    }
+                    button {
+                        class: "px-3 py-2 rounded-lg bg-sky-500/20 text-sky-100 hover:bg-sky-500/30 transition-colors text-sm disabled:opacity-50",
+                        disabled: busy() || pin().is_empty(),
+                        onclick: on_exit,
+                        "Exit"
+                    }
+                    button {
+                        class: "px-3 py-2 rounded-lg text-zinc-400 hover:text-white transition-colors text-sm",
+                        onclick: move |_| {
+                            prompt_open.set(false);
+                            pin.set(String::new());
+                            error.set(None);
+                        },
+                        "Cancel"
+                    }
+                    if let Some(message) = error() {
+                        p { class: "w-full text-xs text-red-300", "{message}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The "Guest mode" section in Settings: choose a PIN and hand the device over.
+#[component]
+pub fn GuestSessionSettings() -> Element {
+    let guest_session = use_context::<GuestSessionController>();
+    let navigation = use_context::<Navigation>();
+    let mut pin = use_signal(String::new);
+    let mut confirm_pin = use_signal(String::new);
+    let mut busy = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    let on_start = move |_| {
+        if busy() {
+            return;
+        }
+        if pin() != confirm_pin() {
+            error.set(Some("The PINs don't match.".to_string()));
+            return;
+        }
+        busy.set(true);
+        error.set(None);
+        let entered = pin();
+        let navigation = navigation.clone();
+        spawn(async move {
+            match guest_session.start(entered).await {
+                Ok(()) => {
+                    pin.set(String::new());
+                    confirm_pin.set(String::new());
+                    navigation.navigate_to(AppView::HomeView {});
+                }
+                Err(message) => error.set(Some(message)),
+            }
+            busy.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "space-y-3",
+            p { class: "text-sm text-zinc-400",
+                "Let someone else play music without changing your library. Settings, downloads and playlist editing are hidden, stars and ratings are off, and their queue and listening history are dropped when guest mode ends. Ending it takes the PIN you choose here."
+            }
+            div { class: "flex flex-wrap items-center gap-2",
+                input {
+                    class: "w-36 bg-zinc-900/60 border border-zinc-700 rounded-lg px-3 py-2 text-white text-sm tracking-widest focus:outline-none focus:border-emerald-500",
+                    r#type: "password",
+                    inputmode: "numeric",
+                    autocomplete: "off",
+                    placeholder: "PIN",
+                    aria_label: "Guest mode PIN",
+                    value: "{pin}",
+                    oninput: move |e| pin.set(e.value()),
+                }
+                input {
+                    class: "w-36 bg-zinc-900/60 border border-zinc-700 rounded-lg px-3 py-2 text-white text-sm tracking-widest focus:outline-none focus:border-emerald-500",
+                    r#type: "password",
+                    inputmode: "numeric",
+                    autocomplete: "off",
+                    placeholder: "Repeat PIN",
+                    aria_label: "Repeat the guest mode PIN",
+                    value: "{confirm_pin}",
+                    oninput: move |e| confirm_pin.set(e.value()),
+                }
+                button {
+                    class: "px-3 py-2 rounded-lg border border-emerald-500/50 text-emerald-300 hover:text-white hover:border-emerald-400 transition-colors text-sm disabled:opacity-50",
+                    disabled: busy() || pin().is_empty(),
+                    onclick: on_start,
+                    "Start guest mode"
+                }
+            }
+            if let Some(message) = error() {
+                p { class: "text-xs text-red-300", "{message}" }
+            }
+        }
+    }
+}
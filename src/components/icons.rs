@@ -356,6 +356,43 @@ pub fn Icon(name: String, class: String) -> Element {
                 path { d: "M3 18h.01" }
             }
         },
+        "grid" => rsx! {
+            svg {
+                class: "{class}",
+                view_box: "0 0 24 24",
+                fill: "none",
+                stroke: "currentColor",
+                stroke_width: "2",
+                rect {
+                    x: "3",
+                    y: "3",
+                    width: "7",
+                    height: "7",
+                    rx: "1",
+                }
+                rect {
+                    x: "14",
+                    y: "3",
+                    width: "7",
+                    height: "7",
+                    rx: "1",
+                }
+                rect {
+                    x: "3",
+                    y: "14",
+                    width: "7",
+                    height: "7",
+                    rx: "1",
+                }
+                rect {
+                    x: "14",
+                    y: "14",
+                    width: "7",
+                    height: "7",
+                    rx: "1",
+                }
+            }
+        },
         "bars" => rsx! {
             svg {
                 class: "{class}",
@@ -418,6 +455,23 @@ pub fn Icon(name: String, class: String) -> Element {
                 }
             }
         },
+        "upload" => rsx! {
+            svg {
+                class: "{class}",
+                view_box: "0 0 24 24",
+                fill: "none",
+                stroke: "currentColor",
+                stroke_width: "2",
+                path { d: "M21 15v4a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2v-4" }
+                polyline { points: "17 8 12 3 7 8" }
+                line {
+                    x1: "12",
+                    y1: "3",
+                    x2: "12",
+                    y2: "15",
+                }
+            }
+        },
         "server" => rsx! {
             svg {
                 class: "{class}",
@@ -513,6 +567,39 @@ pub fn Icon(name: String, class: String) -> Element {
                 polyline { points: "12 6 12 12 16 14" }
             }
         },
+        "moon" => rsx! {
+            svg {
+                class: "{class}",
+                view_box: "0 0 24 24",
+                fill: "none",
+                stroke: "currentColor",
+                stroke_width: "2",
+                path { d: "M21 12.79A9 9 0 1 1 11.21 3 7 7 0 0 0 21 12.79z" }
+            }
+        },
+        "user" => rsx! {
+            svg {
+                class: "{class}",
+                view_box: "0 0 24 24",
+                fill: "none",
+                stroke: "currentColor",
+                stroke_width: "2",
+                path { d: "M20 21v-2a4 4 0 0 0-4-4H8a4 4 0 0 0-4 4v2" }
+                circle { cx: "12", cy: "7", r: "4" }
+            }
+        },
+        "alert" => rsx! {
+            svg {
+                class: "{class}",
+                view_box: "0 0 24 24",
+                fill: "none",
+                stroke: "currentColor",
+                stroke_width: "2",
+                path { d: "M10.29 3.86 1.82 18a2 2 0 0 0 1.71 3h16.94a2 2 0 0 0 1.71-3L13.71 3.86a2 2 0 0 0-3.42 0z" }
+                line { x1: "12", y1: "9", x2: "12", y2: "13" }
+                line { x1: "12", y1: "17", x2: "12.01", y2: "17" }
+            }
+        },
         "trash" => rsx! {
             svg {
                 class: "{class}",
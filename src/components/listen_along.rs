@@ -0,0 +1,472 @@
+//! Listen along from the app's side: following another device's playback, and on desktop
+//! and mobile letting other devices follow this one.
+//!
+//! [`ListenAlongController`] polls the followed host, loads the host's song from this
+//! device's servers and seeks when `crate::listen_along::drift_correction` says it has
+//! drifted. While it follows, the Player swaps its transport buttons for
+//! [`ListenAlongControls`], leaving only volume and "Leave".
+use crate::api::{NavidromeClient, ServerConfig, Song};
+use crate::components::{play_song, seek_to, Icon};
+use crate::listen_along::{
+    drift_correction, listen_along_url, set_following, DriftCorrection, NowPlaying, NowPlayingSong,
+};
+use crate::network_policy::{strict_privacy_enabled, GuardedClient};
+use dioxus::core::spawn_forever;
+use dioxus::prelude::*;
+use once_cell::sync::Lazy;
+
+static LISTEN_ALONG_HTTP_CLIENT: Lazy<GuardedClient> = Lazy::new(GuardedClient::new);
+
+/// How often a follower asks the host what it is playing.
+const FOLLOW_POLL_MS: u64 = 1_000;
+/// How long a follower leaves its position alone after loading or seeking, so a track that
+/// is still buffering isn't seeked again.
+const FOLLOW_SETTLE_MS: u64 = 3_000;
+
+#[cfg(target_arch = "wasm32")]
+async fn follow_pause(ms: u64) {
+    gloo_timers::future::TimeoutFuture::new(ms as u32).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn follow_pause(ms: u64) {
+    tokio::time::sleep(std::time::Duration::from_millis(ms)).await;
+}
+
+fn now_ms() -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Date::now().max(0.0).round() as u64
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or(0)
+    }
+}
+
+/// Whether this device serves its playback to followers.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, PartialEq)]
+pub enum Hosting {
+    Off,
+    /// `address` is what followers type in, when the device knows its network address.
+    On {
+        address: Option<String>,
+    },
+    Failed(String),
+}
+
+/// Per-follow bookkeeping for the poll loop.
+#[derive(Default)]
+struct FollowProgress {
+    last_seek_ms: u64,
+    /// Host song none of this device's servers has, so it isn't looked up every poll.
+    unavailable: Option<String>,
+}
+
+/// Provided by the app root next to the playback signals.
+#[derive(Clone, Copy)]
+pub struct ListenAlongController {
+    servers: Signal<Vec<ServerConfig>>,
+    queue: Signal<Vec<Song>>,
+    queue_index: Signal<usize>,
+    now_playing: Signal<Option<Song>>,
+    is_playing: Signal<bool>,
+    playback_position: Signal<f64>,
+    seek_request: Signal<Option<(String, f64)>>,
+    /// Endpoint of the host being followed.
+    following: Signal<Option<String>>,
+    /// Bumped on every follow and leave so an earlier poll loop stops.
+    generation: Signal<u64>,
+    status: Signal<Option<String>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    hosting: Signal<Hosting>,
+}
+
+impl ListenAlongController {
+    pub fn new(
+        servers: Signal<Vec<ServerConfig>>,
+        queue: Signal<Vec<Song>>,
+        queue_index: Signal<usize>,
+        now_playing: Signal<Option<Song>>,
+        is_playing: Signal<bool>,
+        playback_position: Signal<f64>,
+        seek_request: Signal<Option<(String, f64)>>,
+    ) -> Self {
+        Self {
+            servers,
+            queue,
+            queue_index,
+            now_playing,
+            is_playing,
+            playback_position,
+            seek_request,
+            following: Signal::new(None),
+            generation: Signal::new(0),
+            status: Signal::new(None),
+            #[cfg(not(target_arch = "wasm32"))]
+            hosting: Signal::new(Hosting::Off),
+        }
+    }
+
+    /// Endpoint of the host being followed, if any.
+    pub fn following(&self) -> Option<String> {
+        (self.following)()
+    }
+
+    pub fn is_following(&self) -> bool {
+        self.following.read().is_some()
+    }
+
+    /// Why the follower isn't in step with the host right now, if it isn't.
+    pub fn status(&self) -> Option<String> {
+        (self.status)()
+    }
+
+    /// Starts following the host at `address`. The error is worded for the Settings form.
+    pub fn follow(mut self, address: &str) -> Result<(), &'static str> {
+        let Some(url) = listen_along_url(address) else {
+            return Err("Enter the address shown on the hosting device.");
+        };
+        let generation = *self.generation.peek() + 1;
+        self.generation.set(generation);
+        self.following.set(Some(url.clone()));
+        self.status.set(Some("Connecting…".to_string()));
+        set_following(true);
+        spawn_forever(async move {
+            let mut progress = FollowProgress::default();
+            while *self.generation.peek() == generation {
+                self.poll_host(&url, generation, &mut progress).await;
+                follow_pause(FOLLOW_POLL_MS).await;
+            }
+        });
+        Ok(())
+    }
+
+    /// Stops following. Whatever is playing keeps playing under local control.
+    pub fn leave(mut self) {
+        let generation = *self.generation.peek() + 1;
+        self.generation.set(generation);
+        self.following.set(None);
+        self.status.set(None);
+        set_following(false);
+    }
+
+    fn set_status(mut self, status: Option<String>) {
+        if *self.status.peek() != status {
+            self.status.set(status);
+        }
+    }
+
+    async fn poll_host(mut self, url: &str, generation: u64, progress: &mut FollowProgress) {
+        let sent_at = now_ms();
+        let host = match LISTEN_ALONG_HTTP_CLIENT.get(url).send().await {
+            Ok(response) => response.json::<NowPlaying>().await.ok(),
+            Err(_) => None,
+        };
+        if *self.generation.peek() != generation {
+            return;
+        }
+        let Some(host) = host else {
+            self.set_status(Some(if strict_privacy_enabled() {
+                "Strict privacy mode blocks requests to the host.".to_string()
+            } else {
+                "Can't reach the host. Retrying…".to_string()
+            }));
+            return;
+        };
+        // The host took its snapshot about half a round trip ago.
+        let latency = if host.playing {
+            now_ms().saturating_sub(sent_at) as f64 / 2000.0
+        } else {
+            0.0
+        };
+        let Some(host_song) = host.song else {
+            self.set_status(Some("The host isn't playing anything.".to_string()));
+            if *self.is_playing.peek() {
+                self.is_playing.set(false);
+            }
+            return;
+        };
+
+        let current = self
+            .now_playing
+            .peek()
+            .as_ref()
+            .is_some_and(|song| song.id == host_song.id);
+        if !current {
+            if progress.unavailable.as_deref() == Some(host_song.id.as_str()) {
+                return;
+            }
+            let Some(song) = self.find_song(&host_song).await else {
+                progress.unavailable = Some(host_song.id.clone());
+                self.set_status(Some(format!(
+                    "\"{}\" isn't on your servers.",
+                    host_song.title
+                )));
+                return;
+            };
+            if *self.generation.peek() != generation {
+                return;
+            }
+            let start = host.position + latency;
+            self.seek_request.set(Some((song.id.clone(), start)));
+            self.playback_position.set(start);
+            play_song(
+                song,
+                self.now_playing,
+                self.queue,
+                self.queue_index,
+                self.is_playing,
+            );
+            self.is_playing.set(host.playing);
+            progress.last_seek_ms = now_ms();
+            self.set_status(None);
+            return;
+        }
+
+        self.set_status(None);
+        if *self.is_playing.peek() != host.playing {
+            self.is_playing.set(host.playing);
+        }
+        if now_ms().saturating_sub(progress.last_seek_ms) < FOLLOW_SETTLE_MS {
+            return;
+        }
+        let local = *self.playback_position.peek();
+        if let DriftCorrection::SeekTo(target) = drift_correction(host.position, local, latency) {
+            seek_to(target);
+            progress.last_seek_ms = now_ms();
+        }
+    }
+
+    /// The host's song from this device's copy of the host's server, or else from any
+    /// active server that has it.
+    async fn find_song(&self, host_song: &NowPlayingSong) -> Option<Song> {
+        let host_url = host_song.server_url.trim_end_matches('/');
+        let mut servers: Vec<ServerConfig> = self
+            .servers
+            .peek()
+            .iter()
+            .filter(|server| server.active)
+            .cloned()
+            .collect();
+        servers.sort_by_key(|server| server.url.trim_end_matches('/') != host_url);
+        for server in servers {
+            if let Ok(song) = NavidromeClient::new(server).get_song(&host_song.id).await {
+                return Some(song);
+            }
+        }
+        None
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn hosting(&self) -> Hosting {
+        (self.hosting)()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn start_hosting(mut self) {
+        spawn_forever(async move {
+            let hosting = match crate::listen_along::start_hosting().await {
+                Ok(()) => Hosting::On {
+                    address: crate::listen_along::lan_address(),
+                },
+                Err(err) => Hosting::Failed(format!("Couldn't start sharing: {err}")),
+            };
+            self.hosting.set(hosting);
+        });
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn stop_hosting(mut self) {
+        crate::listen_along::stop_hosting();
+        self.hosting.set(Hosting::Off);
+    }
+}
+
+/// Keeps what followers are told in step with playback while this device hosts.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn use_listen_along_host(controller: ListenAlongController) {
+    use crate::listen_along::publish;
+
+    use_effect(move || {
+        if !matches!((controller.hosting)(), Hosting::On { .. }) {
+            return;
+        }
+        let song = (controller.now_playing)().filter(|song| song.server_name != "Radio");
+        let playing = (controller.is_playing)();
+        let position = (controller.playback_position)();
+        let servers = controller.servers.peek();
+        publish(NowPlaying {
+            song: song.map(|song| NowPlayingSong {
+                server_url: servers
+                    .iter()
+                    .find(|server| server.id == song.server_id)
+                    .map(|server| server.url.clone())
+                    .unwrap_or_default(),
+                id: song.id,
+                title: song.title,
+                artist: song.artist,
+                album: song.album,
+                cover_art: song.cover_art,
+                duration: song.duration,
+            }),
+            position,
+            playing,
+        });
+    });
+}
+
+/// Stands in for the Player's transport buttons while following.
+#[component]
+pub fn ListenAlongControls() -> Element {
+    let listen_along = use_context::<ListenAlongController>();
+
+    rsx! {
+        div { class: "flex items-center gap-3 justify-center w-full min-w-0",
+            Icon { name: "radio".to_string(), class: "w-4 h-4 text-emerald-400 shrink-0".to_string() }
+            div { class: "min-w-0",
+                p { class: "text-sm text-white", "Listening along" }
+                if let Some(status) = listen_along.status() {
+                    p { class: "text-xs text-zinc-400 truncate", "{status}" }
+                }
+            }
+            button {
+                r#type: "button",
+                class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors text-xs shrink-0",
+                onclick: move |_| listen_along.leave(),
+                "Leave"
+            }
+        }
+    }
+}
+
+/// The "Listen Along" section in Settings: follow another device, and share this one.
+#[component]
+pub fn ListenAlongSettings() -> Element {
+    let listen_along = use_context::<ListenAlongController>();
+    let mut address = use_signal(String::new);
+    let mut error = use_signal(|| None::<&'static str>);
+
+    rsx! {
+        div { class: "space-y-4",
+            if let Some(url) = listen_along.following() {
+                div { class: "flex items-center justify-between gap-4",
+                    div { class: "min-w-0",
+                        p { class: "font-medium text-white", "Following another device" }
+                        p { class: "text-xs text-zinc-400 font-mono break-all", "{url}" }
+                        if let Some(status) = listen_along.status() {
+                            p { class: "text-xs text-amber-300 mt-1", "{status}" }
+                        }
+                    }
+                    button {
+                        r#type: "button",
+                        class: "px-3 py-2 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors text-sm shrink-0",
+                        onclick: move |_| listen_along.leave(),
+                        "Leave"
+                    }
+                }
+            } else {
+                div { class: "space-y-2",
+                    p { class: "font-medium text-white", "Follow another device" }
+                    p { class: "text-sm text-zinc-400",
+                        "Plays what another RustySound on your network is playing, in step with it. Both devices need the same server. Only volume stays under your control until you leave."
+                    }
+                    form {
+                        class: "flex items-center gap-2",
+                        onsubmit: move |e: FormEvent| {
+                            e.prevent_default();
+                            let followed = listen_along.follow(&address.peek());
+                            match followed {
+                                Ok(()) => {
+                                    address.set(String::new());
+                                    error.set(None);
+                                }
+                                Err(message) => error.set(Some(message)),
+                            }
+                        },
+                        input {
+                            class: "min-w-0 flex-1 rounded-lg border border-zinc-700 bg-zinc-950/70 px-3 py-2 text-sm text-white focus:outline-none focus:border-emerald-500/50",
+                            r#type: "text",
+                            placeholder: "192.168.1.20:47831",
+                            aria_label: "Host address",
+                            value: address,
+                            oninput: move |e| address.set(e.value()),
+                        }
+                        button {
+                            r#type: "submit",
+                            class: "px-3 py-2 rounded-lg border border-emerald-500/50 text-emerald-300 hover:text-white hover:border-emerald-400 transition-colors text-sm shrink-0",
+                            "Follow"
+                        }
+                    }
+                    if let Some(message) = error() {
+                        p { class: "text-xs text-red-300", "{message}" }
+                    }
+                }
+            }
+            ListenAlongHostSettings {}
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[component]
+fn ListenAlongHostSettings() -> Element {
+    let listen_along = use_context::<ListenAlongController>();
+    let hosting = listen_along.hosting();
+    let on = matches!(hosting, Hosting::On { .. });
+
+    rsx! {
+        div { class: "space-y-2",
+            div { class: "flex items-center justify-between gap-4",
+                div {
+                    p { class: "font-medium text-white", "Let others follow this device" }
+                    p { class: "text-sm text-zinc-400",
+                        "Shares what's playing and where, on your local network only, until you turn it off or close the app."
+                    }
+                }
+                button {
+                    r#type: "button",
+                    aria_label: "Let others follow this device",
+                    aria_pressed: on,
+                    class: if on { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors flex-shrink-0" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors flex-shrink-0" },
+                    onclick: move |_| {
+                        if on {
+                            listen_along.stop_hosting();
+                        } else {
+                            listen_along.start_hosting();
+                        }
+                    },
+                    div { class: if on { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                }
+            }
+            match hosting {
+                Hosting::On { address: Some(address) } => rsx! {
+                    p { class: "text-sm text-zinc-300",
+                        "Others follow with "
+                        span { class: "font-mono text-emerald-300", "{address}" }
+                    }
+                },
+                Hosting::On { address: None } => rsx! {
+                    p { class: "text-sm text-zinc-400",
+                        "Sharing, but this device's network address couldn't be found."
+                    }
+                },
+                Hosting::Failed(message) => rsx! {
+                    p { class: "text-xs text-red-300", "{message}" }
+                },
+                Hosting::Off => rsx! {},
+            }
+        }
+    }
+}
+
+/// Browsers can't accept connections, so they can only follow.
+#[cfg(target_arch = "wasm32")]
+#[component]
+fn ListenAlongHostSettings() -> Element {
+    rsx! {}
+}
@@ -4,15 +4,32 @@ mod add_to_menu;
 mod app;
 mod app_view;
 mod audio_manager;
+mod background_jobs;
 mod cached_image;
+mod crash_report_banner;
+#[cfg(not(target_arch = "wasm32"))]
+mod database_recovery;
+mod favorites_shuffle;
+mod guest_session;
 mod icons;
+mod listen_along;
 mod navigation;
+mod pending_sync;
 mod player;
+mod queue_drop;
+mod quiet_hours;
+mod reauth_prompt;
+mod session_summary_banner;
+mod settings_controller;
 mod sidebar;
+mod sleep_timer;
 mod song_details;
+mod unavailable_songs;
 mod views;
 
+use crate::api::{ServerCapabilities, UserAction, GUEST_DENIED_HINT};
 use dioxus::prelude::Signal;
+use std::collections::HashMap;
 
 #[derive(Clone)]
 pub struct VolumeSignal(pub Signal<f64>);
@@ -39,13 +56,87 @@ pub struct IsPlayingSignal(pub Signal<bool>);
 #[derive(Clone)]
 pub struct ShuffleEnabledSignal(pub Signal<bool>);
 
+/// The armed "stop after" request, cleared once it fires or playback leaves its boundary.
+#[derive(Clone)]
+pub struct StopAfterSignal(pub Signal<Option<StopAfter>>);
+
+/// The running sleep timer, cleared once it pauses playback or is cancelled.
+#[derive(Clone)]
+pub struct SleepTimerSignal(pub Signal<Option<SleepTimer>>);
+
+/// Probed capabilities per server id, and whether a guest session is running. A guest is
+/// refused every [`UserAction`] the way an account without the role is, so one check gates
+/// both.
+#[derive(Clone, Copy)]
+pub struct ServerCapabilitiesSignal {
+    pub capabilities: Signal<HashMap<String, ServerCapabilities>>,
+    pub guest_session: Signal<bool>,
+}
+
+impl ServerCapabilitiesSignal {
+    /// Whether a server supports `feature`. Servers that haven't been probed yet count as
+    /// supporting it, so nothing disappears while a probe is in flight.
+    pub fn supports(&self, server_id: &str, feature: impl Fn(&ServerCapabilities) -> bool) -> bool {
+        (self.capabilities)().get(server_id).is_none_or(feature)
+    }
+
+    /// Whether the signed-in user of a server may do `action`. Unprobed servers and roles
+    /// the server didn't report count as granted; guest sessions may do nothing.
+    pub fn user_can(&self, server_id: &str, action: UserAction) -> bool {
+        !self.is_guest_session()
+            && self.supports(server_id, |capabilities| capabilities.user_can(action))
+    }
+
+    /// Tooltip for an affordance [`Self::user_can`] refused.
+    pub fn denied_hint(&self, action: UserAction) -> &'static str {
+        if self.is_guest_session() {
+            GUEST_DENIED_HINT
+        } else {
+            action.denied_hint()
+        }
+    }
+
+    /// Settings, downloads management and other affordances that aren't tied to one server
+    /// are hidden while this is true.
+    pub fn is_guest_session(&self) -> bool {
+        (self.guest_session)()
+    }
+}
+
+/// Last raw ICY stream title as `(song_id, raw_title)` for the playing radio station.
+#[derive(Clone)]
+pub struct RadioRawTitleSignal(pub Signal<Option<(String, String)>>);
+
 pub use add_to_menu::*;
 pub use app::*;
-pub use app_view::{view_instance_key, view_label, AppView};
+pub use app_view::{hidden_from_guests, view_instance_key, view_label, AppView};
 pub use audio_manager::*;
+pub use background_jobs::{
+    BackgroundJobsIndicator, JobRegistry, AUTO_DOWNLOAD_JOB, CACHE_REFRESH_JOB, DOWNLOAD_VERIFY_JOB,
+};
+pub use cached_image::{sized_cover_art_url, use_lazy_cover_loader, ArtworkKind, CachedImage};
+pub use crash_report_banner::CrashReportBanner;
+#[cfg(not(target_arch = "wasm32"))]
+pub use database_recovery::DatabaseRecoveryScreen;
+pub use favorites_shuffle::{FavoritesShuffleController, FavoritesShuffleNotice};
+pub use guest_session::{GuestSessionBanner, GuestSessionController, GuestSessionSettings};
 pub use icons::*;
-pub use navigation::Navigation;
+#[cfg(not(target_arch = "wasm32"))]
+pub use listen_along::use_listen_along_host;
+pub use listen_along::{ListenAlongController, ListenAlongControls, ListenAlongSettings};
+pub use navigation::{use_restored_scroll, use_view_state, Navigation, ViewStates};
+pub use pending_sync::{PendingSyncBanner, PendingSyncController, PendingSyncSettings};
 pub use player::*;
+pub use queue_drop::{QueueDragController, QueueDropBadge, QueueDropTarget};
+pub use quiet_hours::{use_quiet_hours_clock, QuietHoursController, QuietHoursIndicator};
+pub use reauth_prompt::ReauthPrompt;
+pub use session_summary_banner::SessionSummaryBanner;
+pub(crate) use settings_controller::normalize_volume;
+pub use settings_controller::SettingsController;
 pub use sidebar::*;
+pub use sleep_timer::{use_sleep_timer, SleepTimer, SleepTimerChip, SLEEP_TIMER_MINUTES};
 pub use song_details::*;
+pub use unavailable_songs::{
+    next_available_index, unavailable_skip_notice, UnavailableSongsSignal, UNAVAILABLE_SONG_TOOLTIP,
+};
 // Views are accessed via views::ViewName
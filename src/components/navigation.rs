@@ -1,5 +1,9 @@
+use dioxus::core::spawn_forever;
 use dioxus::prelude::*;
 use dioxus_router::Navigator;
+use std::any::Any;
+use std::collections::HashMap;
+use std::rc::Rc;
 
 use crate::components::app_view::AppView;
 
@@ -13,8 +17,137 @@ pub struct Navigation(
     pub Navigator,
     pub Signal<AppView>,
     pub Signal<Option<AppView>>,
+    pub ViewStates,
 );
 
+/// What list views looked like when they were left: filters, sort, how far they were paged
+/// and where they were scrolled, keyed by route. Going back to a view restores it; navigating
+/// to it afresh drops what was saved, so it starts over.
+#[derive(Clone, Copy)]
+pub struct ViewStates {
+    saved: Signal<HashMap<String, SavedView>>,
+}
+
+#[derive(Default)]
+struct SavedView {
+    values: HashMap<&'static str, Rc<dyn Any>>,
+    scroll_top: Option<f64>,
+}
+
+impl Default for ViewStates {
+    fn default() -> Self {
+        Self {
+            saved: Signal::new(HashMap::new()),
+        }
+    }
+}
+
+impl ViewStates {
+    fn restored<T: Clone + 'static>(&self, route: &str, key: &'static str) -> Option<T> {
+        self.saved
+            .peek()
+            .get(route)?
+            .values
+            .get(key)?
+            .downcast_ref::<T>()
+            .cloned()
+    }
+
+    fn save<T: 'static>(&self, route: &str, key: &'static str, value: T) {
+        let mut saved = self.saved;
+        saved
+            .write()
+            .entry(route.to_string())
+            .or_default()
+            .values
+            .insert(key, Rc::new(value));
+    }
+
+    fn take_scroll_top(&self, route: &str) -> Option<f64> {
+        let mut saved = self.saved;
+        let mut views = saved.write();
+        views.get_mut(route)?.scroll_top.take()
+    }
+
+    fn forget(&self, view: &AppView) {
+        let mut saved = self.saved;
+        if saved.peek().contains_key(&view.to_string()) {
+            saved.write().remove(&view.to_string());
+        }
+    }
+
+    /// Drops every saved view, as after a refresh or a change of servers.
+    pub fn clear(&self) {
+        let mut saved = self.saved;
+        if !saved.peek().is_empty() {
+            saved.write().clear();
+        }
+    }
+
+    fn set_scroll_top(&self, route: &str, scroll_top: f64) {
+        let mut saved = self.saved;
+        let mut views = saved.write();
+        if let Some(view) = views.get_mut(route) {
+            view.scroll_top = Some(scroll_top);
+        }
+    }
+
+    /// Records how far `view` is scrolled as it is left. The position has to be read before
+    /// the navigation resets it, so it is taken synchronously on web and queued ahead of the
+    /// reset on native.
+    #[cfg(target_arch = "wasm32")]
+    fn remember_scroll(&self, view: &AppView) {
+        if let Some(scroll_top) = main_scroll_top() {
+            self.set_scroll_top(&view.to_string(), scroll_top);
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn remember_scroll(&self, view: &AppView) {
+        let route = view.to_string();
+        let states = *self;
+        let read = main_scroll_position();
+        spawn_forever(async move {
+            if let Ok(scroll_top) = read.join::<f64>().await {
+                states.set_scroll_top(&route, scroll_top);
+            }
+        });
+    }
+}
+
+/// A piece of a list view's state, such as a filter or sort, saved under `key` as it changes.
+/// Going back to the view brings it back; otherwise it starts from `initial`.
+pub fn use_view_state<T: Clone + 'static>(
+    key: &'static str,
+    initial: impl FnOnce() -> T,
+) -> Signal<T> {
+    let states = use_context::<Navigation>().3;
+    let route = use_route::<AppView>().to_string();
+    let state = use_signal(|| states.restored(&route, key).unwrap_or_else(initial));
+    use_effect(move || states.save(&route, key, state()));
+    state
+}
+
+/// Scrolls a view back to where it was left once `ready` says its content is showing, when
+/// it was reached by going back.
+pub fn use_restored_scroll(ready: impl Fn() -> bool + 'static) {
+    let states = use_context::<Navigation>().3;
+    let route = use_route::<AppView>().to_string();
+    let mut pending = use_signal(|| states.take_scroll_top(&route));
+    use_effect(move || {
+        if !ready() {
+            return;
+        }
+        let Some(scroll_top) = pending.write().take() else {
+            return;
+        };
+        spawn(async move {
+            route_refresh_pause().await;
+            set_main_scroll_position(scroll_top);
+        });
+    });
+}
+
 fn should_refresh_detail_route(current: &AppView, target: &AppView) -> bool {
     match (current, target) {
         (
@@ -62,13 +195,17 @@ async fn route_refresh_pause() {
 }
 
 fn reset_main_scroll_position() {
+    set_main_scroll_position(0.0);
+}
+
+fn set_main_scroll_position(top: f64) {
     #[cfg(target_arch = "wasm32")]
     {
         if let Some(win) = window() {
             if let Some(document) = win.document() {
                 if let Ok(Some(main)) = document.query_selector("main.main-scroll, .main-scroll") {
                     if let Some(element) = main.dyn_ref::<HtmlElement>() {
-                        element.set_scroll_top(0);
+                        element.set_scroll_top(top.round() as i32);
                     }
                 }
             }
@@ -78,36 +215,60 @@ fn reset_main_scroll_position() {
 
     #[cfg(not(target_arch = "wasm32"))]
     {
-        let _ = document::eval(
+        let _ = document::eval(&format!(
             r#"
-(() => {
+(() => {{
   const main = document.querySelector("main.main-scroll, .main-scroll");
-  if (main && typeof main.scrollTo === "function") {
-    main.scrollTo({ top: 0, left: 0, behavior: "auto" });
-  } else if (main) {
-    main.scrollTop = 0;
-  }
-  if (typeof window !== "undefined" && typeof window.scrollTo === "function") {
+  if (main && typeof main.scrollTo === "function") {{
+    main.scrollTo({{ top: {top}, left: 0, behavior: "auto" }});
+  }} else if (main) {{
+    main.scrollTop = {top};
+  }}
+  if (typeof window !== "undefined" && typeof window.scrollTo === "function") {{
     window.scrollTo(0, 0);
-  }
+  }}
   return true;
-})();
-            "#,
-        );
+}})();
+            "#
+        ));
     }
 }
 
+#[cfg(target_arch = "wasm32")]
+fn main_scroll_top() -> Option<f64> {
+    let main = window()?
+        .document()?
+        .query_selector("main.main-scroll, .main-scroll")
+        .ok()??;
+    Some(main.dyn_ref::<HtmlElement>()?.scroll_top() as f64)
+}
+
+/// Starts reading the main scroll position. Created before any reset is sent, the read
+/// runs first even though its result is awaited later.
+#[cfg(not(target_arch = "wasm32"))]
+fn main_scroll_position() -> document::Eval {
+    document::eval(
+        r#"
+const main = document.querySelector("main.main-scroll, .main-scroll");
+return main ? main.scrollTop : 0;
+        "#,
+    )
+}
+
 impl Navigation {
     pub fn new(
         navigator: Navigator,
         current_view: Signal<AppView>,
         pending_target: Signal<Option<AppView>>,
+        view_states: ViewStates,
     ) -> Self {
-        Self(navigator, current_view, pending_target)
+        Self(navigator, current_view, pending_target, view_states)
     }
 
     pub fn navigate_to(&self, target: AppView) {
         let current_view = self.1();
+        self.3.remember_scroll(&current_view);
+        self.3.forget(&target);
         if should_refresh_detail_route(&current_view, &target) {
             let mut current_view_signal = self.1;
             let mut pending_target = self.2;
@@ -150,11 +311,17 @@ impl Navigation {
         });
     }
 
+    /// Forgets how every list view was left, after an explicit refresh.
+    pub fn clear_view_states(&self) {
+        self.3.clear();
+    }
+
     pub fn can_go_back(&self) -> bool {
         self.0.can_go_back()
     }
 
     pub fn go_back(&self) -> Option<AppView> {
+        self.3.remember_scroll(&self.1.peek());
         self.0.go_back();
         reset_main_scroll_position();
         None // Router handles the navigation, we don't need to return the view
@@ -167,6 +334,7 @@ impl Navigation {
 
     #[cfg(target_arch = "wasm32")]
     pub fn go_forward(&self) -> Option<AppView> {
+        self.3.remember_scroll(&self.1.peek());
         self.0.go_forward();
         reset_main_scroll_position();
         None // Router handles the navigation, we don't need to return the view
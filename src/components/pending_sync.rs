@@ -0,0 +1,237 @@
+//! Replay of server mutations queued while a server was unreachable.
+//!
+//! The API client queues stars, ratings and playlist appends it couldn't send (see
+//! `crate::db::queue_pending_mutation`). [`PendingSyncController`] replays that queue when
+//! the app is back online, collects the conflicts resolved in the server's favour for
+//! [`PendingSyncBanner`], and backs the pending sync list in Settings.
+use crate::api::{NavidromeClient, ServerConfig};
+use crate::components::Icon;
+use crate::db::{
+    discard_pending_mutation, next_pending_mutation, pending_mutations,
+    record_pending_mutation_outcome, retry_pending_mutation, AppSettings, PendingMutation,
+    PendingMutationOutcome, PendingSyncPass,
+};
+use crate::time_format::humanize_epoch_ms_for_display;
+use dioxus::core::spawn_forever;
+use dioxus::prelude::*;
+
+/// Provided by the app root next to the servers signal.
+#[derive(Clone, Copy)]
+pub struct PendingSyncController {
+    servers: Signal<Vec<ServerConfig>>,
+    pending: Signal<Vec<PendingMutation>>,
+    notices: Signal<Vec<String>>,
+    replaying: Signal<bool>,
+}
+
+impl PendingSyncController {
+    pub fn new(servers: Signal<Vec<ServerConfig>>) -> Self {
+        Self {
+            servers,
+            pending: Signal::new(Vec::new()),
+            notices: Signal::new(Vec::new()),
+            replaying: Signal::new(false),
+        }
+    }
+
+    /// Mutations still waiting, as last read from storage.
+    pub fn pending(&self) -> Vec<PendingMutation> {
+        (self.pending)()
+    }
+
+    /// Conflicts from earlier replays that the user hasn't dismissed.
+    pub fn notices(&self) -> Vec<String> {
+        (self.notices)()
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        (self.replaying)()
+    }
+
+    pub fn refresh(&self) {
+        let mut pending = self.pending;
+        spawn_forever(async move {
+            pending.set(pending_mutations().await);
+        });
+    }
+
+    /// Sends queued mutations oldest first. Servers that are disabled or still unreachable
+    /// keep theirs for the next pass.
+    pub fn replay(&self) {
+        let mut replaying = self.replaying;
+        if *replaying.peek() {
+            return;
+        }
+        replaying.set(true);
+        let servers = self.servers;
+        let mut notices = self.notices;
+        let controller = *self;
+
+        spawn_forever(async move {
+            let mut pass = PendingSyncPass::default();
+            while let Some(mutation) = next_pending_mutation(&pass).await {
+                let server = servers
+                    .peek()
+                    .iter()
+                    .find(|server| server.id == mutation.server_id)
+                    .cloned();
+                let outcome = match server {
+                    Some(server) if server.active => {
+                        NavidromeClient::new(server)
+                            .replay_pending_mutation(&mutation)
+                            .await
+                    }
+                    Some(_) => PendingMutationOutcome::Unreachable,
+                    None => PendingMutationOutcome::Failed(
+                        "The server was removed from RustySound.".to_string(),
+                    ),
+                };
+                if record_pending_mutation_outcome(&mut pass, mutation.id, outcome)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            if !pass.conflicts.is_empty() {
+                notices.with_mut(|items| items.extend(pass.conflicts));
+            }
+            replaying.set(false);
+            controller.refresh();
+        });
+    }
+
+    /// Clears a refused mutation's error and replays it.
+    pub fn retry(&self, id: u64) {
+        let controller = *self;
+        spawn_forever(async move {
+            let _ = retry_pending_mutation(id).await;
+            controller.replay();
+        });
+    }
+
+    pub fn discard(&self, id: u64) {
+        let controller = *self;
+        spawn_forever(async move {
+            let _ = discard_pending_mutation(id).await;
+            controller.refresh();
+        });
+    }
+
+    pub fn dismiss_notices(&self) {
+        let mut notices = self.notices;
+        notices.set(Vec::new());
+    }
+}
+
+/// Tells the user which offline changes were dropped because the server had newer state.
+#[component]
+pub fn PendingSyncBanner() -> Element {
+    let pending_sync = use_context::<PendingSyncController>();
+    let notices = pending_sync.notices();
+    if notices.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            class: "mb-4 rounded-xl border border-amber-500/30 bg-amber-500/10 p-3 flex items-start gap-3",
+            role: "status",
+            Icon {
+                name: "alert".to_string(),
+                class: "w-5 h-5 mt-0.5 text-amber-300 shrink-0".to_string(),
+            }
+            div { class: "min-w-0 flex-1 space-y-1",
+                p { class: "text-sm font-medium text-amber-100", "Some offline changes were not applied" }
+                for notice in notices {
+                    p { class: "text-xs text-amber-100/80", "{notice}" }
+                }
+            }
+            button {
+                class: "px-2 py-1 rounded-lg text-zinc-400 hover:text-white transition-colors text-sm shrink-0",
+                aria_label: "Dismiss sync notices",
+                onclick: move |_| pending_sync.dismiss_notices(),
+                Icon { name: "x".to_string(), class: "w-4 h-4".to_string() }
+            }
+        }
+    }
+}
+
+/// The "Pending sync" list in Settings: every queued mutation with retry and discard.
+#[component]
+pub fn PendingSyncSettings() -> Element {
+    let pending_sync = use_context::<PendingSyncController>();
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let app_settings = use_context::<Signal<AppSettings>>();
+
+    use_effect(move || pending_sync.refresh());
+
+    let pending = pending_sync.pending();
+    let nothing_pending = pending.is_empty();
+    let replaying = pending_sync.is_replaying();
+
+    rsx! {
+        div { class: "space-y-3",
+            div { class: "flex items-center justify-between gap-3",
+                div {
+                    p { class: "font-medium text-white", "Pending sync" }
+                    p { class: "text-sm text-zinc-400",
+                        "Stars, ratings and playlist additions made while a server was unreachable. They are sent when it answers again."
+                    }
+                }
+                button {
+                    class: if replaying || nothing_pending { "px-3 py-2 rounded-lg border border-zinc-700 text-zinc-500 cursor-not-allowed text-sm shrink-0" } else { "px-3 py-2 rounded-lg border border-emerald-500/50 text-emerald-300 hover:text-white hover:border-emerald-400 transition-colors text-sm shrink-0" },
+                    disabled: replaying || nothing_pending,
+                    onclick: move |_| pending_sync.replay(),
+                    if replaying {
+                        "Syncing…"
+                    } else {
+                        "Sync now"
+                    }
+                }
+            }
+            if nothing_pending {
+                p { class: "text-sm text-zinc-500", "Nothing waiting to sync." }
+            } else {
+                div { class: "space-y-2",
+                    for mutation in pending {
+                        div {
+                            key: "{mutation.id}",
+                            class: "flex items-start justify-between gap-3 p-3 rounded-xl bg-zinc-800/40 border border-zinc-700/50",
+                            div { class: "min-w-0",
+                                p { class: "text-sm text-white", "{mutation.op.describe()}" }
+                                p { class: "text-xs text-zinc-500 truncate",
+                                    {
+                                        servers()
+                                            .iter()
+                                            .find(|server| server.id == mutation.server_id)
+                                            .map(|server| server.name.clone())
+                                            .unwrap_or_else(|| "Removed server".to_string())
+                                    }
+                                    " · queued {humanize_epoch_ms_for_display(mutation.queued_at_ms, &app_settings())}"
+                                }
+                                if let Some(error) = mutation.last_error.clone() {
+                                    p { class: "text-xs text-red-300 mt-1", "{error}" }
+                                }
+                            }
+                            div { class: "flex items-center gap-2 shrink-0",
+                                if mutation.last_error.is_some() {
+                                    button {
+                                        class: "px-2.5 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-emerald-500/60 transition-colors text-xs",
+                                        onclick: move |_| pending_sync.retry(mutation.id),
+                                        "Retry"
+                                    }
+                                }
+                                button {
+                                    class: "px-2.5 py-1.5 rounded-lg border border-zinc-700 text-zinc-400 hover:text-red-300 hover:border-red-500/50 transition-colors text-xs",
+                                    onclick: move |_| pending_sync.discard(mutation.id),
+                                    "Discard"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
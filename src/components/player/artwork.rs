@@ -0,0 +1,213 @@
+//! Player cover art that keeps the previous track's image up until the next one has loaded,
+//! then fades the new one in over it. At most two images are mounted: the settled one and
+//! the one being loaded.
+use crate::api::{NavidromeClient, ServerConfig, Song};
+use crate::components::{sized_cover_art_url, ArtworkKind, Icon};
+use dioxus::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+struct ArtworkLayer {
+    id: u64,
+    url: String,
+    loaded: bool,
+}
+
+/// The settled image underneath and the incoming one on top of it.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ArtworkLayers {
+    back: Option<ArtworkLayer>,
+    front: Option<ArtworkLayer>,
+    next_id: u64,
+}
+
+impl ArtworkLayers {
+    /// Starts loading `url` over whatever is showing. An incoming image that hasn't loaded
+    /// yet is dropped, which also unmounts its `img` and cancels the request.
+    fn show(&mut self, url: Option<&str>) {
+        let Some(url) = url.filter(|url| !url.is_empty()) else {
+            self.back = None;
+            self.front = None;
+            return;
+        };
+        if self.front.as_ref().is_some_and(|front| front.url == url) {
+            return;
+        }
+        if let Some(front) = self.front.take() {
+            if front.loaded {
+                self.back = Some(front);
+            }
+        }
+        if self.back.as_ref().is_some_and(|back| back.url == url) {
+            return;
+        }
+        self.next_id += 1;
+        self.front = Some(ArtworkLayer {
+            id: self.next_id,
+            url: url.to_string(),
+            loaded: false,
+        });
+    }
+
+    fn loaded(&mut self, id: u64) {
+        if let Some(front) = self.front.as_mut().filter(|front| front.id == id) {
+            front.loaded = true;
+        }
+    }
+
+    /// The incoming image failed: show the placeholder rather than the previous track's art.
+    fn failed(&mut self, id: u64) {
+        if self.front.as_ref().is_some_and(|front| front.id == id) {
+            self.back = None;
+            self.front = None;
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.back.is_none() && self.front.is_none()
+    }
+
+    fn layers(&self) -> impl Iterator<Item = &ArtworkLayer> {
+        self.back.iter().chain(self.front.iter())
+    }
+}
+
+fn title_initials(title: &str) -> String {
+    title
+        .split_whitespace()
+        .filter_map(|word| word.chars().find(|c| c.is_alphanumeric()))
+        .take(2)
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// Cover of the playing song, sized for the player bar.
+#[component]
+pub(super) fn PlayerArtwork() -> Element {
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let now_playing = use_context::<Signal<Option<Song>>>();
+    let mut layers = use_signal(ArtworkLayers::default);
+
+    let cover_url = use_memo(move || {
+        let song = now_playing()?;
+        let cover_art = song.cover_art.as_ref()?;
+        let server = servers().iter().find(|s| s.id == song.server_id)?.clone();
+        let client = NavidromeClient::new(server);
+        Some(sized_cover_art_url(
+            &client,
+            cover_art,
+            ArtworkKind::Song,
+            64,
+        ))
+    });
+
+    use_effect(move || {
+        let url = cover_url();
+        layers.write().show(url.as_deref());
+    });
+
+    let title = now_playing
+        .read()
+        .as_ref()
+        .map(|song| song.title.clone())
+        .unwrap_or_default();
+    let snapshot = layers();
+
+    if snapshot.is_empty() {
+        let initials = title_initials(&title);
+        return rsx! {
+            div { class: "w-full h-full flex items-center justify-center bg-gradient-to-br from-emerald-600 to-teal-700",
+                if initials.is_empty() {
+                    Icon { name: "music".to_string(), class: "w-6 h-6 text-white/70".to_string() }
+                } else {
+                    span { class: "text-white/80 font-bold text-lg", "{initials}" }
+                }
+            }
+        };
+    }
+
+    rsx! {
+        div { class: "relative w-full h-full",
+            for layer in snapshot.layers().cloned() {
+                img {
+                    key: "{layer.id}",
+                    src: "{layer.url}",
+                    alt: "{title}",
+                    class: if layer.loaded { "absolute inset-0 w-full h-full object-cover opacity-100 transition-opacity duration-200" } else { "absolute inset-0 w-full h-full object-cover opacity-0" },
+                    onload: move |_| layers.write().loaded(layer.id),
+                    onerror: move |_| layers.write().failed(layer.id),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(layers: &ArtworkLayers) -> Vec<(&str, bool)> {
+        layers
+            .layers()
+            .map(|layer| (layer.url.as_str(), layer.loaded))
+            .collect()
+    }
+
+    fn front_id(layers: &ArtworkLayers) -> u64 {
+        layers.front.as_ref().expect("incoming layer").id
+    }
+
+    #[test]
+    fn previous_art_stays_until_the_next_one_loads() {
+        let mut layers = ArtworkLayers::default();
+        layers.show(Some("a"));
+        layers.loaded(front_id(&layers));
+        layers.show(Some("b"));
+        assert_eq!(urls(&layers), vec![("a", true), ("b", false)]);
+
+        layers.loaded(front_id(&layers));
+        assert_eq!(urls(&layers), vec![("a", true), ("b", true)]);
+
+        layers.show(Some("c"));
+        assert_eq!(urls(&layers), vec![("b", true), ("c", false)]);
+    }
+
+    #[test]
+    fn rapid_skips_replace_the_pending_load_and_ignore_stale_events() {
+        let mut layers = ArtworkLayers::default();
+        layers.show(Some("a"));
+        layers.loaded(front_id(&layers));
+        layers.show(Some("b"));
+        let stale = front_id(&layers);
+        layers.show(Some("c"));
+        assert_eq!(urls(&layers), vec![("a", true), ("c", false)]);
+
+        layers.loaded(stale);
+        layers.failed(stale);
+        assert_eq!(urls(&layers), vec![("a", true), ("c", false)]);
+
+        // Skipping back to the settled image drops the pending one.
+        layers.show(Some("a"));
+        assert_eq!(urls(&layers), vec![("a", true)]);
+    }
+
+    #[test]
+    fn a_failed_image_falls_back_to_the_placeholder() {
+        let mut layers = ArtworkLayers::default();
+        layers.show(Some("a"));
+        layers.loaded(front_id(&layers));
+        layers.show(Some("b"));
+        layers.failed(front_id(&layers));
+        assert!(layers.is_empty());
+
+        layers.show(Some("c"));
+        layers.show(None);
+        assert!(layers.is_empty());
+    }
+
+    #[test]
+    fn initials_come_from_the_first_two_words() {
+        assert_eq!(title_initials("hey jude"), "HJ");
+        assert_eq!(title_initials("(What's the Story) Morning Glory?"), "WT");
+        assert_eq!(title_initials("  "), "");
+    }
+}
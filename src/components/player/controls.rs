@@ -1,11 +1,14 @@
 use crate::api::*;
 use crate::components::audio_manager::{
-    apply_collection_shuffle_mode, queue_should_generate_similar_on_end, spawn_shuffle_queue,
+    apply_collection_shuffle_mode, queue_should_generate_similar_on_end, song_start_offset,
+    spawn_shuffle_queue,
 };
 use crate::components::{
     ios_diag_log, seek_to, AddIntent, AddMenuController, AudioState, Icon, PlaybackPositionSignal,
+    ServerCapabilitiesSignal, SettingsController, SleepTimer, SleepTimerSignal, StopAfter,
+    StopAfterMode, StopAfterSignal, SLEEP_TIMER_MINUTES,
 };
-use crate::db::{AppSettings, RepeatMode};
+use crate::db::{AppSettings, RepeatMode, PLAYBACK_RATES};
 use dioxus::prelude::*;
 
 /// Bookmark button - capture current playback position on the server
@@ -69,6 +72,7 @@ pub(super) fn BookmarkButton() -> Element {
             id: "bookmark-btn",
             r#type: "button",
             disabled: !has_song || saving() || is_live_radio,
+            aria_label: if saving() { "Saving bookmark" } else { "Save bookmark" },
             class: if saved() { format!(
                 "{base_class} p-1.5 sm:p-2 text-emerald-400 hover:text-emerald-300 transition-colors",
             ) } else { format!("{base_class} p-1.5 sm:p-2 text-zinc-400 hover:text-white transition-colors") },
@@ -91,6 +95,7 @@ pub(super) fn RatingButton() -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
     let now_playing = use_context::<Signal<Option<Song>>>();
     let queue = use_context::<Signal<Vec<Song>>>();
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
     let mut rating_open = use_signal(|| false);
 
     let current = now_playing();
@@ -100,6 +105,9 @@ pub(super) fn RatingButton() -> Element {
         .unwrap_or(0)
         .min(5);
     let has_song = current.is_some();
+    let can_rate = current
+        .as_ref()
+        .is_some_and(|song| capabilities.user_can(&song.server_id, UserAction::Rate));
 
     let on_rate = {
         let servers = servers.clone();
@@ -147,7 +155,10 @@ pub(super) fn RatingButton() -> Element {
             button {
                 id: "rating-btn",
                 r#type: "button",
-                disabled: !has_song,
+                disabled: !has_song || !can_rate,
+                aria_label: if current_rating > 0 { format!("Rating: {current_rating} of 5 stars") } else { "Rate song".to_string() },
+                aria_haspopup: "true",
+                aria_expanded: rating_open() && has_song,
                 class: if current_rating > 0 { "p-1.5 sm:p-2 text-amber-400 hover:text-amber-300 transition-colors" } else { "p-1.5 sm:p-2 text-zinc-400 hover:text-white transition-colors" },
                 onclick: move |_| rating_open.set(!rating_open()),
                 Icon {
@@ -160,6 +171,8 @@ pub(super) fn RatingButton() -> Element {
                     for value in 1..=5 {
                         button {
                             r#type: "button",
+                            aria_label: if value == 1 { "Rate 1 star".to_string() } else { format!("Rate {value} stars") },
+                            aria_pressed: value == current_rating,
                             class: if value <= current_rating { "text-amber-400 hover:text-amber-300 transition-colors" } else { "text-zinc-500 hover:text-zinc-300 transition-colors" },
                             onclick: {
                                 let on_rate = on_rate.clone();
@@ -196,6 +209,7 @@ pub(super) fn PlayPauseButton() -> Element {
         button {
             id: "play-pause-btn",
             r#type: "button",
+            aria_label: if playing { "Pause" } else { "Play" },
             class: "w-10 h-10 rounded-full bg-white flex items-center justify-center hover:scale-105 transition-transform shadow-lg",
             onclick: move |_| {
                 let current = is_playing();
@@ -242,6 +256,7 @@ pub(super) fn PrevButton() -> Element {
         button {
             id: "prev-btn",
             r#type: "button",
+            aria_label: "Previous track",
             disabled: is_radio,
             class: if is_radio { "p-2 sm:p-2.5 text-zinc-600 cursor-not-allowed flex items-center justify-center" } else { "p-2 sm:p-2.5 text-zinc-300 hover:text-white transition-colors flex items-center justify-center" },
             style: "min-width: 44px; min-height: 44px;",
@@ -281,6 +296,7 @@ pub(super) fn PrevButton() -> Element {
 #[component]
 pub(super) fn NextButton() -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let app_settings = use_context::<Signal<AppSettings>>();
     let mut is_playing = use_context::<crate::components::IsPlayingSignal>().0;
     let mut queue_index = use_context::<Signal<usize>>();
     let queue = use_context::<Signal<Vec<Song>>>();
@@ -298,6 +314,7 @@ pub(super) fn NextButton() -> Element {
         button {
             id: "next-btn",
             r#type: "button",
+            aria_label: "Next track",
             disabled: is_radio,
             class: if is_radio { "p-2 sm:p-2.5 text-zinc-600 cursor-not-allowed flex items-center justify-center" } else { "p-2 sm:p-2.5 text-zinc-300 hover:text-white transition-colors flex items-center justify-center" },
             style: "min-width: 44px; min-height: 44px;",
@@ -312,7 +329,12 @@ pub(super) fn NextButton() -> Element {
                         "ui.control",
                         &format!("source=player.next repeat=one was_playing={was_playing} action=restart"),
                     );
-                    seek_to(0.0);
+                    let restart_at = now_playing
+                        .peek()
+                        .as_ref()
+                        .map(|song| song_start_offset(&app_settings.peek(), song))
+                        .unwrap_or(0.0);
+                    seek_to(restart_at);
                     if was_playing {
                         is_playing.set(true);
                     }
@@ -356,7 +378,7 @@ pub(super) fn NextButton() -> Element {
                             is_playing.set(true);
                         }
                     }
-                } else if repeat == RepeatMode::Off
+                } else if matches!(repeat, RepeatMode::Off | RepeatMode::Count)
                     && queue_should_generate_similar_on_end(
                         &queue_list,
                         now_playing.peek().as_ref(),
@@ -400,33 +422,36 @@ pub(super) fn NextButton() -> Element {
 #[component]
 pub(super) fn RepeatButton() -> Element {
     let mut repeat_mode = use_context::<Signal<RepeatMode>>();
-    let mut app_settings = use_context::<Signal<AppSettings>>();
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let settings_controller = use_context::<SettingsController>();
     let mode = repeat_mode();
+    let repeat_count = app_settings().repeat_count;
+    let label = match mode {
+        RepeatMode::Off => "Repeat: off".to_string(),
+        RepeatMode::All => "Repeat: all".to_string(),
+        RepeatMode::One => "Repeat: one".to_string(),
+        RepeatMode::Count => format!("Repeat: {repeat_count} times"),
+    };
 
     rsx! {
         button {
             id: "repeat-btn",
             r#type: "button",
+            aria_label: "{label}",
+            title: "{label}",
+            aria_pressed: mode != RepeatMode::Off,
             class: match mode {
-                RepeatMode::Off => "p-1.5 sm:p-2 text-zinc-400 hover:text-white transition-colors",
-                RepeatMode::All | RepeatMode::One => {
-                    "p-1.5 sm:p-2 text-emerald-400 hover:text-emerald-300 transition-colors"
+                RepeatMode::Off => "relative p-1.5 sm:p-2 text-zinc-400 hover:text-white transition-colors",
+                RepeatMode::All | RepeatMode::One | RepeatMode::Count => {
+                    "relative p-1.5 sm:p-2 text-emerald-400 hover:text-emerald-300 transition-colors"
                 }
             },
             onclick: move |_| {
-                let next = match repeat_mode() {
-                    RepeatMode::Off => RepeatMode::All,
-                    RepeatMode::All => RepeatMode::One,
-                    RepeatMode::One => RepeatMode::Off,
-                };
+                let next = repeat_mode().cycled();
                 repeat_mode.set(next);
-                app_settings.with_mut(|settings| {
+                settings_controller.update_settings(|settings| {
                     settings.repeat_mode = next;
                 });
-                let settings_snapshot = app_settings();
-                spawn(async move {
-                    let _ = crate::db::save_settings(settings_snapshot).await;
-                });
             },
             Icon {
                 name: match mode {
@@ -435,6 +460,95 @@ pub(super) fn RepeatButton() -> Element {
                 },
                 class: "w-4 h-4 sm:w-5 sm:h-5".to_string(),
             }
+            if mode == RepeatMode::Count {
+                span { class: "absolute -top-0.5 -right-0.5 min-w-4 h-4 px-1 rounded-full bg-emerald-500 text-[10px] leading-4 font-semibold text-white",
+                    "{repeat_count}"
+                }
+            }
+        }
+    }
+}
+
+/// "1.0x", "1.25x" and so on.
+fn playback_rate_label(rate: f64) -> String {
+    if rate.fract() == 0.0 {
+        format!("{rate:.1}x")
+    } else {
+        format!("{rate}x")
+    }
+}
+
+/// Speed selector for spoken-word content. The choice is saved; radio always plays at 1x.
+#[component]
+pub(super) fn PlaybackRateButton() -> Element {
+    let now_playing = use_context::<Signal<Option<Song>>>();
+    let audio_state = use_context::<Signal<AudioState>>();
+    let settings_controller = use_context::<SettingsController>();
+    let mut menu_open = use_signal(|| false);
+
+    let is_live_radio = now_playing()
+        .as_ref()
+        .is_some_and(|song| song.server_name == "Radio");
+    let rate = (audio_state().playback_rate)();
+    let label = playback_rate_label(rate);
+    let changed = (rate - 1.0).abs() > f64::EPSILON;
+    let title = if is_live_radio {
+        "Radio always plays at normal speed"
+    } else {
+        "Playback speed"
+    };
+
+    rsx! {
+        div { class: "relative",
+            button {
+                id: "playback-rate-btn",
+                r#type: "button",
+                aria_label: "Playback speed: {label}",
+                title: "{title}",
+                aria_haspopup: "menu",
+                aria_expanded: menu_open(),
+                disabled: is_live_radio,
+                class: if is_live_radio {
+                    "px-1.5 py-1 min-w-10 text-xs font-semibold tabular-nums text-zinc-600 cursor-not-allowed"
+                } else if changed {
+                    "px-1.5 py-1 min-w-10 text-xs font-semibold tabular-nums text-emerald-400 hover:text-emerald-300 transition-colors"
+                } else {
+                    "px-1.5 py-1 min-w-10 text-xs font-semibold tabular-nums text-zinc-400 hover:text-white transition-colors"
+                },
+                onclick: move |_| menu_open.set(!menu_open()),
+                "{label}"
+            }
+            if menu_open() && !is_live_radio {
+                div {
+                    class: "fixed inset-0 z-[9998]",
+                    onclick: move |_| menu_open.set(false),
+                }
+                div {
+                    class: "absolute bottom-full left-1/2 -translate-x-1/2 mb-2 z-[9999] w-28 rounded-xl border border-zinc-700 bg-zinc-900/95 shadow-2xl p-1.5 space-y-1",
+                    role: "menu",
+                    p { class: "px-2.5 pt-1 pb-0.5 text-xs text-zinc-500", "Speed" }
+                    for option in PLAYBACK_RATES {
+                        button {
+                            key: "{option}",
+                            r#type: "button",
+                            role: "menuitemradio",
+                            aria_checked: option == rate,
+                            class: if option == rate {
+                                "w-full px-2.5 py-1.5 rounded-lg text-left text-sm tabular-nums text-emerald-300 bg-emerald-500/10 hover:bg-emerald-500/20 transition-colors"
+                            } else {
+                                "w-full px-2.5 py-1.5 rounded-lg text-left text-sm tabular-nums text-zinc-200 hover:bg-zinc-800/80 transition-colors"
+                            },
+                            onclick: move |_| {
+                                menu_open.set(false);
+                                settings_controller.update_settings(|settings| {
+                                    settings.playback_rate = option;
+                                });
+                            },
+                            "{playback_rate_label(option)}"
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -468,6 +582,8 @@ pub(super) fn AddToMenuButton() -> Element {
         button {
             id: "add-menu-btn",
             r#type: "button",
+            aria_label: "Add to queue or playlist",
+            aria_haspopup: "dialog",
             disabled: !has_song,
             class: if has_song {
                 "p-1.5 sm:p-2 text-zinc-300 hover:text-white transition-colors"
@@ -483,11 +599,143 @@ pub(super) fn AddToMenuButton() -> Element {
     }
 }
 
+/// Player overflow menu with the one-shot "stop after" choices and the sleep timer. Radio
+/// has no track or album to stop after, so it only gets the timer.
+#[component]
+pub(super) fn StopAfterMenuButton() -> Element {
+    let now_playing = use_context::<Signal<Option<Song>>>();
+    let mut stop_after = use_context::<StopAfterSignal>().0;
+    let mut sleep_timer = use_context::<SleepTimerSignal>().0;
+    let mut menu_open = use_signal(|| false);
+
+    let current_song = now_playing();
+    let is_live_radio = current_song
+        .as_ref()
+        .map(|song| song.server_name == "Radio")
+        .unwrap_or(false);
+    let has_song = current_song.is_some();
+    let armed_mode = stop_after().map(|armed| armed.mode);
+    let armed_minutes = sleep_timer().map(|timer| timer.minutes);
+
+    rsx! {
+        div { class: "relative",
+            button {
+                id: "player-overflow-btn",
+                r#type: "button",
+                aria_label: "More playback options",
+                aria_haspopup: "menu",
+                aria_expanded: menu_open(),
+                disabled: !has_song,
+                class: if !has_song {
+                    "p-1.5 sm:p-2 text-zinc-600 cursor-not-allowed"
+                } else if armed_mode.is_some() || armed_minutes.is_some() {
+                    "p-1.5 sm:p-2 text-emerald-400 hover:text-emerald-300 transition-colors"
+                } else {
+                    "p-1.5 sm:p-2 text-zinc-400 hover:text-white transition-colors"
+                },
+                onclick: move |_| menu_open.set(!menu_open()),
+                Icon {
+                    name: "more-horizontal".to_string(),
+                    class: "w-4 h-4 sm:w-5 sm:h-5".to_string(),
+                }
+            }
+            if menu_open() && has_song {
+                div {
+                    class: "fixed inset-0 z-[9998]",
+                    onclick: move |_| menu_open.set(false),
+                }
+                div {
+                    class: "absolute bottom-full right-0 mb-2 z-[9999] w-52 rounded-xl border border-zinc-700 bg-zinc-900/95 shadow-2xl p-1.5 space-y-1",
+                    role: "menu",
+                    if !is_live_radio {
+                        p { class: "px-2.5 pt-1 pb-0.5 text-xs text-zinc-500", "Stop after" }
+                        for mode in [StopAfterMode::Track, StopAfterMode::Album, StopAfterMode::Queue] {
+                            button {
+                                key: "{mode.label()}",
+                                r#type: "button",
+                                role: "menuitemradio",
+                                aria_checked: armed_mode == Some(mode),
+                                class: if armed_mode == Some(mode) {
+                                    "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-emerald-300 bg-emerald-500/10 hover:bg-emerald-500/20 transition-colors"
+                                } else {
+                                    "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors"
+                                },
+                                onclick: move |_| {
+                                    menu_open.set(false);
+                                    if armed_mode == Some(mode) {
+                                        stop_after.set(None);
+                                    } else if let Some(song) = now_playing() {
+                                        stop_after.set(Some(StopAfter::new(mode, song)));
+                                    }
+                                },
+                                Icon {
+                                    name: if armed_mode == Some(mode) { "check".to_string() } else { "clock".to_string() },
+                                    class: "w-4 h-4".to_string(),
+                                }
+                                "{mode.label()}"
+                            }
+                        }
+                    }
+                    p { class: "px-2.5 pt-1 pb-0.5 text-xs text-zinc-500", "Sleep timer" }
+                    for minutes in SLEEP_TIMER_MINUTES {
+                        button {
+                            key: "sleep-{minutes}",
+                            r#type: "button",
+                            role: "menuitemradio",
+                            aria_checked: armed_minutes == Some(minutes),
+                            class: if armed_minutes == Some(minutes) {
+                                "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-emerald-300 bg-emerald-500/10 hover:bg-emerald-500/20 transition-colors"
+                            } else {
+                                "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors"
+                            },
+                            onclick: move |_| {
+                                menu_open.set(false);
+                                if armed_minutes == Some(minutes) {
+                                    sleep_timer.set(None);
+                                } else {
+                                    sleep_timer.set(Some(SleepTimer::start(minutes)));
+                                }
+                            },
+                            Icon {
+                                name: if armed_minutes == Some(minutes) { "check".to_string() } else { "moon".to_string() },
+                                class: "w-4 h-4".to_string(),
+                            }
+                            "{minutes} minutes"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Shown while "stop after" is armed; clicking it cancels the request.
+#[component]
+pub(super) fn StopAfterChip() -> Element {
+    let mut stop_after = use_context::<StopAfterSignal>().0;
+    let Some(armed) = stop_after() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        button {
+            r#type: "button",
+            class: "inline-flex items-center gap-1.5 px-2 py-0.5 rounded-full bg-emerald-500/15 text-[11px] font-medium text-emerald-300 hover:bg-emerald-500/25 transition-colors",
+            aria_label: "{armed.indicator_label()}. Cancel",
+            title: "Cancel stop after",
+            onclick: move |_| stop_after.set(None),
+            Icon { name: "clock".to_string(), class: "w-3 h-3".to_string() }
+            "{armed.indicator_label()}"
+            Icon { name: "x".to_string(), class: "w-3 h-3".to_string() }
+        }
+    }
+}
+
 /// Shuffle button - toggle shuffle mode
 #[component]
 pub(super) fn ShuffleButton() -> Element {
     let mut shuffle_enabled = use_context::<crate::components::ShuffleEnabledSignal>().0;
-    let mut app_settings = use_context::<Signal<AppSettings>>();
+    let settings_controller = use_context::<SettingsController>();
     let queue = use_context::<Signal<Vec<Song>>>();
     let queue_index = use_context::<Signal<usize>>();
     let now_playing = use_context::<Signal<Option<Song>>>();
@@ -497,6 +745,8 @@ pub(super) fn ShuffleButton() -> Element {
         button {
             id: "shuffle-btn",
             r#type: "button",
+            aria_label: "Shuffle",
+            aria_pressed: enabled,
             class: if enabled { "p-1.5 sm:p-2 text-emerald-400 hover:text-emerald-300 transition-colors" } else { "p-1.5 sm:p-2 text-zinc-400 hover:text-white transition-colors" },
             onclick: move |_| {
                 let next = !shuffle_enabled();
@@ -515,13 +765,9 @@ pub(super) fn ShuffleButton() -> Element {
                     next,
                 );
                 eprintln!("[ui.shuffle] applied changed={changed}");
-                app_settings.with_mut(|settings| {
+                settings_controller.update_settings(|settings| {
                     settings.shuffle_enabled = next;
                 });
-                let settings_snapshot = app_settings();
-                spawn(async move {
-                    let _ = crate::db::save_settings(settings_snapshot).await;
-                });
             },
             Icon { name: "shuffle".to_string(), class: "w-4 h-4 sm:w-5 sm:h-5".to_string() }
         }
@@ -2,29 +2,55 @@ use crate::api::models::format_duration;
 use crate::api::*;
 use crate::components::views::artist_links::ArtistNameLinks;
 use crate::components::{
-    seek_to, AppView, AudioState, Icon, Navigation, PlaybackPositionSignal, SongDetailsController,
-    VolumeSignal,
+    AppView, AudioState, Icon, IsPlayingSignal, ListenAlongController, ListenAlongControls,
+    Navigation, QueueDragController, QueueDropBadge, QueueDropTarget, QuietHoursIndicator,
+    ServerCapabilitiesSignal, SleepTimerChip, SongDetailsController, VolumeSignal,
 };
+use crate::db::{AppSettings, PlayerBarMode};
 use dioxus::prelude::*;
 
+mod artwork;
 mod controls;
+mod seek_bar;
 
+use artwork::PlayerArtwork;
 use controls::{
-    AddToMenuButton, NextButton, PlayPauseButton, PrevButton, RatingButton, RepeatButton,
-    ShuffleButton,
+    AddToMenuButton, NextButton, PlayPauseButton, PlaybackRateButton, PrevButton, RatingButton,
+    RepeatButton, ShuffleButton, StopAfterChip, StopAfterMenuButton,
 };
+pub use seek_bar::SeekBar;
 
 #[component]
 pub fn Player() -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
     let now_playing = use_context::<Signal<Option<Song>>>();
+    let app_settings = use_context::<Signal<AppSettings>>();
     let mut volume = use_context::<VolumeSignal>().0;
     let navigation = use_context::<Navigation>();
     let song_details = use_context::<SongDetailsController>();
     let audio_state = use_context::<Signal<AudioState>>();
-    let playback_position = use_context::<PlaybackPositionSignal>().0;
+    let is_playing = use_context::<IsPlayingSignal>().0;
+    let queue = use_context::<Signal<Vec<Song>>>();
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
+    let queue_drag = use_context::<QueueDragController>();
+    let listen_along = use_context::<ListenAlongController>();
 
     let mut is_favorited = use_signal(|| false);
+    let can_rate = now_playing
+        .read()
+        .as_ref()
+        .is_some_and(|song| capabilities.user_can(&song.server_id, UserAction::Rate));
+    // Set by the chevron; wins over the automatic state until the player empties or fills up.
+    let mut collapsed_by_hand = use_signal(|| None::<bool>);
+    let is_empty = use_memo(move || now_playing.read().is_none() && queue.read().is_empty());
+
+    use_effect(move || {
+        let _ = is_empty();
+        collapsed_by_hand.set(None);
+    });
+
+    let collapsed = collapsed_by_hand()
+        .unwrap_or_else(|| is_empty() && app_settings().player_bar_mode == PlayerBarMode::Auto);
 
     let current_song = now_playing();
     let current_song_for_fav = current_song.clone();
@@ -34,15 +60,8 @@ pub fn Player() -> Element {
     let current_time = (audio_state().current_time)();
     let duration = (audio_state().duration)();
     let playback_error = (audio_state().playback_error)();
-
-    // Get cover art URL if available
-    let cover_url = current_song.as_ref().and_then(|song| {
-        let server = servers().iter().find(|s| s.id == song.server_id)?.clone();
-        let client = NavidromeClient::new(server);
-        song.cover_art
-            .as_ref()
-            .map(|ca| client.get_cover_art_url(ca, 100))
-    });
+    let playback_notice = (audio_state().playback_notice)();
+    let stream_reconnecting = (audio_state().stream_reconnecting)();
 
     use_effect(move || {
         let starred = now_playing()
@@ -63,44 +82,83 @@ pub fn Player() -> Element {
         .map(|s| s.server_name == "Radio")
         .unwrap_or(false);
 
-    let on_seek_input = {
-        let mut playback_position = playback_position.clone();
-        let mut audio_state = audio_state.clone();
-        move |e: Event<FormData>| {
-            if is_radio {
-                return;
-            }
-            if let Ok(percent) = e.value().parse::<f64>() {
-                let percent = percent.clamp(0.0, 100.0);
-                if duration > 0.0 {
-                    let new_time = (percent / 100.0) * duration;
-                    playback_position.set(new_time);
-                    audio_state.write().current_time.set(new_time);
-                    seek_to(new_time);
-                }
-            }
-        }
+    // A dropped radio stream is retried indefinitely; say so where LIVE normally sits.
+    let live_label = if stream_reconnecting {
+        "Reconnecting…"
+    } else {
+        "LIVE"
+    };
+    let live_label_class = if stream_reconnecting {
+        "text-xs text-amber-400 whitespace-nowrap"
+    } else {
+        "text-xs text-zinc-500 w-10"
     };
 
-    let on_seek_commit = {
-        let mut playback_position = playback_position.clone();
-        let mut audio_state = audio_state.clone();
-        move |e: Event<FormData>| {
-            if is_radio {
-                return;
-            }
-            if let Ok(percent) = e.value().parse::<f64>() {
-                let dur = duration;
-                if dur > 0.0 {
-                    let new_time = (percent.clamp(0.0, 100.0) / 100.0) * dur;
-                    playback_position.set(new_time);
-                    audio_state.write().current_time.set(new_time);
-                    seek_to(new_time);
-                }
-            }
+    let seek_value_text = if is_radio && stream_reconnecting {
+        "Live stream, reconnecting".to_string()
+    } else if is_radio {
+        "Live stream".to_string()
+    } else {
+        let total = current_song.as_ref().map(|s| s.duration).unwrap_or(0);
+        format!(
+            "{} of {}",
+            format_duration(current_time as u32),
+            format_duration(total)
+        )
+    };
+    let volume_percent = (volume() * 100.0).round() as i32;
+    // Custom start/end offsets and playlist cue points rendered as markers over the seek bar.
+    let offset_markers: Vec<(&'static str, f64, &'static str)> = match current_song.as_ref() {
+        Some(song) if !is_radio && duration > 0.0 => {
+            let settings = app_settings();
+            let offsets = settings.playback_offsets_for(&song.server_id, &song.id);
+            let playlist_cue = song
+                .queue_meta
+                .as_ref()
+                .filter(|meta| meta.source_kind == QueueSourceKind::Playlist)
+                .and_then(|meta| {
+                    let (server_id, playlist_id) = meta.source_id.split_once("::")?;
+                    settings.playlist_cue_for(
+                        server_id,
+                        playlist_id,
+                        &song.id,
+                        Some(meta.source_position),
+                    )
+                });
+            [
+                ("Start offset", offsets.start_secs, "bg-amber-400"),
+                ("End offset", offsets.end_secs, "bg-amber-400"),
+                ("Playlist cue", playlist_cue, "bg-sky-400"),
+            ]
+            .into_iter()
+            .filter_map(|(label, secs, color)| {
+                secs.map(|secs| (label, (secs / duration * 100.0).clamp(0.0, 100.0), color))
+            })
+            .collect()
         }
+        _ => Vec::new(),
     };
 
+    // Text for the screen-reader live region; changes on track switch and play/pause.
+    let now_playing_announcement = current_song
+        .as_ref()
+        .map(|song| {
+            let state = if is_playing() {
+                "Now playing"
+            } else {
+                "Paused"
+            };
+            match song
+                .artist
+                .as_deref()
+                .filter(|artist| !artist.trim().is_empty())
+            {
+                Some(artist) => format!("{state}: {} by {artist}", song.title),
+                None => format!("{state}: {}", song.title),
+            }
+        })
+        .unwrap_or_default();
+
     let on_open_queue = {
         let navigation = navigation.clone();
         move |_| navigation.navigate_to(AppView::QueueView {})
@@ -143,14 +201,70 @@ pub fn Player() -> Element {
 
     rsx! {
         if let Some(message) = playback_error.clone() {
-            div { class: "fixed left-0 right-0 bottom-28 md:bottom-24 px-3 md:px-6 z-[60] pointer-events-none",
-                div { class: "rounded-lg border border-rose-500/35 bg-rose-500/10 px-3 py-2 text-center text-xs text-rose-200 shadow-lg",
+            div {
+                class: if collapsed { "player-toast--collapsed fixed left-0 right-0 px-3 md:px-6 z-[60] pointer-events-none" } else { "fixed left-0 right-0 bottom-28 md:bottom-24 px-3 md:px-6 z-[60] pointer-events-none" },
+                div {
+                    role: "alert",
+                    class: "rounded-lg border border-rose-500/35 bg-rose-500/10 px-3 py-2 text-center text-xs text-rose-200 shadow-lg",
+                    "{message}"
+                }
+            }
+        } else if let Some(message) = playback_notice.clone() {
+            div {
+                class: if collapsed { "player-toast--collapsed fixed left-0 right-0 px-3 md:px-6 z-[60] pointer-events-none" } else { "fixed left-0 right-0 bottom-28 md:bottom-24 px-3 md:px-6 z-[60] pointer-events-none" },
+                div {
+                    role: "status",
+                    class: "rounded-lg border border-amber-500/35 bg-amber-500/10 px-3 py-2 text-center text-xs text-amber-100 shadow-lg",
                     "{message}"
                 }
             }
         }
-        div { class: "player-shell shrink-0 bg-zinc-950/90 backdrop-blur-xl border-t border-zinc-800/60 z-50 md:h-24",
-            div { class: "player-shell__content h-full flex flex-col md:flex-row md:items-center md:justify-between px-4 md:px-6 gap-2 md:gap-8 py-1 md:py-0",
+        div {
+            class: if collapsed { "player-shell player-shell--collapsed relative shrink-0 bg-zinc-950/90 backdrop-blur-xl border-t border-zinc-800/60 z-50" } else { "player-shell relative shrink-0 bg-zinc-950/90 backdrop-blur-xl border-t border-zinc-800/60 z-50 md:h-24" },
+            role: "region",
+            aria_label: "Player",
+            div {
+                class: "sr-only",
+                role: "status",
+                aria_live: "polite",
+                aria_atomic: "true",
+                "{now_playing_announcement}"
+            }
+            // Covers the bar while a song, album or playlist is dragged, to drop it on the queue.
+            if queue_drag.is_dragging() {
+                QueueDropTarget { class: "absolute inset-0 z-20 flex items-center justify-center gap-2 bg-zinc-950/90 text-sm text-emerald-200".to_string(),
+                    Icon { name: "queue".to_string(), class: "w-5 h-5".to_string() }
+                    "Drop to add to the queue · hold Shift to play next"
+                }
+            }
+            QueueDropBadge { class: "-top-3 left-1/2".to_string() }
+            if collapsed {
+                button {
+                    class: "w-full h-6 flex items-center justify-center gap-2 px-4 text-xs text-zinc-400 hover:text-white transition-colors",
+                    aria_label: "Expand player",
+                    aria_expanded: "false",
+                    onclick: move |_| collapsed_by_hand.set(Some(false)),
+                    Icon { name: "chevron-up".to_string(), class: "w-4 h-4".to_string() }
+                    span { class: "truncate",
+                        {
+                            current_song
+                                .as_ref()
+                                .map(|song| song.title.clone())
+                                .unwrap_or_else(|| "Nothing playing".to_string())
+                        }
+                    }
+                }
+            } else {
+                button {
+                    class: "absolute top-0.5 right-1 md:right-2 z-10 p-0.5 rounded text-zinc-500 hover:text-white transition-colors",
+                    aria_label: "Collapse player",
+                    aria_expanded: "true",
+                    onclick: move |_| collapsed_by_hand.set(Some(true)),
+                    Icon { name: "chevron-down".to_string(), class: "w-4 h-4".to_string() }
+                }
+            }
+            // Kept mounted while collapsed so the controls keep their state.
+            div { class: if collapsed { "hidden" } else { "player-shell__content h-full flex flex-col md:flex-row md:items-center md:justify-between px-4 md:px-6 gap-2 md:gap-8 py-1 md:py-0" },
                 // Now playing info
                 div { class: "flex items-center gap-3 md:gap-4 min-w-0 w-full md:w-1/4",
                     {
@@ -168,6 +282,7 @@ pub fn Player() -> Element {
                                     }
                                     // Clickable album art
                                     button {
+                                        aria_label: "Open song details",
                                         class: "rs-player-art w-14 h-14 md:w-16 md:h-16 rounded-lg bg-zinc-800 overflow-hidden shadow-lg hover:ring-2 hover:ring-emerald-500/50 transition-all cursor-pointer",
                                         onclick: {
                                             let song = current_song_for_album.clone();
@@ -178,23 +293,7 @@ pub fn Player() -> Element {
                                                 }
                                             }
                                         },
-                                        {
-                                            match &cover_url {
-                                                Some(url) => rsx! {
-                                                    img {
-                                                        src: "{url}",
-                                                        alt: "{song.title}",
-                                                        class: "w-full h-full object-cover",
-                                                        loading: "lazy",
-                                                    }
-                                                },
-                                                None => rsx! {
-                                                    div { class: "w-full h-full flex items-center justify-center bg-gradient-to-br from-emerald-600 to-teal-700",
-                                                        Icon { name: "music".to_string(), class: "w-6 h-6 text-white/70".to_string() }
-                                                    }
-                                                },
-                                            }
-                                        }
+                                        PlayerArtwork {}
                                     }
                                 }
                                 div { class: "min-w-0 flex-1 overflow-hidden",
@@ -271,7 +370,10 @@ pub fn Player() -> Element {
                                     }
                                 }
                                 button {
+                                    aria_label: "Favorite",
+                                    aria_pressed: is_favorited(),
                                     class: if is_favorited() { "p-2 text-emerald-400 hover:text-emerald-300 transition-colors flex-shrink-0" } else { "p-2 text-zinc-400 hover:text-emerald-400 transition-colors flex-shrink-0" },
+                                    disabled: !can_rate,
                                     onclick: on_favorite_toggle,
                                     Icon {
                                         name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
@@ -291,11 +393,14 @@ pub fn Player() -> Element {
                         }
                     }
                     div { class: "md:hidden flex items-center flex-shrink-0",
+                        QuietHoursIndicator {}
                         input {
                             r#type: "range",
                             min: "0",
                             max: "100",
-                            value: (volume() * 100.0).round() as i32,
+                            value: volume_percent,
+                            aria_label: "Volume",
+                            aria_valuetext: "{volume_percent}%",
                             class: "vertical-range bg-zinc-800 rounded-full cursor-pointer accent-emerald-400",
                             oninput: on_volume_change,
                         }
@@ -304,42 +409,60 @@ pub fn Player() -> Element {
 
                 // Player controls
                 div { class: "flex flex-col items-center gap-3 w-full md:flex-1 md:max-w-2xl",
-                    // Control buttons
-                    div { class: "flex items-center gap-1.5 sm:gap-2 md:gap-4 justify-center w-full",
-                        // Rating button
-                        RatingButton {}
-                        // Shuffle button
-                        ShuffleButton {}
-                        // Previous button
-                        PrevButton {}
-                        // Play/Pause button
-                        PlayPauseButton {}
-                        // Next button
-                        NextButton {}
-                        // Repeat button
-                        RepeatButton {}
-                        // Add menu button
-                        AddToMenuButton {}
+                    // Control buttons; the host has them while listening along
+                    if listen_along.is_following() {
+                        ListenAlongControls {}
+                    } else {
+                        div { class: "flex items-center gap-1.5 sm:gap-2 md:gap-4 justify-center w-full",
+                            // Rating button
+                            RatingButton {}
+                            // Shuffle button
+                            ShuffleButton {}
+                            // Previous button
+                            PrevButton {}
+                            // Play/Pause button
+                            PlayPauseButton {}
+                            // Next button
+                            NextButton {}
+                            // Repeat button
+                            RepeatButton {}
+                            // Playback speed
+                            PlaybackRateButton {}
+                            // Add menu button
+                            AddToMenuButton {}
+                            // Overflow menu ("stop after" and the sleep timer)
+                            StopAfterMenuButton {}
+                        }
                     }
+                    StopAfterChip {}
+                    SleepTimerChip {}
                     // Progress bar
                     div { class: "flex items-center gap-2 md:gap-3 w-full",
                         span { class: "text-xs text-zinc-500 w-10 text-right",
                             {if is_radio { "LIVE".to_string() } else { format_duration(current_time as u32) }}
                         }
-                        input {
-                            r#type: "range",
-                            min: "0",
-                            max: "100",
-                            disabled: is_radio,
-                            value: if duration > 0.0 { (current_time / duration * 100.0).round() as i32 } else { 0 },
-                            class: "flex-1 h-1.5 bg-zinc-800 rounded-full appearance-none cursor-pointer accent-emerald-500",
-                            oninput: on_seek_input,
-                            onchange: on_seek_commit,
+                        div { class: "relative flex-1 flex items-center",
+                            SeekBar {
+                                song: current_song.clone(),
+                                current_time,
+                                duration,
+                                disabled: is_radio || listen_along.is_following(),
+                                class: "w-full h-1.5 bg-zinc-800 rounded-full appearance-none cursor-pointer accent-emerald-500".to_string(),
+                                aria_valuetext: seek_value_text.clone(),
+                            }
+                            for (label, percent, color) in offset_markers.iter().copied() {
+                                span {
+                                    key: "{label}",
+                                    class: "pointer-events-none absolute top-1/2 -translate-y-1/2 w-0.5 h-3 rounded-full {color}",
+                                    style: "left: {percent}%;",
+                                    title: "{label}",
+                                }
+                            }
                         }
-                        span { class: "text-xs text-zinc-500 w-10",
+                        span { class: if is_radio { live_label_class } else { "text-xs text-zinc-500 w-10" },
                             {
                                 if is_radio {
-                                    "LIVE".to_string()
+                                    live_label.to_string()
                                 } else {
                                     current_song
                                         .as_ref()
@@ -357,17 +480,21 @@ pub fn Player() -> Element {
                     div { class: "hidden md:flex items-center gap-3",
                         button {
                             class: "p-2 text-zinc-400 hover:text-white transition-colors",
+                            aria_label: "Open queue",
                             onclick: on_open_queue,
                             Icon {
                                 name: "queue".to_string(),
                                 class: "w-5 h-5".to_string(),
                             }
                         }
+                        QuietHoursIndicator {}
                         input {
                             r#type: "range",
                             min: "0",
                             max: "100",
-                            value: (volume() * 100.0).round() as i32,
+                            value: volume_percent,
+                            aria_label: "Volume",
+                            aria_valuetext: "{volume_percent}%",
                             class: "w-24 h-1.5 bg-zinc-800 rounded-full appearance-none cursor-pointer accent-zinc-400",
                             oninput: on_volume_change,
                         }
@@ -0,0 +1,163 @@
+//! Seek bar with a scrubbing preview: hovering or dragging shows the time under the pointer
+//! and, when synced lyrics for the song are cached, the line sung there. The seek itself only
+//! happens when the drag ends, so scrubbing doesn't make playback stutter.
+use crate::api::models::format_duration;
+use crate::api::{cached_lyrics, LyricLine, LyricsQuery, Song};
+use crate::components::{seek_to, AudioState, PlaybackPositionSignal};
+use crate::db::AppSettings;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+/// Steps of the range input; finer than percent so long songs can be scrubbed by the second.
+const SEEK_STEPS: f64 = 1000.0;
+
+/// The synced line playing at `seconds`, if it has any text.
+fn lyric_at(lines: &[LyricLine], seconds: f64) -> Option<&str> {
+    let index = lines
+        .partition_point(|line| line.timestamp_seconds <= seconds)
+        .checked_sub(1)?;
+    Some(lines[index].text.trim()).filter(|text| !text.is_empty())
+}
+
+fn song_key(song: &Song) -> String {
+    format!("{}:{}", song.server_id, song.id)
+}
+
+#[component]
+pub fn SeekBar(
+    song: Option<Song>,
+    current_time: f64,
+    duration: f64,
+    disabled: bool,
+    class: String,
+    aria_valuetext: String,
+) -> Element {
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let mut playback_position = use_context::<PlaybackPositionSignal>().0;
+    let mut audio_state = use_context::<Signal<AudioState>>();
+    let mut mounted = use_signal(|| None::<Rc<MountedData>>);
+    let mut width = use_signal(|| 0.0f64);
+    let mut hover = use_signal(|| None::<f64>);
+    // Position of the thumb while it's being dragged, as a fraction of the song.
+    let mut drag = use_signal(|| None::<f64>);
+    // Synced lines, tagged with the song they were looked up for.
+    let mut lyric_lines = use_signal(|| None::<(String, Vec<LyricLine>)>);
+
+    let can_seek = !disabled && duration > 0.0;
+
+    let on_pointer_enter = {
+        let song = song.clone();
+        move |_| {
+            // Measured on every entry since the window may have been resized since.
+            if let Some(element) = mounted() {
+                spawn(async move {
+                    if let Ok(rect) = element.get_client_rect().await {
+                        width.set(rect.width());
+                    }
+                });
+            }
+            let Some(song) = song.as_ref() else {
+                return;
+            };
+            let key = song_key(song);
+            if lyric_lines
+                .peek()
+                .as_ref()
+                .is_some_and(|(loaded, _)| *loaded == key)
+            {
+                return;
+            }
+            let lines = cached_lyrics(&LyricsQuery::from_song(song))
+                .map(|lyrics| lyrics.synced_lines)
+                .unwrap_or_default();
+            lyric_lines.set(Some((key, lines)));
+        }
+    };
+
+    let mut commit = move |fraction: f64| {
+        drag.set(None);
+        if !can_seek {
+            return;
+        }
+        let target = fraction.clamp(0.0, 1.0) * duration;
+        playback_position.set(target);
+        audio_state.write().current_time.set(target);
+        seek_to(target);
+    };
+
+    let preview = drag().or(hover()).filter(|_| can_seek);
+    let value = match drag() {
+        Some(fraction) => fraction * SEEK_STEPS,
+        None if duration > 0.0 => (current_time / duration).clamp(0.0, 1.0) * SEEK_STEPS,
+        None => 0.0,
+    }
+    .round() as i32;
+
+    let tooltip = preview.map(|fraction| {
+        let seconds = fraction * duration;
+        let offset = app_settings().lyrics_offset_ms as f64 / 1000.0;
+        let key = song.as_ref().map(song_key);
+        let lyric = lyric_lines
+            .read()
+            .as_ref()
+            .filter(|(loaded, _)| Some(loaded) == key.as_ref())
+            .and_then(|(_, lines)| lyric_at(lines, seconds + offset))
+            .map(str::to_string);
+        // Keep the tooltip from hanging off either end of the bar.
+        let left = (fraction * 100.0).clamp(4.0, 96.0);
+        (format_duration(seconds as u32), lyric, left)
+    });
+
+    rsx! {
+        div { class: "relative w-full flex items-center",
+            input {
+                r#type: "range",
+                min: "0",
+                max: "{SEEK_STEPS}",
+                disabled,
+                value,
+                aria_label: "Seek",
+                aria_valuetext: "{aria_valuetext}",
+                class: "{class}",
+                onmounted: move |evt: MountedEvent| mounted.set(Some(evt.data())),
+                onpointerenter: on_pointer_enter,
+                onpointermove: move |evt: PointerEvent| {
+                    let width = width();
+                    if width > 0.0 {
+                        hover.set(Some((evt.element_coordinates().x / width).clamp(0.0, 1.0)));
+                    }
+                },
+                onpointerleave: move |_| hover.set(None),
+                oninput: move |evt: FormEvent| {
+                    if let Ok(step) = evt.value().parse::<f64>() {
+                        drag.set(Some((step / SEEK_STEPS).clamp(0.0, 1.0)));
+                    }
+                },
+                // Whichever of release and change arrives first seeks; the other finds no drag.
+                onpointerup: move |_| {
+                    if let Some(fraction) = drag() {
+                        commit(fraction);
+                    }
+                },
+                onchange: move |evt: FormEvent| {
+                    if drag().is_none() {
+                        return;
+                    }
+                    if let Ok(step) = evt.value().parse::<f64>() {
+                        commit(step / SEEK_STEPS);
+                    }
+                },
+            }
+            if let Some((time, lyric, left)) = tooltip {
+                div {
+                    class: "pointer-events-none absolute bottom-full mb-2 -translate-x-1/2 max-w-[16rem] px-2 py-1 rounded-lg bg-zinc-900/95 border border-zinc-700/60 shadow-lg text-center z-20",
+                    style: "left: {left}%;",
+                    p { class: "text-xs font-medium text-white tabular-nums", "{time}" }
+                    if let Some(lyric) = lyric {
+                        p { class: "text-[11px] text-zinc-300 truncate", "{lyric}" }
+                    }
+                }
+            }
+        }
+    }
+}
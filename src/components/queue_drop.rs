@@ -0,0 +1,162 @@
+//! Drag-and-drop enqueueing on desktop: song rows, album cards and playlist cards can be
+//! dropped on the sidebar's Queue entry or on the player bar. A drop adds to the end of the
+//! queue, or after the current song with Shift held.
+//!
+//! A drag carries an [`AddTarget`], the same server, kind and id the add menu works from, so
+//! albums and playlists are turned into songs by the add menu's resolver. The payload stays
+//! in app state, like the download queue's reordering, so drops from outside the app are
+//! ignored.
+use crate::api::{ServerConfig, Song};
+use crate::components::{enqueue_items, resolve_target_songs, AddTarget};
+use dioxus::core::spawn_forever;
+use dioxus::prelude::*;
+
+/// How long the "+N" badge shows after a drop.
+const DROP_BADGE_MS: u32 = 1_500;
+
+#[cfg(target_arch = "wasm32")]
+async fn badge_pause(ms: u32) {
+    gloo_timers::future::TimeoutFuture::new(ms).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn badge_pause(ms: u32) {
+    tokio::time::sleep(std::time::Duration::from_millis(u64::from(ms))).await;
+}
+
+/// Provided by the app root next to the add menu.
+#[derive(Clone, Copy)]
+pub struct QueueDragController {
+    servers: Signal<Vec<ServerConfig>>,
+    queue: Signal<Vec<Song>>,
+    queue_index: Signal<usize>,
+    dragging: Signal<Option<AddTarget>>,
+    /// Songs the last drop added, while its badge shows.
+    dropped: Signal<Option<usize>>,
+    drop_generation: Signal<u64>,
+}
+
+impl QueueDragController {
+    pub fn new(
+        servers: Signal<Vec<ServerConfig>>,
+        queue: Signal<Vec<Song>>,
+        queue_index: Signal<usize>,
+    ) -> Self {
+        Self {
+            servers,
+            queue,
+            queue_index,
+            dragging: Signal::new(None),
+            dropped: Signal::new(None),
+            drop_generation: Signal::new(0),
+        }
+    }
+
+    /// `ondragstart` handler for a song row, album card or playlist card showing `target`.
+    pub fn on_drag_start(&self, target: AddTarget) -> impl FnMut(DragEvent) + 'static {
+        let mut dragging = self.dragging;
+        move |_| dragging.set(Some(target.clone()))
+    }
+
+    /// Called from a drag source's `ondragend`, whether or not it was dropped on the queue.
+    pub fn end(&self) {
+        let mut dragging = self.dragging;
+        if dragging.peek().is_some() {
+            dragging.set(None);
+        }
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.dragging.read().is_some()
+    }
+
+    pub fn dropped_count(&self) -> Option<usize> {
+        (self.dropped)()
+    }
+
+    /// Adds what is being dragged to the queue: right after the current song with
+    /// `play_next`, otherwise at the end.
+    pub fn drop_onto_queue(&self, play_next: bool) {
+        let mut dragging = self.dragging;
+        let Some(target) = dragging.write().take() else {
+            return;
+        };
+        let controller = *self;
+        spawn_forever(async move {
+            let servers = controller.servers.peek().clone();
+            let Ok(songs) = resolve_target_songs(&servers, &target).await else {
+                return;
+            };
+            if songs.is_empty() {
+                return;
+            }
+            let count = songs.len();
+            let mode = if play_next { "next" } else { "end" };
+            enqueue_items(controller.queue, controller.queue_index, songs, mode);
+
+            let mut dropped = controller.dropped;
+            let mut drop_generation = controller.drop_generation;
+            let generation = drop_generation.peek().wrapping_add(1);
+            drop_generation.set(generation);
+            dropped.set(Some(count));
+            badge_pause(DROP_BADGE_MS).await;
+            if *drop_generation.peek() == generation {
+                dropped.set(None);
+            }
+        });
+    }
+}
+
+/// Wraps a drop target for queue drags, highlighting it while a drag is in progress and
+/// more strongly while one hovers over it.
+#[component]
+pub fn QueueDropTarget(#[props(default)] class: String, children: Element) -> Element {
+    let queue_drag = use_context::<QueueDragController>();
+    let mut hovered = use_signal(|| false);
+    let dragging = queue_drag.is_dragging();
+
+    let highlight = if dragging && hovered() {
+        "ring-2 ring-emerald-400/80 bg-emerald-500/10"
+    } else if dragging {
+        "ring-1 ring-emerald-500/30"
+    } else {
+        ""
+    };
+
+    rsx! {
+        div {
+            class: "relative rounded-xl transition-all {highlight} {class}",
+            ondragover: move |evt: DragEvent| {
+                if queue_drag.is_dragging() {
+                    evt.prevent_default();
+                    if !hovered() {
+                        hovered.set(true);
+                    }
+                }
+            },
+            ondragleave: move |_| hovered.set(false),
+            ondrop: move |evt: DragEvent| {
+                evt.prevent_default();
+                hovered.set(false);
+                queue_drag.drop_onto_queue(evt.modifiers().shift());
+            },
+            {children}
+        }
+    }
+}
+
+/// "+N" for the songs the last drop added, bouncing in at a drop target's corner.
+#[component]
+pub fn QueueDropBadge(class: String) -> Element {
+    let queue_drag = use_context::<QueueDragController>();
+
+    rsx! {
+        if let Some(count) = queue_drag.dropped_count() {
+            span {
+                class: "pointer-events-none absolute z-10 px-1.5 py-0.5 rounded-full bg-emerald-500 text-[10px] font-semibold text-zinc-950 shadow animate-bounce {class}",
+                aria_hidden: "true",
+                "+{count}"
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Keeps the quiet-hours volume cap current for the audio manager and the Player, and lets
+//! the Player lift it for half an hour.
+use crate::components::Icon;
+use crate::db::AppSettings;
+use crate::quiet_hours::{minute_label, quiet_cap_now};
+use dioxus::prelude::*;
+
+/// How long the Player's override lifts quiet hours.
+const QUIET_OVERRIDE_MS: u64 = 30 * 60 * 1000;
+/// How often the schedule is re-read; windows start and end within this much of their time.
+const QUIET_CHECK_MS: u64 = 15_000;
+
+#[cfg(target_arch = "wasm32")]
+async fn quiet_check_pause(ms: u64) {
+    gloo_timers::future::TimeoutFuture::new(ms as u32).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn quiet_check_pause(ms: u64) {
+    tokio::time::sleep(std::time::Duration::from_millis(ms)).await;
+}
+
+fn now_ms() -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Date::now().max(0.0).round() as u64
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct QuietHoursController {
+    app_settings: Signal<AppSettings>,
+    /// Cap from the schedule alone, ignoring the override.
+    scheduled_cap: Signal<Option<f64>>,
+    override_until_ms: Signal<Option<u64>>,
+}
+
+impl QuietHoursController {
+    pub fn new(app_settings: Signal<AppSettings>) -> Self {
+        Self {
+            app_settings,
+            scheduled_cap: Signal::new(quiet_cap_now(&app_settings.peek().quiet_hours)),
+            override_until_ms: Signal::new(None),
+        }
+    }
+
+    /// The cap the output is held under right now, as a 0..=1 volume.
+    pub fn cap(&self) -> Option<f64> {
+        if (self.override_until_ms)().is_some() {
+            None
+        } else {
+            (self.scheduled_cap)()
+        }
+    }
+
+    /// Whether quiet hours are in force by the schedule, overridden or not.
+    pub fn is_scheduled(&self) -> bool {
+        (self.scheduled_cap)().is_some()
+    }
+
+    pub fn is_overridden(&self) -> bool {
+        (self.override_until_ms)().is_some()
+    }
+
+    /// Lifts the cap for the next half hour.
+    pub fn override_for_a_while(mut self) {
+        self.override_until_ms
+            .set(Some(now_ms().saturating_add(QUIET_OVERRIDE_MS)));
+    }
+
+    pub fn end_override(mut self) {
+        self.override_until_ms.set(None);
+    }
+
+    /// Re-reads the schedule and drops an override that has run out.
+    pub fn refresh(mut self) {
+        let cap = quiet_cap_now(&self.app_settings.peek().quiet_hours);
+        if *self.scheduled_cap.peek() != cap {
+            self.scheduled_cap.set(cap);
+        }
+        let expired = self
+            .override_until_ms
+            .peek()
+            .is_some_and(|until| until <= now_ms());
+        if expired {
+            self.override_until_ms.set(None);
+        }
+    }
+}
+
+/// Keeps `controller` in step with the clock and with edits to the schedule.
+pub fn use_quiet_hours_clock(controller: QuietHoursController) {
+    let app_settings = controller.app_settings;
+    let schedule = use_memo(move || app_settings().quiet_hours);
+    use_effect(move || {
+        let _ = schedule();
+        controller.refresh();
+    });
+    use_hook(move || {
+        spawn(async move {
+            loop {
+                quiet_check_pause(QUIET_CHECK_MS).await;
+                controller.refresh();
+            }
+        });
+    });
+}
+
+/// Moon shown in the Player while quiet hours are on by the schedule. Tapping it lifts the
+/// cap for 30 minutes, or puts it back while overridden.
+#[component]
+pub fn QuietHoursIndicator() -> Element {
+    let quiet_hours = use_context::<QuietHoursController>();
+    let app_settings = use_context::<Signal<AppSettings>>();
+
+    if !quiet_hours.is_scheduled() {
+        return rsx! {};
+    }
+    let schedule = app_settings().quiet_hours;
+    let end = minute_label(schedule.end_minute);
+    let overridden = quiet_hours.is_overridden();
+    let title = if overridden {
+        "Quiet hours lifted for 30 minutes. Tap to cap the volume again.".to_string()
+    } else {
+        format!(
+            "Quiet hours until {end}: volume capped at {}%. Tap to lift for 30 minutes.",
+            schedule.cap_percent
+        )
+    };
+
+    rsx! {
+        button {
+            r#type: "button",
+            class: if overridden { "p-1.5 rounded-full text-zinc-500 hover:text-zinc-300 transition-colors" } else { "p-1.5 rounded-full text-indigo-300 hover:text-indigo-200 transition-colors" },
+            title: "{title}",
+            aria_label: "{title}",
+            aria_pressed: !overridden,
+            onclick: move |_| {
+                if overridden {
+                    quiet_hours.end_override();
+                } else {
+                    quiet_hours.override_for_a_while();
+                }
+            },
+            Icon { name: "moon".to_string(), class: "w-4 h-4".to_string() }
+        }
+    }
+}
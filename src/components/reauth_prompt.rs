@@ -0,0 +1,161 @@
+use crate::api::{
+    clear_server_reauth, servers_needing_reauth, ApiError, NavidromeClient, ServerConfig,
+};
+use crate::components::Icon;
+use crate::db::save_servers;
+use dioxus::prelude::*;
+use std::collections::HashSet;
+
+const REAUTH_POLL_INTERVAL_MS: u64 = 3_000;
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn reauth_poll_sleep() {
+    tokio::time::sleep(std::time::Duration::from_millis(REAUTH_POLL_INTERVAL_MS)).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn reauth_poll_sleep() {
+    gloo_timers::future::TimeoutFuture::new(REAUTH_POLL_INTERVAL_MS as u32).await;
+}
+
+/// Inline banner asking for a new password when a server starts rejecting its credentials.
+#[component]
+pub fn ReauthPrompt() -> Element {
+    let mut servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let mut flagged_ids = use_signal(Vec::<String>::new);
+    let mut dismissed_ids = use_signal(HashSet::<String>::new);
+    let mut password = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut busy = use_signal(|| false);
+
+    // The API client flags servers outside of the component tree, so poll for changes.
+    use_hook(move || {
+        spawn(async move {
+            loop {
+                let latest = servers_needing_reauth();
+                if *flagged_ids.peek() != latest {
+                    flagged_ids.set(latest);
+                }
+                reauth_poll_sleep().await;
+            }
+        });
+    });
+
+    let flagged = flagged_ids();
+    let dismissed = dismissed_ids();
+    let Some(server) = servers()
+        .into_iter()
+        .find(|server| flagged.contains(&server.id) && !dismissed.contains(&server.id))
+    else {
+        return rsx! {};
+    };
+
+    let on_submit = {
+        let server = server.clone();
+        move |_| {
+            if busy() {
+                return;
+            }
+            let new_password = password().trim().to_string();
+            if new_password.is_empty() {
+                error.set(Some("Enter the new password.".to_string()));
+                return;
+            }
+
+            let mut updated = server.clone();
+            updated.password = new_password;
+            busy.set(true);
+            error.set(None);
+            spawn(async move {
+                match NavidromeClient::new(updated.clone()).ping().await {
+                    Ok(_) => {
+                        servers.with_mut(|list| {
+                            if let Some(entry) = list.iter_mut().find(|s| s.id == updated.id) {
+                                entry.password = updated.password.clone();
+                            }
+                        });
+                        let _ = save_servers(servers()).await;
+                        clear_server_reauth(&updated.id);
+                        flagged_ids.with_mut(|ids| ids.retain(|id| id != &updated.id));
+                        password.set(String::new());
+                    }
+                    Err(ApiError::AuthFailed) => {
+                        error.set(Some("The server still rejects this password.".to_string()));
+                    }
+                    Err(err) => {
+                        error.set(Some(err.to_string()));
+                    }
+                }
+                busy.set(false);
+            });
+        }
+    };
+
+    let on_dismiss = {
+        let server_id = server.id.clone();
+        move |_| {
+            dismissed_ids.with_mut(|ids| {
+                ids.insert(server_id.clone());
+            });
+            password.set(String::new());
+            error.set(None);
+        }
+    };
+
+    rsx! {
+        div {
+            class: "mb-4 rounded-xl border border-rose-500/40 bg-rose-500/10 p-3 space-y-3",
+            role: "alert",
+            div { class: "flex items-start gap-3",
+                Icon {
+                    name: "server".to_string(),
+                    class: "w-5 h-5 mt-0.5 text-rose-300 shrink-0".to_string(),
+                }
+                div { class: "min-w-0",
+                    p { class: "text-sm font-medium text-rose-100",
+                        "{server.name} rejected the saved password."
+                    }
+                    p { class: "text-xs text-rose-100/80",
+                        "Enter the current password for {server.username} to reconnect. Downloads and caching for this server are paused until then."
+                    }
+                }
+            }
+            div { class: "flex flex-wrap items-center gap-2",
+                input {
+                    class: "flex-1 min-w-[12rem] px-3 py-2 bg-zinc-900/50 border border-zinc-700/50 rounded-lg text-sm text-white placeholder:text-zinc-500 focus:outline-none focus:border-rose-400/60",
+                    r#type: "password",
+                    placeholder: "New password",
+                    aria_label: "New password for {server.name}",
+                    value: password,
+                    disabled: busy(),
+                    oninput: move |e| {
+                        password.set(e.value());
+                        error.set(None);
+                    },
+                }
+                button {
+                    class: "px-3 py-2 rounded-lg border border-rose-400/60 text-rose-100 hover:text-white hover:border-rose-300 transition-colors text-sm flex items-center gap-2 disabled:opacity-60",
+                    disabled: busy(),
+                    onclick: on_submit,
+                    if busy() {
+                        Icon {
+                            name: "loader".to_string(),
+                            class: "w-4 h-4 animate-spin".to_string(),
+                        }
+                        "Checking..."
+                    } else {
+                        "Re-authenticate"
+                    }
+                }
+                button {
+                    class: "px-3 py-2 rounded-lg text-zinc-400 hover:text-white transition-colors text-sm",
+                    onclick: on_dismiss,
+                    "Later"
+                }
+            }
+            if let Some(message) = error() {
+                p { class: "text-xs text-rose-200", "{message}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::components::Icon;
+use crate::db::{dismiss_session_summary, pending_session_summary, AppSettings};
+use crate::time_format::humanize_epoch_ms_for_display;
+use dioxus::prelude::*;
+
+fn format_listening_time(total_secs: u64) -> String {
+    let minutes = (total_secs + 30) / 60;
+    if minutes < 60 {
+        format!("{minutes} min")
+    } else {
+        format!("{} h {} min", minutes / 60, minutes % 60)
+    }
+}
+
+/// Recap of the last listening session, shown once per session until dismissed when session
+/// summaries are enabled. Computed in the background after startup.
+#[component]
+pub fn SessionSummaryBanner() -> Element {
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let enabled = use_memo(move || app_settings().session_summary_enabled);
+    let mut hidden = use_signal(|| false);
+
+    let summary = use_resource(move || async move {
+        if !enabled() {
+            return None;
+        }
+        pending_session_summary().await
+    });
+
+    if hidden() {
+        return rsx! {};
+    }
+    let Some(Some(summary)) = summary() else {
+        return rsx! {};
+    };
+
+    let songs_label = if summary.songs_played == 1 {
+        "song"
+    } else {
+        "songs"
+    };
+    let listening_time = format_listening_time(summary.total_secs);
+    let started_at_ms = summary.started_at_ms;
+    let started = humanize_epoch_ms_for_display(started_at_ms, &app_settings());
+
+    rsx! {
+        div {
+            class: "mb-4 rounded-xl border border-emerald-500/30 bg-emerald-500/10 p-3 flex items-start gap-3",
+            role: "status",
+            Icon {
+                name: "music".to_string(),
+                class: "w-5 h-5 mt-0.5 text-emerald-300 shrink-0".to_string(),
+            }
+            div { class: "min-w-0 flex-1",
+                p { class: "text-sm font-medium text-emerald-100", "Your last listening session · {started}" }
+                p { class: "text-xs text-emerald-100/80",
+                    "{summary.songs_played} {songs_label} · {listening_time}"
+                    if let Some(artist) = summary.top_artist.as_ref() {
+                        " · Top artist: {artist} ({summary.top_artist_plays} plays)"
+                    }
+                }
+            }
+            button {
+                class: "px-2 py-1 rounded-lg text-zinc-400 hover:text-white transition-colors text-sm shrink-0",
+                aria_label: "Dismiss session summary",
+                onclick: move |_| {
+                    hidden.set(true);
+                    spawn(async move {
+                        dismiss_session_summary(started_at_ms).await;
+                    });
+                },
+                Icon { name: "x".to_string(), class: "w-4 h-4".to_string() }
+            }
+        }
+    }
+}
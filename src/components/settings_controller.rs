@@ -0,0 +1,234 @@
+//! The single write path for `AppSettings`.
+//!
+//! Views change settings through [`SettingsController::update_settings`]. It applies the side
+//! effects of the fields that actually changed (cache service config, which also carries the
+//! offline-mode flag the API clients read, and the volume signal), shows the "Saved" toast,
+//! and writes to storage once edits have settled, so a dragged slider saves once.
+use crate::cache_service::{apply_settings as apply_cache_settings, clear_negative_cache};
+use crate::db::{save_settings, AppSettings};
+use dioxus::core::spawn_forever;
+use dioxus::prelude::*;
+
+/// Quiet period after the last change before settings are written.
+const SAVE_DEBOUNCE_MS: u32 = 400;
+const SAVED_TOAST_MS: u32 = 1400;
+
+#[cfg(target_arch = "wasm32")]
+async fn settings_pause(ms: u32) {
+    gloo_timers::future::TimeoutFuture::new(ms).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn settings_pause(ms: u32) {
+    tokio::time::sleep(std::time::Duration::from_millis(u64::from(ms))).await;
+}
+
+/// Clamps stored volumes into 0..=1, undoing percent values written by older builds.
+pub(crate) fn normalize_volume(mut value: f64) -> f64 {
+    if !value.is_finite() {
+        return 0.8;
+    }
+    let mut passes = 0;
+    while value > 1.0 && passes < 4 {
+        value /= 100.0;
+        passes += 1;
+    }
+    value.clamp(0.0, 1.0)
+}
+
+/// What has to happen outside the settings signal after a change.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct SettingsSideEffects {
+    /// Cache or offline-mode fields changed; the cache service config is re-applied.
+    pub cache_config: bool,
+    /// Negative caching was switched off; remembered misses are dropped.
+    pub clear_negative_cache: bool,
+    /// Normalized volume to push into the volume signal.
+    pub volume: Option<f64>,
+}
+
+impl SettingsSideEffects {
+    /// Everything a freshly loaded settings value needs applied.
+    fn all(settings: &AppSettings) -> Self {
+        Self {
+            cache_config: true,
+            clear_negative_cache: false,
+            volume: Some(normalize_volume(settings.volume)),
+        }
+    }
+}
+
+pub(crate) fn settings_side_effects(
+    before: &AppSettings,
+    after: &AppSettings,
+) -> SettingsSideEffects {
+    let cache_config = before.cache_enabled != after.cache_enabled
+        || before.cache_images_enabled != after.cache_images_enabled
+        || before.cache_expiry_days != after.cache_expiry_days
+        || before.cache_size_mb != after.cache_size_mb
+        || before.offline_mode != after.offline_mode
+        || before.negative_cache_enabled != after.negative_cache_enabled;
+    SettingsSideEffects {
+        cache_config,
+        clear_negative_cache: before.negative_cache_enabled && !after.negative_cache_enabled,
+        volume: ((before.volume - after.volume).abs() > f64::EPSILON)
+            .then(|| normalize_volume(after.volume)),
+    }
+}
+
+/// Provided by the app root next to the settings signal.
+#[derive(Clone, Copy)]
+pub struct SettingsController {
+    settings: Signal<AppSettings>,
+    volume: Signal<f64>,
+    saved_toast: Signal<Option<String>>,
+    toast_generation: Signal<u64>,
+    save_generation: Signal<u64>,
+}
+
+impl SettingsController {
+    pub fn new(settings: Signal<AppSettings>, volume: Signal<f64>) -> Self {
+        Self {
+            settings,
+            volume,
+            saved_toast: Signal::new(None),
+            toast_generation: Signal::new(0),
+            save_generation: Signal::new(0),
+        }
+    }
+
+    /// Changes settings in place, applies side effects for the changed fields, shows the
+    /// saved toast and schedules a write. Mutations that change nothing are dropped.
+    pub fn update_settings(&self, mutate: impl FnOnce(&mut AppSettings)) {
+        let before = self.settings.peek().clone();
+        let mut after = before.clone();
+        mutate(&mut after);
+        if after == before {
+            return;
+        }
+
+        let effects = settings_side_effects(&before, &after);
+        if let Some(volume) = effects.volume {
+            after.volume = volume;
+        }
+        self.apply_side_effects(effects, &after);
+        let mut settings = self.settings;
+        settings.set(after);
+        self.show_saved_toast();
+        self.schedule_save();
+    }
+
+    /// Installs settings read from storage and applies every side effect, without saving.
+    pub fn apply_loaded(&self, mut loaded: AppSettings) {
+        let effects = SettingsSideEffects::all(&loaded);
+        if let Some(volume) = effects.volume {
+            loaded.volume = volume;
+        }
+        self.apply_side_effects(effects, &loaded);
+        let mut settings = self.settings;
+        settings.set(loaded);
+    }
+
+    pub fn saved_toast(&self) -> Option<String> {
+        (self.saved_toast)()
+    }
+
+    fn apply_side_effects(&self, effects: SettingsSideEffects, settings: &AppSettings) {
+        if effects.cache_config {
+            apply_cache_settings(settings);
+        }
+        if effects.clear_negative_cache {
+            clear_negative_cache();
+        }
+        if let Some(volume) = effects.volume {
+            let mut volume_signal = self.volume;
+            if (*volume_signal.peek() - volume).abs() > f64::EPSILON {
+                volume_signal.set(volume);
+            }
+        }
+    }
+
+    fn show_saved_toast(&self) {
+        let mut toast_generation = self.toast_generation;
+        let mut saved_toast = self.saved_toast;
+        toast_generation.with_mut(|generation| *generation = generation.saturating_add(1));
+        let generation = *toast_generation.peek();
+        saved_toast.set(Some("Saved".to_string()));
+
+        spawn_forever(async move {
+            settings_pause(SAVED_TOAST_MS).await;
+            if *toast_generation.peek() == generation {
+                saved_toast.set(None);
+            }
+        });
+    }
+
+    /// Settings can change from views that unmount right away (closing song details), so
+    /// the write runs outside any component scope.
+    fn schedule_save(&self) {
+        let mut save_generation = self.save_generation;
+        save_generation.with_mut(|generation| *generation = generation.saturating_add(1));
+        let generation = *save_generation.peek();
+        let settings = self.settings;
+
+        spawn_forever(async move {
+            settings_pause(SAVE_DEBOUNCE_MS).await;
+            if *save_generation.peek() != generation {
+                return;
+            }
+            let snapshot = settings.peek().clone();
+            let _ = save_settings(snapshot).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_and_offline_fields_reapply_the_cache_config() {
+        let before = AppSettings::default();
+        let mut after = before.clone();
+        after.offline_mode = !before.offline_mode;
+        assert!(settings_side_effects(&before, &after).cache_config);
+
+        let mut after = before.clone();
+        after.cache_size_mb = before.cache_size_mb + 50;
+        let effects = settings_side_effects(&before, &after);
+        assert!(effects.cache_config);
+        assert_eq!(effects.volume, None);
+
+        let mut after = before.clone();
+        after.crossfade_enabled = !before.crossfade_enabled;
+        assert_eq!(
+            settings_side_effects(&before, &after),
+            SettingsSideEffects::default()
+        );
+    }
+
+    #[test]
+    fn disabling_negative_cache_clears_it() {
+        let before = AppSettings {
+            negative_cache_enabled: true,
+            ..AppSettings::default()
+        };
+        let mut after = before.clone();
+        after.negative_cache_enabled = false;
+        let effects = settings_side_effects(&before, &after);
+        assert!(effects.cache_config);
+        assert!(effects.clear_negative_cache);
+        assert!(!settings_side_effects(&after, &before).clear_negative_cache);
+    }
+
+    #[test]
+    fn volume_changes_sync_a_normalized_volume() {
+        let before = AppSettings::default();
+        let mut after = before.clone();
+        after.volume = 65.0;
+        let effects = settings_side_effects(&before, &after);
+        assert_eq!(effects.volume, Some(0.65));
+        assert!(!effects.cache_config);
+        assert_eq!(normalize_volume(f64::NAN), 0.8);
+    }
+}
@@ -1,15 +1,46 @@
-use crate::api::ServerConfig;
-use crate::components::{AppView, Icon, Navigation, SongDetailsController};
+use crate::api::{NavidromeClient, NowPlayingEntry, ServerConfig, Song};
+use crate::components::audio_manager::{
+    queue_should_generate_similar_on_end, song_start_offset, spawn_shuffle_queue,
+};
+use crate::components::{
+    ios_diag_log, seek_to, AppView, AudioState, BackgroundJobsIndicator,
+    FavoritesShuffleController, Icon, IsPlayingSignal, ListenAlongController, Navigation,
+    QueueDropBadge, QueueDropTarget, ServerCapabilitiesSignal, ShuffleEnabledSignal,
+    SongDetailsController,
+};
+use crate::components::{sized_cover_art_url, ArtworkKind};
+use crate::db::{AppSettings, RepeatMode};
 use dioxus::prelude::*;
 
 const SIDEBAR_LOGO: Asset = asset!("/assets/favicon-96x96.png");
+/// Titles longer than this overflow the mini card and get the marquee treatment.
+const MINI_CARD_MARQUEE_CHARS: usize = 22;
+/// Progress ring resolution; the ring only re-renders when this many steps change.
+const MINI_CARD_PROGRESS_STEPS: f64 = 200.0;
+/// How often the expanded "Listening Now" panel asks the servers for fresh entries.
+const LISTENING_NOW_REFRESH_MS: u32 = 30_000;
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn listening_now_refresh_sleep() {
+    tokio::time::sleep(std::time::Duration::from_millis(u64::from(
+        LISTENING_NOW_REFRESH_MS,
+    )))
+    .await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn listening_now_refresh_sleep() {
+    gloo_timers::future::TimeoutFuture::new(LISTENING_NOW_REFRESH_MS).await;
+}
 
 #[component]
 pub fn Sidebar(sidebar_open: Signal<bool>, overlay_mode: bool) -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
     let navigation = use_context::<Navigation>();
     let song_details = use_context::<SongDetailsController>();
+    let favorites_shuffle = use_context::<FavoritesShuffleController>();
     let view = use_route::<AppView>();
+    let guest_session = use_context::<ServerCapabilitiesSignal>().is_guest_session();
 
     let is_open = sidebar_open();
 
@@ -144,34 +175,56 @@ pub fn Sidebar(sidebar_open: Signal<bool>, overlay_mode: bool) -> Element {
                         active: matches!(view, AppView::FavoritesView {}),
                         onclick: nav_to(AppView::FavoritesView {}),
                     }
+                    NavItem {
+                        icon: "shuffle",
+                        label: if favorites_shuffle.is_busy() { "Shuffling favorites…" } else { "Shuffle favorites" },
+                        active: false,
+                        onclick: move |_| {
+                            let mut sidebar_open = sidebar_open;
+                            favorites_shuffle.start();
+                            sidebar_open.set(false);
+                        },
+                    }
                     NavItem {
                         icon: "bookmark",
                         label: "Bookmarks",
                         active: matches!(view, AppView::BookmarksView {}),
                         onclick: nav_to(AppView::BookmarksView {}),
                     }
-                    NavItem {
-                        icon: "download",
-                        label: "Downloads",
-                        active: matches!(view, AppView::DownloadsView {}),
-                        onclick: nav_to(AppView::DownloadsView {}),
+                    if !guest_session {
+                        NavItem {
+                            icon: "download",
+                            label: "Downloads",
+                            active: matches!(view, AppView::DownloadsView {}),
+                            onclick: nav_to(AppView::DownloadsView {}),
+                        }
                     }
-                    NavItem {
-                        icon: "queue",
-                        label: "Queue",
-                        active: matches!(view, AppView::QueueView {}),
-                        onclick: nav_to(AppView::QueueView {}),
+                    // Songs, albums and playlists dragged here join the queue.
+                    QueueDropTarget {
+                        NavItem {
+                            icon: "queue",
+                            label: "Queue",
+                            active: matches!(view, AppView::QueueView {}),
+                            onclick: nav_to(AppView::QueueView {}),
+                        }
+                        QueueDropBadge { class: "top-1.5 right-3".to_string() }
                     }
                 }
 
+                SidebarListeningNow {}
             }
 
-            div { class: "p-4 pt-3 border-t border-zinc-800/50 bg-zinc-950/60",
-                NavItem {
-                    icon: "settings",
-                    label: "Settings",
-                    active: matches!(view, AppView::SettingsView {}),
-                    onclick: nav_to(AppView::SettingsView {}),
+            SidebarNowPlaying { sidebar_open }
+
+            if !guest_session {
+                div { class: "p-4 pt-3 border-t border-zinc-800/50 bg-zinc-950/60",
+                    BackgroundJobsIndicator {}
+                    NavItem {
+                        icon: "settings",
+                        label: "Settings",
+                        active: matches!(view, AppView::SettingsView {}),
+                        onclick: nav_to(AppView::SettingsView {}),
+                    }
                 }
             }
         }
@@ -201,3 +254,418 @@ fn NavItem(
         }
     }
 }
+
+/// Collapsible list of what other listeners on the active servers are playing. Entries are
+/// only fetched while it is expanded; servers that refuse `getNowPlaying` (often limited to
+/// admins) are skipped, and the panel hides itself when none of them answer.
+#[component]
+fn SidebarListeningNow() -> Element {
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let mut expanded = use_signal(|| false);
+    let mut refresh_tick = use_signal(|| 0u64);
+
+    use_future(move || async move {
+        loop {
+            listening_now_refresh_sleep().await;
+            if *expanded.peek() {
+                refresh_tick.with_mut(|tick| *tick = tick.wrapping_add(1));
+            }
+        }
+    });
+
+    let listeners = use_resource(move || {
+        let open = expanded();
+        let _ = refresh_tick();
+        let active_servers: Vec<ServerConfig> =
+            servers().into_iter().filter(|s| s.active).collect();
+        async move {
+            if !open {
+                return Some(Vec::new());
+            }
+            let mut answered = false;
+            let mut entries = Vec::<(ServerConfig, NowPlayingEntry)>::new();
+            for server in active_servers {
+                let client = NavidromeClient::new(server.clone());
+                if let Ok(server_entries) = client.get_now_playing().await {
+                    answered = true;
+                    entries.extend(
+                        server_entries
+                            .into_iter()
+                            .map(|entry| (server.clone(), entry)),
+                    );
+                }
+            }
+            entries.sort_by_key(|(_, entry)| entry.minutes_ago);
+            answered.then_some(entries)
+        }
+    });
+
+    let Some(entries) = listeners.read().clone().unwrap_or(Some(Vec::new())) else {
+        return rsx! {};
+    };
+
+    rsx! {
+        div { class: "mb-6",
+            button {
+                class: "w-full flex items-center justify-between mb-3 px-3 text-xs font-semibold text-zinc-500 uppercase tracking-wider hover:text-zinc-300 transition-colors",
+                aria_expanded: "{expanded()}",
+                onclick: move |_| expanded.set(!expanded()),
+                span { "Listening Now" }
+                Icon {
+                    name: if expanded() { "chevron-up".to_string() } else { "chevron-down".to_string() },
+                    class: "w-3.5 h-3.5".to_string(),
+                }
+            }
+            if expanded() {
+                if listeners.read().is_none() {
+                    p { class: "px-3 text-xs text-zinc-500", "Checking..." }
+                } else if entries.is_empty() {
+                    p { class: "px-3 text-xs text-zinc-500", "Nobody is listening right now." }
+                } else {
+                    div { class: "space-y-1",
+                        for (server , entry) in entries {
+                            ListeningNowRow {
+                                key: "{server.id}:{entry.username}:{entry.song.id}",
+                                server,
+                                entry,
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ListeningNowRow(server: ServerConfig, entry: NowPlayingEntry) -> Element {
+    let cover_url = entry.song.cover_art.as_ref().map(|cover_art| {
+        sized_cover_art_url(
+            &NavidromeClient::new(server.clone()),
+            cover_art,
+            ArtworkKind::Song,
+            32,
+        )
+    });
+    let listener = if entry.username.eq_ignore_ascii_case(&server.username) {
+        "You".to_string()
+    } else if entry.username.trim().is_empty() {
+        "Someone".to_string()
+    } else {
+        entry.username.clone()
+    };
+    let when = match entry.minutes_ago {
+        0 => "now".to_string(),
+        minutes => format!("{minutes} min ago"),
+    };
+    let artist = entry
+        .song
+        .artist
+        .clone()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| "Unknown Artist".to_string());
+
+    rsx! {
+        div { class: "flex items-center gap-2.5 px-3 py-1.5 rounded-lg",
+            div { class: "w-8 h-8 rounded-md overflow-hidden bg-zinc-800 flex-shrink-0",
+                if let Some(url) = cover_url {
+                    img {
+                        src: "{url}",
+                        alt: "{entry.song.title}",
+                        class: "w-full h-full object-cover",
+                        loading: "lazy",
+                    }
+                }
+            }
+            div { class: "min-w-0 flex-1",
+                p { class: "text-xs text-white truncate", "{entry.song.title} · {artist}" }
+                p { class: "text-[11px] text-zinc-500 truncate", "{listener} · {when}" }
+            }
+        }
+    }
+}
+
+/// Compact now-playing card pinned above the sidebar footer.
+#[component]
+fn SidebarNowPlaying(sidebar_open: Signal<bool>) -> Element {
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let now_playing = use_context::<Signal<Option<Song>>>();
+    let song_details = use_context::<SongDetailsController>();
+
+    let Some(song) = now_playing() else {
+        return rsx! {};
+    };
+
+    let cover_url = servers()
+        .iter()
+        .find(|s| s.id == song.server_id)
+        .cloned()
+        .and_then(|server| {
+            let client = NavidromeClient::new(server);
+            song.cover_art
+                .as_ref()
+                .map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Song, 48))
+        });
+    let is_radio = song.server_name == "Radio";
+    let title = if song.title.trim().is_empty() {
+        "Unknown Song".to_string()
+    } else {
+        song.title.clone()
+    };
+    let subtitle = if is_radio {
+        song.album
+            .clone()
+            .or_else(|| song.artist.clone())
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| "Internet Radio".to_string())
+    } else {
+        song.artist
+            .clone()
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| "Unknown Artist".to_string())
+    };
+
+    let on_open_details = {
+        let song = song.clone();
+        let mut song_details = song_details.clone();
+        let mut sidebar_open = sidebar_open;
+        move |_| {
+            song_details.open(song.clone());
+            sidebar_open.set(false);
+        }
+    };
+
+    rsx! {
+        div { class: "px-4 pb-3",
+            div {
+                class: "group relative flex items-center gap-3 rounded-xl border border-zinc-800/60 bg-zinc-900/50 p-2.5 cursor-pointer hover:bg-zinc-800/50 transition-colors",
+                role: "button",
+                aria_label: "Open song details",
+                onclick: on_open_details,
+                div { class: "relative w-12 h-12 flex-shrink-0",
+                    if is_radio {
+                        div { class: "rs-mini-ring absolute inset-0 rounded-full border-2 border-emerald-500/40" }
+                    } else {
+                        SidebarProgressRing {}
+                    }
+                    div { class: "absolute inset-[5px] rounded-full overflow-hidden bg-zinc-800",
+                        match &cover_url {
+                            Some(url) => rsx! {
+                                img {
+                                    src: "{url}",
+                                    alt: "{title}",
+                                    class: "w-full h-full object-cover",
+                                    loading: "lazy",
+                                }
+                            },
+                            None => rsx! {
+                                div { class: "w-full h-full flex items-center justify-center bg-gradient-to-br from-emerald-600 to-teal-700",
+                                    Icon { name: "music".to_string(), class: "w-4 h-4 text-white/70".to_string() }
+                                }
+                            },
+                        }
+                    }
+                }
+                div { class: "min-w-0 flex-1",
+                    MarqueeText {
+                        text: title.clone(),
+                        class: "text-sm font-medium text-white".to_string(),
+                    }
+                    MarqueeText {
+                        text: subtitle,
+                        class: "text-xs text-zinc-400".to_string(),
+                    }
+                }
+                SidebarMiniControls { is_radio }
+            }
+        }
+    }
+}
+
+/// Circular progress ring around the mini card artwork.
+///
+/// Progress is quantized and handed to CSS through `--rs-mini-progress`, so this
+/// component only re-renders when the visible arc actually moves.
+#[component]
+fn SidebarProgressRing() -> Element {
+    let audio_state = use_context::<Signal<AudioState>>();
+    let progress_step = use_memo(move || {
+        let current_time = (audio_state().current_time)();
+        let duration = (audio_state().duration)();
+        if duration > 0.0 && current_time.is_finite() {
+            ((current_time / duration).clamp(0.0, 1.0) * MINI_CARD_PROGRESS_STEPS).round() as u32
+        } else {
+            0
+        }
+    });
+    let progress = progress_step() as f64 / MINI_CARD_PROGRESS_STEPS;
+
+    rsx! {
+        svg {
+            class: "rs-mini-ring absolute inset-0 w-full h-full -rotate-90",
+            style: "--rs-mini-progress: {progress:.3};",
+            view_box: "0 0 48 48",
+            circle {
+                class: "rs-mini-ring__track",
+                cx: "24",
+                cy: "24",
+                r: "22",
+                fill: "none",
+                stroke_width: "2.5",
+            }
+            circle {
+                class: "rs-mini-ring__value",
+                cx: "24",
+                cy: "24",
+                r: "22",
+                fill: "none",
+                stroke_width: "2.5",
+                stroke_linecap: "round",
+                "pathLength": "100",
+            }
+        }
+    }
+}
+
+/// Single-line label that scrolls via a CSS animation when it would be truncated.
+#[component]
+fn MarqueeText(text: String, class: String) -> Element {
+    let char_count = text.chars().count();
+    if char_count <= MINI_CARD_MARQUEE_CHARS {
+        return rsx! {
+            p { class: "{class} truncate", title: "{text}", "{text}" }
+        };
+    }
+
+    // Keep the scroll speed roughly constant regardless of title length.
+    let duration_secs = (char_count as f64 * 0.35).clamp(6.0, 30.0);
+    rsx! {
+        div {
+            class: "{class} rs-marquee",
+            title: "{text}",
+            style: "--rs-marquee-duration: {duration_secs:.1}s;",
+            span { class: "rs-marquee__track",
+                span { "{text}" }
+                span { aria_hidden: "true", "{text}" }
+            }
+        }
+    }
+}
+
+/// Play/pause and next buttons revealed when hovering the mini card.
+#[component]
+fn SidebarMiniControls(is_radio: bool) -> Element {
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let mut is_playing = use_context::<IsPlayingSignal>().0;
+    let mut queue_index = use_context::<Signal<usize>>();
+    let queue = use_context::<Signal<Vec<Song>>>();
+    let audio_state = use_context::<Signal<AudioState>>();
+    let repeat_mode = use_context::<Signal<RepeatMode>>();
+    let shuffle_enabled = use_context::<ShuffleEnabledSignal>().0;
+    let mut now_playing = use_context::<Signal<Option<Song>>>();
+    let listen_along = use_context::<ListenAlongController>();
+    let playing = is_playing();
+
+    let on_toggle = move |e: MouseEvent| {
+        e.stop_propagation();
+        let current = *is_playing.peek();
+        ios_diag_log(
+            "ui.control",
+            &format!(
+                "source=sidebar.play-pause current={current} next={}",
+                !current
+            ),
+        );
+        is_playing.set(!current);
+    };
+
+    let on_next = move |e: MouseEvent| {
+        e.stop_propagation();
+        if is_radio {
+            return;
+        }
+        let was_playing = *is_playing.peek();
+        let repeat = *repeat_mode.peek();
+        if repeat == RepeatMode::One {
+            let restart_at = now_playing
+                .peek()
+                .as_ref()
+                .map(|song| song_start_offset(&app_settings.peek(), song))
+                .unwrap_or(0.0);
+            seek_to(restart_at);
+            return;
+        }
+        let idx = *queue_index.peek();
+        let queue_list = queue.peek().clone();
+        let shuffle = *shuffle_enabled.peek();
+        let target_idx = if idx < queue_list.len().saturating_sub(1) {
+            Some(idx + 1)
+        } else if repeat == RepeatMode::All && !queue_list.is_empty() {
+            Some(0)
+        } else {
+            None
+        };
+        ios_diag_log(
+            "ui.control",
+            &format!(
+                "source=sidebar.next from_idx={idx} to_idx={target_idx:?} queue_len={} was_playing={was_playing}",
+                queue_list.len()
+            ),
+        );
+        if let Some(song) = target_idx.and_then(|next| queue_list.get(next).cloned()) {
+            queue_index.set(target_idx.unwrap_or_default());
+            now_playing.set(Some(song));
+            if was_playing {
+                is_playing.set(true);
+            }
+        } else if matches!(repeat, RepeatMode::Off | RepeatMode::Count)
+            && queue_should_generate_similar_on_end(
+                &queue_list,
+                now_playing.peek().as_ref(),
+                shuffle,
+            )
+        {
+            spawn_shuffle_queue(
+                servers.peek().clone(),
+                queue,
+                queue_index,
+                now_playing,
+                is_playing,
+                audio_state,
+                now_playing.peek().clone(),
+                Some(was_playing),
+            );
+        } else {
+            is_playing.set(false);
+        }
+    };
+
+    if listen_along.is_following() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "flex items-center gap-1 opacity-0 group-hover:opacity-100 focus-within:opacity-100 transition-opacity",
+            button {
+                r#type: "button",
+                class: "w-8 h-8 rounded-full bg-white text-black flex items-center justify-center hover:scale-105 transition-transform",
+                aria_label: if playing { "Pause" } else { "Play" },
+                onclick: on_toggle,
+                Icon {
+                    name: if playing { "pause".to_string() } else { "play".to_string() },
+                    class: "w-4 h-4".to_string(),
+                }
+            }
+            if !is_radio {
+                button {
+                    r#type: "button",
+                    class: "p-1.5 text-zinc-300 hover:text-white transition-colors",
+                    aria_label: "Next track",
+                    onclick: on_next,
+                    Icon { name: "next".to_string(), class: "w-4 h-4".to_string() }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Sleep timer: after a chosen number of minutes the output fades out and playback pauses,
+//! with the queue left where it was. Stopping at the end of the current track is the
+//! "stop after" choice in the same menu.
+use crate::api::models::format_duration;
+use crate::components::{Icon, SleepTimerSignal};
+use dioxus::prelude::*;
+
+/// Lengths offered in the Player's overflow menu.
+pub const SLEEP_TIMER_MINUTES: [u32; 4] = [15, 30, 45, 60];
+/// How long the output takes to fade out before the timer pauses playback.
+const SLEEP_FADE_MS: u64 = 5_000;
+/// How often the deadline is checked, and so how fine the fade's steps are.
+const SLEEP_TICK_MS: u64 = 200;
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep_tick_pause(ms: u64) {
+    gloo_timers::future::TimeoutFuture::new(ms as u32).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep_tick_pause(ms: u64) {
+    tokio::time::sleep(std::time::Duration::from_millis(ms)).await;
+}
+
+fn now_ms() -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Date::now().max(0.0).round() as u64
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or(0)
+    }
+}
+
+/// A running sleep timer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SleepTimer {
+    /// The length it was set to.
+    pub minutes: u32,
+    deadline_ms: u64,
+}
+
+impl SleepTimer {
+    /// A timer that pauses playback `minutes` from now.
+    pub fn start(minutes: u32) -> Self {
+        Self::started_at(minutes, now_ms())
+    }
+
+    fn started_at(minutes: u32, now_ms: u64) -> Self {
+        Self {
+            minutes,
+            deadline_ms: now_ms.saturating_add(u64::from(minutes) * 60_000),
+        }
+    }
+
+    fn remaining_ms(&self, now_ms: u64) -> u64 {
+        self.deadline_ms.saturating_sub(now_ms)
+    }
+
+    /// Share of the output volume left at `now_ms`: all of it until the last
+    /// `SLEEP_FADE_MS`, then less and less until none at the deadline.
+    fn fade_level(&self, now_ms: u64) -> f64 {
+        (self.remaining_ms(now_ms) as f64 / SLEEP_FADE_MS as f64).clamp(0.0, 1.0)
+    }
+
+    /// "Sleep in 29:41"-style label for the Player.
+    fn indicator_label(&self, now_ms: u64) -> String {
+        let remaining_secs = self.remaining_ms(now_ms).div_ceil(1000);
+        format!("Sleep in {}", format_duration(remaining_secs as u32))
+    }
+}
+
+/// Runs the sleep timer for the audio manager. Returns the share of the output volume to
+/// keep, which falls to none over the timer's last seconds; when it runs out playback pauses
+/// and the timer clears. The output comes back once playback resumes, or at once if the
+/// timer is cancelled mid-fade.
+pub fn use_sleep_timer(
+    sleep_timer: Signal<Option<SleepTimer>>,
+    is_playing: Signal<bool>,
+) -> Signal<f64> {
+    let level = use_signal(|| 1.0);
+    use_hook(move || {
+        let mut sleep_timer = sleep_timer;
+        let mut is_playing = is_playing;
+        let mut level = level;
+        spawn(async move {
+            loop {
+                sleep_tick_pause(SLEEP_TICK_MS).await;
+                let now = now_ms();
+                let armed = *sleep_timer.peek();
+                let next_level = match armed {
+                    Some(timer) if timer.remaining_ms(now) == 0 => {
+                        is_playing.set(false);
+                        sleep_timer.set(None);
+                        0.0
+                    }
+                    Some(timer) => timer.fade_level(now),
+                    None if *is_playing.peek() => 1.0,
+                    None => *level.peek(),
+                };
+                if *level.peek() != next_level {
+                    level.set(next_level);
+                }
+            }
+        });
+    });
+    level
+}
+
+/// Time left on the sleep timer, shown under the player controls while one runs. Clicking
+/// it cancels the timer.
+#[component]
+pub fn SleepTimerChip() -> Element {
+    let mut sleep_timer = use_context::<SleepTimerSignal>().0;
+    let mut now = use_signal(now_ms);
+    use_hook(move || {
+        spawn(async move {
+            loop {
+                sleep_tick_pause(1_000).await;
+                now.set(now_ms());
+            }
+        })
+    });
+    let Some(timer) = sleep_timer() else {
+        return rsx! {};
+    };
+    let label = timer.indicator_label(now());
+
+    rsx! {
+        button {
+            r#type: "button",
+            class: "inline-flex items-center gap-1.5 px-2 py-0.5 rounded-full bg-indigo-500/15 text-[11px] font-medium text-indigo-300 hover:bg-indigo-500/25 transition-colors tabular-nums",
+            aria_label: "{label}. Cancel",
+            title: "Cancel sleep timer",
+            onclick: move |_| sleep_timer.set(None),
+            Icon { name: "moon".to_string(), class: "w-3 h-3".to_string() }
+            "{label}"
+            Icon { name: "x".to_string(), class: "w-3 h-3".to_string() }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fades_over_the_last_seconds_and_counts_down() {
+        let timer = SleepTimer::started_at(15, 1_000);
+        let deadline = 1_000 + 15 * 60_000;
+        assert_eq!(timer.remaining_ms(1_000), 15 * 60_000);
+        assert_eq!(timer.fade_level(1_000), 1.0);
+        assert_eq!(timer.fade_level(deadline - SLEEP_FADE_MS), 1.0);
+        assert_eq!(timer.fade_level(deadline - SLEEP_FADE_MS / 2), 0.5);
+        assert_eq!(timer.fade_level(deadline), 0.0);
+        assert_eq!(timer.remaining_ms(deadline + 1), 0);
+
+        assert_eq!(timer.indicator_label(1_000), "Sleep in 15:00");
+        assert_eq!(timer.indicator_label(deadline - 60_500), "Sleep in 1:01");
+        assert_eq!(timer.indicator_label(deadline), "Sleep in 0:00");
+    }
+}
@@ -0,0 +1,34 @@
+// Credits list (composer, conductor, featured artists) from OpenSubsonic song entries.
+
+#[component]
+fn SongCreditsSection(song: Song) -> Element {
+    let credits = song.credits();
+    if credits.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "space-y-2 pt-3 border-t border-zinc-800/70",
+            p { class: "text-[10px] uppercase tracking-[0.18em] text-zinc-500", "Credits" }
+            div { class: "space-y-1.5",
+                for credit in credits {
+                    div {
+                        key: "credit-{song.id}-{credit.role}",
+                        class: "flex flex-wrap items-baseline justify-center gap-x-1.5 gap-y-0.5",
+                        span { class: "text-xs text-zinc-500", "{credit.role}:" }
+                        for (index , name) in credit.names.iter().enumerate() {
+                            CreditArtistName {
+                                key: "{credit.role}-{name}",
+                                name: name.clone(),
+                                server_id: song.server_id.clone(),
+                            }
+                            if index + 1 < credit.names.len() {
+                                span { class: "text-zinc-600", "•" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -9,6 +9,7 @@ struct DetailsPanelProps {
 fn DetailsPanel(props: DetailsPanelProps) -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
     let app_settings = use_context::<Signal<AppSettings>>();
+    let settings_controller = use_context::<SettingsController>();
     let add_menu = use_context::<AddMenuController>();
     let navigation = use_context::<Navigation>();
     let controller = use_context::<SongDetailsController>();
@@ -20,8 +21,11 @@ fn DetailsPanel(props: DetailsPanelProps) -> Element {
     let shuffle_enabled = use_context::<crate::components::ShuffleEnabledSignal>().0;
     let volume = use_context::<VolumeSignal>().0;
     let playback_position = use_context::<PlaybackPositionSignal>().0;
+    let seek_request = use_context::<SeekRequestSignal>().0;
     let audio_state = use_context::<Signal<AudioState>>();
     let mut rating_open = use_signal(|| false);
+    let mut bookmark_status = use_signal(|| None::<String>);
+    let mut download_quality_status = use_signal(|| None::<String>);
 
     let now_playing_song = now_playing();
     let queue_snapshot = queue();
@@ -40,7 +44,14 @@ fn DetailsPanel(props: DetailsPanelProps) -> Element {
                 .map(|song| song.starred.is_some())
         })
         .unwrap_or(props.song.starred.is_some());
+    let can_rate =
+        use_context::<ServerCapabilitiesSignal>().user_can(&props.song.server_id, UserAction::Rate);
     let is_live_stream = is_live_song(&props.song);
+    let following_host = use_context::<ListenAlongController>().is_following();
+    let radio_raw_title = use_context::<crate::components::RadioRawTitleSignal>().0;
+    let stream_raw_title = radio_raw_title()
+        .filter(|(song_id, _)| is_live_stream && *song_id == props.song.id)
+        .map(|(_, raw_title)| raw_title);
     let currently_playing = is_playing();
     let current_repeat_mode = repeat_mode();
     let queue_len = queue_snapshot.len();
@@ -48,7 +59,7 @@ fn DetailsPanel(props: DetailsPanelProps) -> Element {
     let can_next = !is_live_stream
         && (queue_index().saturating_add(1) < queue_len
             || (current_repeat_mode == RepeatMode::All && queue_len > 0)
-            || current_repeat_mode == RepeatMode::Off
+            || matches!(current_repeat_mode, RepeatMode::Off | RepeatMode::Count)
             || (current_repeat_mode == RepeatMode::One && now_playing_song.is_some()));
     let now_playing_rating = now_playing_song
         .as_ref()
@@ -65,11 +76,6 @@ fn DetailsPanel(props: DetailsPanelProps) -> Element {
             .map(|song| song.duration as f64)
             .unwrap_or(0.0)
     };
-    let playback_percent = if display_duration > 0.0 {
-        ((current_time / display_duration) * 100.0).clamp(0.0, 100.0)
-    } else {
-        0.0
-    };
 
     let song_artist = props
         .song
@@ -96,6 +102,33 @@ fn DetailsPanel(props: DetailsPanelProps) -> Element {
         .filter(|value| !value.trim().is_empty())
         .unwrap_or_else(|| "Unknown Album".to_string());
     let panel_song_id = props.song.id.clone();
+    let offsets_duration = if is_selected_song_now_playing && duration > 0.0 {
+        duration
+    } else {
+        props.song.duration as f64
+    };
+    let can_edit_offsets = !is_live_stream && offsets_duration > 0.0;
+    let song_offsets = app_settings().playback_offsets_for(&props.song.server_id, &props.song.id);
+    let offset_start = song_offsets.start_secs.unwrap_or(0.0).clamp(0.0, offsets_duration);
+    let offset_end = song_offsets
+        .end_secs
+        .unwrap_or(offsets_duration)
+        .clamp(offset_start, offsets_duration);
+    let offset_start_percent = if can_edit_offsets {
+        offset_start / offsets_duration * 100.0
+    } else {
+        0.0
+    };
+    let offset_end_percent = if can_edit_offsets {
+        offset_end / offsets_duration * 100.0
+    } else {
+        100.0
+    };
+    let can_loop = is_selected_song_now_playing && !is_live_stream && display_duration > 0.0;
+    let loop_start = (audio_state().loop_start)();
+    let loop_end = (audio_state().loop_end)();
+    let can_pick_download_quality = cfg!(not(target_arch = "wasm32")) && !is_live_stream;
+    let download_quality = download_quality_for(&props.song.server_id, &props.song.id);
 
     let make_on_open_artist_named = {
         let servers = servers.clone();
@@ -275,7 +308,11 @@ fn DetailsPanel(props: DetailsPanelProps) -> Element {
             let was_playing = is_playing();
             let repeat = repeat_mode();
             if repeat == RepeatMode::One {
-                seek_to(0.0);
+                let restart_at = now_playing()
+                    .as_ref()
+                    .map(|song| song_start_offset(&app_settings.peek(), song))
+                    .unwrap_or(0.0);
+                seek_to(restart_at);
                 if was_playing {
                     is_playing.set(true);
                 }
@@ -285,7 +322,7 @@ fn DetailsPanel(props: DetailsPanelProps) -> Element {
             let next_idx = idx.saturating_add(1);
             let queue_list = queue();
             let shuffle = shuffle_enabled();
-            if repeat == RepeatMode::Off
+            if matches!(repeat, RepeatMode::Off | RepeatMode::Count)
                 && idx >= queue_list.len().saturating_sub(1)
                 && queue_should_generate_similar_on_end(
                     &queue_list,
@@ -322,21 +359,37 @@ fn DetailsPanel(props: DetailsPanelProps) -> Element {
         }
     };
 
-    let on_seek_now_playing = {
-        let mut playback_position = playback_position.clone();
-        let mut audio_state = audio_state.clone();
-        move |evt: Event<FormData>| {
-            let Ok(value) = evt.value().parse::<f64>() else {
+    let on_play_from_beginning = {
+        let song = props.song.clone();
+        move |_| {
+            bookmark_status.set(None);
+            play_song_from_beginning(
+                song.clone(),
+                now_playing,
+                queue,
+                queue_index,
+                is_playing,
+                playback_position,
+                seek_request,
+            );
+        }
+    };
+
+    let on_delete_bookmark = {
+        let song = props.song.clone();
+        move |_| {
+            let Some(server) = servers().into_iter().find(|s| s.id == song.server_id) else {
+                bookmark_status.set(Some("Server not found for this song.".to_string()));
                 return;
             };
-            let duration = (audio_state().duration)();
-            if duration <= 0.0 {
-                return;
-            }
-            let target = (value.clamp(0.0, 100.0) / 100.0) * duration;
-            playback_position.set(target);
-            audio_state.write().current_time.set(target);
-            seek_to(target);
+            let song_id = song.id.clone();
+            spawn(async move {
+                let message = match NavidromeClient::new(server).delete_bookmark(&song_id).await {
+                    Ok(()) => "Bookmark deleted.".to_string(),
+                    Err(err) => format!("Could not delete bookmark: {err}"),
+                };
+                bookmark_status.set(Some(message));
+            });
         }
     };
 
@@ -374,26 +427,16 @@ fn DetailsPanel(props: DetailsPanelProps) -> Element {
         }
     };
     let on_cycle_loop = {
-        let mut app_settings = app_settings.clone();
         let mut repeat_mode = repeat_mode.clone();
         move |_| {
-            let next = match repeat_mode() {
-                RepeatMode::Off => RepeatMode::All,
-                RepeatMode::All => RepeatMode::One,
-                RepeatMode::One => RepeatMode::Off,
-            };
+            let next = repeat_mode().cycled();
             repeat_mode.set(next);
-            app_settings.with_mut(|settings| {
+            settings_controller.update_settings(|settings| {
                 settings.repeat_mode = next;
             });
-            let settings_snapshot = app_settings();
-            spawn(async move {
-                let _ = crate::db::save_settings(settings_snapshot).await;
-            });
         }
     };
     let on_toggle_shuffle = {
-        let mut app_settings = app_settings.clone();
         let mut shuffle_enabled = shuffle_enabled.clone();
         let queue = queue.clone();
         let queue_index = queue_index.clone();
@@ -407,12 +450,119 @@ fn DetailsPanel(props: DetailsPanelProps) -> Element {
                 now_playing.clone(),
                 next,
             );
-            app_settings.with_mut(|settings| {
+            settings_controller.update_settings(|settings| {
                 settings.shuffle_enabled = next;
             });
+        }
+    };
+    let on_save_offsets = {
+        let key = song_playback_offsets_key(&props.song.server_id, &props.song.id);
+        move |offsets: SongPlaybackOffsets| {
+            settings_controller.update_settings(|settings| {
+                if offsets.is_empty() {
+                    settings.song_playback_offsets.remove(&key);
+                } else {
+                    settings.song_playback_offsets.insert(key.clone(), offsets);
+                }
+            });
+        }
+    };
+    let on_start_offset_input = {
+        let on_save_offsets = on_save_offsets.clone();
+        move |evt: Event<FormData>| {
+            let Ok(value) = evt.value().parse::<f64>() else {
+                return;
+            };
+            // Keep at least a second of audio between the two markers.
+            let start = value.clamp(0.0, (offset_end - 1.0).max(0.0)).round();
+            on_save_offsets(SongPlaybackOffsets {
+                start_secs: (start > 0.0).then_some(start),
+                ..song_offsets
+            });
+        }
+    };
+    let on_end_offset_input = {
+        let on_save_offsets = on_save_offsets.clone();
+        move |evt: Event<FormData>| {
+            let Ok(value) = evt.value().parse::<f64>() else {
+                return;
+            };
+            let end = value.clamp(offset_start + 1.0, offsets_duration).round();
+            on_save_offsets(SongPlaybackOffsets {
+                end_secs: (end < offsets_duration.floor()).then_some(end),
+                ..song_offsets
+            });
+        }
+    };
+    let on_clear_offsets = {
+        let on_save_offsets = on_save_offsets.clone();
+        move |_| on_save_offsets(SongPlaybackOffsets::default())
+    };
+    let on_set_loop_a = move |_| {
+        let state = audio_state.peek().clone();
+        let (mut loop_start, mut loop_end) = (state.loop_start, state.loop_end);
+        let start = current_time.max(0.0);
+        loop_start.set(Some(start));
+        if loop_end.peek().is_some_and(|end| end < start + AB_LOOP_MIN_SECS) {
+            loop_end.set(None);
+        }
+    };
+    let on_set_loop_b = move |_| {
+        let Some(start) = loop_start else {
+            return;
+        };
+        // B stays short of the end and of any end offset, so the loop restarts before the
+        // track can finish. Crossfade and gapless playback wait while the loop is set.
+        let end = current_time
+            .max(start + AB_LOOP_MIN_SECS)
+            .min(offset_end.min(display_duration) - AB_LOOP_MIN_SECS);
+        if end > start {
+            let mut loop_end = audio_state.peek().loop_end;
+            loop_end.set(Some(end));
+        }
+    };
+    let on_clear_loop_a = move |_| {
+        let mut loop_start = audio_state.peek().loop_start;
+        loop_start.set(None);
+    };
+    let on_clear_loop_b = move |_| {
+        let mut loop_end = audio_state.peek().loop_end;
+        loop_end.set(None);
+    };
+    let on_clear_loop = move |_| {
+        let state = audio_state.peek().clone();
+        let (mut loop_start, mut loop_end) = (state.loop_start, state.loop_end);
+        loop_start.set(None);
+        loop_end.set(None);
+    };
+    let on_download_quality_change = {
+        let song = props.song.clone();
+        move |evt: Event<FormData>| {
+            let Some(quality) = DownloadQuality::from_key(&evt.value()) else {
+                return;
+            };
+            let redownload = set_download_quality_override(&song.server_id, &song.id, quality);
+            if !redownload {
+                download_quality_status.set(Some(format!(
+                    "Future downloads of this song use {}.",
+                    quality.label()
+                )));
+                return;
+            }
+            download_quality_status.set(Some(format!(
+                "Downloading again at {}...",
+                quality.label()
+            )));
+            let song = song.clone();
+            let servers_snapshot = servers();
             let settings_snapshot = app_settings();
             spawn(async move {
-                let _ = crate::db::save_settings(settings_snapshot).await;
+                let message =
+                    match prefetch_song_audio(&song, &servers_snapshot, &settings_snapshot).await {
+                        Ok(()) => format!("Downloaded again at {}.", quality.label()),
+                        Err(err) => format!("Could not download again: {err}"),
+                    };
+                download_quality_status.set(Some(message));
             });
         }
     };
@@ -83,6 +83,25 @@
                         p { class: "text-sm text-zinc-400 whitespace-normal break-words leading-snug", "{song_album}" }
                     }
                 }
+                SongCreditsSection { song: props.song.clone() }
+                SongSkipStatsSection {
+                    key: "{song_lyrics_key(&props.song)}",
+                    song: props.song.clone(),
+                }
+                if let Some(raw_title) = stream_raw_title.clone() {
+                    div { class: "space-y-1 pt-3 border-t border-zinc-800/70",
+                        p { class: "text-[10px] uppercase tracking-[0.18em] text-zinc-500", "Raw stream title" }
+                        p { class: "text-xs font-mono text-zinc-400 whitespace-normal break-all leading-snug select-text",
+                            "{raw_title}"
+                        }
+                    }
+                }
+                if is_selected_song_now_playing {
+                    StreamDiagnosticsSection {
+                        key: "{song_lyrics_key(&props.song)}",
+                        song: props.song.clone(),
+                    }
+                }
             }
 
             div { class: "grid grid-cols-3 gap-2 text-center",
@@ -100,6 +119,168 @@
                 }
             }
 
+            if !is_live_stream {
+                div { class: "space-y-1",
+                    div { class: "flex flex-wrap gap-2",
+                        button {
+                            class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors text-xs flex items-center gap-1.5",
+                            onclick: on_play_from_beginning,
+                            title: "Ignore any saved bookmark and start at 0:00",
+                            Icon { name: "play".to_string(), class: "w-3.5 h-3.5".to_string() }
+                            "Play from beginning"
+                        }
+                        button {
+                            class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-400 hover:text-white hover:border-zinc-500 transition-colors text-xs",
+                            onclick: on_delete_bookmark,
+                            "Delete bookmark"
+                        }
+                    }
+                    if let Some(status) = bookmark_status() {
+                        p { class: "text-xs text-zinc-500", "{status}" }
+                    }
+                }
+            }
+
+            if can_edit_offsets {
+                div { class: "rounded-2xl border border-zinc-800/80 bg-zinc-900/50 p-3 space-y-3",
+                    div { class: "flex items-center justify-between gap-2",
+                        p { class: "text-sm font-medium text-white", "Playback Range" }
+                        if !song_offsets.is_empty() {
+                            button {
+                                class: "text-[11px] text-zinc-500 hover:text-zinc-300 transition-colors",
+                                onclick: on_clear_offsets,
+                                "Clear"
+                            }
+                        }
+                    }
+                    div { class: "relative h-2 rounded-full bg-zinc-800 overflow-hidden",
+                        div {
+                            class: "absolute inset-y-0 bg-emerald-500/60",
+                            style: "left: {offset_start_percent}%; width: {offset_end_percent - offset_start_percent}%;",
+                        }
+                    }
+                    div { class: "space-y-1",
+                        div { class: "flex items-center justify-between text-xs text-zinc-500",
+                            span { "Start" }
+                            span { "{format_duration(offset_start as u32)}" }
+                        }
+                        input {
+                            r#type: "range",
+                            min: "0",
+                            max: "{offsets_duration.floor()}",
+                            step: "1",
+                            value: offset_start.round() as i64,
+                            aria_label: "Start offset",
+                            class: "w-full h-1.5 bg-zinc-800 rounded-full appearance-none cursor-pointer accent-amber-400",
+                            onchange: on_start_offset_input,
+                        }
+                    }
+                    div { class: "space-y-1",
+                        div { class: "flex items-center justify-between text-xs text-zinc-500",
+                            span { "End" }
+                            span { "{format_duration(offset_end as u32)}" }
+                        }
+                        input {
+                            r#type: "range",
+                            min: "0",
+                            max: "{offsets_duration.floor()}",
+                            step: "1",
+                            value: offset_end.round() as i64,
+                            aria_label: "End offset",
+                            class: "w-full h-1.5 bg-zinc-800 rounded-full appearance-none cursor-pointer accent-amber-400",
+                            onchange: on_end_offset_input,
+                        }
+                    }
+                    p { class: "text-xs text-zinc-500",
+                        "Plays of this song start and stop at these points. Bookmarks past the start still resume where you left off."
+                    }
+                }
+            }
+
+            if can_loop {
+                div { class: "rounded-2xl border border-zinc-800/80 bg-zinc-900/50 p-3 space-y-3",
+                    div { class: "flex items-center justify-between gap-2",
+                        p { class: "text-sm font-medium text-white", "A-B Loop" }
+                        if loop_start.is_some() || loop_end.is_some() {
+                            button {
+                                class: "text-[11px] text-zinc-500 hover:text-zinc-300 transition-colors",
+                                onclick: on_clear_loop,
+                                "Clear"
+                            }
+                        }
+                    }
+                    div { class: "flex flex-wrap gap-2",
+                        button {
+                            class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors text-xs tabular-nums",
+                            onclick: on_set_loop_a,
+                            title: "Mark the loop start at the current position",
+                            if let Some(start) = loop_start {
+                                "A {format_duration(start as u32)}"
+                            } else {
+                                "Set A"
+                            }
+                        }
+                        if loop_start.is_some() {
+                            button {
+                                class: "p-1.5 rounded-lg text-zinc-500 hover:text-zinc-300 transition-colors text-xs",
+                                onclick: on_clear_loop_a,
+                                title: "Clear the loop start",
+                                Icon { name: "x".to_string(), class: "w-3.5 h-3.5".to_string() }
+                            }
+                        }
+                        button {
+                            class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors text-xs tabular-nums disabled:opacity-40 disabled:cursor-not-allowed",
+                            disabled: loop_start.is_none(),
+                            onclick: on_set_loop_b,
+                            title: "Mark the loop end at the current position",
+                            if let Some(end) = loop_end {
+                                "B {format_duration(end as u32)}"
+                            } else {
+                                "Set B"
+                            }
+                        }
+                        if loop_end.is_some() {
+                            button {
+                                class: "p-1.5 rounded-lg text-zinc-500 hover:text-zinc-300 transition-colors text-xs",
+                                onclick: on_clear_loop_b,
+                                title: "Clear the loop end",
+                                Icon { name: "x".to_string(), class: "w-3.5 h-3.5".to_string() }
+                            }
+                        }
+                    }
+                    p { class: "text-xs text-zinc-500",
+                        if loop_start.is_some() && loop_end.is_some() {
+                            "Looping between A and B. Clearing either point or changing songs ends the loop."
+                        } else {
+                            "Mark A, then B, to repeat a passage of this song."
+                        }
+                    }
+                }
+            }
+
+            if can_pick_download_quality {
+                div { class: "rounded-2xl border border-zinc-800/80 bg-zinc-900/50 p-3 space-y-2",
+                    div { class: "flex items-center justify-between gap-2",
+                        p { class: "text-sm font-medium text-white", "Download Quality" }
+                        select {
+                            class: "px-2 py-1 bg-zinc-800/50 border border-zinc-700/50 rounded text-xs text-white focus:outline-none focus:border-emerald-500/50",
+                            aria_label: "Download quality for this song",
+                            value: download_quality.key(),
+                            onchange: on_download_quality_change,
+                            for quality in DownloadQuality::ALL {
+                                option { key: "{quality.key()}", value: quality.key(), "{quality.label()}" }
+                            }
+                        }
+                    }
+                    p { class: "text-xs text-zinc-500",
+                        "Overrides the download quality for this song only. Changing it replaces a file that is already downloaded."
+                    }
+                    if let Some(status) = download_quality_status() {
+                        p { class: "text-xs text-zinc-400", "{status}" }
+                    }
+                }
+            }
+
             div { class: "rounded-2xl border border-zinc-800/80 bg-zinc-900/50 p-3 space-y-3",
                 div { class: "flex items-center justify-between gap-2",
                     p { class: "text-sm font-medium text-white", "Now Playing Controls" }
@@ -125,15 +306,17 @@
                             span { "{format_duration(current_time as u32)}" }
                             span { "{format_duration(display_duration.max(0.0) as u32)}" }
                         }
-                        input {
-                            r#type: "range",
-                            min: "0",
-                            max: "100",
-                            value: playback_percent.round() as i32,
-                            disabled: display_duration <= 0.0,
-                            class: "w-full h-1.5 bg-zinc-800 rounded-full appearance-none cursor-pointer accent-emerald-500 disabled:opacity-40 disabled:cursor-not-allowed",
-                            oninput: on_seek_now_playing,
-                            onchange: on_seek_now_playing,
+                        SeekBar {
+                            song: Some(current_song.clone()),
+                            current_time,
+                            duration: display_duration,
+                            disabled: display_duration <= 0.0 || following_host,
+                            class: "w-full h-1.5 bg-zinc-800 rounded-full appearance-none cursor-pointer accent-emerald-500 disabled:opacity-40 disabled:cursor-not-allowed".to_string(),
+                            aria_valuetext: format!(
+                                "{} of {}",
+                                format_duration(current_time as u32),
+                                format_duration(display_duration.max(0.0) as u32),
+                            ),
                         }
                     }
 
@@ -165,6 +348,7 @@
                                     } else {
                                         "p-2 rounded-full border border-zinc-700 text-zinc-400 hover:text-white transition-colors"
                                     },
+                                    disabled: !can_rate,
                                     onclick: on_toggle_song_favorite,
                                     title: if is_selected_song_favorited { "Unfavorite song" } else { "Favorite song" },
                                     Icon {
@@ -186,16 +370,17 @@
                                     }
                                 }
                                 button {
-                                    class: if matches!(current_repeat_mode, RepeatMode::All | RepeatMode::One) {
+                                    class: if current_repeat_mode != RepeatMode::Off {
                                         "p-2 rounded-full border border-emerald-500/50 text-emerald-300 hover:text-emerald-200 transition-colors"
                                     } else {
                                         "p-2 rounded-full border border-zinc-700 text-zinc-400 hover:text-white transition-colors"
                                     },
                                     onclick: on_cycle_loop,
                                     title: match current_repeat_mode {
-                                        RepeatMode::Off => "Repeat (off)",
-                                        RepeatMode::All => "Repeat all (on)",
-                                        RepeatMode::One => "Repeat one (on)",
+                                        RepeatMode::Off => "Repeat (off)".to_string(),
+                                        RepeatMode::All => "Repeat all (on)".to_string(),
+                                        RepeatMode::One => "Repeat one (on)".to_string(),
+                                        RepeatMode::Count => format!("Repeat {} times (on)", app_settings().repeat_count),
                                     },
                                     Icon {
                                         name: if current_repeat_mode == RepeatMode::One { "repeat-1".to_string() } else { "repeat".to_string() },
@@ -217,6 +402,7 @@
                                     } else {
                                         "p-2 rounded-full border border-zinc-700 text-zinc-400 hover:text-white transition-colors"
                                     },
+                                    disabled: !can_rate,
                                     onclick: move |_| rating_open.set(!rating_open()),
                                     title: "Rate now playing",
                                     Icon {
@@ -261,34 +447,38 @@
                 }
             }
 
-            div { class: "grid grid-cols-3 gap-3",
-                button {
-                    class: if can_prev {
-                        "h-11 rounded-xl border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors flex items-center justify-center"
-                    } else {
-                        "h-11 rounded-xl border border-zinc-800 text-zinc-600 cursor-not-allowed flex items-center justify-center"
-                    },
-                    disabled: !can_prev,
-                    onclick: on_prev_song,
-                    Icon { name: "prev".to_string(), class: "w-5 h-5".to_string() }
-                }
-                button {
-                    class: "h-11 rounded-xl bg-emerald-500 hover:bg-emerald-400 text-white transition-colors flex items-center justify-center",
-                    onclick: on_toggle_selected_playback,
-                    Icon {
-                        name: if is_selected_song_now_playing && currently_playing { "pause".to_string() } else { "play".to_string() },
-                        class: "w-5 h-5".to_string(),
+            if following_host {
+                ListenAlongControls {}
+            } else {
+                div { class: "grid grid-cols-3 gap-3",
+                    button {
+                        class: if can_prev {
+                            "h-11 rounded-xl border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors flex items-center justify-center"
+                        } else {
+                            "h-11 rounded-xl border border-zinc-800 text-zinc-600 cursor-not-allowed flex items-center justify-center"
+                        },
+                        disabled: !can_prev,
+                        onclick: on_prev_song,
+                        Icon { name: "prev".to_string(), class: "w-5 h-5".to_string() }
+                    }
+                    button {
+                        class: "h-11 rounded-xl bg-emerald-500 hover:bg-emerald-400 text-white transition-colors flex items-center justify-center",
+                        onclick: on_toggle_selected_playback,
+                        Icon {
+                            name: if is_selected_song_now_playing && currently_playing { "pause".to_string() } else { "play".to_string() },
+                            class: "w-5 h-5".to_string(),
+                        }
+                    }
+                    button {
+                        class: if can_next {
+                            "h-11 rounded-xl border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors flex items-center justify-center"
+                        } else {
+                            "h-11 rounded-xl border border-zinc-800 text-zinc-600 cursor-not-allowed flex items-center justify-center"
+                        },
+                        disabled: !can_next,
+                        onclick: on_next_song,
+                        Icon { name: "next".to_string(), class: "w-5 h-5".to_string() }
                     }
-                }
-                button {
-                    class: if can_next {
-                        "h-11 rounded-xl border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors flex items-center justify-center"
-                    } else {
-                        "h-11 rounded-xl border border-zinc-800 text-zinc-600 cursor-not-allowed flex items-center justify-center"
-                    },
-                    disabled: !can_next,
-                    onclick: on_next_song,
-                    Icon { name: "next".to_string(), class: "w-5 h-5".to_string() }
                 }
             }
         }
@@ -33,6 +33,17 @@ async fn fetch_first_available_lyrics(
     }
 }
 
+/// Key that tags a lyrics lookup with the song it was started for.
+fn song_lyrics_key(song: &Song) -> String {
+    format!("{}:{}", song.server_id, song.id)
+}
+
+/// A finished lyrics lookup, unless it was started for a song other than `song_key`.
+fn lyrics_for_song<T>(lookup: Option<Option<(String, T)>>, song_key: Option<&String>) -> Option<T> {
+    let (lookup_key, result) = lookup.flatten()?;
+    (song_key == Some(&lookup_key)).then_some(result)
+}
+
 fn build_mini_lyrics_preview(
     lyrics: Option<Result<LyricsResult, String>>,
     sync_lyrics: bool,
@@ -299,7 +310,10 @@ async fn fetch_related_candidates(client: &NavidromeClient, song: &Song, count:
         related.extend(by_artist);
     }
 
-    if related.len() < count as usize {
+    let top_songs_supported = client
+        .capabilities()
+        .is_none_or(|capabilities| capabilities.top_songs);
+    if top_songs_supported && related.len() < count as usize {
         if let Some(artist_name) = song.artist.clone().filter(|name| !name.trim().is_empty()) {
             let top_songs = client
                 .get_top_songs(&artist_name, count)
@@ -315,7 +329,7 @@ async fn fetch_related_candidates(client: &NavidromeClient, song: &Song, count:
             song.artist.clone().unwrap_or_default(),
             song.title.clone()
         );
-        if let Ok(search) = client.search(&fallback_query, 0, 0, count).await {
+        if let Ok(search) = client.search(&fallback_query, 0, 0, count, None).await {
             related.extend(search.songs);
         }
     }
@@ -914,7 +914,8 @@ async fn share_screenshot_lyrics_image(
 }
 
 const RUSTYSOUND_MARK: Asset = asset!("/assets/favicon-96x96.png");
-const HTML2CANVAS_BUNDLE: &str = include_str!("../../../assets/vendor/html2canvas.min.js");
+pub(crate) const HTML2CANVAS_BUNDLE: &str =
+    include_str!("../../../assets/vendor/html2canvas.min.js");
 
 #[component]
 fn LyricsPanel(props: LyricsPanelProps) -> Element {
@@ -922,6 +923,7 @@ fn LyricsPanel(props: LyricsPanelProps) -> Element {
     let controller = use_context::<SongDetailsController>();
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
     let app_settings = use_context::<Signal<AppSettings>>();
+    let settings_controller = use_context::<SettingsController>();
     let playback_position = use_context::<PlaybackPositionSignal>().0;
     let audio_state = use_context::<Signal<AudioState>>();
     let search_panel_open = use_signal(|| false);
@@ -1803,16 +1805,12 @@ fn LyricsPanel(props: LyricsPanelProps) -> Element {
                                     class: if screenshot_shot_theme_active == ScreenshotShotTheme::Lagoon { "inline-flex items-center gap-2 rounded-full border border-white/35 bg-white/12 px-3 py-2 text-sm text-white" } else { "inline-flex items-center gap-2 rounded-full border border-white/15 bg-white/5 px-3 py-2 text-sm text-white/72 hover:text-white hover:border-white/25 transition-colors" },
                                     onclick: {
                                         let mut screenshot_shot_theme = screenshot_shot_theme.clone();
-                                        let mut app_settings = app_settings.clone();
                                         let mut theme_picker_open = theme_picker_open.clone();
                                         move |_| {
                                             screenshot_shot_theme.set(ScreenshotShotTheme::Lagoon);
                                             theme_picker_open.set(false);
-                                            let mut settings = app_settings();
-                                            settings.lyrics_default_theme = "lagoon".to_string();
-                                            app_settings.set(settings.clone());
-                                            spawn(async move {
-                                                let _ = crate::db::save_settings(settings).await;
+                                            settings_controller.update_settings(|settings| {
+                                                settings.lyrics_default_theme = "lagoon".to_string();
                                             });
                                         }
                                     },
@@ -1823,16 +1821,12 @@ fn LyricsPanel(props: LyricsPanelProps) -> Element {
                                     class: if screenshot_shot_theme_active == ScreenshotShotTheme::Ember { "inline-flex items-center gap-2 rounded-full border border-white/35 bg-white/12 px-3 py-2 text-sm text-white" } else { "inline-flex items-center gap-2 rounded-full border border-white/15 bg-white/5 px-3 py-2 text-sm text-white/72 hover:text-white hover:border-white/25 transition-colors" },
                                     onclick: {
                                         let mut screenshot_shot_theme = screenshot_shot_theme.clone();
-                                        let mut app_settings = app_settings.clone();
                                         let mut theme_picker_open = theme_picker_open.clone();
                                         move |_| {
                                             screenshot_shot_theme.set(ScreenshotShotTheme::Ember);
                                             theme_picker_open.set(false);
-                                            let mut settings = app_settings();
-                                            settings.lyrics_default_theme = "ember".to_string();
-                                            app_settings.set(settings.clone());
-                                            spawn(async move {
-                                                let _ = crate::db::save_settings(settings).await;
+                                            settings_controller.update_settings(|settings| {
+                                                settings.lyrics_default_theme = "ember".to_string();
                                             });
                                         }
                                     },
@@ -1843,16 +1837,12 @@ fn LyricsPanel(props: LyricsPanelProps) -> Element {
                                     class: if screenshot_shot_theme_active == ScreenshotShotTheme::Midnight { "inline-flex items-center gap-2 rounded-full border border-white/35 bg-white/12 px-3 py-2 text-sm text-white" } else { "inline-flex items-center gap-2 rounded-full border border-white/15 bg-white/5 px-3 py-2 text-sm text-white/72 hover:text-white hover:border-white/25 transition-colors" },
                                     onclick: {
                                         let mut screenshot_shot_theme = screenshot_shot_theme.clone();
-                                        let mut app_settings = app_settings.clone();
                                         let mut theme_picker_open = theme_picker_open.clone();
                                         move |_| {
                                             screenshot_shot_theme.set(ScreenshotShotTheme::Midnight);
                                             theme_picker_open.set(false);
-                                            let mut settings = app_settings();
-                                            settings.lyrics_default_theme = "midnight".to_string();
-                                            app_settings.set(settings.clone());
-                                            spawn(async move {
-                                                let _ = crate::db::save_settings(settings).await;
+                                            settings_controller.update_settings(|settings| {
+                                                settings.lyrics_default_theme = "midnight".to_string();
                                             });
                                         }
                                     },
@@ -1864,16 +1854,12 @@ fn LyricsPanel(props: LyricsPanelProps) -> Element {
                                         class: if screenshot_shot_theme_active == ScreenshotShotTheme::Cover { "inline-flex items-center gap-2 rounded-full border border-white/35 bg-white/12 px-3 py-2 text-sm text-white" } else { "inline-flex items-center gap-2 rounded-full border border-white/15 bg-white/5 px-3 py-2 text-sm text-white/72 hover:text-white hover:border-white/25 transition-colors" },
                                         onclick: {
                                             let mut screenshot_shot_theme = screenshot_shot_theme.clone();
-                                            let mut app_settings = app_settings.clone();
                                             let mut theme_picker_open = theme_picker_open.clone();
                                             move |_| {
                                                 screenshot_shot_theme.set(ScreenshotShotTheme::Cover);
                                                 theme_picker_open.set(false);
-                                                let mut settings = app_settings();
-                                                settings.lyrics_default_theme = "cover".to_string();
-                                                app_settings.set(settings.clone());
-                                                spawn(async move {
-                                                    let _ = crate::db::save_settings(settings).await;
+                                                settings_controller.update_settings(|settings| {
+                                                    settings.lyrics_default_theme = "cover".to_string();
                                                 });
                                             }
                                         },
@@ -1933,17 +1919,13 @@ fn LyricsPanel(props: LyricsPanelProps) -> Element {
                                         let mut screenshot_shot_blur = screenshot_shot_blur.clone();
                                         let mut screenshot_shot_text_palette = screenshot_shot_text_palette.clone();
                                         let mut screenshot_shot_theme = screenshot_shot_theme.clone();
-                                        let mut app_settings = app_settings.clone();
                                         move |_| {
                                             screenshot_shot_font_scale.set(100);
                                             screenshot_shot_blur.set(28);
                                             screenshot_shot_text_palette.set(ScreenshotTextPalette::Auto);
                                             screenshot_shot_theme.set(ScreenshotShotTheme::Cover);
-                                            let mut settings = app_settings();
-                                            settings.lyrics_default_theme = "cover".to_string();
-                                            app_settings.set(settings.clone());
-                                            spawn(async move {
-                                                let _ = crate::db::save_settings(settings).await;
+                                            settings_controller.update_settings(|settings| {
+                                                settings.lyrics_default_theme = "cover".to_string();
                                             });
                                         }
                                     },
@@ -2079,14 +2061,10 @@ fn LyricsPanel(props: LyricsPanelProps) -> Element {
                                         class: if screenshot_shot_theme_active == ScreenshotShotTheme::Lagoon { "inline-flex items-center gap-2 rounded-full border border-white/35 bg-white/12 px-3 py-2 text-sm text-white" } else { "inline-flex items-center gap-2 rounded-full border border-white/15 bg-white/5 px-3 py-2 text-sm text-white/72 hover:text-white hover:border-white/25 transition-colors" },
                                         onclick: {
                                             let mut screenshot_shot_theme = screenshot_shot_theme.clone();
-                                            let mut app_settings = app_settings.clone();
                                             move |_| {
                                                 screenshot_shot_theme.set(ScreenshotShotTheme::Lagoon);
-                                                let mut settings = app_settings();
-                                                settings.lyrics_default_theme = "lagoon".to_string();
-                                                app_settings.set(settings.clone());
-                                                spawn(async move {
-                                                    let _ = crate::db::save_settings(settings).await;
+                                                settings_controller.update_settings(|settings| {
+                                                    settings.lyrics_default_theme = "lagoon".to_string();
                                                 });
                                             }
                                         },
@@ -2097,14 +2075,10 @@ fn LyricsPanel(props: LyricsPanelProps) -> Element {
                                         class: if screenshot_shot_theme_active == ScreenshotShotTheme::Ember { "inline-flex items-center gap-2 rounded-full border border-white/35 bg-white/12 px-3 py-2 text-sm text-white" } else { "inline-flex items-center gap-2 rounded-full border border-white/15 bg-white/5 px-3 py-2 text-sm text-white/72 hover:text-white hover:border-white/25 transition-colors" },
                                         onclick: {
                                             let mut screenshot_shot_theme = screenshot_shot_theme.clone();
-                                            let mut app_settings = app_settings.clone();
                                             move |_| {
                                                 screenshot_shot_theme.set(ScreenshotShotTheme::Ember);
-                                                let mut settings = app_settings();
-                                                settings.lyrics_default_theme = "ember".to_string();
-                                                app_settings.set(settings.clone());
-                                                spawn(async move {
-                                                    let _ = crate::db::save_settings(settings).await;
+                                                settings_controller.update_settings(|settings| {
+                                                    settings.lyrics_default_theme = "ember".to_string();
                                                 });
                                             }
                                         },
@@ -2115,14 +2089,10 @@ fn LyricsPanel(props: LyricsPanelProps) -> Element {
                                         class: if screenshot_shot_theme_active == ScreenshotShotTheme::Midnight { "inline-flex items-center gap-2 rounded-full border border-white/35 bg-white/12 px-3 py-2 text-sm text-white" } else { "inline-flex items-center gap-2 rounded-full border border-white/15 bg-white/5 px-3 py-2 text-sm text-white/72 hover:text-white hover:border-white/25 transition-colors" },
                                         onclick: {
                                             let mut screenshot_shot_theme = screenshot_shot_theme.clone();
-                                            let mut app_settings = app_settings.clone();
                                             move |_| {
                                                 screenshot_shot_theme.set(ScreenshotShotTheme::Midnight);
-                                                let mut settings = app_settings();
-                                                settings.lyrics_default_theme = "midnight".to_string();
-                                                app_settings.set(settings.clone());
-                                                spawn(async move {
-                                                    let _ = crate::db::save_settings(settings).await;
+                                                settings_controller.update_settings(|settings| {
+                                                    settings.lyrics_default_theme = "midnight".to_string();
                                                 });
                                             }
                                         },
@@ -2134,14 +2104,10 @@ fn LyricsPanel(props: LyricsPanelProps) -> Element {
                                             class: if screenshot_shot_theme_active == ScreenshotShotTheme::Cover { "inline-flex items-center gap-2 rounded-full border border-white/35 bg-white/12 px-3 py-2 text-sm text-white" } else { "inline-flex items-center gap-2 rounded-full border border-white/15 bg-white/5 px-3 py-2 text-sm text-white/72 hover:text-white hover:border-white/25 transition-colors" },
                                             onclick: {
                                                 let mut screenshot_shot_theme = screenshot_shot_theme.clone();
-                                                let mut app_settings = app_settings.clone();
                                                 move |_| {
                                                     screenshot_shot_theme.set(ScreenshotShotTheme::Cover);
-                                                    let mut settings = app_settings();
-                                                    settings.lyrics_default_theme = "cover".to_string();
-                                                    app_settings.set(settings.clone());
-                                                    spawn(async move {
-                                                        let _ = crate::db::save_settings(settings).await;
+                                                    settings_controller.update_settings(|settings| {
+                                                        settings.lyrics_default_theme = "cover".to_string();
                                                     });
                                                 }
                                             },
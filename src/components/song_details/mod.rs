@@ -2,18 +2,32 @@
 
 use crate::api::{
     fetch_lyrics_with_fallback, format_duration, normalize_lyrics_provider_order,
-    search_lyrics_candidates, LyricLine, LyricsQuery, LyricsResult, LyricsSearchCandidate,
-    NavidromeClient, ServerConfig, Song,
+    search_lyrics_candidates, LyricLine, LyricsFetchGuard, LyricsQuery, LyricsResult,
+    LyricsSearchCandidate, NavidromeClient, ServerConfig, Song, StreamProbe, UserAction,
+};
+use crate::components::views::artist_links::{
+    parse_artist_names, resolve_artist_id_for_name, CreditArtistName,
+};
+use crate::components::views::song_list_filter::{
+    filter_song_order, scroll_to_row, use_song_list_filter, HighlightedText, SongListFilterBar,
 };
-use crate::components::views::artist_links::{parse_artist_names, resolve_artist_id_for_name};
 use crate::components::{
-    apply_collection_shuffle_mode, generate_queue_extension_from_seed,
-    queue_should_generate_similar_on_end, seek_to, spawn_shuffle_queue, AddIntent,
-    AddMenuController, AppView, AudioState, Icon, Navigation, PlaybackPositionSignal,
-    SidebarOpenSignal, VolumeSignal,
+    apply_collection_shuffle_mode, generate_queue_extension_from_seed, play_song_from_beginning,
+    queue_should_generate_similar_on_end, refresh_skip_shuffle_weights, seek_to, song_start_offset,
+    spawn_shuffle_queue, stream_request_url, AddIntent, AddMenuController, AppView, AudioState,
+    Icon, ListenAlongController, ListenAlongControls, Navigation, PlaybackPositionSignal, SeekBar,
+    SeekRequestSignal, ServerCapabilitiesSignal, SettingsController, SidebarOpenSignal,
+    VolumeSignal, AB_LOOP_MIN_SECS,
+};
+use crate::db::{
+    reset_song_skip_stats, song_playback_offsets_key, song_skip_stats_for, AppSettings, RepeatMode,
+    SongPlaybackOffsets,
+};
+use crate::offline_audio::{
+    download_quality_for, prefetch_song_audio, set_download_quality_override, DownloadQuality,
 };
-use crate::db::{AppSettings, RepeatMode};
 use dioxus::prelude::*;
+use std::rc::Rc;
 
 // Tab/state/controller definitions shared by all song-details panels.
 include!("types.rs");
@@ -21,6 +35,12 @@ include!("types.rs");
 include!("overlay.rs");
 // Desktop/mobile details pane with metadata and transport controls.
 include!("details_panel.rs");
+// Credits section rendered inside the details pane.
+include!("credits_section.rs");
+// Skip counts rendered inside the details pane.
+include!("skip_stats_section.rs");
+// Stream diagnostics section rendered inside the details pane for the playing song.
+include!("stream_diagnostics_section.rs");
 // Up-next queue panel controls.
 include!("queue_panel.rs");
 // Related songs recommendation panel.
@@ -19,12 +19,12 @@ pub fn SongDetailsOverlay(controller: SongDetailsController) -> Element {
     let lrclib_upgrade_auto_retry_for_song = use_signal(|| None::<String>);
     let last_synced_lyrics_for_song = use_signal(|| None::<(String, LyricsResult)>);
     let last_song_key = use_signal(|| None::<String>);
+    let lyrics_fetches = use_hook(|| Rc::new(LyricsFetchGuard::default()));
+    let lrclib_upgrade_fetches = use_hook(|| Rc::new(LyricsFetchGuard::default()));
 
     let state = controller.current();
     let selected_song = state.song.clone();
-    let selected_song_key = selected_song
-        .as_ref()
-        .map(|song| format!("{}:{}", song.server_id, song.id));
+    let selected_song_key = selected_song.as_ref().map(song_lyrics_key);
 
     {
         let mut controller = controller.clone();
@@ -54,11 +54,12 @@ pub fn SongDetailsOverlay(controller: SongDetailsController) -> Element {
         let mut lyrics_auto_retry_for_song = lyrics_auto_retry_for_song.clone();
         let mut lrclib_upgrade_auto_retry_for_song = lrclib_upgrade_auto_retry_for_song.clone();
         let mut last_synced_lyrics_for_song = last_synced_lyrics_for_song.clone();
-        let selected_song_key = selected_song_key.clone();
+        let controller = controller.clone();
         let mut last_song_key = last_song_key.clone();
         use_effect(move || {
+            let selected_song_key = controller.current().song.as_ref().map(song_lyrics_key);
             if last_song_key() != selected_song_key {
-                last_song_key.set(selected_song_key.clone());
+                last_song_key.set(selected_song_key);
                 lyrics_auto_retry_for_song.set(None);
                 lrclib_upgrade_auto_retry_for_song.set(None);
                 last_synced_lyrics_for_song.set(None);
@@ -88,33 +89,32 @@ pub fn SongDetailsOverlay(controller: SongDetailsController) -> Element {
             let settings = app_settings();
             let query_override = lyrics_query_override();
             let _refresh_nonce = lyrics_refresh_nonce();
+            let lyrics_fetches = lyrics_fetches.clone();
             async move {
-                let Some(song) = song else {
-                    return Err("No song selected.".to_string());
-                };
+                let song = song?;
+                let song_key = song_lyrics_key(&song);
                 let query = query_override.unwrap_or_else(|| LyricsQuery::from_song(&song));
-                fetch_first_available_lyrics(
-                    query,
-                    settings.lyrics_provider_order.clone(),
-                    settings.lyrics_request_timeout_secs,
-                )
-                .await
+                let result = lyrics_fetches
+                    .run(
+                        &song_key,
+                        fetch_first_available_lyrics(
+                            query,
+                            settings.lyrics_provider_order.clone(),
+                            settings.lyrics_request_timeout_secs,
+                        ),
+                    )
+                    .await?;
+                Some((song_key, result))
             }
         })
     };
 
     {
-        let selected_song_key = selected_song_key.clone();
         let mut lyrics_auto_retry_for_song = lyrics_auto_retry_for_song.clone();
         let mut lyrics_resource = lyrics_resource.clone();
         let mut lyrics_refresh_nonce = lyrics_refresh_nonce.clone();
         use_effect(move || {
-            let latest_lyrics_result = lyrics_resource();
-            let Some(song_key) = selected_song_key.clone() else {
-                return;
-            };
-
-            let Some(Err(_)) = latest_lyrics_result else {
+            let Some(Some((song_key, Err(_)))) = lyrics_resource() else {
                 return;
             };
 
@@ -170,36 +170,33 @@ pub fn SongDetailsOverlay(controller: SongDetailsController) -> Element {
             let settings = app_settings();
             let query_override = lyrics_query_override();
             let _refresh_nonce = lyrics_refresh_nonce();
+            let lrclib_upgrade_fetches = lrclib_upgrade_fetches.clone();
             async move {
+                let song = song?;
+                let song_key = song_lyrics_key(&song);
                 if settings.lyrics_unsynced_mode {
-                    return Ok(None);
+                    return Some((song_key, Ok(None)));
                 }
-                let Some(song) = song else {
-                    return Ok(None);
-                };
                 let query = query_override.unwrap_or_else(|| LyricsQuery::from_song(&song));
-                match fetch_lyrics_with_fallback(
-                    &query,
-                    &["lrclib".to_string()],
-                    settings.lyrics_request_timeout_secs,
-                )
-                .await
-                {
-                    Ok(result) => Ok(Some(result)),
-                    Err(err) => Err(err),
-                }
+                let result = lrclib_upgrade_fetches
+                    .run(
+                        &song_key,
+                        fetch_lyrics_with_fallback(
+                            &query,
+                            &["lrclib".to_string()],
+                            settings.lyrics_request_timeout_secs,
+                        ),
+                    )
+                    .await?;
+                Some((song_key, result.map(Some)))
             }
         })
     };
     {
-        let selected_song_key = selected_song_key.clone();
         let mut last_synced_lyrics_for_song = last_synced_lyrics_for_song.clone();
         let lrclib_upgrade_resource = lrclib_upgrade_resource.clone();
         use_effect(move || {
-            let Some(song_key) = selected_song_key.clone() else {
-                return;
-            };
-            let Some(Ok(Some(upgrade))) = lrclib_upgrade_resource() else {
+            let Some(Some((song_key, Ok(Some(upgrade))))) = lrclib_upgrade_resource() else {
                 return;
             };
             if upgrade.synced_lines.is_empty() {
@@ -215,7 +212,6 @@ pub fn SongDetailsOverlay(controller: SongDetailsController) -> Element {
         });
     }
     {
-        let selected_song_key = selected_song_key.clone();
         let app_settings = app_settings.clone();
         let mut lrclib_upgrade_resource = lrclib_upgrade_resource.clone();
         let mut lrclib_upgrade_auto_retry_for_song = lrclib_upgrade_auto_retry_for_song.clone();
@@ -225,12 +221,7 @@ pub fn SongDetailsOverlay(controller: SongDetailsController) -> Element {
                 return;
             }
 
-            let latest_upgrade_result = lrclib_upgrade_resource();
-            let Some(song_key) = selected_song_key.clone() else {
-                return;
-            };
-
-            let Some(Err(_)) = latest_upgrade_result else {
+            let Some(Some((song_key, Err(_)))) = lrclib_upgrade_resource() else {
                 return;
             };
 
@@ -264,8 +255,8 @@ pub fn SongDetailsOverlay(controller: SongDetailsController) -> Element {
     });
     let selected_lyrics = pick_display_lyrics(
         sync_lyrics,
-        lyrics_resource(),
-        lrclib_upgrade_resource(),
+        lyrics_for_song(lyrics_resource(), selected_song_key.as_ref()),
+        lyrics_for_song(lrclib_upgrade_resource(), selected_song_key.as_ref()),
         cached_synced_lyrics,
     );
 
@@ -16,6 +16,7 @@ fn QueuePanel(props: QueuePanelProps) -> Element {
     let now_playing = use_context::<Signal<Option<Song>>>();
     let is_playing = use_context::<crate::components::IsPlayingSignal>().0;
     let controller = use_context::<SongDetailsController>();
+    let mut up_next_filter = use_song_list_filter();
 
     let on_create_queue = {
         let seed_song = props.seed_song.clone();
@@ -92,6 +93,16 @@ fn QueuePanel(props: QueuePanelProps) -> Element {
         };
     }
 
+    let up_next_songs: Vec<Song> = props.up_next.iter().map(|(_, song)| song.clone()).collect();
+    let filter_query = up_next_filter.query(&up_next_songs);
+    let all_positions: Vec<usize> = (0..up_next_songs.len()).collect();
+    let visible_entries: Vec<(usize, Song)> =
+        filter_song_order(&up_next_songs, &all_positions, &filter_query)
+            .into_iter()
+            .map(|position| props.up_next[position].clone())
+            .collect();
+    let first_match = visible_entries.first().cloned();
+
     rsx! {
         div { class: "h-full overflow-y-visible md:overflow-y-auto pr-1 space-y-2",
             div { class: "pb-1",
@@ -110,9 +121,44 @@ fn QueuePanel(props: QueuePanelProps) -> Element {
                     }
                 }
             }
-            for (index, entry) in props.up_next.iter() {
+            SongListFilterBar {
+                value: filter_query.clone(),
+                match_count: visible_entries.len(),
+                total: up_next_songs.len(),
+                placeholder: "Filter up next".to_string(),
+                on_input: {
+                    let up_next_songs = up_next_songs.clone();
+                    move |text: String| up_next_filter.set(text, &up_next_songs)
+                },
+                on_jump: {
+                    let first_match = first_match.clone();
+                    move |_| {
+                        if let Some((index, _)) = first_match.as_ref() {
+                            scroll_to_row(&format!("up-next-row-{index}"));
+                        }
+                    }
+                },
+                on_play_first: {
+                    let first_match = first_match.clone();
+                    let mut queue_index = queue_index.clone();
+                    let mut now_playing = now_playing.clone();
+                    let mut is_playing = is_playing.clone();
+                    let mut controller = controller.clone();
+                    move |_| {
+                        let Some((index, song)) = first_match.clone() else {
+                            return;
+                        };
+                        queue_index.set(index);
+                        now_playing.set(Some(song.clone()));
+                        is_playing.set(true);
+                        controller.open(song);
+                    }
+                },
+            }
+            for (index, entry) in visible_entries.iter() {
                 div {
                     key: "{entry.server_id}:{entry.id}:{index}",
+                    id: "up-next-row-{index}",
                     class: "w-full rounded-xl border border-zinc-800/80 transition-all",
                     div { class: "flex items-center gap-2 p-3",
                         button {
@@ -152,9 +198,17 @@ fn QueuePanel(props: QueuePanelProps) -> Element {
                                 }
                             }
                             div { class: "min-w-0 flex-1",
-                                p { class: "text-sm text-white truncate", "{entry.title}" }
+                                p { class: "text-sm text-white truncate",
+                                    HighlightedText {
+                                        text: entry.title.clone(),
+                                        query: filter_query.clone(),
+                                    }
+                                }
                                 p { class: "text-xs text-zinc-500 truncate",
-                                    "{entry.artist.clone().unwrap_or_default()}"
+                                    HighlightedText {
+                                        text: entry.artist.clone().unwrap_or_default(),
+                                        query: filter_query.clone(),
+                                    }
                                 }
                             }
                             span { class: "text-xs text-zinc-500 font-mono flex-shrink-0",
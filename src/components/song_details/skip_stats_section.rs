@@ -0,0 +1,52 @@
+// How often the song was skipped, from the local listening history, with a reset.
+
+#[component]
+fn SongSkipStatsSection(song: Song) -> Element {
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let mut resets = use_signal(|| 0u32);
+
+    let stats = {
+        let server_id = song.server_id.clone();
+        let song_id = song.id.clone();
+        use_resource(move || {
+            let _ = resets();
+            let server_id = server_id.clone();
+            let song_id = song_id.clone();
+            async move { song_skip_stats_for(&server_id, &song_id).await }
+        })
+    };
+
+    let Some(Some(stats)) = stats() else {
+        return rsx! {};
+    };
+    if stats.skips == 0 {
+        return rsx! {};
+    }
+
+    let on_reset = move |_| {
+        let server_id = stats.server_id.clone();
+        let song_id = stats.song_id.clone();
+        spawn(async move {
+            reset_song_skip_stats(&server_id, &song_id).await;
+            refresh_skip_shuffle_weights(&app_settings.peek());
+            resets += 1;
+        });
+    };
+
+    rsx! {
+        div { class: "space-y-1 pt-3 border-t border-zinc-800/70",
+            p { class: "text-[10px] uppercase tracking-[0.18em] text-zinc-500", "Skips" }
+            div { class: "flex flex-wrap items-center justify-center gap-2",
+                p { class: "text-xs text-zinc-400",
+                    "You've skipped this {stats.skips} of {stats.plays} times"
+                }
+                button {
+                    class: "text-xs text-zinc-500 hover:text-white transition-colors",
+                    title: "Forget how often this song was skipped",
+                    onclick: on_reset,
+                    "Reset"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+// Stream diagnostics: the transcoding parameters the player requests for the current track
+// and, on native builds, the headers the server answers with.
+
+/// Transcoding parameters of a Subsonic stream URL, with what the server assumes when absent.
+fn stream_request_params(url: &str) -> Vec<(&'static str, String)> {
+    let query = url
+        .split_once('?')
+        .map(|(_, query)| query)
+        .unwrap_or_default();
+    [
+        ("format", "original"),
+        ("maxBitRate", "no limit"),
+        ("timeOffset", "0"),
+    ]
+    .into_iter()
+    .map(|(name, fallback)| {
+        let value = query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+            .filter(|value| !value.is_empty())
+            .unwrap_or(fallback);
+        (name, value.to_string())
+    })
+    .collect()
+}
+
+/// Collapsible "Stream diagnostics" block for the playing song. Mount it keyed by song so the
+/// probe belongs to one track; the API client probes each track at most once per session.
+#[component]
+fn StreamDiagnosticsSection(song: Song) -> Element {
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let mut expanded = use_signal(|| false);
+
+    let request_url = stream_request_url(&song, &servers.peek(), &app_settings.peek());
+    let remote_url = request_url
+        .clone()
+        .filter(|url| !url.starts_with("file://"));
+    let track_key = song_lyrics_key(&song);
+
+    let probe: Resource<Option<Result<StreamProbe, String>>> = use_resource(move || {
+        let remote_url = remote_url.clone();
+        let track_key = track_key.clone();
+        async move {
+            if !expanded() {
+                return None;
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                let url = remote_url?;
+                Some(
+                    NavidromeClient::probe_stream_headers(&track_key, &url)
+                        .await
+                        .map_err(|error| error.to_string()),
+                )
+            }
+            #[cfg(target_arch = "wasm32")]
+            {
+                let _ = (remote_url, track_key);
+                None
+            }
+        }
+    });
+
+    let params = request_url
+        .as_deref()
+        .map(stream_request_params)
+        .unwrap_or_default();
+    let is_cached = request_url
+        .as_deref()
+        .is_some_and(|url| url.starts_with("file://"));
+
+    rsx! {
+        div { class: "space-y-2 pt-3 border-t border-zinc-800/70",
+            button {
+                class: "w-full flex items-center justify-between text-[10px] uppercase tracking-[0.18em] text-zinc-500 hover:text-zinc-300 transition-colors",
+                onclick: move |_| expanded.set(!expanded()),
+                span { "Stream diagnostics" }
+                Icon {
+                    name: if expanded() { "chevron-up".to_string() } else { "chevron-down".to_string() },
+                    class: "w-3.5 h-3.5".to_string(),
+                }
+            }
+            if expanded() {
+                if request_url.is_none() {
+                    p { class: "text-xs text-zinc-500", "No stream is requested for this song." }
+                } else if is_cached {
+                    p { class: "text-xs text-zinc-500",
+                        "Playing from the offline cache, so no stream is requested from the server."
+                    }
+                } else {
+                    div { class: "space-y-1",
+                        for (name , value) in params {
+                            div {
+                                key: "{name}",
+                                class: "flex items-baseline justify-between gap-3 text-xs",
+                                span { class: "font-mono text-zinc-500", "{name}" }
+                                span { class: "font-mono text-zinc-300 break-all text-right", "{value}" }
+                            }
+                        }
+                    }
+                    if cfg!(target_arch = "wasm32") {
+                        p { class: "text-xs text-zinc-500",
+                            "Response headers are only shown in the desktop and mobile apps."
+                        }
+                    } else {
+                        match probe() {
+                            Some(Some(Ok(probe))) => rsx! {
+                                div { class: "space-y-1 pt-2 border-t border-zinc-800/50",
+                                    div { class: "flex items-baseline justify-between gap-3 text-xs",
+                                        span { class: "font-mono text-zinc-500", "status" }
+                                        span { class: "font-mono text-zinc-300", "{probe.status}" }
+                                    }
+                                    for (name , value) in probe.headers {
+                                        div {
+                                            key: "{name}",
+                                            class: "flex items-baseline justify-between gap-3 text-xs",
+                                            span { class: "font-mono text-zinc-500", "{name}" }
+                                            span { class: "font-mono text-zinc-300 break-all text-right select-text",
+                                                "{value}"
+                                            }
+                                        }
+                                    }
+                                }
+                            },
+                            Some(Some(Err(error))) => rsx! {
+                                p { class: "text-xs text-red-300", "Probe failed: {error}" }
+                            },
+                            _ => rsx! {
+                                p { class: "text-xs text-zinc-500", "Probing stream headers…" }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
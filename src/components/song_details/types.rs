@@ -68,6 +68,11 @@ impl SongDetailsController {
     pub fn current(&self) -> SongDetailsState {
         (self.state)()
     }
+
+    /// Whether the overlay is open, without subscribing the caller to changes.
+    pub fn is_open_untracked(&self) -> bool {
+        self.state.peek().is_open
+    }
 }
 
 const DESKTOP_TABS: [SongDetailsTab; 3] = [
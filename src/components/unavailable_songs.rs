@@ -0,0 +1,121 @@
+//! Songs that are still listed in playlists, albums or the queue but can no longer be
+//! streamed, either because the server flags the entry missing or because loading it found
+//! the file gone. Rows draw them struck through and the queue plays past them.
+use crate::api::Song;
+use dioxus::prelude::*;
+use std::collections::HashSet;
+
+/// Tooltip on the title of an unavailable song.
+pub const UNAVAILABLE_SONG_TOOLTIP: &str = "This song is no longer available on its server";
+
+/// Songs found gone from their server this session, keyed by server and song id.
+#[derive(Clone, Copy, PartialEq)]
+pub struct UnavailableSongsSignal(pub Signal<HashSet<String>>);
+
+fn unavailable_key(song: &Song) -> String {
+    format!("{}:{}", song.server_id, song.id)
+}
+
+impl UnavailableSongsSignal {
+    /// Whether `song` can't be played. Subscribes the caller to later discoveries.
+    pub fn contains(&self, song: &Song) -> bool {
+        song.missing || self.0.read().contains(&unavailable_key(song))
+    }
+
+    pub fn peek_contains(&self, song: &Song) -> bool {
+        song.missing || self.0.peek().contains(&unavailable_key(song))
+    }
+
+    pub fn mark(mut self, song: &Song) {
+        if song.server_name == "Radio" || self.peek_contains(song) {
+            return;
+        }
+        self.0.write().insert(unavailable_key(song));
+    }
+}
+
+/// The first playable entry after `current`, and how many unavailable entries were passed
+/// over on the way, `current` included. Each entry is looked at once, so a queue with
+/// nothing playable left ends the search instead of going round again.
+pub fn next_available_index(
+    queue: &[Song],
+    current: usize,
+    is_unavailable: impl Fn(&Song) -> bool,
+) -> (Option<usize>, usize) {
+    let mut skipped = usize::from(queue.get(current).is_some_and(&is_unavailable));
+    for (index, song) in queue.iter().enumerate().skip(current.saturating_add(1)) {
+        if !is_unavailable(song) {
+            return (Some(index), skipped);
+        }
+        skipped += 1;
+    }
+    (None, skipped)
+}
+
+/// The notice after skipping `skipped` unavailable songs in a row.
+pub fn unavailable_skip_notice(skipped: usize, found_playable: bool) -> String {
+    let songs = if skipped == 1 {
+        "1 unavailable song skipped".to_string()
+    } else {
+        format!("{skipped} unavailable songs skipped")
+    };
+    if found_playable {
+        songs
+    } else {
+        format!("{songs}; nothing playable is left in the queue")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue(flags: &[bool]) -> Vec<Song> {
+        flags
+            .iter()
+            .enumerate()
+            .map(|(index, missing)| Song {
+                id: index.to_string(),
+                missing: *missing,
+                ..Song::default()
+            })
+            .collect()
+    }
+
+    fn missing(song: &Song) -> bool {
+        song.missing
+    }
+
+    #[test]
+    fn skips_a_run_of_unavailable_songs() {
+        let songs = queue(&[false, true, true, true, false]);
+        assert_eq!(next_available_index(&songs, 1, missing), (Some(4), 3));
+        assert_eq!(
+            next_available_index(&queue(&[true, false]), 0, missing),
+            (Some(1), 1)
+        );
+        assert_eq!(next_available_index(&songs, 3, missing), (Some(4), 1));
+    }
+
+    #[test]
+    fn stops_when_nothing_playable_is_left() {
+        let songs = queue(&[false, true, true]);
+        assert_eq!(next_available_index(&songs, 1, missing), (None, 2));
+        let all_gone = queue(&[true, true, true, true]);
+        assert_eq!(next_available_index(&all_gone, 0, missing), (None, 4));
+        assert_eq!(next_available_index(&all_gone, 9, missing), (None, 0));
+        assert_eq!(next_available_index(&[], 0, missing), (None, 0));
+    }
+
+    #[test]
+    fn notice_summarizes_the_run() {
+        assert_eq!(
+            unavailable_skip_notice(1, true),
+            "1 unavailable song skipped"
+        );
+        assert_eq!(
+            unavailable_skip_notice(2, false),
+            "2 unavailable songs skipped; nothing playable is left in the queue"
+        );
+    }
+}
@@ -4,11 +4,18 @@ use crate::components::audio_manager::{
 };
 use crate::components::views::album_song_row::AlbumSongRow;
 use crate::components::views::artist_links::ArtistNameLinks;
-use crate::components::{AddIntent, AddMenuController, AppView, Icon, Navigation};
-use crate::db::AppSettings;
+use crate::components::views::container_resume::{resume_index, ContainerResumeButton};
+use crate::components::views::tracklist_export::TracklistExportDialog;
+use crate::components::{sized_cover_art_url, ArtworkKind};
+use crate::components::{
+    AddIntent, AddMenuController, AppView, Icon, Navigation, PlaybackPositionSignal,
+    SeekRequestSignal, ServerCapabilitiesSignal, SettingsController,
+};
+use crate::continuous_albums::looks_continuous;
+use crate::db::{AppSettings, ContainerResumePoint, ResumeContainerKind};
 use crate::offline_audio::{
-    download_songs_batch, is_album_downloaded, is_song_downloaded, mark_collection_downloaded,
-    sync_downloaded_collection_members,
+    download_songs_batch, is_album_downloaded, is_song_downloaded, list_downloaded_entries,
+    mark_collection_downloaded, sync_downloaded_collection_members,
 };
 use dioxus::prelude::*;
 
@@ -26,6 +33,41 @@ fn anchored_menu_style(
     )
 }
 
+/// Album assembled from its downloaded tracks, for offline mode when nothing is cached.
+fn downloaded_album(server: &ServerConfig, album_id: &str) -> Option<(Album, Vec<Song>)> {
+    let songs: Vec<Song> = list_downloaded_entries()
+        .into_iter()
+        .filter(|entry| entry.server_id == server.id && entry.album_id.as_deref() == Some(album_id))
+        .map(|entry| Song {
+            id: entry.song_id,
+            title: entry.title,
+            album: entry.album,
+            album_id: entry.album_id,
+            artist: entry.artist,
+            artist_id: entry.artist_id,
+            cover_art: entry.cover_art_id.or_else(|| Some(album_id.to_string())),
+            server_id: server.id.clone(),
+            server_name: server.name.clone(),
+            ..Song::default()
+        })
+        .collect();
+    let first = songs.first()?;
+    let album = Album {
+        id: album_id.to_string(),
+        name: first
+            .album
+            .clone()
+            .unwrap_or_else(|| "Unknown Album".to_string()),
+        artist: first.artist.clone().unwrap_or_default(),
+        artist_id: first.artist_id.clone(),
+        cover_art: first.cover_art.clone(),
+        song_count: songs.len() as u32,
+        server_id: server.id.clone(),
+        ..Album::default()
+    };
+    Some((album, songs))
+}
+
 #[component]
 pub fn AlbumDetailView(album_id: String, server_id: String) -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
@@ -35,12 +77,17 @@ pub fn AlbumDetailView(album_id: String, server_id: String) -> Element {
     let mut queue_index = use_context::<Signal<usize>>();
     let mut is_playing = use_context::<crate::components::IsPlayingSignal>().0;
     let shuffle_enabled = use_context::<crate::components::ShuffleEnabledSignal>().0;
+    let mut playback_position = use_context::<PlaybackPositionSignal>().0;
+    let mut seek_request = use_context::<SeekRequestSignal>().0;
     let add_menu = use_context::<AddMenuController>();
     let app_settings = use_context::<Signal<AppSettings>>();
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
+    let settings_controller = use_context::<SettingsController>();
     let download_busy = use_signal(|| false);
     let download_status = use_signal(|| None::<String>);
     let mut album_rating = use_signal(|| 0u32);
     let mut show_album_menu = use_signal(|| false);
+    let mut show_tracklist_export = use_signal(|| false);
     let mut album_menu_x = use_signal(|| 0f64);
     let mut album_menu_y = use_signal(|| 0f64);
     let mut current_album_id = use_signal(|| album_id.clone());
@@ -68,7 +115,12 @@ pub fn AlbumDetailView(album_id: String, server_id: String) -> Element {
         async move {
             if let Some(server) = server {
                 let client = NavidromeClient::new(server);
-                client.get_album(&album_id).await.ok()
+                match client.get_album(&album_id).await {
+                    Ok(data) => Some(data),
+                    // Offline mode blocks the request; fall back to the downloaded tracks.
+                    Err(ApiError::Offline) => downloaded_album(&client.server, &album_id),
+                    Err(_) => None,
+                }
             } else {
                 None
             }
@@ -124,6 +176,44 @@ pub fn AlbumDetailView(album_id: String, server_id: String) -> Element {
         }
     };
 
+    let on_resume = {
+        let album_data_ref = album_data.clone();
+        let app_settings = app_settings.clone();
+        let mut download_status = download_status.clone();
+        move |point: ContainerResumePoint| {
+            let Some(Some((_, songs))) = album_data_ref() else {
+                return;
+            };
+            let playable = if app_settings().offline_mode {
+                songs
+                    .iter()
+                    .filter(|song| is_song_downloaded(song))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            } else {
+                songs.clone()
+            };
+            let playable = assign_collection_queue_meta(
+                playable,
+                QueueSourceKind::Album,
+                point.source_id.clone(),
+            );
+            let Some(index) = resume_index(&playable, &point) else {
+                download_status.set(Some(
+                    "The resume track isn't available for offline playback.".to_string(),
+                ));
+                return;
+            };
+            let song = playable[index].clone();
+            seek_request.set(Some((song.id.clone(), point.position_secs)));
+            playback_position.set(point.position_secs);
+            queue.set(playable);
+            queue_index.set(index);
+            now_playing.set(Some(song));
+            is_playing.set(true);
+        }
+    };
+
     let on_open_album_menu = {
         let album_data_ref = album_data.clone();
         let mut add_menu = add_menu.clone();
@@ -245,6 +335,18 @@ pub fn AlbumDetailView(album_id: String, server_id: String) -> Element {
         }
     };
 
+    // `None` hands the choice back to the guess from the album's title and genre.
+    let make_on_set_continuous = move |continuous: Option<bool>| {
+        move |evt: MouseEvent| {
+            evt.stop_propagation();
+            if let Some(Some((album, _))) = album_data() {
+                settings_controller.update_settings(|settings| {
+                    settings.set_continuous_album_override(&album.server_id, &album.id, continuous);
+                });
+            }
+        }
+    };
+
     let on_view_artist_from_menu = {
         let navigation = navigation.clone();
         let mut show_album_menu = show_album_menu.clone();
@@ -329,13 +431,32 @@ pub fn AlbumDetailView(album_id: String, server_id: String) -> Element {
                                 .find(|s| s.id == album.server_id)
                                 .and_then(|server| cover_art_id.as_ref().map(|cover_art_id| {
                                     let client = NavidromeClient::new(server.clone());
-                                    client.get_cover_art_url(cover_art_id, 500)
+                                    sized_cover_art_url(&client, cover_art_id, ArtworkKind::Album, 256)
                                 }));
                             let downloaded_song_count =
                                 songs.iter().filter(|song| is_song_downloaded(song)).count();
                             let album_downloaded = is_album_downloaded(&album.server_id, &album.id);
                             let album_fully_downloaded =
                                 !songs.is_empty() && downloaded_song_count >= songs.len();
+                            let can_download =
+                                capabilities.user_can(&album.server_id, UserAction::Download);
+                            let can_rate =
+                                capabilities.user_can(&album.server_id, UserAction::Rate);
+                            let continuous_override = app_settings
+                                .read()
+                                .continuous_album_override(&album.server_id, &album.id);
+                            let continuous_choices = [
+                                (
+                                    if looks_continuous(&album.name, album.genre.as_deref()) {
+                                        "Auto (on)"
+                                    } else {
+                                        "Auto (off)"
+                                    },
+                                    None,
+                                ),
+                                ("On", Some(true)),
+                                ("Off", Some(false)),
+                            ];
                             rsx! {
                                 div { class: "flex flex-col md:flex-row gap-8 mb-8 overflow-x-hidden items-center md:items-end",
                                     div { class: "w-64 h-64 rounded-2xl bg-zinc-800 overflow-hidden shadow-2xl flex-shrink-0 mx-auto md:mx-0",
@@ -403,16 +524,18 @@ pub fn AlbumDetailView(album_id: String, server_id: String) -> Element {
                                                 span { class: "hidden md:inline", "Play" }
                                             }
                                             button {
-                                                class: if download_busy() {
+                                                class: if download_busy() || !can_download {
                                                     "col-span-1 p-3 rounded-full border border-zinc-700 text-zinc-500 cursor-not-allowed flex items-center justify-center"
                                                 } else if album_fully_downloaded {
                                                     "col-span-1 p-3 rounded-full bg-emerald-500 text-white hover:bg-emerald-400 transition-colors flex items-center justify-center"
                                                 } else {
                                                     "col-span-1 p-3 rounded-full border border-emerald-500/60 text-emerald-300 hover:text-white hover:border-emerald-400 transition-colors flex items-center justify-center"
                                                 },
-                                                disabled: download_busy(),
+                                                disabled: download_busy() || !can_download,
                                                 onclick: on_download_album,
-                                                title: if download_busy() {
+                                                title: if !can_download {
+                                                    capabilities.denied_hint(UserAction::Download)
+                                                } else if download_busy() {
                                                     "Downloading album"
                                                 } else if album_fully_downloaded {
                                                     "Album fully downloaded"
@@ -459,6 +582,11 @@ pub fn AlbumDetailView(album_id: String, server_id: String) -> Element {
                                                 title: "More album actions",
                                                 Icon { name: "more-horizontal".to_string(), class: "w-5 h-5".to_string() }
                                             }
+                                            ContainerResumeButton {
+                                                kind: ResumeContainerKind::Album,
+                                                source_id: format!("{}::{}", current_server_id(), current_album_id()),
+                                                on_resume,
+                                            }
                                         }
                                         if show_album_menu() {
                                             div {
@@ -497,6 +625,34 @@ pub fn AlbumDetailView(album_id: String, server_id: String) -> Element {
                                                     }
                                                     "Add to..."
                                                 }
+                                                button {
+                                                    class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                                                    onclick: move |evt: MouseEvent| {
+                                                        evt.stop_propagation();
+                                                        show_album_menu.set(false);
+                                                        show_tracklist_export.set(true);
+                                                    },
+                                                    Icon {
+                                                        name: "queue".to_string(),
+                                                        class: "w-4 h-4".to_string(),
+                                                    }
+                                                    "Export tracklist"
+                                                }
+                                                div {
+                                                    class: "px-2.5 pt-1 text-[11px] uppercase tracking-wide text-zinc-500",
+                                                    title: "Plays the album's tracks into each other without crossfading, as for live recordings and DJ mixes",
+                                                    "Continuous playback"
+                                                }
+                                                div { class: "flex items-center gap-1 px-2 pb-1",
+                                                    for (label , choice) in continuous_choices {
+                                                        button {
+                                                            key: "{label}",
+                                                            class: if continuous_override == choice { "px-2 py-1 rounded-md text-xs bg-emerald-500/20 text-emerald-300" } else { "px-2 py-1 rounded-md text-xs text-zinc-400 hover:text-white hover:bg-zinc-800/80 transition-colors" },
+                                                            onclick: make_on_set_continuous(choice),
+                                                            "{label}"
+                                                        }
+                                                    }
+                                                }
                                                 div { class: "px-2.5 pt-1 text-[11px] uppercase tracking-wide text-zinc-500",
                                                     "Rating"
                                                 }
@@ -504,6 +660,7 @@ pub fn AlbumDetailView(album_id: String, server_id: String) -> Element {
                                                     for i in 1u32..=5u32 {
                                                         button {
                                                             class: "p-1 rounded text-amber-400 hover:text-amber-300 transition-colors",
+                                                            disabled: !can_rate,
                                                             onclick: make_on_set_album_rating(i),
                                                             Icon {
                                                                 name: if i <= album_rating() { "star-filled".to_string() } else { "star".to_string() },
@@ -517,6 +674,13 @@ pub fn AlbumDetailView(album_id: String, server_id: String) -> Element {
                                         if let Some(status) = download_status() {
                                             p { class: "text-xs text-zinc-500 mt-2", "{status}" }
                                         }
+                                        if show_tracklist_export() {
+                                            TracklistExportDialog {
+                                                title: album.name.clone(),
+                                                songs: songs.clone(),
+                                                on_close: move |_| show_tracklist_export.set(false),
+                                            }
+                                        }
                                     }
                                 }
 
@@ -2,7 +2,11 @@ use crate::api::*;
 use crate::components::views::artist_links::{
     parse_artist_names, resolve_artist_id_for_name, ArtistNameLinks,
 };
-use crate::components::{AddIntent, AddMenuController, AppView, Icon, Navigation};
+use crate::components::{sized_cover_art_url, ArtworkKind};
+use crate::components::{
+    AddIntent, AddMenuController, AddTarget, AppView, Icon, Navigation, QueueDragController,
+    ServerCapabilitiesSignal, UnavailableSongsSignal, UNAVAILABLE_SONG_TOOLTIP,
+};
 use crate::db::AppSettings;
 use crate::offline_audio::{is_song_downloaded, prefetch_song_audio};
 use dioxus::prelude::*;
@@ -27,12 +31,16 @@ pub fn AlbumSongRow(song: Song, index: usize, onclick: EventHandler<MouseEvent>)
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
     let navigation = use_context::<Navigation>();
     let add_menu = use_context::<AddMenuController>();
+    let queue_drag = use_context::<QueueDragController>();
     let queue = use_context::<Signal<Vec<Song>>>();
     let now_playing = use_context::<Signal<Option<Song>>>();
     let app_settings = use_context::<Signal<AppSettings>>();
     let current_rating = use_signal(|| song.user_rating.unwrap_or(0).min(5));
     let is_favorited = use_signal(|| song.starred.is_some());
     let download_busy = use_signal(|| false);
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
+    let can_download = capabilities.user_can(&song.server_id, UserAction::Download);
+    let can_rate = capabilities.user_can(&song.server_id, UserAction::Rate);
     let mut show_mobile_actions = use_signal(|| false);
     let mut menu_x = use_signal(|| 0f64);
     let mut menu_y = use_signal(|| 0f64);
@@ -42,6 +50,7 @@ pub fn AlbumSongRow(song: Song, index: usize, onclick: EventHandler<MouseEvent>)
         .as_ref()
         .map(|current| current.id == song.id)
         .unwrap_or(false);
+    let unavailable = use_context::<UnavailableSongsSignal>().contains(&song);
 
     let cover_url = servers()
         .iter()
@@ -50,7 +59,7 @@ pub fn AlbumSongRow(song: Song, index: usize, onclick: EventHandler<MouseEvent>)
             let client = NavidromeClient::new(server.clone());
             song.cover_art
                 .as_ref()
-                .map(|ca| client.get_cover_art_url(ca, 80))
+                .map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Song, 48))
         });
 
     let album_id = song.album_id.clone();
@@ -280,6 +289,9 @@ pub fn AlbumSongRow(song: Song, index: usize, onclick: EventHandler<MouseEvent>)
     rsx! {
         div {
             class: if is_current { "relative grid w-full grid-cols-[1.75rem_2.5rem_minmax(0,1fr)_4.5rem] items-center gap-3 p-3 rounded-xl bg-emerald-500/5 transition-colors group cursor-pointer" } else { "relative grid w-full grid-cols-[1.75rem_2.5rem_minmax(0,1fr)_4.5rem] items-center gap-3 p-3 rounded-xl hover:bg-zinc-800/50 transition-colors group cursor-pointer" },
+            draggable: "true",
+            ondragstart: queue_drag.on_drag_start(AddTarget::Song(song.clone())),
+            ondragend: move |_| queue_drag.end(),
             onclick: move |e| {
                 show_mobile_actions.set(false);
                 onclick.call(e);
@@ -338,7 +350,9 @@ pub fn AlbumSongRow(song: Song, index: usize, onclick: EventHandler<MouseEvent>)
             }
             // Song info
             div { class: "min-w-0 flex flex-col items-center text-center md:items-start md:text-left",
-                p { class: if is_current { "max-w-full truncate text-sm font-medium text-emerald-400 transition-colors" } else { "max-w-full truncate text-sm font-medium text-white group-hover:text-emerald-400 transition-colors" },
+                p {
+                    class: if unavailable { "max-w-full truncate text-sm font-medium text-zinc-500 line-through" } else if is_current { "max-w-full truncate text-sm font-medium text-emerald-400 transition-colors" } else { "max-w-full truncate text-sm font-medium text-white group-hover:text-emerald-400 transition-colors" },
+                    title: if unavailable { UNAVAILABLE_SONG_TOOLTIP } else { "" },
                     "{song.title}"
                 }
                 div { class: "mt-1 inline-flex max-w-full items-center justify-center gap-1 text-xs text-zinc-400 md:justify-start",
@@ -362,6 +376,7 @@ pub fn AlbumSongRow(song: Song, index: usize, onclick: EventHandler<MouseEvent>)
                 button {
                     class: if is_favorited() { "p-1.5 rounded-lg text-emerald-400 hover:text-emerald-300 hover:bg-emerald-500/10 transition-colors" } else { "p-1.5 rounded-lg text-zinc-500 hover:text-emerald-400 hover:bg-emerald-500/10 transition-colors" },
                     aria_label: if is_favorited() { "Unfavorite" } else { "Favorite" },
+                    disabled: !can_rate,
                     onclick: make_on_toggle_favorite(),
                     Icon {
                         name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
@@ -443,8 +458,9 @@ pub fn AlbumSongRow(song: Song, index: usize, onclick: EventHandler<MouseEvent>)
                             }
                         } else {
                             button {
-                                class: if download_busy() { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-500 cursor-not-allowed" } else { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors" },
-                                disabled: download_busy(),
+                                class: if download_busy() || !can_download { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-500 cursor-not-allowed" } else { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors" },
+                                disabled: download_busy() || !can_download,
+                                title: if can_download { "" } else { capabilities.denied_hint(UserAction::Download) },
                                 onclick: make_on_download_song(),
                                 Icon {
                                     name: if download_busy() { "loader".to_string() } else { "download".to_string() },
@@ -459,6 +475,7 @@ pub fn AlbumSongRow(song: Song, index: usize, onclick: EventHandler<MouseEvent>)
                         }
                         button {
                             class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                            disabled: !can_rate,
                             onclick: make_on_toggle_favorite(),
                             Icon {
                                 name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
@@ -477,6 +494,7 @@ pub fn AlbumSongRow(song: Song, index: usize, onclick: EventHandler<MouseEvent>)
                             for i in 1..=5 {
                                 button {
                                     class: "p-1 rounded text-amber-400 hover:text-amber-300 transition-colors",
+                                    disabled: !can_rate,
                                     onclick: make_on_set_rating(i as u32),
                                     Icon {
                                         name: if i <= current_rating() { "star-filled".to_string() } else { "star".to_string() },
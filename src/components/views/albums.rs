@@ -1,6 +1,13 @@
+use super::empty_state::{EmptyState, NoServerEmptyState};
 use crate::api::*;
 use crate::components::views::home::AlbumCard;
-use crate::components::{AppView, Icon, Navigation};
+use crate::components::views::music_folder_filter::{
+    use_music_folder_selection, MusicFolderFilter,
+};
+use crate::components::{
+    use_restored_scroll, use_view_state, AppView, Icon, Navigation, SettingsController,
+};
+use crate::db::AppSettings;
 use dioxus::prelude::*;
 
 #[component]
@@ -21,17 +28,20 @@ pub fn AlbumsWithGenre(genre: String) -> Element {
 pub fn AlbumsView(genre: Option<String>) -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
     let navigation = use_context::<Navigation>();
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let settings_controller = use_context::<SettingsController>();
 
-    let mut album_type = use_signal(|| {
+    let mut album_type = use_view_state("album_type", || {
         if genre.is_some() {
             "alphabeticalByName".to_string()
         } else {
             "recent".to_string()
         }
     });
-    let mut search_query = use_signal(String::new);
-    let limit = use_signal(|| 30u32);
+    let mut search_query = use_view_state("search_query", String::new);
+    let limit = use_view_state("limit", || 30u32);
     let mut fallback_applied = use_signal(|| false);
+    let folder_selection = use_music_folder_selection();
 
     let genre_for_title = genre.clone();
     let albums = use_resource(move || {
@@ -39,15 +49,18 @@ pub fn AlbumsView(genre: Option<String>) -> Element {
         let album_type = album_type();
         let limit = limit();
         let query = search_query();
+        let folder_selection = folder_selection();
         let genre_filter = genre.clone();
         async move {
             let mut albums = Vec::new();
             let mut more_available = false;
             if query.trim().is_empty() {
                 for server in servers.into_iter().filter(|s| s.active) {
+                    let folder_id = folder_selection.get(&server.id).cloned();
                     let client = NavidromeClient::new(server);
-                    if let Ok(mut server_albums) =
-                        client.get_albums(&album_type, limit + 1, 0).await
+                    if let Ok(mut server_albums) = client
+                        .get_albums(&album_type, limit + 1, 0, folder_id.as_deref())
+                        .await
                     {
                         if server_albums.len() as u32 > limit {
                             more_available = true;
@@ -58,8 +71,12 @@ pub fn AlbumsView(genre: Option<String>) -> Element {
                 }
             } else {
                 for server in servers.into_iter().filter(|s| s.active) {
+                    let folder_id = folder_selection.get(&server.id).cloned();
                     let client = NavidromeClient::new(server);
-                    if let Ok(results) = client.search(&query, 0, limit + 1, 0).await {
+                    if let Ok(results) = client
+                        .search(&query, 0, limit + 1, 0, folder_id.as_deref())
+                        .await
+                    {
                         if results.albums.len() as u32 > limit {
                             more_available = true;
                         }
@@ -79,6 +96,8 @@ pub fn AlbumsView(genre: Option<String>) -> Element {
         }
     });
 
+    use_restored_scroll(move || albums.read().is_some());
+
     let album_types = vec![
         ("recent", "Recently Played"),
         ("alphabeticalByName", "A-Z"),
@@ -87,6 +106,18 @@ pub fn AlbumsView(genre: Option<String>) -> Element {
         ("random", "Random"),
     ];
 
+    let has_active_servers = servers().iter().any(|server| server.active);
+    let list_view = app_settings().albums_list_view;
+    let grid_density = app_settings().grid_density;
+    let albums_class = if list_view {
+        "rs-album-list flex flex-col gap-1".to_string()
+    } else {
+        let columns = grid_density.columns_class().unwrap_or(
+            "grid-cols-2 sm:grid-cols-3 md:grid-cols-4 lg:grid-cols-5 xl:grid-cols-6 2xl:grid-cols-8",
+        );
+        format!("rs-album-grid grid {columns} gap-4 overflow-x-hidden")
+    };
+
     rsx! {
         div { class: "space-y-8",
             header { class: "page-header gap-4",
@@ -100,7 +131,7 @@ pub fn AlbumsView(genre: Option<String>) -> Element {
 
                 div { class: "flex flex-col gap-3 md:flex-row md:items-center md:justify-between",
                     // Filter tabs
-                    div { class: "flex gap-2 flex-wrap",
+                    div { class: "flex gap-2 flex-wrap items-center",
                         for (value , label) in album_types {
                             button {
                                 class: if album_type() == value { "px-4 py-2 rounded-full bg-emerald-500/20 text-emerald-400 text-sm font-medium" } else { "px-4 py-2 rounded-full bg-zinc-800/50 text-zinc-400 hover:text-white text-sm font-medium transition-colors" },
@@ -108,6 +139,35 @@ pub fn AlbumsView(genre: Option<String>) -> Element {
                                 "{label}"
                             }
                         }
+                        MusicFolderFilter {}
+                        div { class: "flex items-center gap-1 p-1 rounded-full bg-zinc-800/50",
+                            button {
+                                class: if list_view { "p-1.5 rounded-full text-zinc-400 hover:text-white transition-colors" } else { "p-1.5 rounded-full bg-emerald-500/20 text-emerald-400" },
+                                aria_label: "Show albums as a grid",
+                                title: "Grid",
+                                onclick: move |_| {
+                                    settings_controller
+                                        .update_settings(|settings| settings.albums_list_view = false)
+                                },
+                                Icon {
+                                    name: "grid".to_string(),
+                                    class: "w-4 h-4".to_string(),
+                                }
+                            }
+                            button {
+                                class: if list_view { "p-1.5 rounded-full bg-emerald-500/20 text-emerald-400" } else { "p-1.5 rounded-full text-zinc-400 hover:text-white transition-colors" },
+                                aria_label: "Show albums as a list",
+                                title: "List",
+                                onclick: move |_| {
+                                    settings_controller
+                                        .update_settings(|settings| settings.albums_list_view = true)
+                                },
+                                Icon {
+                                    name: "queue".to_string(),
+                                    class: "w-4 h-4".to_string(),
+                                }
+                            }
+                        }
                     }
                     // Search
                     div { class: "relative w-full md:max-w-xs",
@@ -146,22 +206,30 @@ pub fn AlbumsView(genre: Option<String>) -> Element {
                         }
                         rsx! {
                             if albums.is_empty() {
-                                div { class: "flex flex-col items-center justify-center py-20",
-                                    Icon {
-                                        name: "album".to_string(),
-                                        class: "w-16 h-16 text-zinc-600 mb-4".to_string(),
+                                if !has_active_servers {
+                                    NoServerEmptyState {
+                                        icon: "album".to_string(),
+                                        message: "Connect a Navidrome server to browse albums.".to_string(),
+                                    }
+                                } else if has_query {
+                                    EmptyState {
+                                        icon: "album".to_string(),
+                                        title: format!("No albums match \"{raw_query}\""),
                                     }
-                                    if has_query {
-                                        p { class: "text-zinc-300", "No albums match \"{raw_query}\"" }
-                                    } else {
-                                        p { class: "text-zinc-400", "No albums found" }
+                                } else {
+                                    EmptyState {
+                                        icon: "album".to_string(),
+                                        title: "No albums found".to_string(),
+                                        message: "Albums show up here once your server has scanned your music library.".to_string(),
                                     }
                                 }
                             } else {
-                                div { class: "rs-album-grid grid grid-cols-2 sm:grid-cols-3 md:grid-cols-4 lg:grid-cols-5 xl:grid-cols-6 2xl:grid-cols-8 gap-4 overflow-x-hidden",
+                                div { class: "{albums_class}",
                                     for album in albums {
                                         AlbumCard {
                                             album: album.clone(),
+                                            art_px: if list_view { 48 } else { grid_density.tile_px() },
+                                            as_row: list_view,
                                             onclick: {
                                                 let navigation = navigation.clone();
                                                 let album_id = album.id.clone();
@@ -3,7 +3,9 @@ use crate::components::audio_manager::{
     apply_collection_shuffle_mode, assign_collection_queue_meta, normalize_manual_queue_songs,
 };
 use crate::components::views::home::{AlbumCard, SongRow};
-use crate::components::{AppView, Icon, Navigation};
+use crate::components::{sized_cover_art_url, ArtworkKind};
+use crate::components::{AppView, Icon, Navigation, ServerCapabilitiesSignal, SettingsController};
+use crate::db::AppSettings;
 use dioxus::prelude::*;
 
 const ARTIST_ALBUM_BATCH_SIZE: usize = 24;
@@ -33,6 +35,7 @@ fn render_album_item(album: Album, navigation: Navigation) -> Element {
 #[component]
 pub fn ArtistDetailView(artist_id: String, server_id: String) -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
     let navigation = use_context::<Navigation>();
     let mut queue = use_context::<Signal<Vec<Song>>>();
     let mut queue_index = use_context::<Signal<usize>>();
@@ -42,6 +45,11 @@ pub fn ArtistDetailView(artist_id: String, server_id: String) -> Element {
     let mut visible_album_count = use_signal(|| ARTIST_ALBUM_BATCH_SIZE);
     let mut current_artist_id = use_signal(|| artist_id.clone());
     let mut current_server_id = use_signal(|| server_id.clone());
+    let can_rate = capabilities.user_can(&current_server_id(), UserAction::Rate);
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let settings_controller = use_context::<SettingsController>();
+    let mut discography_busy = use_signal(|| false);
+    let mut discography_result = use_signal(|| None::<Result<MusicBrainzDiscography, String>>);
 
     use_effect({
         let artist_id = artist_id.clone();
@@ -55,6 +63,7 @@ pub fn ArtistDetailView(artist_id: String, server_id: String) -> Element {
                 );
                 current_artist_id.set(artist_id.clone());
                 visible_album_count.set(ARTIST_ALBUM_BATCH_SIZE);
+                discography_result.set(None);
             }
             if current_server_id() != server_id {
                 eprintln!(
@@ -114,7 +123,10 @@ pub fn ArtistDetailView(artist_id: String, server_id: String) -> Element {
         let artist_data = artist_data.clone();
         move || {
             let server_id = current_server_id();
-            let server = servers().into_iter().find(|s| s.id == server_id);
+            let server = servers()
+                .into_iter()
+                .find(|s| s.id == server_id)
+                .filter(|s| capabilities.supports(&s.id, |c| c.top_songs));
             let artist_name = artist_data()
                 .and_then(|value| value.map(|(artist, _)| artist.name.clone()))
                 .filter(|name| !name.is_empty());
@@ -208,6 +220,48 @@ pub fn ArtistDetailView(artist_id: String, server_id: String) -> Element {
         }
     };
 
+    let on_pin_station = move |_| {
+        let Some(Some((artist, _))) = artist_data() else {
+            return;
+        };
+        let server_id = if artist.server_id.is_empty() {
+            current_server_id()
+        } else {
+            artist.server_id.clone()
+        };
+        let already_pinned = app_settings()
+            .artist_stations
+            .iter()
+            .any(|station| station.server_id == server_id && station.artist_id == artist.id);
+        if already_pinned {
+            navigation.navigate_to(AppView::RadioView {});
+            return;
+        }
+        settings_controller.update_settings(|settings| {
+            settings.pin_artist_station(
+                &server_id,
+                &artist.id,
+                &artist.name,
+                artist.cover_art.clone(),
+            );
+        });
+    };
+
+    let on_check_discography = move |_| {
+        if discography_busy() {
+            return;
+        }
+        let Some(Some((artist, _))) = artist_data() else {
+            return;
+        };
+        discography_busy.set(true);
+        spawn(async move {
+            let result = fetch_musicbrainz_discography(&artist.name).await;
+            discography_result.set(Some(result));
+            discography_busy.set(false);
+        });
+    };
+
     rsx! {
         button {
             class: "inline-flex items-center justify-center text-zinc-400 hover:text-white transition-colors mb-4 rounded-md p-1 -ml-1",
@@ -251,9 +305,14 @@ pub fn ArtistDetailView(artist_id: String, server_id: String) -> Element {
                             artist
                                 .cover_art
                                 .as_ref()
-                                .map(|ca| client.get_cover_art_url(ca, 500))
+                                .map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Album, 256))
                         });
 
+                        let station_pinned = app_settings().artist_stations.iter().any(|station| {
+                            station.artist_id == artist.id
+                                && (station.server_id == artist.server_id
+                                    || station.server_id == requested_server_id)
+                        });
                         let total_albums = albums.len();
                         let total_songs: u32 = albums.iter().map(|a| a.song_count).sum();
                         let current_album_limit = visible_album_count().min(total_albums);
@@ -301,12 +360,41 @@ pub fn ArtistDetailView(artist_id: String, server_id: String) -> Element {
                                 div { class: "flex gap-3 mt-6 justify-center md:justify-start",
                                     button {
                                         class: if is_favorited() { "p-3 rounded-full border border-zinc-700 text-emerald-400 hover:text-emerald-300 hover:border-emerald-500/50 transition-colors" } else { "p-3 rounded-full border border-zinc-700 text-zinc-400 hover:text-emerald-400 hover:border-emerald-500/50 transition-colors" },
+                                        disabled: !can_rate,
                                         onclick: on_favorite_toggle,
                                         Icon {
                                             name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
                                             class: "w-5 h-5".to_string(),
                                         }
                                     }
+                                    button {
+                                        class: "px-4 py-2 rounded-full border border-zinc-700 text-sm text-zinc-300 hover:text-white hover:border-emerald-500/50 transition-colors flex items-center gap-2",
+                                        title: if station_pinned { "Open in Radio" } else { "Pin an endless mix of this artist to the Radio view" },
+                                        onclick: on_pin_station,
+                                        Icon { name: "radio".to_string(), class: "w-4 h-4".to_string() }
+                                        if station_pinned {
+                                            "Station pinned"
+                                        } else {
+                                            "Pin as station"
+                                        }
+                                    }
+                                    if app_settings().musicbrainz_enabled {
+                                        button {
+                                            class: "px-4 py-2 rounded-full border border-zinc-700 text-sm text-zinc-300 hover:text-white hover:border-emerald-500/50 transition-colors flex items-center gap-2",
+                                            disabled: discography_busy(),
+                                            onclick: on_check_discography,
+                                            if discography_busy() {
+                                                Icon {
+                                                    name: "loader".to_string(),
+                                                    class: "w-4 h-4 animate-spin".to_string(),
+                                                }
+                                                "Checking..."
+                                            } else {
+                                                Icon { name: "album".to_string(), class: "w-4 h-4".to_string() }
+                                                "Check discography"
+                                            }
+                                        }
+                                    }
                                 }
                             }
                         }
@@ -340,6 +428,9 @@ pub fn ArtistDetailView(artist_id: String, server_id: String) -> Element {
                                 }
                             }
                         }
+                        if app_settings().musicbrainz_enabled {
+                            MissingReleases { result: discography_result(), albums: albums.clone() }
+                        }
                         if !top_songs.is_empty() {
                             section { class: "space-y-4 mt-10",
                                 h2 { class: "text-2xl font-bold text-white", "Popular Songs" }
@@ -471,3 +562,63 @@ pub fn ArtistDetailView(artist_id: String, server_id: String) -> Element {
         }
     }
 }
+
+#[component]
+fn MissingReleases(
+    result: Option<Result<MusicBrainzDiscography, String>>,
+    albums: Vec<Album>,
+) -> Element {
+    let Some(result) = result else {
+        return rsx! {};
+    };
+
+    rsx! {
+        section { class: "space-y-4 mt-10",
+            h2 { class: "text-2xl font-bold text-white", "Missing Releases" }
+            {
+                match result {
+                    Err(err) => rsx! {
+                        p { class: "text-sm text-red-300", "{err}" }
+                    },
+                    Ok(discography) => {
+                        let missing = find_missing_releases(&discography.releases, &albums);
+                        let total = discography.releases.len();
+                        let artist_url = discography.artist_url();
+                        rsx! {
+                            p { class: "text-sm text-zinc-400",
+                                "You have {total - missing.len()} of {total} studio albums listed for "
+                                a {
+                                    class: "text-emerald-400 hover:text-emerald-300",
+                                    href: "{artist_url}",
+                                    target: "_blank",
+                                    rel: "noopener noreferrer",
+                                    "{discography.artist_name} on MusicBrainz"
+                                }
+                                "."
+                            }
+                            if missing.is_empty() {
+                                p { class: "text-sm text-emerald-300", "Your library has every studio album." }
+                            } else {
+                                div { class: "rounded-2xl border border-zinc-800/80 bg-zinc-900/30 divide-y divide-zinc-800/80",
+                                    for release in missing {
+                                        a {
+                                            key: "{release.id}",
+                                            class: "flex items-center justify-between gap-3 px-4 py-3 text-sm hover:bg-zinc-800/40 transition-colors",
+                                            href: "{release.url()}",
+                                            target: "_blank",
+                                            rel: "noopener noreferrer",
+                                            span { class: "text-white truncate", "{release.title}" }
+                                            span { class: "text-zinc-500 flex-shrink-0",
+                                                {release.year.map(|year| year.to_string()).unwrap_or_else(|| "—".to_string())}
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
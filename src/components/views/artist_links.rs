@@ -65,7 +65,7 @@ pub(crate) async fn resolve_artist_id_for_name(
     );
     let client = NavidromeClient::new(server);
 
-    if let Ok(results) = client.search(&query, 50, 0, 0).await {
+    if let Ok(results) = client.search(&query, 50, 0, 0, None).await {
         let artist_count = results.artists.len();
         eprintln!(
             "[artist-nav.resolve.search] server_id={} query='{}' artist_results={}",
@@ -88,7 +88,7 @@ pub(crate) async fn resolve_artist_id_for_name(
         );
     }
 
-    if let Ok(artists) = client.get_artists().await {
+    if let Ok(artists) = client.get_artists(None).await {
         eprintln!(
             "[artist-nav.resolve.fallback.artists] server_id={} query='{}' artist_count={}",
             server_id,
@@ -118,6 +118,84 @@ pub(crate) async fn resolve_artist_id_for_name(
     None
 }
 
+/// Library artist id for a credited name, looked up with search3 only. Hits and misses
+/// are cached per server so credits lists don't repeat the search on every render.
+pub(crate) async fn resolve_library_artist_id(
+    server: ServerConfig,
+    artist_name: String,
+) -> Option<String> {
+    let normalized_target = normalize_artist_name_key(&artist_name);
+    if normalized_target.is_empty() {
+        return None;
+    }
+    let cache_key = format!("artist_by_name:{}:{}", server.id, normalized_target);
+    if let Some(artist_id) = crate::cache_service::get_json::<String>(&cache_key) {
+        return Some(artist_id);
+    }
+    if crate::cache_service::is_known_miss(&cache_key) {
+        return None;
+    }
+
+    let client = NavidromeClient::new(server);
+    let results = client
+        .search(artist_name.trim(), 20, 0, 0, None)
+        .await
+        .ok()?;
+    let artist_id = results
+        .artists
+        .into_iter()
+        .find(|artist| normalize_artist_name_key(&artist.name) == normalized_target)
+        .map(|artist| artist.id);
+    match artist_id.as_ref() {
+        Some(artist_id) => {
+            let _ = crate::cache_service::put_json(cache_key, artist_id, None);
+        }
+        None => crate::cache_service::remember_miss(&cache_key),
+    }
+    artist_id
+}
+
+/// A credited name that links to the artist page once it resolves to a library artist,
+/// and stays plain text otherwise.
+#[component]
+pub fn CreditArtistName(
+    name: String,
+    server_id: String,
+    #[props(default = "text-sm text-zinc-300".to_string())] class: String,
+) -> Element {
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let navigation = use_context::<Navigation>();
+
+    let artist_id = use_resource({
+        let name = name.clone();
+        let server_id = server_id.clone();
+        move || {
+            let server = servers().into_iter().find(|s| s.id == server_id);
+            let name = name.clone();
+            async move { resolve_library_artist_id(server?, name).await }
+        }
+    });
+
+    match artist_id().flatten() {
+        Some(artist_id) => rsx! {
+            button {
+                class: "{class} hover:text-emerald-400 transition-colors text-left",
+                onclick: move |evt: MouseEvent| {
+                    evt.stop_propagation();
+                    navigation.navigate_to(AppView::ArtistDetailView {
+                        artist_id: artist_id.clone(),
+                        server_id: server_id.clone(),
+                    });
+                },
+                "{name}"
+            }
+        },
+        None => rsx! {
+            span { class: "{class}", "{name}" }
+        },
+    }
+}
+
 #[component]
 pub fn ArtistNameLinks(
     artist_text: String,
@@ -1,7 +1,15 @@
+use super::empty_state::{EmptyState, NoServerEmptyState};
 use crate::api::*;
 use crate::cache_service::{get_json as cache_get_json, put_json as cache_put_json};
+use crate::collation::sort_by_name;
+use crate::components::views::music_folder_filter::{
+    use_music_folder_selection, MusicFolderFilter,
+};
 use crate::components::views::search::ArtistCard;
-use crate::components::{AppView, Icon, Navigation};
+use crate::components::{
+    use_restored_scroll, use_view_state, AppView, Icon, Navigation, SettingsController,
+};
+use crate::db::{AppSettings, ArtistBrowseMode};
 use dioxus::prelude::*;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -18,10 +26,15 @@ async fn artists_delay_ms(ms: u64) {
 pub fn ArtistsView() -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
     let navigation = use_context::<Navigation>();
-    let mut search_query = use_signal(String::new);
-    let debounced_query = use_signal(String::new);
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let settings_controller = use_context::<SettingsController>();
+    let mut search_query = use_view_state("search_query", String::new);
+    // A search brought back with the view runs at once rather than after the debounce.
+    let debounced_query = use_signal(|| search_query.peek().trim().to_string());
     let debounce_generation = use_signal(|| 0u64);
-    let limit = use_signal(|| 30usize);
+    let limit = use_view_state("limit", || 30usize);
+    let folder_selection = use_music_folder_selection();
+    let ignore_articles = use_memo(move || app_settings().sort_ignore_articles);
 
     {
         let mut debounced_query = debounced_query.clone();
@@ -58,6 +71,8 @@ pub fn ArtistsView() -> Element {
         let servers = servers();
         let limit = limit();
         let query = debounced_query();
+        let folder_selection = folder_selection();
+        let ignore_articles = ignore_articles();
         async move {
             let active_servers: Vec<ServerConfig> =
                 servers.into_iter().filter(|s| s.active).collect();
@@ -67,7 +82,10 @@ pub fn ArtistsView() -> Element {
 
             let mut cache_server_ids: Vec<String> = active_servers
                 .iter()
-                .map(|server| server.id.clone())
+                .map(|server| match folder_selection.get(&server.id) {
+                    Some(folder_id) => format!("{}@{}", server.id, folder_id),
+                    None => server.id.clone(),
+                })
                 .collect();
             cache_server_ids.sort();
             let cache_prefix = format!("view:artists:v1:{}", cache_server_ids.join("|"));
@@ -76,32 +94,30 @@ pub fn ArtistsView() -> Element {
             let mut more_available = false;
             if query.trim().is_empty() {
                 let cache_key = format!("{cache_prefix}:all");
+                // The whole list is returned so the album-artists filter and "View more"
+                // work on it without asking the servers again.
                 if let Some(mut cached_artists) = cache_get_json::<Vec<Artist>>(&cache_key) {
-                    cached_artists
-                        .sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-                    if cached_artists.len() > limit {
-                        more_available = true;
-                    }
-                    cached_artists.truncate(limit);
-                    return (cached_artists, more_available);
+                    sort_by_name(&mut cached_artists, ignore_articles, |artist| &artist.name);
+                    return (cached_artists, false);
                 }
 
                 for server in active_servers.iter().cloned() {
+                    let folder_id = folder_selection.get(&server.id).cloned();
                     let client = NavidromeClient::new(server);
-                    if let Ok(server_artists) = client.get_artists().await {
+                    if let Ok(server_artists) = client.get_artists(folder_id.as_deref()).await {
                         artists.extend(server_artists);
                     }
                 }
-                artists.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+                sort_by_name(&mut artists, ignore_articles, |artist| &artist.name);
                 let _ = cache_put_json(cache_key, &artists, Some(6));
-                if artists.len() > limit {
-                    more_available = true;
-                }
-                artists.truncate(limit);
             } else {
                 for server in active_servers.into_iter() {
+                    let folder_id = folder_selection.get(&server.id).cloned();
                     let client = NavidromeClient::new(server);
-                    if let Ok(results) = client.search(&query, limit as u32 + 1, 0, 0).await {
+                    if let Ok(results) = client
+                        .search(&query, limit as u32 + 1, 0, 0, folder_id.as_deref())
+                        .await
+                    {
                         if results.artists.len() > limit {
                             more_available = true;
                         }
@@ -110,18 +126,73 @@ pub fn ArtistsView() -> Element {
                         artists.extend(subset);
                     }
                 }
-                artists.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+                sort_by_name(&mut artists, ignore_articles, |artist| &artist.name);
             }
             (artists, more_available)
         }
     });
 
+    use_restored_scroll(move || artists.read().is_some());
+
+    let has_active_servers = servers().iter().any(|server| server.active);
+    let settings = app_settings();
+    let active_server_ids: Vec<String> = servers()
+        .iter()
+        .filter(|server| server.active)
+        .map(|server| server.id.clone())
+        .collect();
+    let album_artists_only = !active_server_ids.is_empty()
+        && active_server_ids.iter().all(|server_id| {
+            settings.artist_browse_mode_for(server_id) == ArtistBrowseMode::AlbumArtists
+        });
+
+    // Applies to every active server; each server keeps its own saved mode.
+    let set_browse_mode = move |mode: ArtistBrowseMode| {
+        let active_servers: Vec<ServerConfig> = servers
+            .peek()
+            .iter()
+            .filter(|s| s.active)
+            .cloned()
+            .collect();
+        settings_controller.update_settings(|settings| {
+            for server in &active_servers {
+                settings.set_artist_browse_mode(&server.id, mode);
+            }
+        });
+    };
+    let grid_density = app_settings().grid_density;
+    let columns = grid_density
+        .columns_class()
+        .unwrap_or("grid-cols-2 sm:grid-cols-3 md:grid-cols-4 lg:grid-cols-5 xl:grid-cols-6");
+
     rsx! {
         div { class: "space-y-8",
             header { class: "page-header page-header--split",
                 div {
                     h1 { class: "page-title", "Artists" }
-                    p { class: "page-subtitle", "All artists from your connected servers" }
+                    p { class: "page-subtitle",
+                        if album_artists_only {
+                            "Album artists from your connected servers"
+                        } else {
+                            "All artists from your connected servers"
+                        }
+                    }
+                }
+                div { class: "flex flex-wrap gap-2 items-center",
+                    div { class: "flex items-center gap-1 p-1 bg-zinc-800/50 rounded-xl",
+                        button {
+                            class: if album_artists_only { "px-3 py-1.5 rounded-lg text-xs text-zinc-400 hover:text-white transition-colors" } else { "px-3 py-1.5 rounded-lg text-xs bg-zinc-700 text-white" },
+                            onclick: move |_| set_browse_mode(ArtistBrowseMode::All),
+                            "All artists"
+                        }
+                        button {
+                            class: if album_artists_only { "px-3 py-1.5 rounded-lg text-xs bg-zinc-700 text-white" } else { "px-3 py-1.5 rounded-lg text-xs text-zinc-400 hover:text-white transition-colors" },
+                            title: "Hide artists that only appear on other artists' albums",
+                            onclick: move |_| set_browse_mode(ArtistBrowseMode::AlbumArtists),
+                            "Album artists"
+                        }
+                    }
+                    MusicFolderFilter {}
                 }
                 div { class: "relative w-full md:max-w-xs",
                     Icon {
@@ -145,26 +216,50 @@ pub fn ArtistsView() -> Element {
                         let raw_query = search_query().trim().to_string();
                         let query = raw_query.to_lowercase();
                         let has_query = !query.is_empty();
-                        let display: Vec<Artist> = artists.into_iter().take(limit()).collect();
+                        let matching: Vec<Artist> = artists
+                            .into_iter()
+                            .filter(|artist| {
+                                settings.artist_browse_mode_for(&artist.server_id).includes(artist)
+                            })
+                            .collect();
+                        let total = matching.len();
+                        let more_available = more_available || total > limit();
+                        let display: Vec<Artist> = matching.into_iter().take(limit()).collect();
+                        let noun = match (album_artists_only, total) {
+                            (true, 1) => "album artist",
+                            (true, _) => "album artists",
+                            (false, 1) => "artist",
+                            (false, _) => "artists",
+                        };
 
                         rsx! {
                             if display.is_empty() {
-                                div { class: "flex flex-col items-center justify-center py-20",
-                                    Icon {
-                                        name: "artist".to_string(),
-                                        class: "w-16 h-16 text-zinc-600 mb-4".to_string(),
+                                if !has_active_servers {
+                                    NoServerEmptyState {
+                                        icon: "artist".to_string(),
+                                        message: "Connect a Navidrome server to browse artists.".to_string(),
                                     }
-                                    if has_query {
-                                        p { class: "text-zinc-300", "No artists match \"{raw_query}\"" }
-                                    } else {
-                                        p { class: "text-zinc-400", "No artists found" }
+                                } else if has_query {
+                                    EmptyState {
+                                        icon: "artist".to_string(),
+                                        title: format!("No artists match \"{raw_query}\""),
+                                    }
+                                } else {
+                                    EmptyState {
+                                        icon: "artist".to_string(),
+                                        title: "No artists found".to_string(),
+                                        message: "Artists show up here once your server has scanned your music library.".to_string(),
                                     }
                                 }
                             } else {
-                                div { class: "rs-album-grid grid grid-cols-2 sm:grid-cols-3 md:grid-cols-4 lg:grid-cols-5 xl:grid-cols-6 gap-6",
+                                if !has_query {
+                                    p { class: "text-sm text-zinc-400", "{total} {noun}" }
+                                }
+                                div { class: "rs-album-grid grid {columns} gap-6",
                                     for artist in display {
                                         ArtistCard {
                                             artist: artist.clone(),
+                                            art_px: grid_density.tile_px(),
                                             onclick: {
                                                 let navigation = navigation.clone();
                                                 let artist_id = artist.id.clone();
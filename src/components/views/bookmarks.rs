@@ -1,13 +1,16 @@
+use super::empty_state::{EmptyState, NoServerEmptyState};
 use crate::api::models::format_duration;
 use crate::api::*;
 use crate::components::views::artist_links::ArtistNameLinks;
+use crate::components::{sized_cover_art_url, ArtworkKind};
 use crate::components::{AppView, Icon, Navigation, PlaybackPositionSignal, SeekRequestSignal};
+use crate::db::AppSettings;
+use crate::time_format::humanize_server_timestamp_for_display;
 use dioxus::prelude::*;
 
 #[component]
 pub fn BookmarksView() -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
-    let navigation = use_context::<Navigation>();
     let mut refresh_key = use_signal(|| 0u32);
 
     let bookmarks = use_resource(move || {
@@ -92,37 +95,19 @@ pub fn BookmarksView() -> Element {
             }
 
             if !has_active_server {
-                div { class: "flex flex-col items-center justify-center py-20",
-                    Icon {
-                        name: "bookmark".to_string(),
-                        class: "w-16 h-16 text-zinc-600 mb-4".to_string(),
-                    }
-                    h2 { class: "text-xl font-semibold text-white mb-2", "No servers connected" }
-                    p { class: "text-zinc-400 text-center max-w-md",
-                        "Add a Navidrome server to fetch your bookmarks."
-                    }
-                    button {
-                        class: "mt-6 px-6 py-3 bg-emerald-500 hover:bg-emerald-400 text-white font-medium rounded-xl transition-colors",
-                        onclick: {
-                            let nav = navigation.clone();
-                            move |_| nav.navigate_to(AppView::SettingsView {})
-                        },
-                        "Add server"
-                    }
+                NoServerEmptyState {
+                    icon: "bookmark".to_string(),
+                    message: "Add a Navidrome server to fetch your bookmarks.".to_string(),
                 }
             } else {
                 match bookmarks() {
                     Some(list) => rsx! {
                         if list.is_empty() {
-                            div { class: "flex flex-col items-center justify-center py-20",
-                                Icon {
-                                    name: "bookmark".to_string(),
-                                    class: "w-16 h-16 text-zinc-600 mb-4".to_string(),
-                                }
-                                h2 { class: "text-xl font-semibold text-white mb-2", "No bookmarks yet" }
-                                p { class: "text-zinc-400 text-center max-w-lg",
-                                    "Create a bookmark while listening to jump back to that spot later."
-                                }
+                            EmptyState {
+                                icon: "bookmark".to_string(),
+                                title: "No bookmarks yet".to_string(),
+                                message: "Create a bookmark while listening to jump back to that spot later."
+                                    .to_string(),
                             }
                         } else {
                             div { class: "grid grid-cols-1 md:grid-cols-2 xl:grid-cols-3 gap-4",
@@ -162,6 +147,7 @@ fn BookmarkCard(bookmark: Bookmark, on_deleted: EventHandler<()>) -> Element {
     let is_playing = use_context::<crate::components::IsPlayingSignal>().0;
     let playback_position = use_context::<PlaybackPositionSignal>().0;
     let mut seek_request = use_context::<SeekRequestSignal>().0;
+    let app_settings = use_context::<Signal<AppSettings>>();
     let cover_url = servers()
         .iter()
         .find(|s| s.id == bookmark.server_id)
@@ -171,7 +157,7 @@ fn BookmarkCard(bookmark: Bookmark, on_deleted: EventHandler<()>) -> Element {
                 .entry
                 .cover_art
                 .as_ref()
-                .map(|id| client.get_cover_art_url(id, 200))
+                .map(|id| sized_cover_art_url(&client, id, ArtworkKind::Song, 80))
         });
 
     let position = format_duration((bookmark.position / 1000) as u32);
@@ -318,7 +304,11 @@ fn BookmarkCard(bookmark: Bookmark, on_deleted: EventHandler<()>) -> Element {
                         span { class: "text-xs text-zinc-500 px-2 py-1 rounded-full bg-zinc-800/80",
                             "{song.server_name}"
                         }
-                        if let Some(changed) = bookmark.changed.clone() {
+                        if let Some(changed) = bookmark
+                            .changed
+                            .as_deref()
+                            .and_then(|value| humanize_server_timestamp_for_display(value, &app_settings()))
+                        {
                             span { class: "text-xs text-zinc-500 px-2 py-1 rounded-full bg-zinc-800/80",
                                 "{changed}"
                             }
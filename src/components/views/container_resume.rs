@@ -0,0 +1,58 @@
+use crate::api::Song;
+use crate::components::{Icon, IsPlayingSignal};
+use crate::db::{container_resume_point, AppSettings, ContainerResumePoint, ResumeContainerKind};
+use crate::time_format::humanize_epoch_ms_for_display;
+use dioxus::prelude::*;
+
+/// Queue index to resume at: the remembered song, or its old position if the song moved out.
+pub(super) fn resume_index(songs: &[Song], point: &ContainerResumePoint) -> Option<usize> {
+    songs
+        .iter()
+        .position(|song| song.id == point.song_id)
+        .or_else(|| (point.track_index < songs.len()).then_some(point.track_index))
+}
+
+/// "Resume from disc 2 track 3 · 12:40" for an album or playlist header, shown when the
+/// container has a local resume point.
+#[component]
+pub(super) fn ContainerResumeButton(
+    kind: ResumeContainerKind,
+    source_id: String,
+    on_resume: EventHandler<ContainerResumePoint>,
+) -> Element {
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let is_playing = use_context::<IsPlayingSignal>().0;
+    let mut source = use_signal(|| source_id.clone());
+
+    use_effect({
+        let source_id = source_id.clone();
+        move || {
+            if *source.peek() != source_id {
+                source.set(source_id.clone());
+            }
+        }
+    });
+
+    // Re-read on play/pause so the label follows what was just heard.
+    let point = use_resource(move || {
+        let source_id = source();
+        let _ = is_playing();
+        async move { container_resume_point(kind, source_id).await }
+    });
+
+    let Some(Some(point)) = point() else {
+        return rsx! {};
+    };
+    let label = point.label();
+    let last_played = humanize_epoch_ms_for_display(point.updated_at_ms, &app_settings());
+
+    rsx! {
+        button {
+            class: "col-span-full px-4 py-3 rounded-full border border-emerald-500/40 text-emerald-300 hover:text-white hover:border-emerald-400/70 transition-colors flex items-center justify-center gap-2 text-sm md:col-span-1",
+            title: "{label} · last played {last_played}",
+            onclick: move |_| on_resume.call(point.clone()),
+            Icon { name: "play".to_string(), class: "w-4 h-4".to_string() }
+            span { class: "truncate", "{label}" }
+        }
+    }
+}
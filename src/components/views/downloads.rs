@@ -1,13 +1,19 @@
 use crate::api::{NavidromeClient, ServerConfig, Song};
-use crate::components::{AddIntent, AddMenuController, AppView, Icon, Navigation};
-use crate::db::{save_settings, AppSettings};
+use crate::collation::{search_text, sort_by_name};
+use crate::components::{sized_cover_art_url, ArtworkKind};
+use crate::components::{
+    AddIntent, AddMenuController, AppView, Icon, JobRegistry, Navigation, SettingsController,
+    AUTO_DOWNLOAD_JOB, CACHE_REFRESH_JOB, DOWNLOAD_VERIFY_JOB,
+};
+use crate::db::AppSettings;
 use crate::offline_audio::{
-    clear_downloads, download_stats, list_active_downloads, list_downloaded_collection_memberships,
-    list_downloaded_collections, list_downloaded_entries, refresh_downloaded_cache,
+    clear_downloads, download_favorite_songs, download_stats, list_active_downloads,
+    list_downloaded_collection_memberships, list_downloaded_collections, list_downloaded_entries,
+    list_pending_downloads, prioritize_pending_download, prioritize_pending_group,
     remove_downloaded_album, remove_downloaded_collection, remove_downloaded_song,
-    run_auto_download_pass, sync_downloaded_collection_members,
+    reorder_pending_download, set_download_pinned, sync_downloaded_collection_members,
     sync_downloaded_collection_metadata, ActiveDownloadEntry, DownloadCollectionEntry,
-    DownloadCollectionMembershipEntry, DownloadIndexEntry,
+    DownloadCollectionMembershipEntry, DownloadIndexEntry, PendingDownloadEntry,
 };
 use dioxus::prelude::*;
 use rand::seq::SliceRandom;
@@ -179,13 +185,9 @@ const DOWNLOADS_POLL_IDLE_MS: u64 = 5000;
 const DOWNLOADS_POLL_ACTIVE_MS: u64 = 1400;
 const DOWNLOADS_SONG_PAGE_SIZE: usize = 80;
 const DOWNLOADS_COLLECTION_PAGE_SIZE: usize = 60;
-
-fn normalize_download_field(value: &Option<String>) -> String {
-    value
-        .as_ref()
-        .map(|item| item.trim().to_ascii_lowercase())
-        .unwrap_or_default()
-}
+const DOWNLOADS_PENDING_VISIBLE_LIMIT: usize = 50;
+/// Label of the favorites download in the sidebar's background jobs.
+const FAVORITES_JOB: &str = "Favorites download";
 
 fn to_download_song(entry: &DownloadIndexEntry, servers: &[ServerConfig]) -> Song {
     let server_name = servers
@@ -205,6 +207,7 @@ fn to_download_song(entry: &DownloadIndexEntry, servers: &[ServerConfig]) -> Son
         title: entry.title.clone(),
         album: entry.album.clone(),
         album_id: entry.album_id.clone(),
+        artist_id: entry.artist_id.clone(),
         artist: entry.artist.clone(),
         cover_art: entry
             .cover_art_id
@@ -220,7 +223,7 @@ fn to_download_song(entry: &DownloadIndexEntry, servers: &[ServerConfig]) -> Son
 fn download_entry_cover_url(
     entry: &DownloadIndexEntry,
     servers: &[ServerConfig],
-    size: u32,
+    render_px: u32,
 ) -> Option<String> {
     let cover_id = entry
         .cover_art_id
@@ -238,7 +241,14 @@ fn download_entry_cover_url(
     servers
         .iter()
         .find(|server| server.id == entry.server_id)
-        .map(|server| NavidromeClient::new(server.clone()).get_cover_art_url(&cover_id, size))
+        .map(|server| {
+            sized_cover_art_url(
+                &NavidromeClient::new(server.clone()),
+                &cover_id,
+                ArtworkKind::Song,
+                render_px,
+            )
+        })
 }
 
 fn ordered_download_entries_for_song_ids(
@@ -317,6 +327,8 @@ pub fn DownloadsView() -> Element {
     let mut queue = use_context::<Signal<Vec<Song>>>();
     let mut queue_index = use_context::<Signal<usize>>();
     let app_settings = use_context::<Signal<AppSettings>>();
+    let settings_controller = use_context::<SettingsController>();
+    let job_registry = use_context::<JobRegistry>();
     let refresh_nonce = use_signal(|| 0u64);
     let action_busy = use_signal(|| false);
     let action_status = use_signal(|| None::<String>);
@@ -332,13 +344,16 @@ pub fn DownloadsView() -> Element {
     let selected_collection_modal = use_signal(|| None::<DownloadCollectionEntry>);
     let collection_metadata_sync_signature = use_signal(String::new);
     let pending_delete = use_signal(|| None::<PendingDownloadsDelete>);
+    let dragged_pending_key = use_signal(|| None::<String>);
 
     {
         let mut refresh_nonce = refresh_nonce.clone();
         use_effect(move || {
             spawn(async move {
                 loop {
-                    let wait_ms = if list_active_downloads().is_empty() {
+                    let wait_ms = if list_active_downloads().is_empty()
+                        && list_pending_downloads().is_empty()
+                    {
                         DOWNLOADS_POLL_IDLE_MS
                     } else {
                         DOWNLOADS_POLL_ACTIVE_MS
@@ -379,6 +394,7 @@ pub fn DownloadsView() -> Element {
     let mut all_entries = list_downloaded_entries();
     all_entries.sort_by(|left, right| right.updated_at_ms.cmp(&left.updated_at_ms));
     let active_downloads: Vec<ActiveDownloadEntry> = list_active_downloads();
+    let pending_downloads: Vec<PendingDownloadEntry> = list_pending_downloads();
     let collections = list_downloaded_collections();
     let collection_memberships: Vec<DownloadCollectionMembershipEntry> =
         list_downloaded_collection_memberships();
@@ -540,16 +556,17 @@ pub fn DownloadsView() -> Element {
         },
     );
 
-    let query = search_query().trim().to_ascii_lowercase();
+    let query = search_text(&search_query());
+    let ignore_articles = settings.sort_ignore_articles;
     let mut entries: Vec<DownloadIndexEntry> = if query.is_empty() {
         all_entries.clone()
     } else {
         all_entries
             .iter()
             .filter(|entry| {
-                let title = entry.title.to_ascii_lowercase();
-                let artist = normalize_download_field(&entry.artist);
-                let album = normalize_download_field(&entry.album);
+                let title = search_text(&entry.title);
+                let artist = search_text(entry.artist.as_deref().unwrap_or_default());
+                let album = search_text(entry.album.as_deref().unwrap_or_default());
                 title.contains(&query) || artist.contains(&query) || album.contains(&query)
             })
             .cloned()
@@ -560,20 +577,16 @@ pub fn DownloadsView() -> Element {
             entries.sort_by(|left, right| right.updated_at_ms.cmp(&left.updated_at_ms));
         }
         DownloadSongSort::Title => {
-            entries.sort_by(|left, right| {
-                left.title
-                    .to_ascii_lowercase()
-                    .cmp(&right.title.to_ascii_lowercase())
-            });
+            sort_by_name(&mut entries, false, |entry| &entry.title);
         }
         DownloadSongSort::Artist => {
-            entries.sort_by(|left, right| {
-                normalize_download_field(&left.artist).cmp(&normalize_download_field(&right.artist))
+            sort_by_name(&mut entries, ignore_articles, |entry| {
+                entry.artist.as_deref().unwrap_or_default()
             });
         }
         DownloadSongSort::Album => {
-            entries.sort_by(|left, right| {
-                normalize_download_field(&left.album).cmp(&normalize_download_field(&right.album))
+            sort_by_name(&mut entries, ignore_articles, |entry| {
+                entry.album.as_deref().unwrap_or_default()
             });
         }
         DownloadSongSort::Size => {
@@ -591,12 +604,12 @@ pub fn DownloadsView() -> Element {
     } else {
         downloaded_albums
             .iter()
-            .filter(|album| album.name.to_ascii_lowercase().contains(&query))
+            .filter(|album| search_text(&album.name).contains(&query))
             .cloned()
             .collect()
     };
     match album_sort() {
-        "title" => filtered_albums.sort_by(|left, right| left.name.cmp(&right.name)),
+        "title" => sort_by_name(&mut filtered_albums, ignore_articles, |album| &album.name),
         "oldest" => {
             filtered_albums.sort_by(|left, right| left.updated_at_ms.cmp(&right.updated_at_ms))
         }
@@ -615,12 +628,12 @@ pub fn DownloadsView() -> Element {
     } else {
         downloaded_playlists
             .iter()
-            .filter(|playlist| playlist.name.to_ascii_lowercase().contains(&query))
+            .filter(|playlist| search_text(&playlist.name).contains(&query))
             .cloned()
             .collect()
     };
     match playlist_sort() {
-        "title" => filtered_playlists.sort_by(|left, right| left.name.cmp(&right.name)),
+        "title" => sort_by_name(&mut filtered_playlists, false, |playlist| &playlist.name),
         "oldest" => {
             filtered_playlists.sort_by(|left, right| left.updated_at_ms.cmp(&right.updated_at_ms))
         }
@@ -650,6 +663,14 @@ pub fn DownloadsView() -> Element {
         ((stats.song_count as f64 / count_limit as f64) * 100.0).clamp(0.0, 100.0);
     let size_usage_bar_width = format!("{size_usage_percent:.1}%");
     let count_usage_bar_width = format!("{count_usage_percent:.1}%");
+    // A favorites download keeps counting up here, also after coming back to this screen.
+    let action_status_line = match job_registry
+        .running(FAVORITES_JOB)
+        .and_then(|job| job.detail())
+    {
+        Some(progress) => Some(progress),
+        None => action_status(),
+    };
 
     let on_refresh = {
         let servers = servers.clone();
@@ -664,12 +685,17 @@ pub fn DownloadsView() -> Element {
             let servers_snapshot = servers();
             action_busy.set(true);
             action_status.set(Some("Refreshing downloaded collections...".to_string()));
+            let sync = job_registry.start("Downloaded collection sync").run(
+                async move { sync_downloaded_collection_metadata(&servers_snapshot).await },
+                |changed| Ok(format!("{changed} collection(s) updated")),
+            );
             spawn(async move {
-                let changed = sync_downloaded_collection_metadata(&servers_snapshot).await;
-                action_status.set(Some(if changed > 0 {
-                    format!("Refresh complete: {changed} collection(s) updated.")
-                } else {
-                    "Refresh complete: no collection changes found.".to_string()
+                action_status.set(Some(match sync.await {
+                    Some(changed) if changed > 0 => {
+                        format!("Refresh complete: {changed} collection(s) updated.")
+                    }
+                    Some(_) => "Refresh complete: no collection changes found.".to_string(),
+                    None => "Refresh cancelled.".to_string(),
                 }));
                 refresh_nonce.with_mut(|nonce| *nonce = nonce.saturating_add(1));
                 action_busy.set(false);
@@ -677,30 +703,16 @@ pub fn DownloadsView() -> Element {
         }
     };
 
-    let on_toggle_downloads = {
-        let mut app_settings = app_settings.clone();
-        move |_| {
-            let mut settings = app_settings();
+    let on_toggle_downloads = move |_| {
+        settings_controller.update_settings(|settings| {
             settings.downloads_enabled = !settings.downloads_enabled;
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            spawn(async move {
-                let _ = save_settings(settings_clone).await;
-            });
-        }
+        });
     };
 
-    let on_toggle_auto_downloads = {
-        let mut app_settings = app_settings.clone();
-        move |_| {
-            let mut settings = app_settings();
+    let on_toggle_auto_downloads = move |_| {
+        settings_controller.update_settings(|settings| {
             settings.auto_downloads_enabled = !settings.auto_downloads_enabled;
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            spawn(async move {
-                let _ = save_settings(settings_clone).await;
-            });
-        }
+        });
     };
 
     let on_clear_downloads = {
@@ -710,6 +722,23 @@ pub fn DownloadsView() -> Element {
         }
     };
 
+    let mut on_toggle_pin = {
+        let mut action_status = action_status.clone();
+        let mut refresh_nonce = refresh_nonce.clone();
+        move |entry: DownloadIndexEntry| {
+            let pinned = !entry.pinned;
+            if !set_download_pinned(&entry.server_id, &entry.song_id, pinned) {
+                return;
+            }
+            action_status.set(Some(if pinned {
+                format!("\"{}\" will be kept offline forever.", entry.title)
+            } else {
+                format!("\"{}\" can be cleaned up again.", entry.title)
+            }));
+            refresh_nonce.with_mut(|nonce| *nonce = nonce.saturating_add(1));
+        }
+    };
+
     let on_confirm_delete = {
         let mut pending_delete = pending_delete.clone();
         let mut action_status = action_status.clone();
@@ -784,20 +813,89 @@ pub fn DownloadsView() -> Element {
                 return;
             }
 
+            if job_registry.running(AUTO_DOWNLOAD_JOB).is_some() {
+                action_status.set(Some(
+                    "An auto-download pass is already running.".to_string(),
+                ));
+                return;
+            }
+
             let settings_snapshot = app_settings();
             action_busy.set(true);
             action_status.set(Some("Running auto-download pass...".to_string()));
+            let pass = job_registry.run_auto_download(active_servers, settings_snapshot);
             spawn(async move {
-                match run_auto_download_pass(&active_servers, &settings_snapshot).await {
-                    Ok(report) => {
+                match pass.await {
+                    Some(Ok(report)) => {
                         action_status.set(Some(format!(
-                            "Auto-download finished: {} new, {} skipped, {} failed, {} purged.",
-                            report.downloaded, report.skipped, report.failed, report.purged
+                            "Auto-download finished: {} new, {} skipped, {} failed, {} purged{}.",
+                            report.downloaded,
+                            report.skipped,
+                            report.failed,
+                            report.purged,
+                            report.cleanup_summary()
                         )));
                     }
-                    Err(error) => {
+                    Some(Err(error)) => {
                         action_status.set(Some(format!("Auto-download failed: {error}")));
                     }
+                    None => action_status.set(Some("Auto-download cancelled.".to_string())),
+                }
+                refresh_nonce.with_mut(|nonce| *nonce = nonce.saturating_add(1));
+                action_busy.set(false);
+            });
+        }
+    };
+
+    let on_download_favorites = {
+        let servers = servers.clone();
+        let app_settings = app_settings.clone();
+        let mut action_busy = action_busy.clone();
+        let mut action_status = action_status.clone();
+        let mut refresh_nonce = refresh_nonce.clone();
+        move |_| {
+            if action_busy() {
+                return;
+            }
+
+            let servers_snapshot = servers();
+            let settings_snapshot = app_settings();
+            action_busy.set(true);
+            action_status.set(Some("Loading favorites...".to_string()));
+            let job = job_registry.start(FAVORITES_JOB);
+            let download = job.run(
+                async move {
+                    download_favorite_songs(&servers_snapshot, &settings_snapshot, |done, total| {
+                        if total > 0 {
+                            job.set_progress(done as f64 / total as f64);
+                        }
+                        job.set_detail(format!("Downloading favorites: {done}/{total}..."));
+                    })
+                    .await
+                },
+                |result| {
+                    result.clone().map(|report| {
+                        format!("{} new, {} failed", report.downloaded, report.failed)
+                    })
+                },
+            );
+            spawn(async move {
+                match download.await {
+                    Some(Ok(report)) => {
+                        let limit_suffix = if report.limited > 0 {
+                            format!(" {} left out (download limit reached).", report.limited)
+                        } else {
+                            String::new()
+                        };
+                        action_status.set(Some(format!(
+                            "Favorites downloaded: {} new, {} already offline, {} failed.{}",
+                            report.downloaded, report.skipped, report.failed, limit_suffix
+                        )));
+                    }
+                    Some(Err(error)) => {
+                        action_status.set(Some(format!("Favorites download failed: {error}")));
+                    }
+                    None => action_status.set(Some("Favorites download cancelled.".to_string())),
                 }
                 refresh_nonce.with_mut(|nonce| *nonce = nonce.saturating_add(1));
                 action_busy.set(false);
@@ -822,14 +920,22 @@ pub fn DownloadsView() -> Element {
                 return;
             }
 
+            if job_registry.running(CACHE_REFRESH_JOB).is_some() {
+                action_status.set(Some(
+                    "A downloaded cache refresh is already running.".to_string(),
+                ));
+                return;
+            }
+
             let settings_snapshot = app_settings();
             action_busy.set(true);
             action_status.set(Some(
                 "Refreshing downloaded cache (lyrics + artwork)...".to_string(),
             ));
+            let refresh = job_registry.run_cache_refresh(servers_snapshot, settings_snapshot);
             spawn(async move {
-                match refresh_downloaded_cache(&servers_snapshot, &settings_snapshot).await {
-                    Ok(report) => {
+                match refresh.await {
+                    Some(Ok(report)) => {
                         let missing_suffix = if report.missing_servers > 0 {
                             format!(" ({} missing server mappings)", report.missing_servers)
                         } else {
@@ -844,9 +950,48 @@ pub fn DownloadsView() -> Element {
                             missing_suffix
                         )));
                     }
-                    Err(error) => {
+                    Some(Err(error)) => {
                         action_status.set(Some(format!("Cache refresh failed: {error}")));
                     }
+                    None => action_status.set(Some("Cache refresh cancelled.".to_string())),
+                }
+                refresh_nonce.with_mut(|nonce| *nonce = nonce.saturating_add(1));
+                action_busy.set(false);
+            });
+        }
+    };
+
+    let on_verify_downloads = {
+        let mut action_busy = action_busy.clone();
+        let mut action_status = action_status.clone();
+        let mut refresh_nonce = refresh_nonce.clone();
+        move |_| {
+            if action_busy() {
+                return;
+            }
+
+            if job_registry.running(DOWNLOAD_VERIFY_JOB).is_some() {
+                action_status.set(Some(
+                    "Download verification is already running.".to_string(),
+                ));
+                return;
+            }
+
+            action_busy.set(true);
+            action_status.set(Some("Verifying downloaded files...".to_string()));
+            let verify = job_registry.run_download_verification();
+            spawn(async move {
+                match verify.await {
+                    Some(Ok(report)) => {
+                        action_status.set(Some(format!(
+                            "Verification finished: {} checked, {} missing removed from the index, {} damaged files deleted.",
+                            report.checked, report.missing, report.damaged
+                        )));
+                    }
+                    Some(Err(error)) => {
+                        action_status.set(Some(format!("Verification failed: {error}")));
+                    }
+                    None => action_status.set(Some("Verification cancelled.".to_string())),
                 }
                 refresh_nonce.with_mut(|nonce| *nonce = nonce.saturating_add(1));
                 action_busy.set(false);
@@ -949,8 +1094,12 @@ pub fn DownloadsView() -> Element {
                                                 .iter()
                                                 .find(|server| server.id == entry.server_id)
                                                 .map(|server| {
-                                                    NavidromeClient::new(server.clone())
-                                                        .get_cover_art_url(cover, 120)
+                                                    sized_cover_art_url(
+                                                        &NavidromeClient::new(server.clone()),
+                                                        cover,
+                                                        ArtworkKind::Song,
+                                                        112,
+                                                    )
                                                 })
                                         });
                                     rsx! {
@@ -1006,6 +1155,19 @@ pub fn DownloadsView() -> Element {
                                                         class: "w-3 h-3 mx-auto".to_string(),
                                                     }
                                                 }
+                                                button {
+                                                    class: if entry.pinned { "flex-1 px-2 py-1 rounded text-[10px] border border-amber-500/60 bg-amber-500/20 text-amber-300 hover:bg-amber-500/30 transition-colors" } else { "flex-1 px-2 py-1 rounded text-[10px] border border-zinc-600 text-zinc-400 hover:text-white hover:border-zinc-400 transition-colors" },
+                                                    title: if entry.pinned { "Pinned: kept offline forever" } else { "Keep offline forever" },
+                                                    aria_label: "Keep offline forever",
+                                                    onclick: {
+                                                        let entry = entry.clone();
+                                                        move |_| on_toggle_pin(entry.clone())
+                                                    },
+                                                    Icon {
+                                                        name: "bookmark".to_string(),
+                                                        class: "w-3 h-3 mx-auto".to_string(),
+                                                    }
+                                                }
                                                 button {
                                                     class: "flex-1 px-2 py-1 rounded text-[10px] border border-rose-500/50 text-rose-300 hover:bg-rose-500 hover:border-rose-500 hover:text-white transition-colors",
                                                     onclick: {
@@ -1089,8 +1251,12 @@ pub fn DownloadsView() -> Element {
                                                 .iter()
                                                 .find(|server| server.id == album.server_id)
                                                 .map(|server| {
-                                                    NavidromeClient::new(server.clone())
-                                                        .get_cover_art_url(cover_id, 140)
+                                                    sized_cover_art_url(
+                                                        &NavidromeClient::new(server.clone()),
+                                                        cover_id,
+                                                        ArtworkKind::Album,
+                                                        112,
+                                                    )
                                                 })
                                         });
                                     rsx! {
@@ -1188,7 +1354,7 @@ pub fn DownloadsView() -> Element {
                                                             &(playlist.server_id.clone(), song_id.trim().to_string()),
                                                         )
                                                         .and_then(|entry| {
-                                                            download_entry_cover_url(entry, &servers_snapshot, 140)
+                                                            download_entry_cover_url(entry, &servers_snapshot, 112)
                                                         })
                                                 })
                                         });
@@ -1313,8 +1479,12 @@ pub fn DownloadsView() -> Element {
                                 .iter()
                                 .find(|server| server.id == modal_collection.server_id)
                                 .map(|server| {
-                                    NavidromeClient::new(server.clone())
-                                        .get_cover_art_url(cover_id, 200)
+                                    sized_cover_art_url(
+                                        &NavidromeClient::new(server.clone()),
+                                        cover_id,
+                                        ArtworkKind::Album,
+                                        96,
+                                    )
                                 })
                         });
                     let modal_kind_label = if modal_collection.kind == "playlist" {
@@ -1506,7 +1676,7 @@ pub fn DownloadsView() -> Element {
                                         for entry in modal_entries.iter() {
                                             {
                                                 let entry = entry.clone();
-                                                let cover_url = download_entry_cover_url(&entry, &servers_snapshot, 80);
+                                                let cover_url = download_entry_cover_url(&entry, &servers_snapshot, 40);
                                                 rsx! {
                                                     div {
                                                         key: "{entry.server_id}:{entry.song_id}",
@@ -1552,6 +1722,16 @@ pub fn DownloadsView() -> Element {
                                                                 },
                                                                 Icon { name: "play".to_string(), class: "w-4 h-4".to_string() }
                                                             }
+                                                            button {
+                                                                class: if entry.pinned { "p-1.5 rounded text-amber-300 hover:bg-amber-500/20 transition-colors" } else { "p-1.5 rounded text-zinc-400 hover:text-white hover:bg-zinc-700/40 transition-colors opacity-100 md:opacity-0 md:group-hover:opacity-100" },
+                                                                title: if entry.pinned { "Pinned: kept offline forever" } else { "Keep offline forever" },
+                                                                aria_label: "Keep offline forever",
+                                                                onclick: {
+                                                                    let entry = entry.clone();
+                                                                    move |_| on_toggle_pin(entry.clone())
+                                                                },
+                                                                Icon { name: "bookmark".to_string(), class: "w-4 h-4".to_string() }
+                                                            }
                                                             button {
                                                                 class: "p-1.5 rounded text-rose-300 hover:text-rose-200 hover:bg-rose-500/20 transition-colors opacity-100 md:opacity-0 md:group-hover:opacity-100",
                                                                 title: "Delete song",
@@ -1687,6 +1867,29 @@ pub fn DownloadsView() -> Element {
                     }
                 }
 
+                if !pending_downloads.is_empty() {
+                    div { class: "space-y-2",
+                        div { class: "flex items-center justify-between",
+                            p { class: "text-xs uppercase tracking-wider text-zinc-500",
+                                "Queued Downloads"
+                            }
+                            p { class: "text-xs text-zinc-400",
+                                "{pending_downloads.len()} waiting"
+                            }
+                        }
+                        div { class: "max-h-60 overflow-y-auto rounded-xl border border-zinc-700/50 bg-zinc-900/40 p-2 space-y-1",
+                            for entry in pending_downloads.iter().take(DOWNLOADS_PENDING_VISIBLE_LIMIT).cloned() {
+                                PendingDownloadRow {
+                                    key: "pending:{entry.server_id}:{entry.song_id}",
+                                    entry,
+                                    dragged_key: dragged_pending_key,
+                                    refresh_nonce,
+                                }
+                            }
+                        }
+                    }
+                }
+
                 div { class: "grid grid-cols-2 gap-2 pt-2 sm:flex sm:flex-wrap sm:items-center sm:gap-3",
                     button {
                         class: if settings.downloads_enabled { "w-full sm:w-auto px-3 py-2 rounded-lg border border-emerald-500/50 text-emerald-300 text-center flex items-center justify-center gap-2 hover:bg-emerald-500 hover:border-emerald-500 hover:text-white transition-colors" } else { "w-full sm:w-auto px-3 py-2 rounded-lg border border-zinc-700 text-zinc-300 text-center flex items-center justify-center gap-2 hover:bg-zinc-700 hover:border-zinc-500 hover:text-white transition-colors" },
@@ -1728,6 +1931,17 @@ pub fn DownloadsView() -> Element {
                             "Run Now"
                         }
                     }
+                    button {
+                        class: if action_busy() { "w-full sm:w-auto px-3 py-2 rounded-lg border border-zinc-700 text-zinc-500 cursor-not-allowed text-center flex items-center justify-center gap-2" } else { "w-full sm:w-auto px-3 py-2 rounded-lg border border-rose-400/50 text-rose-200 hover:bg-rose-500 hover:border-rose-500 hover:text-white transition-colors text-center flex items-center justify-center gap-2" },
+                        disabled: action_busy() || !native_downloads_supported,
+                        title: "Download your favorite songs, up to the auto-download tier limit",
+                        onclick: on_download_favorites,
+                        Icon {
+                            name: "heart".to_string(),
+                            class: "w-4 h-4".to_string(),
+                        }
+                        "Download Favorites"
+                    }
                     button {
                         class: if action_busy() { "w-full sm:w-auto px-3 py-2 rounded-lg border border-zinc-700 text-zinc-500 cursor-not-allowed text-center flex items-center justify-center gap-2" } else { "w-full sm:w-auto px-3 py-2 rounded-lg border border-cyan-500/50 text-cyan-300 hover:bg-cyan-500 hover:border-cyan-500 hover:text-white transition-colors text-center flex items-center justify-center gap-2" },
                         disabled: action_busy(),
@@ -1742,6 +1956,17 @@ pub fn DownloadsView() -> Element {
                             "Refresh"
                         }
                     }
+                    button {
+                        class: if action_busy() { "w-full sm:w-auto px-3 py-2 rounded-lg border border-zinc-700 text-zinc-500 cursor-not-allowed text-center flex items-center justify-center gap-2" } else { "w-full sm:w-auto px-3 py-2 rounded-lg border border-amber-500/50 text-amber-300 hover:bg-amber-500 hover:border-amber-500 hover:text-white transition-colors text-center flex items-center justify-center gap-2" },
+                        disabled: action_busy() || !native_downloads_supported,
+                        title: "Check downloaded files and drop the missing or damaged ones",
+                        onclick: on_verify_downloads,
+                        Icon {
+                            name: "check".to_string(),
+                            class: "w-4 h-4".to_string(),
+                        }
+                        "Verify"
+                    }
                     button {
                         class: if action_busy() { "w-full sm:w-auto px-3 py-2 rounded-lg border border-zinc-700 text-zinc-500 cursor-not-allowed text-center flex items-center justify-center gap-2" } else { "w-full sm:w-auto px-3 py-2 rounded-lg border border-zinc-700 text-zinc-300 hover:bg-zinc-700 hover:border-zinc-500 hover:text-white transition-colors text-center flex items-center justify-center gap-2" },
                         disabled: action_busy(),
@@ -1766,7 +1991,7 @@ pub fn DownloadsView() -> Element {
                         "Clear"
                     }
                 }
-                if let Some(status) = action_status() {
+                if let Some(status) = action_status_line.clone() {
                     p { class: "text-xs text-zinc-400 mt-3", "{status}" }
                 }
             }
@@ -1807,3 +2032,92 @@ pub fn DownloadsView() -> Element {
         }
     }
 }
+
+/// One song waiting in a download batch. Rows can be dragged onto each other to reorder the
+/// list, and "Next" moves the song, or its whole album, to the front.
+#[component]
+fn PendingDownloadRow(
+    entry: PendingDownloadEntry,
+    mut dragged_key: Signal<Option<String>>,
+    mut refresh_nonce: Signal<u64>,
+) -> Element {
+    let key = entry.key();
+    let group_key = entry.group_key();
+    let is_dragged = dragged_key().as_deref() == Some(key.as_str());
+    let row_class = if is_dragged {
+        "flex items-center gap-2 px-2 py-1.5 rounded-lg bg-emerald-500/10 border border-emerald-500/40 opacity-60"
+    } else {
+        "flex items-center gap-2 px-2 py-1.5 rounded-lg bg-zinc-900/50 border border-transparent"
+    };
+    let subtitle = match (&entry.artist, &entry.album) {
+        (Some(artist), Some(album)) => format!("{artist} · {album}"),
+        (Some(artist), None) => artist.clone(),
+        (None, Some(album)) => album.clone(),
+        (None, None) => "Unknown artist".to_string(),
+    };
+
+    rsx! {
+        div {
+            class: "{row_class}",
+            draggable: "true",
+            ondragstart: {
+                let key = key.clone();
+                move |_| dragged_key.set(Some(key.clone()))
+            },
+            ondragend: move |_| dragged_key.set(None),
+            ondragover: move |evt: DragEvent| evt.prevent_default(),
+            ondrop: {
+                let key = key.clone();
+                move |evt: DragEvent| {
+                    evt.prevent_default();
+                    if let Some(source) = dragged_key() {
+                        if reorder_pending_download(&source, &key) {
+                            refresh_nonce.with_mut(|nonce| *nonce = nonce.saturating_add(1));
+                        }
+                    }
+                    dragged_key.set(None);
+                }
+            },
+            Icon {
+                name: "menu".to_string(),
+                class: "w-4 h-4 text-zinc-600 flex-shrink-0 cursor-grab".to_string(),
+            }
+            div { class: "min-w-0 flex-1",
+                p { class: "text-sm text-zinc-200 truncate", "{entry.title}" }
+                p { class: "text-xs text-zinc-500 truncate", "{subtitle}" }
+            }
+            if entry.boosted {
+                span {
+                    class: "text-[10px] uppercase tracking-wider text-emerald-300 flex-shrink-0",
+                    title: "Coming up in the play queue",
+                    "Up next"
+                }
+            }
+            button {
+                class: "px-2 py-1 rounded-md border border-zinc-700 text-xs text-zinc-300 hover:text-white hover:border-emerald-500/60 transition-colors flex-shrink-0",
+                title: "Download this song next",
+                onclick: {
+                    let key = key.clone();
+                    move |_| {
+                        if prioritize_pending_download(&key) {
+                            refresh_nonce.with_mut(|nonce| *nonce = nonce.saturating_add(1));
+                        }
+                    }
+                },
+                "Next"
+            }
+            if let Some(group_key) = group_key {
+                button {
+                    class: "px-2 py-1 rounded-md border border-zinc-700 text-xs text-zinc-300 hover:text-white hover:border-emerald-500/60 transition-colors flex-shrink-0",
+                    title: "Download this album next",
+                    onclick: move |_| {
+                        if prioritize_pending_group(&group_key) {
+                            refresh_nonce.with_mut(|nonce| *nonce = nonce.saturating_add(1));
+                        }
+                    },
+                    "Album next"
+                }
+            }
+        }
+    }
+}
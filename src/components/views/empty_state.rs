@@ -0,0 +1,55 @@
+use crate::api::ServerConfig;
+use crate::components::{AppView, Icon, Navigation};
+use dioxus::prelude::*;
+
+/// Centered placeholder for a view with nothing to show.
+#[component]
+pub(super) fn EmptyState(
+    icon: String,
+    title: String,
+    #[props(default)] message: Option<String>,
+    #[props(default)] action_label: Option<String>,
+    #[props(default)] on_action: Option<EventHandler<MouseEvent>>,
+) -> Element {
+    rsx! {
+        div { class: "flex flex-col items-center justify-center py-20 text-center",
+            div { class: "w-20 h-20 rounded-2xl bg-zinc-800/50 flex items-center justify-center mb-6",
+                Icon { name: icon, class: "w-10 h-10 text-zinc-500".to_string() }
+            }
+            h2 { class: "text-xl font-semibold text-white mb-2", "{title}" }
+            if let Some(message) = message {
+                p { class: "text-zinc-400 max-w-md", "{message}" }
+            }
+            if let (Some(label), Some(on_action)) = (action_label, on_action) {
+                button {
+                    class: "mt-6 px-6 py-3 bg-emerald-500 hover:bg-emerald-400 text-white font-medium rounded-xl transition-colors",
+                    onclick: move |evt| on_action.call(evt),
+                    "{label}"
+                }
+            }
+        }
+    }
+}
+
+/// Shown instead of a library view when no server is connected or every server is disabled.
+/// Links to server setup in Settings.
+#[component]
+pub(super) fn NoServerEmptyState(icon: String, message: String) -> Element {
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let navigation = use_context::<Navigation>();
+    let (title, action_label) = if servers().is_empty() {
+        ("No servers connected", "Add Server")
+    } else {
+        ("No servers enabled", "Manage servers")
+    };
+
+    rsx! {
+        EmptyState {
+            icon,
+            title: title.to_string(),
+            message,
+            action_label: action_label.to_string(),
+            on_action: move |_| navigation.navigate_to(AppView::SettingsView {}),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use super::empty_state::{EmptyState, NoServerEmptyState};
 use crate::api::*;
 use crate::cache_service::{get_json as cache_get_json, put_json as cache_put_json};
 use crate::components::audio_manager::{
@@ -5,7 +6,7 @@ use crate::components::audio_manager::{
 };
 use crate::components::views::home::{AlbumCard, SongRow};
 use crate::components::views::search::ArtistCard;
-use crate::components::{AppView, Icon, Navigation};
+use crate::components::{use_restored_scroll, use_view_state, AppView, Icon, Navigation};
 use crate::diagnostics::{log_perf, PerfTimer};
 use dioxus::prelude::*;
 use std::collections::HashSet;
@@ -25,8 +26,8 @@ pub fn FavoritesView() -> Element {
     let mut is_playing = use_context::<crate::components::IsPlayingSignal>().0;
     let shuffle_enabled = use_context::<crate::components::ShuffleEnabledSignal>().0;
 
-    let mut active_tab = use_signal(|| "songs".to_string());
-    let mut display_limit = use_signal(|| FAVORITES_INITIAL_LIMIT);
+    let mut active_tab = use_view_state("active_tab", || "songs".to_string());
+    let mut display_limit = use_view_state("display_limit", || FAVORITES_INITIAL_LIMIT);
 
     let active_servers: Vec<ServerConfig> = servers().into_iter().filter(|s| s.active).collect();
 
@@ -115,8 +116,10 @@ pub fn FavoritesView() -> Element {
             payload
         }
     });
+    use_restored_scroll(move || favorites.read().is_some());
 
     let tab = active_tab();
+    let has_active_servers = servers().iter().any(|server| server.active);
 
     rsx! {
         div { class: "space-y-8",
@@ -152,7 +155,12 @@ pub fn FavoritesView() -> Element {
                 }
             }
 
-            {
+            if !has_active_servers {
+                NoServerEmptyState {
+                    icon: "heart".to_string(),
+                    message: "Connect a Navidrome server to see your starred music.".to_string(),
+                }
+            } else {
                 match favorites() {
                     Some((artists, albums, songs)) => rsx! {
                         {
@@ -322,13 +330,10 @@ pub fn FavoritesView() -> Element {
 #[component]
 fn EmptyFavorites(item_type: String) -> Element {
     rsx! {
-        div { class: "flex flex-col items-center justify-center py-20",
-            Icon {
-                name: "heart".to_string(),
-                class: "w-16 h-16 text-zinc-600 mb-4".to_string(),
-            }
-            h2 { class: "text-xl font-semibold text-white mb-2", "No favorite {item_type}" }
-            p { class: "text-zinc-400", "Star {item_type} in your Navidrome server to see them here" }
+        EmptyState {
+            icon: "heart".to_string(),
+            title: format!("No favorite {item_type}"),
+            message: format!("Star {item_type} with the heart button to see them here."),
         }
     }
 }
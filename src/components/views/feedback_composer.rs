@@ -0,0 +1,319 @@
+//! "Send feedback" dialog: collects a description, an optional screenshot of the current
+//! view and the recent diagnostics, shows the exact issue text for review, and opens a
+//! pre-filled GitHub issue in the browser.
+use crate::api::ServerConfig;
+use crate::components::song_details::HTML2CANVAS_BUNDLE;
+use crate::components::{Icon, ServerCapabilitiesSignal};
+use crate::feedback::{
+    current_platform, redact_line, redacted_recent_diagnostics, FeedbackReport, IssueLink,
+};
+use dioxus::prelude::*;
+
+const FEEDBACK_ISSUE_TITLE: &str = "Feedback: ";
+
+/// Renders the page content (`main`) with the bundled html2canvas into a PNG data URL;
+/// empty when the capture fails.
+fn capture_view_script() -> String {
+    let source = serde_json::to_string(HTML2CANVAS_BUNDLE).unwrap_or_else(|_| "\"\"".to_string());
+    format!(
+        r##"return (async function () {{
+            try {{
+                if (typeof window.html2canvas !== "function") {{
+                    (0, eval)({source});
+                }}
+                const target = document.querySelector("main");
+                if (!target || typeof window.html2canvas !== "function") return "";
+                const canvas = await window.html2canvas(target, {{
+                    backgroundColor: "#09090b",
+                    useCORS: true,
+                    logging: false,
+                    scale: 1,
+                }});
+                return canvas.toDataURL("image/png");
+            }} catch (_err) {{
+                return "";
+            }}
+        }})();"##
+    )
+}
+
+fn copy_text(text: &str) {
+    let payload = serde_json::to_string(text).unwrap_or_else(|_| "\"\"".to_string());
+    let _ = document::eval(&format!(
+        "navigator.clipboard && navigator.clipboard.writeText({payload});"
+    ));
+}
+
+/// Puts a PNG data URL on the clipboard as an image, so it can be pasted into the issue.
+async fn copy_image(data_url: &str) -> bool {
+    let payload = serde_json::to_string(data_url).unwrap_or_else(|_| "\"\"".to_string());
+    let eval = document::eval(&format!(
+        r#"return (async function () {{
+            try {{
+                const blob = await (await fetch({payload})).blob();
+                await navigator.clipboard.write([new ClipboardItem({{ [blob.type]: blob }})]);
+                return true;
+            }} catch (_err) {{
+                return false;
+            }}
+        }})();"#
+    ));
+    eval.join::<bool>().await.unwrap_or(false)
+}
+
+fn open_in_browser(url: &str) {
+    let payload = serde_json::to_string(url).unwrap_or_else(|_| "\"\"".to_string());
+    let _ = document::eval(&format!(
+        "window.open({payload}, \"_blank\", \"noopener\");"
+    ));
+}
+
+#[component]
+pub(super) fn FeedbackComposer(on_close: EventHandler<()>) -> Element {
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
+
+    let mut description = use_signal(String::new);
+    let mut include_diagnostics = use_signal(|| true);
+    let mut screenshot = use_signal(|| None::<String>);
+    let mut capturing = use_signal(|| false);
+    let mut reviewed = use_signal(|| false);
+    let mut status = use_signal(|| None::<String>);
+    let mut sent = use_signal(|| None::<IssueLink>);
+    // Taken once so the preview can't change between review and submit.
+    let diagnostics = use_hook(|| redacted_recent_diagnostics(&servers.peek()));
+    let diagnostics_count = diagnostics.len();
+
+    let report = use_memo(move || {
+        let servers = servers();
+        let caps = capabilities.capabilities.read();
+        let server_flavors = servers
+            .iter()
+            .filter(|server| server.active)
+            .filter_map(|server| caps.get(&server.id).map(|entry| entry.flavor))
+            .collect();
+        FeedbackReport {
+            description: redact_line(&description(), &servers),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            platform: current_platform(),
+            server_flavors,
+            diagnostics: if include_diagnostics() {
+                diagnostics.clone()
+            } else {
+                Vec::new()
+            },
+            has_screenshot: screenshot().is_some(),
+        }
+    });
+    let preview = use_memo(move || report().issue_body());
+
+    let on_capture = move |_| {
+        if capturing() {
+            return;
+        }
+        capturing.set(true);
+        spawn(async move {
+            let data_url = document::eval(&capture_view_script())
+                .join::<String>()
+                .await
+                .unwrap_or_default();
+            if data_url.starts_with("data:image/") {
+                screenshot.set(Some(data_url));
+                status.set(None);
+            } else {
+                status.set(Some("Couldn't capture the current view.".to_string()));
+            }
+            capturing.set(false);
+        });
+    };
+
+    let on_submit = move |_| {
+        if !reviewed() {
+            return;
+        }
+        let report = report();
+        let title = format!(
+            "{FEEDBACK_ISSUE_TITLE}{}",
+            summary_title(&report.description)
+        );
+        let link = report.issue_link(&title);
+        let screenshot = screenshot();
+        match (&link.clipboard_body, screenshot) {
+            (Some(body), _) => {
+                copy_text(body);
+                status.set(Some(
+                    "The report was too long for the link and is on your clipboard; paste it into the issue."
+                        .to_string(),
+                ));
+            }
+            (None, Some(data_url)) => {
+                spawn(async move {
+                    let message = if copy_image(&data_url).await {
+                        "The screenshot is on your clipboard; paste it into the issue."
+                    } else {
+                        "Couldn't copy the screenshot; use Copy screenshot to try again."
+                    };
+                    status.set(Some(message.to_string()));
+                });
+            }
+            (None, None) => status.set(None),
+        }
+        open_in_browser(&link.url);
+        sent.set(Some(link));
+    };
+
+    let on_copy_screenshot = move |_| {
+        let Some(data_url) = screenshot() else {
+            return;
+        };
+        spawn(async move {
+            let message = if copy_image(&data_url).await {
+                "Screenshot copied."
+            } else {
+                "Couldn't copy the screenshot."
+            };
+            status.set(Some(message.to_string()));
+        });
+    };
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-[210] bg-zinc-950/95 backdrop-blur-sm overflow-y-auto px-4 py-8 flex items-center justify-center",
+            onclick: move |_| on_close.call(()),
+            div {
+                class: "w-full max-w-2xl bg-zinc-900/60 border border-zinc-700/50 rounded-2xl p-6 space-y-4",
+                onclick: move |evt: MouseEvent| evt.stop_propagation(),
+                div { class: "space-y-1",
+                    h2 { class: "text-lg font-semibold text-white", "Send feedback" }
+                    p { class: "text-sm text-zinc-400",
+                        "Opens a pre-filled GitHub issue in your browser. Nothing is sent until you submit it there."
+                    }
+                }
+                textarea {
+                    class: "w-full h-32 px-3 py-2.5 rounded-xl border border-zinc-700/50 bg-zinc-900/60 text-zinc-200 text-sm resize-y focus:outline-none focus:border-emerald-500/50 placeholder-zinc-600",
+                    placeholder: "What happened, and what did you expect?",
+                    value: "{description}",
+                    oninput: move |e| {
+                        description.set(e.value());
+                        reviewed.set(false);
+                    },
+                }
+                div { class: "flex flex-wrap items-center gap-2",
+                    button {
+                        class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors text-sm flex items-center gap-2 disabled:opacity-50",
+                        disabled: capturing(),
+                        onclick: on_capture,
+                        if capturing() {
+                            Icon { name: "loader".to_string(), class: "w-4 h-4 animate-spin".to_string() }
+                        }
+                        if screenshot().is_some() {
+                            "Retake screenshot"
+                        } else {
+                            "Add screenshot of this view"
+                        }
+                    }
+                    if screenshot().is_some() {
+                        button {
+                            class: "px-3 py-1.5 rounded-lg text-zinc-400 hover:text-white transition-colors text-sm",
+                            onclick: move |_| {
+                                screenshot.set(None);
+                                reviewed.set(false);
+                            },
+                            "Remove screenshot"
+                        }
+                    }
+                    label { class: "flex items-center gap-2 text-sm text-zinc-300 cursor-pointer ml-auto",
+                        input {
+                            r#type: "checkbox",
+                            checked: include_diagnostics(),
+                            onchange: move |e| {
+                                include_diagnostics.set(e.checked());
+                                reviewed.set(false);
+                            },
+                        }
+                        "Include the last {diagnostics_count} diagnostics lines"
+                    }
+                }
+                if let Some(data_url) = screenshot() {
+                    img {
+                        class: "max-h-40 rounded-lg border border-zinc-700/50",
+                        src: "{data_url}",
+                        alt: "Screenshot of the current view",
+                    }
+                }
+                div { class: "space-y-2",
+                    p { class: "text-sm font-medium text-white", "Preview" }
+                    p { class: "text-xs text-zinc-500",
+                        "This is the full issue text. Server addresses, names and credentials are replaced before it is shown."
+                    }
+                    pre { class: "max-h-64 overflow-auto rounded-lg bg-zinc-950/80 border border-zinc-800 p-3 text-[11px] leading-snug text-zinc-300 whitespace-pre-wrap break-words select-text",
+                        "{preview}"
+                    }
+                }
+                label { class: "flex items-center gap-2 text-sm text-zinc-300 cursor-pointer",
+                    input {
+                        r#type: "checkbox",
+                        checked: reviewed(),
+                        onchange: move |e| reviewed.set(e.checked()),
+                    }
+                    "I've reviewed the preview and want to share it"
+                }
+                if let Some(message) = status() {
+                    p { class: "text-xs text-emerald-300", "{message}" }
+                }
+                div { class: "flex flex-wrap items-center gap-2",
+                    button {
+                        class: "px-3 py-1.5 rounded-lg bg-emerald-500/15 text-emerald-300 hover:bg-emerald-500/25 transition-colors text-sm disabled:opacity-50",
+                        disabled: !reviewed(),
+                        onclick: on_submit,
+                        "Open GitHub issue"
+                    }
+                    if let Some(link) = sent() {
+                        if let Some(body) = link.clipboard_body.clone() {
+                            button {
+                                class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white transition-colors text-sm",
+                                onclick: move |_| {
+                                    copy_text(&body);
+                                    status.set(Some("Report copied.".to_string()));
+                                },
+                                "Copy report"
+                            }
+                        }
+                        if screenshot().is_some() {
+                            button {
+                                class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white transition-colors text-sm",
+                                onclick: on_copy_screenshot,
+                                "Copy screenshot"
+                            }
+                        }
+                    }
+                    button {
+                        class: "px-3 py-1.5 rounded-lg text-zinc-400 hover:text-white transition-colors text-sm",
+                        onclick: move |_| on_close.call(()),
+                        if sent().is_some() {
+                            "Done"
+                        } else {
+                            "Cancel"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// First line of the description, shortened for the issue title.
+fn summary_title(description: &str) -> String {
+    let first_line = description
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty());
+    let Some(line) = first_line else {
+        return "(no summary)".to_string();
+    };
+    let mut title: String = line.chars().take(72).collect();
+    if title.len() < line.len() {
+        title.push('…');
+    }
+    title
+}
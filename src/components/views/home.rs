@@ -1,4 +1,5 @@
 use super::artist_links::{parse_artist_names, resolve_artist_id_for_name, ArtistNameLinks};
+use super::empty_state::NoServerEmptyState;
 use super::home_layout::{
     parse_home_layout_settings, serialize_home_layout_settings, HomeAlbumSectionConfig,
     HomeAlbumSource, HomeFeedLoadProfile, HomeLayoutSettings, HomeQuickPicksLayout,
@@ -6,14 +7,17 @@ use super::home_layout::{
     HomeSortDirection, HomeTopStripMode,
 };
 use crate::api::*;
+use crate::collation::collate;
 use crate::components::audio_manager::{
     apply_collection_shuffle_mode, assign_collection_queue_meta,
 };
 use crate::components::{
-    ios_audio_log_snapshot, ios_diag_log, AddIntent, AddMenuController, AppView, HomeFeedState,
-    HomeRefreshSignal, Icon, Navigation,
+    ios_audio_log_snapshot, ios_diag_log, AddIntent, AddMenuController, AddTarget, AppView,
+    FavoritesShuffleController, HomeFeedState, HomeRefreshSignal, Icon, Navigation,
+    QueueDragController, ServerCapabilitiesSignal, SettingsController,
 };
-use crate::db::{save_settings, AppSettings};
+use crate::components::{sized_cover_art_url, ArtworkKind};
+use crate::db::AppSettings;
 use crate::offline_audio::{
     download_songs_batch, is_album_downloaded, is_song_downloaded, mark_collection_downloaded,
     prefetch_song_audio, sync_downloaded_collection_members,
@@ -158,6 +162,7 @@ fn build_album_section_items(
     source: HomeAlbumSource,
     direction: HomeSortDirection,
     min_rating: u8,
+    ignore_articles: bool,
     recent: &[Album],
     most_played: &[Album],
 ) -> Vec<Album> {
@@ -176,13 +181,7 @@ fn build_album_section_items(
         HomeAlbumSource::AtoZ => {
             let mut sorted = merged;
             sorted.sort_by(|left, right| {
-                compare_direction(
-                    left.name
-                        .to_ascii_lowercase()
-                        .cmp(&right.name.to_ascii_lowercase())
-                        .then_with(|| left.name.cmp(&right.name)),
-                    direction,
-                )
+                compare_direction(collate(&left.name, &right.name, ignore_articles), direction)
             });
             sorted
         }
@@ -193,11 +192,7 @@ fn build_album_section_items(
                     left.user_rating
                         .unwrap_or(0)
                         .cmp(&right.user_rating.unwrap_or(0))
-                        .then_with(|| {
-                            left.name
-                                .to_ascii_lowercase()
-                                .cmp(&right.name.to_ascii_lowercase())
-                        }),
+                        .then_with(|| collate(&left.name, &right.name, ignore_articles)),
                     direction,
                 )
             });
@@ -259,13 +254,7 @@ fn build_song_section_items(
         HomeSongSource::AtoZ => {
             let mut sorted = merged;
             sorted.sort_by(|left, right| {
-                compare_direction(
-                    left.title
-                        .to_ascii_lowercase()
-                        .cmp(&right.title.to_ascii_lowercase())
-                        .then_with(|| left.title.cmp(&right.title)),
-                    direction,
-                )
+                compare_direction(collate(&left.title, &right.title, false), direction)
             });
             sorted
         }
@@ -276,11 +265,7 @@ fn build_song_section_items(
                     left.user_rating
                         .unwrap_or(0)
                         .cmp(&right.user_rating.unwrap_or(0))
-                        .then_with(|| {
-                            left.title
-                                .to_ascii_lowercase()
-                                .cmp(&right.title.to_ascii_lowercase())
-                        }),
+                        .then_with(|| collate(&left.title, &right.title, false)),
                     direction,
                 )
             });
@@ -605,6 +590,8 @@ pub fn HomeView() -> Element {
     let mut is_playing = use_context::<crate::components::IsPlayingSignal>().0;
     let home_refresh_generation = use_context::<HomeRefreshSignal>().0;
     let app_settings = use_context::<Signal<AppSettings>>();
+    let settings_controller = use_context::<SettingsController>();
+    let favorites_shuffle = use_context::<FavoritesShuffleController>();
 
     let home_feed = use_context::<HomeFeedState>();
     let recent_albums = home_feed.recent_albums;
@@ -799,6 +786,7 @@ pub fn HomeView() -> Element {
         let mut home_loading_force_unblocked = home_loading_force_unblocked.clone();
         move |_| {
             home_loading_force_unblocked.set(false);
+            navigation.clear_view_states();
             home_refresh_generation.with_mut(|generation| {
                 *generation = generation.saturating_add(1);
             });
@@ -827,7 +815,7 @@ pub fn HomeView() -> Element {
         let mut show_home_editor = show_home_editor.clone();
         let mut home_layout = home_layout.clone();
         let home_layout_draft = home_layout_draft.clone();
-        let mut app_settings = app_settings.clone();
+        let app_settings = app_settings.clone();
         let mut section_visible_overrides = section_visible_overrides.clone();
         let mut home_refresh_generation = home_refresh_generation.clone();
         move |_| {
@@ -841,12 +829,9 @@ pub fn HomeView() -> Element {
             section_visible_overrides.set(HashMap::new());
             show_home_editor.set(false);
 
-            let mut settings = app_settings();
-            settings.home_layout_json = serialized;
-            settings.home_feed_load_profile = next_profile.as_storage().to_string();
-            app_settings.set(settings.clone());
-            spawn(async move {
-                let _ = save_settings(settings).await;
+            settings_controller.update_settings(|settings| {
+                settings.home_layout_json = serialized;
+                settings.home_feed_load_profile = next_profile.as_storage().to_string();
             });
 
             if previous_profile != next_profile {
@@ -933,10 +918,12 @@ return (function () {
         });
     });
 
+    let sort_ignore_articles = app_settings().sort_ignore_articles;
     let top_album_items = build_album_section_items(
         layout_snapshot.top_album_source,
         layout_snapshot.top_album_direction,
         0,
+        sort_ignore_articles,
         &recent_album_items,
         &most_played_album_items,
     );
@@ -985,6 +972,7 @@ return (function () {
                     section.source,
                     section.direction,
                     section.min_rating,
+                    sort_ignore_articles,
                     &recent_album_items,
                     &most_played_album_items,
                 );
@@ -1168,38 +1156,37 @@ return (function () {
             }
 
             // Welcome header
-            header { class: "page-header",
-                h1 { class: "page-title", "Good evening" }
-                p { class: "page-subtitle",
-                    if has_servers {
-                        "Welcome back. Here's what's new in your library."
-                    } else {
-                        "Connect a Navidrome server to get started."
+            header { class: "page-header page-header--split",
+                div {
+                    h1 { class: "page-title", "Good evening" }
+                    p { class: "page-subtitle",
+                        if has_servers {
+                            "Welcome back. Here's what's new in your library."
+                        } else {
+                            "Connect a Navidrome server to get started."
+                        }
+                    }
+                }
+                if has_servers {
+                    button {
+                        class: "px-4 py-2 rounded-xl bg-gradient-to-r from-rose-600 to-pink-600 hover:from-rose-500 hover:to-pink-500 text-white text-sm font-medium transition-colors flex items-center gap-2 disabled:opacity-60",
+                        disabled: favorites_shuffle.is_busy(),
+                        onclick: move |_| favorites_shuffle.start(),
+                        Icon { name: "shuffle".to_string(), class: "w-4 h-4".to_string() }
+                        if favorites_shuffle.is_busy() {
+                            "Shuffling…"
+                        } else {
+                            "Shuffle favorites"
+                        }
                     }
                 }
             }
 
             if !has_servers {
-                // Empty state - no servers
-                div { class: "flex flex-col items-center justify-center py-20",
-                    div { class: "w-20 h-20 rounded-2xl bg-zinc-800/50 flex items-center justify-center mb-6",
-                        Icon {
-                            name: "server".to_string(),
-                            class: "w-10 h-10 text-zinc-500".to_string(),
-                        }
-                    }
-                    h2 { class: "text-xl font-semibold text-white mb-2", "No servers connected" }
-                    p { class: "text-zinc-400 text-center max-w-md mb-6",
-                        "Add your Navidrome server to start streaming your music collection."
-                    }
-                    button {
-                        class: "px-6 py-3 bg-emerald-500 hover:bg-emerald-400 text-white font-medium rounded-xl transition-colors",
-                        onclick: {
-                            let nav = navigation.clone();
-                            move |_| nav.navigate_to(AppView::SettingsView {})
-                        },
-                        "Add Server"
-                    }
+                NoServerEmptyState {
+                    icon: "server".to_string(),
+                    message: "Add your Navidrome server to start streaming your music collection."
+                        .to_string(),
                 }
             } else {
                 if matches!(
@@ -2560,7 +2547,7 @@ fn AlbumHighlightCard(album: Album, onclick: EventHandler<MouseEvent>) -> Elemen
             album
                 .cover_art
                 .as_ref()
-                .map(|cover_art| client.get_cover_art_url(cover_art, 120))
+                .map(|cover_art| sized_cover_art_url(&client, cover_art, ArtworkKind::Album, 48))
         });
     let album_name = album.name.clone();
     let album_artist = album.artist.clone();
@@ -2612,6 +2599,7 @@ fn LoadMoreStripCard(label: String, onclick: EventHandler<MouseEvent>) -> Elemen
 #[component]
 fn SongCard(song: Song, onclick: EventHandler<MouseEvent>) -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let queue_drag = use_context::<QueueDragController>();
     let now_playing = use_context::<Signal<Option<Song>>>();
     let queue = use_context::<Signal<Vec<Song>>>();
     let add_menu = use_context::<AddMenuController>();
@@ -2620,6 +2608,9 @@ fn SongCard(song: Song, onclick: EventHandler<MouseEvent>) -> Element {
     let is_favorited = use_signal(|| song.starred.is_some());
     let mut show_context_menu = use_signal(|| false);
     let download_busy = use_signal(|| false);
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
+    let can_download = capabilities.user_can(&song.server_id, UserAction::Download);
+    let can_rate = capabilities.user_can(&song.server_id, UserAction::Rate);
     let initially_downloaded = is_song_downloaded(&song);
     let downloaded = use_signal(move || initially_downloaded);
     let mut menu_x = use_signal(|| 0f64);
@@ -2632,7 +2623,7 @@ fn SongCard(song: Song, onclick: EventHandler<MouseEvent>) -> Element {
             let client = NavidromeClient::new(server.clone());
             song.cover_art
                 .as_ref()
-                .map(|ca| client.get_cover_art_url(ca, 120))
+                .map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Song, 120))
         });
 
     let make_on_open_menu = {
@@ -2785,6 +2776,9 @@ fn SongCard(song: Song, onclick: EventHandler<MouseEvent>) -> Element {
         div {
             class: "rs-carousel-item relative group text-left cursor-pointer flex-shrink-0 w-full min-w-0",
             style: "width: 100% !important;",
+            draggable: "true",
+            ondragstart: queue_drag.on_drag_start(AddTarget::Song(song.clone())),
+            ondragend: move |_| queue_drag.end(),
             onclick: move |e| {
                 show_context_menu.set(false);
                 onclick.call(e);
@@ -2858,6 +2852,7 @@ fn SongCard(song: Song, onclick: EventHandler<MouseEvent>) -> Element {
                     button {
                         class: if is_favorited() { "p-1 rounded-lg text-emerald-400 hover:text-emerald-300 hover:bg-emerald-500/10 transition-colors" } else { "p-1 rounded-lg text-zinc-500 hover:text-emerald-400 hover:bg-emerald-500/10 transition-colors" },
                         aria_label: if is_favorited() { "Unfavorite" } else { "Favorite" },
+                        disabled: !can_rate,
                         onclick: make_on_toggle_favorite(),
                         Icon {
                             name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
@@ -2881,6 +2876,7 @@ fn SongCard(song: Song, onclick: EventHandler<MouseEvent>) -> Element {
                     onclick: move |evt: MouseEvent| evt.stop_propagation(),
                     button {
                         class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                        disabled: !can_rate,
                         onclick: make_on_toggle_favorite(),
                         Icon {
                             name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
@@ -2902,8 +2898,9 @@ fn SongCard(song: Song, onclick: EventHandler<MouseEvent>) -> Element {
                         }
                     } else {
                         button {
-                            class: if download_busy() { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-500 cursor-not-allowed" } else { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors" },
-                            disabled: download_busy(),
+                            class: if download_busy() || !can_download { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-500 cursor-not-allowed" } else { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors" },
+                            disabled: download_busy() || !can_download,
+                            title: if can_download { "" } else { capabilities.denied_hint(UserAction::Download) },
                             onclick: make_on_download_song(),
                             Icon {
                                 name: if download_busy() { "loader".to_string() } else { "download".to_string() },
@@ -2923,6 +2920,7 @@ fn SongCard(song: Song, onclick: EventHandler<MouseEvent>) -> Element {
                         for i in 1u32..=5u32 {
                             button {
                                 class: "p-1 rounded text-amber-400 hover:text-amber-300 transition-colors",
+                                disabled: !can_rate,
                                 onclick: make_on_set_rating(i),
                                 Icon {
                                     name: if i <= current_rating() { "star-filled".to_string() } else { "star".to_string() },
@@ -2953,10 +2951,20 @@ fn SongCard(song: Song, onclick: EventHandler<MouseEvent>) -> Element {
 }
 
 #[component]
-pub fn AlbumCard(album: Album, onclick: EventHandler<MouseEvent>) -> Element {
+pub fn AlbumCard(
+    album: Album,
+    onclick: EventHandler<MouseEvent>,
+    /// Widest the cover is drawn, in CSS pixels.
+    #[props(default = 180)]
+    art_px: u32,
+    /// Render as a list row with a small cover instead of a grid tile.
+    #[props(default)]
+    as_row: bool,
+) -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
     let navigation = use_context::<Navigation>();
     let add_menu = use_context::<AddMenuController>();
+    let queue_drag = use_context::<QueueDragController>();
     let app_settings = use_context::<Signal<AppSettings>>();
     let now_playing = use_context::<Signal<Option<Song>>>();
     let queue = use_context::<Signal<Vec<Song>>>();
@@ -2965,6 +2973,8 @@ pub fn AlbumCard(album: Album, onclick: EventHandler<MouseEvent>) -> Element {
     let shuffle_enabled = use_context::<crate::components::ShuffleEnabledSignal>().0;
     let is_favorited = use_signal(|| album.starred.is_some());
     let album_rating = use_signal(|| album.user_rating.unwrap_or(0).min(5));
+    let can_rate =
+        use_context::<ServerCapabilitiesSignal>().user_can(&album.server_id, UserAction::Rate);
     let mut show_context_menu = use_signal(|| false);
     let download_busy = use_signal(|| false);
     let downloaded = use_signal(|| is_album_downloaded(&album.server_id, &album.id));
@@ -2979,7 +2989,7 @@ pub fn AlbumCard(album: Album, onclick: EventHandler<MouseEvent>) -> Element {
             album
                 .cover_art
                 .as_ref()
-                .map(|ca| client.get_cover_art_url(ca, 300))
+                .map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Album, art_px))
         });
 
     let on_play_album = {
@@ -3257,9 +3267,215 @@ pub fn AlbumCard(album: Album, onclick: EventHandler<MouseEvent>) -> Element {
         }
     };
 
+    let context_menu = rsx! {
+        if show_context_menu() {
+            div {
+                class: "fixed inset-0 z-[9998]",
+                onclick: move |evt: MouseEvent| {
+                    evt.stop_propagation();
+                    show_context_menu.set(false);
+                },
+            }
+            div {
+                class: "fixed z-[9999] w-52 rounded-xl border border-zinc-700 bg-zinc-900/95 shadow-2xl p-1.5 space-y-1",
+                style: anchored_menu_style(menu_x(), menu_y(), 208.0, 360.0),
+                onclick: move |evt: MouseEvent| evt.stop_propagation(),
+                button {
+                    class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                    onclick: on_view_album_from_menu,
+                    Icon {
+                        name: "album".to_string(),
+                        class: "w-4 h-4".to_string(),
+                    }
+                    "View album"
+                }
+                button {
+                    class: if shuffle_enabled() { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-emerald-300 bg-emerald-500/10 hover:bg-emerald-500/20 transition-colors" } else { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors" },
+                    onclick: on_toggle_shuffle,
+                    Icon {
+                        name: "shuffle".to_string(),
+                        class: if shuffle_enabled() { "w-4 h-4 text-emerald-300".to_string() } else { "w-4 h-4".to_string() },
+                    }
+                    if shuffle_enabled() {
+                        "Shuffle: On"
+                    } else {
+                        "Shuffle: Off"
+                    }
+                }
+                if downloaded() {
+                    div { class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-emerald-300 bg-emerald-500/10",
+                        Icon {
+                            name: "check".to_string(),
+                            class: "w-4 h-4".to_string(),
+                        }
+                        "Downloaded"
+                    }
+                } else {
+                    button {
+                        class: if download_busy() { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-500 cursor-not-allowed" } else { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors" },
+                        disabled: download_busy(),
+                        onclick: make_on_download_album(),
+                        Icon {
+                            name: if download_busy() { "loader".to_string() } else { "download".to_string() },
+                            class: "w-4 h-4".to_string(),
+                        }
+                        if download_busy() {
+                            "Downloading..."
+                        } else {
+                            "Download"
+                        }
+                    }
+                }
+                div { class: "border-t border-zinc-700/60 my-1" }
+                if !album_artist_names.is_empty() {
+                    for artist_name in album_artist_names.iter() {
+                        button {
+                            key: "album-menu-artist-{artist_name}",
+                            class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                            onclick: make_on_view_artist_from_menu_named(artist_name.clone()),
+                            Icon {
+                                name: "artist".to_string(),
+                                class: "w-4 h-4".to_string(),
+                            }
+                            if album_artist_names.len() > 1 {
+                                "View {artist_name}"
+                            } else {
+                                "View artist"
+                            }
+                        }
+                    }
+                }
+                button {
+                    class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                    onclick: on_open_menu_for_context,
+                    Icon {
+                        name: "plus".to_string(),
+                        class: "w-4 h-4".to_string(),
+                    }
+                    "Add to..."
+                }
+                div { class: "px-2.5 pt-1 text-[11px] uppercase tracking-wide text-zinc-500",
+                    "Rating"
+                }
+                div { class: "flex items-center gap-1 px-2 pb-1",
+                    for i in 1u32..=5u32 {
+                        button {
+                            class: "p-1 rounded text-amber-400 hover:text-amber-300 transition-colors",
+                            disabled: !can_rate,
+                            onclick: make_on_set_album_rating(i),
+                            Icon {
+                                name: if i <= album_rating() { "star-filled".to_string() } else { "star".to_string() },
+                                class: "w-3.5 h-3.5".to_string(),
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    if as_row {
+        let year = album.year.map(|year| year.to_string()).unwrap_or_default();
+        return rsx! {
+            div {
+                class: "rs-album-row relative group flex items-center gap-3 px-3 py-2 rounded-xl hover:bg-zinc-800/50 cursor-pointer transition-colors",
+                draggable: "true",
+                ondragstart: queue_drag.on_drag_start(AddIntent::from_album(&album).target),
+                ondragend: move |_| queue_drag.end(),
+                onclick: move |e| {
+                    show_context_menu.set(false);
+                    onclick.call(e);
+                },
+                div { class: "w-12 h-12 rounded-lg bg-zinc-800 overflow-hidden flex-shrink-0",
+                    {
+                        match cover_url {
+                            Some(url) => rsx! {
+                                img { class: "w-full h-full object-cover", src: "{url}" }
+                            },
+                            None => rsx! {
+                                div { class: "w-full h-full flex items-center justify-center bg-gradient-to-br from-zinc-700 to-zinc-800",
+                                    Icon {
+                                        name: "album".to_string(),
+                                        class: "w-5 h-5 text-zinc-500".to_string(),
+                                    }
+                                }
+                            },
+                        }
+                    }
+                }
+                div { class: "min-w-0 flex-1",
+                    p {
+                        class: "font-medium text-white text-sm group-hover:text-emerald-400 transition-colors truncate",
+                        title: "{album.name}",
+                        "{album.name}"
+                    }
+                    div {
+                        class: "max-w-full inline-flex items-center gap-1 text-xs text-zinc-400 truncate",
+                        title: "{album.artist}",
+                        if downloaded() {
+                            Icon {
+                                name: "download".to_string(),
+                                class: "w-3 h-3 text-emerald-400 flex-shrink-0".to_string(),
+                            }
+                        }
+                        ArtistNameLinks {
+                            artist_text: album.artist.clone(),
+                            server_id: album.server_id.clone(),
+                            fallback_artist_id: album.artist_id.clone(),
+                        }
+                    }
+                }
+                span { class: "hidden sm:block w-12 text-sm text-zinc-400 tabular-nums", "{year}" }
+                span { class: "hidden sm:block w-14 text-right text-sm text-zinc-400 tabular-nums",
+                    "{format_duration(album.duration)}"
+                }
+                button {
+                    class: if is_favorited() { "flex-shrink-0 p-1.5 rounded-lg text-emerald-400 hover:text-emerald-300 hover:bg-emerald-500/10 transition-colors" } else { "flex-shrink-0 p-1.5 rounded-lg text-zinc-500 hover:text-emerald-400 hover:bg-emerald-500/10 transition-colors" },
+                    aria_label: if is_favorited() { "Unfavorite album" } else { "Favorite album" },
+                    disabled: !can_rate,
+                    onclick: make_on_toggle_favorite(),
+                    Icon {
+                        name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
+                        class: "w-4 h-4".to_string(),
+                    }
+                }
+                button {
+                    class: "flex-shrink-0 p-2 rounded-full bg-emerald-500/95 text-white hover:bg-emerald-400 transition-colors",
+                    aria_label: "Play album",
+                    title: "Play album",
+                    onclick: on_play_album,
+                    Icon {
+                        name: "play".to_string(),
+                        class: "w-4 h-4 ml-0.5".to_string(),
+                    }
+                }
+                button {
+                    class: "flex-shrink-0 p-2 rounded-full text-zinc-400 hover:text-white hover:bg-zinc-800 transition-colors",
+                    aria_label: "Album options",
+                    title: "More options",
+                    onclick: move |evt: MouseEvent| {
+                        evt.stop_propagation();
+                        let coords = evt.client_coordinates();
+                        menu_x.set(coords.x);
+                        menu_y.set(coords.y);
+                        show_context_menu.set(!show_context_menu());
+                    },
+                    Icon {
+                        name: "more-horizontal".to_string(),
+                        class: "w-4 h-4".to_string(),
+                    }
+                }
+                {context_menu}
+            }
+        };
+    }
+
     rsx! {
         div {
             class: "rs-album-card relative group text-left cursor-pointer w-full",
+            draggable: "true",
+            ondragstart: queue_drag.on_drag_start(AddIntent::from_album(&album).target),
+            ondragend: move |_| queue_drag.end(),
             onclick: move |e| {
                 show_context_menu.set(false);
                 onclick.call(e);
@@ -3321,6 +3537,7 @@ pub fn AlbumCard(album: Album, onclick: EventHandler<MouseEvent>) -> Element {
                 button {
                     class: if is_favorited() { "flex-shrink-0 p-1 rounded-lg text-emerald-400 hover:text-emerald-300 hover:bg-emerald-500/10 transition-colors" } else { "flex-shrink-0 p-1 rounded-lg text-zinc-500 hover:text-emerald-400 hover:bg-emerald-500/10 transition-colors" },
                     aria_label: if is_favorited() { "Unfavorite album" } else { "Favorite album" },
+                    disabled: !can_rate,
                     onclick: make_on_toggle_favorite(),
                     Icon {
                         name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
@@ -3347,110 +3564,7 @@ pub fn AlbumCard(album: Album, onclick: EventHandler<MouseEvent>) -> Element {
                     separator_class: "text-zinc-500".to_string(),
                 }
             }
-            // Context menu
-            if show_context_menu() {
-                div {
-                    class: "fixed inset-0 z-[9998]",
-                    onclick: move |evt: MouseEvent| {
-                        evt.stop_propagation();
-                        show_context_menu.set(false);
-                    },
-                }
-                div {
-                    class: "fixed z-[9999] w-52 rounded-xl border border-zinc-700 bg-zinc-900/95 shadow-2xl p-1.5 space-y-1",
-                    style: anchored_menu_style(menu_x(), menu_y(), 208.0, 360.0),
-                    onclick: move |evt: MouseEvent| evt.stop_propagation(),
-                    button {
-                        class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
-                        onclick: on_view_album_from_menu,
-                        Icon {
-                            name: "album".to_string(),
-                            class: "w-4 h-4".to_string(),
-                        }
-                        "View album"
-                    }
-                    button {
-                        class: if shuffle_enabled() { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-emerald-300 bg-emerald-500/10 hover:bg-emerald-500/20 transition-colors" } else { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors" },
-                        onclick: on_toggle_shuffle,
-                        Icon {
-                            name: "shuffle".to_string(),
-                            class: if shuffle_enabled() { "w-4 h-4 text-emerald-300".to_string() } else { "w-4 h-4".to_string() },
-                        }
-                        if shuffle_enabled() {
-                            "Shuffle: On"
-                        } else {
-                            "Shuffle: Off"
-                        }
-                    }
-                    if downloaded() {
-                        div { class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-emerald-300 bg-emerald-500/10",
-                            Icon {
-                                name: "check".to_string(),
-                                class: "w-4 h-4".to_string(),
-                            }
-                            "Downloaded"
-                        }
-                    } else {
-                        button {
-                            class: if download_busy() { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-500 cursor-not-allowed" } else { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors" },
-                            disabled: download_busy(),
-                            onclick: make_on_download_album(),
-                            Icon {
-                                name: if download_busy() { "loader".to_string() } else { "download".to_string() },
-                                class: "w-4 h-4".to_string(),
-                            }
-                            if download_busy() {
-                                "Downloading..."
-                            } else {
-                                "Download"
-                            }
-                        }
-                    }
-                    div { class: "border-t border-zinc-700/60 my-1" }
-                    if !album_artist_names.is_empty() {
-                        for artist_name in album_artist_names.iter() {
-                            button {
-                                key: "album-menu-artist-{artist_name}",
-                                class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
-                                onclick: make_on_view_artist_from_menu_named(artist_name.clone()),
-                                Icon {
-                                    name: "artist".to_string(),
-                                    class: "w-4 h-4".to_string(),
-                                }
-                                if album_artist_names.len() > 1 {
-                                    "View {artist_name}"
-                                } else {
-                                    "View artist"
-                                }
-                            }
-                        }
-                    }
-                    button {
-                        class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
-                        onclick: on_open_menu_for_context,
-                        Icon {
-                            name: "plus".to_string(),
-                            class: "w-4 h-4".to_string(),
-                        }
-                        "Add to..."
-                    }
-                    div { class: "px-2.5 pt-1 text-[11px] uppercase tracking-wide text-zinc-500",
-                        "Rating"
-                    }
-                    div { class: "flex items-center gap-1 px-2 pb-1",
-                        for i in 1u32..=5u32 {
-                            button {
-                                class: "p-1 rounded text-amber-400 hover:text-amber-300 transition-colors",
-                                onclick: make_on_set_album_rating(i),
-                                Icon {
-                                    name: if i <= album_rating() { "star-filled".to_string() } else { "star".to_string() },
-                                    class: "w-3.5 h-3.5".to_string(),
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+            {context_menu}
         }
     }
 }
@@ -3466,10 +3580,14 @@ pub fn SongRow(
     #[props(default)] show_duration_in_menu: bool,
 ) -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let queue_drag = use_context::<QueueDragController>();
     let navigation = use_context::<Navigation>();
     let queue = use_context::<Signal<Vec<Song>>>();
     let add_menu = use_context::<AddMenuController>();
     let app_settings = use_context::<Signal<AppSettings>>();
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
+    let can_download = capabilities.user_can(&song.server_id, UserAction::Download);
+    let can_rate = capabilities.user_can(&song.server_id, UserAction::Rate);
     let current_rating = use_signal(|| song.user_rating.unwrap_or(0).min(5));
     let is_favorited = use_signal(|| song.starred.is_some());
     let download_busy = use_signal(|| false);
@@ -3486,7 +3604,7 @@ pub fn SongRow(
             let client = NavidromeClient::new(server.clone());
             song.cover_art
                 .as_ref()
-                .map(|ca| client.get_cover_art_url(ca, 80))
+                .map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Song, 48))
         });
 
     let album_id = song.album_id.clone();
@@ -3717,6 +3835,9 @@ pub fn SongRow(
     rsx! {
         div {
             class: "relative w-full flex items-center gap-4 p-3 rounded-xl hover:bg-zinc-800/50 transition-colors group cursor-pointer",
+            draggable: "true",
+            ondragstart: queue_drag.on_drag_start(AddTarget::Song(song.clone())),
+            ondragend: move |_| queue_drag.end(),
             onclick: move |e| {
                 show_mobile_actions.set(false);
                 onclick.call(e);
@@ -3794,6 +3915,7 @@ pub fn SongRow(
                             class: if is_favorited() { "p-1.5 rounded-lg text-emerald-400 hover:text-emerald-300 hover:bg-emerald-500/10 hover:scale-105 hover:-translate-y-0.5 transition-all" } else { "p-1.5 rounded-lg text-zinc-500 hover:text-emerald-400 hover:bg-emerald-500/10 hover:scale-105 hover:-translate-y-0.5 transition-all" },
                             aria_label: if is_favorited() { "Unfavorite" } else { "Favorite" },
                             title: if show_favorite_indicator { if is_favorited() { "Favorited" } else { "Not favorited" } } else if is_favorited() { "Unfavorite" } else { "Favorite" },
+                            disabled: !can_rate,
                             onclick: make_on_toggle_favorite(),
                             Icon {
                                 name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
@@ -3879,8 +4001,9 @@ pub fn SongRow(
                                 }
                             } else {
                                 button {
-                                    class: if download_busy() { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-500 cursor-not-allowed" } else { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors" },
-                                    disabled: download_busy(),
+                                    class: if download_busy() || !can_download { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-500 cursor-not-allowed" } else { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors" },
+                                    disabled: download_busy() || !can_download,
+                                    title: if can_download { "" } else { capabilities.denied_hint(UserAction::Download) },
                                     onclick: make_on_download_song(),
                                     Icon {
                                         name: if download_busy() { "loader".to_string() } else { "download".to_string() },
@@ -3896,6 +4019,7 @@ pub fn SongRow(
                         }
                         button {
                             class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                            disabled: !can_rate,
                             onclick: make_on_toggle_favorite(),
                             Icon {
                                 name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
@@ -3914,6 +4038,7 @@ pub fn SongRow(
                             for i in 1..=5 {
                                 button {
                                     class: "p-1 rounded text-amber-400 hover:text-amber-300 transition-colors",
+                                    disabled: !can_rate,
                                     onclick: make_on_set_rating(i as u32),
                                     Icon {
                                         name: if i <= current_rating() { "star-filled".to_string() } else { "star".to_string() },
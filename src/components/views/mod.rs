@@ -5,19 +5,35 @@ mod artist_detail;
 pub(super) mod artist_links;
 mod artists;
 mod bookmarks;
+mod container_resume;
 mod downloads;
+mod empty_state;
 mod favorites;
+mod feedback_composer;
 mod home;
 pub(super) mod home_layout;
+mod music_folder_filter;
 mod playlist_detail;
+mod playlist_merge;
+mod playlist_unavailable;
 mod playlists;
 mod queue;
+mod queue_follow;
+mod queue_transfer;
 mod radio;
 mod random;
 mod search;
+mod server_compare;
 mod settings;
+mod settings_profiles;
+mod smart_cache_throttle;
+mod smart_cache_warmup;
+mod song_id_remap;
+pub(super) mod song_list_filter;
+mod song_table;
 mod songs;
 mod stats;
+mod tracklist_export;
 
 pub use album_detail::AlbumDetailView;
 pub use albums::{Albums, AlbumsWithGenre};
@@ -0,0 +1,81 @@
+use crate::api::{MusicFolder, NavidromeClient, ServerConfig};
+use crate::components::SettingsController;
+use crate::db::AppSettings;
+use dioxus::prelude::*;
+use std::collections::BTreeMap;
+
+/// Selected music folder per server id, memoized so library resources only rerun when the
+/// folder selection changes rather than on every settings write.
+pub(super) fn use_music_folder_selection() -> Memo<BTreeMap<String, String>> {
+    let app_settings = use_context::<Signal<AppSettings>>();
+    use_memo(move || app_settings().music_folder_selection.clone())
+}
+
+/// Folder selector for the library filter bars. Only servers reporting more than one music
+/// folder get a dropdown; "All folders" clears the saved selection.
+#[component]
+pub(super) fn MusicFolderFilter() -> Element {
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let settings_controller = use_context::<SettingsController>();
+    let selection = use_music_folder_selection();
+
+    let folders_by_server = use_resource(move || {
+        let active_servers: Vec<ServerConfig> =
+            servers().into_iter().filter(|s| s.active).collect();
+        async move {
+            let mut folders_by_server = Vec::<(ServerConfig, Vec<MusicFolder>)>::new();
+            for server in active_servers {
+                let client = NavidromeClient::new(server.clone());
+                if let Ok(folders) = client.get_music_folders().await {
+                    if folders.len() > 1 {
+                        folders_by_server.push((server, folders));
+                    }
+                }
+            }
+            folders_by_server
+        }
+    });
+
+    let Some(folders_by_server) = folders_by_server() else {
+        return rsx! {};
+    };
+    let show_server_names = folders_by_server.len() > 1;
+    let selected = selection();
+
+    rsx! {
+        for (server , folders) in folders_by_server {
+            div { key: "{server.id}", class: "flex items-center gap-1",
+                span { class: "text-xs text-zinc-400 whitespace-nowrap",
+                    if show_server_names {
+                        "{server.name}:"
+                    } else {
+                        "Folder:"
+                    }
+                }
+                select {
+                    class: "px-2 py-1 bg-zinc-800/50 border border-zinc-700/50 rounded text-xs text-white focus:outline-none focus:border-emerald-500/50 min-w-0",
+                    value: selected.get(&server.id).cloned().unwrap_or_default(),
+                    oninput: {
+                        let server_id = server.id.clone();
+                        move |e: FormEvent| {
+                            let folder_id = e.value();
+                            settings_controller.update_settings(|settings| {
+                                if folder_id.is_empty() {
+                                    settings.music_folder_selection.remove(&server_id);
+                                } else {
+                                    settings
+                                        .music_folder_selection
+                                        .insert(server_id.clone(), folder_id);
+                                }
+                            });
+                        }
+                    },
+                    option { value: "", "All folders" }
+                    for folder in folders {
+                        option { key: "{folder.id}", value: "{folder.id}", "{folder.name}" }
+                    }
+                }
+            }
+        }
+    }
+}
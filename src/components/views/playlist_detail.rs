@@ -1,3 +1,12 @@
+use super::playlist_unavailable::UnavailableSongsDialog;
+use super::song_list_filter::{
+    filter_song_order, scroll_to_row, use_song_list_filter, HighlightedText, SongListFilterBar,
+};
+use super::song_table::{
+    song_table_grid_style, sorted_song_order, visible_song_table_columns, SongTableCells,
+    SongTableColumn, SongTableHeader, SongTableSort, SONG_TABLE_ROW_CLASS,
+};
+use super::tracklist_export::TracklistExportDialog;
 use crate::api::*;
 use crate::cache_service::{get_json as cache_get_json, put_json as cache_put_json};
 use crate::components::audio_manager::{
@@ -6,17 +15,24 @@ use crate::components::audio_manager::{
 use crate::components::views::artist_links::{
     parse_artist_names, resolve_artist_id_for_name, ArtistNameLinks,
 };
+use crate::components::views::container_resume::{resume_index, ContainerResumeButton};
 use crate::components::{
-    AddIntent, AddMenuController, AppView, Icon, Navigation, PlaybackPositionSignal,
-    PreviewPlaybackSignal, SeekRequestSignal,
+    seek_to, AddIntent, AddMenuController, AppView, Icon, Navigation, PlaybackPositionSignal,
+    PreviewPlaybackSignal, SeekRequestSignal, ServerCapabilitiesSignal, SettingsController,
+    UnavailableSongsSignal, UNAVAILABLE_SONG_TOOLTIP,
+};
+use crate::components::{sized_cover_art_url, ArtworkKind};
+use crate::db::{
+    playlist_item_annotation_key, AppSettings, ContainerResumePoint, PlaylistItemAnnotation,
+    ResumeContainerKind,
 };
-use crate::db::AppSettings;
 use crate::diagnostics::{log_perf, PerfTimer};
 use crate::offline_audio::{
     download_songs_batch, is_playlist_auto_download_tracked, is_song_downloaded,
     mark_collection_downloaded, mark_playlist_auto_download_tracked, prefetch_song_audio,
     prefetch_song_audio_with_origin, sync_downloaded_collection_members, DownloadOrigin,
 };
+use crate::storage::save_text_export;
 use dioxus::prelude::*;
 use std::cell::RefCell;
 use std::collections::HashSet;
@@ -62,11 +78,89 @@ async fn playlist_search_delay_ms(ms: u64) {
     gloo_timers::future::TimeoutFuture::new(ms as u32).await;
 }
 
+/// Parses a cue such as "3:40", "1:02:03" or plain seconds.
+fn parse_cue_timestamp(text: &str) -> Option<f64> {
+    let mut total = 0.0;
+    for part in text.trim().split(':') {
+        let value = part
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| *value >= 0.0)?;
+        total = total * 60.0 + value;
+    }
+    Some(total)
+}
+
+/// Re-keys a playlist's annotations to `songs` (dropping the entry at `removed` first) and
+/// persists the settings if anything moved.
+fn sync_playlist_annotations(
+    settings_controller: SettingsController,
+    server_id: &str,
+    playlist_id: &str,
+    songs: &[Song],
+    removed: Option<(&str, usize)>,
+) {
+    let song_ids: Vec<String> = songs.iter().map(|song| song.id.clone()).collect();
+    settings_controller.update_settings(|settings| {
+        if let Some((song_id, position)) = removed {
+            settings
+                .playlist_item_annotations
+                .remove(&playlist_item_annotation_key(
+                    server_id,
+                    playlist_id,
+                    song_id,
+                    position,
+                ));
+        }
+        settings.reconcile_playlist_item_annotations(server_id, playlist_id, &song_ids);
+    });
+}
+
+/// Extended M3U for a playlist. Entry notes and cue points are written as comment lines.
+fn playlist_m3u(
+    playlist: &Playlist,
+    songs: &[Song],
+    settings: &AppSettings,
+    client: &NavidromeClient,
+) -> String {
+    let mut out = format!("#EXTM3U\n#PLAYLIST:{}\n", playlist.name);
+    for (position, song) in songs.iter().enumerate() {
+        if let Some(annotation) =
+            settings.playlist_item_annotation(&playlist.server_id, &playlist.id, &song.id, position)
+        {
+            if let Some(cue) = annotation.cue_secs {
+                out.push_str(&format!("# cue: {}\n", format_duration(cue as u32)));
+            }
+            for line in annotation
+                .notes
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+            {
+                out.push_str(&format!("# note: {line}\n"));
+            }
+        }
+        let artist = song.artist.as_deref().unwrap_or("Unknown Artist");
+        out.push_str(&format!(
+            "#EXTINF:{},{} - {}\n{}\n",
+            song.duration,
+            artist,
+            song.title,
+            client.get_stream_url(&song.id)
+        ));
+    }
+    out
+}
+
 #[component]
 fn PlaylistSongRow(
+    /// Playlist filter text whose matches are highlighted.
+    highlight: String,
     song: Song,
     display_index: usize,
     songs: Vec<Song>,
+    playlist_id: String,
     playlist_source_id: String,
     queue: Signal<Vec<Song>>,
     queue_index: Signal<usize>,
@@ -76,13 +170,19 @@ fn PlaylistSongRow(
     add_menu: AddMenuController,
     can_remove_from_playlist: bool,
     on_remove_from_playlist: EventHandler<usize>,
+    table_columns: Vec<SongTableColumn>,
 ) -> Element {
     let navigation = use_context::<Navigation>();
     let app_settings = use_context::<Signal<AppSettings>>();
+    let settings_controller = use_context::<SettingsController>();
     let shuffle_enabled = use_context::<crate::components::ShuffleEnabledSignal>().0;
+    let seek_request = use_context::<SeekRequestSignal>().0;
     let current_rating = use_signal(|| song.user_rating.unwrap_or(0).min(5));
     let is_favorited = use_signal(|| song.starred.is_some());
     let download_busy = use_signal(|| false);
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
+    let can_download = capabilities.user_can(&song.server_id, UserAction::Download);
+    let can_rate = capabilities.user_can(&song.server_id, UserAction::Rate);
     let mut show_mobile_actions = use_signal(|| false);
     let initially_downloaded = is_song_downloaded(&song);
     let downloaded = use_signal(move || initially_downloaded);
@@ -92,6 +192,16 @@ fn PlaylistSongRow(
         .as_ref()
         .map(|current| current.id == song.id)
         .unwrap_or(false);
+    let unavailable = use_context::<UnavailableSongsSignal>().contains(&song);
+    let position = display_index.saturating_sub(1);
+    let annotation = app_settings()
+        .playlist_item_annotation(&song.server_id, &playlist_id, &song.id, position)
+        .cloned();
+    let mut show_annotation = use_signal(|| false);
+    let mut editing_annotation = use_signal(|| false);
+    let mut note_draft = use_signal(String::new);
+    let mut cue_draft = use_signal(String::new);
+    let mut annotation_error = use_signal(|| None::<String>);
 
     let cover_url = servers()
         .iter()
@@ -100,7 +210,7 @@ fn PlaylistSongRow(
             let client = NavidromeClient::new(server.clone());
             song.cover_art
                 .as_ref()
-                .map(|ca| client.get_cover_art_url(ca, 80))
+                .map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Song, 48))
         });
 
     let make_on_open_menu = {
@@ -251,7 +361,7 @@ fn PlaylistSongRow(
         let shuffle_enabled = shuffle_enabled.clone();
         let playlist_source_id = playlist_source_id.clone();
         let app_settings = app_settings.clone();
-        move |_| {
+        move || {
             let settings = app_settings();
             let playable = if settings.offline_mode {
                 songs_for_queue
@@ -289,6 +399,105 @@ fn PlaylistSongRow(
         }
     };
 
+    let mut on_click_wide_row = on_click_row.clone();
+
+    let make_on_edit_annotation = {
+        let annotation = annotation.clone();
+        move || {
+            let annotation = annotation.clone();
+            move |evt: MouseEvent| {
+                evt.stop_propagation();
+                show_mobile_actions.set(false);
+                let annotation = annotation.clone().unwrap_or_default();
+                note_draft.set(annotation.notes);
+                cue_draft.set(
+                    annotation
+                        .cue_secs
+                        .map(|cue| format_duration(cue as u32))
+                        .unwrap_or_default(),
+                );
+                annotation_error.set(None);
+                editing_annotation.set(true);
+                show_annotation.set(true);
+            }
+        }
+    };
+
+    let save_annotation = {
+        let key = playlist_item_annotation_key(&song.server_id, &playlist_id, &song.id, position);
+        move |annotation: PlaylistItemAnnotation| {
+            settings_controller.update_settings(|settings| {
+                if annotation.is_empty() {
+                    settings.playlist_item_annotations.remove(&key);
+                } else {
+                    settings
+                        .playlist_item_annotations
+                        .insert(key.clone(), annotation);
+                }
+            });
+        }
+    };
+
+    let on_save_annotation = {
+        let save_annotation = save_annotation.clone();
+        move |evt: MouseEvent| {
+            evt.stop_propagation();
+            let cue_text = cue_draft();
+            let cue_secs = if cue_text.trim().is_empty() {
+                None
+            } else {
+                match parse_cue_timestamp(&cue_text) {
+                    Some(cue) => Some(cue),
+                    None => {
+                        annotation_error.set(Some("Use a cue like 3:40 or 1:02:03.".to_string()));
+                        return;
+                    }
+                }
+            };
+            save_annotation(PlaylistItemAnnotation {
+                notes: note_draft().trim().to_string(),
+                cue_secs,
+            });
+            annotation_error.set(None);
+            editing_annotation.set(false);
+        }
+    };
+
+    let on_clear_annotation = {
+        let save_annotation = save_annotation.clone();
+        move |evt: MouseEvent| {
+            evt.stop_propagation();
+            save_annotation(PlaylistItemAnnotation::default());
+            editing_annotation.set(false);
+            show_annotation.set(false);
+        }
+    };
+
+    let make_on_play_from_cue = {
+        let on_click_row = on_click_row.clone();
+        let song = song.clone();
+        move |cue: f64| {
+            let mut on_click_row = on_click_row.clone();
+            let song = song.clone();
+            let mut seek_request = seek_request.clone();
+            let mut is_playing = is_playing.clone();
+            move |evt: MouseEvent| {
+                evt.stop_propagation();
+                let already_current = now_playing.peek().as_ref().is_some_and(|current| {
+                    current.id == song.id && current.server_id == song.server_id
+                });
+                if already_current {
+                    seek_to(cue);
+                    is_playing.set(true);
+                } else {
+                    seek_request.set(Some((song.id.clone(), cue)));
+                    on_click_row();
+                }
+            }
+        }
+    };
+    let table_grid_style = song_table_grid_style(&table_columns);
+
     let on_album_cover = {
         let navigation = navigation.clone();
         let album_id = song.album_id.clone();
@@ -375,10 +584,10 @@ fn PlaylistSongRow(
     };
     rsx! {
         div {
-            class: if is_current { "relative w-full flex items-center gap-4 p-3 rounded-xl bg-emerald-500/5 transition-colors group cursor-pointer" } else { "relative w-full flex items-center gap-4 p-3 rounded-xl hover:bg-zinc-800/50 transition-colors group cursor-pointer" },
-            onclick: move |evt| {
+            class: if is_current { "relative w-full flex 2xl:hidden items-center gap-4 p-3 rounded-xl bg-emerald-500/5 transition-colors group cursor-pointer" } else { "relative w-full flex 2xl:hidden items-center gap-4 p-3 rounded-xl hover:bg-zinc-800/50 transition-colors group cursor-pointer" },
+            onclick: move |_| {
                 show_mobile_actions.set(false);
-                on_click_row(evt);
+                on_click_row();
             },
             if is_current {
                 span { class: "w-6 text-sm text-emerald-400",
@@ -414,8 +623,10 @@ fn PlaylistSongRow(
             div { class: "flex-1 min-w-0 text-center md:text-left",
                 div { class: "flex items-center justify-between gap-2 min-w-0",
                     div { class: "flex flex-col min-w-0 flex-1",
-                        p { class: if is_current { "min-w-0 text-sm font-medium text-emerald-400 truncate transition-colors" } else { "min-w-0 text-sm font-medium text-white truncate group-hover:text-emerald-400 transition-colors" },
-                            "{song.title}"
+                        p {
+                            class: if unavailable { "min-w-0 text-sm font-medium text-zinc-500 line-through truncate" } else if is_current { "min-w-0 text-sm font-medium text-emerald-400 truncate transition-colors" } else { "min-w-0 text-sm font-medium text-white truncate group-hover:text-emerald-400 transition-colors" },
+                            title: if unavailable { UNAVAILABLE_SONG_TOOLTIP } else { "" },
+                            HighlightedText { text: song.title.clone(), query: highlight.clone() }
                         }
                         div { class: "mt-1 text-xs text-zinc-400 inline-flex items-center gap-1 justify-center md:justify-start",
                             ArtistNameLinks {
@@ -438,6 +649,7 @@ fn PlaylistSongRow(
                         button {
                             class: if is_favorited() { "p-1.5 rounded-lg text-emerald-400 hover:text-emerald-300 hover:bg-emerald-500/10 transition-colors" } else { "p-1.5 rounded-lg text-zinc-500 hover:text-emerald-400 hover:bg-emerald-500/10 transition-colors" },
                             aria_label: if is_favorited() { "Unfavorite" } else { "Favorite" },
+                            disabled: !can_rate,
                             onclick: make_on_toggle_favorite(),
                             Icon {
                                 name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
@@ -461,127 +673,269 @@ fn PlaylistSongRow(
                         }
                     }
                 }
-                if show_mobile_actions() {
-                    div {
-                        class: "fixed inset-0 z-[9998]",
-                        onclick: move |evt: MouseEvent| {
-                            evt.stop_propagation();
-                            show_mobile_actions.set(false);
-                        },
+            }
+        }
+        div {
+            class: if is_current { "{SONG_TABLE_ROW_CLASS} bg-emerald-500/5 transition-colors group cursor-pointer" } else { "{SONG_TABLE_ROW_CLASS} hover:bg-zinc-800/50 transition-colors group cursor-pointer" },
+            style: "{table_grid_style}",
+            onclick: move |_| {
+                show_mobile_actions.set(false);
+                on_click_wide_row();
+            },
+            SongTableCells {
+                song: song.clone(),
+                columns: table_columns.clone(),
+                display_index,
+                is_current,
+                rating: current_rating(),
+                highlight: highlight.clone(),
+            }
+            div { class: "flex items-center justify-end gap-1",
+                button {
+                    class: if is_favorited() { "p-1.5 rounded-lg text-emerald-400 hover:text-emerald-300 hover:bg-emerald-500/10 transition-colors" } else { "p-1.5 rounded-lg text-zinc-500 hover:text-emerald-400 hover:bg-emerald-500/10 transition-colors" },
+                    aria_label: if is_favorited() { "Unfavorite" } else { "Favorite" },
+                    disabled: !can_rate,
+                    onclick: make_on_toggle_favorite(),
+                    Icon {
+                        name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
+                        class: "w-4 h-4".to_string(),
                     }
-                    div {
-                        class: "fixed z-[9999] w-44 rounded-xl border border-zinc-700 bg-zinc-900/95 shadow-2xl p-1.5 space-y-1",
-                        style: anchored_menu_style(menu_x(), menu_y(), 176.0, 360.0),
-                        onclick: move |evt: MouseEvent| evt.stop_propagation(),
-                        button {
-                            class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
-                            onclick: make_on_open_menu(),
-                            Icon {
-                                name: "plus".to_string(),
-                                class: "w-4 h-4".to_string(),
-                            }
-                            "Add To..."
-                        }
-                        if song.album_id.is_some() {
+                }
+                button {
+                    class: "p-1.5 rounded-lg text-zinc-500 hover:text-emerald-400 hover:bg-emerald-500/10 transition-colors",
+                    aria_label: "Song actions",
+                    onclick: move |evt: MouseEvent| {
+                        evt.stop_propagation();
+                        let coords = evt.client_coordinates();
+                        menu_x.set(coords.x);
+                        menu_y.set(coords.y);
+                        show_mobile_actions.set(!show_mobile_actions());
+                    },
+                    Icon {
+                        name: "more-horizontal".to_string(),
+                        class: "w-4 h-4".to_string(),
+                    }
+                }
+            }
+        }
+        if let Some(annotation) = annotation.clone().filter(|_| !editing_annotation()) {
+            div { class: "pl-12 pr-3 -mt-1 pb-1 text-xs text-zinc-400",
+                button {
+                    class: "inline-flex items-center gap-1.5 max-w-full text-left hover:text-zinc-200 transition-colors",
+                    aria_expanded: show_annotation(),
+                    onclick: move |evt: MouseEvent| {
+                        evt.stop_propagation();
+                        show_annotation.set(!show_annotation());
+                    },
+                    Icon {
+                        name: if show_annotation() { "chevron-up".to_string() } else { "chevron-down".to_string() },
+                        class: "w-3 h-3 flex-shrink-0".to_string(),
+                    }
+                    if let Some(cue) = annotation.cue_secs {
+                        span { class: "text-sky-300 flex-shrink-0", "Cue {format_duration(cue as u32)}" }
+                    }
+                    span { class: if show_annotation() { "whitespace-pre-wrap" } else { "truncate" },
+                        "{annotation.notes}"
+                    }
+                }
+                if show_annotation() {
+                    div { class: "mt-1 flex items-center gap-2",
+                        if let Some(cue) = annotation.cue_secs {
                             button {
-                                class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
-                                onclick: make_on_view_album(),
-                                Icon {
-                                    name: "album".to_string(),
-                                    class: "w-4 h-4".to_string(),
-                                }
-                                "View album"
+                                class: "px-2 py-1 rounded-md border border-sky-500/40 text-sky-300 hover:bg-sky-500/10 transition-colors",
+                                onclick: make_on_play_from_cue(cue),
+                                "Play from cue"
                             }
                         }
-                        if !song_artist_names.is_empty() {
-                            for artist_name in song_artist_names.iter() {
-                                button {
-                                    key: "playlist-row-menu-artist-{song.id}-{artist_name}",
-                                    class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
-                                    onclick: make_on_view_artist_named(artist_name.clone()),
-                                    Icon {
-                                        name: "artist".to_string(),
-                                        class: "w-4 h-4".to_string(),
-                                    }
-                                    if song_artist_names.len() > 1 {
-                                        "View {artist_name}"
-                                    } else {
-                                        "View artist"
-                                    }
-                                }
-                            }
+                        button {
+                            class: "px-2 py-1 rounded-md border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors",
+                            onclick: make_on_edit_annotation(),
+                            "Edit note"
                         }
-                        if downloaded() {
-                            div { class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-emerald-300 bg-emerald-500/10",
-                                Icon {
-                                    name: "check".to_string(),
-                                    class: "w-4 h-4".to_string(),
-                                }
-                                "Downloaded"
-                            }
-                        } else {
-                            button {
-                                class: if download_busy() { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-500 cursor-not-allowed" } else { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors" },
-                                disabled: download_busy(),
-                                onclick: make_on_download_song(),
-                                Icon {
-                                    name: if download_busy() { "loader".to_string() } else { "download".to_string() },
-                                    class: "w-4 h-4".to_string(),
-                                }
-                                if download_busy() {
-                                    "Downloading..."
-                                } else {
-                                    "Download"
-                                }
-                            }
+                    }
+                }
+            }
+        }
+        if editing_annotation() {
+            div {
+                class: "mx-3 mb-2 p-3 rounded-xl bg-zinc-900/60 border border-zinc-800 space-y-2",
+                onclick: move |evt: MouseEvent| evt.stop_propagation(),
+                textarea {
+                    class: "w-full px-3 py-2 rounded-lg bg-zinc-950/60 border border-zinc-800 text-sm text-white placeholder:text-zinc-600 focus:outline-none focus:border-emerald-500/50",
+                    rows: "2",
+                    placeholder: "Notes, e.g. mix out at 3:40, key Am",
+                    value: note_draft,
+                    oninput: move |e| note_draft.set(e.value()),
+                }
+                div { class: "flex flex-wrap items-center gap-2",
+                    input {
+                        class: "w-28 px-3 py-1.5 rounded-lg bg-zinc-950/60 border border-zinc-800 text-sm text-white placeholder:text-zinc-600 focus:outline-none focus:border-emerald-500/50",
+                        placeholder: "Cue (m:ss)",
+                        aria_label: "Cue point",
+                        value: cue_draft,
+                        oninput: move |e| cue_draft.set(e.value()),
+                    }
+                    button {
+                        class: "px-3 py-1.5 rounded-lg bg-emerald-500 hover:bg-emerald-400 text-sm text-white transition-colors",
+                        onclick: on_save_annotation,
+                        "Save"
+                    }
+                    if annotation.is_some() {
+                        button {
+                            class: "px-3 py-1.5 rounded-lg border border-red-500/40 text-sm text-red-300 hover:bg-red-500/10 transition-colors",
+                            onclick: on_clear_annotation,
+                            "Clear"
+                        }
+                    }
+                    button {
+                        class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-sm text-zinc-300 hover:text-white transition-colors",
+                        onclick: move |evt: MouseEvent| {
+                            evt.stop_propagation();
+                            editing_annotation.set(false);
+                        },
+                        "Cancel"
+                    }
+                }
+                if let Some(err) = annotation_error() {
+                    p { class: "text-xs text-red-300", "{err}" }
+                }
+            }
+        }
+        if show_mobile_actions() {
+            div {
+                class: "fixed inset-0 z-[9998]",
+                onclick: move |evt: MouseEvent| {
+                    evt.stop_propagation();
+                    show_mobile_actions.set(false);
+                },
+            }
+            div {
+                class: "fixed z-[9999] w-44 rounded-xl border border-zinc-700 bg-zinc-900/95 shadow-2xl p-1.5 space-y-1",
+                style: anchored_menu_style(menu_x(), menu_y(), 176.0, 360.0),
+                onclick: move |evt: MouseEvent| evt.stop_propagation(),
+                button {
+                    class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                    onclick: make_on_open_menu(),
+                    Icon {
+                        name: "plus".to_string(),
+                        class: "w-4 h-4".to_string(),
+                    }
+                    "Add To..."
+                }
+                if song.album_id.is_some() {
+                    button {
+                        class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                        onclick: make_on_view_album(),
+                        Icon {
+                            name: "album".to_string(),
+                            class: "w-4 h-4".to_string(),
                         }
+                        "View album"
+                    }
+                }
+                if !song_artist_names.is_empty() {
+                    for artist_name in song_artist_names.iter() {
                         button {
+                            key: "playlist-row-menu-artist-{song.id}-{artist_name}",
                             class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
-                            onclick: make_on_toggle_favorite(),
+                            onclick: make_on_view_artist_named(artist_name.clone()),
                             Icon {
-                                name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
+                                name: "artist".to_string(),
                                 class: "w-4 h-4".to_string(),
                             }
-                            if is_favorited() {
-                                "Unfavorite"
+                            if song_artist_names.len() > 1 {
+                                "View {artist_name}"
                             } else {
-                                "Favorite"
+                                "View artist"
                             }
                         }
-                        if can_remove_from_playlist {
-                            button {
-                                class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-red-300 hover:bg-red-500/10 transition-colors",
-                                onclick: make_on_remove_from_playlist(),
-                                Icon {
-                                    name: "trash".to_string(),
-                                    class: "w-4 h-4".to_string(),
-                                }
-                                "Remove"
-                            }
+                    }
+                }
+                if downloaded() {
+                    div { class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-emerald-300 bg-emerald-500/10",
+                        Icon {
+                            name: "check".to_string(),
+                            class: "w-4 h-4".to_string(),
                         }
-                        div { class: "px-2.5 pt-1 text-[11px] uppercase tracking-wide text-zinc-500",
-                            "Rating"
+                        "Downloaded"
+                    }
+                } else {
+                    button {
+                        class: if download_busy() || !can_download { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-500 cursor-not-allowed" } else { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors" },
+                        disabled: download_busy() || !can_download,
+                        title: if can_download { "" } else { capabilities.denied_hint(UserAction::Download) },
+                        onclick: make_on_download_song(),
+                        Icon {
+                            name: if download_busy() { "loader".to_string() } else { "download".to_string() },
+                            class: "w-4 h-4".to_string(),
                         }
-                        div { class: "flex items-center gap-1 px-2 pb-1",
-                            for i in 1..=5 {
-                                button {
-                                    class: "p-1 rounded text-amber-400 hover:text-amber-300 transition-colors",
-                                    onclick: make_on_set_rating(i as u32),
-                                    Icon {
-                                        name: if i <= current_rating() { "star-filled".to_string() } else { "star".to_string() },
-                                        class: "w-3.5 h-3.5".to_string(),
-                                    }
-                                }
-                            }
+                        if download_busy() {
+                            "Downloading..."
+                        } else {
+                            "Download"
                         }
-                        div { class: "px-2.5 pt-1 text-[11px] uppercase tracking-wide text-zinc-500",
-                            "Length"
+                    }
+                }
+                button {
+                    class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                    disabled: !can_rate,
+                    onclick: make_on_toggle_favorite(),
+                    Icon {
+                        name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
+                        class: "w-4 h-4".to_string(),
+                    }
+                    if is_favorited() {
+                        "Unfavorite"
+                    } else {
+                        "Favorite"
+                    }
+                }
+                button {
+                    class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                    onclick: make_on_edit_annotation(),
+                    Icon {
+                        name: "edit".to_string(),
+                        class: "w-4 h-4".to_string(),
+                    }
+                    if annotation.is_some() {
+                        "Edit note"
+                    } else {
+                        "Add note"
+                    }
+                }
+                if can_remove_from_playlist {
+                    button {
+                        class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-red-300 hover:bg-red-500/10 transition-colors",
+                        onclick: make_on_remove_from_playlist(),
+                        Icon {
+                            name: "trash".to_string(),
+                            class: "w-4 h-4".to_string(),
                         }
-                        p { class: "px-2.5 pb-2 text-xs text-zinc-300",
-                            "{format_duration(song.duration)}"
+                        "Remove"
+                    }
+                }
+                div { class: "px-2.5 pt-1 text-[11px] uppercase tracking-wide text-zinc-500",
+                    "Rating"
+                }
+                div { class: "flex items-center gap-1 px-2 pb-1",
+                    for i in 1..=5 {
+                        button {
+                            class: "p-1 rounded text-amber-400 hover:text-amber-300 transition-colors",
+                            disabled: !can_rate,
+                            onclick: make_on_set_rating(i as u32),
+                            Icon {
+                                name: if i <= current_rating() { "star-filled".to_string() } else { "star".to_string() },
+                                class: "w-3.5 h-3.5".to_string(),
+                            }
                         }
                     }
                 }
+                div { class: "px-2.5 pt-1 text-[11px] uppercase tracking-wide text-zinc-500",
+                    "Length"
+                }
+                p { class: "px-2.5 pb-2 text-xs text-zinc-300",
+                    "{format_duration(song.duration)}"
+                }
             }
         }
     }
@@ -601,6 +955,12 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
     let preview_playback = use_context::<PreviewPlaybackSignal>().0;
     let add_menu = use_context::<AddMenuController>();
     let app_settings = use_context::<Signal<AppSettings>>();
+    let settings_controller = use_context::<SettingsController>();
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
+    let can_download = capabilities.user_can(&server_id, UserAction::Download);
+    let can_rate = capabilities.user_can(&server_id, UserAction::Rate);
+    let can_edit_playlist = capabilities.user_can(&server_id, UserAction::EditPlaylist);
+    let unavailable_songs = use_context::<UnavailableSongsSignal>();
     let mut is_favorited = use_signal(|| false);
     let reload = use_signal(|| 0usize);
     let mut song_search = use_signal(String::new);
@@ -620,6 +980,8 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
     let download_busy = use_signal(|| false);
     let download_status = use_signal(|| None::<String>);
     let mut show_playlist_menu = use_signal(|| false);
+    let mut show_tracklist_export = use_signal(|| false);
+    let mut show_unavailable_songs = use_signal(|| false);
     let mut playlist_menu_x = use_signal(|| 0f64);
     let mut playlist_menu_y = use_signal(|| 0f64);
     let mut show_rename_dialog = use_signal(|| false);
@@ -628,6 +990,8 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
     let rename_error = use_signal(|| None::<String>);
     let mut current_playlist_id = use_signal(|| playlist_id.clone());
     let mut current_server_id = use_signal(|| server_id.clone());
+    let table_sort = use_signal(|| None::<SongTableSort>);
+    let mut playlist_filter = use_song_list_filter();
     let playlist_queue_source = format!("{}::{}", server_id.clone(), playlist_id.clone());
 
     use_effect({
@@ -680,7 +1044,7 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
         async move {
             if let Some(server) = server {
                 let client = NavidromeClient::new(server);
-                client.get_playlist(&playlist_id).await.ok()
+                client.get_playlist_checked(&playlist_id).await.ok()
             } else {
                 None
             }
@@ -834,6 +1198,133 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
         }
     };
 
+    let on_play_first_match = {
+        let playlist_queue_source = playlist_queue_source.clone();
+        let app_settings = app_settings.clone();
+        let mut download_status = download_status.clone();
+        move |(songs, start): (Vec<Song>, Song)| {
+            let playable = if app_settings().offline_mode {
+                songs
+                    .into_iter()
+                    .filter(|song| is_song_downloaded(song))
+                    .collect::<Vec<_>>()
+            } else {
+                songs
+            };
+            let Some(index) = playable.iter().position(|song| song.id == start.id) else {
+                download_status.set(Some(
+                    "The first match isn't available for offline playback.".to_string(),
+                ));
+                return;
+            };
+            let playable = assign_collection_queue_meta(
+                playable,
+                QueueSourceKind::Playlist,
+                playlist_queue_source.clone(),
+            );
+            queue.set(playable.clone());
+            queue_index.set(index);
+            now_playing.set(Some(playable[index].clone()));
+            is_playing.set(true);
+            if shuffle_enabled() {
+                let _ = apply_collection_shuffle_mode(
+                    queue.clone(),
+                    queue_index.clone(),
+                    now_playing.clone(),
+                    true,
+                );
+            }
+        }
+    };
+
+    let on_resume = {
+        let playlist_data_ref = playlist_data.clone();
+        let app_settings = app_settings.clone();
+        let mut download_status = download_status.clone();
+        let mut playback_position = playback_position.clone();
+        let mut seek_request = seek_request.clone();
+        move |point: ContainerResumePoint| {
+            let Some(Some((_, songs))) = playlist_data_ref() else {
+                return;
+            };
+            let playable = if app_settings().offline_mode {
+                songs
+                    .iter()
+                    .filter(|song| is_song_downloaded(song))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            } else {
+                songs.clone()
+            };
+            let playable = assign_collection_queue_meta(
+                playable,
+                QueueSourceKind::Playlist,
+                point.source_id.clone(),
+            );
+            let Some(index) = resume_index(&playable, &point) else {
+                download_status.set(Some(
+                    "The resume track isn't available for offline playback.".to_string(),
+                ));
+                return;
+            };
+            let song = playable[index].clone();
+            seek_request.set(Some((song.id.clone(), point.position_secs)));
+            playback_position.set(point.position_secs);
+            queue.set(playable);
+            queue_index.set(index);
+            now_playing.set(Some(song));
+            is_playing.set(true);
+        }
+    };
+
+    let on_export_m3u = {
+        let playlist_data_ref = playlist_data.clone();
+        let app_settings = app_settings.clone();
+        let mut download_status = download_status.clone();
+        move |_: MouseEvent| {
+            show_playlist_menu.set(false);
+            let Some(Some((playlist, songs))) = playlist_data_ref() else {
+                return;
+            };
+            let Some(server) = servers()
+                .into_iter()
+                .find(|server| server.id == playlist.server_id)
+            else {
+                return;
+            };
+            let songs = if song_list().is_empty() {
+                songs
+            } else {
+                song_list()
+            };
+            let m3u = playlist_m3u(
+                &playlist,
+                &songs,
+                &app_settings(),
+                &NavidromeClient::new(server),
+            );
+            let file_stem: String = playlist
+                .name
+                .chars()
+                .map(|ch| {
+                    if ch.is_alphanumeric() || ch == '-' || ch == ' ' {
+                        ch
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            let file_stem = match file_stem.trim() {
+                "" => "playlist",
+                stem => stem,
+            };
+            download_status.set(Some(
+                save_text_export(&format!("{file_stem}.m3u"), &m3u, "audio/x-mpegurl")
+                    .unwrap_or_else(|err| err),
+            ));
+        }
+    };
+
     let on_toggle_shuffle = {
         let mut shuffle_enabled = shuffle_enabled.clone();
         let queue = queue.clone();
@@ -1002,6 +1493,7 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                     .cloned()
                 {
                     let playlist_id = playlist.id.clone();
+                    let server_id = playlist.server_id.clone();
                     let mut song_list = song_list_signal.clone();
                     if song_index >= song_list().len() {
                         return;
@@ -1012,11 +1504,21 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                             .remove_songs_from_playlist(&playlist_id, &[song_index])
                             .await;
                         if result.is_ok() {
+                            let mut removed_song_id = None;
                             song_list.with_mut(|list| {
                                 if song_index < list.len() {
-                                    list.remove(song_index);
+                                    removed_song_id = Some(list.remove(song_index).id);
                                 }
                             });
+                            if let Some(removed_song_id) = removed_song_id {
+                                sync_playlist_annotations(
+                                    settings_controller,
+                                    &server_id,
+                                    &playlist_id,
+                                    &song_list.peek(),
+                                    Some((&removed_song_id, song_index)),
+                                );
+                            }
                         }
                     });
                 }
@@ -1142,6 +1644,13 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                         .find(|s| s.id == playlist.server_id)
                         .cloned()
                     {
+                        sync_playlist_annotations(
+                            settings_controller,
+                            &playlist.server_id,
+                            &playlist.id,
+                            &song_list.peek(),
+                            None,
+                        );
                         let playlist_id = playlist.id.clone();
                         let total_songs = ordered_song_ids.len();
                         let mut reorder_error = reorder_error.clone();
@@ -1254,7 +1763,7 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                     }
                     Err(err) => {
                         rename_busy.set(false);
-                        rename_error.set(Some(err));
+                        rename_error.set(Some(err.to_string()));
                     }
                 }
             });
@@ -1287,7 +1796,7 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                         let client = NavidromeClient::new(server);
                         match client.delete_playlist(&playlist_id).await {
                             Ok(_) => navigation.navigate_to(AppView::PlaylistsView {}),
-                            Err(err) => delete_error_clone.set(Some(err)),
+                            Err(err) => delete_error_clone.set(Some(err.to_string())),
                         }
                         deleting_playlist_clone.set(false);
                     });
@@ -1313,7 +1822,14 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
     };
 
     use_effect(move || {
-        if let Some(Some((_, songs))) = playlist_data() {
+        if let Some(Some((playlist, songs))) = playlist_data() {
+            sync_playlist_annotations(
+                settings_controller,
+                &playlist.server_id,
+                &playlist.id,
+                &songs,
+                None,
+            );
             song_list.set(songs.clone());
         }
     });
@@ -1340,6 +1856,16 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                     } else {
                         song_list()
                     };
+                    let table_order = sorted_song_order(&displayed_songs, table_sort());
+                    let sorted_songs: Vec<Song> = table_order
+                        .iter()
+                        .map(|&index| displayed_songs[index].clone())
+                        .collect();
+                    let filter_query = playlist_filter.query(&displayed_songs);
+                    let visible_order =
+                        filter_song_order(&displayed_songs, &table_order, &filter_query);
+                    let first_match = visible_order.first().copied();
+                    let table_columns = visible_song_table_columns(&app_settings(), "playlist");
                     let cover_url = servers()
                         .iter()
                         .find(|s| s.id == playlist.server_id)
@@ -1348,14 +1874,14 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                             playlist
                                 .cover_art
                                 .as_ref()
-                                .map(|ca| client.get_cover_art_url(ca, 500))
+                                .map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Playlist, 256))
                         });
                     let hide_comment = playlist
                         .comment
                         .as_ref()
                         .map(|c| c.to_lowercase().contains("auto-imported"))
                         .unwrap_or(false);
-                    let editing_allowed = !is_auto_imported;
+                    let editing_allowed = can_edit_playlist && !is_auto_imported;
                     let downloaded_song_count =
                         songs.iter().filter(|song| is_song_downloaded(song)).count();
                     let playlist_fully_downloaded = !songs.is_empty()
@@ -1428,10 +1954,10 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                                             span { class: "hidden md:inline", "Play" }
                                         }
                                         button {
-                                            class: if download_busy() { "col-span-1 p-3 rounded-full border border-zinc-700 text-zinc-500 cursor-not-allowed flex items-center justify-center" } else if playlist_fully_downloaded { "col-span-1 p-3 rounded-full bg-emerald-500 text-white hover:bg-emerald-400 transition-colors flex items-center justify-center" } else { "col-span-1 p-3 rounded-full border border-emerald-500/60 text-emerald-300 hover:text-white hover:border-emerald-400 transition-colors flex items-center justify-center" },
-                                            disabled: download_busy(),
+                                            class: if download_busy() || !can_download { "col-span-1 p-3 rounded-full border border-zinc-700 text-zinc-500 cursor-not-allowed flex items-center justify-center" } else if playlist_fully_downloaded { "col-span-1 p-3 rounded-full bg-emerald-500 text-white hover:bg-emerald-400 transition-colors flex items-center justify-center" } else { "col-span-1 p-3 rounded-full border border-emerald-500/60 text-emerald-300 hover:text-white hover:border-emerald-400 transition-colors flex items-center justify-center" },
+                                            disabled: download_busy() || !can_download,
                                             onclick: on_download_playlist,
-                                            title: if download_busy() { "Downloading playlist" } else if playlist_fully_downloaded { "Playlist fully downloaded" } else { "Download playlist" },
+                                            title: if !can_download { capabilities.denied_hint(UserAction::Download) } else if download_busy() { "Downloading playlist" } else if playlist_fully_downloaded { "Playlist fully downloaded" } else { "Download playlist" },
                                             Icon {
                                                 name: if download_busy() { "loader".to_string() } else if playlist_fully_downloaded { "check".to_string() } else { "download".to_string() },
                                                 class: "w-5 h-5".to_string(),
@@ -1453,6 +1979,7 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                                         }
                                         button {
                                             class: "col-span-1 p-3 rounded-full border border-zinc-700 text-zinc-400 hover:text-emerald-400 hover:border-emerald-500/50 transition-colors flex items-center justify-center",
+                                            disabled: !can_rate,
                                             onclick: on_favorite_toggle,
                                             Icon {
                                                 name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
@@ -1467,6 +1994,11 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                                                 class: "w-5 h-5".to_string(),
                                             }
                                         }
+                                        ContainerResumeButton {
+                                            kind: ResumeContainerKind::Playlist,
+                                            source_id: playlist_queue_source.clone(),
+                                            on_resume,
+                                        }
                                     }
                                     if show_playlist_menu() {
                                         div {
@@ -1568,6 +2100,39 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                                                 }
                                                 "Add to..."
                                             }
+                                            button {
+                                                class: "w-full flex items-center gap-2 px-2.5 py-2.5 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                                                onclick: on_export_m3u,
+                                                Icon {
+                                                    name: "download".to_string(),
+                                                    class: "w-4 h-4".to_string(),
+                                                }
+                                                "Export M3U"
+                                            }
+                                            button {
+                                                class: "w-full flex items-center gap-2 px-2.5 py-2.5 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                                                onclick: move |_: MouseEvent| {
+                                                    show_playlist_menu.set(false);
+                                                    show_tracklist_export.set(true);
+                                                },
+                                                Icon {
+                                                    name: "queue".to_string(),
+                                                    class: "w-4 h-4".to_string(),
+                                                }
+                                                "Export tracklist"
+                                            }
+                                            button {
+                                                class: "w-full flex items-center gap-2 px-2.5 py-2.5 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                                                onclick: move |_: MouseEvent| {
+                                                    show_playlist_menu.set(false);
+                                                    show_unavailable_songs.set(true);
+                                                },
+                                                Icon {
+                                                    name: "alert".to_string(),
+                                                    class: "w-4 h-4".to_string(),
+                                                }
+                                                "Unavailable songs"
+                                            }
                                         }
                                     }
                                 }
@@ -1577,16 +2142,47 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                             }
                         }
 
+                        if !edit_mode() && !displayed_songs.is_empty() {
+                            SongListFilterBar {
+                                value: filter_query.clone(),
+                                match_count: visible_order.len(),
+                                total: displayed_songs.len(),
+                                placeholder: "Filter this playlist".to_string(),
+                                on_input: {
+                                    let displayed_songs = displayed_songs.clone();
+                                    move |text: String| playlist_filter.set(text, &displayed_songs)
+                                },
+                                on_jump: move |_| {
+                                    if let Some(index) = first_match {
+                                        scroll_to_row(&format!("playlist-row-{index}"));
+                                    }
+                                },
+                                on_play_first: {
+                                    let sorted_songs = sorted_songs.clone();
+                                    let first_song =
+                                        first_match.map(|index| displayed_songs[index].clone());
+                                    let mut on_play_first_match = on_play_first_match.clone();
+                                    move |_| {
+                                        if let Some(song) = first_song.clone() {
+                                            on_play_first_match((sorted_songs.clone(), song));
+                                        }
+                                    }
+                                },
+                            }
+                        }
+                        if !edit_mode() {
+                            SongTableHeader { view_key: "playlist".to_string(), sort: table_sort }
+                        }
                         div { class: "space-y-1",
-                            for (index , song) in displayed_songs.iter().enumerate() {
-                                if edit_mode() {
+                            if edit_mode() {
+                                for (index , song) in displayed_songs.iter().enumerate() {
                                     {
                                         let cover_url = servers()
                                             .iter()
                                             .find(|s| s.id == song.server_id)
                                             .and_then(|server| {
                                                 let client = NavidromeClient::new(server.clone());
-                                                song.cover_art.as_ref().map(|ca| client.get_cover_art_url(ca, 80))
+                                                song.cover_art.as_ref().map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Song, 48))
                                             });
                                         let can_move_up = index > 0;
                                         let can_move_down = index + 1 < displayed_songs.len();
@@ -1651,7 +2247,11 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                                                     }
                                                 }
                                                 div { class: "min-w-0 flex-1",
-                                                    p { class: "text-sm text-white truncate", "{song.title}" }
+                                                    p {
+                                                        class: if unavailable_songs.contains(song) { "text-sm text-zinc-500 line-through truncate" } else { "text-sm text-white truncate" },
+                                                        title: if unavailable_songs.contains(song) { UNAVAILABLE_SONG_TOOLTIP } else { "" },
+                                                        "{song.title}"
+                                                    }
                                                     p { class: "text-xs text-zinc-500 truncate",
                                                         "{song.artist.clone().unwrap_or_default()} • {song.album.clone().unwrap_or_default()}"
                                                     }
@@ -1672,25 +2272,33 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                                             }
                                         }
                                     }
-                                } else {
-                                    PlaylistSongRow {
-                                        key: "{song.server_id}:{song.id}:{index}",
-                                        song: song.clone(),
-                                        display_index: index + 1,
-                                        songs: displayed_songs.clone(),
-                                        playlist_source_id: format!(
-                                            "{}::{}",
-                                            playlist.server_id,
-                                            playlist.id
-                                        ),
-                                        queue: queue.clone(),
-                                        queue_index: queue_index.clone(),
-                                        now_playing: now_playing.clone(),
-                                        is_playing: is_playing.clone(),
-                                        servers: servers.clone(),
-                                        add_menu: add_menu.clone(),
-                                        can_remove_from_playlist: editing_allowed,
-                                        on_remove_from_playlist: move |remove_index| on_remove_song(remove_index),
+                                }
+                            } else {
+                                for index in visible_order.iter().copied() {
+                                    div {
+                                        key: "{displayed_songs[index].server_id}:{displayed_songs[index].id}:{index}",
+                                        id: "playlist-row-{index}",
+                                        PlaylistSongRow {
+                                            highlight: filter_query.clone(),
+                                            song: displayed_songs[index].clone(),
+                                            display_index: index + 1,
+                                            songs: sorted_songs.clone(),
+                                            playlist_id: playlist.id.clone(),
+                                            playlist_source_id: format!(
+                                                "{}::{}",
+                                                playlist.server_id,
+                                                playlist.id
+                                            ),
+                                            queue: queue.clone(),
+                                            queue_index: queue_index.clone(),
+                                            now_playing: now_playing.clone(),
+                                            is_playing: is_playing.clone(),
+                                            servers: servers.clone(),
+                                            add_menu: add_menu.clone(),
+                                            can_remove_from_playlist: editing_allowed,
+                                            on_remove_from_playlist: move |remove_index| on_remove_song(remove_index),
+                                            table_columns: table_columns.clone(),
+                                        }
                                     }
                                 }
                             }
@@ -1735,7 +2343,7 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                                                                         .find(|s| s.id == res.server_id)
                                                                         .and_then(|server| {
                                                                             let client = NavidromeClient::new(server.clone());
-                                                                            res.cover_art.as_ref().map(|ca| client.get_cover_art_url(ca, 80))
+                                                                            res.cover_art.as_ref().map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Song, 48))
                                                                         });
                                                                     let cover_album_id = res.album_id.clone();
                                                                     let cover_server_id = res.server_id.clone();
@@ -1887,7 +2495,7 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                                                             .find(|s| s.id == res.server_id)
                                                             .and_then(|server| {
                                                                 let client = NavidromeClient::new(server.clone());
-                                                                res.cover_art.as_ref().map(|ca| client.get_cover_art_url(ca, 80))
+                                                                res.cover_art.as_ref().map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Song, 48))
                                                             });
                                                         let cover_album_id = res.album_id.clone();
                                                         let cover_server_id = res.server_id.clone();
@@ -2014,6 +2622,52 @@ pub fn PlaylistDetailView(playlist_id: String, server_id: String) -> Element {
                     }
                 },
             }
+            if show_tracklist_export() {
+                if let Some(Some((playlist, songs))) = playlist_data() {
+                    TracklistExportDialog {
+                        title: playlist.name.clone(),
+                        songs: if song_list().is_empty() { songs } else { song_list() },
+                        on_close: move |_| show_tracklist_export.set(false),
+                    }
+                }
+            }
+            if show_unavailable_songs() {
+                if let Some(Some((playlist, songs))) = playlist_data() {
+                    if let Some(server) = servers().into_iter().find(|s| s.id == playlist.server_id) {
+                        UnavailableSongsDialog {
+                            server,
+                            playlist_id: playlist.id.clone(),
+                            songs: if song_list().is_empty() { songs } else { song_list() },
+                            can_remove: can_edit_playlist && !is_auto_imported,
+                            on_removed: {
+                                let playlist = playlist.clone();
+                                move |mut positions: Vec<usize>| {
+                                    positions.sort_unstable_by(|a, b| b.cmp(a));
+                                    let mut removed = Vec::new();
+                                    song_list
+                                        .with_mut(|list| {
+                                            for position in &positions {
+                                                if *position < list.len() {
+                                                    removed.push((list.remove(*position).id, *position));
+                                                }
+                                            }
+                                        });
+                                    for (song_id, position) in &removed {
+                                        sync_playlist_annotations(
+                                            settings_controller,
+                                            &playlist.server_id,
+                                            &playlist.id,
+                                            &song_list.peek(),
+                                            Some((song_id, *position)),
+                                        );
+                                    }
+                                }
+                            },
+                            on_close: move |_| show_unavailable_songs.set(false),
+                        }
+                    }
+                }
+            }
             if show_rename_dialog() {
                 div { class: "fixed inset-0 bg-black/50 flex items-center justify-center z-50",
                     onclick: move |evt: MouseEvent| {
@@ -2123,7 +2777,7 @@ async fn search_playlist_add_candidates(server: Option<ServerConfig>, query: Str
 
     let client = NavidromeClient::new(server);
     let output = client
-        .search(&normalized_query, 0, 0, 25)
+        .search(&normalized_query, 0, 0, 25, None)
         .await
         .map(|res| res.songs)
         .unwrap_or_default();
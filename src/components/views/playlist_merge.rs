@@ -0,0 +1,289 @@
+use crate::api::*;
+use dioxus::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+const MERGE_TARGET_NEW: &str = "__new__";
+
+#[derive(Debug, Clone, PartialEq, Default)]
+struct PlaylistMergePlan {
+    song_ids: Vec<String>,
+    source_songs: usize,
+    duplicates_removed: usize,
+    target_songs: usize,
+}
+
+/// Concatenates sources in order, keeping the first occurrence of each song id and
+/// skipping songs the target playlist already contains.
+fn plan_playlist_merge(target: &[Song], sources: &[Vec<Song>]) -> PlaylistMergePlan {
+    let mut seen: HashSet<&str> = target.iter().map(|song| song.id.as_str()).collect();
+    let mut song_ids = Vec::new();
+    let mut source_songs = 0usize;
+    for songs in sources {
+        for song in songs {
+            source_songs += 1;
+            if seen.insert(song.id.as_str()) {
+                song_ids.push(song.id.clone());
+            }
+        }
+    }
+    PlaylistMergePlan {
+        duplicates_removed: source_songs - song_ids.len(),
+        song_ids,
+        source_songs,
+        target_songs: target.len(),
+    }
+}
+
+#[component]
+pub(super) fn PlaylistMergeDialog(
+    playlists: Vec<Playlist>,
+    on_close: EventHandler<()>,
+    on_merged: EventHandler<String>,
+) -> Element {
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let active_server = servers().into_iter().find(|server| server.active);
+    let server_playlists: Vec<Playlist> = playlists
+        .into_iter()
+        .filter(|playlist| {
+            active_server
+                .as_ref()
+                .is_some_and(|server| server.id == playlist.server_id)
+        })
+        .collect();
+    let playlist_names: HashMap<String, String> = server_playlists
+        .iter()
+        .map(|playlist| (playlist.id.clone(), playlist.name.clone()))
+        .collect();
+
+    let mut selected = use_signal(Vec::<String>::new);
+    let mut target = use_signal(|| MERGE_TARGET_NEW.to_string());
+    let mut new_name = use_signal(String::new);
+    let mut merging = use_signal(|| false);
+    let mut merge_error = use_signal(|| None::<String>);
+
+    let source_ids = use_memo(move || {
+        let target_id = target();
+        selected()
+            .into_iter()
+            .filter(|id| *id != target_id)
+            .collect::<Vec<_>>()
+    });
+
+    let plan = use_resource(move || {
+        let server = servers().into_iter().find(|server| server.active);
+        let sources = source_ids();
+        let target_id = target();
+        async move {
+            let server = server?;
+            if sources.is_empty() {
+                return None;
+            }
+            let client = NavidromeClient::new(server);
+            let target_songs = if target_id == MERGE_TARGET_NEW {
+                Vec::new()
+            } else {
+                client.get_playlist(&target_id).await.ok()?.1
+            };
+            let mut source_songs = Vec::with_capacity(sources.len());
+            for source_id in &sources {
+                source_songs.push(client.get_playlist(source_id).await.ok()?.1);
+            }
+            Some(plan_playlist_merge(&target_songs, &source_songs))
+        }
+    });
+
+    let creating_new = target() == MERGE_TARGET_NEW;
+    let min_sources = if creating_new { 2 } else { 1 };
+    let ready = source_ids().len() >= min_sources
+        && (!creating_new || !new_name().trim().is_empty())
+        && matches!(plan(), Some(Some(_)))
+        && !merging();
+
+    let on_merge = move |_| {
+        if !ready {
+            return;
+        }
+        let Some(Some(merge_plan)) = plan() else {
+            return;
+        };
+        let Some(server) = servers().into_iter().find(|server| server.active) else {
+            merge_error.set(Some("No active server found.".to_string()));
+            return;
+        };
+        let target_id = target();
+        let name = new_name().trim().to_string();
+        let source_names: Vec<String> = source_ids()
+            .iter()
+            .map(|id| {
+                playlist_names
+                    .get(id)
+                    .cloned()
+                    .unwrap_or_else(|| id.clone())
+            })
+            .collect();
+        merging.set(true);
+        merge_error.set(None);
+        spawn(async move {
+            let client = NavidromeClient::new(server);
+            let result = if target_id == MERGE_TARGET_NEW {
+                let comment = format!("Merged from {}", source_names.join(", "));
+                client
+                    .create_playlist(&name, Some(&comment), &merge_plan.song_ids)
+                    .await
+                    .map(|_| {
+                        format!(
+                            "Created \"{name}\" with {} songs.",
+                            merge_plan.song_ids.len()
+                        )
+                    })
+            } else {
+                client
+                    .add_songs_to_playlist(&target_id, &merge_plan.song_ids)
+                    .await
+                    .map(|_| format!("Added {} songs.", merge_plan.song_ids.len()))
+            };
+            merging.set(false);
+            match result {
+                Ok(message) => on_merged.call(message),
+                Err(err) => merge_error.set(Some(err.to_string())),
+            }
+        });
+    };
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-[10000] flex items-center justify-center bg-black/60",
+            onclick: move |evt: MouseEvent| {
+                evt.stop_propagation();
+                if !merging() {
+                    on_close.call(());
+                }
+            },
+            div {
+                class: "bg-zinc-900 border border-zinc-700 rounded-2xl p-6 max-w-lg w-full mx-4 shadow-2xl space-y-4 max-h-[85vh] flex flex-col",
+                onclick: move |evt: MouseEvent| evt.stop_propagation(),
+                div {
+                    h3 { class: "text-lg font-semibold text-white", "Merge playlists" }
+                    p { class: "text-sm text-zinc-400",
+                        "Combine playlists into a new or existing playlist. Songs that appear more than once are only added once."
+                    }
+                }
+                div {
+                    label { class: "block text-xs uppercase tracking-wide text-zinc-500 mb-2",
+                        "Merge into"
+                    }
+                    select {
+                        class: "w-full px-3 py-2 rounded-lg bg-zinc-950/60 border border-zinc-800 text-sm text-white focus:outline-none focus:border-emerald-500/50",
+                        value: target(),
+                        disabled: merging(),
+                        onchange: move |e| target.set(e.value()),
+                        option { value: MERGE_TARGET_NEW, "New playlist" }
+                        for playlist in server_playlists.iter() {
+                            option {
+                                key: "{playlist.id}",
+                                value: "{playlist.id}",
+                                "{playlist.name}"
+                            }
+                        }
+                    }
+                    if creating_new {
+                        input {
+                            class: "w-full mt-2 px-3 py-2 rounded-lg bg-zinc-950/60 border border-zinc-800 text-white placeholder:text-zinc-600 focus:outline-none focus:border-emerald-500/50 focus:ring-2 focus:ring-emerald-500/20",
+                            placeholder: "New playlist name",
+                            value: new_name,
+                            disabled: merging(),
+                            oninput: move |e| new_name.set(e.value()),
+                        }
+                    }
+                }
+                div { class: "min-h-0 flex-1 flex flex-col",
+                    label { class: "block text-xs uppercase tracking-wide text-zinc-500 mb-2",
+                        "Playlists to merge"
+                    }
+                    div { class: "overflow-y-auto rounded-lg border border-zinc-800 divide-y divide-zinc-800/80",
+                        for playlist in server_playlists.iter().filter(|playlist| playlist.id != target()) {
+                            label {
+                                key: "{playlist.id}",
+                                class: "flex items-center gap-3 px-3 py-2 cursor-pointer hover:bg-zinc-800/40",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "w-4 h-4 rounded cursor-pointer",
+                                    disabled: merging(),
+                                    checked: selected().contains(&playlist.id),
+                                    onchange: {
+                                        let playlist_id = playlist.id.clone();
+                                        move |e: Event<FormData>| {
+                                            let checked = e.checked();
+                                            selected
+                                                .with_mut(|ids| {
+                                                    ids.retain(|id| *id != playlist_id);
+                                                    if checked {
+                                                        ids.push(playlist_id.clone());
+                                                    }
+                                                });
+                                        }
+                                    },
+                                }
+                                span { class: "flex-1 min-w-0 truncate text-sm text-white", "{playlist.name}" }
+                                span { class: "text-xs text-zinc-500 flex-shrink-0", "{playlist.song_count} songs" }
+                            }
+                        }
+                    }
+                }
+                div { class: "text-sm",
+                    if source_ids().len() < min_sources {
+                        p { class: "text-zinc-500",
+                            if creating_new {
+                                "Select at least two playlists."
+                            } else {
+                                "Select at least one playlist to add."
+                            }
+                        }
+                    } else {
+                        match plan() {
+                            None => rsx! {
+                                p { class: "text-zinc-500", "Calculating preview..." }
+                            },
+                            Some(None) => rsx! {
+                                p { class: "text-red-400", "Could not load the selected playlists." }
+                            },
+                            Some(Some(preview)) => rsx! {
+                                p { class: "text-zinc-300",
+                                    if creating_new {
+                                        "Result: {preview.song_ids.len()} songs"
+                                    } else {
+                                        "Result: {preview.target_songs + preview.song_ids.len()} songs ({preview.song_ids.len()} new)"
+                                    }
+                                }
+                                p { class: "text-xs text-zinc-500",
+                                    "{preview.source_songs} songs selected • {preview.duplicates_removed} duplicates removed"
+                                }
+                            },
+                        }
+                    }
+                }
+                if let Some(err) = merge_error() {
+                    p { class: "text-sm text-red-400", "{err}" }
+                }
+                div { class: "flex gap-3 justify-end",
+                    button {
+                        class: "px-4 py-2 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors text-sm",
+                        disabled: merging(),
+                        onclick: move |_| on_close.call(()),
+                        "Cancel"
+                    }
+                    button {
+                        class: if ready { "px-4 py-2 rounded-lg bg-emerald-500/20 border border-emerald-500/60 text-emerald-300 hover:text-white hover:bg-emerald-500/30 transition-colors text-sm" } else { "px-4 py-2 rounded-lg border border-zinc-800 text-zinc-600 cursor-not-allowed text-sm" },
+                        disabled: !ready,
+                        onclick: on_merge,
+                        if merging() {
+                            "Merging..."
+                        } else {
+                            "Merge"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Playlist maintenance: lists the entries that can no longer be played, can check every
+//! entry against the server, and removes the unavailable ones from the playlist.
+use crate::api::{NavidromeClient, ServerConfig, Song};
+use crate::components::{Icon, UnavailableSongsSignal};
+use dioxus::prelude::*;
+
+#[component]
+pub(super) fn UnavailableSongsDialog(
+    server: ServerConfig,
+    playlist_id: String,
+    songs: Vec<Song>,
+    can_remove: bool,
+    /// Called with the removed positions once the server has dropped them.
+    on_removed: EventHandler<Vec<usize>>,
+    on_close: EventHandler<()>,
+) -> Element {
+    let unavailable_songs = use_context::<UnavailableSongsSignal>();
+    let mut checked = use_signal(|| None::<(usize, usize)>);
+    let mut busy = use_signal(|| false);
+    let mut status = use_signal(|| None::<String>);
+
+    let entries: Vec<(usize, Song)> = songs
+        .iter()
+        .enumerate()
+        .filter(|(_, song)| unavailable_songs.contains(song))
+        .map(|(position, song)| (position, song.clone()))
+        .collect();
+    let positions: Vec<usize> = entries.iter().map(|(position, _)| *position).collect();
+    let count = entries.len();
+
+    let on_check = {
+        let server = server.clone();
+        let songs = songs.clone();
+        move |_| {
+            if busy() {
+                return;
+            }
+            busy.set(true);
+            status.set(None);
+            let client = NavidromeClient::new(server.clone());
+            let songs = songs.clone();
+            spawn(async move {
+                let total = songs.len();
+                // Runs on this dialog's scope, so closing it stops the check.
+                for (done, song) in songs.iter().enumerate() {
+                    if !unavailable_songs.peek_contains(song) {
+                        if let Err(err) = client.get_song_fresh(&song.id).await {
+                            if err.is_not_found() {
+                                unavailable_songs.mark(song);
+                            }
+                        }
+                    }
+                    checked.set(Some((done + 1, total)));
+                }
+                busy.set(false);
+            });
+        }
+    };
+
+    let on_remove = {
+        let positions = positions.clone();
+        move |_| {
+            if busy() || positions.is_empty() {
+                return;
+            }
+            busy.set(true);
+            status.set(None);
+            let client = NavidromeClient::new(server.clone());
+            let playlist_id = playlist_id.clone();
+            let positions = positions.clone();
+            spawn(async move {
+                match client
+                    .remove_songs_from_playlist(&playlist_id, &positions)
+                    .await
+                {
+                    Ok(()) => {
+                        on_removed.call(positions);
+                        on_close.call(());
+                    }
+                    Err(err) => status.set(Some(err.to_string())),
+                }
+                busy.set(false);
+            });
+        }
+    };
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-[210] bg-zinc-950/95 backdrop-blur-sm overflow-y-auto px-4 py-8 flex items-center justify-center",
+            onclick: move |_| on_close.call(()),
+            div {
+                class: "w-full max-w-lg bg-zinc-900/60 border border-zinc-700/50 rounded-2xl p-6 space-y-4",
+                onclick: move |evt: MouseEvent| evt.stop_propagation(),
+                div { class: "space-y-1",
+                    h2 { class: "text-lg font-semibold text-white", "Unavailable songs" }
+                    p { class: "text-sm text-zinc-400",
+                        "Entries the server flags as missing or that failed to load because their file is gone."
+                    }
+                }
+                if entries.is_empty() {
+                    p { class: "text-sm text-zinc-500", "No unavailable songs found in this playlist." }
+                } else {
+                    div { class: "max-h-72 overflow-y-auto space-y-1",
+                        for (position , song) in entries {
+                            div {
+                                key: "{position}",
+                                class: "flex items-center gap-3 px-3 py-2 rounded-lg bg-zinc-950/60",
+                                span { class: "w-8 text-right text-xs text-zinc-500", "{position + 1}" }
+                                div { class: "min-w-0 flex-1",
+                                    p { class: "text-sm text-zinc-400 line-through truncate", "{song.title}" }
+                                    p { class: "text-xs text-zinc-500 truncate",
+                                        "{song.artist.clone().unwrap_or_default()}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                if let Some((done, total)) = checked() {
+                    p { class: "text-xs text-zinc-500", "Checked {done} of {total} songs with the server." }
+                }
+                if let Some(message) = status() {
+                    p { class: "text-xs text-red-300", "{message}" }
+                }
+                div { class: "flex flex-wrap items-center justify-end gap-2",
+                    button {
+                        class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white transition-colors text-sm flex items-center gap-2 disabled:opacity-50",
+                        disabled: busy(),
+                        onclick: on_check,
+                        if busy() {
+                            Icon { name: "loader".to_string(), class: "w-4 h-4 animate-spin".to_string() }
+                        }
+                        "Check every song"
+                    }
+                    if can_remove && count > 0 {
+                        button {
+                            class: "px-3 py-1.5 rounded-lg bg-red-500/15 text-red-300 hover:bg-red-500/25 transition-colors text-sm disabled:opacity-50",
+                            disabled: busy(),
+                            onclick: on_remove,
+                            if count == 1 {
+                                "Remove 1 song from playlist"
+                            } else {
+                                "Remove {count} songs from playlist"
+                            }
+                        }
+                    }
+                    button {
+                        class: "px-3 py-1.5 rounded-lg text-zinc-400 hover:text-white transition-colors text-sm",
+                        onclick: move |_| on_close.call(()),
+                        "Close"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,9 +1,34 @@
+use super::empty_state::{EmptyState, NoServerEmptyState};
+use super::playlist_merge::PlaylistMergeDialog;
 use crate::api::*;
+use crate::collation::{collate, search_text};
 use crate::components::audio_manager::apply_collection_shuffle_mode;
-use crate::components::{AddIntent, AddMenuController, AppView, Icon, Navigation};
+use crate::components::{sized_cover_art_url, ArtworkKind};
+use crate::components::{
+    use_restored_scroll, use_view_state, AddIntent, AddMenuController, AppView, Icon, Navigation,
+    QueueDragController, ServerCapabilitiesSignal,
+};
+use crate::db::AppSettings;
+use crate::time_format::humanize_server_timestamp_for_display;
 use dioxus::prelude::*;
+use std::collections::HashSet;
 
 const PLAYLIST_INITIAL_LIMIT: usize = 20;
+const PLAYLIST_FRESHNESS_POLL_MS: u64 = 3 * 60 * 1000;
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn playlists_poll_delay_ms(ms: u64) {
+    tokio::time::sleep(std::time::Duration::from_millis(ms)).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn playlists_poll_delay_ms(ms: u64) {
+    gloo_timers::future::TimeoutFuture::new(ms as u32).await;
+}
+
+fn playlist_key(playlist: &Playlist) -> String {
+    format!("{}:{}", playlist.server_id, playlist.id)
+}
 
 fn anchored_menu_style(
     anchor_x: f64,
@@ -23,17 +48,33 @@ fn anchored_menu_style(
 pub fn PlaylistsView() -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
     let navigation = use_context::<Navigation>();
-    let mut search_query = use_signal(String::new);
-    let limit = use_signal(|| PLAYLIST_INITIAL_LIMIT);
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let mut search_query = use_view_state("search_query", String::new);
+    let limit = use_view_state("limit", || PLAYLIST_INITIAL_LIMIT);
     let mut refresh = use_signal(|| 0usize);
     let single_active_server = servers().iter().filter(|s| s.active).count() == 1;
-    let mut hide_auto_imported = use_signal(|| true);
-    let mut owner_filter = use_signal(|| "all".to_string());
-    let mut sort_by = use_signal(|| "newest".to_string());
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
+    let can_create_playlist = servers()
+        .iter()
+        .filter(|server| server.active)
+        .all(|server| capabilities.user_can(&server.id, UserAction::CreatePlaylist));
+    let create_playlist_hint = if !can_create_playlist {
+        capabilities.denied_hint(UserAction::CreatePlaylist)
+    } else if !single_active_server {
+        "Only available with one active server"
+    } else {
+        ""
+    };
+    let mut hide_auto_imported = use_view_state("hide_auto_imported", || true);
+    let mut owner_filter = use_view_state("owner_filter", || "all".to_string());
+    let mut sort_by = use_view_state("sort_by", || "newest".to_string());
     let mut show_create_playlist = use_signal(|| false);
     let mut create_playlist_name = use_signal(String::new);
     let create_playlist_busy = use_signal(|| false);
     let mut create_playlist_status = use_signal(|| None::<(bool, String)>);
+    let mut updated_playlists = use_signal(HashSet::<String>::new);
+    let mut show_merge_playlists = use_signal(|| false);
+    let mut merge_status = use_signal(|| None::<String>);
 
     let playlists = use_resource(move || {
         let servers = servers();
@@ -49,6 +90,51 @@ pub fn PlaylistsView() -> Element {
             playlists
         }
     });
+    use_restored_scroll(move || playlists.read().is_some());
+
+    // Cheap freshness probe: re-read getPlaylists while this view is mounted and flag
+    // entries whose changed/songCount moved since the list we are showing.
+    use_effect(move || {
+        spawn(async move {
+            loop {
+                playlists_poll_delay_ms(PLAYLIST_FRESHNESS_POLL_MS).await;
+                let Some(previous) = playlists.peek().clone() else {
+                    continue;
+                };
+
+                let mut changed_keys = Vec::new();
+                for server in servers.peek().iter().filter(|s| s.active).cloned() {
+                    let client = NavidromeClient::new(server);
+                    let Ok(latest) = client.get_playlists_fresh().await else {
+                        continue;
+                    };
+                    for entry in &latest {
+                        let key = playlist_key(entry);
+                        let changed = previous
+                            .iter()
+                            .find(|playlist| playlist_key(playlist) == key)
+                            .map(|playlist| playlist.differs_from(entry))
+                            .unwrap_or(true);
+                        if changed {
+                            changed_keys.push(key);
+                        }
+                    }
+                }
+
+                if !changed_keys.is_empty() {
+                    updated_playlists.with_mut(|keys| keys.extend(changed_keys));
+                    let next = refresh.peek().saturating_add(1);
+                    refresh.set(next);
+                }
+            }
+        });
+    });
+
+    let has_active_servers = servers().iter().any(|server| server.active);
+    let grid_density = app_settings().grid_density;
+    let columns = grid_density
+        .columns_class()
+        .unwrap_or("grid-cols-2 sm:grid-cols-3 md:grid-cols-4 lg:grid-cols-5");
 
     rsx! {
         div { class: "space-y-8",
@@ -56,6 +142,9 @@ pub fn PlaylistsView() -> Element {
                 div {
                     h1 { class: "page-title", "Playlists" }
                     p { class: "page-subtitle", "Your playlists from all servers" }
+                    if let Some(message) = merge_status() {
+                        p { class: "text-sm text-emerald-300 mt-2", "{message}" }
+                    }
                     if !single_active_server {
                         p { class: "text-sm text-amber-200/80 bg-amber-500/10 border border-amber-500/40 rounded-lg px-3 py-2 mt-2",
                             "Playlist creation and merging require exactly one active server."
@@ -73,20 +162,33 @@ pub fn PlaylistsView() -> Element {
                                         NavidromeClient::new(server).refresh_playlist_cache();
                                     }
                                     show_create_playlist.set(false);
+                                    navigation.clear_view_states();
                                     refresh.set(refresh().saturating_add(1));
                                 }
                             },
                             "Refresh"
                         }
                         button {
-                            class: if !single_active_server { "px-4 py-2 rounded-xl bg-zinc-800/40 text-zinc-500 text-sm font-medium cursor-not-allowed" } else { "px-4 py-2 rounded-xl bg-emerald-500 text-white text-sm font-medium hover:bg-emerald-400 transition-colors" },
-                            disabled: !single_active_server || create_playlist_busy(),
+                            class: if !single_active_server || !can_create_playlist { "px-4 py-2 rounded-xl bg-zinc-800/40 text-zinc-500 text-sm font-medium cursor-not-allowed" } else { "px-4 py-2 rounded-xl bg-emerald-500 text-white text-sm font-medium hover:bg-emerald-400 transition-colors" },
+                            disabled: !single_active_server || !can_create_playlist || create_playlist_busy(),
+                            title: create_playlist_hint,
                             onclick: move |_| {
                                 create_playlist_status.set(None);
                                 show_create_playlist.set(!show_create_playlist());
                             },
                             "Create Playlist"
                         }
+                        button {
+                            class: if !single_active_server || !can_create_playlist { "px-4 py-2 rounded-xl bg-zinc-800/40 text-zinc-500 text-sm font-medium cursor-not-allowed" } else { "px-4 py-2 rounded-xl bg-zinc-800/60 hover:bg-zinc-800 text-zinc-200 text-sm font-medium transition-colors" },
+                            disabled: !single_active_server || !can_create_playlist,
+                            title: create_playlist_hint,
+                            onclick: move |_| {
+                                show_create_playlist.set(false);
+                                merge_status.set(None);
+                                show_merge_playlists.set(true);
+                            },
+                            "Merge"
+                        }
                         if show_create_playlist() {
                             div { class: "absolute top-full right-0 mt-2 z-20 w-[min(30rem,calc(100vw-1.5rem))] rounded-xl border border-zinc-700/70 bg-zinc-900/95 p-3 shadow-2xl space-y-2",
                                 p { class: "text-xs uppercase tracking-wide text-zinc-500", "Create empty playlist" }
@@ -132,7 +234,7 @@ pub fn PlaylistsView() -> Element {
                                                             show_create_playlist.set(false);
                                                             refresh.set(refresh().saturating_add(1));
                                                         }
-                                                        Err(err) => create_playlist_status.set(Some((false, err))),
+                                                        Err(err) => create_playlist_status.set(Some((false, err.to_string()))),
                                                     }
                                                     create_playlist_busy.set(false);
                                                 });
@@ -211,7 +313,7 @@ pub fn PlaylistsView() -> Element {
                 match playlists() {
                     Some(playlists) => {
                         let raw_query = search_query().trim().to_string();
-                        let query = raw_query.to_lowercase();
+                        let query = search_text(&raw_query);
                         let mut filtered = playlists.clone();
 
                         if hide_auto_imported() {
@@ -233,10 +335,10 @@ pub fn PlaylistsView() -> Element {
                                 });
                         }
                         if !query.is_empty() {
-                            filtered.retain(|p| p.name.to_lowercase().contains(&query));
+                            filtered.retain(|p| search_text(&p.name).contains(&query));
                         }
                         match sort_by().as_str() {
-                            "name" => filtered.sort_by(|a, b| a.name.cmp(&b.name)),
+                            "name" => filtered.sort_by(|a, b| collate(&a.name, &b.name, false)),
                             "created" => {
                                 filtered
                                     .sort_by(|a, b| {
@@ -263,28 +365,51 @@ pub fn PlaylistsView() -> Element {
                             .collect();
                         rsx! {
                             if display.is_empty() {
-                                div { class: "flex flex-col items-center justify-center py-20",
-                                    Icon {
-                                        name: "playlist".to_string(),
-                                        class: "w-16 h-16 text-zinc-600 mb-4".to_string(),
+                                if !has_active_servers {
+                                    NoServerEmptyState {
+                                        icon: "playlist".to_string(),
+                                        message: "Connect a Navidrome server to see your playlists.".to_string(),
+                                    }
+                                } else if has_query {
+                                    EmptyState {
+                                        icon: "playlist".to_string(),
+                                        title: format!("No playlists match \"{raw_query}\""),
                                     }
-                                    if has_query {
-                                        p { class: "text-zinc-300", "No playlists match \"{raw_query}\"" }
+                                } else {
+                                    if single_active_server {
+                                        EmptyState {
+                                            icon: "playlist".to_string(),
+                                            title: "No playlists yet".to_string(),
+                                            message: "Create one here, or add songs to a playlist from any song menu."
+                                                .to_string(),
+                                            action_label: "Create Playlist".to_string(),
+                                            on_action: move |_| {
+                                                create_playlist_status.set(None);
+                                                show_create_playlist.set(true);
+                                            },
+                                        }
                                     } else {
-                                        h2 { class: "text-xl font-semibold text-white mb-2", "No playlists found" }
-                                        p { class: "text-zinc-400", "Try adjusting your filters" }
+                                        EmptyState {
+                                            icon: "playlist".to_string(),
+                                            title: "No playlists found".to_string(),
+                                            message: "Try adjusting your filters".to_string(),
+                                        }
                                     }
                                 }
                             } else {
-                                div { class: "rs-album-grid grid grid-cols-2 sm:grid-cols-3 md:grid-cols-4 lg:grid-cols-5 gap-4",
+                                div { class: "rs-album-grid grid {columns} gap-4",
                                     for playlist in display {
                                         PlaylistCard {
                                             playlist: playlist.clone(),
+                                            art_px: grid_density.tile_px(),
+                                            updated: updated_playlists.read().contains(&playlist_key(&playlist)),
                                             onclick: {
                                                 let navigation = navigation.clone();
                                                 let playlist_id = playlist.id.clone();
                                                 let playlist_server_id = playlist.server_id.clone();
+                                                let key = playlist_key(&playlist);
                                                 move |_| {
+                                                    updated_playlists.write().remove(&key);
                                                     navigation
                                                         .navigate_to(AppView::PlaylistDetailView {
                                                             playlist_id: playlist_id.clone(),
@@ -321,6 +446,23 @@ pub fn PlaylistsView() -> Element {
                     },
                 }
             }
+
+            if show_merge_playlists() {
+                PlaylistMergeDialog {
+                    playlists: playlists().unwrap_or_default(),
+                    on_close: move |_| show_merge_playlists.set(false),
+                    on_merged: move |message: String| {
+                        for server in servers().into_iter().filter(|server| server.active) {
+                            NavidromeClient::new(server).refresh_playlist_cache();
+                        }
+                        show_merge_playlists.set(false);
+                        merge_status.set(Some(message));
+                        navigation.clear_view_states();
+                        let next = refresh.peek().saturating_add(1);
+                        refresh.set(next);
+                    },
+                }
+            }
         }
     }
 }
@@ -328,15 +470,19 @@ pub fn PlaylistsView() -> Element {
 #[component]
 fn PlaylistCard(
     playlist: Playlist,
+    art_px: u32,
+    updated: bool,
     onclick: EventHandler<MouseEvent>,
     on_delete: EventHandler<()>,
 ) -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
     let add_menu = use_context::<AddMenuController>();
+    let queue_drag = use_context::<QueueDragController>();
     let shuffle_enabled = use_context::<crate::components::ShuffleEnabledSignal>().0;
     let queue = use_context::<Signal<Vec<Song>>>();
     let queue_index = use_context::<Signal<usize>>();
     let now_playing = use_context::<Signal<Option<Song>>>();
+    let app_settings = use_context::<Signal<AppSettings>>();
 
     let mut show_menu = use_signal(|| false);
     let mut menu_x = use_signal(|| 0f64);
@@ -356,6 +502,10 @@ fn PlaylistCard(
         .unwrap_or(false);
     let editing_allowed = !is_auto_imported;
 
+    let changed_label = playlist
+        .changed
+        .as_deref()
+        .and_then(|value| humanize_server_timestamp_for_display(value, &app_settings()));
     let cover_url = servers()
         .iter()
         .find(|s| s.id == playlist.server_id)
@@ -364,7 +514,7 @@ fn PlaylistCard(
             playlist
                 .cover_art
                 .as_ref()
-                .map(|ca| client.get_cover_art_url(ca, 300))
+                .map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Playlist, art_px))
         });
 
     let on_shuffle = {
@@ -406,7 +556,7 @@ fn PlaylistCard(
                             on_delete.call(());
                         }
                         Err(err) => {
-                            delete_error.set(Some(err));
+                            delete_error.set(Some(err.to_string()));
                             deleting.set(false);
                         }
                     }
@@ -462,7 +612,7 @@ fn PlaylistCard(
                             on_delete.call(());
                         }
                         Err(err) => {
-                            rename_error.set(Some(err));
+                            rename_error.set(Some(err.to_string()));
                             renaming.set(false);
                         }
                     }
@@ -474,7 +624,11 @@ fn PlaylistCard(
     };
 
     rsx! {
-        div { class: "relative",
+        div {
+            class: "relative",
+            draggable: "true",
+            ondragstart: queue_drag.on_drag_start(AddIntent::from_playlist(&playlist).target),
+            ondragend: move |_| queue_drag.end(),
             button {
                 class: "group w-full text-left",
                 onclick: move |e| onclick.call(e),
@@ -524,8 +678,20 @@ fn PlaylistCard(
                 p { class: "font-medium text-white text-sm truncate group-hover:text-emerald-400 transition-colors",
                     "{playlist.name}"
                 }
-                p { class: "text-xs text-zinc-400",
-                    "{playlist.song_count} songs • {format_duration(playlist.duration / 1000)}"
+                p { class: "text-xs text-zinc-400 flex items-center gap-1.5 min-w-0",
+                    span { class: "truncate",
+                        "{playlist.song_count} songs • {format_duration(playlist.duration / 1000)}"
+                    }
+                    if updated {
+                        span {
+                            class: "flex-shrink-0 px-1.5 py-0.5 rounded-full bg-emerald-500/15 text-[10px] font-medium text-emerald-300",
+                            title: "Changed on the server since you opened this view",
+                            "Updated"
+                        }
+                    }
+                }
+                if let Some(changed) = changed_label {
+                    p { class: "text-[11px] text-zinc-500 truncate", "Changed {changed}" }
                 }
             }
 
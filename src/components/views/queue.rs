@@ -1,12 +1,27 @@
+use super::queue_follow::{use_queue_follow, NowPlayingPill};
+use super::queue_transfer::QueueTransferControls;
+use super::song_list_filter::{
+    filter_song_order, scroll_to_row, use_song_list_filter, HighlightedText, SongListFilterBar,
+};
+use super::song_table::{
+    song_table_grid_style, sorted_song_order, visible_song_table_columns, QueueArtwork,
+    QueueRowArt, SongTableCells, SongTableHeader, SongTableSort, SONG_TABLE_ROW_CLASS,
+};
 use crate::api::models::format_duration;
 use crate::api::*;
 use crate::cache_service::{get_json as cache_get_json, put_json as cache_put_json};
-use crate::components::views::artist_links::ArtistNameLinks;
+use crate::components::views::artist_links::{
+    parse_artist_names, resolve_artist_id_for_name, ArtistNameLinks,
+};
 use crate::components::{
     generate_queue_extension_from_seed, AddIntent, AddMenuController, AppView, Icon, Navigation,
-    PlaybackPositionSignal, PreviewPlaybackSignal, SeekRequestSignal,
+    PlaybackPositionSignal, PreviewPlaybackSignal, SeekRequestSignal, ServerCapabilitiesSignal,
+    SettingsController, UnavailableSongsSignal, UNAVAILABLE_SONG_TOOLTIP,
+};
+use crate::components::{sized_cover_art_url, use_lazy_cover_loader, ArtworkKind};
+use crate::db::{
+    load_temporary_queue_snapshots, AppSettings, QueueRowDensity, TemporaryQueueSnapshot,
 };
-use crate::db::{load_temporary_queue_snapshots, AppSettings, TemporaryQueueSnapshot};
 use crate::diagnostics::{log_perf, PerfTimer};
 use crate::offline_audio::{is_song_downloaded, prefetch_song_audio};
 use dioxus::prelude::*;
@@ -107,6 +122,8 @@ pub fn QueueView() -> Element {
     let mut now_playing = use_context::<Signal<Option<Song>>>();
     let mut is_playing = use_context::<crate::components::IsPlayingSignal>().0;
     let app_settings = use_context::<Signal<AppSettings>>();
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
+    let unavailable_songs = use_context::<UnavailableSongsSignal>();
     let playback_position = use_context::<PlaybackPositionSignal>().0;
     let seek_request = use_context::<SeekRequestSignal>().0;
     let preview_playback = use_context::<PreviewPlaybackSignal>().0;
@@ -122,12 +139,29 @@ pub fn QueueView() -> Element {
     let lyrics_prefetch_signature = use_signal(String::new);
     let quick_create_queue_busy = use_signal(|| false);
     let mut queue_song_menu = use_signal(|| None::<(Song, usize, f64, f64)>);
+    let queue_table_sort = use_signal(|| None::<SongTableSort>);
     let saved_queue_snapshots = use_signal(Vec::<TemporaryQueueSnapshot>::new);
     let saved_queue_snapshots_loaded = use_signal(|| false);
+    let mut queue_filter = use_song_list_filter();
+    use_lazy_cover_loader();
+    let settings_controller = use_context::<SettingsController>();
+    let auto_follow = use_memo(move || app_settings().queue_auto_follow);
+    let queue_follow = use_queue_follow(queue_index, auto_follow);
 
     let current_index = queue_index();
     let songs: Vec<Song> = queue().into_iter().collect();
     let queue_len = songs.len();
+    let queue_filter_query = queue_filter.query(&songs);
+    let queue_table_order = filter_song_order(
+        &songs,
+        &sorted_song_order(&songs, queue_table_sort()),
+        &queue_filter_query,
+    );
+    let first_filter_match = queue_table_order.first().copied();
+    let table_columns = visible_song_table_columns(&app_settings(), "queue");
+    let show_queue_art = app_settings().queue_row_density == QueueRowDensity::WithArt;
+    let playback_active = is_playing();
+    let table_grid_style = song_table_grid_style(&table_columns);
     let current_song = now_playing();
     let saved_queue_snapshot_items = saved_queue_snapshots();
     let can_restore_saved_queue =
@@ -435,7 +469,7 @@ pub fn QueueView() -> Element {
                     }
                 }
 
-                div { class: "flex items-center gap-2",
+                div { class: "flex flex-wrap items-center gap-2",
                     button {
                         class: if add_song_panel_open() {
                             "px-4 py-2 rounded-xl bg-emerald-500/20 border border-emerald-500/40 text-emerald-300 hover:text-white transition-colors flex items-center gap-2"
@@ -464,7 +498,27 @@ pub fn QueueView() -> Element {
                             "Save Queue"
                         }
                     }
+                    QueueTransferControls {}
                     if !songs.is_empty() {
+                        button {
+                            class: if auto_follow() {
+                                "px-4 py-2 rounded-xl bg-emerald-500/20 border border-emerald-500/40 text-emerald-300 hover:text-white transition-colors flex items-center gap-2"
+                            } else {
+                                "px-4 py-2 rounded-xl bg-zinc-800 hover:bg-zinc-700 text-zinc-300 hover:text-white transition-colors flex items-center gap-2"
+                            },
+                            title: "Keep the playing song centered as tracks advance. Scrolling pauses it for 10 seconds.",
+                            aria_pressed: auto_follow(),
+                            onclick: move |_| {
+                                settings_controller.update_settings(|settings| {
+                                    settings.queue_auto_follow = !settings.queue_auto_follow;
+                                });
+                            },
+                            Icon {
+                                name: "eye".to_string(),
+                                class: "w-4 h-4".to_string(),
+                            }
+                            "Follow Playing"
+                        }
                         button {
                             class: "px-4 py-2 rounded-xl bg-zinc-800 hover:bg-zinc-700 text-zinc-300 hover:text-white transition-colors flex items-center gap-2",
                             onclick: on_clear,
@@ -520,7 +574,7 @@ pub fn QueueView() -> Element {
                                                             result
                                                                 .cover_art
                                                                 .as_ref()
-                                                                .map(|cover| client.get_cover_art_url(cover, 80))
+                                                                .map(|cover| sized_cover_art_url(&client, cover, ArtworkKind::Song, 48))
                                                         });
                                                     let cover_album_id = result.album_id.clone();
                                                     let cover_server_id = result.server_id.clone();
@@ -703,7 +757,7 @@ pub fn QueueView() -> Element {
                                                             result
                                                                 .cover_art
                                                                 .as_ref()
-                                                                .map(|cover| client.get_cover_art_url(cover, 80))
+                                                                .map(|cover| sized_cover_art_url(&client, cover, ArtworkKind::Song, 48))
                                                         });
                                                     let cover_album_id = result.album_id.clone();
                                                     let cover_server_id = result.server_id.clone();
@@ -910,7 +964,7 @@ pub fn QueueView() -> Element {
                                 .find(|s| s.id == current.server_id)
                                 .and_then(|server| {
                                     let client = NavidromeClient::new(server.clone());
-                                    current.cover_art.as_ref().map(|ca| client.get_cover_art_url(ca, 80))
+                                    current.cover_art.as_ref().map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Song, 48))
                                 });
                             rsx! {
                                 div { class: "p-4 bg-emerald-500/10 border-b border-zinc-700/50",
@@ -1034,15 +1088,49 @@ pub fn QueueView() -> Element {
                     }
 
                     // Queue List
-                    div { class: "divide-y divide-zinc-800/50",
-                        for (idx , song) in songs.into_iter().enumerate() {
+                    div { class: "p-3 border-b border-zinc-700/50",
+                        SongListFilterBar {
+                            value: queue_filter_query.clone(),
+                            match_count: queue_table_order.len(),
+                            total: queue_len,
+                            placeholder: "Filter the queue".to_string(),
+                            on_input: {
+                                let songs = songs.clone();
+                                move |text: String| queue_filter.set(text, &songs)
+                            },
+                            on_jump: move |_| {
+                                if let Some(idx) = first_filter_match {
+                                    scroll_to_row(&format!("queue-row-{idx}"));
+                                }
+                            },
+                            on_play_first: {
+                                let songs = songs.clone();
+                                move |_| {
+                                    let Some(idx) = first_filter_match else {
+                                        return;
+                                    };
+                                    queue_index.set(idx);
+                                    now_playing.set(songs.get(idx).cloned());
+                                    is_playing.set(true);
+                                }
+                            },
+                        }
+                    }
+                    SongTableHeader { view_key: "queue".to_string(), sort: queue_table_sort }
+                    div {
+                        id: "queue-list",
+                        class: "divide-y divide-zinc-800/50",
+                        "data-current-index": "{current_index}",
+                        for idx in queue_table_order.iter().copied() {
                             {
+                                let song = songs[idx].clone();
                                 let is_current = idx == current_index;
+                                let unavailable = unavailable_songs.contains(&song);
                                 let song_id = song.id.clone();
                                 let row_class = if is_current {
-                                    "p-3 bg-emerald-500/5 flex items-center justify-between group cursor-pointer select-none ios-drag-lock"
+                                    "p-3 bg-emerald-500/5 flex 2xl:hidden items-center justify-between group cursor-pointer select-none ios-drag-lock"
                                 } else {
-                                    "p-3 hover:bg-zinc-700/30 transition-colors flex items-center justify-between group cursor-pointer select-none ios-drag-lock"
+                                    "p-3 hover:bg-zinc-700/30 transition-colors flex 2xl:hidden items-center justify-between group cursor-pointer select-none ios-drag-lock"
                                 };
                                 let can_move_up = idx > 0;
                                 let can_move_down = idx + 1 < queue_len;
@@ -1052,249 +1140,303 @@ pub fn QueueView() -> Element {
                                     .find(|s| s.id == song.server_id)
                                     .and_then(|server| {
                                         let client = NavidromeClient::new(server.clone());
-                                        song.cover_art.as_ref().map(|ca| client.get_cover_art_url(ca, 80))
+                                        song.cover_art.as_ref().map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Song, 48))
                                     });
+                                let row_art = QueueRowArt {
+                                    cover_url,
+                                    is_current,
+                                    is_playing: playback_active,
+                                };
                                 rsx! {
                                     div {
                                         key: "{song_id}-{idx}",
-                                        class: "{row_class}",
-                                        onclick: move |_| {
-                                            if !is_current {
-                                                queue_index.set(idx);
-                                                now_playing.set(Some(play_target_song.clone()));
-                                                is_playing.set(true);
-                                            }
-                                        },
+                                        id: "queue-row-{idx}",
+                                        "data-queue-index": "{idx}",
+                                        div {
+                                            class: "{row_class}",
+                                            onclick: move |_| {
+                                                if !is_current {
+                                                    queue_index.set(idx);
+                                                    now_playing.set(Some(play_target_song.clone()));
+                                                    is_playing.set(true);
+                                                }
+                                            },
 
 
 
-                                        div { class: "flex items-center gap-4 overflow-hidden",
-                                            div { class: "w-8 text-center text-sm flex-shrink-0",
-                                                if is_current {
-                                                    Icon {
-                                                        name: "play".to_string(),
-                                                        class: "w-4 h-4 text-emerald-400 mx-auto".to_string(),
+                                            div { class: "flex items-center gap-4 overflow-hidden",
+                                                div { class: "w-8 text-center text-sm flex-shrink-0",
+                                                    if is_current {
+                                                        Icon {
+                                                            name: "play".to_string(),
+                                                            class: "w-4 h-4 text-emerald-400 mx-auto".to_string(),
+                                                        }
+                                                    } else {
+                                                        span { class: "text-zinc-500", "{idx + 1}" }
                                                     }
-                                                } else {
-                                                    span { class: "text-zinc-500", "{idx + 1}" }
                                                 }
-                                            }
-                                            if song.album_id.is_some() {
-                                                button {
-                                                    class: "rs-song-art w-12 h-12 rounded-lg bg-zinc-800 overflow-hidden flex-shrink-0",
-                                                    aria_label: "Play queued song",
-                                                    onclick: {
-                                                        let song = song.clone();
-                                                        let mut queue_index = queue_index.clone();
-                                                        let mut now_playing = now_playing.clone();
-                                                        let mut is_playing = is_playing.clone();
-                                                        let song_index = idx;
-                                                        move |evt: MouseEvent| {
-                                                            evt.stop_propagation();
-                                                            queue_index.set(song_index);
-                                                            now_playing.set(Some(song.clone()));
-                                                            is_playing.set(true);
+                                                if show_queue_art && song.album_id.is_some() {
+                                                    button {
+                                                        class: "rs-song-art w-12 h-12 rounded-lg bg-zinc-800 overflow-hidden flex-shrink-0",
+                                                        aria_label: "Play queued song",
+                                                        onclick: {
+                                                            let song = song.clone();
+                                                            let mut queue_index = queue_index.clone();
+                                                            let mut now_playing = now_playing.clone();
+                                                            let mut is_playing = is_playing.clone();
+                                                            let song_index = idx;
+                                                            move |evt: MouseEvent| {
+                                                                evt.stop_propagation();
+                                                                queue_index.set(song_index);
+                                                                now_playing.set(Some(song.clone()));
+                                                                is_playing.set(true);
+                                                            }
+                                                        },
+                                                        QueueArtwork {
+                                                            art: row_art.clone(),
+                                                            alt: song.title.clone(),
+                                                            class: "w-full h-full".to_string(),
                                                         }
-                                                    },
-                                                    {
-                                                        match cover_url.clone() {
-                                                            Some(url) => rsx! {
-                                                                img {
-                                                                    src: "{url}",
-                                                                    alt: "{song.title}",
-                                                                    class: "w-full h-full object-cover",
-                                                                    loading: "lazy",
-                                                                }
-                                                            },
-                                                            None => rsx! {
-                                                                div { class: "w-full h-full flex items-center justify-center bg-gradient-to-br from-zinc-700 to-zinc-800",
-                                                                    Icon { name: "music".to_string(), class: "w-4 h-4 text-zinc-500".to_string() }
-                                                                }
-                                                            },
+                                                    }
+                                                } else if show_queue_art {
+                                                    button {
+                                                        class: "rs-song-art w-12 h-12 rounded-lg bg-zinc-800 overflow-hidden flex-shrink-0",
+                                                        aria_label: "Play queued song",
+                                                        onclick: {
+                                                            let song = song.clone();
+                                                            let mut queue_index = queue_index.clone();
+                                                            let mut now_playing = now_playing.clone();
+                                                            let mut is_playing = is_playing.clone();
+                                                            let song_index = idx;
+                                                            move |evt: MouseEvent| {
+                                                                evt.stop_propagation();
+                                                                queue_index.set(song_index);
+                                                                now_playing.set(Some(song.clone()));
+                                                                is_playing.set(true);
+                                                            }
+                                                        },
+                                                        QueueArtwork {
+                                                            art: row_art.clone(),
+                                                            alt: song.title.clone(),
+                                                            class: "w-full h-full".to_string(),
                                                         }
                                                     }
                                                 }
-                                            } else {
-                                                button {
-                                                    class: "rs-song-art w-12 h-12 rounded-lg bg-zinc-800 overflow-hidden flex-shrink-0",
-                                                    aria_label: "Play queued song",
-                                                    onclick: {
-                                                        let song = song.clone();
-                                                        let mut queue_index = queue_index.clone();
-                                                        let mut now_playing = now_playing.clone();
-                                                        let mut is_playing = is_playing.clone();
-                                                        let song_index = idx;
-                                                        move |evt: MouseEvent| {
-                                                            evt.stop_propagation();
-                                                            queue_index.set(song_index);
-                                                            now_playing.set(Some(song.clone()));
-                                                            is_playing.set(true);
+
+                                                div { class: "min-w-0",
+                                                    div { class: "flex items-center gap-2 min-w-0",
+                                                        p {
+                                                            class: if unavailable { "text-zinc-500 line-through truncate" } else if is_current { "text-emerald-400 font-medium truncate" } else { "text-zinc-300 truncate group-hover:text-white" },
+                                                            title: if unavailable { UNAVAILABLE_SONG_TOOLTIP } else { "" },
+                                                            HighlightedText {
+                                                                text: song.title.clone(),
+                                                                query: queue_filter_query.clone(),
+                                                            }
                                                         }
-                                                    },
-                                                    {
-                                                        match cover_url {
-                                                            Some(url) => rsx! {
-                                                                img {
-                                                                    src: "{url}",
-                                                                    alt: "{song.title}",
-                                                                    class: "w-full h-full object-cover",
-                                                                    loading: "lazy",
+                                                        button {
+                                                            class: "p-1 rounded-md text-zinc-500 hover:text-white hover:bg-zinc-700/60 transition-colors flex-shrink-0",
+                                                            aria_label: "Song actions",
+                                                            onclick: {
+                                                                let song = song.clone();
+                                                                let mut queue_song_menu = queue_song_menu.clone();
+                                                                let song_index = idx;
+                                                                move |evt: MouseEvent| {
+                                                                    evt.stop_propagation();
+                                                                    let coords = evt.client_coordinates();
+                                                                    queue_song_menu.set(Some((
+                                                                        song.clone(),
+                                                                        song_index,
+                                                                        coords.x,
+                                                                        coords.y,
+                                                                    )));
                                                                 }
                                                             },
-                                                            None => rsx! {
-                                                                div { class: "w-full h-full flex items-center justify-center bg-gradient-to-br from-zinc-700 to-zinc-800",
-                                                                    Icon { name: "music".to_string(), class: "w-4 h-4 text-zinc-500".to_string() }
+                                                            Icon { name: "more-horizontal".to_string(), class: "w-4 h-4".to_string() }
+                                                        }
+                                                    }
+                                                    ArtistNameLinks {
+                                                        artist_text: song.artist.clone().unwrap_or_default(),
+                                                        server_id: song.server_id.clone(),
+                                                        fallback_artist_id: song.artist_id.clone(),
+                                                        container_class: "inline-flex max-w-full min-w-0 items-center gap-1 text-xs text-zinc-500".to_string(),
+                                                        button_class: "inline-flex max-w-fit truncate text-left hover:text-emerald-400 transition-colors".to_string(),
+                                                        separator_class: "text-zinc-600".to_string(),
+                                                    }
+                                                    if song.album_id.is_some() {
+                                                        button {
+                                                            class: "text-xs text-zinc-600 truncate hover:text-emerald-400 transition-colors hidden sm:block",
+                                                            onclick: {
+                                                                let album_id = song.album_id.clone();
+                                                                let server_id = song.server_id.clone();
+                                                                let navigation = navigation.clone();
+                                                                move |evt: MouseEvent| {
+                                                                    evt.stop_propagation();
+                                                                    if let Some(album_id) = album_id.clone() {
+                                                                        navigation
+                                                                            .navigate_to(AppView::AlbumDetailView {
+                                                                                album_id,
+                                                                                server_id: server_id.clone(),
+                                                                            });
+                                                                    }
                                                                 }
                                                             },
+                                                            HighlightedText {
+                                                                text: song.album.clone().unwrap_or_default(),
+                                                                query: queue_filter_query.clone(),
+                                                            }
+                                                        }
+                                                    } else {
+                                                        p { class: "text-xs text-zinc-600 truncate hidden sm:block",
+                                                            HighlightedText {
+                                                                text: song.album.clone().unwrap_or_default(),
+                                                                query: queue_filter_query.clone(),
+                                                            }
                                                         }
                                                     }
                                                 }
                                             }
 
-                                            div { class: "min-w-0",
-                                                div { class: "flex items-center gap-2 min-w-0",
-                                                    p { class: if is_current { "text-emerald-400 font-medium truncate" } else { "text-zinc-300 truncate group-hover:text-white" },
-                                                        "{song.title}"
-                                                    }
+                                            div { class: "flex items-center gap-4",
+                                                span { class: "text-sm text-zinc-600 font-mono group-hover:hidden",
+                                                    "{format_duration(song.duration)}"
+                                                }
+
+                                                div { class: "flex flex-col gap-1",
                                                     button {
-                                                        class: "p-1 rounded-md text-zinc-500 hover:text-white hover:bg-zinc-700/60 transition-colors flex-shrink-0",
-                                                        aria_label: "Song actions",
+                                                        r#type: "button",
+                                                        class: if can_move_up {
+                                                            "w-7 h-7 rounded-md border border-zinc-700/80 text-zinc-300 hover:text-white hover:border-emerald-500/60 transition-colors flex items-center justify-center"
+                                                        } else {
+                                                            "w-7 h-7 rounded-md border border-zinc-800 text-zinc-600 cursor-not-allowed flex items-center justify-center"
+                                                        },
+                                                        title: "Move up",
+                                                        disabled: !can_move_up,
                                                         onclick: {
-                                                            let song = song.clone();
-                                                            let mut queue_song_menu = queue_song_menu.clone();
-                                                            let song_index = idx;
+                                                            let queue = queue.clone();
+                                                            let queue_index = queue_index.clone();
+                                                            let now_playing = now_playing.clone();
+                                                            let source_index = idx;
                                                             move |evt: MouseEvent| {
                                                                 evt.stop_propagation();
-                                                                let coords = evt.client_coordinates();
-                                                                queue_song_menu.set(Some((
-                                                                    song.clone(),
-                                                                    song_index,
-                                                                    coords.x,
-                                                                    coords.y,
-                                                                )));
+                                                                if !can_move_up {
+                                                                    return;
+                                                                }
+                                                                reorder_queue_entry(
+                                                                    queue.clone(),
+                                                                    queue_index.clone(),
+                                                                    now_playing.clone(),
+                                                                    source_index,
+                                                                    source_index.saturating_sub(1),
+                                                                );
                                                             }
                                                         },
-                                                        Icon { name: "more-horizontal".to_string(), class: "w-4 h-4".to_string() }
+                                                        Icon { name: "chevron-up".to_string(), class: "w-3.5 h-3.5".to_string() }
                                                     }
-                                                }
-                                                ArtistNameLinks {
-                                                    artist_text: song.artist.clone().unwrap_or_default(),
-                                                    server_id: song.server_id.clone(),
-                                                    fallback_artist_id: song.artist_id.clone(),
-                                                    container_class: "inline-flex max-w-full min-w-0 items-center gap-1 text-xs text-zinc-500".to_string(),
-                                                    button_class: "inline-flex max-w-fit truncate text-left hover:text-emerald-400 transition-colors".to_string(),
-                                                    separator_class: "text-zinc-600".to_string(),
-                                                }
-                                                if song.album_id.is_some() {
                                                     button {
-                                                        class: "text-xs text-zinc-600 truncate hover:text-emerald-400 transition-colors hidden sm:block",
+                                                        r#type: "button",
+                                                        class: if can_move_down {
+                                                            "w-7 h-7 rounded-md border border-zinc-700/80 text-zinc-300 hover:text-white hover:border-emerald-500/60 transition-colors flex items-center justify-center"
+                                                        } else {
+                                                            "w-7 h-7 rounded-md border border-zinc-800 text-zinc-600 cursor-not-allowed flex items-center justify-center"
+                                                        },
+                                                        title: "Move down",
+                                                        disabled: !can_move_down,
                                                         onclick: {
-                                                            let album_id = song.album_id.clone();
-                                                            let server_id = song.server_id.clone();
-                                                            let navigation = navigation.clone();
+                                                            let queue = queue.clone();
+                                                            let queue_index = queue_index.clone();
+                                                            let now_playing = now_playing.clone();
+                                                            let source_index = idx;
                                                             move |evt: MouseEvent| {
                                                                 evt.stop_propagation();
-                                                                if let Some(album_id) = album_id.clone() {
-                                                                    navigation
-                                                                        .navigate_to(AppView::AlbumDetailView {
-                                                                            album_id,
-                                                                            server_id: server_id.clone(),
-                                                                        });
+                                                                if !can_move_down {
+                                                                    return;
                                                                 }
+                                                                reorder_queue_entry(
+                                                                    queue.clone(),
+                                                                    queue_index.clone(),
+                                                                    now_playing.clone(),
+                                                                    source_index,
+                                                                    source_index.saturating_add(1),
+                                                                );
                                                             }
                                                         },
-                                                        "{song.album.as_ref().map(|s| s.as_str()).unwrap_or(\"\")}"
-                                                    }
-                                                } else {
-                                                    p { class: "text-xs text-zinc-600 truncate hidden sm:block",
-                                                        "{song.album.as_ref().map(|s| s.as_str()).unwrap_or(\"\")}"
+                                                        Icon { name: "chevron-down".to_string(), class: "w-3.5 h-3.5".to_string() }
                                                     }
                                                 }
+
+                                                button {
+                                                    class: "p-2 text-zinc-500 hover:text-red-400 transition-colors opacity-100 md:opacity-0 md:group-hover:opacity-100",
+                                                    onclick: move |evt| {
+                                                        evt.stop_propagation();
+                                                        remove_queue_entry(
+                                                            queue.clone(),
+                                                            queue_index.clone(),
+                                                            now_playing.clone(),
+                                                            is_playing.clone(),
+                                                            idx,
+                                                        );
+                                                    },
+                                                    Icon { name: "x".to_string(), class: "w-4 h-4".to_string() }
+                                                }
                                             }
                                         }
-
-                                        div { class: "flex items-center gap-4",
-                                            span { class: "text-sm text-zinc-600 font-mono group-hover:hidden",
-                                                "{format_duration(song.duration)}"
+                                        div {
+                                            class: if is_current { "{SONG_TABLE_ROW_CLASS} bg-emerald-500/5 group cursor-pointer select-none" } else { "{SONG_TABLE_ROW_CLASS} hover:bg-zinc-700/30 transition-colors group cursor-pointer select-none" },
+                                            style: "{table_grid_style}",
+                                            onclick: {
+                                                let song = song.clone();
+                                                move |_| {
+                                                    if !is_current {
+                                                        queue_index.set(idx);
+                                                        now_playing.set(Some(song.clone()));
+                                                        is_playing.set(true);
+                                                    }
+                                                }
+                                            },
+                                            SongTableCells {
+                                                song: song.clone(),
+                                                columns: table_columns.clone(),
+                                                display_index: idx + 1,
+                                                is_current,
+                                                rating: song.user_rating.unwrap_or(0).min(5),
+                                                art: show_queue_art.then(|| row_art.clone()),
+                                                highlight: queue_filter_query.clone(),
                                             }
-
-                                            div { class: "flex flex-col gap-1",
+                                            div { class: "flex items-center justify-end gap-1",
                                                 button {
-                                                    r#type: "button",
-                                                    class: if can_move_up {
-                                                        "w-7 h-7 rounded-md border border-zinc-700/80 text-zinc-300 hover:text-white hover:border-emerald-500/60 transition-colors flex items-center justify-center"
-                                                    } else {
-                                                        "w-7 h-7 rounded-md border border-zinc-800 text-zinc-600 cursor-not-allowed flex items-center justify-center"
-                                                    },
-                                                    title: "Move up",
-                                                    disabled: !can_move_up,
+                                                    class: "p-1.5 rounded-lg text-zinc-500 hover:text-white hover:bg-zinc-700/60 transition-colors",
+                                                    aria_label: "Song actions",
                                                     onclick: {
-                                                        let queue = queue.clone();
-                                                        let queue_index = queue_index.clone();
-                                                        let now_playing = now_playing.clone();
-                                                        let source_index = idx;
+                                                        let song = song.clone();
                                                         move |evt: MouseEvent| {
                                                             evt.stop_propagation();
-                                                            if !can_move_up {
-                                                                return;
-                                                            }
-                                                            reorder_queue_entry(
-                                                                queue.clone(),
-                                                                queue_index.clone(),
-                                                                now_playing.clone(),
-                                                                source_index,
-                                                                source_index.saturating_sub(1),
-                                                            );
+                                                            let coords = evt.client_coordinates();
+                                                            queue_song_menu.set(Some((
+                                                                song.clone(),
+                                                                idx,
+                                                                coords.x,
+                                                                coords.y,
+                                                            )));
                                                         }
                                                     },
-                                                    Icon { name: "chevron-up".to_string(), class: "w-3.5 h-3.5".to_string() }
+                                                    Icon { name: "more-horizontal".to_string(), class: "w-4 h-4".to_string() }
                                                 }
                                                 button {
-                                                    r#type: "button",
-                                                    class: if can_move_down {
-                                                        "w-7 h-7 rounded-md border border-zinc-700/80 text-zinc-300 hover:text-white hover:border-emerald-500/60 transition-colors flex items-center justify-center"
-                                                    } else {
-                                                        "w-7 h-7 rounded-md border border-zinc-800 text-zinc-600 cursor-not-allowed flex items-center justify-center"
-                                                    },
-                                                    title: "Move down",
-                                                    disabled: !can_move_down,
-                                                    onclick: {
-                                                        let queue = queue.clone();
-                                                        let queue_index = queue_index.clone();
-                                                        let now_playing = now_playing.clone();
-                                                        let source_index = idx;
-                                                        move |evt: MouseEvent| {
-                                                            evt.stop_propagation();
-                                                            if !can_move_down {
-                                                                return;
-                                                            }
-                                                            reorder_queue_entry(
-                                                                queue.clone(),
-                                                                queue_index.clone(),
-                                                                now_playing.clone(),
-                                                                source_index,
-                                                                source_index.saturating_add(1),
-                                                            );
-                                                        }
+                                                    class: "p-1.5 rounded-lg text-zinc-500 hover:text-red-400 transition-colors",
+                                                    aria_label: "Remove from queue",
+                                                    onclick: move |evt| {
+                                                        evt.stop_propagation();
+                                                        remove_queue_entry(
+                                                            queue,
+                                                            queue_index,
+                                                            now_playing,
+                                                            is_playing,
+                                                            idx,
+                                                        );
                                                     },
-                                                    Icon { name: "chevron-down".to_string(), class: "w-3.5 h-3.5".to_string() }
+                                                    Icon { name: "x".to_string(), class: "w-4 h-4".to_string() }
                                                 }
                                             }
-
-                                            button {
-                                                class: "p-2 text-zinc-500 hover:text-red-400 transition-colors opacity-100 md:opacity-0 md:group-hover:opacity-100",
-                                                onclick: move |evt| {
-                                                    evt.stop_propagation();
-                                                    remove_queue_entry(
-                                                        queue.clone(),
-                                                        queue_index.clone(),
-                                                        now_playing.clone(),
-                                                        is_playing.clone(),
-                                                        idx,
-                                                    );
-                                                },
-                                                Icon { name: "x".to_string(), class: "w-4 h-4".to_string() }
-                                            }
                                         }
                                     }
                                 }
@@ -1362,7 +1504,17 @@ pub fn QueueView() -> Element {
             if let Some((menu_song, menu_index, menu_x, menu_y)) = queue_song_menu() {
                 {
                     let downloaded = is_song_downloaded(&menu_song);
+                    let can_download = capabilities.user_can(&menu_song.server_id, UserAction::Download);
+                    let can_rate = capabilities.user_can(&menu_song.server_id, UserAction::Rate);
                     let effective_rating = menu_song.user_rating.unwrap_or(0).min(5);
+                    let menu_album_id = menu_song.album_id.clone();
+                    let menu_primary_artist = menu_song
+                        .artist
+                        .as_deref()
+                        .map(parse_artist_names)
+                        .and_then(|names| names.into_iter().next());
+                    let can_go_to_artist =
+                        menu_song.artist_id.is_some() || menu_primary_artist.is_some();
                     rsx! {
                         div {
                             class: "fixed inset-0 z-[9998]",
@@ -1394,12 +1546,79 @@ pub fn QueueView() -> Element {
                                 Icon { name: "plus".to_string(), class: "w-4 h-4".to_string() }
                                 "Add To..."
                             }
+                            if let Some(album_id) = menu_album_id {
+                                button {
+                                    class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                                    onclick: {
+                                        let server_id = menu_song.server_id.clone();
+                                        move |evt: MouseEvent| {
+                                            evt.stop_propagation();
+                                            queue_song_menu.set(None);
+                                            navigation.navigate_to(AppView::AlbumDetailView {
+                                                album_id: album_id.clone(),
+                                                server_id: server_id.clone(),
+                                            });
+                                        }
+                                    },
+                                    Icon { name: "album".to_string(), class: "w-4 h-4".to_string() }
+                                    "Go to album"
+                                }
+                            }
+                            if can_go_to_artist {
+                                button {
+                                    class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                                    onclick: {
+                                        let artist_id = menu_song.artist_id.clone();
+                                        let artist_name = menu_primary_artist.clone();
+                                        let server_id = menu_song.server_id.clone();
+                                        move |evt: MouseEvent| {
+                                            evt.stop_propagation();
+                                            queue_song_menu.set(None);
+                                            if let Some(artist_id) = artist_id.clone() {
+                                                navigation.navigate_to(AppView::ArtistDetailView {
+                                                    artist_id,
+                                                    server_id: server_id.clone(),
+                                                });
+                                                return;
+                                            }
+                                            let Some(artist_name) = artist_name.clone() else {
+                                                return;
+                                            };
+                                            let Some(server) =
+                                                servers().iter().find(|s| s.id == server_id).cloned()
+                                            else {
+                                                return;
+                                            };
+                                            let server_id = server_id.clone();
+                                            spawn(async move {
+                                                if let Some(artist_id) =
+                                                    resolve_artist_id_for_name(server, artist_name).await
+                                                {
+                                                    navigation.navigate_to(AppView::ArtistDetailView {
+                                                        artist_id,
+                                                        server_id,
+                                                    });
+                                                }
+                                            });
+                                        }
+                                    },
+                                    Icon { name: "artist".to_string(), class: "w-4 h-4".to_string() }
+                                    "Go to artist"
+                                }
+                            }
                             if downloaded {
                                 div {
                                     class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-emerald-300 bg-emerald-500/10",
                                     Icon { name: "check".to_string(), class: "w-4 h-4".to_string() }
                                     "Downloaded"
                                 }
+                            } else if !can_download {
+                                div {
+                                    class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-500 cursor-not-allowed",
+                                    title: capabilities.denied_hint(UserAction::Download),
+                                    Icon { name: "download".to_string(), class: "w-4 h-4".to_string() }
+                                    "Download"
+                                }
                             } else {
                                 button {
                                     class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
@@ -1435,6 +1654,7 @@ pub fn QueueView() -> Element {
                             }
                             button {
                                 class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                                disabled: !can_rate,
                                 onclick: {
                                     let mut queue_song_menu = queue_song_menu.clone();
                                     let mut queue = queue.clone();
@@ -1511,6 +1731,7 @@ pub fn QueueView() -> Element {
                                 for i in 1..=5 {
                                     button {
                                         class: "p-1 rounded text-amber-400 hover:text-amber-300 transition-colors",
+                                        disabled: !can_rate,
                                         onclick: {
                                             let mut queue_song_menu = queue_song_menu.clone();
                                             let mut queue = queue.clone();
@@ -1591,6 +1812,10 @@ pub fn QueueView() -> Element {
                     }
                 }
             }
+
+            if current_index < queue_len {
+                NowPlayingPill { follow: queue_follow, current_index }
+            }
         }
     }
 }
@@ -1786,7 +2011,7 @@ async fn search_queue_add_candidates(servers: Vec<ServerConfig>, query: String)
         let server_start = PerfTimer::now();
         let server_name = server.name.clone();
         let client = NavidromeClient::new(server.clone());
-        let Ok(search) = client.search(&normalized_query, 0, 0, 20).await else {
+        let Ok(search) = client.search(&normalized_query, 0, 0, 20, None).await else {
             log_perf(
                 "queue.search.server",
                 server_start,
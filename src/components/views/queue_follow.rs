@@ -0,0 +1,238 @@
+//! Where the playing row of the queue sits relative to the viewport, a pill that jumps back
+//! to it, and the optional auto-follow that keeps it centered as tracks advance.
+use super::song_list_filter::scroll_to_row;
+use crate::components::Icon;
+use dioxus::prelude::*;
+use serde::Deserialize;
+
+/// How long a manual scroll pauses auto-follow.
+const FOLLOW_PAUSE_AFTER_SCROLL_MS: u64 = 10_000;
+
+/// Watches `#queue-row-{data-current-index}` inside `#queue-list` and reports whether it is
+/// on screen above the player bar. A row without an element (outside a virtualized list's
+/// rendered window, or filtered out) is placed relative to the rows that are rendered.
+/// Wheel, touch and scroll-key input is reported as a manual scroll.
+const CURRENT_ROW_TRACKER_JS: &str = r#"
+window.__rustyQueueTracker?.stop();
+let current = null;
+let placement = null;
+let lastManual = 0;
+let stopTracker = () => {};
+const report = (next) => {
+  if (next === placement) return;
+  placement = next;
+  dioxus.send({ placement: next });
+};
+const list = () => document.getElementById("queue-list");
+const observer = new IntersectionObserver((entries) => {
+  for (const entry of entries) {
+    if (entry.target !== current) continue;
+    if (entry.isIntersecting) {
+      report("visible");
+    } else {
+      const top = entry.rootBounds ? entry.rootBounds.top : 0;
+      report(entry.boundingClientRect.top < top ? "above" : "below");
+    }
+  }
+}, { threshold: 0.5, rootMargin: "0px 0px -96px 0px" });
+const placeMissing = (index) => {
+  const rendered = [...(list()?.querySelectorAll("[data-queue-index]") ?? [])]
+    .map((row) => Number(row.dataset.queueIndex));
+  if (index < 0 || rendered.length === 0) return report("unknown");
+  if (index < Math.min(...rendered)) return report("above");
+  if (index > Math.max(...rendered)) return report("below");
+  report("unknown");
+};
+const retarget = () => {
+  const index = Number(list()?.dataset.currentIndex ?? -1);
+  const row = index >= 0 ? document.getElementById(`queue-row-${index}`) : null;
+  if (row && row === current) return;
+  if (current) observer.unobserve(current);
+  current = row;
+  if (row) {
+    observer.observe(row);
+  } else {
+    placeMissing(index);
+  }
+};
+const scrollKeys = ["ArrowUp", "ArrowDown", "PageUp", "PageDown", "Home", "End", " "];
+const manual = (event) => {
+  if (event.type === "keydown" && !scrollKeys.includes(event.key)) return;
+  const now = Date.now();
+  if (now - lastManual < 500) return;
+  lastManual = now;
+  dioxus.send({ manual_scroll: true });
+};
+const inputs = ["wheel", "touchmove", "keydown"];
+inputs.forEach((name) => window.addEventListener(name, manual, { passive: true, capture: true }));
+const mutations = new MutationObserver(retarget);
+mutations.observe(document.body, {
+  childList: true,
+  subtree: true,
+  attributes: true,
+  attributeFilter: ["data-current-index"],
+});
+window.__rustyQueueTracker = {
+  stop() {
+    observer.disconnect();
+    mutations.disconnect();
+    inputs.forEach((name) => window.removeEventListener(name, manual, { capture: true }));
+    stopTracker();
+  },
+};
+retarget();
+await new Promise((resolve) => { stopTracker = resolve; });
+"#;
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn follow_delay_ms(ms: u64) {
+    tokio::time::sleep(std::time::Duration::from_millis(ms)).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn follow_delay_ms(ms: u64) {
+    gloo_timers::future::TimeoutFuture::new(ms as u32).await;
+}
+
+fn now_ms() -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Date::now().max(0.0).round() as u64
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or(0)
+    }
+}
+
+/// Where the playing row is, as last reported by the tracker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum CurrentRowPlacement {
+    Visible,
+    Above,
+    Below,
+    /// No playing row, or it isn't rendered and can't be placed.
+    Unknown,
+}
+
+#[derive(Deserialize)]
+struct TrackerMessage {
+    #[serde(default)]
+    placement: Option<CurrentRowPlacement>,
+    #[serde(default)]
+    manual_scroll: bool,
+}
+
+/// Whether auto-follow is still paused by a manual scroll at `manual_scroll_at`.
+fn follow_suspended(manual_scroll_at: Option<u64>, now: u64) -> bool {
+    manual_scroll_at
+        .is_some_and(|scrolled_at| now.saturating_sub(scrolled_at) < FOLLOW_PAUSE_AFTER_SCROLL_MS)
+}
+
+fn scroll_to_queue_row(index: usize) {
+    scroll_to_row(&format!("queue-row-{index}"));
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(super) struct QueueFollow {
+    pub placement: Signal<CurrentRowPlacement>,
+    manual_scroll_at: Signal<Option<u64>>,
+}
+
+impl QueueFollow {
+    /// Scrolls back to the playing row; auto-follow resumes right away.
+    pub fn jump_to_current(mut self, index: usize) {
+        self.manual_scroll_at.set(None);
+        scroll_to_queue_row(index);
+    }
+}
+
+/// Tracks the playing row of the queue view and, while `auto_follow` is on, centers it
+/// whenever `queue_index` changes or a manual-scroll pause runs out.
+pub(super) fn use_queue_follow(queue_index: Signal<usize>, auto_follow: Memo<bool>) -> QueueFollow {
+    let mut placement = use_signal(|| CurrentRowPlacement::Unknown);
+    let mut manual_scroll_at = use_signal(|| None::<u64>);
+
+    use_hook(move || {
+        spawn(async move {
+            let mut tracker = document::eval(CURRENT_ROW_TRACKER_JS);
+            while let Ok(message) = tracker.recv::<TrackerMessage>().await {
+                if let Some(next) = message.placement {
+                    placement.set(next);
+                }
+                if !message.manual_scroll {
+                    continue;
+                }
+                let scrolled_at = now_ms();
+                manual_scroll_at.set(Some(scrolled_at));
+                spawn(async move {
+                    follow_delay_ms(FOLLOW_PAUSE_AFTER_SCROLL_MS).await;
+                    let still_latest = *manual_scroll_at.peek() == Some(scrolled_at);
+                    if still_latest
+                        && *auto_follow.peek()
+                        && *placement.peek() != CurrentRowPlacement::Visible
+                    {
+                        scroll_to_queue_row(*queue_index.peek());
+                    }
+                });
+            }
+        });
+    });
+    use_drop(|| {
+        let _ = document::eval("window.__rustyQueueTracker?.stop();");
+    });
+
+    use_effect(move || {
+        let index = queue_index();
+        if auto_follow() && !follow_suspended(*manual_scroll_at.peek(), now_ms()) {
+            scroll_to_queue_row(index);
+        }
+    });
+
+    QueueFollow {
+        placement,
+        manual_scroll_at,
+    }
+}
+
+/// Floating "Now playing" pill shown while the playing row is scrolled out of view.
+#[component]
+pub(super) fn NowPlayingPill(follow: QueueFollow, current_index: usize) -> Element {
+    let direction = match (follow.placement)() {
+        CurrentRowPlacement::Above => "up",
+        CurrentRowPlacement::Below => "down",
+        CurrentRowPlacement::Visible | CurrentRowPlacement::Unknown => return rsx! {},
+    };
+    rsx! {
+        button {
+            r#type: "button",
+            class: "fixed bottom-32 md:bottom-28 left-1/2 -translate-x-1/2 z-40 px-4 py-2 rounded-full bg-emerald-500 text-black text-sm font-medium shadow-lg shadow-black/40 hover:bg-emerald-400 transition-colors flex items-center gap-2",
+            title: "Scroll to the playing song",
+            onclick: move |_| follow.jump_to_current(current_index),
+            "Now playing"
+            Icon {
+                name: format!("chevron-{direction}"),
+                class: "w-4 h-4".to_string(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manual_scroll_pauses_follow_for_ten_seconds() {
+        assert!(!follow_suspended(None, 50_000));
+        assert!(follow_suspended(Some(50_000), 50_000));
+        assert!(follow_suspended(Some(50_000), 59_999));
+        assert!(!follow_suspended(Some(50_000), 60_000));
+        // A clock that went backwards keeps the pause rather than skipping it.
+        assert!(follow_suspended(Some(50_000), 40_000));
+    }
+}
@@ -0,0 +1,228 @@
+//! "Export queue" / "Import queue" for the play queue. Exports are written as server-agnostic
+//! queue files; imports resolve each track against an active server and show what was found
+//! before the queue is replaced or extended.
+use crate::api::{
+    parse_queue_export, queue_export_json, resolve_queue_export, NavidromeClient,
+    QueueImportReport, ServerConfig, Song, TrackDescriptor,
+};
+use crate::components::audio_manager::normalize_manual_queue_songs;
+use crate::components::Icon;
+use crate::storage::save_text_export;
+use dioxus::prelude::*;
+
+/// Unresolved tracks listed under each heading of the report.
+const REPORT_EXAMPLES: usize = 5;
+
+fn descriptor_label(track: &TrackDescriptor) -> String {
+    match track.artist.as_deref() {
+        Some(artist) if !artist.trim().is_empty() => format!("{} · {artist}", track.title),
+        _ => track.title.clone(),
+    }
+}
+
+/// The first few tracks of one report heading.
+#[component]
+fn UnresolvedTracks(label: String, tracks: Vec<TrackDescriptor>) -> Element {
+    if tracks.is_empty() {
+        return rsx! {};
+    }
+    rsx! {
+        div { class: "space-y-0.5",
+            p { class: "text-[10px] uppercase tracking-[0.18em] text-zinc-500", "{label}" }
+            for track in tracks.iter().take(REPORT_EXAMPLES) {
+                p { class: "text-xs text-zinc-400 truncate", "{descriptor_label(track)}" }
+            }
+        }
+    }
+}
+
+/// Header buttons of the queue page, plus the import report shown over the page.
+#[component]
+pub(super) fn QueueTransferControls() -> Element {
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let mut queue = use_context::<Signal<Vec<Song>>>();
+    let mut queue_index = use_context::<Signal<usize>>();
+    let mut now_playing = use_context::<Signal<Option<Song>>>();
+    let mut is_playing = use_context::<crate::components::IsPlayingSignal>().0;
+    let mut report = use_signal(|| None::<(String, QueueImportReport)>);
+    let mut importing = use_signal(|| false);
+    let mut status = use_signal(|| None::<Result<String, String>>);
+    let mut import_server_id = use_signal(|| None::<String>);
+
+    let active_servers: Vec<ServerConfig> = servers()
+        .into_iter()
+        .filter(|server| server.active)
+        .collect();
+    let target_server = import_server_id()
+        .and_then(|id| {
+            active_servers
+                .iter()
+                .find(|server| server.id == id)
+                .cloned()
+        })
+        .or_else(|| active_servers.first().cloned());
+    let has_queue = !queue().is_empty();
+
+    let on_export = move |_| {
+        let songs = queue.peek().clone();
+        let index = (!songs.is_empty()).then(|| *queue_index.peek());
+        let result = queue_export_json(&songs, index)
+            .and_then(|json| save_text_export("queue.json", &json, "application/json"));
+        status.set(Some(result));
+    };
+
+    let on_import_file = {
+        let target_server = target_server.clone();
+        move |evt: FormEvent| {
+            let Some(file) = evt.files().into_iter().next() else {
+                return;
+            };
+            let Some(server) = target_server.clone() else {
+                status.set(Some(Err(
+                    "Add an active server to import a queue.".to_string()
+                )));
+                return;
+            };
+            importing.set(true);
+            status.set(None);
+            spawn(async move {
+                let outcome = match file.read_string().await {
+                    Ok(text) => match parse_queue_export(&text) {
+                        Ok(export) => {
+                            resolve_queue_export(&NavidromeClient::new(server.clone()), &export)
+                                .await
+                                .map_err(|error| {
+                                    format!("Could not search {}: {error}", server.name)
+                                })
+                        }
+                        Err(error) => Err(error),
+                    },
+                    Err(error) => Err(format!("Could not read the file: {error}")),
+                };
+                match outcome {
+                    Ok(resolved) => report.set(Some((server.name.clone(), resolved))),
+                    Err(error) => status.set(Some(Err(error))),
+                }
+                importing.set(false);
+            });
+        }
+    };
+
+    let mut on_apply = move |replace: bool| {
+        let Some((_, resolved)) = report() else {
+            return;
+        };
+        let songs = normalize_manual_queue_songs(resolved.songs);
+        let added = songs.len();
+        if replace {
+            let index = resolved.current_index.unwrap_or(0);
+            queue.set(songs.clone());
+            queue_index.set(index);
+            now_playing.set(songs.get(index).cloned());
+            is_playing.set(true);
+            status.set(Some(Ok(format!(
+                "Replaced the queue with {added} song(s)."
+            ))));
+        } else {
+            queue.with_mut(|items| items.extend(songs));
+            status.set(Some(Ok(format!("Added {added} song(s) to the queue."))));
+        }
+        report.set(None);
+    };
+
+    rsx! {
+        if has_queue {
+            button {
+                class: "px-4 py-2 rounded-xl bg-zinc-800 hover:bg-zinc-700 text-zinc-300 hover:text-white transition-colors flex items-center gap-2",
+                onclick: on_export,
+                Icon { name: "download".to_string(), class: "w-4 h-4".to_string() }
+                "Export Queue"
+            }
+        }
+        label {
+            class: if importing() { "px-4 py-2 rounded-xl bg-zinc-800 text-zinc-500 flex items-center gap-2 cursor-wait" } else { "px-4 py-2 rounded-xl bg-zinc-800 hover:bg-zinc-700 text-zinc-300 hover:text-white transition-colors flex items-center gap-2 cursor-pointer" },
+            Icon { name: "upload".to_string(), class: "w-4 h-4".to_string() }
+            if importing() {
+                "Resolving..."
+            } else {
+                "Import Queue"
+            }
+            input {
+                r#type: "file",
+                accept: ".json,application/json",
+                class: "hidden",
+                disabled: importing(),
+                onchange: on_import_file,
+            }
+        }
+        if active_servers.len() > 1 {
+            select {
+                class: "px-3 py-2 rounded-xl bg-zinc-800 border border-zinc-700 text-sm text-zinc-300",
+                title: "Server to resolve imported queues against",
+                value: target_server.as_ref().map(|server| server.id.clone()).unwrap_or_default(),
+                onchange: move |evt| import_server_id.set(Some(evt.value())),
+                for server in active_servers.iter() {
+                    option { key: "{server.id}", value: "{server.id}", "{server.name}" }
+                }
+            }
+        }
+        match status() {
+            Some(Ok(message)) => rsx! {
+                span { class: "text-xs text-emerald-400", "{message}" }
+            },
+            Some(Err(message)) => rsx! {
+                span { class: "text-xs text-red-400", "{message}" }
+            },
+            None => rsx! {},
+        }
+        if let Some((server_name, resolved)) = report() {
+            div {
+                class: "fixed inset-0 z-[210] bg-zinc-950/95 backdrop-blur-sm overflow-y-auto px-4 py-8 flex items-center justify-center",
+                onclick: move |_| report.set(None),
+                div {
+                    class: "w-full max-w-lg bg-zinc-900/60 border border-zinc-700/50 rounded-2xl p-6 space-y-4",
+                    onclick: move |evt: MouseEvent| evt.stop_propagation(),
+                    div { class: "space-y-1",
+                        h2 { class: "text-lg font-semibold text-white", "Import Queue" }
+                        p { class: "text-sm text-zinc-400",
+                            "{resolved.resolved()} of {resolved.resolved() + resolved.not_found.len()} tracks found on {server_name}."
+                        }
+                    }
+                    div { class: "grid grid-cols-2 gap-2 text-sm",
+                        p { class: "text-zinc-300", "Same id: {resolved.by_id}" }
+                        p { class: "text-zinc-300", "Matched: {resolved.matched}" }
+                        p { class: "text-amber-300", "Ambiguous: {resolved.ambiguous.len()}" }
+                        p { class: "text-red-300", "Not found: {resolved.not_found.len()}" }
+                    }
+                    UnresolvedTracks {
+                        label: "Ambiguous (best guess used)".to_string(),
+                        tracks: resolved.ambiguous.clone(),
+                    }
+                    UnresolvedTracks {
+                        label: "Not found (skipped)".to_string(),
+                        tracks: resolved.not_found.clone(),
+                    }
+                    div { class: "flex flex-wrap items-center gap-2",
+                        button {
+                            class: "px-3 py-1.5 rounded-lg bg-emerald-500/15 text-emerald-300 hover:bg-emerald-500/25 transition-colors text-sm disabled:opacity-50",
+                            disabled: resolved.songs.is_empty(),
+                            onclick: move |_| on_apply(true),
+                            "Replace queue"
+                        }
+                        button {
+                            class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white transition-colors text-sm disabled:opacity-50",
+                            disabled: resolved.songs.is_empty(),
+                            onclick: move |_| on_apply(false),
+                            "Append to queue"
+                        }
+                        button {
+                            class: "px-3 py-1.5 rounded-lg text-zinc-400 hover:text-white transition-colors text-sm",
+                            onclick: move |_| report.set(None),
+                            "Cancel"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,12 @@
 use crate::api::*;
-use crate::components::Icon;
+use crate::components::audio_manager::{
+    assign_collection_queue_meta, generate_artist_station_songs, ARTIST_STATION_BATCH,
+};
+use crate::components::{
+    sized_cover_art_url, ArtworkKind, Icon, RadioRawTitleSignal, ServerCapabilitiesSignal,
+    SettingsController,
+};
+use crate::db::{radio_station_template_key, AppSettings, ArtistStation};
 use dioxus::prelude::*;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -22,20 +29,26 @@ enum RadioFormMode {
 #[component]
 pub fn RadioView() -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
     let mut now_playing = use_context::<Signal<Option<Song>>>();
     let mut queue = use_context::<Signal<Vec<Song>>>();
     let mut queue_index = use_context::<Signal<usize>>();
     let mut is_playing = use_context::<crate::components::IsPlayingSignal>().0;
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let settings_controller = use_context::<SettingsController>();
+    let mut radio_raw_title = use_context::<RadioRawTitleSignal>().0;
 
     let form_mode = use_signal(|| RadioFormMode::Closed);
     let mut form_name = use_signal(String::new);
     let mut form_stream_url = use_signal(String::new);
     let mut form_home_page_url = use_signal(String::new);
     let mut form_server_id = use_signal(String::new);
+    let mut form_title_template = use_signal(String::new);
     let error_message = use_signal(|| None::<String>);
     let is_saving = use_signal(|| false);
     let refresh_key = use_signal(|| 0u32);
     let metadata_poll_generation = use_signal(|| 0u64);
+    let mut loading_artist_station = use_signal(|| None::<String>);
 
     // Poll ICY metadata for the currently playing radio stream and update title/artist live.
     {
@@ -77,8 +90,10 @@ pub fn RadioView() -> Element {
             let is_playing = is_playing.clone();
             let metadata_poll_generation = metadata_poll_generation.clone();
 
+            let template_key = radio_station_template_key(&song.server_id, &song.id);
+
             spawn(async move {
-                let mut last_raw_title = String::new();
+                let mut last_parse_input = String::new();
 
                 loop {
                     if *metadata_poll_generation.peek() != generation {
@@ -96,26 +111,42 @@ pub fn RadioView() -> Element {
                             break;
                         }
 
-                        if !meta.raw_title.is_empty() && meta.raw_title != last_raw_title {
-                            last_raw_title = meta.raw_title.clone();
-                            let mut next_title = meta.title.trim().to_string();
-                            let next_artist = meta
-                                .artist
-                                .filter(|value| !value.trim().is_empty())
-                                .unwrap_or_else(|| fallback_station.clone());
-
-                            if next_title.is_empty()
-                                || next_title.eq_ignore_ascii_case(&fallback_station)
-                                || next_title.eq_ignore_ascii_case(&next_artist)
-                            {
-                                next_title = "Unknown Song".to_string();
-                            }
+                        let (template, slogan_patterns) = {
+                            let settings = app_settings.peek();
+                            (
+                                settings.radio_title_templates.get(&template_key).cloned(),
+                                settings.radio_slogan_patterns.clone(),
+                            )
+                        };
+                        // Re-parse when the template changes, not only when the title does.
+                        let parse_input = format!(
+                            "{}\n{}",
+                            meta.raw_title,
+                            template.as_deref().unwrap_or_default()
+                        );
+                        if !meta.raw_title.is_empty() && parse_input != last_parse_input {
+                            last_parse_input = parse_input;
+                            radio_raw_title.set(Some((song_id.clone(), meta.raw_title.clone())));
+                            let parts = parse_icy_stream_title(
+                                &meta.raw_title,
+                                template.as_deref(),
+                                &slogan_patterns,
+                            );
+                            // Slogans and station IDs show the station name alone.
+                            let (next_title, next_artist) = match parts.title {
+                                Some(title) if !title.eq_ignore_ascii_case(&fallback_station) => {
+                                    let artist =
+                                        parts.artist.unwrap_or_else(|| fallback_station.clone());
+                                    (title, Some(artist))
+                                }
+                                _ => (fallback_station.clone(), None),
+                            };
 
                             now_playing.with_mut(|current_song| {
                                 if let Some(song) = current_song {
                                     if song.id == song_id {
                                         song.title = next_title.clone();
-                                        song.artist = Some(next_artist.clone());
+                                        song.artist = next_artist.clone();
                                     }
                                 }
                             });
@@ -125,7 +156,7 @@ pub fn RadioView() -> Element {
                                 if let Some(song) = items.get_mut(idx) {
                                     if song.id == song_id {
                                         song.title = next_title.clone();
-                                        song.artist = Some(next_artist.clone());
+                                        song.artist = next_artist.clone();
                                     }
                                 }
                             });
@@ -143,7 +174,10 @@ pub fn RadioView() -> Element {
         let servers = servers();
         async move {
             let mut stations = Vec::new();
-            for server in servers.into_iter().filter(|s| s.active) {
+            let radio_servers = servers
+                .into_iter()
+                .filter(|s| s.active && capabilities.supports(&s.id, |c| c.internet_radio));
+            for server in radio_servers {
                 let client = NavidromeClient::new(server);
                 if let Ok(server_stations) = client.get_internet_radio_stations().await {
                     stations.extend(server_stations);
@@ -154,8 +188,12 @@ pub fn RadioView() -> Element {
     });
 
     let server_list = servers();
-    let active_servers: Vec<ServerConfig> =
-        server_list.iter().cloned().filter(|s| s.active).collect();
+    let has_connected_servers = server_list.iter().any(|s| s.active);
+    let active_servers: Vec<ServerConfig> = server_list
+        .iter()
+        .cloned()
+        .filter(|s| s.active && capabilities.supports(&s.id, |c| c.internet_radio))
+        .collect();
     let has_active_servers = !active_servers.is_empty();
 
     let form_mode_value = form_mode();
@@ -179,7 +217,7 @@ pub fn RadioView() -> Element {
         move |_| {
             let server_id = servers()
                 .into_iter()
-                .find(|s| s.active)
+                .find(|s| s.active && capabilities.supports(&s.id, |c| c.internet_radio))
                 .map(|s| s.id)
                 .unwrap_or_default();
             if server_id.is_empty() {
@@ -203,8 +241,10 @@ pub fn RadioView() -> Element {
         let mut error_message = error_message.clone();
         let mut refresh_key = refresh_key.clone();
         move |_| {
-            let active_servers: Vec<ServerConfig> =
-                servers().into_iter().filter(|s| s.active).collect();
+            let active_servers: Vec<ServerConfig> = servers()
+                .into_iter()
+                .filter(|s| s.active && capabilities.supports(&s.id, |c| c.internet_radio))
+                .collect();
             if active_servers.is_empty() {
                 error_message.set(Some(
                     "No active servers found. Please add and activate a server first.".to_string(),
@@ -236,6 +276,70 @@ pub fn RadioView() -> Element {
         }
     };
 
+    // Artist stations stream regular songs from the artist's server; the audio manager
+    // appends the next batch whenever the queue is about to run out.
+    let mut on_play_artist_station = move |station: ArtistStation| {
+        if loading_artist_station().is_some() {
+            return;
+        }
+        let mut error_message = error_message;
+        loading_artist_station.set(Some(station.id.clone()));
+        error_message.set(None);
+        let servers_snapshot = servers();
+        spawn(async move {
+            let songs = generate_artist_station_songs(
+                &servers_snapshot,
+                &station,
+                &[],
+                ARTIST_STATION_BATCH,
+            )
+            .await;
+            loading_artist_station.set(None);
+            if songs.is_empty() {
+                error_message.set(Some(format!(
+                    "Nothing to play on {}. Check that its server is active.",
+                    station.name
+                )));
+                return;
+            }
+            let songs = assign_collection_queue_meta(
+                songs,
+                QueueSourceKind::ArtistStation,
+                station.id.clone(),
+            );
+            queue.set(songs.clone());
+            queue_index.set(0);
+            now_playing.set(songs.first().cloned());
+            is_playing.set(true);
+        });
+    };
+
+    let on_rename_artist_station = move |station_id: String, name: String| {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        settings_controller.update_settings(|settings| {
+            if let Some(station) = settings
+                .artist_stations
+                .iter_mut()
+                .find(|station| station.id == station_id)
+            {
+                station.name = name;
+            }
+        });
+    };
+
+    let on_remove_artist_station = move |station_id: String| {
+        settings_controller.update_settings(|settings| {
+            settings
+                .artist_stations
+                .retain(|station| station.id != station_id);
+        });
+    };
+
+    let artist_stations = app_settings().artist_stations;
+
     let on_cancel_form = {
         let mut form_mode = form_mode.clone();
         let mut error_message = error_message.clone();
@@ -259,6 +363,7 @@ pub fn RadioView() -> Element {
             let name = form_name().trim().to_string();
             let stream_url = form_stream_url().trim().to_string();
             let home_page = form_home_page_url().trim().to_string();
+            let title_template = form_title_template().trim().to_string();
             let mode_snapshot = form_mode();
             let server_id = match &mode_snapshot {
                 RadioFormMode::Edit(station) => station.server_id.clone(),
@@ -278,6 +383,19 @@ pub fn RadioView() -> Element {
                 return;
             }
 
+            if let RadioFormMode::Edit(station) = &mode_snapshot {
+                let key = radio_station_template_key(&station.server_id, &station.id);
+                settings_controller.update_settings(|settings| {
+                    if title_template.is_empty() {
+                        settings.radio_title_templates.remove(&key);
+                    } else {
+                        settings
+                            .radio_title_templates
+                            .insert(key, title_template.clone());
+                    }
+                });
+            }
+
             is_saving.set(true);
             error_message.set(None);
 
@@ -326,7 +444,7 @@ pub fn RadioView() -> Element {
                         refresh_key.with_mut(|value| *value += 1);
                     }
                     Err(err) => {
-                        error_message.set(Some(err));
+                        error_message.set(Some(err.to_string()));
                     }
                 }
 
@@ -340,7 +458,9 @@ pub fn RadioView() -> Element {
             header { class: "page-header page-header--split",
                 div { class: "space-y-1",
                     h1 { class: "page-title", "Radio Stations" }
-                    p { class: "page-subtitle", "Internet radio from your servers" }
+                    p { class: "page-subtitle",
+                        "Internet radio from your servers and artist stations pinned on this device"
+                    }
                 }
                 button {
                     class: "inline-flex items-center gap-2 rounded-full bg-emerald-500/15 px-4 py-2 text-sm font-semibold text-emerald-200 hover:bg-emerald-500/25 transition-colors disabled:opacity-40 disabled:cursor-not-allowed",
@@ -360,10 +480,14 @@ pub fn RadioView() -> Element {
                 }
             }
 
-            if !has_active_servers {
+            if !has_connected_servers {
                 div { class: "rounded-xl border border-amber-500/30 bg-amber-500/10 px-4 py-3 text-sm text-amber-200",
                     "Connect an active server to manage radio stations."
                 }
+            } else if !has_active_servers {
+                div { class: "rounded-xl border border-amber-500/30 bg-amber-500/10 px-4 py-3 text-sm text-amber-200",
+                    "Your servers don't support internet radio."
+                }
             }
 
             if is_form_open {
@@ -442,6 +566,22 @@ pub fn RadioView() -> Element {
                                 }
                             }
                         }
+                        if is_editing {
+                            div { class: "space-y-2 md:col-span-2",
+                                label { class: "text-xs uppercase tracking-widest text-zinc-500",
+                                    "Now playing format (optional)"
+                                }
+                                input {
+                                    class: "w-full rounded-xl border border-zinc-800/80 bg-zinc-950/70 px-4 py-3 text-sm text-white placeholder:text-zinc-600 focus:outline-none focus:border-emerald-500/50 focus:ring-2 focus:ring-emerald-500/20",
+                                    placeholder: "{{title}} / {{artist}}",
+                                    value: form_title_template,
+                                    oninput: move |e| form_title_template.set(e.value()),
+                                }
+                                p { class: "text-xs text-zinc-500",
+                                    "Use {{artist}}, {{title}} and {{*}} to match this station's stream titles. Saved on this device only. The raw stream title is shown in the song details."
+                                }
+                            }
+                        }
                     }
 
                     div { class: "flex flex-wrap items-center gap-3",
@@ -464,6 +604,51 @@ pub fn RadioView() -> Element {
                 }
             }
 
+            if !artist_stations.is_empty() {
+                section { class: "space-y-3",
+                    div { class: "space-y-1",
+                        h2 { class: "text-lg font-semibold text-white", "Artist stations" }
+                        p { class: "text-xs text-zinc-400",
+                            "Endless mixes seeded from an artist. Pin more from any artist page."
+                        }
+                    }
+                    div { class: "grid grid-cols-1 sm:grid-cols-2 lg:grid-cols-3 gap-4",
+                        for station in artist_stations {
+                            ArtistStationCard {
+                                key: "{station.id}",
+                                artwork_url: station.cover_art.as_ref().and_then(|cover_art| {
+                                    server_list
+                                        .iter()
+                                        .find(|server| server.id == station.server_id)
+                                        .map(|server| {
+                                            sized_cover_art_url(
+                                                &NavidromeClient::new(server.clone()),
+                                                cover_art,
+                                                ArtworkKind::Album,
+                                                112,
+                                            )
+                                        })
+                                }),
+                                loading: loading_artist_station().as_deref() == Some(station.id.as_str()),
+                                station: station.clone(),
+                                on_play: {
+                                    let station = station.clone();
+                                    move |_| on_play_artist_station(station.clone())
+                                },
+                                on_rename: {
+                                    let station_id = station.id.clone();
+                                    move |name: String| on_rename_artist_station(station_id.clone(), name)
+                                },
+                                on_remove: {
+                                    let station_id = station.id.clone();
+                                    move |_| on_remove_artist_station(station_id.clone())
+                                },
+                            }
+                        }
+                    }
+                }
+            }
+
             {
                 match stations() {
                     Some(stations) if !stations.is_empty() => rsx! {
@@ -483,6 +668,7 @@ pub fn RadioView() -> Element {
                                                 artist_id: None,
                                                 duration: 0,
                                                 track: None,
+                                                disc_number: None,
                                                 cover_art: None,
                                                 content_type: Some("audio/mpeg".to_string()),
                                                 stream_url: Some(station.stream_url.clone()),
@@ -494,6 +680,12 @@ pub fn RadioView() -> Element {
                                                 played: None,
                                                 year: None,
                                                 genre: None,
+                                                artists: Vec::new(),
+                                                contributors: Vec::new(),
+                                                display_composer: None,
+                                                path: None,
+                                                missing: false,
+                                                replay_gain: None,
                                                 server_id: station.server_id.clone(),
                                                 server_name: "Radio".to_string(),
                                                 queue_meta: None,
@@ -517,6 +709,14 @@ pub fn RadioView() -> Element {
                                             form_stream_url.set(station.stream_url.clone());
                                             form_home_page_url.set(station.home_page_url.clone().unwrap_or_default());
                                             form_server_id.set(station.server_id.clone());
+                                            form_title_template
+                                                .set(
+                                                    app_settings()
+                                                        .radio_title_templates
+                                                        .get(&radio_station_template_key(&station.server_id, &station.id))
+                                                        .cloned()
+                                                        .unwrap_or_default(),
+                                                );
                                             error_message.set(None);
                                             form_mode.set(RadioFormMode::Edit(station.clone()));
                                         }
@@ -545,7 +745,7 @@ pub fn RadioView() -> Element {
                                                         refresh_key.with_mut(|value| *value += 1);
                                                     }
                                                     Err(err) => {
-                                                        error_message.set(Some(err));
+                                                        error_message.set(Some(err.to_string()));
                                                     }
                                                 }
                                             });
@@ -563,11 +763,13 @@ pub fn RadioView() -> Element {
                             }
                             h2 { class: "text-xl font-semibold text-white mb-2", "No radio stations" }
                             p { class: "text-zinc-400 mb-6", "Add radio stations in your Navidrome server" }
-                            button {
-                                class: "inline-flex items-center gap-2 rounded-full bg-emerald-500/15 px-6 py-3 text-sm font-semibold text-emerald-200 hover:bg-emerald-500/25 transition-colors",
-                                onclick: on_add_demo_station,
-                                Icon { name: "plus".to_string(), class: "w-4 h-4".to_string() }
-                                "Add Downtown Hot Radio Demo Station"
+                            if has_active_servers {
+                                button {
+                                    class: "inline-flex items-center gap-2 rounded-full bg-emerald-500/15 px-6 py-3 text-sm font-semibold text-emerald-200 hover:bg-emerald-500/25 transition-colors",
+                                    onclick: on_add_demo_station,
+                                    Icon { name: "plus".to_string(), class: "w-4 h-4".to_string() }
+                                    "Add Downtown Hot Radio Demo Station"
+                                }
                             }
                         }
                     },
@@ -614,6 +816,9 @@ fn RadioStationCard(
                     "{station.name}"
                 }
                 p { class: "text-xs text-zinc-400 truncate", "{station.stream_url}" }
+                span { class: "mt-1 inline-block rounded-full bg-amber-500/15 px-2 py-0.5 text-[10px] uppercase tracking-widest text-amber-300",
+                    "Internet radio"
+                }
             }
             // Actions
             div { class: "flex items-center gap-2",
@@ -651,3 +856,120 @@ fn RadioStationCard(
         }
     }
 }
+
+/// A locally pinned artist station. Round artist artwork and the "Artist station" badge set
+/// it apart from server-side internet radio.
+#[component]
+fn ArtistStationCard(
+    station: ArtistStation,
+    artwork_url: Option<String>,
+    loading: bool,
+    on_play: EventHandler<MouseEvent>,
+    on_rename: EventHandler<String>,
+    on_remove: EventHandler<MouseEvent>,
+) -> Element {
+    let mut renaming = use_signal(|| false);
+    let mut draft_name = use_signal(String::new);
+
+    let mut commit_rename = move || {
+        renaming.set(false);
+        on_rename.call(draft_name());
+    };
+
+    rsx! {
+        div {
+            class: "group flex items-center gap-4 p-4 rounded-xl bg-zinc-800/30 border border-zinc-700/30 hover:bg-zinc-800/50 hover:border-emerald-500/30 transition-all",
+            onclick: move |e| on_play.call(e),
+            div { class: "w-14 h-14 rounded-full overflow-hidden bg-gradient-to-br from-emerald-600 to-teal-700 flex items-center justify-center flex-shrink-0 shadow-lg",
+                match artwork_url {
+                    Some(url) => rsx! {
+                        img {
+                            src: "{url}",
+                            alt: "{station.artist_name}",
+                            class: "w-full h-full object-cover",
+                            loading: "lazy",
+                        }
+                    },
+                    None => rsx! {
+                        Icon { name: "artist".to_string(), class: "w-6 h-6 text-white/80".to_string() }
+                    },
+                }
+            }
+            div { class: "flex-1 min-w-0 text-left",
+                if renaming() {
+                    div {
+                        class: "flex items-center gap-2",
+                        onclick: move |e| e.stop_propagation(),
+                        input {
+                            class: "min-w-0 flex-1 rounded-lg border border-zinc-700 bg-zinc-950/70 px-2 py-1 text-sm text-white focus:outline-none focus:border-emerald-500/50",
+                            value: draft_name,
+                            autofocus: true,
+                            oninput: move |e| draft_name.set(e.value()),
+                        }
+                        button {
+                            class: "text-xs font-semibold text-emerald-300 hover:text-emerald-200",
+                            onclick: move |_| commit_rename(),
+                            "Save"
+                        }
+                        button {
+                            class: "text-xs text-zinc-400 hover:text-white",
+                            onclick: move |_| renaming.set(false),
+                            "Cancel"
+                        }
+                    }
+                } else {
+                    p { class: "font-medium text-white truncate group-hover:text-emerald-400 transition-colors",
+                        "{station.name}"
+                    }
+                }
+                p { class: "text-xs text-zinc-400 truncate", "Seeded from {station.artist_name}" }
+                span { class: "mt-1 inline-block rounded-full bg-emerald-500/15 px-2 py-0.5 text-[10px] uppercase tracking-widest text-emerald-300",
+                    "Artist station"
+                }
+            }
+            div { class: "flex items-center gap-2",
+                button {
+                    class: "p-2 rounded-lg text-zinc-400 hover:text-white hover:bg-zinc-800/70 transition-colors",
+                    aria_label: "Rename station",
+                    onclick: {
+                        let name = station.name.clone();
+                        move |e: MouseEvent| {
+                            e.stop_propagation();
+                            draft_name.set(name.clone());
+                            renaming.set(true);
+                        }
+                    },
+                    Icon {
+                        name: "settings".to_string(),
+                        class: "w-4 h-4".to_string(),
+                    }
+                }
+                button {
+                    class: "p-2 rounded-lg text-zinc-400 hover:text-rose-300 hover:bg-rose-500/10 transition-colors",
+                    aria_label: "Remove station",
+                    onclick: move |e| {
+                        e.stop_propagation();
+                        on_remove.call(e);
+                    },
+                    Icon {
+                        name: "trash".to_string(),
+                        class: "w-4 h-4".to_string(),
+                    }
+                }
+                div { class: "w-10 h-10 rounded-full bg-zinc-700/50 group-hover:bg-emerald-500 flex items-center justify-center transition-colors",
+                    if loading {
+                        Icon {
+                            name: "loader".to_string(),
+                            class: "w-4 h-4 text-zinc-300 animate-spin".to_string(),
+                        }
+                    } else {
+                        Icon {
+                            name: "play".to_string(),
+                            class: "w-4 h-4 text-zinc-400 group-hover:text-white ml-0.5".to_string(),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
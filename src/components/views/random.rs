@@ -1,3 +1,4 @@
+use super::empty_state::{EmptyState, NoServerEmptyState};
 use crate::api::*;
 use crate::cache_service::{get_json as cache_get_json, put_json as cache_put_json};
 use crate::components::audio_manager::{
@@ -381,22 +382,15 @@ pub fn RandomView() -> Element {
                     }
                 }
             } else if !has_active_servers {
-                div { class: "flex flex-col items-center justify-center py-20",
-                    Icon {
-                        name: "shuffle".to_string(),
-                        class: "w-16 h-16 text-zinc-600 mb-4".to_string(),
-                    }
-                    h2 { class: "text-xl font-semibold text-white mb-2", "No songs available" }
-                    p { class: "text-zinc-400", "Connect a server with music to get random picks" }
+                NoServerEmptyState {
+                    icon: "shuffle".to_string(),
+                    message: "Connect a server with music to get random picks".to_string(),
                 }
             } else {
-                div { class: "flex flex-col items-center justify-center py-20",
-                    Icon {
-                        name: "shuffle".to_string(),
-                        class: "w-16 h-16 text-zinc-600 mb-4".to_string(),
-                    }
-                    h2 { class: "text-xl font-semibold text-white mb-2", "No songs available" }
-                    p { class: "text-zinc-400", "Try refreshing to load a new random mix" }
+                EmptyState {
+                    icon: "shuffle".to_string(),
+                    title: "No songs available".to_string(),
+                    message: "Try refreshing to load a new random mix".to_string(),
                 }
             }
         }
@@ -1,9 +1,16 @@
 use crate::api::*;
+use crate::collation::{fold_text, search_text, search_tokens};
 use crate::components::audio_manager::normalize_manual_queue_songs;
 use crate::components::views::home::{AlbumCard, SongRow};
-use crate::components::{AppView, Icon, Navigation};
+use crate::components::{sized_cover_art_url, ArtworkKind};
+use crate::components::{AppView, Icon, Navigation, ServerCapabilitiesSignal};
 use dioxus::prelude::*;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+
+/// Songs shown per artist group before its "Show all" expander.
+const ARTIST_GROUP_PREVIEW: usize = 5;
+/// Most songs fetched when a group is expanded to the artist's full catalogue.
+const ARTIST_GROUP_FETCH_LIMIT: usize = 200;
 
 #[cfg(not(target_arch = "wasm32"))]
 async fn search_delay_ms(ms: u64) {
@@ -19,17 +26,17 @@ async fn search_delay_ms(ms: u64) {
 pub fn SearchView() -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
     let navigation = use_context::<Navigation>();
-    let mut now_playing = use_context::<Signal<Option<Song>>>();
-    let mut queue = use_context::<Signal<Vec<Song>>>();
-    let mut queue_index = use_context::<Signal<usize>>();
-    let mut is_playing = use_context::<crate::components::IsPlayingSignal>().0;
 
     let mut search_query = use_signal(String::new);
+    let mut composer_filter = use_signal(String::new);
     let debounced_query = use_signal(String::new);
     let search_results = use_signal(|| None::<SearchResult>);
     let is_searching = use_signal(|| false);
     let debounce_generation = use_signal(|| 0u64);
     let search_generation = use_signal(|| 0u64);
+    let song_groups_state = use_signal(SongGroupsState::default);
+    // Songs ticked for the selection bar, in the order they were ticked.
+    let selected_songs = use_signal(Vec::<Song>::new);
 
     // Debounce typing to avoid firing search requests on every keystroke.
     {
@@ -79,8 +86,11 @@ pub fn SearchView() -> Element {
         let mut search_results = search_results.clone();
         let mut is_searching = is_searching.clone();
         let mut search_generation = search_generation.clone();
+        let mut selected_songs = selected_songs;
         use_effect(move || {
             let query = debounced_query().trim().to_string();
+            // A selection belongs to one search; a new query starts a new one.
+            selected_songs.set(Vec::new());
             if query.is_empty() {
                 return;
             }
@@ -109,10 +119,10 @@ pub fn SearchView() -> Element {
                 //   token "down"
                 //   token "kanye"
                 // This lets multi-field queries like "song title + artist" work.
-                let tokens = tokenize(&query);
+                let tokens = search_tokens(&query);
                 let mut queries: Vec<String> = vec![query.clone()];
                 for token in &tokens {
-                    if token.len() >= 3 && *token != normalize_text(&query) {
+                    if token.len() >= 3 && *token != search_text(&query) {
                         queries.push(token.clone());
                     }
                 }
@@ -121,7 +131,7 @@ pub fn SearchView() -> Element {
                 for server in active_servers {
                     let client = NavidromeClient::new(server);
                     for q in &queries {
-                        if let Ok(result) = client.search(q, 24, 48, 96).await {
+                        if let Ok(result) = client.search(q, 24, 48, 96, None).await {
                             combined.artists.extend(result.artists);
                             combined.albums.extend(result.albums);
                             combined.songs.extend(result.songs);
@@ -144,6 +154,7 @@ pub fn SearchView() -> Element {
 
     let results = search_results();
     let searching = is_searching();
+    let composer_needle = fold_text(composer_filter().trim());
 
     rsx! {
         div { class: "space-y-8",
@@ -167,6 +178,13 @@ pub fn SearchView() -> Element {
                         },
                     }
                 }
+                input {
+                    class: "w-full max-w-xs px-3 py-2 bg-zinc-800/50 border border-zinc-700/50 rounded-lg text-sm text-white placeholder:text-zinc-500 focus:outline-none focus:border-emerald-500/50",
+                    placeholder: "Filter songs by composer",
+                    aria_label: "Filter songs by composer",
+                    value: composer_filter,
+                    oninput: move |e| composer_filter.set(e.value()),
+                }
             }
 
             if searching {
@@ -181,10 +199,16 @@ pub fn SearchView() -> Element {
                 {
                     let artists: Vec<Artist> = results.artists.iter().take(6).cloned().collect();
                     let albums: Vec<Album> = results.albums.iter().take(6).cloned().collect();
-                    let songs: Vec<Song> = results.songs.iter().take(20).cloned().collect();
+                    let songs: Vec<Song> = results
+                        .songs
+                        .iter()
+                        .filter(|song| song_matches_composer(song, &composer_needle))
+                        .cloned()
+                        .collect();
+                    let song_groups = group_songs_by_artist(&songs);
                     let has_artists = !artists.is_empty();
                     let has_albums = !albums.is_empty();
-                    let has_songs = !songs.is_empty();
+                    let has_songs = !song_groups.is_empty();
                     let no_results = !has_artists && !has_albums && !has_songs;
 
                     rsx! {
@@ -249,26 +273,14 @@ pub fn SearchView() -> Element {
                         if has_songs {
                             section {
                                 h2 { class: "text-xl font-semibold text-white mb-4", "Songs" }
-                                div { class: "space-y-1",
-                                    for (index , song) in songs.iter().enumerate() {
-                                        SongRow {
-                                            key: "{song.id}-{song.server_id}",
-                                            song: song.clone(),
-                                            index: index + 1,
-                                            show_download: true,
-                                            onclick: {
-                                                let song = song.clone();
-                                                move |_| {
-                                                    let single_queue =
-                                                        normalize_manual_queue_songs(vec![
-                                                            song.clone(),
-                                                        ]);
-                                                    queue.set(single_queue.clone());
-                                                    queue_index.set(0);
-                                                    now_playing.set(single_queue.first().cloned());
-                                                    is_playing.set(true);
-                                                }
-                                            },
+                                div { class: "space-y-4",
+                                    for group in song_groups {
+                                        SearchSongGroup {
+                                            key: "{group.key}",
+                                            group,
+                                            composer_needle: composer_needle.clone(),
+                                            state: song_groups_state,
+                                            selection: selected_songs,
                                         }
                                     }
                                 }
@@ -296,12 +308,461 @@ pub fn SearchView() -> Element {
                     p { class: "text-zinc-400", "Search your entire music library" }
                 }
             }
+
+            SearchSelectionBar { selection: selected_songs }
+        }
+    }
+}
+
+/// Search result songs by one artist on one server, in search score order.
+#[derive(Clone, Debug, PartialEq)]
+struct SongArtistGroup {
+    key: String,
+    artist: String,
+    artist_id: Option<String>,
+    server_id: String,
+    songs: Vec<Song>,
+}
+
+/// Groups songs by server and artist. Groups come in the order their artist first appears,
+/// so the best-scoring song still decides which artist is listed first.
+fn group_songs_by_artist(songs: &[Song]) -> Vec<SongArtistGroup> {
+    let mut groups: Vec<SongArtistGroup> = Vec::new();
+    for song in songs {
+        let artist = song
+            .artist
+            .as_deref()
+            .map(str::trim)
+            .filter(|artist| !artist.is_empty())
+            .unwrap_or("Unknown artist");
+        let artist_id = song
+            .artist_id
+            .clone()
+            .filter(|artist_id| !artist_id.trim().is_empty());
+        let key = match artist_id.as_deref() {
+            Some(artist_id) => format!("{}::id:{artist_id}", song.server_id),
+            None => format!("{}::name:{}", song.server_id, search_text(artist)),
+        };
+        match groups.iter_mut().find(|group| group.key == key) {
+            Some(group) => group.songs.push(song.clone()),
+            None => groups.push(SongArtistGroup {
+                key,
+                artist: artist.to_string(),
+                artist_id,
+                server_id: song.server_id.clone(),
+                songs: vec![song.clone()],
+            }),
         }
     }
+    groups
 }
 
+/// Which artist groups are collapsed or expanded, and the songs fetched for expanded ones.
+/// Kept for as long as the search view is open.
+#[derive(Clone, Default)]
+struct SongGroupsState {
+    collapsed: HashSet<String>,
+    expanded: HashSet<String>,
+    loading: HashSet<String>,
+    fetched: HashMap<String, Vec<Song>>,
+}
+
+/// An artist header with that artist's matching songs: the first few, or all of them once
+/// expanded. Expanding fetches the artist's other songs from the group's server.
 #[component]
-pub fn ArtistCard(artist: Artist, onclick: EventHandler<MouseEvent>) -> Element {
+fn SearchSongGroup(
+    group: SongArtistGroup,
+    composer_needle: String,
+    state: Signal<SongGroupsState>,
+    selection: Signal<Vec<Song>>,
+) -> Element {
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let navigation = use_context::<Navigation>();
+    let mut now_playing = use_context::<Signal<Option<Song>>>();
+    let mut queue = use_context::<Signal<Vec<Song>>>();
+    let mut queue_index = use_context::<Signal<usize>>();
+    let mut is_playing = use_context::<crate::components::IsPlayingSignal>().0;
+    let mut state = state;
+    let mut selection = selection;
+    let selected_keys: HashSet<String> = selection.read().iter().map(selection_key).collect();
+
+    let snapshot = state.read();
+    let collapsed = snapshot.collapsed.contains(&group.key);
+    let expanded = snapshot.expanded.contains(&group.key);
+    let loading = snapshot.loading.contains(&group.key);
+    let songs: Vec<Song> = if expanded {
+        snapshot
+            .fetched
+            .get(&group.key)
+            .unwrap_or(&group.songs)
+            .iter()
+            .filter(|song| song_matches_composer(song, &composer_needle))
+            .cloned()
+            .collect()
+    } else {
+        group
+            .songs
+            .iter()
+            .take(ARTIST_GROUP_PREVIEW)
+            .cloned()
+            .collect()
+    };
+    drop(snapshot);
+    let song_count = group.songs.len();
+    let chevron_class = if collapsed {
+        "w-4 h-4 -rotate-90 transition-transform"
+    } else {
+        "w-4 h-4 transition-transform"
+    };
+
+    let toggle_collapsed = {
+        let key = group.key.clone();
+        move |_| {
+            state.with_mut(|state| {
+                if !state.collapsed.remove(&key) {
+                    state.collapsed.insert(key.clone());
+                }
+            });
+        }
+    };
+
+    let show_fewer = {
+        let key = group.key.clone();
+        move |_| {
+            state.with_mut(|state| state.expanded.remove(&key));
+        }
+    };
+
+    let show_all = {
+        let group = group.clone();
+        move |_| {
+            let key = group.key.clone();
+            let needs_fetch = state.with_mut(|state| {
+                state.expanded.insert(key.clone());
+                !state.fetched.contains_key(&key) && state.loading.insert(key.clone())
+            });
+            if !needs_fetch {
+                return;
+            }
+            let server = servers
+                .peek()
+                .iter()
+                .find(|server| server.id == group.server_id)
+                .cloned();
+            let group = group.clone();
+            spawn(async move {
+                let mut songs = group.songs.clone();
+                if let Some(server) = server {
+                    if let Ok(fetched) = NavidromeClient::new(server)
+                        .search_artist_songs(
+                            &group.artist,
+                            group.artist_id.as_deref(),
+                            ARTIST_GROUP_FETCH_LIMIT,
+                        )
+                        .await
+                    {
+                        let mut seen: HashSet<String> =
+                            songs.iter().map(|song| song.id.clone()).collect();
+                        songs.extend(
+                            fetched
+                                .into_iter()
+                                .filter(|song| seen.insert(song.id.clone())),
+                        );
+                    }
+                }
+                state.with_mut(|state| {
+                    state.loading.remove(&group.key);
+                    state.fetched.insert(group.key.clone(), songs);
+                });
+            });
+        }
+    };
+
+    rsx! {
+        div { class: "space-y-1",
+            div { class: "flex items-center gap-2 px-1",
+                button {
+                    class: "p-1 rounded-md text-zinc-500 hover:text-white transition-colors",
+                    aria_label: if collapsed { "Expand artist songs" } else { "Collapse artist songs" },
+                    onclick: toggle_collapsed,
+                    Icon { name: "chevron-down".to_string(), class: chevron_class.to_string() }
+                }
+                if let Some(artist_id) = group.artist_id.clone() {
+                    button {
+                        class: "text-sm font-semibold text-zinc-200 hover:text-emerald-400 transition-colors truncate",
+                        onclick: {
+                            let server_id = group.server_id.clone();
+                            move |_| {
+                                navigation
+                                    .navigate_to(AppView::ArtistDetailView {
+                                        artist_id: artist_id.clone(),
+                                        server_id: server_id.clone(),
+                                    })
+                            }
+                        },
+                        "{group.artist}"
+                    }
+                } else {
+                    span { class: "text-sm font-semibold text-zinc-200 truncate", "{group.artist}" }
+                }
+                span { class: "text-xs text-zinc-500 flex-shrink-0",
+                    if song_count == 1 {
+                        "1 song"
+                    } else {
+                        "{song_count} songs"
+                    }
+                }
+            }
+            if !collapsed {
+                for (index , song) in songs.iter().enumerate() {
+                    div {
+                        key: "{song.id}-{song.server_id}",
+                        class: "flex items-center gap-2",
+                        input {
+                            r#type: "checkbox",
+                            class: "w-4 h-4 ml-1 rounded cursor-pointer flex-shrink-0",
+                            aria_label: "Select {song.title}",
+                            checked: selected_keys.contains(&selection_key(song)),
+                            onchange: {
+                                let song = song.clone();
+                                move |_| {
+                                    let key = selection_key(&song);
+                                    selection.with_mut(|selected| {
+                                        let before = selected.len();
+                                        selected.retain(|entry| selection_key(entry) != key);
+                                        if selected.len() == before {
+                                            selected.push(song.clone());
+                                        }
+                                    });
+                                }
+                            },
+                        }
+                        div { class: "flex-1 min-w-0",
+                            SongRow {
+                                song: song.clone(),
+                                index: index + 1,
+                                show_download: true,
+                                onclick: {
+                                    let song = song.clone();
+                                    move |_| {
+                                        let single_queue =
+                                            normalize_manual_queue_songs(vec![song.clone()]);
+                                        queue.set(single_queue.clone());
+                                        queue_index.set(0);
+                                        now_playing.set(single_queue.first().cloned());
+                                        is_playing.set(true);
+                                    }
+                                },
+                            }
+                        }
+                    }
+                }
+                if expanded {
+                    button {
+                        class: "ml-9 text-xs text-zinc-400 hover:text-white transition-colors",
+                        onclick: show_fewer,
+                        if loading {
+                            "Loading more songs…"
+                        } else {
+                            "Show fewer"
+                        }
+                    }
+                } else if song_count > ARTIST_GROUP_PREVIEW {
+                    button {
+                        class: "ml-9 text-xs text-emerald-400 hover:text-emerald-300 transition-colors",
+                        onclick: show_all,
+                        "Show all {song_count}"
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn selection_key(song: &Song) -> String {
+    format!("{}:{}", song.server_id, song.id)
+}
+
+/// Total play time of a selection: "m:ss", or hours and minutes past an hour.
+fn selection_duration_label(songs: &[Song]) -> String {
+    let total: u32 = songs.iter().map(|song| song.duration).sum();
+    if total < 3600 {
+        format_duration(total)
+    } else {
+        format!("{} h {} min", total / 3600, total % 3600 / 60)
+    }
+}
+
+/// Floating bar for the songs ticked in the results: play them, queue them, or save them as
+/// a new playlist in the order they were ticked. Hidden while nothing is selected.
+#[component]
+fn SearchSelectionBar(selection: Signal<Vec<Song>>) -> Element {
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let mut now_playing = use_context::<Signal<Option<Song>>>();
+    let mut queue = use_context::<Signal<Vec<Song>>>();
+    let mut queue_index = use_context::<Signal<usize>>();
+    let mut is_playing = use_context::<crate::components::IsPlayingSignal>().0;
+    let mut selection = selection;
+    let mut naming = use_signal(|| false);
+    let mut playlist_name = use_signal(String::new);
+    let mut creating = use_signal(|| false);
+    let mut message = use_signal(|| None::<(bool, String)>);
+    let guest_session = use_context::<ServerCapabilitiesSignal>().is_guest_session();
+
+    let selected = selection();
+    if selected.is_empty() {
+        return rsx! {};
+    }
+    let count = selected.len();
+    let duration_label = selection_duration_label(&selected);
+
+    let on_play_all = move |_| {
+        let songs = normalize_manual_queue_songs(selection.peek().clone());
+        queue.set(songs.clone());
+        queue_index.set(0);
+        now_playing.set(songs.first().cloned());
+        is_playing.set(true);
+    };
+
+    let on_queue_all = move |_| {
+        let songs = normalize_manual_queue_songs(selection.peek().clone());
+        let added = songs.len();
+        queue.with_mut(|items| items.extend(songs));
+        message.set(Some((true, format!("Added {added} song(s) to queue."))));
+    };
+
+    let on_create_playlist = move |_| {
+        if creating() {
+            return;
+        }
+        let name = playlist_name().trim().to_string();
+        if name.is_empty() {
+            message.set(Some((false, "Please enter a playlist name.".to_string())));
+            return;
+        }
+        let songs = selection.peek().clone();
+        let server_ids: HashSet<&str> = songs.iter().map(|song| song.server_id.as_str()).collect();
+        if server_ids.len() > 1 {
+            message.set(Some((
+                false,
+                "The selection spans several servers; a playlist can only hold songs from one."
+                    .to_string(),
+            )));
+            return;
+        }
+        let Some(server) = servers()
+            .into_iter()
+            .find(|server| server_ids.contains(server.id.as_str()))
+        else {
+            message.set(Some((
+                false,
+                "The selected songs' server was not found.".to_string(),
+            )));
+            return;
+        };
+        let song_ids: Vec<String> = songs.iter().map(|song| song.id.clone()).collect();
+        creating.set(true);
+        message.set(None);
+        spawn(async move {
+            match NavidromeClient::new(server)
+                .create_playlist(&name, None, &song_ids)
+                .await
+            {
+                Ok(_) => {
+                    message.set(Some((
+                        true,
+                        format!("Created \"{name}\" with {} songs.", song_ids.len()),
+                    )));
+                    naming.set(false);
+                    playlist_name.set(String::new());
+                }
+                Err(error) => message.set(Some((false, error.to_string()))),
+            }
+            creating.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "fixed bottom-24 left-1/2 -translate-x-1/2 z-[180] w-[min(40rem,calc(100vw-2rem))]",
+            div { class: "rounded-2xl border border-zinc-700 bg-zinc-900/95 shadow-2xl p-3 space-y-2",
+                div { class: "flex flex-wrap items-center gap-2",
+                    div { class: "min-w-0 flex-1",
+                        p { class: "text-sm font-medium text-white", "{count} selected" }
+                        p { class: "text-xs text-zinc-500 tabular-nums", "{duration_label}" }
+                    }
+                    button {
+                        class: "px-3 py-1.5 rounded-lg bg-emerald-500/15 text-emerald-300 hover:bg-emerald-500/25 transition-colors text-xs",
+                        onclick: on_play_all,
+                        "Play all"
+                    }
+                    button {
+                        class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-emerald-500/60 transition-colors text-xs",
+                        onclick: on_queue_all,
+                        "Queue all"
+                    }
+                    if !guest_session {
+                        button {
+                            class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-emerald-500/60 transition-colors text-xs",
+                            onclick: move |_| naming.set(!naming()),
+                            "New playlist from selection"
+                        }
+                    }
+                    button {
+                        class: "p-1.5 rounded-lg text-zinc-500 hover:text-white transition-colors",
+                        aria_label: "Clear selection",
+                        onclick: move |_| {
+                            selection.set(Vec::new());
+                            naming.set(false);
+                            message.set(None);
+                        },
+                        Icon { name: "x".to_string(), class: "w-4 h-4".to_string() }
+                    }
+                }
+                if naming() {
+                    div { class: "flex items-center gap-2",
+                        input {
+                            class: "flex-1 min-w-0 px-3 py-1.5 bg-zinc-800/60 border border-zinc-700 rounded-lg text-sm text-white placeholder:text-zinc-500 focus:outline-none focus:border-emerald-500/50",
+                            placeholder: "Playlist name",
+                            value: playlist_name,
+                            oninput: move |evt| playlist_name.set(evt.value()),
+                        }
+                        button {
+                            class: "px-3 py-1.5 rounded-lg bg-emerald-500 text-black font-medium hover:bg-emerald-400 transition-colors text-xs disabled:opacity-50",
+                            disabled: creating(),
+                            onclick: on_create_playlist,
+                            if creating() {
+                                "Creating..."
+                            } else {
+                                "Create"
+                            }
+                        }
+                    }
+                }
+                if let Some((ok, text)) = message() {
+                    p { class: if ok { "text-xs text-emerald-400" } else { "text-xs text-red-400" }, "{text}" }
+                }
+            }
+        }
+    }
+}
+
+/// Composer filter for song results; an empty needle keeps every song. Songs from
+/// servers that don't report composers never match a non-empty filter.
+fn song_matches_composer(song: &Song, needle: &str) -> bool {
+    needle.is_empty()
+        || song
+            .composer()
+            .is_some_and(|composer| fold_text(&composer).contains(needle))
+}
+
+#[component]
+pub fn ArtistCard(
+    artist: Artist,
+    onclick: EventHandler<MouseEvent>,
+    /// Widest the image is drawn, in CSS pixels.
+    #[props(default = 180)]
+    art_px: u32,
+) -> Element {
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
 
     let cover_url = servers()
@@ -312,7 +773,7 @@ pub fn ArtistCard(artist: Artist, onclick: EventHandler<MouseEvent>) -> Element
             artist
                 .cover_art
                 .as_ref()
-                .map(|ca| client.get_cover_art_url(ca, 300))
+                .map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Album, art_px))
         });
 
     let initials: String = artist
@@ -362,25 +823,6 @@ pub fn ArtistCard(artist: Artist, onclick: EventHandler<MouseEvent>) -> Element
     }
 }
 
-/// Strip punctuation (apostrophes, hyphens, etc.) from text for comparison.
-/// e.g. "Don't" -> "dont", "re-enter" -> "reenter"
-fn normalize_text(text: &str) -> String {
-    text.to_lowercase()
-        .chars()
-        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
-        .collect()
-}
-
-/// Tokenize a search query into meaningful words.
-/// Strips punctuation before splitting so "Don't" becomes "dont" (not "don" + "t").
-fn tokenize(text: &str) -> Vec<String> {
-    normalize_text(text)
-        .split_whitespace()
-        .filter(|s| !s.is_empty())
-        .map(String::from)
-        .collect()
-}
-
 fn dedupe_search_results(mut results: SearchResult) -> SearchResult {
     let mut artist_seen = HashSet::new();
     results
@@ -403,7 +845,7 @@ fn dedupe_search_results(mut results: SearchResult) -> SearchResult {
 /// Calculate fuzzy match score for a field against query tokens.
 /// Normalizes the field (strips punctuation) so "Don't" matches "dont".
 fn calculate_score(field: &str, tokens: &[String]) -> i32 {
-    let field_normalized = normalize_text(field);
+    let field_normalized = search_text(field);
     let field_words: Vec<&str> = field_normalized.split_whitespace().collect();
     let mut score = 0;
 
@@ -444,17 +886,9 @@ fn token_matches_field(field_normalized: &str, token: &str) -> bool {
 /// Score songs based on fuzzy matching across title, artist, and album.
 /// Requires ALL tokens to appear somewhere across the combined fields.
 fn score_song(song: &Song, tokens: &[String]) -> i32 {
-    let title_norm = normalize_text(&song.title);
-    let artist_norm = song
-        .artist
-        .as_deref()
-        .map(normalize_text)
-        .unwrap_or_default();
-    let album_norm = song
-        .album
-        .as_deref()
-        .map(normalize_text)
-        .unwrap_or_default();
+    let title_norm = search_text(&song.title);
+    let artist_norm = song.artist.as_deref().map(search_text).unwrap_or_default();
+    let album_norm = song.album.as_deref().map(search_text).unwrap_or_default();
 
     // Every token must appear in at least one field
     for token in tokens {
@@ -483,8 +917,8 @@ fn score_song(song: &Song, tokens: &[String]) -> i32 {
 /// Score albums based on fuzzy matching across name and artist.
 /// Requires ALL tokens to appear somewhere across name or artist.
 fn score_album(album: &Album, tokens: &[String]) -> i32 {
-    let name_norm = normalize_text(&album.name);
-    let artist_norm = normalize_text(&album.artist);
+    let name_norm = search_text(&album.name);
+    let artist_norm = search_text(&album.artist);
 
     for token in tokens {
         if !token_matches_field(&name_norm, token) && !token_matches_field(&artist_norm, token) {
@@ -501,7 +935,7 @@ fn score_album(album: &Album, tokens: &[String]) -> i32 {
 /// Score artists based on fuzzy matching on name.
 /// For single-field entities, at least half the tokens must match.
 fn score_artist(artist: &Artist, tokens: &[String]) -> i32 {
-    let name_norm = normalize_text(&artist.name);
+    let name_norm = search_text(&artist.name);
     let matched = tokens
         .iter()
         .filter(|t| token_matches_field(&name_norm, t))
@@ -514,7 +948,7 @@ fn score_artist(artist: &Artist, tokens: &[String]) -> i32 {
 
 /// Filter and score all search results with fuzzy matching
 fn filter_and_score_results(mut results: SearchResult, query: &str) -> SearchResult {
-    let tokens = tokenize(query);
+    let tokens = search_tokens(query);
 
     if tokens.is_empty() {
         return results;
@@ -567,3 +1001,43 @@ fn filter_and_score_results(mut results: SearchResult, query: &str) -> SearchRes
 
     results
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(id: &str, artist: &str, artist_id: Option<&str>) -> Song {
+        Song {
+            id: id.to_string(),
+            server_id: "srv".to_string(),
+            artist: Some(artist.to_string()),
+            artist_id: artist_id.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn groups_songs_by_artist_in_first_appearance_order() {
+        let songs = vec![
+            song("1", "Kanye West", Some("kw")),
+            song("2", "Daft Punk", None),
+            song("3", "Kanye West", Some("kw")),
+            song("4", "daft punk", None),
+        ];
+        let groups = group_songs_by_artist(&songs);
+        let summary: Vec<(&str, Vec<&str>)> = groups
+            .iter()
+            .map(|group| {
+                let ids = group.songs.iter().map(|song| song.id.as_str()).collect();
+                (group.artist.as_str(), ids)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Kanye West", vec!["1", "3"]),
+                ("Daft Punk", vec!["2", "4"]),
+            ]
+        );
+    }
+}
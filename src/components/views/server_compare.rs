@@ -0,0 +1,602 @@
+use crate::api::*;
+use crate::cache_service::{
+    get_json as cache_get_json, put_json as cache_put_json, remove_by_prefix as cache_remove_prefix,
+};
+use crate::collation::fold_text;
+use crate::components::Icon;
+use crate::storage::save_text_export;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const COMPARE_REQUEST_THROTTLE_MS: u64 = 120;
+/// Artists walked between progress checkpoints; a cancelled or interrupted walk resumes here.
+const COMPARE_CHECKPOINT_EVERY: usize = 10;
+const COMPARE_CACHE_HOURS: u32 = 24 * 7;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct LibraryAlbumEntry {
+    artist: String,
+    album: String,
+    track_count: u32,
+}
+
+/// Progress of one server's library walk, persisted so it can be resumed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct LibraryWalk {
+    /// `(id, name)` of every artist on the server, fetched once up front.
+    artists: Vec<(String, String)>,
+    next_artist: usize,
+    albums: Vec<LibraryAlbumEntry>,
+    complete: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct TrackCountMismatch {
+    artist: String,
+    album: String,
+    tracks_a: u32,
+    tracks_b: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+struct LibraryDiff {
+    only_in_a: Vec<LibraryAlbumEntry>,
+    only_in_b: Vec<LibraryAlbumEntry>,
+    track_mismatches: Vec<TrackCountMismatch>,
+    matched: usize,
+}
+
+/// Folds case and accents the way lists collate, drops punctuation, collapses whitespace,
+/// and ignores a leading "the".
+fn normalize_compare_text(value: &str) -> String {
+    let cleaned: String = fold_text(value)
+        .chars()
+        .map(|ch| if ch.is_alphanumeric() { ch } else { ' ' })
+        .collect();
+    let words: Vec<&str> = cleaned.split_whitespace().collect();
+    match words.split_first() {
+        Some((&"the", rest)) if !rest.is_empty() => rest.join(" "),
+        _ => words.join(" "),
+    }
+}
+
+/// Indexes albums by normalized artist + title. Albums split into several entries on one
+/// server (e.g. per-disc folders) are merged and their track counts summed.
+fn index_albums(entries: &[LibraryAlbumEntry]) -> BTreeMap<(String, String), LibraryAlbumEntry> {
+    let mut index = BTreeMap::<(String, String), LibraryAlbumEntry>::new();
+    for entry in entries {
+        let key = (
+            normalize_compare_text(&entry.artist),
+            normalize_compare_text(&entry.album),
+        );
+        index
+            .entry(key)
+            .and_modify(|existing| existing.track_count += entry.track_count)
+            .or_insert_with(|| entry.clone());
+    }
+    index
+}
+
+fn diff_libraries(a: &[LibraryAlbumEntry], b: &[LibraryAlbumEntry]) -> LibraryDiff {
+    let index_a = index_albums(a);
+    let mut index_b = index_albums(b);
+    let mut diff = LibraryDiff::default();
+
+    for (key, album_a) in index_a {
+        match index_b.remove(&key) {
+            Some(album_b) => {
+                diff.matched += 1;
+                if album_a.track_count != album_b.track_count {
+                    diff.track_mismatches.push(TrackCountMismatch {
+                        artist: album_a.artist,
+                        album: album_a.album,
+                        tracks_a: album_a.track_count,
+                        tracks_b: album_b.track_count,
+                    });
+                }
+            }
+            None => diff.only_in_a.push(album_a),
+        }
+    }
+    diff.only_in_b = index_b.into_values().collect();
+    diff
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn diff_report_csv(diff: &LibraryDiff, name_a: &str, name_b: &str) -> String {
+    let mut lines = vec![format!(
+        "status,artist,album,{},{}",
+        csv_field(&format!("tracks on {name_a}")),
+        csv_field(&format!("tracks on {name_b}"))
+    )];
+    let only_a_status = csv_field(&format!("only on {name_a}"));
+    let only_b_status = csv_field(&format!("only on {name_b}"));
+    for album in &diff.only_in_a {
+        lines.push(format!(
+            "{only_a_status},{},{},{},",
+            csv_field(&album.artist),
+            csv_field(&album.album),
+            album.track_count
+        ));
+    }
+    for album in &diff.only_in_b {
+        lines.push(format!(
+            "{only_b_status},{},{},,{}",
+            csv_field(&album.artist),
+            csv_field(&album.album),
+            album.track_count
+        ));
+    }
+    for mismatch in &diff.track_mismatches {
+        lines.push(format!(
+            "track count mismatch,{},{},{},{}",
+            csv_field(&mismatch.artist),
+            csv_field(&mismatch.album),
+            mismatch.tracks_a,
+            mismatch.tracks_b
+        ));
+    }
+    let mut csv = lines.join("\n");
+    csv.push('\n');
+    csv
+}
+
+fn walk_cache_key(server_id: &str) -> String {
+    format!("server_compare:walk:v1:{server_id}")
+}
+
+fn report_cache_key(server_a: &str, server_b: &str) -> String {
+    format!("server_compare:report:v1:{server_a}:{server_b}")
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn compare_throttle() {
+    tokio::time::sleep(std::time::Duration::from_millis(
+        COMPARE_REQUEST_THROTTLE_MS,
+    ))
+    .await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn compare_throttle() {
+    gloo_timers::future::TimeoutFuture::new(COMPARE_REQUEST_THROTTLE_MS as u32).await;
+}
+
+/// Walks the next artist: getArtist for its albums, then getAlbum for any album whose
+/// listing did not include a track count.
+async fn walk_next_artist(client: &NavidromeClient, walk: &mut LibraryWalk) -> Result<(), String> {
+    let Some((artist_id, artist_name)) = walk.artists.get(walk.next_artist).cloned() else {
+        walk.complete = true;
+        return Ok(());
+    };
+    let (_, albums) = client.get_artist(&artist_id).await?;
+    for album in albums {
+        let mut track_count = album.song_count;
+        if track_count == 0 {
+            compare_throttle().await;
+            track_count = client
+                .get_album(&album.id)
+                .await
+                .map(|(_, songs)| songs.len() as u32)
+                .unwrap_or(0);
+        }
+        let artist = if album.artist.trim().is_empty() {
+            artist_name.clone()
+        } else {
+            album.artist
+        };
+        walk.albums.push(LibraryAlbumEntry {
+            artist,
+            album: album.name,
+            track_count,
+        });
+    }
+    walk.next_artist += 1;
+    walk.complete = walk.next_artist >= walk.artists.len();
+    Ok(())
+}
+
+#[component]
+pub(super) fn ServerCompareDialog(on_close: EventHandler<()>) -> Element {
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let server_list = servers();
+
+    let mut server_a = use_signal(|| {
+        server_list
+            .first()
+            .map(|server| server.id.clone())
+            .unwrap_or_default()
+    });
+    let mut server_b = use_signal(|| {
+        server_list
+            .get(1)
+            .map(|server| server.id.clone())
+            .unwrap_or_default()
+    });
+    let mut running = use_signal(|| false);
+    let mut cancel_requested = use_signal(|| false);
+    let mut progress = use_signal(|| None::<String>);
+    let mut report = use_signal(|| None::<LibraryDiff>);
+    let mut status = use_signal(|| None::<String>);
+
+    // Re-opening a comparison shows the cached report immediately.
+    use_effect(move || {
+        let key = report_cache_key(&server_a(), &server_b());
+        report.set(cache_get_json::<LibraryDiff>(&key));
+    });
+
+    let name_for = move |id: &str| {
+        servers()
+            .into_iter()
+            .find(|server| server.id == id)
+            .map(|server| server.name)
+            .unwrap_or_else(|| id.to_string())
+    };
+
+    let mut start_compare = move |rescan: bool| {
+        if running() {
+            return;
+        }
+        let id_a = server_a();
+        let id_b = server_b();
+        let server_list = servers();
+        let (Some(config_a), Some(config_b)) = (
+            server_list.iter().find(|s| s.id == id_a).cloned(),
+            server_list.iter().find(|s| s.id == id_b).cloned(),
+        ) else {
+            status.set(Some("Pick two servers to compare.".to_string()));
+            return;
+        };
+        if rescan {
+            cache_remove_prefix(&walk_cache_key(&id_a));
+            cache_remove_prefix(&walk_cache_key(&id_b));
+            cache_remove_prefix(&report_cache_key(&id_a, &id_b));
+            report.set(None);
+        }
+        running.set(true);
+        cancel_requested.set(false);
+        status.set(None);
+
+        spawn(async move {
+            let mut walks = Vec::with_capacity(2);
+            for server in [config_a, config_b] {
+                let key = walk_cache_key(&server.id);
+                let client = NavidromeClient::new(server.clone());
+                let mut walk = cache_get_json::<LibraryWalk>(&key).unwrap_or_default();
+                if walk.artists.is_empty() && !walk.complete {
+                    progress.set(Some(format!("{}: loading artists...", server.name)));
+                    match client.get_artists(None).await {
+                        Ok(artists) => {
+                            walk.artists = artists
+                                .into_iter()
+                                .map(|artist| (artist.id, artist.name))
+                                .collect();
+                            walk.complete = walk.artists.is_empty();
+                            cache_put_json(&key, &walk, Some(COMPARE_CACHE_HOURS));
+                        }
+                        Err(err) => {
+                            status.set(Some(format!("{}: {err}", server.name)));
+                            progress.set(None);
+                            running.set(false);
+                            return;
+                        }
+                    }
+                }
+
+                while !walk.complete {
+                    if *cancel_requested.peek() {
+                        cache_put_json(&key, &walk, Some(COMPARE_CACHE_HOURS));
+                        status.set(Some(
+                            "Comparison paused. Start it again to resume where it stopped."
+                                .to_string(),
+                        ));
+                        progress.set(None);
+                        running.set(false);
+                        return;
+                    }
+                    progress.set(Some(format!(
+                        "{}: artist {} of {}",
+                        server.name,
+                        walk.next_artist + 1,
+                        walk.artists.len()
+                    )));
+                    if let Err(err) = walk_next_artist(&client, &mut walk).await {
+                        cache_put_json(&key, &walk, Some(COMPARE_CACHE_HOURS));
+                        status.set(Some(format!(
+                            "{}: {err}. Start again to resume.",
+                            server.name
+                        )));
+                        progress.set(None);
+                        running.set(false);
+                        return;
+                    }
+                    if walk.complete || walk.next_artist % COMPARE_CHECKPOINT_EVERY == 0 {
+                        cache_put_json(&key, &walk, Some(COMPARE_CACHE_HOURS));
+                    }
+                    compare_throttle().await;
+                }
+                walks.push(walk);
+            }
+
+            let diff = diff_libraries(&walks[0].albums, &walks[1].albums);
+            cache_put_json(
+                report_cache_key(&id_a, &id_b),
+                &diff,
+                Some(COMPARE_CACHE_HOURS),
+            );
+            report.set(Some(diff));
+            progress.set(None);
+            running.set(false);
+        });
+    };
+
+    let name_a = name_for(&server_a());
+    let name_b = name_for(&server_b());
+    let same_server = server_a() == server_b();
+
+    let on_export = {
+        let name_a = name_a.clone();
+        let name_b = name_b.clone();
+        move |_| {
+            let Some(diff) = report() else {
+                return;
+            };
+            let csv = diff_report_csv(&diff, &name_a, &name_b);
+            let file_name = format!(
+                "rustysound-compare-{}-vs-{}.csv",
+                normalize_compare_text(&name_a).replace(' ', "-"),
+                normalize_compare_text(&name_b).replace(' ', "-")
+            );
+            status.set(Some(
+                save_text_export(&file_name, &csv, "text/csv;charset=utf-8")
+                    .unwrap_or_else(|err| err),
+            ));
+        }
+    };
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-[10000] flex items-center justify-center bg-black/60",
+            onclick: move |evt: MouseEvent| {
+                evt.stop_propagation();
+                if !running() {
+                    on_close.call(());
+                }
+            },
+            div {
+                class: "bg-zinc-900 border border-zinc-700 rounded-2xl p-6 max-w-2xl w-full mx-4 shadow-2xl space-y-4 max-h-[85vh] flex flex-col",
+                onclick: move |evt: MouseEvent| evt.stop_propagation(),
+                div {
+                    h3 { class: "text-lg font-semibold text-white", "Compare servers" }
+                    p { class: "text-sm text-zinc-400",
+                        "Walks both libraries and lists albums missing from either side, plus albums whose track counts differ."
+                    }
+                }
+                div { class: "grid grid-cols-1 sm:grid-cols-2 gap-3",
+                    div {
+                        label { class: "block text-xs uppercase tracking-wide text-zinc-500 mb-2",
+                            "Server A"
+                        }
+                        select {
+                            class: "w-full px-3 py-2 rounded-lg bg-zinc-950/60 border border-zinc-800 text-sm text-white focus:outline-none focus:border-emerald-500/50",
+                            value: server_a(),
+                            disabled: running(),
+                            onchange: move |e| server_a.set(e.value()),
+                            for server in servers().iter() {
+                                option { key: "{server.id}", value: "{server.id}", "{server.name}" }
+                            }
+                        }
+                    }
+                    div {
+                        label { class: "block text-xs uppercase tracking-wide text-zinc-500 mb-2",
+                            "Server B"
+                        }
+                        select {
+                            class: "w-full px-3 py-2 rounded-lg bg-zinc-950/60 border border-zinc-800 text-sm text-white focus:outline-none focus:border-emerald-500/50",
+                            value: server_b(),
+                            disabled: running(),
+                            onchange: move |e| server_b.set(e.value()),
+                            for server in servers().iter() {
+                                option { key: "{server.id}", value: "{server.id}", "{server.name}" }
+                            }
+                        }
+                    }
+                }
+                if same_server {
+                    p { class: "text-sm text-amber-300", "Pick two different servers." }
+                }
+                if let Some(message) = progress() {
+                    div { class: "flex items-center gap-2 text-sm text-zinc-300",
+                        Icon {
+                            name: "loader".to_string(),
+                            class: "w-4 h-4 animate-spin".to_string(),
+                        }
+                        "{message}"
+                    }
+                }
+                if let Some(message) = status() {
+                    p { class: "text-sm text-zinc-400 break-all", "{message}" }
+                }
+                if let Some(diff) = report() {
+                    div { class: "min-h-0 flex-1 overflow-y-auto rounded-lg border border-zinc-800 p-3 space-y-4 text-sm",
+                        p { class: "text-zinc-300",
+                            "{diff.matched} albums on both • {diff.only_in_a.len()} only on {name_a} • {diff.only_in_b.len()} only on {name_b} • {diff.track_mismatches.len()} track count mismatches"
+                        }
+                        for (title , albums) in [
+                            (format!("Only on {name_a}"), diff.only_in_a.clone()),
+                            (format!("Only on {name_b}"), diff.only_in_b.clone()),
+                        ]
+                        {
+                            if !albums.is_empty() {
+                                div {
+                                    p { class: "text-xs uppercase tracking-wide text-zinc-500 mb-1",
+                                        "{title}"
+                                    }
+                                    ul { class: "space-y-1",
+                                        for album in albums {
+                                            li { class: "text-zinc-300 truncate",
+                                                "{album.artist} — {album.album} ({album.track_count} tracks)"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        if !diff.track_mismatches.is_empty() {
+                            div {
+                                p { class: "text-xs uppercase tracking-wide text-zinc-500 mb-1",
+                                    "Track count mismatches"
+                                }
+                                ul { class: "space-y-1",
+                                    for mismatch in diff.track_mismatches.clone() {
+                                        li { class: "text-zinc-300 truncate",
+                                            "{mismatch.artist} — {mismatch.album}: {mismatch.tracks_a} vs {mismatch.tracks_b}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                div { class: "flex flex-wrap gap-3 justify-end",
+                    if report().is_some() {
+                        button {
+                            class: "px-4 py-2 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors text-sm",
+                            disabled: running(),
+                            onclick: on_export,
+                            "Export CSV"
+                        }
+                    }
+                    if running() {
+                        button {
+                            class: "px-4 py-2 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors text-sm",
+                            disabled: cancel_requested(),
+                            onclick: move |_| cancel_requested.set(true),
+                            if cancel_requested() {
+                                "Stopping..."
+                            } else {
+                                "Cancel"
+                            }
+                        }
+                    } else {
+                        button {
+                            class: "px-4 py-2 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors text-sm",
+                            onclick: move |_| on_close.call(()),
+                            "Close"
+                        }
+                        if report().is_some() {
+                            button {
+                                class: "px-4 py-2 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors text-sm disabled:opacity-60",
+                                disabled: same_server,
+                                onclick: move |_| start_compare(true),
+                                "Rescan"
+                            }
+                        } else {
+                            button {
+                                class: "px-4 py-2 rounded-lg bg-emerald-500/20 border border-emerald-500/60 text-emerald-300 hover:text-white hover:bg-emerald-500/30 transition-colors text-sm disabled:opacity-60",
+                                disabled: same_server,
+                                onclick: move |_| start_compare(false),
+                                "Compare"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn album(artist: &str, album: &str, track_count: u32) -> LibraryAlbumEntry {
+        LibraryAlbumEntry {
+            artist: artist.to_string(),
+            album: album.to_string(),
+            track_count,
+        }
+    }
+
+    #[test]
+    fn normalizes_case_punctuation_and_leading_article() {
+        assert_eq!(normalize_compare_text("The Beatles"), "beatles");
+        assert_eq!(normalize_compare_text("  AC/DC "), "ac dc");
+        assert_eq!(normalize_compare_text("Abbey Road!"), "abbey road");
+        assert_eq!(normalize_compare_text("The"), "the");
+        assert_eq!(normalize_compare_text("ÉLAN"), "elan");
+        assert_eq!(
+            normalize_compare_text("Björk"),
+            normalize_compare_text("BJORK")
+        );
+    }
+
+    #[test]
+    fn diff_reports_missing_albums_on_each_side() {
+        let a = vec![
+            album("Radiohead", "OK Computer", 12),
+            album("Björk", "Post", 11),
+        ];
+        let b = vec![
+            album("radiohead", "OK computer", 12),
+            album("Low", "Things We Lost", 14),
+        ];
+        let diff = diff_libraries(&a, &b);
+        assert_eq!(diff.matched, 1);
+        assert_eq!(diff.only_in_a, vec![album("Björk", "Post", 11)]);
+        assert_eq!(diff.only_in_b, vec![album("Low", "Things We Lost", 14)]);
+        assert!(diff.track_mismatches.is_empty());
+    }
+
+    #[test]
+    fn diff_reports_track_count_mismatches() {
+        let a = vec![album("The National", "Boxer", 12)];
+        let b = vec![album("National", "Boxer", 10)];
+        let diff = diff_libraries(&a, &b);
+        assert_eq!(diff.matched, 1);
+        assert_eq!(
+            diff.track_mismatches,
+            vec![TrackCountMismatch {
+                artist: "The National".to_string(),
+                album: "Boxer".to_string(),
+                tracks_a: 12,
+                tracks_b: 10,
+            }]
+        );
+    }
+
+    #[test]
+    fn split_album_entries_are_summed_before_comparing() {
+        let a = vec![
+            album("Pink Floyd", "The Wall", 13),
+            album("Pink Floyd", "The Wall", 13),
+        ];
+        let b = vec![album("Pink Floyd", "The Wall", 26)];
+        let diff = diff_libraries(&a, &b);
+        assert_eq!(diff.matched, 1);
+        assert!(diff.track_mismatches.is_empty());
+    }
+
+    #[test]
+    fn csv_escapes_fields_and_leaves_missing_side_blank() {
+        let diff = LibraryDiff {
+            only_in_a: vec![album("Crosby, Stills & Nash", "CSN", 10)],
+            only_in_b: vec![album("Low", "\"Tempest\"", 2)],
+            track_mismatches: Vec::new(),
+            matched: 0,
+        };
+        let csv = diff_report_csv(&diff, "Home", "New");
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "status,artist,album,tracks on Home,tracks on New");
+        assert_eq!(lines[1], "only on Home,\"Crosby, Stills & Nash\",CSN,10,");
+        assert_eq!(lines[2], "only on New,Low,\"\"\"Tempest\"\"\",,2");
+    }
+}
@@ -1,19 +1,53 @@
+use super::feedback_composer::FeedbackComposer;
+use super::server_compare::ServerCompareDialog;
+use super::settings_profiles::SettingsProfilesSection;
+use super::smart_cache_warmup::{
+    warm_smart_cache, SmartCacheConfig, SmartCacheOptionsSheet, SmartCacheUpdate,
+};
+use super::song_id_remap::SongIdRemapSection;
 use crate::api::*;
 use crate::cache_service::{
-    apply_settings as apply_cache_settings, clear_all as clear_cache_storage,
-    stats as current_cache_stats,
+    clear_all as clear_cache_storage, clear_negative_cache,
+    reassign_server as reassign_cached_server, stats as current_cache_stats,
 };
 use crate::components::{
-    ios_audio_log_clear, ios_audio_log_export_txt, ios_audio_log_snapshot, AppView, Icon,
-    Navigation, VolumeSignal,
+    ios_audio_log_clear, ios_audio_log_export_txt, ios_audio_log_snapshot, AppView,
+    GuestSessionSettings, Icon, JobRegistry, ListenAlongSettings, Navigation, PendingSyncSettings,
+    ServerCapabilitiesSignal, SettingsController, VolumeSignal, AUTO_DOWNLOAD_JOB,
+    CACHE_REFRESH_JOB,
+};
+use crate::db::{
+    merge_duplicate_server, save_servers_now, AppSettings, ArtworkDownloadPreference,
+    BufferingStrategy, ClockFormat, DateLocale, GridDensity, PlaybackIndicatorMode, PlayerBarMode,
+    QueueRowDensity, ReplayGainMode, SkippedSongShuffle, SleepInhibitMode, REPLAY_GAIN_PREAMP_DB,
+    STREAM_FORMATS,
 };
-use crate::db::{save_servers_now, save_settings, AppSettings, ArtworkDownloadPreference};
+use crate::diagnostics::recent_blocked_requests;
+use crate::equalizer::{
+    band_label, matching_preset, normalized_band_gains, EQ_BAND_FREQUENCIES_HZ, EQ_GAIN_LIMIT_DB,
+    EQ_PRESETS,
+};
+use crate::network_policy::configure as configure_network_policy;
 use crate::offline_audio::{
     clear_downloads, download_stats, prune_temporary_queue_prefetch_downloads,
-    refresh_downloaded_cache, run_auto_download_pass,
+    reassign_server_downloads,
 };
+use crate::quiet_hours::{minute_label, parse_minute};
+use crate::time_format::humanize_server_timestamp_for_display;
 use dioxus::prelude::*;
-use std::collections::HashSet;
+
+/// Label of the smart cache warm-up in the sidebar's background jobs.
+const SMART_CACHE_JOB: &str = "Smart cache warm-up";
+
+/// The client name to store for a server; blank keeps the default.
+fn resolve_client_name(name: &str) -> String {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        DEFAULT_CLIENT_NAME.to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
 
 fn resolve_server_name(name: &str, url: &str) -> String {
     let trimmed_name = name.trim();
@@ -83,8 +117,69 @@ fn sanitize_server_url(raw: &str) -> String {
     normalize_server_url(&trimmed)
 }
 
+fn find_duplicate_server(
+    servers: &[ServerConfig],
+    url: &str,
+    username: &str,
+    exclude_id: Option<&str>,
+) -> Option<ServerConfig> {
+    if url.trim().is_empty() || username.trim().is_empty() {
+        return None;
+    }
+    let key = ServerConfig::identity_key_for(url, username);
+    servers
+        .iter()
+        .filter(|server| Some(server.id.as_str()) != exclude_id)
+        .find(|server| server.identity_key() == key)
+        .cloned()
+}
+
+/// Servers sharing an identity key, as `(kept, duplicates)`. The active entry is kept when
+/// there is one, otherwise the first in list order.
+fn duplicate_server_groups(servers: &[ServerConfig]) -> Vec<(ServerConfig, Vec<ServerConfig>)> {
+    let mut groups = Vec::<(String, Vec<ServerConfig>)>::new();
+    for server in servers {
+        let key = server.identity_key();
+        match groups.iter_mut().find(|(existing, _)| *existing == key) {
+            Some((_, members)) => members.push(server.clone()),
+            None => groups.push((key, vec![server.clone()])),
+        }
+    }
+    groups
+        .into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|(_, mut members)| {
+            let keep_index = members.iter().position(|server| server.active).unwrap_or(0);
+            let keep = members.remove(keep_index);
+            (keep, members)
+        })
+        .collect()
+}
+
 // Format connection errors into user-friendly messages
-fn format_connection_error(error: &str) -> String {
+fn format_connection_error(error: &ApiError) -> String {
+    match error {
+        ApiError::AuthFailed => {
+            return "Authentication failed. Check your username and password.".to_string();
+        }
+        ApiError::Timeout => {
+            return "Server took too long to respond. Check your network connection and server URL."
+                .to_string();
+        }
+        ApiError::Offline => {
+            return "Offline mode is on. Turn it off to test the connection.".to_string();
+        }
+        ApiError::NotFound { .. } => {
+            return "Server endpoint not found. Verify the server is running.".to_string();
+        }
+        ApiError::RateLimited { .. } => {
+            return "The server is rate limiting requests. Wait a moment and try again."
+                .to_string();
+        }
+        _ => {}
+    }
+
+    let error = error.to_string();
     let lower = error.to_lowercase();
 
     // Connection refused - port unreachable or service not responding
@@ -187,321 +282,181 @@ fn artwork_pref_key(pref: ArtworkDownloadPreference) -> &'static str {
     }
 }
 
-fn parse_artwork_pref(value: &str) -> ArtworkDownloadPreference {
-    match value {
-        "server_only" => ArtworkDownloadPreference::ServerOnly,
-        "id3_only" => ArtworkDownloadPreference::Id3Only,
-        "prefer_id3" => ArtworkDownloadPreference::PreferId3,
-        _ => ArtworkDownloadPreference::PreferServer,
+fn sleep_inhibit_key(mode: SleepInhibitMode) -> &'static str {
+    match mode {
+        SleepInhibitMode::Never => "never",
+        SleepInhibitMode::SystemSleep => "system_sleep",
+        SleepInhibitMode::SystemAndDisplay => "system_and_display",
     }
 }
 
-#[derive(Clone)]
-struct ScanResultEntry {
-    server_name: String,
-    status: ScanStatus,
-}
-
-const SMART_CACHE_MIN_ALBUMS_PER_SERVER: u32 = 24;
-const SMART_CACHE_MIN_RANDOM_SONGS_PER_SERVER: u32 = 30;
-const SMART_CACHE_MIN_PLAYLISTS_PER_SERVER: usize = 4;
-const SMART_CACHE_MIN_ALBUM_DETAILS_PER_SERVER: usize = 6;
-const SMART_CACHE_MIN_LYRICS_LIMIT: usize = 36;
-const SMART_CACHE_MIN_ARTWORK_LIMIT: usize = 160;
-const SMART_CACHE_MAX_ALBUMS_PER_SERVER: u32 = 120;
-const SMART_CACHE_MAX_RANDOM_SONGS_PER_SERVER: u32 = 220;
-const SMART_CACHE_MAX_PLAYLISTS_PER_SERVER: usize = 16;
-const SMART_CACHE_MAX_ALBUM_DETAILS_PER_SERVER: usize = 30;
-const SMART_CACHE_MAX_LYRICS_LIMIT: usize = 600;
-const SMART_CACHE_MAX_ARTWORK_LIMIT: usize = 4800;
-const SMART_CACHE_SONG_ART_SIZES: [u32; 3] = [80, 120, 160];
-const SMART_CACHE_ALBUM_ART_SIZES: [u32; 4] = [120, 160, 300, 500];
-const SMART_CACHE_PLAYLIST_ART_SIZES: [u32; 3] = [120, 160, 300];
-
-fn smart_cache_albums_per_server(cache_size_mb: u32) -> u32 {
-    (SMART_CACHE_MIN_ALBUMS_PER_SERVER + cache_size_mb.clamp(25, 2048) / 20).clamp(
-        SMART_CACHE_MIN_ALBUMS_PER_SERVER,
-        SMART_CACHE_MAX_ALBUMS_PER_SERVER,
-    )
-}
-
-fn smart_cache_random_songs_per_server(cache_size_mb: u32) -> u32 {
-    (SMART_CACHE_MIN_RANDOM_SONGS_PER_SERVER + cache_size_mb.clamp(25, 2048) / 12).clamp(
-        SMART_CACHE_MIN_RANDOM_SONGS_PER_SERVER,
-        SMART_CACHE_MAX_RANDOM_SONGS_PER_SERVER,
-    )
+fn replay_gain_mode_key(mode: ReplayGainMode) -> &'static str {
+    match mode {
+        ReplayGainMode::Track => "track",
+        ReplayGainMode::Album => "album",
+    }
 }
 
-fn smart_cache_playlists_per_server(cache_size_mb: u32) -> usize {
-    (SMART_CACHE_MIN_PLAYLISTS_PER_SERVER + (cache_size_mb.clamp(25, 2048) / 180) as usize).clamp(
-        SMART_CACHE_MIN_PLAYLISTS_PER_SERVER,
-        SMART_CACHE_MAX_PLAYLISTS_PER_SERVER,
-    )
+fn buffering_strategy_key(strategy: BufferingStrategy) -> &'static str {
+    match strategy {
+        BufferingStrategy::Aggressive => "aggressive",
+        BufferingStrategy::Balanced => "balanced",
+        BufferingStrategy::Minimal => "minimal",
+    }
 }
 
-fn smart_cache_album_details_per_server(cache_size_mb: u32) -> usize {
-    (SMART_CACHE_MIN_ALBUM_DETAILS_PER_SERVER + (cache_size_mb.clamp(25, 2048) / 60) as usize)
-        .clamp(
-            SMART_CACHE_MIN_ALBUM_DETAILS_PER_SERVER,
-            SMART_CACHE_MAX_ALBUM_DETAILS_PER_SERVER,
-        )
+fn parse_buffering_strategy(value: &str) -> BufferingStrategy {
+    match value {
+        "aggressive" => BufferingStrategy::Aggressive,
+        "minimal" => BufferingStrategy::Minimal,
+        _ => BufferingStrategy::Balanced,
+    }
 }
 
-fn smart_cache_lyrics_limit(cache_size_mb: u32) -> usize {
-    (SMART_CACHE_MIN_LYRICS_LIMIT + (cache_size_mb.clamp(25, 2048) / 3) as usize)
-        .clamp(SMART_CACHE_MIN_LYRICS_LIMIT, SMART_CACHE_MAX_LYRICS_LIMIT)
+fn skipped_song_shuffle_key(mode: SkippedSongShuffle) -> &'static str {
+    match mode {
+        SkippedSongShuffle::Off => "off",
+        SkippedSongShuffle::DownWeight => "down_weight",
+        SkippedSongShuffle::Exclude => "exclude",
+    }
 }
 
-fn smart_cache_artwork_limit(cache_size_mb: u32) -> usize {
-    (SMART_CACHE_MIN_ARTWORK_LIMIT + (cache_size_mb.clamp(25, 2048) * 5) as usize)
-        .clamp(SMART_CACHE_MIN_ARTWORK_LIMIT, SMART_CACHE_MAX_ARTWORK_LIMIT)
+fn parse_skipped_song_shuffle(value: &str) -> SkippedSongShuffle {
+    match value {
+        "down_weight" => SkippedSongShuffle::DownWeight,
+        "exclude" => SkippedSongShuffle::Exclude,
+        _ => SkippedSongShuffle::Off,
+    }
 }
 
-fn push_cover_art_request(
-    client: &NavidromeClient,
-    server: &ServerConfig,
-    cover_art_id: &str,
-    sizes: &[u32],
-    output: &mut Vec<String>,
-    seen_requests: &mut HashSet<String>,
-    limit: usize,
-) {
-    for size in sizes {
-        if output.len() >= limit {
-            return;
-        }
-        let request_key = format!("{}:{}:{}", server.id, cover_art_id, size);
-        if !seen_requests.insert(request_key) {
-            continue;
-        }
-        output.push(client.get_cover_art_url(cover_art_id, *size));
+fn queue_row_density_key(density: QueueRowDensity) -> &'static str {
+    match density {
+        QueueRowDensity::WithArt => "with_art",
+        QueueRowDensity::Compact => "compact",
     }
 }
 
-fn song_cache_key(song: &Song) -> String {
-    format!("{}::{}", song.server_id, song.id)
+fn parse_queue_row_density(value: &str) -> QueueRowDensity {
+    match value {
+        "compact" => QueueRowDensity::Compact,
+        _ => QueueRowDensity::WithArt,
+    }
 }
 
-fn is_id3_cover_art_id(cover_art_id: &str) -> bool {
-    cover_art_id.trim().to_ascii_lowercase().starts_with("mf-")
+fn player_bar_mode_key(mode: PlayerBarMode) -> &'static str {
+    match mode {
+        PlayerBarMode::Auto => "auto",
+        PlayerBarMode::AlwaysExpanded => "always_expanded",
+    }
 }
 
-fn include_cover_for_pref(cover_art_id: &str, pref: ArtworkDownloadPreference) -> bool {
-    match pref {
-        ArtworkDownloadPreference::ServerOnly => !is_id3_cover_art_id(cover_art_id),
-        ArtworkDownloadPreference::Id3Only => is_id3_cover_art_id(cover_art_id),
-        ArtworkDownloadPreference::PreferServer | ArtworkDownloadPreference::PreferId3 => true,
+fn parse_player_bar_mode(value: &str) -> PlayerBarMode {
+    match value {
+        "always_expanded" => PlayerBarMode::AlwaysExpanded,
+        _ => PlayerBarMode::Auto,
     }
 }
 
-fn collect_song_cover_urls(
-    server: &ServerConfig,
-    songs: &[Song],
-    output: &mut Vec<String>,
-    seen_requests: &mut HashSet<String>,
-    limit: usize,
-    pref: ArtworkDownloadPreference,
-) {
-    let client = NavidromeClient::new(server.clone());
-    for song in songs {
-        if output.len() >= limit {
-            break;
-        }
-        if let Some(cover) = song
-            .cover_art
-            .as_ref()
-            .filter(|value| !value.trim().is_empty())
-            .or_else(|| {
-                song.album_id
-                    .as_ref()
-                    .filter(|value| !value.trim().is_empty())
-            })
-        {
-            if include_cover_for_pref(cover, pref) {
-                push_cover_art_request(
-                    &client,
-                    server,
-                    cover,
-                    &SMART_CACHE_SONG_ART_SIZES,
-                    output,
-                    seen_requests,
-                    limit,
-                );
-            }
-        }
+fn grid_density_key(density: GridDensity) -> &'static str {
+    match density {
+        GridDensity::Auto => "auto",
+        GridDensity::Small => "small",
+        GridDensity::Medium => "medium",
+        GridDensity::Large => "large",
     }
 }
 
-fn collect_album_cover_urls(
-    server: &ServerConfig,
-    albums: &[Album],
-    output: &mut Vec<String>,
-    seen_requests: &mut HashSet<String>,
-    limit: usize,
-    pref: ArtworkDownloadPreference,
-) {
-    let client = NavidromeClient::new(server.clone());
-    for album in albums {
-        if output.len() >= limit {
-            break;
-        }
-        if let Some(cover) = album
-            .cover_art
-            .as_ref()
-            .filter(|value| !value.trim().is_empty())
-        {
-            if !include_cover_for_pref(cover, pref) {
-                continue;
-            }
-            push_cover_art_request(
-                &client,
-                server,
-                cover,
-                &SMART_CACHE_ALBUM_ART_SIZES,
-                output,
-                seen_requests,
-                limit,
-            );
-        }
+fn parse_grid_density(value: &str) -> GridDensity {
+    match value {
+        "small" => GridDensity::Small,
+        "medium" => GridDensity::Medium,
+        "large" => GridDensity::Large,
+        _ => GridDensity::Auto,
     }
 }
 
-fn collect_playlist_cover_urls(
-    server: &ServerConfig,
-    playlists: &[Playlist],
-    output: &mut Vec<String>,
-    seen_requests: &mut HashSet<String>,
-    limit: usize,
-    pref: ArtworkDownloadPreference,
-) {
-    let client = NavidromeClient::new(server.clone());
-    for playlist in playlists {
-        if output.len() >= limit {
-            break;
-        }
-        if let Some(cover) = playlist
-            .cover_art
-            .as_ref()
-            .filter(|value| !value.trim().is_empty())
-        {
-            if !include_cover_for_pref(cover, pref) {
-                continue;
-            }
-            push_cover_art_request(
-                &client,
-                server,
-                cover,
-                &SMART_CACHE_PLAYLIST_ART_SIZES,
-                output,
-                seen_requests,
-                limit,
-            );
-        }
+fn date_locale_key(locale: DateLocale) -> &'static str {
+    match locale {
+        DateLocale::EnUs => "en_us",
+        DateLocale::EnGb => "en_gb",
+        DateLocale::De => "de",
+        DateLocale::Fr => "fr",
+        DateLocale::Es => "es",
     }
 }
 
-fn push_unique_songs(
-    target: &mut Vec<Song>,
-    seen: &mut HashSet<String>,
-    incoming: impl IntoIterator<Item = Song>,
-) {
-    for song in incoming {
-        let key = song_cache_key(&song);
-        if seen.insert(key) {
-            target.push(song);
-        }
+fn parse_date_locale(value: &str) -> DateLocale {
+    match value {
+        "en_gb" => DateLocale::EnGb,
+        "de" => DateLocale::De,
+        "fr" => DateLocale::Fr,
+        "es" => DateLocale::Es,
+        _ => DateLocale::EnUs,
     }
 }
 
-fn dedupe_trim_urls(urls: Vec<String>, limit: usize) -> Vec<String> {
-    let mut seen = HashSet::<String>::new();
-    let mut output = Vec::<String>::new();
-    for url in urls {
-        let trimmed = url.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-        if seen.insert(trimmed.to_string()) {
-            output.push(trimmed.to_string());
-        }
-        if output.len() >= limit {
-            break;
-        }
+fn clock_format_key(clock: ClockFormat) -> &'static str {
+    match clock {
+        ClockFormat::Auto => "auto",
+        ClockFormat::TwelveHour => "12h",
+        ClockFormat::TwentyFourHour => "24h",
     }
-    output
 }
 
-#[cfg(target_arch = "wasm32")]
-async fn smart_cache_pause(ms: u32) {
-    gloo_timers::future::TimeoutFuture::new(ms).await;
+fn parse_clock_format(value: &str) -> ClockFormat {
+    match value {
+        "12h" => ClockFormat::TwelveHour,
+        "24h" => ClockFormat::TwentyFourHour,
+        _ => ClockFormat::Auto,
+    }
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-async fn smart_cache_pause(_ms: u32) {}
-
-#[cfg(target_arch = "wasm32")]
-fn warm_cover_art_urls(urls: &[String]) -> Result<usize, String> {
-    let payload = serde_json::to_string(urls).map_err(|error| error.to_string())?;
-    let script = format!(
-        r#"
-(() => {{
-  const urls = {payload};
-  if (!Array.isArray(urls) || urls.length === 0) return 0;
-  const enqueue = window.__rustyCoverArtEnqueue;
-  let queued = 0;
-  for (const raw of urls) {{
-    if (typeof raw !== "string" || !raw.includes("/rest/getCoverArt?")) continue;
-    if (typeof enqueue === "function") {{
-      if (enqueue(raw)) queued++;
-    }} else {{
-      const img = new Image();
-      img.setAttribute("src", raw);
-      queued++;
-    }}
-  }}
-  return queued;
-}})()
-        "#
-    );
+const QUIET_HOURS_DAY_LABELS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
 
-    let result = js_sys::eval(&script).map_err(|error| format!("{error:?}"))?;
-    Ok(result.as_f64().unwrap_or(0.0).round().max(0.0) as usize)
+fn playback_indicator_key(mode: PlaybackIndicatorMode) -> &'static str {
+    match mode {
+        PlaybackIndicatorMode::Off => "off",
+        PlaybackIndicatorMode::WindowTitle => "window_title",
+        PlaybackIndicatorMode::TitleAndBadge => "title_and_badge",
+    }
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-fn warm_cover_art_urls(_urls: &[String]) -> Result<usize, String> {
-    Ok(0)
+fn parse_playback_indicator(value: &str) -> PlaybackIndicatorMode {
+    match value {
+        "off" => PlaybackIndicatorMode::Off,
+        "window_title" => PlaybackIndicatorMode::WindowTitle,
+        _ => PlaybackIndicatorMode::TitleAndBadge,
+    }
 }
 
-#[cfg(target_arch = "wasm32")]
-async fn settings_toast_pause(ms: u32) {
-    gloo_timers::future::TimeoutFuture::new(ms).await;
+fn parse_sleep_inhibit(value: &str) -> SleepInhibitMode {
+    match value {
+        "never" => SleepInhibitMode::Never,
+        "system_and_display" => SleepInhibitMode::SystemAndDisplay,
+        _ => SleepInhibitMode::SystemSleep,
+    }
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-async fn settings_toast_pause(ms: u32) {
-    tokio::time::sleep(std::time::Duration::from_millis(ms as u64)).await;
+fn parse_replay_gain_mode(value: &str) -> ReplayGainMode {
+    match value {
+        "album" => ReplayGainMode::Album,
+        _ => ReplayGainMode::Track,
+    }
 }
 
-fn persist_settings_with_toast(
-    settings: AppSettings,
-    mut saved_toast: Signal<Option<String>>,
-    mut saved_toast_nonce: Signal<u64>,
-) {
-    saved_toast_nonce.with_mut(|nonce| *nonce = nonce.saturating_add(1));
-    let nonce = saved_toast_nonce();
-    saved_toast.set(Some("Saved".to_string()));
-
-    spawn(async move {
-        let _ = save_settings(settings).await;
-    });
+fn parse_artwork_pref(value: &str) -> ArtworkDownloadPreference {
+    match value {
+        "server_only" => ArtworkDownloadPreference::ServerOnly,
+        "id3_only" => ArtworkDownloadPreference::Id3Only,
+        "prefer_id3" => ArtworkDownloadPreference::PreferId3,
+        _ => ArtworkDownloadPreference::PreferServer,
+    }
+}
 
-    spawn(async move {
-        settings_toast_pause(1400).await;
-        if saved_toast_nonce() == nonce {
-            saved_toast.set(None);
-        }
-    });
+#[derive(Clone)]
+struct ScanResultEntry {
+    server_name: String,
+    status: ScanStatus,
 }
 
 fn persist_servers_immediately(servers: Vec<ServerConfig>) {
@@ -518,7 +473,7 @@ fn ServerFormFields(
     server_url: Signal<String>,
     server_user: Signal<String>,
     server_pass: Signal<String>,
-    test_result: Signal<Option<Result<(), String>>>,
+    test_result: Signal<Option<Result<String, String>>>,
     is_testing: bool,
     can_add: bool,
     force_http: Signal<bool>,
@@ -611,10 +566,10 @@ fn ServerFormFields(
             // Test result
             {
                 match test_result() {
-                    Some(Ok(())) => rsx! {
+                    Some(Ok(identity)) => rsx! {
                         div { class: "flex items-center gap-2 text-emerald-400 text-sm",
                             Icon { name: "check".to_string(), class: "w-4 h-4".to_string() }
-                            "Connection successful!"
+                            "Connection successful: {identity}"
                         }
                     },
                     Some(Err(e)) => rsx! {
@@ -661,6 +616,45 @@ fn ServerFormFields(
     }
 }
 
+/// Second onboarding step, shown once the first server is added: suggests warming the cache.
+#[component]
+fn OnboardingSmartCacheCard(
+    busy: bool,
+    on_run: EventHandler<MouseEvent>,
+    on_skip: EventHandler<MouseEvent>,
+) -> Element {
+    rsx! {
+        section { class: "rounded-2xl border border-emerald-500/30 bg-emerald-500/10 p-5 flex flex-col sm:flex-row sm:items-center gap-4",
+            div { class: "w-12 h-12 rounded-xl bg-emerald-500/20 flex items-center justify-center flex-shrink-0",
+                Icon {
+                    name: "check".to_string(),
+                    class: "w-6 h-6 text-emerald-300".to_string(),
+                }
+            }
+            div { class: "min-w-0 flex-1",
+                p { class: "text-xs uppercase tracking-wide text-emerald-300/80", "Step 2 of 2" }
+                h2 { class: "text-lg font-semibold text-white", "You're connected" }
+                p { class: "text-sm text-emerald-100/80",
+                    "Run Smart Cache to prefetch albums, playlists, lyrics and artwork so browsing is quick and works offline. It can be run again any time from the Storage tab."
+                }
+            }
+            div { class: "flex gap-2 flex-shrink-0",
+                button {
+                    class: "px-3 py-2 rounded-lg text-zinc-300 hover:text-white transition-colors text-sm",
+                    onclick: move |evt| on_skip.call(evt),
+                    "Skip"
+                }
+                button {
+                    class: if busy { "px-4 py-2 rounded-lg bg-emerald-500/60 text-white cursor-not-allowed text-sm font-medium" } else { "px-4 py-2 rounded-lg bg-emerald-500 hover:bg-emerald-400 text-white transition-colors text-sm font-medium" },
+                    disabled: busy,
+                    onclick: move |evt| on_run.call(evt),
+                    "Run Smart Cache"
+                }
+            }
+        }
+    }
+}
+
 // ── Theme picker ─────────────────────────────────────────────────────────────
 
 #[derive(Clone, PartialEq, Props)]
@@ -797,7 +791,9 @@ fn ThemeCard(props: ThemeCardProps) -> Element {
 #[component]
 pub fn SettingsView() -> Element {
     let mut servers = use_context::<Signal<Vec<ServerConfig>>>();
-    let mut app_settings = use_context::<Signal<AppSettings>>();
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let settings_controller = use_context::<SettingsController>();
+    let job_registry = use_context::<JobRegistry>();
     let navigation = use_context::<Navigation>();
     let mut volume = use_context::<VolumeSignal>().0;
     let scan_results = use_signal(|| Vec::<ScanResultEntry>::new());
@@ -807,17 +803,19 @@ pub fn SettingsView() -> Element {
     let mut server_url = use_signal(String::new);
     let mut server_user = use_signal(String::new);
     let mut server_pass = use_signal(String::new);
+    let mut server_client_name = use_signal(String::new);
+    let mut server_raw_stream = use_signal(|| false);
     let mut force_http = use_signal(|| true);
     let mut is_testing = use_signal(|| false);
-    let mut test_result = use_signal(|| None::<Result<(), String>>);
+    let mut test_result = use_signal(|| None::<Result<String, String>>);
     let mut editing_server = use_signal(|| None::<ServerConfig>);
+    let mut show_server_compare = use_signal(|| false);
     let mut is_testing_connection = use_signal(|| false);
-    let mut connection_test_result = use_signal(|| None::<Result<(), String>>);
+    let mut connection_test_result = use_signal(|| None::<Result<String, String>>);
     let mut save_status = use_signal(|| None::<String>);
-    let saved_toast = use_signal(|| None::<String>);
-    let saved_toast_nonce = use_signal(|| 0u64);
-    let smart_cache_busy = use_signal(|| false);
-    let smart_cache_progress = use_signal(|| 0u8);
+    // The warm-up runs as a background job, so it is picked up again after leaving Settings.
+    let smart_cache_job = job_registry.running(SMART_CACHE_JOB);
+    let smart_cache_busy = smart_cache_job.is_some();
     let smart_cache_status = use_signal(|| None::<String>);
     let auto_download_busy = use_signal(|| false);
     let download_cache_refresh_busy = use_signal(|| false);
@@ -828,12 +826,23 @@ pub fn SettingsView() -> Element {
     let mut active_tab = use_signal(|| "servers".to_string());
     let mut custom_css_draft = use_signal(|| app_settings().custom_css.clone());
 
+    let duplicate_server = use_memo(move || {
+        let editing_id = editing_server().map(|server| server.id);
+        find_duplicate_server(
+            &servers(),
+            &sanitize_server_url(&server_url()),
+            &server_user(),
+            editing_id.as_deref(),
+        )
+    });
+
     let can_add = use_memo(move || {
         !server_url().trim().is_empty()
             && !server_user().trim().is_empty()
             && !server_pass().trim().is_empty()
-            && test_result().is_some_and(|r: Result<(), String>| r.is_ok())
+            && test_result().is_some_and(|r: Result<String, String>| r.is_ok())
             && editing_server().is_none()
+            && duplicate_server().is_none()
     });
 
     let on_test = {
@@ -854,17 +863,19 @@ pub fn SettingsView() -> Element {
                 url = url.replacen("https://", "http://", 1);
             }
 
+            let client_name = resolve_client_name(&server_client_name());
             is_testing.set(true);
             test_result.set(None);
 
             spawn(async move {
-                let test_server = ServerConfig::new("Test".to_string(), url, user, pass);
+                let mut test_server = ServerConfig::new("Test".to_string(), url, user, pass);
+                test_server.client_name = client_name;
                 let client = NavidromeClient::new(test_server);
-                let result = client.ping().await;
+                let result = client.identify().await;
 
                 // Format error messages for better UX
                 let formatted_result = result.map_err(|e| format_connection_error(&e));
-                test_result.set(Some(formatted_result.map(|_| ())));
+                test_result.set(Some(formatted_result.map(|identity| identity.summary())));
                 is_testing.set(false);
             });
         }
@@ -883,6 +894,8 @@ pub fn SettingsView() -> Element {
             server_url.set(server.url);
             server_user.set(server.username);
             server_pass.set(server.password);
+            server_client_name.set(server.client_name);
+            server_raw_stream.set(server.raw_stream);
             force_http.set(is_http);
             test_result.set(None);
         }
@@ -894,6 +907,8 @@ pub fn SettingsView() -> Element {
         server_url.set(String::new());
         server_user.set(String::new());
         server_pass.set(String::new());
+        server_client_name.set(String::new());
+        server_raw_stream.set(false);
         force_http.set(true);
         test_result.set(None);
     };
@@ -904,6 +919,7 @@ pub fn SettingsView() -> Element {
             let name = resolve_server_name(&server_name(), &url);
             let user = server_user().trim().to_string();
             let pass = server_pass().trim().to_string();
+            let client_name = resolve_client_name(&server_client_name());
 
             // Apply force_http preference
             if force_http() {
@@ -913,6 +929,9 @@ pub fn SettingsView() -> Element {
             if url.is_empty() || user.is_empty() || pass.is_empty() {
                 return;
             }
+            if duplicate_server().is_some() {
+                return;
+            }
 
             servers.with_mut(|list| {
                 if let Some(server) = list.iter_mut().find(|s| s.id == editing.id) {
@@ -920,15 +939,21 @@ pub fn SettingsView() -> Element {
                     server.url = url;
                     server.username = user;
                     server.password = pass;
+                    server.client_name = client_name;
+                    server.raw_stream = server_raw_stream();
                 }
             });
             persist_servers_immediately(servers());
+            // New credentials get a fresh chance; a rejection will flag the server again.
+            clear_server_reauth(&editing.id);
 
             editing_server.set(None);
             server_name.set(String::new());
             server_url.set(String::new());
             server_user.set(String::new());
             server_pass.set(String::new());
+            server_client_name.set(String::new());
+            server_raw_stream.set(false);
             force_http.set(true);
             test_result.set(None);
 
@@ -958,8 +983,13 @@ pub fn SettingsView() -> Element {
         if url.is_empty() || user.is_empty() || pass.is_empty() {
             return;
         }
+        if find_duplicate_server(&servers(), &url, &user, None).is_some() {
+            return;
+        }
 
-        let new_server = ServerConfig::new(name, url, user, pass);
+        let mut new_server = ServerConfig::new(name, url, user, pass);
+        new_server.client_name = resolve_client_name(&server_client_name());
+        new_server.raw_stream = server_raw_stream();
         servers.with_mut(|list| list.push(new_server));
         persist_servers_immediately(servers());
 
@@ -967,6 +997,8 @@ pub fn SettingsView() -> Element {
         server_url.set(String::new());
         server_user.set(String::new());
         server_pass.set(String::new());
+        server_client_name.set(String::new());
+        server_raw_stream.set(false);
         force_http.set(true);
         test_result.set(None);
 
@@ -981,6 +1013,7 @@ pub fn SettingsView() -> Element {
         }
     };
 
+    let mut server_capabilities = use_context::<ServerCapabilitiesSignal>().capabilities;
     let mut on_test_existing = {
         let servers = servers.clone();
         move |server_id: String| {
@@ -993,9 +1026,22 @@ pub fn SettingsView() -> Element {
 
                 spawn(async move {
                     let client = NavidromeClient::new(server);
-                    let result = client.ping().await;
+                    let result = client.identify().await;
+                    // A passing test also re-checks capabilities, e.g. after a server upgrade.
+                    if result.is_ok() {
+                        client.forget_capabilities();
+                        if let Ok(capabilities) = client.probe_capabilities().await {
+                            server_capabilities.with_mut(|probed| {
+                                probed.insert(client.server.id.clone(), capabilities);
+                            });
+                        }
+                    }
 
-                    connection_test_result.set(Some(result.map(|_| ())));
+                    connection_test_result.set(Some(
+                        result
+                            .map(|identity| identity.summary())
+                            .map_err(|err| err.to_string()),
+                    ));
                     is_testing_connection.set(false);
                 });
             }
@@ -1003,40 +1049,258 @@ pub fn SettingsView() -> Element {
     };
 
     let on_crossfade_toggle = move |_| {
-        let mut settings = app_settings();
-        settings.crossfade_enabled = !settings.crossfade_enabled;
-        let settings_clone = settings.clone();
-        app_settings.set(settings);
-        persist_settings_with_toast(
-            settings_clone,
-            saved_toast.clone(),
-            saved_toast_nonce.clone(),
-        );
+        settings_controller.update_settings(|settings| {
+            settings.crossfade_enabled = !settings.crossfade_enabled;
+        });
+    };
+
+    let on_crossfade_on_skip_toggle = move |_| {
+        settings_controller.update_settings(|settings| {
+            settings.crossfade_on_skip = !settings.crossfade_on_skip;
+        });
+    };
+
+    let on_eq_toggle = move |_| {
+        settings_controller.update_settings(|settings| {
+            settings.eq_enabled = !settings.eq_enabled;
+        });
+    };
+
+    let set_eq_band = move |band: usize, gain: f32| {
+        settings_controller.update_settings(|settings| {
+            let mut gains = normalized_band_gains(&settings.eq_band_gains);
+            gains[band] = gain.clamp(-EQ_GAIN_LIMIT_DB, EQ_GAIN_LIMIT_DB);
+            settings.eq_band_gains = gains;
+        });
+    };
+
+    let on_gapless_toggle = move |_| {
+        settings_controller.update_settings(|settings| {
+            settings.gapless_enabled = !settings.gapless_enabled;
+        });
     };
 
     let on_replay_gain_toggle = move |_| {
-        let mut settings = app_settings();
-        settings.replay_gain = !settings.replay_gain;
-        let settings_clone = settings.clone();
-        app_settings.set(settings);
-        persist_settings_with_toast(
-            settings_clone,
-            saved_toast.clone(),
-            saved_toast_nonce.clone(),
-        );
+        settings_controller.update_settings(|settings| {
+            settings.replay_gain = !settings.replay_gain;
+        });
+    };
+
+    let on_replay_gain_mode_change = move |e: Event<FormData>| {
+        settings_controller.update_settings(|settings| {
+            settings.replay_gain_mode = parse_replay_gain_mode(&e.value());
+        });
+    };
+
+    let on_replay_gain_preamp_change = move |e: Event<FormData>| {
+        if let Ok(preamp_db) = e.value().parse::<f64>() {
+            settings_controller.update_settings(|settings| {
+                settings.replay_gain_preamp_db =
+                    preamp_db.clamp(-REPLAY_GAIN_PREAMP_DB, REPLAY_GAIN_PREAMP_DB);
+            });
+        }
+    };
+
+    let on_fast_start_toggle = move |_| {
+        settings_controller.update_settings(|settings| {
+            settings.fast_start_enabled = !settings.fast_start_enabled;
+        });
+    };
+
+    let on_fast_start_bitrate_change = move |e: Event<FormData>| {
+        if let Ok(kbps) = e.value().parse::<u32>() {
+            settings_controller.update_settings(|settings| {
+                settings.fast_start_bitrate_kbps = kbps.clamp(64, 320);
+            });
+        }
+    };
+
+    let on_stream_bitrate_change = move |e: Event<FormData>| {
+        if let Ok(kbps) = e.value().parse::<u32>() {
+            settings_controller.update_settings(|settings| {
+                settings.stream_max_bitrate_kbps = kbps;
+            });
+        }
+    };
+
+    let on_stream_format_change = move |e: Event<FormData>| {
+        settings_controller.update_settings(|settings| {
+            settings.stream_format = e.value();
+        });
+    };
+
+    let on_buffering_strategy_change = move |e: Event<FormData>| {
+        settings_controller.update_settings(|settings| {
+            settings.buffering_strategy = parse_buffering_strategy(&e.value());
+        });
+    };
+
+    let on_fetch_ahead_limit_change = move |e: Event<FormData>| {
+        if let Ok(limit_mb) = e.value().parse::<u32>() {
+            settings_controller.update_settings(|settings| {
+                settings.fetch_ahead_limit_mb = limit_mb.clamp(8, 512);
+            });
+        }
+    };
+
+    let on_progress_poll_interval_change = move |e: Event<FormData>| {
+        if let Ok(interval_ms) = e.value().parse::<u32>() {
+            settings_controller.update_settings(|settings| {
+                settings.progress_poll_interval_ms = interval_ms.clamp(100, 1000);
+            });
+        }
+    };
+
+    let on_smooth_progress_toggle = move |_| {
+        settings_controller.update_settings(|settings| {
+            settings.smooth_progress_updates = !settings.smooth_progress_updates;
+        });
+    };
+
+    let on_pause_on_unplug_toggle = move |_| {
+        settings_controller.update_settings(|settings| {
+            settings.pause_on_unplug = !settings.pause_on_unplug;
+        });
+    };
+
+    let on_quiet_hours_toggle = move |_| {
+        settings_controller.update_settings(|settings| {
+            settings.quiet_hours.enabled = !settings.quiet_hours.enabled;
+        });
+    };
+
+    let on_quiet_hours_start_change = move |e: Event<FormData>| {
+        if let Some(minute) = parse_minute(&e.value()) {
+            settings_controller.update_settings(|settings| {
+                settings.quiet_hours.start_minute = minute;
+            });
+        }
+    };
+
+    let on_quiet_hours_end_change = move |e: Event<FormData>| {
+        if let Some(minute) = parse_minute(&e.value()) {
+            settings_controller.update_settings(|settings| {
+                settings.quiet_hours.end_minute = minute;
+            });
+        }
+    };
+
+    let on_quiet_hours_cap_change = move |e: Event<FormData>| {
+        if let Ok(percent) = e.value().parse::<u8>() {
+            settings_controller.update_settings(|settings| {
+                settings.quiet_hours.cap_percent = percent.clamp(5, 100);
+            });
+        }
+    };
+
+    let on_session_summary_toggle = move |_| {
+        settings_controller.update_settings(|settings| {
+            settings.session_summary_enabled = !settings.session_summary_enabled;
+        });
+    };
+
+    let on_skipped_song_shuffle_change = move |e: Event<FormData>| {
+        settings_controller.update_settings(|settings| {
+            settings.skipped_song_shuffle = parse_skipped_song_shuffle(&e.value());
+        });
+    };
+
+    let on_skip_ratio_threshold_change = move |e: Event<FormData>| {
+        if let Ok(percent) = e.value().parse::<u8>() {
+            settings_controller.update_settings(|settings| {
+                settings.skip_ratio_threshold_percent = percent.clamp(10, 100);
+            });
+        }
+    };
+
+    let on_favorites_shuffle_limit_change = move |e: Event<FormData>| {
+        if let Ok(limit) = e.value().parse::<usize>() {
+            settings_controller.update_settings(|settings| {
+                settings.favorites_shuffle_limit = limit.clamp(10, 1000);
+            });
+        }
+    };
+
+    let on_skip_min_plays_change = move |e: Event<FormData>| {
+        if let Ok(plays) = e.value().parse::<u32>() {
+            settings_controller.update_settings(|settings| {
+                settings.skip_min_plays = plays.clamp(1, 100);
+            });
+        }
+    };
+
+    let on_sleep_inhibit_change = move |e: Event<FormData>| {
+        settings_controller.update_settings(|settings| {
+            settings.sleep_inhibit_mode = parse_sleep_inhibit(&e.value());
+        });
+    };
+
+    let on_radio_slogans_change = move |e: Event<FormData>| {
+        settings_controller.update_settings(|settings| {
+            settings.radio_slogan_patterns = e
+                .value()
+                .lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect();
+        });
+    };
+
+    let on_reset_radio_slogans = move |_| {
+        settings_controller.update_settings(|settings| {
+            settings.radio_slogan_patterns = AppSettings::default().radio_slogan_patterns;
+        });
+    };
+
+    let on_playback_indicator_change = move |e: Event<FormData>| {
+        settings_controller.update_settings(|settings| {
+            settings.playback_indicator_mode = parse_playback_indicator(&e.value());
+        });
+    };
+
+    let on_queue_row_density_change = move |e: Event<FormData>| {
+        settings_controller.update_settings(|settings| {
+            settings.queue_row_density = parse_queue_row_density(&e.value());
+        });
+    };
+
+    let on_player_bar_mode_change = move |e: Event<FormData>| {
+        settings_controller.update_settings(|settings| {
+            settings.player_bar_mode = parse_player_bar_mode(&e.value());
+        });
+    };
+
+    let on_grid_density_change = move |e: Event<FormData>| {
+        settings_controller.update_settings(|settings| {
+            settings.grid_density = parse_grid_density(&e.value());
+        });
+    };
+
+    let on_date_locale_change = move |e: Event<FormData>| {
+        settings_controller.update_settings(|settings| {
+            settings.date_locale = parse_date_locale(&e.value());
+        });
+    };
+
+    let on_clock_format_change = move |e: Event<FormData>| {
+        settings_controller.update_settings(|settings| {
+            settings.clock_format = parse_clock_format(&e.value());
+        });
     };
 
     let on_crossfade_duration_change = move |e: Event<FormData>| {
         if let Ok(duration) = e.value().parse::<u32>() {
-            let mut settings = app_settings();
-            settings.crossfade_duration = duration;
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            persist_settings_with_toast(
-                settings_clone,
-                saved_toast.clone(),
-                saved_toast_nonce.clone(),
-            );
+            settings_controller.update_settings(|settings| {
+                settings.crossfade_duration = duration;
+            });
+        }
+    };
+
+    let on_repeat_count_change = move |e: Event<FormData>| {
+        if let Ok(count) = e.value().parse::<u32>() {
+            settings_controller.update_settings(|settings| {
+                settings.repeat_count = count.clamp(2, 20);
+            });
         }
     };
 
@@ -1048,144 +1312,78 @@ pub fn SettingsView() -> Element {
 
     let on_bookmark_limit_change = move |e: Event<FormData>| {
         if let Ok(limit) = e.value().parse::<u32>() {
-            let mut settings = app_settings();
-            settings.bookmark_limit = limit.clamp(1, 5000);
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            persist_settings_with_toast(
-                settings_clone,
-                saved_toast.clone(),
-                saved_toast_nonce.clone(),
-            );
+            settings_controller.update_settings(|settings| {
+                settings.bookmark_limit = limit.clamp(1, 5000);
+            });
         }
     };
 
     let on_bookmark_auto_save_toggle = move |_| {
-        let mut settings = app_settings();
-        settings.bookmark_auto_save = !settings.bookmark_auto_save;
-        let settings_clone = settings.clone();
-        app_settings.set(settings);
-        persist_settings_with_toast(
-            settings_clone,
-            saved_toast.clone(),
-            saved_toast_nonce.clone(),
-        );
+        settings_controller.update_settings(|settings| {
+            settings.bookmark_auto_save = !settings.bookmark_auto_save;
+        });
     };
 
     let on_bookmark_autoplay_toggle = move |_| {
-        let mut settings = app_settings();
-        settings.bookmark_autoplay_on_launch = !settings.bookmark_autoplay_on_launch;
-        let settings_clone = settings.clone();
-        app_settings.set(settings);
-        persist_settings_with_toast(
-            settings_clone,
-            saved_toast.clone(),
-            saved_toast_nonce.clone(),
-        );
+        settings_controller.update_settings(|settings| {
+            settings.bookmark_autoplay_on_launch = !settings.bookmark_autoplay_on_launch;
+        });
     };
 
     let on_cache_enabled_toggle = {
-        let mut app_settings = app_settings.clone();
         move |_| {
-            let mut settings = app_settings();
-            settings.cache_enabled = !settings.cache_enabled;
-            apply_cache_settings(&settings);
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            persist_settings_with_toast(
-                settings_clone,
-                saved_toast.clone(),
-                saved_toast_nonce.clone(),
-            );
+            settings_controller.update_settings(|settings| {
+                settings.cache_enabled = !settings.cache_enabled;
+            });
         }
     };
 
     let on_cache_images_toggle = {
-        let mut app_settings = app_settings.clone();
         move |_| {
-            let mut settings = app_settings();
-            settings.cache_images_enabled = !settings.cache_images_enabled;
-            apply_cache_settings(&settings);
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            persist_settings_with_toast(
-                settings_clone,
-                saved_toast.clone(),
-                saved_toast_nonce.clone(),
-            );
+            settings_controller.update_settings(|settings| {
+                settings.cache_images_enabled = !settings.cache_images_enabled;
+            });
         }
     };
 
     let on_cache_size_change = {
-        let mut app_settings = app_settings.clone();
         move |e: Event<FormData>| {
             if let Ok(size_mb) = e.value().parse::<u32>() {
-                let mut settings = app_settings();
-                settings.cache_size_mb = size_mb.clamp(25, 2048);
-                apply_cache_settings(&settings);
-                let settings_clone = settings.clone();
-                app_settings.set(settings);
-                persist_settings_with_toast(
-                    settings_clone,
-                    saved_toast.clone(),
-                    saved_toast_nonce.clone(),
-                );
+                settings_controller.update_settings(|settings| {
+                    settings.cache_size_mb = size_mb.clamp(25, 2048);
+                });
             }
         }
     };
 
     let on_cache_expiry_change = {
-        let mut app_settings = app_settings.clone();
         move |e: Event<FormData>| {
             if let Ok(expiry_days) = e.value().parse::<i32>() {
-                let mut settings = app_settings();
-                settings.cache_expiry_days = expiry_days.clamp(-1, 3650);
-                settings.cache_expiry_in_days = true;
-                apply_cache_settings(&settings);
-                let settings_clone = settings.clone();
-                app_settings.set(settings);
-                persist_settings_with_toast(
-                    settings_clone,
-                    saved_toast.clone(),
-                    saved_toast_nonce.clone(),
-                );
+                settings_controller.update_settings(|settings| {
+                    settings.cache_expiry_days = expiry_days.clamp(-1, 3650);
+                    settings.cache_expiry_in_days = true;
+                });
             }
         }
     };
 
     let on_use_recommended_cache = {
-        let mut app_settings = app_settings.clone();
         move |_| {
-            let mut settings = app_settings();
-            settings.cache_enabled = true;
-            settings.cache_images_enabled = true;
-            settings.cache_size_mb = 100;
-            settings.cache_expiry_days = 30;
-            settings.cache_expiry_in_days = true;
-            apply_cache_settings(&settings);
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            persist_settings_with_toast(
-                settings_clone,
-                saved_toast.clone(),
-                saved_toast_nonce.clone(),
-            );
+            settings_controller.update_settings(|settings| {
+                settings.cache_enabled = true;
+                settings.cache_images_enabled = true;
+                settings.cache_size_mb = 100;
+                settings.cache_expiry_days = 30;
+                settings.cache_expiry_in_days = true;
+            });
         }
     };
 
     let on_offline_mode_toggle = {
-        let mut app_settings = app_settings.clone();
         move |_| {
-            let mut settings = app_settings();
-            settings.offline_mode = !settings.offline_mode;
-            apply_cache_settings(&settings);
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            persist_settings_with_toast(
-                settings_clone,
-                saved_toast.clone(),
-                saved_toast_nonce.clone(),
-            );
+            settings_controller.update_settings(|settings| {
+                settings.offline_mode = !settings.offline_mode;
+            });
         }
     };
 
@@ -1193,6 +1391,7 @@ pub fn SettingsView() -> Element {
         let mut save_status = save_status.clone();
         move |_| {
             clear_cache_storage();
+            navigation.clear_view_states();
             save_status.set(Some("Cache cleared.".to_string()));
             #[cfg(target_arch = "wasm32")]
             {
@@ -1205,299 +1404,117 @@ pub fn SettingsView() -> Element {
         }
     };
 
-    let on_smart_cache = {
+    let on_negative_cache_toggle = move |_| {
+        settings_controller.update_settings(|settings| {
+            settings.negative_cache_enabled = !settings.negative_cache_enabled;
+        });
+    };
+
+    let on_musicbrainz_toggle = move |_| {
+        settings_controller.update_settings(|settings| {
+            settings.musicbrainz_enabled = !settings.musicbrainz_enabled;
+        });
+    };
+
+    let on_sort_articles_toggle = move |_| {
+        settings_controller.update_settings(|settings| {
+            settings.sort_ignore_articles = !settings.sort_ignore_articles;
+        });
+    };
+
+    let mut blocked_requests = use_signal(recent_blocked_requests);
+    let mut show_feedback = use_signal(|| false);
+    let on_strict_privacy_toggle = move |_| {
+        settings_controller.update_settings(|settings| {
+            settings.strict_privacy_mode = !settings.strict_privacy_mode;
+        });
+        configure_network_policy(app_settings.peek().strict_privacy_mode, &servers());
+        blocked_requests.set(recent_blocked_requests());
+    };
+
+    let mut negative_cache_status = use_signal(|| None::<String>);
+    let on_clear_negative_cache = move |_| {
+        let removed = clear_negative_cache();
+        negative_cache_status.set(Some(if removed == 1 {
+            "Cleared 1 remembered miss.".to_string()
+        } else {
+            format!("Cleared {removed} remembered misses.")
+        }));
+    };
+
+    let complete_onboarding = move || {
+        settings_controller.update_settings(|settings| {
+            settings.onboarding_completed = true;
+        });
+    };
+
+    let mut start_smart_cache = {
         let servers = servers.clone();
-        let app_settings = app_settings.clone();
-        let mut smart_cache_busy = smart_cache_busy.clone();
-        let mut smart_cache_progress = smart_cache_progress.clone();
         let mut smart_cache_status = smart_cache_status.clone();
-        move |_| {
-            if smart_cache_busy() {
+        move |config: SmartCacheConfig| {
+            if job_registry.running(SMART_CACHE_JOB).is_some() {
                 return;
             }
 
             let active_servers: Vec<ServerConfig> = servers()
                 .into_iter()
-                .filter(|server| server.active)
+                .filter(|server| server.active && !server_needs_reauth(&server.id))
                 .collect();
             if active_servers.is_empty() {
-                smart_cache_progress.set(0);
                 smart_cache_status.set(Some("No active servers to warm cache.".to_string()));
                 return;
             }
 
-            let settings_snapshot = app_settings();
-            let provider_order =
-                normalize_lyrics_provider_order(&settings_snapshot.lyrics_provider_order);
-            let timeout_seconds = settings_snapshot.lyrics_request_timeout_secs.clamp(1, 20);
-            let cache_images_enabled = settings_snapshot.cache_images_enabled;
-            let artwork_pref = settings_snapshot.artwork_download_preference;
-            let cache_size_mb = settings_snapshot.cache_size_mb.clamp(25, 2048);
-            let albums_per_server = smart_cache_albums_per_server(cache_size_mb);
-            let random_songs_per_server = smart_cache_random_songs_per_server(cache_size_mb);
-            let playlists_per_server = smart_cache_playlists_per_server(cache_size_mb);
-            let album_details_per_server = smart_cache_album_details_per_server(cache_size_mb);
-            let lyrics_limit = smart_cache_lyrics_limit(cache_size_mb);
-            let artwork_limit = smart_cache_artwork_limit(cache_size_mb);
-
-            smart_cache_busy.set(true);
-            smart_cache_progress.set(0);
-            smart_cache_status.set(Some(format!(
-                "Smart cache: planning {lyrics_limit} lyric lookups and up to {artwork_limit} artwork variants..."
-            )));
-
+            smart_cache_status.set(Some(config.plan_status()));
+            let job = job_registry.start(SMART_CACHE_JOB);
+            job.allow_pause();
+            let warmup = job.run(
+                async move {
+                    warm_smart_cache(
+                        active_servers,
+                        config,
+                        |update| match update {
+                            SmartCacheUpdate::Progress(percent) => {
+                                job.set_progress(f64::from(percent) / 100.0)
+                            }
+                            SmartCacheUpdate::Status(status) => job.set_detail(status),
+                        },
+                        || job.is_paused(),
+                    )
+                    .await
+                },
+                |report| Ok(report.summary()),
+            );
             spawn(async move {
-                let total_server_steps = (active_servers.len() as f64 * 6.0).max(1.0);
-                let mut server_steps_done = 0.0f64;
-                let mut warmed_album_details = 0usize;
-                let mut warmed_playlists = 0usize;
-                let mut warmed_lyrics = 0usize;
-                let mut queued_artwork = 0usize;
-
-                let mut collected_songs = Vec::<Song>::new();
-                let mut seen_song_keys = HashSet::<String>::new();
-                let mut cover_urls = Vec::<String>::new();
-                let mut seen_cover_requests = HashSet::<String>::new();
-
-                for server in active_servers.iter().cloned() {
-                    let client = NavidromeClient::new(server.clone());
-                    smart_cache_status.set(Some(format!(
-                        "Smart cache: loading metadata for {}...",
-                        server.name
-                    )));
-
-                    let newest_albums = client
-                        .get_albums("newest", albums_per_server, 0)
-                        .await
-                        .unwrap_or_default();
-                    collect_album_cover_urls(
-                        &server,
-                        &newest_albums,
-                        &mut cover_urls,
-                        &mut seen_cover_requests,
-                        artwork_limit,
-                        artwork_pref,
-                    );
-                    server_steps_done += 1.0;
-                    smart_cache_progress.set(
-                        ((server_steps_done / total_server_steps) * 60.0)
-                            .round()
-                            .clamp(0.0, 60.0) as u8,
-                    );
-
-                    let frequent_albums = client
-                        .get_albums("frequent", albums_per_server, 0)
-                        .await
-                        .unwrap_or_default();
-                    collect_album_cover_urls(
-                        &server,
-                        &frequent_albums,
-                        &mut cover_urls,
-                        &mut seen_cover_requests,
-                        artwork_limit,
-                        artwork_pref,
-                    );
-                    server_steps_done += 1.0;
-                    smart_cache_progress.set(
-                        ((server_steps_done / total_server_steps) * 60.0)
-                            .round()
-                            .clamp(0.0, 60.0) as u8,
-                    );
-
-                    for album in newest_albums
-                        .iter()
-                        .chain(frequent_albums.iter())
-                        .take(album_details_per_server)
-                    {
-                        if let Ok((_, songs)) = client.get_album(&album.id).await {
-                            warmed_album_details += 1;
-                            collect_song_cover_urls(
-                                &server,
-                                &songs,
-                                &mut cover_urls,
-                                &mut seen_cover_requests,
-                                artwork_limit,
-                                artwork_pref,
-                            );
-                            push_unique_songs(&mut collected_songs, &mut seen_song_keys, songs);
-                        }
-                        smart_cache_pause(20).await;
-                    }
-                    server_steps_done += 1.0;
-                    smart_cache_progress.set(
-                        ((server_steps_done / total_server_steps) * 60.0)
-                            .round()
-                            .clamp(0.0, 60.0) as u8,
-                    );
-
-                    let playlists = client.get_playlists().await.unwrap_or_default();
-                    collect_playlist_cover_urls(
-                        &server,
-                        &playlists,
-                        &mut cover_urls,
-                        &mut seen_cover_requests,
-                        artwork_limit,
-                        artwork_pref,
-                    );
-                    for playlist in playlists.iter().take(playlists_per_server) {
-                        if let Ok((_, songs)) = client.get_playlist(&playlist.id).await {
-                            warmed_playlists += 1;
-                            collect_song_cover_urls(
-                                &server,
-                                &songs,
-                                &mut cover_urls,
-                                &mut seen_cover_requests,
-                                artwork_limit,
-                                artwork_pref,
-                            );
-                            push_unique_songs(&mut collected_songs, &mut seen_song_keys, songs);
-                        }
-                        smart_cache_pause(20).await;
-                    }
-                    server_steps_done += 1.0;
-                    smart_cache_progress.set(
-                        ((server_steps_done / total_server_steps) * 60.0)
-                            .round()
-                            .clamp(0.0, 60.0) as u8,
-                    );
-
-                    if let Ok((_, starred_albums, starred_songs)) = client.get_starred().await {
-                        collect_album_cover_urls(
-                            &server,
-                            &starred_albums,
-                            &mut cover_urls,
-                            &mut seen_cover_requests,
-                            artwork_limit,
-                            artwork_pref,
-                        );
-                        collect_song_cover_urls(
-                            &server,
-                            &starred_songs,
-                            &mut cover_urls,
-                            &mut seen_cover_requests,
-                            artwork_limit,
-                            artwork_pref,
-                        );
-                        push_unique_songs(&mut collected_songs, &mut seen_song_keys, starred_songs);
-                    }
-                    server_steps_done += 1.0;
-                    smart_cache_progress.set(
-                        ((server_steps_done / total_server_steps) * 60.0)
-                            .round()
-                            .clamp(0.0, 60.0) as u8,
-                    );
-
-                    let random_songs = client
-                        .get_random_songs(random_songs_per_server)
-                        .await
-                        .unwrap_or_default();
-                    collect_song_cover_urls(
-                        &server,
-                        &random_songs,
-                        &mut cover_urls,
-                        &mut seen_cover_requests,
-                        artwork_limit,
-                        artwork_pref,
-                    );
-                    push_unique_songs(&mut collected_songs, &mut seen_song_keys, random_songs);
-                    server_steps_done += 1.0;
-                    smart_cache_progress.set(
-                        ((server_steps_done / total_server_steps) * 60.0)
-                            .round()
-                            .clamp(0.0, 60.0) as u8,
-                    );
-                }
-
-                let mut lyric_candidates = collected_songs.clone();
-                lyric_candidates.truncate(lyrics_limit);
-                let lyric_total = lyric_candidates.len();
-                if lyric_total > 0 {
-                    for (index, song) in lyric_candidates.into_iter().enumerate() {
-                        smart_cache_status.set(Some(format!(
-                            "Smart cache: warming lyrics ({}/{})...",
-                            index + 1,
-                            lyric_total
-                        )));
-                        let query = LyricsQuery::from_song(&song);
-                        if !query.title.trim().is_empty()
-                            && fetch_lyrics_with_fallback(&query, &provider_order, timeout_seconds)
-                                .await
-                                .is_ok()
-                        {
-                            warmed_lyrics += 1;
-                        }
-                        smart_cache_progress.set(
-                            (60.0 + ((index as f64 + 1.0) / lyric_total as f64) * 25.0)
-                                .round()
-                                .clamp(60.0, 85.0) as u8,
-                        );
-                        smart_cache_pause(35).await;
-                    }
-                } else {
-                    smart_cache_progress.set(85);
-                }
-
-                if cache_images_enabled {
-                    let unique_cover_urls = dedupe_trim_urls(cover_urls, artwork_limit);
-                    if !unique_cover_urls.is_empty() {
-                        smart_cache_status.set(Some(format!(
-                            "Smart cache: queueing artwork ({} URLs)...",
-                            unique_cover_urls.len()
-                        )));
-                        match warm_cover_art_urls(&unique_cover_urls) {
-                            Ok(queued) => queued_artwork = queued,
-                            Err(error) => smart_cache_status.set(Some(format!(
-                                "Smart cache: artwork prefetch warning: {}",
-                                error
-                            ))),
-                        }
-                    }
-                }
-
-                smart_cache_progress.set(100);
-                smart_cache_busy.set(false);
-                smart_cache_status.set(Some(format!(
-                    "Smart cache complete: {} songs, {} album details, {} playlists, {} lyrics, {} artwork requests queued (limit {}).",
-                    collected_songs.len(),
-                    warmed_album_details,
-                    warmed_playlists,
-                    warmed_lyrics,
-                    queued_artwork,
-                    artwork_limit
-                )));
+                smart_cache_status.set(Some(match warmup.await {
+                    Some(report) => report.summary(),
+                    None => "Smart cache warm-up cancelled.".to_string(),
+                }));
             });
         }
     };
+    let mut show_smart_cache_options = use_signal(|| false);
+    let on_smart_cache = move |_| {
+        if !smart_cache_busy {
+            show_smart_cache_options.set(true);
+        }
+    };
 
     let on_downloads_enabled_toggle = {
-        let mut app_settings = app_settings.clone();
         move |_| {
-            let mut settings = app_settings();
-            settings.downloads_enabled = !settings.downloads_enabled;
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            persist_settings_with_toast(
-                settings_clone,
-                saved_toast.clone(),
-                saved_toast_nonce.clone(),
-            );
+            settings_controller.update_settings(|settings| {
+                settings.downloads_enabled = !settings.downloads_enabled;
+            });
         }
     };
 
     let on_auto_downloads_enabled_toggle = {
-        let mut app_settings = app_settings.clone();
         let mut auto_download_status = auto_download_status.clone();
         move |_| {
-            let mut settings = app_settings();
-            let auto_enabled = !settings.auto_downloads_enabled;
-            settings.auto_downloads_enabled = auto_enabled;
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            persist_settings_with_toast(
-                settings_clone,
-                saved_toast.clone(),
-                saved_toast_nonce.clone(),
-            );
+            let auto_enabled = !app_settings.peek().auto_downloads_enabled;
+            settings_controller.update_settings(|settings| {
+                settings.auto_downloads_enabled = auto_enabled;
+            });
             if !auto_enabled {
                 let removed = prune_temporary_queue_prefetch_downloads(5);
                 if removed > 0 {
@@ -1509,123 +1526,118 @@ pub fn SettingsView() -> Element {
         }
     };
 
+    let on_pause_downloads_while_streaming_toggle = move |_| {
+        settings_controller.update_settings(|settings| {
+            settings.pause_downloads_while_streaming = !settings.pause_downloads_while_streaming;
+        });
+    };
+
     let on_auto_download_tier_change = {
-        let mut app_settings = app_settings.clone();
         move |e: Event<FormData>| {
             if let Ok(tier) = e.value().parse::<u8>() {
-                let mut settings = app_settings();
-                settings.auto_download_tier = tier.clamp(1, 3);
-                let settings_clone = settings.clone();
-                app_settings.set(settings);
-                persist_settings_with_toast(
-                    settings_clone,
-                    saved_toast.clone(),
-                    saved_toast_nonce.clone(),
-                );
+                settings_controller.update_settings(|settings| {
+                    settings.auto_download_tier = tier.clamp(1, 3);
+                });
             }
         }
     };
 
     let on_auto_download_album_count_change = {
-        let mut app_settings = app_settings.clone();
         move |e: Event<FormData>| {
             if let Ok(count) = e.value().parse::<u32>() {
-                let mut settings = app_settings();
-                settings.auto_download_album_count = count.clamp(0, 25);
-                let settings_clone = settings.clone();
-                app_settings.set(settings);
-                persist_settings_with_toast(
-                    settings_clone,
-                    saved_toast.clone(),
-                    saved_toast_nonce.clone(),
-                );
+                settings_controller.update_settings(|settings| {
+                    settings.auto_download_album_count = count.clamp(0, 25);
+                });
             }
         }
     };
 
     let on_auto_download_playlist_count_change = {
-        let mut app_settings = app_settings.clone();
         move |e: Event<FormData>| {
             if let Ok(count) = e.value().parse::<u32>() {
-                let mut settings = app_settings();
-                settings.auto_download_playlist_count = count.clamp(0, 25);
-                let settings_clone = settings.clone();
-                app_settings.set(settings);
-                persist_settings_with_toast(
-                    settings_clone,
-                    saved_toast.clone(),
-                    saved_toast_nonce.clone(),
-                );
+                settings_controller.update_settings(|settings| {
+                    settings.auto_download_playlist_count = count.clamp(0, 25);
+                });
+            }
+        }
+    };
+
+    let on_auto_download_min_rating_change = {
+        move |e: Event<FormData>| {
+            if let Ok(rating) = e.value().parse::<u8>() {
+                settings_controller.update_settings(|settings| {
+                    settings.auto_download_min_rating = rating.min(5);
+                });
             }
         }
     };
 
+    let on_auto_download_include_unrated_toggle = {
+        move |_| {
+            settings_controller.update_settings(|settings| {
+                settings.auto_download_include_unrated = !settings.auto_download_include_unrated;
+            });
+        }
+    };
+
     let on_download_limit_count_change = {
-        let mut app_settings = app_settings.clone();
         move |e: Event<FormData>| {
             if let Ok(limit) = e.value().parse::<u32>() {
-                let mut settings = app_settings();
-                settings.download_limit_count = limit.clamp(25, 20_000);
-                let settings_clone = settings.clone();
-                app_settings.set(settings);
-                persist_settings_with_toast(
-                    settings_clone,
-                    saved_toast.clone(),
-                    saved_toast_nonce.clone(),
-                );
+                settings_controller.update_settings(|settings| {
+                    settings.download_limit_count = limit.clamp(25, 20_000);
+                });
             }
         }
     };
 
     let on_download_limit_mb_change = {
-        let mut app_settings = app_settings.clone();
         move |e: Event<FormData>| {
             if let Ok(limit_mb) = e.value().parse::<u32>() {
-                let mut settings = app_settings();
-                settings.download_limit_mb = limit_mb.clamp(256, 131_072);
-                let settings_clone = settings.clone();
-                app_settings.set(settings);
-                persist_settings_with_toast(
-                    settings_clone,
-                    saved_toast.clone(),
-                    saved_toast_nonce.clone(),
-                );
+                settings_controller.update_settings(|settings| {
+                    settings.download_limit_mb = limit_mb.clamp(256, 131_072);
+                });
+            }
+        }
+    };
+
+    let on_download_cleanup_idle_days_change = {
+        move |e: Event<FormData>| {
+            if let Ok(days) = e.value().parse::<u32>() {
+                settings_controller.update_settings(|settings| {
+                    settings.download_cleanup_idle_days = days.min(3650);
+                });
+            }
+        }
+    };
+
+    let on_download_cleanup_trigger_change = {
+        move |e: Event<FormData>| {
+            if let Ok(percent) = e.value().parse::<u8>() {
+                settings_controller.update_settings(|settings| {
+                    settings.download_cleanup_trigger_percent = percent.clamp(50, 100);
+                });
             }
         }
     };
 
     let on_use_recommended_downloads = {
-        let mut app_settings = app_settings.clone();
         move |_| {
-            let mut settings = app_settings();
-            settings.downloads_enabled = true;
-            settings.auto_downloads_enabled = true;
-            settings.auto_download_album_count = 15;
-            settings.auto_download_playlist_count = 15;
-            settings.download_limit_count = 5000;
-            settings.download_limit_mb = 6000;
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            persist_settings_with_toast(
-                settings_clone,
-                saved_toast.clone(),
-                saved_toast_nonce.clone(),
-            );
+            settings_controller.update_settings(|settings| {
+                settings.downloads_enabled = true;
+                settings.auto_downloads_enabled = true;
+                settings.auto_download_album_count = 15;
+                settings.auto_download_playlist_count = 15;
+                settings.download_limit_count = 5000;
+                settings.download_limit_mb = 6000;
+            });
         }
     };
 
     let on_artwork_pref_change = {
-        let mut app_settings = app_settings.clone();
         move |e: Event<FormData>| {
-            let mut settings = app_settings();
-            settings.artwork_download_preference = parse_artwork_pref(&e.value());
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            persist_settings_with_toast(
-                settings_clone,
-                saved_toast.clone(),
-                saved_toast_nonce.clone(),
-            );
+            settings_controller.update_settings(|settings| {
+                settings.artwork_download_preference = parse_artwork_pref(&e.value());
+            });
         }
     };
 
@@ -1660,20 +1672,33 @@ pub fn SettingsView() -> Element {
                 return;
             }
 
+            if job_registry.running(AUTO_DOWNLOAD_JOB).is_some() {
+                auto_download_status.set(Some(
+                    "An auto-download pass is already running.".to_string(),
+                ));
+                return;
+            }
+
             let settings_snapshot = app_settings();
             auto_download_busy.set(true);
             auto_download_status.set(Some("Running auto-download pass...".to_string()));
+            let pass = job_registry.run_auto_download(active_servers, settings_snapshot);
             spawn(async move {
-                match run_auto_download_pass(&active_servers, &settings_snapshot).await {
-                    Ok(report) => {
+                match pass.await {
+                    Some(Ok(report)) => {
                         auto_download_status.set(Some(format!(
-                            "Auto-download complete: {} new, {} skipped, {} failed, {} purged.",
-                            report.downloaded, report.skipped, report.failed, report.purged
+                            "Auto-download complete: {} new, {} skipped, {} failed, {} purged{}.",
+                            report.downloaded,
+                            report.skipped,
+                            report.failed,
+                            report.purged,
+                            report.cleanup_summary()
                         )));
                     }
-                    Err(error) => {
+                    Some(Err(error)) => {
                         auto_download_status.set(Some(format!("Auto-download failed: {error}")));
                     }
+                    None => auto_download_status.set(Some("Auto-download cancelled.".to_string())),
                 }
                 download_refresh_nonce.with_mut(|nonce| *nonce = nonce.saturating_add(1));
                 auto_download_busy.set(false);
@@ -1699,14 +1724,22 @@ pub fn SettingsView() -> Element {
                 return;
             }
 
+            if job_registry.running(CACHE_REFRESH_JOB).is_some() {
+                auto_download_status.set(Some(
+                    "A downloaded cache refresh is already running.".to_string(),
+                ));
+                return;
+            }
+
             let settings_snapshot = app_settings();
             download_cache_refresh_busy.set(true);
             auto_download_status.set(Some(
                 "Refreshing downloaded cache (lyrics + artwork)...".to_string(),
             ));
+            let refresh = job_registry.run_cache_refresh(servers_snapshot, settings_snapshot);
             spawn(async move {
-                match refresh_downloaded_cache(&servers_snapshot, &settings_snapshot).await {
-                    Ok(report) => {
+                match refresh.await {
+                    Some(Ok(report)) => {
                         let missing_suffix = if report.missing_servers > 0 {
                             format!(" ({} missing server mappings)", report.missing_servers)
                         } else {
@@ -1721,9 +1754,10 @@ pub fn SettingsView() -> Element {
                             missing_suffix
                         )));
                     }
-                    Err(error) => {
+                    Some(Err(error)) => {
                         auto_download_status.set(Some(format!("Cache refresh failed: {error}")));
                     }
+                    None => auto_download_status.set(Some("Cache refresh cancelled.".to_string())),
                 }
                 download_refresh_nonce.with_mut(|nonce| *nonce = nonce.saturating_add(1));
                 download_cache_refresh_busy.set(false);
@@ -1732,96 +1766,54 @@ pub fn SettingsView() -> Element {
     };
 
     let on_lyrics_sync_toggle = {
-        let mut app_settings = app_settings.clone();
         move |_| {
-            let mut settings = app_settings();
-            settings.lyrics_unsynced_mode = !settings.lyrics_unsynced_mode;
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            persist_settings_with_toast(
-                settings_clone,
-                saved_toast.clone(),
-                saved_toast_nonce.clone(),
-            );
+            settings_controller.update_settings(|settings| {
+                settings.lyrics_unsynced_mode = !settings.lyrics_unsynced_mode;
+            });
         }
     };
 
     let on_lyrics_timeout_change = {
-        let mut app_settings = app_settings.clone();
         move |e: Event<FormData>| {
             if let Ok(timeout) = e.value().parse::<u32>() {
-                let mut settings = app_settings();
-                settings.lyrics_request_timeout_secs = timeout.clamp(1, 20);
-                let settings_clone = settings.clone();
-                app_settings.set(settings);
-                persist_settings_with_toast(
-                    settings_clone,
-                    saved_toast.clone(),
-                    saved_toast_nonce.clone(),
-                );
+                settings_controller.update_settings(|settings| {
+                    settings.lyrics_request_timeout_secs = timeout.clamp(1, 20);
+                });
             }
         }
     };
 
     let on_lyrics_offset_change = {
-        let mut app_settings = app_settings.clone();
         move |e: Event<FormData>| {
             if let Ok(offset) = e.value().parse::<i32>() {
-                let mut settings = app_settings();
-                settings.lyrics_offset_ms = offset.clamp(-5000, 5000);
-                let settings_clone = settings.clone();
-                app_settings.set(settings);
-                persist_settings_with_toast(
-                    settings_clone,
-                    saved_toast.clone(),
-                    saved_toast_nonce.clone(),
-                );
+                settings_controller.update_settings(|settings| {
+                    settings.lyrics_offset_ms = offset.clamp(-5000, 5000);
+                });
             }
         }
     };
 
     let on_lyrics_reset_offset = {
-        let mut app_settings = app_settings.clone();
         move |_| {
-            let mut settings = app_settings();
-            settings.lyrics_offset_ms = 0;
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            persist_settings_with_toast(
-                settings_clone,
-                saved_toast.clone(),
-                saved_toast_nonce.clone(),
-            );
+            settings_controller.update_settings(|settings| {
+                settings.lyrics_offset_ms = 0;
+            });
         }
     };
 
     let on_lyrics_screenshot_toggle = {
-        let mut app_settings = app_settings.clone();
         move |_| {
-            let mut settings = app_settings();
-            settings.lyrics_screenshot_mode = !settings.lyrics_screenshot_mode;
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            persist_settings_with_toast(
-                settings_clone,
-                saved_toast.clone(),
-                saved_toast_nonce.clone(),
-            );
+            settings_controller.update_settings(|settings| {
+                settings.lyrics_screenshot_mode = !settings.lyrics_screenshot_mode;
+            });
         }
     };
 
     let on_lyrics_screenshot_timestamps_toggle = {
-        let mut app_settings = app_settings.clone();
         move |_| {
-            let mut settings = app_settings();
-            settings.lyrics_screenshot_timestamps = !settings.lyrics_screenshot_timestamps;
-            let settings_clone = settings.clone();
-            app_settings.set(settings);
-            persist_settings_with_toast(
-                settings_clone,
-                saved_toast.clone(),
-                saved_toast_nonce.clone(),
-            );
+            settings_controller.update_settings(|settings| {
+                settings.lyrics_screenshot_timestamps = !settings.lyrics_screenshot_timestamps;
+            });
         }
     };
 
@@ -1937,6 +1929,14 @@ pub fn SettingsView() -> Element {
     let has_servers = !server_list.is_empty();
     let settings = app_settings();
     let current_volume = volume();
+    let replay_gain_preamp_label = format!("{:+.1} dB", settings.replay_gain_preamp_db);
+    let eq_gains = normalized_band_gains(&settings.eq_band_gains);
+    let eq_preset = matching_preset(&eq_gains);
+    let eq_bands: Vec<(usize, String, f32)> = eq_gains
+        .iter()
+        .enumerate()
+        .map(|(band, gain)| (band, band_label(EQ_BAND_FREQUENCIES_HZ[band]), *gain))
+        .collect();
     let lyrics_provider_order = normalize_lyrics_provider_order(&settings.lyrics_provider_order);
     let lyrics_sync_enabled = !settings.lyrics_unsynced_mode;
     let lyrics_screenshot_enabled = settings.lyrics_screenshot_mode;
@@ -1954,7 +1954,14 @@ pub fn SettingsView() -> Element {
         "Cache usage: {} entries | {:.1}MB / {:.1}MB ({:.0}% full)",
         cache_stats.entry_count, cache_used_mb, cache_max_mb, cache_usage_percent
     );
-    let smart_cache_percent = smart_cache_progress();
+    let smart_cache_percent = smart_cache_job
+        .and_then(|job| job.progress())
+        .map(|fraction| (fraction * 100.0).round() as u8)
+        .unwrap_or(0);
+    let smart_cache_status_line = match smart_cache_job {
+        Some(job) => job.detail(),
+        None => smart_cache_status(),
+    };
     let smart_cache_progress_style = format!("width: {}%", smart_cache_percent);
     let _download_refresh = download_refresh_nonce();
     let download_snapshot = download_stats();
@@ -1995,13 +2002,25 @@ pub fn SettingsView() -> Element {
                     "{status}"
                 }
             }
-            if let Some(message) = saved_toast() {
+            if let Some(message) = settings_controller.saved_toast() {
                 div { class: "fixed top-16 right-4 px-4 py-2 bg-cyan-500/20 border border-cyan-500/50 rounded-lg text-cyan-200 text-sm shadow-lg",
                     "{message}"
                 }
             }
 
             if has_servers {
+                if !settings.onboarding_completed {
+                    OnboardingSmartCacheCard {
+                        busy: smart_cache_busy,
+                        on_run: move |_: MouseEvent| {
+                            start_smart_cache(SmartCacheConfig::from_settings(&app_settings.peek()));
+                            complete_onboarding();
+                            active_tab.set("storage".to_string());
+                        },
+                        on_skip: move |_| complete_onboarding(),
+                    }
+                }
+
                 // Tab navigation
                 nav { class: "flex overflow-x-auto border-b border-zinc-700/50 -mx-1",
                     button {
@@ -2067,6 +2086,8 @@ pub fn SettingsView() -> Element {
                             for server in server_list.clone() {
                                 ServerCard {
                                     is_editing: editing_server().as_ref().map(|e| e.id == server.id).unwrap_or(false),
+                                    duplicate_of: find_duplicate_server(&server_list, &server.url, &server.username, Some(&server.id))
+                                        .map(|duplicate| duplicate.name),
                                     server: server.clone(),
                                     on_toggle: {
                                         let server_id = server.id.clone();
@@ -2120,10 +2141,10 @@ pub fn SettingsView() -> Element {
                         // Connection test result for existing servers
                         {
                             match connection_test_result() {
-                                Some(Ok(())) => rsx! {
+                                Some(Ok(identity)) => rsx! {
                                     div { class: "mt-4 flex items-center gap-2 text-emerald-400 text-sm",
                                         Icon { name: "check".to_string(), class: "w-4 h-4".to_string() }
-                                        "Connection test successful!"
+                                        "Connection test successful: {identity}"
                                     }
                                 },
                                 Some(Err(e)) => rsx! {
@@ -2138,6 +2159,31 @@ pub fn SettingsView() -> Element {
                     }
                 }
 
+                DuplicateServersSection {}
+
+                SongIdRemapSection {}
+
+                if server_list.len() >= 2 {
+                    section { class: "order-3 bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                        div { class: "flex items-center justify-between gap-4",
+                            div {
+                                h2 { class: "text-lg font-semibold text-white mb-1", "Compare Servers" }
+                                p { class: "text-sm text-zinc-400",
+                                    "Check that a migrated library is complete: lists albums missing from either server and albums with different track counts."
+                                }
+                            }
+                            button {
+                                class: "px-4 py-2 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors text-sm flex-shrink-0",
+                                onclick: move |_| show_server_compare.set(true),
+                                "Compare"
+                            }
+                        }
+                    }
+                }
+                if show_server_compare() {
+                    ServerCompareDialog { on_close: move |_| show_server_compare.set(false) }
+                }
+
                 // Add/Edit server form (kept at top so URL remains immediately visible)
                 section { class: "order-1 bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
                     h2 { class: "text-lg font-semibold text-white mb-4",
@@ -2230,13 +2276,46 @@ pub fn SettingsView() -> Element {
                             }
                         }
 
+                        // Client name reported to the server
+                        div {
+                            label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                "Client Name"
+                            }
+                            input {
+                                class: INPUT_FIELD_CLASS,
+                                placeholder: DEFAULT_CLIENT_NAME,
+                                value: server_client_name,
+                                oninput: move |e| {
+                                    server_client_name.set(e.value());
+                                    test_result.set(None);
+                                },
+                            }
+                            p { class: "text-xs text-zinc-500 mt-2",
+                                "Navidrome admins can set transcoding rules per player, keyed on this name. Leave blank to report \"{DEFAULT_CLIENT_NAME}\"."
+                            }
+                        }
+
+                        div { class: "flex items-center gap-3 p-3 bg-zinc-900/30 rounded-lg border border-zinc-700/30",
+                            div {
+                                p { class: "font-medium text-white text-sm", "Request raw streams" }
+                                p { class: "text-xs text-zinc-400",
+                                    "Ask for the original file instead of the server's transcode. Streaming quality, fast start and download quality still transcode when enabled."
+                                }
+                            }
+                            button {
+                                class: if server_raw_stream() { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors flex-shrink-0" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors flex-shrink-0" },
+                                onclick: move |_| server_raw_stream.set(!server_raw_stream()),
+                                div { class: if server_raw_stream() { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                            }
+                        }
+
                         // Test result
                         {
                             match test_result() {
-                                Some(Ok(())) => rsx! {
+                                Some(Ok(identity)) => rsx! {
                                     div { class: "flex items-center gap-2 text-emerald-400 text-sm",
                                         Icon { name: "check".to_string(), class: "w-4 h-4".to_string() }
-                                        "Connection successful!"
+                                        "Connection successful: {identity}"
                                     }
                                 },
                                 Some(Err(e)) => rsx! {
@@ -2249,6 +2328,12 @@ pub fn SettingsView() -> Element {
                             }
                         }
 
+                        if let Some(duplicate) = duplicate_server() {
+                            div { class: "text-xs text-amber-200/90 bg-amber-500/10 border border-amber-500/30 rounded-lg px-3 py-2",
+                                "\"{duplicate.name}\" already uses this URL and username. Edit that entry instead of adding a duplicate — duplicates fetch the same library twice."
+                            }
+                        }
+
                         // Buttons
                         div { class: "flex flex-col sm:flex-row gap-3 pt-2",
                             button {
@@ -2270,7 +2355,8 @@ pub fn SettingsView() -> Element {
                             }
                             if editing_server().is_some() {
                                 button {
-                                    class: "w-full sm:w-auto px-4 py-2 rounded-xl bg-emerald-500 hover:bg-emerald-400 text-white font-medium transition-colors flex items-center gap-2",
+                                    class: if duplicate_server().is_some() { "w-full sm:w-auto px-4 py-2 rounded-xl bg-zinc-700/50 text-zinc-500 cursor-not-allowed flex items-center gap-2" } else { "w-full sm:w-auto px-4 py-2 rounded-xl bg-emerald-500 hover:bg-emerald-400 text-white font-medium transition-colors flex items-center gap-2" },
+                                    disabled: duplicate_server().is_some(),
                                     onclick: on_save_edit,
                                     Icon {
                                         name: "check".to_string(),
@@ -2371,6 +2457,58 @@ pub fn SettingsView() -> Element {
                                     }
                                 }
                             }
+
+                            div { class: "flex items-center justify-between",
+                                div {
+                                    p { class: "font-medium text-white", "Crossfade When Skipping" }
+                                    p { class: "text-sm text-zinc-400",
+                                        "Also fade when you skip or pick another song, not only when one plays to its end"
+                                    }
+                                }
+                                button {
+                                    class: if settings.crossfade_on_skip { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors" },
+                                    onclick: on_crossfade_on_skip_toggle,
+                                    div { class: if settings.crossfade_on_skip { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                                }
+                            }
+                        }
+
+                        // Gapless toggle
+                        div { class: "flex items-center justify-between",
+                            div {
+                                p { class: "font-medium text-white", "Gapless Playback" }
+                                p { class: "text-sm text-zinc-400",
+                                    "Buffer the next song so albums play without a pause between tracks. Applies when shuffle, repeat one and crossfade are off"
+                                }
+                            }
+                            button {
+                                class: if settings.gapless_enabled { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors" },
+                                onclick: on_gapless_toggle,
+                                div { class: if settings.gapless_enabled { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                            }
+                        }
+
+                        // Plays per track for the "repeat N times" mode
+                        div {
+                            label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                "Repeat Count"
+                            }
+                            div { class: "flex items-center gap-4",
+                                input {
+                                    r#type: "range",
+                                    min: "2",
+                                    max: "20",
+                                    value: settings.repeat_count,
+                                    class: "flex-1 h-2 bg-zinc-700 rounded-lg appearance-none cursor-pointer accent-emerald-500",
+                                    oninput: on_repeat_count_change,
+                                }
+                                span { class: "text-sm text-zinc-400 w-16 text-right",
+                                    "{settings.repeat_count} times"
+                                }
+                            }
+                            p { class: "text-xs text-zinc-500 mt-2",
+                                "How often each track plays when the repeat button is set to N times, before moving on."
+                            }
                         }
 
                         // Replay Gain toggle
@@ -2385,93 +2523,642 @@ pub fn SettingsView() -> Element {
                                 div { class: if settings.replay_gain { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
                             }
                         }
-                    }
-                }
-
-                // Bookmark settings
-                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
-                    h2 { class: "text-lg font-semibold text-white mb-3", "Bookmark Settings" }
-                    p { class: "text-sm text-zinc-400 mb-5",
-                        "Bookmarks remember your listening position so you can quickly continue where you left off."
-                    }
 
-                    div { class: "space-y-5",
-                        div { class: "flex items-center justify-between",
+                        // ReplayGain mode (show only if ReplayGain is enabled)
+                        if settings.replay_gain {
                             div {
-                                p { class: "font-medium text-white", "Auto-save bookmarks" }
-                                p { class: "text-sm text-zinc-400",
-                                    "Automatically save playback position while listening and when switching songs."
+                                label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                    "Replay Gain Mode"
+                                }
+                                p { class: "text-xs text-zinc-500 mb-3",
+                                    "Track levels every song the same; album keeps the louder and quieter songs of an album as mixed. Songs without ReplayGain tags play at your normal volume."
+                                }
+                                select {
+                                    class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                    value: replay_gain_mode_key(settings.replay_gain_mode),
+                                    onchange: on_replay_gain_mode_change,
+                                    for (key , label) in [("track", "Track"), ("album", "Album")] {
+                                        option {
+                                            value: key,
+                                            selected: replay_gain_mode_key(settings.replay_gain_mode) == key,
+                                            "{label}"
+                                        }
+                                    }
                                 }
                             }
-                            button {
-                                class: if settings.bookmark_auto_save { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors" },
-                                onclick: on_bookmark_auto_save_toggle,
-                                div { class: if settings.bookmark_auto_save { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                            div {
+                                label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                    "Replay Gain Preamp"
+                                }
+                                div { class: "flex items-center gap-4",
+                                    input {
+                                        r#type: "range",
+                                        min: -REPLAY_GAIN_PREAMP_DB,
+                                        max: REPLAY_GAIN_PREAMP_DB,
+                                        step: "0.5",
+                                        value: settings.replay_gain_preamp_db,
+                                        class: "flex-1 h-2 bg-zinc-700 rounded-lg appearance-none cursor-pointer accent-emerald-500",
+                                        oninput: on_replay_gain_preamp_change,
+                                    }
+                                    span { class: "text-sm text-zinc-400 w-16 text-right",
+                                        "{replay_gain_preamp_label}"
+                                    }
+                                }
+                                p { class: "text-xs text-zinc-500 mt-2",
+                                    "Raises or lowers songs with ReplayGain tags, which are usually mastered quieter than untagged ones."
+                                }
+                            }
+                        }
+
+                        div {
+                            label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                "Streaming quality"
+                            }
+                            p { class: "text-xs text-zinc-500 mb-3",
+                                "Ask the server to transcode streams down to this bitrate to save data. Applies from the next track; downloads keep their own quality."
+                            }
+                            div { class: "flex flex-wrap gap-2",
+                                select {
+                                    class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                    value: "{settings.stream_max_bitrate_kbps}",
+                                    onchange: on_stream_bitrate_change,
+                                    for kbps in [0u32, 320, 192, 128, 96] {
+                                        option {
+                                            value: "{kbps}",
+                                            selected: settings.stream_max_bitrate_kbps == kbps,
+                                            if kbps == 0 {
+                                                "Original"
+                                            } else {
+                                                "{kbps} kbps"
+                                            }
+                                        }
+                                    }
+                                }
+                                if settings.stream_max_bitrate_kbps > 0 {
+                                    select {
+                                        class: "w-full max-w-[10rem] px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                        value: "{settings.stream_format}",
+                                        onchange: on_stream_format_change,
+                                        for format in STREAM_FORMATS {
+                                            option {
+                                                value: "{format}",
+                                                selected: settings.stream_format == format,
+                                                "{format.to_uppercase()}"
+                                            }
+                                        }
+                                    }
+                                }
                             }
                         }
 
+                        // Low-latency start toggle
                         div { class: "flex items-center justify-between",
                             div {
-                                p { class: "font-medium text-white", "Resume bookmark on launch" }
+                                p { class: "font-medium text-white", "Low-latency start" }
                                 p { class: "text-sm text-zinc-400",
-                                    "Automatically queue your latest bookmark when the app starts (loaded at position, paused)."
+                                    "Ask the server for a compressed transcode so large lossless files start instantly. Downloaded songs still play the original file."
                                 }
                             }
                             button {
-                                class: if settings.bookmark_autoplay_on_launch { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors" },
-                                onclick: on_bookmark_autoplay_toggle,
-                                div { class: if settings.bookmark_autoplay_on_launch { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                                class: if settings.fast_start_enabled { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors" },
+                                onclick: on_fast_start_toggle,
+                                div { class: if settings.fast_start_enabled { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                            }
+                        }
+
+                        if settings.fast_start_enabled {
+                            div {
+                                label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                    "Fast-start bitrate"
+                                }
+                                p { class: "text-xs text-zinc-500 mb-3",
+                                    "Time-to-first-audio is logged as playback.start in diagnostics so you can compare settings."
+                                }
+                                select {
+                                    class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                    value: "{settings.fast_start_bitrate_kbps}",
+                                    onchange: on_fast_start_bitrate_change,
+                                    for kbps in [64u32, 96, 128, 192, 256, 320] {
+                                        option {
+                                            value: "{kbps}",
+                                            selected: settings.fast_start_bitrate_kbps == kbps,
+                                            "{kbps} kbps"
+                                        }
+                                    }
+                                }
                             }
                         }
 
                         div {
                             label { class: "block text-sm font-medium text-zinc-400 mb-2",
-                                "Bookmark Limit"
+                                "Buffering"
                             }
                             p { class: "text-xs text-zinc-500 mb-3",
-                                "Keep only the newest bookmarks per server user. Oldest bookmarks are deleted when this limit is exceeded."
+                                "Aggressive buffers the whole track and fetches upcoming tracks in full, which helps on connections that keep dropping. Minimal only loads what is needed to start playing, for metered connections."
                             }
-                            input {
-                                r#type: "number",
-                                min: "1",
-                                max: "5000",
-                                value: settings.bookmark_limit,
+                            select {
                                 class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
-                                onchange: on_bookmark_limit_change,
+                                value: buffering_strategy_key(settings.buffering_strategy),
+                                onchange: on_buffering_strategy_change,
+                                for (key , label) in [
+                                    ("aggressive", "Aggressive"),
+                                    ("balanced", "Balanced"),
+                                    ("minimal", "Minimal"),
+                                ]
+                                {
+                                    option {
+                                        value: key,
+                                        selected: buffering_strategy_key(settings.buffering_strategy) == key,
+                                        "{label}"
+                                    }
+                                }
                             }
                         }
-                    }
-                }
-
-                } // end playback tab
 
-                if active_tab() == "playback" {
-                // Offline Mode
-                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
-                    h2 { class: "text-lg font-semibold text-white mb-3", "Offline Mode" }
-                    p { class: "text-sm text-zinc-400 mb-5",
-                        "When enabled, RustySound uses downloaded and cached content only. New network requests are blocked until you turn it off."
-                    }
-                    div { class: "flex items-center justify-between",
-                        div {
-                            p { class: "font-medium text-white", "Use offline content only" }
-                            p { class: "text-sm text-zinc-400",
-                                "Disable this to return to live server access."
+                        if settings.buffering_strategy == BufferingStrategy::Aggressive {
+                            div {
+                                label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                    "Fetch-ahead limit"
+                                }
+                                p { class: "text-xs text-zinc-500 mb-3",
+                                    "Tracks larger than this are streamed normally instead of being fetched in full."
+                                }
+                                select {
+                                    class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                    value: "{settings.fetch_ahead_limit_mb}",
+                                    onchange: on_fetch_ahead_limit_change,
+                                    for limit_mb in [16u32, 32, 64, 128, 256] {
+                                        option {
+                                            value: "{limit_mb}",
+                                            selected: settings.fetch_ahead_limit_mb == limit_mb,
+                                            "{limit_mb} MB"
+                                        }
+                                    }
+                                }
                             }
                         }
-                        button {
-                            class: if settings.offline_mode { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors" },
-                            onclick: on_offline_mode_toggle,
-                            div { class: if settings.offline_mode { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
-                        }
-                    }
-                }
-                } // end offline mode tabs
 
-                if active_tab() == "storage" {
-                // Cache settings
-                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
-                    div { class: "flex items-center justify-between gap-3 mb-3",
+                        div { class: "flex items-center justify-between",
+                            div {
+                                p { class: "font-medium text-white", "Pause when headphones disconnect" }
+                                p { class: "text-sm text-zinc-400",
+                                    "Stop playback instead of switching to the speakers when headphones are unplugged or a Bluetooth device drops."
+                                }
+                            }
+                            button {
+                                class: if settings.pause_on_unplug { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors" },
+                                onclick: on_pause_on_unplug_toggle,
+                                div { class: if settings.pause_on_unplug { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                            }
+                        }
+
+                        div { class: "flex items-center justify-between",
+                            div {
+                                p { class: "font-medium text-white", "Quiet hours" }
+                                p { class: "text-sm text-zinc-400",
+                                    "Cap the volume on a schedule, using this device's clock. The moon in the player lifts the cap for 30 minutes."
+                                }
+                            }
+                            button {
+                                class: if settings.quiet_hours.enabled { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors" },
+                                onclick: on_quiet_hours_toggle,
+                                div { class: if settings.quiet_hours.enabled { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                            }
+                        }
+
+                        if settings.quiet_hours.enabled {
+                            div { class: "space-y-4",
+                                div { class: "flex flex-wrap gap-4",
+                                    div {
+                                        label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                            "From"
+                                        }
+                                        input {
+                                            r#type: "time",
+                                            value: minute_label(settings.quiet_hours.start_minute),
+                                            class: "px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                            onchange: on_quiet_hours_start_change,
+                                        }
+                                    }
+                                    div {
+                                        label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                            "Until"
+                                        }
+                                        input {
+                                            r#type: "time",
+                                            value: minute_label(settings.quiet_hours.end_minute),
+                                            class: "px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                            onchange: on_quiet_hours_end_change,
+                                        }
+                                    }
+                                }
+                                div {
+                                    label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                        "Nights starting on"
+                                    }
+                                    div { class: "flex flex-wrap gap-2",
+                                        for (day , label) in QUIET_HOURS_DAY_LABELS.iter().enumerate() {
+                                            button {
+                                                key: "{day}",
+                                                class: if settings.quiet_hours.days[day] { "px-3 py-1.5 rounded-lg text-sm bg-emerald-500/20 text-emerald-300 border border-emerald-500/40" } else { "px-3 py-1.5 rounded-lg text-sm bg-zinc-900 text-zinc-400 border border-zinc-700 hover:text-white" },
+                                                aria_pressed: settings.quiet_hours.days[day],
+                                                onclick: move |_| {
+                                                    settings_controller.update_settings(|settings| {
+                                                        settings.quiet_hours.days[day] = !settings.quiet_hours.days[day];
+                                                    });
+                                                },
+                                                "{label}"
+                                            }
+                                        }
+                                    }
+                                    p { class: "text-xs text-zinc-500 mt-2",
+                                        "A window that runs past midnight belongs to the day it starts on. Set the same start and end time to cover whole days."
+                                    }
+                                }
+                                div {
+                                    label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                        "Volume cap"
+                                    }
+                                    div { class: "flex items-center gap-4",
+                                        input {
+                                            r#type: "range",
+                                            min: "5",
+                                            max: "100",
+                                            value: settings.quiet_hours.cap_percent,
+                                            class: "flex-1 h-2 bg-zinc-700 rounded-lg appearance-none cursor-pointer accent-emerald-500",
+                                            oninput: on_quiet_hours_cap_change,
+                                        }
+                                        span { class: "text-sm text-zinc-400 w-12 text-right",
+                                            "{settings.quiet_hours.cap_percent}%"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        div { class: "flex items-center justify-between",
+                            div {
+                                p { class: "font-medium text-white", "Session summaries" }
+                                p { class: "text-sm text-zinc-400",
+                                    "Keep a local history of finished and skipped songs and show a recap of your last listening session when the app opens. The history stays on this device."
+                                }
+                            }
+                            button {
+                                class: if settings.session_summary_enabled { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors" },
+                                onclick: on_session_summary_toggle,
+                                div { class: if settings.session_summary_enabled { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                            }
+                        }
+
+                        div {
+                            label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                "Songs you usually skip"
+                            }
+                            p { class: "text-xs text-zinc-500 mb-3",
+                                "What shuffle does with songs skipped in most of their plays. Skips are only counted while session summaries are on."
+                            }
+                            select {
+                                class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                value: skipped_song_shuffle_key(settings.skipped_song_shuffle),
+                                onchange: on_skipped_song_shuffle_change,
+                                for (key , label) in [
+                                    ("off", "Shuffle normally"),
+                                    ("down_weight", "Play them less often"),
+                                    ("exclude", "Leave them out"),
+                                ]
+                                {
+                                    option {
+                                        value: key,
+                                        selected: skipped_song_shuffle_key(settings.skipped_song_shuffle) == key,
+                                        "{label}"
+                                    }
+                                }
+                            }
+                        }
+
+                        if settings.skipped_song_shuffle != SkippedSongShuffle::Off {
+                            div { class: "flex flex-wrap gap-4",
+                                div {
+                                    label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                        "Skipped at least"
+                                    }
+                                    select {
+                                        class: "px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                        value: "{settings.skip_ratio_threshold_percent}",
+                                        onchange: on_skip_ratio_threshold_change,
+                                        for percent in [50u8, 60, 75, 90, 100] {
+                                            option {
+                                                value: "{percent}",
+                                                selected: settings.skip_ratio_threshold_percent == percent,
+                                                "{percent}% of plays"
+                                            }
+                                        }
+                                    }
+                                }
+                                div {
+                                    label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                        "After at least"
+                                    }
+                                    select {
+                                        class: "px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                        value: "{settings.skip_min_plays}",
+                                        onchange: on_skip_min_plays_change,
+                                        for plays in [3u32, 5, 10, 20] {
+                                            option {
+                                                value: "{plays}",
+                                                selected: settings.skip_min_plays == plays,
+                                                "{plays} plays"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        div {
+                            label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                "Shuffle favorites"
+                            }
+                            p { class: "text-xs text-zinc-500 mb-3",
+                                "Most songs queued by the Shuffle favorites button. Songs played in the last day go to the end."
+                            }
+                            select {
+                                class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                value: "{settings.favorites_shuffle_limit}",
+                                onchange: on_favorites_shuffle_limit_change,
+                                for limit in [50usize, 100, 200, 500] {
+                                    option {
+                                        value: "{limit}",
+                                        selected: settings.favorites_shuffle_limit == limit,
+                                        "{limit} songs"
+                                    }
+                                }
+                            }
+                        }
+
+                        if !cfg!(target_arch = "wasm32") {
+                            div {
+                                label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                    "Progress polling"
+                                }
+                                p { class: "text-xs text-zinc-500 mb-3",
+                                    "How often the player position is checked while playing. Polling slows to once a second while paused or when the window is hidden."
+                                }
+                                select {
+                                    class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                    value: "{settings.progress_poll_interval_ms}",
+                                    onchange: on_progress_poll_interval_change,
+                                    for interval_ms in [100u32, 250, 500, 1000] {
+                                        option {
+                                            value: "{interval_ms}",
+                                            selected: settings.progress_poll_interval_ms == interval_ms,
+                                            "{interval_ms} ms"
+                                        }
+                                    }
+                                }
+                            }
+                            div { class: "flex items-center justify-between",
+                                div {
+                                    p { class: "font-medium text-white", "Smooth progress updates" }
+                                    p { class: "text-sm text-zinc-400",
+                                        "Redraw the seek bar on every poll instead of once per second. Uses more CPU."
+                                    }
+                                }
+                                button {
+                                    class: if settings.smooth_progress_updates { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors" },
+                                    onclick: on_smooth_progress_toggle,
+                                    div { class: if settings.smooth_progress_updates { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                                }
+                            }
+                        }
+
+                        if cfg!(feature = "desktop") {
+                            div {
+                                label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                    "Keep awake while playing"
+                                }
+                                p { class: "text-xs text-zinc-500 mb-3",
+                                    "Stops the computer from suspending (and optionally blanking the screen) mid-album. Released as soon as playback pauses."
+                                }
+                                select {
+                                    class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                    value: sleep_inhibit_key(settings.sleep_inhibit_mode),
+                                    onchange: on_sleep_inhibit_change,
+                                    for (key , label) in [
+                                        ("never", "Never"),
+                                        ("system_sleep", "Prevent system sleep"),
+                                        ("system_and_display", "Prevent sleep and screen blanking"),
+                                    ]
+                                    {
+                                        option {
+                                            value: key,
+                                            selected: sleep_inhibit_key(settings.sleep_inhibit_mode) == key,
+                                            "{label}"
+                                        }
+                                    }
+                                }
+                            }
+                            div {
+                                label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                    "Taskbar playback status"
+                                }
+                                p { class: "text-xs text-zinc-500 mb-3",
+                                    "Shows the loaded song in the window title. The badge option also adds a play overlay on the Windows taskbar or a badge on the macOS dock, and shows playback progress on the Windows taskbar and Linux launchers that support it."
+                                }
+                                select {
+                                    class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                    value: playback_indicator_key(settings.playback_indicator_mode),
+                                    onchange: on_playback_indicator_change,
+                                    for (key , label) in [
+                                        ("off", "Off"),
+                                        ("window_title", "Window title"),
+                                        ("title_and_badge", "Window title, badge and progress"),
+                                    ]
+                                    {
+                                        option {
+                                            value: key,
+                                            selected: playback_indicator_key(settings.playback_indicator_mode) == key,
+                                            "{label}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        div {
+                            label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                "Radio slogan filter"
+                            }
+                            p { class: "text-xs text-zinc-500 mb-3",
+                                "Stream titles containing any of these phrases (one per line) show the station name instead of a song."
+                            }
+                            textarea {
+                                class: "w-full max-w-md min-h-[120px] px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-zinc-200 text-xs font-mono leading-relaxed focus:outline-none focus:border-emerald-500/50",
+                                value: settings.radio_slogan_patterns.join("\n"),
+                                onchange: on_radio_slogans_change,
+                            }
+                            button {
+                                class: "mt-2 px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors text-xs",
+                                onclick: on_reset_radio_slogans,
+                                "Reset to defaults"
+                            }
+                        }
+                    }
+                }
+
+                if cfg!(target_arch = "wasm32") {
+                    section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                        h2 { class: "text-lg font-semibold text-white mb-3", "Audio" }
+                        p { class: "text-sm text-zinc-400 mb-5",
+                            "Shape the sound with a five-band equalizer. Turning it on takes effect from the next song, and needs your server to allow cross-origin streaming."
+                        }
+
+                        div { class: "space-y-5",
+                            div { class: "flex items-center justify-between",
+                                div {
+                                    p { class: "font-medium text-white", "Equalizer" }
+                                    p { class: "text-sm text-zinc-400", "Off plays every song as it is" }
+                                }
+                                button {
+                                    class: if settings.eq_enabled { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors" },
+                                    onclick: on_eq_toggle,
+                                    div { class: if settings.eq_enabled { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                                }
+                            }
+
+                            if settings.eq_enabled {
+                                div { class: "flex flex-wrap gap-2",
+                                    for (name , gains) in EQ_PRESETS {
+                                        button {
+                                            class: if eq_preset == Some(name) { "px-3 py-1.5 rounded-full bg-emerald-500/20 text-emerald-400 text-sm font-medium" } else { "px-3 py-1.5 rounded-full bg-zinc-800/50 text-zinc-400 hover:text-white text-sm font-medium transition-colors" },
+                                            onclick: move |_| {
+                                                settings_controller.update_settings(|settings| {
+                                                    settings.eq_band_gains = gains.to_vec();
+                                                });
+                                            },
+                                            "{name}"
+                                        }
+                                    }
+                                }
+                                div { class: "space-y-3",
+                                    for (band , label , gain) in eq_bands {
+                                        div { class: "flex items-center gap-4",
+                                            span { class: "text-sm text-zinc-400 w-16",
+                                                "{label}"
+                                            }
+                                            input {
+                                                r#type: "range",
+                                                min: f64::from(-EQ_GAIN_LIMIT_DB),
+                                                max: f64::from(EQ_GAIN_LIMIT_DB),
+                                                step: "0.5",
+                                                value: f64::from(gain),
+                                                class: "flex-1 h-2 bg-zinc-700 rounded-lg appearance-none cursor-pointer accent-emerald-500",
+                                                oninput: move |e: Event<FormData>| {
+                                                    if let Ok(gain) = e.value().parse::<f32>() {
+                                                        set_eq_band(band, gain);
+                                                    }
+                                                },
+                                            }
+                                            span { class: "text-sm text-zinc-400 w-16 text-right",
+                                                "{gain:+.1} dB"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Bookmark settings
+                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                    h2 { class: "text-lg font-semibold text-white mb-3", "Bookmark Settings" }
+                    p { class: "text-sm text-zinc-400 mb-5",
+                        "Bookmarks remember your listening position so you can quickly continue where you left off."
+                    }
+
+                    div { class: "space-y-5",
+                        div { class: "flex items-center justify-between",
+                            div {
+                                p { class: "font-medium text-white", "Auto-save bookmarks" }
+                                p { class: "text-sm text-zinc-400",
+                                    "Automatically save playback position while listening and when switching songs."
+                                }
+                            }
+                            button {
+                                class: if settings.bookmark_auto_save { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors" },
+                                onclick: on_bookmark_auto_save_toggle,
+                                div { class: if settings.bookmark_auto_save { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                            }
+                        }
+
+                        div { class: "flex items-center justify-between",
+                            div {
+                                p { class: "font-medium text-white", "Resume bookmark on launch" }
+                                p { class: "text-sm text-zinc-400",
+                                    "Automatically queue your latest bookmark when the app starts (loaded at position, paused)."
+                                }
+                            }
+                            button {
+                                class: if settings.bookmark_autoplay_on_launch { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors" },
+                                onclick: on_bookmark_autoplay_toggle,
+                                div { class: if settings.bookmark_autoplay_on_launch { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                            }
+                        }
+
+                        div {
+                            label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                "Bookmark Limit"
+                            }
+                            p { class: "text-xs text-zinc-500 mb-3",
+                                "Keep only the newest bookmarks per server user. Oldest bookmarks are deleted when this limit is exceeded."
+                            }
+                            input {
+                                r#type: "number",
+                                min: "1",
+                                max: "5000",
+                                value: settings.bookmark_limit,
+                                class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                onchange: on_bookmark_limit_change,
+                            }
+                        }
+                    }
+                }
+
+                } // end playback tab
+
+                if active_tab() == "playback" {
+                // Offline Mode
+                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                    h2 { class: "text-lg font-semibold text-white mb-3", "Offline Mode" }
+                    p { class: "text-sm text-zinc-400 mb-5",
+                        "When enabled, RustySound uses downloaded and cached content only. New network requests are blocked until you turn it off."
+                    }
+                    div { class: "flex items-center justify-between",
+                        div {
+                            p { class: "font-medium text-white", "Use offline content only" }
+                            p { class: "text-sm text-zinc-400",
+                                "Disable this to return to live server access."
+                            }
+                        }
+                        button {
+                            class: if settings.offline_mode { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors" },
+                            onclick: on_offline_mode_toggle,
+                            div { class: if settings.offline_mode { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                        }
+                    }
+                    div { class: "mt-6 pt-5 border-t border-zinc-700/40",
+                        PendingSyncSettings {}
+                    }
+                }
+                } // end offline mode tabs
+
+                if active_tab() == "storage" {
+                SettingsProfilesSection {}
+
+                // Cache settings
+                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                    div { class: "flex items-center justify-between gap-3 mb-3",
                         h2 { class: "text-lg font-semibold text-white", "Cache" }
                         button {
                             class: "px-3 py-2 rounded-lg border border-emerald-500/40 text-emerald-300 hover:text-white hover:border-emerald-400/70 transition-colors text-sm",
@@ -2566,24 +3253,34 @@ pub fn SettingsView() -> Element {
                                 div {
                                     p { class: "font-medium text-white", "Smart Cache Warm-up" }
                                     p { class: "text-sm text-zinc-400",
-                                        "Prefetch albums, songs, playlists, lyrics, and queue artwork with request throttling."
+                                        "Prefetch albums, songs, playlists, lyrics, and queue artwork with request throttling. Choose what to warm before each run."
                                     }
                                 }
                                 button {
-                                    class: if smart_cache_busy() { "px-3 py-2 rounded-lg border border-zinc-700 text-zinc-400 cursor-not-allowed text-sm" } else { "px-3 py-2 rounded-lg border border-emerald-500/40 text-emerald-300 hover:text-white hover:border-emerald-400/70 transition-colors text-sm" },
-                                    disabled: smart_cache_busy(),
+                                    class: if smart_cache_busy { "px-3 py-2 rounded-lg border border-zinc-700 text-zinc-400 cursor-not-allowed text-sm" } else { "px-3 py-2 rounded-lg border border-emerald-500/40 text-emerald-300 hover:text-white hover:border-emerald-400/70 transition-colors text-sm" },
+                                    disabled: smart_cache_busy,
                                     onclick: on_smart_cache,
-                                    if smart_cache_busy() {
+                                    if smart_cache_busy {
                                         "Warming..."
                                     } else {
                                         "Run Smart Cache"
                                     }
                                 }
                             }
-                            if let Some(status) = smart_cache_status() {
+                            if show_smart_cache_options() {
+                                SmartCacheOptionsSheet {
+                                    defaults: SmartCacheConfig::from_settings(&settings),
+                                    on_start: move |config: SmartCacheConfig| {
+                                        show_smart_cache_options.set(false);
+                                        start_smart_cache(config);
+                                    },
+                                    on_close: move |_| show_smart_cache_options.set(false),
+                                }
+                            }
+                            if let Some(status) = smart_cache_status_line {
                                 p { class: "text-xs text-zinc-500", "{status}" }
                             }
-                            if smart_cache_busy() {
+                            if smart_cache_busy {
                                 div { class: "w-full h-2 rounded-full bg-zinc-700/70 overflow-hidden",
                                     div {
                                         class: "h-full bg-emerald-500/80 transition-all",
@@ -2649,6 +3346,20 @@ pub fn SettingsView() -> Element {
                             }
                         }
 
+                        div { class: "flex items-center justify-between",
+                            div {
+                                p { class: "font-medium text-white", "Pause downloads while streaming" }
+                                p { class: "text-sm text-zinc-400",
+                                    "Hold background downloads while a song or radio station streams from the server, and resume when playback pauses. Keeps playback smooth on slow connections."
+                                }
+                            }
+                            button {
+                                class: if settings.pause_downloads_while_streaming { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors" },
+                                onclick: on_pause_downloads_while_streaming_toggle,
+                                div { class: if settings.pause_downloads_while_streaming { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                            }
+                        }
+
                         div { class: "grid grid-cols-1 md:grid-cols-2 gap-4",
                             div {
                                 label { class: "block text-sm font-medium text-zinc-400 mb-2",
@@ -2714,6 +3425,47 @@ pub fn SettingsView() -> Element {
                             }
                         }
 
+                        div { class: "grid grid-cols-1 md:grid-cols-2 gap-4",
+                            div {
+                                label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                    "Minimum rating"
+                                }
+                                select {
+                                    class: "w-full px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                    value: settings.auto_download_min_rating.to_string(),
+                                    oninput: on_auto_download_min_rating_change,
+                                    option { value: "0", "Any rating" }
+                                    for stars in 1u8..=5 {
+                                        option { value: "{stars}",
+                                            if stars == 5 {
+                                                "5 stars only"
+                                            } else {
+                                                "{stars}+ stars"
+                                            }
+                                        }
+                                    }
+                                }
+                                p { class: "text-xs text-zinc-500 mt-2",
+                                    "Auto-download skips songs rated below this."
+                                }
+                            }
+                            if settings.auto_download_min_rating > 0 {
+                                div { class: "flex items-center justify-between gap-3",
+                                    div {
+                                        p { class: "text-sm font-medium text-zinc-300", "Include unrated songs" }
+                                        p { class: "text-xs text-zinc-500",
+                                            "Off keeps only songs you have rated."
+                                        }
+                                    }
+                                    button {
+                                        class: if settings.auto_download_include_unrated { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors flex-shrink-0" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors flex-shrink-0" },
+                                        onclick: on_auto_download_include_unrated_toggle,
+                                        div { class: if settings.auto_download_include_unrated { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                                    }
+                                }
+                            }
+                        }
+
                         div { class: "grid grid-cols-1 md:grid-cols-2 gap-4",
                             div {
                                 label { class: "block text-sm font-medium text-zinc-400 mb-2",
@@ -2743,6 +3495,41 @@ pub fn SettingsView() -> Element {
                             }
                         }
 
+                        div { class: "space-y-2",
+                            div { class: "grid grid-cols-1 md:grid-cols-2 gap-4",
+                                div {
+                                    label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                        "Remove downloads not played in (days)"
+                                    }
+                                    input {
+                                        r#type: "number",
+                                        min: "0",
+                                        max: "3650",
+                                        value: settings.download_cleanup_idle_days,
+                                        class: "w-full px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                        onchange: on_download_cleanup_idle_days_change,
+                                    }
+                                }
+                                div {
+                                    label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                        "Only once downloads pass (% of MB limit)"
+                                    }
+                                    input {
+                                        r#type: "number",
+                                        min: "50",
+                                        max: "100",
+                                        value: settings.download_cleanup_trigger_percent,
+                                        disabled: settings.download_cleanup_idle_days == 0,
+                                        class: "w-full px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50 disabled:opacity-50",
+                                        onchange: on_download_cleanup_trigger_change,
+                                    }
+                                }
+                            }
+                            p { class: "text-xs text-zinc-500",
+                                "Runs with auto-download; 0 days turns it off. Favorites and pinned downloads are kept. Plays count from the local listening history, otherwise from when the song was downloaded."
+                            }
+                        }
+
                         div { class: "space-y-2",
                             p { class: "text-xs text-zinc-500", "{download_usage_label}" }
                             div { class: "h-2 w-full rounded-full bg-zinc-700/70 overflow-hidden",
@@ -2795,6 +3582,11 @@ pub fn SettingsView() -> Element {
                     p { class: "text-sm text-zinc-400 mb-5",
                         "Configure provider priority, lookup timeout, and sync behavior for the song menu lyrics panel. Changes are auto-saved."
                     }
+                    if settings.strict_privacy_mode {
+                        p { class: "text-xs text-amber-300 mb-5",
+                            "Unavailable while strict privacy mode is on: all lyrics providers are outside your servers. Previously cached lyrics still show."
+                        }
+                    }
                     p { class: "text-xs text-zinc-500 mb-5",
                         "Web note: browser CORS blocks direct Netease and Genius requests in web builds. Keep LRCLIB first on web. Desktop supports all providers."
                     }
@@ -2899,27 +3691,20 @@ pub fn SettingsView() -> Element {
                                             disabled: index == 0,
                                             onclick: {
                                                 let provider = provider.clone();
-                                                let mut app_settings = app_settings.clone();
                                                 move |_| {
-                                                    let mut settings = app_settings();
-                                                    let mut order = normalize_lyrics_provider_order(
-                                                        &settings.lyrics_provider_order,
-                                                    );
-                                                    if let Some(position) =
-                                                        order.iter().position(|entry| entry == &provider)
-                                                    {
-                                                        if position > 0 {
-                                                            order.swap(position, position - 1);
-                                                            settings.lyrics_provider_order = order;
-                                                            let settings_clone = settings.clone();
-                                                            app_settings.set(settings);
-                                                            persist_settings_with_toast(
-                                                                settings_clone,
-                                                                saved_toast.clone(),
-                                                                saved_toast_nonce.clone(),
-                                                            );
+                                                    settings_controller.update_settings(|settings| {
+                                                        let mut order = normalize_lyrics_provider_order(
+                                                            &settings.lyrics_provider_order,
+                                                        );
+                                                        if let Some(position) =
+                                                            order.iter().position(|entry| entry == &provider)
+                                                        {
+                                                            if position > 0 {
+                                                                order.swap(position, position - 1);
+                                                                settings.lyrics_provider_order = order;
+                                                            }
                                                         }
-                                                    }
+                                                    });
                                                 }
                                             },
                                             "Up"
@@ -2929,27 +3714,20 @@ pub fn SettingsView() -> Element {
                                             disabled: index + 1 >= lyrics_provider_order.len(),
                                             onclick: {
                                                 let provider = provider.clone();
-                                                let mut app_settings = app_settings.clone();
                                                 move |_| {
-                                                    let mut settings = app_settings();
-                                                    let mut order = normalize_lyrics_provider_order(
-                                                        &settings.lyrics_provider_order,
-                                                    );
-                                                    if let Some(position) =
-                                                        order.iter().position(|entry| entry == &provider)
-                                                    {
-                                                        if position + 1 < order.len() {
-                                                            order.swap(position, position + 1);
-                                                            settings.lyrics_provider_order = order;
-                                                            let settings_clone = settings.clone();
-                                                            app_settings.set(settings);
-                                                            persist_settings_with_toast(
-                                                                settings_clone,
-                                                                saved_toast.clone(),
-                                                                saved_toast_nonce.clone(),
-                                                            );
+                                                    settings_controller.update_settings(|settings| {
+                                                        let mut order = normalize_lyrics_provider_order(
+                                                            &settings.lyrics_provider_order,
+                                                        );
+                                                        if let Some(position) =
+                                                            order.iter().position(|entry| entry == &provider)
+                                                        {
+                                                            if position + 1 < order.len() {
+                                                                order.swap(position, position + 1);
+                                                                settings.lyrics_provider_order = order;
+                                                            }
                                                         }
-                                                    }
+                                                    });
                                                 }
                                             },
                                             "Down"
@@ -3031,6 +3809,14 @@ pub fn SettingsView() -> Element {
                                                 if let Some(elapsed) = entry.status.seconds_elapsed {
                                                     span { class: "text-xs text-zinc-500", "Elapsed: {elapsed}s" }
                                                 }
+                                                if let Some(last_scan) = entry
+                                                    .status
+                                                    .last_scan
+                                                    .as_deref()
+                                                    .and_then(|value| humanize_server_timestamp_for_display(value, &settings))
+                                                {
+                                                    span { class: "text-xs text-zinc-500", "Last scan: {last_scan}" }
+                                                }
                                             }
                                         }
                                     }
@@ -3040,6 +3826,110 @@ pub fn SettingsView() -> Element {
                     }
                 }
 
+                // Negative cache section
+                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                    h2 { class: "text-lg font-semibold text-white mb-3", "Remember Missing Content" }
+                    div { class: "space-y-4",
+                        div { class: "flex items-center justify-between gap-4",
+                            div {
+                                p { class: "font-medium text-white", "Cache \"not found\" results" }
+                                p { class: "text-sm text-zinc-400",
+                                    "Skip re-fetching lyrics and cover art that were missing within the last hour. Requires the cache to be enabled."
+                                }
+                            }
+                            button {
+                                class: if settings.negative_cache_enabled { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors flex-shrink-0" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors flex-shrink-0" },
+                                onclick: on_negative_cache_toggle,
+                                div { class: if settings.negative_cache_enabled { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                            }
+                        }
+                        div { class: "flex flex-wrap items-center gap-3",
+                            button {
+                                class: "px-4 py-2 rounded-xl bg-zinc-700/60 text-white hover:bg-zinc-700 transition-colors",
+                                onclick: on_clear_negative_cache,
+                                "Clear remembered misses"
+                            }
+                            if let Some(status) = negative_cache_status() {
+                                p { class: "text-xs text-zinc-400", "{status}" }
+                            }
+                        }
+                    }
+                }
+
+                // Strict privacy section
+                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                    h2 { class: "text-lg font-semibold text-white mb-3", "Strict Privacy Mode" }
+                    div { class: "space-y-4",
+                        div { class: "flex items-center justify-between gap-4",
+                            div {
+                                p { class: "font-medium text-white", "Only contact my servers" }
+                                p { class: "text-sm text-zinc-400",
+                                    "Blocks every request to hosts other than your configured servers: external lyrics providers, MusicBrainz, third-party radio streams, and their now-playing metadata."
+                                }
+                            }
+                            button {
+                                class: if settings.strict_privacy_mode { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors flex-shrink-0" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors flex-shrink-0" },
+                                onclick: on_strict_privacy_toggle,
+                                div { class: if settings.strict_privacy_mode { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                            }
+                        }
+                        div { class: "space-y-2",
+                            div { class: "flex items-center justify-between gap-4",
+                                p { class: "text-sm font-medium text-white", "Recently blocked requests" }
+                                button {
+                                    class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors text-xs",
+                                    onclick: move |_| blocked_requests.set(recent_blocked_requests()),
+                                    "Refresh"
+                                }
+                            }
+                            if blocked_requests().is_empty() {
+                                p { class: "text-xs text-zinc-500", "Nothing has been blocked this session." }
+                            } else {
+                                ul { class: "max-h-40 overflow-y-auto space-y-1",
+                                    for url in blocked_requests() {
+                                        li { class: "text-xs text-zinc-400 font-mono break-all", "{url}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Guest mode section
+                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                    h2 { class: "text-lg font-semibold text-white mb-3", "Guest Mode" }
+                    GuestSessionSettings {}
+                }
+
+                // Listen along section
+                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                    h2 { class: "text-lg font-semibold text-white mb-3", "Listen Along" }
+                    ListenAlongSettings {}
+                }
+
+                // MusicBrainz section
+                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                    h2 { class: "text-lg font-semibold text-white mb-3", "MusicBrainz" }
+                    if settings.strict_privacy_mode {
+                        p { class: "text-xs text-amber-300 mb-3",
+                            "Unavailable while strict privacy mode is on: MusicBrainz is outside your servers."
+                        }
+                    }
+                    div { class: "flex items-center justify-between gap-4",
+                        div {
+                            p { class: "font-medium text-white", "Discography check" }
+                            p { class: "text-sm text-zinc-400",
+                                "Adds a \"Check discography\" action to artist pages that compares your albums with MusicBrainz. Sends artist names to musicbrainz.org; results are cached for 30 days."
+                            }
+                        }
+                        button {
+                            class: if settings.musicbrainz_enabled { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors flex-shrink-0" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors flex-shrink-0" },
+                            onclick: on_musicbrainz_toggle,
+                            div { class: if settings.musicbrainz_enabled { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                        }
+                    }
+                }
+
                 if cfg!(target_os = "ios") {
                     section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
                         h2 { class: "text-lg font-semibold text-white mb-3", "iOS Audio Logs" }
@@ -3079,6 +3969,53 @@ pub fn SettingsView() -> Element {
                         }
                     }
                 }
+
+                // Setup guide
+                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                    div { class: "flex flex-wrap items-center justify-between gap-3",
+                        div {
+                            h2 { class: "text-lg font-semibold text-white", "Setup Guide" }
+                            p { class: "text-sm text-zinc-400",
+                                if settings.onboarding_completed {
+                                    "First-run setup is finished. Show it again to revisit the smart cache suggestion."
+                                } else {
+                                    "The setup guide is showing on the Servers tab."
+                                }
+                            }
+                        }
+                        button {
+                            class: if settings.onboarding_completed { "px-3 py-2 rounded-lg border border-emerald-500/40 text-emerald-300 hover:text-white hover:border-emerald-400/70 transition-colors text-sm" } else { "px-3 py-2 rounded-lg border border-zinc-700 text-zinc-500 cursor-not-allowed text-sm" },
+                            disabled: !settings.onboarding_completed,
+                            onclick: move |_| {
+                                settings_controller.update_settings(|settings| {
+                                    settings.onboarding_completed = false;
+                                });
+                                active_tab.set("servers".to_string());
+                            },
+                            "Show Setup Guide"
+                        }
+                    }
+                }
+
+                // Feedback
+                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                    div { class: "flex flex-wrap items-center justify-between gap-3",
+                        div {
+                            h2 { class: "text-lg font-semibold text-white", "Feedback" }
+                            p { class: "text-sm text-zinc-400",
+                                "Report a bug or suggest something through a GitHub issue, with your app version and recent diagnostics attached. You review everything before it leaves the app."
+                            }
+                        }
+                        button {
+                            class: "px-3 py-2 rounded-lg border border-emerald-500/40 text-emerald-300 hover:text-white hover:border-emerald-400/70 transition-colors text-sm",
+                            onclick: move |_| show_feedback.set(true),
+                            "Send feedback"
+                        }
+                    }
+                    if show_feedback() {
+                        FeedbackComposer { on_close: move |_| show_feedback.set(false) }
+                    }
+                }
                 } // end advanced tab
 
                 // ── Appearance tab ───────────────────────────────────────────────────
@@ -3101,7 +4038,7 @@ pub fn SettingsView() -> Element {
                             description: "The classic RustySound look. Deep zinc, emerald green.".to_string(),
                             bg: "#09090b".to_string(), surface: "#18181b".to_string(), accent: "#10b981".to_string(),
                             is_active: { let t = app_settings().theme; t == "rusty" || t == "dark" || t.is_empty() },
-                            on_select: { let mut app_settings = app_settings.clone(); move |name: String| { let mut s = app_settings(); s.theme = name; app_settings.set(s.clone()); spawn(async move { let _ = save_settings(s).await; }); } },
+                            on_select: move |name: String| settings_controller.update_settings(|settings| settings.theme = name),
                         }
                         // ── Spot ──
                         ThemeCard {
@@ -3109,7 +4046,7 @@ pub fn SettingsView() -> Element {
                             description: "Jet-black canvas with vivid Spotify-green accents.".to_string(),
                             bg: "#121212".to_string(), surface: "#1e1e1e".to_string(), accent: "#1ed760".to_string(),
                             is_active: app_settings().theme == "spot",
-                            on_select: { let mut app_settings = app_settings.clone(); move |name: String| { let mut s = app_settings(); s.theme = name; app_settings.set(s.clone()); spawn(async move { let _ = save_settings(s).await; }); } },
+                            on_select: move |name: String| settings_controller.update_settings(|settings| settings.theme = name),
                         }
                         // ── Fruit ──
                         ThemeCard {
@@ -3117,7 +4054,7 @@ pub fn SettingsView() -> Element {
                             description: "iOS dark palette, Apple Music red, heavy frosted glass.".to_string(),
                             bg: "#1c1c1e".to_string(), surface: "#2c2c2e".to_string(), accent: "#fc3c44".to_string(),
                             is_active: app_settings().theme == "fruit",
-                            on_select: { let mut app_settings = app_settings.clone(); move |name: String| { let mut s = app_settings(); s.theme = name; app_settings.set(s.clone()); spawn(async move { let _ = save_settings(s).await; }); } },
+                            on_select: move |name: String| settings_controller.update_settings(|settings| settings.theme = name),
                         }
                         // ── Navi ──
                         ThemeCard {
@@ -3125,7 +4062,155 @@ pub fn SettingsView() -> Element {
                             description: "Deep navy and sky-blue. Calm, organised — Navidrome in spirit.".to_string(),
                             bg: "#0d1117".to_string(), surface: "#161b2e".to_string(), accent: "#4d9de0".to_string(),
                             is_active: app_settings().theme == "navi",
-                            on_select: { let mut app_settings = app_settings.clone(); move |name: String| { let mut s = app_settings(); s.theme = name; app_settings.set(s.clone()); spawn(async move { let _ = save_settings(s).await; }); } },
+                            on_select: move |name: String| settings_controller.update_settings(|settings| settings.theme = name),
+                        }
+                    }
+                }
+
+                // ── Queue ────────────────────────────────────────────────────────
+                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                    h2 { class: "text-lg font-semibold text-white mb-1", "Queue" }
+                    p { class: "text-sm text-zinc-400 mb-4",
+                        "Artwork thumbnails load as rows scroll into view. Compact rows are text-only and fit more songs on screen."
+                    }
+                    select {
+                        class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                        value: queue_row_density_key(settings.queue_row_density),
+                        onchange: on_queue_row_density_change,
+                        for (key , label) in [
+                            ("with_art", "Rows with artwork"),
+                            ("compact", "Compact text rows"),
+                        ]
+                        {
+                            option {
+                                value: key,
+                                selected: queue_row_density_key(settings.queue_row_density) == key,
+                                "{label}"
+                            }
+                        }
+                    }
+                }
+
+                // ── Player bar ───────────────────────────────────────────────────
+                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                    h2 { class: "text-lg font-semibold text-white mb-1", "Player bar" }
+                    p { class: "text-sm text-zinc-400 mb-4",
+                        "Automatic shrinks the player to a slim strip while nothing is playing or queued. The arrow on the bar collapses or expands it by hand either way."
+                    }
+                    select {
+                        class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                        value: player_bar_mode_key(settings.player_bar_mode),
+                        onchange: on_player_bar_mode_change,
+                        for (key , label) in [
+                            ("auto", "Automatic"),
+                            ("always_expanded", "Always full size"),
+                        ]
+                        {
+                            option {
+                                value: key,
+                                selected: player_bar_mode_key(settings.player_bar_mode) == key,
+                                "{label}"
+                            }
+                        }
+                    }
+                }
+
+                // ── Grids ────────────────────────────────────────────────────────
+                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                    h2 { class: "text-lg font-semibold text-white mb-1", "Grid size" }
+                    p { class: "text-sm text-zinc-400 mb-4",
+                        "Tile size on the Albums, Artists and Playlists pages. Automatic fits the columns to the window width."
+                    }
+                    select {
+                        class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                        value: grid_density_key(settings.grid_density),
+                        onchange: on_grid_density_change,
+                        for (key , label) in [
+                            ("auto", "Automatic"),
+                            ("small", "Small tiles"),
+                            ("medium", "Medium tiles"),
+                            ("large", "Large tiles"),
+                        ]
+                        {
+                            option {
+                                value: key,
+                                selected: grid_density_key(settings.grid_density) == key,
+                                "{label}"
+                            }
+                        }
+                    }
+                }
+
+                // ── Sorting ──────────────────────────────────────────────────────
+                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                    h2 { class: "text-lg font-semibold text-white mb-3", "Sorting" }
+                    div { class: "flex items-center justify-between gap-4",
+                        div {
+                            p { class: "font-medium text-white", "Ignore \"The\"" }
+                            p { class: "text-sm text-zinc-400",
+                                "Sorts \"The Beatles\" under B in artist and album lists. Accents and case never affect the order."
+                            }
+                        }
+                        button {
+                            class: if settings.sort_ignore_articles { "w-12 h-6 bg-emerald-500 rounded-full relative transition-colors flex-shrink-0" } else { "w-12 h-6 bg-zinc-700 rounded-full relative transition-colors flex-shrink-0" },
+                            onclick: on_sort_articles_toggle,
+                            div { class: if settings.sort_ignore_articles { "w-5 h-5 bg-white rounded-full absolute top-0.5 right-0.5 transition-all" } else { "w-5 h-5 bg-zinc-400 rounded-full absolute top-0.5 left-0.5 transition-all" } }
+                        }
+                    }
+                }
+
+                // ── Dates and times ──────────────────────────────────────────────
+                section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+                    h2 { class: "text-lg font-semibold text-white mb-1", "Dates and times" }
+                    p { class: "text-sm text-zinc-400 mb-4",
+                        "Recent times read like \"3 hours ago\" or \"yesterday at 9:41 PM\"; older ones show the date."
+                    }
+                    div { class: "grid gap-4 sm:grid-cols-2",
+                        div {
+                            label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                "Date language"
+                            }
+                            select {
+                                class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                value: date_locale_key(settings.date_locale),
+                                onchange: on_date_locale_change,
+                                for (key , label) in [
+                                    ("en_us", "English (US)"),
+                                    ("en_gb", "English (UK)"),
+                                    ("de", "Deutsch"),
+                                    ("fr", "Français"),
+                                    ("es", "Español"),
+                                ]
+                                {
+                                    option {
+                                        value: key,
+                                        selected: date_locale_key(settings.date_locale) == key,
+                                        "{label}"
+                                    }
+                                }
+                            }
+                        }
+                        div {
+                            label { class: "block text-sm font-medium text-zinc-400 mb-2",
+                                "Clock"
+                            }
+                            select {
+                                class: "w-full max-w-xs px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white focus:outline-none focus:border-emerald-500/50",
+                                value: clock_format_key(settings.clock_format),
+                                onchange: on_clock_format_change,
+                                for (key , label) in [
+                                    ("auto", "Match the date language"),
+                                    ("12h", "12-hour"),
+                                    ("24h", "24-hour"),
+                                ]
+                                {
+                                    option {
+                                        value: key,
+                                        selected: clock_format_key(settings.clock_format) == key,
+                                        "{label}"
+                                    }
+                                }
+                            }
                         }
                     }
                 }
@@ -3174,7 +4259,7 @@ pub fn SettingsView() -> Element {
                             description: "Cyber millennium — hot magenta, electric neon, monospace chrome.".to_string(),
                             bg: "#0a0812".to_string(), surface: "#12102a".to_string(), accent: "#ff00cc".to_string(),
                             experimental: true, is_active: app_settings().theme == "y2k",
-                            on_select: { let mut app_settings = app_settings.clone(); move |name: String| { let mut s = app_settings(); s.theme = name; app_settings.set(s.clone()); spawn(async move { let _ = save_settings(s).await; }); } },
+                            on_select: move |name: String| settings_controller.update_settings(|settings| settings.theme = name),
                         }
                         // ── Aero ──
                         ThemeCard {
@@ -3182,7 +4267,7 @@ pub fn SettingsView() -> Element {
                             description: "Frutiger Aero — glossy ocean-blue, nature meets technology, 2006-era.".to_string(),
                             bg: "#0b1a26".to_string(), surface: "#1a3347".to_string(), accent: "#00b4d8".to_string(),
                             experimental: true, is_active: app_settings().theme == "aero",
-                            on_select: { let mut app_settings = app_settings.clone(); move |name: String| { let mut s = app_settings(); s.theme = name; app_settings.set(s.clone()); spawn(async move { let _ = save_settings(s).await; }); } },
+                            on_select: move |name: String| settings_controller.update_settings(|settings| settings.theme = name),
                         }
                         // ── Aqua ──
                         ThemeCard {
@@ -3190,7 +4275,7 @@ pub fn SettingsView() -> Element {
                             description: "macOS Aqua dark — deep ocean navy, gel-blue, pinstripe sidebar.".to_string(),
                             bg: "#0f1c2e".to_string(), surface: "#1e3054".to_string(), accent: "#1e7edc".to_string(),
                             experimental: true, is_active: app_settings().theme == "aqua",
-                            on_select: { let mut app_settings = app_settings.clone(); move |name: String| { let mut s = app_settings(); s.theme = name; app_settings.set(s.clone()); spawn(async move { let _ = save_settings(s).await; }); } },
+                            on_select: move |name: String| settings_controller.update_settings(|settings| settings.theme = name),
                         }
                         // ── Material ──
                         ThemeCard {
@@ -3198,7 +4283,7 @@ pub fn SettingsView() -> Element {
                             description: "M3 dark — warm purple tonal system, very rounded, elevated surfaces.".to_string(),
                             bg: "#1c1b1f".to_string(), surface: "#2b2930".to_string(), accent: "#d0bcff".to_string(),
                             experimental: true, is_active: app_settings().theme == "material",
-                            on_select: { let mut app_settings = app_settings.clone(); move |name: String| { let mut s = app_settings(); s.theme = name; app_settings.set(s.clone()); spawn(async move { let _ = save_settings(s).await; }); } },
+                            on_select: move |name: String| settings_controller.update_settings(|settings| settings.theme = name),
                         }
                         // ── Fluent ──
                         ThemeCard {
@@ -3206,7 +4291,7 @@ pub fn SettingsView() -> Element {
                             description: "Windows 11 Fluent Design — Mica/Acrylic, Windows blue, reveal depth.".to_string(),
                             bg: "#202020".to_string(), surface: "#383838".to_string(), accent: "#0078d4".to_string(),
                             experimental: true, is_active: app_settings().theme == "fluent",
-                            on_select: { let mut app_settings = app_settings.clone(); move |name: String| { let mut s = app_settings(); s.theme = name; app_settings.set(s.clone()); spawn(async move { let _ = save_settings(s).await; }); } },
+                            on_select: move |name: String| settings_controller.update_settings(|settings| settings.theme = name),
                         }
                         // ── HIG ──
                         ThemeCard {
@@ -3214,7 +4299,7 @@ pub fn SettingsView() -> Element {
                             description: "Apple HIG — OLED true-black, iOS system-blue, ultra minimal.".to_string(),
                             bg: "#000000".to_string(), surface: "#2c2c2e".to_string(), accent: "#007aff".to_string(),
                             experimental: true, is_active: app_settings().theme == "hig",
-                            on_select: { let mut app_settings = app_settings.clone(); move |name: String| { let mut s = app_settings(); s.theme = name; app_settings.set(s.clone()); spawn(async move { let _ = save_settings(s).await; }); } },
+                            on_select: move |name: String| settings_controller.update_settings(|settings| settings.theme = name),
                         }
                     }
                 }
@@ -3270,31 +4355,20 @@ pub fn SettingsView() -> Element {
                             if !app_settings().custom_css.is_empty() {
                                 button {
                                     class: "px-3 py-1.5 rounded-lg border border-zinc-700/60 text-zinc-400 hover:text-red-400 hover:border-red-500/40 transition-colors text-sm",
-                                    onclick: {
-                                        let mut app_settings = app_settings.clone();
-                                        let mut custom_css_draft = custom_css_draft.clone();
-                                        move |_| {
-                                            custom_css_draft.set(String::new());
-                                            let mut s = app_settings();
-                                            s.custom_css = String::new();
-                                            app_settings.set(s.clone());
-                                            spawn(async move { let _ = save_settings(s).await; });
-                                        }
+                                    onclick: move |_| {
+                                        custom_css_draft.set(String::new());
+                                        settings_controller.update_settings(|settings| {
+                                            settings.custom_css = String::new();
+                                        });
                                     },
                                     "Clear"
                                 }
                             }
                             button {
                                 class: "px-4 py-1.5 rounded-lg bg-emerald-500 hover:bg-emerald-400 text-white font-medium transition-colors text-sm",
-                                onclick: {
-                                    let mut app_settings = app_settings.clone();
-                                    move |_| {
-                                        let css = custom_css_draft();
-                                        let mut s = app_settings();
-                                        s.custom_css = css;
-                                        app_settings.set(s.clone());
-                                        spawn(async move { let _ = save_settings(s).await; });
-                                    }
+                                onclick: move |_| {
+                                    let css = custom_css_draft();
+                                    settings_controller.update_settings(|settings| settings.custom_css = css);
                                 },
                                 "Apply & Save"
                             }
@@ -3315,6 +4389,11 @@ pub fn SettingsView() -> Element {
                                     class: "w-10 h-10 text-emerald-500".to_string(),
                                 }
                             }
+                            if !settings.onboarding_completed {
+                                p { class: "text-xs uppercase tracking-wide text-emerald-400/80",
+                                    "Step 1 of 2"
+                                }
+                            }
                             h2 { class: "text-3xl font-bold text-white", "Add Your First Server" }
                             p { class: "text-zinc-400 text-lg max-w-lg mx-auto",
                                 "Connect to your Navidrome server to start streaming your music collection."
@@ -3352,6 +4431,7 @@ fn ServerCard(
     on_test: EventHandler<MouseEvent>,
     is_testing: bool,
     is_editing: bool,
+    duplicate_of: Option<String>,
 ) -> Element {
     let initials: String = server
         .name
@@ -3360,6 +4440,46 @@ fn ServerCard(
         .take(2)
         .collect::<String>()
         .to_uppercase();
+    let capabilities = (use_context::<ServerCapabilitiesSignal>().capabilities)()
+        .get(&server.id)
+        .copied();
+    #[cfg(not(target_arch = "wasm32"))]
+    let initial_negotiation = NavidromeClient::new(server.clone())
+        .stream_negotiation()
+        .map(Ok);
+    #[cfg(target_arch = "wasm32")]
+    let initial_negotiation = None;
+    let mut negotiation: Signal<Option<Result<StreamNegotiation, String>>> =
+        use_signal(move || initial_negotiation);
+    let mut checking_stream = use_signal(|| false);
+
+    let on_check_stream = {
+        let server = server.clone();
+        move |_| {
+            if checking_stream() {
+                return;
+            }
+            checking_stream.set(true);
+            let client = NavidromeClient::new(server.clone());
+            spawn(async move {
+                #[cfg(not(target_arch = "wasm32"))]
+                let result = client
+                    .observe_stream_negotiation()
+                    .await
+                    .map_err(|error| error.to_string());
+                #[cfg(target_arch = "wasm32")]
+                let result = {
+                    let _ = client;
+                    Err(
+                        "Stream responses can only be checked in the desktop and mobile apps."
+                            .to_string(),
+                    )
+                };
+                negotiation.set(Some(result));
+                checking_stream.set(false);
+            });
+        }
+    };
 
     rsx! {
         div { class: if is_editing { "p-4 rounded-xl bg-zinc-900/50 border border-amber-500/40" } else { "p-4 rounded-xl bg-zinc-900/50 border border-zinc-700/30" },
@@ -3374,6 +4494,76 @@ fn ServerCard(
                     p { class: "font-medium text-white truncate", "{server.name}" }
                     p { class: "text-sm text-zinc-400 truncate", "{server.url}" }
                     p { class: "text-xs text-zinc-500", "User: {server.username}" }
+                    p { class: "text-xs text-zinc-500",
+                        "Client: {server.reported_client_name()}"
+                        if server.raw_stream {
+                            " · raw streams"
+                        }
+                    }
+                    if let Some(capabilities) = capabilities {
+                        p { class: "text-xs text-zinc-500", "Server: {capabilities.flavor.label()}" }
+                    }
+                }
+            }
+            if let Some(capabilities) = capabilities {
+                div { class: "flex flex-wrap gap-1.5 mb-3",
+                    for (label , supported) in capabilities.features() {
+                        span {
+                            key: "{label}",
+                            class: if supported { "px-2 py-0.5 rounded-full text-[11px] bg-emerald-500/10 text-emerald-300 border border-emerald-500/30" } else { "px-2 py-0.5 rounded-full text-[11px] text-zinc-500 border border-zinc-700/60 line-through" },
+                            title: if supported { "Supported by this server" } else { "Not supported by this server" },
+                            "{label}"
+                        }
+                    }
+                }
+                if let Some(permissions) = capabilities.permissions.filter(|p| !p.summary().is_empty()) {
+                    div { class: "flex flex-wrap items-center gap-1.5 mb-3",
+                        span { class: "text-[11px] text-zinc-500", "Your roles:" }
+                        for (label , granted) in permissions.summary() {
+                            span {
+                                key: "role-{label}",
+                                class: if granted { "px-2 py-0.5 rounded-full text-[11px] bg-sky-500/10 text-sky-300 border border-sky-500/30" } else { "px-2 py-0.5 rounded-full text-[11px] text-zinc-500 border border-zinc-700/60 line-through" },
+                                title: if granted { "Granted to {server.username} on this server" } else { "Not granted to {server.username}; ask the server admin to enable it" },
+                                "{label}"
+                            }
+                        }
+                    }
+                }
+            }
+            div { class: "flex items-start justify-between gap-3 mb-3 text-xs",
+                div { class: "min-w-0",
+                    match negotiation() {
+                        Some(Ok(observed)) => rsx! {
+                            p { class: if observed.transcoded { "text-amber-300" } else { "text-emerald-300" },
+                                "{observed.summary()}"
+                            }
+                            p { class: "text-zinc-500 truncate",
+                                "Sampled \"{observed.sample_title}\" as \"{observed.client_name}\""
+                                if observed.raw_requested {
+                                    " with format=raw"
+                                }
+                                if observed.player_rules {
+                                    ". Navidrome applies its player rules for this name."
+                                }
+                            }
+                        },
+                        Some(Err(error)) => rsx! {
+                            p { class: "text-red-300", "Stream check failed: {error}" }
+                        },
+                        None => rsx! {
+                            p { class: "text-zinc-500", "Transcoding: not checked yet." }
+                        },
+                    }
+                }
+                button {
+                    class: "px-2.5 py-1 rounded-lg border border-zinc-700 text-zinc-400 hover:text-white hover:border-emerald-500/60 transition-colors flex-shrink-0 disabled:opacity-50",
+                    disabled: checking_stream() || !server.active,
+                    onclick: on_check_stream,
+                    if checking_stream() {
+                        "Checking..."
+                    } else {
+                        "Check stream"
+                    }
                 }
             }
             // Action buttons row
@@ -3434,6 +4624,146 @@ fn ServerCard(
                     "Scroll up to edit this server in the form above."
                 }
             }
+            if let Some(duplicate_name) = duplicate_of {
+                div { class: "mt-3 text-xs text-amber-200/90 bg-amber-500/10 border border-amber-500/30 rounded-lg px-3 py-2",
+                    "Same URL and username as \"{duplicate_name}\". Merge the entries under Duplicate Servers to avoid duplicate requests and favorites."
+                }
+            }
+        }
+    }
+}
+
+/// Maintenance card listing servers saved more than once with the same URL and username.
+/// Merging moves the duplicate's downloads, local settings, saved queue and cached
+/// responses to the kept entry and then removes the duplicate.
+#[component]
+fn DuplicateServersSection() -> Element {
+    let mut servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let mut app_settings = use_context::<Signal<AppSettings>>();
+    let mut queue = use_context::<Signal<Vec<Song>>>();
+    let mut now_playing = use_context::<Signal<Option<Song>>>();
+    let mut merge_busy = use_signal(|| false);
+    let mut merge_status = use_signal(|| None::<Result<String, String>>);
+
+    let groups = duplicate_server_groups(&servers());
+    if groups.is_empty() && merge_status().is_none() {
+        return rsx! {};
+    }
+
+    let mut on_merge = move |keep: ServerConfig, duplicate: ServerConfig| {
+        if merge_busy() {
+            return;
+        }
+        merge_busy.set(true);
+        merge_status.set(None);
+        spawn(async move {
+            match merge_duplicate_server(keep.id.clone(), duplicate.id.clone()).await {
+                Ok(()) => {
+                    let moved_downloads =
+                        reassign_server_downloads(&duplicate.id, &keep.id, &keep.name);
+                    let _ = reassign_cached_server(&duplicate.id, &keep.id);
+                    clear_server_reauth(&duplicate.id);
+
+                    // The stored copies are already rewritten; bring the in-memory state in
+                    // line so the next save does not bring the duplicate back.
+                    servers.with_mut(|list| {
+                        list.retain(|server| server.id != duplicate.id);
+                        if duplicate.active {
+                            if let Some(kept) = list.iter_mut().find(|server| server.id == keep.id)
+                            {
+                                kept.active = true;
+                            }
+                        }
+                    });
+                    app_settings
+                        .with_mut(|settings| settings.reassign_server(&duplicate.id, &keep.id));
+                    queue.with_mut(|songs| {
+                        for song in songs.iter_mut() {
+                            if song.server_id == duplicate.id {
+                                song.server_id = keep.id.clone();
+                            }
+                        }
+                    });
+                    if now_playing
+                        .peek()
+                        .as_ref()
+                        .is_some_and(|song| song.server_id == duplicate.id)
+                    {
+                        now_playing.with_mut(|song| {
+                            if let Some(song) = song.as_mut() {
+                                song.server_id = keep.id.clone();
+                            }
+                        });
+                    }
+
+                    merge_status.set(Some(Ok(format!(
+                        "Merged \"{}\" into \"{}\" ({} download{} moved).",
+                        duplicate.name,
+                        keep.name,
+                        moved_downloads,
+                        if moved_downloads == 1 { "" } else { "s" }
+                    ))));
+                }
+                Err(error) => {
+                    merge_status.set(Some(Err(format!("Merge failed: {error}"))));
+                }
+            }
+            merge_busy.set(false);
+        });
+    };
+
+    rsx! {
+        section { class: "order-3 bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+            h2 { class: "text-lg font-semibold text-white mb-1", "Duplicate Servers" }
+            p { class: "text-sm text-zinc-400 mb-4",
+                "These entries point at the same server and account. Merging keeps one entry and moves the other's downloads, notes, playback offsets, saved queue and cache to it."
+            }
+            div { class: "space-y-3",
+                for (keep , duplicates) in groups {
+                    div {
+                        key: "{keep.id}",
+                        class: "p-4 rounded-xl bg-zinc-900/50 border border-amber-500/30 space-y-2",
+                        p { class: "text-sm text-white",
+                            "Keep \"{keep.name}\""
+                            span { class: "text-zinc-500", " · {keep.url} · {keep.username}" }
+                        }
+                        for duplicate in duplicates {
+                            div {
+                                key: "{duplicate.id}",
+                                class: "flex items-center justify-between gap-3",
+                                p { class: "text-sm text-zinc-400 truncate min-w-0",
+                                    "Duplicate: \"{duplicate.name}\""
+                                }
+                                button {
+                                    class: "px-3 py-1.5 rounded-lg bg-amber-500/20 text-amber-300 text-sm hover:bg-amber-500/30 transition-colors flex-shrink-0 disabled:opacity-50",
+                                    disabled: merge_busy(),
+                                    onclick: {
+                                        let keep = keep.clone();
+                                        let duplicate = duplicate.clone();
+                                        move |_| on_merge(keep.clone(), duplicate.clone())
+                                    },
+                                    if merge_busy() {
+                                        "Merging..."
+                                    } else {
+                                        "Merge"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            {
+                match merge_status() {
+                    Some(Ok(message)) => rsx! {
+                        p { class: "mt-3 text-sm text-emerald-400", "{message}" }
+                    },
+                    Some(Err(message)) => rsx! {
+                        p { class: "mt-3 text-sm text-red-400", "{message}" }
+                    },
+                    None => rsx! {},
+                }
+            }
         }
     }
 }
@@ -0,0 +1,203 @@
+//! "Profiles" card of the storage settings: saves the device-dependent cache, download and
+//! performance settings under a name, switches between saved profiles and the built-in
+//! presets, and moves profiles between devices as settings bundles.
+use crate::components::{Icon, SettingsController};
+use crate::db::{parse_settings_bundle, settings_bundle_json, AppSettings, SettingsProfile};
+use crate::storage::save_text_export;
+use dioxus::prelude::*;
+
+/// File name for an exported profile, e.g. "desktop-settings.json".
+fn profile_file_name(profile: &SettingsProfile) -> String {
+    let stem: String = profile
+        .name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let stem = stem.trim_matches('-');
+    if stem.is_empty() {
+        "rustysound-settings.json".to_string()
+    } else {
+        format!("{stem}-settings.json")
+    }
+}
+
+#[component]
+pub(super) fn SettingsProfilesSection() -> Element {
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let settings_controller = use_context::<SettingsController>();
+    let mut new_profile_name = use_signal(String::new);
+    let mut status = use_signal(|| None::<Result<String, String>>);
+
+    let settings = app_settings();
+    let profiles = settings.available_settings_profiles();
+    let active_id = settings.active_settings_profile.clone();
+
+    let on_save = move |_| {
+        let name = new_profile_name().trim().to_string();
+        if name.is_empty() {
+            status.set(Some(Err("Give the profile a name first.".to_string())));
+            return;
+        }
+        settings_controller.update_settings(|settings| {
+            settings.save_settings_profile(&name);
+        });
+        new_profile_name.set(String::new());
+        status.set(Some(Ok(format!(
+            "Saved the current settings as \"{name}\"."
+        ))));
+    };
+
+    let mut on_apply = move |profile: SettingsProfile| {
+        settings_controller.update_settings(|settings| {
+            settings.apply_settings_profile(&profile.id);
+        });
+        status.set(Some(Ok(format!("Switched to \"{}\".", profile.name))));
+    };
+
+    let mut on_export = move |profile: SettingsProfile| {
+        let result = settings_bundle_json(std::slice::from_ref(&profile)).and_then(|json| {
+            save_text_export(&profile_file_name(&profile), &json, "application/json")
+        });
+        status.set(Some(result));
+    };
+
+    let mut on_delete = move |profile: SettingsProfile| {
+        settings_controller.update_settings(|settings| {
+            settings.delete_settings_profile(&profile.id);
+        });
+        status.set(Some(Ok(format!("Deleted \"{}\".", profile.name))));
+    };
+
+    let on_import_file = move |evt: FormEvent| {
+        let Some(file) = evt.files().into_iter().next() else {
+            return;
+        };
+        spawn(async move {
+            let bundle = match file.read_string().await {
+                Ok(text) => parse_settings_bundle(&text),
+                Err(error) => Err(format!("Could not read the file: {error}")),
+            };
+            match bundle {
+                Ok(bundle) => {
+                    let mut imported = 0;
+                    settings_controller.update_settings(|settings| {
+                        imported = settings.import_settings_profiles(bundle.profiles);
+                    });
+                    status.set(Some(Ok(format!(
+                        "Imported {imported} profile(s). Apply one to switch to it."
+                    ))));
+                }
+                Err(error) => status.set(Some(Err(error))),
+            }
+        });
+    };
+
+    rsx! {
+        section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6 space-y-4",
+            div {
+                h2 { class: "text-lg font-semibold text-white mb-1", "Profiles" }
+                p { class: "text-sm text-zinc-400",
+                    "Switch cache, download and performance settings in one go, for example a large cache on a desktop and a small one on a metered laptop. Profiles never include servers or passwords."
+                }
+            }
+            div { class: "space-y-2",
+                for profile in profiles {
+                    div {
+                        key: "{profile.id}",
+                        class: "flex flex-wrap items-center justify-between gap-3 p-3 bg-zinc-900/30 rounded-lg",
+                        div { class: "flex items-center gap-2 min-w-0",
+                            p { class: "font-medium text-white truncate", "{profile.name}" }
+                            if profile.is_builtin() {
+                                span { class: "px-2 py-0.5 rounded-full bg-zinc-800 text-[10px] uppercase tracking-wider text-zinc-400",
+                                    "Preset"
+                                }
+                            }
+                            if active_id.as_deref() == Some(profile.id.as_str()) {
+                                if profile.settings.matches(&settings) {
+                                    span { class: "px-2 py-0.5 rounded-full bg-emerald-500/15 text-[10px] uppercase tracking-wider text-emerald-300",
+                                        "Active"
+                                    }
+                                } else {
+                                    span {
+                                        class: "px-2 py-0.5 rounded-full bg-amber-500/15 text-[10px] uppercase tracking-wider text-amber-300",
+                                        title: "Settings were changed after this profile was applied",
+                                        "Modified"
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "flex items-center gap-2",
+                            button {
+                                class: "px-3 py-1.5 rounded-lg bg-emerald-500/15 text-emerald-300 hover:bg-emerald-500/25 transition-colors text-sm",
+                                onclick: {
+                                    let profile = profile.clone();
+                                    move |_| on_apply(profile.clone())
+                                },
+                                "Apply"
+                            }
+                            button {
+                                class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white transition-colors text-sm",
+                                title: "Save this profile as a settings file",
+                                onclick: {
+                                    let profile = profile.clone();
+                                    move |_| on_export(profile.clone())
+                                },
+                                Icon { name: "download".to_string(), class: "w-4 h-4".to_string() }
+                            }
+                            if !profile.is_builtin() {
+                                button {
+                                    class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-400 hover:text-red-300 hover:border-red-500/50 transition-colors text-sm",
+                                    title: "Delete this profile",
+                                    onclick: {
+                                        let profile = profile.clone();
+                                        move |_| on_delete(profile.clone())
+                                    },
+                                    Icon { name: "trash".to_string(), class: "w-4 h-4".to_string() }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            div { class: "flex flex-wrap items-center gap-2",
+                input {
+                    class: "flex-1 min-w-[12rem] px-3 py-2 rounded-lg border border-zinc-700 bg-zinc-900 text-white text-sm focus:outline-none focus:border-emerald-500/50",
+                    placeholder: "Profile name, e.g. Desktop",
+                    value: "{new_profile_name}",
+                    oninput: move |e| new_profile_name.set(e.value()),
+                }
+                button {
+                    class: "px-3 py-2 rounded-lg border border-emerald-500/40 text-emerald-300 hover:text-white hover:border-emerald-400/70 transition-colors text-sm",
+                    onclick: on_save,
+                    "Save current settings"
+                }
+                label { class: "px-3 py-2 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-zinc-500 transition-colors text-sm flex items-center gap-2 cursor-pointer",
+                    Icon { name: "upload".to_string(), class: "w-4 h-4".to_string() }
+                    "Import"
+                    input {
+                        r#type: "file",
+                        accept: ".json,application/json",
+                        class: "hidden",
+                        onchange: on_import_file,
+                    }
+                }
+            }
+            match status() {
+                Some(Ok(message)) => rsx! {
+                    p { class: "text-xs text-emerald-400", "{message}" }
+                },
+                Some(Err(message)) => rsx! {
+                    p { class: "text-xs text-red-400", "{message}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+// Adaptive pacing for the smart-cache warm-up on large or slow servers.
+//
+// Every server request in the loop reports how long it took. Slow or failed responses
+// halve the batch sizes and lengthen the pause between requests; a run of quick responses
+// walks both back. The user-configured limits always stay the upper bound.
+use crate::api::ApiError;
+
+/// Responses slower than this count as the server struggling.
+const SLOW_RESPONSE_MS: u128 = 2_000;
+/// Responses at or under this count towards recovering speed.
+const FAST_RESPONSE_MS: u128 = 600;
+/// Quick responses in a row needed before easing off the backoff.
+const FAST_STREAK_TO_RECOVER: u8 = 3;
+const MIN_BATCH_SCALE: f64 = 0.1;
+const INITIAL_BACKOFF_MS: u32 = 250;
+const MAX_BACKOFF_MS: u32 = 5_000;
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn backoff_sleep(ms: u32) {
+    tokio::time::sleep(std::time::Duration::from_millis(u64::from(ms))).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn backoff_sleep(ms: u32) {
+    gloo_timers::future::TimeoutFuture::new(ms).await;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct SmartCacheThrottle {
+    /// Fraction of each configured batch size currently requested, 1.0 at full speed.
+    batch_scale: f64,
+    /// Delay added after every request while the server is slow.
+    backoff_ms: u32,
+    fast_streak: u8,
+}
+
+impl Default for SmartCacheThrottle {
+    fn default() -> Self {
+        Self {
+            batch_scale: 1.0,
+            backoff_ms: 0,
+            fast_streak: 0,
+        }
+    }
+}
+
+impl SmartCacheThrottle {
+    /// Records one server round trip. Rate-limit responses honour `Retry-After`.
+    pub(super) fn observe<T>(&mut self, elapsed_ms: u128, result: &Result<T, ApiError>) {
+        let retry_after_ms = match result {
+            Err(ApiError::RateLimited {
+                retry_after: Some(secs),
+            }) => Some((*secs).saturating_mul(1000).min(u64::from(MAX_BACKOFF_MS)) as u32),
+            _ => None,
+        };
+        let struggling = elapsed_ms > SLOW_RESPONSE_MS
+            || matches!(
+                result,
+                Err(ApiError::Timeout | ApiError::RateLimited { .. })
+            );
+
+        if struggling {
+            self.fast_streak = 0;
+            self.batch_scale = (self.batch_scale * 0.5).max(MIN_BATCH_SCALE);
+            self.backoff_ms = self
+                .backoff_ms
+                .saturating_mul(2)
+                .max(INITIAL_BACKOFF_MS)
+                .max(retry_after_ms.unwrap_or(0))
+                .min(MAX_BACKOFF_MS);
+        } else if elapsed_ms <= FAST_RESPONSE_MS && self.is_backing_off() {
+            self.fast_streak = self.fast_streak.saturating_add(1);
+            if self.fast_streak >= FAST_STREAK_TO_RECOVER {
+                self.fast_streak = 0;
+                self.batch_scale = (self.batch_scale * 1.5).min(1.0);
+                self.backoff_ms = if self.backoff_ms / 2 < 50 {
+                    0
+                } else {
+                    self.backoff_ms / 2
+                };
+            }
+        } else {
+            self.fast_streak = 0;
+        }
+    }
+
+    pub(super) fn is_backing_off(&self) -> bool {
+        self.batch_scale < 1.0 || self.backoff_ms > 0
+    }
+
+    /// Current batch size for a configured `limit`, never above it and never below one.
+    pub(super) fn limit(&self, limit: usize) -> usize {
+        if limit == 0 {
+            return 0;
+        }
+        ((limit as f64 * self.batch_scale).round() as usize).clamp(1, limit)
+    }
+
+    pub(super) fn limit_u32(&self, limit: u32) -> u32 {
+        self.limit(limit as usize) as u32
+    }
+
+    /// Sleeps for the current backoff, on top of the loop's regular pause.
+    pub(super) async fn wait(&self) {
+        if self.backoff_ms > 0 {
+            backoff_sleep(self.backoff_ms).await;
+        }
+    }
+
+    /// Suffix for smart-cache status lines; empty at full speed.
+    pub(super) fn status_note(&self) -> String {
+        if !self.is_backing_off() {
+            return String::new();
+        }
+        format!(
+            " (server is slow: batches at {}%, {} ms between requests)",
+            (self.batch_scale * 100.0).round() as u32,
+            self.backoff_ms
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok() -> Result<(), ApiError> {
+        Ok(())
+    }
+
+    #[test]
+    fn slow_responses_shrink_batches_within_configured_limits() {
+        let mut throttle = SmartCacheThrottle::default();
+        assert_eq!(throttle.limit(120), 120);
+        assert!(throttle.status_note().is_empty());
+
+        throttle.observe(3_500, &ok());
+        assert_eq!(throttle.limit(120), 60);
+        assert!(throttle.status_note().contains("50%"));
+
+        for _ in 0..10 {
+            throttle.observe(9_000, &ok());
+        }
+        assert_eq!(throttle.limit(120), 12);
+        assert_eq!(throttle.limit(3), 1);
+        assert!(throttle.status_note().contains("5000 ms"));
+    }
+
+    #[test]
+    fn quick_responses_recover_to_full_speed() {
+        let mut throttle = SmartCacheThrottle::default();
+        throttle.observe(0, &Err::<(), _>(ApiError::Timeout));
+        assert!(throttle.is_backing_off());
+
+        for _ in 0..(FAST_STREAK_TO_RECOVER as usize * 4) {
+            throttle.observe(100, &ok());
+        }
+        assert!(!throttle.is_backing_off());
+        assert_eq!(throttle.limit(30), 30);
+    }
+
+    #[test]
+    fn rate_limits_wait_for_retry_after() {
+        let mut throttle = SmartCacheThrottle::default();
+        throttle.observe(
+            50,
+            &Err::<(), _>(ApiError::RateLimited {
+                retry_after: Some(3),
+            }),
+        );
+        assert!(throttle.status_note().contains("3000 ms"));
+    }
+}
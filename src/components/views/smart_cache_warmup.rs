@@ -0,0 +1,1011 @@
+// Smart cache warm-up: prefetches metadata, lyrics and artwork for the active servers.
+//
+// `warm_smart_cache` takes a `SmartCacheConfig` and reports through a callback instead of
+// touching signals, so the same pass runs from the Settings options sheet or headless. It
+// asks another callback whether it is paused.
+use super::smart_cache_throttle::SmartCacheThrottle;
+use crate::api::*;
+use crate::components::cached_image::{ALBUM_ART_SIZES, PLAYLIST_ART_SIZES, SONG_ART_SIZES};
+use crate::components::Icon;
+use crate::db::{AppSettings, ArtworkDownloadPreference};
+use crate::diagnostics::PerfTimer;
+use dioxus::prelude::*;
+use std::collections::HashSet;
+
+/// Share of the progress bar for server metadata, lyrics and artwork when all three run.
+const METADATA_PROGRESS_WEIGHT: f64 = 60.0;
+const LYRICS_PROGRESS_WEIGHT: f64 = 25.0;
+const ARTWORK_PROGRESS_WEIGHT: f64 = 15.0;
+
+const SMART_CACHE_MIN_ALBUMS_PER_SERVER: u32 = 24;
+const SMART_CACHE_MIN_RANDOM_SONGS_PER_SERVER: u32 = 30;
+const SMART_CACHE_MIN_PLAYLISTS_PER_SERVER: usize = 4;
+const SMART_CACHE_MIN_ALBUM_DETAILS_PER_SERVER: usize = 6;
+const SMART_CACHE_MIN_LYRICS_LIMIT: usize = 36;
+const SMART_CACHE_MIN_ARTWORK_LIMIT: usize = 160;
+const SMART_CACHE_MAX_ALBUMS_PER_SERVER: u32 = 120;
+const SMART_CACHE_MAX_RANDOM_SONGS_PER_SERVER: u32 = 220;
+const SMART_CACHE_MAX_PLAYLISTS_PER_SERVER: usize = 16;
+const SMART_CACHE_MAX_ALBUM_DETAILS_PER_SERVER: usize = 30;
+const SMART_CACHE_MAX_LYRICS_LIMIT: usize = 600;
+const SMART_CACHE_MAX_ARTWORK_LIMIT: usize = 4800;
+/// How often a paused warm-up checks whether it was resumed.
+const SMART_CACHE_PAUSED_POLL_MS: u32 = 250;
+
+fn smart_cache_albums_per_server(cache_size_mb: u32) -> u32 {
+    (SMART_CACHE_MIN_ALBUMS_PER_SERVER + cache_size_mb.clamp(25, 2048) / 20).clamp(
+        SMART_CACHE_MIN_ALBUMS_PER_SERVER,
+        SMART_CACHE_MAX_ALBUMS_PER_SERVER,
+    )
+}
+
+fn smart_cache_random_songs_per_server(cache_size_mb: u32) -> u32 {
+    (SMART_CACHE_MIN_RANDOM_SONGS_PER_SERVER + cache_size_mb.clamp(25, 2048) / 12).clamp(
+        SMART_CACHE_MIN_RANDOM_SONGS_PER_SERVER,
+        SMART_CACHE_MAX_RANDOM_SONGS_PER_SERVER,
+    )
+}
+
+fn smart_cache_playlists_per_server(cache_size_mb: u32) -> usize {
+    (SMART_CACHE_MIN_PLAYLISTS_PER_SERVER + (cache_size_mb.clamp(25, 2048) / 180) as usize).clamp(
+        SMART_CACHE_MIN_PLAYLISTS_PER_SERVER,
+        SMART_CACHE_MAX_PLAYLISTS_PER_SERVER,
+    )
+}
+
+fn smart_cache_album_details_per_server(cache_size_mb: u32) -> usize {
+    (SMART_CACHE_MIN_ALBUM_DETAILS_PER_SERVER + (cache_size_mb.clamp(25, 2048) / 60) as usize)
+        .clamp(
+            SMART_CACHE_MIN_ALBUM_DETAILS_PER_SERVER,
+            SMART_CACHE_MAX_ALBUM_DETAILS_PER_SERVER,
+        )
+}
+
+fn smart_cache_lyrics_limit(cache_size_mb: u32) -> usize {
+    (SMART_CACHE_MIN_LYRICS_LIMIT + (cache_size_mb.clamp(25, 2048) / 3) as usize)
+        .clamp(SMART_CACHE_MIN_LYRICS_LIMIT, SMART_CACHE_MAX_LYRICS_LIMIT)
+}
+
+fn smart_cache_artwork_limit(cache_size_mb: u32) -> usize {
+    (SMART_CACHE_MIN_ARTWORK_LIMIT + (cache_size_mb.clamp(25, 2048) * 5) as usize)
+        .clamp(SMART_CACHE_MIN_ARTWORK_LIMIT, SMART_CACHE_MAX_ARTWORK_LIMIT)
+}
+
+fn push_cover_art_request(
+    client: &NavidromeClient,
+    server: &ServerConfig,
+    cover_art_id: &str,
+    sizes: &[u32],
+    output: &mut Vec<String>,
+    seen_requests: &mut HashSet<String>,
+    limit: usize,
+) {
+    for size in sizes {
+        if output.len() >= limit {
+            return;
+        }
+        let request_key = format!("{}:{}:{}", server.id, cover_art_id, size);
+        if !seen_requests.insert(request_key) {
+            continue;
+        }
+        output.push(client.get_cover_art_url(cover_art_id, *size));
+    }
+}
+
+fn song_cache_key(song: &Song) -> String {
+    format!("{}::{}", song.server_id, song.id)
+}
+
+fn is_id3_cover_art_id(cover_art_id: &str) -> bool {
+    cover_art_id.trim().to_ascii_lowercase().starts_with("mf-")
+}
+
+fn include_cover_for_pref(cover_art_id: &str, pref: ArtworkDownloadPreference) -> bool {
+    match pref {
+        ArtworkDownloadPreference::ServerOnly => !is_id3_cover_art_id(cover_art_id),
+        ArtworkDownloadPreference::Id3Only => is_id3_cover_art_id(cover_art_id),
+        ArtworkDownloadPreference::PreferServer | ArtworkDownloadPreference::PreferId3 => true,
+    }
+}
+
+fn collect_song_cover_urls(
+    server: &ServerConfig,
+    songs: &[Song],
+    output: &mut Vec<String>,
+    seen_requests: &mut HashSet<String>,
+    limit: usize,
+    pref: ArtworkDownloadPreference,
+) {
+    let client = NavidromeClient::new(server.clone());
+    for song in songs {
+        if output.len() >= limit {
+            break;
+        }
+        if let Some(cover) = song
+            .cover_art
+            .as_ref()
+            .filter(|value| !value.trim().is_empty())
+            .or_else(|| {
+                song.album_id
+                    .as_ref()
+                    .filter(|value| !value.trim().is_empty())
+            })
+        {
+            if include_cover_for_pref(cover, pref) {
+                push_cover_art_request(
+                    &client,
+                    server,
+                    cover,
+                    &SONG_ART_SIZES,
+                    output,
+                    seen_requests,
+                    limit,
+                );
+            }
+        }
+    }
+}
+
+fn collect_album_cover_urls(
+    server: &ServerConfig,
+    albums: &[Album],
+    output: &mut Vec<String>,
+    seen_requests: &mut HashSet<String>,
+    limit: usize,
+    pref: ArtworkDownloadPreference,
+) {
+    let client = NavidromeClient::new(server.clone());
+    for album in albums {
+        if output.len() >= limit {
+            break;
+        }
+        if let Some(cover) = album
+            .cover_art
+            .as_ref()
+            .filter(|value| !value.trim().is_empty())
+        {
+            if !include_cover_for_pref(cover, pref) {
+                continue;
+            }
+            push_cover_art_request(
+                &client,
+                server,
+                cover,
+                &ALBUM_ART_SIZES,
+                output,
+                seen_requests,
+                limit,
+            );
+        }
+    }
+}
+
+fn collect_playlist_cover_urls(
+    server: &ServerConfig,
+    playlists: &[Playlist],
+    output: &mut Vec<String>,
+    seen_requests: &mut HashSet<String>,
+    limit: usize,
+    pref: ArtworkDownloadPreference,
+) {
+    let client = NavidromeClient::new(server.clone());
+    for playlist in playlists {
+        if output.len() >= limit {
+            break;
+        }
+        if let Some(cover) = playlist
+            .cover_art
+            .as_ref()
+            .filter(|value| !value.trim().is_empty())
+        {
+            if !include_cover_for_pref(cover, pref) {
+                continue;
+            }
+            push_cover_art_request(
+                &client,
+                server,
+                cover,
+                &PLAYLIST_ART_SIZES,
+                output,
+                seen_requests,
+                limit,
+            );
+        }
+    }
+}
+
+fn push_unique_songs(
+    target: &mut Vec<Song>,
+    seen: &mut HashSet<String>,
+    incoming: impl IntoIterator<Item = Song>,
+) {
+    for song in incoming {
+        let key = song_cache_key(&song);
+        if seen.insert(key) {
+            target.push(song);
+        }
+    }
+}
+
+fn dedupe_trim_urls(urls: Vec<String>, limit: usize) -> Vec<String> {
+    let mut seen = HashSet::<String>::new();
+    let mut output = Vec::<String>::new();
+    for url in urls {
+        let trimmed = url.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if seen.insert(trimmed.to_string()) {
+            output.push(trimmed.to_string());
+        }
+        if output.len() >= limit {
+            break;
+        }
+    }
+    output
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn smart_cache_pause(ms: u32) {
+    gloo_timers::future::TimeoutFuture::new(ms).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn smart_cache_pause(_ms: u32) {}
+
+#[cfg(target_arch = "wasm32")]
+async fn paused_poll_sleep() {
+    gloo_timers::future::TimeoutFuture::new(SMART_CACHE_PAUSED_POLL_MS).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn paused_poll_sleep() {
+    tokio::time::sleep(std::time::Duration::from_millis(u64::from(
+        SMART_CACHE_PAUSED_POLL_MS,
+    )))
+    .await;
+}
+
+/// Waits for as long as `paused` says the warm-up is paused.
+async fn hold_while_paused(paused: &impl Fn() -> bool) {
+    while paused() {
+        paused_poll_sleep().await;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn warm_cover_art_urls(urls: &[String]) -> Result<usize, String> {
+    let payload = serde_json::to_string(urls).map_err(|error| error.to_string())?;
+    let script = format!(
+        r#"
+(() => {{
+  const urls = {payload};
+  if (!Array.isArray(urls) || urls.length === 0) return 0;
+  const enqueue = window.__rustyCoverArtEnqueue;
+  let queued = 0;
+  for (const raw of urls) {{
+    if (typeof raw !== "string" || !raw.includes("/rest/getCoverArt?")) continue;
+    if (typeof enqueue === "function") {{
+      if (enqueue(raw)) queued++;
+    }} else {{
+      const img = new Image();
+      img.setAttribute("src", raw);
+      queued++;
+    }}
+  }}
+  return queued;
+}})()
+        "#
+    );
+
+    let result = js_sys::eval(&script).map_err(|error| format!("{error:?}"))?;
+    Ok(result.as_f64().unwrap_or(0.0).round().max(0.0) as usize)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn warm_cover_art_urls(_urls: &[String]) -> Result<usize, String> {
+    Ok(0)
+}
+
+/// Which parts of the warm-up run. Album details are fetched for whichever album lists
+/// are selected.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct SmartCachePhases {
+    pub(super) newest_albums: bool,
+    pub(super) frequent_albums: bool,
+    pub(super) playlists: bool,
+    pub(super) starred: bool,
+    pub(super) random_songs: bool,
+    pub(super) lyrics: bool,
+    pub(super) artwork: bool,
+}
+
+impl Default for SmartCachePhases {
+    fn default() -> Self {
+        Self {
+            newest_albums: true,
+            frequent_albums: true,
+            playlists: true,
+            starred: true,
+            random_songs: true,
+            lyrics: true,
+            artwork: true,
+        }
+    }
+}
+
+impl SmartCachePhases {
+    fn album_details(&self) -> bool {
+        self.newest_albums || self.frequent_albums
+    }
+
+    /// Metadata steps run against each server.
+    fn server_steps(&self) -> usize {
+        [
+            self.newest_albums,
+            self.frequent_albums,
+            self.album_details(),
+            self.playlists,
+            self.starred,
+            self.random_songs,
+        ]
+        .into_iter()
+        .filter(|selected| *selected)
+        .count()
+    }
+
+    pub(super) fn any(&self) -> bool {
+        self.server_steps() > 0 || self.lyrics || self.artwork
+    }
+}
+
+/// Everything one warm-up pass needs; counts are per server except lyrics and artwork.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct SmartCacheConfig {
+    pub(super) phases: SmartCachePhases,
+    pub(super) newest_albums_per_server: u32,
+    pub(super) frequent_albums_per_server: u32,
+    pub(super) album_details_per_server: usize,
+    pub(super) playlists_per_server: usize,
+    pub(super) random_songs_per_server: u32,
+    pub(super) lyrics_limit: usize,
+    pub(super) artwork_limit: usize,
+    pub(super) lyrics_provider_order: Vec<String>,
+    pub(super) lyrics_timeout_secs: u32,
+    pub(super) cache_images_enabled: bool,
+    pub(super) artwork_pref: ArtworkDownloadPreference,
+}
+
+impl SmartCacheConfig {
+    /// Every phase, with counts sized from the configured cache budget.
+    pub(super) fn from_settings(settings: &AppSettings) -> Self {
+        let cache_size_mb = settings.cache_size_mb.clamp(25, 2048);
+        let albums_per_server = smart_cache_albums_per_server(cache_size_mb);
+        Self {
+            phases: SmartCachePhases::default(),
+            newest_albums_per_server: albums_per_server,
+            frequent_albums_per_server: albums_per_server,
+            album_details_per_server: smart_cache_album_details_per_server(cache_size_mb),
+            playlists_per_server: smart_cache_playlists_per_server(cache_size_mb),
+            random_songs_per_server: smart_cache_random_songs_per_server(cache_size_mb),
+            lyrics_limit: smart_cache_lyrics_limit(cache_size_mb),
+            artwork_limit: smart_cache_artwork_limit(cache_size_mb),
+            lyrics_provider_order: normalize_lyrics_provider_order(&settings.lyrics_provider_order),
+            lyrics_timeout_secs: settings.lyrics_request_timeout_secs.clamp(1, 20),
+            cache_images_enabled: settings.cache_images_enabled,
+            artwork_pref: settings.artwork_download_preference,
+        }
+    }
+
+    /// Status line shown before the first request goes out.
+    pub(super) fn plan_status(&self) -> String {
+        let lyrics_limit = if self.phases.lyrics {
+            self.lyrics_limit
+        } else {
+            0
+        };
+        format!(
+            "Smart cache: planning {lyrics_limit} lyric lookups and up to {} artwork variants...",
+            self.effective_artwork_limit()
+        )
+    }
+
+    /// Artwork variants to collect, zero when artwork is skipped or image caching is off.
+    fn effective_artwork_limit(&self) -> usize {
+        if self.phases.artwork && self.cache_images_enabled {
+            self.artwork_limit
+        } else {
+            0
+        }
+    }
+}
+
+/// Maps finished work onto 0-100. Skipped phases hand their share to the selected ones.
+#[derive(Clone, Copy, Debug)]
+struct SmartCacheProgress {
+    metadata_span: f64,
+    lyrics_span: f64,
+    total_steps: f64,
+    steps_done: f64,
+}
+
+impl SmartCacheProgress {
+    fn new(phases: &SmartCachePhases, server_count: usize) -> Self {
+        let total_steps = (phases.server_steps() * server_count) as f64;
+        let metadata = if total_steps > 0.0 {
+            METADATA_PROGRESS_WEIGHT
+        } else {
+            0.0
+        };
+        let lyrics = if phases.lyrics {
+            LYRICS_PROGRESS_WEIGHT
+        } else {
+            0.0
+        };
+        let artwork = if phases.artwork {
+            ARTWORK_PROGRESS_WEIGHT
+        } else {
+            0.0
+        };
+        let scale = 100.0 / (metadata + lyrics + artwork).max(1.0);
+        Self {
+            metadata_span: metadata * scale,
+            lyrics_span: lyrics * scale,
+            total_steps,
+            steps_done: 0.0,
+        }
+    }
+
+    fn percent(value: f64) -> u8 {
+        value.round().clamp(0.0, 100.0) as u8
+    }
+
+    fn server_step_done(&mut self) -> u8 {
+        self.steps_done += 1.0;
+        Self::percent(self.metadata_span * (self.steps_done / self.total_steps.max(1.0)))
+    }
+
+    fn lyrics(&self, done: usize, total: usize) -> u8 {
+        let fraction = if total == 0 {
+            1.0
+        } else {
+            done as f64 / total as f64
+        };
+        Self::percent(self.metadata_span + self.lyrics_span * fraction)
+    }
+}
+
+pub(super) enum SmartCacheUpdate {
+    Progress(u8),
+    Status(String),
+}
+
+#[derive(Clone, Debug, Default)]
+pub(super) struct SmartCacheReport {
+    songs: usize,
+    album_details: usize,
+    playlists: usize,
+    lyrics: usize,
+    artwork_queued: usize,
+    artwork_limit: usize,
+    throttled: bool,
+}
+
+impl SmartCacheReport {
+    pub(super) fn summary(&self) -> String {
+        let adapted_note = if self.throttled {
+            " Batches were reduced because the server responded slowly."
+        } else {
+            ""
+        };
+        format!(
+            "Smart cache complete: {} songs, {} album details, {} playlists, {} lyrics, {} artwork requests queued (limit {}).{}",
+            self.songs,
+            self.album_details,
+            self.playlists,
+            self.lyrics,
+            self.artwork_queued,
+            self.artwork_limit,
+            adapted_note
+        )
+    }
+}
+
+/// Runs the selected warm-up phases against `servers`, then warms lyrics and queues artwork
+/// for the songs and covers collected on the way. Between requests it waits for as long as
+/// `paused` returns true.
+pub(super) async fn warm_smart_cache(
+    servers: Vec<ServerConfig>,
+    config: SmartCacheConfig,
+    mut report: impl FnMut(SmartCacheUpdate),
+    paused: impl Fn() -> bool,
+) -> SmartCacheReport {
+    let phases = config.phases;
+    let artwork_limit = config.effective_artwork_limit();
+    let artwork_pref = config.artwork_pref;
+    let mut progress = SmartCacheProgress::new(&phases, servers.len());
+    let mut summary = SmartCacheReport {
+        artwork_limit,
+        ..SmartCacheReport::default()
+    };
+
+    let mut collected_songs = Vec::<Song>::new();
+    let mut seen_song_keys = HashSet::<String>::new();
+    let mut cover_urls = Vec::<String>::new();
+    let mut seen_cover_requests = HashSet::<String>::new();
+    let mut throttle = SmartCacheThrottle::default();
+
+    for server in servers.iter().cloned() {
+        if phases.server_steps() == 0 {
+            break;
+        }
+        let client = NavidromeClient::new(server.clone());
+        report(SmartCacheUpdate::Status(format!(
+            "Smart cache: loading metadata for {}...{}",
+            server.name,
+            throttle.status_note()
+        )));
+
+        let mut album_lists = Vec::<Vec<Album>>::new();
+        for (selected, list_type, limit) in [
+            (
+                phases.newest_albums,
+                "newest",
+                config.newest_albums_per_server,
+            ),
+            (
+                phases.frequent_albums,
+                "frequent",
+                config.frequent_albums_per_server,
+            ),
+        ] {
+            if !selected {
+                continue;
+            }
+            let started = PerfTimer::now();
+            let result = client
+                .get_albums(list_type, throttle.limit_u32(limit), 0, None)
+                .await;
+            throttle.observe(started.elapsed_ms(), &result);
+            let albums = result.unwrap_or_default();
+            throttle.wait().await;
+            hold_while_paused(&paused).await;
+            collect_album_cover_urls(
+                &server,
+                &albums,
+                &mut cover_urls,
+                &mut seen_cover_requests,
+                artwork_limit,
+                artwork_pref,
+            );
+            album_lists.push(albums);
+            report(SmartCacheUpdate::Progress(progress.server_step_done()));
+        }
+
+        if phases.album_details() {
+            for (album_details_done, album) in album_lists.iter().flatten().enumerate() {
+                // Re-read each time so a slowing server shortens the remaining batch.
+                if album_details_done >= throttle.limit(config.album_details_per_server) {
+                    break;
+                }
+                let started = PerfTimer::now();
+                let result = client.get_album(&album.id).await;
+                throttle.observe(started.elapsed_ms(), &result);
+                if let Ok((_, songs)) = result {
+                    summary.album_details += 1;
+                    collect_song_cover_urls(
+                        &server,
+                        &songs,
+                        &mut cover_urls,
+                        &mut seen_cover_requests,
+                        artwork_limit,
+                        artwork_pref,
+                    );
+                    push_unique_songs(&mut collected_songs, &mut seen_song_keys, songs);
+                }
+                smart_cache_pause(20).await;
+                throttle.wait().await;
+                hold_while_paused(&paused).await;
+            }
+            report(SmartCacheUpdate::Progress(progress.server_step_done()));
+            if throttle.is_backing_off() {
+                report(SmartCacheUpdate::Status(format!(
+                    "Smart cache: loading metadata for {}...{}",
+                    server.name,
+                    throttle.status_note()
+                )));
+            }
+        }
+
+        if phases.playlists {
+            let started = PerfTimer::now();
+            let result = client.get_playlists().await;
+            throttle.observe(started.elapsed_ms(), &result);
+            let playlists = result.unwrap_or_default();
+            collect_playlist_cover_urls(
+                &server,
+                &playlists,
+                &mut cover_urls,
+                &mut seen_cover_requests,
+                artwork_limit,
+                artwork_pref,
+            );
+            for (playlists_done, playlist) in playlists.iter().enumerate() {
+                if playlists_done >= throttle.limit(config.playlists_per_server) {
+                    break;
+                }
+                let started = PerfTimer::now();
+                let result = client.get_playlist(&playlist.id).await;
+                throttle.observe(started.elapsed_ms(), &result);
+                if let Ok((_, songs)) = result {
+                    summary.playlists += 1;
+                    collect_song_cover_urls(
+                        &server,
+                        &songs,
+                        &mut cover_urls,
+                        &mut seen_cover_requests,
+                        artwork_limit,
+                        artwork_pref,
+                    );
+                    push_unique_songs(&mut collected_songs, &mut seen_song_keys, songs);
+                }
+                smart_cache_pause(20).await;
+                throttle.wait().await;
+                hold_while_paused(&paused).await;
+            }
+            report(SmartCacheUpdate::Progress(progress.server_step_done()));
+        }
+
+        if phases.starred {
+            let started = PerfTimer::now();
+            let result = client.get_starred().await;
+            throttle.observe(started.elapsed_ms(), &result);
+            throttle.wait().await;
+            hold_while_paused(&paused).await;
+            if let Ok((_, starred_albums, starred_songs)) = result {
+                collect_album_cover_urls(
+                    &server,
+                    &starred_albums,
+                    &mut cover_urls,
+                    &mut seen_cover_requests,
+                    artwork_limit,
+                    artwork_pref,
+                );
+                collect_song_cover_urls(
+                    &server,
+                    &starred_songs,
+                    &mut cover_urls,
+                    &mut seen_cover_requests,
+                    artwork_limit,
+                    artwork_pref,
+                );
+                push_unique_songs(&mut collected_songs, &mut seen_song_keys, starred_songs);
+            }
+            report(SmartCacheUpdate::Progress(progress.server_step_done()));
+        }
+
+        if phases.random_songs {
+            let started = PerfTimer::now();
+            let result = client
+                .get_random_songs(throttle.limit_u32(config.random_songs_per_server))
+                .await;
+            throttle.observe(started.elapsed_ms(), &result);
+            let random_songs = result.unwrap_or_default();
+            throttle.wait().await;
+            hold_while_paused(&paused).await;
+            collect_song_cover_urls(
+                &server,
+                &random_songs,
+                &mut cover_urls,
+                &mut seen_cover_requests,
+                artwork_limit,
+                artwork_pref,
+            );
+            push_unique_songs(&mut collected_songs, &mut seen_song_keys, random_songs);
+            report(SmartCacheUpdate::Progress(progress.server_step_done()));
+        }
+    }
+
+    if phases.lyrics {
+        let mut lyric_candidates = collected_songs.clone();
+        lyric_candidates.truncate(config.lyrics_limit);
+        let lyric_total = lyric_candidates.len();
+        for (index, song) in lyric_candidates.into_iter().enumerate() {
+            report(SmartCacheUpdate::Status(format!(
+                "Smart cache: warming lyrics ({}/{})...",
+                index + 1,
+                lyric_total
+            )));
+            let query = LyricsQuery::from_song(&song);
+            if !query.title.trim().is_empty()
+                && fetch_lyrics_with_fallback(
+                    &query,
+                    &config.lyrics_provider_order,
+                    config.lyrics_timeout_secs,
+                )
+                .await
+                .is_ok()
+            {
+                summary.lyrics += 1;
+            }
+            report(SmartCacheUpdate::Progress(
+                progress.lyrics(index + 1, lyric_total),
+            ));
+            smart_cache_pause(35).await;
+            hold_while_paused(&paused).await;
+        }
+        report(SmartCacheUpdate::Progress(progress.lyrics(1, 1)));
+    }
+
+    if artwork_limit > 0 {
+        let unique_cover_urls = dedupe_trim_urls(cover_urls, artwork_limit);
+        if !unique_cover_urls.is_empty() {
+            report(SmartCacheUpdate::Status(format!(
+                "Smart cache: queueing artwork ({} URLs)...",
+                unique_cover_urls.len()
+            )));
+            match warm_cover_art_urls(&unique_cover_urls) {
+                Ok(queued) => summary.artwork_queued = queued,
+                Err(error) => report(SmartCacheUpdate::Status(format!(
+                    "Smart cache: artwork prefetch warning: {}",
+                    error
+                ))),
+            }
+        }
+    }
+
+    report(SmartCacheUpdate::Progress(100));
+    summary.songs = collected_songs.len();
+    summary.throttled = throttle.is_backing_off();
+    summary
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SmartCacheOption {
+    NewestAlbums,
+    FrequentAlbums,
+    Playlists,
+    Starred,
+    RandomSongs,
+    Lyrics,
+    Artwork,
+}
+
+impl SmartCacheOption {
+    const ALL: [SmartCacheOption; 7] = [
+        SmartCacheOption::NewestAlbums,
+        SmartCacheOption::FrequentAlbums,
+        SmartCacheOption::Playlists,
+        SmartCacheOption::Starred,
+        SmartCacheOption::RandomSongs,
+        SmartCacheOption::Lyrics,
+        SmartCacheOption::Artwork,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            SmartCacheOption::NewestAlbums => "Newest albums",
+            SmartCacheOption::FrequentAlbums => "Frequent albums",
+            SmartCacheOption::Playlists => "Playlists",
+            SmartCacheOption::Starred => "Starred",
+            SmartCacheOption::RandomSongs => "Random songs",
+            SmartCacheOption::Lyrics => "Lyrics",
+            SmartCacheOption::Artwork => "Artwork",
+        }
+    }
+
+    fn count_hint(self) -> Option<&'static str> {
+        match self {
+            SmartCacheOption::NewestAlbums
+            | SmartCacheOption::FrequentAlbums
+            | SmartCacheOption::RandomSongs => Some("per server"),
+            SmartCacheOption::Playlists => Some("opened per server"),
+            SmartCacheOption::Lyrics => Some("songs"),
+            SmartCacheOption::Artwork => Some("variants"),
+            SmartCacheOption::Starred => None,
+        }
+    }
+
+    fn max_count(self) -> usize {
+        match self {
+            SmartCacheOption::NewestAlbums | SmartCacheOption::FrequentAlbums => 500,
+            SmartCacheOption::Playlists => 200,
+            SmartCacheOption::RandomSongs => 500,
+            SmartCacheOption::Lyrics => 5000,
+            SmartCacheOption::Artwork => 20_000,
+            SmartCacheOption::Starred => 0,
+        }
+    }
+
+    fn selected(self, phases: &SmartCachePhases) -> bool {
+        match self {
+            SmartCacheOption::NewestAlbums => phases.newest_albums,
+            SmartCacheOption::FrequentAlbums => phases.frequent_albums,
+            SmartCacheOption::Playlists => phases.playlists,
+            SmartCacheOption::Starred => phases.starred,
+            SmartCacheOption::RandomSongs => phases.random_songs,
+            SmartCacheOption::Lyrics => phases.lyrics,
+            SmartCacheOption::Artwork => phases.artwork,
+        }
+    }
+
+    fn set_selected(self, phases: &mut SmartCachePhases, selected: bool) {
+        let flag = match self {
+            SmartCacheOption::NewestAlbums => &mut phases.newest_albums,
+            SmartCacheOption::FrequentAlbums => &mut phases.frequent_albums,
+            SmartCacheOption::Playlists => &mut phases.playlists,
+            SmartCacheOption::Starred => &mut phases.starred,
+            SmartCacheOption::RandomSongs => &mut phases.random_songs,
+            SmartCacheOption::Lyrics => &mut phases.lyrics,
+            SmartCacheOption::Artwork => &mut phases.artwork,
+        };
+        *flag = selected;
+    }
+
+    fn count(self, config: &SmartCacheConfig) -> Option<usize> {
+        match self {
+            SmartCacheOption::NewestAlbums => Some(config.newest_albums_per_server as usize),
+            SmartCacheOption::FrequentAlbums => Some(config.frequent_albums_per_server as usize),
+            SmartCacheOption::Playlists => Some(config.playlists_per_server),
+            SmartCacheOption::RandomSongs => Some(config.random_songs_per_server as usize),
+            SmartCacheOption::Lyrics => Some(config.lyrics_limit),
+            SmartCacheOption::Artwork => Some(config.artwork_limit),
+            SmartCacheOption::Starred => None,
+        }
+    }
+
+    fn set_count(self, config: &mut SmartCacheConfig, count: usize) {
+        let count = count.clamp(1, self.max_count());
+        match self {
+            SmartCacheOption::NewestAlbums => config.newest_albums_per_server = count as u32,
+            SmartCacheOption::FrequentAlbums => config.frequent_albums_per_server = count as u32,
+            SmartCacheOption::Playlists => config.playlists_per_server = count,
+            SmartCacheOption::RandomSongs => config.random_songs_per_server = count as u32,
+            SmartCacheOption::Lyrics => config.lyrics_limit = count,
+            SmartCacheOption::Artwork => config.artwork_limit = count,
+            SmartCacheOption::Starred => {}
+        }
+    }
+}
+
+/// Pre-run sheet for the warm-up: pick the phases and adjust their counts, which start
+/// at the cache-size heuristics in `defaults`.
+#[component]
+pub(super) fn SmartCacheOptionsSheet(
+    defaults: SmartCacheConfig,
+    on_start: EventHandler<SmartCacheConfig>,
+    on_close: EventHandler<()>,
+) -> Element {
+    let mut draft = use_signal(|| defaults.clone());
+    let config = draft();
+    let can_start = config.phases.any();
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-[10000] flex items-center justify-center bg-black/60",
+            onclick: move |evt: MouseEvent| {
+                evt.stop_propagation();
+                on_close.call(());
+            },
+            div {
+                class: "bg-zinc-900 border border-zinc-700 rounded-2xl p-6 max-w-lg w-full mx-4 shadow-2xl space-y-4 max-h-[85vh] flex flex-col",
+                onclick: move |evt: MouseEvent| evt.stop_propagation(),
+                div {
+                    h3 { class: "text-lg font-semibold text-white", "Smart Cache Warm-up" }
+                    p { class: "text-sm text-zinc-400",
+                        "Choose what to prefetch. Counts start from your cache size and can be changed for this run."
+                    }
+                }
+                div { class: "min-h-0 flex-1 overflow-y-auto space-y-2",
+                    for option in SmartCacheOption::ALL {
+                        div {
+                            key: "{option.label()}",
+                            class: "flex items-center justify-between gap-3 px-3 py-2 rounded-lg bg-zinc-800/40 border border-zinc-700/50",
+                            label { class: "flex items-center gap-3 cursor-pointer min-w-0",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "w-4 h-4 rounded cursor-pointer",
+                                    checked: option.selected(&config.phases),
+                                    onchange: move |e: Event<FormData>| {
+                                        let checked = e.checked();
+                                        draft.with_mut(|config| option.set_selected(&mut config.phases, checked));
+                                    },
+                                }
+                                span { class: "text-sm text-zinc-200", "{option.label()}" }
+                            }
+                            if let Some(count) = option.count(&config) {
+                                div { class: "flex items-center gap-2 shrink-0",
+                                    input {
+                                        r#type: "number",
+                                        min: "1",
+                                        max: "{option.max_count()}",
+                                        value: "{count}",
+                                        disabled: !option.selected(&config.phases),
+                                        aria_label: "{option.label()} count",
+                                        class: "w-24 px-2 py-1 rounded-lg border border-zinc-700 bg-zinc-900 text-white text-sm focus:outline-none focus:border-emerald-500/50 disabled:opacity-50",
+                                        onchange: move |e: Event<FormData>| {
+                                            if let Ok(count) = e.value().trim().parse::<usize>() {
+                                                draft.with_mut(|config| option.set_count(config, count));
+                                            }
+                                        },
+                                    }
+                                    if let Some(hint) = option.count_hint() {
+                                        span { class: "text-xs text-zinc-500 w-28", "{hint}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                if config.phases.lyrics && config.phases.server_steps() == 0 {
+                    p { class: "text-xs text-amber-300",
+                        "Lyrics are warmed for songs found by the other phases; select at least one."
+                    }
+                }
+                if config.phases.artwork && !config.cache_images_enabled {
+                    p { class: "text-xs text-amber-300",
+                        "Image caching is off, so artwork will be skipped."
+                    }
+                }
+                div { class: "flex items-center justify-between gap-3",
+                    button {
+                        class: "px-3 py-2 rounded-lg border border-zinc-700 text-zinc-400 hover:text-white transition-colors text-sm",
+                        onclick: move |_| draft.set(defaults.clone()),
+                        "Reset"
+                    }
+                    div { class: "flex items-center gap-2",
+                        button {
+                            class: "px-3 py-2 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white transition-colors text-sm",
+                            onclick: move |_| on_close.call(()),
+                            "Cancel"
+                        }
+                        button {
+                            class: if can_start { "px-3 py-2 rounded-lg border border-emerald-500/40 text-emerald-300 hover:text-white hover:border-emerald-400/70 transition-colors text-sm flex items-center gap-2" } else { "px-3 py-2 rounded-lg border border-zinc-700 text-zinc-500 cursor-not-allowed text-sm flex items-center gap-2" },
+                            disabled: !can_start,
+                            onclick: move |_| on_start.call(draft()),
+                            Icon { name: "play".to_string(), class: "w-4 h-4".to_string() }
+                            "Start warm-up"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_spreads_skipped_phases_over_selected_ones() {
+        let all = SmartCachePhases::default();
+        let mut progress = SmartCacheProgress::new(&all, 2);
+        for _ in 0..11 {
+            progress.server_step_done();
+        }
+        assert_eq!(progress.server_step_done(), 60);
+        assert_eq!(SmartCacheProgress::new(&all, 2).lyrics(1, 1), 85);
+
+        let playlists_only = SmartCachePhases {
+            newest_albums: false,
+            frequent_albums: false,
+            starred: false,
+            random_songs: false,
+            lyrics: false,
+            artwork: false,
+            ..SmartCachePhases::default()
+        };
+        let mut progress = SmartCacheProgress::new(&playlists_only, 2);
+        assert_eq!(progress.server_step_done(), 50);
+        assert_eq!(progress.server_step_done(), 100);
+
+        let playlists_and_lyrics = SmartCachePhases {
+            lyrics: true,
+            ..playlists_only
+        };
+        let mut progress = SmartCacheProgress::new(&playlists_and_lyrics, 1);
+        assert_eq!(progress.server_step_done(), 71);
+        assert_eq!(progress.lyrics(1, 1), 100);
+    }
+}
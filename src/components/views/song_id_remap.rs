@@ -0,0 +1,351 @@
+//! Settings card that re-points local records at new song ids after a server rebuilt its
+//! library database. Checking samples stored ids against the server; the preview matches
+//! every stale record against the rebuilt library and nothing is rewritten until the user
+//! applies it.
+use crate::api::{NavidromeClient, ServerConfig, Song};
+use crate::db::{
+    apply_song_id_remap, history_song_identities, looks_like_library_rebuild, plan_song_id_remap,
+    remap_song, AppSettings, SongIdRemapPlan, SongIdentity,
+};
+use crate::offline_audio::{list_downloaded_entries, remap_downloaded_song_ids};
+use dioxus::prelude::*;
+use std::collections::{BTreeMap, HashSet};
+
+/// Stored ids asked about per check; enough to tell a rebuild from a few deleted songs.
+const CHECK_SAMPLE_SIZE: usize = 20;
+/// Records of each kind listed under the preview counts.
+const PREVIEW_EXAMPLES: usize = 5;
+
+#[derive(Debug, Clone, PartialEq)]
+enum RemapStep {
+    Checked {
+        server_id: String,
+        checked: usize,
+        missing: usize,
+    },
+    Planned {
+        server_id: String,
+        plan: SongIdRemapPlan,
+    },
+}
+
+/// Every song of `server_id` stored locally: downloads first, as they keep the file path,
+/// then the queue and the listening history.
+async fn stored_song_records(server_id: &str, queued: Vec<Song>) -> Vec<SongIdentity> {
+    let mut seen = HashSet::new();
+    let downloads = list_downloaded_entries()
+        .into_iter()
+        .filter(|entry| entry.server_id == server_id)
+        .map(|entry| SongIdentity {
+            id: entry.song_id,
+            title: entry.title,
+            artist: entry.artist,
+            album: entry.album,
+            duration_secs: entry.duration,
+            path: entry.path,
+        });
+    let queued = queued
+        .iter()
+        .filter(|song| song.server_id == server_id)
+        .map(SongIdentity::from_song);
+    downloads
+        .chain(queued)
+        .chain(history_song_identities(server_id).await)
+        .filter(|record| seen.insert(record.id.clone()))
+        .collect()
+}
+
+/// Remaps downloads and stored records as one step: the downloads move first and go back
+/// if the stored records can't be rewritten. Returns how many downloads moved.
+async fn apply_remap_everywhere(
+    server_id: &str,
+    remap: &BTreeMap<String, String>,
+) -> Result<usize, String> {
+    let downloads = remap_downloaded_song_ids(server_id, remap)?;
+    match apply_song_id_remap(server_id.to_string(), remap.clone()).await {
+        Ok(()) => Ok(downloads.commit()),
+        Err(error) => {
+            downloads.roll_back();
+            Err(error.to_string())
+        }
+    }
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+fn record_label(record: &SongIdentity) -> String {
+    match record.artist.as_deref() {
+        Some(artist) if !artist.trim().is_empty() => format!("{} · {artist}", record.title),
+        _ => record.title.clone(),
+    }
+}
+
+/// "Library rebuilt?" card: checks whether a server's song ids went stale and remaps local
+/// downloads, queue, history, resume points, offsets and playlist notes to the new ids.
+#[component]
+pub(super) fn SongIdRemapSection() -> Element {
+    let servers = use_context::<Signal<Vec<ServerConfig>>>();
+    let mut app_settings = use_context::<Signal<AppSettings>>();
+    let mut queue = use_context::<Signal<Vec<Song>>>();
+    let mut now_playing = use_context::<Signal<Option<Song>>>();
+    let mut step = use_signal(|| None::<RemapStep>);
+    let mut busy = use_signal(|| false);
+    let mut status = use_signal(|| None::<Result<String, String>>);
+
+    let active_servers: Vec<ServerConfig> = servers()
+        .into_iter()
+        .filter(|server| server.active)
+        .collect();
+    if active_servers.is_empty() {
+        return rsx! {};
+    }
+
+    let queued_songs = move || {
+        let mut songs = queue.peek().clone();
+        songs.extend(now_playing.peek().clone());
+        songs
+    };
+
+    let mut on_check = move |server: ServerConfig| {
+        if busy() {
+            return;
+        }
+        busy.set(true);
+        status.set(None);
+        step.set(None);
+        spawn(async move {
+            let records = stored_song_records(&server.id, queued_songs()).await;
+            let client = NavidromeClient::new(server.clone());
+            let (mut checked, mut missing) = (0usize, 0usize);
+            let mut failure = None;
+            for record in records.iter().take(CHECK_SAMPLE_SIZE) {
+                match client.get_song_fresh(&record.id).await {
+                    Ok(_) => checked += 1,
+                    Err(error) if error.is_not_found() => {
+                        checked += 1;
+                        missing += 1;
+                    }
+                    Err(error) if error.is_unreachable() => {
+                        failure = Some(format!("{} is unreachable: {error}", server.name));
+                        break;
+                    }
+                    Err(_) => {}
+                }
+            }
+            match failure {
+                Some(message) => status.set(Some(Err(message))),
+                None if checked == 0 => status.set(Some(Ok(format!(
+                    "No songs from {} are stored locally.",
+                    server.name
+                )))),
+                None => step.set(Some(RemapStep::Checked {
+                    server_id: server.id.clone(),
+                    checked,
+                    missing,
+                })),
+            }
+            busy.set(false);
+        });
+    };
+
+    let mut on_preview = move |server: ServerConfig| {
+        if busy() {
+            return;
+        }
+        busy.set(true);
+        status.set(None);
+        spawn(async move {
+            let records = stored_song_records(&server.id, queued_songs()).await;
+            match NavidromeClient::new(server.clone())
+                .get_library_songs()
+                .await
+            {
+                Ok(library) => step.set(Some(RemapStep::Planned {
+                    server_id: server.id.clone(),
+                    plan: plan_song_id_remap(&records, &library),
+                })),
+                Err(error) => status.set(Some(Err(format!(
+                    "Could not read the library of {}: {error}",
+                    server.name
+                )))),
+            }
+            busy.set(false);
+        });
+    };
+
+    let mut on_apply = move |server: ServerConfig, plan: SongIdRemapPlan| {
+        if busy() {
+            return;
+        }
+        busy.set(true);
+        status.set(None);
+        spawn(async move {
+            match apply_remap_everywhere(&server.id, &plan.remap).await {
+                Ok(moved_downloads) => {
+                    // Settings, queue and now playing live in signals that get saved again;
+                    // left on the old ids they would undo the remap on their next save.
+                    app_settings
+                        .with_mut(|settings| settings.remap_song_ids(&server.id, &plan.remap));
+                    queue.with_mut(|songs| {
+                        for song in songs.iter_mut() {
+                            remap_song(song, &server.id, &plan.remap);
+                        }
+                    });
+                    let playing_remapped = now_playing.peek().as_ref().is_some_and(|song| {
+                        song.server_id == server.id && plan.remap.contains_key(&song.id)
+                    });
+                    if playing_remapped {
+                        now_playing.with_mut(|song| {
+                            if let Some(song) = song.as_mut() {
+                                remap_song(song, &server.id, &plan.remap);
+                            }
+                        });
+                    }
+
+                    step.set(None);
+                    status.set(Some(Ok(format!(
+                        "Remapped {} song{} on {} ({} download{} moved).",
+                        plan.remap.len(),
+                        plural(plan.remap.len()),
+                        server.name,
+                        moved_downloads,
+                        plural(moved_downloads)
+                    ))));
+                }
+                Err(error) => status.set(Some(Err(format!("Remap failed: {error}")))),
+            }
+            busy.set(false);
+        });
+    };
+
+    rsx! {
+        section { class: "order-3 bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+            h2 { class: "text-lg font-semibold text-white mb-1", "Library Rebuilt?" }
+            p { class: "text-sm text-zinc-400 mb-4",
+                "If a server rebuilt its library database, its songs get new ids and downloads, history, resume points and playlist notes stop finding them. Check a server, preview the matches, then apply."
+            }
+            div { class: "space-y-3",
+                for server in active_servers {
+                    div {
+                        key: "{server.id}",
+                        class: "p-4 rounded-xl bg-zinc-900/50 border border-zinc-700/50 space-y-3",
+                        div { class: "flex items-center justify-between gap-3",
+                            p { class: "text-sm text-white truncate min-w-0", "{server.name}" }
+                            button {
+                                class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-emerald-500/60 transition-colors text-sm flex-shrink-0 disabled:opacity-50",
+                                disabled: busy(),
+                                onclick: {
+                                    let server = server.clone();
+                                    move |_| on_check(server.clone())
+                                },
+                                "Check ids"
+                            }
+                        }
+                        match step() {
+                            Some(RemapStep::Checked { server_id, checked, missing })
+                                if server_id == server.id =>
+                            {
+                                let suspected = looks_like_library_rebuild(checked, missing);
+                                rsx! {
+                                    p { class: if suspected { "text-sm text-amber-300" } else { "text-sm text-zinc-400" },
+                                        "{missing} of {checked} stored songs are no longer found on this server."
+                                        if suspected {
+                                            " The library looks rebuilt."
+                                        } else {
+                                            " That looks like removed songs rather than a rebuilt library."
+                                        }
+                                    }
+                                    button {
+                                        class: "px-3 py-1.5 rounded-lg bg-emerald-500/15 text-emerald-300 hover:bg-emerald-500/25 transition-colors text-sm disabled:opacity-50",
+                                        disabled: busy() || missing == 0,
+                                        onclick: {
+                                            let server = server.clone();
+                                            move |_| on_preview(server.clone())
+                                        },
+                                        if busy() {
+                                            "Matching..."
+                                        } else {
+                                            "Preview remap"
+                                        }
+                                    }
+                                }
+                            }
+                            Some(RemapStep::Planned { server_id, plan })
+                                if server_id == server.id =>
+                            {
+                                let remapped = plan.remap.len();
+                                let ambiguous = plan.ambiguous.len();
+                                let unmatched = plan.unmatched.len();
+                                let by_path = plan.matched_by_path;
+                                let examples = [
+                                    ("Ambiguous", plan.ambiguous.clone()),
+                                    ("Unmatched", plan.unmatched.clone()),
+                                ];
+                                rsx! {
+                                    div { class: "space-y-1 text-sm",
+                                        p { class: "text-white",
+                                            "{remapped} song{plural(remapped)} can be remapped ({by_path} by file path)."
+                                        }
+                                        p { class: "text-zinc-400",
+                                            "{ambiguous} ambiguous and {unmatched} unmatched record{plural(ambiguous + unmatched)} will be left as they are."
+                                        }
+                                    }
+                                    for (label , records) in examples {
+                                        if !records.is_empty() {
+                                            div { class: "space-y-0.5",
+                                                p { class: "text-[10px] uppercase tracking-[0.18em] text-zinc-500", "{label}" }
+                                                for record in records.iter().take(PREVIEW_EXAMPLES) {
+                                                    p { class: "text-xs text-zinc-400 truncate", "{record_label(record)}" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                    div { class: "flex items-center gap-2",
+                                        button {
+                                            class: "px-3 py-1.5 rounded-lg bg-amber-500/20 text-amber-300 text-sm hover:bg-amber-500/30 transition-colors disabled:opacity-50",
+                                            disabled: busy() || plan.is_empty(),
+                                            onclick: {
+                                                let server = server.clone();
+                                                let plan = plan.clone();
+                                                move |_| on_apply(server.clone(), plan.clone())
+                                            },
+                                            if busy() {
+                                                "Applying..."
+                                            } else {
+                                                "Apply remap"
+                                            }
+                                        }
+                                        button {
+                                            class: "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-400 hover:text-white transition-colors text-sm disabled:opacity-50",
+                                            disabled: busy(),
+                                            onclick: move |_| step.set(None),
+                                            "Cancel"
+                                        }
+                                    }
+                                }
+                            }
+                            _ => rsx! {},
+                        }
+                    }
+                }
+            }
+            {
+                match status() {
+                    Some(Ok(message)) => rsx! {
+                        p { class: "mt-3 text-sm text-emerald-400", "{message}" }
+                    },
+                    Some(Err(message)) => rsx! {
+                        p { class: "mt-3 text-sm text-red-400", "{message}" }
+                    },
+                    None => rsx! {},
+                }
+            }
+        }
+    }
+}
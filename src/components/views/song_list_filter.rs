@@ -0,0 +1,243 @@
+//! Local filtering of song lists that are already loaded (the queue, Up Next, playlists).
+//!
+//! Matching follows the search page: every word typed must appear in the title, artist or
+//! album, ignoring case, accents and punctuation. Views filter their row order rather than
+//! the list itself, so each visible row keeps its real index for playback and editing.
+use crate::api::Song;
+use crate::collation::{search_text, search_tokens};
+use crate::components::Icon;
+use dioxus::prelude::*;
+
+fn song_list_key(song: &Song) -> String {
+    format!("{}:{}", song.server_id, song.id)
+}
+
+/// Filter text for one song list. The text belongs to the list it was typed against: once
+/// that list is replaced (its first song at the time is gone), the filter reads as empty.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct SongListFilter {
+    text: Signal<String>,
+    anchor: Signal<Option<String>>,
+}
+
+pub(crate) fn use_song_list_filter() -> SongListFilter {
+    SongListFilter {
+        text: use_signal(String::new),
+        anchor: use_signal(|| None::<String>),
+    }
+}
+
+impl SongListFilter {
+    /// The filter text in effect for `songs`.
+    pub(crate) fn query(&self, songs: &[Song]) -> String {
+        let text = (self.text)();
+        if text.trim().is_empty() {
+            return String::new();
+        }
+        let anchor = (self.anchor)();
+        let still_loaded = anchor
+            .as_deref()
+            .is_none_or(|anchor| songs.iter().any(|song| song_list_key(song) == anchor));
+        if still_loaded {
+            text
+        } else {
+            String::new()
+        }
+    }
+
+    pub(crate) fn set(&mut self, text: String, songs: &[Song]) {
+        self.anchor.set(songs.first().map(song_list_key));
+        self.text.set(text);
+    }
+}
+
+/// Whether every word of `tokens` appears in the song's title, artist or album.
+pub(crate) fn song_matches_filter(song: &Song, tokens: &[String]) -> bool {
+    let fields = [
+        search_text(&song.title),
+        song.artist.as_deref().map(search_text).unwrap_or_default(),
+        song.album.as_deref().map(search_text).unwrap_or_default(),
+    ];
+    tokens
+        .iter()
+        .all(|token| fields.iter().any(|field| field.contains(token.as_str())))
+}
+
+/// Keeps the entries of `order` (indices into `songs`) whose song matches `query`.
+pub(crate) fn filter_song_order(songs: &[Song], order: &[usize], query: &str) -> Vec<usize> {
+    let tokens = search_tokens(query);
+    if tokens.is_empty() {
+        return order.to_vec();
+    }
+    order
+        .iter()
+        .copied()
+        .filter(|index| {
+            songs
+                .get(*index)
+                .is_some_and(|song| song_matches_filter(song, &tokens))
+        })
+        .collect()
+}
+
+/// Splits `text` into runs, flagging the ones that match a word of `query`.
+pub(crate) fn highlight_segments(text: &str, query: &str) -> Vec<(String, bool)> {
+    let tokens = search_tokens(query);
+    let chars: Vec<char> = text.chars().collect();
+    if tokens.is_empty() || chars.is_empty() {
+        return vec![(text.to_string(), false)];
+    }
+
+    // Normalized characters with the position of the original character they came from.
+    let mut normalized = Vec::<char>::new();
+    let mut origin = Vec::<usize>::new();
+    for (position, ch) in chars.iter().enumerate() {
+        for folded in search_text(ch.encode_utf8(&mut [0; 4])).chars() {
+            normalized.push(folded);
+            origin.push(position);
+        }
+    }
+
+    let mut marked = vec![false; chars.len()];
+    for token in &tokens {
+        let token: Vec<char> = token.chars().collect();
+        if token.is_empty() || token.len() > normalized.len() {
+            continue;
+        }
+        for start in 0..=normalized.len() - token.len() {
+            if normalized[start..start + token.len()] == token[..] {
+                // Covers the punctuation in between too, so "Don't" highlights as one run.
+                let last = origin[start + token.len() - 1];
+                for flag in &mut marked[origin[start]..=last] {
+                    *flag = true;
+                }
+            }
+        }
+    }
+
+    let mut segments = Vec::<(String, bool)>::new();
+    for (ch, is_match) in chars.into_iter().zip(marked) {
+        match segments.last_mut() {
+            Some((run, run_match)) if *run_match == is_match => run.push(ch),
+            _ => segments.push((ch.to_string(), is_match)),
+        }
+    }
+    segments
+}
+
+/// Scrolls the row with DOM id `element_id` to the middle of the view.
+pub(crate) fn scroll_to_row(element_id: &str) {
+    let _ = document::eval(&format!(
+        r#"document.getElementById({element_id:?})
+  ?.scrollIntoView({{ behavior: "smooth", block: "center" }});"#
+    ));
+}
+
+/// `text` with the parts matching `query` highlighted.
+#[component]
+pub(crate) fn HighlightedText(text: String, query: String) -> Element {
+    if query.trim().is_empty() {
+        return rsx! { "{text}" };
+    }
+    rsx! {
+        for (run , is_match) in highlight_segments(&text, &query) {
+            if is_match {
+                mark { class: "bg-emerald-500/25 text-emerald-200 rounded-sm", "{run}" }
+            } else {
+                "{run}"
+            }
+        }
+    }
+}
+
+/// Filter box with "jump to" and "play from first match" for a loaded song list.
+#[component]
+pub(crate) fn SongListFilterBar(
+    value: String,
+    match_count: usize,
+    total: usize,
+    on_input: EventHandler<String>,
+    on_jump: EventHandler<()>,
+    on_play_first: EventHandler<()>,
+    #[props(default = "Filter songs".to_string())] placeholder: String,
+) -> Element {
+    let filtering = !value.trim().is_empty();
+    let has_match = match_count > 0;
+
+    rsx! {
+        div { class: "flex flex-col gap-2 sm:flex-row sm:items-center",
+            div { class: "relative flex-1 min-w-0",
+                Icon {
+                    name: "search".to_string(),
+                    class: "absolute left-3 top-1/2 -translate-y-1/2 w-4 h-4 text-zinc-500".to_string(),
+                }
+                input {
+                    class: "w-full pl-9 pr-9 py-2 bg-zinc-900/60 border border-zinc-800 rounded-xl text-sm text-white placeholder:text-zinc-500 focus:outline-none focus:border-emerald-500/50 focus:ring-2 focus:ring-emerald-500/20",
+                    placeholder: "{placeholder}",
+                    value: "{value}",
+                    oninput: move |evt| on_input.call(evt.value()),
+                }
+                if filtering {
+                    button {
+                        class: "absolute right-2 top-1/2 -translate-y-1/2 p-1 rounded-md text-zinc-500 hover:text-white transition-colors",
+                        aria_label: "Clear filter",
+                        onclick: move |_| on_input.call(String::new()),
+                        Icon { name: "x".to_string(), class: "w-3.5 h-3.5".to_string() }
+                    }
+                }
+            }
+            if filtering {
+                div { class: "flex items-center gap-2 flex-shrink-0",
+                    span { class: "text-xs text-zinc-500 tabular-nums", "{match_count} of {total}" }
+                    button {
+                        class: if has_match { "px-3 py-1.5 rounded-lg border border-zinc-700 text-zinc-300 hover:text-white hover:border-emerald-500/60 transition-colors text-xs" } else { "px-3 py-1.5 rounded-lg border border-zinc-800 text-zinc-600 cursor-not-allowed text-xs" },
+                        disabled: !has_match,
+                        onclick: move |_| on_jump.call(()),
+                        "Jump to"
+                    }
+                    button {
+                        class: if has_match { "px-3 py-1.5 rounded-lg bg-emerald-500/15 text-emerald-300 hover:bg-emerald-500/25 transition-colors text-xs" } else { "px-3 py-1.5 rounded-lg bg-zinc-800/60 text-zinc-600 cursor-not-allowed text-xs" },
+                        disabled: !has_match,
+                        onclick: move |_| on_play_first.call(()),
+                        "Play from first match"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlights_matches_across_punctuation() {
+        assert_eq!(
+            highlight_segments("Don't Stop", "dont"),
+            vec![("Don't".to_string(), true), (" Stop".to_string(), false)]
+        );
+        assert_eq!(
+            highlight_segments("Hello", "zzz"),
+            vec![("Hello".to_string(), false)]
+        );
+    }
+
+    #[test]
+    fn accents_match_and_highlight_without_being_typed() {
+        let songs = vec![Song {
+            title: "Jóga".to_string(),
+            artist: Some("Björk".to_string()),
+            ..Song::default()
+        }];
+        assert_eq!(filter_song_order(&songs, &[0], "bjork joga"), vec![0]);
+        assert_eq!(
+            highlight_segments("Björk", "bjork"),
+            vec![("Björk".to_string(), true)]
+        );
+        assert_eq!(
+            highlight_segments("Straße 5", "strasse"),
+            vec![("Straße".to_string(), true), (" 5".to_string(), false)]
+        );
+    }
+}
@@ -0,0 +1,419 @@
+// Wide-screen table layout shared by song lists (queue, playlists).
+//
+// Rows keep rendering their narrow layout below the `2xl` breakpoint and swap to a
+// grid row above it, so both layouts read the same songs, selection and menus.
+use crate::api::*;
+use crate::collation::sort_key;
+use crate::components::views::artist_links::ArtistNameLinks;
+use crate::components::views::song_list_filter::HighlightedText;
+use crate::components::{
+    Icon, SettingsController, UnavailableSongsSignal, UNAVAILABLE_SONG_TOOLTIP,
+};
+use crate::db::AppSettings;
+use dioxus::prelude::*;
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SongTableColumn {
+    Number,
+    Title,
+    Artist,
+    Album,
+    Composer,
+    Duration,
+    Plays,
+    Rating,
+}
+
+impl SongTableColumn {
+    pub(super) const ALL: [SongTableColumn; 8] = [
+        SongTableColumn::Number,
+        SongTableColumn::Title,
+        SongTableColumn::Artist,
+        SongTableColumn::Album,
+        SongTableColumn::Composer,
+        SongTableColumn::Duration,
+        SongTableColumn::Plays,
+        SongTableColumn::Rating,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            SongTableColumn::Number => "number",
+            SongTableColumn::Title => "title",
+            SongTableColumn::Artist => "artist",
+            SongTableColumn::Album => "album",
+            SongTableColumn::Composer => "composer",
+            SongTableColumn::Duration => "duration",
+            SongTableColumn::Plays => "plays",
+            SongTableColumn::Rating => "rating",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SongTableColumn::Number => "#",
+            SongTableColumn::Title => "Title",
+            SongTableColumn::Artist => "Artist",
+            SongTableColumn::Album => "Album",
+            SongTableColumn::Composer => "Composer",
+            SongTableColumn::Duration => "Duration",
+            SongTableColumn::Plays => "Plays",
+            SongTableColumn::Rating => "Rating",
+        }
+    }
+
+    fn track(self) -> &'static str {
+        match self {
+            SongTableColumn::Number => "3rem",
+            SongTableColumn::Title => "minmax(12rem,2fr)",
+            SongTableColumn::Artist => "minmax(8rem,1.2fr)",
+            SongTableColumn::Album => "minmax(8rem,1.2fr)",
+            SongTableColumn::Composer => "minmax(8rem,1fr)",
+            SongTableColumn::Duration => "5rem",
+            SongTableColumn::Plays => "4.5rem",
+            SongTableColumn::Rating => "6.5rem",
+        }
+    }
+
+    /// Title is the row's anchor and can't be hidden.
+    fn can_hide(self) -> bool {
+        self != SongTableColumn::Title
+    }
+
+    /// Columns most servers leave empty start hidden and are opted into per view.
+    fn shown_by_default(self) -> bool {
+        self != SongTableColumn::Composer
+    }
+
+    fn cell_class(self) -> &'static str {
+        match self {
+            SongTableColumn::Duration | SongTableColumn::Plays => {
+                "min-w-0 text-right text-sm text-zinc-500 font-mono"
+            }
+            _ => "min-w-0",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct SongTableSort {
+    pub column: SongTableColumn,
+    pub descending: bool,
+}
+
+/// Clicking a header cycles ascending -> descending -> original order.
+fn next_sort(current: Option<SongTableSort>, column: SongTableColumn) -> Option<SongTableSort> {
+    match current {
+        Some(sort) if sort.column == column && !sort.descending => Some(SongTableSort {
+            column,
+            descending: true,
+        }),
+        Some(sort) if sort.column == column => None,
+        _ => Some(SongTableSort {
+            column,
+            descending: false,
+        }),
+    }
+}
+
+fn compare_text(left: Option<&str>, right: Option<&str>) -> Ordering {
+    sort_key(left.unwrap_or_default(), false).cmp(&sort_key(right.unwrap_or_default(), false))
+}
+
+/// Returns indices into `songs` in display order. Sorting is stable, so ties keep
+/// their original position, and `None` leaves the list untouched.
+pub(super) fn sorted_song_order(songs: &[Song], sort: Option<SongTableSort>) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..songs.len()).collect();
+    let Some(sort) = sort else {
+        return order;
+    };
+    order.sort_by(|&left, &right| {
+        let (a, b) = (&songs[left], &songs[right]);
+        let ordering = match sort.column {
+            SongTableColumn::Number => left.cmp(&right),
+            SongTableColumn::Title => compare_text(Some(&a.title), Some(&b.title)),
+            SongTableColumn::Artist => compare_text(a.artist.as_deref(), b.artist.as_deref()),
+            SongTableColumn::Album => compare_text(a.album.as_deref(), b.album.as_deref())
+                .then_with(|| a.track.cmp(&b.track)),
+            SongTableColumn::Composer => {
+                compare_text(a.composer().as_deref(), b.composer().as_deref())
+            }
+            SongTableColumn::Duration => a.duration.cmp(&b.duration),
+            SongTableColumn::Plays => a.play_count.unwrap_or(0).cmp(&b.play_count.unwrap_or(0)),
+            SongTableColumn::Rating => a.user_rating.unwrap_or(0).cmp(&b.user_rating.unwrap_or(0)),
+        };
+        if sort.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+    order
+}
+
+pub(super) fn visible_song_table_columns(
+    settings: &AppSettings,
+    view_key: &str,
+) -> Vec<SongTableColumn> {
+    let hidden = settings.song_table_hidden_columns.get(view_key);
+    let shown = settings.song_table_shown_columns.get(view_key);
+    let listed = |keys: Option<&Vec<String>>, column: SongTableColumn| {
+        keys.is_some_and(|keys| keys.iter().any(|key| key == column.key()))
+    };
+    SongTableColumn::ALL
+        .into_iter()
+        .filter(|column| {
+            if !column.can_hide() {
+                true
+            } else if column.shown_by_default() {
+                !listed(hidden, *column)
+            } else {
+                listed(shown, *column)
+            }
+        })
+        .collect()
+}
+
+/// Inline grid template for a header or row; the trailing track holds row actions.
+pub(super) fn song_table_grid_style(columns: &[SongTableColumn]) -> String {
+    let tracks = columns
+        .iter()
+        .map(|column| column.track())
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("grid-template-columns: {tracks} 5.5rem;")
+}
+
+pub(super) const SONG_TABLE_ROW_CLASS: &str =
+    "hidden 2xl:grid items-center gap-3 px-3 py-2 rounded-lg";
+
+#[component]
+pub(super) fn SongTableHeader(view_key: String, sort: Signal<Option<SongTableSort>>) -> Element {
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let settings_controller = use_context::<SettingsController>();
+    let mut show_columns = use_signal(|| false);
+    let columns = visible_song_table_columns(&app_settings(), &view_key);
+    let grid_style = song_table_grid_style(&columns);
+    let current_sort = sort();
+
+    let toggle_column = {
+        let view_key = view_key.clone();
+        move |column: SongTableColumn| {
+            let view_key = view_key.clone();
+            move |_| {
+                settings_controller.update_settings(|settings| {
+                    let toggled = if column.shown_by_default() {
+                        &mut settings.song_table_hidden_columns
+                    } else {
+                        &mut settings.song_table_shown_columns
+                    };
+                    let keys = toggled.entry(view_key.clone()).or_default();
+                    if let Some(position) = keys.iter().position(|key| key == column.key()) {
+                        keys.remove(position);
+                    } else {
+                        keys.push(column.key().to_string());
+                    }
+                });
+            }
+        }
+    };
+
+    rsx! {
+        div {
+            class: "hidden 2xl:grid items-center gap-3 px-3 pb-2 mb-1 border-b border-zinc-800/70 text-xs uppercase tracking-wide text-zinc-500",
+            style: "{grid_style}",
+            for column in columns.iter().copied() {
+                button {
+                    key: "{column.key()}",
+                    r#type: "button",
+                    class: if matches!(column, SongTableColumn::Duration | SongTableColumn::Plays) { "flex items-center justify-end gap-1 min-w-0 hover:text-white transition-colors" } else { "flex items-center gap-1 min-w-0 text-left hover:text-white transition-colors" },
+                    aria_label: "Sort by {column.label()}",
+                    onclick: move |_| sort.set(next_sort(current_sort, column)),
+                    span { class: "truncate", "{column.label()}" }
+                    if let Some(active) = current_sort.filter(|active| active.column == column) {
+                        Icon {
+                            name: if active.descending { "chevron-down".to_string() } else { "chevron-up".to_string() },
+                            class: "w-3 h-3 text-emerald-400 flex-shrink-0".to_string(),
+                        }
+                    }
+                }
+            }
+            div { class: "relative flex justify-end",
+                button {
+                    r#type: "button",
+                    class: "p-1.5 rounded-lg text-zinc-500 hover:text-white hover:bg-zinc-800/60 transition-colors",
+                    aria_label: "Choose columns",
+                    aria_expanded: show_columns(),
+                    onclick: move |_| show_columns.set(!show_columns()),
+                    Icon {
+                        name: "settings".to_string(),
+                        class: "w-4 h-4".to_string(),
+                    }
+                }
+                if show_columns() {
+                    div {
+                        class: "fixed inset-0 z-[9998]",
+                        onclick: move |_| show_columns.set(false),
+                    }
+                    div { class: "absolute right-0 top-full mt-2 z-[9999] w-44 rounded-xl border border-zinc-700 bg-zinc-900/95 shadow-2xl p-1.5 normal-case tracking-normal",
+                        for column in SongTableColumn::ALL.into_iter().filter(|column| column.can_hide()) {
+                            label {
+                                key: "{column.key()}",
+                                class: "flex items-center gap-2 px-2.5 py-1.5 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 cursor-pointer",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "w-3.5 h-3.5 rounded cursor-pointer",
+                                    checked: columns.contains(&column),
+                                    onchange: toggle_column(column),
+                                }
+                                if column == SongTableColumn::Number {
+                                    "Track number"
+                                } else {
+                                    "{column.label()}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Renders the data cells of a wide row; the caller supplies the grid container
+/// and the trailing actions cell.
+#[component]
+pub(super) fn SongTableCells(
+    song: Song,
+    columns: Vec<SongTableColumn>,
+    display_index: usize,
+    is_current: bool,
+    rating: u32,
+    /// Thumbnail drawn before the title, for tables that show artwork.
+    #[props(default)]
+    art: Option<QueueRowArt>,
+    /// Filter text whose matches are highlighted in the title and album.
+    #[props(default)]
+    highlight: String,
+) -> Element {
+    let unavailable = use_context::<UnavailableSongsSignal>().contains(&song);
+
+    rsx! {
+        for column in columns.iter().copied() {
+            div { key: "{column.key()}", class: column.cell_class(),
+                match column {
+                    SongTableColumn::Number => rsx! {
+                        if is_current {
+                            Icon {
+                                name: "play".to_string(),
+                                class: "w-4 h-4 text-emerald-400".to_string(),
+                            }
+                        } else {
+                            span { class: "text-sm text-zinc-500", "{display_index}" }
+                        }
+                    },
+                    SongTableColumn::Title => rsx! {
+                        div { class: "flex items-center gap-3 min-w-0",
+                            if let Some(art) = art.clone() {
+                                QueueArtwork {
+                                    art,
+                                    alt: song.title.clone(),
+                                    class: "w-8 h-8 rounded".to_string(),
+                                }
+                            }
+                            p {
+                                class: if unavailable { "text-sm font-medium text-zinc-500 line-through truncate" } else if is_current { "text-sm font-medium text-emerald-400 truncate" } else { "text-sm font-medium text-white truncate group-hover:text-emerald-400 transition-colors" },
+                                title: if unavailable { UNAVAILABLE_SONG_TOOLTIP } else { "" },
+                                HighlightedText { text: song.title.clone(), query: highlight.clone() }
+                            }
+                        }
+                    },
+                    SongTableColumn::Artist => rsx! {
+                        ArtistNameLinks {
+                            artist_text: song.artist.clone().unwrap_or_default(),
+                            server_id: song.server_id.clone(),
+                            fallback_artist_id: song.artist_id.clone(),
+                            container_class: "inline-flex max-w-full min-w-0 items-center gap-1 text-sm text-zinc-400".to_string(),
+                        }
+                    },
+                    SongTableColumn::Album => rsx! {
+                        p { class: "text-sm text-zinc-400 truncate",
+                            HighlightedText {
+                                text: song.album.clone().unwrap_or_default(),
+                                query: highlight.clone(),
+                            }
+                        }
+                    },
+                    SongTableColumn::Composer => rsx! {
+                        p { class: "text-sm text-zinc-400 truncate",
+                            "{song.composer().unwrap_or_default()}"
+                        }
+                    },
+                    SongTableColumn::Duration => rsx! { "{format_duration(song.duration)}" },
+                    SongTableColumn::Plays => rsx! { "{song.play_count.unwrap_or(0)}" },
+                    SongTableColumn::Rating => rsx! {
+                        div { class: "flex items-center gap-0.5 text-amber-400",
+                            for i in 1..=5u32 {
+                                Icon {
+                                    name: if i <= rating { "star-filled".to_string() } else { "star".to_string() },
+                                    class: "w-3.5 h-3.5".to_string(),
+                                }
+                            }
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// Artwork state for one queue row.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct QueueRowArt {
+    pub cover_url: Option<String>,
+    pub is_current: bool,
+    pub is_playing: bool,
+}
+
+/// Queue row thumbnail. The image is fetched by the lazy cover loader once the row nears the
+/// viewport, and the playing row gets an equalizer glyph over it.
+#[component]
+pub(super) fn QueueArtwork(art: QueueRowArt, alt: String, class: String) -> Element {
+    let eq_class = if art.is_playing {
+        "rs-eq"
+    } else {
+        "rs-eq rs-eq--paused"
+    };
+
+    rsx! {
+        div { class: "relative overflow-hidden bg-zinc-800 flex-shrink-0 {class}",
+            match art.cover_url {
+                Some(url) => rsx! {
+                    img {
+                        "data-cover-src": "{url}",
+                        alt: "{alt}",
+                        class: "w-full h-full object-cover",
+                        decoding: "async",
+                    }
+                },
+                None => rsx! {
+                    div { class: "w-full h-full flex items-center justify-center bg-gradient-to-br from-zinc-700 to-zinc-800",
+                        Icon { name: "music".to_string(), class: "w-4 h-4 text-zinc-500".to_string() }
+                    }
+                },
+            }
+            if art.is_current {
+                div {
+                    class: "absolute inset-0 flex items-center justify-center bg-black/50",
+                    aria_hidden: "true",
+                    span { class: eq_class,
+                        span { class: "rs-eq__bar" }
+                        span { class: "rs-eq__bar" }
+                        span { class: "rs-eq__bar" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,21 +1,31 @@
+use super::empty_state::{EmptyState, NoServerEmptyState};
 use crate::api::*;
+use crate::collation::{collate, search_text};
 use crate::components::views::artist_links::{
     parse_artist_names, resolve_artist_id_for_name, ArtistNameLinks,
 };
+use crate::components::views::music_folder_filter::{
+    use_music_folder_selection, MusicFolderFilter,
+};
 use crate::components::Icon;
-use crate::components::{AddIntent, AddMenuController, AppView, Navigation};
+use crate::components::{sized_cover_art_url, ArtworkKind};
+use crate::components::{
+    use_restored_scroll, use_view_state, AddIntent, AddMenuController, AppView, Navigation,
+    ServerCapabilitiesSignal,
+};
 use crate::db::AppSettings;
 use crate::offline_audio::{is_song_downloaded, prefetch_song_audio};
 use chrono::{DateTime, NaiveDateTime};
 use dioxus::prelude::*;
 use futures_util::future::join_all;
 use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 async fn fetch_songs_from_album_order(
     active_servers: &[ServerConfig],
     album_type: &str,
     desired_song_count: u32,
+    folder_selection: &BTreeMap<String, String>,
 ) -> Vec<Song> {
     if active_servers.is_empty() {
         return Vec::new();
@@ -27,10 +37,11 @@ async fn fetch_songs_from_album_order(
 
     let tasks = active_servers.iter().cloned().map(|server| {
         let album_type = album_type.to_string();
+        let folder_id = folder_selection.get(&server.id).cloned();
         async move {
             let client = NavidromeClient::new(server.clone());
             let albums = client
-                .get_albums(&album_type, per_server_album_count, 0)
+                .get_albums(&album_type, per_server_album_count, 0, folder_id.as_deref())
                 .await
                 .unwrap_or_default();
             let album_ids: Vec<String> = albums
@@ -78,6 +89,10 @@ async fn fetch_songs_from_native_order(
         ((desired_song_count as usize).max(30) / active_servers.len()).max(20);
     let tasks = active_servers.iter().cloned().map(|server| async move {
         let client = NavidromeClient::new(server);
+        // Servers without the Navidrome API fall back to album ordering in the caller.
+        if client.capabilities().is_some_and(|c| !c.native_api) {
+            return Vec::new();
+        }
         client
             .get_native_songs(sort, NativeSortOrder::Desc, 0, per_server_song_target)
             .await
@@ -98,56 +113,69 @@ pub fn SongsView() -> Element {
     let queue = use_context::<Signal<Vec<Song>>>();
     let queue_index = use_context::<Signal<usize>>();
 
-    let mut search_query = use_signal(String::new);
-    let mut sort_by = use_signal(|| "last_played".to_string());
-    let mut sort_order = use_signal(|| "desc".to_string());
-    let mut filter_min_rating = use_signal(|| 0i32);
+    let mut search_query = use_view_state("search_query", String::new);
+    let mut sort_by = use_view_state("sort_by", || "last_played".to_string());
+    let mut sort_order = use_view_state("sort_order", || "desc".to_string());
+    let mut filter_min_rating = use_view_state("filter_min_rating", || 0i32);
     let rating_overrides = use_signal(HashMap::<String, u32>::new);
-    let limit = use_signal(|| 30u32);
+    let limit = use_view_state("limit", || 30u32);
+    let folder_selection = use_music_folder_selection();
 
     let songs = use_resource(move || {
         let servers = servers();
         let limit = limit();
         let sort_option_snapshot = sort_by();
         let query_snapshot = search_query();
+        let folder_selection = folder_selection();
         async move {
             let mut songs = Vec::new();
             let active_servers: Vec<ServerConfig> =
                 servers.into_iter().filter(|s| s.active).collect();
             let candidate_limit = limit.saturating_mul(3).clamp(45, 5000);
+            // The native song API can't be limited to a music folder, so folder-scoped
+            // servers go straight to album ordering.
+            let folder_scoped = active_servers
+                .iter()
+                .any(|server| folder_selection.contains_key(&server.id));
 
             if query_snapshot.trim().is_empty() {
                 match sort_option_snapshot.as_str() {
                     // Prefer Navidrome's native song ordering when the UI is sorting songs directly.
                     "last_played" => {
-                        songs = fetch_songs_from_native_order(
-                            &active_servers,
-                            NativeSongSortField::PlayDate,
-                            candidate_limit,
-                        )
-                        .await;
+                        if !folder_scoped {
+                            songs = fetch_songs_from_native_order(
+                                &active_servers,
+                                NativeSongSortField::PlayDate,
+                                candidate_limit,
+                            )
+                            .await;
+                        }
                         if songs.is_empty() {
                             songs = fetch_songs_from_album_order(
                                 &active_servers,
                                 "recent",
                                 candidate_limit,
+                                &folder_selection,
                             )
                             .await;
                         }
                     }
                     // Fall back to album ordering when the native Navidrome API is unavailable.
                     "most_played" => {
-                        songs = fetch_songs_from_native_order(
-                            &active_servers,
-                            NativeSongSortField::PlayCount,
-                            candidate_limit,
-                        )
-                        .await;
+                        if !folder_scoped {
+                            songs = fetch_songs_from_native_order(
+                                &active_servers,
+                                NativeSongSortField::PlayCount,
+                                candidate_limit,
+                            )
+                            .await;
+                        }
                         if songs.is_empty() {
                             songs = fetch_songs_from_album_order(
                                 &active_servers,
                                 "frequent",
                                 candidate_limit,
+                                &folder_selection,
                             )
                             .await;
                         }
@@ -158,6 +186,7 @@ pub fn SongsView() -> Element {
                             &active_servers,
                             "highest",
                             candidate_limit,
+                            &folder_selection,
                         )
                         .await;
                     }
@@ -167,6 +196,7 @@ pub fn SongsView() -> Element {
                             &active_servers,
                             "alphabeticalByName",
                             candidate_limit,
+                            &folder_selection,
                         )
                         .await;
                     }
@@ -187,9 +217,16 @@ pub fn SongsView() -> Element {
             } else {
                 // Search mode - query backend directly
                 for server in active_servers {
+                    let folder_id = folder_selection.get(&server.id).cloned();
                     let client = NavidromeClient::new(server);
                     if let Ok(results) = client
-                        .search(&query_snapshot, 0, 0, candidate_limit + 20)
+                        .search(
+                            &query_snapshot,
+                            0,
+                            0,
+                            candidate_limit + 20,
+                            folder_id.as_deref(),
+                        )
                         .await
                     {
                         songs.extend(results.songs);
@@ -202,6 +239,15 @@ pub fn SongsView() -> Element {
             songs
         }
     });
+    use_restored_scroll(move || songs.read().is_some());
+
+    let has_active_servers = servers().iter().any(|server| server.active);
+    // Without Navidrome's API, play-date and play-count sorts come from album lists instead.
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
+    let native_sorts = servers()
+        .iter()
+        .filter(|server| server.active)
+        .any(|server| capabilities.supports(&server.id, |c| c.native_api));
 
     rsx! {
         div { class: "space-y-8",
@@ -238,8 +284,13 @@ pub fn SongsView() -> Element {
                                 class: "px-2 py-1 bg-zinc-800/50 border border-zinc-700/50 rounded text-xs text-white focus:outline-none focus:border-emerald-500/50 min-w-0",
                                 value: sort_by,
                                 oninput: move |e| sort_by.set(e.value()),
-                                option { value: "last_played", "Last Played" }
-                                option { value: "most_played", "Most Played" }
+                                if native_sorts {
+                                    option { value: "last_played", "Last Played" }
+                                    option { value: "most_played", "Most Played" }
+                                } else {
+                                    option { value: "last_played", "Recently Played Albums" }
+                                    option { value: "most_played", "Most Played Albums" }
+                                }
                                 option { value: "alphabetical", "Alphabetical" }
                                 option { value: "rating", "Rating" }
                                 option { value: "duration", "Duration" }
@@ -260,6 +311,8 @@ pub fn SongsView() -> Element {
                             }
                         }
 
+                        MusicFolderFilter {}
+
                         if sort_by() == "rating" {
                             // Min rating filter
                             div { class: "flex items-center gap-1",
@@ -290,7 +343,7 @@ pub fn SongsView() -> Element {
                 match songs() {
                     Some(songs) => {
                         let raw_query = search_query().trim().to_string();
-                        let query = raw_query.to_lowercase();
+                        let query = search_text(&raw_query);
                         let sort_option = sort_by();
                         let sort_ascending = sort_order() == "asc";
                         let rating_filter_active = sort_option == "rating";
@@ -304,17 +357,13 @@ pub fn SongsView() -> Element {
                             songs
                                 .iter()
                                 .filter(|song| {
-                                    let title = song.title.to_lowercase();
-                                    let artist = song
-                                        .artist
-                                        .clone()
-                                        .unwrap_or_default()
-                                        .to_lowercase();
-                                    let album = song
-                                        .album
-                                        .clone()
-                                        .unwrap_or_default()
-                                        .to_lowercase();
+                                    let title = search_text(&song.title);
+                                    let artist = search_text(
+                                        song.artist.as_deref().unwrap_or_default(),
+                                    );
+                                    let album = search_text(
+                                        song.album.as_deref().unwrap_or_default(),
+                                    );
                                     title.contains(&query) || artist.contains(&query)
                                         || album.contains(&query)
                                 })
@@ -405,19 +454,26 @@ pub fn SongsView() -> Element {
                         };
                         rsx! {
                             if filtered.is_empty() {
-                                div { class: "flex flex-col items-center justify-center py-20",
-                                    Icon {
-                                        name: "music".to_string(),
-                                        class: "w-16 h-16 text-zinc-600 mb-4".to_string(),
+                                if !has_active_servers {
+                                    NoServerEmptyState {
+                                        icon: "music".to_string(),
+                                        message: "Connect a Navidrome server to browse songs.".to_string(),
                                     }
-                                    if has_query {
-                                        if raw_query.is_empty() {
-                                            p { class: "text-zinc-300", "No songs match the selected filters" }
+                                } else if has_query {
+                                    EmptyState {
+                                        icon: "music".to_string(),
+                                        title: if raw_query.is_empty() {
+                                            "No songs match the selected filters".to_string()
                                         } else {
-                                            p { class: "text-zinc-300", "No songs match \"{raw_query}\"" }
-                                        }
-                                    } else {
-                                        p { class: "text-zinc-400", "No songs found" }
+                                            format!("No songs match \"{raw_query}\"")
+                                        },
+                                    }
+                                } else {
+                                    EmptyState {
+                                        icon: "music".to_string(),
+                                        title: "No songs found".to_string(),
+                                        message: "Songs show up here once your server has scanned your music library."
+                                            .to_string(),
                                     }
                                 }
                             } else {
@@ -499,12 +555,10 @@ fn effective_song_rating(song: &Song, overrides: &HashMap<String, u32>) -> u32 {
 }
 
 fn compare_song_title(left: &Song, right: &Song, ascending: bool) -> Ordering {
-    let left_title = left.title.to_lowercase();
-    let right_title = right.title.to_lowercase();
     if ascending {
-        left_title.cmp(&right_title)
+        collate(&left.title, &right.title, false)
     } else {
-        right_title.cmp(&left_title)
+        collate(&right.title, &left.title, false)
     }
 }
 
@@ -577,7 +631,7 @@ fn SongRowWithRating(
             let client = NavidromeClient::new(server.clone());
             song.cover_art
                 .as_ref()
-                .map(|ca| client.get_cover_art_url(ca, 80))
+                .map(|ca| sized_cover_art_url(&client, ca, ArtworkKind::Song, 48))
         });
 
     let album_id = song.album_id.clone();
@@ -590,6 +644,9 @@ fn SongRowWithRating(
     };
     let is_favorited = use_signal(|| song.starred.is_some());
     let download_busy = use_signal(|| false);
+    let capabilities = use_context::<ServerCapabilitiesSignal>();
+    let can_download = capabilities.user_can(&song.server_id, UserAction::Download);
+    let can_rate = capabilities.user_can(&song.server_id, UserAction::Rate);
     let initially_downloaded = is_song_downloaded(&song);
     let downloaded = use_signal(move || initially_downloaded);
     let mut show_mobile_actions = use_signal(|| false);
@@ -890,6 +947,7 @@ fn SongRowWithRating(
                         button {
                             class: if is_favorited() { "p-1.5 rounded-lg text-emerald-400 hover:text-emerald-300 hover:bg-emerald-500/10 transition-colors" } else { "p-1.5 rounded-lg text-zinc-500 hover:text-emerald-400 hover:bg-emerald-500/10 transition-colors" },
                             aria_label: if is_favorited() { "Unfavorite" } else { "Favorite" },
+                            disabled: !can_rate,
                             onclick: make_on_toggle_favorite(),
                             Icon {
                                 name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
@@ -967,8 +1025,9 @@ fn SongRowWithRating(
                                     }
                                 } else {
                                     button {
-                                        class: if download_busy() { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-500 cursor-not-allowed" } else { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors" },
-                                        disabled: download_busy(),
+                                        class: if download_busy() || !can_download { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-500 cursor-not-allowed" } else { "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors" },
+                                        disabled: download_busy() || !can_download,
+                                        title: if can_download { "" } else { capabilities.denied_hint(UserAction::Download) },
                                         onclick: make_on_download_song(),
                                         Icon {
                                             name: if download_busy() { "loader".to_string() } else { "download".to_string() },
@@ -983,6 +1042,7 @@ fn SongRowWithRating(
                                 }
                                 button {
                                     class: "w-full flex items-center gap-2 px-2.5 py-2 rounded-lg text-sm text-zinc-200 hover:bg-zinc-800/80 transition-colors",
+                                    disabled: !can_rate,
                                     onclick: make_on_toggle_favorite(),
                                     Icon {
                                         name: if is_favorited() { "heart-filled".to_string() } else { "heart".to_string() },
@@ -1001,6 +1061,7 @@ fn SongRowWithRating(
                                     for i in 1..=5 {
                                         button {
                                             class: "p-1 rounded text-amber-400 hover:text-amber-300 transition-colors",
+                                            disabled: !can_rate,
                                             onclick: make_on_set_rating(i as u32),
                                             Icon {
                                                 name: if i <= effective_rating { "star-filled".to_string() } else { "star".to_string() },
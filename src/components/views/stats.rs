@@ -1,12 +1,93 @@
 use crate::api::*;
-use crate::components::{Icon, Navigation};
-use crate::db::AppSettings;
+use crate::components::{refresh_skip_shuffle_weights, Icon, Navigation};
+use crate::db::{most_skipped_songs, reset_song_skip_stats, AppSettings, SongSkipStats};
+use crate::time_format::humanize_server_timestamp_for_display;
 use dioxus::prelude::*;
 
+/// Songs listed under "Most skipped".
+const MOST_SKIPPED_LIMIT: usize = 10;
+
+/// The songs skipped most often, from the local listening history, each with a reset.
+#[component]
+fn MostSkippedSection() -> Element {
+    let app_settings = use_context::<Signal<AppSettings>>();
+    let mut resets = use_signal(|| 0u32);
+    let skipped = use_resource(move || {
+        let _ = resets();
+        async move { most_skipped_songs(MOST_SKIPPED_LIMIT).await }
+    });
+
+    let on_reset = move |song: SongSkipStats| {
+        spawn(async move {
+            reset_song_skip_stats(&song.server_id, &song.song_id).await;
+            refresh_skip_shuffle_weights(&app_settings.peek());
+            resets += 1;
+        });
+    };
+
+    rsx! {
+        section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
+            h2 { class: "text-lg font-semibold text-white mb-6 flex items-center gap-2",
+                Icon { name: "next".to_string(), class: "w-5 h-5".to_string() }
+                "Most Skipped"
+            }
+            match skipped() {
+                None => rsx! {
+                    div { class: "flex items-center justify-center py-8",
+                        Icon {
+                            name: "loader".to_string(),
+                            class: "w-6 h-6 text-zinc-500".to_string(),
+                        }
+                    }
+                },
+                Some(songs) if songs.is_empty() => rsx! {
+                    p { class: "text-sm text-zinc-400",
+                        if app_settings().session_summary_enabled {
+                            "No skips recorded yet."
+                        } else {
+                            "Turn on session summaries in Settings to keep track of skipped songs."
+                        }
+                    }
+                },
+                Some(songs) => rsx! {
+                    div { class: "space-y-2",
+                        for song in songs {
+                            div {
+                                key: "{song.server_id}:{song.song_id}",
+                                class: "flex items-center justify-between gap-3 p-3 bg-zinc-900/30 rounded-lg",
+                                div { class: "min-w-0",
+                                    p { class: "font-medium text-white truncate", "{song.title}" }
+                                    if let Some(artist) = song.artist.clone() {
+                                        p { class: "text-sm text-zinc-400 truncate", "{artist}" }
+                                    }
+                                }
+                                div { class: "flex items-center gap-3 shrink-0",
+                                    span { class: "text-sm text-zinc-400",
+                                        "{song.skips} of {song.plays} skipped"
+                                    }
+                                    button {
+                                        class: "text-xs text-zinc-500 hover:text-white transition-colors",
+                                        title: "Forget how often this song was skipped",
+                                        onclick: {
+                                            let song = song.clone();
+                                            move |_| on_reset(song.clone())
+                                        },
+                                        "Reset"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
 #[component]
 pub fn StatsView() -> Element {
     let _navigation = use_context::<Navigation>();
-    let _app_settings = use_context::<Signal<AppSettings>>();
+    let app_settings = use_context::<Signal<AppSettings>>();
     let servers = use_context::<Signal<Vec<ServerConfig>>>();
 
     // Fetch scan status for all active servers
@@ -28,6 +109,7 @@ pub fn StatsView() -> Element {
                             current_task: None,
                             seconds_remaining: None,
                             seconds_elapsed: None,
+                            last_scan: None,
                         },
                     )),
                 }
@@ -104,6 +186,15 @@ pub fn StatsView() -> Element {
                                                             "Progress: {elapsed}s elapsed, {remaining}s remaining"
                                                         }
                                                     }
+                                                    if let Some(last_scan) = status
+                                                        .last_scan
+                                                        .as_deref()
+                                                        .and_then(|value| {
+                                                            humanize_server_timestamp_for_display(value, &app_settings())
+                                                        })
+                                                    {
+                                                        div { class: "text-sm text-zinc-400", "Last scan: {last_scan}" }
+                                                    }
                                                 }
                                             }
                                         }
@@ -143,6 +234,8 @@ pub fn StatsView() -> Element {
                 }
             }
 
+            MostSkippedSection {}
+
             // Performance Statistics
             section { class: "bg-zinc-800/30 rounded-2xl border border-zinc-700/30 p-6",
                 h2 { class: "text-lg font-semibold text-white mb-6 flex items-center gap-2",
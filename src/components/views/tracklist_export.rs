@@ -0,0 +1,147 @@
+//! "Export tracklist" dialog for playlists and albums: pick a format and the columns, then
+//! save the list or open it as a page for printing.
+use crate::api::Song;
+use crate::components::Icon;
+use crate::storage::{open_html_export, save_text_export};
+use crate::tracklist_export::{
+    tracklist_file_name, TracklistColumns, TracklistFormat, TracklistWriter,
+};
+use dioxus::prelude::*;
+
+/// Rows written between yields to the UI, so a several-thousand-track playlist doesn't
+/// stall rendering while its list is built.
+const TRACKLIST_CHUNK_ROWS: usize = 400;
+
+/// Label and accessor for one column checkbox.
+type ColumnToggle = (&'static str, fn(&mut TracklistColumns) -> &mut bool);
+
+#[cfg(target_arch = "wasm32")]
+async fn tracklist_chunk_pause() {
+    gloo_timers::future::TimeoutFuture::new(0).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn tracklist_chunk_pause() {
+    tokio::task::yield_now().await;
+}
+
+#[component]
+pub(super) fn TracklistExportDialog(
+    title: String,
+    songs: Vec<Song>,
+    on_close: EventHandler<()>,
+) -> Element {
+    let mut format = use_signal(|| TracklistFormat::PlainText);
+    let mut columns = use_signal(TracklistColumns::default);
+    let mut busy = use_signal(|| false);
+    let mut status = use_signal(|| None::<String>);
+
+    let song_count = songs.len();
+    let on_export = {
+        let title = title.clone();
+        move |_| {
+            if busy() {
+                return;
+            }
+            busy.set(true);
+            status.set(None);
+            let title = title.clone();
+            let songs = songs.clone();
+            let format = format();
+            let columns = columns();
+            spawn(async move {
+                let mut writer = TracklistWriter::new(format, &title, &songs, columns);
+                while !writer.write_rows(TRACKLIST_CHUNK_ROWS) {
+                    tracklist_chunk_pause().await;
+                }
+                let contents = writer.finish();
+                let file_name = tracklist_file_name(&title, format);
+                let result = match format {
+                    TracklistFormat::Html => open_html_export(&file_name, &contents),
+                    _ => save_text_export(&file_name, &contents, format.mime()),
+                };
+                status.set(Some(result.unwrap_or_else(|err| err)));
+                busy.set(false);
+            });
+        }
+    };
+
+    let column_toggles: [ColumnToggle; 5] = [
+        ("#", |columns| &mut columns.index),
+        ("Title", |columns| &mut columns.title),
+        ("Artist", |columns| &mut columns.artist),
+        ("Album", |columns| &mut columns.album),
+        ("Duration", |columns| &mut columns.duration),
+    ];
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-[210] bg-zinc-950/95 backdrop-blur-sm overflow-y-auto px-4 py-8 flex items-center justify-center",
+            onclick: move |_| on_close.call(()),
+            div {
+                class: "w-full max-w-md bg-zinc-900/60 border border-zinc-700/50 rounded-2xl p-6 space-y-4",
+                onclick: move |evt: MouseEvent| evt.stop_propagation(),
+                div { class: "space-y-1",
+                    h2 { class: "text-lg font-semibold text-white", "Export tracklist" }
+                    p { class: "text-sm text-zinc-400 truncate", "{title} · {song_count} tracks" }
+                }
+                div { class: "space-y-2",
+                    p { class: "text-sm font-medium text-zinc-400", "Format" }
+                    for option in TracklistFormat::ALL {
+                        label {
+                            key: "{option.extension()}",
+                            class: "flex items-center gap-2 text-sm text-zinc-300 cursor-pointer",
+                            input {
+                                r#type: "radio",
+                                name: "tracklist-format",
+                                checked: format() == option,
+                                onchange: move |_| format.set(option),
+                            }
+                            "{option.label()}"
+                        }
+                    }
+                }
+                div { class: "space-y-2",
+                    p { class: "text-sm font-medium text-zinc-400", "Columns" }
+                    div { class: "flex flex-wrap gap-x-4 gap-y-2",
+                        for (heading , field) in column_toggles {
+                            label {
+                                key: "{heading}",
+                                class: "flex items-center gap-2 text-sm text-zinc-300 cursor-pointer",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: *field(&mut columns()),
+                                    onchange: move |e| *field(&mut columns.write()) = e.checked(),
+                                }
+                                "{heading}"
+                            }
+                        }
+                    }
+                }
+                if let Some(message) = status() {
+                    p { class: "text-xs text-zinc-500 break-all", "{message}" }
+                }
+                div { class: "flex items-center justify-end gap-2",
+                    button {
+                        class: "px-3 py-1.5 rounded-lg text-zinc-400 hover:text-white transition-colors text-sm",
+                        onclick: move |_| on_close.call(()),
+                        "Close"
+                    }
+                    button {
+                        class: "px-3 py-1.5 rounded-lg bg-emerald-500/15 text-emerald-300 hover:bg-emerald-500/25 transition-colors text-sm flex items-center gap-2 disabled:opacity-50",
+                        disabled: busy(),
+                        onclick: on_export,
+                        if busy() {
+                            Icon { name: "loader".to_string(), class: "w-4 h-4 animate-spin".to_string() }
+                        }
+                        if format() == TracklistFormat::Html {
+                            "Open for printing"
+                        } else {
+                            "Save"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Continuous albums: live recordings and DJ mixes split into tracks that run into each
+//! other, so a crossfade between two of them cuts into the performance.
+//!
+//! Only album metadata is looked at. The title and genre are matched word by word against
+//! phrases that mark a live or mixed release, so "Alive" or "Live Through This" don't count.
+//! The "Continuous playback" choice in an album header beats the guess either way.
+use crate::api::Song;
+use crate::db::AppSettings;
+
+/// Phrases that mark a live or mixed release wherever they appear in a title.
+const CONTINUOUS_TITLE_PHRASES: &[&[&str]] = &[
+    &["live", "at"],
+    &["live", "from"],
+    &["in", "concert"],
+    &["unplugged"],
+    &["dj", "mix"],
+    &["continuous", "mix"],
+    &["mixed", "by"],
+];
+
+/// Words that may follow "live" at the start of a title part, as in "Live in Tokyo".
+const LIVE_LEAD_WORDS: &[&str] = &["at", "from", "in", "on"];
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn has_phrase(words: &[String], phrase: &[&str]) -> bool {
+    words
+        .windows(phrase.len())
+        .any(|window| window.iter().zip(phrase).all(|(word, want)| word == want))
+}
+
+/// Whether one part of a title, such as the "(Live)" in "Nevermind (Live)", names a
+/// live or mixed release.
+fn title_part_is_continuous(part: &str) -> bool {
+    let words = words(part);
+    let leads_with_live = match words.as_slice() {
+        [first] => first == "live",
+        [first, next, ..] => {
+            first == "live"
+                && (LIVE_LEAD_WORDS.contains(&next.as_str())
+                    || next.chars().all(|c| c.is_ascii_digit()))
+        }
+        [] => false,
+    };
+    leads_with_live
+        || CONTINUOUS_TITLE_PHRASES
+            .iter()
+            .any(|phrase| has_phrase(&words, phrase))
+}
+
+/// Whether an album titled `title` in `genre` looks like a live recording or a mix.
+pub fn looks_continuous(title: &str, genre: Option<&str>) -> bool {
+    let by_title = title
+        .split(['(', ')', '[', ']', ':', '-', '–', '—'])
+        .any(title_part_is_continuous);
+    let by_genre = genre.is_some_and(|genre| {
+        let words = words(genre);
+        words.iter().any(|word| word == "live")
+            || CONTINUOUS_TITLE_PHRASES
+                .iter()
+                .any(|phrase| has_phrase(&words, phrase))
+    });
+    by_title || by_genre
+}
+
+/// Whether the album plays without crossfades between its own tracks: the album header
+/// override if one is set, otherwise the guess from its title and genre.
+pub fn album_is_continuous(
+    settings: &AppSettings,
+    server_id: &str,
+    album_id: &str,
+    title: &str,
+    genre: Option<&str>,
+) -> bool {
+    settings
+        .continuous_album_override(server_id, album_id)
+        .unwrap_or_else(|| looks_continuous(title, genre))
+}
+
+/// Whether going from `song` to `next` stays inside one continuous album. Transitions into
+/// or out of such an album are ordinary ones.
+pub fn continuous_transition(settings: &AppSettings, song: &Song, next: &Song) -> bool {
+    let Some(album_id) = song.album_id.as_deref().filter(|id| !id.is_empty()) else {
+        return false;
+    };
+    song.server_id == next.server_id
+        && next.album_id.as_deref() == Some(album_id)
+        && album_is_continuous(
+            settings,
+            &song.server_id,
+            album_id,
+            song.album.as_deref().unwrap_or_default(),
+            song.genre.as_deref(),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Album title, genre, and whether it should play continuously.
+    const LABELED_ALBUMS: &[(&str, Option<&str>, bool)] = &[
+        ("Live at Leeds", None, true),
+        ("MTV Unplugged in New York", Some("Grunge"), true),
+        ("Nevermind (Live)", None, true),
+        ("Abbey Road [Live in Tokyo]", None, true),
+        ("Hunky Dory - Live from the BBC", None, true),
+        ("Live 1975-85", Some("Rock"), true),
+        ("Stop Making Sense", Some("Live"), true),
+        ("Fabric 50", Some("DJ Mix"), true),
+        (
+            "Global Underground 013: Ibiza",
+            Some("Electronic; Continuous Mix"),
+            true,
+        ),
+        (
+            "Balance 005 (Mixed by James Holden)",
+            Some("Electronic"),
+            true,
+        ),
+        ("Elvis in Concert", None, true),
+        ("Live Through This", Some("Alternative Rock"), false),
+        ("Long Live Rock 'n' Roll", Some("Heavy Metal"), false),
+        ("Alive", Some("Rock"), false),
+        ("Oliver's Army", None, false),
+        ("Songs in the Key of Life", Some("Soul"), false),
+        ("Deliverance", Some("Progressive Metal"), false),
+        ("Piano Concerto No. 2", Some("Classical"), false),
+        ("Remixed", Some("Electronic"), false),
+        ("Rumours", None, false),
+        ("", None, false),
+    ];
+
+    fn song(id: &str, album_id: &str, album: &str) -> Song {
+        Song {
+            id: id.to_string(),
+            server_id: "home".to_string(),
+            album_id: Some(album_id.to_string()),
+            album: Some(album.to_string()),
+            ..Song::default()
+        }
+    }
+
+    #[test]
+    fn heuristic_matches_the_labeled_albums() {
+        for (title, genre, continuous) in LABELED_ALBUMS {
+            assert_eq!(
+                looks_continuous(title, *genre),
+                *continuous,
+                "{title:?} ({genre:?})"
+            );
+        }
+    }
+
+    #[test]
+    fn only_transitions_within_the_album_are_continuous() {
+        let mut settings = AppSettings::default();
+        let first = song("s1", "al1", "Live at Leeds");
+        let second = song("s2", "al1", "Live at Leeds");
+        let elsewhere = song("s3", "al2", "Live at Leeds");
+        assert!(continuous_transition(&settings, &first, &second));
+        assert!(!continuous_transition(&settings, &second, &elsewhere));
+
+        settings.set_continuous_album_override("home", "al1", Some(false));
+        assert!(!continuous_transition(&settings, &first, &second));
+        let studio = song("s4", "al3", "Rumours");
+        settings.set_continuous_album_override("home", "al3", Some(true));
+        assert!(continuous_transition(
+            &settings,
+            &studio,
+            &song("s5", "al3", "Rumours")
+        ));
+    }
+}
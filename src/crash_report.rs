@@ -0,0 +1,192 @@
+//! Local crash reports for native builds.
+//!
+//! A panic hook writes the panic message, backtrace, app version, OS and the latest
+//! diagnostics lines to the profile's `crashes/` directory, and marks the report as pending
+//! so the next launch of the same profile can offer it to the user. Nothing is uploaded.
+#[cfg(not(target_arch = "wasm32"))]
+use once_cell::sync::OnceCell;
+#[cfg(not(target_arch = "wasm32"))]
+use std::any::Any;
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+
+#[cfg(not(target_arch = "wasm32"))]
+const CRASH_DIR_NAME: &str = "crashes";
+/// Holds the file name of the report the user has not seen yet.
+#[cfg(not(target_arch = "wasm32"))]
+const PENDING_MARKER: &str = "pending";
+
+/// Resolved once at startup so the hook never has to create or migrate directories.
+#[cfg(not(target_arch = "wasm32"))]
+static CRASH_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrashReport {
+    pub file_name: String,
+    pub contents: String,
+}
+
+/// Installs the crash-report hook in front of the existing panic hook.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn install_panic_hook() {
+    if let Some(data_dir) = crate::storage::profile_data_dir() {
+        install_panic_hook_in(&data_dir);
+    }
+}
+
+/// Installs the hook with reports kept under `data_dir`, the profile's data directory.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn install_panic_hook_in(data_dir: &Path) {
+    let _ = CRASH_DIR.set(data_dir.join(CRASH_DIR_NAME));
+
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if let Some(dir) = CRASH_DIR.get() {
+            let location = info
+                .location()
+                .map(|location| format!("{}:{}", location.file(), location.line()));
+            let _ = record_panic(dir, info.payload(), location);
+        }
+        previous(info);
+    }));
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn install_panic_hook() {}
+
+/// The report left by a crash since the last launch, if the user hasn't dismissed it.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn pending_crash_report() -> Option<CrashReport> {
+    read_pending_report(CRASH_DIR.get()?)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn pending_crash_report() -> Option<CrashReport> {
+    None
+}
+
+/// Clears the pending marker; the report file itself stays in the crashes directory.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn dismiss_pending_crash_report() {
+    if let Some(dir) = CRASH_DIR.get() {
+        let _ = fs::remove_file(dir.join(PENDING_MARKER));
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn dismiss_pending_crash_report() {}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<non-string panic payload>".to_string()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn build_report(message: &str, location: Option<&str>, thread: &str, backtrace: &str) -> String {
+    let mut report = String::new();
+    report.push_str("RustySound crash report\n");
+    report.push_str(&format!("Version: {}\n", env!("CARGO_PKG_VERSION")));
+    report.push_str(&format!(
+        "OS: {} ({})\n",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    ));
+    report.push_str(&format!("Time: {}\n", chrono::Utc::now().to_rfc3339()));
+    report.push_str(&format!("Thread: {thread}\n"));
+    report.push_str(&format!("Panic: {message}\n"));
+    if let Some(location) = location {
+        report.push_str(&format!("Location: {location}\n"));
+    }
+    report.push_str("\nBacktrace:\n");
+    report.push_str(backtrace);
+    report.push_str("\n\nRecent diagnostics (oldest first):\n");
+    let diagnostics = crate::diagnostics::recent_diagnostics();
+    if diagnostics.is_empty() {
+        report.push_str("(none)\n");
+    }
+    for line in diagnostics {
+        report.push_str(&line);
+        report.push('\n');
+    }
+    report
+}
+
+/// Writes a report for the current panic and marks it pending. This runs inside the panic
+/// hook, where a second panic aborts the process, so nothing here unwraps: locks are only
+/// tried and every filesystem step gives up quietly.
+#[cfg(not(target_arch = "wasm32"))]
+fn record_panic(
+    dir: &Path,
+    payload: &(dyn Any + Send),
+    location: Option<String>,
+) -> Option<PathBuf> {
+    let message = panic_message(payload);
+    let thread = std::thread::current();
+    let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+    let report = build_report(
+        &message,
+        location.as_deref(),
+        thread.name().unwrap_or("<unnamed>"),
+        &backtrace,
+    );
+    write_report(dir, &report)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_report(dir: &Path, report: &str) -> Option<PathBuf> {
+    fs::create_dir_all(dir).ok()?;
+    let file_name = format!(
+        "crash-{}.txt",
+        chrono::Utc::now().format("%Y%m%d-%H%M%S%.3f")
+    );
+    let path = dir.join(&file_name);
+    fs::write(&path, report).ok()?;
+    fs::write(dir.join(PENDING_MARKER), &file_name).ok()?;
+    Some(path)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_pending_report(dir: &Path) -> Option<CrashReport> {
+    let file_name = fs::read_to_string(dir.join(PENDING_MARKER)).ok()?;
+    let file_name = file_name.trim();
+    // The marker only ever names a file inside the crashes directory.
+    if file_name.is_empty() || file_name.contains(['/', '\\']) {
+        return None;
+    }
+    let contents = fs::read_to_string(dir.join(file_name)).ok()?;
+    Some(CrashReport {
+        file_name: file_name.to_string(),
+        contents,
+    })
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "rustysound-crash-test-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn marker_outside_the_crash_dir_is_ignored() {
+        let dir = scratch_dir("marker");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(PENDING_MARKER), "../settings.db").unwrap();
+        assert!(read_pending_report(&dir).is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
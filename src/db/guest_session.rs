@@ -0,0 +1,306 @@
+//! Guest sessions for shared devices.
+//!
+//! While a guest session runs, the playback state, queue snapshots, listening history,
+//! resume points and settings writes are kept in memory here instead of in storage, and
+//! everything is dropped when the session ends. Only the lock itself, a salted hash of the
+//! PIN that ends the session, is stored, so a restart doesn't end it.
+use super::history::ListeningHistory;
+use super::resume_points::ResumePoints;
+use super::{AppSettings, PlaybackState, TemporaryQueueSnapshot};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, MutexGuard};
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::{get_db_connection, DbError};
+#[cfg(target_arch = "wasm32")]
+use gloo_storage::{errors::StorageError, LocalStorage, Storage};
+
+#[cfg(not(target_arch = "wasm32"))]
+type GuestSessionError = DbError;
+#[cfg(target_arch = "wasm32")]
+type GuestSessionError = StorageError;
+
+#[cfg(target_arch = "wasm32")]
+const GUEST_LOCK_KEY: &str = "rustysound.guest_session";
+const GUEST_PIN_MIN_DIGITS: usize = 4;
+const GUEST_PIN_MAX_DIGITS: usize = 8;
+
+/// What a guest session has written so far.
+#[derive(Default)]
+pub(super) struct GuestStore {
+    lock: GuestLock,
+    pub(super) playback_state: PlaybackState,
+    pub(super) queue_snapshots: Vec<TemporaryQueueSnapshot>,
+    pub(super) history: ListeningHistory,
+    pub(super) resume_points: ResumePoints,
+    pub(super) settings: Option<AppSettings>,
+}
+
+static GUEST_STORE: Lazy<Mutex<Option<GuestStore>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+struct GuestLock {
+    pin_hash: String,
+    salt: String,
+}
+
+impl GuestLock {
+    fn new(pin: &str) -> Self {
+        let salt = uuid::Uuid::new_v4().simple().to_string();
+        Self {
+            pin_hash: guest_pin_hash(&salt, pin.trim()),
+            salt,
+        }
+    }
+
+    fn matches(&self, pin: &str) -> bool {
+        guest_pin_hash(&self.salt, pin.trim()) == self.pin_hash
+    }
+}
+
+fn guest_store() -> MutexGuard<'static, Option<GuestStore>> {
+    GUEST_STORE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Runs `f` on the guest store while a guest session is active.
+pub(super) fn with_guest_store<R>(f: impl FnOnce(&mut GuestStore) -> R) -> Option<R> {
+    guest_store().as_mut().map(f)
+}
+
+/// Hands `value` to `keep` while a guest session is active. Otherwise it is given back for
+/// the caller to store.
+pub(super) fn divert_to_guest_store<T>(
+    value: T,
+    keep: impl FnOnce(&mut GuestStore, T),
+) -> Option<T> {
+    match guest_store().as_mut() {
+        Some(store) => {
+            keep(store, value);
+            None
+        }
+        None => Some(value),
+    }
+}
+
+fn guest_session_active() -> bool {
+    guest_store().is_some()
+}
+
+/// PINs are 4 to 8 digits.
+pub fn is_valid_guest_pin(pin: &str) -> bool {
+    (GUEST_PIN_MIN_DIGITS..=GUEST_PIN_MAX_DIGITS).contains(&pin.len())
+        && pin.chars().all(|c| c.is_ascii_digit())
+}
+
+/// MD5 of the salt and PIN. This only keeps the PIN out of plain sight in storage: the PIN
+/// locks the UI of a shared device and guards no secrets, and a 4 to 8 digit PIN can be
+/// brute-forced from its hash whatever the hash function.
+fn guest_pin_hash(salt: &str, pin: &str) -> String {
+    format!(
+        "{:x}",
+        md5::compute(format!("rustysound-guest:{salt}:{pin}"))
+    )
+}
+
+fn begin(lock: GuestLock) {
+    let mut store = guest_store();
+    if store.is_none() {
+        *store = Some(GuestStore {
+            lock,
+            ..GuestStore::default()
+        });
+    }
+}
+
+fn finish() {
+    *guest_store() = None;
+}
+
+fn guest_pin_matches(pin: &str) -> bool {
+    with_guest_store(|store| store.lock.matches(pin)).unwrap_or(false)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_guest_lock() -> Result<Option<GuestLock>, GuestSessionError> {
+    let conn = get_db_connection()?;
+    let result: Result<String, rusqlite::Error> = conn.query_row(
+        "SELECT value FROM settings WHERE key = 'guest_session'",
+        [],
+        |row: &rusqlite::Row| row.get(0),
+    );
+
+    match result {
+        Ok(json) => serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| DbError::new(e.to_string())),
+        Err(_) => Ok(None),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_guest_lock(lock: Option<&GuestLock>) -> Result<(), GuestSessionError> {
+    let conn = get_db_connection()?;
+    match lock {
+        Some(lock) => {
+            let payload = serde_json::to_string(lock).map_err(|e| DbError::new(e.to_string()))?;
+            conn.execute(
+                "INSERT OR REPLACE INTO settings (key, value) VALUES ('guest_session', ?1)",
+                [&payload],
+            )
+        }
+        None => conn.execute("DELETE FROM settings WHERE key = 'guest_session'", []),
+    }
+    .map_err(|e| DbError::new(e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn load_guest_lock() -> Result<Option<GuestLock>, GuestSessionError> {
+    Ok(LocalStorage::get(GUEST_LOCK_KEY).ok())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn save_guest_lock(lock: Option<&GuestLock>) -> Result<(), GuestSessionError> {
+    match lock {
+        Some(lock) => LocalStorage::set(GUEST_LOCK_KEY, lock),
+        None => {
+            LocalStorage::delete(GUEST_LOCK_KEY);
+            Ok(())
+        }
+    }
+}
+
+/// Starts a guest session that only `pin` ends.
+pub async fn start_guest_session(pin: &str) -> Result<(), GuestSessionError> {
+    let lock = GuestLock::new(pin);
+    save_guest_lock(Some(&lock))?;
+    begin(lock);
+    Ok(())
+}
+
+/// Ends the guest session and drops what it wrote, if `pin` is the one it was started with.
+/// Returns whether it ended.
+pub async fn end_guest_session(pin: &str) -> Result<bool, GuestSessionError> {
+    if !guest_pin_matches(pin) {
+        return Ok(false);
+    }
+    save_guest_lock(None)?;
+    finish();
+    Ok(true)
+}
+
+/// Picks up the guest session the previous launch left running, with an empty store.
+/// Returns whether one is running.
+pub async fn resume_guest_session() -> bool {
+    if let Ok(Some(lock)) = load_guest_lock() {
+        begin(lock);
+    }
+    guest_session_active()
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::api::{QueueSongMeta, QueueSourceKind, Song};
+    use crate::db::{
+        last_played_by_song, load_playback_state, load_temporary_queue_snapshots,
+        record_listening_play, record_listening_skip, save_container_resume_point,
+        save_playback_state, save_settings, save_temporary_queue_snapshot, ContainerResumePoint,
+        DB_CONNECTIONS_OPENED, GUEST_SESSION_TEST_LOCK,
+    };
+
+    fn song(id: &str) -> Song {
+        Song {
+            id: id.to_string(),
+            server_id: "home".to_string(),
+            title: format!("Song {id}"),
+            duration: 200,
+            queue_meta: Some(QueueSongMeta {
+                group_id: "g1".to_string(),
+                source_kind: QueueSourceKind::Album,
+                source_id: "al1".to_string(),
+                source_position: 1,
+            }),
+            ..Song::default()
+        }
+    }
+
+    #[test]
+    fn pins_are_four_to_eight_digits() {
+        assert!(is_valid_guest_pin("1234"));
+        assert!(is_valid_guest_pin("12345678"));
+        assert!(!is_valid_guest_pin("123"));
+        assert!(!is_valid_guest_pin("123456789"));
+        assert!(!is_valid_guest_pin("12a4"));
+    }
+
+    #[test]
+    fn pin_hashes_are_salted_per_lock() {
+        let (first, second) = (GuestLock::new("1234"), GuestLock::new("1234"));
+        assert_ne!(first.salt, second.salt);
+        assert_ne!(first.pin_hash, second.pin_hash);
+        assert!(first.matches("1234") && second.matches(" 1234 "));
+        assert!(!first.matches("4321"));
+
+        let unsalted = format!(r#"{{"pin_hash":"{}"}}"#, first.pin_hash);
+        assert!(serde_json::from_str::<GuestLock>(&unsalted).is_err());
+    }
+
+    /// Ends the test's guest session even when an assertion fails, so the rest of the suite
+    /// writes to the database again.
+    struct GuestSessionGuard;
+
+    impl Drop for GuestSessionGuard {
+        fn drop(&mut self) {
+            finish();
+        }
+    }
+
+    #[tokio::test]
+    async fn guest_writes_never_reach_the_database() {
+        let _serial = GUEST_SESSION_TEST_LOCK.lock().await;
+        begin(GuestLock::new("2468"));
+        let guard = GuestSessionGuard;
+        // The single-threaded test runtime keeps every write below on this thread's counter.
+        let opened = DB_CONNECTIONS_OPENED.with(|opened| opened.get());
+
+        save_settings(AppSettings {
+            volume: 0.2,
+            ..AppSettings::default()
+        })
+        .await
+        .unwrap();
+        save_playback_state(PlaybackState {
+            song_id: Some("s1".to_string()),
+            server_id: Some("home".to_string()),
+            ..PlaybackState::default()
+        })
+        .await
+        .unwrap();
+        save_temporary_queue_snapshot(TemporaryQueueSnapshot {
+            id: "queue-1".to_string(),
+            queue: vec![song("s1"), song("s2")],
+            ..TemporaryQueueSnapshot::default()
+        })
+        .await
+        .unwrap();
+        record_listening_play(song("s1"), None).await.unwrap();
+        record_listening_skip(song("s2"), 12.0, None).await.unwrap();
+        let point = ContainerResumePoint::for_song(&song("s2"), 40.0, 1_000).unwrap();
+        save_container_resume_point(point).await.unwrap();
+
+        // The guest reads back its own writes.
+        let state = load_playback_state().await.unwrap();
+        assert_eq!(state.song_id.as_deref(), Some("s1"));
+        assert_eq!(load_temporary_queue_snapshots().await.unwrap().len(), 1);
+        assert_eq!(last_played_by_song().await.len(), 1);
+        assert_eq!(DB_CONNECTIONS_OPENED.with(|opened| opened.get()), opened);
+
+        assert!(!guest_pin_matches("1357"));
+        assert!(guest_pin_matches("2468"));
+        drop(guard);
+        assert!(!guest_session_active());
+        assert!(with_guest_store(|store| store.history.clone()).is_none());
+    }
+}
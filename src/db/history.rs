@@ -0,0 +1,447 @@
+//! Local listening history behind the end-of-session summary.
+//!
+//! Finished plays are appended to the current session. A session ends after
+//! `SESSION_GAP_MS` without a finished play, or when the app closes; its summary is offered
+//! on a later launch until dismissed. How each song ended, finished or skipped, is kept
+//! separately for the skip statistics in `skip_stats`. Nothing here is sent to the server.
+use super::guest_session::with_guest_store;
+use crate::api::Song;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::{get_db_connection, DbError};
+#[cfg(target_arch = "wasm32")]
+use gloo_storage::{errors::StorageError, LocalStorage, Storage};
+
+#[cfg(not(target_arch = "wasm32"))]
+type HistoryError = DbError;
+#[cfg(target_arch = "wasm32")]
+type HistoryError = StorageError;
+
+#[cfg(target_arch = "wasm32")]
+pub(super) const LISTENING_HISTORY_KEY: &str = "rustysound.listening_history";
+/// Thirty minutes without a finished song starts a new session.
+const SESSION_GAP_MS: u64 = 30 * 60 * 1000;
+const SESSION_HISTORY_LIMIT: usize = 30;
+/// Sessions shorter than this are not worth a summary.
+const SESSION_SUMMARY_MIN_PLAYS: usize = 3;
+/// Finished and skipped plays kept for skip statistics; the oldest are dropped first.
+const TRACK_OUTCOME_LIMIT: usize = 2000;
+/// A song left for another before this share of it had played counts as skipped.
+const SKIP_PLAYED_FRACTION: f64 = 0.8;
+
+/// Sessions still open from before this launch ended when the app was closed.
+static LAUNCHED_AT_MS: Lazy<u64> = Lazy::new(now_timestamp_millis);
+
+fn now_timestamp_millis() -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        return js_sys::Date::now().max(0.0).round() as u64;
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct ListeningHistory {
+    #[serde(default)]
+    pub sessions: Vec<ListeningSession>,
+    #[serde(default)]
+    pub outcomes: Vec<TrackOutcome>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct ListeningSession {
+    pub started_at_ms: u64,
+    pub last_played_at_ms: u64,
+    #[serde(default)]
+    pub ended_at_ms: Option<u64>,
+    #[serde(default)]
+    pub summary_dismissed: bool,
+    #[serde(default)]
+    pub plays: Vec<ListeningPlay>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct ListeningPlay {
+    pub server_id: String,
+    pub song_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub artist: Option<String>,
+    #[serde(default)]
+    pub duration_secs: u32,
+    pub played_at_ms: u64,
+}
+
+/// How one play of a song ended.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct TrackOutcome {
+    pub server_id: String,
+    pub song_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub artist: Option<String>,
+    /// Playback position when the song was skipped; `None` when it played to the end.
+    #[serde(default)]
+    pub skipped_at_secs: Option<u32>,
+    /// What the song was queued from, such as "album" or "playlist".
+    #[serde(default)]
+    pub context: Option<String>,
+    pub at_ms: u64,
+}
+
+/// Whether leaving a song at `position_secs` for another one counts as a skip.
+pub fn is_skip(position_secs: f64, duration_secs: u32) -> bool {
+    duration_secs > 0 && position_secs < f64::from(duration_secs) * SKIP_PLAYED_FRACTION
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SessionSummary {
+    pub started_at_ms: u64,
+    pub ended_at_ms: u64,
+    pub songs_played: usize,
+    pub total_secs: u64,
+    pub top_artist: Option<String>,
+    pub top_artist_plays: usize,
+}
+
+impl ListeningHistory {
+    /// Points plays of `server_id` at the new song ids in `remap`.
+    pub(super) fn remap_song_ids(
+        &mut self,
+        server_id: &str,
+        remap: &std::collections::BTreeMap<String, String>,
+    ) {
+        for play in self
+            .sessions
+            .iter_mut()
+            .flat_map(|session| session.plays.iter_mut())
+            .filter(|play| play.server_id == server_id)
+        {
+            if let Some(new_id) = remap.get(&play.song_id) {
+                play.song_id = new_id.clone();
+            }
+        }
+        for outcome in self
+            .outcomes
+            .iter_mut()
+            .filter(|outcome| outcome.server_id == server_id)
+        {
+            if let Some(new_id) = remap.get(&outcome.song_id) {
+                outcome.song_id = new_id.clone();
+            }
+        }
+    }
+
+    pub(super) fn push_outcome(&mut self, outcome: TrackOutcome) {
+        self.outcomes.push(outcome);
+        if self.outcomes.len() > TRACK_OUTCOME_LIMIT {
+            let excess = self.outcomes.len() - TRACK_OUTCOME_LIMIT;
+            self.outcomes.drain(..excess);
+        }
+    }
+}
+
+impl ListeningSession {
+    pub fn summary(&self) -> SessionSummary {
+        let mut artist_counts = HashMap::<&str, usize>::new();
+        for play in &self.plays {
+            if let Some(artist) = play.artist.as_deref().filter(|a| !a.trim().is_empty()) {
+                *artist_counts.entry(artist).or_default() += 1;
+            }
+        }
+        // Ties go to the artist heard first in the session.
+        let top_artist = self
+            .plays
+            .iter()
+            .filter_map(|play| play.artist.as_deref())
+            .filter_map(|artist| artist_counts.get(artist).map(|count| (artist, *count)))
+            .fold(None::<(&str, usize)>, |best, candidate| match best {
+                Some(best) if best.1 >= candidate.1 => Some(best),
+                _ => Some(candidate),
+            });
+
+        SessionSummary {
+            started_at_ms: self.started_at_ms,
+            ended_at_ms: self.ended_at_ms.unwrap_or(self.last_played_at_ms),
+            songs_played: self.plays.len(),
+            total_secs: self
+                .plays
+                .iter()
+                .map(|play| u64::from(play.duration_secs))
+                .sum(),
+            top_artist: top_artist.map(|(artist, _)| artist.to_string()),
+            top_artist_plays: top_artist.map(|(_, count)| count).unwrap_or(0),
+        }
+    }
+}
+
+/// Ends open sessions left over from an earlier launch or idle for longer than the gap.
+fn close_finished_sessions(history: &mut ListeningHistory, now_ms: u64, launched_at_ms: u64) {
+    for session in history
+        .sessions
+        .iter_mut()
+        .filter(|session| session.ended_at_ms.is_none())
+    {
+        if session.last_played_at_ms < launched_at_ms
+            || now_ms.saturating_sub(session.last_played_at_ms) > SESSION_GAP_MS
+        {
+            session.ended_at_ms = Some(session.last_played_at_ms);
+        }
+    }
+}
+
+fn append_play(history: &mut ListeningHistory, play: ListeningPlay, launched_at_ms: u64) {
+    close_finished_sessions(history, play.played_at_ms, launched_at_ms);
+    match history
+        .sessions
+        .last_mut()
+        .filter(|session| session.ended_at_ms.is_none())
+    {
+        Some(session) => {
+            session.last_played_at_ms = play.played_at_ms;
+            session.plays.push(play);
+        }
+        None => history.sessions.push(ListeningSession {
+            started_at_ms: play.played_at_ms,
+            last_played_at_ms: play.played_at_ms,
+            ended_at_ms: None,
+            summary_dismissed: false,
+            plays: vec![play],
+        }),
+    }
+    if history.sessions.len() > SESSION_HISTORY_LIMIT {
+        let excess = history.sessions.len() - SESSION_HISTORY_LIMIT;
+        history.sessions.drain(..excess);
+    }
+}
+
+/// The most recent ended session that hasn't been dismissed, if it is long enough to show.
+fn latest_pending_summary(history: &ListeningHistory) -> Option<SessionSummary> {
+    history
+        .sessions
+        .iter()
+        .rev()
+        .find(|session| session.ended_at_ms.is_some())
+        .filter(|session| {
+            !session.summary_dismissed && session.plays.len() >= SESSION_SUMMARY_MIN_PLAYS
+        })
+        .map(ListeningSession::summary)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_stored_listening_history() -> Result<ListeningHistory, HistoryError> {
+    let conn = get_db_connection()?;
+    let result: Result<String, rusqlite::Error> = conn.query_row(
+        "SELECT value FROM settings WHERE key = 'listening_history'",
+        [],
+        |row: &rusqlite::Row| row.get(0),
+    );
+
+    match result {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| DbError::new(e.to_string())),
+        Err(_) => Ok(ListeningHistory::default()),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_stored_listening_history(history: &ListeningHistory) -> Result<(), HistoryError> {
+    let payload = serde_json::to_string(history).map_err(|e| DbError::new(e.to_string()))?;
+    let conn = get_db_connection()?;
+    conn.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES ('listening_history', ?1)",
+        [&payload],
+    )
+    .map_err(|e| DbError::new(e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn load_stored_listening_history() -> Result<ListeningHistory, HistoryError> {
+    match LocalStorage::get(LISTENING_HISTORY_KEY) {
+        Ok(history) => Ok(history),
+        Err(_) => Ok(ListeningHistory::default()),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn save_stored_listening_history(history: &ListeningHistory) -> Result<(), HistoryError> {
+    LocalStorage::set(LISTENING_HISTORY_KEY, history)
+}
+
+/// The listening history, or the guest session's own while one runs.
+pub(super) fn load_listening_history() -> Result<ListeningHistory, HistoryError> {
+    if let Some(history) = with_guest_store(|store| store.history.clone()) {
+        return Ok(history);
+    }
+    load_stored_listening_history()
+}
+
+pub(super) fn save_listening_history(history: &ListeningHistory) -> Result<(), HistoryError> {
+    if with_guest_store(|store| store.history = history.clone()).is_some() {
+        return Ok(());
+    }
+    save_stored_listening_history(history)
+}
+
+/// Appends a finished song to the current listening session.
+pub async fn record_listening_play(
+    song: Song,
+    context: Option<String>,
+) -> Result<(), HistoryError> {
+    let launched_at_ms = *LAUNCHED_AT_MS;
+    let played_at_ms = now_timestamp_millis();
+    let mut history = load_listening_history()?;
+    history.push_outcome(TrackOutcome {
+        server_id: song.server_id.clone(),
+        song_id: song.id.clone(),
+        title: song.title.clone(),
+        artist: song.artist.clone(),
+        skipped_at_secs: None,
+        context,
+        at_ms: played_at_ms,
+    });
+    append_play(
+        &mut history,
+        ListeningPlay {
+            server_id: song.server_id,
+            song_id: song.id,
+            title: song.title,
+            artist: song.artist,
+            duration_secs: song.duration,
+            played_at_ms,
+        },
+        launched_at_ms,
+    );
+    save_listening_history(&history)
+}
+
+/// Records that `song` was left for another track at `position_secs`.
+pub async fn record_listening_skip(
+    song: Song,
+    position_secs: f64,
+    context: Option<String>,
+) -> Result<(), HistoryError> {
+    let mut history = load_listening_history()?;
+    history.push_outcome(TrackOutcome {
+        server_id: song.server_id,
+        song_id: song.id,
+        title: song.title,
+        artist: song.artist,
+        skipped_at_secs: Some(position_secs.max(0.0).round() as u32),
+        context,
+        at_ms: now_timestamp_millis(),
+    });
+    save_listening_history(&history)
+}
+
+/// When each song was last played, keyed by server id and song id.
+pub async fn last_played_by_song() -> HashMap<(String, String), u64> {
+    let mut last_played = HashMap::new();
+    let Ok(history) = load_listening_history() else {
+        return last_played;
+    };
+    for play in history
+        .sessions
+        .into_iter()
+        .flat_map(|session| session.plays)
+    {
+        let played_at_ms = last_played
+            .entry((play.server_id, play.song_id))
+            .or_insert(0);
+        *played_at_ms = (*played_at_ms).max(play.played_at_ms);
+    }
+    last_played
+}
+
+/// Summary of the last finished session, unless it was dismissed or too short. Sessions left
+/// open by the previous launch are closed here.
+pub async fn pending_session_summary() -> Option<SessionSummary> {
+    let launched_at_ms = *LAUNCHED_AT_MS;
+    let mut history = load_listening_history().ok()?;
+    let before = history.clone();
+    close_finished_sessions(&mut history, now_timestamp_millis(), launched_at_ms);
+    if history != before {
+        let _ = save_listening_history(&history);
+    }
+    latest_pending_summary(&history)
+}
+
+pub async fn dismiss_session_summary(started_at_ms: u64) {
+    let Ok(mut history) = load_listening_history() else {
+        return;
+    };
+    if let Some(session) = history
+        .sessions
+        .iter_mut()
+        .find(|session| session.started_at_ms == started_at_ms)
+    {
+        session.summary_dismissed = true;
+        let _ = save_listening_history(&history);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(artist: &str, duration_secs: u32, played_at_ms: u64) -> ListeningPlay {
+        ListeningPlay {
+            server_id: "s".to_string(),
+            song_id: format!("{artist}-{played_at_ms}"),
+            title: "Song".to_string(),
+            artist: Some(artist.to_string()),
+            duration_secs,
+            played_at_ms,
+        }
+    }
+
+    #[test]
+    fn idle_gap_and_relaunch_end_sessions() {
+        let mut history = ListeningHistory::default();
+        let minute = 60 * 1000;
+        append_play(&mut history, play("A", 200, minute), 0);
+        append_play(&mut history, play("B", 180, 5 * minute), 0);
+        append_play(&mut history, play("B", 100, 10 * minute), 0);
+        assert_eq!(history.sessions.len(), 1);
+
+        // Idle past the gap: the next play opens a second session.
+        append_play(&mut history, play("C", 240, 60 * minute), 0);
+        assert_eq!(history.sessions.len(), 2);
+        assert_eq!(history.sessions[0].ended_at_ms, Some(10 * minute));
+
+        // A relaunch closes the open session even within the gap.
+        close_finished_sessions(&mut history, 61 * minute, 61 * minute);
+        assert_eq!(history.sessions[1].ended_at_ms, Some(60 * minute));
+    }
+
+    #[test]
+    fn summary_counts_plays_time_and_top_artist() {
+        let mut history = ListeningHistory::default();
+        for (index, artist) in ["A", "B", "B", "A", "B"].iter().enumerate() {
+            append_play(&mut history, play(artist, 120, index as u64 * 1000), 0);
+        }
+        assert_eq!(latest_pending_summary(&history), None);
+
+        close_finished_sessions(&mut history, 10_000, 10_000);
+        let summary = latest_pending_summary(&history).expect("summary");
+        assert_eq!(summary.songs_played, 5);
+        assert_eq!(summary.total_secs, 600);
+        assert_eq!(summary.top_artist.as_deref(), Some("B"));
+        assert_eq!(summary.top_artist_plays, 3);
+
+        history.sessions[0].summary_dismissed = true;
+        assert_eq!(latest_pending_summary(&history), None);
+    }
+}
@@ -0,0 +1,541 @@
+//! Re-pointing local song records at new ids after a server rebuilt its library database.
+//!
+//! A rebuilt Navidrome database gives every song a new id, so downloads, history, playback
+//! offsets and playlist notes stored against the old ids stop resolving. [`plan_song_id_remap`]
+//! matches the stale records to the rebuilt library: by file path first, then by title,
+//! artist, album and duration. Records that kept no album, such as listening history, match
+//! on title, artist and a known duration instead. It only remaps a record when exactly one
+//! song fits and no other record claims that song; everything else is reported and left
+//! alone. The plan is shown as a dry run before [`apply_song_id_remap`] rewrites the stored
+//! records.
+use super::{AppSettings, PlaybackState, TemporaryQueueSnapshot};
+use crate::api::Song;
+use crate::collation::match_key;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::{get_db_connection, rewrite_settings_row, DbError};
+#[cfg(target_arch = "wasm32")]
+use gloo_storage::{errors::StorageError, LocalStorage, Storage};
+
+#[cfg(not(target_arch = "wasm32"))]
+type RemapError = DbError;
+#[cfg(target_arch = "wasm32")]
+type RemapError = StorageError;
+
+/// Fewer checked ids than this can't tell a rebuilt library from a few deleted songs.
+const REBUILD_MIN_CHECKED: usize = 5;
+/// Share of checked ids, in percent, that must be gone before a rebuild is suspected.
+const REBUILD_MISSING_PERCENT: usize = 80;
+/// A rescan re-reads tags, so durations may be off by a second or two.
+const DURATION_TOLERANCE_SECS: u32 = 2;
+
+/// What is known locally about a song stored under an id that may be stale.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SongIdentity {
+    pub id: String,
+    pub title: String,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    /// Zero when the record didn't keep the duration.
+    pub duration_secs: u32,
+    pub path: Option<String>,
+}
+
+impl SongIdentity {
+    pub fn from_song(song: &Song) -> Self {
+        Self {
+            id: song.id.clone(),
+            title: song.title.clone(),
+            artist: song.artist.clone(),
+            album: song.album.clone(),
+            duration_secs: song.duration,
+            path: song.path.clone(),
+        }
+    }
+}
+
+/// Dry-run result of matching stale records against a rebuilt library.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SongIdRemapPlan {
+    /// Old id to new id for every record matched to exactly one song.
+    pub remap: BTreeMap<String, String>,
+    /// How many of the remapped records were matched by file path.
+    pub matched_by_path: usize,
+    /// Records that fit more than one song, or whose song another record also fits.
+    pub ambiguous: Vec<SongIdentity>,
+    /// Records no song in the library fits.
+    pub unmatched: Vec<SongIdentity>,
+}
+
+impl SongIdRemapPlan {
+    pub fn is_empty(&self) -> bool {
+        self.remap.is_empty()
+    }
+}
+
+/// Whether `missing` of `checked` stored ids returning "not found" looks like a rebuilt
+/// library rather than songs removed one by one.
+pub fn looks_like_library_rebuild(checked: usize, missing: usize) -> bool {
+    checked >= REBUILD_MIN_CHECKED && missing * 100 >= checked * REBUILD_MISSING_PERCENT
+}
+
+fn normalize_path(path: Option<&str>) -> Option<String> {
+    let path = path?.trim().replace('\\', "/");
+    let path = path.trim_start_matches("./").trim_start_matches('/');
+    (!path.is_empty()).then(|| path.to_string())
+}
+
+/// `value` as [`match_key`] reads it, empty when missing.
+fn normalize_identity_text(value: Option<&str>) -> String {
+    match_key(value.unwrap_or_default())
+}
+
+/// Title, artist and album key. Records without a title, or with neither artist nor album,
+/// have too little to go on and get no key.
+fn metadata_key(title: &str, artist: Option<&str>, album: Option<&str>) -> Option<String> {
+    let title = normalize_identity_text(Some(title));
+    let artist = normalize_identity_text(artist);
+    let album = normalize_identity_text(album);
+    (!title.is_empty() && (!artist.is_empty() || !album.is_empty()))
+        .then(|| format!("{title}\u{1f}{artist}\u{1f}{album}"))
+}
+
+/// Title and artist key, for records that kept no album. Both have to be known.
+fn title_artist_key(title: &str, artist: Option<&str>) -> Option<String> {
+    let title = normalize_identity_text(Some(title));
+    let artist = normalize_identity_text(artist);
+    (!title.is_empty() && !artist.is_empty()).then(|| format!("{title}\u{1f}{artist}"))
+}
+
+fn durations_agree(left: u32, right: u32) -> bool {
+    left == 0 || right == 0 || left.abs_diff(right) <= DURATION_TOLERANCE_SECS
+}
+
+enum Candidate {
+    One(usize),
+    Many,
+    None,
+}
+
+fn single_candidate(candidates: impl Iterator<Item = usize>) -> Candidate {
+    let mut found = None;
+    for index in candidates {
+        if found.is_some_and(|found| found != index) {
+            return Candidate::Many;
+        }
+        found = Some(index);
+    }
+    found.map_or(Candidate::None, Candidate::One)
+}
+
+/// Matches stale `records` to the songs of a rebuilt `library`. Records whose id still exists
+/// in the library are left out of the plan; duplicate records of one id count once.
+pub fn plan_song_id_remap(records: &[SongIdentity], library: &[Song]) -> SongIdRemapPlan {
+    let live_ids: HashSet<&str> = library.iter().map(|song| song.id.as_str()).collect();
+    let mut by_path = HashMap::<String, Vec<usize>>::new();
+    let mut by_metadata = HashMap::<String, Vec<usize>>::new();
+    let mut by_title_artist = HashMap::<String, Vec<usize>>::new();
+    for (index, song) in library.iter().enumerate() {
+        if let Some(path) = normalize_path(song.path.as_deref()) {
+            by_path.entry(path).or_default().push(index);
+        }
+        if let Some(key) = metadata_key(&song.title, song.artist.as_deref(), song.album.as_deref())
+        {
+            by_metadata.entry(key).or_default().push(index);
+        }
+        if let Some(key) = title_artist_key(&song.title, song.artist.as_deref()) {
+            by_title_artist.entry(key).or_default().push(index);
+        }
+    }
+
+    let mut plan = SongIdRemapPlan::default();
+    let mut seen = HashSet::<&str>::new();
+    // (record, library index, matched by path)
+    let mut matches = Vec::<(&SongIdentity, usize, bool)>::new();
+    for record in records {
+        let id = record.id.trim();
+        if id.is_empty() || live_ids.contains(id) || !seen.insert(id) {
+            continue;
+        }
+
+        let path_match = normalize_path(record.path.as_deref())
+            .and_then(|path| by_path.get(&path))
+            .map(|indices| single_candidate(indices.iter().copied()))
+            .unwrap_or(Candidate::None);
+        match path_match {
+            Candidate::One(index) => {
+                matches.push((record, index, true));
+                continue;
+            }
+            Candidate::Many => {
+                plan.ambiguous.push(record.clone());
+                continue;
+            }
+            Candidate::None => {}
+        }
+
+        // Without an album, title and artist alone are too loose unless the duration agrees.
+        let has_album = !normalize_identity_text(record.album.as_deref()).is_empty();
+        let candidates = if has_album {
+            metadata_key(
+                &record.title,
+                record.artist.as_deref(),
+                record.album.as_deref(),
+            )
+            .and_then(|key| by_metadata.get(&key))
+        } else if record.duration_secs > 0 {
+            title_artist_key(&record.title, record.artist.as_deref())
+                .and_then(|key| by_title_artist.get(&key))
+        } else {
+            None
+        };
+        let metadata_match = candidates
+            .map(|indices| {
+                single_candidate(indices.iter().copied().filter(|index| {
+                    durations_agree(record.duration_secs, library[*index].duration)
+                }))
+            })
+            .unwrap_or(Candidate::None);
+        match metadata_match {
+            Candidate::One(index) => matches.push((record, index, false)),
+            Candidate::Many => plan.ambiguous.push(record.clone()),
+            Candidate::None => plan.unmatched.push(record.clone()),
+        }
+    }
+
+    // Two records claiming one song can't both be right, so neither is remapped.
+    let mut claims = HashMap::<usize, usize>::new();
+    for (_, index, _) in &matches {
+        *claims.entry(*index).or_default() += 1;
+    }
+    for (record, index, by_path) in matches {
+        if claims[&index] > 1 {
+            plan.ambiguous.push(record.clone());
+            continue;
+        }
+        plan.remap
+            .insert(record.id.trim().to_string(), library[index].id.clone());
+        if by_path {
+            plan.matched_by_path += 1;
+        }
+    }
+    plan
+}
+
+impl PlaybackState {
+    pub fn remap_song_ids(&mut self, server_id: &str, remap: &BTreeMap<String, String>) {
+        if self.server_id.as_deref() == Some(server_id) {
+            if let Some(new_id) = self.song_id.as_ref().and_then(|id| remap.get(id)) {
+                self.song_id = Some(new_id.clone());
+            }
+        }
+        for item in self
+            .queue
+            .iter_mut()
+            .filter(|item| item.server_id == server_id)
+        {
+            if let Some(new_id) = remap.get(&item.song_id) {
+                item.song_id = new_id.clone();
+            }
+        }
+    }
+}
+
+impl TemporaryQueueSnapshot {
+    pub fn remap_song_ids(&mut self, server_id: &str, remap: &BTreeMap<String, String>) {
+        for song in self.queue.iter_mut().chain(self.now_playing.as_mut()) {
+            remap_song(song, server_id, remap);
+        }
+    }
+}
+
+/// Songs of `server_id` in the listening history, one record per id. History keeps no
+/// album or path, so these records only match by title, artist and duration.
+pub async fn history_song_identities(server_id: &str) -> Vec<SongIdentity> {
+    let Ok(history) = super::history::load_listening_history() else {
+        return Vec::new();
+    };
+    let mut seen = HashSet::new();
+    history
+        .sessions
+        .iter()
+        .flat_map(|session| session.plays.iter())
+        .filter(|play| play.server_id == server_id && seen.insert(play.song_id.clone()))
+        .map(|play| SongIdentity {
+            id: play.song_id.clone(),
+            title: play.title.clone(),
+            artist: play.artist.clone(),
+            album: None,
+            duration_secs: play.duration_secs,
+            path: None,
+        })
+        .collect()
+}
+
+/// Points `song` at its new id when it belongs to `server_id` and was remapped.
+pub fn remap_song(song: &mut Song, server_id: &str, remap: &BTreeMap<String, String>) {
+    if song.server_id == server_id {
+        if let Some(new_id) = remap.get(&song.id) {
+            song.id = new_id.clone();
+        }
+    }
+}
+
+/// Rewrites every stored song id of `server_id` found in `remap`: per-song offsets and
+/// playlist notes in the settings, the saved playback state and queue snapshots, listening
+/// history and resume points. Downloads are remapped separately by the offline cache.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn apply_song_id_remap(
+    server_id: String,
+    remap: BTreeMap<String, String>,
+) -> Result<(), RemapError> {
+    let mut conn = get_db_connection()?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| DbError::new(e.to_string()))?;
+
+    rewrite_settings_row(&tx, "app_settings", |settings: &mut AppSettings| {
+        settings.remap_song_ids(&server_id, &remap)
+    })?;
+    rewrite_settings_row(&tx, "playback_state", |state: &mut PlaybackState| {
+        state.remap_song_ids(&server_id, &remap)
+    })?;
+    rewrite_settings_row(
+        &tx,
+        "temporary_queue_snapshots",
+        |snapshots: &mut Vec<TemporaryQueueSnapshot>| {
+            for snapshot in snapshots.iter_mut() {
+                snapshot.remap_song_ids(&server_id, &remap);
+            }
+        },
+    )?;
+    rewrite_settings_row(
+        &tx,
+        "listening_history",
+        |history: &mut super::history::ListeningHistory| history.remap_song_ids(&server_id, &remap),
+    )?;
+    rewrite_settings_row(
+        &tx,
+        "container_resume_points",
+        |points: &mut super::resume_points::ResumePoints| points.remap_song_ids(&server_id, &remap),
+    )?;
+
+    tx.commit().map_err(|e| DbError::new(e.to_string()))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn apply_song_id_remap(
+    server_id: String,
+    remap: BTreeMap<String, String>,
+) -> Result<(), RemapError> {
+    if let Ok(mut settings) = LocalStorage::get::<AppSettings>(super::SETTINGS_KEY) {
+        settings.remap_song_ids(&server_id, &remap);
+        LocalStorage::set(super::SETTINGS_KEY, settings)?;
+    }
+    if let Ok(mut state) = LocalStorage::get::<PlaybackState>(super::PLAYBACK_KEY) {
+        state.remap_song_ids(&server_id, &remap);
+        LocalStorage::set(super::PLAYBACK_KEY, state)?;
+    }
+    if let Ok(mut snapshots) =
+        LocalStorage::get::<Vec<TemporaryQueueSnapshot>>(super::TEMP_QUEUE_SNAPSHOTS_KEY)
+    {
+        for snapshot in snapshots.iter_mut() {
+            snapshot.remap_song_ids(&server_id, &remap);
+        }
+        LocalStorage::set(super::TEMP_QUEUE_SNAPSHOTS_KEY, snapshots)?;
+    }
+    if let Ok(mut history) =
+        LocalStorage::get::<super::history::ListeningHistory>(super::history::LISTENING_HISTORY_KEY)
+    {
+        history.remap_song_ids(&server_id, &remap);
+        LocalStorage::set(super::history::LISTENING_HISTORY_KEY, history)?;
+    }
+    if let Ok(mut points) = LocalStorage::get::<super::resume_points::ResumePoints>(
+        super::resume_points::RESUME_POINTS_KEY,
+    ) {
+        points.remap_song_ids(&server_id, &remap);
+        LocalStorage::set(super::resume_points::RESUME_POINTS_KEY, points)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library_song(id: &str, title: &str, artist: &str, album: &str, duration: u32) -> Song {
+        Song {
+            id: id.to_string(),
+            title: title.to_string(),
+            artist: Some(artist.to_string()),
+            album: Some(album.to_string()),
+            duration,
+            server_id: "srv".to_string(),
+            ..Song::default()
+        }
+    }
+
+    fn record(id: &str, title: &str, artist: &str, album: &str, duration: u32) -> SongIdentity {
+        SongIdentity {
+            id: id.to_string(),
+            title: title.to_string(),
+            artist: Some(artist.to_string()),
+            album: Some(album.to_string()),
+            duration_secs: duration,
+            path: None,
+        }
+    }
+
+    #[test]
+    fn rebuild_needs_enough_checks_and_mostly_missing_ids() {
+        assert!(!looks_like_library_rebuild(3, 3));
+        assert!(!looks_like_library_rebuild(10, 7));
+        assert!(looks_like_library_rebuild(10, 8));
+        assert!(looks_like_library_rebuild(5, 5));
+    }
+
+    #[test]
+    fn matches_by_path_before_metadata() {
+        let mut renamed = library_song("new-1", "Retitled", "Someone", "Else", 200);
+        renamed.path = Some("Artist/Album/01 Song.flac".to_string());
+        let mut old = record("old-1", "Song", "Artist", "Album", 180);
+        old.path = Some("/Artist/Album/01 Song.flac".to_string());
+
+        let plan = plan_song_id_remap(&[old], &[renamed]);
+        assert_eq!(plan.remap.get("old-1").map(String::as_str), Some("new-1"));
+        assert_eq!(plan.matched_by_path, 1);
+    }
+
+    #[test]
+    fn matches_by_metadata_ignoring_case_accents_punctuation_and_small_duration_drift() {
+        let library = vec![
+            library_song("new-1", "Don't Stop", "The Band", "Live!", 241),
+            library_song("new-2", "Other", "The Band", "Live!", 100),
+            library_song("new-3", "Jóga", "Björk", "Homogenic", 305),
+        ];
+        let plan = plan_song_id_remap(
+            &[
+                record("old-1", "dont stop", "the band", "live", 240),
+                record("old-3", "Joga", "Bjork", "Homogenic", 305),
+            ],
+            &library,
+        );
+        assert_eq!(plan.remap.get("old-1").map(String::as_str), Some("new-1"));
+        assert_eq!(plan.remap.get("old-3").map(String::as_str), Some("new-3"));
+        assert_eq!(plan.matched_by_path, 0);
+    }
+
+    #[test]
+    fn duration_mismatch_is_not_a_match() {
+        let library = vec![library_song("new-1", "Song", "Artist", "Album", 300)];
+        let plan = plan_song_id_remap(&[record("old-1", "Song", "Artist", "Album", 200)], &library);
+        assert!(plan.remap.is_empty());
+        assert_eq!(plan.unmatched.len(), 1);
+    }
+
+    #[test]
+    fn several_fitting_songs_leave_the_record_alone() {
+        // Same song on a deluxe reissue tagged with the same album name.
+        let library = vec![
+            library_song("new-1", "Song", "Artist", "Album", 200),
+            library_song("new-2", "Song", "Artist", "Album", 201),
+        ];
+        let plan = plan_song_id_remap(&[record("old-1", "Song", "Artist", "Album", 200)], &library);
+        assert!(plan.remap.is_empty());
+        assert_eq!(plan.ambiguous.len(), 1);
+    }
+
+    #[test]
+    fn two_records_claiming_one_song_are_both_ambiguous() {
+        let library = vec![library_song("new-1", "Song", "Artist", "Album", 200)];
+        let plan = plan_song_id_remap(
+            &[
+                record("old-1", "Song", "Artist", "Album", 200),
+                record("old-2", "Song", "Artist", "Album", 0),
+            ],
+            &library,
+        );
+        assert!(plan.remap.is_empty());
+        assert_eq!(plan.ambiguous.len(), 2);
+    }
+
+    #[test]
+    fn ids_still_in_the_library_and_repeated_records_are_skipped() {
+        let library = vec![
+            library_song("kept", "Kept", "Artist", "Album", 100),
+            library_song("new-1", "Song", "Artist", "Album", 200),
+        ];
+        let plan = plan_song_id_remap(
+            &[
+                record("kept", "Kept", "Artist", "Album", 100),
+                record("old-1", "Song", "Artist", "Album", 200),
+                record("old-1", "Song", "Artist", "Album", 200),
+            ],
+            &library,
+        );
+        assert_eq!(plan.remap.len(), 1);
+        assert!(plan.ambiguous.is_empty());
+        assert!(plan.unmatched.is_empty());
+    }
+
+    #[test]
+    fn records_with_only_a_title_are_not_matched() {
+        let library = vec![library_song("new-1", "Intro", "Artist", "Album", 60)];
+        let bare = SongIdentity {
+            id: "old-1".to_string(),
+            title: "Intro".to_string(),
+            ..SongIdentity::default()
+        };
+        let plan = plan_song_id_remap(&[bare], &library);
+        assert!(plan.remap.is_empty());
+        assert_eq!(plan.unmatched.len(), 1);
+    }
+
+    #[test]
+    fn album_less_history_records_match_on_title_artist_and_duration() {
+        let library = vec![
+            library_song("new-1", "Song", "Artist", "Album", 200),
+            library_song("new-2", "Other", "Artist", "Album", 150),
+        ];
+        let history = |id: &str, title: &str, duration: u32| SongIdentity {
+            album: None,
+            ..record(id, title, "Artist", "", duration)
+        };
+        let plan = plan_song_id_remap(
+            &[
+                history("old-1", "Song", 201),
+                history("old-2", "Other", 90),
+                history("old-3", "Other", 0),
+            ],
+            &library,
+        );
+        assert_eq!(plan.remap.len(), 1);
+        assert_eq!(plan.remap.get("old-1").map(String::as_str), Some("new-1"));
+        assert_eq!(plan.unmatched.len(), 2);
+    }
+
+    #[test]
+    fn remaps_playback_state_for_the_server_only() {
+        let mut state = PlaybackState {
+            song_id: Some("old-1".to_string()),
+            server_id: Some("srv".to_string()),
+            queue: vec![
+                super::super::QueueItem {
+                    song_id: "old-1".to_string(),
+                    server_id: "srv".to_string(),
+                },
+                super::super::QueueItem {
+                    song_id: "old-1".to_string(),
+                    server_id: "other".to_string(),
+                },
+            ],
+            ..PlaybackState::default()
+        };
+        let remap = BTreeMap::from([("old-1".to_string(), "new-1".to_string())]);
+        state.remap_song_ids("srv", &remap);
+        assert_eq!(state.song_id.as_deref(), Some("new-1"));
+        assert_eq!(state.queue[0].song_id, "new-1");
+        assert_eq!(state.queue[1].song_id, "old-1");
+    }
+}
@@ -1,16 +1,61 @@
 use crate::api::{
     default_lyrics_provider_order,
-    models::{ServerConfig, Song},
+    icy_titles::DEFAULT_RADIO_SLOGAN_PATTERNS,
+    models::{Artist, ServerConfig, Song, DEFAULT_CLIENT_NAME},
     normalize_lyrics_provider_order,
 };
+use crate::equalizer::{default_eq_band_gains, normalized_band_gains};
 #[cfg(not(target_arch = "wasm32"))]
-use crate::storage::app_data_dir;
+use crate::storage::{app_data_dir, database_file_name};
 use dioxus::prelude::*;
+use guest_session::{divert_to_guest_store, with_guest_store};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[cfg(target_arch = "wasm32")]
 use gloo_storage::{errors::StorageError, LocalStorage, Storage};
 
+mod guest_session;
+mod history;
+mod id_remap;
+mod pending_sync;
+#[cfg(not(target_arch = "wasm32"))]
+mod recovery;
+mod resume_points;
+mod settings_profiles;
+mod skip_stats;
+
+pub use guest_session::{
+    end_guest_session, is_valid_guest_pin, resume_guest_session, start_guest_session,
+};
+pub use history::{
+    dismiss_session_summary, is_skip, last_played_by_song, pending_session_summary,
+    record_listening_play, record_listening_skip,
+};
+pub use id_remap::{
+    apply_song_id_remap, history_song_identities, looks_like_library_rebuild, plan_song_id_remap,
+    remap_song, SongIdRemapPlan, SongIdentity,
+};
+pub use pending_sync::{
+    discard_pending_mutation, next_pending_mutation, pending_mutations, queue_pending_mutation,
+    record_pending_mutation_outcome, retry_pending_mutation, PendingMutation, PendingMutationOp,
+    PendingMutationOutcome, PendingSyncPass,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use recovery::{
+    backup_database_on_shutdown, has_database_backup, restore_database_backup,
+    start_fresh_database, DatabaseRecovery,
+};
+pub use resume_points::{
+    clear_container_resume_point, container_resume_point, save_container_resume_point,
+    ContainerResumePoint, ResumeContainerKind, ResumePointDebounce,
+};
+pub use settings_profiles::{parse_settings_bundle, settings_bundle_json, SettingsProfile};
+pub use skip_stats::{
+    most_skipped_songs, reset_song_skip_stats, song_skip_stats_for, SkipShuffleWeights,
+    SongSkipStats,
+};
+
 /// Error type for database operations on native platforms
 #[cfg(not(target_arch = "wasm32"))]
 #[derive(Debug)]
@@ -50,6 +95,31 @@ pub enum RepeatMode {
     Off,
     All,
     One,
+    /// Play each track `AppSettings::repeat_count` times, then move on.
+    Count,
+}
+
+impl RepeatMode {
+    /// The next mode for the repeat button: off, all, one, N times.
+    pub fn cycled(self) -> Self {
+        match self {
+            RepeatMode::Off => RepeatMode::All,
+            RepeatMode::All => RepeatMode::One,
+            RepeatMode::One => RepeatMode::Count,
+            RepeatMode::Count => RepeatMode::Off,
+        }
+    }
+}
+
+/// Which ReplayGain value levels playback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayGainMode {
+    /// Every track at the same loudness.
+    #[default]
+    Track,
+    /// Whole albums at the same loudness, keeping the differences between their tracks.
+    Album,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
@@ -62,6 +132,235 @@ pub enum ArtworkDownloadPreference {
     PreferId3,
 }
 
+/// What the desktop app keeps awake while audio is playing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SleepInhibitMode {
+    Never,
+    #[default]
+    SystemSleep,
+    SystemAndDisplay,
+}
+
+/// How the desktop window reflects playback in the taskbar/dock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaybackIndicatorMode {
+    Off,
+    WindowTitle,
+    #[default]
+    TitleAndBadge,
+}
+
+/// How eagerly audio is buffered ahead of the playhead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BufferingStrategy {
+    /// Let the player buffer the whole track and fetch upcoming tracks in full, for
+    /// connections that drop out.
+    Aggressive,
+    #[default]
+    Balanced,
+    /// Only load metadata up front and skip preloading the next track, for metered links.
+    Minimal,
+}
+
+impl BufferingStrategy {
+    /// Value for the `preload` attribute of the playing `<audio>` element.
+    pub fn preload_attribute(self) -> &'static str {
+        match self {
+            BufferingStrategy::Aggressive => "auto",
+            BufferingStrategy::Balanced | BufferingStrategy::Minimal => "metadata",
+        }
+    }
+}
+
+/// What shuffle does with songs that are usually skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SkippedSongShuffle {
+    #[default]
+    Off,
+    /// Shuffle them towards the end of the order.
+    DownWeight,
+    /// Leave them out of generated shuffle queues and play them last when shuffling a
+    /// collection.
+    Exclude,
+}
+
+/// Which artists the artists view lists for a server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtistBrowseMode {
+    #[default]
+    All,
+    /// Only artists credited with at least one album, leaving out featured-only artists.
+    AlbumArtists,
+}
+
+impl ArtistBrowseMode {
+    pub fn includes(self, artist: &Artist) -> bool {
+        match self {
+            ArtistBrowseMode::All => true,
+            ArtistBrowseMode::AlbumArtists => artist.album_count > 0,
+        }
+    }
+}
+
+/// How much each play-queue row shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueRowDensity {
+    #[default]
+    WithArt,
+    /// Text-only rows, so more of the queue fits on screen.
+    Compact,
+}
+
+/// When the player bar shrinks to a slim strip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerBarMode {
+    /// Collapsed while nothing is playing or queued, full size otherwise.
+    #[default]
+    Auto,
+    /// Always full size unless collapsed by hand.
+    AlwaysExpanded,
+}
+
+/// Language of dates and relative times ("3 hours ago").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DateLocale {
+    #[default]
+    EnUs,
+    EnGb,
+    De,
+    Fr,
+    Es,
+}
+
+/// Whether times use a 12-hour or 24-hour clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockFormat {
+    /// Whatever is usual for the date language.
+    #[default]
+    Auto,
+    TwelveHour,
+    TwentyFourHour,
+}
+
+/// Tile size of the album, artist and playlist grids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GridDensity {
+    /// Column count follows the window width.
+    #[default]
+    Auto,
+    Small,
+    Medium,
+    Large,
+}
+
+impl GridDensity {
+    /// Column classes for a grid, or `None` to keep the view's own responsive columns.
+    pub fn columns_class(self) -> Option<&'static str> {
+        match self {
+            GridDensity::Auto => None,
+            GridDensity::Small => {
+                Some("grid-cols-3 sm:grid-cols-4 md:grid-cols-5 lg:grid-cols-6 xl:grid-cols-8")
+            }
+            GridDensity::Medium => {
+                Some("grid-cols-2 sm:grid-cols-3 md:grid-cols-4 lg:grid-cols-5 xl:grid-cols-6")
+            }
+            GridDensity::Large => Some("grid-cols-1 sm:grid-cols-2 md:grid-cols-3 lg:grid-cols-4"),
+        }
+    }
+
+    /// Widest a tile's artwork is drawn, in CSS pixels, for picking the artwork size.
+    pub fn tile_px(self) -> u32 {
+        match self {
+            GridDensity::Small => 140,
+            GridDensity::Auto | GridDensity::Medium => 180,
+            GridDensity::Large => 300,
+        }
+    }
+}
+
+/// Custom playback range for one song, in seconds from the start of the track.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub struct SongPlaybackOffsets {
+    #[serde(default)]
+    pub start_secs: Option<f64>,
+    #[serde(default)]
+    pub end_secs: Option<f64>,
+}
+
+impl SongPlaybackOffsets {
+    pub fn is_empty(&self) -> bool {
+        self.start_secs.is_none() && self.end_secs.is_none()
+    }
+}
+
+/// A station pinned from an artist page. It lives on this device only and plays as an
+/// endless mix of the artist's top songs and similar songs, streamed as regular tracks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct ArtistStation {
+    pub id: String,
+    pub server_id: String,
+    pub artist_id: String,
+    pub artist_name: String,
+    /// Shown in the Radio view; starts as the artist name.
+    pub name: String,
+    /// The artist's image, used as the station artwork.
+    #[serde(default)]
+    pub cover_art: Option<String>,
+}
+
+/// A weekly window during which the output volume is capped, e.g. while someone sleeps
+/// next door. Times are local wall-clock minutes after midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    #[serde(default)]
+    pub enabled: bool,
+    pub start_minute: u16,
+    /// Earlier than `start_minute` for windows that run past midnight; equal to it for a
+    /// window covering the whole day.
+    pub end_minute: u16,
+    /// Days the window starts on, Monday first.
+    pub days: [bool; 7],
+    /// Highest output volume while quiet hours are on, in percent.
+    pub cap_percent: u8,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start_minute: 21 * 60,
+            end_minute: 7 * 60,
+            days: [true; 7],
+            cap_percent: 30,
+        }
+    }
+}
+
+/// Local note and optional cue point attached to one playlist entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct PlaylistItemAnnotation {
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub cue_secs: Option<f64>,
+}
+
+impl PlaylistItemAnnotation {
+    pub fn is_empty(&self) -> bool {
+        self.notes.trim().is_empty() && self.cue_secs.is_none()
+    }
+}
+
 /// App settings stored in the database
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AppSettings {
@@ -70,11 +369,34 @@ pub struct AppSettings {
     pub theme: String,
     pub crossfade_enabled: bool,
     pub crossfade_duration: u32, // seconds
+    /// Crossfades skips and picked tracks too, not only tracks that play to their end.
+    #[serde(default)]
+    pub crossfade_on_skip: bool,
+    /// Buffers the next queued track and starts it the moment this one ends.
+    #[serde(default)]
+    pub gapless_enabled: bool,
+    /// Speed the last song played at. Radio always plays at 1x.
+    #[serde(default = "default_playback_rate")]
+    pub playback_rate: f64,
     pub replay_gain: bool,
     #[serde(default)]
+    pub replay_gain_mode: ReplayGainMode,
+    /// Added to the gain of songs with ReplayGain tags, within ±`REPLAY_GAIN_PREAMP_DB`.
+    #[serde(default)]
+    pub replay_gain_preamp_db: f64,
+    /// Runs the web player through the graphic equalizer.
+    #[serde(default)]
+    pub eq_enabled: bool,
+    /// Boost or cut per equalizer band in dB, low to high.
+    #[serde(default = "default_eq_band_gains")]
+    pub eq_band_gains: Vec<f32>,
+    #[serde(default)]
     pub shuffle_enabled: bool,
     #[serde(default)]
     pub repeat_mode: RepeatMode,
+    /// Plays per track in `RepeatMode::Count`.
+    #[serde(default = "default_repeat_count")]
+    pub repeat_count: u32,
     #[serde(default)]
     pub cache_enabled: bool,
     #[serde(default)]
@@ -111,16 +433,31 @@ pub struct AppSettings {
     pub downloads_enabled: bool,
     #[serde(default)]
     pub auto_downloads_enabled: bool,
+    /// Hold background downloads while a track is streaming from the server.
+    #[serde(default)]
+    pub pause_downloads_while_streaming: bool,
     #[serde(default = "default_auto_download_tier")]
     pub auto_download_tier: u8,
     #[serde(default = "default_auto_download_album_count")]
     pub auto_download_album_count: u32,
     #[serde(default = "default_auto_download_playlist_count")]
     pub auto_download_playlist_count: u32,
+    /// Lowest `userRating` (1-5) auto-download keeps; 0 turns the filter off.
+    #[serde(default)]
+    pub auto_download_min_rating: u8,
+    /// Whether unrated songs pass the rating filter.
+    #[serde(default = "default_auto_download_include_unrated")]
+    pub auto_download_include_unrated: bool,
     #[serde(default = "default_download_limit_count")]
     pub download_limit_count: u32,
     #[serde(default = "default_download_limit_mb")]
     pub download_limit_mb: u32,
+    /// Auto-download removes downloads not played for this many days; 0 turns it off.
+    #[serde(default)]
+    pub download_cleanup_idle_days: u32,
+    /// Share of the download size limit the store must pass before that cleanup runs.
+    #[serde(default = "default_download_cleanup_trigger_percent")]
+    pub download_cleanup_trigger_percent: u8,
     #[serde(default = "default_artwork_download_preference")]
     pub artwork_download_preference: ArtworkDownloadPreference,
     #[serde(default)]
@@ -129,6 +466,442 @@ pub struct AppSettings {
     pub home_layout_json: String,
     #[serde(default = "default_home_feed_load_profile")]
     pub home_feed_load_profile: String,
+    #[serde(default)]
+    pub fast_start_enabled: bool,
+    #[serde(default = "default_fast_start_bitrate_kbps")]
+    pub fast_start_bitrate_kbps: u32,
+    /// Bitrate cap asked of the server for streams, in kbps; 0 streams the original file.
+    /// Downloads keep their own quality.
+    #[serde(default)]
+    pub stream_max_bitrate_kbps: u32,
+    /// Format capped streams are transcoded to.
+    #[serde(default = "default_stream_format")]
+    pub stream_format: String,
+    /// How often the native player is polled for progress while playing, in milliseconds.
+    #[serde(default = "default_progress_poll_interval_ms")]
+    pub progress_poll_interval_ms: u32,
+    /// Publish progress on every poll instead of once per displayed second.
+    #[serde(default)]
+    pub smooth_progress_updates: bool,
+    #[serde(default)]
+    pub buffering_strategy: BufferingStrategy,
+    /// Largest track, in MB, fetched in full ahead of time by the aggressive strategy.
+    #[serde(default = "default_fetch_ahead_limit_mb")]
+    pub fetch_ahead_limit_mb: u32,
+    /// Pause when headphones or another audio output disconnect.
+    #[serde(default = "default_pause_on_unplug")]
+    pub pause_on_unplug: bool,
+    #[serde(default)]
+    pub quiet_hours: QuietHours,
+    #[serde(default)]
+    pub queue_row_density: QueueRowDensity,
+    /// Keep the playing row of the queue view centered as tracks advance.
+    #[serde(default)]
+    pub queue_auto_follow: bool,
+    #[serde(default)]
+    pub grid_density: GridDensity,
+    #[serde(default)]
+    pub player_bar_mode: PlayerBarMode,
+    #[serde(default)]
+    pub date_locale: DateLocale,
+    #[serde(default)]
+    pub clock_format: ClockFormat,
+    /// Show the albums page as rows instead of a grid.
+    #[serde(default)]
+    pub albums_list_view: bool,
+    /// Sort "The Beatles" under B in artist and album lists.
+    #[serde(default)]
+    pub sort_ignore_articles: bool,
+    /// Keep a local listening history and show a summary of the last session on launch.
+    #[serde(default)]
+    pub session_summary_enabled: bool,
+    #[serde(default)]
+    pub skipped_song_shuffle: SkippedSongShuffle,
+    /// Share of plays, in percent, a song must be skipped in to count as usually skipped.
+    #[serde(default = "default_skip_ratio_threshold_percent")]
+    pub skip_ratio_threshold_percent: u8,
+    /// Plays a song needs before its skip ratio is trusted.
+    #[serde(default = "default_skip_min_plays")]
+    pub skip_min_plays: u32,
+    /// Most songs queued by "Shuffle favorites".
+    #[serde(default = "default_favorites_shuffle_limit")]
+    pub favorites_shuffle_limit: usize,
+    #[serde(default)]
+    pub sleep_inhibit_mode: SleepInhibitMode,
+    #[serde(default)]
+    pub playback_indicator_mode: PlaybackIndicatorMode,
+    #[serde(default = "default_negative_cache_enabled")]
+    pub negative_cache_enabled: bool,
+    #[serde(default)]
+    pub musicbrainz_enabled: bool,
+    /// Block every request to hosts other than the configured servers.
+    #[serde(default)]
+    pub strict_privacy_mode: bool,
+    /// Hidden wide-layout song table columns, keyed by view.
+    #[serde(default)]
+    pub song_table_hidden_columns: BTreeMap<String, Vec<String>>,
+    /// Opt-in wide-layout song table columns (e.g. composer) turned on, keyed by view.
+    #[serde(default)]
+    pub song_table_shown_columns: BTreeMap<String, Vec<String>>,
+    /// ICY title templates such as "{title} / {artist}", keyed by "server_id:station_id".
+    #[serde(default)]
+    pub radio_title_templates: BTreeMap<String, String>,
+    #[serde(default = "default_radio_slogan_patterns")]
+    pub radio_slogan_patterns: Vec<String>,
+    /// Per-song start/end offsets, keyed by "server_id:song_id".
+    #[serde(default)]
+    pub song_playback_offsets: BTreeMap<String, SongPlaybackOffsets>,
+    /// Playlist entry notes and cue points, keyed by "server_id:playlist_id:song_id:position".
+    #[serde(default)]
+    pub playlist_item_annotations: BTreeMap<String, PlaylistItemAnnotation>,
+    /// Music folder the library views are limited to, keyed by server id. Missing means
+    /// "All folders".
+    #[serde(default)]
+    pub music_folder_selection: BTreeMap<String, String>,
+    /// Artists view mode, keyed by server id. Missing means all artists.
+    #[serde(default)]
+    pub artist_browse_modes: BTreeMap<String, ArtistBrowseMode>,
+    /// Album header "Continuous playback" choices, keyed by "server_id:album_id". Missing
+    /// means the album's title and genre decide.
+    #[serde(default)]
+    pub continuous_album_overrides: BTreeMap<String, bool>,
+    /// Artist stations pinned from artist pages, in the order they were pinned.
+    #[serde(default)]
+    pub artist_stations: Vec<ArtistStation>,
+    /// Saved settings profiles, in the order they were saved. Built-in presets aren't stored.
+    #[serde(default)]
+    pub settings_profiles: Vec<SettingsProfile>,
+    /// The profile last applied or saved, if any.
+    #[serde(default)]
+    pub active_settings_profile: Option<String>,
+    /// First-run setup (add a server, then the smart cache suggestion) was finished or skipped.
+    #[serde(default)]
+    pub onboarding_completed: bool,
+}
+
+fn default_radio_slogan_patterns() -> Vec<String> {
+    DEFAULT_RADIO_SLOGAN_PATTERNS
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect()
+}
+
+pub fn radio_station_template_key(server_id: &str, station_id: &str) -> String {
+    format!("{server_id}:{station_id}")
+}
+
+pub fn song_playback_offsets_key(server_id: &str, song_id: &str) -> String {
+    format!("{server_id}:{song_id}")
+}
+
+pub fn continuous_album_key(server_id: &str, album_id: &str) -> String {
+    format!("{server_id}:{album_id}")
+}
+
+pub fn playlist_item_annotation_key(
+    server_id: &str,
+    playlist_id: &str,
+    song_id: &str,
+    position: usize,
+) -> String {
+    format!("{server_id}:{playlist_id}:{song_id}:{position}")
+}
+
+/// Splits a playlist annotation key back into `(song_id, position)` for one playlist.
+fn parse_playlist_item_annotation_key<'a>(
+    key: &'a str,
+    server_id: &str,
+    playlist_id: &str,
+) -> Option<(&'a str, usize)> {
+    let rest = key
+        .strip_prefix(server_id)?
+        .strip_prefix(':')?
+        .strip_prefix(playlist_id)?
+        .strip_prefix(':')?;
+    let (song_id, position) = rest.rsplit_once(':')?;
+    Some((song_id, position.parse().ok()?))
+}
+
+impl AppSettings {
+    pub fn artist_browse_mode_for(&self, server_id: &str) -> ArtistBrowseMode {
+        self.artist_browse_modes
+            .get(server_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn set_artist_browse_mode(&mut self, server_id: &str, mode: ArtistBrowseMode) {
+        if mode == ArtistBrowseMode::All {
+            self.artist_browse_modes.remove(server_id);
+        } else {
+            self.artist_browse_modes.insert(server_id.to_string(), mode);
+        }
+    }
+
+    pub fn continuous_album_override(&self, server_id: &str, album_id: &str) -> Option<bool> {
+        self.continuous_album_overrides
+            .get(&continuous_album_key(server_id, album_id))
+            .copied()
+    }
+
+    pub fn set_continuous_album_override(
+        &mut self,
+        server_id: &str,
+        album_id: &str,
+        continuous: Option<bool>,
+    ) {
+        let key = continuous_album_key(server_id, album_id);
+        match continuous {
+            Some(continuous) => {
+                self.continuous_album_overrides.insert(key, continuous);
+            }
+            None => {
+                self.continuous_album_overrides.remove(&key);
+            }
+        }
+    }
+
+    pub fn playback_offsets_for(&self, server_id: &str, song_id: &str) -> SongPlaybackOffsets {
+        self.song_playback_offsets
+            .get(&song_playback_offsets_key(server_id, song_id))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn playlist_item_annotation(
+        &self,
+        server_id: &str,
+        playlist_id: &str,
+        song_id: &str,
+        position: usize,
+    ) -> Option<&PlaylistItemAnnotation> {
+        self.playlist_item_annotations
+            .get(&playlist_item_annotation_key(
+                server_id,
+                playlist_id,
+                song_id,
+                position,
+            ))
+    }
+
+    /// Cue point for `song_id` in a playlist. The entry at `position` wins; otherwise the
+    /// first annotated occurrence of the song is used.
+    pub fn playlist_cue_for(
+        &self,
+        server_id: &str,
+        playlist_id: &str,
+        song_id: &str,
+        position: Option<usize>,
+    ) -> Option<f64> {
+        if let Some(cue) = position.and_then(|position| {
+            self.playlist_item_annotation(server_id, playlist_id, song_id, position)
+                .and_then(|annotation| annotation.cue_secs)
+        }) {
+            return Some(cue);
+        }
+        self.playlist_item_annotations
+            .iter()
+            .filter(|(key, _)| {
+                parse_playlist_item_annotation_key(key, server_id, playlist_id)
+                    .is_some_and(|(id, _)| id == song_id)
+            })
+            .find_map(|(_, annotation)| annotation.cue_secs)
+    }
+
+    /// Re-keys a playlist's annotations after its order changed. Entries whose position no
+    /// longer holds their song move to the first free occurrence of that song id; entries
+    /// for songs that left the playlist are dropped. Returns whether anything changed.
+    pub fn reconcile_playlist_item_annotations(
+        &mut self,
+        server_id: &str,
+        playlist_id: &str,
+        song_ids: &[String],
+    ) -> bool {
+        let mut current = Vec::new();
+        for (key, annotation) in &self.playlist_item_annotations {
+            if let Some((song_id, position)) =
+                parse_playlist_item_annotation_key(key, server_id, playlist_id)
+            {
+                current.push((
+                    key.clone(),
+                    song_id.to_string(),
+                    position,
+                    annotation.clone(),
+                ));
+            }
+        }
+        if current.is_empty() {
+            return false;
+        }
+
+        let mut placed = BTreeMap::<usize, (String, PlaylistItemAnnotation)>::new();
+        let mut stale = Vec::new();
+        for (_, song_id, position, annotation) in &current {
+            if song_ids.get(*position) == Some(song_id) {
+                placed.insert(*position, (song_id.clone(), annotation.clone()));
+            } else {
+                stale.push((song_id, *position, annotation));
+            }
+        }
+        if stale.is_empty() {
+            return false;
+        }
+        stale.sort_by_key(|(_, position, _)| *position);
+        for (song_id, _, annotation) in stale {
+            let free_position = song_ids
+                .iter()
+                .enumerate()
+                .position(|(index, id)| id == song_id && !placed.contains_key(&index));
+            if let Some(position) = free_position {
+                placed.insert(position, (song_id.clone(), annotation.clone()));
+            }
+        }
+
+        for (key, ..) in current {
+            self.playlist_item_annotations.remove(&key);
+        }
+        for (position, (song_id, annotation)) in placed {
+            self.playlist_item_annotations.insert(
+                playlist_item_annotation_key(server_id, playlist_id, &song_id, position),
+                annotation,
+            );
+        }
+        true
+    }
+
+    /// Moves every per-server entry from `from_id` to `to_id`. Entries the kept server
+    /// already has win over the duplicate's.
+    pub fn reassign_server(&mut self, from_id: &str, to_id: &str) {
+        rekey_server_entries(&mut self.radio_title_templates, from_id, to_id);
+        rekey_server_entries(&mut self.song_playback_offsets, from_id, to_id);
+        rekey_server_entries(&mut self.playlist_item_annotations, from_id, to_id);
+        rekey_server_entries(&mut self.continuous_album_overrides, from_id, to_id);
+        if let Some(folder_id) = self.music_folder_selection.remove(from_id) {
+            self.music_folder_selection
+                .entry(to_id.to_string())
+                .or_insert(folder_id);
+        }
+        if let Some(mode) = self.artist_browse_modes.remove(from_id) {
+            self.artist_browse_modes
+                .entry(to_id.to_string())
+                .or_insert(mode);
+        }
+        let kept_artists: Vec<String> = self
+            .artist_stations
+            .iter()
+            .filter(|station| station.server_id == to_id)
+            .map(|station| station.artist_id.clone())
+            .collect();
+        self.artist_stations.retain(|station| {
+            station.server_id != from_id || !kept_artists.contains(&station.artist_id)
+        });
+        for station in &mut self.artist_stations {
+            if station.server_id == from_id {
+                station.server_id = to_id.to_string();
+            }
+        }
+        if self.last_server_id.as_deref() == Some(from_id) {
+            self.last_server_id = Some(to_id.to_string());
+        }
+    }
+
+    pub fn artist_station(&self, station_id: &str) -> Option<&ArtistStation> {
+        self.artist_stations
+            .iter()
+            .find(|station| station.id == station_id)
+    }
+
+    /// Pins a station for the artist and returns its id. An artist already pinned on the
+    /// same server keeps its existing station.
+    pub fn pin_artist_station(
+        &mut self,
+        server_id: &str,
+        artist_id: &str,
+        artist_name: &str,
+        cover_art: Option<String>,
+    ) -> String {
+        if let Some(existing) = self
+            .artist_stations
+            .iter()
+            .find(|station| station.server_id == server_id && station.artist_id == artist_id)
+        {
+            return existing.id.clone();
+        }
+        let id = uuid::Uuid::new_v4().to_string();
+        self.artist_stations.push(ArtistStation {
+            id: id.clone(),
+            server_id: server_id.to_string(),
+            artist_id: artist_id.to_string(),
+            artist_name: artist_name.to_string(),
+            name: artist_name.to_string(),
+            cover_art,
+        });
+        id
+    }
+}
+
+impl AppSettings {
+    /// Moves per-song offsets and playlist notes of `server_id` from old song ids to the new
+    /// ids in `remap`. Entries already stored under a new id win.
+    pub fn remap_song_ids(&mut self, server_id: &str, remap: &BTreeMap<String, String>) {
+        let prefix = format!("{server_id}:");
+        let offset_keys: Vec<String> = self
+            .song_playback_offsets
+            .keys()
+            .filter(|key| {
+                key.strip_prefix(&prefix)
+                    .is_some_and(|song_id| remap.contains_key(song_id))
+            })
+            .cloned()
+            .collect();
+        for key in offset_keys {
+            let new_id = &remap[&key[prefix.len()..]];
+            if let Some(offsets) = self.song_playback_offsets.remove(&key) {
+                self.song_playback_offsets
+                    .entry(song_playback_offsets_key(server_id, new_id))
+                    .or_insert(offsets);
+            }
+        }
+
+        // "server_id:playlist_id:song_id:position"
+        let annotation_keys: Vec<(String, String)> = self
+            .playlist_item_annotations
+            .keys()
+            .filter_map(|key| {
+                let (playlist_id, entry) = key.strip_prefix(&prefix)?.split_once(':')?;
+                let (song_id, position) = entry.rsplit_once(':')?;
+                let new_id = remap.get(song_id)?;
+                Some((
+                    key.clone(),
+                    format!("{server_id}:{playlist_id}:{new_id}:{position}"),
+                ))
+            })
+            .collect();
+        for (key, new_key) in annotation_keys {
+            if let Some(annotation) = self.playlist_item_annotations.remove(&key) {
+                self.playlist_item_annotations
+                    .entry(new_key)
+                    .or_insert(annotation);
+            }
+        }
+    }
+}
+
+/// Re-keys `"server_id:..."` map entries from one server to another without overwriting
+/// entries already stored for the target.
+fn rekey_server_entries<V>(map: &mut BTreeMap<String, V>, from_id: &str, to_id: &str) {
+    let prefix = format!("{from_id}:");
+    let keys: Vec<String> = map
+        .keys()
+        .filter(|key| key.starts_with(&prefix))
+        .cloned()
+        .collect();
+    for key in keys {
+        if let Some(value) = map.remove(&key) {
+            map.entry(format!("{to_id}:{}", &key[prefix.len()..]))
+                .or_insert(value);
+        }
+    }
 }
 
 fn default_lyrics_request_timeout_secs() -> u32 {
@@ -147,6 +920,10 @@ fn default_cache_expiry_days() -> i32 {
     30
 }
 
+fn default_repeat_count() -> u32 {
+    3
+}
+
 fn default_bookmark_limit() -> u32 {
     10
 }
@@ -171,6 +948,10 @@ fn default_auto_download_playlist_count() -> u32 {
     2
 }
 
+fn default_auto_download_include_unrated() -> bool {
+    true
+}
+
 fn default_download_limit_count() -> u32 {
     500
 }
@@ -179,6 +960,10 @@ fn default_download_limit_mb() -> u32 {
     4096
 }
 
+fn default_download_cleanup_trigger_percent() -> u8 {
+    90
+}
+
 fn default_artwork_download_preference() -> ArtworkDownloadPreference {
     ArtworkDownloadPreference::PreferServer
 }
@@ -191,6 +976,82 @@ fn default_home_feed_load_profile() -> String {
     "standard".to_string()
 }
 
+fn default_fast_start_bitrate_kbps() -> u32 {
+    128
+}
+
+fn default_stream_format() -> String {
+    "mp3".to_string()
+}
+
+/// Formats the server can be asked to transcode capped streams to.
+pub const STREAM_FORMATS: [&str; 3] = ["mp3", "opus", "aac"];
+
+/// `value` when it names one of `STREAM_FORMATS`, otherwise mp3.
+fn normalized_stream_format(value: &str) -> String {
+    let value = value.trim().to_ascii_lowercase();
+    if STREAM_FORMATS.contains(&value.as_str()) {
+        value
+    } else {
+        default_stream_format()
+    }
+}
+
+fn default_progress_poll_interval_ms() -> u32 {
+    250
+}
+
+fn default_fetch_ahead_limit_mb() -> u32 {
+    64
+}
+
+fn default_pause_on_unplug() -> bool {
+    cfg!(any(target_os = "ios", target_os = "android"))
+}
+
+fn default_negative_cache_enabled() -> bool {
+    true
+}
+
+fn default_skip_ratio_threshold_percent() -> u8 {
+    75
+}
+
+fn default_skip_min_plays() -> u32 {
+    5
+}
+
+fn default_favorites_shuffle_limit() -> usize {
+    100
+}
+
+fn default_playback_rate() -> f64 {
+    1.0
+}
+
+/// Speeds the player's speed selector offers.
+pub const PLAYBACK_RATES: [f64; 5] = [0.75, 1.0, 1.25, 1.5, 2.0];
+
+/// `rate` held within the slowest and fastest of `PLAYBACK_RATES`.
+fn normalized_playback_rate(rate: f64) -> f64 {
+    if rate.is_finite() {
+        rate.clamp(PLAYBACK_RATES[0], PLAYBACK_RATES[PLAYBACK_RATES.len() - 1])
+    } else {
+        default_playback_rate()
+    }
+}
+
+/// Furthest the ReplayGain preamp goes either way, in dB.
+pub const REPLAY_GAIN_PREAMP_DB: f64 = 12.0;
+
+fn normalized_replay_gain_preamp(preamp_db: f64) -> f64 {
+    if preamp_db.is_finite() {
+        preamp_db.clamp(-REPLAY_GAIN_PREAMP_DB, REPLAY_GAIN_PREAMP_DB)
+    } else {
+        0.0
+    }
+}
+
 fn migrate_settings(mut settings: AppSettings) -> AppSettings {
     let normalized = normalize_lyrics_provider_order(&settings.lyrics_provider_order);
     let legacy_default_v1 = vec![
@@ -232,10 +1093,18 @@ fn migrate_settings(mut settings: AppSettings) -> AppSettings {
     }
 
     settings.auto_download_tier = settings.auto_download_tier.clamp(1, 3);
+    // URL schemes used to be default slogans, which hid every title that mentions a link.
+    settings
+        .radio_slogan_patterns
+        .retain(|pattern| !matches!(pattern.trim(), "http://" | "https://"));
     settings.auto_download_album_count = settings.auto_download_album_count.clamp(0, 25);
     settings.auto_download_playlist_count = settings.auto_download_playlist_count.clamp(0, 25);
+    settings.auto_download_min_rating = settings.auto_download_min_rating.min(5);
     settings.download_limit_count = settings.download_limit_count.clamp(25, 20000);
     settings.download_limit_mb = settings.download_limit_mb.clamp(256, 131072);
+    settings.download_cleanup_idle_days = settings.download_cleanup_idle_days.min(3650);
+    settings.download_cleanup_trigger_percent =
+        settings.download_cleanup_trigger_percent.clamp(50, 100);
     settings.home_feed_load_profile = match settings
         .home_feed_load_profile
         .trim()
@@ -246,6 +1115,17 @@ fn migrate_settings(mut settings: AppSettings) -> AppSettings {
         "super" => "super".to_string(),
         _ => "standard".to_string(),
     };
+    settings.fast_start_bitrate_kbps = settings.fast_start_bitrate_kbps.clamp(64, 320);
+    if settings.stream_max_bitrate_kbps > 0 {
+        settings.stream_max_bitrate_kbps = settings.stream_max_bitrate_kbps.clamp(64, 320);
+    }
+    settings.stream_format = normalized_stream_format(&settings.stream_format);
+    settings.progress_poll_interval_ms = settings.progress_poll_interval_ms.clamp(100, 1000);
+    settings.fetch_ahead_limit_mb = settings.fetch_ahead_limit_mb.clamp(8, 512);
+    settings.favorites_shuffle_limit = settings.favorites_shuffle_limit.clamp(10, 1000);
+    settings.playback_rate = normalized_playback_rate(settings.playback_rate);
+    settings.replay_gain_preamp_db = normalized_replay_gain_preamp(settings.replay_gain_preamp_db);
+    settings.eq_band_gains = normalized_band_gains(&settings.eq_band_gains);
 
     settings
 }
@@ -258,9 +1138,17 @@ impl Default for AppSettings {
             theme: "rusty".to_string(),
             crossfade_enabled: false,
             crossfade_duration: 3,
+            crossfade_on_skip: false,
+            gapless_enabled: false,
+            playback_rate: default_playback_rate(),
             replay_gain: false,
+            replay_gain_mode: ReplayGainMode::default(),
+            replay_gain_preamp_db: 0.0,
+            eq_enabled: false,
+            eq_band_gains: default_eq_band_gains(),
             shuffle_enabled: false,
             repeat_mode: RepeatMode::Off,
+            repeat_count: default_repeat_count(),
             cache_enabled: true,
             cache_size_mb: 100,
             cache_expiry_days: default_cache_expiry_days(),
@@ -279,15 +1167,61 @@ impl Default for AppSettings {
             bookmark_autoplay_on_launch: false,
             downloads_enabled: default_downloads_enabled(),
             auto_downloads_enabled: false,
+            pause_downloads_while_streaming: false,
             auto_download_tier: default_auto_download_tier(),
             auto_download_album_count: default_auto_download_album_count(),
             auto_download_playlist_count: default_auto_download_playlist_count(),
+            auto_download_min_rating: 0,
+            auto_download_include_unrated: default_auto_download_include_unrated(),
             download_limit_count: default_download_limit_count(),
             download_limit_mb: default_download_limit_mb(),
+            download_cleanup_idle_days: 0,
+            download_cleanup_trigger_percent: default_download_cleanup_trigger_percent(),
             artwork_download_preference: default_artwork_download_preference(),
             custom_css: String::new(),
             home_layout_json: default_home_layout_json(),
             home_feed_load_profile: default_home_feed_load_profile(),
+            fast_start_enabled: false,
+            fast_start_bitrate_kbps: default_fast_start_bitrate_kbps(),
+            stream_max_bitrate_kbps: 0,
+            stream_format: default_stream_format(),
+            progress_poll_interval_ms: default_progress_poll_interval_ms(),
+            smooth_progress_updates: false,
+            buffering_strategy: BufferingStrategy::default(),
+            fetch_ahead_limit_mb: default_fetch_ahead_limit_mb(),
+            pause_on_unplug: default_pause_on_unplug(),
+            quiet_hours: QuietHours::default(),
+            queue_row_density: QueueRowDensity::default(),
+            queue_auto_follow: false,
+            grid_density: GridDensity::default(),
+            player_bar_mode: PlayerBarMode::default(),
+            date_locale: DateLocale::default(),
+            clock_format: ClockFormat::default(),
+            albums_list_view: false,
+            sort_ignore_articles: false,
+            session_summary_enabled: false,
+            skipped_song_shuffle: SkippedSongShuffle::Off,
+            skip_ratio_threshold_percent: default_skip_ratio_threshold_percent(),
+            skip_min_plays: default_skip_min_plays(),
+            favorites_shuffle_limit: default_favorites_shuffle_limit(),
+            sleep_inhibit_mode: SleepInhibitMode::default(),
+            playback_indicator_mode: PlaybackIndicatorMode::default(),
+            negative_cache_enabled: default_negative_cache_enabled(),
+            musicbrainz_enabled: false,
+            strict_privacy_mode: false,
+            song_table_hidden_columns: BTreeMap::new(),
+            song_table_shown_columns: BTreeMap::new(),
+            radio_title_templates: BTreeMap::new(),
+            radio_slogan_patterns: default_radio_slogan_patterns(),
+            song_playback_offsets: BTreeMap::new(),
+            playlist_item_annotations: BTreeMap::new(),
+            music_folder_selection: BTreeMap::new(),
+            artist_browse_modes: BTreeMap::new(),
+            continuous_album_overrides: BTreeMap::new(),
+            artist_stations: Vec::new(),
+            settings_profiles: Vec::new(),
+            active_settings_profile: None,
+            onboarding_completed: false,
         }
     }
 }
@@ -302,6 +1236,41 @@ pub struct PlaybackState {
     pub queue_index: usize,
 }
 
+impl PlaybackState {
+    /// The state to save for `queue`, playing `now_playing` at `position` seconds.
+    pub fn capture(
+        queue: &[Song],
+        queue_index: usize,
+        now_playing: Option<&Song>,
+        position: f64,
+    ) -> Self {
+        Self {
+            song_id: now_playing.map(|song| song.id.clone()),
+            server_id: now_playing.map(|song| song.server_id.clone()),
+            position,
+            queue: queue
+                .iter()
+                .map(|song| QueueItem {
+                    song_id: song.id.clone(),
+                    server_id: song.server_id.clone(),
+                })
+                .collect(),
+            queue_index,
+        }
+    }
+
+    pub fn reassign_server(&mut self, from_id: &str, to_id: &str) {
+        if self.server_id.as_deref() == Some(from_id) {
+            self.server_id = Some(to_id.to_string());
+        }
+        for item in &mut self.queue {
+            if item.server_id == from_id {
+                item.server_id = to_id.to_string();
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct QueueItem {
     pub song_id: String,
@@ -318,6 +1287,16 @@ pub struct TemporaryQueueSnapshot {
     pub playback_position: f64,
 }
 
+impl TemporaryQueueSnapshot {
+    pub fn reassign_server(&mut self, from_id: &str, to_id: &str) {
+        for song in self.queue.iter_mut().chain(self.now_playing.as_mut()) {
+            if song.server_id == from_id {
+                song.server_id = to_id.to_string();
+            }
+        }
+    }
+}
+
 fn snapshot_signature(snapshot: &TemporaryQueueSnapshot) -> String {
     let mut signature = String::new();
     signature.push_str(&snapshot.queue_index.to_string());
@@ -428,7 +1407,9 @@ fn save_servers_inner(
 
     for server in servers {
         tx.execute(
-            "INSERT INTO servers (id, name, url, username, password, active) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO servers \
+             (id, name, url, username, password, active, client_name, raw_stream) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             rusqlite::params![
                 &server.id,
                 &server.name,
@@ -436,6 +1417,8 @@ fn save_servers_inner(
                 &server.username,
                 &server.password,
                 if server.active { "1" } else { "0" },
+                &server.client_name,
+                if server.raw_stream { "1" } else { "0" },
             ],
         )
         .map_err(|e| DbError::new(e.to_string()))?;
@@ -454,12 +1437,164 @@ pub fn save_servers_now(servers: &[ServerConfig]) -> Result<(), StorageError> {
     LocalStorage::set(SERVERS_KEY, servers.to_vec()).map_err(|e| e)
 }
 
+/// Folds a duplicate server entry into the one being kept: the duplicate's row is removed
+/// and the per-server settings, saved playback state and queue snapshots that pointed at
+/// it are re-pointed at `keep_id`, all in one transaction. If the duplicate was the active
+/// server, the kept one becomes active.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn merge_duplicate_server(keep_id: String, duplicate_id: String) -> Result<(), DbError> {
+    let mut conn = get_db_connection()?;
+    merge_duplicate_server_inner(&mut conn, &keep_id, &duplicate_id)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn merge_duplicate_server_inner(
+    conn: &mut rusqlite::Connection,
+    keep_id: &str,
+    duplicate_id: &str,
+) -> Result<(), DbError> {
+    use rusqlite::OptionalExtension;
+
+    if keep_id == duplicate_id {
+        return Err(DbError::new("Cannot merge a server into itself"));
+    }
+
+    let tx = conn
+        .transaction()
+        .map_err(|e| DbError::new(e.to_string()))?;
+
+    let keep_exists = tx
+        .query_row("SELECT 1 FROM servers WHERE id = ?1", [keep_id], |_| Ok(()))
+        .optional()
+        .map_err(|e| DbError::new(e.to_string()))?
+        .is_some();
+    if !keep_exists {
+        return Err(DbError::new("The server to keep no longer exists"));
+    }
+    let duplicate_active = tx
+        .query_row(
+            "SELECT active FROM servers WHERE id = ?1",
+            [duplicate_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|e| DbError::new(e.to_string()))?
+        .ok_or_else(|| DbError::new("The duplicate server no longer exists"))?
+        == "1";
+
+    tx.execute("DELETE FROM servers WHERE id = ?1", [duplicate_id])
+        .map_err(|e| DbError::new(e.to_string()))?;
+    if duplicate_active {
+        tx.execute("UPDATE servers SET active = '1' WHERE id = ?1", [keep_id])
+            .map_err(|e| DbError::new(e.to_string()))?;
+    }
+
+    rewrite_settings_row(&tx, "app_settings", |settings: &mut AppSettings| {
+        settings.reassign_server(duplicate_id, keep_id)
+    })?;
+    rewrite_settings_row(&tx, "playback_state", |state: &mut PlaybackState| {
+        state.reassign_server(duplicate_id, keep_id)
+    })?;
+    rewrite_settings_row(
+        &tx,
+        "temporary_queue_snapshots",
+        |snapshots: &mut Vec<TemporaryQueueSnapshot>| {
+            for snapshot in snapshots.iter_mut() {
+                snapshot.reassign_server(duplicate_id, keep_id);
+            }
+        },
+    )?;
+
+    tx.commit().map_err(|e| DbError::new(e.to_string()))
+}
+
+/// Loads one JSON value from the settings table, applies `rewrite` and stores it again.
+/// Missing rows are left alone; an unreadable row fails the surrounding transaction.
+#[cfg(not(target_arch = "wasm32"))]
+fn rewrite_settings_row<T>(
+    tx: &rusqlite::Transaction<'_>,
+    key: &str,
+    rewrite: impl FnOnce(&mut T),
+) -> Result<(), DbError>
+where
+    T: Serialize + serde::de::DeserializeOwned,
+{
+    use rusqlite::OptionalExtension;
+
+    let Some(json) = tx
+        .query_row("SELECT value FROM settings WHERE key = ?1", [key], |row| {
+            row.get::<_, String>(0)
+        })
+        .optional()
+        .map_err(|e| DbError::new(e.to_string()))?
+    else {
+        return Ok(());
+    };
+
+    let mut value: T = serde_json::from_str(&json).map_err(|e| DbError::new(e.to_string()))?;
+    rewrite(&mut value);
+    let json = serde_json::to_string(&value).map_err(|e| DbError::new(e.to_string()))?;
+    tx.execute(
+        "UPDATE settings SET value = ?1 WHERE key = ?2",
+        rusqlite::params![json, key],
+    )
+    .map_err(|e| DbError::new(e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn merge_duplicate_server(
+    keep_id: String,
+    duplicate_id: String,
+) -> Result<(), StorageError> {
+    let mut servers: Vec<ServerConfig> = LocalStorage::get(SERVERS_KEY).unwrap_or_default();
+    if keep_id == duplicate_id || !servers.iter().any(|server| server.id == keep_id) {
+        return Err(StorageError::KeyNotFound(keep_id));
+    }
+    let Some(position) = servers.iter().position(|server| server.id == duplicate_id) else {
+        return Err(StorageError::KeyNotFound(duplicate_id));
+    };
+    let duplicate = servers.remove(position);
+    if duplicate.active {
+        if let Some(kept) = servers.iter_mut().find(|server| server.id == keep_id) {
+            kept.active = true;
+        }
+    }
+
+    // Local storage has no transactions; write the re-pointed data first so an interrupted
+    // merge leaves the duplicate in place rather than orphaning its entries.
+    if let Ok(mut settings) = LocalStorage::get::<AppSettings>(SETTINGS_KEY) {
+        settings.reassign_server(&duplicate_id, &keep_id);
+        LocalStorage::set(SETTINGS_KEY, settings)?;
+    }
+    if let Ok(mut state) = LocalStorage::get::<PlaybackState>(PLAYBACK_KEY) {
+        state.reassign_server(&duplicate_id, &keep_id);
+        LocalStorage::set(PLAYBACK_KEY, state)?;
+    }
+    if let Ok(mut snapshots) =
+        LocalStorage::get::<Vec<TemporaryQueueSnapshot>>(TEMP_QUEUE_SNAPSHOTS_KEY)
+    {
+        for snapshot in snapshots.iter_mut() {
+            snapshot.reassign_server(&duplicate_id, &keep_id);
+        }
+        LocalStorage::set(TEMP_QUEUE_SNAPSHOTS_KEY, snapshots)?;
+    }
+    LocalStorage::set(SERVERS_KEY, servers)
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub async fn load_servers() -> Result<Vec<ServerConfig>, DbError> {
     let conn = get_db_connection()?;
+    load_servers_inner(&conn)
+}
 
+#[cfg(not(target_arch = "wasm32"))]
+fn load_servers_inner(conn: &rusqlite::Connection) -> Result<Vec<ServerConfig>, DbError> {
     let mut stmt = conn
-        .prepare("SELECT id, name, url, username, password, active FROM servers")
+        .prepare(
+            "SELECT id, name, url, username, password, active, client_name, raw_stream \
+             FROM servers",
+        )
         .map_err(|e| DbError::new(e.to_string()))?;
 
     let servers = stmt
@@ -471,6 +1606,8 @@ pub async fn load_servers() -> Result<Vec<ServerConfig>, DbError> {
                 username: row.get(3)?,
                 password: row.get(4)?,
                 active: row.get::<_, String>(5)? == "1",
+                client_name: row.get(6)?,
+                raw_stream: row.get::<_, String>(7)? == "1",
             })
         })
         .map_err(|e| DbError::new(e.to_string()))?
@@ -490,6 +1627,11 @@ pub async fn load_servers() -> Result<Vec<ServerConfig>, StorageError> {
 
 #[cfg(not(target_arch = "wasm32"))]
 pub async fn save_settings(settings: AppSettings) -> Result<(), DbError> {
+    let Some(settings) =
+        divert_to_guest_store(settings, |store, settings| store.settings = Some(settings))
+    else {
+        return Ok(());
+    };
     let conn = get_db_connection()?;
 
     let settings_json =
@@ -506,6 +1648,11 @@ pub async fn save_settings(settings: AppSettings) -> Result<(), DbError> {
 
 #[cfg(target_arch = "wasm32")]
 pub async fn save_settings(settings: AppSettings) -> Result<(), StorageError> {
+    let Some(settings) =
+        divert_to_guest_store(settings, |store, settings| store.settings = Some(settings))
+    else {
+        return Ok(());
+    };
     LocalStorage::set(SETTINGS_KEY, settings).map_err(|e| e)
 }
 
@@ -537,6 +1684,10 @@ pub async fn load_settings() -> Result<AppSettings, StorageError> {
 
 #[cfg(not(target_arch = "wasm32"))]
 pub async fn save_playback_state(state: PlaybackState) -> Result<(), DbError> {
+    let Some(state) = divert_to_guest_store(state, |store, state| store.playback_state = state)
+    else {
+        return Ok(());
+    };
     let conn = get_db_connection()?;
 
     let state_json = serde_json::to_string(&state).map_err(|e| DbError::new(e.to_string()))?;
@@ -552,11 +1703,18 @@ pub async fn save_playback_state(state: PlaybackState) -> Result<(), DbError> {
 
 #[cfg(target_arch = "wasm32")]
 pub async fn save_playback_state(state: PlaybackState) -> Result<(), StorageError> {
+    let Some(state) = divert_to_guest_store(state, |store, state| store.playback_state = state)
+    else {
+        return Ok(());
+    };
     LocalStorage::set(PLAYBACK_KEY, state).map_err(|e| e)
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 pub async fn load_playback_state() -> Result<PlaybackState, DbError> {
+    if let Some(state) = with_guest_store(|store| store.playback_state.clone()) {
+        return Ok(state);
+    }
     let conn = get_db_connection()?;
 
     let result: Result<String, rusqlite::Error> = conn.query_row(
@@ -573,6 +1731,9 @@ pub async fn load_playback_state() -> Result<PlaybackState, DbError> {
 
 #[cfg(target_arch = "wasm32")]
 pub async fn load_playback_state() -> Result<PlaybackState, StorageError> {
+    if let Some(state) = with_guest_store(|store| store.playback_state.clone()) {
+        return Ok(state);
+    }
     match LocalStorage::get(PLAYBACK_KEY) {
         Ok(state) => Ok(state),
         Err(_) => Ok(PlaybackState::default()),
@@ -586,6 +1747,12 @@ pub async fn save_temporary_queue_snapshot(
     if snapshot.queue.is_empty() {
         return Ok(());
     }
+    let Some(snapshot) = divert_to_guest_store(snapshot, |store, snapshot| {
+        store.queue_snapshots =
+            upsert_queue_snapshot(std::mem::take(&mut store.queue_snapshots), snapshot);
+    }) else {
+        return Ok(());
+    };
 
     let mut snapshots = load_temporary_queue_snapshots().await?;
     snapshots = upsert_queue_snapshot(snapshots, snapshot);
@@ -606,6 +1773,12 @@ pub async fn save_temporary_queue_snapshot(
     if snapshot.queue.is_empty() {
         return Ok(());
     }
+    let Some(snapshot) = divert_to_guest_store(snapshot, |store, snapshot| {
+        store.queue_snapshots =
+            upsert_queue_snapshot(std::mem::take(&mut store.queue_snapshots), snapshot);
+    }) else {
+        return Ok(());
+    };
 
     let existing = load_temporary_queue_snapshots().await.unwrap_or_default();
     let snapshots = upsert_queue_snapshot(existing, snapshot);
@@ -614,6 +1787,9 @@ pub async fn save_temporary_queue_snapshot(
 
 #[cfg(not(target_arch = "wasm32"))]
 pub async fn load_temporary_queue_snapshots() -> Result<Vec<TemporaryQueueSnapshot>, DbError> {
+    if let Some(snapshots) = with_guest_store(|store| store.queue_snapshots.clone()) {
+        return Ok(snapshots);
+    }
     let conn = get_db_connection()?;
     let result: Result<String, rusqlite::Error> = conn.query_row(
         "SELECT value FROM settings WHERE key = 'temporary_queue_snapshots'",
@@ -633,6 +1809,9 @@ pub async fn load_temporary_queue_snapshots() -> Result<Vec<TemporaryQueueSnapsh
 
 #[cfg(target_arch = "wasm32")]
 pub async fn load_temporary_queue_snapshots() -> Result<Vec<TemporaryQueueSnapshot>, StorageError> {
+    if let Some(snapshots) = with_guest_store(|store| store.queue_snapshots.clone()) {
+        return Ok(snapshots);
+    }
     match LocalStorage::get(TEMP_QUEUE_SNAPSHOTS_KEY) {
         Ok(snapshots) => Ok(normalize_queue_snapshots(snapshots)),
         Err(_) => Ok(Vec::new()),
@@ -641,9 +1820,13 @@ pub async fn load_temporary_queue_snapshots() -> Result<Vec<TemporaryQueueSnapsh
 
 #[cfg(not(target_arch = "wasm32"))]
 pub async fn initialize_database() -> Result<(), DbError> {
+    recovery::verify_database()?;
     let conn = get_db_connection()?;
+    create_tables(&conn)
+}
 
-    // Create tables
+#[cfg(not(target_arch = "wasm32"))]
+fn create_tables(conn: &rusqlite::Connection) -> Result<(), DbError> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS servers (
             id TEXT PRIMARY KEY,
@@ -656,6 +1839,7 @@ pub async fn initialize_database() -> Result<(), DbError> {
         [],
     )
     .map_err(|e| DbError::new(e.to_string()))?;
+    migrate_server_columns(conn)?;
 
     conn.execute(
         "CREATE TABLE IF NOT EXISTS settings (
@@ -669,6 +1853,38 @@ pub async fn initialize_database() -> Result<(), DbError> {
     Ok(())
 }
 
+/// Adds the server columns introduced after the table was first created. Existing servers
+/// keep reporting the client name the app always used.
+#[cfg(not(target_arch = "wasm32"))]
+fn migrate_server_columns(conn: &rusqlite::Connection) -> Result<(), DbError> {
+    let mut stmt = conn
+        .prepare("PRAGMA table_info(servers)")
+        .map_err(|e| DbError::new(e.to_string()))?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get(1))
+        .map_err(|e| DbError::new(e.to_string()))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let added = [
+        (
+            "client_name",
+            format!("TEXT NOT NULL DEFAULT '{DEFAULT_CLIENT_NAME}'"),
+        ),
+        ("raw_stream", "TEXT NOT NULL DEFAULT '0'".to_string()),
+    ];
+    for (column, definition) in added {
+        if !columns.iter().any(|existing| existing == column) {
+            conn.execute(
+                &format!("ALTER TABLE servers ADD COLUMN {column} {definition}"),
+                [],
+            )
+            .map_err(|e| DbError::new(e.to_string()))?;
+        }
+    }
+    Ok(())
+}
+
 #[cfg(target_arch = "wasm32")]
 pub async fn initialize_database() -> Result<(), StorageError> {
     Ok(())
@@ -677,9 +1893,11 @@ pub async fn initialize_database() -> Result<(), StorageError> {
 #[cfg(not(target_arch = "wasm32"))]
 #[allow(dead_code)]
 fn get_db_connection() -> Result<rusqlite::Connection, DbError> {
+    #[cfg(test)]
+    DB_CONNECTIONS_OPENED.with(|opened| opened.set(opened.get() + 1));
     let data_dir = app_data_dir()
         .ok_or_else(|| DbError::new("Failed to resolve application data directory"))?;
-    let db_path = data_dir.join("rustysound.db");
+    let db_path = data_dir.join(database_file_name());
 
     rusqlite::Connection::open(&db_path).map_err(|e| {
         DbError::new(format!(
@@ -689,3 +1907,342 @@ fn get_db_connection() -> Result<rusqlite::Connection, DbError> {
         ))
     })
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+thread_local! {
+    /// Connections opened by `get_db_connection` on this thread, so a test can check a path
+    /// never reached storage without counting the connections of tests running alongside.
+    pub(crate) static DB_CONNECTIONS_OPENED: std::cell::Cell<usize> =
+        const { std::cell::Cell::new(0) };
+}
+
+/// Held by tests that start a guest session, so only one of them runs at a time.
+#[cfg(all(test, not(target_arch = "wasm32")))]
+pub(crate) static GUEST_SESSION_TEST_LOCK: tokio::sync::Mutex<()> =
+    tokio::sync::Mutex::const_new(());
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    fn server(id: &str, active: bool) -> ServerConfig {
+        ServerConfig {
+            id: id.to_string(),
+            name: format!("Server {id}"),
+            url: "https://music.example.com".to_string(),
+            username: "alice".to_string(),
+            password: "secret".to_string(),
+            active,
+            client_name: DEFAULT_CLIENT_NAME.to_string(),
+            raw_stream: false,
+        }
+    }
+
+    fn song(server_id: &str, id: &str) -> Song {
+        Song {
+            id: id.to_string(),
+            server_id: server_id.to_string(),
+            ..Song::default()
+        }
+    }
+
+    fn put_row<T: Serialize>(conn: &rusqlite::Connection, key: &str, value: &T) {
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+            rusqlite::params![key, serde_json::to_string(value).unwrap()],
+        )
+        .unwrap();
+    }
+
+    fn get_row<T: serde::de::DeserializeOwned>(conn: &rusqlite::Connection, key: &str) -> T {
+        let json: String = conn
+            .query_row("SELECT value FROM settings WHERE key = ?1", [key], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        serde_json::from_str(&json).unwrap()
+    }
+
+    fn server_ids(conn: &rusqlite::Connection) -> Vec<(String, String)> {
+        let mut stmt = conn
+            .prepare("SELECT id, active FROM servers ORDER BY id")
+            .unwrap();
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+    }
+
+    /// Two entries for the same account: "keep" is inactive, "dup" is active and owns the
+    /// saved settings, playback state and queue snapshot.
+    fn seeded_connection() -> rusqlite::Connection {
+        let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        save_servers_inner(
+            &mut conn,
+            &[
+                server("keep", false),
+                server("dup", true),
+                server("other", false),
+            ],
+        )
+        .unwrap();
+
+        let mut settings = AppSettings::default();
+        settings.last_server_id = Some("dup".to_string());
+        settings.radio_title_templates.insert(
+            radio_station_template_key("dup", "st1"),
+            "{title}".to_string(),
+        );
+        settings.song_playback_offsets.insert(
+            song_playback_offsets_key("dup", "s1"),
+            SongPlaybackOffsets {
+                start_secs: Some(5.0),
+                end_secs: None,
+            },
+        );
+        settings.song_playback_offsets.insert(
+            song_playback_offsets_key("keep", "s2"),
+            SongPlaybackOffsets {
+                start_secs: Some(1.0),
+                end_secs: None,
+            },
+        );
+        settings.song_playback_offsets.insert(
+            song_playback_offsets_key("dup", "s2"),
+            SongPlaybackOffsets {
+                start_secs: Some(9.0),
+                end_secs: None,
+            },
+        );
+        settings.playlist_item_annotations.insert(
+            playlist_item_annotation_key("dup", "pl1", "s1", 0),
+            PlaylistItemAnnotation {
+                notes: "intro".to_string(),
+                cue_secs: Some(12.0),
+            },
+        );
+        settings
+            .music_folder_selection
+            .insert("dup".to_string(), "2".to_string());
+        settings.set_artist_browse_mode("dup", ArtistBrowseMode::AlbumArtists);
+        put_row(&conn, "app_settings", &settings);
+
+        let state = PlaybackState {
+            song_id: Some("s1".to_string()),
+            server_id: Some("dup".to_string()),
+            position: 42.0,
+            queue: vec![
+                QueueItem {
+                    song_id: "s1".to_string(),
+                    server_id: "dup".to_string(),
+                },
+                QueueItem {
+                    song_id: "s9".to_string(),
+                    server_id: "other".to_string(),
+                },
+            ],
+            queue_index: 0,
+        };
+        put_row(&conn, "playback_state", &state);
+
+        let snapshots = vec![TemporaryQueueSnapshot {
+            id: "queue-1".to_string(),
+            saved_at_epoch_ms: 1,
+            queue: vec![song("dup", "s1"), song("other", "s9")],
+            queue_index: 0,
+            now_playing: Some(song("dup", "s1")),
+            playback_position: 42.0,
+        }];
+        put_row(&conn, "temporary_queue_snapshots", &snapshots);
+        conn
+    }
+
+    #[test]
+    fn merge_repoints_local_data_and_removes_duplicate() {
+        let mut conn = seeded_connection();
+        merge_duplicate_server_inner(&mut conn, "keep", "dup").unwrap();
+
+        assert_eq!(
+            server_ids(&conn),
+            vec![
+                ("keep".to_string(), "1".to_string()),
+                ("other".to_string(), "0".to_string()),
+            ]
+        );
+
+        let settings: AppSettings = get_row(&conn, "app_settings");
+        assert_eq!(settings.last_server_id.as_deref(), Some("keep"));
+        assert_eq!(
+            settings
+                .radio_title_templates
+                .get("keep:st1")
+                .map(String::as_str),
+            Some("{title}")
+        );
+        assert_eq!(
+            settings.playback_offsets_for("keep", "s1").start_secs,
+            Some(5.0)
+        );
+        // The kept server's own entry wins over the duplicate's.
+        assert_eq!(
+            settings.playback_offsets_for("keep", "s2").start_secs,
+            Some(1.0)
+        );
+        assert_eq!(
+            settings.playlist_cue_for("keep", "pl1", "s1", Some(0)),
+            Some(12.0)
+        );
+        assert_eq!(
+            settings
+                .music_folder_selection
+                .get("keep")
+                .map(String::as_str),
+            Some("2")
+        );
+        assert_eq!(
+            settings.artist_browse_mode_for("keep"),
+            ArtistBrowseMode::AlbumArtists
+        );
+        assert!(settings
+            .song_playback_offsets
+            .keys()
+            .all(|key| !key.starts_with("dup:")));
+        assert!(!settings.music_folder_selection.contains_key("dup"));
+
+        let state: PlaybackState = get_row(&conn, "playback_state");
+        assert_eq!(state.server_id.as_deref(), Some("keep"));
+        assert_eq!(state.queue[0].server_id, "keep");
+        assert_eq!(state.queue[1].server_id, "other");
+
+        let snapshots: Vec<TemporaryQueueSnapshot> = get_row(&conn, "temporary_queue_snapshots");
+        assert_eq!(snapshots[0].queue[0].server_id, "keep");
+        assert_eq!(snapshots[0].queue[1].server_id, "other");
+        assert_eq!(
+            snapshots[0]
+                .now_playing
+                .as_ref()
+                .map(|song| song.server_id.as_str()),
+            Some("keep")
+        );
+    }
+
+    #[test]
+    fn server_columns_migrate_to_the_default_client_name() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE servers (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                url TEXT NOT NULL,
+                username TEXT NOT NULL,
+                password TEXT NOT NULL,
+                active TEXT NOT NULL DEFAULT '1'
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO servers VALUES ('old', 'Old', 'https://music.example.com', 'a', 'b', '1')",
+            [],
+        )
+        .unwrap();
+
+        create_tables(&conn).unwrap();
+        // Running the migration again must not fail on the added columns.
+        create_tables(&conn).unwrap();
+
+        let servers = load_servers_inner(&conn).unwrap();
+        assert_eq!(servers[0].client_name, DEFAULT_CLIENT_NAME);
+        assert!(!servers[0].raw_stream);
+    }
+
+    #[test]
+    fn failed_merge_rolls_back_every_change() {
+        let mut conn = seeded_connection();
+        conn.execute(
+            "UPDATE settings SET value = 'not json' WHERE key = 'temporary_queue_snapshots'",
+            [],
+        )
+        .unwrap();
+        let settings_before: AppSettings = get_row(&conn, "app_settings");
+
+        assert!(merge_duplicate_server_inner(&mut conn, "keep", "dup").is_err());
+
+        assert_eq!(
+            server_ids(&conn),
+            vec![
+                ("dup".to_string(), "1".to_string()),
+                ("keep".to_string(), "0".to_string()),
+                ("other".to_string(), "0".to_string()),
+            ]
+        );
+        let settings_after: AppSettings = get_row(&conn, "app_settings");
+        assert_eq!(settings_after, settings_before);
+        let state: PlaybackState = get_row(&conn, "playback_state");
+        assert_eq!(state.server_id.as_deref(), Some("dup"));
+    }
+
+    #[test]
+    fn merge_rejects_missing_or_identical_servers() {
+        let mut conn = seeded_connection();
+        assert!(merge_duplicate_server_inner(&mut conn, "keep", "keep").is_err());
+        assert!(merge_duplicate_server_inner(&mut conn, "gone", "dup").is_err());
+        assert!(merge_duplicate_server_inner(&mut conn, "keep", "gone").is_err());
+        assert_eq!(server_ids(&conn).len(), 3);
+    }
+
+    #[test]
+    fn artist_stations_pin_once_and_follow_server_merges() {
+        let mut settings = AppSettings::default();
+        let first = settings.pin_artist_station("dup", "ar1", "Band", Some("ar-ar1".to_string()));
+        assert_eq!(
+            settings.pin_artist_station("dup", "ar1", "Band", None),
+            first
+        );
+        settings.pin_artist_station("keep", "ar1", "Band", None);
+        settings.pin_artist_station("dup", "ar2", "Other Band", None);
+        assert_eq!(settings.artist_stations.len(), 3);
+
+        settings.reassign_server("dup", "keep");
+
+        // The kept server already had "ar1" pinned, so the duplicate's station is dropped.
+        let pinned: Vec<(&str, &str)> = settings
+            .artist_stations
+            .iter()
+            .map(|station| (station.server_id.as_str(), station.artist_id.as_str()))
+            .collect();
+        assert_eq!(pinned, [("keep", "ar1"), ("keep", "ar2")]);
+        assert!(settings.artist_station(&first).is_none());
+    }
+
+    #[test]
+    fn playback_rate_defaults_to_normal_and_stays_in_range() {
+        let mut stored = serde_json::to_value(AppSettings::default()).unwrap();
+        stored.as_object_mut().unwrap().remove("playback_rate");
+        let settings: AppSettings = serde_json::from_value(stored).unwrap();
+        assert_eq!(settings.playback_rate, 1.0);
+
+        for (saved, loaded) in [(1.5, 1.5), (8.0, 2.0), (0.1, 0.75), (f64::NAN, 1.0)] {
+            let settings = migrate_settings(AppSettings {
+                playback_rate: saved,
+                ..AppSettings::default()
+            });
+            assert_eq!(settings.playback_rate, loaded);
+        }
+    }
+
+    #[test]
+    fn album_artists_mode_leaves_out_artists_without_albums() {
+        let artist = |album_count| Artist {
+            id: "ar1".to_string(),
+            name: "Artist".to_string(),
+            album_count,
+            ..Artist::default()
+        };
+        assert!(ArtistBrowseMode::All.includes(&artist(0)));
+        assert!(ArtistBrowseMode::All.includes(&artist(3)));
+        assert!(!ArtistBrowseMode::AlbumArtists.includes(&artist(0)));
+        assert!(ArtistBrowseMode::AlbumArtists.includes(&artist(3)));
+    }
+}
@@ -0,0 +1,455 @@
+//! Server mutations made while a server was unreachable, kept until they can be replayed.
+//!
+//! Stars, ratings and playlist appends that fail because the server can't be reached (or
+//! offline mode is on) are queued here with the time they were made. When the server answers
+//! again they are replayed oldest first; the client keeps the server's state when it changed
+//! after the mutation was queued. A mutation the server refuses stays queued with its error
+//! until it is retried or discarded from Settings, and holds back later changes to the same
+//! item so they are never applied out of order.
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::{get_db_connection, DbError};
+#[cfg(target_arch = "wasm32")]
+use gloo_storage::{errors::StorageError, LocalStorage, Storage};
+
+#[cfg(not(target_arch = "wasm32"))]
+type PendingSyncError = DbError;
+#[cfg(target_arch = "wasm32")]
+type PendingSyncError = StorageError;
+
+#[cfg(target_arch = "wasm32")]
+const PENDING_SYNC_KEY: &str = "rustysound.pending_sync";
+
+fn now_timestamp_millis() -> u64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        return js_sys::Date::now().max(0.0).round() as u64;
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PendingMutationOp {
+    Star {
+        item_id: String,
+        /// "song", "album", "artist" or "playlist", as passed to `NavidromeClient::star`.
+        item_type: String,
+    },
+    Unstar {
+        item_id: String,
+        item_type: String,
+    },
+    SetRating {
+        item_id: String,
+        rating: u32,
+        /// The rating the app last saw before this change, when it was known. Replay keeps
+        /// the server's rating if it has moved away from this in the meantime.
+        #[serde(default)]
+        previous: Option<u32>,
+    },
+    AppendToPlaylist {
+        playlist_id: String,
+        song_ids: Vec<String>,
+    },
+}
+
+impl PendingMutationOp {
+    /// The part of a server item this mutation changes. Mutations with the same target are
+    /// coalesced when queued and replayed strictly in order.
+    fn target(&self) -> String {
+        match self {
+            PendingMutationOp::Star { item_id, item_type }
+            | PendingMutationOp::Unstar { item_id, item_type } => {
+                format!("star:{item_type}:{item_id}")
+            }
+            PendingMutationOp::SetRating { item_id, .. } => format!("rating:{item_id}"),
+            PendingMutationOp::AppendToPlaylist { playlist_id, .. } => {
+                format!("playlist:{playlist_id}")
+            }
+        }
+    }
+
+    /// "Star song", "Rate 4/5", "Add 3 songs to playlist".
+    pub fn describe(&self) -> String {
+        match self {
+            PendingMutationOp::Star { item_type, .. } => format!("Star {item_type}"),
+            PendingMutationOp::Unstar { item_type, .. } => format!("Unstar {item_type}"),
+            PendingMutationOp::SetRating { rating: 0, .. } => "Clear rating".to_string(),
+            PendingMutationOp::SetRating { rating, .. } => format!("Rate {rating}/5"),
+            PendingMutationOp::AppendToPlaylist { song_ids, .. } if song_ids.len() == 1 => {
+                "Add 1 song to playlist".to_string()
+            }
+            PendingMutationOp::AppendToPlaylist { song_ids, .. } => {
+                format!("Add {} songs to playlist", song_ids.len())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingMutation {
+    pub id: u64,
+    pub server_id: String,
+    pub op: PendingMutationOp,
+    pub queued_at_ms: u64,
+    #[serde(default)]
+    pub attempts: u32,
+    /// Why the server refused the last replay. Set mutations wait for a manual retry.
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+impl PendingMutation {
+    fn target(&self) -> (String, String) {
+        (self.server_id.clone(), self.op.target())
+    }
+}
+
+/// What replaying one mutation did.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PendingMutationOutcome {
+    Applied,
+    /// The server's state was newer and was kept; the message says what was dropped.
+    Conflict(String),
+    /// The server still can't be reached; its mutations wait for the next pass.
+    Unreachable,
+    /// The server refused the mutation.
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+struct PendingMutations {
+    #[serde(default)]
+    next_id: u64,
+    #[serde(default)]
+    items: Vec<PendingMutation>,
+}
+
+impl PendingMutations {
+    /// Queues `op`, folding it into an earlier change of the same item: a later star or
+    /// rating replaces the earlier one, and a change that undoes it drops both. Playlist
+    /// appends are never folded.
+    fn push(&mut self, server_id: &str, op: PendingMutationOp, queued_at_ms: u64) {
+        let target = (server_id.to_string(), op.target());
+        let earlier = if matches!(op, PendingMutationOp::AppendToPlaylist { .. }) {
+            None
+        } else {
+            self.items
+                .iter()
+                .rposition(|item| item.last_error.is_none() && item.target() == target)
+        };
+        let earlier_op = earlier.map(|index| self.items.remove(index).op);
+
+        let op = match (earlier_op, op) {
+            (Some(PendingMutationOp::Star { .. }), PendingMutationOp::Unstar { .. })
+            | (Some(PendingMutationOp::Unstar { .. }), PendingMutationOp::Star { .. }) => return,
+            (
+                Some(PendingMutationOp::SetRating { previous, .. }),
+                PendingMutationOp::SetRating {
+                    item_id, rating, ..
+                },
+            ) => {
+                if previous == Some(rating) {
+                    return;
+                }
+                PendingMutationOp::SetRating {
+                    item_id,
+                    rating,
+                    previous,
+                }
+            }
+            (_, op) => op,
+        };
+
+        self.next_id = self.next_id.saturating_add(1);
+        self.items.push(PendingMutation {
+            id: self.next_id,
+            server_id: server_id.to_string(),
+            op,
+            queued_at_ms,
+            attempts: 0,
+            last_error: None,
+        });
+    }
+}
+
+/// Bookkeeping for one replay pass over the queue.
+#[derive(Debug, Clone, Default)]
+pub struct PendingSyncPass {
+    attempted: HashSet<u64>,
+    unreachable_servers: HashSet<String>,
+    /// Conflicts resolved in favour of the server during this pass.
+    pub conflicts: Vec<String>,
+    pub applied: usize,
+}
+
+impl PendingSyncPass {
+    /// The oldest mutation to send next: skips servers that didn't answer this pass and
+    /// anything queued behind a refused change of the same item.
+    fn next(&self, queue: &PendingMutations) -> Option<PendingMutation> {
+        let mut blocked = HashSet::new();
+        for item in &queue.items {
+            let target = item.target();
+            if item.last_error.is_some() || self.attempted.contains(&item.id) {
+                blocked.insert(target);
+                continue;
+            }
+            if blocked.contains(&target) || self.unreachable_servers.contains(&item.server_id) {
+                continue;
+            }
+            return Some(item.clone());
+        }
+        None
+    }
+
+    fn record(&mut self, queue: &mut PendingMutations, id: u64, outcome: PendingMutationOutcome) {
+        self.attempted.insert(id);
+        let Some(index) = queue.items.iter().position(|item| item.id == id) else {
+            return;
+        };
+        match outcome {
+            PendingMutationOutcome::Applied => {
+                queue.items.remove(index);
+                self.applied += 1;
+            }
+            PendingMutationOutcome::Conflict(message) => {
+                queue.items.remove(index);
+                self.conflicts.push(message);
+            }
+            PendingMutationOutcome::Unreachable => {
+                self.unreachable_servers
+                    .insert(queue.items[index].server_id.clone());
+            }
+            PendingMutationOutcome::Failed(message) => {
+                let item = &mut queue.items[index];
+                item.attempts = item.attempts.saturating_add(1);
+                item.last_error = Some(message);
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_pending_mutations() -> Result<PendingMutations, PendingSyncError> {
+    let conn = get_db_connection()?;
+    let result: Result<String, rusqlite::Error> = conn.query_row(
+        "SELECT value FROM settings WHERE key = 'pending_sync'",
+        [],
+        |row: &rusqlite::Row| row.get(0),
+    );
+
+    match result {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| DbError::new(e.to_string())),
+        Err(_) => Ok(PendingMutations::default()),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_pending_mutations(queue: &PendingMutations) -> Result<(), PendingSyncError> {
+    let payload = serde_json::to_string(queue).map_err(|e| DbError::new(e.to_string()))?;
+    let conn = get_db_connection()?;
+    conn.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES ('pending_sync', ?1)",
+        [&payload],
+    )
+    .map_err(|e| DbError::new(e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn load_pending_mutations() -> Result<PendingMutations, PendingSyncError> {
+    match LocalStorage::get(PENDING_SYNC_KEY) {
+        Ok(queue) => Ok(queue),
+        Err(_) => Ok(PendingMutations::default()),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn save_pending_mutations(queue: &PendingMutations) -> Result<(), PendingSyncError> {
+    LocalStorage::set(PENDING_SYNC_KEY, queue)
+}
+
+pub async fn queue_pending_mutation(
+    server_id: String,
+    op: PendingMutationOp,
+) -> Result<(), PendingSyncError> {
+    let mut queue = load_pending_mutations()?;
+    queue.push(&server_id, op, now_timestamp_millis());
+    save_pending_mutations(&queue)
+}
+
+pub async fn pending_mutations() -> Vec<PendingMutation> {
+    load_pending_mutations()
+        .map(|queue| queue.items)
+        .unwrap_or_default()
+}
+
+/// The next mutation `pass` should replay, read fresh so changes queued meanwhile count.
+pub async fn next_pending_mutation(pass: &PendingSyncPass) -> Option<PendingMutation> {
+    pass.next(&load_pending_mutations().ok()?)
+}
+
+pub async fn record_pending_mutation_outcome(
+    pass: &mut PendingSyncPass,
+    id: u64,
+    outcome: PendingMutationOutcome,
+) -> Result<(), PendingSyncError> {
+    let mut queue = load_pending_mutations()?;
+    pass.record(&mut queue, id, outcome);
+    save_pending_mutations(&queue)
+}
+
+/// Clears a refused mutation's error so the next pass sends it again.
+pub async fn retry_pending_mutation(id: u64) -> Result<(), PendingSyncError> {
+    let mut queue = load_pending_mutations()?;
+    if let Some(item) = queue.items.iter_mut().find(|item| item.id == id) {
+        item.last_error = None;
+    }
+    save_pending_mutations(&queue)
+}
+
+pub async fn discard_pending_mutation(id: u64) -> Result<(), PendingSyncError> {
+    let mut queue = load_pending_mutations()?;
+    queue.items.retain(|item| item.id != id);
+    save_pending_mutations(&queue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn star(item_id: &str) -> PendingMutationOp {
+        PendingMutationOp::Star {
+            item_id: item_id.to_string(),
+            item_type: "song".to_string(),
+        }
+    }
+
+    fn unstar(item_id: &str) -> PendingMutationOp {
+        PendingMutationOp::Unstar {
+            item_id: item_id.to_string(),
+            item_type: "song".to_string(),
+        }
+    }
+
+    fn rate(item_id: &str, rating: u32, previous: Option<u32>) -> PendingMutationOp {
+        PendingMutationOp::SetRating {
+            item_id: item_id.to_string(),
+            rating,
+            previous,
+        }
+    }
+
+    fn append(playlist_id: &str, song_id: &str) -> PendingMutationOp {
+        PendingMutationOp::AppendToPlaylist {
+            playlist_id: playlist_id.to_string(),
+            song_ids: vec![song_id.to_string()],
+        }
+    }
+
+    /// Replays the whole queue, answering each mutation with `respond`.
+    fn replay(
+        queue: &mut PendingMutations,
+        mut respond: impl FnMut(&PendingMutation) -> PendingMutationOutcome,
+    ) -> (PendingSyncPass, Vec<u64>) {
+        let mut pass = PendingSyncPass::default();
+        let mut sent = Vec::new();
+        while let Some(item) = pass.next(queue) {
+            sent.push(item.id);
+            let outcome = respond(&item);
+            pass.record(queue, item.id, outcome);
+        }
+        (pass, sent)
+    }
+
+    #[test]
+    fn queued_changes_to_one_item_coalesce() {
+        let mut queue = PendingMutations::default();
+        queue.push("s1", star("a"), 1);
+        queue.push("s1", unstar("a"), 2);
+        assert!(queue.items.is_empty());
+
+        queue.push("s1", rate("a", 3, Some(1)), 3);
+        queue.push("s1", rate("a", 5, Some(3)), 4);
+        assert_eq!(queue.items.len(), 1);
+        assert_eq!(queue.items[0].op, rate("a", 5, Some(1)));
+        assert_eq!(queue.items[0].queued_at_ms, 4);
+
+        // Rating back to what the server had leaves nothing to send.
+        queue.push("s1", rate("a", 1, Some(5)), 5);
+        assert!(queue.items.is_empty());
+
+        // Playlist appends keep every entry in order.
+        queue.push("s1", append("p", "x"), 6);
+        queue.push("s1", append("p", "y"), 7);
+        queue.push("s2", star("a"), 8);
+        queue.push("s1", star("a"), 9);
+        assert_eq!(queue.items.len(), 4);
+    }
+
+    #[test]
+    fn replay_sends_oldest_first_and_waits_for_unreachable_servers() {
+        let mut queue = PendingMutations::default();
+        queue.push("s1", append("p", "x"), 1);
+        queue.push("s2", star("a"), 2);
+        queue.push("s1", append("p", "y"), 3);
+        queue.push("s2", star("b"), 4);
+
+        let (pass, sent) = replay(&mut queue, |item| {
+            if item.server_id == "s2" {
+                PendingMutationOutcome::Unreachable
+            } else {
+                PendingMutationOutcome::Applied
+            }
+        });
+        // s2's second star isn't tried once the server failed to answer.
+        assert_eq!(sent, vec![1, 2, 3]);
+        assert_eq!(pass.applied, 2);
+        let left: Vec<u64> = queue.items.iter().map(|item| item.id).collect();
+        assert_eq!(left, vec![2, 4]);
+    }
+
+    #[test]
+    fn a_refused_mutation_holds_back_later_changes_to_the_same_item() {
+        let mut queue = PendingMutations::default();
+        queue.push("s1", append("p", "x"), 1);
+        queue.push("s1", star("a"), 2);
+        queue.push("s1", append("p", "y"), 3);
+        queue.push("s1", append("q", "z"), 4);
+
+        let (pass, sent) = replay(&mut queue, |item| match item.id {
+            1 => PendingMutationOutcome::Failed("Playlist is read-only".to_string()),
+            2 => PendingMutationOutcome::Conflict("Kept the server's star".to_string()),
+            _ => PendingMutationOutcome::Applied,
+        });
+        assert_eq!(sent, vec![1, 2, 4]);
+        assert_eq!(pass.conflicts, vec!["Kept the server's star".to_string()]);
+        assert_eq!(queue.items.len(), 2);
+        assert_eq!(queue.items[0].attempts, 1);
+        assert_eq!(
+            queue.items[0].last_error.as_deref(),
+            Some("Playlist is read-only")
+        );
+
+        // The next pass leaves both alone until the refused one is retried or discarded.
+        let (_, sent) = replay(&mut queue, |_| PendingMutationOutcome::Applied);
+        assert!(sent.is_empty());
+        queue.items[0].last_error = None;
+        let (pass, sent) = replay(&mut queue, |_| PendingMutationOutcome::Applied);
+        assert_eq!(sent, vec![1, 3]);
+        assert_eq!(pass.applied, 2);
+        assert!(queue.items.is_empty());
+    }
+}
@@ -0,0 +1,313 @@
+//! Recovery from a damaged local database (native builds).
+//!
+//! Every clean shutdown copies the database into `<app data>/db_backups/`, keeping the three
+//! most recent copies. When the database can't be opened or fails SQLite's integrity check at
+//! startup, the app shows a recovery screen instead of mounting anything that reads it. From
+//! there the user can retry, restore the newest healthy backup, or start with an empty
+//! database. The damaged file is never deleted: both recovery paths move it aside first.
+use super::DbError;
+use crate::storage::{app_data_dir, database_file_name};
+use rusqlite::{Connection, OpenFlags};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const BACKUP_DIR_NAME: &str = "db_backups";
+/// Backups kept; the oldest is dropped when a new one is written.
+const BACKUP_COPIES: usize = 3;
+/// Files SQLite keeps next to the database, moved aside together with it.
+const SIDECAR_SUFFIXES: [&str; 3] = ["-journal", "-wal", "-shm"];
+
+/// Set once the database passed its integrity check this launch.
+static DATABASE_VERIFIED: AtomicBool = AtomicBool::new(false);
+
+/// What a recovery action did, for the recovery screen to report.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DatabaseRecovery {
+    /// Where the damaged database was moved to, if there was one.
+    pub preserved_at: Option<PathBuf>,
+    /// The backup copied into place.
+    pub restored_from: Option<PathBuf>,
+}
+
+fn db_error(context: &str, error: impl std::fmt::Display) -> DbError {
+    DbError::new(format!("{context}: {error}"))
+}
+
+fn database_path() -> Result<PathBuf, DbError> {
+    let data_dir = app_data_dir()
+        .ok_or_else(|| DbError::new("Failed to resolve application data directory"))?;
+    Ok(data_dir.join(database_file_name()))
+}
+
+fn backup_dir() -> Result<PathBuf, DbError> {
+    let data_dir = app_data_dir()
+        .ok_or_else(|| DbError::new("Failed to resolve application data directory"))?;
+    Ok(data_dir.join(BACKUP_DIR_NAME))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("rustysound.db")
+        .to_string()
+}
+
+/// `<dir>/<database file name>.<n>`, newest first from 1.
+fn backup_path(dir: &Path, db_name: &str, slot: usize) -> PathBuf {
+    dir.join(format!("{db_name}.{slot}"))
+}
+
+/// Opens `path` without creating it and runs SQLite's integrity check. A missing file is
+/// fine: it is created on first use.
+fn verify_database_file(path: &Path) -> Result<(), DbError> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        .map_err(|e| db_error(&format!("Failed to open {}", path.display()), e))?;
+    let verdict: String = conn
+        .query_row("PRAGMA quick_check", [], |row| row.get(0))
+        .map_err(|e| db_error(&format!("{} failed its integrity check", path.display()), e))?;
+    if verdict != "ok" {
+        return Err(DbError::new(format!(
+            "{} failed its integrity check: {verdict}",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Checks the active profile's database once per launch.
+pub(super) fn verify_database() -> Result<(), DbError> {
+    if DATABASE_VERIFIED.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    verify_database_file(&database_path()?)?;
+    DATABASE_VERIFIED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+fn write_rotating_backup(db_path: &Path, dir: &Path) -> Result<PathBuf, DbError> {
+    if !db_path.exists() {
+        return Err(DbError::new("There is no database to back up yet"));
+    }
+    // A damaged database must never push a healthy backup out of rotation.
+    verify_database_file(db_path)?;
+    fs::create_dir_all(dir).map_err(|e| db_error("Failed to create the backup folder", e))?;
+
+    let db_name = file_name(db_path);
+    let staging = dir.join(format!("{db_name}.new"));
+    let _ = fs::remove_file(&staging);
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        .map_err(|e| db_error("Failed to open the database for backup", e))?;
+    conn.execute("VACUUM INTO ?1", [staging.to_string_lossy()])
+        .map_err(|e| db_error("Failed to write the backup", e))?;
+
+    let _ = fs::remove_file(backup_path(dir, &db_name, BACKUP_COPIES));
+    for slot in (1..BACKUP_COPIES).rev() {
+        let from = backup_path(dir, &db_name, slot);
+        if from.exists() {
+            fs::rename(&from, backup_path(dir, &db_name, slot + 1))
+                .map_err(|e| db_error("Failed to rotate backups", e))?;
+        }
+    }
+    let newest = backup_path(dir, &db_name, 1);
+    fs::rename(&staging, &newest).map_err(|e| db_error("Failed to store the backup", e))?;
+    Ok(newest)
+}
+
+/// The newest backup of `db_path` that passes the integrity check.
+fn newest_healthy_backup(db_path: &Path, dir: &Path) -> Option<PathBuf> {
+    let db_name = file_name(db_path);
+    (1..=BACKUP_COPIES)
+        .map(|slot| backup_path(dir, &db_name, slot))
+        .find(|path| path.exists() && verify_database_file(path).is_ok())
+}
+
+/// Renames the database and its journal files to `<name>.corrupt-<timestamp>`.
+fn set_aside_damaged(db_path: &Path) -> Result<Option<PathBuf>, DbError> {
+    if !db_path.exists() {
+        return Ok(None);
+    }
+    let suffix = format!("corrupt-{}", chrono::Utc::now().format("%Y%m%d-%H%M%S%.3f"));
+    let preserved = db_path.with_file_name(format!("{}.{suffix}", file_name(db_path)));
+    fs::rename(db_path, &preserved)
+        .map_err(|e| db_error("Failed to move the damaged database aside", e))?;
+    for sidecar in SIDECAR_SUFFIXES {
+        let from = db_path.with_file_name(format!("{}{sidecar}", file_name(db_path)));
+        if from.exists() {
+            let to = preserved.with_file_name(format!("{}{sidecar}", file_name(&preserved)));
+            let _ = fs::rename(from, to);
+        }
+    }
+    Ok(Some(preserved))
+}
+
+fn restore_newest_backup(db_path: &Path, dir: &Path) -> Result<DatabaseRecovery, DbError> {
+    let backup = newest_healthy_backup(db_path, dir)
+        .ok_or_else(|| DbError::new("No healthy backup was found"))?;
+    let preserved_at = set_aside_damaged(db_path)?;
+    fs::copy(&backup, db_path).map_err(|e| db_error("Failed to restore the backup", e))?;
+    Ok(DatabaseRecovery {
+        preserved_at,
+        restored_from: Some(backup),
+    })
+}
+
+/// Set once this launch's shutdown backup was attempted.
+static SHUTDOWN_BACKUP_TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Backs the database up into the three-copy rotation. Called on clean shutdown.
+fn backup_database() -> Result<PathBuf, DbError> {
+    write_rotating_backup(&database_path()?, &backup_dir()?)
+}
+
+/// Runs `backup_database` once per launch. Closing the desktop window both flushes on quit
+/// and tears the app down, and one quit shouldn't rotate out two older backups.
+pub fn backup_database_on_shutdown() {
+    if SHUTDOWN_BACKUP_TAKEN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    if let Err(error) = backup_database() {
+        eprintln!("Skipped database backup: {error}");
+    }
+}
+
+/// Whether a healthy backup exists to restore from.
+pub fn has_database_backup() -> bool {
+    match (database_path(), backup_dir()) {
+        (Ok(db_path), Ok(dir)) => newest_healthy_backup(&db_path, &dir).is_some(),
+        _ => false,
+    }
+}
+
+/// Replaces the database with its newest healthy backup, keeping the damaged file.
+pub fn restore_database_backup() -> Result<DatabaseRecovery, DbError> {
+    DATABASE_VERIFIED.store(false, Ordering::Relaxed);
+    restore_newest_backup(&database_path()?, &backup_dir()?)
+}
+
+/// Moves the damaged database aside so the next start creates an empty one.
+pub fn start_fresh_database() -> Result<DatabaseRecovery, DbError> {
+    DATABASE_VERIFIED.store(false, Ordering::Relaxed);
+    Ok(DatabaseRecovery {
+        preserved_at: set_aside_damaged(&database_path()?)?,
+        restored_from: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "rustysound-db-recovery-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_database(path: &Path, value: &str) {
+        let conn = Connection::open(path).unwrap();
+        super::super::create_tables(&conn).unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES ('marker', ?1)",
+            [value],
+        )
+        .unwrap();
+    }
+
+    fn read_marker(path: &Path) -> String {
+        Connection::open(path)
+            .unwrap()
+            .query_row(
+                "SELECT value FROM settings WHERE key = 'marker'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    /// Overwrites the database header, as a torn write during power loss would.
+    fn corrupt(path: &Path) {
+        let mut bytes = fs::read(path).unwrap();
+        bytes[..100].fill(0xA5);
+        fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn backups_rotate_and_keep_three_copies() {
+        let dir = scratch_dir("rotate");
+        let db_path = dir.join("rustysound.db");
+        let backups = dir.join(BACKUP_DIR_NAME);
+
+        for generation in 1..=4 {
+            write_database(&db_path, &format!("generation {generation}"));
+            write_rotating_backup(&db_path, &backups).unwrap();
+        }
+
+        assert_eq!(
+            read_marker(&backups.join("rustysound.db.1")),
+            "generation 4"
+        );
+        assert_eq!(
+            read_marker(&backups.join("rustysound.db.3")),
+            "generation 2"
+        );
+        assert!(!backups.join("rustysound.db.4").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn corrupt_database_is_detected_and_restored_from_the_newest_healthy_backup() {
+        let dir = scratch_dir("restore");
+        let db_path = dir.join("rustysound.db");
+        let backups = dir.join(BACKUP_DIR_NAME);
+        write_database(&db_path, "older");
+        write_rotating_backup(&db_path, &backups).unwrap();
+        write_database(&db_path, "newer");
+        write_rotating_backup(&db_path, &backups).unwrap();
+
+        corrupt(&db_path);
+        assert!(verify_database_file(&db_path).is_err());
+        // A damaged database is never written into the rotation.
+        assert!(write_rotating_backup(&db_path, &backups).is_err());
+        // Nor is a damaged backup restored.
+        corrupt(&backups.join("rustysound.db.1"));
+
+        let recovery = restore_newest_backup(&db_path, &backups).unwrap();
+        assert_eq!(
+            recovery.restored_from,
+            Some(backups.join("rustysound.db.2"))
+        );
+        assert!(verify_database_file(&db_path).is_ok());
+        assert_eq!(read_marker(&db_path), "older");
+
+        let preserved = recovery.preserved_at.expect("damaged file kept");
+        assert!(verify_database_file(&preserved).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn starting_fresh_keeps_the_damaged_file_and_its_journal() {
+        let dir = scratch_dir("fresh");
+        let db_path = dir.join("rustysound.db");
+        write_database(&db_path, "lost");
+        corrupt(&db_path);
+        fs::write(dir.join("rustysound.db-journal"), b"journal").unwrap();
+
+        let preserved = set_aside_damaged(&db_path).unwrap().expect("moved aside");
+        assert!(!db_path.exists());
+        assert!(!dir.join("rustysound.db-journal").exists());
+        assert!(preserved
+            .with_file_name(format!("{}-journal", file_name(&preserved)))
+            .exists());
+        assert!(verify_database_file(&db_path).is_ok());
+        assert!(restore_newest_backup(&db_path, &dir.join(BACKUP_DIR_NAME)).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
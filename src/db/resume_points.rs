@@ -0,0 +1,334 @@
+//! Local resume points for albums and playlists, for long works such as box sets and DJ mixes.
+//!
+//! Each container remembers the track and position last heard in it. Points are kept for the
+//! `RESUME_POINT_LIMIT` most recently played containers and dropped once a container is played
+//! to the end. This is separate from server bookmarks and never leaves the device.
+use super::guest_session::with_guest_store;
+use crate::api::{format_duration, QueueSourceKind, Song};
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::{get_db_connection, DbError};
+#[cfg(target_arch = "wasm32")]
+use gloo_storage::{errors::StorageError, LocalStorage, Storage};
+
+#[cfg(not(target_arch = "wasm32"))]
+type ResumePointError = DbError;
+#[cfg(target_arch = "wasm32")]
+type ResumePointError = StorageError;
+
+#[cfg(target_arch = "wasm32")]
+pub(super) const RESUME_POINTS_KEY: &str = "rustysound.container_resume_points";
+const RESUME_POINT_LIMIT: usize = 200;
+/// Progress within a track is written at most this often.
+const RESUME_SAVE_INTERVAL_MS: u64 = 15_000;
+/// Ticks this close to the end of a track are left to the end-of-track handling.
+const RESUME_TRACK_END_MARGIN_SECS: f64 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResumeContainerKind {
+    Album,
+    Playlist,
+}
+
+impl ResumeContainerKind {
+    /// Only albums and playlists have a stable track order worth resuming.
+    pub fn from_queue_source(kind: &QueueSourceKind) -> Option<Self> {
+        match kind {
+            QueueSourceKind::Album => Some(Self::Album),
+            QueueSourceKind::Playlist => Some(Self::Playlist),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContainerResumePoint {
+    pub kind: ResumeContainerKind,
+    /// "server_id::container_id", the same id the queue uses for the container.
+    pub source_id: String,
+    /// Position of the track in the container's play order.
+    pub track_index: usize,
+    pub song_id: String,
+    #[serde(default)]
+    pub disc_number: Option<u32>,
+    #[serde(default)]
+    pub track_number: Option<u32>,
+    pub position_secs: f64,
+    pub updated_at_ms: u64,
+}
+
+impl ContainerResumePoint {
+    /// Resume point for `song`, if it was queued from an album or playlist.
+    pub fn for_song(song: &Song, position_secs: f64, updated_at_ms: u64) -> Option<Self> {
+        let meta = song.queue_meta.as_ref()?;
+        Some(Self {
+            kind: ResumeContainerKind::from_queue_source(&meta.source_kind)?,
+            source_id: meta.source_id.clone(),
+            track_index: meta.source_position,
+            song_id: song.id.clone(),
+            disc_number: song.disc_number,
+            track_number: song.track,
+            position_secs: position_secs.max(0.0),
+            updated_at_ms,
+        })
+    }
+
+    /// "Resume from disc 2 track 3 · 12:40". Playlists count entries rather than album tracks.
+    pub fn label(&self) -> String {
+        let position = format_duration(self.position_secs as u32);
+        let track_number = match self.kind {
+            ResumeContainerKind::Album => self.track_number,
+            ResumeContainerKind::Playlist => None,
+        }
+        .unwrap_or(self.track_index as u32 + 1);
+        match (self.kind, self.disc_number) {
+            (ResumeContainerKind::Album, Some(disc)) => {
+                format!("Resume from disc {disc} track {track_number} · {position}")
+            }
+            _ => format!("Resume from track {track_number} · {position}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub(super) struct ResumePoints {
+    #[serde(default)]
+    points: Vec<ContainerResumePoint>,
+}
+
+impl ResumePoints {
+    /// Points resume points in containers of `server_id` at the new song ids in `remap`.
+    pub(super) fn remap_song_ids(
+        &mut self,
+        server_id: &str,
+        remap: &std::collections::BTreeMap<String, String>,
+    ) {
+        let prefix = format!("{server_id}::");
+        for point in self
+            .points
+            .iter_mut()
+            .filter(|point| point.source_id.starts_with(&prefix))
+        {
+            if let Some(new_id) = remap.get(&point.song_id) {
+                point.song_id = new_id.clone();
+            }
+        }
+    }
+}
+
+/// Replaces the container's point and keeps the most recent `RESUME_POINT_LIMIT`.
+fn upsert_point(points: &mut ResumePoints, point: ContainerResumePoint) {
+    points
+        .points
+        .retain(|existing| existing.kind != point.kind || existing.source_id != point.source_id);
+    points.points.push(point);
+    if points.points.len() > RESUME_POINT_LIMIT {
+        let excess = points.points.len() - RESUME_POINT_LIMIT;
+        points.points.drain(..excess);
+    }
+}
+
+/// Decides which playback ticks are written as resume points.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ResumePointDebounce {
+    last: Option<(ResumeContainerKind, String, usize, bool, u64)>,
+}
+
+impl ResumePointDebounce {
+    /// A new track or container, or pausing, is written at once; progress while playing at
+    /// most every `RESUME_SAVE_INTERVAL_MS`. Ticks at the very end of a track are skipped so
+    /// a finished container isn't recorded again after it was cleared.
+    pub fn should_save(
+        &mut self,
+        point: &ContainerResumePoint,
+        duration_secs: f64,
+        playing: bool,
+    ) -> bool {
+        if duration_secs > 0.0
+            && point.position_secs >= duration_secs - RESUME_TRACK_END_MARGIN_SECS
+        {
+            return false;
+        }
+        let due = match &self.last {
+            Some((kind, source_id, track_index, was_playing, saved_at_ms))
+                if *kind == point.kind
+                    && *source_id == point.source_id
+                    && *track_index == point.track_index =>
+            {
+                if playing {
+                    point.updated_at_ms.saturating_sub(*saved_at_ms) >= RESUME_SAVE_INTERVAL_MS
+                } else {
+                    *was_playing
+                }
+            }
+            _ => true,
+        };
+        if due {
+            self.last = Some((
+                point.kind,
+                point.source_id.clone(),
+                point.track_index,
+                playing,
+                point.updated_at_ms,
+            ));
+        }
+        due
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_stored_resume_points() -> Result<ResumePoints, ResumePointError> {
+    let conn = get_db_connection()?;
+    let result: Result<String, rusqlite::Error> = conn.query_row(
+        "SELECT value FROM settings WHERE key = 'container_resume_points'",
+        [],
+        |row: &rusqlite::Row| row.get(0),
+    );
+
+    match result {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| DbError::new(e.to_string())),
+        Err(_) => Ok(ResumePoints::default()),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_stored_resume_points(points: &ResumePoints) -> Result<(), ResumePointError> {
+    let payload = serde_json::to_string(points).map_err(|e| DbError::new(e.to_string()))?;
+    let conn = get_db_connection()?;
+    conn.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES ('container_resume_points', ?1)",
+        [&payload],
+    )
+    .map_err(|e| DbError::new(e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn load_stored_resume_points() -> Result<ResumePoints, ResumePointError> {
+    match LocalStorage::get(RESUME_POINTS_KEY) {
+        Ok(points) => Ok(points),
+        Err(_) => Ok(ResumePoints::default()),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn save_stored_resume_points(points: &ResumePoints) -> Result<(), ResumePointError> {
+    LocalStorage::set(RESUME_POINTS_KEY, points)
+}
+
+fn load_resume_points() -> Result<ResumePoints, ResumePointError> {
+    if let Some(points) = with_guest_store(|store| store.resume_points.clone()) {
+        return Ok(points);
+    }
+    load_stored_resume_points()
+}
+
+fn save_resume_points(points: &ResumePoints) -> Result<(), ResumePointError> {
+    if with_guest_store(|store| store.resume_points = points.clone()).is_some() {
+        return Ok(());
+    }
+    save_stored_resume_points(points)
+}
+
+pub async fn save_container_resume_point(
+    point: ContainerResumePoint,
+) -> Result<(), ResumePointError> {
+    let mut points = load_resume_points()?;
+    upsert_point(&mut points, point);
+    save_resume_points(&points)
+}
+
+pub async fn container_resume_point(
+    kind: ResumeContainerKind,
+    source_id: String,
+) -> Option<ContainerResumePoint> {
+    load_resume_points()
+        .ok()?
+        .points
+        .into_iter()
+        .find(|point| point.kind == kind && point.source_id == source_id)
+}
+
+pub async fn clear_container_resume_point(kind: ResumeContainerKind, source_id: String) {
+    let Ok(mut points) = load_resume_points() else {
+        return;
+    };
+    let before = points.points.len();
+    points
+        .points
+        .retain(|point| point.kind != kind || point.source_id != source_id);
+    if points.points.len() != before {
+        let _ = save_resume_points(&points);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(
+        source_id: &str,
+        track_index: usize,
+        position_secs: f64,
+        at_ms: u64,
+    ) -> ContainerResumePoint {
+        ContainerResumePoint {
+            kind: ResumeContainerKind::Album,
+            source_id: source_id.to_string(),
+            track_index,
+            song_id: format!("song-{track_index}"),
+            disc_number: Some(2),
+            track_number: Some(3),
+            position_secs,
+            updated_at_ms: at_ms,
+        }
+    }
+
+    #[test]
+    fn debounce_writes_track_changes_pauses_and_spaced_progress() {
+        let mut debounce = ResumePointDebounce::default();
+        assert!(debounce.should_save(&point("s::a", 0, 1.0, 0), 600.0, true));
+        assert!(!debounce.should_save(&point("s::a", 0, 5.0, 4_000), 600.0, true));
+        assert!(debounce.should_save(&point("s::a", 0, 16.0, 15_000), 600.0, true));
+
+        // Pausing writes once; further paused ticks don't.
+        assert!(debounce.should_save(&point("s::a", 0, 17.0, 16_000), 600.0, false));
+        assert!(!debounce.should_save(&point("s::a", 0, 17.0, 40_000), 600.0, false));
+
+        assert!(debounce.should_save(&point("s::a", 1, 0.5, 41_000), 600.0, true));
+        assert!(debounce.should_save(&point("s::b", 1, 0.5, 41_500), 600.0, true));
+
+        // The last moments of a track are left to the end-of-container handling.
+        assert!(!debounce.should_save(&point("s::b", 2, 599.0, 90_000), 600.0, true));
+    }
+
+    #[test]
+    fn upsert_replaces_and_caps_to_the_most_recent_containers() {
+        let mut points = ResumePoints::default();
+        for index in 0..RESUME_POINT_LIMIT + 5 {
+            upsert_point(
+                &mut points,
+                point(&format!("s::{index}"), 0, 10.0, index as u64),
+            );
+        }
+        assert_eq!(points.points.len(), RESUME_POINT_LIMIT);
+        assert_eq!(points.points[0].source_id, "s::5");
+
+        upsert_point(&mut points, point("s::5", 4, 20.0, 1_000));
+        assert_eq!(points.points.len(), RESUME_POINT_LIMIT);
+        assert_eq!(points.points.last().map(|p| p.track_index), Some(4));
+        assert_eq!(points.points[0].source_id, "s::6");
+    }
+
+    #[test]
+    fn label_names_disc_and_track_for_albums() {
+        assert_eq!(
+            point("s::a", 2, 760.0, 0).label(),
+            "Resume from disc 2 track 3 · 12:40"
+        );
+        let mut entry = point("s::p", 6, 65.0, 0);
+        entry.kind = ResumeContainerKind::Playlist;
+        assert_eq!(entry.label(), "Resume from track 7 · 1:05");
+    }
+}
@@ -0,0 +1,415 @@
+//! Named settings profiles: the cache, download and performance settings that depend on the
+//! device, saved under a name so an install can switch between them. A profile never holds
+//! servers or their credentials, which are stored apart from `AppSettings`, nor personal
+//! settings such as the theme, home layout or pinned stations.
+//!
+//! Profiles travel as settings bundles: versioned JSON files holding one or more profiles.
+use super::{normalized_stream_format, AppSettings, ArtworkDownloadPreference, BufferingStrategy};
+use serde::{Deserialize, Serialize};
+
+/// Identifies a settings bundle among other JSON documents.
+pub const SETTINGS_BUNDLE_FORMAT: &str = "rustysound-settings";
+/// Bumped whenever a change to the file layout would confuse older readers.
+pub const SETTINGS_BUNDLE_VERSION: u32 = 1;
+
+const PERFORMANCE_PROFILE_ID: &str = "builtin:performance";
+const LIGHT_PROFILE_ID: &str = "builtin:light";
+
+/// The part of `AppSettings` a profile carries. Fields missing from an older bundle keep
+/// their defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileSettings {
+    pub cache_enabled: bool,
+    pub cache_size_mb: u32,
+    pub cache_expiry_days: i32,
+    pub cache_expiry_in_days: bool,
+    pub cache_images_enabled: bool,
+    pub negative_cache_enabled: bool,
+    pub downloads_enabled: bool,
+    pub auto_downloads_enabled: bool,
+    pub pause_downloads_while_streaming: bool,
+    pub auto_download_tier: u8,
+    pub auto_download_album_count: u32,
+    pub auto_download_playlist_count: u32,
+    pub download_limit_count: u32,
+    pub download_limit_mb: u32,
+    pub download_cleanup_idle_days: u32,
+    pub download_cleanup_trigger_percent: u8,
+    pub artwork_download_preference: ArtworkDownloadPreference,
+    pub home_feed_load_profile: String,
+    pub fast_start_enabled: bool,
+    pub fast_start_bitrate_kbps: u32,
+    pub stream_max_bitrate_kbps: u32,
+    pub stream_format: String,
+    pub progress_poll_interval_ms: u32,
+    pub smooth_progress_updates: bool,
+    pub buffering_strategy: BufferingStrategy,
+    pub fetch_ahead_limit_mb: u32,
+}
+
+impl Default for ProfileSettings {
+    fn default() -> Self {
+        Self::from_settings(&AppSettings::default())
+    }
+}
+
+impl ProfileSettings {
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            cache_enabled: settings.cache_enabled,
+            cache_size_mb: settings.cache_size_mb,
+            cache_expiry_days: settings.cache_expiry_days,
+            cache_expiry_in_days: settings.cache_expiry_in_days,
+            cache_images_enabled: settings.cache_images_enabled,
+            negative_cache_enabled: settings.negative_cache_enabled,
+            downloads_enabled: settings.downloads_enabled,
+            auto_downloads_enabled: settings.auto_downloads_enabled,
+            pause_downloads_while_streaming: settings.pause_downloads_while_streaming,
+            auto_download_tier: settings.auto_download_tier,
+            auto_download_album_count: settings.auto_download_album_count,
+            auto_download_playlist_count: settings.auto_download_playlist_count,
+            download_limit_count: settings.download_limit_count,
+            download_limit_mb: settings.download_limit_mb,
+            download_cleanup_idle_days: settings.download_cleanup_idle_days,
+            download_cleanup_trigger_percent: settings.download_cleanup_trigger_percent,
+            artwork_download_preference: settings.artwork_download_preference,
+            home_feed_load_profile: settings.home_feed_load_profile.clone(),
+            fast_start_enabled: settings.fast_start_enabled,
+            fast_start_bitrate_kbps: settings.fast_start_bitrate_kbps,
+            stream_max_bitrate_kbps: settings.stream_max_bitrate_kbps,
+            stream_format: settings.stream_format.clone(),
+            progress_poll_interval_ms: settings.progress_poll_interval_ms,
+            smooth_progress_updates: settings.smooth_progress_updates,
+            buffering_strategy: settings.buffering_strategy,
+            fetch_ahead_limit_mb: settings.fetch_ahead_limit_mb,
+        }
+    }
+
+    /// Writes these values over `settings`, clamped to the ranges the settings page allows.
+    /// Every other field is left alone.
+    pub fn apply_to(&self, settings: &mut AppSettings) {
+        settings.cache_enabled = self.cache_enabled;
+        settings.cache_size_mb = self.cache_size_mb.clamp(25, 2048);
+        settings.cache_expiry_days = self.cache_expiry_days.clamp(-1, 3650);
+        settings.cache_expiry_in_days = self.cache_expiry_in_days;
+        settings.cache_images_enabled = self.cache_images_enabled;
+        settings.negative_cache_enabled = self.negative_cache_enabled;
+        settings.downloads_enabled = self.downloads_enabled;
+        settings.auto_downloads_enabled = self.auto_downloads_enabled;
+        settings.pause_downloads_while_streaming = self.pause_downloads_while_streaming;
+        settings.auto_download_tier = self.auto_download_tier.clamp(1, 3);
+        settings.auto_download_album_count = self.auto_download_album_count;
+        settings.auto_download_playlist_count = self.auto_download_playlist_count;
+        settings.download_limit_count = self.download_limit_count.clamp(25, 20_000);
+        settings.download_limit_mb = self.download_limit_mb.clamp(256, 131_072);
+        settings.download_cleanup_idle_days = self.download_cleanup_idle_days.min(3650);
+        settings.download_cleanup_trigger_percent =
+            self.download_cleanup_trigger_percent.clamp(50, 100);
+        settings.artwork_download_preference = self.artwork_download_preference;
+        settings.home_feed_load_profile = self.home_feed_load_profile.clone();
+        settings.fast_start_enabled = self.fast_start_enabled;
+        settings.fast_start_bitrate_kbps = self.fast_start_bitrate_kbps.clamp(64, 320);
+        settings.stream_max_bitrate_kbps = match self.stream_max_bitrate_kbps {
+            0 => 0,
+            kbps => kbps.clamp(64, 320),
+        };
+        settings.stream_format = normalized_stream_format(&self.stream_format);
+        settings.progress_poll_interval_ms = self.progress_poll_interval_ms.clamp(100, 1000);
+        settings.smooth_progress_updates = self.smooth_progress_updates;
+        settings.buffering_strategy = self.buffering_strategy;
+        settings.fetch_ahead_limit_mb = self.fetch_ahead_limit_mb.clamp(8, 512);
+    }
+
+    /// Whether `settings` currently matches this profile.
+    pub fn matches(&self, settings: &AppSettings) -> bool {
+        let mut applied = settings.clone();
+        self.apply_to(&mut applied);
+        applied == *settings
+    }
+}
+
+/// A named set of profile settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettingsProfile {
+    pub id: String,
+    pub name: String,
+    pub settings: ProfileSettings,
+}
+
+impl SettingsProfile {
+    pub fn is_builtin(&self) -> bool {
+        self.id.starts_with("builtin:")
+    }
+}
+
+/// The presets offered next to saved profiles.
+pub fn builtin_settings_profiles() -> Vec<SettingsProfile> {
+    let defaults = ProfileSettings::default();
+    vec![
+        SettingsProfile {
+            id: PERFORMANCE_PROFILE_ID.to_string(),
+            name: "Performance".to_string(),
+            settings: ProfileSettings {
+                cache_enabled: true,
+                cache_size_mb: 1024,
+                cache_expiry_days: 30,
+                cache_expiry_in_days: true,
+                cache_images_enabled: true,
+                downloads_enabled: true,
+                auto_downloads_enabled: true,
+                pause_downloads_while_streaming: false,
+                auto_download_tier: 3,
+                auto_download_album_count: 15,
+                auto_download_playlist_count: 15,
+                download_limit_count: 5000,
+                download_limit_mb: 6000,
+                home_feed_load_profile: "super".to_string(),
+                fast_start_enabled: false,
+                progress_poll_interval_ms: 100,
+                smooth_progress_updates: true,
+                buffering_strategy: BufferingStrategy::Aggressive,
+                fetch_ahead_limit_mb: 128,
+                ..defaults.clone()
+            },
+        },
+        SettingsProfile {
+            id: LIGHT_PROFILE_ID.to_string(),
+            name: "Light/metered".to_string(),
+            settings: ProfileSettings {
+                cache_enabled: true,
+                cache_size_mb: 50,
+                cache_expiry_days: 7,
+                cache_expiry_in_days: true,
+                cache_images_enabled: true,
+                auto_downloads_enabled: false,
+                pause_downloads_while_streaming: true,
+                auto_download_tier: 1,
+                download_limit_count: 200,
+                download_limit_mb: 1024,
+                download_cleanup_idle_days: 30,
+                home_feed_load_profile: "conservative".to_string(),
+                fast_start_enabled: true,
+                fast_start_bitrate_kbps: 96,
+                stream_max_bitrate_kbps: 128,
+                progress_poll_interval_ms: 1000,
+                smooth_progress_updates: false,
+                buffering_strategy: BufferingStrategy::Minimal,
+                fetch_ahead_limit_mb: 16,
+                ..defaults
+            },
+        },
+    ]
+}
+
+/// The contents of a settings bundle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettingsBundle {
+    pub format: String,
+    pub version: u32,
+    pub profiles: Vec<SettingsProfile>,
+}
+
+/// `profiles` as the pretty-printed JSON of a settings bundle.
+pub fn settings_bundle_json(profiles: &[SettingsProfile]) -> Result<String, String> {
+    serde_json::to_string_pretty(&SettingsBundle {
+        format: SETTINGS_BUNDLE_FORMAT.to_string(),
+        version: SETTINGS_BUNDLE_VERSION,
+        profiles: profiles.to_vec(),
+    })
+    .map_err(|error| error.to_string())
+}
+
+/// Reads a settings bundle, refusing other JSON documents and bundles from a newer version.
+pub fn parse_settings_bundle(text: &str) -> Result<SettingsBundle, String> {
+    let bundle: SettingsBundle =
+        serde_json::from_str(text).map_err(|error| format!("Not a settings file: {error}"))?;
+    if bundle.format != SETTINGS_BUNDLE_FORMAT {
+        return Err(format!(
+            "Not a settings file (format \"{}\").",
+            bundle.format
+        ));
+    }
+    if bundle.version > SETTINGS_BUNDLE_VERSION {
+        return Err(format!(
+            "This settings file is version {}; this app reads up to version {}.",
+            bundle.version, SETTINGS_BUNDLE_VERSION
+        ));
+    }
+    if bundle.profiles.is_empty() {
+        return Err("This settings file has no profiles.".to_string());
+    }
+    Ok(bundle)
+}
+
+impl AppSettings {
+    /// Saved profiles followed by the built-in presets.
+    pub fn available_settings_profiles(&self) -> Vec<SettingsProfile> {
+        let mut profiles = self.settings_profiles.clone();
+        profiles.extend(builtin_settings_profiles());
+        profiles
+    }
+
+    pub fn settings_profile(&self, id: &str) -> Option<SettingsProfile> {
+        self.available_settings_profiles()
+            .into_iter()
+            .find(|profile| profile.id == id)
+    }
+
+    /// Saves the current values as a new profile named `name`, or over the saved profile of
+    /// that name. Returns the profile id.
+    pub fn save_settings_profile(&mut self, name: &str) -> String {
+        let name = name.trim().to_string();
+        let settings = ProfileSettings::from_settings(self);
+        let id = match self
+            .settings_profiles
+            .iter_mut()
+            .find(|profile| profile.name.eq_ignore_ascii_case(&name))
+        {
+            Some(existing) => {
+                existing.settings = settings;
+                existing.id.clone()
+            }
+            None => {
+                let id = uuid::Uuid::new_v4().to_string();
+                self.settings_profiles.push(SettingsProfile {
+                    id: id.clone(),
+                    name,
+                    settings,
+                });
+                id
+            }
+        };
+        self.active_settings_profile = Some(id.clone());
+        id
+    }
+
+    /// Applies the profile `id`. Returns `false` when there is no such profile.
+    pub fn apply_settings_profile(&mut self, id: &str) -> bool {
+        let Some(profile) = self.settings_profile(id) else {
+            return false;
+        };
+        profile.settings.apply_to(self);
+        self.active_settings_profile = Some(profile.id);
+        true
+    }
+
+    /// Adds imported profiles, replacing saved profiles of the same name. Built-in presets
+    /// in the bundle are imported as regular profiles. Returns how many were added or
+    /// replaced.
+    pub fn import_settings_profiles(&mut self, profiles: Vec<SettingsProfile>) -> usize {
+        let mut imported = 0;
+        for profile in profiles {
+            let name = profile.name.trim();
+            if name.is_empty() {
+                continue;
+            }
+            match self
+                .settings_profiles
+                .iter_mut()
+                .find(|existing| existing.name.eq_ignore_ascii_case(name))
+            {
+                Some(existing) => existing.settings = profile.settings,
+                None => self.settings_profiles.push(SettingsProfile {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    name: name.to_string(),
+                    settings: profile.settings,
+                }),
+            }
+            imported += 1;
+        }
+        imported
+    }
+
+    pub fn delete_settings_profile(&mut self, id: &str) {
+        self.settings_profiles.retain(|profile| profile.id != id);
+        if self.active_settings_profile.as_deref() == Some(id) {
+            self.active_settings_profile = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applying_a_profile_only_touches_profile_fields() {
+        let mut settings = AppSettings {
+            theme: "midnight".to_string(),
+            volume: 0.4,
+            last_server_id: Some("server-1".to_string()),
+            ..AppSettings::default()
+        };
+        assert!(settings.apply_settings_profile(LIGHT_PROFILE_ID));
+
+        assert_eq!(settings.cache_size_mb, 50);
+        assert_eq!(settings.buffering_strategy, BufferingStrategy::Minimal);
+        assert!(!settings.auto_downloads_enabled);
+        assert_eq!(settings.theme, "midnight");
+        assert_eq!(settings.volume, 0.4);
+        assert_eq!(settings.last_server_id.as_deref(), Some("server-1"));
+        assert_eq!(
+            settings.active_settings_profile.as_deref(),
+            Some(LIGHT_PROFILE_ID)
+        );
+        assert!(builtin_settings_profiles()[1].settings.matches(&settings));
+        assert!(!builtin_settings_profiles()[0].settings.matches(&settings));
+    }
+
+    #[test]
+    fn saved_profiles_round_trip_through_a_bundle() {
+        let mut desktop = AppSettings {
+            cache_size_mb: 1500,
+            download_limit_mb: 20_000,
+            ..AppSettings::default()
+        };
+        let id = desktop.save_settings_profile("Desktop");
+        let profile = desktop.settings_profile(&id).expect("saved profile");
+
+        let json = settings_bundle_json(std::slice::from_ref(&profile)).unwrap();
+        assert!(!json.contains("password"));
+        assert!(!json.contains("theme"));
+
+        let mut laptop = AppSettings::default();
+        let bundle = parse_settings_bundle(&json).unwrap();
+        assert_eq!(laptop.import_settings_profiles(bundle.profiles), 1);
+        let imported = laptop.settings_profiles[0].clone();
+        assert_eq!(imported.name, "Desktop");
+        assert!(laptop.apply_settings_profile(&imported.id));
+        assert_eq!(laptop.cache_size_mb, 1500);
+        assert_eq!(laptop.download_limit_mb, 20_000);
+
+        // Importing the same name again replaces the profile instead of adding one.
+        let bundle = parse_settings_bundle(&json).unwrap();
+        assert_eq!(laptop.import_settings_profiles(bundle.profiles), 1);
+        assert_eq!(laptop.settings_profiles.len(), 1);
+    }
+
+    #[test]
+    fn other_documents_and_newer_bundles_are_refused() {
+        assert!(parse_settings_bundle(
+            r#"{"format":"rustysound-queue","version":1,"profiles":[]}"#
+        )
+        .is_err());
+        let newer = format!(
+            r#"{{"format":"{SETTINGS_BUNDLE_FORMAT}","version":{},"profiles":[]}}"#,
+            SETTINGS_BUNDLE_VERSION + 1
+        );
+        assert!(parse_settings_bundle(&newer)
+            .unwrap_err()
+            .contains("version"));
+
+        // Fields added after a bundle was written keep their defaults.
+        let sparse = format!(
+            r#"{{"format":"{SETTINGS_BUNDLE_FORMAT}","version":1,
+            "profiles":[{{"id":"x","name":"Old","settings":{{"cache_size_mb":300}}}}]}}"#
+        );
+        let bundle = parse_settings_bundle(&sparse).unwrap();
+        assert_eq!(bundle.profiles[0].settings.cache_size_mb, 300);
+        assert_eq!(
+            bundle.profiles[0].settings.fetch_ahead_limit_mb,
+            ProfileSettings::default().fetch_ahead_limit_mb
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! Per-song skip statistics from the listening history, and the shuffle preference built on
+//! them. A song counts as usually skipped once it has come up `skip_min_plays` times and was
+//! skipped in at least `skip_ratio_threshold_percent` of them.
+use super::history::{load_listening_history, save_listening_history, ListeningHistory};
+use super::{AppSettings, SkippedSongShuffle};
+use crate::api::Song;
+use std::collections::HashMap;
+
+/// Smallest shuffle weight a down-weighted song keeps, so it can still come up early.
+const MIN_SHUFFLE_WEIGHT: f64 = 0.05;
+
+/// How often one song was skipped.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SongSkipStats {
+    pub server_id: String,
+    pub song_id: String,
+    pub title: String,
+    pub artist: Option<String>,
+    /// Times the song came up, finished or skipped.
+    pub plays: u32,
+    pub skips: u32,
+}
+
+impl SongSkipStats {
+    pub fn skip_ratio(&self) -> f64 {
+        if self.plays == 0 {
+            0.0
+        } else {
+            f64::from(self.skips) / f64::from(self.plays)
+        }
+    }
+}
+
+/// Skip counts per song, in the order each song first came up.
+fn song_skip_stats(history: &ListeningHistory) -> Vec<SongSkipStats> {
+    let mut index = HashMap::<(&str, &str), usize>::new();
+    let mut stats = Vec::<SongSkipStats>::new();
+    for outcome in &history.outcomes {
+        let key = (outcome.server_id.as_str(), outcome.song_id.as_str());
+        let slot = *index.entry(key).or_insert_with(|| {
+            stats.push(SongSkipStats {
+                server_id: outcome.server_id.clone(),
+                song_id: outcome.song_id.clone(),
+                ..SongSkipStats::default()
+            });
+            stats.len() - 1
+        });
+        let entry = &mut stats[slot];
+        // Keep the newest title and artist in case the tags changed.
+        entry.title = outcome.title.clone();
+        entry.artist = outcome.artist.clone();
+        entry.plays += 1;
+        if outcome.skipped_at_secs.is_some() {
+            entry.skips += 1;
+        }
+    }
+    stats
+}
+
+/// Songs skipped at least once, most skipped first.
+fn rank_most_skipped(mut stats: Vec<SongSkipStats>, limit: usize) -> Vec<SongSkipStats> {
+    stats.retain(|song| song.skips > 0);
+    stats.sort_by(|left, right| {
+        right
+            .skips
+            .cmp(&left.skips)
+            .then_with(|| right.skip_ratio().total_cmp(&left.skip_ratio()))
+    });
+    stats.truncate(limit);
+    stats
+}
+
+/// Skip counts of one song, if it has come up since the history was kept.
+pub async fn song_skip_stats_for(server_id: &str, song_id: &str) -> Option<SongSkipStats> {
+    let history = load_listening_history().ok()?;
+    song_skip_stats(&history)
+        .into_iter()
+        .find(|song| song.server_id == server_id && song.song_id == song_id)
+}
+
+/// The `limit` most skipped songs.
+pub async fn most_skipped_songs(limit: usize) -> Vec<SongSkipStats> {
+    load_listening_history()
+        .map(|history| rank_most_skipped(song_skip_stats(&history), limit))
+        .unwrap_or_default()
+}
+
+/// Forgets how often a song was finished or skipped. Listening sessions are left alone.
+pub async fn reset_song_skip_stats(server_id: &str, song_id: &str) {
+    let Ok(mut history) = load_listening_history() else {
+        return;
+    };
+    let before = history.outcomes.len();
+    history
+        .outcomes
+        .retain(|outcome| !(outcome.server_id == server_id && outcome.song_id == song_id));
+    if history.outcomes.len() != before {
+        let _ = save_listening_history(&history);
+    }
+}
+
+/// Shuffle weights of the songs the skipped-songs preference applies to. Songs not listed
+/// shuffle normally.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SkipShuffleWeights {
+    mode: SkippedSongShuffle,
+    /// Skip ratio of each usually skipped song, keyed by server id and song id.
+    ratios: HashMap<(String, String), f64>,
+}
+
+impl SkipShuffleWeights {
+    fn from_history(history: &ListeningHistory, settings: &AppSettings) -> Self {
+        if settings.skipped_song_shuffle == SkippedSongShuffle::Off {
+            return Self::default();
+        }
+        let threshold = f64::from(settings.skip_ratio_threshold_percent.min(100)) / 100.0;
+        let min_plays = settings.skip_min_plays.max(1);
+        let ratios = song_skip_stats(history)
+            .into_iter()
+            .filter(|song| song.plays >= min_plays && song.skip_ratio() >= threshold)
+            .map(|song| {
+                let ratio = song.skip_ratio();
+                ((song.server_id, song.song_id), ratio)
+            })
+            .collect();
+        Self {
+            mode: settings.skipped_song_shuffle,
+            ratios,
+        }
+    }
+
+    /// Weights for the current settings. Empty when the preference is off.
+    pub fn load(settings: &AppSettings) -> Self {
+        if settings.skipped_song_shuffle == SkippedSongShuffle::Off {
+            return Self::default();
+        }
+        load_listening_history()
+            .map(|history| Self::from_history(&history, settings))
+            .unwrap_or_default()
+    }
+
+    /// Whether shuffling with these weights is the same as a plain shuffle.
+    pub fn is_empty(&self) -> bool {
+        self.ratios.is_empty()
+    }
+
+    fn ratio(&self, song: &Song) -> Option<f64> {
+        self.ratios
+            .get(&(song.server_id.clone(), song.id.clone()))
+            .copied()
+    }
+
+    /// 1.0 for a normal song, less for a usually skipped one, 0.0 when it is excluded.
+    fn weight(&self, song: &Song) -> f64 {
+        match (self.mode, self.ratio(song)) {
+            (_, None) | (SkippedSongShuffle::Off, _) => 1.0,
+            (SkippedSongShuffle::DownWeight, Some(ratio)) => (1.0 - ratio).max(MIN_SHUFFLE_WEIGHT),
+            (SkippedSongShuffle::Exclude, Some(_)) => 0.0,
+        }
+    }
+
+    /// Drops usually skipped songs from a generated queue when they are excluded. The list is
+    /// kept as is if nothing would be left.
+    pub fn exclude(&self, songs: Vec<Song>) -> Vec<Song> {
+        if self.mode != SkippedSongShuffle::Exclude || self.is_empty() {
+            return songs;
+        }
+        let kept: Vec<Song> = songs
+            .iter()
+            .filter(|song| self.ratio(song).is_none())
+            .cloned()
+            .collect();
+        if kept.is_empty() {
+            songs
+        } else {
+            kept
+        }
+    }
+
+    /// Weighted shuffle: each song draws `u^(1/weight)` from `random` (uniform in [0, 1)) and
+    /// songs are ordered by their draw, so lighter songs tend to come later. Excluded songs
+    /// draw below every other song and end up last.
+    pub fn shuffle(&self, songs: &mut [Song], mut random: impl FnMut() -> f64) {
+        let mut keyed: Vec<(f64, Song)> = songs
+            .iter()
+            .map(|song| {
+                let draw = random().clamp(0.0, 1.0);
+                let weight = self.weight(song);
+                let key = if weight > 0.0 {
+                    draw.powf(1.0 / weight)
+                } else {
+                    draw - 1.0
+                };
+                (key, song.clone())
+            })
+            .collect();
+        keyed.sort_by(|left, right| right.0.total_cmp(&left.0));
+        for (slot, (_, song)) in songs.iter_mut().zip(keyed) {
+            *slot = song;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::history::TrackOutcome;
+    use super::*;
+
+    fn outcome(song_id: &str, skipped: bool) -> TrackOutcome {
+        TrackOutcome {
+            server_id: "s".to_string(),
+            song_id: song_id.to_string(),
+            title: format!("Song {song_id}"),
+            artist: None,
+            skipped_at_secs: skipped.then_some(12),
+            context: Some("album".to_string()),
+            at_ms: 0,
+        }
+    }
+
+    /// `skips` skipped and `finishes` finished plays of each listed song.
+    fn history(songs: &[(&str, u32, u32)]) -> ListeningHistory {
+        let mut history = ListeningHistory::default();
+        for (song_id, skips, finishes) in songs {
+            for _ in 0..*skips {
+                history.outcomes.push(outcome(song_id, true));
+            }
+            for _ in 0..*finishes {
+                history.outcomes.push(outcome(song_id, false));
+            }
+        }
+        history
+    }
+
+    fn song(id: &str) -> Song {
+        Song {
+            id: id.to_string(),
+            server_id: "s".to_string(),
+            title: format!("Song {id}"),
+            ..Song::default()
+        }
+    }
+
+    fn settings(mode: SkippedSongShuffle) -> AppSettings {
+        AppSettings {
+            skipped_song_shuffle: mode,
+            skip_ratio_threshold_percent: 75,
+            skip_min_plays: 5,
+            ..AppSettings::default()
+        }
+    }
+
+    /// Deterministic uniform draws in [0, 1).
+    fn lcg(seed: u64) -> impl FnMut() -> f64 {
+        let mut state = seed;
+        move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        }
+    }
+
+    #[test]
+    fn skip_ratio_needs_enough_plays() {
+        // "skipper" was skipped 8 of 9 times, "fresh" 2 of 2, "liked" 1 of 6.
+        let history = history(&[("skipper", 8, 1), ("fresh", 2, 0), ("liked", 1, 5)]);
+        let stats = song_skip_stats(&history);
+        assert_eq!((stats[0].skips, stats[0].plays), (8, 9));
+
+        let weights =
+            SkipShuffleWeights::from_history(&history, &settings(SkippedSongShuffle::Exclude));
+        assert!(weights.ratio(&song("skipper")).is_some());
+        assert!(weights.ratio(&song("fresh")).is_none());
+        assert!(weights.ratio(&song("liked")).is_none());
+
+        let ranked = rank_most_skipped(stats, 2);
+        let ids: Vec<&str> = ranked.iter().map(|song| song.song_id.as_str()).collect();
+        assert_eq!(ids, vec!["skipper", "fresh"]);
+    }
+
+    #[test]
+    fn preference_off_leaves_shuffle_alone() {
+        let history = history(&[("skipper", 9, 0)]);
+        let weights =
+            SkipShuffleWeights::from_history(&history, &settings(SkippedSongShuffle::Off));
+        assert!(weights.is_empty());
+        assert_eq!(weights.exclude(vec![song("skipper")]).len(), 1);
+    }
+
+    #[test]
+    fn exclude_drops_usually_skipped_songs_unless_nothing_is_left() {
+        let history = history(&[("skipper", 9, 0), ("liked", 0, 9)]);
+        let weights =
+            SkipShuffleWeights::from_history(&history, &settings(SkippedSongShuffle::Exclude));
+
+        let kept = weights.exclude(vec![song("skipper"), song("liked"), song("new")]);
+        let ids: Vec<&str> = kept.iter().map(|song| song.id.as_str()).collect();
+        assert_eq!(ids, vec!["liked", "new"]);
+        assert_eq!(weights.exclude(vec![song("skipper")]).len(), 1);
+
+        // When shuffling a collection, excluded songs always end up last.
+        for seed in 0..50 {
+            let mut songs = vec![song("skipper"), song("liked"), song("new"), song("other")];
+            weights.shuffle(&mut songs, lcg(seed));
+            assert_eq!(songs[3].id, "skipper");
+        }
+    }
+
+    #[test]
+    fn down_weighted_songs_tend_to_come_late() {
+        let history = history(&[("skipper", 8, 1)]);
+        let weights =
+            SkipShuffleWeights::from_history(&history, &settings(SkippedSongShuffle::DownWeight));
+        let ids: Vec<String> = (0..10).map(|index| format!("song-{index}")).collect();
+
+        let mut draws = lcg(7);
+        let trials = 2000;
+        let mut position_sum = 0;
+        let mut first = 0;
+        for _ in 0..trials {
+            let mut songs: Vec<Song> = std::iter::once(song("skipper"))
+                .chain(ids.iter().map(|id| song(id)))
+                .collect();
+            weights.shuffle(&mut songs, &mut draws);
+            let position = songs.iter().position(|song| song.id == "skipper").unwrap();
+            position_sum += position;
+            if position == 0 {
+                first += 1;
+            }
+            // Every song is still there exactly once.
+            assert_eq!(songs.len(), 11);
+        }
+
+        // A plain shuffle would average position 5 and put it first about 9% of the time.
+        assert!(
+            position_sum / trials >= 8,
+            "average {}",
+            position_sum / trials
+        );
+        assert!(first * 100 / trials < 3, "first {first} times");
+    }
+}
@@ -1,3 +1,7 @@
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
 #[cfg(not(target_arch = "wasm32"))]
 #[derive(Clone, Copy, Debug)]
 pub struct PerfTimer {
@@ -29,7 +33,7 @@ impl PerfTimer {
     }
 
     #[inline]
-    fn elapsed_ms(self) -> u128 {
+    pub fn elapsed_ms(self) -> u128 {
         #[cfg(not(target_arch = "wasm32"))]
         {
             return self.started_at.elapsed().as_millis();
@@ -45,9 +49,67 @@ impl PerfTimer {
 #[inline]
 pub fn log_perf(scope: &str, started_at: PerfTimer, details: &str) {
     let elapsed_ms = started_at.elapsed_ms();
-    if details.trim().is_empty() {
-        eprintln!("[perf] {scope} took {elapsed_ms}ms");
+    let line = if details.trim().is_empty() {
+        format!("[perf] {scope} took {elapsed_ms}ms")
     } else {
-        eprintln!("[perf] {scope} took {elapsed_ms}ms | {details}");
+        format!("[perf] {scope} took {elapsed_ms}ms | {details}")
+    };
+    eprintln!("{line}");
+    record_diagnostic(line);
+}
+
+const DIAGNOSTIC_LOG_LIMIT: usize = 50;
+
+static DIAGNOSTIC_LOG: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// Keeps the latest diagnostic lines in memory so crash reports can include them.
+pub fn record_diagnostic(line: impl Into<String>) {
+    let line = format!(
+        "{} {}",
+        chrono::Utc::now().format("%H:%M:%S%.3f"),
+        line.into()
+    );
+    let mut log = DIAGNOSTIC_LOG.lock().unwrap_or_else(|e| e.into_inner());
+    log.push_back(line);
+    while log.len() > DIAGNOSTIC_LOG_LIMIT {
+        log.pop_front();
+    }
+}
+
+/// Recent diagnostic lines, oldest first. Uses `try_lock` so a panic raised while the log
+/// is held can still be reported instead of deadlocking the panic hook.
+pub fn recent_diagnostics() -> Vec<String> {
+    match DIAGNOSTIC_LOG.try_lock() {
+        Ok(log) => log.iter().cloned().collect(),
+        Err(std::sync::TryLockError::Poisoned(poisoned)) => {
+            poisoned.into_inner().iter().cloned().collect()
+        }
+        Err(std::sync::TryLockError::WouldBlock) => Vec::new(),
+    }
+}
+
+const BLOCKED_REQUEST_LOG_LIMIT: usize = 50;
+
+static BLOCKED_REQUESTS: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// Records a request stopped by strict privacy mode so users can audit what was blocked.
+pub fn log_blocked_request(url: &str) {
+    eprintln!("[privacy] blocked request to {url}");
+    record_diagnostic(format!("[privacy] blocked request to {url}"));
+    let mut blocked = BLOCKED_REQUESTS.lock().unwrap_or_else(|e| e.into_inner());
+    if blocked.front().is_some_and(|latest| latest == url) {
+        return;
     }
+    blocked.push_front(url.to_string());
+    blocked.truncate(BLOCKED_REQUEST_LOG_LIMIT);
+}
+
+/// Most recent blocked request URLs, newest first.
+pub fn recent_blocked_requests() -> Vec<String> {
+    BLOCKED_REQUESTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .cloned()
+        .collect()
 }
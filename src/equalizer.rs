@@ -0,0 +1,68 @@
+//! Graphic equalizer settings: five bands of boost or cut, and the presets offered for them.
+//! Only the web player applies them, through a Web Audio filter chain.
+
+/// Centre frequencies of the bands, low to high. The outer two are shelves.
+pub const EQ_BAND_FREQUENCIES_HZ: [f32; 5] = [60.0, 230.0, 910.0, 3_600.0, 14_000.0];
+/// Furthest a band goes either way, in dB.
+pub const EQ_GAIN_LIMIT_DB: f32 = 12.0;
+
+/// Named band settings offered above the sliders.
+pub const EQ_PRESETS: [(&str, [f32; 5]); 4] = [
+    ("Flat", [0.0, 0.0, 0.0, 0.0, 0.0]),
+    ("Bass Boost", [6.0, 4.0, 0.0, 0.0, 0.0]),
+    ("Vocal", [-2.0, -1.0, 3.0, 4.0, 1.0]),
+    ("Treble", [0.0, 0.0, 0.0, 4.0, 6.0]),
+];
+
+pub fn default_eq_band_gains() -> Vec<f32> {
+    vec![0.0; EQ_BAND_FREQUENCIES_HZ.len()]
+}
+
+/// One gain per band, each within ±`EQ_GAIN_LIMIT_DB`. Missing bands are flat.
+pub fn normalized_band_gains(gains: &[f32]) -> Vec<f32> {
+    (0..EQ_BAND_FREQUENCIES_HZ.len())
+        .map(|band| match gains.get(band) {
+            Some(gain) if gain.is_finite() => gain.clamp(-EQ_GAIN_LIMIT_DB, EQ_GAIN_LIMIT_DB),
+            _ => 0.0,
+        })
+        .collect()
+}
+
+/// The preset `gains` match, if they are one.
+pub fn matching_preset(gains: &[f32]) -> Option<&'static str> {
+    EQ_PRESETS
+        .iter()
+        .find(|(_, preset)| preset.as_slice() == gains)
+        .map(|(name, _)| *name)
+}
+
+/// "60 Hz" or "3.6 kHz" for the band at `frequency_hz`.
+pub fn band_label(frequency_hz: f32) -> String {
+    if frequency_hz >= 1_000.0 {
+        format!("{} kHz", frequency_hz / 1_000.0)
+    } else {
+        format!("{frequency_hz} Hz")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gains_are_padded_clamped_and_matched_to_presets() {
+        assert_eq!(
+            normalized_band_gains(&[20.0, -20.0, f32::NAN]),
+            vec![12.0, -12.0, 0.0, 0.0, 0.0]
+        );
+        assert_eq!(
+            normalized_band_gains(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).len(),
+            5
+        );
+        assert_eq!(matching_preset(&default_eq_band_gains()), Some("Flat"));
+        assert_eq!(matching_preset(&EQ_PRESETS[1].1), Some("Bass Boost"));
+        assert_eq!(matching_preset(&[1.0, 0.0, 0.0, 0.0, 0.0]), None);
+        assert_eq!(band_label(910.0), "910 Hz");
+        assert_eq!(band_label(3_600.0), "3.6 kHz");
+    }
+}
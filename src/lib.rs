@@ -0,0 +1,5 @@
+//! The parts of the app that its integration tests link against: crash reports, the
+//! diagnostics log they quote and the storage paths they are written under.
+pub mod crash_report;
+pub mod diagnostics;
+pub mod storage;
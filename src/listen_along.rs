@@ -0,0 +1,256 @@
+//! Listen along: another device on the network mirrors what this one is playing.
+//!
+//! A hosting device answers `GET /listen-along` on [`LISTEN_ALONG_PORT`] with a [`NowPlaying`]
+//! snapshot. A follower polls it, streams the same song from its own server (the devices
+//! share a Navidrome instance) and stays within [`DRIFT_TOLERANCE_SECS`] of the host, as
+//! decided by [`drift_correction`]. Hosting needs a socket listener, so browsers can follow
+//! but not host.
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub const LISTEN_ALONG_PORT: u16 = 47831;
+const LISTEN_ALONG_PATH: &str = "/listen-along";
+/// How far a follower may sit from the host before it seeks.
+pub const DRIFT_TOLERANCE_SECS: f64 = 2.0;
+
+static FOLLOWING: AtomicBool = AtomicBool::new(false);
+
+/// What the host is playing, as served to followers.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NowPlaying {
+    #[serde(default)]
+    pub song: Option<NowPlayingSong>,
+    #[serde(default)]
+    pub position: f64,
+    #[serde(default)]
+    pub playing: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NowPlayingSong {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub artist: Option<String>,
+    #[serde(default)]
+    pub album: Option<String>,
+    #[serde(default)]
+    pub cover_art: Option<String>,
+    #[serde(default)]
+    pub duration: u32,
+    /// URL of the server the host streams from, so a follower signed in to several servers
+    /// picks the same one.
+    #[serde(default)]
+    pub server_url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DriftCorrection {
+    InSync,
+    SeekTo(f64),
+}
+
+/// What a follower at `local_pos` should do about a host that was at `host_pos` `latency`
+/// seconds ago. The host has played on for the latency, so that is where the follower aims.
+pub fn drift_correction(host_pos: f64, local_pos: f64, latency: f64) -> DriftCorrection {
+    if !host_pos.is_finite() {
+        return DriftCorrection::InSync;
+    }
+    let target = (host_pos + latency.max(0.0)).max(0.0);
+    if (target - local_pos).abs() <= DRIFT_TOLERANCE_SECS {
+        DriftCorrection::InSync
+    } else {
+        DriftCorrection::SeekTo(target)
+    }
+}
+
+/// The endpoint for what the user typed to follow a host: a full URL, `address:port`, or
+/// just the host's address. The port defaults to [`LISTEN_ALONG_PORT`].
+pub fn listen_along_url(input: &str) -> Option<String> {
+    let input = input.trim().trim_end_matches('/');
+    let (scheme, rest) = input.split_once("://").unwrap_or(("http", input));
+    if !matches!(scheme, "http" | "https") {
+        return None;
+    }
+    let (authority, path) = match rest.find('/') {
+        Some(split) => rest.split_at(split),
+        None => (rest, ""),
+    };
+    if authority.is_empty() || authority.contains(char::is_whitespace) {
+        return None;
+    }
+    let has_port = authority
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| port.parse::<u16>().is_ok());
+    let port = if has_port {
+        String::new()
+    } else {
+        format!(":{LISTEN_ALONG_PORT}")
+    };
+    let path = if path.is_empty() {
+        LISTEN_ALONG_PATH
+    } else {
+        path
+    };
+    Some(format!("{scheme}://{authority}{port}{path}"))
+}
+
+/// Whether this device is following a host. Transport actions from media keys and remotes
+/// are refused while it is.
+pub fn is_following() -> bool {
+    FOLLOWING.load(Ordering::Relaxed)
+}
+
+pub fn set_following(following: bool) {
+    FOLLOWING.store(following, Ordering::Relaxed);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use host::{lan_address, publish, start_hosting, stop_hosting};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod host {
+    use super::{NowPlaying, LISTEN_ALONG_PATH, LISTEN_ALONG_PORT};
+    use once_cell::sync::Lazy;
+    use std::sync::Mutex;
+    use std::time::Instant;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::task::JoinHandle;
+
+    static PUBLISHED: Lazy<Mutex<(NowPlaying, Instant)>> =
+        Lazy::new(|| Mutex::new((NowPlaying::default(), Instant::now())));
+    static SERVER: Lazy<Mutex<Option<JoinHandle<()>>>> = Lazy::new(|| Mutex::new(None));
+
+    /// Replaces what followers are told is playing.
+    pub fn publish(now_playing: NowPlaying) {
+        *PUBLISHED.lock().unwrap_or_else(|e| e.into_inner()) = (now_playing, Instant::now());
+    }
+
+    /// The published state, with the position carried forward to now while playing.
+    fn snapshot() -> NowPlaying {
+        let (mut now_playing, published_at) =
+            PUBLISHED.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        if now_playing.playing {
+            now_playing.position += published_at.elapsed().as_secs_f64();
+            if let Some(duration) = now_playing.song.as_ref().map(|song| song.duration) {
+                if duration > 0 {
+                    now_playing.position = now_playing.position.min(f64::from(duration));
+                }
+            }
+        }
+        now_playing
+    }
+
+    fn is_hosting() -> bool {
+        SERVER
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .is_some_and(|server| !server.is_finished())
+    }
+
+    /// Starts answering followers on every interface.
+    pub async fn start_hosting() -> std::io::Result<()> {
+        if is_hosting() {
+            return Ok(());
+        }
+        let listener = TcpListener::bind(("0.0.0.0", LISTEN_ALONG_PORT)).await?;
+        let server = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(answer(stream));
+            }
+        });
+        *SERVER.lock().unwrap_or_else(|e| e.into_inner()) = Some(server);
+        Ok(())
+    }
+
+    pub fn stop_hosting() {
+        if let Some(server) = SERVER.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            server.abort();
+        }
+    }
+
+    async fn answer(mut stream: TcpStream) {
+        let mut request = [0u8; 2048];
+        let Ok(read) = stream.read(&mut request).await else {
+            return;
+        };
+        let request_line = String::from_utf8_lossy(&request[..read]);
+        let wanted = format!("GET {LISTEN_ALONG_PATH} ");
+        let (status, body) = if request_line.starts_with(&wanted) {
+            let body = serde_json::to_string(&snapshot()).unwrap_or_else(|_| "{}".to_string());
+            ("200 OK", body)
+        } else {
+            ("404 Not Found", "{}".to_string())
+        };
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+             Access-Control-Allow-Origin: *\r\nCache-Control: no-store\r\n\
+             Connection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let _ = stream.write_all(response.as_bytes()).await;
+        let _ = stream.shutdown().await;
+    }
+
+    /// This device's address on the local network, which followers type in to follow it.
+    pub fn lan_address() -> Option<String> {
+        // Connecting a UDP socket sends nothing; it only picks the outgoing interface.
+        let socket = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
+        socket.connect("192.0.2.1:80").ok()?;
+        let address = socket.local_addr().ok()?.ip();
+        (!address.is_loopback() && !address.is_unspecified())
+            .then(|| format!("{address}:{LISTEN_ALONG_PORT}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drift_correction_aims_past_the_latency_outside_the_tolerance() {
+        assert_eq!(drift_correction(60.0, 61.5, 0.2), DriftCorrection::InSync);
+        assert_eq!(drift_correction(60.0, 58.3, 0.0), DriftCorrection::InSync);
+        assert_eq!(
+            drift_correction(60.0, 57.0, 0.5),
+            DriftCorrection::SeekTo(60.5)
+        );
+        assert_eq!(
+            drift_correction(60.0, 64.0, 0.0),
+            DriftCorrection::SeekTo(60.0)
+        );
+        // Close to where the host was, but not to where it is now.
+        assert_eq!(
+            drift_correction(60.0, 58.5, 1.5),
+            DriftCorrection::SeekTo(61.5)
+        );
+        assert_eq!(
+            drift_correction(f64::NAN, 3.0, 0.1),
+            DriftCorrection::InSync
+        );
+        assert_eq!(
+            drift_correction(0.0, 5.0, -1.0),
+            DriftCorrection::SeekTo(0.0)
+        );
+    }
+
+    #[test]
+    fn listen_along_url_fills_in_the_port_and_path() {
+        assert_eq!(
+            listen_along_url(" 192.168.1.5 ").as_deref(),
+            Some("http://192.168.1.5:47831/listen-along")
+        );
+        assert_eq!(
+            listen_along_url("192.168.1.5:9000/").as_deref(),
+            Some("http://192.168.1.5:9000/listen-along")
+        );
+        assert_eq!(
+            listen_along_url("http://living-room.local:47831/listen-along").as_deref(),
+            Some("http://living-room.local:47831/listen-along")
+        );
+        assert_eq!(listen_along_url(""), None);
+        assert_eq!(listen_along_url("ftp://host"), None);
+    }
+}
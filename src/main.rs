@@ -3,14 +3,31 @@ use dioxus::prelude::*;
 mod api;
 mod cache;
 mod cache_service;
+mod collation;
 mod components;
+mod continuous_albums;
 mod db;
-mod diagnostics;
+mod equalizer;
+mod feedback;
+mod listen_along;
+mod network_policy;
 mod offline_art;
 mod offline_audio;
-mod storage;
+#[cfg(any(feature = "desktop", test))]
+mod playback_title;
+mod quiet_hours;
+#[cfg(not(target_arch = "wasm32"))]
+mod quit_flush;
+mod replay_gain;
+#[cfg(feature = "desktop")]
+mod sleep_inhibit;
+#[cfg(feature = "desktop")]
+mod taskbar_status;
+mod time_format;
+mod tracklist_export;
 
 use components::AppView;
+use rustysound::{crash_report, diagnostics, storage};
 
 #[cfg(feature = "desktop")]
 const FAVICON_ICO: &str = "/assets/favicon.ico";
@@ -134,7 +151,24 @@ fn desktop_app_icon() -> Option<dioxus::desktop::tao::window::Icon> {
     Icon::from_rgba(rgba, info.width, info.height).ok()
 }
 
+/// Window and document title, naming the profile when one is active.
+fn app_title() -> String {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        if let Some(profile) = storage::active_profile() {
+            return format!("RustySound ({profile})");
+        }
+    }
+    "RustySound".to_string()
+}
+
 fn main() {
+    crash_report::install_panic_hook();
+    #[cfg(feature = "desktop")]
+    quit_flush::report_previous_quit();
+    #[cfg(all(feature = "desktop", target_os = "linux"))]
+    quit_flush::install_sigterm_handler();
+
     #[cfg(feature = "desktop")]
     {
         use dioxus::desktop::{Config, WindowBuilder};
@@ -147,7 +181,7 @@ fn main() {
             }
         }
 
-        let mut window = WindowBuilder::new().with_title("RustySound");
+        let mut window = WindowBuilder::new().with_title(app_title());
         if let Some(icon) = desktop_app_icon() {
             window = window.with_window_icon(Some(icon.clone()));
 
@@ -345,7 +379,7 @@ fn macos_menu_bar() -> dioxus::desktop::muda::Menu {
 #[component]
 fn App() -> Element {
     rsx! {
-        document::Title { "RustySound" }
+        document::Title { "{app_title()}" }
         // Favicon and icons
         document::Link { rel: "icon", r#type: "image/svg+xml", href: FAVICON_SVG }
         document::Link { rel: "shortcut icon", href: FAVICON_ICO }
@@ -398,7 +432,37 @@ fn App() -> Element {
 
         GlobalStyles {}
 
-        Router::<AppView> {}
+        DatabaseGate {}
+    }
+}
+
+/// Mounts the app once the local database opens and passes its integrity check, and the
+/// recovery screen otherwise. Nothing that reads the database is mounted before this.
+#[component]
+fn DatabaseGate() -> Element {
+    let mut database_check = use_resource(|| async {
+        db::initialize_database()
+            .await
+            .map_err(|error| error.to_string())
+    });
+
+    match database_check() {
+        Some(Ok(())) => rsx! {
+            Router::<AppView> {}
+        },
+        #[cfg(not(target_arch = "wasm32"))]
+        Some(Err(error)) => rsx! {
+            components::DatabaseRecoveryScreen {
+                error,
+                on_retry: move |_| database_check.restart(),
+            }
+        },
+        // Browser storage is best-effort; the app runs on defaults without it.
+        #[cfg(target_arch = "wasm32")]
+        Some(Err(_)) => rsx! {
+            Router::<AppView> {}
+        },
+        None => rsx! {},
     }
 }
 
@@ -0,0 +1,92 @@
+//! Outgoing request allowlist for strict privacy mode.
+//!
+//! Every HTTP client in the app is a [`GuardedClient`], so the allowlist is checked in one
+//! place. With strict privacy off, every host is allowed; with it on, only the hosts of
+//! configured servers are.
+use crate::api::ServerConfig;
+use crate::diagnostics::log_blocked_request;
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+/// Unroutable target swapped in for blocked requests so they fail without touching the network.
+const BLOCKED_REQUEST_URL: &str = "privacy://blocked-by-strict-mode";
+
+#[derive(Debug, Default)]
+struct NetworkPolicy {
+    strict_privacy: bool,
+    allowed_hosts: HashSet<String>,
+}
+
+static POLICY: Lazy<Mutex<NetworkPolicy>> = Lazy::new(|| Mutex::new(NetworkPolicy::default()));
+
+/// Rebuilds the allowlist from the current setting and configured servers.
+pub fn configure(strict_privacy: bool, servers: &[ServerConfig]) {
+    let allowed_hosts = servers
+        .iter()
+        .filter_map(|server| url_host(&server.url))
+        .collect();
+    let mut policy = POLICY.lock().unwrap_or_else(|e| e.into_inner());
+    policy.strict_privacy = strict_privacy;
+    policy.allowed_hosts = allowed_hosts;
+}
+
+pub fn strict_privacy_enabled() -> bool {
+    POLICY
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .strict_privacy
+}
+
+/// Lowercased host of an absolute http(s) URL.
+fn url_host(url: &str) -> Option<String> {
+    let parsed = reqwest::Url::parse(url.trim()).ok()?;
+    parsed.host_str().map(|host| host.to_ascii_lowercase())
+}
+
+/// Whether `url` may be requested. Blocked attempts are logged for auditing.
+pub fn allows_url(url: &str) -> bool {
+    let allowed = {
+        let policy = POLICY.lock().unwrap_or_else(|e| e.into_inner());
+        if !policy.strict_privacy || url.starts_with("file://") {
+            true
+        } else {
+            url_host(url).is_some_and(|host| policy.allowed_hosts.contains(&host))
+        }
+    };
+    if !allowed {
+        log_blocked_request(url);
+    }
+    allowed
+}
+
+/// A `reqwest::Client` that routes every request through the privacy allowlist.
+pub struct GuardedClient(reqwest::Client);
+
+impl GuardedClient {
+    pub fn new() -> Self {
+        Self(reqwest::Client::new())
+    }
+
+    fn target<'a>(&self, url: &'a str) -> &'a str {
+        if allows_url(url) {
+            url
+        } else {
+            BLOCKED_REQUEST_URL
+        }
+    }
+
+    pub fn get(&self, url: impl AsRef<str>) -> reqwest::RequestBuilder {
+        self.0.get(self.target(url.as_ref()))
+    }
+
+    pub fn post(&self, url: impl AsRef<str>) -> reqwest::RequestBuilder {
+        self.0.post(self.target(url.as_ref()))
+    }
+}
+
+impl Default for GuardedClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
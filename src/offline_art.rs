@@ -1,5 +1,7 @@
 #[cfg(not(target_arch = "wasm32"))]
-use crate::cache_service::is_enabled as cache_enabled;
+use crate::cache_service::{is_enabled as cache_enabled, is_known_miss, remember_miss};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::network_policy::GuardedClient;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::storage::app_cache_dir;
 #[cfg(not(target_arch = "wasm32"))]
@@ -21,7 +23,7 @@ const COVER_ART_CACHE_SUBDIR: &str = "cover_art_cache";
 #[cfg(not(target_arch = "wasm32"))]
 static IN_FLIGHT_ART: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
 #[cfg(not(target_arch = "wasm32"))]
-static ART_HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
+static ART_HTTP_CLIENT: Lazy<GuardedClient> = Lazy::new(GuardedClient::new);
 
 #[cfg(not(target_arch = "wasm32"))]
 fn sanitize_file_component(raw: &str) -> String {
@@ -94,6 +96,10 @@ pub fn maybe_prefetch_cover_art(
     if path.exists() {
         return;
     }
+    let miss_key = format!("cover_art:{server_id}:{cover_art_id}");
+    if is_known_miss(&miss_key) {
+        return;
+    }
 
     let inflight_key = format!("{server_id}:{cover_art_id}:{size}");
     {
@@ -105,7 +111,9 @@ pub fn maybe_prefetch_cover_art(
 
     tokio::spawn(async move {
         if let Ok(response) = ART_HTTP_CLIENT.get(remote_url).send().await {
-            if response.status().is_success() {
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                remember_miss(&miss_key);
+            } else if response.status().is_success() {
                 if let Ok(bytes) = response.bytes().await {
                     if !bytes.is_empty() {
                         let _ = tokio::fs::write(&path, bytes).await;
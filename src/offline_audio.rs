@@ -1,11 +1,14 @@
 #[cfg(not(target_arch = "wasm32"))]
 use crate::api::{
-    fetch_lyrics_with_fallback, normalize_lyrics_provider_order, LyricsQuery, NavidromeClient,
+    fetch_lyrics_with_fallback, normalize_lyrics_provider_order, server_needs_reauth, LyricsQuery,
+    NavidromeClient,
 };
 use crate::api::{ServerConfig, Song};
 use crate::db::AppSettings;
 #[cfg(not(target_arch = "wasm32"))]
-use crate::db::ArtworkDownloadPreference;
+use crate::db::{last_played_by_song, ArtworkDownloadPreference};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::network_policy::GuardedClient;
 #[cfg(not(target_arch = "wasm32"))]
 use crate::storage::app_cache_dir;
 use serde::{Deserialize, Serialize};
@@ -17,6 +20,7 @@ use serde::{Deserialize, Serialize};
 use base64::{engine::general_purpose, Engine as _};
 #[cfg(not(target_arch = "wasm32"))]
 use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
 #[cfg(not(target_arch = "wasm32"))]
 use std::collections::{HashMap, HashSet};
 #[cfg(not(target_arch = "wasm32"))]
@@ -30,7 +34,7 @@ use std::path::Path;
 use std::path::PathBuf;
 
 #[cfg(not(target_arch = "wasm32"))]
-static AUDIO_HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
+static AUDIO_HTTP_CLIENT: Lazy<GuardedClient> = Lazy::new(GuardedClient::new);
 
 #[cfg(not(target_arch = "wasm32"))]
 const AUDIO_CACHE_SUBDIR: &str = "audio_cache";
@@ -41,12 +45,17 @@ const COLLECTION_INDEX_FILE: &str = "download_collections.json";
 #[cfg(not(target_arch = "wasm32"))]
 const COLLECTION_MEMBERSHIP_INDEX_FILE: &str = "download_collection_memberships.json";
 #[cfg(not(target_arch = "wasm32"))]
+const QUALITY_OVERRIDE_FILE: &str = "download_quality_overrides.json";
+#[cfg(not(target_arch = "wasm32"))]
 const DOWNLOAD_ARTWORK_SIZES: [u32; 7] = [80, 100, 120, 160, 300, 500, 512];
 #[cfg(not(target_arch = "wasm32"))]
 const CACHE_AUDIO_EXTENSIONS: [&str; 8] =
     ["audio", "mp3", "flac", "ogg", "m4a", "aac", "wav", "mp4"];
 #[cfg(not(target_arch = "wasm32"))]
 const TEMP_QUEUE_PREFETCH_LIMIT_WHEN_AUTO_OFF: usize = 5;
+/// How long a download request waits on an identical one already in flight.
+#[cfg(not(target_arch = "wasm32"))]
+const ACTIVE_DOWNLOAD_WAIT_MS: u64 = 5 * 60 * 1000;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -57,6 +66,62 @@ pub enum DownloadOrigin {
     QueuePrefetch,
 }
 
+/// Quality a song is downloaded at. Downloads keep the original file unless a song has a
+/// per-song override.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadQuality {
+    #[default]
+    Original,
+    Mp3_320,
+    Mp3_192,
+}
+
+impl DownloadQuality {
+    pub const ALL: [DownloadQuality; 3] = [
+        DownloadQuality::Original,
+        DownloadQuality::Mp3_320,
+        DownloadQuality::Mp3_192,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DownloadQuality::Original => "Original",
+            DownloadQuality::Mp3_320 => "320 kbps",
+            DownloadQuality::Mp3_192 => "192 kbps",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            DownloadQuality::Original => "original",
+            DownloadQuality::Mp3_320 => "mp3_320",
+            DownloadQuality::Mp3_192 => "mp3_192",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|quality| quality.key() == key)
+    }
+
+    /// Transcode cap requested from the server, `None` for the original file.
+    pub fn max_bitrate_kbps(self) -> Option<u32> {
+        match self {
+            DownloadQuality::Original => None,
+            DownloadQuality::Mp3_320 => Some(320),
+            DownloadQuality::Mp3_192 => Some(192),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct DownloadQualityOverride {
+    server_id: String,
+    song_id: String,
+    quality: DownloadQuality,
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct DownloadStats {
     pub song_count: usize,
@@ -71,6 +136,24 @@ pub struct AutoDownloadReport {
     pub failed: usize,
     pub purged: usize,
     pub indexed: usize,
+    /// Downloads removed by the age-based cleanup, and the space they took.
+    pub cleaned_up: usize,
+    pub cleaned_up_bytes: u64,
+}
+
+impl AutoDownloadReport {
+    /// ", 3 idle removed (412.0 MB)" for the pass summary, empty when the cleanup removed
+    /// nothing.
+    pub fn cleanup_summary(&self) -> String {
+        if self.cleaned_up == 0 {
+            return String::new();
+        }
+        format!(
+            ", {} idle removed ({:.1} MB)",
+            self.cleaned_up,
+            self.cleaned_up_bytes as f64 / (1024.0 * 1024.0)
+        )
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -81,6 +164,8 @@ pub struct DownloadBatchReport {
     pub failed: usize,
     pub purged: usize,
     pub indexed: usize,
+    /// Songs left out because the download count or size limit was already reached.
+    pub limited: usize,
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -92,6 +177,37 @@ pub struct DownloadCacheRefreshReport {
     pub artwork_refreshed: usize,
 }
 
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DownloadVerifyReport {
+    pub checked: usize,
+    /// Entries whose file was gone; they are dropped from the index.
+    pub missing: usize,
+    /// Files that were empty or no longer had the indexed size; they are deleted with
+    /// their entries so the song downloads again.
+    pub damaged: usize,
+}
+
+/// What a verification pass found for one download.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DownloadFileCheck {
+    Intact,
+    Missing,
+    Damaged,
+}
+
+/// Checks `entry` against the size of its file on disk, `None` when there is no file.
+/// Entries indexed without a size only need a non-empty file.
+#[cfg(not(target_arch = "wasm32"))]
+fn check_download_file(entry: &DownloadIndexEntry, file_len: Option<u64>) -> DownloadFileCheck {
+    match file_len {
+        None => DownloadFileCheck::Missing,
+        Some(0) => DownloadFileCheck::Damaged,
+        Some(len) if entry.size_bytes > 0 && len != entry.size_bytes => DownloadFileCheck::Damaged,
+        Some(_) => DownloadFileCheck::Intact,
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct DownloadIndexEntry {
     pub server_id: String,
@@ -104,9 +220,22 @@ pub struct DownloadIndexEntry {
     #[serde(default)]
     pub album_id: Option<String>,
     #[serde(default)]
+    pub artist_id: Option<String>,
+    #[serde(default)]
     pub cover_art_id: Option<String>,
     #[serde(default)]
+    pub duration: u32,
+    /// Server file path, used to find the song again if the server library is rebuilt.
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
     pub origin: DownloadOrigin,
+    /// Quality of the file on disk; entries from before overrides existed are originals.
+    #[serde(default)]
+    pub quality: DownloadQuality,
+    /// "Keep offline forever": never removed by the age-based cleanup.
+    #[serde(default)]
+    pub pinned: bool,
     pub size_bytes: u64,
     pub updated_at_ms: u64,
 }
@@ -177,6 +306,67 @@ pub struct ActiveDownloadEntry {
 static ACTIVE_DOWNLOADS: Lazy<std::sync::Mutex<Vec<ActiveDownloadEntry>>> =
     Lazy::new(|| std::sync::Mutex::new(Vec::new()));
 
+/// A song waiting in a manual download batch. Batches take their next song from the shared
+/// pending list, so reordering it changes what downloads next without touching transfers
+/// that are already running.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PendingDownloadEntry {
+    pub batch_id: u64,
+    pub server_id: String,
+    pub song_id: String,
+    pub title: String,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub album_id: Option<String>,
+    /// Set on snapshots when the song is coming up soon in the play queue.
+    pub boosted: bool,
+}
+
+impl PendingDownloadEntry {
+    pub fn key(&self) -> String {
+        format!("{}::{}", self.server_id, self.song_id)
+    }
+
+    /// Songs of the same album share a group, so the whole album can be moved up at once.
+    pub fn group_key(&self) -> Option<String> {
+        match (&self.album_id, &self.album) {
+            (Some(album_id), _) if !album_id.trim().is_empty() => {
+                Some(format!("{}::album:{}", self.server_id, album_id))
+            }
+            (_, Some(album)) if !album.trim().is_empty() => {
+                Some(format!("{}::name:{}", self.server_id, album))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Upcoming play-queue songs get downloaded ahead of the rest of a batch.
+#[cfg(not(target_arch = "wasm32"))]
+const DOWNLOAD_QUEUE_BOOST_LIMIT: usize = 10;
+
+#[cfg(not(target_arch = "wasm32"))]
+static PENDING_DOWNLOADS: Lazy<std::sync::Mutex<Vec<PendingDownloadEntry>>> =
+    Lazy::new(|| std::sync::Mutex::new(Vec::new()));
+
+/// Keys of the next songs in the play queue, in play order.
+#[cfg(not(target_arch = "wasm32"))]
+static DOWNLOAD_QUEUE_BOOST: Lazy<std::sync::Mutex<Vec<String>>> =
+    Lazy::new(|| std::sync::Mutex::new(Vec::new()));
+
+/// Set while playback streams from the server and the user asked downloads to wait for it.
+#[cfg(not(target_arch = "wasm32"))]
+static DOWNLOADS_HELD_FOR_STREAMING: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+/// Per-song quality overrides, read from disk once. The song details panel asks on every
+/// render, so lookups must not touch the filesystem.
+#[cfg(not(target_arch = "wasm32"))]
+static QUALITY_OVERRIDES: Lazy<std::sync::Mutex<Option<Vec<DownloadQualityOverride>>>> =
+    Lazy::new(|| std::sync::Mutex::new(None));
+#[cfg(not(target_arch = "wasm32"))]
+static NEXT_DOWNLOAD_BATCH_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+
 #[cfg(not(target_arch = "wasm32"))]
 fn now_timestamp_millis() -> u64 {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -448,6 +638,34 @@ fn save_collection_membership_index(index: &[DownloadCollectionMembershipEntry])
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn load_quality_overrides() -> Vec<DownloadQualityOverride> {
+    let mut cached = QUALITY_OVERRIDES
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    cached
+        .get_or_insert_with(|| {
+            audio_cache_dir()
+                .and_then(|dir| fs::read_to_string(dir.join(QUALITY_OVERRIDE_FILE)).ok())
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default()
+        })
+        .clone()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_quality_overrides(overrides: &[DownloadQualityOverride]) {
+    *QUALITY_OVERRIDES
+        .lock()
+        .unwrap_or_else(|err| err.into_inner()) = Some(overrides.to_vec());
+    let Some(path) = audio_cache_dir().map(|dir| dir.join(QUALITY_OVERRIDE_FILE)) else {
+        return;
+    };
+    if let Ok(json) = serde_json::to_string(overrides) {
+        let _ = fs::write(path, json);
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 fn merged_download_origin(existing: DownloadOrigin, incoming: DownloadOrigin) -> DownloadOrigin {
     match (existing, incoming) {
@@ -457,26 +675,67 @@ fn merged_download_origin(existing: DownloadOrigin, incoming: DownloadOrigin) ->
     }
 }
 
+/// Copies `song`'s metadata onto an existing index entry. `updated_at_ms`, which idle
+/// cleanup ages downloads by, only moves when something actually changed. Returns whether it
+/// did.
+#[cfg(not(target_arch = "wasm32"))]
+fn refresh_download_entry(
+    entry: &mut DownloadIndexEntry,
+    song: &Song,
+    size_bytes: u64,
+    origin: DownloadOrigin,
+    quality: Option<DownloadQuality>,
+    now_ms: u64,
+) -> bool {
+    let before = entry.clone();
+    entry.server_name = if song.server_name.trim().is_empty() {
+        None
+    } else {
+        Some(song.server_name.clone())
+    };
+    entry.title = song.title.clone();
+    entry.artist = song.artist.clone();
+    entry.album = song.album.clone();
+    entry.album_id = song.album_id.clone();
+    entry.artist_id = song.artist_id.clone();
+    entry.cover_art_id = song.cover_art.clone();
+    entry.duration = song.duration;
+    entry.path = song.path.clone();
+    entry.origin = merged_download_origin(entry.origin, origin);
+    if let Some(quality) = quality {
+        entry.quality = quality;
+    }
+    entry.size_bytes = size_bytes;
+    let changed = *entry != before;
+    if changed {
+        entry.updated_at_ms = now_ms;
+    }
+    changed
+}
+
 #[cfg(not(target_arch = "wasm32"))]
-fn upsert_download_index(song: &Song, size_bytes: u64, origin: DownloadOrigin) {
+fn upsert_download_index(
+    song: &Song,
+    size_bytes: u64,
+    origin: DownloadOrigin,
+    quality: Option<DownloadQuality>,
+) {
     let mut index = load_download_index();
     if let Some(entry) = index
         .iter_mut()
         .find(|entry| entry.server_id == song.server_id && entry.song_id == song.id)
     {
-        entry.server_name = if song.server_name.trim().is_empty() {
-            None
-        } else {
-            Some(song.server_name.clone())
-        };
-        entry.title = song.title.clone();
-        entry.artist = song.artist.clone();
-        entry.album = song.album.clone();
-        entry.album_id = song.album_id.clone();
-        entry.cover_art_id = song.cover_art.clone();
-        entry.origin = merged_download_origin(entry.origin, origin);
-        entry.size_bytes = size_bytes;
-        entry.updated_at_ms = now_timestamp_millis();
+        let changed = refresh_download_entry(
+            entry,
+            song,
+            size_bytes,
+            origin,
+            quality,
+            now_timestamp_millis(),
+        );
+        if !changed {
+            return;
+        }
     } else {
         index.push(DownloadIndexEntry {
             server_id: song.server_id.clone(),
@@ -490,8 +749,13 @@ fn upsert_download_index(song: &Song, size_bytes: u64, origin: DownloadOrigin) {
             artist: song.artist.clone(),
             album: song.album.clone(),
             album_id: song.album_id.clone(),
+            artist_id: song.artist_id.clone(),
             cover_art_id: song.cover_art.clone(),
+            duration: song.duration,
+            path: song.path.clone(),
             origin,
+            quality: quality.unwrap_or_default(),
+            pinned: false,
             size_bytes,
             updated_at_ms: now_timestamp_millis(),
         });
@@ -540,7 +804,11 @@ fn prune_audio_cache(max_size_mb: u32) {
         if path
             .file_name()
             .and_then(|name| name.to_str())
-            .is_some_and(|name| name == DOWNLOAD_INDEX_FILE || name == COLLECTION_INDEX_FILE)
+            .is_some_and(|name| {
+                name == DOWNLOAD_INDEX_FILE
+                    || name == COLLECTION_INDEX_FILE
+                    || name == QUALITY_OVERRIDE_FILE
+            })
         {
             continue;
         }
@@ -654,6 +922,54 @@ pub fn list_downloaded_entries() -> Vec<DownloadIndexEntry> {
     Vec::new()
 }
 
+/// Per-song download quality, `Original` unless the user picked something else.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn download_quality_for(server_id: &str, song_id: &str) -> DownloadQuality {
+    load_quality_overrides()
+        .into_iter()
+        .find(|entry| entry.server_id == server_id && entry.song_id == song_id)
+        .map(|entry| entry.quality)
+        .unwrap_or_default()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn download_quality_for(_server_id: &str, _song_id: &str) -> DownloadQuality {
+    DownloadQuality::Original
+}
+
+/// Saves the per-song download quality. Choosing `Original` drops the override. Returns
+/// whether the song is already downloaded at a different quality and should be fetched again.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn set_download_quality_override(
+    server_id: &str,
+    song_id: &str,
+    quality: DownloadQuality,
+) -> bool {
+    let mut overrides = load_quality_overrides();
+    overrides.retain(|entry| !(entry.server_id == server_id && entry.song_id == song_id));
+    if quality != DownloadQuality::Original {
+        overrides.push(DownloadQualityOverride {
+            server_id: server_id.to_string(),
+            song_id: song_id.to_string(),
+            quality,
+        });
+    }
+    save_quality_overrides(&overrides);
+
+    load_download_index().iter().any(|entry| {
+        entry.server_id == server_id && entry.song_id == song_id && entry.quality != quality
+    })
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn set_download_quality_override(
+    _server_id: &str,
+    _song_id: &str,
+    _quality: DownloadQuality,
+) -> bool {
+    false
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub fn mark_collection_downloaded(
     kind: &str,
@@ -1026,6 +1342,25 @@ fn pop_active_download(server_id: &str, song_id: &str) {
     downloads.retain(|entry| !(entry.server_id == server_id && entry.song_id == song_id));
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn is_download_active(server_id: &str, song_id: &str) -> bool {
+    ACTIVE_DOWNLOADS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .iter()
+        .any(|entry| entry.server_id == server_id.trim() && entry.song_id == song_id.trim())
+}
+
+/// Waits for another task's download of the same song to finish, up to `ACTIVE_DOWNLOAD_WAIT_MS`.
+#[cfg(not(target_arch = "wasm32"))]
+async fn wait_for_active_download(server_id: &str, song_id: &str) {
+    let mut waited_ms = 0u64;
+    while is_download_active(server_id, song_id) && waited_ms < ACTIVE_DOWNLOAD_WAIT_MS {
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        waited_ms += 250;
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub fn list_active_downloads() -> Vec<ActiveDownloadEntry> {
     let downloads = ACTIVE_DOWNLOADS
@@ -1065,6 +1400,213 @@ impl Drop for ActiveDownloadGuard {
     }
 }
 
+/// Called by the audio controller whenever playback starts or stops streaming. Downloads
+/// already in flight finish; the next one waits until this is cleared.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn set_downloads_held_for_streaming(held: bool) {
+    DOWNLOADS_HELD_FOR_STREAMING.store(held, std::sync::atomic::Ordering::Relaxed);
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn set_downloads_held_for_streaming(_held: bool) {}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn wait_while_streaming() {
+    while DOWNLOADS_HELD_FOR_STREAMING.load(std::sync::atomic::Ordering::Relaxed) {
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    }
+}
+
+/// Pending indices in download order: boosted songs first, in play-queue order, then the
+/// rest in list order.
+#[cfg(not(target_arch = "wasm32"))]
+fn effective_pending_order(pending: &[PendingDownloadEntry], boost: &[String]) -> Vec<usize> {
+    let mut order = Vec::with_capacity(pending.len());
+    for key in boost {
+        if let Some(index) = pending.iter().position(|entry| entry.key() == *key) {
+            if !order.contains(&index) {
+                order.push(index);
+            }
+        }
+    }
+    for index in 0..pending.len() {
+        if !order.contains(&index) {
+            order.push(index);
+        }
+    }
+    order
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn next_pending_index(
+    pending: &[PendingDownloadEntry],
+    batch_id: u64,
+    boost: &[String],
+) -> Option<usize> {
+    effective_pending_order(pending, boost)
+        .into_iter()
+        .find(|index| pending[*index].batch_id == batch_id)
+}
+
+/// Moves every matching entry to the front, keeping the relative order on both sides.
+#[cfg(not(target_arch = "wasm32"))]
+fn move_pending_to_front(
+    pending: &mut Vec<PendingDownloadEntry>,
+    matches: impl Fn(&PendingDownloadEntry) -> bool,
+) -> bool {
+    let (mut front, rest): (Vec<_>, Vec<_>) = pending.drain(..).partition(|entry| matches(entry));
+    let moved = !front.is_empty();
+    front.extend(rest);
+    *pending = front;
+    moved
+}
+
+/// Moves the entry with `key` into the position held by `target_key`, as a drag and drop
+/// would.
+#[cfg(not(target_arch = "wasm32"))]
+fn move_pending_entry(
+    pending: &mut Vec<PendingDownloadEntry>,
+    key: &str,
+    target_key: &str,
+) -> bool {
+    let Some(from) = pending.iter().position(|entry| entry.key() == key) else {
+        return false;
+    };
+    let Some(to) = pending.iter().position(|entry| entry.key() == target_key) else {
+        return false;
+    };
+    if from == to {
+        return false;
+    }
+    let entry = pending.remove(from);
+    pending.insert(to, entry);
+    true
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn queue_pending_downloads(batch_id: u64, songs: &[Song]) -> Vec<Song> {
+    let mut pending = PENDING_DOWNLOADS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    let mut queued = Vec::new();
+    for song in songs {
+        let key = format!("{}::{}", song.server_id, song.id);
+        // Another batch already has it lined up; that batch downloads it.
+        if pending.iter().any(|entry| entry.key() == key) {
+            continue;
+        }
+        pending.push(PendingDownloadEntry {
+            batch_id,
+            server_id: song.server_id.clone(),
+            song_id: song.id.clone(),
+            title: song.title.clone(),
+            artist: song.artist.clone(),
+            album: song.album.clone(),
+            album_id: song.album_id.clone(),
+            boosted: false,
+        });
+        queued.push(song.clone());
+    }
+    queued
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn take_next_pending_download(batch_id: u64) -> Option<PendingDownloadEntry> {
+    let boost = DOWNLOAD_QUEUE_BOOST
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone();
+    let mut pending = PENDING_DOWNLOADS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    let index = next_pending_index(&pending, batch_id, &boost)?;
+    Some(pending.remove(index))
+}
+
+/// Songs waiting in download batches, in the order they will be fetched.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn list_pending_downloads() -> Vec<PendingDownloadEntry> {
+    let boost = DOWNLOAD_QUEUE_BOOST
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone();
+    let pending = PENDING_DOWNLOADS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    effective_pending_order(&pending, &boost)
+        .into_iter()
+        .map(|index| {
+            let mut entry = pending[index].clone();
+            entry.boosted = boost.contains(&entry.key());
+            entry
+        })
+        .collect()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn list_pending_downloads() -> Vec<PendingDownloadEntry> {
+    Vec::new()
+}
+
+/// "Download next" for a single queued song.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn prioritize_pending_download(key: &str) -> bool {
+    let mut pending = PENDING_DOWNLOADS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    move_pending_to_front(&mut pending, |entry| entry.key() == key)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn prioritize_pending_download(_key: &str) -> bool {
+    false
+}
+
+/// "Download next" for every queued song of an album group.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn prioritize_pending_group(group_key: &str) -> bool {
+    let mut pending = PENDING_DOWNLOADS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    move_pending_to_front(&mut pending, |entry| {
+        entry.group_key().as_deref() == Some(group_key)
+    })
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn prioritize_pending_group(_group_key: &str) -> bool {
+    false
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn reorder_pending_download(key: &str, target_key: &str) -> bool {
+    let mut pending = PENDING_DOWNLOADS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    move_pending_entry(&mut pending, key, target_key)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn reorder_pending_download(_key: &str, _target_key: &str) -> bool {
+    false
+}
+
+/// Records the songs about to play so pending downloads fetch them first.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn set_download_queue_boost(upcoming: &[Song]) {
+    let keys: Vec<String> = upcoming
+        .iter()
+        .take(DOWNLOAD_QUEUE_BOOST_LIMIT)
+        .map(|song| format!("{}::{}", song.server_id, song.id))
+        .collect();
+    *DOWNLOAD_QUEUE_BOOST
+        .lock()
+        .unwrap_or_else(|err| err.into_inner()) = keys;
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn set_download_queue_boost(_upcoming: &[Song]) {}
+
 #[cfg(not(target_arch = "wasm32"))]
 fn collection_index_key(
     kind: &str,
@@ -1314,14 +1856,297 @@ pub fn remove_downloaded_song(_server_id: &str, _song_id: &str) -> usize {
     0
 }
 
+/// Pins or unpins a download against the age-based cleanup. Returns false when the song is
+/// not downloaded.
 #[cfg(not(target_arch = "wasm32"))]
-pub fn remove_downloaded_collection(kind: &str, server_id: &str, collection_id: &str) -> usize {
-    if kind.trim().is_empty() || server_id.trim().is_empty() || collection_id.trim().is_empty() {
-        return 0;
-    }
-
-    let mut index = load_collection_index();
-    let before = index.len();
+pub fn set_download_pinned(server_id: &str, song_id: &str, pinned: bool) -> bool {
+    let mut index = load_download_index();
+    let Some(entry) = index
+        .iter_mut()
+        .find(|entry| entry.server_id == server_id && entry.song_id == song_id)
+    else {
+        return false;
+    };
+    entry.pinned = pinned;
+    save_download_index(&index);
+    true
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn set_download_pinned(_server_id: &str, _song_id: &str, _pinned: bool) -> bool {
+    false
+}
+
+/// Moves downloads saved under a duplicate server entry to the server it is merged into.
+/// Audio files are renamed to the kept server's prefix; songs the kept server already has
+/// are dropped from the duplicate. Returns how many downloads were moved.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn reassign_server_downloads(from_id: &str, to_id: &str, to_name: &str) -> usize {
+    let Some(dir) = audio_cache_dir() else {
+        return 0;
+    };
+    let from_prefix = sanitize_file_component(from_id);
+    let to_prefix = sanitize_file_component(to_id);
+
+    let mut index = load_download_index();
+    let kept_song_ids = index
+        .iter()
+        .filter(|entry| entry.server_id == to_id)
+        .map(|entry| entry.song_id.clone())
+        .collect::<HashSet<_>>();
+    let mut moved = 0usize;
+    index.retain_mut(|entry| {
+        if entry.server_id != from_id {
+            return true;
+        }
+        if kept_song_ids.contains(&entry.song_id) {
+            let _ = remove_audio_cache_files_by_ids(from_id, &entry.song_id);
+            return false;
+        }
+        let song_component = sanitize_file_component(&entry.song_id);
+        for ext in CACHE_AUDIO_EXTENSIONS {
+            let source = dir.join(format!("{from_prefix}__{song_component}.{ext}"));
+            if source.exists() {
+                let _ = fs::rename(
+                    &source,
+                    dir.join(format!("{to_prefix}__{song_component}.{ext}")),
+                );
+            }
+        }
+        entry.server_id = to_id.to_string();
+        entry.server_name = Some(to_name.to_string());
+        moved += 1;
+        true
+    });
+    save_download_index(&index);
+
+    let mut collections = load_collection_index();
+    let kept_collections = collections
+        .iter()
+        .filter(|entry| entry.server_id == to_id)
+        .map(|entry| collection_index_key(&entry.kind, to_id, &entry.collection_id))
+        .collect::<HashSet<_>>();
+    collections.retain_mut(|entry| {
+        if entry.server_id != from_id {
+            return true;
+        }
+        if kept_collections.contains(&collection_index_key(
+            &entry.kind,
+            to_id,
+            &entry.collection_id,
+        )) {
+            return false;
+        }
+        entry.server_id = to_id.to_string();
+        true
+    });
+    save_collection_index(&collections);
+
+    let mut memberships = load_collection_membership_index();
+    let mut merged = HashMap::<(String, String, String), DownloadCollectionMembershipEntry>::new();
+    let mut order = Vec::new();
+    for mut entry in memberships.drain(..) {
+        if entry.server_id == from_id {
+            entry.server_id = to_id.to_string();
+        }
+        let key = collection_index_key(&entry.kind, &entry.server_id, &entry.collection_id);
+        match merged.get_mut(&key) {
+            Some(existing) => {
+                for song_id in entry.song_ids {
+                    if !existing.song_ids.contains(&song_id) {
+                        existing.song_ids.push(song_id);
+                    }
+                }
+                existing.updated_at_ms = existing.updated_at_ms.max(entry.updated_at_ms);
+            }
+            None => {
+                order.push(key.clone());
+                merged.insert(key, entry);
+            }
+        }
+    }
+    memberships = order
+        .into_iter()
+        .filter_map(|key| merged.remove(&key))
+        .collect();
+    save_collection_membership_index(&memberships);
+
+    sync_collection_memberships_with_index(&index);
+    sync_collection_download_counts_with_index(&index);
+    moved
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn reassign_server_downloads(_from_id: &str, _to_id: &str, _to_name: &str) -> usize {
+    0
+}
+
+/// Downloads moved to new song ids by [`remap_downloaded_song_ids`], kept undoable until the
+/// rest of the song id remap is saved.
+#[derive(Debug, Default)]
+pub struct PendingDownloadRemap {
+    moved: usize,
+    #[cfg(not(target_arch = "wasm32"))]
+    server_id: String,
+    /// Files renamed so far, from and to.
+    #[cfg(not(target_arch = "wasm32"))]
+    renamed: Vec<(PathBuf, PathBuf)>,
+    /// Old ids of songs already downloaded under their new id; their files go on commit.
+    #[cfg(not(target_arch = "wasm32"))]
+    superseded: Vec<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    index_before: Vec<DownloadIndexEntry>,
+    #[cfg(not(target_arch = "wasm32"))]
+    memberships_before: Vec<DownloadCollectionMembershipEntry>,
+    #[cfg(not(target_arch = "wasm32"))]
+    overrides_before: Vec<DownloadQualityOverride>,
+}
+
+impl PendingDownloadRemap {
+    /// Keeps the move and drops the copies it superseded. Returns how many downloads moved.
+    pub fn commit(self) -> usize {
+        #[cfg(not(target_arch = "wasm32"))]
+        for song_id in &self.superseded {
+            let _ = remove_audio_cache_files_by_ids(&self.server_id, song_id);
+        }
+        self.moved
+    }
+
+    /// Puts every file and index back the way they were before the move.
+    pub fn roll_back(self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            for (from, to) in self.renamed.iter().rev() {
+                let _ = fs::rename(to, from);
+            }
+            save_download_index(&self.index_before);
+            save_collection_membership_index(&self.memberships_before);
+            save_quality_overrides(&self.overrides_before);
+            sync_collection_memberships_with_index(&self.index_before);
+            sync_collection_download_counts_with_index(&self.index_before);
+        }
+    }
+}
+
+/// Moves downloads of `server_id` from old song ids to the new ids in `remap`, renaming the
+/// audio files and updating collection memberships and quality overrides. A song already
+/// downloaded under its new id keeps that copy. If a file can't be renamed, the files moved
+/// so far go back and nothing changes; otherwise the move stays undoable until committed.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn remap_downloaded_song_ids(
+    server_id: &str,
+    remap: &BTreeMap<String, String>,
+) -> Result<PendingDownloadRemap, String> {
+    let Some(dir) = audio_cache_dir() else {
+        return Ok(PendingDownloadRemap::default());
+    };
+    let server_prefix = sanitize_file_component(server_id);
+
+    let index_before = load_download_index();
+    let mut pending = PendingDownloadRemap {
+        server_id: server_id.to_string(),
+        memberships_before: load_collection_membership_index(),
+        overrides_before: load_quality_overrides(),
+        ..PendingDownloadRemap::default()
+    };
+    let downloaded_ids = index_before
+        .iter()
+        .filter(|entry| entry.server_id == server_id)
+        .map(|entry| entry.song_id.clone())
+        .collect::<HashSet<_>>();
+    let mut index = Vec::with_capacity(index_before.len());
+    for mut entry in index_before.iter().cloned() {
+        let new_id = (entry.server_id == server_id)
+            .then(|| remap.get(&entry.song_id))
+            .flatten();
+        let Some(new_id) = new_id else {
+            index.push(entry);
+            continue;
+        };
+        if downloaded_ids.contains(new_id) {
+            pending.superseded.push(entry.song_id.clone());
+            continue;
+        }
+        let old_component = sanitize_file_component(&entry.song_id);
+        let new_component = sanitize_file_component(new_id);
+        for ext in CACHE_AUDIO_EXTENSIONS {
+            let source = dir.join(format!("{server_prefix}__{old_component}.{ext}"));
+            if !source.exists() {
+                continue;
+            }
+            let target = dir.join(format!("{server_prefix}__{new_component}.{ext}"));
+            if let Err(error) = fs::rename(&source, &target) {
+                for (from, to) in pending.renamed.iter().rev() {
+                    let _ = fs::rename(to, from);
+                }
+                return Err(format!("Could not move {}: {error}", source.display()));
+            }
+            pending.renamed.push((source, target));
+        }
+        entry.song_id = new_id.clone();
+        pending.moved += 1;
+        index.push(entry);
+    }
+    pending.index_before = index_before;
+    save_download_index(&index);
+
+    let mut memberships = pending.memberships_before.clone();
+    for entry in memberships
+        .iter_mut()
+        .filter(|entry| entry.server_id == server_id)
+    {
+        let mut seen = HashSet::new();
+        entry.song_ids = entry
+            .song_ids
+            .drain(..)
+            .map(|song_id| remap.get(&song_id).cloned().unwrap_or(song_id))
+            .filter(|song_id| seen.insert(song_id.clone()))
+            .collect();
+    }
+    save_collection_membership_index(&memberships);
+
+    let mut overrides = pending.overrides_before.clone();
+    let overridden_ids = overrides
+        .iter()
+        .filter(|entry| entry.server_id == server_id)
+        .map(|entry| entry.song_id.clone())
+        .collect::<HashSet<_>>();
+    overrides.retain_mut(|entry| {
+        if entry.server_id != server_id {
+            return true;
+        }
+        let Some(new_id) = remap.get(&entry.song_id) else {
+            return true;
+        };
+        if overridden_ids.contains(new_id) {
+            return false;
+        }
+        entry.song_id = new_id.clone();
+        true
+    });
+    save_quality_overrides(&overrides);
+
+    sync_collection_memberships_with_index(&index);
+    sync_collection_download_counts_with_index(&index);
+    Ok(pending)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn remap_downloaded_song_ids(
+    _server_id: &str,
+    _remap: &BTreeMap<String, String>,
+) -> Result<PendingDownloadRemap, String> {
+    Ok(PendingDownloadRemap::default())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn remove_downloaded_collection(kind: &str, server_id: &str, collection_id: &str) -> usize {
+    if kind.trim().is_empty() || server_id.trim().is_empty() || collection_id.trim().is_empty() {
+        return 0;
+    }
+
+    let mut index = load_collection_index();
+    let before = index.len();
     index.retain(|entry| {
         !(entry.kind == kind
             && entry.server_id == server_id.trim()
@@ -1541,6 +2366,106 @@ pub fn prune_download_cache(_max_count: u32, _max_size_mb: u32) -> usize {
     0
 }
 
+/// When the age-based cleanup removes downloads: once the store passes `trigger_percent`
+/// of `limit_bytes`, downloads idle for `idle_days` go until it is back under.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct StaleCleanupPolicy {
+    /// 0 turns the cleanup off.
+    idle_days: u32,
+    trigger_percent: u8,
+    limit_bytes: u64,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl StaleCleanupPolicy {
+    fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            idle_days: settings.download_cleanup_idle_days,
+            trigger_percent: settings.download_cleanup_trigger_percent,
+            limit_bytes: (settings.download_limit_mb.clamp(256, 131_072) as u64) * 1024 * 1024,
+        }
+    }
+}
+
+/// Downloads the age-based cleanup removes, largest first. A download is idle when neither
+/// its last play in `last_played_ms` nor its own download time is within the policy's idle
+/// days; pinned downloads and `protected` ones (the favorites tier) are never picked.
+#[cfg(not(target_arch = "wasm32"))]
+fn select_stale_downloads(
+    entries: &[DownloadIndexEntry],
+    last_played_ms: &HashMap<(String, String), u64>,
+    protected: &HashSet<(String, String)>,
+    policy: StaleCleanupPolicy,
+    now_ms: u64,
+) -> Vec<(String, String)> {
+    if policy.idle_days == 0 {
+        return Vec::new();
+    }
+    let mut total_bytes: u64 = entries.iter().map(|entry| entry.size_bytes).sum();
+    let trigger_bytes = policy.limit_bytes / 100 * u64::from(policy.trigger_percent.min(100));
+    if total_bytes <= trigger_bytes {
+        return Vec::new();
+    }
+
+    let idle_cutoff_ms = now_ms.saturating_sub(u64::from(policy.idle_days) * 86_400_000);
+    let mut stale = entries
+        .iter()
+        .filter_map(|entry| {
+            let key = (entry.server_id.clone(), entry.song_id.clone());
+            if entry.pinned || protected.contains(&key) {
+                return None;
+            }
+            let last_active_ms = last_played_ms
+                .get(&key)
+                .copied()
+                .unwrap_or(0)
+                .max(entry.updated_at_ms);
+            (last_active_ms < idle_cutoff_ms).then_some((key, entry.size_bytes, last_active_ms))
+        })
+        .collect::<Vec<_>>();
+    stale.sort_by(|left, right| right.1.cmp(&left.1).then(left.2.cmp(&right.2)));
+
+    let mut selected = Vec::new();
+    for (key, size_bytes, _) in stale {
+        if total_bytes <= trigger_bytes {
+            break;
+        }
+        total_bytes = total_bytes.saturating_sub(size_bytes);
+        selected.push(key);
+    }
+    selected
+}
+
+/// Runs the age-based cleanup and returns how many downloads it removed and their size.
+#[cfg(not(target_arch = "wasm32"))]
+async fn cleanup_stale_downloads(
+    settings: &AppSettings,
+    protected: &HashSet<(String, String)>,
+) -> (usize, u64) {
+    let policy = StaleCleanupPolicy::from_settings(settings);
+    if policy.idle_days == 0 {
+        return (0, 0);
+    }
+    let entries = load_download_index();
+    let last_played_ms = last_played_by_song().await;
+    let keys = select_stale_downloads(
+        &entries,
+        &last_played_ms,
+        protected,
+        policy,
+        now_timestamp_millis(),
+    )
+    .into_iter()
+    .collect::<HashSet<_>>();
+    let freed_bytes = entries
+        .iter()
+        .filter(|entry| keys.contains(&(entry.server_id.clone(), entry.song_id.clone())))
+        .map(|entry| entry.size_bytes)
+        .sum();
+    (remove_download_index_keys(&keys), freed_bytes)
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 fn auto_download_favorite_limit(tier: u8) -> usize {
     match tier {
@@ -1561,6 +2486,19 @@ fn push_unique_song(target: &mut Vec<Song>, seen: &mut HashSet<String>, song: So
     }
 }
 
+/// Auto-download rating filter. A `min_rating` of 0 keeps everything; otherwise songs
+/// rated below it are skipped and unrated songs follow `include_unrated`.
+#[cfg(not(target_arch = "wasm32"))]
+fn meets_auto_download_rating(song: &Song, min_rating: u8, include_unrated: bool) -> bool {
+    if min_rating == 0 {
+        return true;
+    }
+    match song.user_rating.filter(|rating| *rating > 0) {
+        Some(rating) => rating >= u32::from(min_rating),
+        None => include_unrated,
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 fn is_id3_cover_art_id(cover_art_id: &str) -> bool {
     cover_art_id.trim().to_ascii_lowercase().starts_with("mf-")
@@ -1665,7 +2603,12 @@ pub async fn run_auto_download_pass(
         return Ok(report);
     }
 
-    let active_servers: Vec<ServerConfig> = servers.iter().filter(|s| s.active).cloned().collect();
+    // Servers waiting on re-authentication are skipped until the user updates the password.
+    let active_servers: Vec<ServerConfig> = servers
+        .iter()
+        .filter(|s| s.active && !server_needs_reauth(&s.id))
+        .cloned()
+        .collect();
     if active_servers.is_empty() {
         report.indexed = list_downloaded_entries().len();
         return Ok(report);
@@ -1673,6 +2616,10 @@ pub async fn run_auto_download_pass(
 
     let mut candidates = Vec::<Song>::new();
     let mut seen = HashSet::<String>::new();
+    // Favorites-tier songs, kept out of the age-based cleanup. Servers whose favorites could
+    // not be loaded are left out of the cleanup entirely.
+    let mut favorite_keys = HashSet::<(String, String)>::new();
+    let mut unchecked_server_ids = HashSet::<String>::new();
     let favorite_limit = auto_download_favorite_limit(settings.auto_download_tier.clamp(1, 3));
     let tracked_playlists_by_server = list_downloaded_collections().into_iter().fold(
         HashMap::<String, HashSet<String>>::new(),
@@ -1698,6 +2645,7 @@ pub async fn run_auto_download_pass(
             let _ = starred_artists;
             starred_songs.sort_by(|left, right| right.played.cmp(&left.played));
             for song in starred_songs.into_iter().take(favorite_limit) {
+                favorite_keys.insert((song.server_id.clone(), song.id.clone()));
                 push_unique_song(&mut candidates, &mut seen, song);
             }
 
@@ -1711,6 +2659,8 @@ pub async fn run_auto_download_pass(
                     }
                 }
             }
+        } else {
+            unchecked_server_ids.insert(server.id.clone());
         }
 
         if let Ok(mut playlists) = client.get_playlists().await {
@@ -1778,6 +2728,14 @@ pub async fn run_auto_download_pass(
         }
     }
 
+    candidates.retain(|song| {
+        meets_auto_download_rating(
+            song,
+            settings.auto_download_min_rating.min(5),
+            settings.auto_download_include_unrated,
+        )
+    });
+
     let max_count = settings.download_limit_count.clamp(25, 20_000) as usize;
     if candidates.len() > max_count {
         candidates.truncate(max_count);
@@ -1790,6 +2748,7 @@ pub async fn run_auto_download_pass(
             continue;
         }
 
+        wait_while_streaming().await;
         match prefetch_song_audio_with_origin(&song, servers, settings, DownloadOrigin::Auto).await
         {
             Ok(()) => report.downloaded += 1,
@@ -1799,6 +2758,20 @@ pub async fn run_auto_download_pass(
         tokio::time::sleep(std::time::Duration::from_millis(80)).await;
     }
 
+    let mut protected = favorite_keys;
+    protected.extend(
+        load_download_index()
+            .into_iter()
+            .filter(|entry| {
+                unchecked_server_ids.contains(&entry.server_id)
+                    || !active_servers
+                        .iter()
+                        .any(|server| server.id == entry.server_id)
+            })
+            .map(|entry| (entry.server_id, entry.song_id)),
+    );
+    (report.cleaned_up, report.cleaned_up_bytes) =
+        cleanup_stale_downloads(settings, &protected).await;
     report.purged = prune_download_cache(settings.download_limit_count, settings.download_limit_mb);
     report.indexed = list_downloaded_entries().len();
 
@@ -1846,11 +2819,27 @@ pub async fn download_songs_batch(
     let mut effective_settings = settings.clone();
     effective_settings.downloads_enabled = true;
 
-    for song in ordered {
-        if is_song_downloaded(&song) {
-            report.skipped += 1;
+    let (already_downloaded, to_download): (Vec<Song>, Vec<Song>) =
+        ordered.into_iter().partition(is_song_downloaded);
+    report.skipped += already_downloaded.len();
+
+    let batch_id = NEXT_DOWNLOAD_BATCH_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let queued = queue_pending_downloads(batch_id, &to_download);
+    report.skipped += to_download.len() - queued.len();
+    let mut songs_by_key: HashMap<String, Song> = queued
+        .into_iter()
+        .map(|song| (format!("{}::{}", song.server_id, song.id), song))
+        .collect();
+
+    // Pull one song at a time so priority changes apply to the next pick.
+    loop {
+        wait_while_streaming().await;
+        let Some(entry) = take_next_pending_download(batch_id) else {
+            break;
+        };
+        let Some(song) = songs_by_key.remove(&entry.key()) else {
             continue;
-        }
+        };
 
         match prefetch_song_audio(&song, servers, &effective_settings).await {
             Ok(()) => report.downloaded += 1,
@@ -1877,6 +2866,95 @@ pub async fn download_songs_batch(
     DownloadBatchReport::default()
 }
 
+/// Downloads starred songs from every active server, most played first, capped per server by
+/// the auto-download tier's favorite limit. Stops queueing once the download count or size
+/// limit is reached. `on_progress` receives `(processed, total)` after each song.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn download_favorite_songs(
+    servers: &[ServerConfig],
+    settings: &AppSettings,
+    mut on_progress: impl FnMut(usize, usize),
+) -> Result<DownloadBatchReport, String> {
+    let mut report = DownloadBatchReport::default();
+    let active_servers: Vec<ServerConfig> = servers
+        .iter()
+        .filter(|s| s.active && !server_needs_reauth(&s.id))
+        .cloned()
+        .collect();
+    if active_servers.is_empty() {
+        return Err("No active servers available.".to_string());
+    }
+
+    let favorite_limit = auto_download_favorite_limit(settings.auto_download_tier.clamp(1, 3));
+    let mut candidates = Vec::<Song>::new();
+    let mut seen = HashSet::<String>::new();
+    let mut last_error = None;
+    let mut loaded_any = false;
+    for server in active_servers {
+        let client = NavidromeClient::new(server);
+        match client.get_starred().await {
+            Ok((_, _, mut starred_songs)) => {
+                loaded_any = true;
+                starred_songs.sort_by(|left, right| right.played.cmp(&left.played));
+                for song in starred_songs.into_iter().take(favorite_limit) {
+                    push_unique_song(&mut candidates, &mut seen, song);
+                }
+            }
+            Err(error) => last_error = Some(error.to_string()),
+        }
+    }
+    if !loaded_any {
+        return Err(last_error.unwrap_or_else(|| "Could not load favorites.".to_string()));
+    }
+
+    let mut effective_settings = settings.clone();
+    effective_settings.downloads_enabled = true;
+    let max_count = settings.download_limit_count.clamp(25, 20_000) as usize;
+    let max_bytes = (settings.download_limit_mb.clamp(256, 131_072) as u64) * 1024 * 1024;
+
+    let total = candidates.len();
+    report.attempted = total;
+    for (position, song) in candidates.into_iter().enumerate() {
+        if is_song_downloaded(&song) {
+            report.skipped += 1;
+            on_progress(position + 1, total);
+            continue;
+        }
+
+        wait_while_streaming().await;
+        let stats = download_stats();
+        if stats.song_count >= max_count || stats.total_size_bytes >= max_bytes {
+            report.limited = total - position;
+            on_progress(total, total);
+            break;
+        }
+
+        match prefetch_song_audio(&song, servers, &effective_settings).await {
+            Ok(()) => report.downloaded += 1,
+            Err(_) => report.failed += 1,
+        }
+        on_progress(position + 1, total);
+
+        tokio::time::sleep(std::time::Duration::from_millis(70)).await;
+    }
+
+    report.purged = prune_download_cache(
+        effective_settings.download_limit_count,
+        effective_settings.download_limit_mb,
+    );
+    report.indexed = list_downloaded_entries().len();
+    Ok(report)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn download_favorite_songs(
+    _servers: &[ServerConfig],
+    _settings: &AppSettings,
+    _on_progress: impl FnMut(usize, usize),
+) -> Result<DownloadBatchReport, String> {
+    Err("Downloads are only available in native builds.".to_string())
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub async fn refresh_downloaded_cache(
     servers: &[ServerConfig],
@@ -1909,6 +2987,7 @@ pub async fn refresh_downloaded_cache(
             title: entry.title.clone(),
             album: entry.album.clone(),
             album_id: entry.album_id.clone(),
+            artist_id: entry.artist_id.clone(),
             artist: entry.artist.clone(),
             cover_art: entry
                 .cover_art_id
@@ -1946,6 +3025,67 @@ pub async fn refresh_downloaded_cache(
     Ok(report)
 }
 
+/// Checks every downloaded file against the index. Missing files lose their entries, and
+/// empty or resized files are deleted with theirs. Songs downloading right now are left
+/// alone. `on_progress` gets the number checked and the total; while `paused` holds, the
+/// pass waits.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn verify_downloads(
+    mut on_progress: impl FnMut(usize, usize),
+    paused: impl Fn() -> bool,
+) -> Result<DownloadVerifyReport, String> {
+    let entries = load_download_index();
+    let total = entries.len();
+    let mut report = DownloadVerifyReport::default();
+    let mut dropped = HashSet::<(String, String)>::new();
+
+    for entry in entries {
+        while paused() {
+            tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        }
+        if !is_download_active(&entry.server_id, &entry.song_id) {
+            let file_len = audio_cache_file_path_by_ids(&entry.server_id, &entry.song_id)
+                .and_then(|path| fs::metadata(path).ok())
+                .filter(|meta| meta.is_file())
+                .map(|meta| meta.len());
+            match check_download_file(&entry, file_len) {
+                DownloadFileCheck::Intact => {}
+                DownloadFileCheck::Missing => {
+                    report.missing += 1;
+                    dropped.insert((entry.server_id, entry.song_id));
+                }
+                DownloadFileCheck::Damaged => {
+                    report.damaged += 1;
+                    remove_audio_cache_files_by_ids(&entry.server_id, &entry.song_id);
+                    dropped.insert((entry.server_id, entry.song_id));
+                }
+            }
+        }
+        report.checked += 1;
+        on_progress(report.checked, total);
+        tokio::task::yield_now().await;
+    }
+
+    if !dropped.is_empty() {
+        // Downloads may have finished during the pass, so drop from the index as it is now.
+        let mut index = load_download_index();
+        index.retain(|entry| !dropped.contains(&(entry.server_id.clone(), entry.song_id.clone())));
+        save_download_index(&index);
+        sync_collection_memberships_with_index(&index);
+        sync_collection_download_counts_with_index(&index);
+    }
+
+    Ok(report)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn verify_downloads(
+    _on_progress: impl FnMut(usize, usize),
+    _paused: impl Fn() -> bool,
+) -> Result<DownloadVerifyReport, String> {
+    Err("Downloads are only available in native builds.".to_string())
+}
+
 #[cfg(target_arch = "wasm32")]
 pub async fn refresh_downloaded_cache(
     _servers: &[ServerConfig],
@@ -1971,9 +3111,16 @@ pub async fn prefetch_song_audio_with_origin(
     let Some(path) = audio_cache_file_path(song) else {
         return Err("Audio cache path is unavailable.".to_string());
     };
-    if path.exists() {
+    let quality = download_quality_for(&song.server_id, &song.id);
+    // A file downloaded at another quality is replaced below rather than reused.
+    let stale_quality = path.exists()
+        && load_download_index()
+            .iter()
+            .find(|entry| entry.server_id == song.server_id && entry.song_id == song.id)
+            .is_some_and(|entry| entry.quality != quality);
+    if path.exists() && !stale_quality {
         if let Ok(meta) = fs::metadata(&path) {
-            upsert_download_index(song, meta.len(), origin);
+            upsert_download_index(song, meta.len(), origin, None);
             if !settings.auto_downloads_enabled {
                 let _ = prune_temporary_queue_prefetch_downloads(
                     TEMP_QUEUE_PREFETCH_LIMIT_WHEN_AUTO_OFF,
@@ -1991,9 +3138,25 @@ pub async fn prefetch_song_audio_with_origin(
         return Ok(());
     };
 
+    // Queue fetch-ahead and the download manager can ask for the same song at once; let the
+    // first request download it and record this request's origin on the finished file.
+    if is_download_active(&song.server_id, &song.id) {
+        wait_for_active_download(&song.server_id, &song.id).await;
+        if let Some(meta) = audio_cache_file_path(song)
+            .filter(|path| path.exists())
+            .and_then(|path| fs::metadata(path).ok())
+        {
+            upsert_download_index(song, meta.len(), origin, None);
+        }
+        return Ok(());
+    }
+
     let client = NavidromeClient::new(server);
     let _active_download_guard = ActiveDownloadGuard::new(song);
-    let stream_url = client.get_stream_url(&song.id);
+    let stream_url = match quality.max_bitrate_kbps() {
+        Some(kbps) => client.get_transcoded_stream_url(&song.id, "mp3", kbps),
+        None => client.get_stream_url(&song.id),
+    };
     let response = AUDIO_HTTP_CLIENT
         .get(stream_url)
         .send()
@@ -2017,11 +3180,19 @@ pub async fn prefetch_song_audio_with_origin(
         payload.truncate(max_per_song_bytes as usize);
     }
 
-    tokio::fs::write(&path, &payload)
+    let target_path = match quality.max_bitrate_kbps() {
+        Some(_) => path.with_extension("mp3"),
+        None if stale_quality => path.with_extension(preferred_audio_file_extension(song)),
+        None => path.clone(),
+    };
+    if stale_quality && target_path != path {
+        let _ = fs::remove_file(&path);
+    }
+    tokio::fs::write(&target_path, &payload)
         .await
         .map_err(|err| err.to_string())?;
 
-    upsert_download_index(song, payload.len() as u64, origin);
+    upsert_download_index(song, payload.len() as u64, origin, Some(quality));
 
     // Warm cover art alongside downloads so album/song artwork is available offline.
     let mut seen_cover_requests = HashSet::<String>::new();
@@ -2076,3 +3247,232 @@ pub async fn prefetch_song_audio(
 ) -> Result<(), String> {
     Ok(())
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    fn pending(batch_id: u64, song_id: &str, album_id: &str) -> PendingDownloadEntry {
+        PendingDownloadEntry {
+            batch_id,
+            server_id: "s".to_string(),
+            song_id: song_id.to_string(),
+            title: song_id.to_string(),
+            album_id: Some(album_id.to_string()),
+            ..PendingDownloadEntry::default()
+        }
+    }
+
+    const DAY_MS: u64 = 86_400_000;
+    const MB: u64 = 1024 * 1024;
+
+    fn downloaded(song_id: &str, size_mb: u64, downloaded_day: u64) -> DownloadIndexEntry {
+        DownloadIndexEntry {
+            server_id: "s".to_string(),
+            song_id: song_id.to_string(),
+            size_bytes: size_mb * MB,
+            updated_at_ms: downloaded_day * DAY_MS,
+            ..DownloadIndexEntry::default()
+        }
+    }
+
+    #[test]
+    fn verification_flags_missing_empty_and_resized_files() {
+        let entry = downloaded("a", 3, 1);
+        let check = |len| check_download_file(&entry, len);
+        assert_eq!(check(Some(3 * MB)), DownloadFileCheck::Intact);
+        assert_eq!(check(None), DownloadFileCheck::Missing);
+        assert_eq!(check(Some(0)), DownloadFileCheck::Damaged);
+        assert_eq!(check(Some(MB)), DownloadFileCheck::Damaged);
+
+        let without_size = DownloadIndexEntry {
+            size_bytes: 0,
+            ..entry.clone()
+        };
+        assert_eq!(
+            check_download_file(&without_size, Some(MB)),
+            DownloadFileCheck::Intact
+        );
+        assert_eq!(
+            check_download_file(&without_size, Some(0)),
+            DownloadFileCheck::Damaged
+        );
+    }
+
+    #[test]
+    fn refreshing_an_unchanged_download_keeps_its_age() {
+        let song = Song {
+            id: "a".to_string(),
+            server_id: "s".to_string(),
+            title: "A".to_string(),
+            ..Song::default()
+        };
+        let mut entry = downloaded("a", 3, 1);
+        assert!(refresh_download_entry(
+            &mut entry,
+            &song,
+            3 * MB,
+            DownloadOrigin::Auto,
+            None,
+            5 * DAY_MS
+        ));
+        assert_eq!(entry.updated_at_ms, 5 * DAY_MS);
+
+        assert!(!refresh_download_entry(
+            &mut entry,
+            &song,
+            3 * MB,
+            DownloadOrigin::Auto,
+            None,
+            9 * DAY_MS
+        ));
+        assert_eq!(entry.updated_at_ms, 5 * DAY_MS);
+
+        assert!(refresh_download_entry(
+            &mut entry,
+            &song,
+            4 * MB,
+            DownloadOrigin::Auto,
+            None,
+            9 * DAY_MS
+        ));
+        assert_eq!(entry.updated_at_ms, 9 * DAY_MS);
+    }
+
+    fn key(song_id: &str) -> (String, String) {
+        ("s".to_string(), song_id.to_string())
+    }
+
+    fn cleanup_policy(idle_days: u32) -> StaleCleanupPolicy {
+        StaleCleanupPolicy {
+            idle_days,
+            trigger_percent: 50,
+            limit_bytes: 100 * MB,
+        }
+    }
+
+    #[test]
+    fn stale_cleanup_removes_the_largest_idle_downloads_until_under_the_trigger() {
+        let entries = vec![
+            downloaded("small", 10, 0),
+            downloaded("large", 30, 0),
+            downloaded("played", 30, 0),
+            downloaded("fresh", 20, 95),
+        ];
+        // "played" was downloaded long ago but played recently.
+        let last_played = HashMap::from([(key("played"), 90 * DAY_MS)]);
+        let now_ms = 100 * DAY_MS;
+
+        // 90 MB stored against a 50 MB trigger: "large" and "small" are idle, largest first.
+        let selected = select_stale_downloads(
+            &entries,
+            &last_played,
+            &HashSet::new(),
+            cleanup_policy(30),
+            now_ms,
+        );
+        assert_eq!(selected, [key("large"), key("small")]);
+
+        let mut pinned = entries.clone();
+        pinned[1].pinned = true;
+        let protected = HashSet::from([key("small")]);
+        let selected = select_stale_downloads(
+            &pinned,
+            &last_played,
+            &protected,
+            cleanup_policy(30),
+            now_ms,
+        );
+        assert!(selected.is_empty());
+    }
+
+    #[test]
+    fn stale_cleanup_waits_for_the_trigger_and_can_be_turned_off() {
+        let entries = vec![downloaded("a", 30, 0), downloaded("b", 30, 0)];
+        let now_ms = 100 * DAY_MS;
+        let no_plays = HashMap::new();
+        let nothing_protected = HashSet::new();
+
+        let selected = select_stale_downloads(
+            &entries,
+            &no_plays,
+            &nothing_protected,
+            cleanup_policy(0),
+            now_ms,
+        );
+        assert!(selected.is_empty());
+
+        // One removal is enough to get back under the trigger.
+        let selected = select_stale_downloads(
+            &entries,
+            &no_plays,
+            &nothing_protected,
+            cleanup_policy(30),
+            now_ms,
+        );
+        assert_eq!(selected.len(), 1);
+
+        let under_trigger = &entries[..1];
+        let selected = select_stale_downloads(
+            under_trigger,
+            &no_plays,
+            &nothing_protected,
+            cleanup_policy(30),
+            now_ms,
+        );
+        assert!(selected.is_empty());
+    }
+
+    fn song_ids(entries: &[PendingDownloadEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.song_id.as_str()).collect()
+    }
+
+    #[test]
+    fn download_next_moves_a_song_or_album_to_the_front() {
+        let mut list = vec![
+            pending(1, "a", "x"),
+            pending(1, "b", "y"),
+            pending(1, "c", "x"),
+            pending(1, "d", "y"),
+        ];
+        assert!(move_pending_to_front(&mut list, |entry| entry.song_id == "c"));
+        assert_eq!(song_ids(&list), ["c", "a", "b", "d"]);
+
+        assert!(move_pending_to_front(&mut list, |entry| {
+            entry.group_key().as_deref() == Some("s::album:y")
+        }));
+        assert_eq!(song_ids(&list), ["b", "d", "c", "a"]);
+        assert!(!move_pending_to_front(&mut list, |entry| entry.song_id == "z"));
+    }
+
+    #[test]
+    fn drag_reorder_takes_the_target_position() {
+        let mut list = vec![
+            pending(1, "a", "x"),
+            pending(1, "b", "x"),
+            pending(1, "c", "x"),
+        ];
+        assert!(move_pending_entry(&mut list, "s::c", "s::a"));
+        assert_eq!(song_ids(&list), ["c", "a", "b"]);
+        assert!(move_pending_entry(&mut list, "s::c", "s::b"));
+        assert_eq!(song_ids(&list), ["a", "b", "c"]);
+        assert!(!move_pending_entry(&mut list, "s::a", "s::missing"));
+    }
+
+    #[test]
+    fn upcoming_queue_songs_are_picked_first_within_a_batch() {
+        let list = vec![
+            pending(1, "a", "x"),
+            pending(2, "b", "x"),
+            pending(1, "c", "x"),
+            pending(1, "d", "x"),
+        ];
+        assert_eq!(next_pending_index(&list, 1, &[]), Some(0));
+
+        let boost = vec!["s::d".to_string(), "s::b".to_string(), "s::c".to_string()];
+        assert_eq!(effective_pending_order(&list, &boost), [3, 1, 2, 0]);
+        assert_eq!(next_pending_index(&list, 1, &boost), Some(3));
+        assert_eq!(next_pending_index(&list, 2, &boost), Some(1));
+        assert_eq!(next_pending_index(&list, 3, &boost), None);
+    }
+}
@@ -0,0 +1,75 @@
+//! The window title for the loaded track. `taskbar_status` hands it to the desktop window;
+//! it's kept apart from those OS calls so its tests run without the desktop feature.
+use crate::api::Song;
+
+const APP_TITLE: &str = "RustySound";
+
+/// Longest "Artist — Title" part of the window title, in characters.
+const MAX_TRACK_TITLE_CHARS: usize = 80;
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    format!("{}…", kept.trim_end())
+}
+
+/// "Artist — Title · RustySound" for a loaded track, "RustySound" otherwise.
+pub fn window_title(song: Option<&Song>) -> String {
+    let Some(song) = song else {
+        return APP_TITLE.to_string();
+    };
+    let title = song.title.trim();
+    let track = match song
+        .artist
+        .as_deref()
+        .map(str::trim)
+        .filter(|artist| !artist.is_empty())
+    {
+        Some(artist) if !title.is_empty() => format!("{artist} — {title}"),
+        Some(artist) => artist.to_string(),
+        None if !title.is_empty() => title.to_string(),
+        None => return APP_TITLE.to_string(),
+    };
+    format!(
+        "{} · {APP_TITLE}",
+        truncate_chars(&track, MAX_TRACK_TITLE_CHARS)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(artist: Option<&str>, title: &str) -> Song {
+        Song {
+            artist: artist.map(str::to_string),
+            title: title.to_string(),
+            ..Song::default()
+        }
+    }
+
+    #[test]
+    fn titles_name_the_track_and_fall_back_to_the_app() {
+        assert_eq!(window_title(None), "RustySound");
+        assert_eq!(
+            window_title(Some(&song(Some("Daft Punk"), "Digital Love"))),
+            "Daft Punk — Digital Love · RustySound"
+        );
+        assert_eq!(
+            window_title(Some(&song(Some("  "), "Intro"))),
+            "Intro · RustySound"
+        );
+        assert_eq!(window_title(Some(&song(None, " "))), "RustySound");
+    }
+
+    #[test]
+    fn long_titles_are_truncated() {
+        let long = "Ä".repeat(200);
+        let title = window_title(Some(&song(Some("Artist"), &long)));
+        let track = title.strip_suffix(" · RustySound").unwrap();
+        assert_eq!(track.chars().count(), MAX_TRACK_TITLE_CHARS);
+        assert!(track.ends_with('…'));
+    }
+}
@@ -0,0 +1,210 @@
+//! Quiet hours: a weekly schedule during which the output volume is capped.
+//!
+//! The schedule is read against local wall-clock time, so a window keeps its "21:00 to
+//! 07:00" meaning across daylight-saving changes: on the night the clocks move it is simply
+//! an hour shorter or longer. `quiet_cap_at` is pure so it can be tested against fixed
+//! clocks; `quiet_cap_now` feeds it the device's time.
+use crate::db::QuietHours;
+use chrono::{DateTime, Datelike, Local, TimeZone, Timelike};
+
+const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// Whether the window that starts on day `weekday` (0 = Monday) covers `minute` of that day,
+/// or of the following day for windows that run past midnight.
+fn covers(schedule: &QuietHours, weekday: usize, minute: u16) -> bool {
+    let start = schedule.start_minute.min(MINUTES_PER_DAY - 1);
+    let end = schedule.end_minute.min(MINUTES_PER_DAY - 1);
+    let today = schedule.days[weekday];
+    let yesterday = schedule.days[(weekday + 6) % 7];
+    match start.cmp(&end) {
+        std::cmp::Ordering::Equal => today,
+        std::cmp::Ordering::Less => today && minute >= start && minute < end,
+        std::cmp::Ordering::Greater => (today && minute >= start) || (yesterday && minute < end),
+    }
+}
+
+/// The volume cap (0..=1) in force at `now`, if quiet hours are on.
+pub fn quiet_cap_at<Tz: TimeZone>(schedule: &QuietHours, now: &DateTime<Tz>) -> Option<f64> {
+    if !schedule.enabled {
+        return None;
+    }
+    let weekday = now.weekday().num_days_from_monday() as usize;
+    let minute = (now.hour() * 60 + now.minute()) as u16;
+    covers(schedule, weekday, minute).then(|| f64::from(schedule.cap_percent.min(100)) / 100.0)
+}
+
+pub fn quiet_cap_now(schedule: &QuietHours) -> Option<f64> {
+    quiet_cap_at(schedule, &Local::now())
+}
+
+/// What actually reaches the output: the user's volume, held under the quiet-hours cap.
+pub fn effective_output_volume(volume: f64, cap: Option<f64>) -> f64 {
+    let volume = volume.clamp(0.0, 1.0);
+    match cap {
+        Some(cap) => volume.min(cap.clamp(0.0, 1.0)),
+        None => volume,
+    }
+}
+
+/// Intermediate volumes for moving from `from` to `to` in `steps` even steps, ending on `to`.
+pub fn volume_ramp(from: f64, to: f64, steps: u32) -> Vec<f64> {
+    let steps = steps.max(1);
+    (1..=steps)
+        .map(|step| {
+            if step == steps {
+                to
+            } else {
+                from + (to - from) * f64::from(step) / f64::from(steps)
+            }
+        })
+        .collect()
+}
+
+/// "21:00"-style label for a minute of the day.
+pub fn minute_label(minute: u16) -> String {
+    let minute = minute.min(MINUTES_PER_DAY - 1);
+    format!("{:02}:{:02}", minute / 60, minute % 60)
+}
+
+/// Parses the "HH:MM" value of a time input.
+pub fn parse_minute(value: &str) -> Option<u16> {
+    let (hours, minutes) = value.trim().split_once(':')?;
+    let hours: u16 = hours.parse().ok()?;
+    let minutes: u16 = minutes.get(..2).unwrap_or(minutes).parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, NaiveDate, Utc};
+
+    fn night(days: [bool; 7]) -> QuietHours {
+        QuietHours {
+            enabled: true,
+            start_minute: 21 * 60,
+            end_minute: 7 * 60,
+            days,
+            cap_percent: 25,
+        }
+    }
+
+    /// 2024-06-03 is a Monday.
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        NaiveDate::from_ymd_opt(2024, 6, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    #[test]
+    fn overnight_window_runs_past_midnight() {
+        let schedule = night([true; 7]);
+        assert_eq!(quiet_cap_at(&schedule, &at(3, 20, 59)), None);
+        assert_eq!(quiet_cap_at(&schedule, &at(3, 21, 0)), Some(0.25));
+        assert_eq!(quiet_cap_at(&schedule, &at(4, 0, 30)), Some(0.25));
+        assert_eq!(quiet_cap_at(&schedule, &at(4, 6, 59)), Some(0.25));
+        assert_eq!(quiet_cap_at(&schedule, &at(4, 7, 0)), None);
+        assert_eq!(quiet_cap_at(&schedule, &at(4, 12, 0)), None);
+    }
+
+    #[test]
+    fn overnight_window_belongs_to_the_day_it_starts_on() {
+        // Friday night only.
+        let mut days = [false; 7];
+        days[4] = true;
+        let schedule = night(days);
+        assert_eq!(
+            quiet_cap_at(&schedule, &at(6, 23, 0)),
+            None,
+            "Thursday night"
+        );
+        assert_eq!(
+            quiet_cap_at(&schedule, &at(7, 6, 0)),
+            None,
+            "Friday morning"
+        );
+        assert_eq!(quiet_cap_at(&schedule, &at(7, 22, 0)), Some(0.25));
+        assert_eq!(
+            quiet_cap_at(&schedule, &at(8, 6, 0)),
+            Some(0.25),
+            "Saturday morning"
+        );
+        assert_eq!(quiet_cap_at(&schedule, &at(8, 22, 0)), None);
+    }
+
+    #[test]
+    fn same_day_and_whole_day_windows() {
+        let afternoon = QuietHours {
+            start_minute: 13 * 60,
+            end_minute: 15 * 60,
+            ..night([true; 7])
+        };
+        assert_eq!(quiet_cap_at(&afternoon, &at(3, 12, 59)), None);
+        assert_eq!(quiet_cap_at(&afternoon, &at(3, 14, 0)), Some(0.25));
+        assert_eq!(quiet_cap_at(&afternoon, &at(3, 15, 0)), None);
+
+        let mut sundays = [false; 7];
+        sundays[6] = true;
+        let all_day = QuietHours {
+            start_minute: 0,
+            end_minute: 0,
+            ..night(sundays)
+        };
+        assert_eq!(quiet_cap_at(&all_day, &at(9, 23, 59)), Some(0.25));
+        assert_eq!(quiet_cap_at(&all_day, &at(10, 0, 0)), None);
+
+        let disabled = QuietHours {
+            enabled: false,
+            ..all_day
+        };
+        assert_eq!(quiet_cap_at(&disabled, &at(9, 12, 0)), None);
+    }
+
+    #[test]
+    fn daylight_saving_changes_follow_the_wall_clock() {
+        let schedule = night([true; 7]);
+        let est = FixedOffset::west_opt(5 * 3600).unwrap();
+        let edt = FixedOffset::west_opt(4 * 3600).unwrap();
+        let utc = |day: u32, hour: u32| {
+            NaiveDate::from_ymd_opt(2024, 3, day)
+                .unwrap()
+                .and_hms_opt(hour, 30, 0)
+                .unwrap()
+                .and_utc()
+        };
+        // US clocks spring forward at 02:00 on 10 March 2024. 11:30 UTC is 07:30 EDT, past
+        // the end of the window, though it would still be 06:30 on standard time.
+        assert_eq!(
+            quiet_cap_at(&schedule, &utc(10, 11).with_timezone(&edt)),
+            None
+        );
+        assert_eq!(
+            quiet_cap_at(&schedule, &utc(10, 11).with_timezone(&est)),
+            Some(0.25)
+        );
+        // 01:30 EST, just before the change, is inside the window.
+        assert_eq!(
+            quiet_cap_at(&schedule, &utc(10, 6).with_timezone(&est)),
+            Some(0.25)
+        );
+    }
+
+    #[test]
+    fn cap_only_ever_lowers_the_volume() {
+        assert_eq!(effective_output_volume(0.8, Some(0.3)), 0.3);
+        assert_eq!(effective_output_volume(0.2, Some(0.3)), 0.2);
+        assert_eq!(effective_output_volume(1.4, None), 1.0);
+        assert_eq!(volume_ramp(0.8, 0.3, 5).last(), Some(&0.3));
+        assert_eq!(volume_ramp(0.2, 0.2, 0), vec![0.2]);
+    }
+
+    #[test]
+    fn time_input_values_round_trip() {
+        assert_eq!(parse_minute("07:05"), Some(425));
+        assert_eq!(parse_minute("21:00:00"), Some(1260));
+        assert_eq!(parse_minute("24:00"), None);
+        assert_eq!(minute_label(425), "07:05");
+    }
+}
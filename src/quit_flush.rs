@@ -0,0 +1,621 @@
+//! Final save of playback when the desktop app quits.
+//!
+//! Bookmarks are written every 15 seconds and the saved playback state is debounced, so
+//! closing the window mid-song used to lose up to that much. On window close (and SIGTERM on
+//! Linux) the exact position goes into the saved playback state, the server bookmark is
+//! updated and scrobbles the server hasn't confirmed are sent again, all under a short
+//! timeout so quitting never hangs, and the rotating database backup is refreshed after it.
+//! The player and the app keep the state here current, so the flush never needs the UI.
+use crate::api::ServerConfig;
+#[cfg(any(feature = "desktop", test))]
+use crate::db::PlaybackState;
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+#[cfg(any(feature = "desktop", test))]
+use std::time::Duration;
+
+/// Longest a quit waits for the flush before giving up.
+#[cfg(feature = "desktop")]
+const QUIT_FLUSH_TIMEOUT: Duration = Duration::from_millis(2500);
+/// Bookmarks this close to the start aren't worth keeping, same as during playback.
+#[cfg(any(feature = "desktop", test))]
+const MIN_BOOKMARK_MS: u64 = 1500;
+#[cfg(feature = "desktop")]
+const LAST_QUIT_FILE: &str = "last-quit.log";
+
+/// What is playing, as the app last saw it.
+#[cfg(any(feature = "desktop", test))]
+#[derive(Debug, Clone, Default)]
+pub struct QuitSnapshot {
+    pub state: PlaybackState,
+    /// Server of the playing song; `None` leaves the bookmark alone.
+    pub server: Option<ServerConfig>,
+    pub bookmark_auto_save: bool,
+    pub bookmark_limit: usize,
+}
+
+/// Position the player reported on its latest tick.
+#[cfg_attr(not(feature = "desktop"), allow(dead_code))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerPosition {
+    pub song_id: String,
+    pub position: f64,
+    /// Whether the song may get a server bookmark, e.g. not radio and not held after a restart.
+    pub bookmark_allowed: bool,
+}
+
+/// A scrobble sent to the server that hasn't been confirmed yet.
+#[cfg_attr(not(feature = "desktop"), allow(dead_code))]
+#[derive(Debug, Clone)]
+pub struct PendingScrobble {
+    ticket: u64,
+    pub server: ServerConfig,
+    pub song_id: String,
+    pub submission: bool,
+    /// Whether a request for it is still out. A failed one stays listed with this cleared.
+    in_flight: bool,
+}
+
+#[cfg(feature = "desktop")]
+static QUIT_SNAPSHOT: Lazy<Mutex<Option<QuitSnapshot>>> = Lazy::new(|| Mutex::new(None));
+static PLAYER_POSITION: Lazy<Mutex<Option<PlayerPosition>>> = Lazy::new(|| Mutex::new(None));
+static PENDING_SCROBBLES: Lazy<Mutex<Vec<PendingScrobble>>> = Lazy::new(|| Mutex::new(Vec::new()));
+static NEXT_SCROBBLE_TICKET: AtomicU64 = AtomicU64::new(1);
+#[cfg(feature = "desktop")]
+static FLUSHED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Replaces what the flush saves; `None` when nothing is playing or a preview is.
+#[cfg(feature = "desktop")]
+pub fn set_quit_snapshot(snapshot: Option<QuitSnapshot>) {
+    *QUIT_SNAPSHOT.lock().unwrap_or_else(|e| e.into_inner()) = snapshot;
+}
+
+/// Called by the player on every progress tick.
+pub fn note_player_position(song_id: &str, position: f64, bookmark_allowed: bool) {
+    *PLAYER_POSITION.lock().unwrap_or_else(|e| e.into_inner()) = Some(PlayerPosition {
+        song_id: song_id.to_string(),
+        position,
+        bookmark_allowed,
+    });
+}
+
+/// Remembers a scrobble whose request is about to go out until the server confirms it
+/// through `scrobble_settled` with the returned ticket.
+pub fn track_scrobble(server: ServerConfig, song_id: &str, submission: bool) -> u64 {
+    let ticket = NEXT_SCROBBLE_TICKET.fetch_add(1, Ordering::Relaxed);
+    PENDING_SCROBBLES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(PendingScrobble {
+            ticket,
+            server,
+            song_id: song_id.to_string(),
+            submission,
+            in_flight: true,
+        });
+    ticket
+}
+
+/// Called when a scrobble's request finishes. Only a scrobble the server took is forgotten; a
+/// failed one waits for the quit flush.
+pub fn scrobble_settled(ticket: u64, sent: bool) {
+    settle_scrobble(
+        &mut PENDING_SCROBBLES.lock().unwrap_or_else(|e| e.into_inner()),
+        ticket,
+        sent,
+    );
+}
+
+fn settle_scrobble(pending: &mut Vec<PendingScrobble>, ticket: u64, sent: bool) {
+    if sent {
+        pending.retain(|scrobble| scrobble.ticket != ticket);
+    } else if let Some(scrobble) = pending
+        .iter_mut()
+        .find(|scrobble| scrobble.ticket == ticket)
+    {
+        scrobble.in_flight = false;
+    }
+}
+
+/// Marks the scrobbles no request is out for as in flight and returns them to be resent. A
+/// request still out is left to settle on its own, so the server doesn't count it twice.
+#[cfg(any(feature = "desktop", test))]
+fn claim_unsent_scrobbles(pending: &mut [PendingScrobble]) -> Vec<PendingScrobble> {
+    pending
+        .iter_mut()
+        .filter(|scrobble| !scrobble.in_flight)
+        .map(|scrobble| {
+            scrobble.in_flight = true;
+            scrobble.clone()
+        })
+        .collect()
+}
+
+/// The server and database calls the flush makes, so tests can stand in for them.
+#[cfg(any(feature = "desktop", test))]
+pub(crate) trait QuitFlushClient {
+    async fn save_playback_state(&self, state: PlaybackState) -> Result<(), String>;
+    async fn save_bookmark(
+        &self,
+        server: &ServerConfig,
+        song_id: &str,
+        position_ms: u64,
+        limit: usize,
+    ) -> Result<(), String>;
+    async fn scrobble(
+        &self,
+        server: &ServerConfig,
+        song_id: &str,
+        submission: bool,
+    ) -> Result<(), String>;
+}
+
+/// What a flush managed to save.
+#[cfg(any(feature = "desktop", test))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct QuitFlushReport {
+    pub state_saved: bool,
+    /// Bookmark position in milliseconds, when one was written.
+    pub bookmark_ms: Option<u64>,
+    pub scrobbles_sent: usize,
+    pub scrobbles_failed: usize,
+}
+
+/// Saves the playback state with the player's latest position, then writes the bookmark and
+/// sends the pending scrobbles side by side.
+#[cfg(any(feature = "desktop", test))]
+pub(crate) async fn flush_playback(
+    client: &impl QuitFlushClient,
+    snapshot: Option<QuitSnapshot>,
+    position: Option<PlayerPosition>,
+    scrobbles: Vec<PendingScrobble>,
+) -> QuitFlushReport {
+    use futures_util::future::{join, join_all};
+
+    let mut report = QuitFlushReport::default();
+    let mut bookmark = None;
+    if let Some(mut snapshot) = snapshot {
+        // Ticks for another song are left over from before a track change.
+        let live = position.filter(|live| snapshot.state.song_id.as_ref() == Some(&live.song_id));
+        if let Some(live) = &live {
+            snapshot.state.position = live.position.max(0.0);
+        }
+        let position_ms = (snapshot.state.position.max(0.0) * 1000.0).round() as u64;
+        report.state_saved = client
+            .save_playback_state(snapshot.state.clone())
+            .await
+            .is_ok();
+
+        let allowed = live.as_ref().is_some_and(|live| live.bookmark_allowed);
+        if snapshot.bookmark_auto_save && allowed && position_ms > MIN_BOOKMARK_MS {
+            if let (Some(server), Some(song_id)) = (snapshot.server, snapshot.state.song_id) {
+                bookmark = Some((server, song_id, position_ms, snapshot.bookmark_limit));
+            }
+        }
+    }
+
+    let bookmark_write = async {
+        let (server, song_id, position_ms, limit) = bookmark?;
+        client
+            .save_bookmark(&server, &song_id, position_ms, limit.clamp(1, 5000))
+            .await
+            .ok()
+            .map(|_| position_ms)
+    };
+    let scrobble_sends = join_all(
+        scrobbles
+            .iter()
+            .map(|pending| client.scrobble(&pending.server, &pending.song_id, pending.submission)),
+    );
+    let (bookmark_ms, scrobble_results) = join(bookmark_write, scrobble_sends).await;
+    for (pending, sent) in scrobbles.iter().zip(&scrobble_results) {
+        scrobble_settled(pending.ticket, sent.is_ok());
+    }
+
+    report.bookmark_ms = bookmark_ms;
+    report.scrobbles_sent = scrobble_results.iter().filter(|sent| sent.is_ok()).count();
+    report.scrobbles_failed = scrobble_results.len() - report.scrobbles_sent;
+    report
+}
+
+/// `flush_playback`, or `None` when it didn't finish within `timeout`.
+#[cfg(any(feature = "desktop", test))]
+pub(crate) async fn flush_with_timeout(
+    client: &impl QuitFlushClient,
+    snapshot: Option<QuitSnapshot>,
+    position: Option<PlayerPosition>,
+    scrobbles: Vec<PendingScrobble>,
+    timeout: Duration,
+) -> Option<QuitFlushReport> {
+    tokio::time::timeout(
+        timeout,
+        flush_playback(client, snapshot, position, scrobbles),
+    )
+    .await
+    .ok()
+}
+
+/// Diagnostics line for a flush triggered by `trigger`.
+#[cfg(any(feature = "desktop", test))]
+fn quit_flush_diagnostic(
+    trigger: &str,
+    report: Option<&QuitFlushReport>,
+    timeout: Duration,
+) -> String {
+    let Some(report) = report else {
+        return format!(
+            "[quit] flush on {trigger} timed out after {}ms",
+            timeout.as_millis()
+        );
+    };
+    let state = if report.state_saved {
+        "position saved"
+    } else {
+        "position not saved"
+    };
+    let bookmark = match report.bookmark_ms {
+        Some(ms) => format!("bookmark at {:.1}s", ms as f64 / 1000.0),
+        None => "no bookmark".to_string(),
+    };
+    format!(
+        "[quit] flush on {trigger} completed: {state}, {bookmark}, {} scrobble(s) sent, {} failed",
+        report.scrobbles_sent, report.scrobbles_failed
+    )
+}
+
+#[cfg(feature = "desktop")]
+struct LiveQuitFlush;
+
+#[cfg(feature = "desktop")]
+impl QuitFlushClient for LiveQuitFlush {
+    async fn save_playback_state(&self, state: PlaybackState) -> Result<(), String> {
+        crate::db::save_playback_state(state)
+            .await
+            .map_err(|error| error.to_string())
+    }
+
+    async fn save_bookmark(
+        &self,
+        server: &ServerConfig,
+        song_id: &str,
+        position_ms: u64,
+        limit: usize,
+    ) -> Result<(), String> {
+        crate::api::NavidromeClient::new(server.clone())
+            .create_bookmark_with_limit(song_id, position_ms, None, Some(limit))
+            .await
+            .map_err(|error| error.to_string())
+    }
+
+    async fn scrobble(
+        &self,
+        server: &ServerConfig,
+        song_id: &str,
+        submission: bool,
+    ) -> Result<(), String> {
+        crate::api::NavidromeClient::new(server.clone())
+            .scrobble(song_id, submission)
+            .await
+            .map_err(|error| error.to_string())
+    }
+}
+
+/// Runs the flush once per process, blocking the caller for at most `QUIT_FLUSH_TIMEOUT`.
+/// The work gets its own thread and runtime, so this is safe to call from the event loop or
+/// a signal thread.
+#[cfg(feature = "desktop")]
+pub fn flush_on_quit(trigger: &'static str) {
+    if FLUSHED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let snapshot = QUIT_SNAPSHOT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    let position = PLAYER_POSITION
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    let scrobbles =
+        claim_unsent_scrobbles(&mut PENDING_SCROBBLES.lock().unwrap_or_else(|e| e.into_inner()));
+
+    let worker = std::thread::Builder::new()
+        .name("rustysound-quit-flush".to_string())
+        .spawn(move || {
+            let report = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .ok()
+                .and_then(|runtime| {
+                    runtime.block_on(flush_with_timeout(
+                        &LiveQuitFlush,
+                        snapshot,
+                        position,
+                        scrobbles,
+                        QUIT_FLUSH_TIMEOUT,
+                    ))
+                });
+            // After the flush, so the backup holds the final playback state.
+            crate::db::backup_database_on_shutdown();
+            report
+        });
+    let report = worker.ok().and_then(|worker| worker.join().ok()).flatten();
+
+    let line = quit_flush_diagnostic(trigger, report.as_ref(), QUIT_FLUSH_TIMEOUT);
+    eprintln!("{line}");
+    crate::diagnostics::record_diagnostic(line.clone());
+    // The in-memory log goes away with the process, so keep the line for the next launch.
+    if let Some(dir) = crate::storage::profile_data_dir() {
+        let _ = std::fs::write(dir.join(LAST_QUIT_FILE), line);
+    }
+}
+
+/// Copies how the previous quit's flush went into this session's diagnostics.
+#[cfg(feature = "desktop")]
+pub fn report_previous_quit() {
+    let Some(path) = crate::storage::profile_data_dir().map(|dir| dir.join(LAST_QUIT_FILE)) else {
+        return;
+    };
+    if let Ok(line) = std::fs::read_to_string(&path) {
+        crate::diagnostics::record_diagnostic(format!("[previous session] {}", line.trim()));
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Flushes and exits on SIGTERM, which would otherwise end the process without it.
+#[cfg(all(feature = "desktop", target_os = "linux"))]
+pub fn install_sigterm_handler() {
+    let _ = std::thread::Builder::new()
+        .name("rustysound-sigterm".to_string())
+        .spawn(|| {
+            let Ok(runtime) = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            else {
+                return;
+            };
+            let received = runtime.block_on(async {
+                use tokio::signal::unix::{signal, SignalKind};
+                match signal(SignalKind::terminate()) {
+                    Ok(mut terminate) => terminate.recv().await.is_some(),
+                    Err(_) => false,
+                }
+            });
+            if received {
+                flush_on_quit("SIGTERM");
+                std::process::exit(0);
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::QueueItem;
+
+    #[derive(Default)]
+    struct MockClient {
+        delay: Option<Duration>,
+        offline: bool,
+        saved_states: Mutex<Vec<PlaybackState>>,
+        bookmarks: Mutex<Vec<(String, u64, usize)>>,
+        scrobbles: Mutex<Vec<(String, bool)>>,
+    }
+
+    impl MockClient {
+        async fn wait(&self) {
+            if let Some(delay) = self.delay {
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+
+    impl QuitFlushClient for MockClient {
+        async fn save_playback_state(&self, state: PlaybackState) -> Result<(), String> {
+            self.saved_states.lock().unwrap().push(state);
+            Ok(())
+        }
+
+        async fn save_bookmark(
+            &self,
+            _server: &ServerConfig,
+            song_id: &str,
+            position_ms: u64,
+            limit: usize,
+        ) -> Result<(), String> {
+            self.wait().await;
+            if self.offline {
+                return Err("offline".to_string());
+            }
+            self.bookmarks
+                .lock()
+                .unwrap()
+                .push((song_id.to_string(), position_ms, limit));
+            Ok(())
+        }
+
+        async fn scrobble(
+            &self,
+            _server: &ServerConfig,
+            song_id: &str,
+            submission: bool,
+        ) -> Result<(), String> {
+            self.wait().await;
+            if self.offline {
+                return Err("offline".to_string());
+            }
+            self.scrobbles
+                .lock()
+                .unwrap()
+                .push((song_id.to_string(), submission));
+            Ok(())
+        }
+    }
+
+    fn server() -> ServerConfig {
+        ServerConfig::new(
+            "Home".to_string(),
+            "http://localhost:4533".to_string(),
+            "user".to_string(),
+            "pass".to_string(),
+        )
+    }
+
+    /// Song "b" of a two-song queue, last saved by the debounced autosave at 60s.
+    fn snapshot(bookmark_auto_save: bool) -> QuitSnapshot {
+        let server = server();
+        QuitSnapshot {
+            state: PlaybackState {
+                song_id: Some("b".to_string()),
+                server_id: Some(server.id.clone()),
+                position: 60.0,
+                queue: ["a", "b"]
+                    .iter()
+                    .map(|id| QueueItem {
+                        song_id: id.to_string(),
+                        server_id: server.id.clone(),
+                    })
+                    .collect(),
+                queue_index: 1,
+            },
+            server: Some(server),
+            bookmark_auto_save,
+            bookmark_limit: 20,
+        }
+    }
+
+    fn position(song_id: &str, position: f64) -> Option<PlayerPosition> {
+        Some(PlayerPosition {
+            song_id: song_id.to_string(),
+            position,
+            bookmark_allowed: true,
+        })
+    }
+
+    fn pending(song_id: &str, submission: bool) -> PendingScrobble {
+        PendingScrobble {
+            ticket: 0,
+            server: server(),
+            song_id: song_id.to_string(),
+            submission,
+            in_flight: false,
+        }
+    }
+
+    #[test]
+    fn failed_scrobbles_stay_listed_until_a_resend_succeeds() {
+        let mut list = vec![
+            PendingScrobble {
+                ticket: 1,
+                ..pending("a", true)
+            },
+            PendingScrobble {
+                ticket: 2,
+                in_flight: true,
+                ..pending("b", true)
+            },
+        ];
+        settle_scrobble(&mut list, 1, false);
+        assert_eq!(list.len(), 2);
+
+        let claimed = claim_unsent_scrobbles(&mut list);
+        assert_eq!(
+            claimed.iter().map(|s| s.ticket).collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert!(claim_unsent_scrobbles(&mut list).is_empty());
+
+        settle_scrobble(&mut list, 1, false);
+        settle_scrobble(&mut list, 2, true);
+        assert_eq!(list.iter().map(|s| s.ticket).collect::<Vec<_>>(), vec![1]);
+        assert!(!list[0].in_flight);
+    }
+
+    #[tokio::test]
+    async fn flush_saves_exact_position_bookmark_and_scrobbles() {
+        let client = MockClient::default();
+
+        let report = flush_playback(
+            &client,
+            Some(snapshot(true)),
+            position("b", 73.4567),
+            vec![pending("a", true), pending("b", false)],
+        )
+        .await;
+
+        let states = client.saved_states.lock().unwrap();
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].position, 73.4567);
+        assert_eq!(states[0].queue_index, 1);
+        assert_eq!(
+            *client.bookmarks.lock().unwrap(),
+            vec![("b".to_string(), 73457, 20)]
+        );
+        assert_eq!(
+            *client.scrobbles.lock().unwrap(),
+            vec![("a".to_string(), true), ("b".to_string(), false)]
+        );
+        assert_eq!(
+            report,
+            QuitFlushReport {
+                state_saved: true,
+                bookmark_ms: Some(73457),
+                scrobbles_sent: 2,
+                scrobbles_failed: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn flush_respects_bookmark_setting_and_stale_ticks() {
+        let client = MockClient::default();
+        let report =
+            flush_playback(&client, Some(snapshot(false)), position("b", 90.0), vec![]).await;
+        assert!(report.state_saved && report.bookmark_ms.is_none());
+        assert!(client.bookmarks.lock().unwrap().is_empty());
+
+        // A tick from the previous song keeps the autosaved position and skips the bookmark.
+        let client = MockClient::default();
+        let report =
+            flush_playback(&client, Some(snapshot(true)), position("a", 200.0), vec![]).await;
+        assert_eq!(client.saved_states.lock().unwrap()[0].position, 60.0);
+        assert!(report.bookmark_ms.is_none());
+    }
+
+    #[tokio::test]
+    async fn flush_reports_failures_and_gives_up_after_the_timeout() {
+        let offline = MockClient {
+            offline: true,
+            ..MockClient::default()
+        };
+        let report = flush_playback(
+            &offline,
+            Some(snapshot(true)),
+            position("b", 30.0),
+            vec![pending("a", true)],
+        )
+        .await;
+        assert!(report.state_saved);
+        assert_eq!((report.bookmark_ms, report.scrobbles_failed), (None, 1));
+
+        let hanging = MockClient {
+            delay: Some(Duration::from_secs(30)),
+            ..MockClient::default()
+        };
+        let timeout = Duration::from_millis(50);
+        let report = flush_with_timeout(
+            &hanging,
+            Some(snapshot(true)),
+            position("b", 30.0),
+            vec![pending("a", true)],
+            timeout,
+        )
+        .await;
+        assert!(report.is_none());
+        assert_eq!(
+            quit_flush_diagnostic("window close", None, timeout),
+            "[quit] flush on window close timed out after 50ms"
+        );
+    }
+}
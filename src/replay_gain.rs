@@ -0,0 +1,79 @@
+//! ReplayGain: levels playback with the loudness tags the server reports for each song, on
+//! top of the user's volume.
+//!
+//! A gain in dB, plus the preamp from Settings, becomes a linear multiplier, held down so the
+//! track's peak stays under full scale. Outputs can't go past full volume, so a boost only
+//! shows below it. Songs without tags, such as radio streams, play as they are: the preamp
+//! only moves tagged songs.
+use crate::api::{ReplayGain, Song};
+use crate::db::{AppSettings, ReplayGainMode, REPLAY_GAIN_PREAMP_DB};
+
+/// The gain and peak `mode` levels by, falling back to the other kind when a file only
+/// carries one of them.
+fn gain_and_peak(tags: &ReplayGain, mode: ReplayGainMode) -> Option<(f64, Option<f64>)> {
+    let track = tags.track_gain.map(|gain| (gain, tags.track_peak));
+    let album = tags.album_gain.map(|gain| (gain, tags.album_peak));
+    match mode {
+        ReplayGainMode::Track => track.or(album),
+        ReplayGainMode::Album => album.or(track),
+    }
+}
+
+/// Linear multiplier for `gain_db`, kept low enough that `peak` doesn't clip.
+fn gain_multiplier(gain_db: f64, peak: Option<f64>) -> f64 {
+    let multiplier = 10f64.powf(gain_db / 20.0);
+    match peak.filter(|peak| peak.is_finite() && *peak > 0.0) {
+        Some(peak) => multiplier.min(1.0 / peak),
+        None => multiplier,
+    }
+}
+
+/// What the output volume is multiplied by while `song` plays: 1.0 with ReplayGain off or
+/// for songs without tags.
+pub fn replay_gain_multiplier(settings: &AppSettings, song: Option<&Song>) -> f64 {
+    if !settings.replay_gain {
+        return 1.0;
+    }
+    let preamp_db = settings
+        .replay_gain_preamp_db
+        .clamp(-REPLAY_GAIN_PREAMP_DB, REPLAY_GAIN_PREAMP_DB);
+    song.and_then(|song| song.replay_gain.as_ref())
+        .and_then(|tags| gain_and_peak(tags, settings.replay_gain_mode))
+        .map(|(gain, peak)| gain_multiplier(gain + preamp_db, peak))
+        .unwrap_or(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follows_the_mode_and_keeps_peaks_under_full_scale() {
+        let song: Song = serde_json::from_value(serde_json::json!({
+            "id": "s1",
+            "title": "Song",
+            "replayGain": { "trackGain": -6.0, "albumGain": -3.0, "trackPeak": 0.9 },
+        }))
+        .unwrap();
+        let mut settings = AppSettings::default();
+        assert_eq!(replay_gain_multiplier(&settings, Some(&song)), 1.0);
+
+        settings.replay_gain = true;
+        let track = replay_gain_multiplier(&settings, Some(&song));
+        assert!((track - 0.501).abs() < 0.001, "{track}");
+        settings.replay_gain_mode = ReplayGainMode::Album;
+        let album = replay_gain_multiplier(&settings, Some(&song));
+        assert!((album - 0.708).abs() < 0.001, "{album}");
+
+        settings.replay_gain_preamp_db = 3.0;
+        let preamped = replay_gain_multiplier(&settings, Some(&song));
+        assert!((preamped - 1.0).abs() < 0.001, "{preamped}");
+
+        assert!((gain_multiplier(6.0, Some(0.8)) - 1.25).abs() < 1e-9);
+        assert_eq!(
+            replay_gain_multiplier(&settings, Some(&Song::default())),
+            1.0
+        );
+        assert_eq!(replay_gain_multiplier(&settings, None), 1.0);
+    }
+}
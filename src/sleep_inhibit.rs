@@ -0,0 +1,192 @@
+// Desktop sleep/idle inhibition while audio is playing.
+//
+// A single worker thread owns the active inhibitor so rapid play/pause toggles are
+// debounced into one acquire/release. Helper processes are tied to our pid, so they
+// also go away if the app exits without running its shutdown path.
+use crate::db::SleepInhibitMode;
+use once_cell::sync::Lazy;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::time::Duration;
+
+const INHIBIT_DEBOUNCE: Duration = Duration::from_millis(800);
+
+enum InhibitCommand {
+    Set(SleepInhibitMode),
+    Shutdown,
+}
+
+static INHIBIT_WORKER: Lazy<Mutex<Option<Sender<InhibitCommand>>>> = Lazy::new(|| {
+    let (tx, rx) = mpsc::channel::<InhibitCommand>();
+    let spawned = std::thread::Builder::new()
+        .name("rustysound-sleep-inhibit".to_string())
+        .spawn(move || {
+            let mut active: Option<(SleepInhibitMode, Inhibitor)> = None;
+            let mut pending: Option<SleepInhibitMode> = None;
+            loop {
+                let next = if pending.is_some() {
+                    rx.recv_timeout(INHIBIT_DEBOUNCE)
+                } else {
+                    rx.recv().map_err(|_| RecvTimeoutError::Disconnected)
+                };
+                match next {
+                    Ok(InhibitCommand::Set(mode)) => pending = Some(mode),
+                    Ok(InhibitCommand::Shutdown) | Err(RecvTimeoutError::Disconnected) => break,
+                    Err(RecvTimeoutError::Timeout) => {
+                        let Some(mode) = pending.take() else {
+                            continue;
+                        };
+                        if active.as_ref().map(|(current, _)| *current) == Some(mode) {
+                            continue;
+                        }
+                        // Release before acquiring so we never hold two inhibitors.
+                        active = None;
+                        if mode != SleepInhibitMode::Never {
+                            active = Inhibitor::acquire(mode).map(|inhibitor| (mode, inhibitor));
+                        }
+                    }
+                }
+            }
+            drop(active);
+        });
+    Mutex::new(spawned.ok().map(|_| tx))
+});
+
+/// Updates the desired inhibitor state; applied after a short debounce.
+pub fn update_sleep_inhibit(mode: SleepInhibitMode, playing: bool) {
+    let desired = if playing {
+        mode
+    } else {
+        SleepInhibitMode::Never
+    };
+    let worker = INHIBIT_WORKER.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(tx) = worker.as_ref() {
+        let _ = tx.send(InhibitCommand::Set(desired));
+    }
+}
+
+/// Releases any held inhibitor immediately. Call on app exit.
+pub fn release_sleep_inhibit() {
+    let mut worker = INHIBIT_WORKER.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(tx) = worker.take() {
+        let _ = tx.send(InhibitCommand::Shutdown);
+    }
+}
+
+struct Inhibitor {
+    #[cfg(not(target_os = "windows"))]
+    child: std::process::Child,
+}
+
+#[cfg(target_os = "linux")]
+impl Inhibitor {
+    fn acquire(mode: SleepInhibitMode) -> Option<Self> {
+        // logind inhibitor lock held for as long as the helper runs; `tail --pid`
+        // exits with us so a crash never leaves the machine pinned awake.
+        let what = match mode {
+            SleepInhibitMode::SystemAndDisplay => "sleep:idle",
+            _ => "sleep",
+        };
+        let pid = std::process::id().to_string();
+        let child = std::process::Command::new("systemd-inhibit")
+            .args([
+                &format!("--what={what}"),
+                "--who=RustySound",
+                "--why=Playing music",
+                "--mode=block",
+                "tail",
+                &format!("--pid={pid}"),
+                "-f",
+                "/dev/null",
+            ])
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+        match child {
+            Ok(child) => Some(Self { child }),
+            Err(err) => {
+                eprintln!("[sleep-inhibit] systemd-inhibit unavailable: {err}");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl Inhibitor {
+    fn acquire(mode: SleepInhibitMode) -> Option<Self> {
+        // caffeinate holds the IOPMAssertion and exits with us via `-w`.
+        let flags = match mode {
+            SleepInhibitMode::SystemAndDisplay => "-di",
+            _ => "-i",
+        };
+        let pid = std::process::id().to_string();
+        let child = std::process::Command::new("caffeinate")
+            .args([flags, "-w", pid.as_str()])
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+        match child {
+            Ok(child) => Some(Self { child }),
+            Err(err) => {
+                eprintln!("[sleep-inhibit] caffeinate unavailable: {err}");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+impl Inhibitor {
+    fn acquire(_mode: SleepInhibitMode) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+impl Drop for Inhibitor {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod win {
+    pub const ES_CONTINUOUS: u32 = 0x8000_0000;
+    pub const ES_SYSTEM_REQUIRED: u32 = 0x0000_0001;
+    pub const ES_DISPLAY_REQUIRED: u32 = 0x0000_0002;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        pub fn SetThreadExecutionState(flags: u32) -> u32;
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Inhibitor {
+    fn acquire(mode: SleepInhibitMode) -> Option<Self> {
+        // Execution state is per-thread; the worker thread lives for the whole session.
+        let mut flags = win::ES_CONTINUOUS | win::ES_SYSTEM_REQUIRED;
+        if mode == SleepInhibitMode::SystemAndDisplay {
+            flags |= win::ES_DISPLAY_REQUIRED;
+        }
+        let previous = unsafe { win::SetThreadExecutionState(flags) };
+        if previous == 0 {
+            eprintln!("[sleep-inhibit] SetThreadExecutionState failed");
+            return None;
+        }
+        Some(Self {})
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for Inhibitor {
+    fn drop(&mut self) {
+        unsafe {
+            win::SetThreadExecutionState(win::ES_CONTINUOUS);
+        }
+    }
+}
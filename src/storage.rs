@@ -16,6 +16,70 @@ const LINUX_APP_DIR: &str = "rustysound";
 const FALLBACK_APP_DIR: &str = ".rustysound";
 #[cfg(not(target_arch = "wasm32"))]
 const LEGACY_CACHE_DIR: &str = "rustysound";
+#[cfg(not(target_arch = "wasm32"))]
+const PROFILE_ENV_VAR: &str = "RUSTYSOUND_PROFILE";
+#[cfg(not(target_arch = "wasm32"))]
+const PROFILES_DIR: &str = "profiles";
+
+#[cfg(not(target_arch = "wasm32"))]
+static ACTIVE_PROFILE: std::sync::OnceLock<Option<String>> = std::sync::OnceLock::new();
+
+/// The profile picked with `--profile <name>` or `RUSTYSOUND_PROFILE`, if any. Each
+/// profile gets its own database and cache directory; `None` is the default profile,
+/// which keeps the original paths.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn active_profile() -> Option<&'static str> {
+    ACTIVE_PROFILE
+        .get_or_init(|| {
+            profile_from_args(std::env::args().skip(1)).or_else(|| {
+                std::env::var(PROFILE_ENV_VAR)
+                    .ok()
+                    .and_then(|name| sanitize_profile_name(&name))
+            })
+        })
+        .as_deref()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn profile_from_args(args: impl IntoIterator<Item = String>) -> Option<String> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--profile" {
+            return args.next().and_then(|name| sanitize_profile_name(&name));
+        }
+        if let Some(name) = arg.strip_prefix("--profile=") {
+            return sanitize_profile_name(name);
+        }
+    }
+    None
+}
+
+/// Profile names end up in file names, so only letters, digits, `-` and `_` are allowed.
+/// "default" and empty names select the default profile.
+#[cfg(not(target_arch = "wasm32"))]
+fn sanitize_profile_name(name: &str) -> Option<String> {
+    let name = name.trim();
+    if name.is_empty() || name.eq_ignore_ascii_case("default") {
+        return None;
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        eprintln!("Ignoring profile name {name:?}: use letters, digits, '-' or '_'.");
+        return None;
+    }
+    Some(name.to_string())
+}
+
+/// SQLite file name for the active profile: `rustysound.db` or `rustysound-<profile>.db`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn database_file_name() -> String {
+    match active_profile() {
+        Some(profile) => format!("rustysound-{profile}.db"),
+        None => "rustysound.db".to_string(),
+    }
+}
 
 #[cfg(not(target_arch = "wasm32"))]
 pub fn app_data_dir() -> Option<PathBuf> {
@@ -27,12 +91,27 @@ pub fn app_data_dir() -> Option<PathBuf> {
 
 #[cfg(not(target_arch = "wasm32"))]
 pub fn app_cache_dir() -> Option<PathBuf> {
-    let dir = preferred_cache_dir()?;
+    let mut dir = preferred_cache_dir()?;
     migrate_dir_if_missing(&dir, &legacy_cache_dir_candidates());
+    if let Some(profile) = active_profile() {
+        dir = dir.join(PROFILES_DIR).join(profile);
+    }
     fs::create_dir_all(&dir).ok()?;
     Some(dir)
 }
 
+/// Data directory for files that belong to one profile, like crash reports: the data
+/// directory itself for the default profile, `profiles/<name>` inside it otherwise.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn profile_data_dir() -> Option<PathBuf> {
+    let mut dir = app_data_dir()?;
+    if let Some(profile) = active_profile() {
+        dir = dir.join(PROFILES_DIR).join(profile);
+        fs::create_dir_all(&dir).ok()?;
+    }
+    Some(dir)
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 fn preferred_data_dir() -> Option<PathBuf> {
     #[cfg(any(target_os = "macos", target_os = "ios"))]
@@ -183,3 +262,95 @@ fn dedupe_paths(paths: Vec<PathBuf>) -> Vec<PathBuf> {
 fn home_dir() -> Option<PathBuf> {
     std::env::var_os("HOME").map(PathBuf::from)
 }
+
+/// Saves a text export to the downloads folder and returns a status line for the UI.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_text_export(file_name: &str, contents: &str, _mime: &str) -> Result<String, String> {
+    let dir = dirs::download_dir()
+        .or_else(app_data_dir)
+        .ok_or_else(|| "No writable folder found for the export.".to_string())?;
+    let path = dir.join(file_name);
+    std::fs::write(&path, contents).map_err(|e| e.to_string())?;
+    Ok(format!("Saved {}", path.display()))
+}
+
+/// Triggers a browser download of a text export and returns a status line for the UI.
+#[cfg(target_arch = "wasm32")]
+pub fn save_text_export(file_name: &str, contents: &str, mime: &str) -> Result<String, String> {
+    let file_name_json = serde_json::to_string(file_name).map_err(|e| e.to_string())?;
+    let contents_json = serde_json::to_string(contents).map_err(|e| e.to_string())?;
+    let mime_json = serde_json::to_string(mime).map_err(|e| e.to_string())?;
+    let script = format!(
+        r#"(function(){{const blob=new Blob([{contents_json}],{{type:{mime_json}}});const url=URL.createObjectURL(blob);const a=document.createElement("a");a.href=url;a.download={file_name_json};document.body.appendChild(a);a.click();a.remove();setTimeout(()=>URL.revokeObjectURL(url),1000);}})();"#
+    );
+    let _ = dioxus::document::eval(&script);
+    Ok(format!("Downloaded {file_name}"))
+}
+
+/// Saves an HTML export and opens it in the system browser, where it can be printed.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn open_html_export(file_name: &str, contents: &str) -> Result<String, String> {
+    let dir = dirs::download_dir()
+        .or_else(app_data_dir)
+        .ok_or_else(|| "No writable folder found for the export.".to_string())?;
+    let path = dir.join(file_name);
+    std::fs::write(&path, contents).map_err(|e| e.to_string())?;
+
+    #[cfg(target_os = "macos")]
+    let opened = std::process::Command::new("open").arg(&path).spawn();
+    #[cfg(target_os = "windows")]
+    let opened = std::process::Command::new("cmd")
+        .args(["/C", "start", ""])
+        .arg(&path)
+        .spawn();
+    #[cfg(all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    ))]
+    let opened = std::process::Command::new("xdg-open").arg(&path).spawn();
+    #[cfg(any(target_os = "ios", target_os = "android"))]
+    let opened: std::io::Result<()> = Err(std::io::ErrorKind::Unsupported.into());
+
+    Ok(match opened {
+        Ok(_) => format!("Opened {} in your browser", path.display()),
+        Err(_) => format!("Saved {}; open it in a browser to print", path.display()),
+    })
+}
+
+/// Opens an HTML export in a new browser tab and returns a status line for the UI.
+#[cfg(target_arch = "wasm32")]
+pub fn open_html_export(file_name: &str, contents: &str) -> Result<String, String> {
+    let file_name_json = serde_json::to_string(file_name).map_err(|e| e.to_string())?;
+    let contents_json = serde_json::to_string(contents).map_err(|e| e.to_string())?;
+    // A blocked pop-up falls back to downloading the page.
+    let script = format!(
+        r#"(function(){{const blob=new Blob([{contents_json}],{{type:"text/html"}});const url=URL.createObjectURL(blob);if(!window.open(url,"_blank")){{const a=document.createElement("a");a.href=url;a.download={file_name_json};document.body.appendChild(a);a.click();a.remove();}}setTimeout(()=>URL.revokeObjectURL(url),60000);}})();"#
+    );
+    let _ = dioxus::document::eval(&script);
+    Ok(format!("Opened {file_name} in a new tab"))
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn profile_flag_accepts_both_forms_and_rejects_unsafe_names() {
+        assert_eq!(
+            profile_from_args(args(&["--profile", "work"])),
+            Some("work".to_string())
+        );
+        assert_eq!(
+            profile_from_args(args(&["--verbose", "--profile=home_2"])),
+            Some("home_2".to_string())
+        );
+        assert_eq!(profile_from_args(args(&["--profile", "default"])), None);
+        assert_eq!(profile_from_args(args(&["--profile", "../other"])), None);
+        assert_eq!(profile_from_args(args(&["--profile"])), None);
+        assert_eq!(profile_from_args(Vec::new()), None);
+    }
+}
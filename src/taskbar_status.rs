@@ -0,0 +1,131 @@
+// Reflects playback in the desktop window title, taskbar progress and taskbar/dock badge.
+use crate::api::Song;
+use crate::db::PlaybackIndicatorMode;
+use crate::playback_title::window_title;
+use dioxus::desktop::window;
+use std::cell::RefCell;
+
+thread_local! {
+    /// Title last handed to the window, so position ticks and metadata edits of the same
+    /// track don't set it again.
+    static LAST_TITLE: RefCell<Option<String>> = const { RefCell::new(None) };
+    /// Progress percent and paused flag last shown on the taskbar.
+    static LAST_PROGRESS: RefCell<Option<Option<(u64, bool)>>> = const { RefCell::new(None) };
+}
+
+/// Shows the loaded track in the window title, or just the app name once playback stops.
+/// Called from the `now_playing` effect that sends the native track metadata, and again
+/// when the setting changes; only a different title reaches the window.
+pub fn update_window_title(mode: PlaybackIndicatorMode, song: Option<&Song>) {
+    let song = song.filter(|_| mode != PlaybackIndicatorMode::Off);
+    let title = window_title(song);
+    let changed = LAST_TITLE.with(|last| {
+        let mut last = last.borrow_mut();
+        if last.as_deref() == Some(title.as_str()) {
+            return false;
+        }
+        *last = Some(title.clone());
+        true
+    });
+    if changed {
+        window().window.set_title(&title);
+    }
+}
+
+/// Shows or hides the "playing" overlay on the taskbar or dock icon.
+pub fn update_playing_badge(mode: PlaybackIndicatorMode, playing: bool) {
+    let show_badge = playing && mode == PlaybackIndicatorMode::TitleAndBadge;
+    set_playing_badge(&window().window, show_badge);
+}
+
+/// Mirrors the playback position in the taskbar button: normal while playing, paused styling
+/// while paused, cleared when stopped or for live streams. `fraction` is `None` without a
+/// known duration. Updates only go out when the whole percent or the paused state changes.
+pub fn update_taskbar_progress(mode: PlaybackIndicatorMode, fraction: Option<f64>, playing: bool) {
+    let progress = fraction
+        .filter(|_| mode == PlaybackIndicatorMode::TitleAndBadge)
+        .map(|fraction| ((fraction.clamp(0.0, 1.0) * 100.0).round() as u64, !playing));
+    let changed = LAST_PROGRESS.with(|last| {
+        let mut last = last.borrow_mut();
+        if *last == Some(progress) {
+            return false;
+        }
+        *last = Some(progress);
+        true
+    });
+    if changed {
+        set_taskbar_progress(&window().window, progress);
+    }
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn set_taskbar_progress(
+    window: &dioxus::desktop::tao::window::Window,
+    progress: Option<(u64, bool)>,
+) {
+    use dioxus::desktop::tao::window::{ProgressBarState, ProgressState};
+    let (state, percent) = match progress {
+        Some((percent, true)) => (ProgressState::Paused, Some(percent)),
+        Some((percent, false)) => (ProgressState::Normal, Some(percent)),
+        None => (ProgressState::None, None),
+    };
+    window.set_progress_bar(ProgressBarState {
+        state: Some(state),
+        progress: percent,
+        // Unity launcher entries are matched by desktop file; ignored on Windows.
+        desktop_filename: cfg!(target_os = "linux").then(|| "rustysound.desktop".to_string()),
+    });
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn set_taskbar_progress(
+    _window: &dioxus::desktop::tao::window::Window,
+    _progress: Option<(u64, bool)>,
+) {
+}
+
+#[cfg(target_os = "windows")]
+fn set_playing_badge(window: &dioxus::desktop::tao::window::Window, visible: bool) {
+    use dioxus::desktop::tao::platform::windows::WindowExtWindows;
+    let icon = if visible {
+        playing_overlay_icon()
+    } else {
+        None
+    };
+    window.set_overlay_icon(icon.as_ref());
+}
+
+#[cfg(target_os = "macos")]
+fn set_playing_badge(window: &dioxus::desktop::tao::window::Window, visible: bool) {
+    use dioxus::desktop::tao::platform::macos::WindowExtMacOS;
+    window.set_badge_label(visible.then(|| "▶".to_string()));
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn set_playing_badge(_window: &dioxus::desktop::tao::window::Window, _visible: bool) {}
+
+/// 16x16 emerald disc with a white play triangle, drawn at runtime so no extra asset ships.
+#[cfg(target_os = "windows")]
+fn playing_overlay_icon() -> Option<dioxus::desktop::tao::window::Icon> {
+    const SIZE: u32 = 16;
+    let mut rgba = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+    let center = (SIZE as f32 - 1.0) / 2.0;
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let (fx, fy) = (x as f32, y as f32);
+            let inside_disc = (fx - center).powi(2) + (fy - center).powi(2) <= center * center;
+            // Triangle pointing right, spanning x 5..11 and narrowing toward the tip.
+            let half_height = (11.0 - fx) * 0.55;
+            let inside_play = (5.0..=11.0).contains(&fx) && (fy - center).abs() <= half_height;
+            let pixel = if !inside_disc {
+                [0, 0, 0, 0]
+            } else if inside_play {
+                [255, 255, 255, 255]
+            } else {
+                [16, 185, 129, 255]
+            };
+            rgba.extend_from_slice(&pixel);
+        }
+    }
+    dioxus::desktop::tao::window::Icon::from_rgba(rgba, SIZE, SIZE).ok()
+}
@@ -0,0 +1,491 @@
+//! Human-friendly timestamps: "just now", "3 hours ago", "yesterday at 9:41 PM", then plain
+//! dates, in the date language and clock the user picked.
+//!
+//! `humanize_timestamp` is pure so it can be tested against fixed clocks and time zones; the
+//! `*_for_display` helpers feed it the current time and the device's time zone.
+use crate::db::{AppSettings, ClockFormat, DateLocale};
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone, Timelike, Utc, Weekday};
+
+/// Timestamps up to this far ahead still read "just now"; server clocks drift.
+const CLOCK_SKEW_SECS: i64 = 5 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TimeFormatPrefs {
+    pub locale: DateLocale,
+    pub clock: ClockFormat,
+}
+
+impl TimeFormatPrefs {
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            locale: settings.date_locale,
+            clock: settings.clock_format,
+        }
+    }
+
+    fn uses_24_hour_clock(self) -> bool {
+        match self.clock {
+            ClockFormat::Auto => self.locale != DateLocale::EnUs,
+            ClockFormat::TwelveHour => false,
+            ClockFormat::TwentyFourHour => true,
+        }
+    }
+}
+
+/// Reads the ISO 8601 timestamps servers send. Times without an offset are taken as UTC.
+pub fn parse_server_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(value) {
+        return Some(parsed.with_timezone(&Utc));
+    }
+    [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S",
+    ]
+    .into_iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .map(|parsed| parsed.and_utc())
+}
+
+fn clock_time<Tz: TimeZone>(time: &DateTime<Tz>, prefs: TimeFormatPrefs) -> String {
+    if prefs.uses_24_hour_clock() {
+        return format!("{:02}:{:02}", time.hour(), time.minute());
+    }
+    let (pm, hour) = time.hour12();
+    let suffix = if pm { "PM" } else { "AM" };
+    format!("{hour}:{:02} {suffix}", time.minute())
+}
+
+fn weekday_name(locale: DateLocale, weekday: Weekday) -> &'static str {
+    const EN: [&str; 7] = [
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+        "Sunday",
+    ];
+    const DE: [&str; 7] = [
+        "Montag",
+        "Dienstag",
+        "Mittwoch",
+        "Donnerstag",
+        "Freitag",
+        "Samstag",
+        "Sonntag",
+    ];
+    const FR: [&str; 7] = [
+        "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+    ];
+    const ES: [&str; 7] = [
+        "lunes",
+        "martes",
+        "miércoles",
+        "jueves",
+        "viernes",
+        "sábado",
+        "domingo",
+    ];
+    let names = match locale {
+        DateLocale::EnUs | DateLocale::EnGb => EN,
+        DateLocale::De => DE,
+        DateLocale::Fr => FR,
+        DateLocale::Es => ES,
+    };
+    names[weekday.num_days_from_monday() as usize]
+}
+
+fn month_name(locale: DateLocale, month0: u32) -> &'static str {
+    const EN: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    const DE: [&str; 12] = [
+        "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.",
+        "Dez.",
+    ];
+    const FR: [&str; 12] = [
+        "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.",
+        "déc.",
+    ];
+    const ES: [&str; 12] = [
+        "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+    ];
+    let names = match locale {
+        DateLocale::EnUs | DateLocale::EnGb => EN,
+        DateLocale::De => DE,
+        DateLocale::Fr => FR,
+        DateLocale::Es => ES,
+    };
+    names[month0 as usize % 12]
+}
+
+/// "Mar 4" this year, "Mar 4, 2023" otherwise, in the locale's day/month order.
+fn calendar_date<Tz: TimeZone>(date: &DateTime<Tz>, show_year: bool, locale: DateLocale) -> String {
+    let day = date.day();
+    let month = month_name(locale, date.month0());
+    let year = date.year();
+    match (locale, show_year) {
+        (DateLocale::EnUs, false) => format!("{month} {day}"),
+        (DateLocale::EnUs, true) => format!("{month} {day}, {year}"),
+        (DateLocale::De, false) => format!("{day}. {month}"),
+        (DateLocale::De, true) => format!("{day}. {month} {year}"),
+        (_, false) => format!("{day} {month}"),
+        (_, true) => format!("{day} {month} {year}"),
+    }
+}
+
+fn relative_phrase(locale: DateLocale, amount: i64, minutes: bool) -> String {
+    match (locale, minutes, amount) {
+        (DateLocale::EnUs | DateLocale::EnGb, true, 1) => "1 minute ago".to_string(),
+        (DateLocale::EnUs | DateLocale::EnGb, true, n) => format!("{n} minutes ago"),
+        (DateLocale::EnUs | DateLocale::EnGb, false, 1) => "1 hour ago".to_string(),
+        (DateLocale::EnUs | DateLocale::EnGb, false, n) => format!("{n} hours ago"),
+        (DateLocale::De, true, 1) => "vor 1 Minute".to_string(),
+        (DateLocale::De, true, n) => format!("vor {n} Minuten"),
+        (DateLocale::De, false, 1) => "vor 1 Stunde".to_string(),
+        (DateLocale::De, false, n) => format!("vor {n} Stunden"),
+        (DateLocale::Fr, true, 1) => "il y a 1 minute".to_string(),
+        (DateLocale::Fr, true, n) => format!("il y a {n} minutes"),
+        (DateLocale::Fr, false, 1) => "il y a 1 heure".to_string(),
+        (DateLocale::Fr, false, n) => format!("il y a {n} heures"),
+        (DateLocale::Es, true, 1) => "hace 1 minuto".to_string(),
+        (DateLocale::Es, true, n) => format!("hace {n} minutos"),
+        (DateLocale::Es, false, 1) => "hace 1 hora".to_string(),
+        (DateLocale::Es, false, n) => format!("hace {n} horas"),
+    }
+}
+
+fn just_now(locale: DateLocale) -> &'static str {
+    match locale {
+        DateLocale::EnUs | DateLocale::EnGb => "just now",
+        DateLocale::De => "gerade eben",
+        DateLocale::Fr => "à l'instant",
+        DateLocale::Es => "ahora mismo",
+    }
+}
+
+/// "yesterday at 9:41 PM" when `weekday` is `None`, otherwise "Tuesday at 9:41 PM".
+fn day_at(locale: DateLocale, weekday: Option<Weekday>, time: &str) -> String {
+    let day = weekday.map(|weekday| weekday_name(locale, weekday));
+    match (locale, day) {
+        (DateLocale::EnUs | DateLocale::EnGb, None) => format!("yesterday at {time}"),
+        (DateLocale::EnUs | DateLocale::EnGb, Some(day)) => format!("{day} at {time}"),
+        (DateLocale::De, None) => format!("gestern um {time}"),
+        (DateLocale::De, Some(day)) => format!("{day} um {time}"),
+        (DateLocale::Fr, None) => format!("hier à {time}"),
+        (DateLocale::Fr, Some(day)) => format!("{day} à {time}"),
+        (DateLocale::Es, None) => format!("ayer a las {time}"),
+        (DateLocale::Es, Some(day)) => format!("el {day} a las {time}"),
+    }
+}
+
+/// How long ago `timestamp` was as of `now`, in `tz`: relative within the last week, a
+/// calendar date after that. "Yesterday" and weekdays follow calendar days in `tz`, so days
+/// made shorter or longer by a DST change still read right. Timestamps more than a few
+/// minutes in the future, from a server with a skewed clock, get a plain date.
+pub fn humanize_timestamp<Tz: TimeZone>(
+    timestamp: DateTime<Utc>,
+    now: DateTime<Utc>,
+    tz: &Tz,
+    prefs: TimeFormatPrefs,
+) -> String {
+    let locale = prefs.locale;
+    let elapsed_secs = now.signed_duration_since(timestamp).num_seconds();
+    let local = timestamp.with_timezone(tz);
+    let local_now = now.with_timezone(tz);
+    let show_year = local.year() != local_now.year();
+
+    if elapsed_secs < -CLOCK_SKEW_SECS {
+        return calendar_date(&local, show_year, locale);
+    }
+    if elapsed_secs < 60 {
+        return just_now(locale).to_string();
+    }
+    if elapsed_secs < 60 * 60 {
+        return relative_phrase(locale, elapsed_secs / 60, true);
+    }
+
+    let days_ago = local_now
+        .date_naive()
+        .signed_duration_since(local.date_naive())
+        .num_days();
+    match days_ago {
+        0 => relative_phrase(locale, elapsed_secs / 3600, false),
+        1 => day_at(locale, None, &clock_time(&local, prefs)),
+        2..=6 => day_at(locale, Some(local.weekday()), &clock_time(&local, prefs)),
+        _ => calendar_date(&local, show_year, locale),
+    }
+}
+
+/// `humanize_timestamp` for the current time in the device's time zone.
+pub fn humanize_for_display(timestamp: DateTime<Utc>, settings: &AppSettings) -> String {
+    humanize_timestamp(
+        timestamp,
+        Utc::now(),
+        &Local,
+        TimeFormatPrefs::from_settings(settings),
+    )
+}
+
+/// A server timestamp for display, or `None` when it can't be read.
+pub fn humanize_server_timestamp_for_display(
+    value: &str,
+    settings: &AppSettings,
+) -> Option<String> {
+    parse_server_timestamp(value).map(|timestamp| humanize_for_display(timestamp, settings))
+}
+
+/// A local timestamp in milliseconds since the epoch, for display.
+pub fn humanize_epoch_ms_for_display(epoch_ms: u64, settings: &AppSettings) -> String {
+    let timestamp = i64::try_from(epoch_ms)
+        .ok()
+        .and_then(DateTime::from_timestamp_millis)
+        .unwrap_or_default();
+    humanize_for_display(timestamp, settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, FixedOffset, MappedLocalTime, NaiveDate};
+
+    /// US Eastern time for 2024: EDT from 10 March 07:00 UTC to 3 November 06:00 UTC.
+    #[derive(Debug, Clone, Copy)]
+    struct Eastern2024;
+
+    impl Eastern2024 {
+        fn est() -> FixedOffset {
+            FixedOffset::west_opt(5 * 3600).unwrap()
+        }
+
+        fn edt() -> FixedOffset {
+            FixedOffset::west_opt(4 * 3600).unwrap()
+        }
+
+        fn offset_at(utc: &NaiveDateTime) -> FixedOffset {
+            let starts = NaiveDate::from_ymd_opt(2024, 3, 10)
+                .unwrap()
+                .and_hms_opt(7, 0, 0)
+                .unwrap();
+            let ends = NaiveDate::from_ymd_opt(2024, 11, 3)
+                .unwrap()
+                .and_hms_opt(6, 0, 0)
+                .unwrap();
+            if *utc >= starts && *utc < ends {
+                Self::edt()
+            } else {
+                Self::est()
+            }
+        }
+    }
+
+    impl TimeZone for Eastern2024 {
+        type Offset = FixedOffset;
+
+        fn from_offset(_offset: &FixedOffset) -> Self {
+            Eastern2024
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_hms_opt(12, 0, 0).unwrap())
+        }
+
+        fn offset_from_local_datetime(
+            &self,
+            local: &NaiveDateTime,
+        ) -> MappedLocalTime<FixedOffset> {
+            let fits = |offset: FixedOffset| {
+                let utc = *local - Duration::seconds(offset.local_minus_utc().into());
+                Self::offset_at(&utc) == offset
+            };
+            match (fits(Self::edt()), fits(Self::est())) {
+                (true, true) => MappedLocalTime::Ambiguous(Self::edt(), Self::est()),
+                (true, false) => MappedLocalTime::Single(Self::edt()),
+                (false, true) => MappedLocalTime::Single(Self::est()),
+                (false, false) => MappedLocalTime::None,
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            Self::offset_at(&utc.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            Self::offset_at(utc)
+        }
+    }
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        parse_server_timestamp(value).unwrap()
+    }
+
+    fn prefs(locale: DateLocale, clock: ClockFormat) -> TimeFormatPrefs {
+        TimeFormatPrefs { locale, clock }
+    }
+
+    const US: TimeFormatPrefs = TimeFormatPrefs {
+        locale: DateLocale::EnUs,
+        clock: ClockFormat::Auto,
+    };
+
+    fn at(timestamp: &str, now: &str, prefs: TimeFormatPrefs) -> String {
+        humanize_timestamp(utc(timestamp), utc(now), &Utc, prefs)
+    }
+
+    fn eastern(timestamp: &str, now: &str) -> String {
+        humanize_timestamp(utc(timestamp), utc(now), &Eastern2024, US)
+    }
+
+    #[test]
+    fn recent_times_are_relative() {
+        let now = "2024-06-12T18:00:00Z";
+        for (timestamp, expected) in [
+            ("2024-06-12T17:59:30Z", "just now"),
+            ("2024-06-12T17:59:00Z", "1 minute ago"),
+            ("2024-06-12T17:15:00Z", "45 minutes ago"),
+            ("2024-06-12T17:00:00Z", "1 hour ago"),
+            ("2024-06-12T14:59:00Z", "3 hours ago"),
+            ("2024-06-12T00:00:00Z", "18 hours ago"),
+        ] {
+            assert_eq!(at(timestamp, now, US), expected, "{timestamp}");
+        }
+    }
+
+    #[test]
+    fn older_times_use_days_then_dates() {
+        let now = "2024-06-12T18:00:00Z"; // a Wednesday
+        for (timestamp, expected) in [
+            ("2024-06-11T21:41:00Z", "yesterday at 9:41 PM"),
+            ("2024-06-11T09:05:00Z", "yesterday at 9:05 AM"),
+            ("2024-06-11T00:00:00Z", "yesterday at 12:00 AM"),
+            ("2024-06-10T12:00:00Z", "Monday at 12:00 PM"),
+            ("2024-06-06T08:30:00Z", "Thursday at 8:30 AM"),
+            ("2024-06-05T23:59:00Z", "Jun 5"),
+            ("2024-01-01T00:00:00Z", "Jan 1"),
+            ("2023-12-31T23:59:00Z", "Dec 31, 2023"),
+        ] {
+            assert_eq!(at(timestamp, now, US), expected, "{timestamp}");
+        }
+    }
+
+    #[test]
+    fn a_few_minutes_ahead_is_just_now_and_further_ahead_is_a_date() {
+        let now = "2024-06-12T18:00:00Z";
+        assert_eq!(at("2024-06-12T18:04:00Z", now, US), "just now");
+        assert_eq!(at("2024-06-12T19:00:00Z", now, US), "Jun 12");
+        assert_eq!(at("2025-02-01T00:00:00Z", now, US), "Feb 1, 2025");
+        let german = prefs(DateLocale::De, ClockFormat::Auto);
+        assert_eq!(at("2025-02-01T00:00:00Z", now, german), "1. Feb. 2025");
+    }
+
+    #[test]
+    fn locales_and_clock_preference() {
+        let now = "2024-06-12T18:00:00Z";
+        let cases = [
+            (DateLocale::EnGb, "2024-06-12T15:00:00Z", "3 hours ago"),
+            (
+                DateLocale::EnGb,
+                "2024-06-11T21:41:00Z",
+                "yesterday at 21:41",
+            ),
+            (DateLocale::EnGb, "2023-03-04T12:00:00Z", "4 Mar 2023"),
+            (DateLocale::De, "2024-06-12T17:59:30Z", "gerade eben"),
+            (DateLocale::De, "2024-06-12T17:50:00Z", "vor 10 Minuten"),
+            (DateLocale::De, "2024-06-12T17:00:00Z", "vor 1 Stunde"),
+            (DateLocale::De, "2024-06-10T07:05:00Z", "Montag um 07:05"),
+            (DateLocale::De, "2024-03-04T12:00:00Z", "4. März"),
+            (DateLocale::Fr, "2024-06-12T15:00:00Z", "il y a 3 heures"),
+            (DateLocale::Fr, "2024-06-11T21:41:00Z", "hier à 21:41"),
+            (DateLocale::Fr, "2024-02-04T12:00:00Z", "4 févr."),
+            (DateLocale::Es, "2024-06-12T17:59:00Z", "hace 1 minuto"),
+            (
+                DateLocale::Es,
+                "2024-06-08T21:41:00Z",
+                "el sábado a las 21:41",
+            ),
+            (DateLocale::Es, "2023-08-15T12:00:00Z", "15 ago 2023"),
+        ];
+        for (locale, timestamp, expected) in cases {
+            let prefs = prefs(locale, ClockFormat::Auto);
+            assert_eq!(
+                at(timestamp, now, prefs),
+                expected,
+                "{locale:?} {timestamp}"
+            );
+        }
+
+        let us_24 = prefs(DateLocale::EnUs, ClockFormat::TwentyFourHour);
+        assert_eq!(at("2024-06-11T21:41:00Z", now, us_24), "yesterday at 21:41");
+        let gb_12 = prefs(DateLocale::EnGb, ClockFormat::TwelveHour);
+        assert_eq!(
+            at("2024-06-11T21:41:00Z", now, gb_12),
+            "yesterday at 9:41 PM"
+        );
+    }
+
+    #[test]
+    fn local_time_zone_decides_the_day_and_clock() {
+        // 03:30 UTC on the 12th is still the evening of the 11th in New York.
+        assert_eq!(
+            eastern("2024-06-12T03:30:00Z", "2024-06-12T18:00:00Z"),
+            "yesterday at 11:30 PM"
+        );
+        assert_eq!(
+            at("2024-06-12T03:30:00Z", "2024-06-12T18:00:00Z", US),
+            "14 hours ago"
+        );
+    }
+
+    #[test]
+    fn dst_changes_keep_real_hours_and_calendar_days() {
+        // Spring forward: 01:30 EST to 03:45 EDT is 75 real minutes, not two and a quarter hours.
+        assert_eq!(
+            eastern("2024-03-10T06:30:00Z", "2024-03-10T07:45:00Z"),
+            "1 hour ago"
+        );
+        // The 23-hour day: 11:30 PM the night before is yesterday even 22.5 hours later.
+        assert_eq!(
+            eastern("2024-03-10T04:30:00Z", "2024-03-11T03:00:00Z"),
+            "yesterday at 11:30 PM"
+        );
+        // Fall back: 00:30 EDT to 23:30 EST the same day is 24 hours on one calendar day.
+        assert_eq!(
+            eastern("2024-11-03T04:30:00Z", "2024-11-04T04:30:00Z"),
+            "24 hours ago"
+        );
+        // 01:30 happens twice, an hour apart; both read as 1:30 AM the next day.
+        assert_eq!(
+            eastern("2024-11-03T05:30:00Z", "2024-11-03T07:00:00Z"),
+            "1 hour ago"
+        );
+        assert_eq!(
+            eastern("2024-11-03T05:30:00Z", "2024-11-04T12:00:00Z"),
+            "yesterday at 1:30 AM"
+        );
+        assert_eq!(
+            eastern("2024-11-03T06:30:00Z", "2024-11-04T12:00:00Z"),
+            "yesterday at 1:30 AM"
+        );
+    }
+
+    #[test]
+    fn server_timestamps_with_and_without_offsets() {
+        let expected = utc("2024-06-12T18:00:00Z");
+        for value in [
+            "2024-06-12T18:00:00Z",
+            "2024-06-12T18:00:00.000Z",
+            "2024-06-12T20:00:00+02:00",
+            "2024-06-12T18:00:00",
+            "2024-06-12 18:00:00.5",
+            " 2024-06-12 18:00:00 ",
+        ] {
+            let parsed = parse_server_timestamp(value).unwrap();
+            assert_eq!(parsed.timestamp(), expected.timestamp(), "{value}");
+        }
+        assert!(parse_server_timestamp("yesterday").is_none());
+        assert!(parse_server_timestamp("").is_none());
+    }
+}
@@ -0,0 +1,512 @@
+//! Printable tracklists for playlists and albums, as plain text, a Markdown table or an HTML
+//! page.
+//!
+//! `TracklistWriter` renders a few rows at a time so the UI can spread a long playlist over
+//! several frames.
+use crate::api::models::format_duration;
+use crate::api::Song;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TracklistFormat {
+    PlainText,
+    Markdown,
+    Html,
+}
+
+impl TracklistFormat {
+    pub const ALL: [TracklistFormat; 3] = [
+        TracklistFormat::PlainText,
+        TracklistFormat::Markdown,
+        TracklistFormat::Html,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TracklistFormat::PlainText => "Plain text",
+            TracklistFormat::Markdown => "Markdown table",
+            TracklistFormat::Html => "Printable page",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            TracklistFormat::PlainText => "txt",
+            TracklistFormat::Markdown => "md",
+            TracklistFormat::Html => "html",
+        }
+    }
+
+    pub fn mime(self) -> &'static str {
+        match self {
+            TracklistFormat::PlainText => "text/plain",
+            TracklistFormat::Markdown => "text/markdown",
+            TracklistFormat::Html => "text/html",
+        }
+    }
+}
+
+/// Which columns a tracklist shows. The total runtime is always written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TracklistColumns {
+    pub index: bool,
+    pub title: bool,
+    pub artist: bool,
+    pub album: bool,
+    pub duration: bool,
+}
+
+impl Default for TracklistColumns {
+    fn default() -> Self {
+        Self {
+            index: true,
+            title: true,
+            artist: true,
+            album: true,
+            duration: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Index,
+    Title,
+    Artist,
+    Album,
+    Duration,
+}
+
+impl Column {
+    fn heading(self) -> &'static str {
+        match self {
+            Column::Index => "#",
+            Column::Title => "Title",
+            Column::Artist => "Artist",
+            Column::Album => "Album",
+            Column::Duration => "Time",
+        }
+    }
+
+    fn right_aligned(self) -> bool {
+        matches!(self, Column::Index | Column::Duration)
+    }
+
+    fn cell(self, position: usize, song: &Song) -> String {
+        match self {
+            Column::Index => (position + 1).to_string(),
+            Column::Title => song.title.clone(),
+            Column::Artist => song.artist.clone().unwrap_or_default(),
+            Column::Album => song.album.clone().unwrap_or_default(),
+            Column::Duration => format_duration(song.duration),
+        }
+    }
+}
+
+impl TracklistColumns {
+    fn selected(self) -> Vec<Column> {
+        [
+            (self.index, Column::Index),
+            (self.title, Column::Title),
+            (self.artist, Column::Artist),
+            (self.album, Column::Album),
+            (self.duration, Column::Duration),
+        ]
+        .into_iter()
+        .filter_map(|(shown, column)| shown.then_some(column))
+        .collect()
+    }
+}
+
+/// "48:12", or "1:02:03" once it runs past an hour.
+pub fn runtime_label(seconds: u64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    let secs = seconds % 60;
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{secs:02}")
+    } else {
+        format!("{minutes}:{secs:02}")
+    }
+}
+
+fn track_count_label(count: usize) -> String {
+    if count == 1 {
+        "1 track".to_string()
+    } else {
+        format!("{count} tracks")
+    }
+}
+
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn markdown_cell(text: &str) -> String {
+    single_line(text).replace('\\', "\\\\").replace('|', "\\|")
+}
+
+fn html_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+const HTML_STYLE: &str =
+    "body{font-family:-apple-system,system-ui,sans-serif;margin:2rem;color:#111}\
+h1{font-size:1.5rem;margin:0 0 .25rem}p{margin:0 0 1rem;color:#555}\
+table{border-collapse:collapse;width:100%;font-size:.9rem}\
+th,td{padding:.35rem .6rem;border-bottom:1px solid #ddd;text-align:left}\
+th{border-bottom:2px solid #111}.num{text-align:right;font-variant-numeric:tabular-nums}\
+tfoot td{font-weight:600;border-bottom:none}@media print{body{margin:0}}";
+
+/// Builds a tracklist row by row. Plain-text column widths and the runtime come from one
+/// pass over the songs up front, so rows can be written in any number of chunks.
+pub struct TracklistWriter<'a> {
+    format: TracklistFormat,
+    columns: Vec<Column>,
+    songs: &'a [Song],
+    widths: Vec<usize>,
+    next_row: usize,
+    out: String,
+}
+
+impl<'a> TracklistWriter<'a> {
+    pub fn new(
+        format: TracklistFormat,
+        title: &str,
+        songs: &'a [Song],
+        columns: TracklistColumns,
+    ) -> Self {
+        let columns = columns.selected();
+        let widths = if format == TracklistFormat::PlainText {
+            columns
+                .iter()
+                .map(|column| {
+                    songs
+                        .iter()
+                        .enumerate()
+                        .map(|(position, song)| {
+                            single_line(&column.cell(position, song)).chars().count()
+                        })
+                        .fold(column.heading().len(), usize::max)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        // Rows rarely run past a hundred bytes; reserving that avoids most regrowth.
+        let mut writer = Self {
+            format,
+            columns,
+            songs,
+            widths,
+            next_row: 0,
+            out: String::with_capacity(256 + songs.len() * 96),
+        };
+        writer.header(&single_line(title));
+        writer
+    }
+
+    fn total_seconds(&self) -> u64 {
+        self.songs.iter().map(|song| u64::from(song.duration)).sum()
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "{} · {}",
+            track_count_label(self.songs.len()),
+            runtime_label(self.total_seconds())
+        )
+    }
+
+    fn plain_row(&mut self, cells: &[String]) {
+        let mut line = String::new();
+        for (slot, cell) in cells.iter().enumerate() {
+            if slot > 0 {
+                line.push_str("  ");
+            }
+            let width = self.widths[slot];
+            if self.columns[slot].right_aligned() {
+                let _ = write!(line, "{cell:>width$}");
+            } else {
+                let _ = write!(line, "{cell:<width$}");
+            }
+        }
+        self.out.push_str(line.trim_end());
+        self.out.push('\n');
+    }
+
+    fn header(&mut self, title: &str) {
+        let headings: Vec<String> = self
+            .columns
+            .iter()
+            .map(|column| column.heading().to_string())
+            .collect();
+        let summary = self.summary();
+        match self.format {
+            TracklistFormat::PlainText => {
+                let _ = writeln!(self.out, "{title}\n{summary}\n");
+                if !self.columns.is_empty() {
+                    self.plain_row(&headings);
+                    let rule = self.widths.iter().sum::<usize>() + 2 * (self.widths.len() - 1);
+                    let _ = writeln!(self.out, "{}", "-".repeat(rule));
+                }
+            }
+            TracklistFormat::Markdown => {
+                let _ = writeln!(self.out, "# {}\n\n{summary}\n", markdown_cell(title));
+                if !self.columns.is_empty() {
+                    let _ = writeln!(self.out, "| {} |", headings.join(" | "));
+                    let rules: Vec<&str> = self
+                        .columns
+                        .iter()
+                        .map(|column| {
+                            if column.right_aligned() {
+                                "---:"
+                            } else {
+                                "---"
+                            }
+                        })
+                        .collect();
+                    let _ = writeln!(self.out, "| {} |", rules.join(" | "));
+                }
+            }
+            TracklistFormat::Html => {
+                let title = html_escape(title);
+                let _ = write!(
+                    self.out,
+                    "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+                     <title>{title}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n\
+                     <h1>{title}</h1>\n<p>{}</p>\n<table>\n<thead><tr>",
+                    html_escape(&summary)
+                );
+                for column in &self.columns {
+                    let class = if column.right_aligned() {
+                        " class=\"num\""
+                    } else {
+                        ""
+                    };
+                    let _ = write!(self.out, "<th{class}>{}</th>", column.heading());
+                }
+                self.out.push_str("</tr></thead>\n<tbody>\n");
+            }
+        }
+    }
+
+    /// Writes up to `max_rows` more rows; returns whether every song has been written.
+    pub fn write_rows(&mut self, max_rows: usize) -> bool {
+        let end = self.songs.len().min(self.next_row.saturating_add(max_rows));
+        for position in self.next_row..end {
+            let song = &self.songs[position];
+            let cells: Vec<String> = self
+                .columns
+                .iter()
+                .map(|column| column.cell(position, song))
+                .collect();
+            match self.format {
+                TracklistFormat::PlainText => {
+                    let cells: Vec<String> = cells.iter().map(|cell| single_line(cell)).collect();
+                    if !cells.is_empty() {
+                        self.plain_row(&cells);
+                    }
+                }
+                TracklistFormat::Markdown => {
+                    if !cells.is_empty() {
+                        let cells: Vec<String> =
+                            cells.iter().map(|cell| markdown_cell(cell)).collect();
+                        let _ = writeln!(self.out, "| {} |", cells.join(" | "));
+                    }
+                }
+                TracklistFormat::Html => {
+                    self.out.push_str("<tr>");
+                    for (column, cell) in self.columns.iter().zip(&cells) {
+                        let class = if column.right_aligned() {
+                            " class=\"num\""
+                        } else {
+                            ""
+                        };
+                        let _ = write!(self.out, "<td{class}>{}</td>", html_escape(cell));
+                    }
+                    self.out.push_str("</tr>\n");
+                }
+            }
+        }
+        self.next_row = end;
+        end >= self.songs.len()
+    }
+
+    /// Writes the remaining rows and the total runtime.
+    pub fn finish(mut self) -> String {
+        self.write_rows(usize::MAX);
+        let total = runtime_label(self.total_seconds());
+        match self.format {
+            TracklistFormat::PlainText => {
+                let _ = write!(self.out, "\nTotal runtime: {total}\n");
+            }
+            TracklistFormat::Markdown => {
+                let _ = write!(self.out, "\n**Total runtime:** {total}\n");
+            }
+            TracklistFormat::Html => {
+                let span = self.columns.len().max(1);
+                let _ = write!(
+                    self.out,
+                    "</tbody>\n<tfoot><tr><td colspan=\"{span}\" class=\"num\">\
+                     Total runtime: {total}</td></tr></tfoot>\n</table>\n</body>\n</html>\n"
+                );
+            }
+        }
+        self.out
+    }
+}
+
+/// A file name for the export, from the playlist or album name.
+pub fn tracklist_file_name(title: &str, format: TracklistFormat) -> String {
+    let stem: String = title
+        .chars()
+        .map(|ch| {
+            if ch.is_alphanumeric() || ch == '-' || ch == ' ' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let stem = match stem.trim() {
+        "" => "tracklist",
+        stem => stem,
+    };
+    format!("{stem} - tracklist.{}", format.extension())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The whole tracklist in one call.
+    fn render_tracklist(
+        format: TracklistFormat,
+        title: &str,
+        songs: &[Song],
+        columns: TracklistColumns,
+    ) -> String {
+        TracklistWriter::new(format, title, songs, columns).finish()
+    }
+
+    fn song(title: &str, artist: &str, album: &str, duration: u32) -> Song {
+        Song {
+            id: title.to_string(),
+            title: title.to_string(),
+            artist: Some(artist.to_string()),
+            album: Some(album.to_string()),
+            duration,
+            ..Song::default()
+        }
+    }
+
+    fn side_a() -> Vec<Song> {
+        vec![
+            song("So What", "Miles Davis", "Kind of Blue", 562),
+            song("Freddie Freeloader", "Miles Davis", "Kind of Blue", 589),
+            song("Blue | Green", "Miles Davis", "Kind of Blue", 337),
+        ]
+    }
+
+    #[test]
+    fn plain_text_snapshot() {
+        let text = render_tracklist(
+            TracklistFormat::PlainText,
+            "Vinyl Night",
+            &side_a(),
+            TracklistColumns::default(),
+        );
+        assert_eq!(
+            text,
+            "Vinyl Night\n\
+             3 tracks · 24:48\n\
+             \n\
+             #  Title               Artist       Album         Time\n\
+             ------------------------------------------------------\n\
+             1  So What             Miles Davis  Kind of Blue  9:22\n\
+             2  Freddie Freeloader  Miles Davis  Kind of Blue  9:49\n\
+             3  Blue | Green        Miles Davis  Kind of Blue  5:37\n\
+             \n\
+             Total runtime: 24:48\n"
+        );
+    }
+
+    #[test]
+    fn markdown_snapshot_with_columns_left_out() {
+        let columns = TracklistColumns {
+            artist: false,
+            album: false,
+            ..TracklistColumns::default()
+        };
+        let text = render_tracklist(TracklistFormat::Markdown, "Vinyl Night", &side_a(), columns);
+        assert_eq!(
+            text,
+            "# Vinyl Night\n\
+             \n\
+             3 tracks · 24:48\n\
+             \n\
+             | # | Title | Time |\n\
+             | ---: | --- | ---: |\n\
+             | 1 | So What | 9:22 |\n\
+             | 2 | Freddie Freeloader | 9:49 |\n\
+             | 3 | Blue \\| Green | 5:37 |\n\
+             \n\
+             **Total runtime:** 24:48\n"
+        );
+    }
+
+    #[test]
+    fn chunked_rendering_matches_a_single_pass() {
+        let songs: Vec<Song> = (0..1_234)
+            .map(|n| song(&format!("Track {n}"), "Artist", "Album", 180 + n % 97))
+            .collect();
+        for format in TracklistFormat::ALL {
+            let whole = render_tracklist(format, "Long", &songs, TracklistColumns::default());
+            let mut writer =
+                TracklistWriter::new(format, "Long", &songs, TracklistColumns::default());
+            let mut chunks = 0;
+            while !writer.write_rows(100) {
+                chunks += 1;
+            }
+            assert_eq!(chunks, 12);
+            assert_eq!(writer.finish(), whole);
+        }
+    }
+
+    #[test]
+    fn html_is_escaped_and_totals_long_runtimes() {
+        let songs = vec![
+            song("<script>", "A & B", "\"Quoted\"", 3_000),
+            song("Second", "C", "D", 1_000),
+        ];
+        let html = render_tracklist(
+            TracklistFormat::Html,
+            "Mine & Yours",
+            &songs,
+            TracklistColumns::default(),
+        );
+        assert!(html.contains("<title>Mine &amp; Yours</title>"));
+        assert!(
+            html.contains("<td>&lt;script&gt;</td><td>A &amp; B</td><td>&quot;Quoted&quot;</td>")
+        );
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("Total runtime: 1:06:40"));
+        assert_eq!(runtime_label(59), "0:59");
+        assert_eq!(
+            tracklist_file_name("Friday / Night", TracklistFormat::Markdown),
+            "Friday _ Night - tracklist.md"
+        );
+    }
+}
@@ -0,0 +1,37 @@
+//! Runs a real panic through the crash-report hook. The hook and its directory are
+//! process-wide, so this is the only test in a binary of its own, run without the test
+//! harness so nothing else can panic while the hook is installed.
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    use rustysound::{crash_report, diagnostics};
+
+    let data_dir =
+        std::env::temp_dir().join(format!("rustysound-crash-test-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&data_dir);
+    diagnostics::record_diagnostic("[test] before the crash");
+
+    crash_report::install_panic_hook_in(&data_dir);
+    assert!(crash_report::pending_crash_report().is_none());
+    let result = std::thread::Builder::new()
+        .name("crash-test".to_string())
+        .spawn(|| panic!("controlled test panic"))
+        .expect("spawn crash-test thread")
+        .join();
+    assert!(result.is_err());
+
+    let report = crash_report::pending_crash_report().expect("pending report");
+    assert!(report.file_name.starts_with("crash-"));
+    assert!(data_dir.join("crashes").join(&report.file_name).exists());
+    assert!(report.contents.contains("Panic: controlled test panic"));
+    assert!(report.contents.contains("Thread: crash-test"));
+    assert!(report.contents.contains(env!("CARGO_PKG_VERSION")));
+    assert!(report.contents.contains("[test] before the crash"));
+
+    crash_report::dismiss_pending_crash_report();
+    assert!(crash_report::pending_crash_report().is_none());
+    let _ = std::fs::remove_dir_all(&data_dir);
+    println!("test controlled_panic_writes_a_pending_report ... ok");
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}